@@ -39,13 +39,16 @@
 //! without any additional terms or conditions.
 
 pub use argmin::core::checkpointing::{Checkpoint, CheckpointingFrequency};
-use argmin::core::Error;
+use argmin::core::{Error, RunInfo};
 use serde::{de::DeserializeOwned, Serialize};
 use std::fs::File;
 use std::io::{BufReader, BufWriter};
 use std::path::PathBuf;
 
 /// Handles saving a checkpoint to disk as a binary file.
+///
+/// The [`RunInfo`] of the run (identifier, name, tags and metadata) is saved next to the
+/// checkpoint in a file with the same name and the extension `.run`.
 #[derive(Clone, Eq, PartialEq, Debug, Hash)]
 pub struct FileCheckpoint {
     /// Indicates how often a checkpoint is created
@@ -108,6 +111,11 @@ impl FileCheckpoint {
             filename: PathBuf::from(format!("{}.arg", name.as_ref())),
         }
     }
+
+    /// Path of the file the run information is saved to
+    fn run_path(&self) -> PathBuf {
+        self.directory.join(self.filename.with_extension("run"))
+    }
 }
 
 impl<S, I> Checkpoint<S, I> for FileCheckpoint
@@ -202,13 +210,58 @@ where
     fn frequency(&self) -> CheckpointingFrequency {
         self.frequency
     }
+
+    /// Writes the run information to disk.
+    ///
+    /// The file has the same name as the checkpoint, but with the extension `.run`. If the
+    /// directory does not exist already, it will be created.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use argmin_checkpointing_file::{FileCheckpoint, CheckpointingFrequency, Checkpoint};
+    /// # use argmin::core::{Error, RunInfo};
+    ///
+    /// # fn main() -> Result<(), Error> {
+    /// # let checkpoint = FileCheckpoint::new(".checkpoints", "save_run_test" , CheckpointingFrequency::Always);
+    /// let run = RunInfo::new().name("rosenbrock");
+    /// Checkpoint::<u64, u64>::save_run(&checkpoint, &run)?;
+    /// # let loaded = Checkpoint::<u64, u64>::load_run(&checkpoint)?;
+    /// # assert_eq!(loaded, Some(run));
+    /// # let _ = std::fs::remove_file(".checkpoints/save_run_test.run");
+    /// # Ok(())
+    /// # }
+    /// ```
+    fn save_run(&self, run: &RunInfo) -> Result<(), Error> {
+        if !self.directory.exists() {
+            std::fs::create_dir_all(&self.directory)?
+        }
+        let f = BufWriter::new(File::create(self.run_path())?);
+        bincode::serialize_into(f, run)?;
+        Ok(())
+    }
+
+    /// Loads the run information from disk.
+    ///
+    /// If there is no run information on disk, it will return `Ok(None)`.
+    fn load_run(&self) -> Result<Option<RunInfo>, Error> {
+        let path = self.run_path();
+        if !path.exists() {
+            return Ok(None);
+        }
+        let reader = BufReader::new(File::open(path)?);
+        Ok(Some(bincode::deserialize_from(reader)?))
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
     use argmin::core::test_utils::TestSolver;
-    use argmin::core::{IterState, State};
+    use argmin::core::{CostFunction, Executor, IterState, PopulationState, Solver, State};
+    use argmin::solver::globalopt::{LatinHypercubeConfig, RandomSearchConfig};
+    use argmin::solver::particleswarm::{Particle, ParticleSwarmConfig};
+    use argmin::solver::simulatedannealing::{Anneal, SimulatedAnnealingConfig};
 
     #[test]
     #[allow(clippy::type_complexity)]
@@ -222,4 +275,158 @@ mod tests {
         let _loaded: Option<(TestSolver, IterState<Vec<f64>, (), (), (), (), f64>)> =
             check.load().unwrap();
     }
+
+    #[test]
+    fn test_run_info() {
+        type TState = IterState<Vec<f64>, (), (), (), (), f64>;
+
+        let check = FileCheckpoint::new("checkpoints", "run_info", CheckpointingFrequency::Always);
+        let _ = std::fs::remove_file("checkpoints/run_info.arg");
+        let _ = std::fs::remove_file("checkpoints/run_info.run");
+        assert!(Checkpoint::<TestSolver, TState>::load_run(&check)
+            .unwrap()
+            .is_none());
+
+        let executor = Executor::new(Sphere {}, TestSolver::new())
+            .configure(|state| state.param(vec![1.0, 0.0]).max_iters(2))
+            .checkpointing(check.clone())
+            .run_name("first");
+        let run = executor.run_info().clone();
+        executor.run().unwrap();
+        let loaded = Checkpoint::<TestSolver, TState>::load_run(&check)
+            .unwrap()
+            .unwrap();
+        assert_eq!(loaded, run);
+
+        // Resuming keeps the identifier of the interrupted run
+        let executor = Executor::new(Sphere {}, TestSolver::new())
+            .checkpointing(check.clone())
+            .run_name("second");
+        assert_ne!(executor.run_info().id(), run.id());
+        executor.run().unwrap();
+        let loaded = Checkpoint::<TestSolver, TState>::load_run(&check)
+            .unwrap()
+            .unwrap();
+        assert_eq!(loaded.id(), run.id());
+        assert_eq!(loaded.get_name(), Some("second"));
+
+        std::fs::remove_file("checkpoints/run_info.arg").unwrap();
+        std::fs::remove_file("checkpoints/run_info.run").unwrap();
+    }
+
+    /// Test problem which is cheap to evaluate and anneals deterministically, such that all
+    /// randomness of a run stems from the solver
+    struct Sphere {}
+
+    impl CostFunction for Sphere {
+        type Param = Vec<f64>;
+        type Output = f64;
+
+        fn cost(&self, p: &Self::Param) -> Result<Self::Output, Error> {
+            Ok(p.iter().map(|x| x * x).sum())
+        }
+    }
+
+    impl Anneal for Sphere {
+        type Param = Vec<f64>;
+        type Output = Vec<f64>;
+        type Float = f64;
+
+        fn anneal(&self, p: &Self::Param, extent: f64) -> Result<Self::Output, Error> {
+            Ok(p.iter().map(|x| x - extent * (100.0 * x).sin()).collect())
+        }
+    }
+
+    /// Runs 10 iterations, resumes the run from the checkpoint for another 10 iterations and
+    /// compares the final state to the state of an uninterrupted run of 20 iterations.
+    fn assert_resumes_exactly<S, I>(name: &str, solver: impl Fn() -> S, init: impl Fn(I, u64) -> I)
+    where
+        S: Solver<Sphere, I> + Serialize + DeserializeOwned,
+        I: State + Serialize + DeserializeOwned + PartialEq + std::fmt::Debug,
+    {
+        let uninterrupted = Executor::new(Sphere {}, solver())
+            .configure(|state| init(state, 20))
+            .run()
+            .unwrap();
+
+        let check = FileCheckpoint::new("checkpoints", name, CheckpointingFrequency::Every(10));
+        Executor::new(Sphere {}, solver())
+            .configure(|state| init(state, 10))
+            .checkpointing(check.clone())
+            .run()
+            .unwrap();
+
+        let (solver, state): (S, I) = check.load().unwrap().unwrap();
+        assert_eq!(state.get_iter(), 10);
+        let resumed = Executor::new(Sphere {}, solver)
+            .configure(|_| init(state, 20))
+            .run()
+            .unwrap();
+
+        std::fs::remove_file(format!("checkpoints/{name}.arg")).unwrap();
+        std::fs::remove_file(format!("checkpoints/{name}.run")).unwrap();
+
+        assert_eq!(resumed.state(), uninterrupted.state());
+    }
+
+    #[test]
+    fn test_resume_simulatedannealing() {
+        let config = SimulatedAnnealingConfig {
+            initial_temperature: 10.0,
+            seed: Some(1729),
+            ..Default::default()
+        };
+        assert_resumes_exactly(
+            "simulatedannealing",
+            || config.build().unwrap(),
+            |state: IterState<Vec<f64>, (), (), (), (), f64>, max_iters| {
+                state.param(vec![3.0, -2.0]).max_iters(max_iters)
+            },
+        );
+    }
+
+    #[test]
+    fn test_resume_particleswarm() {
+        let config = ParticleSwarmConfig {
+            bounds: (vec![-4.0, -4.0], vec![4.0, 4.0]),
+            num_particles: 10,
+            seed: Some(1729),
+            ..Default::default()
+        };
+        assert_resumes_exactly(
+            "particleswarm",
+            || config.build().unwrap(),
+            |state: PopulationState<Particle<Vec<f64>, f64>, f64>, max_iters| {
+                state.max_iters(max_iters)
+            },
+        );
+    }
+
+    #[test]
+    fn test_resume_randomsearch() {
+        let config = RandomSearchConfig {
+            bounds: (vec![-4.0, -4.0], vec![4.0, 4.0]),
+            batch_size: Some(5),
+            seed: Some(1729),
+        };
+        assert_resumes_exactly(
+            "randomsearch",
+            || config.build().unwrap(),
+            |state: IterState<Vec<f64>, (), (), (), (), f64>, max_iters| state.max_iters(max_iters),
+        );
+    }
+
+    #[test]
+    fn test_resume_latinhypercube() {
+        let config = LatinHypercubeConfig {
+            bounds: (vec![-4.0, -4.0], vec![4.0, 4.0]),
+            num_samples: 5,
+            seed: Some(1729),
+        };
+        assert_resumes_exactly(
+            "latinhypercube",
+            || config.build().unwrap(),
+            |state: IterState<Vec<f64>, (), (), (), (), f64>, max_iters| state.max_iters(max_iters),
+        );
+    }
 }
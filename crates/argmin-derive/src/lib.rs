@@ -0,0 +1,366 @@
+// Copyright 2018-2024 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+//! Derive macros for the problem traits of argmin.
+//!
+//! Implementing [`CostFunction`], [`Gradient`], [`Hessian`], [`Jacobian`] and [`Operator`] by
+//! hand requires an `impl` block per trait, each repeating the associated types. The derive macros
+//! of this crate generate these `impl` blocks from `#[argmin(...)]` attributes on the struct, which
+//! name the associated types and the method computing the respective quantity.
+//!
+//! [`CostFunction`]: https://docs.rs/argmin/latest/argmin/core/trait.CostFunction.html
+//! [`Gradient`]: https://docs.rs/argmin/latest/argmin/core/trait.Gradient.html
+//! [`Hessian`]: https://docs.rs/argmin/latest/argmin/core/trait.Hessian.html
+//! [`Jacobian`]: https://docs.rs/argmin/latest/argmin/core/trait.Jacobian.html
+//! [`Operator`]: https://docs.rs/argmin/latest/argmin/core/trait.Operator.html
+//!
+//! # Usage
+//!
+//! The macros are re-exported by argmin if the `derive` feature is enabled:
+//!
+//! ```toml
+//! [dependencies]
+//! argmin = { version = "0.10.0", features = ["derive"] }
+//! ```
+//!
+//! Alternatively, this crate can be added directly:
+//!
+//! ```toml
+//! [dependencies]
+#![doc = concat!("argmin-derive = \"", env!("CARGO_PKG_VERSION"), "\"")]
+//! ```
+//!
+//! # Attributes
+//!
+//! The type of the parameter vector is set for all traits via `#[argmin(param = ...)]`. Each
+//! derived trait has its own attribute, named after the trait method:
+//!
+//! | Derive         | Attribute                                         |
+//! |----------------|---------------------------------------------------|
+//! | `CostFunction` | `#[argmin(cost(output = ..., with = ...))]`         |
+//! | `Gradient`     | `#[argmin(gradient(gradient = ..., with = ...))]`   |
+//! | `Hessian`      | `#[argmin(hessian(hessian = ..., with = ...))]`     |
+//! | `Jacobian`     | `#[argmin(jacobian(jacobian = ..., with = ...))]`   |
+//! | `Operator`     | `#[argmin(apply(output = ..., with = ...))]`        |
+//!
+//! The first key sets the output type of the trait method. `with` is the path to a function with
+//! the signature `fn(&Self, &Param) -> Result<Output, Error>`, usually a method of the struct.
+//! The parameter type can be overridden per trait with a `param = ...` key.
+//!
+//! Instead of `with`, a field of the struct holding a closure (or any other callable) of type
+//! `Fn(&Param) -> Result<Output, Error>` can be marked with the name of the trait method, such as
+//! `#[argmin(gradient)]`.
+//!
+//! # Example
+//!
+//! ```
+//! use argmin::core::{CostFunction, Error, Gradient};
+//! use argmin_derive::{CostFunction, Gradient};
+//!
+//! #[derive(CostFunction, Gradient)]
+//! #[argmin(param = Vec<f64>)]
+//! #[argmin(cost(output = f64, with = Self::value))]
+//! #[argmin(gradient(gradient = Vec<f64>, with = Self::derivative))]
+//! struct Paraboloid {
+//!     center: Vec<f64>,
+//! }
+//!
+//! impl Paraboloid {
+//!     fn value(&self, p: &Vec<f64>) -> Result<f64, Error> {
+//!         Ok(p.iter().zip(&self.center).map(|(x, c)| (x - c).powi(2)).sum())
+//!     }
+//!
+//!     fn derivative(&self, p: &Vec<f64>) -> Result<Vec<f64>, Error> {
+//!         Ok(p.iter().zip(&self.center).map(|(x, c)| 2.0 * (x - c)).collect())
+//!     }
+//! }
+//!
+//! # fn main() -> Result<(), Error> {
+//! let problem = Paraboloid { center: vec![1.0, 2.0] };
+//! assert_eq!(problem.cost(&vec![0.0, 0.0])?, 5.0);
+//! assert_eq!(problem.gradient(&vec![0.0, 0.0])?, vec![-2.0, -4.0]);
+//! # Ok(())
+//! # }
+//! ```
+
+use proc_macro::TokenStream;
+use proc_macro2::{Span, TokenStream as TokenStream2};
+use quote::quote;
+use syn::{parse_macro_input, Attribute, Data, DeriveInput, Fields, Ident, Member, Path, Type};
+
+/// Derives `argmin::core::CostFunction`
+///
+/// Requires `#[argmin(cost(output = ..., with = ...))]` or a field marked with `#[argmin(cost)]`.
+/// See the [crate documentation](crate) for details.
+#[proc_macro_derive(CostFunction, attributes(argmin))]
+pub fn derive_cost_function(input: TokenStream) -> TokenStream {
+    expand(
+        parse_macro_input!(input as DeriveInput),
+        &TraitInfo {
+            name: "CostFunction",
+            method: "cost",
+            output_key: "output",
+            output_type: "Output",
+        },
+    )
+}
+
+/// Derives `argmin::core::Gradient`
+///
+/// Requires `#[argmin(gradient(gradient = ..., with = ...))]` or a field marked with
+/// `#[argmin(gradient)]`. See the [crate documentation](crate) for details.
+#[proc_macro_derive(Gradient, attributes(argmin))]
+pub fn derive_gradient(input: TokenStream) -> TokenStream {
+    expand(
+        parse_macro_input!(input as DeriveInput),
+        &TraitInfo {
+            name: "Gradient",
+            method: "gradient",
+            output_key: "gradient",
+            output_type: "Gradient",
+        },
+    )
+}
+
+/// Derives `argmin::core::Hessian`
+///
+/// Requires `#[argmin(hessian(hessian = ..., with = ...))]` or a field marked with
+/// `#[argmin(hessian)]`. See the [crate documentation](crate) for details.
+#[proc_macro_derive(Hessian, attributes(argmin))]
+pub fn derive_hessian(input: TokenStream) -> TokenStream {
+    expand(
+        parse_macro_input!(input as DeriveInput),
+        &TraitInfo {
+            name: "Hessian",
+            method: "hessian",
+            output_key: "hessian",
+            output_type: "Hessian",
+        },
+    )
+}
+
+/// Derives `argmin::core::Jacobian`
+///
+/// Requires `#[argmin(jacobian(jacobian = ..., with = ...))]` or a field marked with
+/// `#[argmin(jacobian)]`. See the [crate documentation](crate) for details.
+#[proc_macro_derive(Jacobian, attributes(argmin))]
+pub fn derive_jacobian(input: TokenStream) -> TokenStream {
+    expand(
+        parse_macro_input!(input as DeriveInput),
+        &TraitInfo {
+            name: "Jacobian",
+            method: "jacobian",
+            output_key: "jacobian",
+            output_type: "Jacobian",
+        },
+    )
+}
+
+/// Derives `argmin::core::Operator`
+///
+/// Requires `#[argmin(apply(output = ..., with = ...))]` or a field marked with
+/// `#[argmin(apply)]`. See the [crate documentation](crate) for details.
+#[proc_macro_derive(Operator, attributes(argmin))]
+pub fn derive_operator(input: TokenStream) -> TokenStream {
+    expand(
+        parse_macro_input!(input as DeriveInput),
+        &TraitInfo {
+            name: "Operator",
+            method: "apply",
+            output_key: "output",
+            output_type: "Output",
+        },
+    )
+}
+
+/// Names of a trait, its method and its associated output type
+struct TraitInfo {
+    /// Name of the trait
+    name: &'static str,
+    /// Name of the trait method, which is also the name of the attribute
+    method: &'static str,
+    /// Attribute key of the output type
+    output_key: &'static str,
+    /// Name of the associated output type
+    output_type: &'static str,
+}
+
+/// How the trait method is computed
+enum Source {
+    /// Function called with `self` and the parameter vector
+    With(Path),
+    /// Field holding a callable which is called with the parameter vector
+    Field(Member),
+}
+
+/// Settings of a derived trait collected from the `#[argmin(...)]` attributes
+#[derive(Default)]
+struct Spec {
+    /// Type of the parameter vector
+    param: Option<Type>,
+    /// Output type of the trait method
+    output: Option<Type>,
+    /// Function computing the output
+    with: Option<Path>,
+    /// Field computing the output
+    field: Option<Member>,
+}
+
+fn expand(input: DeriveInput, info: &TraitInfo) -> TokenStream {
+    impl_trait(&input, info)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+fn impl_trait(input: &DeriveInput, info: &TraitInfo) -> syn::Result<TokenStream2> {
+    let spec = parse_spec(input, info)?;
+
+    let param = spec.param.ok_or_else(|| {
+        syn::Error::new(
+            Span::call_site(),
+            format!(
+                "missing parameter type for `{}`, add `#[argmin(param = ...)]`",
+                info.name
+            ),
+        )
+    })?;
+    let output = spec.output.ok_or_else(|| {
+        syn::Error::new(
+            Span::call_site(),
+            format!(
+                "missing output type for `{}`, add `#[argmin({}({} = ...))]`",
+                info.name, info.method, info.output_key
+            ),
+        )
+    })?;
+    let source = match (spec.with, spec.field) {
+        (Some(with), None) => Source::With(with),
+        (None, Some(field)) => Source::Field(field),
+        (Some(with), Some(_)) => {
+            return Err(syn::Error::new_spanned(
+                with,
+                format!(
+                    "`{}` is computed by both a function and a field",
+                    info.method
+                ),
+            ))
+        }
+        (None, None) => {
+            return Err(syn::Error::new(
+                Span::call_site(),
+                format!(
+                    "missing implementation of `{}`, add `#[argmin({}(with = ...))]` or mark a \
+                     field with `#[argmin({})]`",
+                    info.method, info.method, info.method
+                ),
+            ))
+        }
+    };
+
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    let trait_name = Ident::new(info.name, Span::call_site());
+    let method = Ident::new(info.method, Span::call_site());
+    let output_type = Ident::new(info.output_type, Span::call_site());
+    let body = match source {
+        Source::With(path) => quote! { #path(self, param) },
+        Source::Field(member) => quote! { (self.#member)(param) },
+    };
+
+    Ok(quote! {
+        #[automatically_derived]
+        impl #impl_generics ::argmin::core::#trait_name for #name #ty_generics #where_clause {
+            type Param = #param;
+            type #output_type = #output;
+
+            fn #method(
+                &self,
+                param: &Self::Param,
+            ) -> ::std::result::Result<Self::#output_type, ::argmin::core::Error> {
+                #body
+            }
+        }
+    })
+}
+
+/// Collects the settings of the trait described by `info` from the struct and field attributes
+fn parse_spec(input: &DeriveInput, info: &TraitInfo) -> syn::Result<Spec> {
+    let mut spec = Spec::default();
+    let mut param = None;
+
+    for attr in argmin_attrs(&input.attrs) {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("param") {
+                param = Some(meta.value()?.parse::<Type>()?);
+            } else if meta.path.is_ident(info.method) {
+                meta.parse_nested_meta(|meta| {
+                    if meta.path.is_ident("param") {
+                        spec.param = Some(meta.value()?.parse()?);
+                    } else if meta.path.is_ident(info.output_key) {
+                        spec.output = Some(meta.value()?.parse()?);
+                    } else if meta.path.is_ident("with") {
+                        spec.with = Some(meta.value()?.parse()?);
+                    } else {
+                        return Err(meta.error(format!(
+                            "unknown key, expected `param`, `{}` or `with`",
+                            info.output_key
+                        )));
+                    }
+                    Ok(())
+                })?;
+            } else if is_known_method(&meta.path) {
+                // Belongs to another derive, skip its content
+                if !meta.input.is_empty() && !meta.input.peek(syn::Token![,]) {
+                    let content;
+                    syn::parenthesized!(content in meta.input);
+                    content.parse::<TokenStream2>()?;
+                }
+            } else {
+                return Err(meta.error("unknown argmin attribute"));
+            }
+            Ok(())
+        })?;
+    }
+    spec.param = spec.param.or(param);
+
+    let fields = match &input.data {
+        Data::Struct(data) => &data.fields,
+        _ => return Ok(spec),
+    };
+    for (idx, field) in fields.iter().enumerate() {
+        for attr in argmin_attrs(&field.attrs) {
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident(info.method) {
+                    if spec.field.is_some() {
+                        return Err(meta.error(format!(
+                            "more than one field is marked with `#[argmin({})]`",
+                            info.method
+                        )));
+                    }
+                    spec.field = Some(match (&field.ident, fields) {
+                        (Some(ident), Fields::Named(_)) => Member::Named(ident.clone()),
+                        _ => Member::Unnamed(idx.into()),
+                    });
+                } else if !is_known_method(&meta.path) {
+                    return Err(meta.error("unknown argmin attribute"));
+                }
+                Ok(())
+            })?;
+        }
+    }
+    Ok(spec)
+}
+
+fn argmin_attrs(attrs: &[Attribute]) -> impl Iterator<Item = &Attribute> {
+    attrs.iter().filter(|attr| attr.path().is_ident("argmin"))
+}
+
+fn is_known_method(path: &Path) -> bool {
+    ["cost", "gradient", "hessian", "jacobian", "apply"]
+        .iter()
+        .any(|method| path.is_ident(method))
+}
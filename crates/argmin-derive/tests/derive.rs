@@ -0,0 +1,131 @@
+// Copyright 2018-2024 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use argmin::core::{CostFunction, Error, Gradient, Hessian, Jacobian, Operator};
+use argmin_derive::{CostFunction, Gradient, Hessian, Jacobian, Operator};
+
+#[derive(CostFunction, Gradient, Hessian, Jacobian, Operator)]
+#[argmin(param = Vec<f64>)]
+#[argmin(cost(output = f64, with = Self::cost_impl))]
+#[argmin(gradient(gradient = Vec<f64>, with = Self::gradient_impl))]
+#[argmin(hessian(hessian = Vec<Vec<f64>>, with = Self::hessian_impl))]
+#[argmin(jacobian(jacobian = Vec<Vec<f64>>, with = Self::hessian_impl))]
+#[argmin(apply(output = Vec<f64>, with = Self::gradient_impl))]
+struct Quadratic {
+    scale: f64,
+}
+
+impl Quadratic {
+    fn cost_impl(&self, p: &[f64]) -> Result<f64, Error> {
+        Ok(self.scale * p.iter().map(|x| x * x).sum::<f64>())
+    }
+
+    fn gradient_impl(&self, p: &[f64]) -> Result<Vec<f64>, Error> {
+        Ok(p.iter().map(|x| 2.0 * self.scale * x).collect())
+    }
+
+    fn hessian_impl(&self, p: &[f64]) -> Result<Vec<Vec<f64>>, Error> {
+        Ok((0..p.len())
+            .map(|i| {
+                (0..p.len())
+                    .map(|j| if i == j { 2.0 * self.scale } else { 0.0 })
+                    .collect()
+            })
+            .collect())
+    }
+}
+
+#[derive(CostFunction, Gradient)]
+#[argmin(param = f64)]
+#[argmin(cost(output = f64))]
+#[argmin(gradient(gradient = f64))]
+struct Closures<C, G>
+where
+    C: Fn(&f64) -> Result<f64, Error>,
+    G: Fn(&f64) -> Result<f64, Error>,
+{
+    #[argmin(cost)]
+    cost: C,
+    #[argmin(gradient)]
+    gradient: G,
+}
+
+#[derive(CostFunction, Gradient)]
+#[argmin(param = f64)]
+#[argmin(cost(output = f64))]
+#[argmin(gradient(param = f32, gradient = f32, with = sign))]
+struct Tuple(#[argmin(cost)] fn(&f64) -> Result<f64, Error>);
+
+fn sign(_problem: &Tuple, p: &f32) -> Result<f32, Error> {
+    Ok(p.signum())
+}
+
+#[test]
+fn test_methods() {
+    let problem = Quadratic { scale: 2.0 };
+    let p = vec![1.0, 2.0];
+    assert_eq!(
+        problem.cost(&p).unwrap().to_ne_bytes(),
+        10.0f64.to_ne_bytes()
+    );
+    assert_eq!(problem.gradient(&p).unwrap(), vec![4.0, 8.0]);
+    assert_eq!(
+        problem.hessian(&p).unwrap(),
+        vec![vec![4.0, 0.0], vec![0.0, 4.0]]
+    );
+    assert_eq!(problem.jacobian(&p).unwrap(), problem.hessian(&p).unwrap());
+    assert_eq!(problem.apply(&p).unwrap(), vec![4.0, 8.0]);
+}
+
+#[test]
+fn test_fields() {
+    let problem = Closures {
+        cost: |p: &f64| Ok(p.powi(3)),
+        gradient: |p: &f64| Ok(3.0 * p.powi(2)),
+    };
+    assert_eq!(
+        problem.cost(&2.0).unwrap().to_ne_bytes(),
+        8.0f64.to_ne_bytes()
+    );
+    assert_eq!(
+        problem.gradient(&2.0).unwrap().to_ne_bytes(),
+        12.0f64.to_ne_bytes()
+    );
+}
+
+#[test]
+fn test_tuple_struct_and_param_override() {
+    fn cost(p: &f64) -> Result<f64, Error> {
+        Ok(p.abs())
+    }
+
+    let problem = Tuple(cost);
+    assert_eq!(
+        problem.cost(&-3.0).unwrap().to_ne_bytes(),
+        3.0f64.to_ne_bytes()
+    );
+    assert_eq!(
+        problem.gradient(&-3.0f32).unwrap().to_ne_bytes(),
+        (-1.0f32).to_ne_bytes()
+    );
+}
+
+#[test]
+fn test_errors_are_propagated() {
+    #[derive(CostFunction)]
+    #[argmin(param = f64)]
+    #[argmin(cost(output = f64, with = Self::fail))]
+    struct Failing {}
+
+    impl Failing {
+        fn fail(&self, _p: &f64) -> Result<f64, Error> {
+            Err(Error::msg("failed"))
+        }
+    }
+
+    assert_eq!(Failing {}.cost(&1.0).unwrap_err().to_string(), "failed");
+}
@@ -0,0 +1,203 @@
+// Copyright 2018-2024 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+//! Benchmarks of the hot `Vec` operations.
+//!
+//! Each operation is compared to a plain loop (`loop`). Run with
+//!
+//! ```bash
+//! cargo bench -p argmin-math --bench vec
+//! cargo bench -p argmin-math --bench vec --features vec_simd
+//! cargo bench -p argmin-math --bench vec --features vec_faer
+//! ```
+//!
+//! to compare the default implementation with the accelerated kernels.
+
+use argmin_math::{ArgminAdd, ArgminAxpy, ArgminDot, ArgminL2Norm, ArgminScaledAdd, ArgminSub};
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion};
+
+const SIZES: &[usize] = &[16, 1024, 65536];
+
+/// Number of rows and columns of the matrices, in the range of typical Hessians
+const MATRIX_SIZES: &[usize] = &[10, 100, 300];
+
+macro_rules! make_bench {
+    ($name:ident, $t:ty) => {
+        pub fn $name(c: &mut Criterion) {
+            for &n in SIZES {
+                let x: Vec<$t> = (0..n).map(|i| (i % 13) as $t / 7.0).collect();
+                let y: Vec<$t> = (0..n).map(|i| (i % 7) as $t / 5.0).collect();
+                let alpha: $t = 0.5;
+
+                let mut g = c.benchmark_group(concat!("dot ", stringify!($t)));
+                g.bench_with_input(BenchmarkId::new("loop", n), &n, |b, _| {
+                    b.iter(|| {
+                        black_box(&x)
+                            .iter()
+                            .zip(black_box(&y).iter())
+                            .map(|(a, b)| a * b)
+                            .sum::<$t>()
+                    })
+                });
+                g.bench_with_input(BenchmarkId::new("argmin-math", n), &n, |b, _| {
+                    b.iter(|| {
+                        <Vec<$t> as ArgminDot<Vec<$t>, $t>>::dot(black_box(&x), black_box(&y))
+                    })
+                });
+                g.finish();
+
+                let mut g = c.benchmark_group(concat!("add ", stringify!($t)));
+                g.bench_with_input(BenchmarkId::new("loop", n), &n, |b, _| {
+                    b.iter(|| {
+                        black_box(&x)
+                            .iter()
+                            .zip(black_box(&y).iter())
+                            .map(|(a, b)| a + b)
+                            .collect::<Vec<$t>>()
+                    })
+                });
+                g.bench_with_input(BenchmarkId::new("argmin-math", n), &n, |b, _| {
+                    b.iter(|| {
+                        <Vec<$t> as ArgminAdd<Vec<$t>, Vec<$t>>>::add(black_box(&x), black_box(&y))
+                    })
+                });
+                g.finish();
+
+                let mut g = c.benchmark_group(concat!("sub ", stringify!($t)));
+                g.bench_with_input(BenchmarkId::new("loop", n), &n, |b, _| {
+                    b.iter(|| {
+                        black_box(&x)
+                            .iter()
+                            .zip(black_box(&y).iter())
+                            .map(|(a, b)| a - b)
+                            .collect::<Vec<$t>>()
+                    })
+                });
+                g.bench_with_input(BenchmarkId::new("argmin-math", n), &n, |b, _| {
+                    b.iter(|| {
+                        <Vec<$t> as ArgminSub<Vec<$t>, Vec<$t>>>::sub(black_box(&x), black_box(&y))
+                    })
+                });
+                g.finish();
+
+                let mut g = c.benchmark_group(concat!("scaled_add ", stringify!($t)));
+                g.bench_with_input(BenchmarkId::new("loop", n), &n, |b, _| {
+                    b.iter(|| {
+                        black_box(&x)
+                            .iter()
+                            .zip(black_box(&y).iter())
+                            .map(|(a, b)| a + alpha * b)
+                            .collect::<Vec<$t>>()
+                    })
+                });
+                g.bench_with_input(BenchmarkId::new("argmin-math", n), &n, |b, _| {
+                    b.iter(|| {
+                        <Vec<$t> as ArgminScaledAdd<Vec<$t>, $t, Vec<$t>>>::scaled_add(
+                            black_box(&x),
+                            black_box(&alpha),
+                            black_box(&y),
+                        )
+                    })
+                });
+                g.finish();
+
+                let mut g = c.benchmark_group(concat!("axpy ", stringify!($t)));
+                g.bench_with_input(BenchmarkId::new("loop", n), &n, |b, _| {
+                    let mut z = x.clone();
+                    b.iter(|| {
+                        z.iter_mut()
+                            .zip(black_box(&y).iter())
+                            .for_each(|(a, b)| *a += alpha * b);
+                    })
+                });
+                g.bench_with_input(BenchmarkId::new("argmin-math", n), &n, |b, _| {
+                    let mut z = x.clone();
+                    b.iter(|| {
+                        <Vec<$t> as ArgminAxpy<Vec<$t>, $t>>::axpy(
+                            &mut z,
+                            black_box(&alpha),
+                            black_box(&y),
+                        )
+                    })
+                });
+                g.finish();
+
+                let mut g = c.benchmark_group(concat!("l2_norm ", stringify!($t)));
+                g.bench_with_input(BenchmarkId::new("loop", n), &n, |b, _| {
+                    b.iter(|| black_box(&x).iter().map(|a| a * a).sum::<$t>().sqrt())
+                });
+                g.bench_with_input(BenchmarkId::new("argmin-math", n), &n, |b, _| {
+                    b.iter(|| <Vec<$t> as ArgminL2Norm<$t>>::l2_norm(black_box(&x)))
+                });
+                g.finish();
+            }
+
+            for &n in MATRIX_SIZES {
+                let a: Vec<Vec<$t>> = (0..n)
+                    .map(|i| (0..n).map(|j| ((i + j) % 13) as $t / 7.0).collect())
+                    .collect();
+                let m: Vec<Vec<$t>> = (0..n)
+                    .map(|i| (0..n).map(|j| ((i * j) % 7) as $t / 5.0).collect())
+                    .collect();
+                let x: Vec<$t> = (0..n).map(|i| (i % 13) as $t / 7.0).collect();
+
+                let mut g = c.benchmark_group(concat!("matmul ", stringify!($t)));
+                g.bench_with_input(BenchmarkId::new("loop", n), &n, |b, _| {
+                    b.iter(|| {
+                        let (a, m) = (black_box(&a), black_box(&m));
+                        (0..n)
+                            .map(|i| {
+                                (0..n)
+                                    .map(|j| (0..n).map(|k| a[i][k] * m[k][j]).sum::<$t>())
+                                    .collect::<Vec<$t>>()
+                            })
+                            .collect::<Vec<Vec<$t>>>()
+                    })
+                });
+                g.bench_with_input(BenchmarkId::new("argmin-math", n), &n, |b, _| {
+                    b.iter(|| {
+                        <Vec<Vec<$t>> as ArgminDot<Vec<Vec<$t>>, Vec<Vec<$t>>>>::dot(
+                            black_box(&a),
+                            black_box(&m),
+                        )
+                    })
+                });
+                g.finish();
+
+                let mut g = c.benchmark_group(concat!("matvec ", stringify!($t)));
+                g.bench_with_input(BenchmarkId::new("loop", n), &n, |b, _| {
+                    b.iter(|| {
+                        black_box(&a)
+                            .iter()
+                            .map(|row| {
+                                row.iter()
+                                    .zip(black_box(&x).iter())
+                                    .map(|(a, b)| a * b)
+                                    .sum()
+                            })
+                            .collect::<Vec<$t>>()
+                    })
+                });
+                g.bench_with_input(BenchmarkId::new("argmin-math", n), &n, |b, _| {
+                    b.iter(|| {
+                        <Vec<Vec<$t>> as ArgminDot<Vec<$t>, Vec<$t>>>::dot(
+                            black_box(&a),
+                            black_box(&x),
+                        )
+                    })
+                });
+                g.finish();
+            }
+        }
+    };
+}
+
+make_bench!(bench_f32, f32);
+make_bench!(bench_f64, f64);
+
+criterion_group!(benches, bench_f32, bench_f64);
+criterion_main!(benches);
@@ -1,6 +1,24 @@
 mod add {
     include!(concat!(env!("CARGO_MANIFEST_DIR"), "/../../ndarray-tests-src/add.rs"));
 }
+mod allfinite {
+    include!(concat!(
+        env!("CARGO_MANIFEST_DIR"),
+        "/../../ndarray-tests-src/allfinite.rs"
+    ));
+}
+mod axpy {
+    include!(concat!(
+        env!("CARGO_MANIFEST_DIR"),
+        "/../../ndarray-tests-src/axpy.rs"
+    ));
+}
+mod bound {
+    include!(concat!(
+        env!("CARGO_MANIFEST_DIR"),
+        "/../../ndarray-tests-src/bound.rs"
+    ));
+}
 mod conj {
     include!(concat!(env!("CARGO_MANIFEST_DIR"), "/../../ndarray-tests-src/conj.rs"));
 }
@@ -31,6 +49,9 @@ mod mul {
 mod random {
     include!(concat!(env!("CARGO_MANIFEST_DIR"), "/../../ndarray-tests-src/random.rs"));
 }
+mod scal {
+    include!(concat!(env!("CARGO_MANIFEST_DIR"), "/../../ndarray-tests-src/scal.rs"));
+}
 mod scaledadd {
     include!(concat!(env!("CARGO_MANIFEST_DIR"), "/../../ndarray-tests-src/scaledadd.rs"));
 }
@@ -4,6 +4,24 @@ mod add {
         "/../../ndarray-tests-src/add.rs"
     ));
 }
+mod allfinite {
+    include!(concat!(
+        env!("CARGO_MANIFEST_DIR"),
+        "/../../ndarray-tests-src/allfinite.rs"
+    ));
+}
+mod axpy {
+    include!(concat!(
+        env!("CARGO_MANIFEST_DIR"),
+        "/../../ndarray-tests-src/axpy.rs"
+    ));
+}
+mod bound {
+    include!(concat!(
+        env!("CARGO_MANIFEST_DIR"),
+        "/../../ndarray-tests-src/bound.rs"
+    ));
+}
 mod conj {
     include!(concat!(
         env!("CARGO_MANIFEST_DIR"),
@@ -64,6 +82,12 @@ mod random {
         "/../../ndarray-tests-src/random.rs"
     ));
 }
+mod scal {
+    include!(concat!(
+        env!("CARGO_MANIFEST_DIR"),
+        "/../../ndarray-tests-src/scal.rs"
+    ));
+}
 mod scaledadd {
     include!(concat!(
         env!("CARGO_MANIFEST_DIR"),
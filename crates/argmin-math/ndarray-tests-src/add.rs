@@ -12,11 +12,45 @@ mod tests {
     use approx::assert_relative_eq;
     use argmin_math::ArgminAdd;
     use ndarray::array;
-    use ndarray::{Array1, Array2};
+    use ndarray::{Array1, Array2, ArrayView1, ArrayView2};
     use paste::item;
 
     macro_rules! make_test {
         ($t:ty) => {
+            item! {
+                #[test]
+                fn [<test_add_views_ $t>]() {
+                    let m = array![
+                        [1 as $t, 2 as $t, 3 as $t],
+                        [4 as $t, 5 as $t, 6 as $t]
+                    ];
+                    // Contiguous rows
+                    let res = <ArrayView1<$t> as ArgminAdd<ArrayView1<$t>, Array1<$t>>>::add(&m.row(0), &m.row(1));
+                    let target = array![5 as $t, 7 as $t, 9 as $t];
+                    for i in 0..3 {
+                        assert_relative_eq!(target[i] as f64, res[i] as f64, epsilon = f64::EPSILON);
+                    }
+                    // Strided column and scalar
+                    let res = <ArrayView1<$t> as ArgminAdd<$t, Array1<$t>>>::add(&m.column(1), &(2 as $t));
+                    let target = array![4 as $t, 7 as $t];
+                    for i in 0..2 {
+                        assert_relative_eq!(target[i] as f64, res[i] as f64, epsilon = f64::EPSILON);
+                    }
+                    let res = <$t as ArgminAdd<ArrayView1<$t>, Array1<$t>>>::add(&(2 as $t), &m.column(1));
+                    let target = array![4 as $t, 7 as $t];
+                    for i in 0..2 {
+                        assert_relative_eq!(target[i] as f64, res[i] as f64, epsilon = f64::EPSILON);
+                    }
+                    // Transposed view and owned array
+                    let res = <ArrayView2<$t> as ArgminAdd<Array2<$t>, Array2<$t>>>::add(&m.t(), &m.t().to_owned());
+                    for i in 0..3 {
+                        for j in 0..2 {
+                            assert_relative_eq!(2.0 * (m[(j, i)] as f64), res[(i, j)] as f64, epsilon = f64::EPSILON);
+                        }
+                    }
+                }
+            }
+
             item! {
                 #[test]
                 fn [<test_add_vec_scalar_ $t>]() {
@@ -0,0 +1,68 @@
+// Copyright 2018-2024 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+#[cfg(test)]
+mod tests {
+    #[allow(unused_imports)]
+    use super::*;
+    use argmin_math::ArgminAllFinite;
+    use ndarray::{array, Array1, Array2};
+    use num_complex::Complex;
+    use paste::item;
+
+    macro_rules! make_test {
+        ($t:ty) => {
+            item! {
+                #[test]
+                fn [<test_allfinite_ $t>]() {
+                    let a = array![4 as $t, 3 as $t];
+                    assert!(<Array1<$t> as ArgminAllFinite>::all_finite(&a));
+                    let a = array![Complex::new(4 as $t, 2 as $t), Complex::new(3 as $t, 4 as $t)];
+                    assert!(<Array1<Complex<$t>> as ArgminAllFinite>::all_finite(&a));
+                }
+            }
+
+            item! {
+                #[test]
+                fn [<test_allfinite_mat_ $t>]() {
+                    let a = array![[4 as $t, 3 as $t], [1 as $t, 2 as $t]];
+                    assert!(<Array2<$t> as ArgminAllFinite>::all_finite(&a));
+                }
+            }
+        };
+    }
+
+    macro_rules! make_test_float {
+        ($t:ident) => {
+            item! {
+                #[test]
+                fn [<test_allfinite_nonfinite_ $t>]() {
+                    let a = array![4 as $t, $t::NAN];
+                    assert!(!<Array1<$t> as ArgminAllFinite>::all_finite(&a));
+                    let a = array![$t::INFINITY, 4 as $t];
+                    assert!(!<Array1<$t> as ArgminAllFinite>::all_finite(&a));
+                    let a = array![[4 as $t, 3 as $t], [1 as $t, $t::NEG_INFINITY]];
+                    assert!(!<Array2<$t> as ArgminAllFinite>::all_finite(&a));
+                }
+            }
+        };
+    }
+
+    make_test!(i8);
+    make_test!(u8);
+    make_test!(i16);
+    make_test!(u16);
+    make_test!(i32);
+    make_test!(u32);
+    make_test!(i64);
+    make_test!(u64);
+    make_test!(f32);
+    make_test!(f64);
+
+    make_test_float!(f32);
+    make_test_float!(f64);
+}
@@ -0,0 +1,110 @@
+// Copyright 2018-2024 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+#[cfg(test)]
+mod tests {
+    #[allow(unused_imports)]
+    use super::*;
+    use approx::assert_relative_eq;
+    use argmin_math::ArgminAxpy;
+    use ndarray::{array, Array1, Array2};
+    use paste::item;
+
+    macro_rules! make_test {
+        ($t:ty) => {
+            item! {
+                #[test]
+                fn [<test_axpy_vec_ $t>]() {
+                    let mut a = array![1 as $t, 2 as $t, 3 as $t];
+                    let b = 2 as $t;
+                    let c = array![4 as $t, 5 as $t, 6 as $t];
+                    <Array1<$t> as ArgminAxpy<Array1<$t>, $t>>::axpy(&mut a, &b, &c);
+                    let target = array![9 as $t, 12 as $t, 15 as $t];
+                    for i in 0..3 {
+                        assert_relative_eq!(a[i] as f64, target[i] as f64, epsilon = f64::EPSILON);
+                    }
+                }
+            }
+
+            item! {
+                #[test]
+                #[should_panic]
+                fn [<test_axpy_vec_panic_1_ $t>]() {
+                    let mut a = array![1 as $t, 2 as $t, 3 as $t];
+                    let b = 2 as $t;
+                    let c = array![4 as $t, 5 as $t];
+                    <Array1<$t> as ArgminAxpy<Array1<$t>, $t>>::axpy(&mut a, &b, &c);
+                }
+            }
+
+            item! {
+                #[test]
+                #[should_panic]
+                fn [<test_axpy_vec_panic_2_ $t>]() {
+                    let mut a = array![1 as $t, 2 as $t];
+                    let b = 2 as $t;
+                    let c = array![4 as $t, 5 as $t, 6 as $t];
+                    <Array1<$t> as ArgminAxpy<Array1<$t>, $t>>::axpy(&mut a, &b, &c);
+                }
+            }
+
+            item! {
+                #[test]
+                fn [<test_axpy_mat_ $t>]() {
+                    let mut a = array![
+                        [1 as $t, 2 as $t],
+                        [3 as $t, 4 as $t]
+                    ];
+                    let b = 2 as $t;
+                    let c = array![
+                        [4 as $t, 5 as $t],
+                        [6 as $t, 7 as $t]
+                    ];
+                    <Array2<$t> as ArgminAxpy<Array2<$t>, $t>>::axpy(&mut a, &b, &c);
+                    let target = array![
+                        [9 as $t, 12 as $t],
+                        [15 as $t, 18 as $t]
+                    ];
+                    for i in 0..2 {
+                        for j in 0..2 {
+                            assert_relative_eq!(
+                                a[(i, j)] as f64,
+                                target[(i, j)] as f64,
+                                epsilon = f64::EPSILON
+                            );
+                        }
+                    }
+                }
+            }
+
+            item! {
+                #[test]
+                #[should_panic]
+                fn [<test_axpy_mat_panic_ $t>]() {
+                    let mut a = array![
+                        [1 as $t, 2 as $t],
+                        [3 as $t, 4 as $t]
+                    ];
+                    let b = 2 as $t;
+                    let c = array![[4 as $t, 5 as $t]];
+                    <Array2<$t> as ArgminAxpy<Array2<$t>, $t>>::axpy(&mut a, &b, &c);
+                }
+            }
+        };
+    }
+
+    make_test!(i8);
+    make_test!(u8);
+    make_test!(i16);
+    make_test!(u16);
+    make_test!(i32);
+    make_test!(u32);
+    make_test!(i64);
+    make_test!(u64);
+    make_test!(f32);
+    make_test!(f64);
+}
@@ -0,0 +1,86 @@
+// Copyright 2018-2024 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+#[cfg(test)]
+mod tests {
+    #[allow(unused_imports)]
+    use super::*;
+    use approx::assert_relative_eq;
+    use argmin_math::ArgminBound;
+    use ndarray::{array, Array1, Array2};
+    use num_complex::Complex;
+    use paste::item;
+
+    macro_rules! make_test {
+        ($t:ident) => {
+            item! {
+                #[test]
+                fn [<test_max_step_in_bounds_ $t>]() {
+                    let x = array![0 as $t, 0.5 as $t];
+                    let l = array![-1 as $t, -1 as $t];
+                    let u = array![1 as $t, 1 as $t];
+                    let alpha: $t = x.max_step_in_bounds(&array![2 as $t, 1 as $t], &l, &u);
+                    assert_relative_eq!(alpha as f64, 0.5, epsilon = $t::EPSILON as f64);
+                    let alpha: $t = x.max_step_in_bounds(&array![0 as $t, 0 as $t], &l, &u);
+                    assert!(alpha.is_infinite());
+                }
+            }
+
+            item! {
+                #[test]
+                fn [<test_max_step_in_bounds_complex_ $t>]() {
+                    let x = array![Complex::new(0 as $t, 0.5 as $t)];
+                    let d = array![Complex::new(2 as $t, 1 as $t)];
+                    let l = array![Complex::new(-1 as $t, -1 as $t)];
+                    let u = array![Complex::new(1 as $t, 1 as $t)];
+                    let alpha: $t = x.max_step_in_bounds(&d, &l, &u);
+                    assert_relative_eq!(alpha as f64, 0.5, epsilon = $t::EPSILON as f64);
+                }
+            }
+
+            item! {
+                #[test]
+                fn [<test_max_step_in_bounds_mat_ $t>]() {
+                    let x = array![[0 as $t, 0.5 as $t], [0 as $t, 0 as $t]];
+                    let d = array![[0 as $t, 1 as $t], [-4 as $t, 0 as $t]];
+                    let l = Array2::from_elem((2, 2), -1 as $t);
+                    let u = Array2::from_elem((2, 2), 1 as $t);
+                    let alpha: $t = x.max_step_in_bounds(&d, &l, &u);
+                    assert_relative_eq!(alpha as f64, 0.25, epsilon = $t::EPSILON as f64);
+                }
+            }
+
+            item! {
+                #[test]
+                fn [<test_project_direction_ $t>]() {
+                    let x = array![1 as $t, -1 as $t, 0 as $t, 1 as $t];
+                    let d = array![2 as $t, -3 as $t, 4 as $t, -5 as $t];
+                    let l = Array1::from_elem(4, -1 as $t);
+                    let u = Array1::from_elem(4, 1 as $t);
+                    let p = <Array1<$t> as ArgminBound<Array1<$t>, $t>>::project_direction(
+                        &x, &d, &l, &u
+                    );
+                    assert_eq!(p, array![0 as $t, 0 as $t, 4 as $t, -5 as $t]);
+                }
+            }
+
+            item! {
+                #[test]
+                #[should_panic]
+                fn [<test_max_step_in_bounds_panic_ $t>]() {
+                    let x = array![0 as $t, 0.5 as $t];
+                    let l = array![-1 as $t];
+                    let u = array![1 as $t, 1 as $t];
+                    let _: $t = x.max_step_in_bounds(&array![2 as $t, 1 as $t], &l, &u);
+                }
+            }
+        };
+    }
+
+    make_test!(f32);
+    make_test!(f64);
+}
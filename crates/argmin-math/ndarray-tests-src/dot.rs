@@ -12,12 +12,46 @@ mod tests {
     use approx::assert_relative_eq;
     use argmin_math::ArgminDot;
     use ndarray::array;
-    use ndarray::{Array1, Array2};
+    use ndarray::s;
+    use ndarray::{Array1, Array2, ArrayView1, ArrayView2};
     use num_complex::Complex;
     use paste::item;
 
     macro_rules! make_test {
         ($t:ty) => {
+            item! {
+                #[test]
+                fn [<test_dot_views_ $t>]() {
+                    let m = array![
+                        [1 as $t, 2 as $t, 3 as $t],
+                        [4 as $t, 5 as $t, 6 as $t],
+                        [7 as $t, 8 as $t, 9 as $t]
+                    ];
+                    // Contiguous row and strided column
+                    let res: $t = <ArrayView1<$t> as ArgminDot<ArrayView1<$t>, $t>>::dot(&m.row(0), &m.column(2));
+                    assert_relative_eq!(res as f64, 42 as f64, epsilon = f64::EPSILON);
+                    // Owned array and view
+                    let a = array![1 as $t, 1 as $t, 1 as $t];
+                    let res: $t = <Array1<$t> as ArgminDot<ArrayView1<$t>, $t>>::dot(&a, &m.column(2));
+                    assert_relative_eq!(res as f64, 18 as f64, epsilon = f64::EPSILON);
+                    // Transposed matrix and vector
+                    let res: Array1<$t> = <ArrayView2<$t> as ArgminDot<ArrayView1<$t>, Array1<$t>>>::dot(&m.t(), &m.row(0));
+                    let target = array![30 as $t, 36 as $t, 42 as $t];
+                    for i in 0..3 {
+                        assert_relative_eq!(target[i] as f64, res[i] as f64, epsilon = f64::EPSILON);
+                    }
+                    // Sliced matrix and its transpose
+                    let b = m.slice(s![1.., ..2]);
+                    let res: Array2<$t> = <ArrayView2<$t> as ArgminDot<ArrayView2<$t>, Array2<$t>>>::dot(&b, &b.t());
+                    let target = array![[41 as $t, 68 as $t], [68 as $t, 113 as $t]];
+                    for i in 0..2 {
+                        for j in 0..2 {
+                            assert_relative_eq!(target[(i, j)] as f64, res[(i, j)] as f64, epsilon = f64::EPSILON);
+                        }
+                    }
+                }
+            }
+
             item! {
                 #[test]
                 fn [<test_vec_vec_ $t>]() {
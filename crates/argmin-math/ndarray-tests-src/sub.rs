@@ -12,11 +12,45 @@ mod tests {
     use approx::assert_relative_eq;
     use argmin_math::ArgminSub;
     use ndarray::array;
-    use ndarray::{Array1, Array2};
+    use ndarray::{Array1, Array2, ArrayView1, ArrayView2};
     use paste::item;
 
     macro_rules! make_test {
         ($t:ty) => {
+            item! {
+                #[test]
+                fn [<test_sub_views_ $t>]() {
+                    let m = array![
+                        [4 as $t, 5 as $t, 6 as $t],
+                        [1 as $t, 2 as $t, 3 as $t]
+                    ];
+                    // Contiguous rows
+                    let res = <ArrayView1<$t> as ArgminSub<ArrayView1<$t>, Array1<$t>>>::sub(&m.row(0), &m.row(1));
+                    let target = array![3 as $t, 3 as $t, 3 as $t];
+                    for i in 0..3 {
+                        assert_relative_eq!(target[i] as f64, res[i] as f64, epsilon = f64::EPSILON);
+                    }
+                    // Strided column and scalar
+                    let res = <ArrayView1<$t> as ArgminSub<$t, Array1<$t>>>::sub(&m.column(1), &(1 as $t));
+                    let target = array![4 as $t, 1 as $t];
+                    for i in 0..2 {
+                        assert_relative_eq!(target[i] as f64, res[i] as f64, epsilon = f64::EPSILON);
+                    }
+                    let res = <$t as ArgminSub<ArrayView1<$t>, Array1<$t>>>::sub(&(7 as $t), &m.column(1));
+                    let target = array![2 as $t, 5 as $t];
+                    for i in 0..2 {
+                        assert_relative_eq!(target[i] as f64, res[i] as f64, epsilon = f64::EPSILON);
+                    }
+                    // Transposed view and owned array
+                    let res = <ArrayView2<$t> as ArgminSub<Array2<$t>, Array2<$t>>>::sub(&m.t(), &m.t().to_owned());
+                    for i in 0..3 {
+                        for j in 0..2 {
+                            assert_relative_eq!(0.0, res[(i, j)] as f64, epsilon = f64::EPSILON);
+                        }
+                    }
+                }
+            }
+
             item! {
                 #[test]
                 fn [<test_sub_vec_scalar_ $t>]() {
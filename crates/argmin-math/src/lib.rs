@@ -37,6 +37,36 @@
 //! | `primitives`           | yes     | basic integer and floating point types                |
 //! | `vec`                  | yes     | `Vec`s (basic functionality)                          |
 //!
+//! ### Accelerated `Vec` kernels
+//!
+//! | Feature                | Default | Comment                                               |
+//! |------------------------|---------|-------------------------------------------------------|
+//! | `vec_faer`             | no      | dispatches `Vec<f32>`/`Vec<f64>` kernels to `faer`    |
+//! | `vec_simd`             | no      | explicit SIMD kernels for `Vec<f32>`/`Vec<f64>`       |
+//!
+//! By default, the `Vec` backend implements all operations as plain loops. The compiler
+//! vectorizes element-wise operations such as additions on its own, but not reductions such as dot
+//! products and l2-norms of floating point numbers, since this would change the order of
+//! summation. With the `vec_faer` feature, these reductions are computed for `Vec<f32>` and
+//! `Vec<f64>` with the SIMD-accelerated kernels of the pure Rust linear algebra library
+//! [`faer`](https://crates.io/crates/faer). The `vec_simd` feature provides explicit SIMD
+//! implementations based on the lightweight [`wide`](https://crates.io/crates/wide) crate instead.
+//! If both features are enabled, `vec_faer` takes precedence. This transparently speeds up solvers
+//! such as L-BFGS and nonlinear conjugate gradient on large dense problems without linking against
+//! a BLAS library. The results may differ from the plain loops in the last bits due to a different
+//! order of summation.
+//!
+//! In addition, the `ArgminAxpy` trait offers in-place scaled additions (`self += factor * vec`)
+//! and the `ArgminScal` trait in-place scaling (`self *= factor`), which avoid the allocations of
+//! `ArgminScaledAdd` and `ArgminMul`, respectively.
+//!
+//! The gain can be measured with the benchmarks of this crate, which compare the operations to
+//! plain loops:
+//!
+//! ```bash
+//! cargo bench -p argmin-math --bench vec --features vec_simd
+//! ```
+//!
 //! ### `ndarray`
 //!
 //! | Feature                         | Default | Comment                                                            |
@@ -63,6 +93,10 @@
 //! ndarray-linalg = { version = "<appropriate_version>", features = ["<linalg_backend>"] }
 //! ```
 //!
+//! `ArgminAdd`, `ArgminSub`, `ArgminMul`, `ArgminDiv` and `ArgminDot` are implemented for any
+//! `ArrayBase` with `Data` storage and therefore also accept array views such as rows, columns,
+//! slices and transposes without copying them first. The results are always owned arrays.
+//!
 //! ### `nalgebra`
 //!
 //! | Feature                | Default | Comment                                  |
@@ -74,6 +108,18 @@
 //! | `nalgebra_v0_30`       | no      | version 0.30                             |
 //! | `nalgebra_v0_29`       | no      | version 0.29                             |
 //!
+//! ### High precision floating point types
+//!
+//! | Feature                | Default | Comment                                               |
+//! |------------------------|---------|-------------------------------------------------------|
+//! | `twofloat`             | no      | `TwoFloat` (double-double precision), also in `Vec`s  |
+//!
+//! Any type which is `Copy` and implements the `num_traits` float traits can serve as the float
+//! type of argmin. For ill-conditioned problems where `f64` stagnates, the `twofloat` feature
+//! provides the math implementations for `twofloat::TwoFloat`, which offers roughly twice the
+//! precision of `f64`. Arbitrary precision types such as `rug::Float` are not `Copy` and can
+//! therefore not be used.
+//!
 //!
 //! ## Choosing a backend
 //!
@@ -96,6 +142,21 @@
 #![doc = concat!("argmin-math = { version = \"", env!("CARGO_PKG_VERSION"), "\", features = [\"ndarray_latest\"] }")]
 //! ```
 //!
+//! # Complex numbers
+//!
+//! Most traits are also implemented for complex numbers. With the `vec` backend, gradient based
+//! solvers such as L-BFGS and nonlinear conjugate gradient can minimize real-valued cost functions
+//! of complex parameter vectors (`Vec<Complex<f64>>`) by treating them as real vectors of twice the length, holding the real and imaginary parts. The
+//! gradient must be provided in the same form: the derivatives with respect to the real and
+//! imaginary parts of each parameter are the real and imaginary part of the corresponding element
+//! of the gradient. This is twice the Wirtinger derivative with respect to the complex conjugate
+//! of the parameters.
+//!
+//! Accordingly, `ArgminDot<Vec<Complex<T>>, T>` computes the real part of the Hermitian inner
+//! product, and `ArgminSignum`, `ArgminMinMax` as well as adding or subtracting a real scalar
+//! operate on the real and imaginary parts separately. The `ArgminDot<Vec<Complex<T>>,
+//! Complex<T>>` implementation on the other hand is the (non-conjugated) bilinear product.
+//!
 //! # Semantic versioning
 //!
 //! This crate follows semantic versioning. Adding a new backend or a new version of a backend is
@@ -237,6 +298,9 @@ mod vec;
 #[allow(unused_imports)]
 pub use crate::vec::*;
 
+#[cfg(feature = "twofloat")]
+mod twofloat_m;
+
 // Re-export of types appearing in the api as recommended here: https://www.lurklurk.org/effective-rust/re-export.html
 pub use anyhow::Error;
 pub use rand::Rng;
@@ -318,6 +382,18 @@ pub trait ArgminScaledSub<T, U, V> {
     fn scaled_sub(&self, factor: &U, vec: &T) -> V;
 }
 
+/// Add a `T` scaled by an `U` to `self` in place (`self += factor * vec`)
+pub trait ArgminAxpy<T, U> {
+    /// Add a `T` scaled by an `U` to `self` in place (`self += factor * vec`)
+    fn axpy(&mut self, factor: &U, vec: &T);
+}
+
+/// Scale `self` by an `U` in place (`self *= factor`)
+pub trait ArgminScal<U> {
+    /// Scale `self` by an `U` in place (`self *= factor`)
+    fn scal(&mut self, factor: &U);
+}
+
 /// Compute the l1-norm (`U`) of `self`
 pub trait ArgminL1Norm<U> {
     /// Compute the l1-norm (`U`) of `self`
@@ -362,3 +438,24 @@ pub trait ArgminSignum {
     /// Returns a number that represents the sign of `self`.
     fn signum(self) -> Self;
 }
+
+/// Checks whether all elements of `self` are finite (neither NaN nor infinite)
+pub trait ArgminAllFinite {
+    /// Returns `true` if all elements of `self` are finite
+    fn all_finite(&self) -> bool;
+}
+
+/// Steps from `self` along a direction (`D`) within the box given by `lower` and `upper`
+///
+/// The real and imaginary parts of complex numbers are treated as independent entries.
+pub trait ArgminBound<D, F> {
+    /// Returns the largest step length `alpha >= 0` (`F`) for which `self + alpha * direction`
+    /// lies within `lower` and `upper`.
+    ///
+    /// The step length is infinite if the bounds do not restrict steps along `direction`.
+    fn max_step_in_bounds(&self, direction: &D, lower: &Self, upper: &Self) -> F;
+
+    /// Returns `direction` with all entries set to zero which would immediately leave the bounds,
+    /// i.e. which point below `lower` or above `upper` where `self` lies on that bound.
+    fn project_direction(&self, direction: &D, lower: &Self, upper: &Self) -> D;
+}
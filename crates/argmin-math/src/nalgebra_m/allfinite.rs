@@ -0,0 +1,81 @@
+// Copyright 2018-2024 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use crate::ArgminAllFinite;
+
+use nalgebra::{
+    base::{dimension::Dim, storage::Storage},
+    Matrix, Scalar,
+};
+
+impl<N, R, C, S> ArgminAllFinite for Matrix<N, R, C, S>
+where
+    N: Scalar + ArgminAllFinite,
+    R: Dim,
+    C: Dim,
+    S: Storage<N, R, C>,
+{
+    #[inline]
+    fn all_finite(&self) -> bool {
+        self.iter().all(|a| a.all_finite())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use nalgebra::{Matrix2x3, Vector2};
+    use paste::item;
+
+    macro_rules! make_test {
+        ($t:ty) => {
+            item! {
+                #[test]
+                fn [<test_allfinite_ $t>]() {
+                    let a = Vector2::new(4 as $t, 3 as $t);
+                    assert!(<Vector2<$t> as ArgminAllFinite>::all_finite(&a));
+                    let a = Matrix2x3::new(
+                        1 as $t, 2 as $t, 3 as $t,
+                        4 as $t, 5 as $t, 6 as $t
+                    );
+                    assert!(<Matrix2x3<$t> as ArgminAllFinite>::all_finite(&a));
+                }
+            }
+        };
+    }
+
+    macro_rules! make_test_float {
+        ($t:ident) => {
+            item! {
+                #[test]
+                fn [<test_allfinite_nonfinite_ $t>]() {
+                    let a = Vector2::new(4 as $t, $t::NAN);
+                    assert!(!<Vector2<$t> as ArgminAllFinite>::all_finite(&a));
+                    let a = Matrix2x3::new(
+                        1 as $t, 2 as $t, 3 as $t,
+                        4 as $t, 5 as $t, $t::INFINITY
+                    );
+                    assert!(!<Matrix2x3<$t> as ArgminAllFinite>::all_finite(&a));
+                }
+            }
+        };
+    }
+
+    make_test!(i8);
+    make_test!(u8);
+    make_test!(i16);
+    make_test!(u16);
+    make_test!(i32);
+    make_test!(u32);
+    make_test!(i64);
+    make_test!(u64);
+    make_test!(f32);
+    make_test!(f64);
+
+    make_test_float!(f32);
+    make_test_float!(f64);
+}
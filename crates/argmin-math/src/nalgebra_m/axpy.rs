@@ -0,0 +1,116 @@
+// Copyright 2018-2024 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use crate::ArgminAxpy;
+
+use crate::{ClosedAdd, ClosedMul};
+use nalgebra::{
+    base::{
+        constraint::{SameNumberOfColumns, SameNumberOfRows, ShapeConstraint},
+        dimension::Dim,
+        storage::{Storage, StorageMut},
+        Scalar,
+    },
+    Matrix,
+};
+
+impl<N, R1, C1, R2, C2, SA, SB> ArgminAxpy<Matrix<N, R2, C2, SB>, N> for Matrix<N, R1, C1, SA>
+where
+    N: Scalar + Copy + ClosedAdd + ClosedMul,
+    R1: Dim,
+    C1: Dim,
+    R2: Dim,
+    C2: Dim,
+    SA: StorageMut<N, R1, C1>,
+    SB: Storage<N, R2, C2>,
+    ShapeConstraint: SameNumberOfRows<R1, R2> + SameNumberOfColumns<C1, C2>,
+{
+    #[inline]
+    fn axpy(&mut self, factor: &N, vec: &Matrix<N, R2, C2, SB>) {
+        assert_eq!(self.shape(), vec.shape());
+        self.iter_mut()
+            .zip(vec.iter())
+            .for_each(|(a, b)| *a += *factor * *b);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_relative_eq;
+    use nalgebra::{DVector, Matrix2, Vector3};
+    use paste::item;
+
+    macro_rules! make_test {
+        ($t:ty) => {
+            item! {
+                #[test]
+                fn [<test_axpy_vec_ $t>]() {
+                    let mut a = Vector3::new(1 as $t, 2 as $t, 3 as $t);
+                    let b = 2 as $t;
+                    let c = Vector3::new(4 as $t, 5 as $t, 6 as $t);
+                    <Vector3<$t> as ArgminAxpy<Vector3<$t>, $t>>::axpy(&mut a, &b, &c);
+                    let target = Vector3::new(9 as $t, 12 as $t, 15 as $t);
+                    for i in 0..3 {
+                        assert_relative_eq!(a[i] as f64, target[i] as f64, epsilon = f64::EPSILON);
+                    }
+                }
+            }
+
+            item! {
+                #[test]
+                #[should_panic]
+                fn [<test_axpy_vec_panic_ $t>]() {
+                    let mut a = DVector::from_vec(vec![1 as $t, 2 as $t, 3 as $t]);
+                    let b = 2 as $t;
+                    let c = DVector::from_vec(vec![4 as $t, 5 as $t]);
+                    <DVector<$t> as ArgminAxpy<DVector<$t>, $t>>::axpy(&mut a, &b, &c);
+                }
+            }
+
+            item! {
+                #[test]
+                fn [<test_axpy_mat_ $t>]() {
+                    let mut a = Matrix2::new(
+                        1 as $t, 2 as $t,
+                        3 as $t, 4 as $t
+                    );
+                    let b = 2 as $t;
+                    let c = Matrix2::new(
+                        4 as $t, 5 as $t,
+                        6 as $t, 7 as $t
+                    );
+                    <Matrix2<$t> as ArgminAxpy<Matrix2<$t>, $t>>::axpy(&mut a, &b, &c);
+                    let target = Matrix2::new(
+                        9 as $t, 12 as $t,
+                        15 as $t, 18 as $t
+                    );
+                    for i in 0..2 {
+                        for j in 0..2 {
+                            assert_relative_eq!(
+                                a[(i, j)] as f64,
+                                target[(i, j)] as f64,
+                                epsilon = f64::EPSILON
+                            );
+                        }
+                    }
+                }
+            }
+        };
+    }
+
+    make_test!(i8);
+    make_test!(u8);
+    make_test!(i16);
+    make_test!(u16);
+    make_test!(i32);
+    make_test!(u32);
+    make_test!(i64);
+    make_test!(u64);
+    make_test!(f32);
+    make_test!(f64);
+}
@@ -0,0 +1,129 @@
+// Copyright 2018-2024 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use crate::{Allocator, ArgminBound};
+
+use nalgebra::{
+    base::{dimension::Dim, Scalar},
+    DefaultAllocator, OMatrix,
+};
+use num_traits::Float;
+
+impl<N, R, C, F> ArgminBound<OMatrix<N, R, C>, F> for OMatrix<N, R, C>
+where
+    N: Scalar + ArgminBound<N, F>,
+    R: Dim,
+    C: Dim,
+    F: Float,
+    DefaultAllocator: Allocator<N, R, C>,
+{
+    #[inline]
+    fn max_step_in_bounds(
+        &self,
+        direction: &OMatrix<N, R, C>,
+        lower: &OMatrix<N, R, C>,
+        upper: &OMatrix<N, R, C>,
+    ) -> F {
+        assert_eq!(self.shape(), direction.shape());
+        assert_eq!(self.shape(), lower.shape());
+        assert_eq!(self.shape(), upper.shape());
+
+        self.iter()
+            .zip(direction.iter())
+            .zip(lower.iter().zip(upper.iter()))
+            .map(|((x, d), (l, u))| x.max_step_in_bounds(d, l, u))
+            .fold(F::infinity(), F::min)
+    }
+
+    #[inline]
+    fn project_direction(
+        &self,
+        direction: &OMatrix<N, R, C>,
+        lower: &OMatrix<N, R, C>,
+        upper: &OMatrix<N, R, C>,
+    ) -> OMatrix<N, R, C> {
+        assert_eq!(self.shape(), direction.shape());
+        assert_eq!(self.shape(), lower.shape());
+        assert_eq!(self.shape(), upper.shape());
+
+        let mut out = direction.clone();
+        for (o, (x, (l, u))) in out
+            .iter_mut()
+            .zip(self.iter().zip(lower.iter().zip(upper.iter())))
+        {
+            *o = x.project_direction(o, l, u);
+        }
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_relative_eq;
+    use nalgebra::{Matrix2, Vector2, Vector4};
+    use num_complex::Complex;
+    use paste::item;
+
+    macro_rules! make_test {
+        ($t:ident) => {
+            item! {
+                #[test]
+                fn [<test_max_step_in_bounds_ $t>]() {
+                    let x = Vector2::new(0 as $t, 0.5 as $t);
+                    let l = Vector2::new(-1 as $t, -1 as $t);
+                    let u = Vector2::new(1 as $t, 1 as $t);
+                    let alpha: $t = x.max_step_in_bounds(&Vector2::new(2 as $t, 1 as $t), &l, &u);
+                    assert_relative_eq!(alpha as f64, 0.5, epsilon = $t::EPSILON as f64);
+                    let alpha: $t = x.max_step_in_bounds(&Vector2::new(0 as $t, 0 as $t), &l, &u);
+                    assert!(alpha.is_infinite());
+                }
+            }
+
+            item! {
+                #[test]
+                fn [<test_max_step_in_bounds_complex_ $t>]() {
+                    let x = Vector2::new(Complex::new(0 as $t, 0.5 as $t), Complex::new(0 as $t, 0 as $t));
+                    let d = Vector2::new(Complex::new(2 as $t, 1 as $t), Complex::new(0 as $t, 0 as $t));
+                    let l = Vector2::new(Complex::new(-1 as $t, -1 as $t), Complex::new(-1 as $t, -1 as $t));
+                    let u = Vector2::new(Complex::new(1 as $t, 1 as $t), Complex::new(1 as $t, 1 as $t));
+                    let alpha: $t = x.max_step_in_bounds(&d, &l, &u);
+                    assert_relative_eq!(alpha as f64, 0.5, epsilon = $t::EPSILON as f64);
+                }
+            }
+
+            item! {
+                #[test]
+                fn [<test_max_step_in_bounds_mat_ $t>]() {
+                    let x = Matrix2::new(0 as $t, 0.5 as $t, 0 as $t, 0 as $t);
+                    let d = Matrix2::new(0 as $t, 1 as $t, -4 as $t, 0 as $t);
+                    let l = Matrix2::repeat(-1 as $t);
+                    let u = Matrix2::repeat(1 as $t);
+                    let alpha: $t = x.max_step_in_bounds(&d, &l, &u);
+                    assert_relative_eq!(alpha as f64, 0.25, epsilon = $t::EPSILON as f64);
+                }
+            }
+
+            item! {
+                #[test]
+                fn [<test_project_direction_ $t>]() {
+                    let x = Vector4::new(1 as $t, -1 as $t, 0 as $t, 1 as $t);
+                    let d = Vector4::new(2 as $t, -3 as $t, 4 as $t, -5 as $t);
+                    let l = Vector4::repeat(-1 as $t);
+                    let u = Vector4::repeat(1 as $t);
+                    let p = <Vector4<$t> as ArgminBound<Vector4<$t>, $t>>::project_direction(
+                        &x, &d, &l, &u
+                    );
+                    assert_eq!(p, Vector4::new(0 as $t, 0 as $t, 4 as $t, -5 as $t));
+                }
+            }
+        };
+    }
+
+    make_test!(f32);
+    make_test!(f64);
+}
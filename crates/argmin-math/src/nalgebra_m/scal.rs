@@ -0,0 +1,86 @@
+// Copyright 2018-2024 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use crate::ArgminScal;
+
+use crate::ClosedMul;
+use nalgebra::{
+    base::{dimension::Dim, storage::StorageMut, Scalar},
+    Matrix,
+};
+
+impl<N, R, C, S> ArgminScal<N> for Matrix<N, R, C, S>
+where
+    N: Scalar + Copy + ClosedMul,
+    R: Dim,
+    C: Dim,
+    S: StorageMut<N, R, C>,
+{
+    #[inline]
+    fn scal(&mut self, factor: &N) {
+        self.iter_mut().for_each(|a| *a *= *factor);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_relative_eq;
+    use nalgebra::{Matrix2, Vector3};
+    use paste::item;
+
+    macro_rules! make_test {
+        ($t:ty) => {
+            item! {
+                #[test]
+                fn [<test_scal_vec_ $t>]() {
+                    let mut a = Vector3::new(1 as $t, 2 as $t, 3 as $t);
+                    <Vector3<$t> as ArgminScal<$t>>::scal(&mut a, &(2 as $t));
+                    let target = Vector3::new(2 as $t, 4 as $t, 6 as $t);
+                    for i in 0..3 {
+                        assert_relative_eq!(a[i] as f64, target[i] as f64, epsilon = f64::EPSILON);
+                    }
+                }
+            }
+
+            item! {
+                #[test]
+                fn [<test_scal_mat_ $t>]() {
+                    let mut a = Matrix2::new(
+                        1 as $t, 2 as $t,
+                        3 as $t, 4 as $t
+                    );
+                    <Matrix2<$t> as ArgminScal<$t>>::scal(&mut a, &(2 as $t));
+                    let target = Matrix2::new(
+                        2 as $t, 4 as $t,
+                        6 as $t, 8 as $t
+                    );
+                    for i in 0..2 {
+                        for j in 0..2 {
+                            assert_relative_eq!(
+                                a[(i, j)] as f64,
+                                target[(i, j)] as f64,
+                                epsilon = f64::EPSILON
+                            );
+                        }
+                    }
+                }
+            }
+        };
+    }
+
+    make_test!(i8);
+    make_test!(u8);
+    make_test!(i16);
+    make_test!(u16);
+    make_test!(i32);
+    make_test!(u32);
+    make_test!(i64);
+    make_test!(u64);
+    make_test!(f32);
+    make_test!(f64);
+}
@@ -6,40 +6,57 @@
 // copied, modified, or distributed except according to those terms.
 
 use crate::ArgminAdd;
-use ndarray::{Array1, Array2};
+use ndarray::{Array1, Array2, ArrayBase, Data, Ix1, Ix2};
 use num_complex::Complex;
 
 macro_rules! make_add {
     ($t:ty) => {
-        impl ArgminAdd<$t, Array1<$t>> for Array1<$t> {
+        impl<S> ArgminAdd<$t, Array1<$t>> for ArrayBase<S, Ix1>
+        where
+            S: Data<Elem = $t>,
+        {
             #[inline]
             fn add(&self, other: &$t) -> Array1<$t> {
                 self + *other
             }
         }
 
-        impl ArgminAdd<Array1<$t>, Array1<$t>> for $t {
+        impl<S> ArgminAdd<ArrayBase<S, Ix1>, Array1<$t>> for $t
+        where
+            S: Data<Elem = $t>,
+        {
             #[inline]
-            fn add(&self, other: &Array1<$t>) -> Array1<$t> {
+            fn add(&self, other: &ArrayBase<S, Ix1>) -> Array1<$t> {
                 *self + other
             }
         }
 
-        impl ArgminAdd<Array1<$t>, Array1<$t>> for Array1<$t> {
+        impl<S1, S2> ArgminAdd<ArrayBase<S2, Ix1>, Array1<$t>> for ArrayBase<S1, Ix1>
+        where
+            S1: Data<Elem = $t>,
+            S2: Data<Elem = $t>,
+        {
             #[inline]
-            fn add(&self, other: &Array1<$t>) -> Array1<$t> {
+            fn add(&self, other: &ArrayBase<S2, Ix1>) -> Array1<$t> {
                 self + other
             }
         }
 
-        impl ArgminAdd<Array2<$t>, Array2<$t>> for Array2<$t> {
+        impl<S1, S2> ArgminAdd<ArrayBase<S2, Ix2>, Array2<$t>> for ArrayBase<S1, Ix2>
+        where
+            S1: Data<Elem = $t>,
+            S2: Data<Elem = $t>,
+        {
             #[inline]
-            fn add(&self, other: &Array2<$t>) -> Array2<$t> {
+            fn add(&self, other: &ArrayBase<S2, Ix2>) -> Array2<$t> {
                 self + other
             }
         }
 
-        impl ArgminAdd<$t, Array2<$t>> for Array2<$t> {
+        impl<S> ArgminAdd<$t, Array2<$t>> for ArrayBase<S, Ix2>
+        where
+            S: Data<Elem = $t>,
+        {
             #[inline]
             fn add(&self, other: &$t) -> Array2<$t> {
                 self + *other
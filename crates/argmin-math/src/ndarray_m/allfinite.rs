@@ -0,0 +1,28 @@
+// Copyright 2018-2024 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use crate::ArgminAllFinite;
+use ndarray::{ArrayBase, Data, Dimension};
+
+impl<A, S, D> ArgminAllFinite for ArrayBase<S, D>
+where
+    A: ArgminAllFinite,
+    S: Data<Elem = A>,
+    D: Dimension,
+{
+    #[inline]
+    fn all_finite(&self) -> bool {
+        self.iter().all(|a| a.all_finite())
+    }
+}
+
+#[cfg(test)]
+use crate as argmin_math;
+include!(concat!(
+    env!("CARGO_MANIFEST_DIR"),
+    "/ndarray-tests-src/allfinite.rs"
+));
@@ -0,0 +1,33 @@
+// Copyright 2018-2024 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use crate::ArgminAxpy;
+use ndarray::{ArrayBase, Data, DataMut, Dimension, LinalgScalar};
+
+impl<A, S1, S2, D> ArgminAxpy<ArrayBase<S2, D>, A> for ArrayBase<S1, D>
+where
+    A: LinalgScalar,
+    S1: DataMut<Elem = A>,
+    S2: Data<Elem = A>,
+    D: Dimension,
+{
+    #[inline]
+    fn axpy(&mut self, factor: &A, vec: &ArrayBase<S2, D>) {
+        assert_eq!(self.shape(), vec.shape());
+        ArrayBase::scaled_add(self, *factor, vec);
+    }
+}
+
+// All code that does not depend on a linked ndarray-linalg backend can still be tested as normal.
+// To avoid dublicating tests and to allow convenient testing of functionality that does not need ndarray-linalg the tests are still included here.
+// The tests expect the name for the crate containing the tested functions to be argmin_math
+#[cfg(test)]
+use crate as argmin_math;
+include!(concat!(
+    env!("CARGO_MANIFEST_DIR"),
+    "/ndarray-tests-src/axpy.rs"
+));
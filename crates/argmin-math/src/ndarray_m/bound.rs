@@ -0,0 +1,63 @@
+// Copyright 2018-2024 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use crate::ArgminBound;
+use ndarray::{Array, Dimension};
+use num_traits::Float;
+
+impl<A, D, F> ArgminBound<Array<A, D>, F> for Array<A, D>
+where
+    A: Clone + ArgminBound<A, F>,
+    D: Dimension,
+    F: Float,
+{
+    #[inline]
+    fn max_step_in_bounds(
+        &self,
+        direction: &Array<A, D>,
+        lower: &Array<A, D>,
+        upper: &Array<A, D>,
+    ) -> F {
+        assert_eq!(self.shape(), direction.shape());
+        assert_eq!(self.shape(), lower.shape());
+        assert_eq!(self.shape(), upper.shape());
+
+        self.iter()
+            .zip(direction.iter())
+            .zip(lower.iter().zip(upper.iter()))
+            .map(|((x, d), (l, u))| x.max_step_in_bounds(d, l, u))
+            .fold(F::infinity(), F::min)
+    }
+
+    #[inline]
+    fn project_direction(
+        &self,
+        direction: &Array<A, D>,
+        lower: &Array<A, D>,
+        upper: &Array<A, D>,
+    ) -> Array<A, D> {
+        assert_eq!(self.shape(), direction.shape());
+        assert_eq!(self.shape(), lower.shape());
+        assert_eq!(self.shape(), upper.shape());
+
+        let mut out = direction.clone();
+        for (o, (x, (l, u))) in out
+            .iter_mut()
+            .zip(self.iter().zip(lower.iter().zip(upper.iter())))
+        {
+            *o = x.project_direction(o, l, u);
+        }
+        out
+    }
+}
+
+#[cfg(test)]
+use crate as argmin_math;
+include!(concat!(
+    env!("CARGO_MANIFEST_DIR"),
+    "/ndarray-tests-src/bound.rs"
+));
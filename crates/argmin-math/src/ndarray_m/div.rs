@@ -6,35 +6,49 @@
 // copied, modified, or distributed except according to those terms.
 
 use crate::ArgminDiv;
-use ndarray::{Array1, Array2};
+use ndarray::{Array1, Array2, ArrayBase, Data, Ix1, Ix2};
 use num_complex::Complex;
 
 macro_rules! make_div {
     ($t:ty) => {
-        impl ArgminDiv<$t, Array1<$t>> for Array1<$t> {
+        impl<S> ArgminDiv<$t, Array1<$t>> for ArrayBase<S, Ix1>
+        where
+            S: Data<Elem = $t>,
+        {
             #[inline]
             fn div(&self, other: &$t) -> Array1<$t> {
                 self / *other
             }
         }
 
-        impl ArgminDiv<Array1<$t>, Array1<$t>> for $t {
+        impl<S> ArgminDiv<ArrayBase<S, Ix1>, Array1<$t>> for $t
+        where
+            S: Data<Elem = $t>,
+        {
             #[inline]
-            fn div(&self, other: &Array1<$t>) -> Array1<$t> {
+            fn div(&self, other: &ArrayBase<S, Ix1>) -> Array1<$t> {
                 *self / other
             }
         }
 
-        impl ArgminDiv<Array1<$t>, Array1<$t>> for Array1<$t> {
+        impl<S1, S2> ArgminDiv<ArrayBase<S2, Ix1>, Array1<$t>> for ArrayBase<S1, Ix1>
+        where
+            S1: Data<Elem = $t>,
+            S2: Data<Elem = $t>,
+        {
             #[inline]
-            fn div(&self, other: &Array1<$t>) -> Array1<$t> {
+            fn div(&self, other: &ArrayBase<S2, Ix1>) -> Array1<$t> {
                 self / other
             }
         }
 
-        impl ArgminDiv<Array2<$t>, Array2<$t>> for Array2<$t> {
+        impl<S1, S2> ArgminDiv<ArrayBase<S2, Ix2>, Array2<$t>> for ArrayBase<S1, Ix2>
+        where
+            S1: Data<Elem = $t>,
+            S2: Data<Elem = $t>,
+        {
             #[inline]
-            fn div(&self, other: &Array2<$t>) -> Array2<$t> {
+            fn div(&self, other: &ArrayBase<S2, Ix2>) -> Array2<$t> {
                 self / other
             }
         }
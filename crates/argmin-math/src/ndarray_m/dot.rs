@@ -6,121 +6,177 @@
 // copied, modified, or distributed except according to those terms.
 
 use crate::ArgminDot;
-use ndarray::{Array1, Array2};
+use ndarray::{Array1, Array2, ArrayBase, Data, Ix1, Ix2};
 use num_complex::Complex;
 
 macro_rules! make_dot_ndarray {
     ($t:ty) => {
-        impl ArgminDot<Array1<$t>, $t> for Array1<$t> {
+        impl<S1, S2> ArgminDot<ArrayBase<S2, Ix1>, $t> for ArrayBase<S1, Ix1>
+        where
+            S1: Data<Elem = $t>,
+            S2: Data<Elem = $t>,
+        {
             #[inline]
-            fn dot(&self, other: &Array1<$t>) -> $t {
-                ndarray::Array1::dot(self, other)
+            fn dot(&self, other: &ArrayBase<S2, Ix1>) -> $t {
+                ndarray::linalg::Dot::dot(self, other)
             }
         }
 
-        impl ArgminDot<$t, Array1<$t>> for Array1<$t> {
+        impl<S> ArgminDot<$t, Array1<$t>> for ArrayBase<S, Ix1>
+        where
+            S: Data<Elem = $t>,
+        {
             #[inline]
             fn dot(&self, other: &$t) -> Array1<$t> {
                 self.iter().cloned().map(|s| s * *other).collect()
             }
         }
 
-        impl ArgminDot<Array1<$t>, Array1<$t>> for $t {
+        impl<S> ArgminDot<ArrayBase<S, Ix1>, Array1<$t>> for $t
+        where
+            S: Data<Elem = $t>,
+        {
             #[inline]
-            fn dot(&self, other: &Array1<$t>) -> Array1<$t> {
+            fn dot(&self, other: &ArrayBase<S, Ix1>) -> Array1<$t> {
                 other.iter().cloned().map(|o| o * *self).collect()
             }
         }
 
-        impl ArgminDot<Array1<$t>, Array2<$t>> for Array1<$t> {
+        impl<S1, S2> ArgminDot<ArrayBase<S2, Ix1>, Array2<$t>> for ArrayBase<S1, Ix1>
+        where
+            S1: Data<Elem = $t>,
+            S2: Data<Elem = $t>,
+        {
             #[inline]
-            fn dot(&self, other: &Array1<$t>) -> Array2<$t> {
+            fn dot(&self, other: &ArrayBase<S2, Ix1>) -> Array2<$t> {
                 Array2::from_shape_fn((self.len(), other.len()), |(i, j)| self[i] * other[j])
             }
         }
 
-        impl ArgminDot<Array1<$t>, Array1<$t>> for Array2<$t> {
+        impl<S1, S2> ArgminDot<ArrayBase<S2, Ix1>, Array1<$t>> for ArrayBase<S1, Ix2>
+        where
+            S1: Data<Elem = $t>,
+            S2: Data<Elem = $t>,
+        {
             #[inline]
-            fn dot(&self, other: &Array1<$t>) -> Array1<$t> {
-                ndarray::Array2::dot(self, other)
+            fn dot(&self, other: &ArrayBase<S2, Ix1>) -> Array1<$t> {
+                ndarray::linalg::Dot::dot(self, other)
             }
         }
 
-        impl ArgminDot<Array2<$t>, Array2<$t>> for Array2<$t> {
+        impl<S1, S2> ArgminDot<ArrayBase<S2, Ix2>, Array2<$t>> for ArrayBase<S1, Ix2>
+        where
+            S1: Data<Elem = $t>,
+            S2: Data<Elem = $t>,
+        {
             #[inline]
-            fn dot(&self, other: &Array2<$t>) -> Array2<$t> {
-                ndarray::Array2::dot(self, other)
+            fn dot(&self, other: &ArrayBase<S2, Ix2>) -> Array2<$t> {
+                ndarray::linalg::Dot::dot(self, other)
             }
         }
 
-        impl ArgminDot<$t, Array2<$t>> for Array2<$t> {
+        impl<S> ArgminDot<$t, Array2<$t>> for ArrayBase<S, Ix2>
+        where
+            S: Data<Elem = $t>,
+        {
             #[inline]
             fn dot(&self, other: &$t) -> Array2<$t> {
                 Array2::from_shape_fn((self.nrows(), self.ncols()), |(i, j)| *other * self[(i, j)])
             }
         }
 
-        impl ArgminDot<Array2<$t>, Array2<$t>> for $t {
+        impl<S> ArgminDot<ArrayBase<S, Ix2>, Array2<$t>> for $t
+        where
+            S: Data<Elem = $t>,
+        {
             #[inline]
-            fn dot(&self, other: &Array2<$t>) -> Array2<$t> {
+            fn dot(&self, other: &ArrayBase<S, Ix2>) -> Array2<$t> {
                 Array2::from_shape_fn((other.nrows(), other.ncols()), |(i, j)| {
                     *self * other[(i, j)]
                 })
             }
         }
 
-        impl ArgminDot<Array1<Complex<$t>>, Complex<$t>> for Array1<Complex<$t>> {
+        impl<S1, S2> ArgminDot<ArrayBase<S2, Ix1>, Complex<$t>> for ArrayBase<S1, Ix1>
+        where
+            S1: Data<Elem = Complex<$t>>,
+            S2: Data<Elem = Complex<$t>>,
+        {
             #[inline]
-            fn dot(&self, other: &Array1<Complex<$t>>) -> Complex<$t> {
-                ndarray::Array1::dot(self, other)
+            fn dot(&self, other: &ArrayBase<S2, Ix1>) -> Complex<$t> {
+                ndarray::linalg::Dot::dot(self, other)
             }
         }
 
-        impl ArgminDot<Complex<$t>, Array1<Complex<$t>>> for Array1<Complex<$t>> {
+        impl<S> ArgminDot<Complex<$t>, Array1<Complex<$t>>> for ArrayBase<S, Ix1>
+        where
+            S: Data<Elem = Complex<$t>>,
+        {
             #[inline]
             fn dot(&self, other: &Complex<$t>) -> Array1<Complex<$t>> {
                 self.iter().cloned().map(|s| s * *other).collect()
             }
         }
 
-        impl ArgminDot<Array1<Complex<$t>>, Array1<Complex<$t>>> for Complex<$t> {
+        impl<S> ArgminDot<ArrayBase<S, Ix1>, Array1<Complex<$t>>> for Complex<$t>
+        where
+            S: Data<Elem = Complex<$t>>,
+        {
             #[inline]
-            fn dot(&self, other: &Array1<Complex<$t>>) -> Array1<Complex<$t>> {
+            fn dot(&self, other: &ArrayBase<S, Ix1>) -> Array1<Complex<$t>> {
                 other.iter().cloned().map(|o| o * *self).collect()
             }
         }
 
-        impl ArgminDot<Array1<Complex<$t>>, Array2<Complex<$t>>> for Array1<Complex<$t>> {
+        impl<S1, S2> ArgminDot<ArrayBase<S2, Ix1>, Array2<Complex<$t>>> for ArrayBase<S1, Ix1>
+        where
+            S1: Data<Elem = Complex<$t>>,
+            S2: Data<Elem = Complex<$t>>,
+        {
             #[inline]
-            fn dot(&self, other: &Array1<Complex<$t>>) -> Array2<Complex<$t>> {
+            fn dot(&self, other: &ArrayBase<S2, Ix1>) -> Array2<Complex<$t>> {
                 Array2::from_shape_fn((self.len(), other.len()), |(i, j)| self[i] * other[j])
             }
         }
 
-        impl ArgminDot<Array1<Complex<$t>>, Array1<Complex<$t>>> for Array2<Complex<$t>> {
+        impl<S1, S2> ArgminDot<ArrayBase<S2, Ix1>, Array1<Complex<$t>>> for ArrayBase<S1, Ix2>
+        where
+            S1: Data<Elem = Complex<$t>>,
+            S2: Data<Elem = Complex<$t>>,
+        {
             #[inline]
-            fn dot(&self, other: &Array1<Complex<$t>>) -> Array1<Complex<$t>> {
-                ndarray::Array2::dot(self, other)
+            fn dot(&self, other: &ArrayBase<S2, Ix1>) -> Array1<Complex<$t>> {
+                ndarray::linalg::Dot::dot(self, other)
             }
         }
 
-        impl ArgminDot<Array2<Complex<$t>>, Array2<Complex<$t>>> for Array2<Complex<$t>> {
+        impl<S1, S2> ArgminDot<ArrayBase<S2, Ix2>, Array2<Complex<$t>>> for ArrayBase<S1, Ix2>
+        where
+            S1: Data<Elem = Complex<$t>>,
+            S2: Data<Elem = Complex<$t>>,
+        {
             #[inline]
-            fn dot(&self, other: &Array2<Complex<$t>>) -> Array2<Complex<$t>> {
-                ndarray::Array2::dot(self, other)
+            fn dot(&self, other: &ArrayBase<S2, Ix2>) -> Array2<Complex<$t>> {
+                ndarray::linalg::Dot::dot(self, other)
             }
         }
 
-        impl ArgminDot<Complex<$t>, Array2<Complex<$t>>> for Array2<Complex<$t>> {
+        impl<S> ArgminDot<Complex<$t>, Array2<Complex<$t>>> for ArrayBase<S, Ix2>
+        where
+            S: Data<Elem = Complex<$t>>,
+        {
             #[inline]
             fn dot(&self, other: &Complex<$t>) -> Array2<Complex<$t>> {
                 Array2::from_shape_fn((self.nrows(), self.ncols()), |(i, j)| *other * self[(i, j)])
             }
         }
 
-        impl ArgminDot<Array2<Complex<$t>>, Array2<Complex<$t>>> for Complex<$t> {
+        impl<S> ArgminDot<ArrayBase<S, Ix2>, Array2<Complex<$t>>> for Complex<$t>
+        where
+            S: Data<Elem = Complex<$t>>,
+        {
             #[inline]
-            fn dot(&self, other: &Array2<Complex<$t>>) -> Array2<Complex<$t>> {
+            fn dot(&self, other: &ArrayBase<S, Ix2>) -> Array2<Complex<$t>> {
                 Array2::from_shape_fn((other.nrows(), other.ncols()), |(i, j)| {
                     *self * other[(i, j)]
                 })
@@ -8,6 +8,9 @@
 #![allow(unused_imports)]
 
 mod add;
+mod allfinite;
+mod axpy;
+mod bound;
 mod conj;
 mod div;
 mod dot;
@@ -19,6 +22,7 @@ mod l2norm;
 mod minmax;
 mod mul;
 mod random;
+mod scal;
 mod scaledadd;
 mod scaledsub;
 mod signum;
@@ -27,6 +31,9 @@ mod transpose;
 mod zero;
 
 pub use add::*;
+pub use allfinite::*;
+pub use axpy::*;
+pub use bound::*;
 pub use conj::*;
 pub use div::*;
 pub use dot::*;
@@ -37,6 +44,7 @@ pub use l1norm::*;
 pub use l2norm::*;
 pub use minmax::*;
 pub use mul::*;
+pub use scal::*;
 pub use scaledadd::*;
 pub use scaledsub::*;
 pub use signum::*;
@@ -6,12 +6,15 @@
 // copied, modified, or distributed except according to those terms.
 
 use crate::ArgminMul;
-use ndarray::{Array1, Array2};
+use ndarray::{Array1, Array2, ArrayBase, Data, Ix1, Ix2};
 use num_complex::Complex;
 
 macro_rules! make_mul {
     ($t:ty) => {
-        impl ArgminMul<$t, Array1<$t>> for Array1<$t> {
+        impl<S> ArgminMul<$t, Array1<$t>> for ArrayBase<S, Ix1>
+        where
+            S: Data<Elem = $t>,
+        {
             #[inline]
             fn mul(&self, other: &$t) -> Array1<$t> {
                 cfg_if::cfg_if! {
@@ -26,9 +29,12 @@ macro_rules! make_mul {
             }
         }
 
-        impl ArgminMul<Array1<$t>, Array1<$t>> for $t {
+        impl<S> ArgminMul<ArrayBase<S, Ix1>, Array1<$t>> for $t
+        where
+            S: Data<Elem = $t>,
+        {
             #[inline]
-            fn mul(&self, other: &Array1<$t>) -> Array1<$t> {
+            fn mul(&self, other: &ArrayBase<S, Ix1>) -> Array1<$t> {
                 cfg_if::cfg_if! {
                     if #[cfg(feature = "ndarray_0_14")] {
                         other.iter().map(|o| o * *self).collect()
@@ -41,9 +47,13 @@ macro_rules! make_mul {
             }
         }
 
-        impl ArgminMul<Array1<$t>, Array1<$t>> for Array1<$t> {
+        impl<S1, S2> ArgminMul<ArrayBase<S2, Ix1>, Array1<$t>> for ArrayBase<S1, Ix1>
+        where
+            S1: Data<Elem = $t>,
+            S2: Data<Elem = $t>,
+        {
             #[inline]
-            fn mul(&self, other: &Array1<$t>) -> Array1<$t> {
+            fn mul(&self, other: &ArrayBase<S2, Ix1>) -> Array1<$t> {
                 cfg_if::cfg_if! {
                     if #[cfg(feature = "ndarray_0_14")] {
                         // Need to assert that the shapes are the same here because the iterators
@@ -62,23 +72,33 @@ macro_rules! make_mul {
             }
         }
 
-        impl ArgminMul<Array2<$t>, Array2<$t>> for Array2<$t> {
+        impl<S1, S2> ArgminMul<ArrayBase<S2, Ix2>, Array2<$t>> for ArrayBase<S1, Ix2>
+        where
+            S1: Data<Elem = $t>,
+            S2: Data<Elem = $t>,
+        {
             #[inline]
-            fn mul(&self, other: &Array2<$t>) -> Array2<$t> {
+            fn mul(&self, other: &ArrayBase<S2, Ix2>) -> Array2<$t> {
                 self * other
             }
         }
 
-        impl ArgminMul<$t, Array2<$t>> for Array2<$t> {
+        impl<S> ArgminMul<$t, Array2<$t>> for ArrayBase<S, Ix2>
+        where
+            S: Data<Elem = $t>,
+        {
             #[inline]
             fn mul(&self, other: &$t) -> Array2<$t> {
                 self * *other
             }
         }
 
-        impl ArgminMul<Array2<$t>, Array2<$t>> for $t {
+        impl<S> ArgminMul<ArrayBase<S, Ix2>, Array2<$t>> for $t
+        where
+            S: Data<Elem = $t>,
+        {
             #[inline]
-            fn mul(&self, other: &Array2<$t>) -> Array2<$t> {
+            fn mul(&self, other: &ArrayBase<S, Ix2>) -> Array2<$t> {
                 other * *self
             }
         }
@@ -87,72 +107,104 @@ macro_rules! make_mul {
 
 macro_rules! make_complex_mul {
     ($t:ty) => {
-        impl ArgminMul<Complex<$t>, Array1<Complex<$t>>> for Array1<Complex<$t>> {
+        impl<S> ArgminMul<Complex<$t>, Array1<Complex<$t>>> for ArrayBase<S, Ix1>
+        where
+            S: Data<Elem = Complex<$t>>,
+        {
             #[inline]
             fn mul(&self, other: &Complex<$t>) -> Array1<Complex<$t>> {
                 self * *other
             }
         }
 
-        impl ArgminMul<$t, Array1<Complex<$t>>> for Array1<Complex<$t>> {
+        impl<S> ArgminMul<$t, Array1<Complex<$t>>> for ArrayBase<S, Ix1>
+        where
+            S: Data<Elem = Complex<$t>>,
+        {
             #[inline]
             fn mul(&self, other: &$t) -> Array1<Complex<$t>> {
                 self * *other
             }
         }
 
-        impl ArgminMul<Array1<Complex<$t>>, Array1<Complex<$t>>> for Complex<$t> {
+        impl<S> ArgminMul<ArrayBase<S, Ix1>, Array1<Complex<$t>>> for Complex<$t>
+        where
+            S: Data<Elem = Complex<$t>>,
+        {
             #[inline]
-            fn mul(&self, other: &Array1<Complex<$t>>) -> Array1<Complex<$t>> {
+            fn mul(&self, other: &ArrayBase<S, Ix1>) -> Array1<Complex<$t>> {
                 *self * other
             }
         }
 
-        impl ArgminMul<Array1<Complex<$t>>, Array1<Complex<$t>>> for $t {
+        impl<S> ArgminMul<ArrayBase<S, Ix1>, Array1<Complex<$t>>> for $t
+        where
+            S: Data<Elem = Complex<$t>>,
+        {
             #[inline]
-            fn mul(&self, other: &Array1<Complex<$t>>) -> Array1<Complex<$t>> {
+            fn mul(&self, other: &ArrayBase<S, Ix1>) -> Array1<Complex<$t>> {
                 Complex::new(*self, 0 as $t) * other
             }
         }
 
-        impl ArgminMul<Array1<Complex<$t>>, Array1<Complex<$t>>> for Array1<Complex<$t>> {
+        impl<S1, S2> ArgminMul<ArrayBase<S2, Ix1>, Array1<Complex<$t>>> for ArrayBase<S1, Ix1>
+        where
+            S1: Data<Elem = Complex<$t>>,
+            S2: Data<Elem = Complex<$t>>,
+        {
             #[inline]
-            fn mul(&self, other: &Array1<Complex<$t>>) -> Array1<Complex<$t>> {
+            fn mul(&self, other: &ArrayBase<S2, Ix1>) -> Array1<Complex<$t>> {
                 self * other
             }
         }
 
-        impl ArgminMul<Array2<Complex<$t>>, Array2<Complex<$t>>> for Array2<Complex<$t>> {
+        impl<S1, S2> ArgminMul<ArrayBase<S2, Ix2>, Array2<Complex<$t>>> for ArrayBase<S1, Ix2>
+        where
+            S1: Data<Elem = Complex<$t>>,
+            S2: Data<Elem = Complex<$t>>,
+        {
             #[inline]
-            fn mul(&self, other: &Array2<Complex<$t>>) -> Array2<Complex<$t>> {
+            fn mul(&self, other: &ArrayBase<S2, Ix2>) -> Array2<Complex<$t>> {
                 self * other
             }
         }
 
-        impl ArgminMul<Complex<$t>, Array2<Complex<$t>>> for Array2<Complex<$t>> {
+        impl<S> ArgminMul<Complex<$t>, Array2<Complex<$t>>> for ArrayBase<S, Ix2>
+        where
+            S: Data<Elem = Complex<$t>>,
+        {
             #[inline]
             fn mul(&self, other: &Complex<$t>) -> Array2<Complex<$t>> {
                 self * *other
             }
         }
 
-        impl ArgminMul<$t, Array2<Complex<$t>>> for Array2<Complex<$t>> {
+        impl<S> ArgminMul<$t, Array2<Complex<$t>>> for ArrayBase<S, Ix2>
+        where
+            S: Data<Elem = Complex<$t>>,
+        {
             #[inline]
             fn mul(&self, other: &$t) -> Array2<Complex<$t>> {
                 self * *other
             }
         }
 
-        impl ArgminMul<Array2<Complex<$t>>, Array2<Complex<$t>>> for Complex<$t> {
+        impl<S> ArgminMul<ArrayBase<S, Ix2>, Array2<Complex<$t>>> for Complex<$t>
+        where
+            S: Data<Elem = Complex<$t>>,
+        {
             #[inline]
-            fn mul(&self, other: &Array2<Complex<$t>>) -> Array2<Complex<$t>> {
+            fn mul(&self, other: &ArrayBase<S, Ix2>) -> Array2<Complex<$t>> {
                 *self * other
             }
         }
 
-        impl ArgminMul<Array2<Complex<$t>>, Array2<Complex<$t>>> for $t {
+        impl<S> ArgminMul<ArrayBase<S, Ix2>, Array2<Complex<$t>>> for $t
+        where
+            S: Data<Elem = Complex<$t>>,
+        {
             #[inline]
-            fn mul(&self, other: &Array2<Complex<$t>>) -> Array2<Complex<$t>> {
+            fn mul(&self, other: &ArrayBase<S, Ix2>) -> Array2<Complex<$t>> {
                 Complex::new(*self, 0 as $t) * other
             }
         }
@@ -161,75 +213,107 @@ macro_rules! make_complex_mul {
 
 macro_rules! make_complex_integer_mul {
     ($t:ty) => {
-        impl ArgminMul<Complex<$t>, Array1<Complex<$t>>> for Array1<Complex<$t>> {
+        impl<S> ArgminMul<Complex<$t>, Array1<Complex<$t>>> for ArrayBase<S, Ix1>
+        where
+            S: Data<Elem = Complex<$t>>,
+        {
             #[inline]
             fn mul(&self, other: &Complex<$t>) -> Array1<Complex<$t>> {
                 self.iter().map(|s| s * *other).collect()
             }
         }
 
-        impl ArgminMul<$t, Array1<Complex<$t>>> for Array1<Complex<$t>> {
+        impl<S> ArgminMul<$t, Array1<Complex<$t>>> for ArrayBase<S, Ix1>
+        where
+            S: Data<Elem = Complex<$t>>,
+        {
             #[inline]
             fn mul(&self, other: &$t) -> Array1<Complex<$t>> {
                 self.iter().map(|s| s * *other).collect()
             }
         }
 
-        impl ArgminMul<Array1<Complex<$t>>, Array1<Complex<$t>>> for Complex<$t> {
+        impl<S> ArgminMul<ArrayBase<S, Ix1>, Array1<Complex<$t>>> for Complex<$t>
+        where
+            S: Data<Elem = Complex<$t>>,
+        {
             #[inline]
-            fn mul(&self, other: &Array1<Complex<$t>>) -> Array1<Complex<$t>> {
+            fn mul(&self, other: &ArrayBase<S, Ix1>) -> Array1<Complex<$t>> {
                 other.iter().map(|o| o * *self).collect()
             }
         }
 
-        impl ArgminMul<Array1<Complex<$t>>, Array1<Complex<$t>>> for $t {
+        impl<S> ArgminMul<ArrayBase<S, Ix1>, Array1<Complex<$t>>> for $t
+        where
+            S: Data<Elem = Complex<$t>>,
+        {
             #[inline]
-            fn mul(&self, other: &Array1<Complex<$t>>) -> Array1<Complex<$t>> {
+            fn mul(&self, other: &ArrayBase<S, Ix1>) -> Array1<Complex<$t>> {
                 let s = Complex::new(*self, 0 as $t);
                 other.iter().map(|o| o * s).collect()
             }
         }
 
-        impl ArgminMul<Array1<Complex<$t>>, Array1<Complex<$t>>> for Array1<Complex<$t>> {
+        impl<S1, S2> ArgminMul<ArrayBase<S2, Ix1>, Array1<Complex<$t>>> for ArrayBase<S1, Ix1>
+        where
+            S1: Data<Elem = Complex<$t>>,
+            S2: Data<Elem = Complex<$t>>,
+        {
             #[inline]
-            fn mul(&self, other: &Array1<Complex<$t>>) -> Array1<Complex<$t>> {
+            fn mul(&self, other: &ArrayBase<S2, Ix1>) -> Array1<Complex<$t>> {
                 self * other
             }
         }
 
-        impl ArgminMul<Array2<Complex<$t>>, Array2<Complex<$t>>> for Array2<Complex<$t>> {
+        impl<S1, S2> ArgminMul<ArrayBase<S2, Ix2>, Array2<Complex<$t>>> for ArrayBase<S1, Ix2>
+        where
+            S1: Data<Elem = Complex<$t>>,
+            S2: Data<Elem = Complex<$t>>,
+        {
             #[inline]
-            fn mul(&self, other: &Array2<Complex<$t>>) -> Array2<Complex<$t>> {
+            fn mul(&self, other: &ArrayBase<S2, Ix2>) -> Array2<Complex<$t>> {
                 self * other
             }
         }
 
-        impl ArgminMul<Complex<$t>, Array2<Complex<$t>>> for Array2<Complex<$t>> {
+        impl<S> ArgminMul<Complex<$t>, Array2<Complex<$t>>> for ArrayBase<S, Ix2>
+        where
+            S: Data<Elem = Complex<$t>>,
+        {
             #[inline]
             fn mul(&self, other: &Complex<$t>) -> Array2<Complex<$t>> {
                 Array2::from_shape_fn((self.nrows(), self.ncols()), |(i, j)| self[(i, j)] * *other)
             }
         }
 
-        impl ArgminMul<$t, Array2<Complex<$t>>> for Array2<Complex<$t>> {
+        impl<S> ArgminMul<$t, Array2<Complex<$t>>> for ArrayBase<S, Ix2>
+        where
+            S: Data<Elem = Complex<$t>>,
+        {
             #[inline]
             fn mul(&self, other: &$t) -> Array2<Complex<$t>> {
                 self * *other
             }
         }
 
-        impl ArgminMul<Array2<Complex<$t>>, Array2<Complex<$t>>> for Complex<$t> {
+        impl<S> ArgminMul<ArrayBase<S, Ix2>, Array2<Complex<$t>>> for Complex<$t>
+        where
+            S: Data<Elem = Complex<$t>>,
+        {
             #[inline]
-            fn mul(&self, other: &Array2<Complex<$t>>) -> Array2<Complex<$t>> {
+            fn mul(&self, other: &ArrayBase<S, Ix2>) -> Array2<Complex<$t>> {
                 Array2::from_shape_fn((other.nrows(), other.ncols()), |(i, j)| {
                     other[(i, j)] * *self
                 })
             }
         }
 
-        impl ArgminMul<Array2<Complex<$t>>, Array2<Complex<$t>>> for $t {
+        impl<S> ArgminMul<ArrayBase<S, Ix2>, Array2<Complex<$t>>> for $t
+        where
+            S: Data<Elem = Complex<$t>>,
+        {
             #[inline]
-            fn mul(&self, other: &Array2<Complex<$t>>) -> Array2<Complex<$t>> {
+            fn mul(&self, other: &ArrayBase<S, Ix2>) -> Array2<Complex<$t>> {
                 Array2::from_shape_fn((other.nrows(), other.ncols()), |(i, j)| {
                     other[(i, j)] * *self
                 })
@@ -0,0 +1,123 @@
+// Copyright 2018-2024 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use crate::ArgminAllFinite;
+use num_complex::Complex;
+
+macro_rules! make_allfinite {
+    ($t:ty) => {
+        impl ArgminAllFinite for $t {
+            #[inline]
+            fn all_finite(&self) -> bool {
+                self.is_finite()
+            }
+        }
+
+        impl ArgminAllFinite for Complex<$t> {
+            #[inline]
+            fn all_finite(&self) -> bool {
+                self.is_finite()
+            }
+        }
+    };
+}
+
+macro_rules! make_allfinite_integer {
+    ($t:ty) => {
+        impl ArgminAllFinite for $t {
+            #[inline]
+            fn all_finite(&self) -> bool {
+                true
+            }
+        }
+
+        impl ArgminAllFinite for Complex<$t> {
+            #[inline]
+            fn all_finite(&self) -> bool {
+                true
+            }
+        }
+    };
+}
+
+/// The unit type has no elements and is therefore always finite. This allows solvers which do
+/// not use certain quantities (such as gradients) to still be checked for non-finite values.
+impl ArgminAllFinite for () {
+    #[inline]
+    fn all_finite(&self) -> bool {
+        true
+    }
+}
+
+make_allfinite_integer!(i8);
+make_allfinite_integer!(i16);
+make_allfinite_integer!(i32);
+make_allfinite_integer!(i64);
+make_allfinite_integer!(u8);
+make_allfinite_integer!(u16);
+make_allfinite_integer!(u32);
+make_allfinite_integer!(u64);
+make_allfinite!(f32);
+make_allfinite!(f64);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use paste::item;
+
+    macro_rules! make_test {
+        ($t:ty) => {
+            item! {
+                #[test]
+                fn [<test_allfinite_ $t>]() {
+                    assert!(<$t as ArgminAllFinite>::all_finite(&(8 as $t)));
+                    assert!(<Complex<$t> as ArgminAllFinite>::all_finite(
+                        &Complex::new(8 as $t, 4 as $t)
+                    ));
+                }
+            }
+        };
+    }
+
+    macro_rules! make_test_float {
+        ($t:ident) => {
+            item! {
+                #[test]
+                fn [<test_allfinite_nonfinite_ $t>]() {
+                    assert!(!<$t as ArgminAllFinite>::all_finite(&$t::NAN));
+                    assert!(!<$t as ArgminAllFinite>::all_finite(&$t::INFINITY));
+                    assert!(!<$t as ArgminAllFinite>::all_finite(&$t::NEG_INFINITY));
+                    assert!(!<Complex<$t> as ArgminAllFinite>::all_finite(
+                        &Complex::new(1.0, $t::NAN)
+                    ));
+                    assert!(!<Complex<$t> as ArgminAllFinite>::all_finite(
+                        &Complex::new($t::INFINITY, 1.0)
+                    ));
+                }
+            }
+        };
+    }
+
+    #[test]
+    fn test_allfinite_unit() {
+        assert!(<() as ArgminAllFinite>::all_finite(&()));
+    }
+
+    make_test!(i8);
+    make_test!(u8);
+    make_test!(i16);
+    make_test!(u16);
+    make_test!(i32);
+    make_test!(u32);
+    make_test!(i64);
+    make_test!(u64);
+    make_test!(f32);
+    make_test!(f64);
+
+    make_test_float!(f32);
+    make_test_float!(f64);
+}
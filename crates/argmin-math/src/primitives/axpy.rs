@@ -0,0 +1,83 @@
+// Copyright 2018-2024 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use crate::ArgminAxpy;
+use num_complex::Complex;
+
+macro_rules! make_axpy {
+    ($t:ty) => {
+        impl ArgminAxpy<$t, $t> for $t {
+            #[inline]
+            fn axpy(&mut self, factor: &$t, vec: &$t) {
+                *self += factor * vec;
+            }
+        }
+
+        impl ArgminAxpy<Complex<$t>, Complex<$t>> for Complex<$t> {
+            #[inline]
+            fn axpy(&mut self, factor: &Complex<$t>, vec: &Complex<$t>) {
+                *self += factor * vec;
+            }
+        }
+    };
+}
+
+make_axpy!(i8);
+make_axpy!(i16);
+make_axpy!(i32);
+make_axpy!(i64);
+make_axpy!(u8);
+make_axpy!(u16);
+make_axpy!(u32);
+make_axpy!(u64);
+make_axpy!(f32);
+make_axpy!(f64);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_relative_eq;
+    use paste::item;
+
+    macro_rules! make_test {
+        ($t:ty) => {
+            item! {
+                #[test]
+                fn [<test_axpy_ $t>]() {
+                    let mut a = 2 as $t;
+                    let b = 4 as $t;
+                    let c = 10 as $t;
+                    <$t as ArgminAxpy<$t, $t>>::axpy(&mut a, &b, &c);
+                    assert_relative_eq!(42 as f64, a as f64, epsilon = f64::EPSILON);
+                }
+            }
+
+            item! {
+                #[test]
+                fn [<test_axpy_complex_ $t>]() {
+                    let mut a = Complex::new(2 as $t, 1 as $t);
+                    let b = Complex::new(2 as $t, 3 as $t);
+                    let c = Complex::new(3 as $t, 1 as $t);
+                    let target = a + b * c;
+                    <Complex<$t> as ArgminAxpy<Complex<$t>, Complex<$t>>>::axpy(&mut a, &b, &c);
+                    assert_eq!(a, target);
+                }
+            }
+        };
+    }
+
+    make_test!(i8);
+    make_test!(u8);
+    make_test!(i16);
+    make_test!(u16);
+    make_test!(i32);
+    make_test!(u32);
+    make_test!(i64);
+    make_test!(u64);
+    make_test!(f32);
+    make_test!(f64);
+}
@@ -0,0 +1,157 @@
+// Copyright 2018-2024 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use crate::ArgminBound;
+use num_complex::Complex;
+
+macro_rules! make_bound {
+    ($t:ident) => {
+        impl ArgminBound<$t, $t> for $t {
+            #[inline]
+            fn max_step_in_bounds(&self, direction: &$t, lower: &$t, upper: &$t) -> $t {
+                let bound = if *direction > 0.0 {
+                    *upper
+                } else if *direction < 0.0 {
+                    *lower
+                } else {
+                    return $t::INFINITY;
+                };
+                let mut alpha = ((bound - self) / direction).max(0.0);
+                // Due to rounding, `self + alpha * direction` may end up just outside of the bound
+                while alpha > 0.0 && (self + alpha * direction - bound) * direction > 0.0 {
+                    alpha *= 1.0 - $t::EPSILON;
+                }
+                alpha
+            }
+
+            #[inline]
+            fn project_direction(&self, direction: &$t, lower: &$t, upper: &$t) -> $t {
+                if (*direction < 0.0 && self <= lower) || (*direction > 0.0 && self >= upper) {
+                    0.0
+                } else {
+                    *direction
+                }
+            }
+        }
+
+        impl ArgminBound<Complex<$t>, $t> for Complex<$t> {
+            #[inline]
+            fn max_step_in_bounds(
+                &self,
+                direction: &Complex<$t>,
+                lower: &Complex<$t>,
+                upper: &Complex<$t>,
+            ) -> $t {
+                let re = self
+                    .re
+                    .max_step_in_bounds(&direction.re, &lower.re, &upper.re);
+                let im = self
+                    .im
+                    .max_step_in_bounds(&direction.im, &lower.im, &upper.im);
+                re.min(im)
+            }
+
+            #[inline]
+            fn project_direction(
+                &self,
+                direction: &Complex<$t>,
+                lower: &Complex<$t>,
+                upper: &Complex<$t>,
+            ) -> Complex<$t> {
+                Complex::new(
+                    self.re
+                        .project_direction(&direction.re, &lower.re, &upper.re),
+                    self.im
+                        .project_direction(&direction.im, &lower.im, &upper.im),
+                )
+            }
+        }
+    };
+}
+
+make_bound!(f32);
+make_bound!(f64);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_relative_eq;
+    use paste::item;
+
+    macro_rules! make_test {
+        ($t:ident) => {
+            item! {
+                #[test]
+                fn [<test_max_step_in_bounds_ $t>]() {
+                    let x = 0.5 as $t;
+                    let (l, u) = (-1 as $t, 1 as $t);
+                    let alpha = <$t as ArgminBound<$t, $t>>::max_step_in_bounds(&x, &2.0, &l, &u);
+                    assert_relative_eq!(alpha as f64, 0.25, epsilon = $t::EPSILON as f64);
+                    let alpha = <$t as ArgminBound<$t, $t>>::max_step_in_bounds(&x, &-3.0, &l, &u);
+                    assert_relative_eq!(alpha as f64, 0.5, epsilon = $t::EPSILON as f64);
+                    let alpha = <$t as ArgminBound<$t, $t>>::max_step_in_bounds(&x, &0.0, &l, &u);
+                    assert!(alpha.is_infinite());
+                    let alpha = <$t as ArgminBound<$t, $t>>::max_step_in_bounds(&u, &1.0, &l, &u);
+                    assert_eq!(alpha.to_ne_bytes(), (0 as $t).to_ne_bytes());
+                }
+            }
+
+            item! {
+                #[test]
+                fn [<test_max_step_in_bounds_feasible_ $t>]() {
+                    let (l, u) = (-1 as $t, 1 as $t);
+                    for (x, d) in [(0.1 as $t, 0.3 as $t), (-0.7, 0.9), (0.3, -0.7), (0.2, -1e-3)] {
+                        let alpha = <$t as ArgminBound<$t, $t>>::max_step_in_bounds(&x, &d, &l, &u);
+                        assert!(alpha > 0.0);
+                        assert!((l..=u).contains(&(x + alpha * d)));
+                    }
+                }
+            }
+
+            item! {
+                #[test]
+                fn [<test_max_step_in_bounds_complex_ $t>]() {
+                    let x = Complex::new(0.5 as $t, 0.0 as $t);
+                    let d = Complex::new(1.0 as $t, -4.0 as $t);
+                    let l = Complex::new(-1.0 as $t, -1.0 as $t);
+                    let u = Complex::new(1.0 as $t, 1.0 as $t);
+                    let alpha = <Complex<$t> as ArgminBound<Complex<$t>, $t>>::max_step_in_bounds(
+                        &x, &d, &l, &u
+                    );
+                    assert_relative_eq!(alpha as f64, 0.25, epsilon = $t::EPSILON as f64);
+                }
+            }
+
+            item! {
+                #[test]
+                fn [<test_project_direction_ $t>]() {
+                    let (l, u) = (-1 as $t, 1 as $t);
+                    let project = |x: $t, d: $t| {
+                        <$t as ArgminBound<$t, $t>>::project_direction(&x, &d, &l, &u)
+                    };
+                    assert_eq!(project(u, 2.0).to_ne_bytes(), (0 as $t).to_ne_bytes());
+                    assert_eq!(project(u, -2.0).to_ne_bytes(), (-2 as $t).to_ne_bytes());
+                    assert_eq!(project(l, -2.0).to_ne_bytes(), (0 as $t).to_ne_bytes());
+                    assert_eq!(project(l, 2.0).to_ne_bytes(), (2 as $t).to_ne_bytes());
+                    assert_eq!(project(0.0, 2.0).to_ne_bytes(), (2 as $t).to_ne_bytes());
+                    let x = Complex::new(1.0 as $t, 0.0 as $t);
+                    let d = Complex::new(1.0 as $t, 1.0 as $t);
+                    let p = <Complex<$t> as ArgminBound<Complex<$t>, $t>>::project_direction(
+                        &x,
+                        &d,
+                        &Complex::new(l, l),
+                        &Complex::new(u, u),
+                    );
+                    assert_eq!(p, Complex::new(0 as $t, 1 as $t));
+                }
+            }
+        };
+    }
+
+    make_test!(f32);
+    make_test!(f64);
+}
@@ -8,6 +8,9 @@
 #![allow(unused_imports)]
 
 mod add;
+mod allfinite;
+mod axpy;
+mod bound;
 mod conj;
 mod div;
 mod dot;
@@ -16,6 +19,7 @@ mod l2norm;
 mod minmax;
 mod mul;
 mod random;
+mod scal;
 mod scaledadd;
 mod scaledsub;
 mod sub;
@@ -24,6 +28,9 @@ mod weighteddot;
 mod zero;
 
 pub use add::*;
+pub use allfinite::*;
+pub use axpy::*;
+pub use bound::*;
 pub use conj::*;
 pub use div::*;
 pub use dot::*;
@@ -32,6 +39,7 @@ pub use l2norm::*;
 pub use minmax::*;
 pub use mul::*;
 pub use random::*;
+pub use scal::*;
 pub use scaledadd::*;
 pub use scaledsub::*;
 pub use sub::*;
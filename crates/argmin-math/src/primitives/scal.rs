@@ -0,0 +1,80 @@
+// Copyright 2018-2024 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use crate::ArgminScal;
+use num_complex::Complex;
+
+macro_rules! make_scal {
+    ($t:ty) => {
+        impl ArgminScal<$t> for $t {
+            #[inline]
+            fn scal(&mut self, factor: &$t) {
+                *self *= *factor;
+            }
+        }
+
+        impl ArgminScal<Complex<$t>> for Complex<$t> {
+            #[inline]
+            fn scal(&mut self, factor: &Complex<$t>) {
+                *self *= *factor;
+            }
+        }
+    };
+}
+
+make_scal!(i8);
+make_scal!(i16);
+make_scal!(i32);
+make_scal!(i64);
+make_scal!(u8);
+make_scal!(u16);
+make_scal!(u32);
+make_scal!(u64);
+make_scal!(f32);
+make_scal!(f64);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_relative_eq;
+    use paste::item;
+
+    macro_rules! make_test {
+        ($t:ty) => {
+            item! {
+                #[test]
+                fn [<test_scal_ $t>]() {
+                    let mut a = 2 as $t;
+                    <$t as ArgminScal<$t>>::scal(&mut a, &(4 as $t));
+                    assert_relative_eq!(8 as f64, a as f64, epsilon = f64::EPSILON);
+                }
+            }
+
+            item! {
+                #[test]
+                fn [<test_scal_complex_ $t>]() {
+                    let mut a = Complex::new(2 as $t, 1 as $t);
+                    let b = Complex::new(2 as $t, 3 as $t);
+                    let target = a * b;
+                    <Complex<$t> as ArgminScal<Complex<$t>>>::scal(&mut a, &b);
+                    assert_eq!(a, target);
+                }
+            }
+        };
+    }
+
+    make_test!(i8);
+    make_test!(u8);
+    make_test!(i16);
+    make_test!(u16);
+    make_test!(i32);
+    make_test!(u32);
+    make_test!(i64);
+    make_test!(u64);
+    make_test!(f32);
+    make_test!(f64);
+}
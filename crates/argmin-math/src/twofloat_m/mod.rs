@@ -0,0 +1,627 @@
+// Copyright 2018-2024 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+//! Implementations for high precision floating point types which are not primitives.
+//!
+//! Unlike the primitive floats, these types usually neither implement arithmetic on references nor
+//! `Sum` or `SampleUniform`. The implementations below therefore only rely on the type being
+//! `Copy` and implementing `num_traits::Float` and `From<f64>`. They cover the type itself as well
+//! as `Vec`s and `Vec<Vec<_>>`s (if the `vec` feature is enabled) of it.
+
+#![allow(unused_imports)]
+
+use crate::{
+    ArgminAdd, ArgminAllFinite, ArgminAxpy, ArgminConj, ArgminDiv, ArgminDot, ArgminEye,
+    ArgminL1Norm, ArgminL2Norm, ArgminMinMax, ArgminMul, ArgminRandom, ArgminScal, ArgminSignum,
+    ArgminSub, ArgminTranspose, ArgminZero, ArgminZeroLike,
+};
+use num_traits::{Float, One, Zero};
+use rand::Rng;
+
+/// Samples uniformly from `[min, max)` by scaling a uniformly distributed `f64`
+#[inline]
+fn sample<T: Float + From<f64>, R: Rng>(min: T, max: T, rng: &mut R) -> T {
+    min + (max - min) * <T as From<f64>>::from(rng.gen::<f64>())
+}
+
+macro_rules! make_scalar {
+    ($t:ty) => {
+        impl ArgminAdd<$t, $t> for $t {
+            #[inline]
+            fn add(&self, other: &$t) -> $t {
+                *self + *other
+            }
+        }
+
+        impl ArgminSub<$t, $t> for $t {
+            #[inline]
+            fn sub(&self, other: &$t) -> $t {
+                *self - *other
+            }
+        }
+
+        impl ArgminMul<$t, $t> for $t {
+            #[inline]
+            fn mul(&self, other: &$t) -> $t {
+                *self * *other
+            }
+        }
+
+        impl ArgminDiv<$t, $t> for $t {
+            #[inline]
+            fn div(&self, other: &$t) -> $t {
+                *self / *other
+            }
+        }
+
+        impl ArgminDot<$t, $t> for $t {
+            #[inline]
+            fn dot(&self, other: &$t) -> $t {
+                *self * *other
+            }
+        }
+
+        impl ArgminZero for $t {
+            #[inline]
+            fn zero() -> $t {
+                <$t as Zero>::zero()
+            }
+        }
+
+        impl ArgminZeroLike for $t {
+            #[inline]
+            fn zero_like(&self) -> $t {
+                <$t as Zero>::zero()
+            }
+        }
+
+        impl ArgminConj for $t {
+            #[inline]
+            fn conj(&self) -> $t {
+                *self
+            }
+        }
+
+        impl ArgminTranspose<$t> for $t {
+            #[inline]
+            fn t(self) -> $t {
+                self
+            }
+        }
+
+        impl ArgminL1Norm<$t> for $t {
+            #[inline]
+            fn l1_norm(&self) -> $t {
+                Float::abs(*self)
+            }
+        }
+
+        impl ArgminL2Norm<$t> for $t {
+            #[inline]
+            fn l2_norm(&self) -> $t {
+                Float::abs(*self)
+            }
+        }
+
+        impl ArgminMinMax for $t {
+            #[inline]
+            fn min(x: &Self, y: &Self) -> $t {
+                if x <= y {
+                    *x
+                } else {
+                    *y
+                }
+            }
+
+            #[inline]
+            fn max(x: &Self, y: &Self) -> $t {
+                if x >= y {
+                    *x
+                } else {
+                    *y
+                }
+            }
+        }
+
+        impl ArgminRandom for $t {
+            #[inline]
+            fn rand_from_range<R: Rng>(min: &Self, max: &Self, rng: &mut R) -> $t {
+                sample(*min, *max, rng)
+            }
+        }
+
+        impl ArgminAllFinite for $t {
+            #[inline]
+            fn all_finite(&self) -> bool {
+                Float::is_finite(*self)
+            }
+        }
+
+        impl ArgminAxpy<$t, $t> for $t {
+            #[inline]
+            fn axpy(&mut self, factor: &$t, vec: &$t) {
+                *self += *factor * *vec;
+            }
+        }
+
+        impl ArgminScal<$t> for $t {
+            #[inline]
+            fn scal(&mut self, factor: &$t) {
+                *self *= *factor;
+            }
+        }
+    };
+}
+
+#[cfg(feature = "vec")]
+macro_rules! make_vec {
+    ($t:ty) => {
+        impl ArgminAdd<$t, Vec<$t>> for Vec<$t> {
+            #[inline]
+            fn add(&self, other: &$t) -> Vec<$t> {
+                self.iter().map(|a| *a + *other).collect()
+            }
+        }
+
+        impl ArgminAdd<Vec<$t>, Vec<$t>> for $t {
+            #[inline]
+            fn add(&self, other: &Vec<$t>) -> Vec<$t> {
+                other.iter().map(|a| *self + *a).collect()
+            }
+        }
+
+        impl ArgminAdd<Vec<$t>, Vec<$t>> for Vec<$t> {
+            #[inline]
+            fn add(&self, other: &Vec<$t>) -> Vec<$t> {
+                assert!(!self.is_empty());
+                assert_eq!(self.len(), other.len());
+                self.iter()
+                    .zip(other.iter())
+                    .map(|(a, b)| *a + *b)
+                    .collect()
+            }
+        }
+
+        impl ArgminAdd<Vec<Vec<$t>>, Vec<Vec<$t>>> for Vec<Vec<$t>> {
+            #[inline]
+            fn add(&self, other: &Vec<Vec<$t>>) -> Vec<Vec<$t>> {
+                assert!(!self.is_empty());
+                assert_eq!(self.len(), other.len());
+                self.iter()
+                    .zip(other.iter())
+                    .map(|(a, b)| a.add(b))
+                    .collect()
+            }
+        }
+
+        impl ArgminAdd<$t, Vec<Vec<$t>>> for Vec<Vec<$t>> {
+            #[inline]
+            fn add(&self, other: &$t) -> Vec<Vec<$t>> {
+                self.iter().map(|a| a.add(other)).collect()
+            }
+        }
+
+        impl ArgminSub<$t, Vec<$t>> for Vec<$t> {
+            #[inline]
+            fn sub(&self, other: &$t) -> Vec<$t> {
+                self.iter().map(|a| *a - *other).collect()
+            }
+        }
+
+        impl ArgminSub<Vec<$t>, Vec<$t>> for $t {
+            #[inline]
+            fn sub(&self, other: &Vec<$t>) -> Vec<$t> {
+                other.iter().map(|a| *self - *a).collect()
+            }
+        }
+
+        impl ArgminSub<Vec<$t>, Vec<$t>> for Vec<$t> {
+            #[inline]
+            fn sub(&self, other: &Vec<$t>) -> Vec<$t> {
+                assert!(!self.is_empty());
+                assert_eq!(self.len(), other.len());
+                self.iter()
+                    .zip(other.iter())
+                    .map(|(a, b)| *a - *b)
+                    .collect()
+            }
+        }
+
+        impl ArgminSub<Vec<Vec<$t>>, Vec<Vec<$t>>> for Vec<Vec<$t>> {
+            #[inline]
+            fn sub(&self, other: &Vec<Vec<$t>>) -> Vec<Vec<$t>> {
+                assert!(!self.is_empty());
+                assert_eq!(self.len(), other.len());
+                self.iter()
+                    .zip(other.iter())
+                    .map(|(a, b)| a.sub(b))
+                    .collect()
+            }
+        }
+
+        impl ArgminSub<$t, Vec<Vec<$t>>> for Vec<Vec<$t>> {
+            #[inline]
+            fn sub(&self, other: &$t) -> Vec<Vec<$t>> {
+                self.iter().map(|a| a.sub(other)).collect()
+            }
+        }
+
+        impl ArgminMul<$t, Vec<$t>> for Vec<$t> {
+            #[inline]
+            fn mul(&self, other: &$t) -> Vec<$t> {
+                self.iter().map(|a| *a * *other).collect()
+            }
+        }
+
+        impl ArgminMul<Vec<$t>, Vec<$t>> for $t {
+            #[inline]
+            fn mul(&self, other: &Vec<$t>) -> Vec<$t> {
+                other.iter().map(|a| *self * *a).collect()
+            }
+        }
+
+        impl ArgminMul<Vec<$t>, Vec<$t>> for Vec<$t> {
+            #[inline]
+            fn mul(&self, other: &Vec<$t>) -> Vec<$t> {
+                assert!(!self.is_empty());
+                assert_eq!(self.len(), other.len());
+                self.iter()
+                    .zip(other.iter())
+                    .map(|(a, b)| *a * *b)
+                    .collect()
+            }
+        }
+
+        impl ArgminMul<Vec<Vec<$t>>, Vec<Vec<$t>>> for Vec<Vec<$t>> {
+            #[inline]
+            fn mul(&self, other: &Vec<Vec<$t>>) -> Vec<Vec<$t>> {
+                assert!(!self.is_empty());
+                assert_eq!(self.len(), other.len());
+                self.iter()
+                    .zip(other.iter())
+                    .map(|(a, b)| a.mul(b))
+                    .collect()
+            }
+        }
+
+        impl ArgminMul<$t, Vec<Vec<$t>>> for Vec<Vec<$t>> {
+            #[inline]
+            fn mul(&self, other: &$t) -> Vec<Vec<$t>> {
+                self.iter().map(|a| a.mul(other)).collect()
+            }
+        }
+
+        impl ArgminMul<Vec<Vec<$t>>, Vec<Vec<$t>>> for $t {
+            #[inline]
+            fn mul(&self, other: &Vec<Vec<$t>>) -> Vec<Vec<$t>> {
+                other.iter().map(|a| a.mul(self)).collect()
+            }
+        }
+
+        impl ArgminDiv<$t, Vec<$t>> for Vec<$t> {
+            #[inline]
+            fn div(&self, other: &$t) -> Vec<$t> {
+                self.iter().map(|a| *a / *other).collect()
+            }
+        }
+
+        impl ArgminDiv<Vec<$t>, Vec<$t>> for $t {
+            #[inline]
+            fn div(&self, other: &Vec<$t>) -> Vec<$t> {
+                other.iter().map(|a| *self / *a).collect()
+            }
+        }
+
+        impl ArgminDiv<Vec<$t>, Vec<$t>> for Vec<$t> {
+            #[inline]
+            fn div(&self, other: &Vec<$t>) -> Vec<$t> {
+                assert!(!self.is_empty());
+                assert_eq!(self.len(), other.len());
+                self.iter()
+                    .zip(other.iter())
+                    .map(|(a, b)| *a / *b)
+                    .collect()
+            }
+        }
+
+        impl ArgminDiv<Vec<Vec<$t>>, Vec<Vec<$t>>> for Vec<Vec<$t>> {
+            #[inline]
+            fn div(&self, other: &Vec<Vec<$t>>) -> Vec<Vec<$t>> {
+                assert!(!self.is_empty());
+                assert_eq!(self.len(), other.len());
+                self.iter()
+                    .zip(other.iter())
+                    .map(|(a, b)| a.div(b))
+                    .collect()
+            }
+        }
+
+        impl ArgminDot<Vec<$t>, $t> for Vec<$t> {
+            #[inline]
+            fn dot(&self, other: &Vec<$t>) -> $t {
+                self.iter()
+                    .zip(other.iter())
+                    .fold(<$t as Zero>::zero(), |acc, (a, b)| acc + *a * *b)
+            }
+        }
+
+        impl ArgminDot<$t, Vec<$t>> for Vec<$t> {
+            #[inline]
+            fn dot(&self, other: &$t) -> Vec<$t> {
+                self.mul(other)
+            }
+        }
+
+        impl ArgminDot<Vec<$t>, Vec<$t>> for $t {
+            #[inline]
+            fn dot(&self, other: &Vec<$t>) -> Vec<$t> {
+                self.mul(other)
+            }
+        }
+
+        impl ArgminDot<Vec<$t>, Vec<Vec<$t>>> for Vec<$t> {
+            #[inline]
+            fn dot(&self, other: &Vec<$t>) -> Vec<Vec<$t>> {
+                self.iter()
+                    .map(|b| other.iter().map(|a| *a * *b).collect())
+                    .collect()
+            }
+        }
+
+        impl ArgminDot<Vec<$t>, Vec<$t>> for Vec<Vec<$t>> {
+            #[inline]
+            fn dot(&self, other: &Vec<$t>) -> Vec<$t> {
+                self.iter()
+                    .map(|row| <Vec<$t> as ArgminDot<Vec<$t>, $t>>::dot(row, other))
+                    .collect()
+            }
+        }
+
+        impl ArgminDot<Vec<Vec<$t>>, Vec<Vec<$t>>> for Vec<Vec<$t>> {
+            #[inline]
+            fn dot(&self, other: &Vec<Vec<$t>>) -> Vec<Vec<$t>> {
+                let other = other.clone().t();
+                self.iter()
+                    .map(|row| {
+                        other
+                            .iter()
+                            .map(|col| <Vec<$t> as ArgminDot<Vec<$t>, $t>>::dot(row, col))
+                            .collect()
+                    })
+                    .collect()
+            }
+        }
+
+        impl ArgminDot<$t, Vec<Vec<$t>>> for Vec<Vec<$t>> {
+            #[inline]
+            fn dot(&self, other: &$t) -> Vec<Vec<$t>> {
+                self.mul(other)
+            }
+        }
+
+        impl ArgminDot<Vec<Vec<$t>>, Vec<Vec<$t>>> for $t {
+            #[inline]
+            fn dot(&self, other: &Vec<Vec<$t>>) -> Vec<Vec<$t>> {
+                self.mul(other)
+            }
+        }
+
+        impl ArgminConj for Vec<$t> {
+            #[inline]
+            fn conj(&self) -> Vec<$t> {
+                self.clone()
+            }
+        }
+
+        impl ArgminConj for Vec<Vec<$t>> {
+            #[inline]
+            fn conj(&self) -> Vec<Vec<$t>> {
+                self.clone()
+            }
+        }
+
+        impl ArgminTranspose<Vec<Vec<$t>>> for Vec<Vec<$t>> {
+            fn t(self) -> Self {
+                let n1 = self.len();
+                let n2 = self[0].len();
+                let mut out = vec![vec![<$t as Zero>::zero(); n1]; n2];
+                for i in 0..n1 {
+                    for j in 0..n2 {
+                        out[j][i] = self[i][j];
+                    }
+                }
+                out
+            }
+        }
+
+        impl ArgminEye for Vec<Vec<$t>> {
+            fn eye_like(&self) -> Vec<Vec<$t>> {
+                assert!(!self.is_empty());
+                assert_eq!(self.len(), self[0].len());
+                <Self as ArgminEye>::eye(self.len())
+            }
+
+            fn eye(n: usize) -> Vec<Vec<$t>> {
+                let mut out = vec![vec![<$t as Zero>::zero(); n]; n];
+                for (i, row) in out.iter_mut().enumerate() {
+                    row[i] = <$t as One>::one();
+                }
+                out
+            }
+        }
+
+        impl ArgminL1Norm<$t> for Vec<$t> {
+            #[inline]
+            fn l1_norm(&self) -> $t {
+                self.iter()
+                    .fold(<$t as Zero>::zero(), |acc, a| acc + Float::abs(*a))
+            }
+        }
+
+        impl ArgminL2Norm<$t> for Vec<$t> {
+            #[inline]
+            fn l2_norm(&self) -> $t {
+                Float::sqrt(
+                    self.iter()
+                        .fold(<$t as Zero>::zero(), |acc, a| acc + *a * *a),
+                )
+            }
+        }
+
+        impl ArgminMinMax for Vec<$t> {
+            fn min(x: &Self, y: &Self) -> Self {
+                assert!(!x.is_empty());
+                assert_eq!(x.len(), y.len());
+                x.iter()
+                    .zip(y.iter())
+                    .map(|(a, b)| <$t as ArgminMinMax>::min(a, b))
+                    .collect()
+            }
+
+            fn max(x: &Self, y: &Self) -> Self {
+                assert!(!x.is_empty());
+                assert_eq!(x.len(), y.len());
+                x.iter()
+                    .zip(y.iter())
+                    .map(|(a, b)| <$t as ArgminMinMax>::max(a, b))
+                    .collect()
+            }
+        }
+
+        impl ArgminMinMax for Vec<Vec<$t>> {
+            fn min(x: &Self, y: &Self) -> Self {
+                assert!(!x.is_empty());
+                assert_eq!(x.len(), y.len());
+                x.iter()
+                    .zip(y.iter())
+                    .map(|(a, b)| <Vec<$t> as ArgminMinMax>::min(a, b))
+                    .collect()
+            }
+
+            fn max(x: &Self, y: &Self) -> Self {
+                assert!(!x.is_empty());
+                assert_eq!(x.len(), y.len());
+                x.iter()
+                    .zip(y.iter())
+                    .map(|(a, b)| <Vec<$t> as ArgminMinMax>::max(a, b))
+                    .collect()
+            }
+        }
+
+        impl ArgminRandom for Vec<$t> {
+            fn rand_from_range<R: Rng>(min: &Self, max: &Self, rng: &mut R) -> Vec<$t> {
+                assert!(!min.is_empty());
+                assert_eq!(min.len(), max.len());
+                min.iter()
+                    .zip(max.iter())
+                    .map(|(a, b)| sample(*a, *b, rng))
+                    .collect()
+            }
+        }
+
+        impl ArgminRandom for Vec<Vec<$t>> {
+            fn rand_from_range<R: Rng>(min: &Self, max: &Self, rng: &mut R) -> Vec<Vec<$t>> {
+                assert!(!min.is_empty());
+                assert_eq!(min.len(), max.len());
+                min.iter()
+                    .zip(max.iter())
+                    .map(|(a, b)| Vec::<$t>::rand_from_range(a, b, rng))
+                    .collect()
+            }
+        }
+
+        impl ArgminAxpy<Vec<$t>, $t> for Vec<$t> {
+            #[inline]
+            fn axpy(&mut self, factor: &$t, vec: &Vec<$t>) {
+                assert_eq!(self.len(), vec.len());
+                self.iter_mut()
+                    .zip(vec.iter())
+                    .for_each(|(a, b)| *a += *factor * *b);
+            }
+        }
+
+        impl ArgminScal<$t> for Vec<$t> {
+            #[inline]
+            fn scal(&mut self, factor: &$t) {
+                self.iter_mut().for_each(|a| *a *= *factor);
+            }
+        }
+
+        impl ArgminSignum for Vec<$t> {
+            fn signum(mut self) -> Self {
+                for x in &mut self {
+                    *x = Float::signum(*x);
+                }
+                self
+            }
+        }
+    };
+}
+
+make_scalar!(twofloat::TwoFloat);
+#[cfg(feature = "vec")]
+make_vec!(twofloat::TwoFloat);
+
+#[cfg(all(test, feature = "vec"))]
+mod tests {
+    use super::*;
+    use twofloat::TwoFloat;
+
+    fn tf(x: f64) -> TwoFloat {
+        TwoFloat::from(x)
+    }
+
+    #[test]
+    fn test_vec_ops() {
+        let a = vec![tf(1.0), tf(2.0)];
+        let b = vec![tf(3.0), tf(4.0)];
+        assert_eq!(a.add(&b), vec![tf(4.0), tf(6.0)]);
+        assert_eq!(b.sub(&a), vec![tf(2.0), tf(2.0)]);
+        assert_eq!(a.mul(&tf(2.0)), vec![tf(2.0), tf(4.0)]);
+        assert_eq!(
+            <Vec<TwoFloat> as ArgminDot<_, TwoFloat>>::dot(&a, &b),
+            tf(11.0)
+        );
+        assert_eq!(b.l2_norm(), tf(5.0));
+        assert_eq!(b.sub(&tf(10.0)).l1_norm(), tf(13.0));
+        let mut c = a.clone();
+        c.axpy(&tf(2.0), &b);
+        assert_eq!(c, vec![tf(7.0), tf(10.0)]);
+        c.scal(&tf(0.5));
+        assert_eq!(c, vec![tf(3.5), tf(5.0)]);
+    }
+
+    #[test]
+    fn test_matrix_ops() {
+        let m = vec![vec![tf(1.0), tf(2.0)], vec![tf(3.0), tf(4.0)]];
+        let eye = <Vec<Vec<TwoFloat>> as ArgminEye>::eye(2);
+        assert_eq!(m.dot(&eye), m);
+        assert_eq!(
+            m.clone().t(),
+            vec![vec![tf(1.0), tf(3.0)], vec![tf(2.0), tf(4.0)]]
+        );
+        assert_eq!(m.dot(&vec![tf(1.0), tf(1.0)]), vec![tf(3.0), tf(7.0)]);
+    }
+
+    #[test]
+    fn test_precision() {
+        // 1 + 2^-60 is not representable as `f64`
+        let eps = tf(2.0).powi(-60);
+        let a = vec![tf(1.0) + eps];
+        assert_eq!(a.sub(&vec![tf(1.0)]), vec![eps]);
+    }
+
+    #[test]
+    fn test_random() {
+        let mut rng = rand::thread_rng();
+        let min = vec![tf(-1.0), tf(2.0)];
+        let max = vec![tf(1.0), tf(3.0)];
+        let x = Vec::<TwoFloat>::rand_from_range(&min, &max, &mut rng);
+        assert!(x.iter().zip(min.iter()).all(|(x, m)| x >= m));
+        assert!(x.iter().zip(max.iter()).all(|(x, m)| x < m));
+    }
+}
@@ -5,7 +5,9 @@
 // http://opensource.org/licenses/MIT>, at your option. This file may not be
 // copied, modified, or distributed except according to those terms.
 
+use super::kernels::Kernels;
 use crate::ArgminAdd;
+use num_complex::Complex;
 
 macro_rules! make_add {
     ($t:ty) => {
@@ -31,7 +33,7 @@ macro_rules! make_add {
                 assert!(n1 > 0);
                 assert!(n2 > 0);
                 assert_eq!(n1, n2);
-                self.iter().zip(other.iter()).map(|(a, b)| a + b).collect()
+                <$t as Kernels>::add(self, other)
             }
         }
 
@@ -82,6 +84,42 @@ make_add!(u32);
 make_add!(u64);
 make_add!(f32);
 make_add!(f64);
+make_add!(Complex<i8>);
+make_add!(Complex<i16>);
+make_add!(Complex<i32>);
+make_add!(Complex<i64>);
+make_add!(Complex<u8>);
+make_add!(Complex<u16>);
+make_add!(Complex<u32>);
+make_add!(Complex<u64>);
+make_add!(Complex<f32>);
+make_add!(Complex<f64>);
+
+// A complex vector is treated as the real vector of its real and imaginary parts, which is
+// consistent with `ArgminSignum` and `ArgminL1Norm`. Therefore a real scalar is added to both parts.
+macro_rules! make_add_real {
+    ($t:ty) => {
+        impl ArgminAdd<$t, Vec<Complex<$t>>> for Vec<Complex<$t>> {
+            #[inline]
+            fn add(&self, other: &$t) -> Vec<Complex<$t>> {
+                self.iter()
+                    .map(|a| Complex::new(a.re + other, a.im + other))
+                    .collect()
+            }
+        }
+    };
+}
+
+make_add_real!(i8);
+make_add_real!(i16);
+make_add_real!(i32);
+make_add_real!(i64);
+make_add_real!(u8);
+make_add_real!(u16);
+make_add_real!(u32);
+make_add_real!(u64);
+make_add_real!(f32);
+make_add_real!(f64);
 
 #[cfg(test)]
 mod tests {
@@ -130,6 +168,73 @@ mod tests {
                 }
             }
 
+            item! {
+                #[test]
+                fn [<test_add_vec_scalar_complex_ $t>]() {
+                    let a = vec![
+                        Complex::new(1 as $t, 4 as $t),
+                        Complex::new(12 as $t, 21 as $t),
+                        Complex::new(6 as $t, 10 as $t)
+                    ];
+                    let b = Complex::new(34 as $t, 12 as $t);
+                    let target = vec![a[0] + b, a[1] + b, a[2] + b];
+                    let res = <Vec<Complex<$t>> as ArgminAdd<Complex<$t>, Vec<Complex<$t>>>>::add(&a, &b);
+                    for i in 0..3 {
+                        assert_relative_eq!(target[i].re as f64, res[i].re as f64, epsilon = f64::EPSILON);
+                        assert_relative_eq!(target[i].im as f64, res[i].im as f64, epsilon = f64::EPSILON);
+                    }
+                    let res = <Complex<$t> as ArgminAdd<Vec<Complex<$t>>, Vec<Complex<$t>>>>::add(&b, &a);
+                    for i in 0..3 {
+                        assert_relative_eq!(target[i].re as f64, res[i].re as f64, epsilon = f64::EPSILON);
+                        assert_relative_eq!(target[i].im as f64, res[i].im as f64, epsilon = f64::EPSILON);
+                    }
+                }
+            }
+
+            item! {
+                #[test]
+                fn [<test_add_vec_real_scalar_complex_ $t>]() {
+                    let a = vec![
+                        Complex::new(1 as $t, 4 as $t),
+                        Complex::new(12 as $t, 21 as $t),
+                        Complex::new(6 as $t, 10 as $t)
+                    ];
+                    let b = 34 as $t;
+                    let target = vec![
+                        Complex::new(35 as $t, 38 as $t),
+                        Complex::new(46 as $t, 55 as $t),
+                        Complex::new(40 as $t, 44 as $t)
+                    ];
+                    let res = <Vec<Complex<$t>> as ArgminAdd<$t, Vec<Complex<$t>>>>::add(&a, &b);
+                    for i in 0..3 {
+                        assert_relative_eq!(target[i].re as f64, res[i].re as f64, epsilon = f64::EPSILON);
+                        assert_relative_eq!(target[i].im as f64, res[i].im as f64, epsilon = f64::EPSILON);
+                    }
+                }
+            }
+
+            item! {
+                #[test]
+                fn [<test_add_vec_vec_complex_ $t>]() {
+                    let a = vec![
+                        Complex::new(100 as $t, 40 as $t),
+                        Complex::new(76 as $t, 42 as $t),
+                        Complex::new(44 as $t, 35 as $t),
+                    ];
+                    let b = vec![
+                        Complex::new(1 as $t, 4 as $t),
+                        Complex::new(12 as $t, 21 as $t),
+                        Complex::new(6 as $t, 10 as $t)
+                    ];
+                    let target = vec![a[0] + b[0], a[1] + b[1], a[2] + b[2]];
+                    let res = <Vec<Complex<$t>> as ArgminAdd<Vec<Complex<$t>>, Vec<Complex<$t>>>>::add(&a, &b);
+                    for i in 0..3 {
+                        assert_relative_eq!(target[i].re as f64, res[i].re as f64, epsilon = f64::EPSILON);
+                        assert_relative_eq!(target[i].im as f64, res[i].im as f64, epsilon = f64::EPSILON);
+                    }
+                }
+            }
+
             item! {
                 #[test]
                 #[should_panic]
@@ -184,6 +289,34 @@ mod tests {
                 }
             }
 
+            item! {
+                #[test]
+                fn [<test_add_mat_mat_complex_ $t>]() {
+                    let a = vec![
+                        vec![Complex::new(100 as $t, 40 as $t), Complex::new(76 as $t, 42 as $t)],
+                        vec![Complex::new(44 as $t, 30 as $t), Complex::new(56 as $t, 52 as $t)],
+                        vec![Complex::new(64 as $t, 40 as $t), Complex::new(86 as $t, 72 as $t)],
+                    ];
+                    let b = vec![
+                        vec![Complex::new(10 as $t, 4 as $t), Complex::new(7 as $t, 4 as $t)],
+                        vec![Complex::new(4 as $t, 3 as $t), Complex::new(6 as $t, 5 as $t)],
+                        vec![Complex::new(6 as $t, 4 as $t), Complex::new(8 as $t, 2 as $t)],
+                    ];
+                    let target = vec![
+                        vec![a[0][0] + b[0][0], a[0][1] + b[0][1]],
+                        vec![a[1][0] + b[1][0], a[1][1] + b[1][1]],
+                        vec![a[2][0] + b[2][0], a[2][1] + b[2][1]],
+                    ];
+                    let res = <Vec<Vec<Complex<$t>>> as ArgminAdd<Vec<Vec<Complex<$t>>>, Vec<Vec<Complex<$t>>>>>::add(&a, &b);
+                    for i in 0..2 {
+                        for j in 0..3 {
+                            assert_relative_eq!(target[j][i].re as f64, res[j][i].re as f64, epsilon = f64::EPSILON);
+                            assert_relative_eq!(target[j][i].im as f64, res[j][i].im as f64, epsilon = f64::EPSILON);
+                        }
+                    }
+                }
+            }
+
             item! {
                 #[test]
                 fn [<test_add_mat_scalar_ $t>]() {
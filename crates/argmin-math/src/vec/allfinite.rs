@@ -0,0 +1,74 @@
+// Copyright 2018-2024 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use crate::ArgminAllFinite;
+
+impl<T: ArgminAllFinite> ArgminAllFinite for Vec<T> {
+    #[inline]
+    fn all_finite(&self) -> bool {
+        self.iter().all(|a| a.all_finite())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use num_complex::Complex;
+    use paste::item;
+
+    macro_rules! make_test {
+        ($t:ty) => {
+            item! {
+                #[test]
+                fn [<test_allfinite_ $t>]() {
+                    let a = vec![4 as $t, 3 as $t];
+                    assert!(<Vec<$t> as ArgminAllFinite>::all_finite(&a));
+                    let a = vec![Complex::new(4 as $t, 2 as $t), Complex::new(3 as $t, 4 as $t)];
+                    assert!(<Vec<Complex<$t>> as ArgminAllFinite>::all_finite(&a));
+                }
+            }
+
+            item! {
+                #[test]
+                fn [<test_allfinite_mat_ $t>]() {
+                    let a = vec![vec![4 as $t, 3 as $t], vec![1 as $t, 2 as $t]];
+                    assert!(<Vec<Vec<$t>> as ArgminAllFinite>::all_finite(&a));
+                }
+            }
+        };
+    }
+
+    macro_rules! make_test_float {
+        ($t:ident) => {
+            item! {
+                #[test]
+                fn [<test_allfinite_nonfinite_ $t>]() {
+                    let a = vec![4 as $t, $t::NAN];
+                    assert!(!<Vec<$t> as ArgminAllFinite>::all_finite(&a));
+                    let a = vec![$t::INFINITY, 4 as $t];
+                    assert!(!<Vec<$t> as ArgminAllFinite>::all_finite(&a));
+                    let a = vec![vec![4 as $t, 3 as $t], vec![1 as $t, $t::NEG_INFINITY]];
+                    assert!(!<Vec<Vec<$t>> as ArgminAllFinite>::all_finite(&a));
+                }
+            }
+        };
+    }
+
+    make_test!(i8);
+    make_test!(u8);
+    make_test!(i16);
+    make_test!(u16);
+    make_test!(i32);
+    make_test!(u32);
+    make_test!(i64);
+    make_test!(u64);
+    make_test!(f32);
+    make_test!(f64);
+
+    make_test_float!(f32);
+    make_test_float!(f64);
+}
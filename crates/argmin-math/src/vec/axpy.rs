@@ -0,0 +1,200 @@
+// Copyright 2018-2024 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use super::kernels::Kernels;
+use crate::ArgminAxpy;
+use num_complex::Complex;
+
+macro_rules! make_axpy {
+    ($t:ty) => {
+        impl ArgminAxpy<Vec<$t>, $t> for Vec<$t> {
+            #[inline]
+            fn axpy(&mut self, factor: &$t, vec: &Vec<$t>) {
+                <$t as Kernels>::axpy(factor, vec, self);
+            }
+        }
+
+        impl ArgminAxpy<Vec<Vec<$t>>, $t> for Vec<Vec<$t>> {
+            #[inline]
+            fn axpy(&mut self, factor: &$t, vec: &Vec<Vec<$t>>) {
+                assert_eq!(self.len(), vec.len());
+                self.iter_mut()
+                    .zip(vec.iter())
+                    .for_each(|(a, b)| <$t as Kernels>::axpy(factor, b, a));
+            }
+        }
+    };
+}
+
+make_axpy!(i8);
+make_axpy!(i16);
+make_axpy!(i32);
+make_axpy!(i64);
+make_axpy!(u8);
+make_axpy!(u16);
+make_axpy!(u32);
+make_axpy!(u64);
+make_axpy!(f32);
+make_axpy!(f64);
+make_axpy!(Complex<i8>);
+make_axpy!(Complex<i16>);
+make_axpy!(Complex<i32>);
+make_axpy!(Complex<i64>);
+make_axpy!(Complex<u8>);
+make_axpy!(Complex<u16>);
+make_axpy!(Complex<u32>);
+make_axpy!(Complex<u64>);
+make_axpy!(Complex<f32>);
+make_axpy!(Complex<f64>);
+
+macro_rules! make_axpy_real {
+    ($t:ty) => {
+        impl ArgminAxpy<Vec<Complex<$t>>, $t> for Vec<Complex<$t>> {
+            #[inline]
+            fn axpy(&mut self, factor: &$t, vec: &Vec<Complex<$t>>) {
+                assert_eq!(self.len(), vec.len());
+                self.iter_mut()
+                    .zip(vec.iter())
+                    .for_each(|(a, b)| *a += b.scale(*factor));
+            }
+        }
+    };
+}
+
+make_axpy_real!(i8);
+make_axpy_real!(i16);
+make_axpy_real!(i32);
+make_axpy_real!(i64);
+make_axpy_real!(u8);
+make_axpy_real!(u16);
+make_axpy_real!(u32);
+make_axpy_real!(u64);
+make_axpy_real!(f32);
+make_axpy_real!(f64);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_relative_eq;
+    use paste::item;
+
+    macro_rules! make_test {
+        ($t:ty) => {
+            item! {
+                #[test]
+                fn [<test_axpy_vec_ $t>]() {
+                    let mut a = vec![1 as $t, 2 as $t, 3 as $t];
+                    let b = 2 as $t;
+                    let c = vec![4 as $t, 5 as $t, 6 as $t];
+                    <Vec<$t> as ArgminAxpy<Vec<$t>, $t>>::axpy(&mut a, &b, &c);
+                    let target = vec![9 as $t, 12 as $t, 15 as $t];
+                    for i in 0..3 {
+                        assert_relative_eq!(a[i] as f64, target[i] as f64, epsilon = f64::EPSILON);
+                    }
+                }
+            }
+
+            item! {
+                #[test]
+                fn [<test_axpy_vec_complex_ $t>]() {
+                    let mut a = vec![Complex::new(1 as $t, 2 as $t), Complex::new(3 as $t, 1 as $t)];
+                    let b = Complex::new(2 as $t, 1 as $t);
+                    let c = vec![Complex::new(4 as $t, 1 as $t), Complex::new(3 as $t, 1 as $t)];
+                    let target = vec![a[0] + b * c[0], a[1] + b * c[1]];
+                    <Vec<Complex<$t>> as ArgminAxpy<Vec<Complex<$t>>, Complex<$t>>>::axpy(&mut a, &b, &c);
+                    assert_eq!(a, target);
+                }
+            }
+
+            item! {
+                #[test]
+                fn [<test_axpy_vec_complex_real_ $t>]() {
+                    let mut a = vec![Complex::new(1 as $t, 2 as $t), Complex::new(3 as $t, 1 as $t)];
+                    let b = 2 as $t;
+                    let c = vec![Complex::new(4 as $t, 1 as $t), Complex::new(3 as $t, 1 as $t)];
+                    <Vec<Complex<$t>> as ArgminAxpy<Vec<Complex<$t>>, $t>>::axpy(&mut a, &b, &c);
+                    let target = vec![Complex::new(9 as $t, 4 as $t), Complex::new(9 as $t, 3 as $t)];
+                    assert_eq!(a, target);
+                }
+            }
+
+            item! {
+                #[test]
+                #[should_panic]
+                fn [<test_axpy_vec_complex_real_panic_ $t>]() {
+                    let mut a = vec![Complex::new(1 as $t, 2 as $t), Complex::new(3 as $t, 1 as $t)];
+                    let c = vec![Complex::new(4 as $t, 1 as $t)];
+                    <Vec<Complex<$t>> as ArgminAxpy<Vec<Complex<$t>>, $t>>::axpy(&mut a, &(2 as $t), &c);
+                }
+            }
+
+            item! {
+                #[test]
+                #[should_panic]
+                fn [<test_axpy_vec_panic_1_ $t>]() {
+                    let mut a = vec![1 as $t, 2 as $t, 3 as $t];
+                    let b = 2 as $t;
+                    let c = vec![4 as $t, 5 as $t];
+                    <Vec<$t> as ArgminAxpy<Vec<$t>, $t>>::axpy(&mut a, &b, &c);
+                }
+            }
+
+            item! {
+                #[test]
+                #[should_panic]
+                fn [<test_axpy_vec_panic_2_ $t>]() {
+                    let mut a = vec![1 as $t, 2 as $t];
+                    let b = 2 as $t;
+                    let c = vec![4 as $t, 5 as $t, 6 as $t];
+                    <Vec<$t> as ArgminAxpy<Vec<$t>, $t>>::axpy(&mut a, &b, &c);
+                }
+            }
+
+            item! {
+                #[test]
+                fn [<test_axpy_mat_ $t>]() {
+                    let mut a = vec![vec![1 as $t, 2 as $t], vec![3 as $t, 4 as $t]];
+                    let b = 2 as $t;
+                    let c = vec![vec![4 as $t, 5 as $t], vec![6 as $t, 7 as $t]];
+                    <Vec<Vec<$t>> as ArgminAxpy<Vec<Vec<$t>>, $t>>::axpy(&mut a, &b, &c);
+                    let target = vec![vec![9 as $t, 12 as $t], vec![15 as $t, 18 as $t]];
+                    for i in 0..2 {
+                        for j in 0..2 {
+                            assert_relative_eq!(
+                                a[i][j] as f64,
+                                target[i][j] as f64,
+                                epsilon = f64::EPSILON
+                            );
+                        }
+                    }
+                }
+            }
+
+            item! {
+                #[test]
+                #[should_panic]
+                fn [<test_axpy_mat_panic_ $t>]() {
+                    let mut a = vec![vec![1 as $t, 2 as $t], vec![3 as $t, 4 as $t]];
+                    let b = 2 as $t;
+                    let c = vec![vec![4 as $t, 5 as $t]];
+                    <Vec<Vec<$t>> as ArgminAxpy<Vec<Vec<$t>>, $t>>::axpy(&mut a, &b, &c);
+                }
+            }
+        };
+    }
+
+    make_test!(i8);
+    make_test!(u8);
+    make_test!(i16);
+    make_test!(u16);
+    make_test!(i32);
+    make_test!(u32);
+    make_test!(i64);
+    make_test!(u64);
+    make_test!(f32);
+    make_test!(f64);
+}
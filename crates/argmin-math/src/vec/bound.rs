@@ -0,0 +1,118 @@
+// Copyright 2018-2024 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use crate::ArgminBound;
+use num_traits::Float;
+
+impl<T, F> ArgminBound<Vec<T>, F> for Vec<T>
+where
+    T: ArgminBound<T, F>,
+    F: Float,
+{
+    #[inline]
+    fn max_step_in_bounds(&self, direction: &Vec<T>, lower: &Vec<T>, upper: &Vec<T>) -> F {
+        assert_eq!(self.len(), direction.len());
+        assert_eq!(self.len(), lower.len());
+        assert_eq!(self.len(), upper.len());
+
+        self.iter()
+            .zip(direction.iter())
+            .zip(lower.iter().zip(upper.iter()))
+            .map(|((x, d), (l, u))| x.max_step_in_bounds(d, l, u))
+            .fold(F::infinity(), F::min)
+    }
+
+    #[inline]
+    fn project_direction(&self, direction: &Vec<T>, lower: &Vec<T>, upper: &Vec<T>) -> Vec<T> {
+        assert_eq!(self.len(), direction.len());
+        assert_eq!(self.len(), lower.len());
+        assert_eq!(self.len(), upper.len());
+
+        self.iter()
+            .zip(direction.iter())
+            .zip(lower.iter().zip(upper.iter()))
+            .map(|((x, d), (l, u))| x.project_direction(d, l, u))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_relative_eq;
+    use num_complex::Complex;
+    use paste::item;
+
+    macro_rules! make_test {
+        ($t:ident) => {
+            item! {
+                #[test]
+                fn [<test_max_step_in_bounds_ $t>]() {
+                    let x = vec![0 as $t, 0.5 as $t];
+                    let l = vec![-1 as $t, -1 as $t];
+                    let u = vec![1 as $t, 1 as $t];
+                    let alpha: $t = x.max_step_in_bounds(&vec![2 as $t, 1 as $t], &l, &u);
+                    assert_relative_eq!(alpha as f64, 0.5, epsilon = $t::EPSILON as f64);
+                    let alpha: $t = x.max_step_in_bounds(&vec![0 as $t, 0 as $t], &l, &u);
+                    assert!(alpha.is_infinite());
+                    let alpha: $t = x.max_step_in_bounds(&vec![-4 as $t, -1 as $t], &l, &u);
+                    assert_relative_eq!(alpha as f64, 0.25, epsilon = $t::EPSILON as f64);
+                }
+            }
+
+            item! {
+                #[test]
+                fn [<test_max_step_in_bounds_complex_ $t>]() {
+                    let x = vec![Complex::new(0 as $t, 0.5 as $t)];
+                    let d = vec![Complex::new(2 as $t, 1 as $t)];
+                    let l = vec![Complex::new(-1 as $t, -1 as $t)];
+                    let u = vec![Complex::new(1 as $t, 1 as $t)];
+                    let alpha: $t = x.max_step_in_bounds(&d, &l, &u);
+                    assert_relative_eq!(alpha as f64, 0.5, epsilon = $t::EPSILON as f64);
+                }
+            }
+
+            item! {
+                #[test]
+                fn [<test_max_step_in_bounds_mat_ $t>]() {
+                    let x = vec![vec![0 as $t, 0.5 as $t], vec![0 as $t, 0 as $t]];
+                    let d = vec![vec![0 as $t, 1 as $t], vec![-4 as $t, 0 as $t]];
+                    let l = vec![vec![-1 as $t, -1 as $t], vec![-1 as $t, -1 as $t]];
+                    let u = vec![vec![1 as $t, 1 as $t], vec![1 as $t, 1 as $t]];
+                    let alpha: $t = x.max_step_in_bounds(&d, &l, &u);
+                    assert_relative_eq!(alpha as f64, 0.25, epsilon = $t::EPSILON as f64);
+                }
+            }
+
+            item! {
+                #[test]
+                fn [<test_project_direction_ $t>]() {
+                    let x = vec![1 as $t, -1 as $t, 0 as $t, 1 as $t];
+                    let d = vec![2 as $t, -3 as $t, 4 as $t, -5 as $t];
+                    let l = vec![-1 as $t; 4];
+                    let u = vec![1 as $t; 4];
+                    let p = <Vec<$t> as ArgminBound<Vec<$t>, $t>>::project_direction(&x, &d, &l, &u);
+                    assert_eq!(p, vec![0 as $t, 0 as $t, 4 as $t, -5 as $t]);
+                }
+            }
+
+            item! {
+                #[test]
+                #[should_panic]
+                fn [<test_max_step_in_bounds_panic_ $t>]() {
+                    let x = vec![0 as $t, 0.5 as $t];
+                    let l = vec![-1 as $t];
+                    let u = vec![1 as $t, 1 as $t];
+                    let _: $t = x.max_step_in_bounds(&vec![2 as $t, 1 as $t], &l, &u);
+                }
+            }
+        };
+    }
+
+    make_test!(f32);
+    make_test!(f64);
+}
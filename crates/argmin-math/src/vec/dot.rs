@@ -5,8 +5,9 @@
 // http://opensource.org/licenses/MIT>, at your option. This file may not be
 // copied, modified, or distributed except according to those terms.
 
+use super::kernels::Kernels;
+use super::matrix::RowMajor;
 use crate::ArgminDot;
-use crate::ArgminTranspose;
 use num_complex::Complex;
 
 macro_rules! make_dot_vec {
@@ -14,21 +15,21 @@ macro_rules! make_dot_vec {
         impl ArgminDot<Vec<$t>, $t> for Vec<$t> {
             #[inline]
             fn dot(&self, other: &Vec<$t>) -> $t {
-                self.iter().zip(other.iter()).map(|(a, b)| a * b).sum()
+                <$t as Kernels>::dot(self, other)
             }
         }
 
         impl ArgminDot<$t, Vec<$t>> for Vec<$t> {
             #[inline]
             fn dot(&self, other: &$t) -> Vec<$t> {
-                self.iter().map(|a| a * other).collect()
+                <$t as Kernels>::scal(other, self)
             }
         }
 
         impl ArgminDot<Vec<$t>, Vec<$t>> for $t {
             #[inline]
             fn dot(&self, other: &Vec<$t>) -> Vec<$t> {
-                other.iter().map(|a| a * self).collect()
+                <$t as Kernels>::scal(self, other)
             }
         }
 
@@ -36,7 +37,7 @@ macro_rules! make_dot_vec {
             #[inline]
             fn dot(&self, other: &Vec<$t>) -> Vec<Vec<$t>> {
                 self.iter()
-                    .map(|b| other.iter().map(|a| a * b).collect())
+                    .map(|b| <$t as Kernels>::scal(b, other))
                     .collect()
             }
         }
@@ -44,41 +45,26 @@ macro_rules! make_dot_vec {
         impl ArgminDot<Vec<$t>, Vec<$t>> for Vec<Vec<$t>> {
             #[inline]
             fn dot(&self, other: &Vec<$t>) -> Vec<$t> {
-                (0..self.len()).map(|i| self[i].dot(other)).collect()
+                self.iter()
+                    .map(|row| <$t as Kernels>::dot(row, other))
+                    .collect()
             }
         }
 
         impl ArgminDot<Vec<Vec<$t>>, Vec<Vec<$t>>> for Vec<Vec<$t>> {
             #[inline]
             fn dot(&self, other: &Vec<Vec<$t>>) -> Vec<Vec<$t>> {
-                // Would be more efficient if this wasn't necessary!
-                let other = other.clone().t();
-                let sr = self.len();
-                assert!(sr > 0);
-                let sc = self[0].len();
-                assert!(sc > 0);
-                let or = other.len();
-                assert!(or > 0);
-                let oc = other[0].len();
-                assert_eq!(sc, or);
-                assert!(oc > 0);
-                let v = vec![<$t>::default(); oc];
-                let mut out = vec![v; sr];
-                for i in 0..sr {
-                    assert_eq!(self[i].len(), sc);
-                    for j in 0..oc {
-                        out[i][j] = self[i].dot(&other[j]);
-                    }
-                }
-                out
+                RowMajor::from_rows(self)
+                    .matmul(&RowMajor::from_rows(other))
+                    .into_rows()
             }
         }
 
         impl ArgminDot<$t, Vec<Vec<$t>>> for Vec<Vec<$t>> {
             #[inline]
             fn dot(&self, other: &$t) -> Vec<Vec<$t>> {
-                (0..self.len())
-                    .map(|i| self[i].iter().map(|a| a * other).collect())
+                self.iter()
+                    .map(|row| <$t as Kernels>::scal(other, row))
                     .collect()
             }
         }
@@ -86,8 +72,9 @@ macro_rules! make_dot_vec {
         impl ArgminDot<Vec<Vec<$t>>, Vec<Vec<$t>>> for $t {
             #[inline]
             fn dot(&self, other: &Vec<Vec<$t>>) -> Vec<Vec<$t>> {
-                (0..other.len())
-                    .map(|i| other[i].iter().map(|a| a * self).collect())
+                other
+                    .iter()
+                    .map(|row| <$t as Kernels>::scal(self, row))
                     .collect()
             }
         }
@@ -115,6 +102,34 @@ make_dot_vec!(Complex<u16>);
 make_dot_vec!(Complex<u32>);
 make_dot_vec!(Complex<u64>);
 
+macro_rules! make_dot_real {
+    ($t:ty) => {
+        /// Real part of the Hermitian inner product `Re(self^H other)`, which is the dot product
+        /// of the real vectors of real and imaginary parts. This is the inner product needed by
+        /// gradient based solvers to optimize real-valued functions of complex parameters.
+        impl ArgminDot<Vec<Complex<$t>>, $t> for Vec<Complex<$t>> {
+            #[inline]
+            fn dot(&self, other: &Vec<Complex<$t>>) -> $t {
+                self.iter()
+                    .zip(other.iter())
+                    .map(|(a, b)| a.re * b.re + a.im * b.im)
+                    .sum()
+            }
+        }
+    };
+}
+
+make_dot_real!(f32);
+make_dot_real!(f64);
+make_dot_real!(i8);
+make_dot_real!(i16);
+make_dot_real!(i32);
+make_dot_real!(i64);
+make_dot_real!(u8);
+make_dot_real!(u16);
+make_dot_real!(u32);
+make_dot_real!(u64);
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -153,6 +168,27 @@ mod tests {
                 }
             }
 
+            item! {
+                #[test]
+                fn [<test_vec_vec_complex_real_ $t>]() {
+                    let a = vec![
+                        Complex::new(2 as $t, 2 as $t),
+                        Complex::new(5 as $t, 2 as $t),
+                        Complex::new(3 as $t, 2 as $t),
+                    ];
+                    let b = vec![
+                        Complex::new(5 as $t, 3 as $t),
+                        Complex::new(2 as $t, 4 as $t),
+                        Complex::new(8 as $t, 4 as $t),
+                    ];
+                    let res: $t = a.dot(&b);
+                    assert_relative_eq!(res as f64, 66 as f64, epsilon = f64::EPSILON);
+                    // The squared l2-norm
+                    let res: $t = a.dot(&a);
+                    assert_relative_eq!(res as f64, 50 as f64, epsilon = f64::EPSILON);
+                }
+            }
+
             item! {
                 #[test]
                 fn [<test_vec_scalar_ $t>]() {
@@ -326,6 +362,33 @@ mod tests {
                 }
             }
 
+            item! {
+                #[test]
+                fn [<test_mat_mat_non_square_ $t>]() {
+                    let a = vec![
+                        vec![1 as $t, 2 as $t, 3 as $t],
+                        vec![4 as $t, 5 as $t, 6 as $t],
+                    ];
+                    let b = vec![
+                        vec![3 as $t, 2 as $t],
+                        vec![1 as $t, 5 as $t],
+                        vec![2 as $t, 4 as $t],
+                    ];
+                    let res = vec![
+                        vec![11 as $t, 24 as $t],
+                        vec![29 as $t, 57 as $t],
+                    ];
+                    let product = a.dot(&b);
+                    assert_eq!(product.len(), 2);
+                    for i in 0..2 {
+                        assert_eq!(product[i].len(), 2);
+                        for j in 0..2 {
+                            assert!((((res[i][j] - product[i][j]) as f64).abs()) < f64::EPSILON);
+                        }
+                    }
+                }
+            }
+
             item! {
                 #[test]
                 fn [<test_mat_mat_complex $t>]() {
@@ -413,9 +476,8 @@ mod tests {
                         vec![3 as $t, 2 as $t, 1 as $t]
                     ];
                     let b = vec![
-                        vec![3 as $t, 2 as $t],
-                        vec![6 as $t, 5 as $t],
-                        vec![3 as $t, 2 as $t]
+                        vec![3 as $t, 2 as $t, 1 as $t],
+                        vec![6 as $t, 5 as $t, 4 as $t]
                     ];
                     a.dot(&b);
                 }
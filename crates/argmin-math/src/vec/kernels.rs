@@ -0,0 +1,329 @@
+// Copyright 2018-2024 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+//! Level 1 BLAS-like kernels on slices used by the `Vec` backend.
+//!
+//! By default, all kernels are plain loops. The compiler vectorizes element-wise operations on
+//! its own, but not floating point reductions such as dot products, because this would change the
+//! order of summation. Therefore the reductions for `f32` and `f64` are dispatched to `faer` with
+//! the `vec_faer` feature and to explicit SIMD implementations based on `wide` with the
+//! `vec_simd` feature. If both features are enabled, `vec_faer` takes precedence.
+//!
+//! The benchmarks in `benches/vec.rs` compare the kernels to plain loops.
+
+use num_complex::Complex;
+use std::iter::Sum;
+use std::ops::{Add, AddAssign, Mul, Sub};
+
+/// Kernels on slices of `Self`
+pub(crate) trait Kernels:
+    Copy + Add<Output = Self> + Sub<Output = Self> + Mul<Output = Self> + AddAssign + Sum
+{
+    /// Dot product of the first `min(x.len(), y.len())` elements of `x` and `y`
+    #[inline]
+    fn dot(x: &[Self], y: &[Self]) -> Self {
+        x.iter().zip(y.iter()).map(|(&a, &b)| a * b).sum()
+    }
+
+    /// Returns `x + y`. `x` and `y` must be of the same length.
+    #[inline]
+    fn add(x: &[Self], y: &[Self]) -> Vec<Self> {
+        assert_eq!(x.len(), y.len());
+        x.iter().zip(y.iter()).map(|(&a, &b)| a + b).collect()
+    }
+
+    /// Returns `x - y`. `x` and `y` must be of the same length.
+    #[inline]
+    fn sub(x: &[Self], y: &[Self]) -> Vec<Self> {
+        assert_eq!(x.len(), y.len());
+        x.iter().zip(y.iter()).map(|(&a, &b)| a - b).collect()
+    }
+
+    /// Returns `alpha * x`
+    #[inline]
+    fn scal(alpha: &Self, x: &[Self]) -> Vec<Self> {
+        x.iter().map(|&a| a * *alpha).collect()
+    }
+
+    /// Computes `y += alpha * x` in place. `x` and `y` must be of the same length.
+    #[inline]
+    fn axpy(alpha: &Self, x: &[Self], y: &mut [Self]) {
+        assert_eq!(x.len(), y.len());
+        y.iter_mut()
+            .zip(x.iter())
+            .for_each(|(b, &a)| *b += *alpha * a);
+    }
+}
+
+/// Kernels which are only available for floating point types
+pub(crate) trait FloatKernels: Kernels {
+    /// l2-norm of `x`
+    fn nrm2(x: &[Self]) -> Self;
+}
+
+#[cfg(not(any(feature = "vec_faer", feature = "vec_simd")))]
+macro_rules! make_float_kernels_naive {
+    ($t:ty) => {
+        impl Kernels for $t {}
+
+        impl FloatKernels for $t {
+            #[inline]
+            fn nrm2(x: &[$t]) -> $t {
+                x.iter().map(|a| a.powi(2)).sum::<$t>().sqrt()
+            }
+        }
+    };
+}
+
+#[cfg(feature = "vec_faer")]
+macro_rules! make_float_kernels_faer {
+    ($t:ty) => {
+        impl Kernels for $t {
+            #[inline]
+            fn dot(x: &[$t], y: &[$t]) -> $t {
+                let n = x.len().min(y.len());
+                faer::linalg::matmul::dot::inner_prod(
+                    faer::RowRef::from_slice(&x[..n]),
+                    faer::Conj::No,
+                    faer::ColRef::from_slice(&y[..n]),
+                    faer::Conj::No,
+                )
+            }
+        }
+
+        impl FloatKernels for $t {
+            // `faer`s own l2-norm avoids overflow by scaling, which is considerably slower than
+            // the plain loop. The dot product offers the same overflow behavior as the loop.
+            #[inline]
+            fn nrm2(x: &[$t]) -> $t {
+                <$t as Kernels>::dot(x, x).sqrt()
+            }
+        }
+    };
+}
+
+/// Explicit SIMD implementations of the kernels based on `wide`
+#[cfg(all(feature = "vec_simd", not(feature = "vec_faer")))]
+mod simd {
+    use std::ops::{Add, Mul};
+    use wide::{f32x8, f64x4};
+
+    /// Floating point types with a corresponding SIMD vector type
+    pub(super) trait SimdFloat:
+        Copy + Add<Output = Self> + Mul<Output = Self> + std::iter::Sum
+    {
+        /// SIMD vector type
+        type Simd: Copy;
+
+        /// Number of lanes of `Self::Simd`
+        const LANES: usize;
+
+        /// SIMD vector with all lanes set to zero
+        const ZERO: Self::Simd;
+
+        /// Loads `x`, which must have exactly `LANES` elements
+        fn load(x: &[Self]) -> Self::Simd;
+
+        /// Returns `a * b + c`
+        fn mul_add(a: Self::Simd, b: Self::Simd, c: Self::Simd) -> Self::Simd;
+
+        /// Sum of all lanes
+        fn reduce_add(x: Self::Simd) -> Self;
+    }
+
+    macro_rules! make_simd_float {
+        ($t:ty, $simd:ty, $lanes:expr) => {
+            impl SimdFloat for $t {
+                type Simd = $simd;
+
+                const LANES: usize = $lanes;
+
+                const ZERO: $simd = <$simd>::ZERO;
+
+                #[inline]
+                fn load(x: &[$t]) -> $simd {
+                    <$simd>::from(<[$t; $lanes]>::try_from(x).unwrap())
+                }
+
+                #[inline]
+                fn mul_add(a: $simd, b: $simd, c: $simd) -> $simd {
+                    a.mul_add(b, c)
+                }
+
+                #[inline]
+                fn reduce_add(x: $simd) -> $t {
+                    x.reduce_add()
+                }
+            }
+        };
+    }
+
+    make_simd_float!(f32, f32x8, 8);
+    make_simd_float!(f64, f64x4, 4);
+
+    /// Dot product of the first `min(x.len(), y.len())` elements of `x` and `y`
+    #[inline]
+    pub(super) fn dot<T: SimdFloat>(x: &[T], y: &[T]) -> T {
+        let n = x.len().min(y.len());
+        let xc = x[..n].chunks_exact(T::LANES);
+        let yc = y[..n].chunks_exact(T::LANES);
+        let rest: T = xc
+            .remainder()
+            .iter()
+            .zip(yc.remainder().iter())
+            .map(|(&a, &b)| a * b)
+            .sum();
+        let acc = xc.zip(yc).fold(T::ZERO, |acc, (a, b)| {
+            T::mul_add(T::load(a), T::load(b), acc)
+        });
+        T::reduce_add(acc) + rest
+    }
+}
+
+#[cfg(all(feature = "vec_simd", not(feature = "vec_faer")))]
+macro_rules! make_float_kernels_simd {
+    ($t:ty) => {
+        impl Kernels for $t {
+            #[inline]
+            fn dot(x: &[$t], y: &[$t]) -> $t {
+                simd::dot(x, y)
+            }
+        }
+
+        impl FloatKernels for $t {
+            #[inline]
+            fn nrm2(x: &[$t]) -> $t {
+                simd::dot(x, x).sqrt()
+            }
+        }
+    };
+}
+
+impl Kernels for i8 {}
+impl Kernels for i16 {}
+impl Kernels for i32 {}
+impl Kernels for i64 {}
+impl Kernels for u8 {}
+impl Kernels for u16 {}
+impl Kernels for u32 {}
+impl Kernels for u64 {}
+impl Kernels for Complex<i8> {}
+impl Kernels for Complex<i16> {}
+impl Kernels for Complex<i32> {}
+impl Kernels for Complex<i64> {}
+impl Kernels for Complex<u8> {}
+impl Kernels for Complex<u16> {}
+impl Kernels for Complex<u32> {}
+impl Kernels for Complex<u64> {}
+impl Kernels for Complex<f32> {}
+impl Kernels for Complex<f64> {}
+
+cfg_if::cfg_if! {
+    if #[cfg(feature = "vec_faer")] {
+        make_float_kernels_faer!(f32);
+        make_float_kernels_faer!(f64);
+    } else if #[cfg(feature = "vec_simd")] {
+        make_float_kernels_simd!(f32);
+        make_float_kernels_simd!(f64);
+    } else {
+        make_float_kernels_naive!(f32);
+        make_float_kernels_naive!(f64);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_relative_eq;
+    use paste::item;
+
+    macro_rules! make_test {
+        ($t:ty) => {
+            item! {
+                #[test]
+                fn [<test_kernels_ $t>]() {
+                    // Long enough to exercise the SIMD paths and their remainder handling
+                    let x: Vec<$t> = (0..1027).map(|i| ((i % 13) as $t - 6.0) / 7.0).collect();
+                    let y: Vec<$t> = (0..1027).map(|i| ((i % 7) as $t - 3.0) / 5.0).collect();
+                    let alpha = -1.5 as $t;
+
+                    let dot: f64 = x.iter().zip(y.iter()).map(|(a, b)| (a * b) as f64).sum();
+                    assert_relative_eq!(
+                        <$t as Kernels>::dot(&x, &y) as f64,
+                        dot,
+                        max_relative = 1e-5
+                    );
+                    // Only the common elements are considered
+                    assert_relative_eq!(
+                        <$t as Kernels>::dot(&x[..10], &y) as f64,
+                        <$t as Kernels>::dot(&x[..10], &y[..10]) as f64,
+                        epsilon = f64::EPSILON
+                    );
+
+                    let nrm2 = x.iter().map(|a| (a * a) as f64).sum::<f64>().sqrt();
+                    assert_relative_eq!(
+                        <$t as FloatKernels>::nrm2(&x) as f64,
+                        nrm2,
+                        max_relative = 1e-5
+                    );
+
+                    let add = <$t as Kernels>::add(&x, &y);
+                    let sub = <$t as Kernels>::sub(&x, &y);
+                    let scal = <$t as Kernels>::scal(&alpha, &x);
+                    let mut axpy = y.clone();
+                    <$t as Kernels>::axpy(&alpha, &x, &mut axpy);
+                    for i in 0..x.len() {
+                        assert_relative_eq!(add[i], x[i] + y[i], epsilon = <$t>::EPSILON);
+                        assert_relative_eq!(sub[i], x[i] - y[i], epsilon = <$t>::EPSILON);
+                        assert_relative_eq!(scal[i], alpha * x[i], epsilon = <$t>::EPSILON);
+                        assert_relative_eq!(axpy[i], y[i] + alpha * x[i], epsilon = <$t>::EPSILON);
+                    }
+                }
+            }
+
+            item! {
+                #[test]
+                fn [<test_kernels_short_ $t>]() {
+                    // Shorter than a single SIMD vector
+                    let x = vec![1 as $t, 2 as $t, 3 as $t];
+                    let y = vec![4 as $t, 5 as $t, 6 as $t];
+                    assert_relative_eq!(<$t as Kernels>::dot(&x, &y), 32 as $t);
+                    assert_relative_eq!(<$t as FloatKernels>::nrm2(&x[..0]), 0 as $t);
+                    assert_eq!(<$t as Kernels>::add(&x, &y), vec![5 as $t, 7 as $t, 9 as $t]);
+                    assert_eq!(<$t as Kernels>::sub(&y, &x), vec![3 as $t, 3 as $t, 3 as $t]);
+                    assert_eq!(
+                        <$t as Kernels>::scal(&(2 as $t), &x),
+                        vec![2 as $t, 4 as $t, 6 as $t]
+                    );
+                }
+            }
+
+            item! {
+                #[test]
+                #[should_panic]
+                fn [<test_kernels_add_panic_ $t>]() {
+                    let x = vec![1 as $t, 2 as $t, 3 as $t];
+                    let y = vec![1 as $t, 2 as $t];
+                    <$t as Kernels>::add(&x, &y);
+                }
+            }
+
+            item! {
+                #[test]
+                #[should_panic]
+                fn [<test_kernels_axpy_panic_ $t>]() {
+                    let x = vec![1 as $t, 2 as $t, 3 as $t];
+                    let mut y = vec![1 as $t, 2 as $t];
+                    <$t as Kernels>::axpy(&(2 as $t), &x, &mut y);
+                }
+            }
+        };
+    }
+
+    make_test!(f32);
+    make_test!(f64);
+}
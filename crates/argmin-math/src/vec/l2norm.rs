@@ -5,6 +5,7 @@
 // http://opensource.org/licenses/MIT>, at your option. This file may not be
 // copied, modified, or distributed except according to those terms.
 
+use super::kernels::FloatKernels;
 use crate::ArgminL2Norm;
 use num_complex::Complex;
 use num_integer::Roots;
@@ -14,7 +15,7 @@ macro_rules! make_norm_float {
         impl ArgminL2Norm<$t> for Vec<$t> {
             #[inline]
             fn l2_norm(&self) -> $t {
-                self.iter().map(|a| a.powi(2)).sum::<$t>().sqrt()
+                <$t as FloatKernels>::nrm2(self)
             }
         }
     };
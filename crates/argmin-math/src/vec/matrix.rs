@@ -0,0 +1,177 @@
+// Copyright 2018-2024 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+//! Contiguous row-major matrices used internally by the `Vec` backend.
+//!
+//! Matrices of the `Vec` backend are represented as `Vec<Vec<T>>`, where each row is a separate
+//! allocation. Matrix products are computed on contiguous copies instead, which is considerably
+//! more cache friendly. Copying is cheap compared to the product itself.
+
+use super::kernels::Kernels;
+
+/// Number of rows of the right-hand side which are processed at once in a matrix product, chosen
+/// such that they remain in cache while all rows of the left-hand side are processed.
+const BLOCK_ROWS: usize = 64;
+
+/// Dense matrix stored contiguously in row-major order
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) struct RowMajor<T> {
+    /// Number of rows
+    nrows: usize,
+    /// Number of columns
+    ncols: usize,
+    /// Elements, row after row
+    data: Vec<T>,
+}
+
+impl<T: Kernels + Default> RowMajor<T> {
+    /// Matrix of zeros (`T::default()`) of the given shape
+    pub(crate) fn zeros(nrows: usize, ncols: usize) -> Self {
+        RowMajor {
+            nrows,
+            ncols,
+            data: vec![T::default(); nrows * ncols],
+        }
+    }
+
+    /// Copies a `Vec<Vec<T>>` into contiguous memory.
+    ///
+    /// Panics if there are no rows, if the rows are empty or if they are of different lengths.
+    pub(crate) fn from_rows(rows: &[Vec<T>]) -> Self {
+        let nrows = rows.len();
+        assert!(nrows > 0);
+        let ncols = rows[0].len();
+        assert!(ncols > 0);
+        let mut data = Vec::with_capacity(nrows * ncols);
+        for row in rows {
+            assert_eq!(row.len(), ncols);
+            data.extend_from_slice(row);
+        }
+        RowMajor { nrows, ncols, data }
+    }
+
+    /// Converts the matrix back into a `Vec<Vec<T>>`
+    pub(crate) fn into_rows(self) -> Vec<Vec<T>> {
+        self.data
+            .chunks_exact(self.ncols)
+            .map(|row| row.to_vec())
+            .collect()
+    }
+
+    /// Matrix product `self * other`
+    ///
+    /// Each row of the result is accumulated as a linear combination of the rows of `other`
+    /// (i-k-j loop order), such that all operands are traversed contiguously. The rows of `other`
+    /// are processed in blocks to keep them in cache.
+    ///
+    /// Panics if the number of columns of `self` and the number of rows of `other` differ.
+    pub(crate) fn matmul(&self, other: &RowMajor<T>) -> RowMajor<T> {
+        assert_eq!(self.ncols, other.nrows);
+        let mut out = RowMajor::zeros(self.nrows, other.ncols);
+        let block = BLOCK_ROWS * other.ncols;
+        for (k0, other_block) in other.data.chunks(block).enumerate() {
+            let k0 = k0 * BLOCK_ROWS;
+            for (a_row, out_row) in self
+                .data
+                .chunks_exact(self.ncols)
+                .zip(out.data.chunks_exact_mut(out.ncols))
+            {
+                for (a, b_row) in a_row[k0..]
+                    .iter()
+                    .zip(other_block.chunks_exact(other.ncols))
+                {
+                    T::axpy(a, b_row, out_row);
+                }
+            }
+        }
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_relative_eq;
+
+    fn naive_matmul(a: &[Vec<f64>], b: &[Vec<f64>]) -> Vec<Vec<f64>> {
+        (0..a.len())
+            .map(|i| {
+                (0..b[0].len())
+                    .map(|j| (0..b.len()).map(|k| a[i][k] * b[k][j]).sum())
+                    .collect()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_from_into_rows() {
+        let rows = vec![vec![1.0f64, 2.0, 3.0], vec![4.0, 5.0, 6.0]];
+        let m = RowMajor::from_rows(&rows);
+        assert_eq!(m.nrows, 2);
+        assert_eq!(m.ncols, 3);
+        assert_eq!(m.data, vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0]);
+        assert_eq!(m.into_rows(), rows);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_from_rows_ragged() {
+        RowMajor::from_rows(&[vec![1.0f64, 2.0], vec![3.0]]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_from_rows_empty() {
+        RowMajor::<f64>::from_rows(&[]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_from_rows_empty_rows() {
+        RowMajor::<f64>::from_rows(&[vec![], vec![]]);
+    }
+
+    #[test]
+    fn test_matmul() {
+        // Sizes which are not multiples of the block size
+        for (n, k, m) in [(1, 1, 1), (3, 2, 4), (7, 150, 5), (130, 70, 129)] {
+            let a: Vec<Vec<f64>> = (0..n)
+                .map(|i| {
+                    (0..k)
+                        .map(|j| ((i * 7 + j * 3) % 11) as f64 - 5.0)
+                        .collect()
+                })
+                .collect();
+            let b: Vec<Vec<f64>> = (0..k)
+                .map(|i| {
+                    (0..m)
+                        .map(|j| ((i * 5 + j * 2) % 13) as f64 / 3.0)
+                        .collect()
+                })
+                .collect();
+            let res = RowMajor::from_rows(&a)
+                .matmul(&RowMajor::from_rows(&b))
+                .into_rows();
+            let target = naive_matmul(&a, &b);
+            assert_eq!(res.len(), n);
+            for (r, t) in res.iter().zip(target.iter()) {
+                assert_eq!(r.len(), m);
+                for (x, y) in r.iter().zip(t.iter()) {
+                    assert_relative_eq!(x, y, max_relative = 1e-12);
+                }
+            }
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_matmul_shape_mismatch() {
+        let a = RowMajor::from_rows(&[vec![1.0f64, 2.0], vec![3.0, 4.0]]);
+        let b = RowMajor::from_rows(&[vec![1.0f64, 2.0]]);
+        a.matmul(&b);
+    }
+}
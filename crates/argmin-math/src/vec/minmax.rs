@@ -6,6 +6,7 @@
 // copied, modified, or distributed except according to those terms.
 
 use crate::ArgminMinMax;
+use num_complex::Complex;
 
 macro_rules! make_minmax {
     ($t:ty) => {
@@ -66,6 +67,55 @@ make_minmax!(u64);
 make_minmax!(f32);
 make_minmax!(f64);
 
+// Complex numbers are not ordered. Consistent with `ArgminSignum`, the minimum and maximum are
+// selected separately for the real and imaginary parts.
+macro_rules! make_minmax_complex {
+    ($t:ty) => {
+        impl ArgminMinMax for Vec<Complex<$t>> {
+            fn min(x: &Self, y: &Self) -> Self {
+                assert!(!x.is_empty());
+                assert_eq!(x.len(), y.len());
+
+                x.iter()
+                    .zip(y.iter())
+                    .map(|(a, b)| {
+                        Complex::new(
+                            if a.re < b.re { a.re } else { b.re },
+                            if a.im < b.im { a.im } else { b.im },
+                        )
+                    })
+                    .collect()
+            }
+
+            fn max(x: &Self, y: &Self) -> Self {
+                assert!(!x.is_empty());
+                assert_eq!(x.len(), y.len());
+
+                x.iter()
+                    .zip(y.iter())
+                    .map(|(a, b)| {
+                        Complex::new(
+                            if a.re > b.re { a.re } else { b.re },
+                            if a.im > b.im { a.im } else { b.im },
+                        )
+                    })
+                    .collect()
+            }
+        }
+    };
+}
+
+make_minmax_complex!(i8);
+make_minmax_complex!(u8);
+make_minmax_complex!(i16);
+make_minmax_complex!(u16);
+make_minmax_complex!(i32);
+make_minmax_complex!(u32);
+make_minmax_complex!(i64);
+make_minmax_complex!(u64);
+make_minmax_complex!(f32);
+make_minmax_complex!(f64);
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -90,6 +140,36 @@ mod tests {
                 }
             }
 
+            item! {
+                #[test]
+                fn [<test_minmax_vec_vec_complex_ $t>]() {
+                    let a = vec![
+                        Complex::new(1 as $t, 4 as $t),
+                        Complex::new(8 as $t, 2 as $t)
+                    ];
+                    let b = vec![
+                        Complex::new(2 as $t, 3 as $t),
+                        Complex::new(4 as $t, 5 as $t)
+                    ];
+                    let target_max = vec![
+                        Complex::new(2 as $t, 4 as $t),
+                        Complex::new(8 as $t, 5 as $t)
+                    ];
+                    let target_min = vec![
+                        Complex::new(1 as $t, 3 as $t),
+                        Complex::new(4 as $t, 2 as $t)
+                    ];
+                    let res_max = <Vec<Complex<$t>> as ArgminMinMax>::max(&a, &b);
+                    let res_min = <Vec<Complex<$t>> as ArgminMinMax>::min(&a, &b);
+                    for i in 0..2 {
+                        assert_relative_eq!(target_max[i].re as f64, res_max[i].re as f64, epsilon = f64::EPSILON);
+                        assert_relative_eq!(target_max[i].im as f64, res_max[i].im as f64, epsilon = f64::EPSILON);
+                        assert_relative_eq!(target_min[i].re as f64, res_min[i].re as f64, epsilon = f64::EPSILON);
+                        assert_relative_eq!(target_min[i].im as f64, res_min[i].im as f64, epsilon = f64::EPSILON);
+                    }
+                }
+            }
+
             item! {
                 #[test]
                 fn [<test_minmax_mat_mat_ $t>]() {
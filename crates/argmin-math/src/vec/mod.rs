@@ -8,15 +8,21 @@
 #![allow(unused_imports)]
 
 mod add;
+mod allfinite;
+mod axpy;
+mod bound;
 mod conj;
 mod div;
 mod dot;
 mod eye;
+mod kernels;
 mod l1norm;
 mod l2norm;
+mod matrix;
 mod minmax;
 mod mul;
 mod random;
+mod scal;
 mod scaledadd;
 mod scaledsub;
 mod signum;
@@ -25,6 +31,9 @@ mod transpose;
 mod zero;
 
 pub use add::*;
+pub use allfinite::*;
+pub use axpy::*;
+pub use bound::*;
 pub use conj::*;
 pub use div::*;
 pub use dot::*;
@@ -34,6 +43,7 @@ pub use l2norm::*;
 pub use minmax::*;
 pub use mul::*;
 pub use random::*;
+pub use scal::*;
 pub use scaledadd::*;
 pub use scaledsub::*;
 pub use signum::*;
@@ -5,6 +5,7 @@
 // http://opensource.org/licenses/MIT>, at your option. This file may not be
 // copied, modified, or distributed except according to those terms.
 
+use super::kernels::Kernels;
 use crate::ArgminMul;
 use num_complex::Complex;
 
@@ -13,14 +14,14 @@ macro_rules! make_mul {
         impl ArgminMul<$t, Vec<$t>> for Vec<$t> {
             #[inline]
             fn mul(&self, other: &$t) -> Vec<$t> {
-                self.iter().map(|a| a * other).collect()
+                <$t as Kernels>::scal(other, self)
             }
         }
 
         impl ArgminMul<Vec<$t>, Vec<$t>> for $t {
             #[inline]
             fn mul(&self, other: &Vec<$t>) -> Vec<$t> {
-                other.iter().map(|a| a * self).collect()
+                <$t as Kernels>::scal(self, other)
             }
         }
 
@@ -93,6 +94,35 @@ make_mul!(Complex<u64>);
 make_mul!(Complex<f32>);
 make_mul!(Complex<f64>);
 
+macro_rules! make_mul_real {
+    ($t:ty) => {
+        impl ArgminMul<$t, Vec<Complex<$t>>> for Vec<Complex<$t>> {
+            #[inline]
+            fn mul(&self, other: &$t) -> Vec<Complex<$t>> {
+                self.iter().map(|a| a.scale(*other)).collect()
+            }
+        }
+
+        impl ArgminMul<Vec<Complex<$t>>, Vec<Complex<$t>>> for $t {
+            #[inline]
+            fn mul(&self, other: &Vec<Complex<$t>>) -> Vec<Complex<$t>> {
+                other.iter().map(|a| a.scale(*self)).collect()
+            }
+        }
+    };
+}
+
+make_mul_real!(i8);
+make_mul_real!(u8);
+make_mul_real!(i16);
+make_mul_real!(u16);
+make_mul_real!(i32);
+make_mul_real!(u32);
+make_mul_real!(i64);
+make_mul_real!(u64);
+make_mul_real!(f32);
+make_mul_real!(f64);
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -161,6 +191,31 @@ mod tests {
                 }
             }
 
+            item! {
+                #[test]
+                fn [<test_mul_vec_real_scalar_complex_ $t>]() {
+                    let a = vec![
+                        Complex::new(5 as $t, 3 as $t),
+                        Complex::new(8 as $t, 2 as $t)
+                    ];
+                    let b = 2 as $t;
+                    let target = vec![
+                        Complex::new(10 as $t, 6 as $t),
+                        Complex::new(16 as $t, 4 as $t)
+                    ];
+                    let res = <Vec<Complex<$t>> as ArgminMul<$t, Vec<Complex<$t>>>>::mul(&a, &b);
+                    for i in 0..2 {
+                        assert_relative_eq!(target[i].re as f64, res[i].re as f64, epsilon = f64::EPSILON);
+                        assert_relative_eq!(target[i].im as f64, res[i].im as f64, epsilon = f64::EPSILON);
+                    }
+                    let res = <$t as ArgminMul<Vec<Complex<$t>>, Vec<Complex<$t>>>>::mul(&b, &a);
+                    for i in 0..2 {
+                        assert_relative_eq!(target[i].re as f64, res[i].re as f64, epsilon = f64::EPSILON);
+                        assert_relative_eq!(target[i].im as f64, res[i].im as f64, epsilon = f64::EPSILON);
+                    }
+                }
+            }
+
             item! {
                 #[test]
                 fn [<test_mul_vec_vec_ $t>]() {
@@ -0,0 +1,145 @@
+// Copyright 2018-2024 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use crate::ArgminScal;
+use num_complex::Complex;
+
+macro_rules! make_scal {
+    ($t:ty) => {
+        impl ArgminScal<$t> for Vec<$t> {
+            #[inline]
+            fn scal(&mut self, factor: &$t) {
+                self.iter_mut().for_each(|a| *a *= *factor);
+            }
+        }
+
+        impl ArgminScal<$t> for Vec<Vec<$t>> {
+            #[inline]
+            fn scal(&mut self, factor: &$t) {
+                self.iter_mut()
+                    .flat_map(|row| row.iter_mut())
+                    .for_each(|a| *a *= *factor);
+            }
+        }
+    };
+}
+
+make_scal!(i8);
+make_scal!(i16);
+make_scal!(i32);
+make_scal!(i64);
+make_scal!(u8);
+make_scal!(u16);
+make_scal!(u32);
+make_scal!(u64);
+make_scal!(f32);
+make_scal!(f64);
+make_scal!(Complex<i8>);
+make_scal!(Complex<i16>);
+make_scal!(Complex<i32>);
+make_scal!(Complex<i64>);
+make_scal!(Complex<u8>);
+make_scal!(Complex<u16>);
+make_scal!(Complex<u32>);
+make_scal!(Complex<u64>);
+make_scal!(Complex<f32>);
+make_scal!(Complex<f64>);
+
+macro_rules! make_scal_real {
+    ($t:ty) => {
+        impl ArgminScal<$t> for Vec<Complex<$t>> {
+            #[inline]
+            fn scal(&mut self, factor: &$t) {
+                self.iter_mut().for_each(|a| *a = a.scale(*factor));
+            }
+        }
+    };
+}
+
+make_scal_real!(i8);
+make_scal_real!(i16);
+make_scal_real!(i32);
+make_scal_real!(i64);
+make_scal_real!(u8);
+make_scal_real!(u16);
+make_scal_real!(u32);
+make_scal_real!(u64);
+make_scal_real!(f32);
+make_scal_real!(f64);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_relative_eq;
+    use paste::item;
+
+    macro_rules! make_test {
+        ($t:ty) => {
+            item! {
+                #[test]
+                fn [<test_scal_vec_ $t>]() {
+                    let mut a = vec![1 as $t, 2 as $t, 3 as $t];
+                    <Vec<$t> as ArgminScal<$t>>::scal(&mut a, &(2 as $t));
+                    let target = vec![2 as $t, 4 as $t, 6 as $t];
+                    for i in 0..3 {
+                        assert_relative_eq!(a[i] as f64, target[i] as f64, epsilon = f64::EPSILON);
+                    }
+                }
+            }
+
+            item! {
+                #[test]
+                fn [<test_scal_vec_complex_ $t>]() {
+                    let mut a = vec![Complex::new(1 as $t, 2 as $t), Complex::new(3 as $t, 1 as $t)];
+                    let b = Complex::new(2 as $t, 1 as $t);
+                    let target = vec![a[0] * b, a[1] * b];
+                    <Vec<Complex<$t>> as ArgminScal<Complex<$t>>>::scal(&mut a, &b);
+                    assert_eq!(a, target);
+                }
+            }
+
+            item! {
+                #[test]
+                fn [<test_scal_vec_complex_real_ $t>]() {
+                    let mut a = vec![Complex::new(1 as $t, 2 as $t), Complex::new(3 as $t, 1 as $t)];
+                    <Vec<Complex<$t>> as ArgminScal<$t>>::scal(&mut a, &(2 as $t));
+                    let target = vec![Complex::new(2 as $t, 4 as $t), Complex::new(6 as $t, 2 as $t)];
+                    assert_eq!(a, target);
+                }
+            }
+
+            item! {
+                #[test]
+                fn [<test_scal_mat_ $t>]() {
+                    let mut a = vec![vec![1 as $t, 2 as $t], vec![3 as $t, 4 as $t]];
+                    <Vec<Vec<$t>> as ArgminScal<$t>>::scal(&mut a, &(2 as $t));
+                    let target = vec![vec![2 as $t, 4 as $t], vec![6 as $t, 8 as $t]];
+                    for i in 0..2 {
+                        for j in 0..2 {
+                            assert_relative_eq!(
+                                a[i][j] as f64,
+                                target[i][j] as f64,
+                                epsilon = f64::EPSILON
+                            );
+                        }
+                    }
+                }
+            }
+        };
+    }
+
+    make_test!(i8);
+    make_test!(u8);
+    make_test!(i16);
+    make_test!(u16);
+    make_test!(i32);
+    make_test!(u32);
+    make_test!(i64);
+    make_test!(u64);
+    make_test!(f32);
+    make_test!(f64);
+}
@@ -5,6 +5,7 @@
 // http://opensource.org/licenses/MIT>, at your option. This file may not be
 // copied, modified, or distributed except according to those terms.
 
+use super::kernels::Kernels;
 use crate::ArgminSub;
 use num_complex::Complex;
 
@@ -31,7 +32,7 @@ macro_rules! make_sub {
                 let n2 = other.len();
                 assert!(n1 > 0);
                 assert_eq!(n1, n2);
-                self.iter().zip(other.iter()).map(|(a, b)| a - b).collect()
+                <$t as Kernels>::sub(self, other)
             }
         }
 
@@ -93,6 +94,33 @@ make_sub!(Complex<u64>);
 make_sub!(Complex<f32>);
 make_sub!(Complex<f64>);
 
+// A complex vector is treated as the real vector of its real and imaginary parts, which is
+// consistent with `ArgminSignum` and `ArgminL1Norm`. Therefore a real scalar is subtracted from
+// both parts.
+macro_rules! make_sub_real {
+    ($t:ty) => {
+        impl ArgminSub<$t, Vec<Complex<$t>>> for Vec<Complex<$t>> {
+            #[inline]
+            fn sub(&self, other: &$t) -> Vec<Complex<$t>> {
+                self.iter()
+                    .map(|a| Complex::new(a.re - other, a.im - other))
+                    .collect()
+            }
+        }
+    };
+}
+
+make_sub_real!(i8);
+make_sub_real!(i16);
+make_sub_real!(i32);
+make_sub_real!(i64);
+make_sub_real!(u8);
+make_sub_real!(u16);
+make_sub_real!(u32);
+make_sub_real!(u64);
+make_sub_real!(f32);
+make_sub_real!(f64);
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -132,6 +160,28 @@ mod tests {
                 }
             }
 
+            item! {
+                #[test]
+                fn [<test_sub_vec_real_scalar_complex_ $t>]() {
+                    let a = vec![
+                        Complex::new(100 as $t, 40 as $t),
+                        Complex::new(76 as $t, 42 as $t),
+                        Complex::new(44 as $t, 35 as $t),
+                    ];
+                    let b = 34 as $t;
+                    let target = vec![
+                        Complex::new(66 as $t, 6 as $t),
+                        Complex::new(42 as $t, 8 as $t),
+                        Complex::new(10 as $t, 1 as $t),
+                    ];
+                    let res = <Vec<Complex<$t>> as ArgminSub<$t, Vec<Complex<$t>>>>::sub(&a, &b);
+                    for i in 0..3 {
+                        assert_relative_eq!(target[i].re as f64, res[i].re as f64, epsilon = f64::EPSILON);
+                        assert_relative_eq!(target[i].im as f64, res[i].im as f64, epsilon = f64::EPSILON);
+                    }
+                }
+            }
+
             item! {
                 #[test]
                 fn [<test_sub_scalar_vec_ $t>]() {
@@ -0,0 +1,621 @@
+// Copyright 2018-2024 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+//! Writes the optimization trace to an [HDF5](https://www.hdfgroup.org/solutions/hdf5/) file.
+//!
+//! See documentation of [`Hdf5Writer`] for details.
+//!
+//! # Usage
+//!
+//! Add the following line to your dependencies list:
+//!
+//! ```toml
+//! [dependencies]
+#![doc = concat!("argmin-observer-hdf5 = \"", env!("CARGO_PKG_VERSION"), "\"")]
+//! ```
+//!
+//! This crate links against the HDF5 library (version 1.8.4 or later), which needs to be
+//! installed on the system (for instance via the `libhdf5-dev` package on Debian and Ubuntu).
+//!
+//! # License
+//!
+//! Licensed under either of
+//!
+//!   * Apache License, Version 2.0,
+//!     ([LICENSE-APACHE](https://github.com/argmin-rs/argmin/blob/main/LICENSE-APACHE) or
+//!     <http://www.apache.org/licenses/LICENSE-2.0>)
+//!   * MIT License ([LICENSE-MIT](https://github.com/argmin-rs/argmin/blob/main/LICENSE-MIT) or
+//!     <http://opensource.org/licenses/MIT>)
+//!
+//! at your option.
+//!
+//! ## Contribution
+//!
+//! Unless you explicitly state otherwise, any contribution intentionally submitted for inclusion
+//! in the work by you, as defined in the Apache-2.0 license, shall be dual licensed as above,
+//! without any additional terms or conditions.
+
+use argmin::core::observers::Observe;
+use argmin::core::{ArgminFloat, Error, State, KV};
+use hdf5::types::VarLenUnicode;
+use hdf5::{Dataset, File, H5Type, SimpleExtents};
+use ndarray::ArrayView2;
+use std::path::PathBuf;
+
+/// Name of the attribute of the root group which holds the name of the solver
+pub const SOLVER_ATTRIBUTE: &str = "solver";
+
+/// Write the optimization trace to an HDF5 file.
+///
+/// Each quantity is stored in its own dataset in the root group of the file. Each call to
+/// `observe_iter` appends one element to each of the following datasets:
+///
+/// * `iter` (`u64`): iteration number
+/// * `cost` (`f64`): current cost function value
+/// * `best_cost` (`f64`): best cost function value so far
+/// * `time` (`f64`): elapsed time in seconds (NaN if time measurement is disabled)
+/// * one `u64` dataset per function evaluation counter (e.g. `cost_count`)
+/// * one `f64` dataset per selected KV key (see [`Hdf5Writer::kv_keys`]). Integer and boolean
+///   values are converted to floats. The value is NaN if the key is missing in an iteration or
+///   if it holds a string.
+/// * `param` (`f64`, two-dimensional): the current parameter vector as one row per iteration,
+///   only if enabled via [`Hdf5Writer::param`]. Iterations without a parameter vector are
+///   filled with NaN.
+///
+/// The datasets are created in the first observed iteration, therefore function evaluation
+/// counters which only appear later in the run are not recorded and the length of the parameter
+/// vector must not change during the run. The name of the solver is stored in the attribute
+/// [`SOLVER_ATTRIBUTE`] of the root group.
+///
+/// All datasets are resizable, chunked along the iterations and compressed with deflate (gzip).
+/// Rows are buffered in memory and appended to the datasets once a full chunk
+/// (see [`chunk_size`](`Hdf5Writer::chunk_size`)) is collected as well as in `observe_final` (or
+/// when the observer is dropped). In contrast to Parquet files, the file can be read while the
+/// optimization is still running.
+///
+/// # Example
+///
+/// ```
+/// use argmin_observer_hdf5::Hdf5Writer;
+///
+/// let observer = Hdf5Writer::new("trace.h5")
+///     .kv_keys(["gamma"])
+///     .param(true)
+///     .chunk_size(4096)
+///     .compression(6);
+/// ```
+pub struct Hdf5Writer {
+    /// Path of the output file
+    path: PathBuf,
+    /// KV entries which are written to the file
+    kv_keys: Vec<String>,
+    /// Whether the parameter vector is written to the file
+    write_param: bool,
+    /// Number of rows per chunk
+    chunk_size: usize,
+    /// Deflate compression level (0 disables compression)
+    compression: u8,
+    /// Name of the solver
+    solver: Option<String>,
+    /// File and dataset buffers, created in the first observed iteration
+    inner: Option<Inner>,
+}
+
+impl Hdf5Writer {
+    /// Create a new instance of `Hdf5Writer` which writes to the file at `path`.
+    ///
+    /// By default, no KV entries and no parameter vectors are written, chunks contain 1024 rows
+    /// and the compression level is 4. An existing file at `path` will be overwritten.
+    ///
+    /// # Example
+    /// ```
+    /// # use argmin_observer_hdf5::Hdf5Writer;
+    /// let observer = Hdf5Writer::new("trace.h5");
+    /// ```
+    pub fn new<P: Into<PathBuf>>(path: P) -> Self {
+        Hdf5Writer {
+            path: path.into(),
+            kv_keys: vec![],
+            write_param: false,
+            chunk_size: 1024,
+            compression: 4,
+            solver: None,
+            inner: None,
+        }
+    }
+
+    /// Select the KV entries which are written to the file.
+    ///
+    /// # Example
+    /// ```
+    /// # use argmin_observer_hdf5::Hdf5Writer;
+    /// let observer = Hdf5Writer::new("trace.h5").kv_keys(["t", "new_be"]);
+    /// ```
+    #[must_use]
+    pub fn kv_keys<K, S>(mut self, keys: K) -> Self
+    where
+        K: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        self.kv_keys = keys.into_iter().map(|k| k.as_ref().to_string()).collect();
+        self
+    }
+
+    /// Enable or disable writing the parameter vector (default: disabled).
+    ///
+    /// # Example
+    /// ```
+    /// # use argmin_observer_hdf5::Hdf5Writer;
+    /// let observer = Hdf5Writer::new("trace.h5").param(true);
+    /// ```
+    #[must_use]
+    pub fn param(mut self, write_param: bool) -> Self {
+        self.write_param = write_param;
+        self
+    }
+
+    /// Set the number of rows per chunk (default: 1024). This is also the number of rows which
+    /// are buffered before they are written to the file.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `rows` is 0.
+    ///
+    /// # Example
+    /// ```
+    /// # use argmin_observer_hdf5::Hdf5Writer;
+    /// let observer = Hdf5Writer::new("trace.h5").chunk_size(4096);
+    /// ```
+    #[must_use]
+    pub fn chunk_size(mut self, rows: usize) -> Self {
+        assert!(rows > 0, "chunk size must be larger than 0");
+        self.chunk_size = rows;
+        self
+    }
+
+    /// Set the deflate compression level between 0 (no compression) and 9 (default: 4).
+    ///
+    /// # Panics
+    ///
+    /// Panics if `level` is larger than 9.
+    ///
+    /// # Example
+    /// ```
+    /// # use argmin_observer_hdf5::Hdf5Writer;
+    /// let observer = Hdf5Writer::new("trace.h5").compression(9);
+    /// ```
+    #[must_use]
+    pub fn compression(mut self, level: u8) -> Self {
+        assert!(level <= 9, "compression level must be between 0 and 9");
+        self.compression = level;
+        self
+    }
+
+    /// Create the file and the datasets based on the state of the first iteration
+    fn create<I>(&self, state: &I) -> Result<Inner, Error>
+    where
+        I: State,
+        I::Param: IntoIterator<Item = I::Float> + Clone,
+    {
+        let mut counts: Vec<String> = state.get_func_counts().keys().cloned().collect();
+        counts.sort();
+
+        let mut names: Vec<&str> = vec!["iter", "cost", "best_cost", "time"];
+        names.extend(counts.iter().map(|c| c.as_str()));
+        names.extend(self.kv_keys.iter().map(|k| k.as_str()));
+        if self.write_param {
+            names.push("param");
+        }
+        for (i, name) in names.iter().enumerate() {
+            if names[..i].contains(name) {
+                return Err(argmin::argmin_error!(
+                    InvalidParameter,
+                    format!(
+                        "`Hdf5Writer`: dataset `{}` is not unique; KV keys must not clash with \
+                         other datasets.",
+                        name
+                    )
+                ));
+            }
+        }
+
+        if let Some(dir) = self.path.parent() {
+            if !dir.as_os_str().is_empty() && !dir.exists() {
+                std::fs::create_dir_all(dir)?
+            }
+        }
+        let file = File::create(&self.path)?;
+        if let Some(solver) = self.solver.as_ref() {
+            let solver: VarLenUnicode = solver.parse()?;
+            file.new_attr::<VarLenUnicode>()
+                .create(SOLVER_ATTRIBUTE)?
+                .write_scalar(&solver)?;
+        }
+
+        let param = if self.write_param {
+            let dim = state
+                .get_param()
+                .map(|p| p.clone().into_iter().count())
+                .ok_or_else(argmin::argmin_error_closure!(
+                    NotInitialized,
+                    "`Hdf5Writer`: parameter vector must be available in the first observed \
+                     iteration."
+                ))?;
+            Some(ParamDataset {
+                dataset: self.dataset::<f64>(&file, "param", Some(dim))?,
+                dim,
+                buffer: vec![],
+            })
+        } else {
+            None
+        };
+
+        Ok(Inner {
+            iter: Column::new(self.dataset::<u64>(&file, "iter", None)?),
+            cost: Column::new(self.dataset::<f64>(&file, "cost", None)?),
+            best_cost: Column::new(self.dataset::<f64>(&file, "best_cost", None)?),
+            time: Column::new(self.dataset::<f64>(&file, "time", None)?),
+            count_cols: counts
+                .into_iter()
+                .map(|name| Ok((Column::new(self.dataset::<u64>(&file, &name, None)?), name)))
+                .collect::<Result<_, Error>>()?,
+            kv_cols: self
+                .kv_keys
+                .iter()
+                .map(|key| {
+                    Ok((
+                        Column::new(self.dataset::<f64>(&file, key, None)?),
+                        key.clone(),
+                    ))
+                })
+                .collect::<Result<_, Error>>()?,
+            param,
+            file,
+            rows: 0,
+            offset: 0,
+        })
+    }
+
+    /// Create an empty, resizable, chunked and compressed dataset. If `dim` is given, the
+    /// dataset is two-dimensional with `dim` columns.
+    fn dataset<T: H5Type>(
+        &self,
+        file: &File,
+        name: &str,
+        dim: Option<usize>,
+    ) -> Result<Dataset, Error> {
+        let builder = file.new_dataset::<T>();
+        let builder = match dim {
+            Some(dim) => builder
+                .chunk((self.chunk_size, dim.max(1)))
+                .shape(SimpleExtents::resizable([0, dim])),
+            None => builder
+                .chunk(self.chunk_size)
+                .shape(SimpleExtents::resizable([0])),
+        };
+        let builder = if self.compression > 0 {
+            builder.deflate(self.compression)
+        } else {
+            builder
+        };
+        Ok(builder.create(name)?)
+    }
+
+    /// Write buffered rows and flush the file
+    fn finish(&mut self) -> Result<(), Error> {
+        if let Some(mut inner) = self.inner.take() {
+            inner.flush()?;
+        }
+        Ok(())
+    }
+}
+
+/// One-dimensional dataset and buffered values
+struct Column<T> {
+    dataset: Dataset,
+    buffer: Vec<T>,
+}
+
+impl<T: H5Type> Column<T> {
+    fn new(dataset: Dataset) -> Self {
+        Column {
+            dataset,
+            buffer: vec![],
+        }
+    }
+
+    /// Append the buffered values to the dataset, starting at row `offset`
+    fn flush(&mut self, offset: usize) -> Result<(), Error> {
+        let end = offset + self.buffer.len();
+        self.dataset.resize(end)?;
+        self.dataset
+            .write_slice(self.buffer.as_slice(), offset..end)?;
+        self.buffer.clear();
+        Ok(())
+    }
+}
+
+/// Two-dimensional dataset of parameter vectors and buffered rows
+struct ParamDataset {
+    dataset: Dataset,
+    dim: usize,
+    buffer: Vec<f64>,
+}
+
+impl ParamDataset {
+    /// Append the buffered rows to the dataset, starting at row `offset`
+    fn flush(&mut self, offset: usize, rows: usize) -> Result<(), Error> {
+        let end = offset + rows;
+        self.dataset.resize((end, self.dim))?;
+        let rows = ArrayView2::from_shape((rows, self.dim), &self.buffer)?;
+        self.dataset.write_slice(rows, (offset..end, ..))?;
+        self.buffer.clear();
+        Ok(())
+    }
+}
+
+/// HDF5 file and buffers of all datasets
+struct Inner {
+    file: File,
+    iter: Column<u64>,
+    cost: Column<f64>,
+    best_cost: Column<f64>,
+    time: Column<f64>,
+    count_cols: Vec<(Column<u64>, String)>,
+    kv_cols: Vec<(Column<f64>, String)>,
+    param: Option<ParamDataset>,
+    /// Number of buffered rows
+    rows: usize,
+    /// Number of rows already written to the file
+    offset: usize,
+}
+
+impl Inner {
+    /// Append buffered rows to the datasets
+    fn flush(&mut self) -> Result<(), Error> {
+        if self.rows == 0 {
+            return Ok(());
+        }
+        self.iter.flush(self.offset)?;
+        self.cost.flush(self.offset)?;
+        self.best_cost.flush(self.offset)?;
+        self.time.flush(self.offset)?;
+        for (col, _) in self.count_cols.iter_mut() {
+            col.flush(self.offset)?;
+        }
+        for (col, _) in self.kv_cols.iter_mut() {
+            col.flush(self.offset)?;
+        }
+        if let Some(param) = self.param.as_mut() {
+            param.flush(self.offset, self.rows)?;
+        }
+        self.file.flush()?;
+        self.offset += self.rows;
+        self.rows = 0;
+        Ok(())
+    }
+}
+
+impl<I> Observe<I> for Hdf5Writer
+where
+    I: State,
+    I::Param: IntoIterator<Item = I::Float> + Clone,
+    I::Float: ArgminFloat,
+    f64: From<I::Float>,
+{
+    fn observe_init(&mut self, name: &str, _state: &I, _kv: &KV) -> Result<(), Error> {
+        self.solver = Some(name.to_string());
+        Ok(())
+    }
+
+    fn observe_iter(&mut self, state: &I, kv: &KV) -> Result<(), Error> {
+        if self.inner.is_none() {
+            self.inner = Some(self.create(state)?);
+        }
+        let inner = self.inner.as_mut().unwrap();
+
+        if let Some(param) = inner.param.as_mut() {
+            let len = param.buffer.len();
+            match state.get_param() {
+                Some(p) => param.buffer.extend(p.clone().into_iter().map(f64::from)),
+                None => param.buffer.resize(len + param.dim, f64::NAN),
+            }
+            if param.buffer.len() - len != param.dim {
+                let found = param.buffer.len() - len;
+                param.buffer.truncate(len);
+                return Err(argmin::argmin_error!(
+                    InvalidParameter,
+                    format!(
+                        "`Hdf5Writer`: length of parameter vector changed from {} to {}.",
+                        param.dim, found
+                    )
+                ));
+            }
+        }
+        inner.iter.buffer.push(state.get_iter());
+        inner.cost.buffer.push(f64::from(state.get_cost()));
+        inner
+            .best_cost
+            .buffer
+            .push(f64::from(state.get_best_cost()));
+        inner.time.buffer.push(
+            state
+                .get_time()
+                .map(|t| t.as_secs_f64())
+                .unwrap_or(f64::NAN),
+        );
+        let counts = state.get_func_counts();
+        for (col, name) in inner.count_cols.iter_mut() {
+            col.buffer.push(counts.get(name).copied().unwrap_or(0));
+        }
+        for (col, key) in inner.kv_cols.iter_mut() {
+            col.buffer
+                .push(kv.get(key).and_then(|v| v.get_float()).unwrap_or(f64::NAN));
+        }
+        inner.rows += 1;
+
+        if inner.rows >= self.chunk_size {
+            inner.flush()?;
+        }
+        Ok(())
+    }
+
+    fn observe_final(&mut self, _state: &I) -> Result<(), Error> {
+        self.finish()
+    }
+}
+
+/// Writes buffered rows in case `observe_final` was not called. Errors are ignored.
+impl Drop for Hdf5Writer {
+    fn drop(&mut self) {
+        let _ = self.finish();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use argmin::core::{IterState, KvValue};
+    use std::time::Duration;
+
+    type TestState = IterState<Vec<f64>, (), (), (), (), f64>;
+
+    fn test_file(name: &str) -> PathBuf {
+        std::env::temp_dir().join(format!(
+            "argmin_observer_hdf5_{}_{}.h5",
+            name,
+            std::process::id()
+        ))
+    }
+
+    fn run(observer: &mut Hdf5Writer, iters: u64) {
+        let mut state = TestState::new();
+        Observe::<TestState>::observe_init(observer, "TestSolver", &state, &KV::new()).unwrap();
+        for i in 0..iters {
+            state = state
+                .param(vec![i as f64, -(i as f64)])
+                .cost(10.0 - i as f64);
+            state.best_cost = state.cost;
+            state.iter = i + 1;
+            state.time = Some(Duration::from_millis(500 * (i + 1)));
+            state.counts.insert("cost_count".to_string(), 2 * (i + 1));
+            let mut kv = KV::new();
+            if i % 2 == 0 {
+                kv.insert("gamma", KvValue::Float(0.5 * i as f64));
+            }
+            kv.insert("name", KvValue::Str("not a number".to_string()));
+            observer.observe_iter(&state, &kv).unwrap();
+        }
+        Observe::<TestState>::observe_final(observer, &state).unwrap();
+    }
+
+    #[test]
+    fn test_write_and_read() {
+        let path = test_file("write_and_read");
+        let mut observer = Hdf5Writer::new(&path)
+            .kv_keys(["gamma", "name"])
+            .param(true)
+            .chunk_size(3);
+        run(&mut observer, 7);
+
+        let file = File::open(&path).unwrap();
+        let solver: VarLenUnicode = file.attr(SOLVER_ATTRIBUTE).unwrap().read_scalar().unwrap();
+        assert_eq!(solver.as_str(), "TestSolver");
+
+        let mut names = file.member_names().unwrap();
+        names.sort();
+        assert_eq!(
+            names,
+            [
+                "best_cost",
+                "cost",
+                "cost_count",
+                "gamma",
+                "iter",
+                "name",
+                "param",
+                "time"
+            ]
+        );
+
+        let iter = file.dataset("iter").unwrap().read_raw::<u64>().unwrap();
+        assert_eq!(iter, vec![1, 2, 3, 4, 5, 6, 7]);
+        let cost = file.dataset("cost").unwrap().read_raw::<f64>().unwrap();
+        assert_eq!(cost[6].to_ne_bytes(), 4.0f64.to_ne_bytes());
+        let time = file.dataset("time").unwrap().read_raw::<f64>().unwrap();
+        assert_eq!(time[1].to_ne_bytes(), 1.0f64.to_ne_bytes());
+        let counts = file
+            .dataset("cost_count")
+            .unwrap()
+            .read_raw::<u64>()
+            .unwrap();
+        assert_eq!(counts[3], 8);
+        let gamma = file.dataset("gamma").unwrap().read_raw::<f64>().unwrap();
+        assert_eq!(gamma[2].to_ne_bytes(), 1.0f64.to_ne_bytes());
+        assert!(gamma[1].is_nan());
+        let name = file.dataset("name").unwrap().read_raw::<f64>().unwrap();
+        assert!(name.iter().all(|x| x.is_nan()));
+
+        let param = file.dataset("param").unwrap();
+        assert_eq!(param.shape(), vec![7, 2]);
+        assert_eq!(param.chunk(), Some(vec![3, 2]));
+        let param = param.read_2d::<f64>().unwrap();
+        assert_eq!(param.row(4).to_vec(), vec![4.0, -4.0]);
+
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn test_flushed_on_drop() {
+        let path = test_file("flushed_on_drop");
+        {
+            let mut observer = Hdf5Writer::new(&path).compression(0);
+            let state = TestState::new().cost(1.0);
+            Observe::<TestState>::observe_iter(&mut observer, &state, &KV::new()).unwrap();
+        }
+        let file = File::open(&path).unwrap();
+        assert_eq!(file.member_names().unwrap().len(), 4);
+        let cost = file.dataset("cost").unwrap().read_raw::<f64>().unwrap();
+        assert_eq!(cost.len(), 1);
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn test_param_length_changed() {
+        let path = test_file("param_length_changed");
+        let mut observer = Hdf5Writer::new(&path).param(true);
+        let state = TestState::new().param(vec![1.0, 2.0]);
+        Observe::<TestState>::observe_iter(&mut observer, &state, &KV::new()).unwrap();
+        let state = state.param(vec![1.0, 2.0, 3.0]);
+        let res = Observe::<TestState>::observe_iter(&mut observer, &state, &KV::new());
+        assert!(res.is_err());
+        drop(observer);
+        let _ = std::fs::remove_file(path);
+    }
+
+    #[test]
+    fn test_clashing_datasets() {
+        let path = test_file("clashing_datasets");
+        let mut observer = Hdf5Writer::new(&path).kv_keys(["cost"]);
+        let state = TestState::new();
+        let res = Observe::<TestState>::observe_iter(&mut observer, &state, &KV::new());
+        assert!(res.is_err());
+        let _ = std::fs::remove_file(path);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_chunk_size_zero() {
+        let _ = Hdf5Writer::new("trace.h5").chunk_size(0);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_compression_level_too_large() {
+        let _ = Hdf5Writer::new("trace.h5").compression(10);
+    }
+}
@@ -36,20 +36,28 @@
 //! in the work by you, as defined in the Apache-2.0 license, shall be dual licensed as above,
 //! without any additional terms or conditions.
 
+mod npy;
+
 use argmin::core::observers::Observe;
 use argmin::core::{Error, State, KV};
+use npy::{append_to_zip, Array, ZipEntry};
 use serde::Serialize;
+use std::collections::VecDeque;
 use std::default::Default;
-use std::fs::File;
-use std::io::BufWriter;
+use std::fs::{File, OpenOptions};
+use std::io::{BufWriter, Write};
 use std::path::PathBuf;
 
 /// Write parameter vectors to a file during optimization.
 ///
-/// This observer requires a directory to save the files to and a file prefix. Files will be
-/// written to disk as `<directory>/<file_prefix>_<iteration_number>.<extension>`. For
-/// serialization either `JSON` or `Binary` (via [bincode](https://crates.io/crates/bincode))
-/// can be chosen via the enum [`ParamWriterFormat`].
+/// This observer requires a directory to save the files to and a file prefix. By default, files
+/// will be written to disk as `<directory>/<file_prefix>_<iteration_number>.<extension>`. For
+/// serialization either `JSON`, `Binary` (via [bincode](https://crates.io/crates/bincode)) or
+/// `Npy` (NumPy arrays) can be chosen via the enum [`ParamWriterFormat`].
+///
+/// Since writing one file per observed iteration can produce a large number of files, only the
+/// files of the last `n` observed iterations can be kept or all parameter vectors can be appended
+/// to a single file instead (see [`ParamWriterMode`]).
 ///
 /// # Example
 ///
@@ -69,6 +77,16 @@ use std::path::PathBuf;
 ///
 /// let observer = ParamWriter::new("directory", "file_prefix", ParamWriterFormat::Binary);
 /// ```
+///
+/// Create an observer which appends all parameter vectors to the file
+/// `directory/file_prefix.ndjson`, one JSON object per line.
+///
+/// ```
+/// use argmin_observer_paramwriter::{ParamWriter, ParamWriterFormat, ParamWriterMode};
+///
+/// let observer = ParamWriter::new("directory", "file_prefix", ParamWriterFormat::JSON)
+///     .mode(ParamWriterMode::Append);
+/// ```
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct ParamWriter {
     /// Directory where files are saved to
@@ -77,6 +95,12 @@ pub struct ParamWriter {
     prefix: String,
     /// Chosen serializer
     serializer: ParamWriterFormat,
+    /// How files are written
+    mode: ParamWriterMode,
+    /// Files written in `KeepLast` mode, oldest first
+    written: VecDeque<PathBuf>,
+    /// Entries of the `.npz` file in `Append` mode
+    npz_entries: Vec<ZipEntry>,
 }
 
 impl ParamWriter {
@@ -92,18 +116,170 @@ impl ParamWriter {
             dir: PathBuf::from(dir.as_ref()),
             prefix: String::from(prefix.as_ref()),
             serializer,
+            mode: ParamWriterMode::default(),
+            written: VecDeque::new(),
+            npz_entries: vec![],
+        }
+    }
+
+    /// Set how the parameter vectors are written to disk (default:
+    /// [`ParamWriterMode::PerIteration`]).
+    ///
+    /// # Panics
+    ///
+    /// Panics if the mode is `ParamWriterMode::KeepLast(0)`.
+    ///
+    /// # Example
+    /// ```
+    /// # use argmin_observer_paramwriter::{ParamWriter, ParamWriterFormat, ParamWriterMode};
+    /// let observer = ParamWriter::new("directory", "file_prefix", ParamWriterFormat::JSON)
+    ///     .mode(ParamWriterMode::KeepLast(10));
+    /// ```
+    #[must_use]
+    pub fn mode(mut self, mode: ParamWriterMode) -> Self {
+        assert!(
+            mode != ParamWriterMode::KeepLast(0),
+            "number of files to keep must be larger than 0"
+        );
+        self.mode = mode;
+        self
+    }
+
+    /// Path of the file all parameter vectors are appended to in `Append` mode
+    fn stream_path(&self) -> PathBuf {
+        self.dir.join(format!(
+            "{}.{}",
+            self.prefix,
+            self.serializer.stream_extension()
+        ))
+    }
+
+    /// Writes `param` of iteration `iter` into its own file
+    fn write_file<P: Serialize>(&mut self, iter: u64, param: &P) -> Result<(), Error> {
+        let fname = self.dir.join(format!(
+            "{}_{}.{}",
+            self.prefix,
+            iter,
+            self.serializer.extension()
+        ));
+        let f = BufWriter::new(File::create(&fname)?);
+
+        match self.serializer {
+            ParamWriterFormat::Binary => {
+                bincode::serialize_into(f, param)?;
+            }
+            ParamWriterFormat::JSON => {
+                serde_json::to_writer_pretty(f, param)?;
+            }
+            ParamWriterFormat::Npy => {
+                let mut f = f;
+                f.write_all(&Array::from_param(param)?.to_npy())?;
+                f.flush()?;
+            }
         }
+
+        if let ParamWriterMode::KeepLast(n) = self.mode {
+            self.written.retain(|f| *f != fname);
+            self.written.push_back(fname);
+            while self.written.len() > n {
+                if let Some(old) = self.written.pop_front() {
+                    match std::fs::remove_file(old) {
+                        Err(e) if e.kind() != std::io::ErrorKind::NotFound => return Err(e.into()),
+                        _ => {}
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Appends `param` of iteration `iter` to the stream file
+    fn append<P: Serialize>(&mut self, iter: u64, param: &P) -> Result<(), Error> {
+        if self.serializer == ParamWriterFormat::Npy {
+            let name = format!("{}_{}.npy", self.prefix, iter);
+            let data = Array::from_param(param)?.to_npy();
+            return append_to_zip(&self.stream_path(), &mut self.npz_entries, &name, &data);
+        }
+
+        let mut f = BufWriter::new(
+            OpenOptions::new()
+                .create(true)
+                .append(true)
+                .open(self.stream_path())?,
+        );
+        let record = Record { iter, param };
+
+        match self.serializer {
+            ParamWriterFormat::Binary => {
+                bincode::serialize_into(&mut f, &record)?;
+            }
+            ParamWriterFormat::JSON => {
+                serde_json::to_writer(&mut f, &record)?;
+                writeln!(f)?;
+            }
+            ParamWriterFormat::Npy => unreachable!(),
+        }
+        f.flush()?;
+        Ok(())
     }
 }
 
-/// `ParamWriter` only implements `observer_iter` and not `observe_init` to avoid saving the
-/// initial parameter vector. It will only save if there is a parameter vector available in the
-/// state, otherwise it will skip saving silently.
+/// Entry of the stream file in `Append` mode
+#[derive(Serialize)]
+struct Record<'a, P> {
+    /// Iteration number
+    iter: u64,
+    /// Parameter vector
+    param: &'a P,
+}
+
+/// Defines how [`ParamWriter`] writes parameter vectors to disk.
+///
+/// # Example
+///
+/// ```
+/// use argmin_observer_paramwriter::ParamWriterMode;
+///
+/// let per_iteration = ParamWriterMode::PerIteration;
+/// let keep_last = ParamWriterMode::KeepLast(10);
+/// let append = ParamWriterMode::Append;
+/// ```
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Default)]
+pub enum ParamWriterMode {
+    /// Write one file per observed iteration
+    #[default]
+    PerIteration,
+    /// Write one file per observed iteration, but only keep the files of the last `n` observed
+    /// iterations. Older files are deleted.
+    KeepLast(usize),
+    /// Append all parameter vectors to the single file `<directory>/<file_prefix>.<extension>`,
+    /// where the extension is `ndjson` for JSON, `bin` for binary and `npz` for NumPy files.
+    /// Each JSON and binary entry holds the iteration number (`iter`) and the parameter vector
+    /// (`param`). JSON entries are written one per line, binary entries are written
+    /// consecutively. NumPy arrays are named after the iteration (see [`ParamWriterFormat`]). The
+    /// file is truncated at the start of the optimization.
+    Append,
+}
+
+/// `ParamWriter` does not save the initial parameter vector in `observe_init`; in `Append` mode,
+/// it only truncates the stream file. It will only save if there is a parameter vector available
+/// in the state, otherwise it will skip saving silently.
 impl<I> Observe<I> for ParamWriter
 where
     I: State,
     <I as State>::Param: Serialize,
 {
+    fn observe_init(&mut self, _name: &str, _state: &I, _kv: &KV) -> Result<(), Error> {
+        if self.mode == ParamWriterMode::Append {
+            if !self.dir.exists() {
+                std::fs::create_dir_all(&self.dir)?
+            }
+            File::create(self.stream_path())?;
+            self.npz_entries.clear();
+        }
+        Ok(())
+    }
+
     fn observe_iter(&mut self, state: &I, _kv: &KV) -> Result<(), Error> {
         if let Some(param) = state.get_param() {
             let iter = state.get_iter();
@@ -111,20 +287,10 @@ where
                 std::fs::create_dir_all(&self.dir)?
             }
 
-            let fname = self.dir.join(format!(
-                "{}_{}.{}",
-                self.prefix,
-                iter,
-                self.serializer.extension()
-            ));
-            let f = BufWriter::new(File::create(fname)?);
-
-            match self.serializer {
-                ParamWriterFormat::Binary => {
-                    bincode::serialize_into(f, param)?;
-                }
-                ParamWriterFormat::JSON => {
-                    serde_json::to_writer_pretty(f, param)?;
+            match self.mode {
+                ParamWriterMode::Append => self.append(iter, param)?,
+                ParamWriterMode::PerIteration | ParamWriterMode::KeepLast(_) => {
+                    self.write_file(iter, param)?
                 }
             }
         }
@@ -136,8 +302,18 @@ where
 ///
 /// # Extensions
 ///
-/// * JSON: `.json`
+/// * JSON: `.json` (`.ndjson` in [`ParamWriterMode::Append`] mode)
 /// * Binary: `.bin`
+/// * Npy: `.npy` (`.npz` in [`ParamWriterMode::Append`] mode)
+///
+/// # NumPy format
+///
+/// With `Npy`, each parameter vector is stored as NumPy array of `f64` which can be loaded via
+/// `numpy.load`. Supported are parameter vectors which serialize to numbers or (nested) sequences
+/// of equal length, such as `f64`, `Vec<f64>` and `Vec<Vec<f64>>`, as well as `ndarray` arrays
+/// (which requires the `serde` feature of `ndarray`). All elements are converted to `f64`. In
+/// [`ParamWriterMode::Append`] mode, all arrays are stored in a single uncompressed `.npz` archive
+/// with one array named `<file_prefix>_<iteration_number>` per observed iteration.
 ///
 /// # Example
 ///
@@ -146,6 +322,7 @@ where
 ///
 /// let bincode = ParamWriterFormat::Binary;
 /// let json = ParamWriterFormat::JSON;
+/// let npy = ParamWriterFormat::Npy;
 /// ```
 #[derive(Copy, Clone, Debug, Eq, PartialEq, Default)]
 pub enum ParamWriterFormat {
@@ -154,6 +331,8 @@ pub enum ParamWriterFormat {
     Binary,
     /// Use [`serde_json`](https://crates.io/crates/serde_json) for creating JSON files
     JSON,
+    /// Create NumPy `.npy` files (or `.npz` archives in [`ParamWriterMode::Append`] mode)
+    Npy,
 }
 
 impl ParamWriterFormat {
@@ -161,9 +340,187 @@ impl ParamWriterFormat {
         match *self {
             ParamWriterFormat::Binary => "bin",
             ParamWriterFormat::JSON => "json",
+            ParamWriterFormat::Npy => "npy",
+        }
+    }
+
+    /// Extension of the stream file in `Append` mode
+    fn stream_extension(&self) -> &str {
+        match *self {
+            ParamWriterFormat::Binary => "bin",
+            ParamWriterFormat::JSON => "ndjson",
+            ParamWriterFormat::Npy => "npz",
         }
     }
 }
 
 #[cfg(test)]
-mod tests {}
+mod tests {
+    use super::*;
+    use argmin::core::IterState;
+    use serde::Deserialize;
+
+    type TestState = IterState<Vec<f64>, (), (), (), (), f64>;
+
+    #[derive(Debug, Deserialize, PartialEq)]
+    struct OwnedRecord {
+        iter: u64,
+        param: Vec<f64>,
+    }
+
+    fn test_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!(
+            "argmin_observer_paramwriter_{}_{}",
+            name,
+            std::process::id()
+        ));
+        let _ = std::fs::remove_dir_all(&dir);
+        dir
+    }
+
+    fn run(observer: &mut ParamWriter, iters: u64) {
+        let mut state = TestState::new();
+        observer
+            .observe_init("TestSolver", &state, &KV::new())
+            .unwrap();
+        for i in 0..iters {
+            state = state.param(vec![i as f64, 2.0 * i as f64]);
+            state.iter = i;
+            observer.observe_iter(&state, &KV::new()).unwrap();
+        }
+    }
+
+    fn files(dir: &PathBuf) -> Vec<String> {
+        let mut files: Vec<String> = std::fs::read_dir(dir)
+            .unwrap()
+            .map(|e| e.unwrap().file_name().into_string().unwrap())
+            .collect();
+        files.sort();
+        files
+    }
+
+    #[test]
+    fn test_per_iteration() {
+        let dir = test_dir("per_iteration");
+        let mut observer = ParamWriter::new(dir.to_str().unwrap(), "p", ParamWriterFormat::JSON);
+        run(&mut observer, 3);
+        assert_eq!(files(&dir), vec!["p_0.json", "p_1.json", "p_2.json"]);
+        let param: Vec<f64> =
+            serde_json::from_reader(File::open(dir.join("p_2.json")).unwrap()).unwrap();
+        assert_eq!(param, vec![2.0, 4.0]);
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_keep_last() {
+        let dir = test_dir("keep_last");
+        let mut observer = ParamWriter::new(dir.to_str().unwrap(), "p", ParamWriterFormat::Binary)
+            .mode(ParamWriterMode::KeepLast(2));
+        run(&mut observer, 5);
+        assert_eq!(files(&dir), vec!["p_3.bin", "p_4.bin"]);
+        let param: Vec<f64> =
+            bincode::deserialize_from(File::open(dir.join("p_4.bin")).unwrap()).unwrap();
+        assert_eq!(param, vec![4.0, 8.0]);
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_keep_last_zero() {
+        let _ = ParamWriter::new("dir", "p", ParamWriterFormat::JSON)
+            .mode(ParamWriterMode::KeepLast(0));
+    }
+
+    #[test]
+    fn test_append_json() {
+        let dir = test_dir("append_json");
+        let mut observer = ParamWriter::new(dir.to_str().unwrap(), "p", ParamWriterFormat::JSON)
+            .mode(ParamWriterMode::Append);
+        // A second run truncates the file
+        run(&mut observer, 5);
+        run(&mut observer, 3);
+        assert_eq!(files(&dir), vec!["p.ndjson"]);
+        let content = std::fs::read_to_string(dir.join("p.ndjson")).unwrap();
+        let records: Vec<OwnedRecord> = content
+            .lines()
+            .map(|l| serde_json::from_str(l).unwrap())
+            .collect();
+        assert_eq!(records.len(), 3);
+        assert_eq!(
+            records[2],
+            OwnedRecord {
+                iter: 2,
+                param: vec![2.0, 4.0]
+            }
+        );
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_npy() {
+        let dir = test_dir("npy");
+        let mut observer = ParamWriter::new(dir.to_str().unwrap(), "p", ParamWriterFormat::Npy);
+        run(&mut observer, 2);
+        assert_eq!(files(&dir), vec!["p_0.npy", "p_1.npy"]);
+        let bytes = std::fs::read(dir.join("p_1.npy")).unwrap();
+        assert_eq!(bytes, Array::from_param(&vec![1.0, 2.0]).unwrap().to_npy());
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_append_npz() {
+        let dir = test_dir("append_npz");
+        let mut observer = ParamWriter::new(dir.to_str().unwrap(), "p", ParamWriterFormat::Npy)
+            .mode(ParamWriterMode::Append);
+        run(&mut observer, 4);
+        run(&mut observer, 3);
+        assert_eq!(files(&dir), vec!["p.npz"]);
+        let bytes = std::fs::read(dir.join("p.npz")).unwrap();
+
+        // Walk through the local file headers
+        let mut offset = 0;
+        for i in 0..3 {
+            let npy = Array::from_param(&vec![i as f64, 2.0 * i as f64])
+                .unwrap()
+                .to_npy();
+            let name = format!("p_{i}.npy");
+            assert_eq!(&bytes[offset..offset + 4], &0x04034b50u32.to_le_bytes());
+            let size = u32::from_le_bytes(bytes[offset + 22..offset + 26].try_into().unwrap());
+            assert_eq!(size as usize, npy.len());
+            let data = offset + 30 + name.len();
+            assert_eq!(&bytes[offset + 30..data], name.as_bytes());
+            assert_eq!(&bytes[data..data + npy.len()], &npy[..]);
+            offset = data + npy.len();
+        }
+
+        // End of central directory record lists all entries
+        let eocd = &bytes[bytes.len() - 22..];
+        assert_eq!(&eocd[..4], &0x06054b50u32.to_le_bytes());
+        assert_eq!(u16::from_le_bytes([eocd[10], eocd[11]]), 3);
+        let cd_offset = u32::from_le_bytes(eocd[16..20].try_into().unwrap());
+        assert_eq!(cd_offset as usize, offset);
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_append_binary() {
+        let dir = test_dir("append_binary");
+        let mut observer = ParamWriter::new(dir.to_str().unwrap(), "p", ParamWriterFormat::Binary)
+            .mode(ParamWriterMode::Append);
+        run(&mut observer, 3);
+        assert_eq!(files(&dir), vec!["p.bin"]);
+        let mut f = std::io::BufReader::new(File::open(dir.join("p.bin")).unwrap());
+        for i in 0..3 {
+            let record: OwnedRecord = bincode::deserialize_from(&mut f).unwrap();
+            assert_eq!(
+                record,
+                OwnedRecord {
+                    iter: i,
+                    param: vec![i as f64, 2.0 * i as f64]
+                }
+            );
+        }
+        assert!(bincode::deserialize_from::<_, OwnedRecord>(&mut f).is_err());
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,283 @@
+// Copyright 2018-2024 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+//! Minimal writers for the NumPy `.npy` and `.npz` formats.
+//!
+//! Parameter vectors are converted to arrays of `f64` via their serde representation. Numbers,
+//! (nested) sequences of equal length and `ndarray` arrays are supported.
+//!
+//! `.npz` files are written as uncompressed ZIP archives, which is also what `numpy.savez` does.
+
+use argmin::core::Error;
+use serde::Serialize;
+use serde_json::Value;
+use std::fs::OpenOptions;
+use std::io::{Seek, SeekFrom, Write};
+use std::path::Path;
+
+/// Array of `f64` in C order
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) struct Array {
+    /// Shape of the array
+    pub shape: Vec<usize>,
+    /// Elements in C order
+    pub data: Vec<f64>,
+}
+
+impl Array {
+    /// Converts a parameter vector into an array via its serde representation
+    pub fn from_param<P: Serialize>(param: &P) -> Result<Self, Error> {
+        let value = serde_json::to_value(param)?;
+        let shape = shape(&value)?;
+        let mut array = Array {
+            data: Vec::with_capacity(shape.iter().product()),
+            shape,
+        };
+        array.collect(&value)?;
+        Ok(array)
+    }
+
+    /// Appends all elements of `value` to `data`
+    fn collect(&mut self, value: &Value) -> Result<(), Error> {
+        match value {
+            Value::Number(x) => self.data.push(x.as_f64().unwrap_or(f64::NAN)),
+            // serde_json represents NaN and infinite values as null
+            Value::Null => self.data.push(f64::NAN),
+            Value::Array(values) => {
+                for value in values {
+                    self.collect(value)?;
+                }
+            }
+            Value::Object(map) if is_ndarray(map) => self.collect(&map["data"])?,
+            _ => return Err(unsupported()),
+        }
+        Ok(())
+    }
+
+    /// Serializes the array in `.npy` format (version 1.0)
+    pub fn to_npy(&self) -> Vec<u8> {
+        let shape = match self.shape.len() {
+            1 => format!("({},)", self.shape[0]),
+            _ => format!(
+                "({})",
+                self.shape
+                    .iter()
+                    .map(|s| s.to_string())
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+        };
+        let mut header = format!("{{'descr': '<f8', 'fortran_order': False, 'shape': {shape}, }}");
+        // Magic string (6 bytes), version (2 bytes) and header length (2 bytes) followed by the
+        // header, padded with spaces and terminated by a newline such that the data is aligned
+        // to 64 bytes.
+        let padding = 63 - (10 + header.len()) % 64;
+        header.push_str(&" ".repeat(padding));
+        header.push('\n');
+
+        let mut bytes = Vec::with_capacity(10 + header.len() + 8 * self.data.len());
+        bytes.extend_from_slice(b"\x93NUMPY\x01\x00");
+        bytes.extend_from_slice(&(header.len() as u16).to_le_bytes());
+        bytes.extend_from_slice(header.as_bytes());
+        for x in self.data.iter() {
+            bytes.extend_from_slice(&x.to_le_bytes());
+        }
+        bytes
+    }
+}
+
+/// Whether `map` is the serde representation of an `ndarray` array
+fn is_ndarray(map: &serde_json::Map<String, Value>) -> bool {
+    map.contains_key("v") && map.contains_key("dim") && map.contains_key("data")
+}
+
+/// Error returned for parameter vectors which cannot be represented as array
+fn unsupported() -> Error {
+    Error::msg("`ParamWriter`: parameter vector cannot be converted into a NumPy array.")
+}
+
+/// Determines the shape of `value`. Sequences must be of equal length on each level.
+fn shape(value: &Value) -> Result<Vec<usize>, Error> {
+    match value {
+        Value::Number(_) | Value::Null => Ok(vec![]),
+        Value::Array(values) => {
+            let inner = match values.first() {
+                Some(first) => shape(first)?,
+                None => vec![],
+            };
+            for value in values.iter().skip(1) {
+                if shape(value)? != inner {
+                    return Err(unsupported());
+                }
+            }
+            Ok(std::iter::once(values.len()).chain(inner).collect())
+        }
+        Value::Object(map) if is_ndarray(map) => map["dim"]
+            .as_array()
+            .ok_or_else(unsupported)?
+            .iter()
+            .map(|d| d.as_u64().map(|d| d as usize).ok_or_else(unsupported))
+            .collect(),
+        _ => Err(unsupported()),
+    }
+}
+
+/// Entry of a ZIP archive
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub(crate) struct ZipEntry {
+    /// File name
+    name: String,
+    /// CRC-32 checksum of the data
+    crc: u32,
+    /// Size of the data
+    size: u32,
+    /// Offset of the local file header
+    offset: u32,
+}
+
+/// Appends the file `name` with content `data` to the uncompressed ZIP archive at `path`.
+///
+/// `entries` holds all entries written so far. The new entry is written where the central
+/// directory started, followed by the updated central directory.
+pub(crate) fn append_to_zip(
+    path: &Path,
+    entries: &mut Vec<ZipEntry>,
+    name: &str,
+    data: &[u8],
+) -> Result<(), Error> {
+    if entries.len() >= usize::from(u16::MAX) {
+        return Err(Error::msg(
+            "`ParamWriter`: maximum number of entries of `.npz` file reached.",
+        ));
+    }
+    let too_large = || Error::msg("`ParamWriter`: `.npz` file exceeds 4 GiB.");
+    let offset = entries
+        .last()
+        .map(|e| e.offset as u64 + 30 + e.name.len() as u64 + e.size as u64)
+        .unwrap_or(0);
+    let entry = ZipEntry {
+        name: name.to_string(),
+        crc: crc32fast::hash(data),
+        size: u32::try_from(data.len()).map_err(|_| too_large())?,
+        offset: u32::try_from(offset).map_err(|_| too_large())?,
+    };
+
+    let mut bytes = vec![];
+    // Local file header
+    bytes.extend_from_slice(&0x04034b50u32.to_le_bytes());
+    bytes.extend_from_slice(&entry_fields(&entry));
+    bytes.extend_from_slice(&0u16.to_le_bytes());
+    bytes.extend_from_slice(entry.name.as_bytes());
+    bytes.extend_from_slice(data);
+    entries.push(entry);
+
+    // Central directory
+    let cd_offset = offset + bytes.len() as u64;
+    let mut cd = vec![];
+    for entry in entries.iter() {
+        cd.extend_from_slice(&0x02014b50u32.to_le_bytes());
+        cd.extend_from_slice(&20u16.to_le_bytes());
+        cd.extend_from_slice(&entry_fields(entry));
+        // Extra field length, comment length, disk number, internal and external attributes
+        cd.extend_from_slice(&[0u8; 12]);
+        cd.extend_from_slice(&entry.offset.to_le_bytes());
+        cd.extend_from_slice(entry.name.as_bytes());
+    }
+    // End of central directory record
+    let num_entries = entries.len() as u16;
+    bytes.extend_from_slice(&cd);
+    bytes.extend_from_slice(&0x06054b50u32.to_le_bytes());
+    bytes.extend_from_slice(&[0u8; 4]);
+    bytes.extend_from_slice(&num_entries.to_le_bytes());
+    bytes.extend_from_slice(&num_entries.to_le_bytes());
+    bytes.extend_from_slice(&(cd.len() as u32).to_le_bytes());
+    bytes.extend_from_slice(
+        &u32::try_from(cd_offset)
+            .map_err(|_| too_large())?
+            .to_le_bytes(),
+    );
+    bytes.extend_from_slice(&0u16.to_le_bytes());
+
+    let mut f = OpenOptions::new()
+        .create(true)
+        .truncate(false)
+        .write(true)
+        .open(path)?;
+    f.seek(SeekFrom::Start(offset))?;
+    f.write_all(&bytes)?;
+    f.set_len(offset + bytes.len() as u64)?;
+    Ok(())
+}
+
+/// Fields shared by local file headers and central directory headers, from "version needed" up
+/// to the file name length
+fn entry_fields(entry: &ZipEntry) -> Vec<u8> {
+    let mut bytes = vec![];
+    // Version needed to extract
+    bytes.extend_from_slice(&20u16.to_le_bytes());
+    // Flags and compression method (stored)
+    bytes.extend_from_slice(&[0u8; 4]);
+    // Modification time and date (1980-01-01 00:00)
+    bytes.extend_from_slice(&0u16.to_le_bytes());
+    bytes.extend_from_slice(&0x21u16.to_le_bytes());
+    bytes.extend_from_slice(&entry.crc.to_le_bytes());
+    // Compressed and uncompressed size
+    bytes.extend_from_slice(&entry.size.to_le_bytes());
+    bytes.extend_from_slice(&entry.size.to_le_bytes());
+    bytes.extend_from_slice(&(entry.name.len() as u16).to_le_bytes());
+    bytes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_shapes() {
+        let array = Array::from_param(&vec![1.0, 2.0, 3.0]).unwrap();
+        assert_eq!(array.shape, vec![3]);
+        assert_eq!(array.data, vec![1.0, 2.0, 3.0]);
+
+        let array = Array::from_param(&vec![vec![1, 2], vec![3, 4], vec![5, 6]]).unwrap();
+        assert_eq!(array.shape, vec![3, 2]);
+        assert_eq!(array.data, vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0]);
+
+        let array = Array::from_param(&2.5).unwrap();
+        assert_eq!(array.shape, Vec::<usize>::new());
+        assert_eq!(array.data, vec![2.5]);
+
+        let array = Array::from_param(&vec![f64::NAN]).unwrap();
+        assert!(array.data[0].is_nan());
+
+        assert!(Array::from_param(&vec![vec![1.0], vec![2.0, 3.0]]).is_err());
+        assert!(Array::from_param(&"string").is_err());
+    }
+
+    #[test]
+    fn test_ndarray() {
+        let value = serde_json::json!({"v": 1, "dim": [2, 2], "data": [1.0, 2.0, 3.0, 4.0]});
+        let array = Array::from_param(&value).unwrap();
+        assert_eq!(array.shape, vec![2, 2]);
+        assert_eq!(array.data, vec![1.0, 2.0, 3.0, 4.0]);
+    }
+
+    #[test]
+    fn test_npy() {
+        let bytes = Array::from_param(&vec![1.0, 2.0]).unwrap().to_npy();
+        assert_eq!(&bytes[..8], b"\x93NUMPY\x01\x00");
+        let header_len = u16::from_le_bytes([bytes[8], bytes[9]]) as usize;
+        assert_eq!((10 + header_len) % 64, 0);
+        let header = std::str::from_utf8(&bytes[10..10 + header_len]).unwrap();
+        assert!(header.starts_with("{'descr': '<f8', 'fortran_order': False, 'shape': (2,), }"));
+        assert!(header.ends_with('\n'));
+        assert_eq!(bytes.len(), 10 + header_len + 16);
+        assert_eq!(
+            &bytes[10 + header_len..10 + header_len + 8],
+            &1.0f64.to_le_bytes()
+        );
+    }
+}
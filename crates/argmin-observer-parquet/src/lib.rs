@@ -0,0 +1,513 @@
+// Copyright 2018-2024 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+//! Writes the optimization trace to an [Apache Parquet](https://parquet.apache.org/) file.
+//!
+//! See documentation of [`ParquetWriter`] for details.
+//!
+//! # Usage
+//!
+//! Add the following line to your dependencies list:
+//!
+//! ```toml
+//! [dependencies]
+#![doc = concat!("argmin-observer-parquet = \"", env!("CARGO_PKG_VERSION"), "\"")]
+//! ```
+//!
+//! # License
+//!
+//! Licensed under either of
+//!
+//!   * Apache License, Version 2.0,
+//!     ([LICENSE-APACHE](https://github.com/argmin-rs/argmin/blob/main/LICENSE-APACHE) or
+//!     <http://www.apache.org/licenses/LICENSE-2.0>)
+//!   * MIT License ([LICENSE-MIT](https://github.com/argmin-rs/argmin/blob/main/LICENSE-MIT) or
+//!     <http://opensource.org/licenses/MIT>)
+//!
+//! at your option.
+//!
+//! ## Contribution
+//!
+//! Unless you explicitly state otherwise, any contribution intentionally submitted for inclusion
+//! in the work by you, as defined in the Apache-2.0 license, shall be dual licensed as above,
+//! without any additional terms or conditions.
+
+use argmin::core::observers::Observe;
+use argmin::core::{ArgminFloat, Error, State, KV};
+use arrow_array::builder::{Float64Builder, ListBuilder, UInt64Builder};
+use arrow_array::{ArrayRef, RecordBatch};
+use arrow_schema::{DataType, Field, Schema, SchemaRef};
+use parquet::arrow::ArrowWriter;
+use parquet::file::metadata::KeyValue;
+use parquet::file::properties::WriterProperties;
+use std::fs::File;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+/// Key of the Parquet file metadata entry which holds the name of the solver
+pub const SOLVER_METADATA_KEY: &str = "argmin.solver";
+
+/// Write the optimization trace to a Parquet file.
+///
+/// Each call to `observe_iter` appends one row with the following columns:
+///
+/// * `iter` (`UInt64`): iteration number
+/// * `cost` (`Float64`): current cost function value
+/// * `best_cost` (`Float64`): best cost function value so far
+/// * `time` (`Float64`, nullable): elapsed time in seconds
+/// * one `UInt64` column per function evaluation counter (e.g. `cost_count`)
+/// * one nullable `Float64` column per selected KV key (see [`ParquetWriter::kv_keys`]).
+///   Integer and boolean values are converted to floats. The value is null if the key is missing
+///   in an iteration or if it holds a string.
+/// * `param` (`List<Float64>`, nullable): the current parameter vector, only if enabled via
+///   [`ParquetWriter::param`].
+///
+/// The schema is determined in the first observed iteration, therefore function evaluation
+/// counters which only appear later in the run are not recorded. The name of the solver is stored
+/// in the file metadata under the key [`SOLVER_METADATA_KEY`].
+///
+/// Rows are buffered in memory and written to disk as a row group once
+/// [`row_group_size`](`ParquetWriter::row_group_size`) rows are collected. The file is finalized
+/// in `observe_final` (or when the observer is dropped). Since Parquet stores its metadata at the
+/// end of the file, the file is only readable after it was finalized.
+///
+/// # Example
+///
+/// ```
+/// use argmin_observer_parquet::ParquetWriter;
+///
+/// let observer = ParquetWriter::new("trace.parquet")
+///     .kv_keys(["gamma"])
+///     .param(true)
+///     .row_group_size(4096);
+/// ```
+pub struct ParquetWriter {
+    /// Path of the output file
+    path: PathBuf,
+    /// KV entries which are written to the file
+    kv_keys: Vec<String>,
+    /// Whether the parameter vector is written to the file
+    write_param: bool,
+    /// Number of rows per row group
+    row_group_size: usize,
+    /// Name of the solver
+    solver: Option<String>,
+    /// Writer and column buffers, created in the first observed iteration
+    inner: Option<Inner>,
+}
+
+impl ParquetWriter {
+    /// Create a new instance of `ParquetWriter` which writes to the file at `path`.
+    ///
+    /// By default, no KV entries and no parameter vectors are written and row groups contain 1024
+    /// rows. An existing file at `path` will be overwritten.
+    ///
+    /// # Example
+    /// ```
+    /// # use argmin_observer_parquet::ParquetWriter;
+    /// let observer = ParquetWriter::new("trace.parquet");
+    /// ```
+    pub fn new<P: Into<PathBuf>>(path: P) -> Self {
+        ParquetWriter {
+            path: path.into(),
+            kv_keys: vec![],
+            write_param: false,
+            row_group_size: 1024,
+            solver: None,
+            inner: None,
+        }
+    }
+
+    /// Select the KV entries which are written to the file.
+    ///
+    /// # Example
+    /// ```
+    /// # use argmin_observer_parquet::ParquetWriter;
+    /// let observer = ParquetWriter::new("trace.parquet").kv_keys(["t", "new_be"]);
+    /// ```
+    #[must_use]
+    pub fn kv_keys<K, S>(mut self, keys: K) -> Self
+    where
+        K: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        self.kv_keys = keys.into_iter().map(|k| k.as_ref().to_string()).collect();
+        self
+    }
+
+    /// Enable or disable writing the parameter vector (default: disabled).
+    ///
+    /// # Example
+    /// ```
+    /// # use argmin_observer_parquet::ParquetWriter;
+    /// let observer = ParquetWriter::new("trace.parquet").param(true);
+    /// ```
+    #[must_use]
+    pub fn param(mut self, write_param: bool) -> Self {
+        self.write_param = write_param;
+        self
+    }
+
+    /// Set the number of rows which are buffered before they are written as a row group
+    /// (default: 1024).
+    ///
+    /// # Panics
+    ///
+    /// Panics if `rows` is 0.
+    ///
+    /// # Example
+    /// ```
+    /// # use argmin_observer_parquet::ParquetWriter;
+    /// let observer = ParquetWriter::new("trace.parquet").row_group_size(4096);
+    /// ```
+    #[must_use]
+    pub fn row_group_size(mut self, rows: usize) -> Self {
+        assert!(rows > 0, "row group size must be larger than 0");
+        self.row_group_size = rows;
+        self
+    }
+
+    /// Create the file and the column buffers based on the state of the first iteration
+    fn create<I: State>(&self, state: &I) -> Result<Inner, Error> {
+        let mut counts: Vec<String> = state.get_func_counts().keys().cloned().collect();
+        counts.sort();
+
+        let mut fields = vec![
+            Field::new("iter", DataType::UInt64, false),
+            Field::new("cost", DataType::Float64, false),
+            Field::new("best_cost", DataType::Float64, false),
+            Field::new("time", DataType::Float64, true),
+        ];
+        fields.extend(
+            counts
+                .iter()
+                .map(|name| Field::new(name, DataType::UInt64, false)),
+        );
+        fields.extend(
+            self.kv_keys
+                .iter()
+                .map(|key| Field::new(key, DataType::Float64, true)),
+        );
+        if self.write_param {
+            fields.push(Field::new(
+                "param",
+                DataType::List(Arc::new(Field::new_list_field(DataType::Float64, true))),
+                true,
+            ));
+        }
+        for (i, field) in fields.iter().enumerate() {
+            if fields[..i].iter().any(|f| f.name() == field.name()) {
+                return Err(argmin::argmin_error!(
+                    InvalidParameter,
+                    format!(
+                        "`ParquetWriter`: column `{}` is not unique; KV keys must not clash with \
+                         other columns.",
+                        field.name()
+                    )
+                ));
+            }
+        }
+        let schema = Arc::new(Schema::new(fields));
+
+        let props = WriterProperties::builder()
+            .set_max_row_group_size(self.row_group_size)
+            .set_key_value_metadata(self.solver.as_ref().map(|name| {
+                vec![KeyValue::new(
+                    SOLVER_METADATA_KEY.to_string(),
+                    name.to_string(),
+                )]
+            }))
+            .build();
+
+        if let Some(dir) = self.path.parent() {
+            if !dir.as_os_str().is_empty() && !dir.exists() {
+                std::fs::create_dir_all(dir)?
+            }
+        }
+        let writer = ArrowWriter::try_new(File::create(&self.path)?, schema.clone(), Some(props))?;
+
+        Ok(Inner {
+            writer,
+            schema,
+            iter: UInt64Builder::new(),
+            cost: Float64Builder::new(),
+            best_cost: Float64Builder::new(),
+            time: Float64Builder::new(),
+            count_cols: counts
+                .into_iter()
+                .map(|name| (name, UInt64Builder::new()))
+                .collect(),
+            kv_cols: self
+                .kv_keys
+                .iter()
+                .map(|key| (key.clone(), Float64Builder::new()))
+                .collect(),
+            param: self
+                .write_param
+                .then(|| ListBuilder::new(Float64Builder::new())),
+            rows: 0,
+        })
+    }
+
+    /// Write buffered rows and close the file
+    fn finish(&mut self) -> Result<(), Error> {
+        if let Some(mut inner) = self.inner.take() {
+            inner.flush()?;
+            inner.writer.close()?;
+        }
+        Ok(())
+    }
+}
+
+/// Parquet writer and buffers of all columns
+struct Inner {
+    writer: ArrowWriter<File>,
+    schema: SchemaRef,
+    iter: UInt64Builder,
+    cost: Float64Builder,
+    best_cost: Float64Builder,
+    time: Float64Builder,
+    count_cols: Vec<(String, UInt64Builder)>,
+    kv_cols: Vec<(String, Float64Builder)>,
+    param: Option<ListBuilder<Float64Builder>>,
+    rows: usize,
+}
+
+impl Inner {
+    /// Write buffered rows as a row group
+    fn flush(&mut self) -> Result<(), Error> {
+        if self.rows == 0 {
+            return Ok(());
+        }
+        let mut columns: Vec<ArrayRef> = vec![
+            Arc::new(self.iter.finish()),
+            Arc::new(self.cost.finish()),
+            Arc::new(self.best_cost.finish()),
+            Arc::new(self.time.finish()),
+        ];
+        columns.extend(
+            self.count_cols
+                .iter_mut()
+                .map(|(_, col)| Arc::new(col.finish()) as ArrayRef),
+        );
+        columns.extend(
+            self.kv_cols
+                .iter_mut()
+                .map(|(_, col)| Arc::new(col.finish()) as ArrayRef),
+        );
+        if let Some(param) = self.param.as_mut() {
+            columns.push(Arc::new(param.finish()));
+        }
+        let batch = RecordBatch::try_new(self.schema.clone(), columns)?;
+        self.writer.write(&batch)?;
+        self.writer.flush()?;
+        self.rows = 0;
+        Ok(())
+    }
+}
+
+impl<I> Observe<I> for ParquetWriter
+where
+    I: State,
+    I::Param: IntoIterator<Item = I::Float> + Clone,
+    I::Float: ArgminFloat,
+    f64: From<I::Float>,
+{
+    fn observe_init(&mut self, name: &str, _state: &I, _kv: &KV) -> Result<(), Error> {
+        self.solver = Some(name.to_string());
+        Ok(())
+    }
+
+    fn observe_iter(&mut self, state: &I, kv: &KV) -> Result<(), Error> {
+        if self.inner.is_none() {
+            self.inner = Some(self.create(state)?);
+        }
+        let inner = self.inner.as_mut().unwrap();
+
+        inner.iter.append_value(state.get_iter());
+        inner.cost.append_value(f64::from(state.get_cost()));
+        inner
+            .best_cost
+            .append_value(f64::from(state.get_best_cost()));
+        inner
+            .time
+            .append_option(state.get_time().map(|t| t.as_secs_f64()));
+        let counts = state.get_func_counts();
+        for (name, col) in inner.count_cols.iter_mut() {
+            col.append_value(counts.get(name).copied().unwrap_or(0));
+        }
+        for (key, col) in inner.kv_cols.iter_mut() {
+            col.append_option(kv.get(key).and_then(|v| v.get_float()));
+        }
+        if let Some(col) = inner.param.as_mut() {
+            col.append_option(
+                state
+                    .get_param()
+                    .map(|p| p.clone().into_iter().map(|x| Some(f64::from(x)))),
+            );
+        }
+        inner.rows += 1;
+
+        if inner.rows >= self.row_group_size {
+            inner.flush()?;
+        }
+        Ok(())
+    }
+
+    fn observe_final(&mut self, _state: &I) -> Result<(), Error> {
+        self.finish()
+    }
+}
+
+/// Finalizes the file in case `observe_final` was not called. Errors are ignored.
+impl Drop for ParquetWriter {
+    fn drop(&mut self) {
+        let _ = self.finish();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use argmin::core::{IterState, KvValue};
+    use arrow_array::{Array, Float64Array, ListArray, UInt64Array};
+    use parquet::arrow::arrow_reader::ParquetRecordBatchReaderBuilder;
+    use std::time::Duration;
+
+    type TestState = IterState<Vec<f64>, (), (), (), (), f64>;
+
+    fn test_file(name: &str) -> PathBuf {
+        std::env::temp_dir().join(format!(
+            "argmin_observer_parquet_{}_{}.parquet",
+            name,
+            std::process::id()
+        ))
+    }
+
+    fn run(observer: &mut ParquetWriter, iters: u64) {
+        let mut state = TestState::new();
+        Observe::<TestState>::observe_init(observer, "TestSolver", &state, &KV::new()).unwrap();
+        for i in 0..iters {
+            state = state
+                .param(vec![i as f64, -(i as f64)])
+                .cost(10.0 - i as f64);
+            state.best_cost = state.cost;
+            state.iter = i + 1;
+            state.time = Some(Duration::from_millis(500 * (i + 1)));
+            state.counts.insert("cost_count".to_string(), 2 * (i + 1));
+            let mut kv = KV::new();
+            if i % 2 == 0 {
+                kv.insert("gamma", KvValue::Float(0.5 * i as f64));
+            }
+            kv.insert("name", KvValue::Str("not a number".to_string()));
+            observer.observe_iter(&state, &kv).unwrap();
+        }
+        Observe::<TestState>::observe_final(observer, &state).unwrap();
+    }
+
+    #[test]
+    fn test_write_and_read() {
+        let path = test_file("write_and_read");
+        let mut observer = ParquetWriter::new(&path)
+            .kv_keys(["gamma", "name"])
+            .param(true)
+            .row_group_size(3);
+        run(&mut observer, 7);
+
+        let builder = ParquetRecordBatchReaderBuilder::try_new(File::open(&path).unwrap()).unwrap();
+        assert_eq!(builder.metadata().num_row_groups(), 3);
+        let solver = builder
+            .metadata()
+            .file_metadata()
+            .key_value_metadata()
+            .unwrap()
+            .iter()
+            .find(|kv| kv.key == SOLVER_METADATA_KEY)
+            .and_then(|kv| kv.value.clone());
+        assert_eq!(solver.as_deref(), Some("TestSolver"));
+
+        let batches: Vec<RecordBatch> = builder.build().unwrap().collect::<Result<_, _>>().unwrap();
+        assert_eq!(batches.len(), 1);
+        let batch = &batches[0];
+        assert_eq!(batch.num_rows(), 7);
+        let names: Vec<&str> = batch
+            .schema_ref()
+            .fields()
+            .iter()
+            .map(|f| f.name().as_str())
+            .collect();
+        assert_eq!(
+            names,
+            [
+                "iter",
+                "cost",
+                "best_cost",
+                "time",
+                "cost_count",
+                "gamma",
+                "name",
+                "param"
+            ]
+        );
+
+        let column = |name: &str| batch.column_by_name(name).unwrap().clone();
+        let iter = column("iter");
+        let iter = iter.as_any().downcast_ref::<UInt64Array>().unwrap();
+        assert_eq!(iter.values().to_vec(), vec![1, 2, 3, 4, 5, 6, 7]);
+        let cost = column("cost");
+        let cost = cost.as_any().downcast_ref::<Float64Array>().unwrap();
+        assert_eq!(cost.value(6), 4.0);
+        let time = column("time");
+        let time = time.as_any().downcast_ref::<Float64Array>().unwrap();
+        assert_eq!(time.value(1), 1.0);
+        let counts = column("cost_count");
+        let counts = counts.as_any().downcast_ref::<UInt64Array>().unwrap();
+        assert_eq!(counts.value(3), 8);
+        let gamma = column("gamma");
+        let gamma = gamma.as_any().downcast_ref::<Float64Array>().unwrap();
+        assert_eq!(gamma.value(2), 1.0);
+        assert!(gamma.is_null(1));
+        assert_eq!(column("name").null_count(), 7);
+        let param = column("param");
+        let param = param.as_any().downcast_ref::<ListArray>().unwrap();
+        let p = param.value(4);
+        let p = p.as_any().downcast_ref::<Float64Array>().unwrap();
+        assert_eq!(p.values().to_vec(), vec![4.0, -4.0]);
+
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn test_finalized_on_drop() {
+        let path = test_file("finalized_on_drop");
+        {
+            let mut observer = ParquetWriter::new(&path);
+            let state = TestState::new().cost(1.0);
+            Observe::<TestState>::observe_iter(&mut observer, &state, &KV::new()).unwrap();
+        }
+        let builder = ParquetRecordBatchReaderBuilder::try_new(File::open(&path).unwrap()).unwrap();
+        assert_eq!(builder.metadata().file_metadata().num_rows(), 1);
+        assert_eq!(builder.schema().fields().len(), 4);
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn test_clashing_columns() {
+        let path = test_file("clashing_columns");
+        let mut observer = ParquetWriter::new(&path).kv_keys(["cost"]);
+        let state = TestState::new();
+        let res = Observe::<TestState>::observe_iter(&mut observer, &state, &KV::new());
+        assert!(res.is_err());
+        let _ = std::fs::remove_file(path);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_row_group_size_zero() {
+        let _ = ParquetWriter::new("trace.parquet").row_group_size(0);
+    }
+}
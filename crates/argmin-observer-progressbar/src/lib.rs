@@ -0,0 +1,268 @@
+// Copyright 2018-2024 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+//! Displays the progress of the optimization as a progress bar in the terminal, based on the
+//! [`indicatif`](https://crates.io/crates/indicatif) crate.
+//!
+//! See documentation of [`ProgressBarObserver`] for details.
+//!
+//! # Usage
+//!
+//! Add the following line to your dependencies list:
+//!
+//! ```toml
+//! [dependencies]
+#![doc = concat!("argmin-observer-progressbar = \"", env!("CARGO_PKG_VERSION"), "\"")]
+//! ```
+//!
+//! # License
+//!
+//! Licensed under either of
+//!
+//!   * Apache License, Version 2.0,
+//!     ([LICENSE-APACHE](https://github.com/argmin-rs/argmin/blob/main/LICENSE-APACHE) or
+//!     <http://www.apache.org/licenses/LICENSE-2.0>)
+//!   * MIT License ([LICENSE-MIT](https://github.com/argmin-rs/argmin/blob/main/LICENSE-MIT) or
+//!     <http://opensource.org/licenses/MIT>)
+//!
+//! at your option.
+//!
+//! ## Contribution
+//!
+//! Unless you explicitly state otherwise, any contribution intentionally submitted for inclusion
+//! in the work by you, as defined in the Apache-2.0 license, shall be dual licensed as above,
+//! without any additional terms or conditions.
+
+use argmin::core::observers::Observe;
+use argmin::core::{Error, State, KV};
+use indicatif::{ProgressBar, ProgressStyle};
+
+/// Template used if the maximum number of iterations is known
+const TEMPLATE: &str = "{prefix} [{bar:40}] {pos}/{len} [{elapsed_precise} < {eta_precise}] {msg}";
+
+/// Template used if no maximum number of iterations is set
+const TEMPLATE_UNBOUNDED: &str = "{prefix} {spinner} {pos} [{elapsed_precise}] {msg}";
+
+/// Displays a live progress bar in the terminal.
+///
+/// The bar shows the name of the solver, the number of iterations out of the maximum number of
+/// iterations, the elapsed time, the estimated remaining time as well as the current and best
+/// cost. If no maximum number of iterations is set, a spinner is shown instead of the bar. After
+/// the run, the termination reason is displayed and the bar is left on screen.
+///
+/// The bar is drawn to stderr and redrawing is rate limited, therefore even fast iterations are
+/// not slowed down noticeably. If stderr is not a terminal, nothing is drawn.
+///
+/// # Example
+///
+/// ```
+/// use argmin::core::observers::ObserverMode;
+/// use argmin_observer_progressbar::ProgressBarObserver;
+/// # use argmin::core::{CostFunction, Error, Executor};
+/// # use argmin::solver::neldermead::NelderMead;
+/// #
+/// # struct Sphere {}
+/// #
+/// # impl CostFunction for Sphere {
+/// #     type Param = Vec<f64>;
+/// #     type Output = f64;
+/// #
+/// #     fn cost(&self, p: &Self::Param) -> Result<Self::Output, Error> {
+/// #         Ok(p.iter().map(|x| x * x).sum())
+/// #     }
+/// # }
+///
+/// # fn main() -> Result<(), Error> {
+/// # let solver = NelderMead::new(vec![vec![1.0, 1.0], vec![2.0, 1.0], vec![1.0, 2.0]]);
+/// let res = Executor::new(Sphere {}, solver)
+///     .configure(|state| state.max_iters(100))
+///     .add_observer(ProgressBarObserver::new(), ObserverMode::Always)
+///     .run()?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone)]
+pub struct ProgressBarObserver {
+    /// The progress bar
+    bar: ProgressBar,
+    /// User defined style
+    style: Option<ProgressStyle>,
+}
+
+impl ProgressBarObserver {
+    /// Create a new progress bar which is drawn to stderr.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use argmin_observer_progressbar::ProgressBarObserver;
+    ///
+    /// let observer = ProgressBarObserver::new();
+    /// ```
+    pub fn new() -> Self {
+        ProgressBarObserver::with_progress_bar(ProgressBar::no_length())
+    }
+
+    /// Use an existing progress bar.
+    ///
+    /// This allows for instance to show the bar as part of an `indicatif::MultiProgress` or to
+    /// draw it to a different target.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use argmin_observer_progressbar::ProgressBarObserver;
+    /// use indicatif::{MultiProgress, ProgressBar};
+    ///
+    /// let multi = MultiProgress::new();
+    /// let bar = multi.add(ProgressBar::no_length());
+    /// let observer = ProgressBarObserver::with_progress_bar(bar);
+    /// ```
+    pub fn with_progress_bar(bar: ProgressBar) -> Self {
+        ProgressBarObserver { bar, style: None }
+    }
+
+    /// Set a custom template for the progress bar (see the documentation of
+    /// [`indicatif::ProgressStyle`] for the syntax).
+    ///
+    /// The name of the solver is available as `{prefix}`, the current and best cost as `{msg}`.
+    /// Returns an error if the template is invalid.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use argmin_observer_progressbar::ProgressBarObserver;
+    /// # use argmin::core::Error;
+    ///
+    /// # fn main() -> Result<(), Error> {
+    /// let observer = ProgressBarObserver::new().template("{bar:60} {pos}/{len} {msg}")?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn template(mut self, template: &str) -> Result<Self, Error> {
+        self.style = Some(ProgressStyle::with_template(template)?);
+        Ok(self)
+    }
+
+    /// Returns the underlying progress bar.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use argmin_observer_progressbar::ProgressBarObserver;
+    ///
+    /// let observer = ProgressBarObserver::new();
+    /// let position = observer.progress_bar().position();
+    /// # assert_eq!(position, 0);
+    /// ```
+    pub fn progress_bar(&self) -> &ProgressBar {
+        &self.bar
+    }
+
+    /// Returns the style of the bar, depending on whether the number of iterations is bounded.
+    fn style(&self, bounded: bool) -> Result<ProgressStyle, Error> {
+        if let Some(style) = &self.style {
+            return Ok(style.clone());
+        }
+        Ok(if bounded {
+            ProgressStyle::with_template(TEMPLATE)?.progress_chars("=> ")
+        } else {
+            ProgressStyle::with_template(TEMPLATE_UNBOUNDED)?
+        })
+    }
+}
+
+impl Default for ProgressBarObserver {
+    fn default() -> Self {
+        ProgressBarObserver::new()
+    }
+}
+
+/// Formats the current and best cost
+fn cost_message<I: State>(state: &I) -> String {
+    format!(
+        "cost: {}, best cost: {}",
+        state.get_cost(),
+        state.get_best_cost()
+    )
+}
+
+impl<I> Observe<I> for ProgressBarObserver
+where
+    I: State,
+{
+    /// Sets up the bar with the maximum number of iterations and the name of the solver.
+    fn observe_init(&mut self, name: &str, state: &I, _kv: &KV) -> Result<(), Error> {
+        let max_iters = state.get_max_iters();
+        let bounded = max_iters < u64::MAX;
+        self.bar.reset();
+        self.bar.set_style(self.style(bounded)?);
+        if bounded {
+            self.bar.set_length(max_iters);
+        }
+        self.bar.set_prefix(name.to_string());
+        self.bar.set_position(state.get_iter());
+        Ok(())
+    }
+
+    /// Updates the number of iterations as well as the current and best cost.
+    fn observe_iter(&mut self, state: &I, _kv: &KV) -> Result<(), Error> {
+        self.bar.set_position(state.get_iter());
+        self.bar.set_message(cost_message(state));
+        Ok(())
+    }
+
+    /// Displays the termination reason and leaves the bar on screen.
+    fn observe_final(&mut self, state: &I) -> Result<(), Error> {
+        self.bar.set_position(state.get_iter());
+        let message = match state.get_termination_reason() {
+            Some(reason) => format!("{} ({})", reason, cost_message(state)),
+            None => cost_message(state),
+        };
+        self.bar.abandon_with_message(message);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use argmin::core::{IterState, TerminationReason};
+
+    type TestState = IterState<Vec<f64>, (), (), (), (), f64>;
+
+    #[test]
+    fn test_progress() {
+        let mut observer = ProgressBarObserver::with_progress_bar(ProgressBar::hidden());
+        let mut state = TestState::new().max_iters(10);
+        observer
+            .observe_init("TestSolver", &state, &KV::new())
+            .unwrap();
+        assert_eq!(observer.progress_bar().length(), Some(10));
+        assert_eq!(observer.progress_bar().prefix(), "TestSolver");
+        assert_eq!(observer.progress_bar().position(), 0);
+
+        for i in 0..3 {
+            state = state.cost(3.0 - i as f64);
+            state.best_cost = state.cost;
+            state.iter = i + 1;
+            observer.observe_iter(&state, &KV::new()).unwrap();
+        }
+        assert_eq!(observer.progress_bar().position(), 3);
+        assert_eq!(observer.progress_bar().message(), "cost: 1, best cost: 1");
+        assert!(!observer.progress_bar().is_finished());
+
+        state = state.terminate_with(TerminationReason::SolverConverged);
+        Observe::<TestState>::observe_final(&mut observer, &state).unwrap();
+        assert!(observer.progress_bar().is_finished());
+        assert_eq!(observer.progress_bar().position(), 3);
+        assert_eq!(
+            observer.progress_bar().message(),
+            "Solver converged (cost: 1, best cost: 1)"
+        );
+    }
+}
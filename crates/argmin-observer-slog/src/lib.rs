@@ -40,15 +40,71 @@
 //! without any additional terms or conditions.
 
 use argmin::core::observers::Observe;
-use argmin::core::{Error, State, KV};
+use argmin::core::{Error, KvValue, State, KV};
 use slog::{info, o, Drain, Key, Record, Serializer};
 use slog_async::OverflowStrategy;
 
+/// Formatting of floating point values
+///
+/// # Example
+///
+/// ```
+/// use argmin_observer_slog::FloatFormat;
+///
+/// assert_eq!(FloatFormat::Default.format(1234.5678), "1234.5678");
+/// assert_eq!(FloatFormat::Fixed(2).format(1234.5678), "1234.57");
+/// assert_eq!(FloatFormat::Scientific(3).format(1234.5678), "1.235e3");
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum FloatFormat {
+    /// Shortest representation which preserves the value
+    #[default]
+    Default,
+    /// Fixed number of decimal places
+    Fixed(usize),
+    /// Scientific notation with a fixed number of decimal places
+    Scientific(usize),
+}
+
+impl FloatFormat {
+    /// Formats `value` according to the chosen format
+    pub fn format(&self, value: f64) -> String {
+        match *self {
+            FloatFormat::Default => value.to_string(),
+            FloatFormat::Fixed(precision) => format!("{value:.precision$}"),
+            FloatFormat::Scientific(precision) => format!("{value:.precision$e}"),
+        }
+    }
+}
+
 /// A logger using the [`slog`](https://crates.io/crates/slog) crate as backend.
+///
+/// By default, all function evaluation counts, the cost, the best cost and all key-value pairs
+/// provided by the solver are logged after each iteration. For high-dimensional problems or
+/// solvers which report many values, the output can be restricted to selected keys via
+/// [`keys`](`SlogLogger::keys`) and the formatting of floating point values can be set via
+/// [`float_format`](`SlogLogger::float_format`).
+///
+/// The norm of the gradient is logged under the key `gradient_norm` if it is reported by the
+/// `Executor` (see `Executor::report_gradient_norm`).
+///
+/// # Example
+///
+/// ```
+/// use argmin_observer_slog::{FloatFormat, SlogLogger};
+///
+/// let logger = SlogLogger::term()
+///     .keys(&["cost", "best_cost", "gradient_norm"])
+///     .float_format(FloatFormat::Scientific(4));
+/// ```
 #[derive(Clone)]
 pub struct SlogLogger {
     /// the logger
     logger: slog::Logger,
+    /// Keys which are logged after each iteration (all if `None`)
+    keys: Option<Vec<String>>,
+    /// Formatting of floating point values
+    float_format: FloatFormat,
 }
 
 impl SlogLogger {
@@ -93,9 +149,7 @@ impl SlogLogger {
             .overflow_strategy(overflow_strategy)
             .build()
             .fuse();
-        SlogLogger {
-            logger: slog::Logger::root(drain, o!()),
-        }
+        SlogLogger::from_logger(slog::Logger::root(drain, o!()))
     }
 
     /// Log JSON to a file while blocking execution in case of full buffers.
@@ -156,36 +210,124 @@ impl SlogLogger {
             .overflow_strategy(overflow_strategy)
             .build()
             .fuse();
-        Ok(SlogLogger {
-            logger: slog::Logger::root(drain, o!()),
-        })
+        Ok(SlogLogger::from_logger(slog::Logger::root(drain, o!())))
+    }
+
+    /// Create logger with default settings from a `slog::Logger`.
+    fn from_logger(logger: slog::Logger) -> Self {
+        SlogLogger {
+            logger,
+            keys: None,
+            float_format: FloatFormat::Default,
+        }
+    }
+
+    /// Only log the given keys after each iteration.
+    ///
+    /// Keys refer to the cost (`cost`), the best cost (`best_cost`), function evaluation counts
+    /// (such as `cost_count`) and the key-value pairs provided by the solver or the `Executor`
+    /// (such as `gradient_norm` or `time`). The iteration number is always logged. By default,
+    /// all keys are logged.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use argmin_observer_slog::SlogLogger;
+    ///
+    /// let terminal_logger = SlogLogger::term().keys(&["cost", "best_cost"]);
+    /// ```
+    #[must_use]
+    pub fn keys<K: AsRef<str>>(mut self, keys: &[K]) -> Self {
+        self.keys = Some(keys.iter().map(|k| k.as_ref().to_string()).collect());
+        self
+    }
+
+    /// Set the formatting of floating point values (default: [`FloatFormat::Default`]).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use argmin_observer_slog::{FloatFormat, SlogLogger};
+    ///
+    /// let terminal_logger = SlogLogger::term().float_format(FloatFormat::Fixed(6));
+    /// ```
+    #[must_use]
+    pub fn float_format(mut self, float_format: FloatFormat) -> Self {
+        self.float_format = float_format;
+        self
+    }
+
+    /// Returns whether `key` is logged after each iteration.
+    fn is_selected(&self, key: &str) -> bool {
+        self.keys
+            .as_ref()
+            .map(|keys| keys.iter().any(|k| k == key))
+            .unwrap_or(true)
+    }
+
+    /// Formats the value of `key` including its unit (if set).
+    fn format_value(&self, kv: &KV, key: &str) -> Option<String> {
+        let val = match kv.get(key)? {
+            KvValue::Float(x) => self.float_format.format(*x),
+            val => val.to_string(),
+        };
+        match kv.get_meta(key).and_then(|meta| meta.unit.as_ref()) {
+            Some(unit) => Some(format!("{val} {unit}")),
+            None => Some(val),
+        }
     }
 }
 
-struct SlogKV<'a>(&'a KV);
+/// Key-value pairs of a `KV`. If `select` is `true`, only the keys selected in the logger are
+/// emitted.
+struct SlogKV<'a> {
+    kv: &'a KV,
+    logger: &'a SlogLogger,
+    select: bool,
+}
 
 impl slog::KV for SlogKV<'_> {
     fn serialize(&self, _record: &Record, serializer: &mut dyn Serializer) -> slog::Result {
-        for idx in self.0.kv.iter() {
-            serializer.emit_str(Key::from(idx.0.to_string()), &idx.1.to_string())?;
+        for key in self.kv.kv.keys() {
+            if self.select && !self.logger.is_selected(key) {
+                continue;
+            }
+            // Appends the unit of the value if provided by the solver
+            let val = self.logger.format_value(self.kv, key).unwrap();
+            serializer.emit_str(Key::from(key.to_string()), &val)?;
         }
         Ok(())
     }
 }
 
-struct LogState<I>(I);
+struct LogState<'a, I> {
+    state: &'a I,
+    logger: &'a SlogLogger,
+}
 
-impl<I> slog::KV for LogState<&'_ I>
+impl<I> slog::KV for LogState<'_, I>
 where
     I: State,
 {
     fn serialize(&self, _record: &Record, serializer: &mut dyn Serializer) -> slog::Result {
-        for (k, &v) in self.0.get_func_counts().iter() {
-            serializer.emit_u64(Key::from(k.clone()), v)?;
+        for (k, &v) in self.state.get_func_counts().iter() {
+            if self.logger.is_selected(k) {
+                serializer.emit_u64(Key::from(k.clone()), v)?;
+            }
+        }
+        for (key, value) in [
+            ("best_cost", self.state.get_best_cost()),
+            ("cost", self.state.get_cost()),
+        ] {
+            if self.logger.is_selected(key) {
+                let value = match value.into() {
+                    KvValue::Float(value) => self.logger.float_format.format(value),
+                    value => value.to_string(),
+                };
+                serializer.emit_str(Key::from(key), &value)?;
+            }
         }
-        serializer.emit_str(Key::from("best_cost"), &self.0.get_best_cost().to_string())?;
-        serializer.emit_str(Key::from("cost"), &self.0.get_cost().to_string())?;
-        serializer.emit_u64(Key::from("iter"), self.0.get_iter())?;
+        serializer.emit_u64(Key::from("iter"), self.state.get_iter())?;
         Ok(())
     }
 }
@@ -196,18 +338,70 @@ where
 {
     /// Log basic information about the optimization after initialization.
     fn observe_init(&mut self, msg: &str, _state: &I, kv: &KV) -> Result<(), Error> {
-        info!(self.logger, "{}", msg; SlogKV(kv));
+        let kv = SlogKV {
+            kv,
+            logger: self,
+            select: false,
+        };
+        info!(self.logger, "{}", msg; kv);
         Ok(())
     }
 
     /// Logs information about the progress of the optimization after every iteration.
     fn observe_iter(&mut self, state: &I, kv: &KV) -> Result<(), Error> {
-        info!(self.logger, ""; LogState(state), SlogKV(kv));
+        let log_state = LogState {
+            state,
+            logger: self,
+        };
+        let kv = SlogKV {
+            kv,
+            logger: self,
+            select: true,
+        };
+        info!(self.logger, ""; log_state, kv);
         Ok(())
     }
 }
 
 #[cfg(test)]
 mod tests {
-    // TODO
+    use super::*;
+    use argmin::core::KvMeta;
+
+    #[test]
+    fn test_float_format() {
+        assert_eq!(FloatFormat::default(), FloatFormat::Default);
+        assert_eq!(FloatFormat::Default.format(0.1), "0.1");
+        assert_eq!(FloatFormat::Fixed(3).format(2.0 / 3.0), "0.667");
+        assert_eq!(FloatFormat::Scientific(2).format(-0.000123), "-1.23e-4");
+    }
+
+    #[test]
+    fn test_keys() {
+        let logger = SlogLogger::term();
+        assert!(logger.is_selected("cost"));
+        assert!(logger.is_selected("anything"));
+
+        let logger = logger.keys(&["cost", "gradient_norm"]);
+        assert!(logger.is_selected("cost"));
+        assert!(logger.is_selected("gradient_norm"));
+        assert!(!logger.is_selected("best_cost"));
+    }
+
+    #[test]
+    fn test_format_value() {
+        let mut kv = argmin::kv!(
+            "time" => 1.23456;
+            "restart" => true;
+        );
+        kv.set_meta("time", KvMeta::new().unit("s"));
+
+        let logger = SlogLogger::term();
+        assert_eq!(logger.format_value(&kv, "time").unwrap(), "1.23456 s");
+        assert_eq!(logger.format_value(&kv, "restart").unwrap(), "true");
+        assert_eq!(logger.format_value(&kv, "missing"), None);
+
+        let logger = logger.float_format(FloatFormat::Fixed(2));
+        assert_eq!(logger.format_value(&kv, "time").unwrap(), "1.23 s");
+    }
 }
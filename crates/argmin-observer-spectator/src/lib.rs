@@ -31,6 +31,9 @@
 //! instance running on `127.0.0.1:5498`. For details on how to configure the observer the reader
 //! is referred to the documentation of [`SpectatorBuilder`].
 //! Make sure a Spectator instance is running when calling `.build()` on [`SpectatorBuilder`].
+//! Messages are sent in batches. If the connection is lost during a run (for instance because
+//! Spectator was restarted), the messages are buffered and the observer reconnects with
+//! exponential backoff.
 //!
 //! # Usage
 //!
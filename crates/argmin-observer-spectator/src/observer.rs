@@ -15,7 +15,7 @@ use spectator::{Message, DEFAULT_PORT};
 use time::Duration;
 use uuid::Uuid;
 
-use crate::sender::sender;
+use crate::sender::{sender, SenderConfig};
 
 const DEFAULT_HOST: &str = "127.0.0.1";
 
@@ -44,6 +44,8 @@ pub struct SpectatorBuilder {
     name: String,
     selected: HashSet<String>,
     capacity: usize,
+    batch_size: usize,
+    max_retries: u32,
     host: String,
     port: u16,
 }
@@ -61,6 +63,8 @@ impl SpectatorBuilder {
             name: Uuid::new_v4().to_string(),
             selected: HashSet::new(),
             capacity: 10_000,
+            batch_size: 100,
+            max_retries: 10,
             host: DEFAULT_HOST.to_string(),
             port: DEFAULT_PORT,
         }
@@ -133,6 +137,42 @@ impl SpectatorBuilder {
         self
     }
 
+    /// Set the maximum number of messages sent to Spectator at once
+    ///
+    /// All messages which are queued when sending are combined into batches of at most this size
+    /// in order to reduce the number of writes to the network. Defaults to 100.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin_observer_spectator::SpectatorBuilder;
+    /// let builder = SpectatorBuilder::new().with_batch_size(1000);
+    /// # assert_eq!(builder.batch_size(), 1000);
+    /// ```
+    pub fn with_batch_size(mut self, batch_size: usize) -> Self {
+        self.batch_size = batch_size;
+        self
+    }
+
+    /// Set the number of reconnection attempts
+    ///
+    /// If the connection to Spectator is lost during a run (for instance because Spectator was
+    /// restarted), the observer buffers up to `channel_capacity` messages and tries to reconnect
+    /// with exponentially increasing delays (starting at 100ms, at most 10s). After this number of
+    /// failed attempts, the observer stops sending. Defaults to 10.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin_observer_spectator::SpectatorBuilder;
+    /// let builder = SpectatorBuilder::new().with_max_retries(20);
+    /// # assert_eq!(builder.max_retries(), 20);
+    /// ```
+    pub fn with_max_retries(mut self, max_retries: u32) -> Self {
+        self.max_retries = max_retries;
+        self
+    }
+
     /// Define which metrics will be selected in Spectator by default
     ///
     /// If none are set, all metrics will be selected and shown. Providing zero or more metrics
@@ -209,6 +249,34 @@ impl SpectatorBuilder {
         self.capacity
     }
 
+    /// Returns the batch size
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin_observer_spectator::SpectatorBuilder;
+    /// # let builder = SpectatorBuilder::new();
+    /// let batch_size = builder.batch_size();
+    /// # assert_eq!(batch_size, 100);
+    /// ```
+    pub fn batch_size(&self) -> usize {
+        self.batch_size
+    }
+
+    /// Returns the number of reconnection attempts
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin_observer_spectator::SpectatorBuilder;
+    /// # let builder = SpectatorBuilder::new();
+    /// let max_retries = builder.max_retries();
+    /// # assert_eq!(max_retries, 10);
+    /// ```
+    pub fn max_retries(&self) -> u32 {
+        self.max_retries
+    }
+
     /// Returns the selected metrics
     ///
     /// # Example
@@ -236,7 +304,14 @@ impl SpectatorBuilder {
     /// ```
     pub fn build(self) -> Spectator {
         let (tx, rx) = tokio::sync::mpsc::channel(self.capacity);
-        let thread_handle = std::thread::spawn(move || sender(rx, self.host, self.port));
+        let config = SenderConfig {
+            host: self.host,
+            port: self.port,
+            capacity: self.capacity,
+            batch_size: self.batch_size,
+            max_retries: self.max_retries,
+        };
+        let thread_handle = std::thread::spawn(move || sender(rx, config));
 
         Spectator {
             tx,
@@ -5,28 +5,279 @@
 // http://opensource.org/licenses/MIT>, at your option. This file may not be
 // copied, modified, or distributed except according to those terms.
 
+use std::{collections::VecDeque, time::Duration};
+
+use bytes::Bytes;
 use futures::SinkExt;
 use spectator::Message;
-use tokio::net::TcpStream;
+use tokio::{
+    net::TcpStream,
+    time::{sleep_until, Instant},
+};
 use tokio_util::codec::{Framed, LengthDelimitedCodec};
 
+/// Delay before the first reconnection attempt
+const INITIAL_BACKOFF: Duration = Duration::from_millis(100);
+/// Maximum delay between two reconnection attempts
+const MAX_BACKOFF: Duration = Duration::from_secs(10);
+
+/// Settings of the sender
+pub(crate) struct SenderConfig {
+    pub host: String,
+    pub port: u16,
+    /// Maximum number of messages kept while the connection is lost
+    pub capacity: usize,
+    /// Maximum number of messages sent at once
+    pub batch_size: usize,
+    /// Number of failed reconnection attempts after which the sender gives up
+    pub max_retries: u32,
+}
+
+/// Messages waiting to be sent
+struct Buffer {
+    messages: VecDeque<Message>,
+    capacity: usize,
+    /// `NewRun` message of this run, which needs to be sent again after reconnecting
+    new_run: Option<Message>,
+}
+
+impl Buffer {
+    fn new(capacity: usize) -> Self {
+        Buffer {
+            messages: VecDeque::new(),
+            capacity: capacity.max(1),
+            new_run: None,
+        }
+    }
+
+    /// Adds a message; if the buffer is full, the oldest message is dropped.
+    fn push(&mut self, msg: Message) {
+        if let Message::NewRun { .. } = msg {
+            self.new_run = Some(msg.clone());
+        }
+        if self.messages.len() >= self.capacity {
+            self.messages.pop_front();
+        }
+        self.messages.push_back(msg);
+    }
+
+    /// Makes sure that a (restarted) Spectator instance is informed about the run before it
+    /// receives any other messages.
+    fn reconnected(&mut self) {
+        if let Some(new_run) = &self.new_run {
+            if !matches!(self.messages.front(), Some(Message::NewRun { .. })) {
+                self.messages.push_front(new_run.clone());
+            }
+        }
+    }
+}
+
+/// Sends up to `batch_size` messages and flushes the stream afterwards.
+///
+/// The messages are only removed from the buffer if sending succeeded. Returns `true` if a
+/// `Termination` message was sent.
+async fn send_batch(
+    stream: &mut Framed<TcpStream, LengthDelimitedCodec>,
+    buffer: &mut Buffer,
+    batch_size: usize,
+) -> Result<bool, anyhow::Error> {
+    let n = buffer.messages.len().min(batch_size.max(1));
+    for msg in buffer.messages.iter().take(n) {
+        stream.feed(msg.pack()?).await?;
+    }
+    SinkExt::<Bytes>::flush(stream).await?;
+    Ok(buffer
+        .messages
+        .drain(..n)
+        .any(|msg| matches!(msg, Message::Termination { .. })))
+}
+
+/// Receives messages from the observer and sends them to Spectator in batches.
+///
+/// If the connection is lost (for instance because Spectator was restarted), the messages are
+/// buffered and reconnection is attempted with exponential backoff. After reconnecting, the
+/// `NewRun` message is sent again followed by the buffered messages.
 #[tokio::main(flavor = "current_thread")]
 pub(crate) async fn sender(
     mut rx: tokio::sync::mpsc::Receiver<Message>,
-    host: String,
-    port: u16,
+    config: SenderConfig,
 ) -> Result<(), anyhow::Error> {
-    let codec = LengthDelimitedCodec::new();
-    if let Ok(stream) = TcpStream::connect(format!("{host}:{port}")).await {
-        let mut stream = Framed::new(stream, codec);
-        while let Some(msg) = rx.recv().await {
-            stream.send(msg.pack()?).await?;
-            if let Message::Termination { .. } = msg {
+    let address = format!("{}:{}", config.host, config.port);
+    let mut stream = match TcpStream::connect(&address).await {
+        Ok(stream) => Some(Framed::new(stream, LengthDelimitedCodec::new())),
+        Err(_) => {
+            eprintln!("Can't connect to spectator on {address}");
+            return Ok(());
+        }
+    };
+    let mut buffer = Buffer::new(config.capacity);
+    let mut closed = false;
+    let mut retries = 0;
+    let mut backoff = INITIAL_BACKOFF;
+    let mut retry_at = Instant::now();
+
+    loop {
+        if stream.is_none() && Instant::now() >= retry_at {
+            match TcpStream::connect(&address).await {
+                Ok(s) => {
+                    stream = Some(Framed::new(s, LengthDelimitedCodec::new()));
+                    buffer.reconnected();
+                    retries = 0;
+                    backoff = INITIAL_BACKOFF;
+                }
+                Err(e) => {
+                    retries += 1;
+                    if retries > config.max_retries {
+                        eprintln!("Can't reconnect to spectator on {address}: {e}. Giving up.");
+                        return Ok(());
+                    }
+                    retry_at = Instant::now() + backoff;
+                    backoff = (backoff * 2).min(MAX_BACKOFF);
+                }
+            }
+        }
+
+        if let Some(s) = stream.as_mut() {
+            if !buffer.messages.is_empty() {
+                match send_batch(s, &mut buffer, config.batch_size).await {
+                    Ok(true) => return Ok(()),
+                    Ok(false) => {}
+                    Err(e) => {
+                        eprintln!("Lost connection to spectator on {address}: {e}. Reconnecting.");
+                        stream = None;
+                        retry_at = Instant::now();
+                    }
+                }
+            }
+        }
+
+        if closed {
+            if buffer.messages.is_empty() {
                 return Ok(());
             }
+            if stream.is_none() {
+                sleep_until(retry_at).await;
+            }
+            continue;
+        }
+
+        // Wait for new messages unless there are still messages to be sent. While disconnected,
+        // also wake up for the next reconnection attempt.
+        if buffer.messages.is_empty() || stream.is_none() {
+            tokio::select! {
+                msg = rx.recv() => match msg {
+                    Some(msg) => buffer.push(msg),
+                    None => closed = true,
+                },
+                _ = sleep_until(retry_at), if stream.is_none() => {}
+            }
+        }
+        // Collect everything which is already available into the next batch
+        while buffer.messages.len() < config.batch_size {
+            match rx.try_recv() {
+                Ok(msg) => buffer.push(msg),
+                Err(_) => break,
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use argmin::core::{TerminationReason, TerminationStatus, KV};
+    use bytes::BytesMut;
+    use std::collections::HashSet;
+    use std::io::Read;
+    use std::net::{TcpListener, TcpStream};
+
+    fn new_run() -> Message {
+        Message::NewRun {
+            name: "test".to_string(),
+            solver: "solver".to_string(),
+            max_iter: 10,
+            target_cost: 0.0,
+            init_param: None,
+            settings: KV::new(),
+            selected: HashSet::new(),
+        }
+    }
+
+    fn param(iter: u64) -> Message {
+        Message::Param {
+            name: "test".to_string(),
+            iter,
+            param: vec![1.0],
+        }
+    }
+
+    fn termination() -> Message {
+        Message::Termination {
+            name: "test".to_string(),
+            termination_status: TerminationStatus::Terminated(TerminationReason::MaxItersReached),
+        }
+    }
+
+    fn read_msg(stream: &mut TcpStream) -> Message {
+        let mut len = [0u8; 4];
+        stream.read_exact(&mut len).unwrap();
+        let mut buf = vec![0u8; u32::from_be_bytes(len) as usize];
+        stream.read_exact(&mut buf).unwrap();
+        Message::unpack(&BytesMut::from(&buf[..])).unwrap()
+    }
+
+    #[test]
+    fn test_buffer() {
+        let mut buffer = Buffer::new(2);
+        buffer.push(new_run());
+        buffer.push(param(1));
+        buffer.push(param(2));
+        assert_eq!(buffer.messages, [param(1), param(2)]);
+        buffer.reconnected();
+        assert_eq!(buffer.messages, [new_run(), param(1), param(2)]);
+        buffer.reconnected();
+        assert_eq!(buffer.messages, [new_run(), param(1), param(2)]);
+    }
+
+    #[test]
+    fn test_reconnect() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let (tx, rx) = tokio::sync::mpsc::channel(100);
+        let config = SenderConfig {
+            host: "127.0.0.1".to_string(),
+            port,
+            capacity: 100,
+            batch_size: 10,
+            max_retries: 100,
+        };
+        let handle = std::thread::spawn(move || sender(rx, config));
+
+        let (mut conn, _) = listener.accept().unwrap();
+        tx.blocking_send(new_run()).unwrap();
+        tx.blocking_send(param(1)).unwrap();
+        assert_eq!(read_msg(&mut conn), new_run());
+        assert_eq!(read_msg(&mut conn), param(1));
+
+        // Simulate a restart of Spectator
+        drop(conn);
+        drop(listener);
+        // Keep sending until the sender notices the lost connection
+        for iter in 2..50 {
+            tx.blocking_send(param(iter)).unwrap();
+            std::thread::sleep(Duration::from_millis(5));
+        }
+        let listener = TcpListener::bind(("127.0.0.1", port)).unwrap();
+        let (mut conn, _) = listener.accept().unwrap();
+        assert_eq!(read_msg(&mut conn), new_run());
+        tx.blocking_send(termination()).unwrap();
+        loop {
+            let msg = read_msg(&mut conn);
+            if msg == termination() {
+                break;
+            }
+            assert!(matches!(msg, Message::Param { .. }));
         }
-    } else {
-        eprintln!("Can't connect to spectator on {host}:{port}");
+        handle.join().unwrap().unwrap();
     }
-    Ok(())
 }
@@ -0,0 +1,514 @@
+// Copyright 2018-2024 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+//! Records optimization runs and their metrics in a [SQLite](https://www.sqlite.org/) database.
+//!
+//! See documentation of [`SqliteTracker`] for details.
+//!
+//! # Usage
+//!
+//! Add the following line to your dependencies list:
+//!
+//! ```toml
+//! [dependencies]
+#![doc = concat!("argmin-observer-sqlite = \"", env!("CARGO_PKG_VERSION"), "\"")]
+//! ```
+//!
+//! # License
+//!
+//! Licensed under either of
+//!
+//!   * Apache License, Version 2.0,
+//!     ([LICENSE-APACHE](https://github.com/argmin-rs/argmin/blob/main/LICENSE-APACHE) or
+//!     <http://www.apache.org/licenses/LICENSE-2.0>)
+//!   * MIT License ([LICENSE-MIT](https://github.com/argmin-rs/argmin/blob/main/LICENSE-MIT) or
+//!     <http://opensource.org/licenses/MIT>)
+//!
+//! at your option.
+//!
+//! ## Contribution
+//!
+//! Unless you explicitly state otherwise, any contribution intentionally submitted for inclusion
+//! in the work by you, as defined in the Apache-2.0 license, shall be dual licensed as above,
+//! without any additional terms or conditions.
+
+use argmin::core::observers::Observe;
+use argmin::core::{Error, KvValue, State, KV};
+use rusqlite::types::Value;
+use rusqlite::{params, Connection};
+use std::path::Path;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Database schema. All statements are idempotent such that several runs can be recorded in the
+/// same database.
+const SCHEMA: &str = "
+CREATE TABLE IF NOT EXISTS runs (
+    run_id TEXT PRIMARY KEY,
+    name TEXT,
+    solver TEXT,
+    config TEXT,
+    seed INTEGER,
+    started_at REAL,
+    finished_at REAL,
+    iterations INTEGER,
+    best_cost REAL,
+    termination TEXT
+);
+CREATE TABLE IF NOT EXISTS run_metadata (
+    run_id TEXT NOT NULL REFERENCES runs(run_id),
+    key TEXT NOT NULL,
+    value TEXT,
+    PRIMARY KEY (run_id, key)
+);
+CREATE TABLE IF NOT EXISTS iterations (
+    run_id TEXT NOT NULL REFERENCES runs(run_id),
+    iter INTEGER NOT NULL,
+    cost REAL,
+    best_cost REAL,
+    time REAL,
+    PRIMARY KEY (run_id, iter)
+);
+CREATE TABLE IF NOT EXISTS metrics (
+    run_id TEXT NOT NULL REFERENCES runs(run_id),
+    iter INTEGER NOT NULL,
+    key TEXT NOT NULL,
+    value,
+    PRIMARY KEY (run_id, iter, key)
+);
+";
+
+/// Record optimization runs and per-iteration metrics in a SQLite database.
+///
+/// Each instance records a single run which is identified by a unique run ID (see
+/// [`SqliteTracker::run_id`]). Any number of runs can be recorded in the same database file. The
+/// database contains the following tables:
+///
+/// * `runs`: one row per run with `run_id`, `name`, `solver`, `config`, `seed`, `started_at`,
+///   `finished_at` (both in seconds since the UNIX epoch), `iterations`, `best_cost` and
+///   `termination` (the termination reason). The last four columns are set at the end of the run.
+/// * `run_metadata`: `(run_id, key, value)` rows holding the KV entries provided by the solver
+///   during initialization as well as user defined metadata (see [`SqliteTracker::metadata`]).
+/// * `iterations`: one row per observed iteration with `run_id`, `iter`, `cost`, `best_cost` and
+///   `time` (elapsed time in seconds).
+/// * `metrics`: `(run_id, iter, key, value)` rows holding all KV entries and function evaluation
+///   counts (e.g. `cost_count`) of each observed iteration.
+///
+/// Rows are written in transactions which are committed every
+/// [`commit_every`](`SqliteTracker::commit_every`) iterations and at the end of the run.
+///
+/// # Example
+///
+/// ```
+/// use argmin_observer_sqlite::SqliteTracker;
+/// # use argmin::core::Error;
+///
+/// # fn run() -> Result<(), Error> {
+/// # let dir = std::env::temp_dir().join(format!("argmin_observer_sqlite_doc_{}", std::process::id()));
+/// # std::fs::create_dir_all(&dir)?;
+/// # let path = dir.join("experiments.db");
+/// let observer = SqliteTracker::new(&path)?
+///     .name("rosenbrock-lbfgs")
+///     .config(r#"{"m": 7}"#)
+///     .seed(42)
+///     .metadata("problem", "rosenbrock");
+///
+/// // The run ID allows to find this run in the database later on
+/// let run_id = observer.run_id().to_string();
+/// # std::fs::remove_dir_all(&dir)?;
+/// # Ok(())
+/// # }
+/// # run().unwrap();
+/// ```
+///
+/// Runs can then be compared with SQL, for instance:
+///
+/// ```sql
+/// SELECT name, seed, iterations, best_cost FROM runs WHERE solver = 'L-BFGS' ORDER BY best_cost;
+/// ```
+pub struct SqliteTracker {
+    /// Connection to the database
+    conn: Connection,
+    /// Unique ID of the run
+    run_id: String,
+    /// Human readable name of the run
+    name: Option<String>,
+    /// Solver configuration
+    config: Option<String>,
+    /// Seed of the random number generator
+    seed: Option<u64>,
+    /// User defined metadata
+    metadata: Vec<(String, String)>,
+    /// Number of iterations per transaction
+    commit_every: u64,
+    /// Number of iterations in the currently open transaction
+    pending: u64,
+    /// Whether the run was already inserted into the `runs` table
+    started: bool,
+}
+
+impl SqliteTracker {
+    /// Open (or create) the database at `path` and prepare recording a new run.
+    ///
+    /// A new unique run ID is generated.
+    pub fn new<P: AsRef<Path>>(path: P) -> Result<Self, Error> {
+        let conn = Connection::open(path)?;
+        conn.pragma_update(None, "journal_mode", "WAL")?;
+        conn.execute_batch(SCHEMA)?;
+        Ok(SqliteTracker {
+            conn,
+            run_id: uuid::Uuid::new_v4().to_string(),
+            name: None,
+            config: None,
+            seed: None,
+            metadata: vec![],
+            commit_every: 100,
+            pending: 0,
+            started: false,
+        })
+    }
+
+    /// Returns the unique ID of the run
+    pub fn run_id(&self) -> &str {
+        &self.run_id
+    }
+
+    /// Set a human readable name of the run
+    #[must_use]
+    pub fn name<S: Into<String>>(mut self, name: S) -> Self {
+        self.name = Some(name.into());
+        self
+    }
+
+    /// Set the configuration of the solver (for instance a serialized configuration struct)
+    #[must_use]
+    pub fn config<S: Into<String>>(mut self, config: S) -> Self {
+        self.config = Some(config.into());
+        self
+    }
+
+    /// Set the seed of the random number generator used in the run
+    ///
+    /// Since SQLite only supports signed 64 bit integers, the seed is stored with the same bit
+    /// pattern as an `i64`.
+    #[must_use]
+    pub fn seed(mut self, seed: u64) -> Self {
+        self.seed = Some(seed);
+        self
+    }
+
+    /// Add a user defined metadata entry
+    ///
+    /// User defined entries take precedence over KV entries of the same name provided by the
+    /// solver during initialization.
+    #[must_use]
+    pub fn metadata<K: Into<String>, V: Into<String>>(mut self, key: K, value: V) -> Self {
+        self.metadata.push((key.into(), value.into()));
+        self
+    }
+
+    /// Set the number of iterations which are written in a single transaction (default: 100).
+    ///
+    /// # Panics
+    ///
+    /// Panics if `iters` is 0.
+    #[must_use]
+    pub fn commit_every(mut self, iters: u64) -> Self {
+        assert!(
+            iters > 0,
+            "number of iterations per transaction must be larger than 0"
+        );
+        self.commit_every = iters;
+        self
+    }
+
+    /// Insert the run into the `runs` table
+    fn start(&mut self, solver: Option<&str>, kv: &KV) -> Result<(), Error> {
+        let seed = self.seed.map(|s| s as i64);
+        self.conn.execute(
+            "INSERT INTO runs (run_id, name, solver, config, seed, started_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+            params![self.run_id, self.name, solver, self.config, seed, now()],
+        )?;
+        let mut stmt = self.conn.prepare(
+            "INSERT OR REPLACE INTO run_metadata (run_id, key, value) VALUES (?1, ?2, ?3)",
+        )?;
+        for (key, value) in kv.kv.iter() {
+            stmt.execute(params![self.run_id, key, value.as_string()])?;
+        }
+        for (key, value) in self.metadata.iter() {
+            stmt.execute(params![self.run_id, key, value])?;
+        }
+        drop(stmt);
+        self.started = true;
+        Ok(())
+    }
+
+    /// Commit the currently open transaction
+    fn commit(&mut self) -> Result<(), Error> {
+        if self.pending > 0 {
+            self.conn.execute_batch("COMMIT")?;
+            self.pending = 0;
+        }
+        Ok(())
+    }
+}
+
+/// Current time in seconds since the UNIX epoch
+fn now() -> f64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs_f64())
+        .unwrap_or(0.0)
+}
+
+/// Convert a `KvValue` into the corresponding SQLite value
+fn to_sql_value(value: &KvValue) -> Value {
+    match value {
+        KvValue::Float(x) => Value::Real(*x),
+        KvValue::Int(x) => Value::Integer(*x),
+        KvValue::Uint(x) => i64::try_from(*x)
+            .map(Value::Integer)
+            .unwrap_or(Value::Real(*x as f64)),
+        KvValue::Bool(x) => Value::Integer(i64::from(*x)),
+        KvValue::Str(x) => Value::Text(x.clone()),
+    }
+}
+
+impl<I> Observe<I> for SqliteTracker
+where
+    I: State,
+    f64: From<I::Float>,
+{
+    fn observe_init(&mut self, name: &str, _state: &I, kv: &KV) -> Result<(), Error> {
+        self.start(Some(name), kv)
+    }
+
+    fn observe_iter(&mut self, state: &I, kv: &KV) -> Result<(), Error> {
+        if !self.started {
+            self.start(None, &KV::new())?;
+        }
+        if self.pending == 0 {
+            self.conn.execute_batch("BEGIN")?;
+        }
+        let iter = state.get_iter() as i64;
+        self.conn.execute(
+            "INSERT OR REPLACE INTO iterations (run_id, iter, cost, best_cost, time)
+             VALUES (?1, ?2, ?3, ?4, ?5)",
+            params![
+                self.run_id,
+                iter,
+                f64::from(state.get_cost()),
+                f64::from(state.get_best_cost()),
+                state.get_time().map(|t| t.as_secs_f64())
+            ],
+        )?;
+        let mut stmt = self.conn.prepare_cached(
+            "INSERT OR REPLACE INTO metrics (run_id, iter, key, value) VALUES (?1, ?2, ?3, ?4)",
+        )?;
+        for (key, value) in kv.kv.iter() {
+            stmt.execute(params![self.run_id, iter, key, to_sql_value(value)])?;
+        }
+        for (key, &count) in state.get_func_counts().iter() {
+            stmt.execute(params![
+                self.run_id,
+                iter,
+                key,
+                to_sql_value(&KvValue::Uint(count))
+            ])?;
+        }
+        drop(stmt);
+        self.pending += 1;
+
+        if self.pending >= self.commit_every {
+            self.commit()?;
+        }
+        Ok(())
+    }
+
+    fn observe_final(&mut self, state: &I) -> Result<(), Error> {
+        self.commit()?;
+        if !self.started {
+            self.start(None, &KV::new())?;
+        }
+        self.conn.execute(
+            "UPDATE runs SET finished_at = ?2, iterations = ?3, best_cost = ?4, termination = ?5
+             WHERE run_id = ?1",
+            params![
+                self.run_id,
+                now(),
+                state.get_iter() as i64,
+                f64::from(state.get_best_cost()),
+                state.get_termination_reason().map(|r| r.to_string())
+            ],
+        )?;
+        Ok(())
+    }
+}
+
+/// Commits the open transaction in case `observe_final` was not called. Errors are ignored.
+impl Drop for SqliteTracker {
+    fn drop(&mut self) {
+        let _ = self.commit();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use argmin::core::{IterState, TerminationReason};
+    use std::path::PathBuf;
+
+    type TestState = IterState<Vec<f64>, (), (), (), (), f64>;
+
+    fn test_file(name: &str) -> PathBuf {
+        let path = std::env::temp_dir().join(format!(
+            "argmin_observer_sqlite_{}_{}.db",
+            name,
+            std::process::id()
+        ));
+        let _ = std::fs::remove_file(&path);
+        path
+    }
+
+    fn run(observer: &mut SqliteTracker, iters: u64) {
+        let mut state = TestState::new();
+        let init_kv = argmin::kv!("m" => 7u64;);
+        observer
+            .observe_init("TestSolver", &state, &init_kv)
+            .unwrap();
+        for i in 0..iters {
+            state = state.cost(10.0 - i as f64);
+            state.best_cost = state.cost;
+            state.iter = i + 1;
+            state.counts.insert("cost_count".to_string(), 2 * (i + 1));
+            let kv = argmin::kv!(
+                "gamma" => 0.5 * i as f64;
+                "restart" => i % 2 == 0;
+                "phase" => "descent";
+            );
+            observer.observe_iter(&state, &kv).unwrap();
+        }
+        state = state.terminate_with(TerminationReason::MaxItersReached);
+        Observe::<TestState>::observe_final(observer, &state).unwrap();
+    }
+
+    #[test]
+    fn test_record_runs() {
+        let path = test_file("record_runs");
+        let mut first = SqliteTracker::new(&path)
+            .unwrap()
+            .name("first")
+            .config("{}")
+            .seed(3)
+            .metadata("m", "user")
+            .commit_every(2);
+        let first_id = first.run_id().to_string();
+        run(&mut first, 5);
+        let mut second = SqliteTracker::new(&path).unwrap().name("second");
+        assert_ne!(first_id, second.run_id());
+        run(&mut second, 3);
+        drop(first);
+        drop(second);
+
+        let conn = Connection::open(&path).unwrap();
+        let runs: Vec<(String, String, Option<i64>, i64, f64, String)> = conn
+            .prepare(
+                "SELECT name, solver, seed, iterations, best_cost, termination FROM runs
+                 ORDER BY name",
+            )
+            .unwrap()
+            .query_map([], |r| {
+                Ok((
+                    r.get(0)?,
+                    r.get(1)?,
+                    r.get(2)?,
+                    r.get(3)?,
+                    r.get(4)?,
+                    r.get(5)?,
+                ))
+            })
+            .unwrap()
+            .collect::<Result<_, _>>()
+            .unwrap();
+        assert_eq!(runs.len(), 2);
+        assert_eq!(
+            runs[0],
+            (
+                "first".to_string(),
+                "TestSolver".to_string(),
+                Some(3),
+                5,
+                6.0,
+                TerminationReason::MaxItersReached.to_string()
+            )
+        );
+        assert_eq!(runs[1].2, None);
+        assert_eq!(runs[1].3, 3);
+
+        // User defined metadata takes precedence over the KV of the solver
+        let m: String = conn
+            .query_row(
+                "SELECT value FROM run_metadata WHERE run_id = ?1 AND key = 'm'",
+                [&first_id],
+                |r| r.get(0),
+            )
+            .unwrap();
+        assert_eq!(m, "user");
+
+        let n: i64 = conn
+            .query_row(
+                "SELECT COUNT(*) FROM iterations WHERE run_id = ?1",
+                [&first_id],
+                |r| r.get(0),
+            )
+            .unwrap();
+        assert_eq!(n, 5);
+
+        let metric = |key: &str, iter: i64| -> Value {
+            conn.query_row(
+                "SELECT value FROM metrics WHERE run_id = ?1 AND iter = ?2 AND key = ?3",
+                params![first_id, iter, key],
+                |r| r.get(0),
+            )
+            .unwrap()
+        };
+        assert_eq!(metric("gamma", 3), Value::Real(1.0));
+        assert_eq!(metric("restart", 3), Value::Integer(1));
+        assert_eq!(metric("phase", 3), Value::Text("descent".to_string()));
+        assert_eq!(metric("cost_count", 4), Value::Integer(8));
+
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn test_commit_on_drop() {
+        let path = test_file("commit_on_drop");
+        {
+            let mut observer = SqliteTracker::new(&path).unwrap();
+            let state = TestState::new().cost(1.0);
+            Observe::<TestState>::observe_iter(&mut observer, &state, &KV::new()).unwrap();
+        }
+        let conn = Connection::open(&path).unwrap();
+        let n: i64 = conn
+            .query_row("SELECT COUNT(*) FROM iterations", [], |r| r.get(0))
+            .unwrap();
+        assert_eq!(n, 1);
+        let solver: Option<String> = conn
+            .query_row("SELECT solver FROM runs", [], |r| r.get(0))
+            .unwrap();
+        assert_eq!(solver, None);
+        drop(conn);
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_commit_every_zero() {
+        let path = test_file("commit_every_zero");
+        let _ = SqliteTracker::new(path).unwrap().commit_every(0);
+    }
+}
@@ -0,0 +1,175 @@
+// Copyright 2018-2024 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+//! Logs optimization runs to experiment trackers.
+//!
+//! * [`MlflowLogger`] logs to an [MLflow](https://mlflow.org/) tracking server via its REST API.
+//! * [`WandbLogger`] logs to [Weights & Biases](https://wandb.ai/).
+//!
+//! Both observers map iterations to steps. In each observed iteration, the cost, the best cost,
+//! the function evaluation counts (e.g. `cost_count`) and all numeric KV entries are logged as
+//! metrics. Non-finite values and string KV entries are skipped. The name of the solver, the KV
+//! entries provided by the solver during initialization (which usually hold its configuration)
+//! and user-defined values are logged as run parameters.
+//!
+//! Metrics are buffered and sent in batches in order to reduce the number of requests. All
+//! requests are blocking; a failing request terminates the optimization run with an error.
+//!
+//! # Usage
+//!
+//! Add the following line to your dependencies list:
+//!
+//! ```toml
+//! [dependencies]
+#![doc = concat!("argmin-observer-tracking = \"", env!("CARGO_PKG_VERSION"), "\"")]
+//! ```
+//!
+//! # License
+//!
+//! Licensed under either of
+//!
+//!   * Apache License, Version 2.0,
+//!     ([LICENSE-APACHE](https://github.com/argmin-rs/argmin/blob/main/LICENSE-APACHE) or
+//!     <http://www.apache.org/licenses/LICENSE-2.0>)
+//!   * MIT License ([LICENSE-MIT](https://github.com/argmin-rs/argmin/blob/main/LICENSE-MIT) or
+//!     <http://opensource.org/licenses/MIT>)
+//!
+//! at your option.
+//!
+//! ## Contribution
+//!
+//! Unless you explicitly state otherwise, any contribution intentionally submitted for inclusion
+//! in the work by you, as defined in the Apache-2.0 license, shall be dual licensed as above,
+//! without any additional terms or conditions.
+
+mod mlflow;
+#[cfg(test)]
+mod testserver;
+mod wandb;
+
+pub use mlflow::MlflowLogger;
+pub use wandb::WandbLogger;
+
+use argmin::core::{Error, State, TerminationReason, KV};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Collect the metrics of an iteration
+///
+/// Returns cost, best cost, function evaluation counts and numeric KV entries. Non-finite values
+/// are skipped.
+fn metrics<I>(state: &I, kv: &KV) -> Vec<(String, f64)>
+where
+    I: State,
+    f64: From<I::Float>,
+{
+    let mut out = vec![
+        ("cost".to_string(), f64::from(state.get_cost())),
+        ("best_cost".to_string(), f64::from(state.get_best_cost())),
+    ];
+    let mut counts: Vec<_> = state.get_func_counts().iter().collect();
+    counts.sort();
+    out.extend(counts.into_iter().map(|(k, &v)| (k.clone(), v as f64)));
+    let mut kv: Vec<_> = kv
+        .kv
+        .iter()
+        .filter_map(|(k, v)| v.get_float().map(|v| (k.clone(), v)))
+        .collect();
+    kv.sort_by(|a, b| a.0.cmp(&b.0));
+    out.extend(kv);
+    out.retain(|(_, v)| v.is_finite());
+    out
+}
+
+/// Collect the run parameters: solver name, KV entries provided at initialization and user
+/// defined parameters (which take precedence)
+fn run_params(solver: &str, kv: &KV, user: &[(String, String)]) -> Vec<(String, String)> {
+    let mut params = vec![("solver".to_string(), solver.to_string())];
+    let mut kv: Vec<_> = kv
+        .kv
+        .iter()
+        .map(|(k, v)| (k.clone(), v.as_string()))
+        .collect();
+    kv.sort();
+    params.extend(kv);
+    for (key, value) in user {
+        params.retain(|(k, _)| k != key);
+        params.push((key.clone(), value.clone()));
+    }
+    params
+}
+
+/// Returns `true` if the run terminated regularly
+fn terminated_regularly(reason: Option<&TerminationReason>) -> bool {
+    !matches!(
+        reason,
+        Some(TerminationReason::Interrupt)
+            | Some(TerminationReason::InvalidNumericValue(_))
+            | Some(TerminationReason::LineSearchFailed(_))
+    )
+}
+
+/// Current time since the UNIX epoch
+fn now() -> std::time::Duration {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+}
+
+/// Convert an error returned by `ureq` into an `Error`, including the response body if available
+fn request_error(service: &str, endpoint: &str, err: ureq::Error) -> Error {
+    match err {
+        ureq::Error::Status(code, response) => Error::msg(format!(
+            "{service} request to `{endpoint}` failed with status {code}: {}",
+            response.into_string().unwrap_or_default()
+        )),
+        err => Error::msg(format!("{service} request to `{endpoint}` failed: {err}")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use argmin::core::{IterState, KvValue};
+
+    #[test]
+    fn test_metrics() {
+        let mut state: IterState<Vec<f64>, (), (), (), (), f64> = IterState::new().cost(2.0);
+        state.counts.insert("cost_count".to_string(), 4);
+        let kv = argmin::kv!(
+            "gamma" => 0.5;
+            "restart" => true;
+            "phase" => "descent".to_string();
+            "nan" => f64::NAN;
+        );
+        // best cost is still infinite and therefore skipped
+        assert_eq!(
+            metrics(&state, &kv),
+            vec![
+                ("cost".to_string(), 2.0),
+                ("cost_count".to_string(), 4.0),
+                ("gamma".to_string(), 0.5),
+                ("restart".to_string(), 1.0),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_run_params() {
+        let mut kv = KV::new();
+        kv.insert("m", KvValue::Uint(7));
+        kv.insert("tol", KvValue::Float(1e-6));
+        let params = run_params("L-BFGS", &kv, &[("m".to_string(), "8".to_string())]);
+        assert_eq!(
+            params,
+            vec![
+                ("solver".to_string(), "L-BFGS".to_string()),
+                ("tol".to_string(), "0.000001".to_string()),
+                ("m".to_string(), "8".to_string()),
+            ]
+        );
+    }
+}
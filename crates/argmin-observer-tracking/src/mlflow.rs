@@ -0,0 +1,483 @@
+// Copyright 2018-2024 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use crate::{metrics, now, request_error, run_params};
+use argmin::core::{observers::Observe, Error, State, TerminationReason, KV};
+use serde_json::{json, Value};
+
+/// Maximum number of parameters per `log-batch` request allowed by MLflow
+const MAX_PARAMS_PER_BATCH: usize = 100;
+
+/// Maximum number of metrics per `log-batch` request allowed by MLflow
+const MAX_METRICS_PER_BATCH: usize = 1000;
+
+/// Experiment the run is logged to
+enum Experiment {
+    Id(String),
+    Name(String),
+}
+
+/// Log optimization runs to an [MLflow](https://mlflow.org/) tracking server.
+///
+/// Each instance creates a single MLflow run during initialization of the solver. The name of
+/// the solver, the KV entries provided by the solver during initialization and user defined
+/// parameters (see [`MlflowLogger::param`]) are logged as run parameters. In each observed
+/// iteration, cost, best cost, function evaluation counts and all numeric KV entries are logged
+/// as metrics with the iteration number as step. The solver name is also stored in the tag
+/// `argmin.solver` and the termination reason in the tag `argmin.termination`.
+///
+/// Metrics are buffered and sent via `log-batch` requests every
+/// [`batch_size`](`MlflowLogger::batch_size`) metrics and at the end of the run. The run is then
+/// marked as `FINISHED`, or as `KILLED` if it was interrupted and `FAILED` if it terminated due
+/// to invalid numeric values or a failed line search. If the observer is dropped before the run ended (for instance
+/// because the solver returned an error), buffered metrics are sent and the run is marked as
+/// `FAILED`.
+///
+/// # Example
+///
+/// ```
+/// use argmin_observer_tracking::MlflowLogger;
+///
+/// let observer = MlflowLogger::new("http://localhost:5000")
+///     .experiment_name("rosenbrock")
+///     .run_name("lbfgs-m7")
+///     .param("problem", "rosenbrock")
+///     .tag("owner", "argmin");
+/// ```
+pub struct MlflowLogger {
+    /// HTTP agent
+    agent: ureq::Agent,
+    /// URI of the tracking server
+    tracking_uri: String,
+    /// Experiment the run is logged to
+    experiment: Experiment,
+    /// Name of the run
+    run_name: Option<String>,
+    /// User defined parameters
+    params: Vec<(String, String)>,
+    /// User defined tags
+    tags: Vec<(String, String)>,
+    /// Access token
+    token: Option<String>,
+    /// Number of buffered metrics which triggers a request
+    batch_size: usize,
+    /// ID of the run (available after initialization)
+    run_id: Option<String>,
+    /// Buffered metrics
+    buffer: Vec<Value>,
+    /// Whether the run was marked as terminated
+    finished: bool,
+}
+
+impl MlflowLogger {
+    /// Create a new `MlflowLogger` which logs to the tracking server at `tracking_uri` (for
+    /// instance `http://localhost:5000`).
+    ///
+    /// By default, runs are logged to the default experiment (ID `0`).
+    pub fn new<S: Into<String>>(tracking_uri: S) -> Self {
+        MlflowLogger {
+            agent: ureq::AgentBuilder::new().build(),
+            tracking_uri: tracking_uri.into().trim_end_matches('/').to_string(),
+            experiment: Experiment::Id("0".to_string()),
+            run_name: None,
+            params: vec![],
+            tags: vec![],
+            token: None,
+            batch_size: 100,
+            run_id: None,
+            buffer: vec![],
+            finished: false,
+        }
+    }
+
+    /// Log the run to the experiment with ID `id`
+    #[must_use]
+    pub fn experiment_id<S: Into<String>>(mut self, id: S) -> Self {
+        self.experiment = Experiment::Id(id.into());
+        self
+    }
+
+    /// Log the run to the experiment with name `name`
+    ///
+    /// The experiment is created if it does not exist yet.
+    #[must_use]
+    pub fn experiment_name<S: Into<String>>(mut self, name: S) -> Self {
+        self.experiment = Experiment::Name(name.into());
+        self
+    }
+
+    /// Set the name of the run
+    #[must_use]
+    pub fn run_name<S: Into<String>>(mut self, name: S) -> Self {
+        self.run_name = Some(name.into());
+        self
+    }
+
+    /// Add a run parameter
+    ///
+    /// Takes precedence over a KV entry of the solver with the same key.
+    #[must_use]
+    pub fn param<K: Into<String>, V: Into<String>>(mut self, key: K, value: V) -> Self {
+        self.params.push((key.into(), value.into()));
+        self
+    }
+
+    /// Add a run tag
+    #[must_use]
+    pub fn tag<K: Into<String>, V: Into<String>>(mut self, key: K, value: V) -> Self {
+        self.tags.push((key.into(), value.into()));
+        self
+    }
+
+    /// Set a token which is sent as bearer token in the `Authorization` header
+    #[must_use]
+    pub fn token<S: Into<String>>(mut self, token: S) -> Self {
+        self.token = Some(token.into());
+        self
+    }
+
+    /// Set the number of buffered metrics which triggers a request (default: 100)
+    ///
+    /// # Panics
+    ///
+    /// Panics if `batch_size` is zero or larger than 1000 (the maximum allowed by MLflow).
+    #[must_use]
+    pub fn batch_size(mut self, batch_size: usize) -> Self {
+        assert!(
+            (1..=MAX_METRICS_PER_BATCH).contains(&batch_size),
+            "batch_size must be in [1, {MAX_METRICS_PER_BATCH}]"
+        );
+        self.batch_size = batch_size;
+        self
+    }
+
+    /// Returns the ID of the run, which is only available after the solver was initialized
+    pub fn run_id(&self) -> Option<&str> {
+        self.run_id.as_deref()
+    }
+
+    fn request(&self, method: &str, endpoint: &str) -> ureq::Request {
+        let request = self.agent.request(
+            method,
+            &format!("{}/api/2.0/mlflow/{endpoint}", self.tracking_uri),
+        );
+        match self.token.as_ref() {
+            Some(token) => request.set("Authorization", &format!("Bearer {token}")),
+            None => request,
+        }
+    }
+
+    fn post(&self, endpoint: &str, body: Value) -> Result<Value, Error> {
+        self.request("POST", endpoint)
+            .send_json(body)
+            .map_err(|e| request_error("MLflow", endpoint, e))?
+            .into_json()
+            .map_err(Error::from)
+    }
+
+    /// Returns the ID of the experiment, looking it up (or creating it) by name if necessary
+    fn experiment_id_resolved(&self) -> Result<String, Error> {
+        let name = match &self.experiment {
+            Experiment::Id(id) => return Ok(id.clone()),
+            Experiment::Name(name) => name,
+        };
+        let endpoint = "experiments/get-by-name";
+        let response = match self
+            .request("GET", endpoint)
+            .query("experiment_name", name)
+            .call()
+        {
+            Ok(response) => response.into_json::<Value>()?,
+            Err(ureq::Error::Status(404, _)) => {
+                self.post("experiments/create", json!({ "name": name }))?
+            }
+            Err(e) => return Err(request_error("MLflow", endpoint, e)),
+        };
+        response
+            .pointer("/experiment/experiment_id")
+            .or_else(|| response.get("experiment_id"))
+            .and_then(Value::as_str)
+            .map(String::from)
+            .ok_or_else(|| Error::msg(format!("MLflow: no ID for experiment `{name}`")))
+    }
+
+    fn run_id_checked(&self) -> Result<&str, Error> {
+        self.run_id
+            .as_deref()
+            .ok_or_else(|| Error::msg("MLflow: run was not initialized"))
+    }
+
+    /// Send all buffered metrics
+    fn flush(&mut self) -> Result<(), Error> {
+        if self.buffer.is_empty() {
+            return Ok(());
+        }
+        let run_id = self.run_id_checked()?.to_string();
+        let metrics = std::mem::take(&mut self.buffer);
+        for chunk in metrics.chunks(MAX_METRICS_PER_BATCH) {
+            self.post(
+                "runs/log-batch",
+                json!({ "run_id": run_id, "metrics": chunk }),
+            )?;
+        }
+        Ok(())
+    }
+
+    /// Send buffered metrics and mark the run as terminated with `status`
+    fn finish(&mut self, status: &str, tags: Vec<(String, String)>) -> Result<(), Error> {
+        self.finished = true;
+        self.flush()?;
+        let run_id = self.run_id_checked()?.to_string();
+        if !tags.is_empty() {
+            self.post(
+                "runs/log-batch",
+                json!({ "run_id": run_id, "tags": key_values(&tags) }),
+            )?;
+        }
+        self.post(
+            "runs/update",
+            json!({
+                "run_id": run_id,
+                "status": status,
+                "end_time": now().as_millis() as u64,
+            }),
+        )?;
+        Ok(())
+    }
+}
+
+fn key_values(entries: &[(String, String)]) -> Vec<Value> {
+    entries
+        .iter()
+        .map(|(k, v)| json!({ "key": k, "value": v }))
+        .collect()
+}
+
+impl<I> Observe<I> for MlflowLogger
+where
+    I: State,
+    f64: From<I::Float>,
+{
+    fn observe_init(&mut self, name: &str, _state: &I, kv: &KV) -> Result<(), Error> {
+        let experiment_id = self.experiment_id_resolved()?;
+        let mut tags = vec![("argmin.solver".to_string(), name.to_string())];
+        tags.extend(self.tags.iter().cloned());
+        let mut body = json!({
+            "experiment_id": experiment_id,
+            "start_time": now().as_millis() as u64,
+            "tags": key_values(&tags),
+        });
+        if let Some(run_name) = &self.run_name {
+            body["run_name"] = json!(run_name);
+        }
+        let response = self.post("runs/create", body)?;
+        let run_id = response
+            .pointer("/run/info/run_id")
+            .and_then(Value::as_str)
+            .ok_or_else(|| Error::msg("MLflow: response of `runs/create` contains no run ID"))?
+            .to_string();
+        self.run_id = Some(run_id.clone());
+        self.finished = false;
+
+        let params = run_params(name, kv, &self.params);
+        for chunk in params.chunks(MAX_PARAMS_PER_BATCH) {
+            self.post(
+                "runs/log-batch",
+                json!({ "run_id": run_id, "params": key_values(chunk) }),
+            )?;
+        }
+        Ok(())
+    }
+
+    fn observe_iter(&mut self, state: &I, kv: &KV) -> Result<(), Error> {
+        let timestamp = now().as_millis() as u64;
+        let step = state.get_iter();
+        self.buffer
+            .extend(metrics(state, kv).into_iter().map(|(key, value)| {
+                json!({ "key": key, "value": value, "timestamp": timestamp, "step": step })
+            }));
+        if self.buffer.len() >= self.batch_size {
+            self.flush()?;
+        }
+        Ok(())
+    }
+
+    fn observe_final(&mut self, state: &I) -> Result<(), Error> {
+        let reason = state.get_termination_reason();
+        let status = match reason {
+            Some(TerminationReason::Interrupt) => "KILLED",
+            Some(
+                TerminationReason::InvalidNumericValue(_) | TerminationReason::LineSearchFailed(_),
+            ) => "FAILED",
+            _ => "FINISHED",
+        };
+        let tags = reason
+            .map(|r| vec![("argmin.termination".to_string(), r.to_string())])
+            .unwrap_or_default();
+        self.finish(status, tags)
+    }
+}
+
+impl Drop for MlflowLogger {
+    fn drop(&mut self) {
+        if self.run_id.is_some() && !self.finished {
+            // Errors cannot be propagated from `drop`
+            let _ = self.finish("FAILED", vec![]);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testserver::TestServer;
+    use argmin::core::IterState;
+
+    type TestState = IterState<Vec<f64>, (), (), (), (), f64>;
+
+    fn handler(method: &str, path: &str, _body: &Value) -> (u16, Value) {
+        match (method, path) {
+            ("GET", p) if p.starts_with("/api/2.0/mlflow/experiments/get-by-name") => {
+                if p.ends_with("experiment_name=existing") {
+                    (200, json!({ "experiment": { "experiment_id": "12" } }))
+                } else {
+                    (404, json!({ "error_code": "RESOURCE_DOES_NOT_EXIST" }))
+                }
+            }
+            ("POST", "/api/2.0/mlflow/experiments/create") => {
+                (200, json!({ "experiment_id": "13" }))
+            }
+            ("POST", "/api/2.0/mlflow/runs/create") => {
+                (200, json!({ "run": { "info": { "run_id": "abc" } } }))
+            }
+            ("POST", "/api/2.0/mlflow/runs/log-batch")
+            | ("POST", "/api/2.0/mlflow/runs/update") => (200, json!({})),
+            _ => (400, json!({ "error_code": "INVALID_PARAMETER_VALUE" })),
+        }
+    }
+
+    #[test]
+    fn test_mlflow_run() {
+        let server = TestServer::start(handler);
+        let mut observer = MlflowLogger::new(server.url())
+            .experiment_name("new")
+            .run_name("test")
+            .param("problem", "sphere")
+            .tag("owner", "me")
+            .token("secret")
+            .batch_size(5);
+        let mut state: TestState = IterState::new();
+        let kv = argmin::kv!("m" => 7u64;);
+        observer.observe_init("L-BFGS", &state, &kv).unwrap();
+        assert_eq!(observer.run_id(), Some("abc"));
+        for iter in 0..3 {
+            state.iter = iter;
+            state.cost = 3.0 - iter as f64;
+            state.best_cost = state.cost;
+            observer
+                .observe_iter(&state, &argmin::kv!("gamma" => 0.5;))
+                .unwrap();
+        }
+        state = state.terminate_with(TerminationReason::MaxItersReached);
+        observer.observe_final(&state).unwrap();
+        drop(observer);
+
+        let requests = server.requests();
+        let paths: Vec<_> = requests.iter().map(|r| r.path.as_str()).collect();
+        assert_eq!(
+            paths,
+            vec![
+                "/api/2.0/mlflow/experiments/get-by-name?experiment_name=new",
+                "/api/2.0/mlflow/experiments/create",
+                "/api/2.0/mlflow/runs/create",
+                // params
+                "/api/2.0/mlflow/runs/log-batch",
+                // three metrics per iteration with a batch size of 5
+                "/api/2.0/mlflow/runs/log-batch",
+                "/api/2.0/mlflow/runs/log-batch",
+                // termination tag
+                "/api/2.0/mlflow/runs/log-batch",
+                "/api/2.0/mlflow/runs/update",
+            ]
+        );
+        assert!(requests
+            .iter()
+            .all(|r| r.header("authorization") == Some("Bearer secret")));
+
+        let create = &requests[2].body;
+        assert_eq!(create["experiment_id"], "13");
+        assert_eq!(create["run_name"], "test");
+        assert_eq!(
+            create["tags"],
+            json!([
+                { "key": "argmin.solver", "value": "L-BFGS" },
+                { "key": "owner", "value": "me" },
+            ])
+        );
+        assert_eq!(
+            requests[3].body["params"],
+            json!([
+                { "key": "solver", "value": "L-BFGS" },
+                { "key": "m", "value": "7" },
+                { "key": "problem", "value": "sphere" },
+            ])
+        );
+
+        let metrics: Vec<_> = requests[4..6]
+            .iter()
+            .flat_map(|r| r.body["metrics"].as_array().unwrap().clone())
+            .collect();
+        assert_eq!(metrics.len(), 9);
+        let gamma: Vec<_> = metrics.iter().filter(|m| m["key"] == "gamma").collect();
+        assert_eq!(gamma.len(), 3);
+        for (step, m) in gamma.iter().enumerate() {
+            assert_eq!(m["step"], step as u64);
+            assert_eq!(m["value"], 0.5);
+        }
+        assert_eq!(
+            requests[6].body["tags"],
+            json!([{ "key": "argmin.termination", "value": "Maximum number of iterations reached" }])
+        );
+        assert_eq!(requests[7].body["run_id"], "abc");
+        assert_eq!(requests[7].body["status"], "FINISHED");
+    }
+
+    #[test]
+    fn test_mlflow_existing_experiment_and_drop() {
+        let server = TestServer::start(handler);
+        let mut observer = MlflowLogger::new(server.url()).experiment_name("existing");
+        let state: TestState = IterState::new().cost(1.0);
+        observer.observe_init("Solver", &state, &KV::new()).unwrap();
+        observer.observe_iter(&state, &KV::new()).unwrap();
+        drop(observer);
+
+        let requests = server.requests();
+        assert_eq!(requests[1].path, "/api/2.0/mlflow/runs/create");
+        assert_eq!(requests[1].body["experiment_id"], "12");
+        assert!(requests[1].header("authorization").is_none());
+        // buffered metric is sent on drop and the run is marked as failed
+        let update = requests.last().unwrap();
+        assert_eq!(update.path, "/api/2.0/mlflow/runs/update");
+        assert_eq!(update.body["status"], "FAILED");
+        let metrics = &requests[requests.len() - 2].body["metrics"];
+        assert_eq!(metrics[0]["key"], "cost");
+        assert_eq!(metrics[0]["value"], 1.0);
+    }
+
+    #[test]
+    fn test_mlflow_error() {
+        let server = TestServer::start(handler);
+        let mut observer = MlflowLogger::new(format!("{}/wrong/", server.url()));
+        let state: TestState = IterState::new();
+        let err = observer
+            .observe_init("Solver", &state, &KV::new())
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("`runs/create` failed with status 400"));
+        assert!(err.contains("INVALID_PARAMETER_VALUE"));
+    }
+}
@@ -0,0 +1,422 @@
+// Copyright 2018-2024 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use crate::{metrics, now, request_error, terminated_regularly};
+use argmin::core::{observers::Observe, Error, KvValue, State, KV};
+use base64::Engine;
+use serde_json::{json, Map, Value};
+use std::time::Instant;
+
+/// GraphQL mutation which creates (or updates) a run
+const UPSERT_RUN: &str = "\
+mutation UpsertBucket($name: String, $project: String, $entity: String, $displayName: String, $config: JSONString) {
+  upsertBucket(input: {name: $name, modelName: $project, entityName: $entity, displayName: $displayName, config: $config}) {
+    bucket { id name displayName }
+  }
+}";
+
+/// Log optimization runs to [Weights & Biases](https://wandb.ai/).
+///
+/// Each instance creates a single W&B run during initialization of the solver. The name of the
+/// solver, the KV entries provided by the solver during initialization and user defined values
+/// (see [`WandbLogger::config`]) are logged as run config. In each observed iteration, cost, best
+/// cost, function evaluation counts and all numeric KV entries are logged as a history row with
+/// the iteration number as step. At the end of the run, the metrics of the last observed
+/// iteration and the termination reason (`termination`) are stored in the run summary.
+///
+/// History rows are buffered and sent every [`batch_size`](`WandbLogger::batch_size`) iterations
+/// and at the end of the run. The run is then marked as finished, with a non-zero exit code if it
+/// was interrupted or terminated due to invalid numeric values. If the observer is dropped before
+/// the run ended (for instance because the solver returned an error), buffered rows are sent and
+/// the run is marked as failed.
+///
+/// # Example
+///
+/// ```
+/// use argmin_observer_tracking::WandbLogger;
+///
+/// let api_key = std::env::var("WANDB_API_KEY").unwrap_or_default();
+/// let observer = WandbLogger::new(api_key, "my-team", "rosenbrock")
+///     .display_name("lbfgs-m7")
+///     .config("problem", "rosenbrock");
+/// ```
+pub struct WandbLogger {
+    /// HTTP agent
+    agent: ureq::Agent,
+    /// URL of the W&B server
+    base_url: String,
+    /// Value of the `Authorization` header
+    auth: String,
+    /// Entity (user or team)
+    entity: String,
+    /// Project
+    project: String,
+    /// ID of the run
+    run_id: String,
+    /// Display name of the run
+    display_name: Option<String>,
+    /// User defined config values
+    config: Vec<(String, Value)>,
+    /// Number of buffered history rows which triggers a request
+    batch_size: usize,
+    /// Start of the run
+    start: Option<Instant>,
+    /// Buffered history rows
+    buffer: Vec<String>,
+    /// Number of history rows sent so far
+    offset: usize,
+    /// Metrics of the last observed iteration
+    summary: Map<String, Value>,
+    /// Whether the run was marked as finished
+    finished: bool,
+}
+
+impl WandbLogger {
+    /// Create a new `WandbLogger` which logs to project `project` of entity `entity` (a user or a
+    /// team) using the API key `api_key`.
+    ///
+    /// A new unique run ID is generated.
+    pub fn new<K, E, P>(api_key: K, entity: E, project: P) -> Self
+    where
+        K: AsRef<str>,
+        E: Into<String>,
+        P: Into<String>,
+    {
+        let credentials =
+            base64::engine::general_purpose::STANDARD.encode(format!("api:{}", api_key.as_ref()));
+        WandbLogger {
+            agent: ureq::AgentBuilder::new().build(),
+            base_url: "https://api.wandb.ai".to_string(),
+            auth: format!("Basic {credentials}"),
+            entity: entity.into(),
+            project: project.into(),
+            run_id: uuid::Uuid::new_v4().simple().to_string()[..8].to_string(),
+            display_name: None,
+            config: vec![],
+            batch_size: 10,
+            start: None,
+            buffer: vec![],
+            offset: 0,
+            summary: Map::new(),
+            finished: false,
+        }
+    }
+
+    /// Set the URL of the W&B server (default: `https://api.wandb.ai`)
+    #[must_use]
+    pub fn base_url<S: Into<String>>(mut self, base_url: S) -> Self {
+        self.base_url = base_url.into().trim_end_matches('/').to_string();
+        self
+    }
+
+    /// Set the display name of the run
+    #[must_use]
+    pub fn display_name<S: Into<String>>(mut self, name: S) -> Self {
+        self.display_name = Some(name.into());
+        self
+    }
+
+    /// Add a config value
+    ///
+    /// Takes precedence over a KV entry of the solver with the same key.
+    #[must_use]
+    pub fn config<K: Into<String>, V: Into<Value>>(mut self, key: K, value: V) -> Self {
+        self.config.push((key.into(), value.into()));
+        self
+    }
+
+    /// Set the number of buffered history rows (iterations) which triggers a request
+    /// (default: 10)
+    ///
+    /// # Panics
+    ///
+    /// Panics if `batch_size` is zero.
+    #[must_use]
+    pub fn batch_size(mut self, batch_size: usize) -> Self {
+        assert!(batch_size > 0, "batch_size must be positive");
+        self.batch_size = batch_size;
+        self
+    }
+
+    /// Returns the ID of the run
+    pub fn run_id(&self) -> &str {
+        &self.run_id
+    }
+
+    fn post(&self, endpoint: &str, body: Value) -> Result<Value, Error> {
+        self.agent
+            .post(&format!("{}/{endpoint}", self.base_url))
+            .set("Authorization", &self.auth)
+            .send_json(body)
+            .map_err(|e| request_error("W&B", endpoint, e))?
+            .into_json()
+            .map_err(Error::from)
+    }
+
+    fn file_stream(&self, files: Value, extra: Value) -> Result<(), Error> {
+        let endpoint = format!(
+            "files/{}/{}/{}/file_stream",
+            self.entity, self.project, self.run_id
+        );
+        let mut body = json!({ "files": files });
+        if let (Some(body), Value::Object(extra)) = (body.as_object_mut(), extra) {
+            body.extend(extra);
+        }
+        self.post(&endpoint, body)?;
+        Ok(())
+    }
+
+    /// Send all buffered history rows
+    fn flush(&mut self) -> Result<(), Error> {
+        if self.buffer.is_empty() {
+            return Ok(());
+        }
+        let rows = std::mem::take(&mut self.buffer);
+        self.file_stream(
+            json!({ "wandb-history.jsonl": { "offset": self.offset, "content": rows } }),
+            json!({}),
+        )?;
+        self.offset += rows.len();
+        Ok(())
+    }
+
+    /// Send buffered history rows and the summary and mark the run as finished
+    fn finish(&mut self, exitcode: i32) -> Result<(), Error> {
+        self.finished = true;
+        self.flush()?;
+        let summary = Value::Object(self.summary.clone()).to_string();
+        self.file_stream(
+            json!({ "wandb-summary.json": { "offset": 0, "content": [summary] } }),
+            json!({}),
+        )?;
+        self.file_stream(json!({}), json!({ "complete": true, "exitcode": exitcode }))
+    }
+}
+
+fn kv_to_json(value: &KvValue) -> Value {
+    match value {
+        KvValue::Float(x) => json!(x),
+        KvValue::Int(x) => json!(x),
+        KvValue::Uint(x) => json!(x),
+        KvValue::Bool(x) => json!(x),
+        KvValue::Str(x) => json!(x),
+    }
+}
+
+impl<I> Observe<I> for WandbLogger
+where
+    I: State,
+    f64: From<I::Float>,
+{
+    fn observe_init(&mut self, name: &str, _state: &I, kv: &KV) -> Result<(), Error> {
+        let mut config = Map::new();
+        config.insert("solver".to_string(), json!(name));
+        config.extend(kv.kv.iter().map(|(k, v)| (k.clone(), kv_to_json(v))));
+        config.extend(self.config.iter().cloned());
+        let config: Map<String, Value> = config
+            .into_iter()
+            .map(|(k, v)| (k, json!({ "value": v })))
+            .collect();
+
+        let response = self.post(
+            "graphql",
+            json!({
+                "query": UPSERT_RUN,
+                "variables": {
+                    "name": self.run_id,
+                    "project": self.project,
+                    "entity": self.entity,
+                    "displayName": self.display_name,
+                    "config": Value::Object(config).to_string(),
+                },
+            }),
+        )?;
+        if let Some(errors) = response.get("errors") {
+            return Err(Error::msg(format!("W&B: creating run failed: {errors}")));
+        }
+        self.start = Some(Instant::now());
+        self.finished = false;
+        Ok(())
+    }
+
+    fn observe_iter(&mut self, state: &I, kv: &KV) -> Result<(), Error> {
+        let mut row = Map::new();
+        for (key, value) in metrics(state, kv) {
+            row.insert(key, json!(value));
+        }
+        self.summary = row.clone();
+        row.insert("_step".to_string(), json!(state.get_iter()));
+        row.insert(
+            "_runtime".to_string(),
+            json!(self.start.map(|s| s.elapsed().as_secs_f64()).unwrap_or(0.0)),
+        );
+        row.insert("_timestamp".to_string(), json!(now().as_secs_f64()));
+        self.buffer.push(Value::Object(row).to_string());
+        if self.buffer.len() >= self.batch_size {
+            self.flush()?;
+        }
+        Ok(())
+    }
+
+    fn observe_final(&mut self, state: &I) -> Result<(), Error> {
+        let reason = state.get_termination_reason();
+        if let Some(reason) = reason {
+            self.summary
+                .insert("termination".to_string(), json!(reason.to_string()));
+        }
+        self.finish(if terminated_regularly(reason) { 0 } else { 1 })
+    }
+}
+
+impl Drop for WandbLogger {
+    fn drop(&mut self) {
+        if self.start.is_some() && !self.finished {
+            // Errors cannot be propagated from `drop`
+            let _ = self.finish(1);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testserver::TestServer;
+    use argmin::core::{IterState, TerminationReason};
+
+    type TestState = IterState<Vec<f64>, (), (), (), (), f64>;
+
+    fn handler(method: &str, path: &str, body: &Value) -> (u16, Value) {
+        match (method, path) {
+            ("POST", "/graphql") if body["variables"]["entity"] == "team" => {
+                (200, json!({ "data": { "upsertBucket": { "bucket": {} } } }))
+            }
+            ("POST", "/graphql") => (200, json!({ "errors": [{ "message": "not found" }] })),
+            ("POST", p) if p.starts_with("/files/team/project/") => (200, json!({})),
+            _ => (404, json!({})),
+        }
+    }
+
+    fn history(body: &Value) -> Vec<Value> {
+        body["files"]["wandb-history.jsonl"]["content"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|row| serde_json::from_str(row.as_str().unwrap()).unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn test_wandb_run() {
+        let server = TestServer::start(handler);
+        let mut observer = WandbLogger::new("key", "team", "project")
+            .base_url(server.url())
+            .display_name("test")
+            .config("m", 8)
+            .config("problem", "sphere")
+            .batch_size(2);
+        let run_id = observer.run_id().to_string();
+        assert_eq!(run_id.len(), 8);
+
+        let mut state: TestState = IterState::new();
+        let kv = argmin::kv!("m" => 7u64; "tol" => 1e-6;);
+        observer.observe_init("L-BFGS", &state, &kv).unwrap();
+        for iter in 0..3 {
+            state.iter = iter;
+            state.cost = 3.0 - iter as f64;
+            state.best_cost = state.cost;
+            observer.observe_iter(&state, &KV::new()).unwrap();
+        }
+        state = state.terminate_with(TerminationReason::SolverConverged);
+        observer.observe_final(&state).unwrap();
+        drop(observer);
+
+        let requests = server.requests();
+        let stream = format!("/files/team/project/{run_id}/file_stream");
+        let paths: Vec<_> = requests.iter().map(|r| r.path.as_str()).collect();
+        assert_eq!(paths, vec!["/graphql", &stream, &stream, &stream, &stream]);
+        // `api:key` in base64
+        assert!(requests
+            .iter()
+            .all(|r| r.header("authorization") == Some("Basic YXBpOmtleQ==")));
+
+        let vars = &requests[0].body["variables"];
+        assert_eq!(vars["name"], json!(run_id));
+        assert_eq!(vars["displayName"], "test");
+        let config: Value = serde_json::from_str(vars["config"].as_str().unwrap()).unwrap();
+        assert_eq!(
+            config,
+            json!({
+                "solver": { "value": "L-BFGS" },
+                "m": { "value": 8 },
+                "tol": { "value": 1e-6 },
+                "problem": { "value": "sphere" },
+            })
+        );
+
+        let first = history(&requests[1].body);
+        assert_eq!(
+            requests[1].body["files"]["wandb-history.jsonl"]["offset"],
+            0
+        );
+        assert_eq!(first.len(), 2);
+        assert_eq!(first[1]["_step"], 1);
+        assert_eq!(first[1]["cost"], 2.0);
+        assert_eq!(first[1]["best_cost"], 2.0);
+        let second = history(&requests[2].body);
+        assert_eq!(
+            requests[2].body["files"]["wandb-history.jsonl"]["offset"],
+            2
+        );
+        assert_eq!(second.len(), 1);
+        assert_eq!(second[0]["_step"], 2);
+
+        let summary: Value = serde_json::from_str(
+            requests[3].body["files"]["wandb-summary.json"]["content"][0]
+                .as_str()
+                .unwrap(),
+        )
+        .unwrap();
+        assert_eq!(
+            summary,
+            json!({ "cost": 1.0, "best_cost": 1.0, "termination": "Solver converged" })
+        );
+        assert_eq!(requests[4].body["complete"], true);
+        assert_eq!(requests[4].body["exitcode"], 0);
+    }
+
+    #[test]
+    fn test_wandb_drop() {
+        let server = TestServer::start(handler);
+        let mut observer = WandbLogger::new("key", "team", "project").base_url(server.url());
+        let state: TestState = IterState::new().cost(1.0);
+        observer.observe_init("Solver", &state, &KV::new()).unwrap();
+        observer.observe_iter(&state, &KV::new()).unwrap();
+        drop(observer);
+
+        let requests = server.requests();
+        assert_eq!(requests.len(), 4);
+        assert_eq!(history(&requests[1].body)[0]["cost"], 1.0);
+        assert_eq!(requests[3].body["complete"], true);
+        assert_eq!(requests[3].body["exitcode"], 1);
+    }
+
+    #[test]
+    fn test_wandb_error() {
+        let server = TestServer::start(handler);
+        let mut observer = WandbLogger::new("key", "other", "project").base_url(server.url());
+        let state: TestState = IterState::new();
+        let err = observer
+            .observe_init("Solver", &state, &KV::new())
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("creating run failed"));
+        assert!(err.contains("not found"));
+        drop(observer);
+        // nothing is sent on drop if the run was not created
+        assert_eq!(server.requests().len(), 1);
+    }
+}
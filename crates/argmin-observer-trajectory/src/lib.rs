@@ -0,0 +1,355 @@
+// Copyright 2018-2024 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+//! Records the trajectory of the parameter vector during optimization.
+//!
+//! See documentation of [`Trajectory`] for details. With the `plot` feature, trajectories of
+//! two-dimensional problems can be rendered over a contour plot of the cost function via
+//! `ContourPlot`.
+//!
+//! # Usage
+//!
+//! Add the following line to your dependencies list:
+//!
+//! ```toml
+//! [dependencies]
+#![doc = concat!("argmin-observer-trajectory = \"", env!("CARGO_PKG_VERSION"), "\"")]
+//! ```
+//!
+//! For plotting, enable the `plot` feature:
+//!
+//! ```toml
+//! [dependencies]
+#![doc = concat!("argmin-observer-trajectory = { version = \"", env!("CARGO_PKG_VERSION"), "\", features = [\"plot\"] }")]
+//! ```
+//!
+//! # License
+//!
+//! Licensed under either of
+//!
+//!   * Apache License, Version 2.0,
+//!     ([LICENSE-APACHE](https://github.com/argmin-rs/argmin/blob/main/LICENSE-APACHE) or
+//!     <http://www.apache.org/licenses/LICENSE-2.0>)
+//!   * MIT License ([LICENSE-MIT](https://github.com/argmin-rs/argmin/blob/main/LICENSE-MIT) or
+//!     <http://opensource.org/licenses/MIT>)
+//!
+//! at your option.
+//!
+//! ## Contribution
+//!
+//! Unless you explicitly state otherwise, any contribution intentionally submitted for inclusion
+//! in the work by you, as defined in the Apache-2.0 license, shall be dual licensed as above,
+//! without any additional terms or conditions.
+
+#[cfg(feature = "plot")]
+mod plot;
+
+#[cfg(feature = "plot")]
+pub use plot::ContourPlot;
+
+use argmin::core::observers::Observe;
+use argmin::core::{Error, State, KV};
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::Path;
+use std::sync::{Arc, Mutex, MutexGuard};
+
+/// Records the trajectory of the parameter vector during optimization.
+///
+/// The parameter vector and the cost are recorded after initialization and after each observed
+/// iteration. Parameter vectors need to be convertible to a slice of floats (such as `Vec<f64>`
+/// or `Vec<f32>`), their elements are stored as `f64`.
+///
+/// Since the observer is moved into the `Executor`, `Trajectory` is a handle to the recorded
+/// points: Clones share the same points, which can therefore be accessed after the run. The
+/// recorded points are cleared at the start of each run.
+///
+/// The trajectory can be written to a CSV file via [`write_csv`](`Trajectory::write_csv`). With
+/// the `plot` feature, trajectories of two-dimensional problems can be rendered over a contour
+/// plot of the cost function via `ContourPlot`.
+///
+/// # Example
+///
+/// ```
+/// use argmin::core::{observers::ObserverMode, Error, Executor};
+/// use argmin_observer_trajectory::Trajectory;
+/// # use argmin::core::test_utils::{TestProblem, TestSolver};
+///
+/// # fn main() -> Result<(), Error> {
+/// # let problem = TestProblem::new();
+/// # let solver = TestSolver::new();
+/// let trajectory = Trajectory::new();
+///
+/// Executor::new(problem, solver)
+///     .configure(|state| state.param(vec![1.0f64, 2.0]).max_iters(3))
+///     .add_observer(trajectory.clone(), ObserverMode::Always)
+///     .run()?;
+///
+/// // Initial parameter vector and one point per iteration
+/// assert_eq!(trajectory.len(), 4);
+/// assert_eq!(trajectory.points()[0].param, vec![1.0, 2.0]);
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct Trajectory {
+    /// Recorded points, shared between all clones
+    points: Arc<Mutex<Vec<TrajectoryPoint>>>,
+}
+
+/// A single point of a [`Trajectory`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct TrajectoryPoint {
+    /// Iteration number
+    pub iter: u64,
+    /// Parameter vector
+    pub param: Vec<f64>,
+    /// Cost function value
+    pub cost: f64,
+}
+
+impl Trajectory {
+    /// Create a new, empty `Trajectory`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin_observer_trajectory::Trajectory;
+    /// let trajectory = Trajectory::new();
+    /// assert!(trajectory.is_empty());
+    /// ```
+    pub fn new() -> Self {
+        Trajectory::default()
+    }
+
+    /// Returns a copy of the recorded points.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin_observer_trajectory::Trajectory;
+    /// let trajectory = Trajectory::new();
+    /// let points = trajectory.points();
+    /// # assert!(points.is_empty());
+    /// ```
+    pub fn points(&self) -> Vec<TrajectoryPoint> {
+        self.lock().clone()
+    }
+
+    /// Returns the number of recorded points.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin_observer_trajectory::Trajectory;
+    /// let trajectory = Trajectory::new();
+    /// assert_eq!(trajectory.len(), 0);
+    /// ```
+    pub fn len(&self) -> usize {
+        self.lock().len()
+    }
+
+    /// Returns `true` if no points were recorded.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin_observer_trajectory::Trajectory;
+    /// let trajectory = Trajectory::new();
+    /// assert!(trajectory.is_empty());
+    /// ```
+    pub fn is_empty(&self) -> bool {
+        self.lock().is_empty()
+    }
+
+    /// Writes the recorded points to a CSV file.
+    ///
+    /// The file has a header line followed by one line per point with the columns `iter`, `cost`
+    /// and `x0`, `x1`, ... for the elements of the parameter vector.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use argmin::core::Error;
+    /// # use argmin_observer_trajectory::Trajectory;
+    /// # fn main() -> Result<(), Error> {
+    /// let trajectory = Trajectory::new();
+    /// // Run the optimization ...
+    /// trajectory.write_csv("trajectory.csv")?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn write_csv<P: AsRef<Path>>(&self, path: P) -> Result<(), Error> {
+        let points = self.lock();
+        let mut f = BufWriter::new(File::create(path)?);
+        let dim = points.iter().map(|p| p.param.len()).max().unwrap_or(0);
+        write!(f, "iter,cost")?;
+        for i in 0..dim {
+            write!(f, ",x{i}")?;
+        }
+        writeln!(f)?;
+        for point in points.iter() {
+            write!(f, "{},{}", point.iter, point.cost)?;
+            for x in point.param.iter() {
+                write!(f, ",{x}")?;
+            }
+            writeln!(f)?;
+        }
+        f.flush()?;
+        Ok(())
+    }
+
+    /// Locks the recorded points, also if another thread panicked while holding the lock
+    fn lock(&self) -> MutexGuard<'_, Vec<TrajectoryPoint>> {
+        self.points
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    /// Records the parameter vector and cost of `state` (if a parameter vector is available)
+    fn record<I>(&self, state: &I)
+    where
+        I: State,
+        I::Param: AsRef<[I::Float]>,
+        I::Float: Into<f64>,
+    {
+        if let Some(param) = state.get_param() {
+            self.lock().push(TrajectoryPoint {
+                iter: state.get_iter(),
+                param: param.as_ref().iter().map(|&x| x.into()).collect(),
+                cost: state.get_cost().into(),
+            });
+        }
+    }
+}
+
+/// `Trajectory` records the parameter vector after initialization and after each observed
+/// iteration. States without a parameter vector are skipped silently.
+impl<I> Observe<I> for Trajectory
+where
+    I: State,
+    I::Param: AsRef<[I::Float]>,
+    I::Float: Into<f64>,
+{
+    fn observe_init(&mut self, _name: &str, state: &I, _kv: &KV) -> Result<(), Error> {
+        self.lock().clear();
+        self.record(state);
+        Ok(())
+    }
+
+    fn observe_iter(&mut self, state: &I, _kv: &KV) -> Result<(), Error> {
+        self.record(state);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use argmin::core::{
+        observers::ObserverMode, CostFunction, Executor, Gradient, IterState, State,
+    };
+    use argmin::solver::{gradientdescent::SteepestDescent, linesearch::MoreThuenteLineSearch};
+    use argmin_testfunctions::{rosenbrock, rosenbrock_derivative};
+
+    struct Rosenbrock {}
+
+    impl CostFunction for Rosenbrock {
+        type Param = Vec<f64>;
+        type Output = f64;
+
+        fn cost(&self, p: &Self::Param) -> Result<Self::Output, Error> {
+            Ok(rosenbrock(p))
+        }
+    }
+
+    impl Gradient for Rosenbrock {
+        type Param = Vec<f64>;
+        type Gradient = Vec<f64>;
+
+        fn gradient(&self, p: &Self::Param) -> Result<Self::Gradient, Error> {
+            Ok(rosenbrock_derivative(p))
+        }
+    }
+
+    #[test]
+    fn test_record() {
+        let trajectory = Trajectory::new();
+        let res = Executor::new(
+            Rosenbrock {},
+            SteepestDescent::new(MoreThuenteLineSearch::new()),
+        )
+        .configure(|state| state.param(vec![-1.2, 1.0]).max_iters(5))
+        .add_observer(trajectory.clone(), ObserverMode::Always)
+        .run()
+        .unwrap();
+
+        let points = trajectory.points();
+        assert_eq!(points.len(), 6);
+        assert_eq!(points[0].iter, 0);
+        assert_eq!(points[0].param, vec![-1.2, 1.0]);
+        // Iterations are observed before the iteration number is incremented
+        assert_eq!(points[1].iter, 0);
+        assert_eq!(points[5].iter, 4);
+        assert_eq!(&points[5].param, res.state.get_param().unwrap());
+        assert_eq!(
+            points[5].cost.to_ne_bytes(),
+            res.state.get_cost().to_ne_bytes()
+        );
+        assert!(points.windows(2).all(|w| w[1].cost <= w[0].cost));
+
+        // A new run starts a new trajectory
+        Executor::new(
+            Rosenbrock {},
+            SteepestDescent::new(MoreThuenteLineSearch::new()),
+        )
+        .configure(|state| state.param(vec![-1.2, 1.0]).max_iters(2))
+        .add_observer(trajectory.clone(), ObserverMode::Always)
+        .run()
+        .unwrap();
+        assert_eq!(trajectory.len(), 3);
+    }
+
+    #[test]
+    fn test_record_f32() {
+        let mut trajectory = Trajectory::new();
+        let state: IterState<Vec<f32>, (), (), (), (), f32> =
+            IterState::new().param(vec![0.5f32, 2.0]).cost(1.5);
+        Observe::observe_iter(&mut trajectory, &state, &KV::new()).unwrap();
+        assert_eq!(
+            trajectory.points(),
+            vec![TrajectoryPoint {
+                iter: 0,
+                param: vec![0.5, 2.0],
+                cost: 1.5
+            }]
+        );
+    }
+
+    #[test]
+    fn test_write_csv() {
+        let mut trajectory = Trajectory::new();
+        for (iter, param, cost) in [(0, vec![1.0, 2.0], 3.0), (1, vec![0.5, -1.5], 0.25)] {
+            let mut state: IterState<Vec<f64>, (), (), (), (), f64> =
+                IterState::new().param(param).cost(cost);
+            for _ in 0..iter {
+                state.increment_iter();
+            }
+            Observe::observe_iter(&mut trajectory, &state, &KV::new()).unwrap();
+        }
+
+        let dir = std::env::temp_dir().join("argmin-observer-trajectory-csv");
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("trajectory.csv");
+        trajectory.write_csv(&path).unwrap();
+        assert_eq!(
+            std::fs::read_to_string(&path).unwrap(),
+            "iter,cost,x0,x1\n0,3,1,2\n1,0.25,0.5,-1.5\n"
+        );
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,406 @@
+// Copyright 2018-2024 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use crate::Trajectory;
+use argmin::core::Error;
+use plotters::coord::Shift;
+use plotters::prelude::*;
+use std::path::Path;
+
+/// Renders the trajectory of a two-dimensional problem over a contour plot of the cost function.
+///
+/// The cost function is evaluated on a regular grid (default: 200 x 200 cells) and the values
+/// are divided into a number of equally spaced levels (default: 20), which are filled with the
+/// colors of the viridis color map. For cost functions which span several orders of magnitude
+/// (such as the Rosenbrock function), the levels can be spaced logarithmically via
+/// [`log_scale`](`ContourPlot::log_scale`). The trajectory is drawn on top as a line through all
+/// recorded points.
+///
+/// By default, the plotted region is the bounding box of the trajectory enlarged by 10 % on all
+/// sides. It can be set via [`x_range`](`ContourPlot::x_range`) and
+/// [`y_range`](`ContourPlot::y_range`).
+///
+/// The format of the image is determined by the extension of the file, which needs to be either
+/// `png` or `svg`. The plot contains no text such as axis labels or tick marks, which avoids a
+/// dependency on system fonts.
+///
+/// This requires the `plot` feature.
+///
+/// # Example
+///
+/// ```no_run
+/// use argmin::core::Error;
+/// use argmin_observer_trajectory::{ContourPlot, Trajectory};
+/// use argmin_testfunctions::rosenbrock;
+///
+/// # fn main() -> Result<(), Error> {
+/// let trajectory = Trajectory::new();
+/// // Run the optimization with `trajectory` as observer ...
+///
+/// ContourPlot::new(|p: &[f64]| rosenbrock(p))
+///     .x_range(-2.0, 2.0)
+///     .y_range(-1.0, 3.0)
+///     .log_scale(true)
+///     .render(&trajectory, "trajectory.png")?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug)]
+pub struct ContourPlot<C> {
+    /// Cost function
+    cost: C,
+    /// Plotted range of the first parameter
+    x_range: Option<(f64, f64)>,
+    /// Plotted range of the second parameter
+    y_range: Option<(f64, f64)>,
+    /// Number of grid cells in each direction
+    resolution: usize,
+    /// Number of contour levels
+    levels: usize,
+    /// Space levels logarithmically
+    log_scale: bool,
+    /// Width and height of the image in pixels
+    size: (u32, u32),
+}
+
+impl<C> ContourPlot<C>
+where
+    C: Fn(&[f64]) -> f64,
+{
+    /// Create a new `ContourPlot` from a cost function which accepts a two-dimensional parameter
+    /// vector, such as the test functions of `argmin_testfunctions`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin_observer_trajectory::ContourPlot;
+    /// let plot = ContourPlot::new(|p: &[f64]| p[0].powi(2) + p[1].powi(2));
+    /// ```
+    pub fn new(cost: C) -> Self {
+        ContourPlot {
+            cost,
+            x_range: None,
+            y_range: None,
+            resolution: 200,
+            levels: 20,
+            log_scale: false,
+            size: (800, 800),
+        }
+    }
+
+    /// Set the plotted range of the first parameter.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `min >= max`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin_observer_trajectory::ContourPlot;
+    /// let plot = ContourPlot::new(|p: &[f64]| p[0] + p[1]).x_range(-1.0, 1.0);
+    /// ```
+    #[must_use]
+    pub fn x_range(mut self, min: f64, max: f64) -> Self {
+        assert!(
+            min < max,
+            "lower bound of x range must be smaller than upper bound"
+        );
+        self.x_range = Some((min, max));
+        self
+    }
+
+    /// Set the plotted range of the second parameter.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `min >= max`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin_observer_trajectory::ContourPlot;
+    /// let plot = ContourPlot::new(|p: &[f64]| p[0] + p[1]).y_range(-1.0, 1.0);
+    /// ```
+    #[must_use]
+    pub fn y_range(mut self, min: f64, max: f64) -> Self {
+        assert!(
+            min < max,
+            "lower bound of y range must be smaller than upper bound"
+        );
+        self.y_range = Some((min, max));
+        self
+    }
+
+    /// Set the number of grid cells in each direction on which the cost function is evaluated
+    /// (default: 200).
+    ///
+    /// # Panics
+    ///
+    /// Panics if `resolution` is 0.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin_observer_trajectory::ContourPlot;
+    /// let plot = ContourPlot::new(|p: &[f64]| p[0] + p[1]).resolution(400);
+    /// ```
+    #[must_use]
+    pub fn resolution(mut self, resolution: usize) -> Self {
+        assert!(resolution > 0, "resolution must be larger than 0");
+        self.resolution = resolution;
+        self
+    }
+
+    /// Set the number of contour levels (default: 20).
+    ///
+    /// # Panics
+    ///
+    /// Panics if `levels` is 0.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin_observer_trajectory::ContourPlot;
+    /// let plot = ContourPlot::new(|p: &[f64]| p[0] + p[1]).levels(10);
+    /// ```
+    #[must_use]
+    pub fn levels(mut self, levels: usize) -> Self {
+        assert!(levels > 0, "number of levels must be larger than 0");
+        self.levels = levels;
+        self
+    }
+
+    /// Space the contour levels logarithmically (default: `false`).
+    ///
+    /// Since the cost function may be negative or zero, the levels are computed from
+    /// `ln(1 + c - c_min)`, where `c_min` is the smallest cost function value on the grid.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin_observer_trajectory::ContourPlot;
+    /// let plot = ContourPlot::new(|p: &[f64]| p[0] + p[1]).log_scale(true);
+    /// ```
+    #[must_use]
+    pub fn log_scale(mut self, log_scale: bool) -> Self {
+        self.log_scale = log_scale;
+        self
+    }
+
+    /// Set the width and height of the image in pixels (default: 800 x 800).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin_observer_trajectory::ContourPlot;
+    /// let plot = ContourPlot::new(|p: &[f64]| p[0] + p[1]).size(1024, 768);
+    /// ```
+    #[must_use]
+    pub fn size(mut self, width: u32, height: u32) -> Self {
+        self.size = (width, height);
+        self
+    }
+
+    /// Renders `trajectory` over the contour plot to a PNG or SVG file, depending on the extension
+    /// of `path`.
+    ///
+    /// Returns an error if the extension is neither `png` nor `svg`, if the trajectory contains
+    /// parameter vectors which are not two-dimensional or if the image cannot be written.
+    pub fn render<P: AsRef<Path>>(&self, trajectory: &Trajectory, path: P) -> Result<(), Error> {
+        let path = path.as_ref();
+        let points = trajectory
+            .points()
+            .into_iter()
+            .map(|point| match point.param[..] {
+                [x, y] => Ok((x, y)),
+                _ => Err(Error::msg(format!(
+                    "`ContourPlot` requires two-dimensional parameter vectors, got {} dimensions",
+                    point.param.len()
+                ))),
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let extension = path
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .map(str::to_lowercase);
+        match extension.as_deref() {
+            Some("png") => self.draw(
+                BitMapBackend::new(path, self.size).into_drawing_area(),
+                &points,
+            ),
+            Some("svg") => self.draw(
+                SVGBackend::new(path, self.size).into_drawing_area(),
+                &points,
+            ),
+            _ => Err(Error::msg(format!(
+                "`ContourPlot`: Unsupported image format of {}, expected `png` or `svg`",
+                path.display()
+            ))),
+        }
+    }
+
+    /// Plotted range of both parameters
+    fn ranges(&self, points: &[(f64, f64)]) -> ((f64, f64), (f64, f64)) {
+        let bounding = |coords: Vec<f64>| {
+            let (min, max) = coords
+                .into_iter()
+                .filter(|c| c.is_finite())
+                .fold((f64::INFINITY, f64::NEG_INFINITY), |(min, max), c| {
+                    (min.min(c), max.max(c))
+                });
+            if min > max {
+                (-1.0, 1.0)
+            } else {
+                let margin = if max > min { 0.1 * (max - min) } else { 1.0 };
+                (min - margin, max + margin)
+            }
+        };
+        (
+            self.x_range
+                .unwrap_or_else(|| bounding(points.iter().map(|p| p.0).collect())),
+            self.y_range
+                .unwrap_or_else(|| bounding(points.iter().map(|p| p.1).collect())),
+        )
+    }
+
+    /// Draws the contour plot and the trajectory onto `root`
+    fn draw<DB: DrawingBackend>(
+        &self,
+        root: DrawingArea<DB, Shift>,
+        points: &[(f64, f64)],
+    ) -> Result<(), Error>
+    where
+        DB::ErrorType: 'static,
+    {
+        let ((x_min, x_max), (y_min, y_max)) = self.ranges(points);
+        let n = self.resolution;
+        let dx = (x_max - x_min) / n as f64;
+        let dy = (y_max - y_min) / n as f64;
+
+        // Evaluate the cost function at the centers of the grid cells
+        let mut cells = Vec::with_capacity(n * n);
+        for i in 0..n {
+            for j in 0..n {
+                let x = x_min + i as f64 * dx;
+                let y = y_min + j as f64 * dy;
+                let cost = (self.cost)(&[x + 0.5 * dx, y + 0.5 * dy]);
+                cells.push((x, y, cost));
+            }
+        }
+        let finite = cells.iter().map(|c| c.2).filter(|c| c.is_finite());
+        let c_min = finite.clone().fold(f64::INFINITY, f64::min);
+        let scale = |c: f64| {
+            if self.log_scale {
+                (c - c_min).ln_1p()
+            } else {
+                c
+            }
+        };
+        let lower = scale(c_min);
+        let upper = finite.map(scale).fold(f64::NEG_INFINITY, f64::max);
+        let levels = self.levels as f64;
+        let level = |c: f64| {
+            if upper > lower {
+                (((scale(c) - lower) / (upper - lower) * levels).floor()).min(levels - 1.0)
+            } else {
+                0.0
+            }
+        };
+
+        root.fill(&WHITE)?;
+        let mut chart = ChartBuilder::on(&root).build_cartesian_2d(x_min..x_max, y_min..y_max)?;
+        chart.draw_series(
+            cells
+                .into_iter()
+                .filter(|c| c.2.is_finite())
+                .map(|(x, y, c)| {
+                    let color = if self.levels > 1 {
+                        ViridisRGB::get_color(level(c) / (levels - 1.0))
+                    } else {
+                        ViridisRGB::get_color(0.0)
+                    };
+                    Rectangle::new([(x, y), (x + dx, y + dy)], color.filled())
+                }),
+        )?;
+        chart.draw_series(LineSeries::new(points.iter().copied(), RED.stroke_width(2)))?;
+        chart.draw_series(
+            points
+                .iter()
+                .map(|&point| Circle::new(point, 3, RED.filled())),
+        )?;
+        root.present()?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use argmin::core::observers::Observe;
+    use argmin::core::{IterState, State, KV};
+
+    fn trajectory(params: Vec<Vec<f64>>) -> Trajectory {
+        let mut trajectory = Trajectory::new();
+        for param in params {
+            let state: IterState<Vec<f64>, (), (), (), (), f64> = IterState::new().param(param);
+            trajectory.observe_iter(&state, &KV::new()).unwrap();
+        }
+        trajectory
+    }
+
+    #[test]
+    fn test_ranges() {
+        let plot = ContourPlot::new(|p: &[f64]| p[0] + p[1]);
+        let points = [(0.0, 1.0), (1.0, 1.0)];
+        let ((x_min, x_max), (y_min, y_max)) = plot.ranges(&points);
+        assert_eq!(x_min.to_ne_bytes(), (-0.1f64).to_ne_bytes());
+        assert_eq!(x_max.to_ne_bytes(), 1.1f64.to_ne_bytes());
+        assert_eq!(y_min.to_ne_bytes(), 0.0f64.to_ne_bytes());
+        assert_eq!(y_max.to_ne_bytes(), 2.0f64.to_ne_bytes());
+
+        let plot = plot.x_range(-2.0, 2.0).y_range(-3.0, 3.0);
+        assert_eq!(plot.ranges(&points), ((-2.0, 2.0), (-3.0, 3.0)));
+        assert_eq!(plot.ranges(&[]), ((-2.0, 2.0), (-3.0, 3.0)));
+    }
+
+    #[test]
+    fn test_render() {
+        let trajectory = trajectory(vec![vec![-1.2, 1.0], vec![-0.5, 0.5], vec![1.0, 1.0]]);
+        let dir = std::env::temp_dir().join("argmin-observer-trajectory-plot");
+        std::fs::create_dir_all(&dir).unwrap();
+
+        let plot = ContourPlot::new(|p: &[f64]| argmin_testfunctions::rosenbrock(p))
+            .resolution(20)
+            .size(100, 100)
+            .log_scale(true);
+        plot.render(&trajectory, dir.join("trajectory.png"))
+            .unwrap();
+        plot.render(&trajectory, dir.join("trajectory.svg"))
+            .unwrap();
+        let png = std::fs::read(dir.join("trajectory.png")).unwrap();
+        assert_eq!(&png[1..4], b"PNG");
+        let svg = std::fs::read_to_string(dir.join("trajectory.svg")).unwrap();
+        assert!(svg.starts_with("<svg"));
+        assert!(svg.contains("<polyline"));
+
+        let res = plot.render(&trajectory, dir.join("trajectory.pdf"));
+        assert!(res.is_err());
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_render_dimension_mismatch() {
+        let trajectory = trajectory(vec![vec![1.0, 2.0, 3.0]]);
+        let res = ContourPlot::new(|p: &[f64]| p[0]).render(&trajectory, "trajectory.png");
+        assert_eq!(
+            res.unwrap_err().to_string(),
+            "`ContourPlot` requires two-dimensional parameter vectors, got 3 dimensions"
+        );
+    }
+}
@@ -1,27 +1,36 @@
 use argmin_testfunctions::{
-    ackley, ackley_derivative, ackley_derivative_const, ackley_hessian, ackley_hessian_const,
-    beale, beale_derivative, beale_hessian, booth, booth_derivative, booth_hessian, bukin_n6,
-    bukin_n6_derivative, bukin_n6_hessian, cross_in_tray, cross_in_tray_derivative,
-    cross_in_tray_hessian, easom, easom_derivative, easom_hessian, eggholder, eggholder_derivative,
-    eggholder_hessian, goldsteinprice, goldsteinprice_derivative, goldsteinprice_hessian,
+    ackley, ackley_derivative, ackley_derivative_const, ackley_hessian, ackley_hessian_const, bard,
+    bard_jacobian, bard_residuals, beale, beale_derivative, beale_hessian, booth, booth_derivative,
+    booth_hessian, broyden_tridiagonal, broyden_tridiagonal_derivative,
+    broyden_tridiagonal_derivative_const, bukin_n6, bukin_n6_derivative, bukin_n6_hessian,
+    chained_wood, chained_wood_derivative, chained_wood_derivative_const, cross_in_tray,
+    cross_in_tray_derivative, cross_in_tray_hessian, easom, easom_derivative, easom_hessian,
+    eggholder, eggholder_derivative, eggholder_hessian, extended_rosenbrock,
+    extended_rosenbrock_derivative, extended_rosenbrock_derivative_const, g06, g06_derivative,
+    g06_ineq, g06_ineq_jacobian, goldsteinprice, goldsteinprice_derivative, goldsteinprice_hessian,
     himmelblau, himmelblau_derivative, himmelblau_hessian, holder_table, holder_table_derivative,
-    holder_table_hessian, levy, levy_derivative, levy_derivative_const, levy_hessian,
+    holder_table_hessian, hs71, hs71_derivative, hs71_eq, hs71_eq_jacobian, hs71_ineq,
+    hs71_ineq_jacobian, levy, levy_derivative, levy_derivative_const, levy_hessian,
     levy_hessian_const, levy_n13, levy_n13_derivative, levy_n13_hessian, matyas, matyas_derivative,
-    matyas_hessian, mccorminck, mccorminck_derivative, mccorminck_hessian, picheny,
-    picheny_derivative, picheny_hessian, rastrigin, rastrigin_derivative,
-    rastrigin_derivative_const, rastrigin_hessian, rastrigin_hessian_const, rosenbrock,
-    rosenbrock_derivative, rosenbrock_derivative_const, rosenbrock_hessian,
+    matyas_hessian, mccorminck, mccorminck_derivative, mccorminck_hessian, osborne2,
+    osborne2_jacobian, osborne2_residuals, picheny, picheny_derivative, picheny_hessian,
+    powell_singular, powell_singular_derivative, powell_singular_derivative_const, rastrigin,
+    rastrigin_derivative, rastrigin_derivative_const, rastrigin_hessian, rastrigin_hessian_const,
+    rosenbrock, rosenbrock_derivative, rosenbrock_derivative_const, rosenbrock_hessian,
     rosenbrock_hessian_const, schaffer_n2, schaffer_n2_derivative, schaffer_n2_hessian,
     schaffer_n4, schaffer_n4_derivative, schaffer_n4_hessian, sphere, sphere_derivative,
     sphere_derivative_const, sphere_hessian, sphere_hessian_const, styblinski_tang,
     styblinski_tang_derivative, styblinski_tang_derivative_const, styblinski_tang_hessian,
     styblinski_tang_hessian_const, threehumpcamel, threehumpcamel_derivative,
-    threehumpcamel_hessian, zero, zero_derivative, zero_derivative_const, zero_hessian,
-    zero_hessian_const,
+    threehumpcamel_hessian, trigonometric, trigonometric_derivative,
+    trigonometric_derivative_const, watson, watson_jacobian, watson_jacobian_const,
+    watson_residuals, watson_residuals_const, zero, zero_derivative, zero_derivative_const,
+    zero_hessian, zero_hessian_const, BARD_START, OSBORNE2_START,
 };
 use criterion::{black_box, criterion_group, criterion_main, Criterion};
 
 const P2: &[f64; 2] = &[1.0; 2];
+const P4: &[f64; 4] = &[1.0; 4];
 const P10: &[f64; 10] = &[1.0; 10];
 const P20: &[f64; 20] = &[1.0; 20];
 
@@ -74,6 +83,21 @@ pub fn bm_ackley(c: &mut Criterion) {
     g.finish();
 }
 
+pub fn bm_bard(c: &mut Criterion) {
+    let mut g = c.benchmark_group("bard");
+    // Test function
+    g.bench_function("bard 3", |b| b.iter(|| bard(black_box(&BARD_START))));
+    // Residuals
+    g.bench_function("bard_residuals 3", |b| {
+        b.iter(|| bard_residuals(black_box(&BARD_START)))
+    });
+    // Jacobian of residuals
+    g.bench_function("bard_jacobian 3", |b| {
+        b.iter(|| bard_jacobian(black_box(&BARD_START)))
+    });
+    g.finish();
+}
+
 pub fn bm_beale(c: &mut Criterion) {
     let mut g = c.benchmark_group("beale");
     // Test function
@@ -104,6 +128,23 @@ pub fn bm_booth(c: &mut Criterion) {
     g.finish();
 }
 
+pub fn bm_broyden_tridiagonal(c: &mut Criterion) {
+    let mut g = c.benchmark_group("broyden_tridiagonal");
+    // Test function
+    g.bench_function("broyden_tridiagonal 20", |b| {
+        b.iter(|| broyden_tridiagonal(black_box(P20)))
+    });
+    // Derivative
+    g.bench_function("broyden_tridiagonal_derivative 20", |b| {
+        b.iter(|| broyden_tridiagonal_derivative(black_box(P20)))
+    });
+    // Derivative const
+    g.bench_function("broyden_tridiagonal_derivative_const 20", |b| {
+        b.iter(|| broyden_tridiagonal_derivative_const(black_box(P20)))
+    });
+    g.finish();
+}
+
 pub fn bm_bukin_n6(c: &mut Criterion) {
     let mut g = c.benchmark_group("bukin_n6");
     // Test function
@@ -119,6 +160,23 @@ pub fn bm_bukin_n6(c: &mut Criterion) {
     g.finish();
 }
 
+pub fn bm_chained_wood(c: &mut Criterion) {
+    let mut g = c.benchmark_group("chained_wood");
+    // Test function
+    g.bench_function("chained_wood 20", |b| {
+        b.iter(|| chained_wood(black_box(P20)))
+    });
+    // Derivative
+    g.bench_function("chained_wood_derivative 20", |b| {
+        b.iter(|| chained_wood_derivative(black_box(P20)))
+    });
+    // Derivative const
+    g.bench_function("chained_wood_derivative_const 20", |b| {
+        b.iter(|| chained_wood_derivative_const(black_box(P20)))
+    });
+    g.finish();
+}
+
 pub fn bm_cross_in_tray(c: &mut Criterion) {
     let mut g = c.benchmark_group("cross_in_tray");
     // Test function
@@ -166,6 +224,40 @@ pub fn bm_eggholder(c: &mut Criterion) {
     g.finish();
 }
 
+pub fn bm_extended_rosenbrock(c: &mut Criterion) {
+    let mut g = c.benchmark_group("extended_rosenbrock");
+    // Test function
+    g.bench_function("extended_rosenbrock 20", |b| {
+        b.iter(|| extended_rosenbrock(black_box(P20)))
+    });
+    // Derivative
+    g.bench_function("extended_rosenbrock_derivative 20", |b| {
+        b.iter(|| extended_rosenbrock_derivative(black_box(P20)))
+    });
+    // Derivative const
+    g.bench_function("extended_rosenbrock_derivative_const 20", |b| {
+        b.iter(|| extended_rosenbrock_derivative_const(black_box(P20)))
+    });
+    g.finish();
+}
+
+pub fn bm_g06(c: &mut Criterion) {
+    let mut g = c.benchmark_group("g06");
+    // Test function
+    g.bench_function("g06 2", |b| b.iter(|| g06(black_box(P2))));
+    // Derivative
+    g.bench_function("g06_derivative 2", |b| {
+        b.iter(|| g06_derivative(black_box(P2)))
+    });
+    // Constraints
+    g.bench_function("g06_ineq 2", |b| b.iter(|| g06_ineq(black_box(P2))));
+    // Jacobian of constraints
+    g.bench_function("g06_ineq_jacobian 2", |b| {
+        b.iter(|| g06_ineq_jacobian(black_box(P2)))
+    });
+    g.finish();
+}
+
 pub fn bm_goldsteinprice(c: &mut Criterion) {
     let mut g = c.benchmark_group("goldsteinprice");
     // Test function
@@ -213,6 +305,27 @@ pub fn bm_holder_table(c: &mut Criterion) {
     g.finish();
 }
 
+pub fn bm_hs71(c: &mut Criterion) {
+    let mut g = c.benchmark_group("hs71");
+    // Test function
+    g.bench_function("hs71 4", |b| b.iter(|| hs71(black_box(P4))));
+    // Derivative
+    g.bench_function("hs71_derivative 4", |b| {
+        b.iter(|| hs71_derivative(black_box(P4)))
+    });
+    // Constraints
+    g.bench_function("hs71_ineq 4", |b| b.iter(|| hs71_ineq(black_box(P4))));
+    g.bench_function("hs71_eq 4", |b| b.iter(|| hs71_eq(black_box(P4))));
+    // Jacobian of constraints
+    g.bench_function("hs71_ineq_jacobian 4", |b| {
+        b.iter(|| hs71_ineq_jacobian(black_box(P4)))
+    });
+    g.bench_function("hs71_eq_jacobian 4", |b| {
+        b.iter(|| hs71_eq_jacobian(black_box(P4)))
+    });
+    g.finish();
+}
+
 pub fn bm_levy(c: &mut Criterion) {
     let mut g = c.benchmark_group("levy");
     // Test function
@@ -307,6 +420,23 @@ pub fn bm_mccorminck(c: &mut Criterion) {
     g.finish();
 }
 
+pub fn bm_osborne2(c: &mut Criterion) {
+    let mut g = c.benchmark_group("osborne2");
+    // Test function
+    g.bench_function("osborne2 11", |b| {
+        b.iter(|| osborne2(black_box(&OSBORNE2_START)))
+    });
+    // Residuals
+    g.bench_function("osborne2_residuals 11", |b| {
+        b.iter(|| osborne2_residuals(black_box(&OSBORNE2_START)))
+    });
+    // Jacobian of residuals
+    g.bench_function("osborne2_jacobian 11", |b| {
+        b.iter(|| osborne2_jacobian(black_box(&OSBORNE2_START)))
+    });
+    g.finish();
+}
+
 pub fn bm_picheny(c: &mut Criterion) {
     let mut g = c.benchmark_group("picheny");
     // Test function
@@ -322,6 +452,23 @@ pub fn bm_picheny(c: &mut Criterion) {
     g.finish();
 }
 
+pub fn bm_powell_singular(c: &mut Criterion) {
+    let mut g = c.benchmark_group("powell_singular");
+    // Test function
+    g.bench_function("powell_singular 20", |b| {
+        b.iter(|| powell_singular(black_box(P20)))
+    });
+    // Derivative
+    g.bench_function("powell_singular_derivative 20", |b| {
+        b.iter(|| powell_singular_derivative(black_box(P20)))
+    });
+    // Derivative const
+    g.bench_function("powell_singular_derivative_const 20", |b| {
+        b.iter(|| powell_singular_derivative_const(black_box(P20)))
+    });
+    g.finish();
+}
+
 pub fn bm_rastrigin(c: &mut Criterion) {
     let mut g = c.benchmark_group("rastrigin");
     // Test function
@@ -571,6 +718,46 @@ pub fn bm_threehumpcamel(c: &mut Criterion) {
     g.finish();
 }
 
+pub fn bm_trigonometric(c: &mut Criterion) {
+    let mut g = c.benchmark_group("trigonometric");
+    // Test function
+    g.bench_function("trigonometric 20", |b| {
+        b.iter(|| trigonometric(black_box(P20)))
+    });
+    // Derivative
+    g.bench_function("trigonometric_derivative 20", |b| {
+        b.iter(|| trigonometric_derivative(black_box(P20)))
+    });
+    // Derivative const
+    g.bench_function("trigonometric_derivative_const 20", |b| {
+        b.iter(|| trigonometric_derivative_const(black_box(P20)))
+    });
+    g.finish();
+}
+
+pub fn bm_watson(c: &mut Criterion) {
+    let mut g = c.benchmark_group("watson");
+    // Test function
+    g.bench_function("watson 10", |b| b.iter(|| watson(black_box(P10))));
+    // Residuals
+    g.bench_function("watson_residuals 10", |b| {
+        b.iter(|| watson_residuals(black_box(P10)))
+    });
+    // Residuals const
+    g.bench_function("watson_residuals_const 10", |b| {
+        b.iter(|| watson_residuals_const(black_box(P10)))
+    });
+    // Jacobian of residuals
+    g.bench_function("watson_jacobian 10", |b| {
+        b.iter(|| watson_jacobian(black_box(P10)))
+    });
+    // Jacobian of residuals const
+    g.bench_function("watson_jacobian_const 10", |b| {
+        b.iter(|| watson_jacobian_const(black_box(P10)))
+    });
+    g.finish();
+}
+
 pub fn bm_zero(c: &mut Criterion) {
     let mut g = c.benchmark_group("zero");
     // Test function
@@ -623,20 +810,28 @@ pub fn bm_zero(c: &mut Criterion) {
 criterion_group!(
     benches,
     bm_ackley,
+    bm_bard,
     bm_beale,
     bm_booth,
+    bm_broyden_tridiagonal,
     bm_bukin_n6,
+    bm_chained_wood,
     bm_cross_in_tray,
     bm_easom,
     bm_eggholder,
+    bm_extended_rosenbrock,
+    bm_g06,
     bm_goldsteinprice,
     bm_himmelblau,
     bm_holder_table,
+    bm_hs71,
     bm_levy,
     bm_levy_n13,
     bm_matyas,
     bm_mccorminck,
+    bm_osborne2,
     bm_picheny,
+    bm_powell_singular,
     bm_rastrigin,
     bm_rosenbrock,
     bm_schaffer_n2,
@@ -644,6 +839,8 @@ criterion_group!(
     bm_sphere,
     bm_styblinski_tang,
     bm_threehumpcamel,
+    bm_trigonometric,
+    bm_watson,
     bm_zero,
 );
 criterion_main!(benches);
@@ -0,0 +1,134 @@
+// Copyright 2018-2024 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+//! # Bard test problem
+//!
+//! Nonlinear least-squares problem with 3 parameters and 15 residuals
+//!
+//! `r_i(x_1, x_2, x_3) = y_i - (x_1 + u_i / (v_i * x_2 + w_i * x_3))`
+//!
+//! where `u_i = i`, `v_i = 16 - i` and `w_i = min(u_i, v_i)` for `i = 1, ..., 15`. The objective
+//! is `f(x) = \sum_{i=1}^{15} r_i(x)^2`.
+//!
+//! The minimum is `f = 8.21487e-3`. The usual starting point is `(1, 1, 1)`.
+//!
+//! Reference: J. J. Moré, B. S. Garbow and K. E. Hillstrom, "Testing Unconstrained
+//! Optimization Software", ACM Transactions on Mathematical Software, 7(1), 1981.
+
+use num::{Float, FromPrimitive};
+
+const Y: [f64; 15] = [
+    0.14, 0.18, 0.22, 0.25, 0.29, 0.32, 0.35, 0.39, 0.37, 0.58, 0.73, 0.96, 1.34, 2.10, 4.39,
+];
+
+/// Starting point of the Bard problem
+pub const BARD_START: [f64; 3] = [1.0, 1.0, 1.0];
+
+/// Minimizer of the Bard problem
+pub const BARD_OPTIMUM: [f64; 3] = [0.0824105597514598, 1.1330360920856457, 2.343695178588754];
+
+/// Minimum of the Bard problem
+pub const BARD_OPTIMUM_VALUE: f64 = 0.008214877306578966;
+
+/// Returns `(u_i, v_i, w_i)` for zero-based index `i`
+fn uvw<T: FromPrimitive>(i: usize) -> (T, T, T) {
+    let u = i + 1;
+    let v = 15 - i;
+    (
+        T::from_usize(u).unwrap(),
+        T::from_usize(v).unwrap(),
+        T::from_usize(u.min(v)).unwrap(),
+    )
+}
+
+/// Bard test problem
+///
+/// Sum of squares of the residuals returned by [`bard_residuals`].
+///
+/// The minimum is `f = 8.21487e-3`.
+pub fn bard<T>(param: &[T; 3]) -> T
+where
+    T: Float + FromPrimitive,
+{
+    bard_residuals(param)
+        .iter()
+        .fold(T::zero(), |acc, r| acc + r.powi(2))
+}
+
+/// Residuals of Bard test problem
+pub fn bard_residuals<T>(param: &[T; 3]) -> [T; 15]
+where
+    T: Float + FromPrimitive,
+{
+    let [x1, x2, x3] = *param;
+    let mut out = [T::zero(); 15];
+    for (i, o) in out.iter_mut().enumerate() {
+        let (u, v, w) = uvw::<T>(i);
+        *o = T::from_f64(Y[i]).unwrap() - (x1 + u / (v * x2 + w * x3));
+    }
+    out
+}
+
+/// Jacobian of the residuals of Bard test problem
+pub fn bard_jacobian<T>(param: &[T; 3]) -> [[T; 3]; 15]
+where
+    T: Float + FromPrimitive,
+{
+    let [_, x2, x3] = *param;
+    let mut out = [[T::zero(); 3]; 15];
+    for (i, o) in out.iter_mut().enumerate() {
+        let (u, v, w) = uvw::<T>(i);
+        let d2 = (v * x2 + w * x3).powi(2);
+        *o = [-T::one(), u * v / d2, u * w / d2];
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_relative_eq;
+    use finitediff::FiniteDiff;
+    use proptest::prelude::*;
+
+    #[test]
+    fn test_bard_optimum() {
+        assert_relative_eq!(bard(&BARD_OPTIMUM), BARD_OPTIMUM_VALUE, epsilon = 1e-12);
+
+        // Gradient `2 * J^T r` vanishes at the optimum
+        let r = bard_residuals(&BARD_OPTIMUM);
+        let jac = bard_jacobian(&BARD_OPTIMUM);
+        for j in 0..3 {
+            let g: f64 = jac
+                .iter()
+                .zip(r.iter())
+                .map(|(row, r)| 2.0 * row[j] * r)
+                .sum();
+            assert_relative_eq!(g, 0.0, epsilon = 1e-8);
+        }
+    }
+
+    proptest! {
+        #[test]
+        fn test_bard_jacobian_finitediff(a in -2.0..2.0, b in 0.5..3.0, c in 0.5..3.0) {
+            let param = [a, b, c];
+            let jacobian = bard_jacobian(&param);
+            for (i, row) in jacobian.iter().enumerate() {
+                let row_fd = Vec::from(param)
+                    .central_diff(&|x| bard_residuals(&[x[0], x[1], x[2]])[i]);
+                for (d, d_fd) in row.iter().zip(row_fd.iter()) {
+                    assert_relative_eq!(
+                        d,
+                        d_fd,
+                        epsilon = 1e-5,
+                        max_relative = 1e-2
+                    );
+                }
+            }
+        }
+    }
+}
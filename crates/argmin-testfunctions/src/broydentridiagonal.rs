@@ -0,0 +1,152 @@
+// Copyright 2018-2024 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+//! # Broyden tridiagonal test function
+//!
+//! Defined as
+//!
+//! `f(x_1, x_2, ..., x_n) = \sum_{i=1}^{n} r_i^2`
+//!
+//! with `r_i = (3 - 2*x_i)*x_i - x_{i-1} - 2*x_{i+1} + 1` and `x_0 = x_{n+1} = 0`.
+//!
+//! The global minimum is `0`. The usual starting point is `(-1, -1, ..., -1)`.
+//!
+//! Reference: J. J. Moré, B. S. Garbow and K. E. Hillstrom, "Testing Unconstrained
+//! Optimization Software", ACM Transactions on Mathematical Software, 7(1), 1981.
+
+use num::{Float, FromPrimitive};
+use std::iter::Sum;
+
+/// Residual `r_i` (zero-based index) of the Broyden tridiagonal function
+fn residual<T>(param: &[T], i: usize) -> T
+where
+    T: Float + FromPrimitive,
+{
+    let n1 = T::from_f64(1.0).unwrap();
+    let n2 = T::from_f64(2.0).unwrap();
+    let n3 = T::from_f64(3.0).unwrap();
+
+    let prev = if i > 0 { param[i - 1] } else { T::zero() };
+    let next = param.get(i + 1).copied().unwrap_or(T::zero());
+    (n3 - n2 * param[i]) * param[i] - prev - n2 * next + n1
+}
+
+/// Broyden tridiagonal test function
+///
+/// Defined as
+///
+/// `f(x_1, x_2, ..., x_n) = \sum_{i=1}^{n} r_i^2`
+///
+/// with `r_i = (3 - 2*x_i)*x_i - x_{i-1} - 2*x_{i+1} + 1` and `x_0 = x_{n+1} = 0`.
+///
+/// The global minimum is `0`.
+pub fn broyden_tridiagonal<T>(param: &[T]) -> T
+where
+    T: Float + FromPrimitive + Sum,
+{
+    assert!(!param.is_empty());
+
+    (0..param.len()).map(|i| residual(param, i).powi(2)).sum()
+}
+
+fn broyden_tridiagonal_derivative_into<T>(param: &[T], out: &mut [T])
+where
+    T: Float + FromPrimitive,
+{
+    assert!(!param.is_empty());
+
+    let n2 = T::from_f64(2.0).unwrap();
+    let n3 = T::from_f64(3.0).unwrap();
+    let n4 = T::from_f64(4.0).unwrap();
+
+    let n = param.len();
+    let r: Vec<T> = (0..n).map(|i| residual(param, i)).collect();
+    for (k, o) in out.iter_mut().enumerate() {
+        // x_k appears in r_{k-1} (with factor -2), r_k and r_{k+1} (with factor -1)
+        let mut d = r[k] * (n3 - n4 * param[k]);
+        if k > 0 {
+            d = d - n2 * r[k - 1];
+        }
+        if k + 1 < n {
+            d = d - r[k + 1];
+        }
+        *o = n2 * d;
+    }
+}
+
+/// Derivative of Broyden tridiagonal test function
+pub fn broyden_tridiagonal_derivative<T>(param: &[T]) -> Vec<T>
+where
+    T: Float + FromPrimitive,
+{
+    let mut out = vec![T::zero(); param.len()];
+    broyden_tridiagonal_derivative_into(param, &mut out);
+    out
+}
+
+/// Derivative of Broyden tridiagonal test function
+///
+/// This is the const generics version, which requires the number of parameters to be known
+/// at compile time.
+pub fn broyden_tridiagonal_derivative_const<const N: usize, T>(param: &[T; N]) -> [T; N]
+where
+    T: Float + FromPrimitive,
+{
+    let mut out = [T::zero(); N];
+    broyden_tridiagonal_derivative_into(param, &mut out);
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_relative_eq;
+    use finitediff::FiniteDiff;
+    use proptest::prelude::*;
+
+    #[test]
+    fn test_broyden_tridiagonal_start() {
+        // r_1 = -5 + 2 + 1, r_i = -5 + 1 + 2 + 1 and r_n = -5 + 1 + 1
+        assert_relative_eq!(
+            broyden_tridiagonal(&[-1.0_f64; 4]),
+            4.0 + 1.0 + 1.0 + 9.0,
+            epsilon = f64::EPSILON
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_broyden_tridiagonal_param_length() {
+        broyden_tridiagonal::<f64>(&[]);
+    }
+
+    proptest! {
+        #[test]
+        fn test_broyden_tridiagonal_derivative_finitediff(a in -5.0..5.0,
+                                                          b in -5.0..5.0,
+                                                          c in -5.0..5.0,
+                                                          d in -5.0..5.0,
+                                                          e in -5.0..5.0,
+                                                          f in -5.0..5.0,
+                                                          g in -5.0..5.0,
+                                                          h in -5.0..5.0) {
+            let param = [a, b, c, d, e, f, g, h];
+            let derivative = broyden_tridiagonal_derivative(&param);
+            let derivative_const = broyden_tridiagonal_derivative_const(&param);
+            let derivative_fd = Vec::from(param).central_diff(&|x| broyden_tridiagonal(x));
+            for i in 0..derivative.len() {
+                assert_relative_eq!(derivative[i], derivative_const[i]);
+                assert_relative_eq!(
+                    derivative[i],
+                    derivative_fd[i],
+                    epsilon = 1e-4,
+                    max_relative = 1e-2
+                );
+            }
+        }
+    }
+}
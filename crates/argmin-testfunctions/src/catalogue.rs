@@ -0,0 +1,414 @@
+// Copyright 2018-2024 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+//! # Catalogue of test functions
+//!
+//! Structured information about the box-bounded global optimization test functions in this
+//! crate: search domain, known global minimum and minimizer(s) as well as whether the function is
+//! multimodal. This allows benchmark harnesses to compute optimality gaps without hardcoding
+//! targets.
+//!
+//! ```
+//! use argmin_testfunctions::{rastrigin, test_function_info};
+//!
+//! let info = test_function_info("rastrigin").unwrap();
+//! let bounds = info.bounds(3);
+//! assert_eq!(bounds, vec![(-5.12, 5.12); 3]);
+//!
+//! let x = [0.1, -0.2, 0.05];
+//! let gap = info.optimality_gap(rastrigin(&x), x.len());
+//! assert!(gap > 0.0);
+//! ```
+
+/// Number of parameters of a test function
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Dimension {
+    /// Fixed number of parameters
+    Fixed(usize),
+    /// Arbitrary number of parameters, with a lower limit
+    Variable {
+        /// Minimum number of parameters
+        min: usize,
+    },
+}
+
+impl Dimension {
+    /// Returns `true` if the function can be evaluated with `n` parameters
+    pub fn admits(&self, n: usize) -> bool {
+        match *self {
+            Dimension::Fixed(d) => n == d,
+            Dimension::Variable { min } => n >= min,
+        }
+    }
+}
+
+/// Known global minimum of a test function
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum GlobalMinimum {
+    /// Minimum does not depend on the number of parameters
+    Constant(f64),
+    /// Minimum grows linearly with the number of parameters, i.e. `f* = value * n`
+    PerDimension(f64),
+}
+
+impl GlobalMinimum {
+    /// Returns the minimum for `n` parameters
+    pub fn value(&self, n: usize) -> f64 {
+        match *self {
+            GlobalMinimum::Constant(v) => v,
+            GlobalMinimum::PerDimension(v) => v * n as f64,
+        }
+    }
+}
+
+/// Metadata of a test function
+///
+/// For functions with [`Dimension::Variable`], `bounds` contains a single entry and each
+/// minimizer in `minimizers` contains a single coordinate. Both apply to every parameter. Use
+/// [`bounds`](`TestFunctionInfo::bounds`) and [`minimizers`](`TestFunctionInfo::minimizers`) to
+/// obtain them for a given number of parameters.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct TestFunctionInfo {
+    /// Name of the function in this crate
+    pub name: &'static str,
+    /// Number of parameters
+    pub dimension: Dimension,
+    /// Lower and upper bound of the usual search domain of each parameter
+    pub bounds: &'static [(f64, f64)],
+    /// Known global minimum
+    pub minimum: GlobalMinimum,
+    /// Known global minimizer(s)
+    pub minimizers: &'static [&'static [f64]],
+    /// Whether the function has more than one local minimum
+    pub multimodal: bool,
+}
+
+impl TestFunctionInfo {
+    /// Returns the bounds of the search domain for `n` parameters
+    ///
+    /// # Panics
+    ///
+    /// Panics if the function cannot be evaluated with `n` parameters.
+    pub fn bounds(&self, n: usize) -> Vec<(f64, f64)> {
+        assert!(self.dimension.admits(n));
+        match self.dimension {
+            Dimension::Fixed(_) => self.bounds.to_vec(),
+            Dimension::Variable { .. } => vec![self.bounds[0]; n],
+        }
+    }
+
+    /// Returns the global minimizer(s) for `n` parameters
+    ///
+    /// # Panics
+    ///
+    /// Panics if the function cannot be evaluated with `n` parameters.
+    pub fn minimizers(&self, n: usize) -> Vec<Vec<f64>> {
+        assert!(self.dimension.admits(n));
+        self.minimizers
+            .iter()
+            .map(|m| match self.dimension {
+                Dimension::Fixed(_) => m.to_vec(),
+                Dimension::Variable { .. } => vec![m[0]; n],
+            })
+            .collect()
+    }
+
+    /// Returns the global minimum for `n` parameters
+    pub fn minimum(&self, n: usize) -> f64 {
+        self.minimum.value(n)
+    }
+
+    /// Returns the difference between `value` and the global minimum for `n` parameters
+    pub fn optimality_gap(&self, value: f64, n: usize) -> f64 {
+        value - self.minimum(n)
+    }
+}
+
+/// Catalogue of the box-bounded global optimization test functions, sorted by name
+pub static TEST_FUNCTIONS: &[TestFunctionInfo] = &[
+    TestFunctionInfo {
+        name: "ackley",
+        dimension: Dimension::Variable { min: 1 },
+        bounds: &[(-32.768, 32.768)],
+        minimum: GlobalMinimum::Constant(0.0),
+        minimizers: &[&[0.0]],
+        multimodal: true,
+    },
+    TestFunctionInfo {
+        name: "beale",
+        dimension: Dimension::Fixed(2),
+        bounds: &[(-4.5, 4.5), (-4.5, 4.5)],
+        minimum: GlobalMinimum::Constant(0.0),
+        minimizers: &[&[3.0, 0.5]],
+        multimodal: false,
+    },
+    TestFunctionInfo {
+        name: "booth",
+        dimension: Dimension::Fixed(2),
+        bounds: &[(-10.0, 10.0), (-10.0, 10.0)],
+        minimum: GlobalMinimum::Constant(0.0),
+        minimizers: &[&[1.0, 3.0]],
+        multimodal: false,
+    },
+    TestFunctionInfo {
+        name: "bukin_n6",
+        dimension: Dimension::Fixed(2),
+        bounds: &[(-15.0, -5.0), (-3.0, 3.0)],
+        minimum: GlobalMinimum::Constant(0.0),
+        minimizers: &[&[-10.0, 1.0]],
+        multimodal: true,
+    },
+    TestFunctionInfo {
+        name: "cross_in_tray",
+        dimension: Dimension::Fixed(2),
+        bounds: &[(-10.0, 10.0), (-10.0, 10.0)],
+        minimum: GlobalMinimum::Constant(-2.062_611_870_822_739),
+        minimizers: &[
+            &[1.349_406_608_602_084, 1.349_406_608_602_084],
+            &[1.349_406_608_602_084, -1.349_406_608_602_084],
+            &[-1.349_406_608_602_084, 1.349_406_608_602_084],
+            &[-1.349_406_608_602_084, -1.349_406_608_602_084],
+        ],
+        multimodal: true,
+    },
+    TestFunctionInfo {
+        name: "easom",
+        dimension: Dimension::Fixed(2),
+        bounds: &[(-100.0, 100.0), (-100.0, 100.0)],
+        minimum: GlobalMinimum::Constant(-1.0),
+        minimizers: &[&[std::f64::consts::PI, std::f64::consts::PI]],
+        multimodal: true,
+    },
+    TestFunctionInfo {
+        name: "eggholder",
+        dimension: Dimension::Fixed(2),
+        bounds: &[(-512.0, 512.0), (-512.0, 512.0)],
+        minimum: GlobalMinimum::Constant(-959.640_662_720_850_7),
+        minimizers: &[&[512.0, 404.231_805_800_851_2]],
+        multimodal: true,
+    },
+    TestFunctionInfo {
+        name: "goldsteinprice",
+        dimension: Dimension::Fixed(2),
+        bounds: &[(-2.0, 2.0), (-2.0, 2.0)],
+        minimum: GlobalMinimum::Constant(3.0),
+        minimizers: &[&[0.0, -1.0]],
+        multimodal: true,
+    },
+    TestFunctionInfo {
+        name: "himmelblau",
+        dimension: Dimension::Fixed(2),
+        bounds: &[(-5.0, 5.0), (-5.0, 5.0)],
+        minimum: GlobalMinimum::Constant(0.0),
+        minimizers: &[
+            &[3.0, 2.0],
+            &[-2.805_118_086_952_745, 3.131_312_518_250_573],
+            &[-3.779_310_253_377_747, -3.283_185_991_286_17],
+            &[3.584_428_340_330_492, -1.848_126_526_964_404],
+        ],
+        multimodal: true,
+    },
+    TestFunctionInfo {
+        name: "holder_table",
+        dimension: Dimension::Fixed(2),
+        bounds: &[(-10.0, 10.0), (-10.0, 10.0)],
+        minimum: GlobalMinimum::Constant(-19.208_502_567_886_74),
+        minimizers: &[
+            &[8.055_023_472_141_116, 9.664_590_028_909_654],
+            &[8.055_023_472_141_116, -9.664_590_028_909_654],
+            &[-8.055_023_472_141_116, 9.664_590_028_909_654],
+            &[-8.055_023_472_141_116, -9.664_590_028_909_654],
+        ],
+        multimodal: true,
+    },
+    TestFunctionInfo {
+        name: "levy",
+        dimension: Dimension::Variable { min: 2 },
+        bounds: &[(-10.0, 10.0)],
+        minimum: GlobalMinimum::Constant(0.0),
+        minimizers: &[&[1.0]],
+        multimodal: true,
+    },
+    TestFunctionInfo {
+        name: "levy_n13",
+        dimension: Dimension::Fixed(2),
+        bounds: &[(-10.0, 10.0), (-10.0, 10.0)],
+        minimum: GlobalMinimum::Constant(0.0),
+        minimizers: &[&[1.0, 1.0]],
+        multimodal: true,
+    },
+    TestFunctionInfo {
+        name: "matyas",
+        dimension: Dimension::Fixed(2),
+        bounds: &[(-10.0, 10.0), (-10.0, 10.0)],
+        minimum: GlobalMinimum::Constant(0.0),
+        minimizers: &[&[0.0, 0.0]],
+        multimodal: false,
+    },
+    TestFunctionInfo {
+        name: "mccorminck",
+        dimension: Dimension::Fixed(2),
+        bounds: &[(-1.5, 4.0), (-3.0, 4.0)],
+        minimum: GlobalMinimum::Constant(-1.913_222_954_981_037),
+        minimizers: &[&[-0.547_197_551_196_598, -1.547_197_551_196_598]],
+        multimodal: true,
+    },
+    TestFunctionInfo {
+        name: "picheny",
+        dimension: Dimension::Fixed(2),
+        bounds: &[(0.0, 1.0), (0.0, 1.0)],
+        minimum: GlobalMinimum::Constant(-3.385_199_318_203_682_6),
+        minimizers: &[&[0.5, 0.25]],
+        multimodal: true,
+    },
+    TestFunctionInfo {
+        name: "rastrigin",
+        dimension: Dimension::Variable { min: 1 },
+        bounds: &[(-5.12, 5.12)],
+        minimum: GlobalMinimum::Constant(0.0),
+        minimizers: &[&[0.0]],
+        multimodal: true,
+    },
+    TestFunctionInfo {
+        name: "rosenbrock",
+        dimension: Dimension::Variable { min: 2 },
+        bounds: &[(-5.0, 10.0)],
+        minimum: GlobalMinimum::Constant(0.0),
+        minimizers: &[&[1.0]],
+        multimodal: false,
+    },
+    TestFunctionInfo {
+        name: "schaffer_n2",
+        dimension: Dimension::Fixed(2),
+        bounds: &[(-100.0, 100.0), (-100.0, 100.0)],
+        minimum: GlobalMinimum::Constant(0.0),
+        minimizers: &[&[0.0, 0.0]],
+        multimodal: true,
+    },
+    TestFunctionInfo {
+        name: "schaffer_n4",
+        dimension: Dimension::Fixed(2),
+        bounds: &[(-100.0, 100.0), (-100.0, 100.0)],
+        minimum: GlobalMinimum::Constant(0.292_578_632_035_980_56),
+        minimizers: &[
+            &[0.0, 1.253_131_828_792_882],
+            &[0.0, -1.253_131_828_792_882],
+            &[1.253_131_828_792_882, 0.0],
+            &[-1.253_131_828_792_882, 0.0],
+        ],
+        multimodal: true,
+    },
+    TestFunctionInfo {
+        name: "sphere",
+        dimension: Dimension::Variable { min: 1 },
+        bounds: &[(-5.12, 5.12)],
+        minimum: GlobalMinimum::Constant(0.0),
+        minimizers: &[&[0.0]],
+        multimodal: false,
+    },
+    TestFunctionInfo {
+        name: "styblinski_tang",
+        dimension: Dimension::Variable { min: 1 },
+        bounds: &[(-5.0, 5.0)],
+        minimum: GlobalMinimum::PerDimension(-39.166_165_703_771_42),
+        minimizers: &[&[-2.903_534_027_771_178]],
+        multimodal: true,
+    },
+    TestFunctionInfo {
+        name: "threehumpcamel",
+        dimension: Dimension::Fixed(2),
+        bounds: &[(-5.0, 5.0), (-5.0, 5.0)],
+        minimum: GlobalMinimum::Constant(0.0),
+        minimizers: &[&[0.0, 0.0]],
+        multimodal: true,
+    },
+];
+
+/// Returns the metadata of the test function called `name`
+pub fn test_function_info(name: &str) -> Option<&'static TestFunctionInfo> {
+    TEST_FUNCTIONS.iter().find(|info| info.name == name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::*;
+    use approx::assert_relative_eq;
+
+    fn eval(name: &str, x: &[f64]) -> f64 {
+        let x2 = || -> &[f64; 2] { x.try_into().unwrap() };
+        match name {
+            "ackley" => ackley(x),
+            "beale" => beale(x2()),
+            "booth" => booth(x2()),
+            "bukin_n6" => bukin_n6(x2()),
+            "cross_in_tray" => cross_in_tray(x2()),
+            "easom" => easom(x2()),
+            "eggholder" => eggholder(x2()),
+            "goldsteinprice" => goldsteinprice(x2()),
+            "himmelblau" => himmelblau(x2()),
+            "holder_table" => holder_table(x2()),
+            "levy" => levy(x),
+            "levy_n13" => levy_n13(x2()),
+            "matyas" => matyas(x2()),
+            "mccorminck" => mccorminck(x2()),
+            "picheny" => picheny(x2()),
+            "rastrigin" => rastrigin(x),
+            "rosenbrock" => rosenbrock(x),
+            "schaffer_n2" => schaffer_n2(x2()),
+            "schaffer_n4" => schaffer_n4(x2()),
+            "sphere" => sphere(x),
+            "styblinski_tang" => styblinski_tang(x),
+            "threehumpcamel" => threehumpcamel(x2()),
+            _ => panic!("unknown test function {name}"),
+        }
+    }
+
+    #[test]
+    fn test_catalogue_minimizers() {
+        for info in TEST_FUNCTIONS {
+            let ns = match info.dimension {
+                Dimension::Fixed(n) => vec![n],
+                Dimension::Variable { min } => vec![min, min + 1, 10],
+            };
+            for n in ns {
+                let bounds = info.bounds(n);
+                assert_eq!(bounds.len(), n);
+                for m in info.minimizers(n) {
+                    assert_eq!(m.len(), n);
+                    for (x, (lo, hi)) in m.iter().zip(bounds.iter()) {
+                        assert!(lo <= x && x <= hi, "{}", info.name);
+                    }
+                    let gap = info.optimality_gap(eval(info.name, &m), n);
+                    assert_relative_eq!(gap, 0.0, epsilon = 1e-10);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_catalogue_sorted() {
+        for w in TEST_FUNCTIONS.windows(2) {
+            assert!(w[0].name < w[1].name);
+        }
+    }
+
+    #[test]
+    fn test_test_function_info() {
+        let info = test_function_info("styblinski_tang").unwrap();
+        assert_eq!(info.dimension, Dimension::Variable { min: 1 });
+        assert_relative_eq!(info.minimum(2), -78.332_331_407_542_84);
+        assert!(info.multimodal);
+        assert!(test_function_info("does_not_exist").is_none());
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_catalogue_wrong_dimension() {
+        test_function_info("beale").unwrap().bounds(3);
+    }
+}
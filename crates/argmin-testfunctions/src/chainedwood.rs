@@ -0,0 +1,178 @@
+// Copyright 2018-2024 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+//! # Chained Wood test function
+//!
+//! Defined as
+//!
+//! `f(x_1, x_2, ..., x_n) = \sum_{j=1,3,5,...}^{n-3} \left[ 100*(x_j^2 - x_{j+1})^2 + (x_j - 1)^2
+//! + 90*(x_{j+2}^2 - x_{j+3})^2 + (x_{j+2} - 1)^2 + 10*(x_{j+1} + x_{j+3} - 2)^2
+//! + 0.1*(x_{j+1} - x_{j+3})^2 \right]`
+//!
+//! where `n >= 4` is even. For `n = 4`, this is the Wood function.
+//!
+//! The global minimum is at `f(x_1, x_2, ..., x_n) = f(1, 1, ..., 1) = 0`. The usual starting
+//! point is `(-3, -1, -3, -1, ...)`.
+//!
+//! Reference: L. Lukšan and J. Vlček, "Test Problems for Unconstrained Optimization", Technical
+//! Report 897, Institute of Computer Science, Academy of Sciences of the Czech Republic, 2003.
+
+use num::{Float, FromPrimitive};
+use std::iter::Sum;
+
+/// Chained Wood test function
+///
+/// Defined as
+///
+/// `f(x_1, x_2, ..., x_n) = \sum_{j=1,3,5,...}^{n-3} \left[ 100*(x_j^2 - x_{j+1})^2 + (x_j - 1)^2
+/// + 90*(x_{j+2}^2 - x_{j+3})^2 + (x_{j+2} - 1)^2 + 10*(x_{j+1} + x_{j+3} - 2)^2
+/// + 0.1*(x_{j+1} - x_{j+3})^2 \right]`
+///
+/// where `n >= 4` is even.
+///
+/// The global minimum is at `f(x_1, x_2, ..., x_n) = f(1, 1, ..., 1) = 0`.
+pub fn chained_wood<T>(param: &[T]) -> T
+where
+    T: Float + FromPrimitive + Sum,
+{
+    assert!(param.len() >= 4);
+    assert_eq!(param.len() % 2, 0);
+
+    let n0_1 = T::from_f64(0.1).unwrap();
+    let n1 = T::from_f64(1.0).unwrap();
+    let n2 = T::from_f64(2.0).unwrap();
+    let n10 = T::from_f64(10.0).unwrap();
+    let n90 = T::from_f64(90.0).unwrap();
+    let n100 = T::from_f64(100.0).unwrap();
+
+    param
+        .windows(4)
+        .step_by(2)
+        .map(|x| {
+            n100 * (x[0].powi(2) - x[1]).powi(2)
+                + (x[0] - n1).powi(2)
+                + n90 * (x[2].powi(2) - x[3]).powi(2)
+                + (x[2] - n1).powi(2)
+                + n10 * (x[1] + x[3] - n2).powi(2)
+                + n0_1 * (x[1] - x[3]).powi(2)
+        })
+        .sum()
+}
+
+fn chained_wood_derivative_into<T>(param: &[T], out: &mut [T])
+where
+    T: Float + FromPrimitive,
+{
+    assert!(param.len() >= 4);
+    assert_eq!(param.len() % 2, 0);
+
+    let n0_2 = T::from_f64(0.2).unwrap();
+    let n1 = T::from_f64(1.0).unwrap();
+    let n2 = T::from_f64(2.0).unwrap();
+    let n20 = T::from_f64(20.0).unwrap();
+    let n180 = T::from_f64(180.0).unwrap();
+    let n200 = T::from_f64(200.0).unwrap();
+    let n360 = T::from_f64(360.0).unwrap();
+    let n400 = T::from_f64(400.0).unwrap();
+
+    out.iter_mut().for_each(|o| *o = T::zero());
+    for j in (0..param.len() - 3).step_by(2) {
+        let [a, b, c, d] = [param[j], param[j + 1], param[j + 2], param[j + 3]];
+        let t1 = a.powi(2) - b;
+        let t2 = c.powi(2) - d;
+        let t3 = n20 * (b + d - n2);
+        let t4 = n0_2 * (b - d);
+        out[j] = out[j] + n400 * a * t1 + n2 * (a - n1);
+        out[j + 1] = out[j + 1] - n200 * t1 + t3 + t4;
+        out[j + 2] = out[j + 2] + n360 * c * t2 + n2 * (c - n1);
+        out[j + 3] = out[j + 3] - n180 * t2 + t3 - t4;
+    }
+}
+
+/// Derivative of chained Wood test function
+pub fn chained_wood_derivative<T>(param: &[T]) -> Vec<T>
+where
+    T: Float + FromPrimitive,
+{
+    let mut out = vec![T::zero(); param.len()];
+    chained_wood_derivative_into(param, &mut out);
+    out
+}
+
+/// Derivative of chained Wood test function
+///
+/// This is the const generics version, which requires the number of parameters to be known
+/// at compile time.
+pub fn chained_wood_derivative_const<const N: usize, T>(param: &[T; N]) -> [T; N]
+where
+    T: Float + FromPrimitive,
+{
+    let mut out = [T::zero(); N];
+    chained_wood_derivative_into(param, &mut out);
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_relative_eq;
+    use finitediff::FiniteDiff;
+    use proptest::prelude::*;
+    use std::f32;
+
+    #[test]
+    fn test_chained_wood_optimum() {
+        assert_relative_eq!(chained_wood(&[1.0_f32; 8]), 0.0, epsilon = f32::EPSILON);
+        assert_relative_eq!(chained_wood(&[1.0_f64; 100]), 0.0, epsilon = f64::EPSILON);
+
+        for d in chained_wood_derivative(&[1.0_f64; 100]) {
+            assert_relative_eq!(d, 0.0, epsilon = f64::EPSILON);
+        }
+    }
+
+    #[test]
+    fn test_wood() {
+        // For n = 4, this is the Wood function
+        assert_relative_eq!(
+            chained_wood(&[-3.0_f64, -1.0, -3.0, -1.0]),
+            19192.0,
+            epsilon = 1e-10
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_chained_wood_param_length() {
+        chained_wood(&[0.0_f64; 5]);
+    }
+
+    proptest! {
+        #[test]
+        fn test_chained_wood_derivative_finitediff(a in -5.0..5.0,
+                                                   b in -5.0..5.0,
+                                                   c in -5.0..5.0,
+                                                   d in -5.0..5.0,
+                                                   e in -5.0..5.0,
+                                                   f in -5.0..5.0,
+                                                   g in -5.0..5.0,
+                                                   h in -5.0..5.0) {
+            let param = [a, b, c, d, e, f, g, h];
+            let derivative = chained_wood_derivative(&param);
+            let derivative_const = chained_wood_derivative_const(&param);
+            let derivative_fd = Vec::from(param).central_diff(&|x| chained_wood(x));
+            for i in 0..derivative.len() {
+                assert_relative_eq!(derivative[i], derivative_const[i]);
+                assert_relative_eq!(
+                    derivative[i],
+                    derivative_fd[i],
+                    epsilon = 1e-4,
+                    max_relative = 1e-2
+                );
+            }
+        }
+    }
+}
@@ -0,0 +1,148 @@
+// Copyright 2018-2024 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+//! # Extended Rosenbrock test function
+//!
+//! Defined as
+//!
+//! `f(x_1, x_2, ..., x_n) = \sum_{i=1}^{n/2} \left[ 100 * (x_{2i} - x_{2i-1}^2)^2 + (1 - x_{2i-1})^2 \right]`
+//!
+//! where `n` is even. In contrast to the [Rosenbrock function](`crate::rosenbrock`), the
+//! parameters are coupled pairwise only.
+//!
+//! The global minimum is at `f(x_1, x_2, ..., x_n) = f(1, 1, ..., 1) = 0`. The usual starting
+//! point is `(-1.2, 1, -1.2, 1, ...)`.
+//!
+//! Reference: J. J. Moré, B. S. Garbow and K. E. Hillstrom, "Testing Unconstrained
+//! Optimization Software", ACM Transactions on Mathematical Software, 7(1), 1981.
+
+use num::{Float, FromPrimitive};
+use std::iter::Sum;
+
+/// Extended Rosenbrock test function
+///
+/// Defined as
+///
+/// `f(x_1, x_2, ..., x_n) = \sum_{i=1}^{n/2} \left[ 100 * (x_{2i} - x_{2i-1}^2)^2 + (1 - x_{2i-1})^2 \right]`
+///
+/// where `n` is even.
+///
+/// The global minimum is at `f(x_1, x_2, ..., x_n) = f(1, 1, ..., 1) = 0`.
+pub fn extended_rosenbrock<T>(param: &[T]) -> T
+where
+    T: Float + FromPrimitive + Sum,
+{
+    assert!(param.len() >= 2);
+    assert_eq!(param.len() % 2, 0);
+
+    let n1 = T::from_f64(1.0).unwrap();
+    let n100 = T::from_f64(100.0).unwrap();
+
+    param
+        .chunks_exact(2)
+        .map(|x| n100 * (x[1] - x[0].powi(2)).powi(2) + (n1 - x[0]).powi(2))
+        .sum()
+}
+
+fn extended_rosenbrock_derivative_into<T>(param: &[T], out: &mut [T])
+where
+    T: Float + FromPrimitive,
+{
+    assert!(param.len() >= 2);
+    assert_eq!(param.len() % 2, 0);
+
+    let n1 = T::from_f64(1.0).unwrap();
+    let n2 = T::from_f64(2.0).unwrap();
+    let n200 = T::from_f64(200.0).unwrap();
+    let n400 = T::from_f64(400.0).unwrap();
+
+    for (x, o) in param.chunks_exact(2).zip(out.chunks_exact_mut(2)) {
+        let t = x[1] - x[0].powi(2);
+        o[0] = -n400 * x[0] * t - n2 * (n1 - x[0]);
+        o[1] = n200 * t;
+    }
+}
+
+/// Derivative of extended Rosenbrock test function
+pub fn extended_rosenbrock_derivative<T>(param: &[T]) -> Vec<T>
+where
+    T: Float + FromPrimitive,
+{
+    let mut out = vec![T::zero(); param.len()];
+    extended_rosenbrock_derivative_into(param, &mut out);
+    out
+}
+
+/// Derivative of extended Rosenbrock test function
+///
+/// This is the const generics version, which requires the number of parameters to be known
+/// at compile time.
+pub fn extended_rosenbrock_derivative_const<const N: usize, T>(param: &[T; N]) -> [T; N]
+where
+    T: Float + FromPrimitive,
+{
+    let mut out = [T::zero(); N];
+    extended_rosenbrock_derivative_into(param, &mut out);
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_relative_eq;
+    use finitediff::FiniteDiff;
+    use proptest::prelude::*;
+    use std::f32;
+
+    #[test]
+    fn test_extended_rosenbrock_optimum() {
+        assert_relative_eq!(
+            extended_rosenbrock(&[1.0_f32; 6]),
+            0.0,
+            epsilon = f32::EPSILON
+        );
+        assert_relative_eq!(
+            extended_rosenbrock(&[1.0_f64; 100]),
+            0.0,
+            epsilon = f64::EPSILON
+        );
+
+        for d in extended_rosenbrock_derivative(&[1.0_f64; 100]) {
+            assert_relative_eq!(d, 0.0, epsilon = f64::EPSILON);
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_extended_rosenbrock_param_length() {
+        extended_rosenbrock(&[0.0_f64; 3]);
+    }
+
+    proptest! {
+        #[test]
+        fn test_extended_rosenbrock_derivative_finitediff(a in -5.0..5.0,
+                                                          b in -5.0..5.0,
+                                                          c in -5.0..5.0,
+                                                          d in -5.0..5.0,
+                                                          e in -5.0..5.0,
+                                                          f in -5.0..5.0) {
+            let param = [a, b, c, d, e, f];
+            let derivative = extended_rosenbrock_derivative(&param);
+            let derivative_const = extended_rosenbrock_derivative_const(&param);
+            let derivative_fd = Vec::from(param).central_diff(&|x| extended_rosenbrock(x));
+            for i in 0..derivative.len() {
+                assert_relative_eq!(derivative[i], derivative_const[i]);
+                assert_relative_eq!(
+                    derivative[i],
+                    derivative_fd[i],
+                    epsilon = 1e-4,
+                    max_relative = 1e-2
+                );
+            }
+        }
+    }
+}
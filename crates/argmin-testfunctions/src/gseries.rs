@@ -0,0 +1,454 @@
+// Copyright 2018-2024 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+//! # G-series constrained test problems
+//!
+//! A subset of the constrained benchmark problems collected in
+//!
+//! Liang et al., "Problem Definitions and Evaluation Criteria for the CEC 2006 Special Session on
+//! Constrained Real-Parameter Optimization", 2006.
+//!
+//! Inequality constraints are stated as `g_i(x) <= 0` and equality constraints as `h_j(x) = 0`.
+//! For each problem, the objective (`<name>`), its derivative (`<name>_derivative`), the
+//! constraints (`<name>_ineq` and/or `<name>_eq`) and their Jacobians (`<name>_ineq_jacobian`
+//! and/or `<name>_eq_jacobian`) are provided. The rows of the Jacobians correspond to the
+//! constraints. Bounds and the known optimum are available as constants.
+
+use num::{Float, FromPrimitive};
+use std::f64::consts::PI;
+
+/// Bounds `(lower, upper)` of each parameter of G06
+pub const G06_BOUNDS: [(f64, f64); 2] = [(13.0, 100.0), (0.0, 100.0)];
+
+/// Minimizer of G06
+pub const G06_OPTIMUM: [f64; 2] = [14.095, 0.842_960_789_215_479_6];
+
+/// Minimum of G06
+pub const G06_OPTIMUM_VALUE: f64 = -6_961.813_875_580_15;
+
+/// G06 test problem
+///
+/// Defined as
+///
+/// `f(x_1, x_2) = (x_1 - 10)^3 + (x_2 - 20)^3`
+///
+/// subject to
+///
+/// `g_1(x_1, x_2) = -(x_1 - 5)^2 - (x_2 - 5)^2 + 100 <= 0`
+///
+/// `g_2(x_1, x_2) = (x_1 - 6)^2 + (x_2 - 5)^2 - 82.81 <= 0`
+///
+/// where `x_1 \in [13, 100]` and `x_2 \in [0, 100]`.
+///
+/// The minimum is at `f(14.095, 0.84296) = -6961.81388`. Both constraints are active.
+pub fn g06<T>(param: &[T; 2]) -> T
+where
+    T: Float + FromPrimitive,
+{
+    let n10 = T::from_f64(10.0).unwrap();
+    let n20 = T::from_f64(20.0).unwrap();
+
+    let [x1, x2] = *param;
+    (x1 - n10).powi(3) + (x2 - n20).powi(3)
+}
+
+/// Derivative of G06 test problem
+pub fn g06_derivative<T>(param: &[T; 2]) -> [T; 2]
+where
+    T: Float + FromPrimitive,
+{
+    let n3 = T::from_f64(3.0).unwrap();
+    let n10 = T::from_f64(10.0).unwrap();
+    let n20 = T::from_f64(20.0).unwrap();
+
+    let [x1, x2] = *param;
+    [n3 * (x1 - n10).powi(2), n3 * (x2 - n20).powi(2)]
+}
+
+/// Inequality constraints of G06 test problem
+pub fn g06_ineq<T>(param: &[T; 2]) -> [T; 2]
+where
+    T: Float + FromPrimitive,
+{
+    let n5 = T::from_f64(5.0).unwrap();
+    let n6 = T::from_f64(6.0).unwrap();
+    let n82_81 = T::from_f64(82.81).unwrap();
+    let n100 = T::from_f64(100.0).unwrap();
+
+    let [x1, x2] = *param;
+    [
+        -(x1 - n5).powi(2) - (x2 - n5).powi(2) + n100,
+        (x1 - n6).powi(2) + (x2 - n5).powi(2) - n82_81,
+    ]
+}
+
+/// Jacobian of the inequality constraints of G06 test problem
+pub fn g06_ineq_jacobian<T>(param: &[T; 2]) -> [[T; 2]; 2]
+where
+    T: Float + FromPrimitive,
+{
+    let n2 = T::from_f64(2.0).unwrap();
+    let n5 = T::from_f64(5.0).unwrap();
+    let n6 = T::from_f64(6.0).unwrap();
+
+    let [x1, x2] = *param;
+    [
+        [-n2 * (x1 - n5), -n2 * (x2 - n5)],
+        [n2 * (x1 - n6), n2 * (x2 - n5)],
+    ]
+}
+
+/// Bounds `(lower, upper)` of each parameter of G08
+pub const G08_BOUNDS: [(f64, f64); 2] = [(0.0, 10.0), (0.0, 10.0)];
+
+/// Minimizer of G08
+pub const G08_OPTIMUM: [f64; 2] = [1.227_971_352_607_526, 4.245_373_366_122_749];
+
+/// Minimum of G08
+pub const G08_OPTIMUM_VALUE: f64 = -0.095_825_041_418_035_9;
+
+/// G08 test problem
+///
+/// Defined as
+///
+/// `f(x_1, x_2) = -sin^3(2*pi*x_1) * sin(2*pi*x_2) / (x_1^3 * (x_1 + x_2))`
+///
+/// subject to
+///
+/// `g_1(x_1, x_2) = x_1^2 - x_2 + 1 <= 0`
+///
+/// `g_2(x_1, x_2) = 1 - x_1 + (x_2 - 4)^2 <= 0`
+///
+/// where `x_i \in [0, 10]`.
+///
+/// The minimum is at `f(1.22797, 4.24537) = -0.0958250`. It lies in the interior of the feasible
+/// region, which contains several local minima.
+pub fn g08<T>(param: &[T; 2]) -> T
+where
+    T: Float + FromPrimitive,
+{
+    let pi2 = T::from_f64(2.0 * PI).unwrap();
+
+    let [x1, x2] = *param;
+    -(pi2 * x1).sin().powi(3) * (pi2 * x2).sin() / (x1.powi(3) * (x1 + x2))
+}
+
+/// Derivative of G08 test problem
+pub fn g08_derivative<T>(param: &[T; 2]) -> [T; 2]
+where
+    T: Float + FromPrimitive,
+{
+    let n3 = T::from_f64(3.0).unwrap();
+    let n4 = T::from_f64(4.0).unwrap();
+    let pi2 = T::from_f64(2.0 * PI).unwrap();
+
+    let [x1, x2] = *param;
+    let (s1, c1) = (pi2 * x1).sin_cos();
+    let (s2, c2) = (pi2 * x2).sin_cos();
+
+    let num = s1.powi(3) * s2;
+    let den = x1.powi(3) * (x1 + x2);
+    let num_x1 = n3 * pi2 * s1.powi(2) * c1 * s2;
+    let num_x2 = pi2 * s1.powi(3) * c2;
+    let den_x1 = n4 * x1.powi(3) + n3 * x1.powi(2) * x2;
+    let den_x2 = x1.powi(3);
+    let den2 = den.powi(2);
+
+    [
+        -(num_x1 * den - num * den_x1) / den2,
+        -(num_x2 * den - num * den_x2) / den2,
+    ]
+}
+
+/// Inequality constraints of G08 test problem
+pub fn g08_ineq<T>(param: &[T; 2]) -> [T; 2]
+where
+    T: Float + FromPrimitive,
+{
+    let n1 = T::from_f64(1.0).unwrap();
+    let n4 = T::from_f64(4.0).unwrap();
+
+    let [x1, x2] = *param;
+    [x1.powi(2) - x2 + n1, n1 - x1 + (x2 - n4).powi(2)]
+}
+
+/// Jacobian of the inequality constraints of G08 test problem
+pub fn g08_ineq_jacobian<T>(param: &[T; 2]) -> [[T; 2]; 2]
+where
+    T: Float + FromPrimitive,
+{
+    let n1 = T::from_f64(1.0).unwrap();
+    let n2 = T::from_f64(2.0).unwrap();
+    let n4 = T::from_f64(4.0).unwrap();
+
+    let [x1, x2] = *param;
+    [[n2 * x1, -n1], [-n1, n2 * (x2 - n4)]]
+}
+
+/// Bounds `(lower, upper)` of each parameter of G11
+pub const G11_BOUNDS: [(f64, f64); 2] = [(-1.0, 1.0), (-1.0, 1.0)];
+
+/// Minimizer of G11 (the second minimizer is `[-G11_OPTIMUM[0], G11_OPTIMUM[1]]`)
+pub const G11_OPTIMUM: [f64; 2] = [std::f64::consts::FRAC_1_SQRT_2, 0.5];
+
+/// Minimum of G11
+pub const G11_OPTIMUM_VALUE: f64 = 0.75;
+
+/// G11 test problem
+///
+/// Defined as
+///
+/// `f(x_1, x_2) = x_1^2 + (x_2 - 1)^2`
+///
+/// subject to
+///
+/// `h(x_1, x_2) = x_2 - x_1^2 = 0`
+///
+/// where `x_i \in [-1, 1]`.
+///
+/// The minimum is at `f(+-1/sqrt(2), 1/2) = 0.75`.
+pub fn g11<T>(param: &[T; 2]) -> T
+where
+    T: Float + FromPrimitive,
+{
+    let n1 = T::from_f64(1.0).unwrap();
+
+    let [x1, x2] = *param;
+    x1.powi(2) + (x2 - n1).powi(2)
+}
+
+/// Derivative of G11 test problem
+pub fn g11_derivative<T>(param: &[T; 2]) -> [T; 2]
+where
+    T: Float + FromPrimitive,
+{
+    let n1 = T::from_f64(1.0).unwrap();
+    let n2 = T::from_f64(2.0).unwrap();
+
+    let [x1, x2] = *param;
+    [n2 * x1, n2 * (x2 - n1)]
+}
+
+/// Equality constraint of G11 test problem
+pub fn g11_eq<T>(param: &[T; 2]) -> [T; 1]
+where
+    T: Float + FromPrimitive,
+{
+    let [x1, x2] = *param;
+    [x2 - x1.powi(2)]
+}
+
+/// Jacobian of the equality constraint of G11 test problem
+pub fn g11_eq_jacobian<T>(param: &[T; 2]) -> [[T; 2]; 1]
+where
+    T: Float + FromPrimitive,
+{
+    let n1 = T::from_f64(1.0).unwrap();
+    let n2 = T::from_f64(2.0).unwrap();
+
+    let [x1, _] = *param;
+    [[-n2 * x1, n1]]
+}
+
+/// Bounds `(lower, upper)` of each parameter of G24
+pub const G24_BOUNDS: [(f64, f64); 2] = [(0.0, 3.0), (0.0, 4.0)];
+
+/// Minimizer of G24
+pub const G24_OPTIMUM: [f64; 2] = [2.329_520_197_477_623, 3.178_493_074_117_74];
+
+/// Minimum of G24
+pub const G24_OPTIMUM_VALUE: f64 = -5.508_013_271_595_36;
+
+/// G24 test problem
+///
+/// Defined as
+///
+/// `f(x_1, x_2) = -x_1 - x_2`
+///
+/// subject to
+///
+/// `g_1(x_1, x_2) = -2*x_1^4 + 8*x_1^3 - 8*x_1^2 + x_2 - 2 <= 0`
+///
+/// `g_2(x_1, x_2) = -4*x_1^4 + 32*x_1^3 - 88*x_1^2 + 96*x_1 + x_2 - 36 <= 0`
+///
+/// where `x_1 \in [0, 3]` and `x_2 \in [0, 4]`.
+///
+/// The minimum is at `f(2.32952, 3.17849) = -5.50801`. The feasible region consists of two
+/// disconnected parts.
+pub fn g24<T>(param: &[T; 2]) -> T
+where
+    T: Float + FromPrimitive,
+{
+    let [x1, x2] = *param;
+    -x1 - x2
+}
+
+/// Derivative of G24 test problem
+///
+/// Returns [-1, -1] for every input.
+pub fn g24_derivative<T>(_param: &[T; 2]) -> [T; 2]
+where
+    T: Float + FromPrimitive,
+{
+    let n1 = T::from_f64(1.0).unwrap();
+
+    [-n1, -n1]
+}
+
+/// Inequality constraints of G24 test problem
+pub fn g24_ineq<T>(param: &[T; 2]) -> [T; 2]
+where
+    T: Float + FromPrimitive,
+{
+    let n2 = T::from_f64(2.0).unwrap();
+    let n4 = T::from_f64(4.0).unwrap();
+    let n8 = T::from_f64(8.0).unwrap();
+    let n32 = T::from_f64(32.0).unwrap();
+    let n36 = T::from_f64(36.0).unwrap();
+    let n88 = T::from_f64(88.0).unwrap();
+    let n96 = T::from_f64(96.0).unwrap();
+
+    let [x1, x2] = *param;
+    [
+        -n2 * x1.powi(4) + n8 * x1.powi(3) - n8 * x1.powi(2) + x2 - n2,
+        -n4 * x1.powi(4) + n32 * x1.powi(3) - n88 * x1.powi(2) + n96 * x1 + x2 - n36,
+    ]
+}
+
+/// Jacobian of the inequality constraints of G24 test problem
+pub fn g24_ineq_jacobian<T>(param: &[T; 2]) -> [[T; 2]; 2]
+where
+    T: Float + FromPrimitive,
+{
+    let n1 = T::from_f64(1.0).unwrap();
+    let n8 = T::from_f64(8.0).unwrap();
+    let n16 = T::from_f64(16.0).unwrap();
+    let n24 = T::from_f64(24.0).unwrap();
+    let n96 = T::from_f64(96.0).unwrap();
+    let n176 = T::from_f64(176.0).unwrap();
+
+    let [x1, _] = *param;
+    [
+        [-n8 * x1.powi(3) + n24 * x1.powi(2) - n16 * x1, n1],
+        [-n16 * x1.powi(3) + n96 * x1.powi(2) - n176 * x1 + n96, n1],
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_relative_eq;
+    use finitediff::FiniteDiff;
+    use proptest::prelude::*;
+
+    fn assert_feasible<const M: usize>(ineq: [f64; M], tol: f64) {
+        for g in ineq {
+            assert!(g <= tol, "constraint violated: {g}");
+        }
+    }
+
+    #[test]
+    fn test_g06_optimum() {
+        assert_relative_eq!(g06(&G06_OPTIMUM), G06_OPTIMUM_VALUE, epsilon = 1e-8);
+        assert_feasible(g06_ineq(&G06_OPTIMUM), 1e-8);
+    }
+
+    #[test]
+    fn test_g08_optimum() {
+        assert_relative_eq!(g08(&G08_OPTIMUM), G08_OPTIMUM_VALUE, epsilon = 1e-12);
+        assert_feasible(g08_ineq(&G08_OPTIMUM), 0.0);
+
+        for d in g08_derivative(&G08_OPTIMUM) {
+            assert_relative_eq!(d, 0.0, epsilon = 1e-6);
+        }
+    }
+
+    #[test]
+    fn test_g11_optimum() {
+        assert_relative_eq!(g11(&G11_OPTIMUM), G11_OPTIMUM_VALUE, epsilon = 1e-12);
+        assert_relative_eq!(g11_eq(&G11_OPTIMUM)[0], 0.0, epsilon = 1e-12);
+        let other = [-G11_OPTIMUM[0], G11_OPTIMUM[1]];
+        assert_relative_eq!(g11(&other), G11_OPTIMUM_VALUE, epsilon = 1e-12);
+        assert_relative_eq!(g11_eq(&other)[0], 0.0, epsilon = 1e-12);
+    }
+
+    #[test]
+    fn test_g24_optimum() {
+        assert_relative_eq!(g24(&G24_OPTIMUM), G24_OPTIMUM_VALUE, epsilon = 1e-12);
+        assert_feasible(g24_ineq(&G24_OPTIMUM), 1e-8);
+    }
+
+    #[test]
+    fn test_optima_within_bounds() {
+        for (optimum, bounds) in [
+            (G06_OPTIMUM, G06_BOUNDS),
+            (G08_OPTIMUM, G08_BOUNDS),
+            (G11_OPTIMUM, G11_BOUNDS),
+            (G24_OPTIMUM, G24_BOUNDS),
+        ] {
+            for (x, (lower, upper)) in optimum.iter().zip(bounds) {
+                assert!(lower <= *x && *x <= upper);
+            }
+        }
+    }
+
+    /// Compares the derivative of `f` and the Jacobian of the constraints `c` with finite
+    /// differences at `param`.
+    fn check_derivatives<const M: usize>(
+        param: [f64; 2],
+        f: fn(&[f64; 2]) -> f64,
+        df: fn(&[f64; 2]) -> [f64; 2],
+        c: fn(&[f64; 2]) -> [f64; M],
+        dc: fn(&[f64; 2]) -> [[f64; 2]; M],
+    ) {
+        let derivative = df(&param);
+        let derivative_fd = Vec::from(param).central_diff(&|x| f(&[x[0], x[1]]));
+        for i in 0..2 {
+            assert_relative_eq!(
+                derivative[i],
+                derivative_fd[i],
+                epsilon = 1e-4,
+                max_relative = 1e-2
+            );
+        }
+        let jacobian = dc(&param);
+        for (j, row) in jacobian.iter().enumerate() {
+            let row_fd = Vec::from(param).central_diff(&|x| c(&[x[0], x[1]])[j]);
+            for i in 0..2 {
+                assert_relative_eq!(row[i], row_fd[i], epsilon = 1e-4, max_relative = 1e-2);
+            }
+        }
+    }
+
+    proptest! {
+        #[test]
+        fn test_g06_derivative_finitediff(a in 13.0..100.0, b in 0.0..100.0) {
+            check_derivatives([a, b], g06, g06_derivative, g06_ineq, g06_ineq_jacobian);
+        }
+    }
+
+    proptest! {
+        #[test]
+        fn test_g08_derivative_finitediff(a in 0.5..10.0, b in 0.0..10.0) {
+            check_derivatives([a, b], g08, g08_derivative, g08_ineq, g08_ineq_jacobian);
+        }
+    }
+
+    proptest! {
+        #[test]
+        fn test_g11_derivative_finitediff(a in -1.0..1.0, b in -1.0..1.0) {
+            check_derivatives([a, b], g11, g11_derivative, g11_eq, g11_eq_jacobian);
+        }
+    }
+
+    proptest! {
+        #[test]
+        fn test_g24_derivative_finitediff(a in 0.0..3.0, b in 0.0..4.0) {
+            check_derivatives([a, b], g24, g24_derivative, g24_ineq, g24_ineq_jacobian);
+        }
+    }
+}
@@ -0,0 +1,426 @@
+// Copyright 2018-2024 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+//! # Hock-Schittkowski constrained test problems
+//!
+//! A subset of the problems collected in
+//!
+//! W. Hock and K. Schittkowski, "Test Examples for Nonlinear Programming Codes", Lecture Notes in
+//! Economics and Mathematical Systems, vol. 187, Springer, 1981.
+//!
+//! As for the [G-series problems](`crate::g06`), inequality constraints are stated as
+//! `g_i(x) <= 0` and equality constraints as `h_j(x) = 0`. For each problem, the objective
+//! (`<name>`), its derivative (`<name>_derivative`), the constraints (`<name>_ineq` and/or
+//! `<name>_eq`) and their Jacobians (`<name>_ineq_jacobian` and/or `<name>_eq_jacobian`) are
+//! provided. Bounds and the known optimum are available as constants; unbounded parameters have
+//! infinite bounds.
+
+use num::{Float, FromPrimitive};
+
+/// Bounds `(lower, upper)` of each parameter of HS6
+pub const HS6_BOUNDS: [(f64, f64); 2] = [(f64::NEG_INFINITY, f64::INFINITY); 2];
+
+/// Minimizer of HS6
+pub const HS6_OPTIMUM: [f64; 2] = [1.0, 1.0];
+
+/// Minimum of HS6
+pub const HS6_OPTIMUM_VALUE: f64 = 0.0;
+
+/// Hock-Schittkowski problem 6
+///
+/// Defined as
+///
+/// `f(x_1, x_2) = (1 - x_1)^2`
+///
+/// subject to
+///
+/// `h(x_1, x_2) = 10 * (x_2 - x_1^2) = 0`.
+///
+/// The minimum is at `f(1, 1) = 0`. The usual starting point is `(-1.2, 1)`.
+pub fn hs6<T>(param: &[T; 2]) -> T
+where
+    T: Float + FromPrimitive,
+{
+    let n1 = T::from_f64(1.0).unwrap();
+
+    let [x1, _] = *param;
+    (n1 - x1).powi(2)
+}
+
+/// Derivative of Hock-Schittkowski problem 6
+pub fn hs6_derivative<T>(param: &[T; 2]) -> [T; 2]
+where
+    T: Float + FromPrimitive,
+{
+    let n1 = T::from_f64(1.0).unwrap();
+    let n2 = T::from_f64(2.0).unwrap();
+
+    let [x1, _] = *param;
+    [-n2 * (n1 - x1), T::zero()]
+}
+
+/// Equality constraint of Hock-Schittkowski problem 6
+pub fn hs6_eq<T>(param: &[T; 2]) -> [T; 1]
+where
+    T: Float + FromPrimitive,
+{
+    let n10 = T::from_f64(10.0).unwrap();
+
+    let [x1, x2] = *param;
+    [n10 * (x2 - x1.powi(2))]
+}
+
+/// Jacobian of the equality constraint of Hock-Schittkowski problem 6
+pub fn hs6_eq_jacobian<T>(param: &[T; 2]) -> [[T; 2]; 1]
+where
+    T: Float + FromPrimitive,
+{
+    let n10 = T::from_f64(10.0).unwrap();
+    let n20 = T::from_f64(20.0).unwrap();
+
+    let [x1, _] = *param;
+    [[-n20 * x1, n10]]
+}
+
+/// Bounds `(lower, upper)` of each parameter of HS21
+pub const HS21_BOUNDS: [(f64, f64); 2] = [(2.0, 50.0), (-50.0, 50.0)];
+
+/// Minimizer of HS21
+pub const HS21_OPTIMUM: [f64; 2] = [2.0, 0.0];
+
+/// Minimum of HS21
+pub const HS21_OPTIMUM_VALUE: f64 = -99.96;
+
+/// Hock-Schittkowski problem 21
+///
+/// Defined as
+///
+/// `f(x_1, x_2) = 0.01 * x_1^2 + x_2^2 - 100`
+///
+/// subject to
+///
+/// `g(x_1, x_2) = -10 * x_1 + x_2 + 10 <= 0`
+///
+/// where `x_1 \in [2, 50]` and `x_2 \in [-50, 50]`.
+///
+/// The minimum is at `f(2, 0) = -99.96`, where the lower bound of `x_1` is active.
+pub fn hs21<T>(param: &[T; 2]) -> T
+where
+    T: Float + FromPrimitive,
+{
+    let n0_01 = T::from_f64(0.01).unwrap();
+    let n100 = T::from_f64(100.0).unwrap();
+
+    let [x1, x2] = *param;
+    n0_01 * x1.powi(2) + x2.powi(2) - n100
+}
+
+/// Derivative of Hock-Schittkowski problem 21
+pub fn hs21_derivative<T>(param: &[T; 2]) -> [T; 2]
+where
+    T: Float + FromPrimitive,
+{
+    let n0_02 = T::from_f64(0.02).unwrap();
+    let n2 = T::from_f64(2.0).unwrap();
+
+    let [x1, x2] = *param;
+    [n0_02 * x1, n2 * x2]
+}
+
+/// Inequality constraint of Hock-Schittkowski problem 21
+pub fn hs21_ineq<T>(param: &[T; 2]) -> [T; 1]
+where
+    T: Float + FromPrimitive,
+{
+    let n10 = T::from_f64(10.0).unwrap();
+
+    let [x1, x2] = *param;
+    [-n10 * x1 + x2 + n10]
+}
+
+/// Jacobian of the inequality constraint of Hock-Schittkowski problem 21
+///
+/// Returns [[-10, 1]] for every input.
+pub fn hs21_ineq_jacobian<T>(_param: &[T; 2]) -> [[T; 2]; 1]
+where
+    T: Float + FromPrimitive,
+{
+    let n1 = T::from_f64(1.0).unwrap();
+    let n10 = T::from_f64(10.0).unwrap();
+
+    [[-n10, n1]]
+}
+
+/// Bounds `(lower, upper)` of each parameter of HS35
+pub const HS35_BOUNDS: [(f64, f64); 3] = [(0.0, f64::INFINITY); 3];
+
+/// Minimizer of HS35
+pub const HS35_OPTIMUM: [f64; 3] = [4.0 / 3.0, 7.0 / 9.0, 4.0 / 9.0];
+
+/// Minimum of HS35
+pub const HS35_OPTIMUM_VALUE: f64 = 1.0 / 9.0;
+
+/// Hock-Schittkowski problem 35 (Beale's problem)
+///
+/// Defined as
+///
+/// `f(x_1, x_2, x_3) = 9 - 8*x_1 - 6*x_2 - 4*x_3 + 2*x_1^2 + 2*x_2^2 + x_3^2 + 2*x_1*x_2 + 2*x_1*x_3`
+///
+/// subject to
+///
+/// `g(x_1, x_2, x_3) = x_1 + x_2 + 2*x_3 - 3 <= 0`
+///
+/// where `x_i >= 0`.
+///
+/// The minimum is at `f(4/3, 7/9, 4/9) = 1/9`.
+pub fn hs35<T>(param: &[T; 3]) -> T
+where
+    T: Float + FromPrimitive,
+{
+    let n2 = T::from_f64(2.0).unwrap();
+    let n4 = T::from_f64(4.0).unwrap();
+    let n6 = T::from_f64(6.0).unwrap();
+    let n8 = T::from_f64(8.0).unwrap();
+    let n9 = T::from_f64(9.0).unwrap();
+
+    let [x1, x2, x3] = *param;
+    n9 - n8 * x1 - n6 * x2 - n4 * x3
+        + n2 * x1.powi(2)
+        + n2 * x2.powi(2)
+        + x3.powi(2)
+        + n2 * x1 * x2
+        + n2 * x1 * x3
+}
+
+/// Derivative of Hock-Schittkowski problem 35
+pub fn hs35_derivative<T>(param: &[T; 3]) -> [T; 3]
+where
+    T: Float + FromPrimitive,
+{
+    let n2 = T::from_f64(2.0).unwrap();
+    let n4 = T::from_f64(4.0).unwrap();
+    let n6 = T::from_f64(6.0).unwrap();
+    let n8 = T::from_f64(8.0).unwrap();
+
+    let [x1, x2, x3] = *param;
+    [
+        -n8 + n4 * x1 + n2 * x2 + n2 * x3,
+        -n6 + n4 * x2 + n2 * x1,
+        -n4 + n2 * x3 + n2 * x1,
+    ]
+}
+
+/// Inequality constraint of Hock-Schittkowski problem 35
+pub fn hs35_ineq<T>(param: &[T; 3]) -> [T; 1]
+where
+    T: Float + FromPrimitive,
+{
+    let n2 = T::from_f64(2.0).unwrap();
+    let n3 = T::from_f64(3.0).unwrap();
+
+    let [x1, x2, x3] = *param;
+    [x1 + x2 + n2 * x3 - n3]
+}
+
+/// Jacobian of the inequality constraint of Hock-Schittkowski problem 35
+///
+/// Returns [[1, 1, 2]] for every input.
+pub fn hs35_ineq_jacobian<T>(_param: &[T; 3]) -> [[T; 3]; 1]
+where
+    T: Float + FromPrimitive,
+{
+    let n1 = T::from_f64(1.0).unwrap();
+    let n2 = T::from_f64(2.0).unwrap();
+
+    [[n1, n1, n2]]
+}
+
+/// Bounds `(lower, upper)` of each parameter of HS71
+pub const HS71_BOUNDS: [(f64, f64); 4] = [(1.0, 5.0); 4];
+
+/// Minimizer of HS71
+pub const HS71_OPTIMUM: [f64; 4] = [
+    1.0,
+    4.742_999_637_264_417,
+    3.821_149_984_184_874,
+    1.379_408_293_172_672,
+];
+
+/// Minimum of HS71
+pub const HS71_OPTIMUM_VALUE: f64 = 17.014_017_289_156_3;
+
+/// Hock-Schittkowski problem 71
+///
+/// Defined as
+///
+/// `f(x_1, x_2, x_3, x_4) = x_1 * x_4 * (x_1 + x_2 + x_3) + x_3`
+///
+/// subject to
+///
+/// `g(x_1, x_2, x_3, x_4) = 25 - x_1 * x_2 * x_3 * x_4 <= 0`
+///
+/// `h(x_1, x_2, x_3, x_4) = x_1^2 + x_2^2 + x_3^2 + x_4^2 - 40 = 0`
+///
+/// where `x_i \in [1, 5]`.
+///
+/// The minimum is at `f(1, 4.74300, 3.82115, 1.37941) = 17.0140173`. The usual starting point is
+/// `(1, 5, 5, 1)`.
+pub fn hs71<T>(param: &[T; 4]) -> T
+where
+    T: Float + FromPrimitive,
+{
+    let [x1, x2, x3, x4] = *param;
+    x1 * x4 * (x1 + x2 + x3) + x3
+}
+
+/// Derivative of Hock-Schittkowski problem 71
+pub fn hs71_derivative<T>(param: &[T; 4]) -> [T; 4]
+where
+    T: Float + FromPrimitive,
+{
+    let n1 = T::from_f64(1.0).unwrap();
+
+    let [x1, x2, x3, x4] = *param;
+    [
+        x4 * (x1 + x2 + x3) + x1 * x4,
+        x1 * x4,
+        x1 * x4 + n1,
+        x1 * (x1 + x2 + x3),
+    ]
+}
+
+/// Inequality constraint of Hock-Schittkowski problem 71
+pub fn hs71_ineq<T>(param: &[T; 4]) -> [T; 1]
+where
+    T: Float + FromPrimitive,
+{
+    let n25 = T::from_f64(25.0).unwrap();
+
+    let [x1, x2, x3, x4] = *param;
+    [n25 - x1 * x2 * x3 * x4]
+}
+
+/// Jacobian of the inequality constraint of Hock-Schittkowski problem 71
+pub fn hs71_ineq_jacobian<T>(param: &[T; 4]) -> [[T; 4]; 1]
+where
+    T: Float + FromPrimitive,
+{
+    let [x1, x2, x3, x4] = *param;
+    [[-x2 * x3 * x4, -x1 * x3 * x4, -x1 * x2 * x4, -x1 * x2 * x3]]
+}
+
+/// Equality constraint of Hock-Schittkowski problem 71
+pub fn hs71_eq<T>(param: &[T; 4]) -> [T; 1]
+where
+    T: Float + FromPrimitive,
+{
+    let n40 = T::from_f64(40.0).unwrap();
+
+    [param
+        .iter()
+        .map(|x| x.powi(2))
+        .fold(T::zero(), |a, b| a + b)
+        - n40]
+}
+
+/// Jacobian of the equality constraint of Hock-Schittkowski problem 71
+pub fn hs71_eq_jacobian<T>(param: &[T; 4]) -> [[T; 4]; 1]
+where
+    T: Float + FromPrimitive,
+{
+    let n2 = T::from_f64(2.0).unwrap();
+
+    [param.map(|x| n2 * x)]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_relative_eq;
+    use finitediff::FiniteDiff;
+    use proptest::prelude::*;
+
+    /// Compares the Jacobian `dc` of the constraints `c` (or the derivative of the objective if
+    /// `M == 1`) with finite differences at `param`.
+    fn check_jacobian<const N: usize, const M: usize>(
+        param: [f64; N],
+        c: impl Fn(&[f64; N]) -> [f64; M],
+        dc: impl Fn(&[f64; N]) -> [[f64; N]; M],
+    ) {
+        let jacobian = dc(&param);
+        for (j, row) in jacobian.iter().enumerate() {
+            let row_fd = Vec::from(param).central_diff(&|x| c(&x.clone().try_into().unwrap())[j]);
+            for i in 0..N {
+                assert_relative_eq!(row[i], row_fd[i], epsilon = 1e-4, max_relative = 1e-2);
+            }
+        }
+    }
+
+    #[test]
+    fn test_hs6_optimum() {
+        assert_relative_eq!(hs6(&HS6_OPTIMUM), HS6_OPTIMUM_VALUE, epsilon = f64::EPSILON);
+        assert_relative_eq!(hs6_eq(&HS6_OPTIMUM)[0], 0.0, epsilon = f64::EPSILON);
+    }
+
+    #[test]
+    fn test_hs21_optimum() {
+        assert_relative_eq!(hs21(&HS21_OPTIMUM), HS21_OPTIMUM_VALUE, epsilon = 1e-12);
+        assert!(hs21_ineq(&HS21_OPTIMUM)[0] <= 0.0);
+    }
+
+    #[test]
+    fn test_hs35_optimum() {
+        assert_relative_eq!(hs35(&HS35_OPTIMUM), HS35_OPTIMUM_VALUE, epsilon = 1e-12);
+        // The constraint is active at the optimum
+        assert_relative_eq!(hs35_ineq(&HS35_OPTIMUM)[0], 0.0, epsilon = 1e-12);
+    }
+
+    #[test]
+    fn test_hs71_optimum() {
+        assert_relative_eq!(hs71(&HS71_OPTIMUM), HS71_OPTIMUM_VALUE, epsilon = 1e-8);
+        assert!(hs71_ineq(&HS71_OPTIMUM)[0] <= 1e-8);
+        assert_relative_eq!(hs71_eq(&HS71_OPTIMUM)[0], 0.0, epsilon = 1e-8);
+    }
+
+    proptest! {
+        #[test]
+        fn test_hs6_derivative_finitediff(a in -10.0..10.0, b in -10.0..10.0) {
+            check_jacobian([a, b], |x| [hs6(x)], |x| [hs6_derivative(x)]);
+            check_jacobian([a, b], hs6_eq, hs6_eq_jacobian);
+        }
+    }
+
+    proptest! {
+        #[test]
+        fn test_hs21_derivative_finitediff(a in 2.0..50.0, b in -50.0..50.0) {
+            check_jacobian([a, b], |x| [hs21(x)], |x| [hs21_derivative(x)]);
+            check_jacobian([a, b], hs21_ineq, hs21_ineq_jacobian);
+        }
+    }
+
+    proptest! {
+        #[test]
+        fn test_hs35_derivative_finitediff(a in 0.0..10.0, b in 0.0..10.0, c in 0.0..10.0) {
+            check_jacobian([a, b, c], |x| [hs35(x)], |x| [hs35_derivative(x)]);
+            check_jacobian([a, b, c], hs35_ineq, hs35_ineq_jacobian);
+        }
+    }
+
+    proptest! {
+        #[test]
+        fn test_hs71_derivative_finitediff(
+            a in 1.0..5.0,
+            b in 1.0..5.0,
+            c in 1.0..5.0,
+            d in 1.0..5.0
+        ) {
+            check_jacobian([a, b, c, d], |x| [hs71(x)], |x| [hs71_derivative(x)]);
+            check_jacobian([a, b, c, d], hs71_ineq, hs71_ineq_jacobian);
+            check_jacobian([a, b, c, d], hs71_eq, hs71_eq_jacobian);
+        }
+    }
+}
@@ -0,0 +1,145 @@
+// Copyright 2018-2024 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+//! # Kowalik-Osborne test problem
+//!
+//! Nonlinear least-squares problem with 4 parameters and 11 residuals
+//!
+//! `r_i(x_1, x_2, x_3, x_4) = y_i - x_1 * (u_i^2 + u_i * x_2) / (u_i^2 + u_i * x_3 + x_4)`
+//!
+//! for `i = 1, ..., 11`. The objective is `f(x) = \sum_{i=1}^{11} r_i(x)^2`.
+//!
+//! The minimum is `f = 3.07505e-4`. The usual starting point is `(0.25, 0.39, 0.415, 0.39)`.
+//!
+//! Reference: J. J. Moré, B. S. Garbow and K. E. Hillstrom, "Testing Unconstrained
+//! Optimization Software", ACM Transactions on Mathematical Software, 7(1), 1981.
+
+use num::{Float, FromPrimitive};
+
+const Y: [f64; 11] = [
+    0.1957, 0.1947, 0.1735, 0.1600, 0.0844, 0.0627, 0.0456, 0.0342, 0.0323, 0.0235, 0.0246,
+];
+
+const U: [f64; 11] = [
+    4.0, 2.0, 1.0, 0.5, 0.25, 0.167, 0.125, 0.1, 0.0833, 0.0714, 0.0625,
+];
+
+/// Starting point of the Kowalik-Osborne problem
+pub const KOWALIK_OSBORNE_START: [f64; 4] = [0.25, 0.39, 0.415, 0.39];
+
+/// Minimizer of the Kowalik-Osborne problem
+pub const KOWALIK_OSBORNE_OPTIMUM: [f64; 4] = [
+    0.19280693474543575,
+    0.19128232502807427,
+    0.12305650621652557,
+    0.13606232898173504,
+];
+
+/// Minimum of the Kowalik-Osborne problem
+pub const KOWALIK_OSBORNE_OPTIMUM_VALUE: f64 = 0.00030750560384923637;
+
+/// Kowalik-Osborne test problem
+///
+/// Sum of squares of the residuals returned by [`kowalik_osborne_residuals`].
+///
+/// The minimum is `f = 3.07505e-4`.
+pub fn kowalik_osborne<T>(param: &[T; 4]) -> T
+where
+    T: Float + FromPrimitive,
+{
+    kowalik_osborne_residuals(param)
+        .iter()
+        .fold(T::zero(), |acc, r| acc + r.powi(2))
+}
+
+/// Residuals of Kowalik-Osborne test problem
+pub fn kowalik_osborne_residuals<T>(param: &[T; 4]) -> [T; 11]
+where
+    T: Float + FromPrimitive,
+{
+    let [x1, x2, x3, x4] = *param;
+    let mut out = [T::zero(); 11];
+    for (o, (&y, &u)) in out.iter_mut().zip(Y.iter().zip(U.iter())) {
+        let y = T::from_f64(y).unwrap();
+        let u = T::from_f64(u).unwrap();
+        *o = y - x1 * (u.powi(2) + u * x2) / (u.powi(2) + u * x3 + x4);
+    }
+    out
+}
+
+/// Jacobian of the residuals of Kowalik-Osborne test problem
+pub fn kowalik_osborne_jacobian<T>(param: &[T; 4]) -> [[T; 4]; 11]
+where
+    T: Float + FromPrimitive,
+{
+    let [x1, x2, x3, x4] = *param;
+    let mut out = [[T::zero(); 4]; 11];
+    for (o, &u) in out.iter_mut().zip(U.iter()) {
+        let u = T::from_f64(u).unwrap();
+        let a = u.powi(2) + u * x2;
+        let d = u.powi(2) + u * x3 + x4;
+        *o = [
+            -a / d,
+            -x1 * u / d,
+            x1 * a * u / d.powi(2),
+            x1 * a / d.powi(2),
+        ];
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_relative_eq;
+    use finitediff::FiniteDiff;
+    use proptest::prelude::*;
+
+    #[test]
+    fn test_kowalik_osborne_optimum() {
+        assert_relative_eq!(
+            kowalik_osborne(&KOWALIK_OSBORNE_OPTIMUM),
+            KOWALIK_OSBORNE_OPTIMUM_VALUE,
+            epsilon = 1e-12
+        );
+
+        // Gradient `2 * J^T r` vanishes at the optimum
+        let r = kowalik_osborne_residuals(&KOWALIK_OSBORNE_OPTIMUM);
+        let jac = kowalik_osborne_jacobian(&KOWALIK_OSBORNE_OPTIMUM);
+        for j in 0..4 {
+            let g: f64 = jac
+                .iter()
+                .zip(r.iter())
+                .map(|(row, r)| 2.0 * row[j] * r)
+                .sum();
+            assert_relative_eq!(g, 0.0, epsilon = 1e-8);
+        }
+    }
+
+    proptest! {
+        #[test]
+        fn test_kowalik_osborne_jacobian_finitediff(a in -1.0..1.0,
+                                                    b in 0.0..1.0,
+                                                    c in 0.0..1.0,
+                                                    d in 0.0..1.0) {
+            let param = [a, b, c, d];
+            let jacobian = kowalik_osborne_jacobian(&param);
+            for (i, row) in jacobian.iter().enumerate() {
+                let row_fd = Vec::from(param)
+                    .central_diff(&|x| kowalik_osborne_residuals(&[x[0], x[1], x[2], x[3]])[i]);
+                for (d, d_fd) in row.iter().zip(row_fd.iter()) {
+                    assert_relative_eq!(
+                        d,
+                        d_fd,
+                        epsilon = 1e-5,
+                        max_relative = 1e-2
+                    );
+                }
+            }
+        }
+    }
+}
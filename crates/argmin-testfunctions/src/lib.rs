@@ -23,6 +23,42 @@
 //!
 //! All functions are generic over their inputs and work with `[f64]` and `[f32]`.
 //!
+//! For benchmarking solvers on large problems, the collection contains several problems which scale
+//! to an arbitrary number of parameters: `extended_rosenbrock`, `powell_singular`,
+//! `broyden_tridiagonal`, `trigonometric` and `chained_wood`. These only provide derivatives and no
+//! Hessians.
+//!
+//! ## Constrained test problems
+//!
+//! A selection of constrained problems from the G-series (CEC 2006) and the Hock-Schittkowski
+//! collection is available as well (for instance `g06` and `hs71`). Inequality constraints are
+//! stated as `g_i(x) <= 0` and equality constraints as `h_j(x) = 0`. The constraints are
+//! provided by `<test function name>_ineq` and `<test function name>_eq`, and their Jacobians by
+//! `<test function name>_ineq_jacobian` and `<test function name>_eq_jacobian`. Bounds and known
+//! optima are exposed as constants (e.g. `G06_BOUNDS`, `G06_OPTIMUM` and `G06_OPTIMUM_VALUE`).
+//!
+//! ## Nonlinear least-squares problems
+//!
+//! Nonlinear least-squares problems from the Moré-Garbow-Hillstrom collection (`bard`,
+//! `kowalik_osborne`, `meyer`, `osborne1`, `osborne2` and `watson`) additionally expose their
+//! residuals and the Jacobian of the residuals via `<test function name>_residuals` and
+//! `<test function name>_jacobian`, which makes them suitable for Gauss-Newton type solvers. The
+//! usual starting points and known optima are exposed as constants (e.g. `BARD_START`,
+//! `BARD_OPTIMUM` and `BARD_OPTIMUM_VALUE`).
+//!
+//! ## Catalogue
+//!
+//! The search domain, the known global minimum and minimizer(s) and the modality of the box-bounded
+//! global optimization test functions are available programmatically via [`TEST_FUNCTIONS`] and
+//! [`test_function_info`]. Benchmark harnesses can use this to compute optimality gaps with
+//! [`TestFunctionInfo::optimality_gap`] instead of hardcoding targets.
+//!
+//! ## Noisy functions
+//!
+//! Any test function and derivative can be wrapped in [`Noisy`], which adds Gaussian or uniform
+//! noise (see [`Noise`]) drawn from a seedable RNG to the output. This allows for evaluating the
+//! robustness of solvers under noisy function evaluations.
+//!
 //! ## Python wrapper
 //!
 //! Thanks to the python module
@@ -75,45 +111,73 @@
 //! as defined in the Apache-2.0 license, shall be dual licensed as above, without any additional terms or conditions.
 
 mod ackley;
+mod bard;
 mod beale;
 mod booth;
+mod broydentridiagonal;
 mod bukin;
+mod catalogue;
+mod chainedwood;
 mod crossintray;
 mod easom;
 mod eggholder;
+mod extendedrosenbrock;
 mod goldsteinprice;
+mod gseries;
 mod himmelblau;
+mod hockschittkowski;
 mod holdertable;
+mod kowalikosborne;
 mod levy;
 mod matyas;
 mod mccorminck;
+mod meyer;
+mod noise;
+mod osborne;
 mod picheny;
+mod powellsingular;
 mod rastrigin;
 mod rosenbrock;
 mod schaffer;
 mod sphere;
 mod styblinskitang;
 mod threehumpcamel;
+mod trigonometric;
+mod watson;
 mod zero;
 
 pub use ackley::*;
+pub use bard::*;
 pub use beale::*;
 pub use booth::*;
+pub use broydentridiagonal::*;
 pub use bukin::*;
+pub use catalogue::*;
+pub use chainedwood::*;
 pub use crossintray::*;
 pub use easom::*;
 pub use eggholder::*;
+pub use extendedrosenbrock::*;
 pub use goldsteinprice::*;
+pub use gseries::*;
 pub use himmelblau::*;
+pub use hockschittkowski::*;
 pub use holdertable::*;
+pub use kowalikosborne::*;
 pub use levy::*;
 pub use matyas::*;
 pub use mccorminck::*;
+pub use meyer::*;
+pub use noise::*;
+pub use osborne::*;
 pub use picheny::*;
+pub use powellsingular::*;
 pub use rastrigin::*;
 pub use rosenbrock::*;
 pub use schaffer::*;
 pub use sphere::*;
 pub use styblinskitang::*;
 pub use threehumpcamel::*;
+pub use trigonometric::*;
+pub use watson::*;
 pub use zero::*;
@@ -0,0 +1,134 @@
+// Copyright 2018-2024 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+//! # Meyer test problem
+//!
+//! Nonlinear least-squares problem with 3 parameters and 16 residuals
+//!
+//! `r_i(x_1, x_2, x_3) = x_1 * exp(x_2 / (t_i + x_3)) - y_i`
+//!
+//! where `t_i = 45 + 5*i` for `i = 1, ..., 16`. The objective is
+//! `f(x) = \sum_{i=1}^{16} r_i(x)^2`.
+//!
+//! The minimum is `f = 87.9458`. The problem is badly scaled and the usual starting point is
+//! `(0.02, 4000, 250)`.
+//!
+//! Reference: J. J. Moré, B. S. Garbow and K. E. Hillstrom, "Testing Unconstrained
+//! Optimization Software", ACM Transactions on Mathematical Software, 7(1), 1981.
+
+use num::{Float, FromPrimitive};
+
+const Y: [f64; 16] = [
+    34780.0, 28610.0, 23650.0, 19630.0, 16370.0, 13720.0, 11540.0, 9744.0, 8261.0, 7030.0, 6005.0,
+    5147.0, 4427.0, 3820.0, 3307.0, 2872.0,
+];
+
+/// Starting point of the Meyer problem
+pub const MEYER_START: [f64; 3] = [0.02, 4000.0, 250.0];
+
+/// Minimizer of the Meyer problem
+pub const MEYER_OPTIMUM: [f64; 3] = [0.005609636471028847, 6181.34634628625, 345.2236346241323];
+
+/// Minimum of the Meyer problem
+pub const MEYER_OPTIMUM_VALUE: f64 = 87.94585517063032;
+
+/// Returns `t_i` for zero-based index `i`
+fn t<T: FromPrimitive>(i: usize) -> T {
+    T::from_usize(50 + 5 * i).unwrap()
+}
+
+/// Meyer test problem
+///
+/// Sum of squares of the residuals returned by [`meyer_residuals`].
+///
+/// The minimum is `f = 87.9458`.
+pub fn meyer<T>(param: &[T; 3]) -> T
+where
+    T: Float + FromPrimitive,
+{
+    meyer_residuals(param)
+        .iter()
+        .fold(T::zero(), |acc, r| acc + r.powi(2))
+}
+
+/// Residuals of Meyer test problem
+pub fn meyer_residuals<T>(param: &[T; 3]) -> [T; 16]
+where
+    T: Float + FromPrimitive,
+{
+    let [x1, x2, x3] = *param;
+    let mut out = [T::zero(); 16];
+    for (i, o) in out.iter_mut().enumerate() {
+        *o = x1 * (x2 / (t::<T>(i) + x3)).exp() - T::from_f64(Y[i]).unwrap();
+    }
+    out
+}
+
+/// Jacobian of the residuals of Meyer test problem
+pub fn meyer_jacobian<T>(param: &[T; 3]) -> [[T; 3]; 16]
+where
+    T: Float + FromPrimitive,
+{
+    let [x1, x2, x3] = *param;
+    let mut out = [[T::zero(); 3]; 16];
+    for (i, o) in out.iter_mut().enumerate() {
+        let d = t::<T>(i) + x3;
+        let e = (x2 / d).exp();
+        *o = [e, x1 * e / d, -x1 * x2 * e / d.powi(2)];
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_relative_eq;
+    use finitediff::FiniteDiff;
+    use proptest::prelude::*;
+
+    #[test]
+    fn test_meyer_optimum() {
+        assert_relative_eq!(
+            meyer(&MEYER_OPTIMUM),
+            MEYER_OPTIMUM_VALUE,
+            max_relative = 1e-10
+        );
+
+        // Gradient `2 * J^T r` vanishes at the optimum. Because the problem is badly scaled, the
+        // gradient is scaled by the parameters and compared relative to the function value.
+        let r = meyer_residuals(&MEYER_OPTIMUM);
+        let jac = meyer_jacobian(&MEYER_OPTIMUM);
+        for (j, x) in MEYER_OPTIMUM.iter().enumerate() {
+            let g: f64 = jac
+                .iter()
+                .zip(r.iter())
+                .map(|(row, r)| 2.0 * row[j] * r)
+                .sum();
+            assert_relative_eq!(g * x / MEYER_OPTIMUM_VALUE, 0.0, epsilon = 1e-5);
+        }
+    }
+
+    proptest! {
+        #[test]
+        fn test_meyer_jacobian_finitediff(a in 0.001..0.1, b in 3000.0..7000.0, c in 200.0..400.0) {
+            let param = [a, b, c];
+            let jacobian = meyer_jacobian(&param);
+            for (i, row) in jacobian.iter().enumerate() {
+                let row_fd = Vec::from(param)
+                    .central_diff(&|x| meyer_residuals(&[x[0], x[1], x[2]])[i]);
+                for (d, d_fd) in row.iter().zip(row_fd.iter()) {
+                    assert_relative_eq!(
+                        d,
+                        d_fd,
+                        epsilon = 1e-4,
+                        max_relative = 1e-2
+                    );
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,186 @@
+// Copyright 2018-2024 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+//! # Noisy test functions
+//!
+//! Wraps any test function (or its derivative) and adds random noise to its output. This is
+//! useful for evaluating the robustness of solvers such as simulated annealing, particle swarm
+//! optimization or surrogate-based methods under noisy function evaluations.
+//!
+//! ```
+//! use argmin_testfunctions::{rosenbrock, rosenbrock_derivative, Noise, Noisy};
+//!
+//! let cost = Noisy::new(rosenbrock, Noise::Gaussian { std_dev: 0.1 }, 42);
+//! let grad = Noisy::new(rosenbrock_derivative, Noise::Uniform { amplitude: 0.01 }, 42);
+//!
+//! let c: f64 = cost.eval(&[1.0, 1.0]);
+//! let g: Vec<f64> = grad.eval_vec(&[1.0, 1.0]);
+//! # assert!(c.abs() < 1.0);
+//! # assert_eq!(g.len(), 2);
+//! ```
+
+use num::{Float, FromPrimitive};
+use rand::{Rng, SeedableRng};
+use rand_xoshiro::Xoshiro256PlusPlus;
+use std::sync::Mutex;
+
+/// Type of noise added to the output of a function
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Noise {
+    /// Gaussian noise with zero mean and standard deviation `std_dev`
+    Gaussian {
+        /// Standard deviation
+        std_dev: f64,
+    },
+    /// Uniformly distributed noise in `[-amplitude, amplitude)`
+    Uniform {
+        /// Half width of the interval
+        amplitude: f64,
+    },
+}
+
+impl Noise {
+    /// Draws a single sample using the given RNG
+    pub fn sample<R: Rng>(&self, rng: &mut R) -> f64 {
+        match *self {
+            Noise::Gaussian { std_dev } => {
+                // Box-Muller transform; `1 - u` avoids taking the logarithm of zero
+                let u1: f64 = 1.0 - rng.gen::<f64>();
+                let u2: f64 = rng.gen();
+                std_dev * (-2.0 * u1.ln()).sqrt() * (2.0 * std::f64::consts::PI * u2).cos()
+            }
+            Noise::Uniform { amplitude } => amplitude * (2.0 * rng.gen::<f64>() - 1.0),
+        }
+    }
+}
+
+/// Adds noise to the output of a function
+///
+/// The wrapped function can be a test function (returning a scalar) or a derivative (returning a
+/// `Vec` or an array), which are evaluated via [`eval`](`Noisy::eval`) and
+/// [`eval_vec`](`Noisy::eval_vec`), respectively. Each element of a derivative receives
+/// independent noise.
+///
+/// The RNG is stored behind a `Mutex`, therefore evaluation only requires a shared reference.
+#[derive(Debug)]
+pub struct Noisy<F, R = Xoshiro256PlusPlus> {
+    func: F,
+    noise: Noise,
+    rng: Mutex<R>,
+}
+
+impl<F> Noisy<F, Xoshiro256PlusPlus> {
+    /// Constructs a new instance of `Noisy`
+    ///
+    /// Uses the `Xoshiro256PlusPlus` RNG seeded with `seed`, which makes the noise reproducible.
+    /// For use of another RNG, consider using [`Noisy::new_with_rng`].
+    pub fn new(func: F, noise: Noise, seed: u64) -> Self {
+        Noisy::new_with_rng(func, noise, Xoshiro256PlusPlus::seed_from_u64(seed))
+    }
+}
+
+impl<F, R: Rng> Noisy<F, R> {
+    /// Constructs a new instance of `Noisy` with a custom RNG
+    pub fn new_with_rng(func: F, noise: Noise, rng: R) -> Self {
+        Noisy {
+            func,
+            noise,
+            rng: Mutex::new(rng),
+        }
+    }
+
+    /// Returns the noise configuration
+    pub fn noise(&self) -> Noise {
+        self.noise
+    }
+
+    /// Evaluates a scalar valued function and adds noise to its output
+    pub fn eval<P, T>(&self, param: &P) -> T
+    where
+        P: ?Sized,
+        F: Fn(&P) -> T,
+        T: Float + FromPrimitive,
+    {
+        let out = (self.func)(param);
+        out + self.sample()
+    }
+
+    /// Evaluates a vector valued function (such as a derivative) and adds independent noise to
+    /// each element of its output
+    pub fn eval_vec<P, T, V>(&self, param: &P) -> V
+    where
+        P: ?Sized,
+        F: Fn(&P) -> V,
+        T: Float + FromPrimitive,
+        V: AsMut<[T]>,
+    {
+        let mut out = (self.func)(param);
+        for o in out.as_mut().iter_mut() {
+            *o = *o + self.sample();
+        }
+        out
+    }
+
+    fn sample<T: FromPrimitive>(&self) -> T {
+        let mut rng = self.rng.lock().unwrap();
+        T::from_f64(self.noise.sample(&mut *rng)).unwrap()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{sphere, sphere_derivative, sphere_derivative_const};
+    use approx::assert_relative_eq;
+
+    const N: usize = 20_000;
+
+    #[test]
+    fn test_noisy_reproducible() {
+        let a = Noisy::new(sphere, Noise::Gaussian { std_dev: 1.0 }, 7);
+        let b = Noisy::new(sphere, Noise::Gaussian { std_dev: 1.0 }, 7);
+        let c = Noisy::new(sphere, Noise::Gaussian { std_dev: 1.0 }, 8);
+        for _ in 0..10 {
+            let va: f64 = a.eval(&[1.0, 2.0]);
+            let vb: f64 = b.eval(&[1.0, 2.0]);
+            let vc: f64 = c.eval(&[1.0, 2.0]);
+            assert_eq!(va.to_bits(), vb.to_bits());
+            assert_ne!(va.to_bits(), vc.to_bits());
+        }
+    }
+
+    #[test]
+    fn test_noisy_zero_noise() {
+        let f = Noisy::new(sphere, Noise::Uniform { amplitude: 0.0 }, 0);
+        assert_relative_eq!(f.eval(&[1.0_f64, 2.0]), 5.0, epsilon = f64::EPSILON);
+        let d = Noisy::new(sphere_derivative_const, Noise::Gaussian { std_dev: 0.0 }, 0);
+        assert_eq!(d.eval_vec(&[1.0_f32, 2.0]), [2.0, 4.0]);
+    }
+
+    #[test]
+    fn test_noisy_gaussian_moments() {
+        let f = Noisy::new(sphere, Noise::Gaussian { std_dev: 2.0 }, 1);
+        let samples: Vec<f64> = (0..N).map(|_| f.eval(&[0.0_f64])).collect();
+        let mean = samples.iter().sum::<f64>() / N as f64;
+        let var = samples.iter().map(|s| (s - mean).powi(2)).sum::<f64>() / N as f64;
+        assert_relative_eq!(mean, 0.0, epsilon = 0.05);
+        assert_relative_eq!(var.sqrt(), 2.0, epsilon = 0.05);
+    }
+
+    #[test]
+    fn test_noisy_uniform_bounds() {
+        let d = Noisy::new(sphere_derivative, Noise::Uniform { amplitude: 0.5 }, 2);
+        let mut mean = 0.0;
+        for _ in 0..N {
+            let g: Vec<f64> = d.eval_vec(&[1.0_f64, -1.0]);
+            assert!((1.5..2.5).contains(&g[0]));
+            assert!((-2.5..-1.5).contains(&g[1]));
+            mean += (g[0] - 2.0) / N as f64;
+        }
+        assert_relative_eq!(mean, 0.0, epsilon = 0.02);
+    }
+}
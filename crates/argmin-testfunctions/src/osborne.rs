@@ -0,0 +1,282 @@
+// Copyright 2018-2024 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+//! # Osborne test problems
+//!
+//! ## Osborne 1
+//!
+//! Nonlinear least-squares problem with 5 parameters and 33 residuals
+//!
+//! `r_i(x) = y_i - (x_1 + x_2 * exp(-t_i * x_4) + x_3 * exp(-t_i * x_5))`
+//!
+//! where `t_i = 10 * (i - 1)` for `i = 1, ..., 33`. The objective is
+//! `f(x) = \sum_{i=1}^{33} r_i(x)^2`.
+//!
+//! The minimum is `f = 5.46489e-5`. The usual starting point is `(0.5, 1.5, -1, 0.01, 0.02)`.
+//!
+//! ## Osborne 2
+//!
+//! Nonlinear least-squares problem with 11 parameters and 65 residuals
+//!
+//! `r_i(x) = y_i - (x_1 * exp(-t_i * x_5) + x_2 * exp(-(t_i - x_9)^2 * x_6)
+//! + x_3 * exp(-(t_i - x_10)^2 * x_7) + x_4 * exp(-(t_i - x_11)^2 * x_8))`
+//!
+//! where `t_i = (i - 1) / 10` for `i = 1, ..., 65`. The objective is
+//! `f(x) = \sum_{i=1}^{65} r_i(x)^2`.
+//!
+//! The minimum is `f = 4.01377e-2`. The usual starting point is
+//! `(1.3, 0.65, 0.65, 0.7, 0.6, 3, 5, 7, 2, 4.5, 5.5)`.
+//!
+//! Reference: J. J. Moré, B. S. Garbow and K. E. Hillstrom, "Testing Unconstrained
+//! Optimization Software", ACM Transactions on Mathematical Software, 7(1), 1981.
+
+use num::{Float, FromPrimitive};
+
+const Y1: [f64; 33] = [
+    0.844, 0.908, 0.932, 0.936, 0.925, 0.908, 0.881, 0.850, 0.818, 0.784, 0.751, 0.718, 0.685,
+    0.658, 0.628, 0.603, 0.580, 0.558, 0.538, 0.522, 0.506, 0.490, 0.478, 0.467, 0.457, 0.448,
+    0.438, 0.431, 0.424, 0.420, 0.414, 0.411, 0.406,
+];
+
+const Y2: [f64; 65] = [
+    1.366, 1.191, 1.112, 1.013, 0.991, 0.885, 0.831, 0.847, 0.786, 0.725, 0.746, 0.679, 0.608,
+    0.655, 0.616, 0.606, 0.602, 0.626, 0.651, 0.724, 0.649, 0.649, 0.694, 0.644, 0.624, 0.661,
+    0.612, 0.558, 0.533, 0.495, 0.500, 0.423, 0.395, 0.375, 0.372, 0.391, 0.396, 0.405, 0.428,
+    0.429, 0.523, 0.562, 0.607, 0.653, 0.672, 0.708, 0.633, 0.668, 0.645, 0.632, 0.591, 0.559,
+    0.597, 0.625, 0.739, 0.710, 0.729, 0.720, 0.636, 0.581, 0.428, 0.292, 0.162, 0.098, 0.054,
+];
+
+/// Starting point of the Osborne 1 problem
+pub const OSBORNE1_START: [f64; 5] = [0.5, 1.5, -1.0, 0.01, 0.02];
+
+/// Minimizer of the Osborne 1 problem
+pub const OSBORNE1_OPTIMUM: [f64; 5] = [
+    0.37541005166573826,
+    1.9358468576047423,
+    -1.4646870812013764,
+    0.01286753452990425,
+    0.02212269988790338,
+];
+
+/// Minimum of the Osborne 1 problem
+pub const OSBORNE1_OPTIMUM_VALUE: f64 = 5.4648946974823545e-5;
+
+/// Starting point of the Osborne 2 problem
+pub const OSBORNE2_START: [f64; 11] = [1.3, 0.65, 0.65, 0.7, 0.6, 3.0, 5.0, 7.0, 2.0, 4.5, 5.5];
+
+/// Minimizer of the Osborne 2 problem
+pub const OSBORNE2_OPTIMUM: [f64; 11] = [
+    1.3099771546272614,
+    0.43155379460291127,
+    0.6336616989606959,
+    0.5994305347858447,
+    0.75418322632782,
+    0.9042885798601329,
+    1.3658118352366553,
+    4.823698817227818,
+    2.398684866131777,
+    4.568874597667687,
+    5.675341470580659,
+];
+
+/// Minimum of the Osborne 2 problem
+pub const OSBORNE2_OPTIMUM_VALUE: f64 = 0.04013773629354771;
+
+/// Osborne 1 test problem
+///
+/// Sum of squares of the residuals returned by [`osborne1_residuals`].
+///
+/// The minimum is `f = 5.46489e-5`.
+pub fn osborne1<T>(param: &[T; 5]) -> T
+where
+    T: Float + FromPrimitive,
+{
+    osborne1_residuals(param)
+        .iter()
+        .fold(T::zero(), |acc, r| acc + r.powi(2))
+}
+
+/// Residuals of Osborne 1 test problem
+pub fn osborne1_residuals<T>(param: &[T; 5]) -> [T; 33]
+where
+    T: Float + FromPrimitive,
+{
+    let [x1, x2, x3, x4, x5] = *param;
+    let mut out = [T::zero(); 33];
+    for (i, o) in out.iter_mut().enumerate() {
+        let t = T::from_usize(10 * i).unwrap();
+        *o = T::from_f64(Y1[i]).unwrap() - (x1 + x2 * (-t * x4).exp() + x3 * (-t * x5).exp());
+    }
+    out
+}
+
+/// Jacobian of the residuals of Osborne 1 test problem
+pub fn osborne1_jacobian<T>(param: &[T; 5]) -> [[T; 5]; 33]
+where
+    T: Float + FromPrimitive,
+{
+    let [_, x2, x3, x4, x5] = *param;
+    let mut out = [[T::zero(); 5]; 33];
+    for (i, o) in out.iter_mut().enumerate() {
+        let t = T::from_usize(10 * i).unwrap();
+        let e4 = (-t * x4).exp();
+        let e5 = (-t * x5).exp();
+        *o = [-T::one(), -e4, -e5, t * x2 * e4, t * x3 * e5];
+    }
+    out
+}
+
+/// Osborne 2 test problem
+///
+/// Sum of squares of the residuals returned by [`osborne2_residuals`].
+///
+/// The minimum is `f = 4.01377e-2`.
+pub fn osborne2<T>(param: &[T; 11]) -> T
+where
+    T: Float + FromPrimitive,
+{
+    osborne2_residuals(param)
+        .iter()
+        .fold(T::zero(), |acc, r| acc + r.powi(2))
+}
+
+/// Residuals of Osborne 2 test problem
+pub fn osborne2_residuals<T>(param: &[T; 11]) -> [T; 65]
+where
+    T: Float + FromPrimitive,
+{
+    let x = param;
+    let mut out = [T::zero(); 65];
+    for (i, o) in out.iter_mut().enumerate() {
+        let t = T::from_f64(i as f64 / 10.0).unwrap();
+        // Exponential decay plus three Gaussian peaks with amplitude x_k, width x_{k+4} and
+        // location x_{k+7}
+        let peaks = (1..4).fold(T::zero(), |acc, k| {
+            acc + x[k] * (-(t - x[k + 7]).powi(2) * x[k + 4]).exp()
+        });
+        *o = T::from_f64(Y2[i]).unwrap() - (x[0] * (-t * x[4]).exp() + peaks);
+    }
+    out
+}
+
+/// Jacobian of the residuals of Osborne 2 test problem
+pub fn osborne2_jacobian<T>(param: &[T; 11]) -> [[T; 11]; 65]
+where
+    T: Float + FromPrimitive,
+{
+    let n2 = T::from_f64(2.0).unwrap();
+
+    let x = param;
+    let mut out = [[T::zero(); 11]; 65];
+    for (i, o) in out.iter_mut().enumerate() {
+        let t = T::from_f64(i as f64 / 10.0).unwrap();
+        let e = (-t * x[4]).exp();
+        o[0] = -e;
+        o[4] = t * x[0] * e;
+        for k in 1..4 {
+            let d = t - x[k + 7];
+            let g = (-d.powi(2) * x[k + 4]).exp();
+            o[k] = -g;
+            o[k + 4] = x[k] * d.powi(2) * g;
+            o[k + 7] = -n2 * x[k] * d * x[k + 4] * g;
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_relative_eq;
+    use finitediff::FiniteDiff;
+    use proptest::prelude::*;
+
+    #[test]
+    fn test_osborne1_optimum() {
+        assert_relative_eq!(
+            osborne1(&OSBORNE1_OPTIMUM),
+            OSBORNE1_OPTIMUM_VALUE,
+            epsilon = 1e-12
+        );
+
+        // Gradient `2 * J^T r` vanishes at the optimum
+        let r = osborne1_residuals(&OSBORNE1_OPTIMUM);
+        let jac = osborne1_jacobian(&OSBORNE1_OPTIMUM);
+        for j in 0..5 {
+            let g: f64 = jac
+                .iter()
+                .zip(r.iter())
+                .map(|(row, r)| 2.0 * row[j] * r)
+                .sum();
+            assert_relative_eq!(g, 0.0, epsilon = 1e-8);
+        }
+    }
+
+    #[test]
+    fn test_osborne2_optimum() {
+        assert_relative_eq!(
+            osborne2(&OSBORNE2_OPTIMUM),
+            OSBORNE2_OPTIMUM_VALUE,
+            epsilon = 1e-12
+        );
+
+        // Gradient `2 * J^T r` vanishes at the optimum
+        let r = osborne2_residuals(&OSBORNE2_OPTIMUM);
+        let jac = osborne2_jacobian(&OSBORNE2_OPTIMUM);
+        for j in 0..11 {
+            let g: f64 = jac
+                .iter()
+                .zip(r.iter())
+                .map(|(row, r)| 2.0 * row[j] * r)
+                .sum();
+            assert_relative_eq!(g, 0.0, epsilon = 1e-8);
+        }
+    }
+
+    proptest! {
+        #[test]
+        fn test_osborne1_jacobian_finitediff(a in -2.0..2.0,
+                                             b in -2.0..2.0,
+                                             c in -2.0..2.0,
+                                             d in 0.0..0.1,
+                                             e in 0.0..0.1) {
+            let param = [a, b, c, d, e];
+            let jacobian = osborne1_jacobian(&param);
+            for (i, row) in jacobian.iter().enumerate() {
+                let row_fd = Vec::from(param)
+                    .central_diff(&|x| osborne1_residuals(&x.clone().try_into().unwrap())[i]);
+                for (d, d_fd) in row.iter().zip(row_fd.iter()) {
+                    assert_relative_eq!(
+                        d,
+                        d_fd,
+                        epsilon = 1e-4,
+                        max_relative = 1e-2
+                    );
+                }
+            }
+        }
+    }
+
+    proptest! {
+        #[test]
+        fn test_osborne2_jacobian_finitediff(param in prop::array::uniform11(0.1..6.0)) {
+            let jacobian = osborne2_jacobian(&param);
+            for (i, row) in jacobian.iter().enumerate() {
+                let row_fd = Vec::from(param)
+                    .central_diff(&|x| osborne2_residuals(&x.clone().try_into().unwrap())[i]);
+                for (d, d_fd) in row.iter().zip(row_fd.iter()) {
+                    assert_relative_eq!(
+                        d,
+                        d_fd,
+                        epsilon = 1e-4,
+                        max_relative = 1e-2
+                    );
+                }
+            }
+        }
+    }
+}
@@ -18,7 +18,7 @@
 //!
 //! where `\bar{x}_i = 4*x_i - 2` and `x_i \in [0, 1]`.
 //!
-//! The global minimum is at `f(x_1, x_2) = f(0.5, 0.25) = 3.3851993182036826`.
+//! The global minimum is at `f(x_1, x_2) = f(0.5, 0.25) = -3.3851993182036826`.
 
 //  (1/2.427) * (log_10([1 + ((4*x_1-2) + (4*x_2-2) + 1)^2 * (19 - 14*(4*x_1-2) + 3*(4*x_1-2)^2 - 14*(4*x_2-2) + 6*(4*x_1-2)*(4*x_2-2) + 3*(4*x_2-2)^2)]    * [30 + (2*(4*x_1-2) - 3*(4*x_2-2))^2*(18 - 32 * (4*x_1-2) + 12* (4*x_1-2)^2 +   48 * (4*x_2-2) - 36 * (4*x_1-2) * (4*x_2-2) + 27 * (4*x_2-2)^2) ]) - 8.693)
 
@@ -37,7 +37,7 @@ use num::{Float, FromPrimitive};
 ///
 /// where `\bar{x}_i = 4*x_i - 2` and `x_i \in [0, 1]`.
 ///
-/// The global minimum is at `f(x_1, x_2) = f(0.5, 0.25) = 3.3851993182036826`.
+/// The global minimum is at `f(x_1, x_2) = f(0.5, 0.25) = -3.3851993182036826`.
 pub fn picheny<T>(param: &[T; 2]) -> T
 where
     T: Float + FromPrimitive,
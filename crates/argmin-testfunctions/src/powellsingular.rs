@@ -0,0 +1,170 @@
+// Copyright 2018-2024 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+//! # Powell singular test function
+//!
+//! Defined as
+//!
+//! `f(x_1, x_2, ..., x_n) = \sum_{i=1}^{n/4} \left[ (x_{4i-3} + 10*x_{4i-2})^2 + 5*(x_{4i-1} - x_{4i})^2
+//! + (x_{4i-2} - 2*x_{4i-1})^4 + 10*(x_{4i-3} - x_{4i})^4 \right]`
+//!
+//! where `n` is a multiple of 4.
+//!
+//! The global minimum is at `f(x_1, x_2, ..., x_n) = f(0, 0, ..., 0) = 0`. The Hessian is
+//! singular at the minimum, which slows down the convergence of Newton-type methods. The usual
+//! starting point is `(3, -1, 0, 1, 3, -1, 0, 1, ...)`.
+//!
+//! Reference: J. J. Moré, B. S. Garbow and K. E. Hillstrom, "Testing Unconstrained
+//! Optimization Software", ACM Transactions on Mathematical Software, 7(1), 1981.
+
+use num::{Float, FromPrimitive};
+use std::iter::Sum;
+
+/// Powell singular test function
+///
+/// Defined as
+///
+/// `f(x_1, x_2, ..., x_n) = \sum_{i=1}^{n/4} \left[ (x_{4i-3} + 10*x_{4i-2})^2 + 5*(x_{4i-1} - x_{4i})^2
+/// + (x_{4i-2} - 2*x_{4i-1})^4 + 10*(x_{4i-3} - x_{4i})^4 \right]`
+///
+/// where `n` is a multiple of 4.
+///
+/// The global minimum is at `f(x_1, x_2, ..., x_n) = f(0, 0, ..., 0) = 0`.
+pub fn powell_singular<T>(param: &[T]) -> T
+where
+    T: Float + FromPrimitive + Sum,
+{
+    assert!(param.len() >= 4);
+    assert_eq!(param.len() % 4, 0);
+
+    let n2 = T::from_f64(2.0).unwrap();
+    let n5 = T::from_f64(5.0).unwrap();
+    let n10 = T::from_f64(10.0).unwrap();
+
+    param
+        .chunks_exact(4)
+        .map(|x| {
+            (x[0] + n10 * x[1]).powi(2)
+                + n5 * (x[2] - x[3]).powi(2)
+                + (x[1] - n2 * x[2]).powi(4)
+                + n10 * (x[0] - x[3]).powi(4)
+        })
+        .sum()
+}
+
+fn powell_singular_derivative_into<T>(param: &[T], out: &mut [T])
+where
+    T: Float + FromPrimitive,
+{
+    assert!(param.len() >= 4);
+    assert_eq!(param.len() % 4, 0);
+
+    let n2 = T::from_f64(2.0).unwrap();
+    let n4 = T::from_f64(4.0).unwrap();
+    let n8 = T::from_f64(8.0).unwrap();
+    let n10 = T::from_f64(10.0).unwrap();
+    let n20 = T::from_f64(20.0).unwrap();
+    let n40 = T::from_f64(40.0).unwrap();
+
+    for (x, o) in param.chunks_exact(4).zip(out.chunks_exact_mut(4)) {
+        let t1 = x[0] + n10 * x[1];
+        let t2 = x[2] - x[3];
+        let t3 = (x[1] - n2 * x[2]).powi(3);
+        let t4 = (x[0] - x[3]).powi(3);
+        o[0] = n2 * t1 + n40 * t4;
+        o[1] = n20 * t1 + n4 * t3;
+        o[2] = n10 * t2 - n8 * t3;
+        o[3] = -n10 * t2 - n40 * t4;
+    }
+}
+
+/// Derivative of Powell singular test function
+pub fn powell_singular_derivative<T>(param: &[T]) -> Vec<T>
+where
+    T: Float + FromPrimitive,
+{
+    let mut out = vec![T::zero(); param.len()];
+    powell_singular_derivative_into(param, &mut out);
+    out
+}
+
+/// Derivative of Powell singular test function
+///
+/// This is the const generics version, which requires the number of parameters to be known
+/// at compile time.
+pub fn powell_singular_derivative_const<const N: usize, T>(param: &[T; N]) -> [T; N]
+where
+    T: Float + FromPrimitive,
+{
+    let mut out = [T::zero(); N];
+    powell_singular_derivative_into(param, &mut out);
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_relative_eq;
+    use finitediff::FiniteDiff;
+    use proptest::prelude::*;
+    use std::f32;
+
+    #[test]
+    fn test_powell_singular_optimum() {
+        assert_relative_eq!(powell_singular(&[0.0_f32; 8]), 0.0, epsilon = f32::EPSILON);
+        assert_relative_eq!(
+            powell_singular(&[0.0_f64; 100]),
+            0.0,
+            epsilon = f64::EPSILON
+        );
+
+        for d in powell_singular_derivative(&[0.0_f64; 100]) {
+            assert_relative_eq!(d, 0.0, epsilon = f64::EPSILON);
+        }
+    }
+
+    #[test]
+    fn test_powell_singular_start() {
+        assert_relative_eq!(
+            powell_singular(&[3.0_f64, -1.0, 0.0, 1.0]),
+            215.0,
+            epsilon = f64::EPSILON
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_powell_singular_param_length() {
+        powell_singular(&[0.0_f64; 6]);
+    }
+
+    proptest! {
+        #[test]
+        fn test_powell_singular_derivative_finitediff(a in -5.0..5.0,
+                                                      b in -5.0..5.0,
+                                                      c in -5.0..5.0,
+                                                      d in -5.0..5.0,
+                                                      e in -5.0..5.0,
+                                                      f in -5.0..5.0,
+                                                      g in -5.0..5.0,
+                                                      h in -5.0..5.0) {
+            let param = [a, b, c, d, e, f, g, h];
+            let derivative = powell_singular_derivative(&param);
+            let derivative_const = powell_singular_derivative_const(&param);
+            let derivative_fd = Vec::from(param).central_diff(&|x| powell_singular(x));
+            for i in 0..derivative.len() {
+                assert_relative_eq!(derivative[i], derivative_const[i]);
+                assert_relative_eq!(
+                    derivative[i],
+                    derivative_fd[i],
+                    epsilon = 1e-4,
+                    max_relative = 1e-2
+                );
+            }
+        }
+    }
+}
@@ -0,0 +1,145 @@
+// Copyright 2018-2024 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+//! # Trigonometric test function
+//!
+//! Defined as
+//!
+//! `f(x_1, x_2, ..., x_n) = \sum_{i=1}^{n} r_i^2`
+//!
+//! with `r_i = n - \sum_{j=1}^{n} cos(x_j) + i*(1 - cos(x_i)) - sin(x_i)`.
+//!
+//! The global minimum is at `f(x_1, x_2, ..., x_n) = f(0, 0, ..., 0) = 0`. The function has
+//! several local minima. The usual starting point is `(1/n, 1/n, ..., 1/n)`.
+//!
+//! Reference: J. J. Moré, B. S. Garbow and K. E. Hillstrom, "Testing Unconstrained
+//! Optimization Software", ACM Transactions on Mathematical Software, 7(1), 1981.
+
+use num::{Float, FromPrimitive};
+use std::iter::Sum;
+
+/// Residuals `r_i` of the trigonometric function
+fn residuals<T>(param: &[T]) -> impl Iterator<Item = T> + '_
+where
+    T: Float + FromPrimitive,
+{
+    let n = T::from_usize(param.len()).unwrap();
+    let cos_sum = param.iter().fold(T::zero(), |acc, x| acc + x.cos());
+    param.iter().enumerate().map(move |(i, x)| {
+        let i = T::from_usize(i + 1).unwrap();
+        n - cos_sum + i * (T::one() - x.cos()) - x.sin()
+    })
+}
+
+/// Trigonometric test function
+///
+/// Defined as
+///
+/// `f(x_1, x_2, ..., x_n) = \sum_{i=1}^{n} r_i^2`
+///
+/// with `r_i = n - \sum_{j=1}^{n} cos(x_j) + i*(1 - cos(x_i)) - sin(x_i)`.
+///
+/// The global minimum is at `f(x_1, x_2, ..., x_n) = f(0, 0, ..., 0) = 0`.
+pub fn trigonometric<T>(param: &[T]) -> T
+where
+    T: Float + FromPrimitive + Sum,
+{
+    assert!(!param.is_empty());
+
+    residuals(param).map(|r| r.powi(2)).sum()
+}
+
+fn trigonometric_derivative_into<T>(param: &[T], out: &mut [T])
+where
+    T: Float + FromPrimitive,
+{
+    assert!(!param.is_empty());
+
+    let n2 = T::from_f64(2.0).unwrap();
+
+    // dr_i/dx_k = sin(x_k) + delta_{ik} * (i*sin(x_i) - cos(x_i))
+    let r: Vec<T> = residuals(param).collect();
+    let r_sum = r.iter().fold(T::zero(), |acc, &r| acc + r);
+    for (k, ((x, r), o)) in param.iter().zip(r).zip(out.iter_mut()).enumerate() {
+        let k = T::from_usize(k + 1).unwrap();
+        *o = n2 * (x.sin() * r_sum + r * (k * x.sin() - x.cos()));
+    }
+}
+
+/// Derivative of trigonometric test function
+pub fn trigonometric_derivative<T>(param: &[T]) -> Vec<T>
+where
+    T: Float + FromPrimitive,
+{
+    let mut out = vec![T::zero(); param.len()];
+    trigonometric_derivative_into(param, &mut out);
+    out
+}
+
+/// Derivative of trigonometric test function
+///
+/// This is the const generics version, which requires the number of parameters to be known
+/// at compile time.
+pub fn trigonometric_derivative_const<const N: usize, T>(param: &[T; N]) -> [T; N]
+where
+    T: Float + FromPrimitive,
+{
+    let mut out = [T::zero(); N];
+    trigonometric_derivative_into(param, &mut out);
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_relative_eq;
+    use finitediff::FiniteDiff;
+    use proptest::prelude::*;
+    use std::f32;
+
+    #[test]
+    fn test_trigonometric_optimum() {
+        assert_relative_eq!(trigonometric(&[0.0_f32; 10]), 0.0, epsilon = f32::EPSILON);
+        assert_relative_eq!(trigonometric(&[0.0_f64; 100]), 0.0, epsilon = f64::EPSILON);
+
+        for d in trigonometric_derivative(&[0.0_f64; 100]) {
+            assert_relative_eq!(d, 0.0, epsilon = f64::EPSILON);
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_trigonometric_param_length() {
+        trigonometric::<f64>(&[]);
+    }
+
+    proptest! {
+        #[test]
+        fn test_trigonometric_derivative_finitediff(a in -5.0..5.0,
+                                                    b in -5.0..5.0,
+                                                    c in -5.0..5.0,
+                                                    d in -5.0..5.0,
+                                                    e in -5.0..5.0,
+                                                    f in -5.0..5.0,
+                                                    g in -5.0..5.0,
+                                                    h in -5.0..5.0) {
+            let param = [a, b, c, d, e, f, g, h];
+            let derivative = trigonometric_derivative(&param);
+            let derivative_const = trigonometric_derivative_const(&param);
+            let derivative_fd = Vec::from(param).central_diff(&|x| trigonometric(x));
+            for i in 0..derivative.len() {
+                assert_relative_eq!(derivative[i], derivative_const[i]);
+                assert_relative_eq!(
+                    derivative[i],
+                    derivative_fd[i],
+                    epsilon = 1e-4,
+                    max_relative = 1e-2
+                );
+            }
+        }
+    }
+}
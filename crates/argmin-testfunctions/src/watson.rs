@@ -0,0 +1,226 @@
+// Copyright 2018-2024 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+//! # Watson test problem
+//!
+//! Nonlinear least-squares problem with `2 <= n <= 31` parameters and 31 residuals
+//!
+//! `r_i(x) = \sum_{j=2}^{n} (j - 1) * x_j * t_i^{j-2} - (\sum_{j=1}^{n} x_j * t_i^{j-1})^2 - 1`
+//!
+//! where `t_i = i / 29` for `i = 1, ..., 29`, and `r_30(x) = x_1` and
+//! `r_31(x) = x_2 - x_1^2 - 1`. The objective is `f(x) = \sum_{i=1}^{31} r_i(x)^2`.
+//!
+//! The minimum is `f = 2.28767e-3` for `n = 6`, `f = 1.39976e-6` for `n = 9` and
+//! `f = 4.72238e-10` for `n = 12`. The usual starting point is `(0, 0, ..., 0)`.
+//!
+//! Reference: J. J. Moré, B. S. Garbow and K. E. Hillstrom, "Testing Unconstrained
+//! Optimization Software", ACM Transactions on Mathematical Software, 7(1), 1981.
+
+use num::{Float, FromPrimitive};
+
+/// Number of residuals of the Watson problem
+const M: usize = 31;
+
+/// Minimizer of the Watson problem for `n = 6`
+pub const WATSON6_OPTIMUM: [f64; 6] = [
+    -0.015725083965817677,
+    1.0124348696193266,
+    -0.2329916278094022,
+    1.2604301057212712,
+    -1.5137289497589683,
+    0.9929964487853855,
+];
+
+/// Minimum of the Watson problem for `n = 6`
+pub const WATSON6_OPTIMUM_VALUE: f64 = 0.0022876700535523283;
+
+fn watson_residuals_into<T>(param: &[T], out: &mut [T])
+where
+    T: Float + FromPrimitive,
+{
+    assert!(param.len() >= 2 && param.len() <= M);
+
+    let n1 = T::from_f64(1.0).unwrap();
+
+    for (i, o) in out.iter_mut().take(29).enumerate() {
+        let t = T::from_f64((i + 1) as f64 / 29.0).unwrap();
+        // s1 = \sum_{j=2}^{n} (j - 1) * x_j * t^{j-2} and s2 = \sum_{j=1}^{n} x_j * t^{j-1}
+        let mut s1 = T::zero();
+        let mut s2 = T::zero();
+        let mut tj = T::one();
+        for (j, &x) in param.iter().enumerate() {
+            if j > 0 {
+                s1 = s1 + T::from_usize(j).unwrap() * x * tj / t;
+            }
+            s2 = s2 + x * tj;
+            tj = tj * t;
+        }
+        *o = s1 - s2.powi(2) - n1;
+    }
+    out[29] = param[0];
+    out[30] = param[1] - param[0].powi(2) - n1;
+}
+
+fn watson_jacobian_into<T, R>(param: &[T], out: &mut [R])
+where
+    T: Float + FromPrimitive,
+    R: AsMut<[T]>,
+{
+    assert!(param.len() >= 2 && param.len() <= M);
+
+    let n2 = T::from_f64(2.0).unwrap();
+
+    for (i, row) in out.iter_mut().take(29).enumerate() {
+        let t = T::from_f64((i + 1) as f64 / 29.0).unwrap();
+        let s2 = param.iter().rev().fold(T::zero(), |acc, &x| acc * t + x);
+        let mut tj = T::one();
+        for (j, o) in row.as_mut().iter_mut().enumerate() {
+            // d/dx_j of (j - 1) * x_j * t^{j-2} (one-based j) is j * t^{j-1} (zero-based j)
+            let d1 = if j > 0 {
+                T::from_usize(j).unwrap() * tj / t
+            } else {
+                T::zero()
+            };
+            *o = d1 - n2 * s2 * tj;
+            tj = tj * t;
+        }
+    }
+    let row = out[29].as_mut();
+    row.iter_mut().for_each(|o| *o = T::zero());
+    row[0] = T::one();
+    let row = out[30].as_mut();
+    row.iter_mut().for_each(|o| *o = T::zero());
+    row[0] = -n2 * param[0];
+    row[1] = T::one();
+}
+
+/// Watson test problem
+///
+/// Sum of squares of the residuals returned by [`watson_residuals`].
+///
+/// The minimum is `f = 2.28767e-3` for `n = 6`, `f = 1.39976e-6` for `n = 9` and
+/// `f = 4.72238e-10` for `n = 12`.
+pub fn watson<T>(param: &[T]) -> T
+where
+    T: Float + FromPrimitive,
+{
+    watson_residuals(param)
+        .iter()
+        .fold(T::zero(), |acc, r| acc + r.powi(2))
+}
+
+/// Residuals of Watson test problem
+pub fn watson_residuals<T>(param: &[T]) -> Vec<T>
+where
+    T: Float + FromPrimitive,
+{
+    let mut out = vec![T::zero(); M];
+    watson_residuals_into(param, &mut out);
+    out
+}
+
+/// Residuals of Watson test problem
+///
+/// This is the const generics version, which requires the number of parameters to be known
+/// at compile time.
+pub fn watson_residuals_const<const N: usize, T>(param: &[T; N]) -> [T; M]
+where
+    T: Float + FromPrimitive,
+{
+    let mut out = [T::zero(); M];
+    watson_residuals_into(param, &mut out);
+    out
+}
+
+/// Jacobian of the residuals of Watson test problem
+pub fn watson_jacobian<T>(param: &[T]) -> Vec<Vec<T>>
+where
+    T: Float + FromPrimitive,
+{
+    let mut out = vec![vec![T::zero(); param.len()]; M];
+    watson_jacobian_into(param, &mut out);
+    out
+}
+
+/// Jacobian of the residuals of Watson test problem
+///
+/// This is the const generics version, which requires the number of parameters to be known
+/// at compile time.
+pub fn watson_jacobian_const<const N: usize, T>(param: &[T; N]) -> [[T; N]; M]
+where
+    T: Float + FromPrimitive,
+{
+    let mut out = [[T::zero(); N]; M];
+    watson_jacobian_into(param, &mut out);
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_relative_eq;
+    use finitediff::FiniteDiff;
+    use proptest::prelude::*;
+
+    #[test]
+    fn test_watson_optimum() {
+        assert_relative_eq!(
+            watson(&WATSON6_OPTIMUM),
+            WATSON6_OPTIMUM_VALUE,
+            epsilon = 1e-12
+        );
+
+        // Gradient `2 * J^T r` vanishes at the optimum
+        let r = watson_residuals_const(&WATSON6_OPTIMUM);
+        let jac = watson_jacobian_const(&WATSON6_OPTIMUM);
+        for j in 0..6 {
+            let g: f64 = jac
+                .iter()
+                .zip(r.iter())
+                .map(|(row, r)| 2.0 * row[j] * r)
+                .sum();
+            assert_relative_eq!(g, 0.0, epsilon = 1e-8);
+        }
+    }
+
+    #[test]
+    fn test_watson_start() {
+        // At the origin, r_1 to r_29 and r_31 are -1 and r_30 is 0
+        assert_relative_eq!(watson(&[0.0_f64; 9]), 30.0, epsilon = f64::EPSILON);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_watson_param_length() {
+        watson(&[0.0_f64]);
+    }
+
+    proptest! {
+        #[test]
+        fn test_watson_jacobian_finitediff(param in prop::array::uniform6(-2.0..2.0)) {
+            let jacobian = watson_jacobian(&param);
+            let jacobian_const = watson_jacobian_const(&param);
+            let residuals = watson_residuals(&param);
+            let residuals_const = watson_residuals_const(&param);
+            for (r, r_const) in residuals.iter().zip(residuals_const.iter()) {
+                assert_relative_eq!(r, r_const);
+            }
+            for (i, (row, row_const)) in jacobian.iter().zip(jacobian_const.iter()).enumerate() {
+                let row_fd = Vec::from(param).central_diff(&|x| watson_residuals(x)[i]);
+                for ((d, d_const), d_fd) in row.iter().zip(row_const.iter()).zip(row_fd.iter()) {
+                    assert_relative_eq!(d, d_const);
+                    assert_relative_eq!(
+                        d,
+                        d_fd,
+                        epsilon = 1e-4,
+                        max_relative = 1e-2
+                    );
+                }
+            }
+        }
+    }
+}
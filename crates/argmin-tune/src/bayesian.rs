@@ -0,0 +1,312 @@
+// Copyright 2018-2024 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use crate::gp::{expected_improvement, GaussianProcess};
+use crate::random::{argmin, sample_unit};
+use argmin::core::{CostFunction, Error, IterState, Problem, Solver, SyncAlias, KV};
+use argmin::{argmin_error, kv};
+use rand::{Rng, SeedableRng};
+use rand_xoshiro::Xoshiro256PlusPlus;
+
+/// # Bayesian optimization
+///
+/// Minimizes an expensive cost function on the unit hypercube `[0, 1]^dim` with a Gaussian process
+/// surrogate model (Matérn 5/2 kernel) and the expected improvement acquisition function.
+///
+/// The first [`initial_samples`](`BayesianOptimization::with_initial_samples`) points are sampled
+/// uniformly at random. Afterwards, the Gaussian process is fitted to all observations in each
+/// iteration (with the length scale chosen by maximizing the log marginal likelihood) and the
+/// point with the largest expected improvement among
+/// [`num_candidates`](`BayesianOptimization::with_num_candidates`) candidates is evaluated. Half
+/// of the candidates are sampled uniformly, the other half around the best point found so far.
+///
+/// In each iteration, a batch of [`batch_size`](`BayesianOptimization::with_batch_size`) points
+/// is evaluated via `bulk_cost`, which allows for parallel evaluation. Batches are proposed with
+/// the "constant liar" heuristic: after selecting a point, it is added to the observations with
+/// the best cost observed so far and the next point is selected based on the updated model.
+///
+/// Non-finite costs are replaced by the worst finite cost when fitting the model.
+///
+/// ## Reference
+///
+/// Jones, D.R., Schonlau, M. & Welch, W.J. Efficient Global Optimization of Expensive Black-Box
+/// Functions. Journal of Global Optimization 13, 455–492 (1998).
+#[derive(Clone, Debug)]
+pub struct BayesianOptimization<R = Xoshiro256PlusPlus> {
+    /// Dimension of the search space
+    dim: usize,
+    /// Number of points evaluated per iteration
+    batch_size: usize,
+    /// Number of uniformly sampled points before the surrogate model is used
+    initial_samples: usize,
+    /// Number of candidates the acquisition function is evaluated at
+    num_candidates: usize,
+    /// Random number generator
+    rng: R,
+    /// All evaluated points and their costs
+    observations: Vec<(Vec<f64>, f64)>,
+}
+
+impl BayesianOptimization<Xoshiro256PlusPlus> {
+    /// Construct a new instance of `BayesianOptimization` for a search space of dimension `dim`
+    ///
+    /// Defaults to a batch size of 1, `max(5, 2 * dim)` initial samples and 1000 candidates.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin_tune::BayesianOptimization;
+    /// let solver = BayesianOptimization::new(3);
+    /// ```
+    pub fn new(dim: usize) -> Self {
+        BayesianOptimization {
+            dim,
+            batch_size: 1,
+            initial_samples: (2 * dim).max(5),
+            num_candidates: 1000,
+            rng: Xoshiro256PlusPlus::from_entropy(),
+            observations: vec![],
+        }
+    }
+}
+
+impl<R: Rng> BayesianOptimization<R> {
+    /// Set the number of points evaluated per iteration (default: 1)
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin_tune::BayesianOptimization;
+    /// # use argmin::core::Error;
+    /// # fn main() -> Result<(), Error> {
+    /// let solver = BayesianOptimization::new(3).with_batch_size(4)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_batch_size(mut self, batch_size: usize) -> Result<Self, Error> {
+        if batch_size == 0 {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "`BayesianOptimization`: batch size must be positive."
+            ));
+        }
+        self.batch_size = batch_size;
+        Ok(self)
+    }
+
+    /// Set the number of uniformly sampled points before the surrogate model is used
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin_tune::BayesianOptimization;
+    /// # use argmin::core::Error;
+    /// # fn main() -> Result<(), Error> {
+    /// let solver = BayesianOptimization::new(3).with_initial_samples(10)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_initial_samples(mut self, initial_samples: usize) -> Result<Self, Error> {
+        if initial_samples == 0 {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "`BayesianOptimization`: number of initial samples must be positive."
+            ));
+        }
+        self.initial_samples = initial_samples;
+        Ok(self)
+    }
+
+    /// Set the number of candidates the acquisition function is evaluated at (default: 1000)
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin_tune::BayesianOptimization;
+    /// # use argmin::core::Error;
+    /// # fn main() -> Result<(), Error> {
+    /// let solver = BayesianOptimization::new(3).with_num_candidates(5000)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_num_candidates(mut self, num_candidates: usize) -> Result<Self, Error> {
+        if num_candidates < 2 {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "`BayesianOptimization`: number of candidates must be at least 2."
+            ));
+        }
+        self.num_candidates = num_candidates;
+        Ok(self)
+    }
+
+    /// Set the random number generator (defaults to `Xoshiro256PlusPlus::from_entropy()`)
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin_tune::BayesianOptimization;
+    /// use rand::SeedableRng;
+    ///
+    /// let solver =
+    ///     BayesianOptimization::new(3).with_rng_generator(rand::rngs::StdRng::seed_from_u64(42));
+    /// ```
+    pub fn with_rng_generator<R1: Rng>(self, rng: R1) -> BayesianOptimization<R1> {
+        BayesianOptimization {
+            dim: self.dim,
+            batch_size: self.batch_size,
+            initial_samples: self.initial_samples,
+            num_candidates: self.num_candidates,
+            rng,
+            observations: self.observations,
+        }
+    }
+
+    /// Proposes a batch of points based on the surrogate model. Returns the points and the length
+    /// scale of the kernel.
+    fn propose(&mut self) -> Result<(Vec<Vec<f64>>, f64), Error> {
+        let worst = self
+            .observations
+            .iter()
+            .map(|(_, c)| *c)
+            .filter(|c| c.is_finite())
+            .fold(f64::NEG_INFINITY, f64::max);
+        if worst == f64::NEG_INFINITY {
+            // No finite observation yet, nothing to learn from
+            return Ok((
+                sample_unit(&mut self.rng, self.dim, self.batch_size),
+                f64::NAN,
+            ));
+        }
+        let mut x: Vec<Vec<f64>> = self.observations.iter().map(|(p, _)| p.clone()).collect();
+        let mut y: Vec<f64> = self
+            .observations
+            .iter()
+            .map(|(_, c)| if c.is_finite() { *c } else { worst })
+            .collect();
+
+        let mut gp = GaussianProcess::fit(&x, &y)?;
+        let length_scale = gp.length_scale();
+        let mut batch = Vec::with_capacity(self.batch_size);
+        for b in 0..self.batch_size {
+            if b > 0 {
+                gp = GaussianProcess::fit_with_length_scale(&x, &y, length_scale)?;
+            }
+            let best_idx = argmin(&y).unwrap();
+            let best = y[best_idx];
+            let mut candidates = sample_unit(&mut self.rng, self.dim, self.num_candidates / 2);
+            for _ in 0..(self.num_candidates - self.num_candidates / 2) {
+                let c = x[best_idx]
+                    .iter()
+                    .map(|xi| {
+                        (xi + 0.1 * length_scale * (2.0 * self.rng.gen::<f64>() - 1.0))
+                            .clamp(0.0, 1.0)
+                    })
+                    .collect();
+                candidates.push(c);
+            }
+            let point = candidates
+                .into_iter()
+                .map(|c| {
+                    let (mean, std) = gp.predict(&c);
+                    (expected_improvement(mean, std, best), c)
+                })
+                .max_by(|a, b| a.0.total_cmp(&b.0))
+                .map(|(_, c)| c)
+                .unwrap();
+            x.push(point.clone());
+            y.push(best);
+            batch.push(point);
+        }
+        Ok((batch, length_scale))
+    }
+}
+
+impl<O, R> Solver<O, IterState<Vec<f64>, (), (), (), (), f64>> for BayesianOptimization<R>
+where
+    O: CostFunction<Param = Vec<f64>, Output = f64> + SyncAlias,
+    R: Rng,
+{
+    fn name(&self) -> &str {
+        "Bayesian Optimization"
+    }
+
+    fn next_iter(
+        &mut self,
+        problem: &mut Problem<O>,
+        state: IterState<Vec<f64>, (), (), (), (), f64>,
+    ) -> Result<(IterState<Vec<f64>, (), (), (), (), f64>, Option<KV>), Error> {
+        let (points, kv) = if self.observations.len() < self.initial_samples {
+            let n = self
+                .batch_size
+                .min(self.initial_samples - self.observations.len());
+            (sample_unit(&mut self.rng, self.dim, n), None)
+        } else {
+            let (points, length_scale) = self.propose()?;
+            (points, Some(kv!("length_scale" => length_scale;)))
+        };
+        let costs = problem.bulk_cost(&points)?;
+        let best = argmin(&costs);
+        let state = match best {
+            Some(i) => state.param(points[i].clone()).cost(costs[i]),
+            None => state,
+        };
+        self.observations.extend(points.into_iter().zip(costs));
+        Ok((state, kv))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use argmin::core::Executor;
+
+    struct Branin {}
+
+    impl CostFunction for Branin {
+        type Param = Vec<f64>;
+        type Output = f64;
+
+        fn cost(&self, p: &Self::Param) -> Result<Self::Output, Error> {
+            // Branin function rescaled onto the unit square, global minimum 0.397887
+            let x1 = 15.0 * p[0] - 5.0;
+            let x2 = 15.0 * p[1];
+            let pi = std::f64::consts::PI;
+            Ok(
+                (x2 - 5.1 / (4.0 * pi * pi) * x1 * x1 + 5.0 / pi * x1 - 6.0).powi(2)
+                    + 10.0 * (1.0 - 1.0 / (8.0 * pi)) * x1.cos()
+                    + 10.0,
+            )
+        }
+    }
+
+    #[test]
+    fn test_bayesian_optimization() {
+        let solver = BayesianOptimization::new(2)
+            .with_initial_samples(6)
+            .unwrap()
+            .with_batch_size(2)
+            .unwrap()
+            .with_rng_generator(Xoshiro256PlusPlus::seed_from_u64(3));
+        let res = Executor::new(Branin {}, solver)
+            .configure(|state| state.max_iters(20))
+            .run()
+            .unwrap();
+        assert_eq!(res.problem.counts["cost_count"], 40);
+        assert_eq!(res.solver.observations.len(), 40);
+        assert!(res.state.get_best_cost() < 0.6);
+    }
+
+    #[test]
+    fn test_bayesian_optimization_parameters() {
+        assert!(BayesianOptimization::new(2).with_batch_size(0).is_err());
+        assert!(BayesianOptimization::new(2)
+            .with_initial_samples(0)
+            .is_err());
+        assert!(BayesianOptimization::new(2).with_num_candidates(1).is_err());
+    }
+}
@@ -0,0 +1,245 @@
+// Copyright 2018-2024 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+//! Gaussian process regression with a Matérn 5/2 kernel, used as surrogate model in Bayesian
+//! optimization.
+
+use argmin::argmin_error;
+use argmin::core::Error;
+
+/// Candidate length scales (relative to the diagonal of the unit hypercube), selected by
+/// maximizing the log marginal likelihood
+const LENGTH_SCALES: [f64; 6] = [0.05, 0.1, 0.2, 0.4, 0.8, 1.6];
+
+/// Gaussian process fitted to standardized observations
+pub(crate) struct GaussianProcess {
+    /// Training points
+    x: Vec<Vec<f64>>,
+    /// Lower triangular Cholesky factor of the kernel matrix
+    chol: Vec<Vec<f64>>,
+    /// `K^-1 y` (of the standardized observations)
+    alpha: Vec<f64>,
+    /// Mean of the observations
+    mean: f64,
+    /// Standard deviation of the observations
+    std: f64,
+    /// Length scale of the kernel
+    length_scale: f64,
+    /// Log marginal likelihood of the standardized observations (up to a constant)
+    log_marginal_likelihood: f64,
+}
+
+impl GaussianProcess {
+    /// Fits a Gaussian process to the observations `y` at the points `x`. The length scale is
+    /// chosen from a fixed set of candidates by maximizing the log marginal likelihood.
+    pub(crate) fn fit(x: &[Vec<f64>], y: &[f64]) -> Result<Self, Error> {
+        let dim = x.first().map(Vec::len).unwrap_or(1).max(1);
+        let diag = (dim as f64).sqrt();
+        let mut best: Option<(f64, GaussianProcess)> = None;
+        for &scale in LENGTH_SCALES.iter() {
+            let Ok(gp) = GaussianProcess::fit_with_length_scale(x, y, scale * diag) else {
+                continue;
+            };
+            let lml = gp.log_marginal_likelihood;
+            if best.as_ref().map(|(b, _)| lml > *b).unwrap_or(true) {
+                best = Some((lml, gp));
+            }
+        }
+        best.map(|(_, gp)| gp).ok_or_else(|| {
+            argmin_error!(
+                PotentialBug,
+                "`GaussianProcess`: Cholesky decomposition of the kernel matrix failed."
+            )
+        })
+    }
+
+    /// Fits a Gaussian process with a given length scale
+    pub(crate) fn fit_with_length_scale(
+        x: &[Vec<f64>],
+        y: &[f64],
+        length_scale: f64,
+    ) -> Result<Self, Error> {
+        let n = y.len();
+        let mean = y.iter().sum::<f64>() / n as f64;
+        let var = y.iter().map(|v| (v - mean).powi(2)).sum::<f64>() / n as f64;
+        let std = if var > 0.0 { var.sqrt() } else { 1.0 };
+        let ys: Vec<f64> = y.iter().map(|v| (v - mean) / std).collect();
+
+        // Increase the jitter on the diagonal until the kernel matrix is numerically positive
+        // definite
+        let mut jitter = 1e-8;
+        let chol = loop {
+            let k: Vec<Vec<f64>> = (0..n)
+                .map(|i| {
+                    (0..n)
+                        .map(|j| {
+                            matern52(&x[i], &x[j], length_scale) + if i == j { jitter } else { 0.0 }
+                        })
+                        .collect()
+                })
+                .collect();
+            match cholesky(&k) {
+                Some(chol) => break chol,
+                None if jitter < 1e-2 => jitter *= 10.0,
+                None => {
+                    return Err(argmin_error!(
+                        PotentialBug,
+                        "`GaussianProcess`: Cholesky decomposition of the kernel matrix failed."
+                    ))
+                }
+            }
+        };
+        // y^T K^-1 y = z^T z with z = L^-1 y
+        let z = solve_lower(&chol, &ys);
+        let log_det: f64 = chol.iter().enumerate().map(|(i, row)| row[i].ln()).sum();
+        let log_marginal_likelihood = -0.5 * z.iter().map(|z| z * z).sum::<f64>() - log_det;
+        let alpha = solve_upper_transposed(&chol, &z);
+        Ok(GaussianProcess {
+            x: x.to_vec(),
+            chol,
+            alpha,
+            mean,
+            std,
+            length_scale,
+            log_marginal_likelihood,
+        })
+    }
+
+    /// Returns the length scale of the kernel
+    pub(crate) fn length_scale(&self) -> f64 {
+        self.length_scale
+    }
+
+    /// Predicts mean and standard deviation at `point` in the original scale of the observations
+    pub(crate) fn predict(&self, point: &[f64]) -> (f64, f64) {
+        let k: Vec<f64> = self
+            .x
+            .iter()
+            .map(|x| matern52(x, point, self.length_scale))
+            .collect();
+        let mean: f64 = k.iter().zip(self.alpha.iter()).map(|(a, b)| a * b).sum();
+        let v = solve_lower(&self.chol, &k);
+        let var = (1.0 - v.iter().map(|v| v * v).sum::<f64>()).max(0.0);
+        (self.mean + self.std * mean, self.std * var.sqrt())
+    }
+}
+
+/// Matérn 5/2 kernel with unit variance
+fn matern52(a: &[f64], b: &[f64], length_scale: f64) -> f64 {
+    let r = a
+        .iter()
+        .zip(b.iter())
+        .map(|(a, b)| (a - b).powi(2))
+        .sum::<f64>()
+        .sqrt()
+        / length_scale;
+    let s = 5.0f64.sqrt() * r;
+    (1.0 + s + s * s / 3.0) * (-s).exp()
+}
+
+/// Cholesky decomposition `A = L L^T`. Returns `None` if `A` is not positive definite.
+fn cholesky(a: &[Vec<f64>]) -> Option<Vec<Vec<f64>>> {
+    let n = a.len();
+    let mut l = vec![vec![0.0; n]; n];
+    for i in 0..n {
+        for j in 0..=i {
+            let sum: f64 = (0..j).map(|k| l[i][k] * l[j][k]).sum();
+            if i == j {
+                let d = a[i][i] - sum;
+                if d <= 0.0 || !d.is_finite() {
+                    return None;
+                }
+                l[i][i] = d.sqrt();
+            } else {
+                l[i][j] = (a[i][j] - sum) / l[j][j];
+            }
+        }
+    }
+    Some(l)
+}
+
+/// Solves `L x = b` for lower triangular `L`
+fn solve_lower(l: &[Vec<f64>], b: &[f64]) -> Vec<f64> {
+    let mut x = vec![0.0; b.len()];
+    for i in 0..b.len() {
+        let sum: f64 = (0..i).map(|k| l[i][k] * x[k]).sum();
+        x[i] = (b[i] - sum) / l[i][i];
+    }
+    x
+}
+
+/// Solves `L^T x = b` for lower triangular `L`
+fn solve_upper_transposed(l: &[Vec<f64>], b: &[f64]) -> Vec<f64> {
+    let n = b.len();
+    let mut x = vec![0.0; n];
+    for i in (0..n).rev() {
+        let sum: f64 = (i + 1..n).map(|k| l[k][i] * x[k]).sum();
+        x[i] = (b[i] - sum) / l[i][i];
+    }
+    x
+}
+
+/// Expected improvement (for minimization) over `best` given the predicted `mean` and `std`
+pub(crate) fn expected_improvement(mean: f64, std: f64, best: f64) -> f64 {
+    if std <= 0.0 {
+        return (best - mean).max(0.0);
+    }
+    let z = (best - mean) / std;
+    (best - mean) * normal_cdf(z) + std * normal_pdf(z)
+}
+
+fn normal_pdf(z: f64) -> f64 {
+    (-0.5 * z * z).exp() / (2.0 * std::f64::consts::PI).sqrt()
+}
+
+fn normal_cdf(z: f64) -> f64 {
+    0.5 * (1.0 + erf(z / std::f64::consts::SQRT_2))
+}
+
+/// Error function (Abramowitz and Stegun, 7.1.26; maximum absolute error 1.5e-7)
+fn erf(x: f64) -> f64 {
+    let sign = x.signum();
+    let x = x.abs();
+    let t = 1.0 / (1.0 + 0.327_591_1 * x);
+    let poly = t
+        * (0.254_829_592
+            + t * (-0.284_496_736
+                + t * (1.421_413_741 + t * (-1.453_152_027 + t * 1.061_405_429))));
+    sign * (1.0 - poly * (-x * x).exp())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_gp_interpolates() {
+        let x: Vec<Vec<f64>> = (0..8).map(|i| vec![i as f64 / 7.0]).collect();
+        let y: Vec<f64> = x.iter().map(|x| (6.0 * x[0]).sin()).collect();
+        let gp = GaussianProcess::fit(&x, &y).unwrap();
+        for (x, y) in x.iter().zip(y.iter()) {
+            let (mean, std) = gp.predict(x);
+            assert!((mean - y).abs() < 1e-3);
+            assert!(std < 1e-2);
+        }
+        // uncertainty grows away from the data
+        let (_, std) = gp.predict(&[3.0]);
+        assert!(std > 0.5);
+        assert!(gp.length_scale() > 0.0);
+    }
+
+    #[test]
+    fn test_expected_improvement() {
+        assert_eq!(expected_improvement(1.0, 0.0, 0.0), 0.0);
+        assert_eq!(expected_improvement(-1.0, 0.0, 0.0), 1.0);
+        let a = expected_improvement(0.0, 1.0, 0.0);
+        assert!((a - normal_pdf(0.0)).abs() < 1e-12);
+        assert!(expected_improvement(0.0, 2.0, 0.0) > a);
+        assert!(expected_improvement(-0.5, 1.0, 0.0) > a);
+        assert!((normal_cdf(1.96) - 0.975).abs() < 1e-4);
+    }
+}
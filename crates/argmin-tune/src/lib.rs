@@ -0,0 +1,90 @@
+// Copyright 2018-2024 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+//! Hyperparameter tuning built on argmin.
+//!
+//! A [`SearchSpace`] consists of continuous, log-uniform, integer and categorical
+//! hyperparameters. Each hyperparameter is represented by a coordinate in `[0, 1]` which is
+//! transformed onto its domain, such that hyperparameter search becomes the minimization of a
+//! cost function on the unit hypercube. This cost function decodes a point into a [`Config`] and
+//! evaluates the user-provided [`Objective`] (usually training and validating a model).
+//!
+//! The [`Tuner`] performs the search with one of the following strategies (see [`Strategy`]):
+//!
+//! * Random search ([`RandomSearch`])
+//! * Particle swarm optimization
+//!   ([`ParticleSwarm`](`argmin::solver::particleswarm::ParticleSwarm`))
+//! * Bayesian optimization with a Gaussian process surrogate model ([`BayesianOptimization`])
+//!
+//! Evaluations of the objective are run in parallel and the budget can be limited by the number
+//! of evaluations and the runtime. [`RandomSearch`] and [`BayesianOptimization`] are regular
+//! argmin solvers and can also be used directly with an `Executor` on any cost function defined
+//! on the unit hypercube.
+//!
+//! # Usage
+//!
+//! Add the following line to your dependencies list:
+//!
+//! ```toml
+//! [dependencies]
+#![doc = concat!("argmin-tune = \"", env!("CARGO_PKG_VERSION"), "\"")]
+//! ```
+//!
+//! # Example
+//!
+//! ```
+//! use argmin::core::Error;
+//! use argmin_tune::{Config, SearchSpace, Strategy, Tuner};
+//!
+//! # fn main() -> Result<(), Error> {
+//! let space = SearchSpace::new()
+//!     .continuous("momentum", 0.0, 0.99)
+//!     .log_uniform("learning_rate", 1e-5, 1e-1);
+//!
+//! let result = Tuner::new(space, |config: &Config| -> Result<f64, Error> {
+//!     let momentum = config.float("momentum")?;
+//!     let lr = config.float("learning_rate")?;
+//!     Ok((momentum - 0.9).powi(2) + (lr.log10() + 3.0).powi(2))
+//! })
+//! .strategy(Strategy::ParticleSwarm { num_particles: 10 })
+//! .max_evals(100)
+//! .parallelism(4)
+//! .run()?;
+//!
+//! println!("{}", result.best().unwrap().config);
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! # License
+//!
+//! Licensed under either of
+//!
+//!   * Apache License, Version 2.0,
+//!     ([LICENSE-APACHE](https://github.com/argmin-rs/argmin/blob/main/LICENSE-APACHE) or
+//!     <http://www.apache.org/licenses/LICENSE-2.0>)
+//!   * MIT License ([LICENSE-MIT](https://github.com/argmin-rs/argmin/blob/main/LICENSE-MIT) or
+//!     <http://opensource.org/licenses/MIT>)
+//!
+//! at your option.
+//!
+//! ## Contribution
+//!
+//! Unless you explicitly state otherwise, any contribution intentionally submitted for inclusion
+//! in the work by you, as defined in the Apache-2.0 license, shall be dual licensed as above,
+//! without any additional terms or conditions.
+
+mod bayesian;
+mod gp;
+mod random;
+mod space;
+mod tuner;
+
+pub use bayesian::BayesianOptimization;
+pub use random::RandomSearch;
+pub use space::{Config, Domain, SearchSpace};
+pub use tuner::{Objective, Strategy, Trial, TuneResult, Tuner};
@@ -0,0 +1,173 @@
+// Copyright 2018-2024 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use argmin::argmin_error;
+use argmin::core::{CostFunction, Error, IterState, Problem, Solver, SyncAlias, KV};
+use rand::{Rng, SeedableRng};
+use rand_xoshiro::Xoshiro256PlusPlus;
+
+/// # Random search
+///
+/// Samples points uniformly from the unit hypercube `[0, 1]^dim`. In each iteration, a batch of
+/// [`batch_size`](`RandomSearch::with_batch_size`) points is evaluated via `bulk_cost`, which
+/// allows for parallel evaluation.
+///
+/// The best point of the batch is stored as the current parameter vector of the state.
+#[derive(Clone, Debug)]
+pub struct RandomSearch<R = Xoshiro256PlusPlus> {
+    /// Dimension of the search space
+    dim: usize,
+    /// Number of points evaluated per iteration
+    batch_size: usize,
+    /// Random number generator
+    rng: R,
+}
+
+impl RandomSearch<Xoshiro256PlusPlus> {
+    /// Construct a new instance of `RandomSearch` for a search space of dimension `dim`
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin_tune::RandomSearch;
+    /// let solver = RandomSearch::new(3);
+    /// ```
+    pub fn new(dim: usize) -> Self {
+        RandomSearch {
+            dim,
+            batch_size: 1,
+            rng: Xoshiro256PlusPlus::from_entropy(),
+        }
+    }
+}
+
+impl<R: Rng> RandomSearch<R> {
+    /// Set the number of points evaluated per iteration (default: 1)
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin_tune::RandomSearch;
+    /// # use argmin::core::Error;
+    /// # fn main() -> Result<(), Error> {
+    /// let solver = RandomSearch::new(3).with_batch_size(8)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_batch_size(mut self, batch_size: usize) -> Result<Self, Error> {
+        if batch_size == 0 {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "`RandomSearch`: batch size must be positive."
+            ));
+        }
+        self.batch_size = batch_size;
+        Ok(self)
+    }
+
+    /// Set the random number generator (defaults to `Xoshiro256PlusPlus::from_entropy()`)
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin_tune::RandomSearch;
+    /// use rand::SeedableRng;
+    ///
+    /// let solver = RandomSearch::new(3).with_rng_generator(rand::rngs::StdRng::seed_from_u64(42));
+    /// ```
+    pub fn with_rng_generator<R1: Rng>(self, rng: R1) -> RandomSearch<R1> {
+        RandomSearch {
+            dim: self.dim,
+            batch_size: self.batch_size,
+            rng,
+        }
+    }
+}
+
+/// Samples `n` points uniformly from the unit hypercube of dimension `dim`
+pub(crate) fn sample_unit<R: Rng>(rng: &mut R, dim: usize, n: usize) -> Vec<Vec<f64>> {
+    (0..n)
+        .map(|_| (0..dim).map(|_| rng.gen::<f64>()).collect())
+        .collect()
+}
+
+/// Returns the index of the smallest (non-NaN) cost
+pub(crate) fn argmin(costs: &[f64]) -> Option<usize> {
+    costs
+        .iter()
+        .enumerate()
+        .filter(|(_, c)| !c.is_nan())
+        .min_by(|a, b| a.1.total_cmp(b.1))
+        .map(|(i, _)| i)
+}
+
+impl<O, R> Solver<O, IterState<Vec<f64>, (), (), (), (), f64>> for RandomSearch<R>
+where
+    O: CostFunction<Param = Vec<f64>, Output = f64> + SyncAlias,
+    R: Rng,
+{
+    fn name(&self) -> &str {
+        "Random Search"
+    }
+
+    fn next_iter(
+        &mut self,
+        problem: &mut Problem<O>,
+        state: IterState<Vec<f64>, (), (), (), (), f64>,
+    ) -> Result<(IterState<Vec<f64>, (), (), (), (), f64>, Option<KV>), Error> {
+        let points = sample_unit(&mut self.rng, self.dim, self.batch_size);
+        let costs = problem.bulk_cost(&points)?;
+        Ok(match argmin(&costs) {
+            Some(i) => (state.param(points[i].clone()).cost(costs[i]), None),
+            None => (state, None),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use argmin::core::{Executor, State};
+
+    struct Sphere {}
+
+    impl CostFunction for Sphere {
+        type Param = Vec<f64>;
+        type Output = f64;
+
+        fn cost(&self, p: &Self::Param) -> Result<Self::Output, Error> {
+            Ok(p.iter().map(|x| (x - 0.5).powi(2)).sum())
+        }
+    }
+
+    #[test]
+    fn test_random_search() {
+        let solver = RandomSearch::new(2)
+            .with_batch_size(4)
+            .unwrap()
+            .with_rng_generator(Xoshiro256PlusPlus::seed_from_u64(1));
+        let res = Executor::new(Sphere {}, solver)
+            .configure(|state| state.max_iters(50))
+            .run()
+            .unwrap();
+        assert_eq!(res.problem.counts["cost_count"], 200);
+        assert!(res.state.get_best_cost() < 1e-2);
+        let best = res.state.get_best_param().unwrap();
+        assert!(best.iter().all(|x| (0.0..=1.0).contains(x)));
+    }
+
+    #[test]
+    fn test_random_search_batch_size() {
+        assert!(RandomSearch::new(2).with_batch_size(0).is_err());
+    }
+
+    #[test]
+    fn test_argmin() {
+        assert_eq!(argmin(&[2.0, f64::NAN, 1.0, 3.0]), Some(2));
+        assert_eq!(argmin(&[f64::NAN]), None);
+    }
+}
@@ -0,0 +1,389 @@
+// Copyright 2018-2024 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use argmin::argmin_error;
+use argmin::core::{Error, KvValue, KV};
+use std::collections::HashSet;
+use std::fmt;
+
+/// Domain of a hyperparameter
+///
+/// Each hyperparameter is represented by a coordinate `u` in `[0, 1]` which is transformed onto
+/// its domain by [`Domain::decode`].
+#[derive(Clone, Debug, PartialEq)]
+pub enum Domain {
+    /// Real number in `[lower, upper]`: `x = lower + u * (upper - lower)`
+    Continuous {
+        /// Lower bound
+        lower: f64,
+        /// Upper bound
+        upper: f64,
+    },
+    /// Positive real number in `[lower, upper]` on a logarithmic scale:
+    /// `x = exp(ln(lower) + u * (ln(upper) - ln(lower)))`
+    LogUniform {
+        /// Lower bound
+        lower: f64,
+        /// Upper bound
+        upper: f64,
+    },
+    /// Integer in `[lower, upper]` (both inclusive). The unit interval is divided into
+    /// `upper - lower + 1` cells of equal width.
+    Integer {
+        /// Lower bound
+        lower: i64,
+        /// Upper bound
+        upper: i64,
+    },
+    /// One of a list of choices. The unit interval is divided into one cell of equal width per
+    /// choice.
+    Categorical(Vec<String>),
+}
+
+impl Domain {
+    /// Maps the coordinate `u` onto the domain. `u` is clamped to `[0, 1]`.
+    ///
+    /// Continuous and log-uniform hyperparameters are returned as `KvValue::Float`, integers as
+    /// `KvValue::Int` and choices as `KvValue::Str`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use argmin::core::KvValue;
+    /// use argmin_tune::Domain;
+    ///
+    /// let domain = Domain::LogUniform { lower: 1e-4, upper: 1.0 };
+    /// let x = domain.decode(0.5).get_float().unwrap();
+    /// assert!((x - 1e-2).abs() < 1e-12);
+    ///
+    /// let domain = Domain::Integer { lower: 1, upper: 4 };
+    /// assert_eq!(domain.decode(0.3), KvValue::Int(2));
+    /// ```
+    pub fn decode(&self, u: f64) -> KvValue {
+        let u = u.clamp(0.0, 1.0);
+        match self {
+            Domain::Continuous { lower, upper } => KvValue::Float(lower + u * (upper - lower)),
+            // the bounds are returned exactly, which is not guaranteed by `exp(ln(x))`
+            Domain::LogUniform { lower, .. } if u == 0.0 => KvValue::Float(*lower),
+            Domain::LogUniform { upper, .. } if u == 1.0 => KvValue::Float(*upper),
+            Domain::LogUniform { lower, upper } => {
+                let (l, u_) = (lower.ln(), upper.ln());
+                KvValue::Float((l + u * (u_ - l)).exp().clamp(*lower, *upper))
+            }
+            Domain::Integer { lower, upper } => {
+                let cells = (upper - lower + 1) as u64;
+                KvValue::Int(lower + cell(u, cells) as i64)
+            }
+            Domain::Categorical(choices) => {
+                KvValue::Str(choices[cell(u, choices.len() as u64) as usize].clone())
+            }
+        }
+    }
+
+    fn validate(&self, name: &str) -> Result<(), Error> {
+        let valid = match self {
+            Domain::Continuous { lower, upper } => {
+                lower.is_finite() && upper.is_finite() && lower < upper
+            }
+            Domain::LogUniform { lower, upper } => {
+                upper.is_finite() && *lower > 0.0 && lower < upper
+            }
+            Domain::Integer { lower, upper } => lower <= upper,
+            Domain::Categorical(choices) => !choices.is_empty(),
+        };
+        if valid {
+            Ok(())
+        } else {
+            Err(argmin_error!(
+                InvalidParameter,
+                format!("`SearchSpace`: invalid domain of hyperparameter `{name}`: {self:?}")
+            ))
+        }
+    }
+}
+
+/// Returns the index of the cell which contains `u` if `[0, 1]` is divided into `cells` cells
+fn cell(u: f64, cells: u64) -> u64 {
+    ((u * cells as f64).floor() as u64).min(cells - 1)
+}
+
+/// Search space of a hyperparameter search
+///
+/// # Example
+///
+/// ```
+/// use argmin_tune::SearchSpace;
+///
+/// let space = SearchSpace::new()
+///     .continuous("momentum", 0.0, 0.99)
+///     .log_uniform("learning_rate", 1e-5, 1e-1)
+///     .integer("layers", 1, 4)
+///     .categorical("activation", ["relu", "tanh"]);
+///
+/// assert_eq!(space.dim(), 4);
+///
+/// let config = space.decode(&[0.0, 0.5, 1.0, 0.7]).unwrap();
+/// assert_eq!(config.float("momentum").unwrap(), 0.0);
+/// assert_eq!(config.int("layers").unwrap(), 4);
+/// assert_eq!(config.str("activation").unwrap(), "tanh");
+/// ```
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct SearchSpace {
+    /// Names and domains of the hyperparameters
+    params: Vec<(String, Domain)>,
+}
+
+impl SearchSpace {
+    /// Construct a new, empty search space
+    pub fn new() -> Self {
+        SearchSpace { params: vec![] }
+    }
+
+    /// Add a hyperparameter with domain `domain`
+    #[must_use]
+    pub fn add<S: Into<String>>(mut self, name: S, domain: Domain) -> Self {
+        self.params.push((name.into(), domain));
+        self
+    }
+
+    /// Add a real valued hyperparameter in `[lower, upper]`
+    #[must_use]
+    pub fn continuous<S: Into<String>>(self, name: S, lower: f64, upper: f64) -> Self {
+        self.add(name, Domain::Continuous { lower, upper })
+    }
+
+    /// Add a positive real valued hyperparameter in `[lower, upper]` which is searched on a
+    /// logarithmic scale
+    #[must_use]
+    pub fn log_uniform<S: Into<String>>(self, name: S, lower: f64, upper: f64) -> Self {
+        self.add(name, Domain::LogUniform { lower, upper })
+    }
+
+    /// Add an integer hyperparameter in `[lower, upper]` (both inclusive)
+    #[must_use]
+    pub fn integer<S: Into<String>>(self, name: S, lower: i64, upper: i64) -> Self {
+        self.add(name, Domain::Integer { lower, upper })
+    }
+
+    /// Add a categorical hyperparameter which takes one of `choices`
+    #[must_use]
+    pub fn categorical<S, I, C>(self, name: S, choices: I) -> Self
+    where
+        S: Into<String>,
+        I: IntoIterator<Item = C>,
+        C: Into<String>,
+    {
+        let choices = choices.into_iter().map(Into::into).collect();
+        self.add(name, Domain::Categorical(choices))
+    }
+
+    /// Returns the number of hyperparameters, which is the dimension of the unit hypercube the
+    /// search is performed on
+    pub fn dim(&self) -> usize {
+        self.params.len()
+    }
+
+    /// Returns names and domains of all hyperparameters
+    pub fn params(&self) -> &[(String, Domain)] {
+        &self.params
+    }
+
+    /// Checks that the search space is not empty, that names are unique and that all domains are
+    /// valid
+    pub fn validate(&self) -> Result<(), Error> {
+        if self.params.is_empty() {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "`SearchSpace`: search space is empty."
+            ));
+        }
+        let mut names = HashSet::new();
+        for (name, domain) in self.params.iter() {
+            if !names.insert(name.as_str()) {
+                return Err(argmin_error!(
+                    InvalidParameter,
+                    format!("`SearchSpace`: hyperparameter `{name}` is defined more than once.")
+                ));
+            }
+            domain.validate(name)?;
+        }
+        Ok(())
+    }
+
+    /// Maps a point of the unit hypercube onto a configuration
+    pub fn decode(&self, point: &[f64]) -> Result<Config, Error> {
+        if point.len() != self.params.len() {
+            return Err(argmin_error!(
+                InvalidParameter,
+                format!(
+                    "`SearchSpace`: point has {} coordinates, expected {}.",
+                    point.len(),
+                    self.params.len()
+                )
+            ));
+        }
+        Ok(Config {
+            values: self
+                .params
+                .iter()
+                .zip(point.iter())
+                .map(|((name, domain), &u)| (name.clone(), domain.decode(u)))
+                .collect(),
+        })
+    }
+}
+
+/// A configuration of hyperparameters, obtained by decoding a point of a [`SearchSpace`]
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Config {
+    /// Names and values of the hyperparameters
+    values: Vec<(String, KvValue)>,
+}
+
+impl Config {
+    /// Returns the value of hyperparameter `name`
+    pub fn get(&self, name: &str) -> Option<&KvValue> {
+        self.values.iter().find(|(n, _)| n == name).map(|(_, v)| v)
+    }
+
+    /// Returns the value of the real valued (or integer) hyperparameter `name`
+    pub fn float(&self, name: &str) -> Result<f64, Error> {
+        self.typed(name, "float", KvValue::get_float)
+    }
+
+    /// Returns the value of the integer hyperparameter `name`
+    pub fn int(&self, name: &str) -> Result<i64, Error> {
+        self.typed(name, "integer", KvValue::get_int)
+    }
+
+    /// Returns the value of the categorical hyperparameter `name`
+    pub fn str(&self, name: &str) -> Result<&str, Error> {
+        match self.get(name) {
+            Some(KvValue::Str(s)) => Ok(s),
+            _ => Err(self.missing(name, "categorical")),
+        }
+    }
+
+    /// Returns an iterator over names and values of all hyperparameters
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &KvValue)> {
+        self.values.iter().map(|(n, v)| (n.as_str(), v))
+    }
+
+    /// Converts the configuration into a `KV`, for instance for use in observers
+    pub fn to_kv(&self) -> KV {
+        let mut kv = KV::new();
+        for (name, value) in self.values.iter() {
+            kv.insert(name, value.clone());
+        }
+        kv
+    }
+
+    fn typed<T>(
+        &self,
+        name: &str,
+        kind: &str,
+        get: impl Fn(&KvValue) -> Option<T>,
+    ) -> Result<T, Error> {
+        self.get(name)
+            .and_then(get)
+            .ok_or_else(|| self.missing(name, kind))
+    }
+
+    fn missing(&self, name: &str, kind: &str) -> Error {
+        argmin_error!(
+            InvalidParameter,
+            format!("`Config`: no {kind} hyperparameter `{name}`.")
+        )
+    }
+}
+
+impl fmt::Display for Config {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (i, (name, value)) in self.values.iter().enumerate() {
+            if i > 0 {
+                f.write_str(", ")?;
+            }
+            write!(f, "{name}={}", value.as_string())?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_decode_domains() {
+        let continuous = Domain::Continuous {
+            lower: -1.0,
+            upper: 3.0,
+        };
+        assert_eq!(continuous.decode(0.25), KvValue::Float(0.0));
+        assert_eq!(continuous.decode(-0.5), KvValue::Float(-1.0));
+        assert_eq!(continuous.decode(1.5), KvValue::Float(3.0));
+
+        let log = Domain::LogUniform {
+            lower: 1e-3,
+            upper: 10.0,
+        };
+        assert_eq!(log.decode(0.0), KvValue::Float(1e-3));
+        assert_eq!(log.decode(1.0), KvValue::Float(10.0));
+        let x = log.decode(0.75).get_float().unwrap();
+        assert!((x - 1.0).abs() < 1e-12);
+
+        let int = Domain::Integer {
+            lower: -2,
+            upper: 2,
+        };
+        let values: Vec<_> = [0.0, 0.19, 0.2, 0.5, 0.99, 1.0]
+            .iter()
+            .map(|&u| int.decode(u).get_int().unwrap())
+            .collect();
+        assert_eq!(values, vec![-2, -2, -1, 0, 2, 2]);
+
+        let cat = Domain::Categorical(vec!["a".to_string(), "b".to_string()]);
+        assert_eq!(cat.decode(0.49), KvValue::Str("a".to_string()));
+        assert_eq!(cat.decode(0.5), KvValue::Str("b".to_string()));
+        assert_eq!(cat.decode(1.0), KvValue::Str("b".to_string()));
+    }
+
+    #[test]
+    fn test_validate() {
+        let space = SearchSpace::new().continuous("x", 0.0, 1.0);
+        assert!(space.validate().is_ok());
+        assert!(SearchSpace::new().validate().is_err());
+        assert!(space.clone().integer("x", 0, 1).validate().is_err());
+        assert!(space.clone().continuous("y", 1.0, 1.0).validate().is_err());
+        assert!(space.clone().log_uniform("y", 0.0, 1.0).validate().is_err());
+        assert!(space.clone().integer("y", 2, 1).validate().is_err());
+        assert!(space.clone().integer("y", 1, 1).validate().is_ok());
+        assert!(space
+            .categorical("y", Vec::<String>::new())
+            .validate()
+            .is_err());
+    }
+
+    #[test]
+    fn test_config() {
+        let space = SearchSpace::new()
+            .continuous("x", 0.0, 1.0)
+            .integer("n", 0, 9)
+            .categorical("kind", ["a", "b"]);
+        assert!(space.decode(&[0.5, 0.5]).is_err());
+        let config = space.decode(&[0.5, 0.5, 0.0]).unwrap();
+        assert_eq!(config.float("x").unwrap(), 0.5);
+        assert_eq!(config.int("n").unwrap(), 5);
+        assert_eq!(config.float("n").unwrap(), 5.0);
+        assert_eq!(config.str("kind").unwrap(), "a");
+        assert!(config.int("x").is_err());
+        assert!(config.str("n").is_err());
+        assert!(config.float("missing").is_err());
+        assert_eq!(config.to_string(), "x=0.5, n=5, kind=a");
+        assert_eq!(config.to_kv().get("n"), Some(&KvValue::Int(5)));
+    }
+}
@@ -0,0 +1,508 @@
+// Copyright 2018-2024 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use crate::{BayesianOptimization, Config, RandomSearch, SearchSpace};
+use argmin::core::{
+    CostFunction, Error, Executor, State, SyncAlias, TerminationReason, TerminationStatus,
+};
+use argmin::solver::particleswarm::ParticleSwarm;
+use argmin::{argmin_error, argmin_error_closure};
+use rand::SeedableRng;
+use rand_xoshiro::Xoshiro256PlusPlus;
+use std::sync::Mutex;
+use std::time::Duration;
+
+/// Objective of a hyperparameter search, which is minimized
+///
+/// Implemented for all closures `Fn(&Config) -> Result<f64, Error>`. Since evaluations may run
+/// in parallel, objectives need to be `Sync`.
+pub trait Objective {
+    /// Evaluates the objective for the configuration `config`
+    fn evaluate(&self, config: &Config) -> Result<f64, Error>;
+}
+
+impl<T> Objective for T
+where
+    T: Fn(&Config) -> Result<f64, Error>,
+{
+    fn evaluate(&self, config: &Config) -> Result<f64, Error> {
+        (self)(config)
+    }
+}
+
+/// Search strategy
+#[derive(Clone, Debug, PartialEq)]
+pub enum Strategy {
+    /// Random search (see [`RandomSearch`])
+    RandomSearch,
+    /// Particle swarm optimization (see
+    /// [`ParticleSwarm`](`argmin::solver::particleswarm::ParticleSwarm`))
+    ParticleSwarm {
+        /// Number of particles, which are evaluated in parallel
+        num_particles: usize,
+    },
+    /// Bayesian optimization (see [`BayesianOptimization`])
+    Bayesian {
+        /// Number of uniformly sampled configurations before the surrogate model is used
+        initial_samples: usize,
+    },
+}
+
+/// A single evaluation of the objective
+#[derive(Clone, Debug, PartialEq)]
+pub struct Trial {
+    /// Evaluated configuration
+    pub config: Config,
+    /// Value of the objective
+    pub value: f64,
+}
+
+/// Result of a hyperparameter search
+#[derive(Clone, Debug, PartialEq)]
+pub struct TuneResult {
+    /// All trials in the order of evaluation
+    pub trials: Vec<Trial>,
+    /// Reason why the search stopped
+    pub termination_reason: Option<TerminationReason>,
+}
+
+impl TuneResult {
+    /// Returns the trial with the smallest value of the objective (NaN values are ignored)
+    pub fn best(&self) -> Option<&Trial> {
+        self.trials
+            .iter()
+            .filter(|t| !t.value.is_nan())
+            .min_by(|a, b| a.value.total_cmp(&b.value))
+    }
+}
+
+/// Hyperparameter search
+///
+/// Treats hyperparameter search as an argmin problem: the [`SearchSpace`] is mapped onto the unit
+/// hypercube, on which the chosen [`Strategy`] minimizes the [`Objective`].
+///
+/// The budget is given by the maximum number of evaluations of the objective
+/// ([`max_evals`](`Tuner::max_evals`), default: 100) and optionally a
+/// [`timeout`](`Tuner::timeout`), which is checked after each iteration of the solver.
+///
+/// Up to [`parallelism`](`Tuner::parallelism`) evaluations run in parallel in separate threads.
+/// Random search and Bayesian optimization evaluate batches of `parallelism` configurations,
+/// particle swarm optimization evaluates all particles of an iteration in parallel. Since all
+/// evaluations of an iteration are performed before the budget is checked, the number of
+/// evaluations is rounded down to a multiple of the batch size (or the number of particles,
+/// respectively).
+///
+/// # Example
+///
+/// ```
+/// use argmin::core::Error;
+/// use argmin_tune::{Config, SearchSpace, Strategy, Tuner};
+///
+/// # fn main() -> Result<(), Error> {
+/// let space = SearchSpace::new()
+///     .log_uniform("learning_rate", 1e-4, 1.0)
+///     .integer("layers", 1, 4)
+///     .categorical("activation", ["relu", "tanh"]);
+///
+/// // Usually, this would train and validate a model
+/// let objective = |config: &Config| -> Result<f64, Error> {
+///     let lr = config.float("learning_rate")?;
+///     let layers = config.int("layers")?;
+///     let penalty = if config.str("activation")? == "relu" { 0.0 } else { 0.5 };
+///     Ok((lr.log10() + 2.0).powi(2) + (layers - 3).pow(2) as f64 + penalty)
+/// };
+///
+/// let result = Tuner::new(space, objective)
+///     .strategy(Strategy::Bayesian { initial_samples: 8 })
+///     .max_evals(30)
+///     .parallelism(2)
+///     .seed(42)
+///     .run()?;
+///
+/// let best = result.best().unwrap();
+/// println!("best configuration: {} ({})", best.config, best.value);
+/// # assert_eq!(result.trials.len(), 30);
+/// # Ok(())
+/// # }
+/// ```
+pub struct Tuner<O> {
+    /// Search space
+    space: SearchSpace,
+    /// Objective
+    objective: O,
+    /// Search strategy
+    strategy: Strategy,
+    /// Maximum number of evaluations of the objective
+    max_evals: usize,
+    /// Maximum number of parallel evaluations
+    parallelism: usize,
+    /// Seed of the random number generator
+    seed: Option<u64>,
+    /// Maximum runtime
+    timeout: Option<Duration>,
+}
+
+impl<O> Tuner<O>
+where
+    O: Objective + Sync,
+{
+    /// Construct a new `Tuner` which minimizes `objective` over `space`
+    ///
+    /// Defaults to random search with a budget of 100 evaluations, which are performed
+    /// sequentially.
+    pub fn new(space: SearchSpace, objective: O) -> Self {
+        Tuner {
+            space,
+            objective,
+            strategy: Strategy::RandomSearch,
+            max_evals: 100,
+            parallelism: 1,
+            seed: None,
+            timeout: None,
+        }
+    }
+
+    /// Set the search strategy
+    #[must_use]
+    pub fn strategy(mut self, strategy: Strategy) -> Self {
+        self.strategy = strategy;
+        self
+    }
+
+    /// Set the maximum number of evaluations of the objective
+    #[must_use]
+    pub fn max_evals(mut self, max_evals: usize) -> Self {
+        self.max_evals = max_evals;
+        self
+    }
+
+    /// Set the maximum number of parallel evaluations
+    #[must_use]
+    pub fn parallelism(mut self, parallelism: usize) -> Self {
+        self.parallelism = parallelism;
+        self
+    }
+
+    /// Set the seed of the random number generator, which makes the search reproducible
+    /// (provided that the objective is deterministic)
+    #[must_use]
+    pub fn seed(mut self, seed: u64) -> Self {
+        self.seed = Some(seed);
+        self
+    }
+
+    /// Set the maximum runtime, which is checked after each iteration of the solver
+    #[must_use]
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Run the hyperparameter search
+    pub fn run(self) -> Result<TuneResult, Error> {
+        self.space.validate()?;
+        if self.max_evals == 0 || self.parallelism == 0 {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "`Tuner`: `max_evals` and `parallelism` must be positive."
+            ));
+        }
+        let dim = self.space.dim();
+        let rng = match self.seed {
+            Some(seed) => Xoshiro256PlusPlus::seed_from_u64(seed),
+            None => Xoshiro256PlusPlus::from_entropy(),
+        };
+        let batch_size = self.parallelism.min(self.max_evals);
+        let iters = (self.max_evals / batch_size) as u64;
+        let problem = TuneProblem {
+            space: self.space,
+            objective: self.objective,
+            parallelism: self.parallelism,
+            trials: Mutex::new(vec![]),
+        };
+        let timeout = self.timeout;
+
+        let (problem, status) = match self.strategy {
+            Strategy::RandomSearch => {
+                let solver = RandomSearch::new(dim)
+                    .with_batch_size(batch_size)?
+                    .with_rng_generator(rng);
+                let mut executor =
+                    Executor::new(problem, solver).configure(|state| state.max_iters(iters));
+                if let Some(timeout) = timeout {
+                    executor = executor.timeout(timeout);
+                }
+                let mut res = executor.run()?;
+                (
+                    res.problem.take_problem(),
+                    res.state.get_termination_status().clone(),
+                )
+            }
+            Strategy::Bayesian { initial_samples } => {
+                let initial_samples = initial_samples.min(self.max_evals);
+                let solver = BayesianOptimization::new(dim)
+                    .with_batch_size(batch_size)?
+                    .with_initial_samples(initial_samples)?
+                    .with_rng_generator(rng);
+                // The initial samples are evaluated in batches as well, which may require an
+                // additional iteration
+                let mut executor = Executor::new(problem, solver).configure(|state| {
+                    state.max_iters(bayesian_iters(self.max_evals, batch_size, initial_samples))
+                });
+                if let Some(timeout) = timeout {
+                    executor = executor.timeout(timeout);
+                }
+                let mut res = executor.run()?;
+                (
+                    res.problem.take_problem(),
+                    res.state.get_termination_status().clone(),
+                )
+            }
+            Strategy::ParticleSwarm { num_particles } => {
+                if num_particles == 0 || num_particles > self.max_evals {
+                    return Err(argmin_error!(
+                        InvalidParameter,
+                        "`Tuner`: number of particles must be positive and must not exceed `max_evals`."
+                    ));
+                }
+                let solver = ParticleSwarm::new((vec![0.0; dim], vec![1.0; dim]), num_particles)
+                    .with_rng_generator(rng);
+                // Initialization evaluates all particles once
+                let iters = (self.max_evals / num_particles - 1) as u64;
+                let mut executor =
+                    Executor::new(problem, solver).configure(|state| state.max_iters(iters));
+                if let Some(timeout) = timeout {
+                    executor = executor.timeout(timeout);
+                }
+                let mut res = executor.run()?;
+                (
+                    res.problem.take_problem(),
+                    res.state.get_termination_status().clone(),
+                )
+            }
+        };
+        let problem = problem.ok_or_else(argmin_error_closure!(
+            PotentialBug,
+            "`Tuner`: problem was not returned by executor."
+        ))?;
+        Ok(TuneResult {
+            trials: problem.trials.into_inner().unwrap(),
+            termination_reason: match status {
+                TerminationStatus::Terminated(reason) => Some(reason),
+                TerminationStatus::NotTerminated => None,
+            },
+        })
+    }
+}
+
+/// Number of iterations of Bayesian optimization such that at most `max_evals` evaluations are
+/// performed
+fn bayesian_iters(max_evals: usize, batch_size: usize, initial_samples: usize) -> u64 {
+    let initial = initial_samples.min(max_evals);
+    let initial_iters = initial.div_ceil(batch_size);
+    (initial_iters + (max_evals - initial) / batch_size) as u64
+}
+
+/// Maps the unit hypercube onto the search space and evaluates the objective
+struct TuneProblem<O> {
+    space: SearchSpace,
+    objective: O,
+    parallelism: usize,
+    trials: Mutex<Vec<Trial>>,
+}
+
+impl<O: Objective + Sync> TuneProblem<O> {
+    /// Evaluates all configurations, using up to `parallelism` threads
+    fn evaluate(&self, configs: &[Config]) -> Result<Vec<f64>, Error> {
+        let chunk_size = configs.len().div_ceil(self.parallelism.max(1)).max(1);
+        let values: Vec<f64> = if chunk_size >= configs.len() {
+            configs
+                .iter()
+                .map(|c| self.objective.evaluate(c))
+                .collect::<Result<_, _>>()?
+        } else {
+            std::thread::scope(|s| {
+                let handles: Vec<_> = configs
+                    .chunks(chunk_size)
+                    .map(|chunk| {
+                        s.spawn(move || {
+                            chunk
+                                .iter()
+                                .map(|c| self.objective.evaluate(c))
+                                .collect::<Result<Vec<f64>, Error>>()
+                        })
+                    })
+                    .collect();
+                handles
+                    .into_iter()
+                    .map(|h| h.join().expect("objective panicked"))
+                    .collect::<Result<Vec<_>, _>>()
+            })?
+            .into_iter()
+            .flatten()
+            .collect()
+        };
+        self.trials
+            .lock()
+            .unwrap()
+            .extend(
+                configs
+                    .iter()
+                    .zip(values.iter())
+                    .map(|(config, &value)| Trial {
+                        config: config.clone(),
+                        value,
+                    }),
+            );
+        Ok(values)
+    }
+}
+
+impl<O: Objective + Sync> CostFunction for TuneProblem<O> {
+    type Param = Vec<f64>;
+    type Output = f64;
+
+    fn cost(&self, param: &Self::Param) -> Result<Self::Output, Error> {
+        let config = self.space.decode(param)?;
+        Ok(self.evaluate(&[config])?[0])
+    }
+
+    fn bulk_cost<P>(&self, params: &[P]) -> Result<Vec<Self::Output>, Error>
+    where
+        P: std::borrow::Borrow<Self::Param> + SyncAlias,
+        Self::Output: argmin::core::SendAlias,
+        Self: SyncAlias,
+    {
+        let configs = params
+            .iter()
+            .map(|p| self.space.decode(p.borrow()))
+            .collect::<Result<Vec<_>, _>>()?;
+        self.evaluate(&configs)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    fn space() -> SearchSpace {
+        SearchSpace::new()
+            .continuous("x", -2.0, 2.0)
+            .integer("n", 0, 3)
+            .categorical("kind", ["a", "b", "c"])
+    }
+
+    fn objective(config: &Config) -> Result<f64, Error> {
+        let x = config.float("x")?;
+        let n = config.int("n")?;
+        let kind = match config.str("kind")? {
+            "b" => 0.0,
+            _ => 1.0,
+        };
+        Ok((x - 1.0).powi(2) + ((n - 2) as f64).abs() + kind)
+    }
+
+    #[test]
+    fn test_strategies() {
+        for (strategy, max_evals, expected) in [
+            (Strategy::RandomSearch, 61, 60),
+            (Strategy::ParticleSwarm { num_particles: 8 }, 61, 56),
+            (Strategy::Bayesian { initial_samples: 5 }, 61, 59),
+        ] {
+            let result = Tuner::new(space(), objective)
+                .strategy(strategy.clone())
+                .max_evals(max_evals)
+                .parallelism(3)
+                .seed(7)
+                .run()
+                .unwrap();
+            assert_eq!(result.trials.len(), expected, "{strategy:?}");
+            assert_eq!(
+                result.termination_reason,
+                Some(TerminationReason::MaxItersReached)
+            );
+            let best = result.best().unwrap();
+            assert!(best.value < 1.0, "{strategy:?}: {best:?}");
+            assert_eq!(best.config.str("kind").unwrap(), "b");
+        }
+    }
+
+    #[test]
+    fn test_bayesian_iters() {
+        // 5 initial samples in batches of 3 (3 + 2), then batches of 3
+        assert_eq!(bayesian_iters(61, 3, 5), 2 + 18);
+        assert_eq!(bayesian_iters(4, 3, 4), 2);
+        assert_eq!(bayesian_iters(10, 1, 5), 10);
+    }
+
+    #[test]
+    fn test_reproducible_and_parallel() {
+        let threads = Mutex::new(HashSet::new());
+        let calls = AtomicUsize::new(0);
+        let tracked = |config: &Config| -> Result<f64, Error> {
+            threads.lock().unwrap().insert(std::thread::current().id());
+            calls.fetch_add(1, Ordering::SeqCst);
+            objective(config)
+        };
+        let parallel = Tuner::new(space(), &tracked)
+            .max_evals(40)
+            .parallelism(4)
+            .seed(1)
+            .run()
+            .unwrap();
+        assert_eq!(calls.load(Ordering::SeqCst), 40);
+        assert!(threads.lock().unwrap().len() > 1);
+
+        // Random search samples the same configurations independent of the batch size
+        let sequential = Tuner::new(space(), objective)
+            .max_evals(40)
+            .parallelism(1)
+            .seed(1)
+            .run()
+            .unwrap();
+        assert_eq!(parallel, sequential);
+    }
+
+    #[test]
+    fn test_errors() {
+        let failing = |_: &Config| -> Result<f64, Error> { Err(Error::msg("training failed")) };
+        let err = Tuner::new(space(), failing)
+            .parallelism(2)
+            .run()
+            .unwrap_err();
+        assert_eq!(err.root_cause().to_string(), "training failed");
+        assert_eq!(
+            format!("{err:#}"),
+            "`Random Search` failed in iteration 0: training failed"
+        );
+
+        assert!(Tuner::new(SearchSpace::new(), objective).run().is_err());
+        assert!(Tuner::new(space(), objective).max_evals(0).run().is_err());
+        assert!(Tuner::new(space(), objective).parallelism(0).run().is_err());
+        assert!(Tuner::new(space(), objective)
+            .strategy(Strategy::ParticleSwarm { num_particles: 200 })
+            .run()
+            .is_err());
+    }
+
+    #[test]
+    fn test_timeout() {
+        let slow = |config: &Config| -> Result<f64, Error> {
+            std::thread::sleep(Duration::from_millis(5));
+            objective(config)
+        };
+        let result = Tuner::new(space(), slow)
+            .max_evals(10_000)
+            .timeout(Duration::from_millis(50))
+            .run()
+            .unwrap();
+        assert!(result.trials.len() < 100);
+        assert_eq!(result.termination_reason, Some(TerminationReason::Timeout));
+    }
+}
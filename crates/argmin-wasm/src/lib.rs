@@ -0,0 +1,371 @@
+// Copyright 2018-2024 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+//! This crate provides JavaScript bindings for a small set of argmin solvers via WebAssembly.
+//!
+//! The bindings are generated with `wasm-bindgen` and can be built with `wasm-pack`:
+//!
+//! ```bash
+//! wasm-pack build crates/argmin-wasm --target web
+//! ```
+//!
+//! Cost functions are plain JavaScript functions which receive the parameter vector as a
+//! `Float64Array` and return a number. Parameter vectors are passed to and returned from the
+//! solvers as `Float64Array`s. The following solvers are available:
+//!
+//! * [`NelderMead`]
+//! * [`ParticleSwarm`]
+//! * [`LBFGS`], where the gradient is approximated via central differences
+//!
+//! The solver options are set via properties; they are validated when `minimize` is called.
+//!
+//! ```js
+//! import init, { LBFGS, NelderMead, ParticleSwarm } from "./pkg/argmin_wasm.js";
+//!
+//! await init();
+//!
+//! const rosenbrock = (p) => (1 - p[0]) ** 2 + 100 * (p[1] - p[0] ** 2) ** 2;
+//!
+//! const lbfgs = new LBFGS(7);
+//! lbfgs.toleranceGrad = 1e-8;
+//! let res = lbfgs.minimize(rosenbrock, new Float64Array([-1.2, 1.0]), 100);
+//! console.log(res.bestParam, res.bestCost, res.terminationReason);
+//!
+//! const nm = new NelderMead();
+//! nm.sdTolerance = 1e-6;
+//! res = nm.minimize(rosenbrock, [[-1.0, 3.0], [2.0, 1.5], [2.0, -1.0]], 500);
+//!
+//! const pso = new ParticleSwarm(new Float64Array([-4, -4]), new Float64Array([4, 4]), 40);
+//! pso.seed = 42;
+//! res = pso.minimize(rosenbrock, 100);
+//! ```
+//!
+//! This crate enables the `wasm-bindgen` feature of argmin. CTRL-C handling and parallelization
+//! via `rayon` are not available on `wasm32` targets.
+//!
+//! # License
+//!
+//! Licensed under either of
+//!
+//!   * Apache License, Version 2.0,
+//!     ([LICENSE-APACHE](https://github.com/argmin-rs/argmin/blob/main/LICENSE-APACHE) or
+//!     <http://www.apache.org/licenses/LICENSE-2.0>)
+//!   * MIT License ([LICENSE-MIT](https://github.com/argmin-rs/argmin/blob/main/LICENSE-MIT) or
+//!     <http://opensource.org/licenses/MIT>)
+//!
+//! at your option.
+//!
+//! ## Contribution
+//!
+//! Unless you explicitly state otherwise, any contribution intentionally submitted for inclusion
+//! in the work by you, as defined in the Apache-2.0 license, shall be dual licensed as above,
+//! without any additional terms or conditions.
+
+use argmin::core::{
+    CostFunction, Error, Executor, Gradient, IterState, PopulationState, State, TerminationStatus,
+};
+use argmin::solver::linesearch::MoreThuenteLineSearchConfig;
+use argmin::solver::neldermead::NelderMeadConfig;
+use argmin::solver::particleswarm::{Particle, ParticleSwarmConfig};
+use argmin::solver::quasinewton::LBFGSConfig;
+use js_sys::{Array, Float64Array, Function};
+use wasm_bindgen::prelude::*;
+
+/// Converts an argmin error into a JavaScript error.
+fn to_js_error(err: Error) -> JsError {
+    JsError::new(&format!("{err:#}"))
+}
+
+/// Optimization problem defined by a JavaScript cost function.
+struct JsProblem {
+    cost: Function,
+}
+
+impl CostFunction for JsProblem {
+    type Param = Vec<f64>;
+    type Output = f64;
+
+    fn cost(&self, param: &Self::Param) -> Result<Self::Output, Error> {
+        let param = Float64Array::from(param.as_slice());
+        let cost = self
+            .cost
+            .call1(&JsValue::NULL, &param)
+            .map_err(|e| Error::msg(e.as_string().unwrap_or_else(|| format!("{e:?}"))))?;
+        cost.as_f64()
+            .ok_or_else(|| Error::msg("Cost function must return a number."))
+    }
+}
+
+impl Gradient for JsProblem {
+    type Param = Vec<f64>;
+    type Gradient = Vec<f64>;
+
+    /// Approximates the gradient via central differences.
+    fn gradient(&self, param: &Self::Param) -> Result<Self::Gradient, Error> {
+        let cost = |p: &Vec<f64>| self.cost(p);
+        let gradient = finitediff::vec::central_diff(&cost)(param);
+        gradient
+    }
+}
+
+/// Result of an optimization run
+#[wasm_bindgen]
+pub struct OptimizationResult {
+    best_param: Vec<f64>,
+    best_cost: f64,
+    iterations: u32,
+    termination_reason: String,
+}
+
+impl OptimizationResult {
+    fn new<I: State<Float = f64>>(state: &I, best_param: Option<Vec<f64>>) -> Self {
+        OptimizationResult {
+            best_param: best_param.unwrap_or_default(),
+            best_cost: state.get_best_cost(),
+            iterations: u32::try_from(state.get_iter()).unwrap_or(u32::MAX),
+            termination_reason: match state.get_termination_status() {
+                TerminationStatus::Terminated(reason) => reason.text().to_string(),
+                TerminationStatus::NotTerminated => "Not terminated".to_string(),
+            },
+        }
+    }
+}
+
+#[wasm_bindgen]
+impl OptimizationResult {
+    /// Best parameter vector
+    #[wasm_bindgen(getter, js_name = bestParam)]
+    pub fn best_param(&self) -> Vec<f64> {
+        self.best_param.clone()
+    }
+
+    /// Best cost function value
+    #[wasm_bindgen(getter, js_name = bestCost)]
+    pub fn best_cost(&self) -> f64 {
+        self.best_cost
+    }
+
+    /// Number of iterations
+    #[wasm_bindgen(getter)]
+    pub fn iterations(&self) -> u32 {
+        self.iterations
+    }
+
+    /// Textual representation of why the solver terminated
+    #[wasm_bindgen(getter, js_name = terminationReason)]
+    pub fn termination_reason(&self) -> String {
+        self.termination_reason.clone()
+    }
+}
+
+/// Nelder-Mead method
+#[wasm_bindgen]
+#[derive(Default)]
+pub struct NelderMead {
+    config: NelderMeadConfig<Vec<f64>, f64>,
+}
+
+#[wasm_bindgen]
+impl NelderMead {
+    /// Constructs a Nelder-Mead solver with default options.
+    #[wasm_bindgen(constructor)]
+    pub fn new() -> NelderMead {
+        NelderMead::default()
+    }
+
+    /// Sample standard deviation tolerance
+    #[wasm_bindgen(setter, js_name = sdTolerance)]
+    pub fn set_sd_tolerance(&mut self, sd_tolerance: f64) {
+        self.config.sd_tolerance = Some(sd_tolerance);
+    }
+
+    /// Reflection coefficient
+    #[wasm_bindgen(setter)]
+    pub fn set_alpha(&mut self, alpha: f64) {
+        self.config.alpha = Some(alpha);
+    }
+
+    /// Expansion coefficient
+    #[wasm_bindgen(setter)]
+    pub fn set_gamma(&mut self, gamma: f64) {
+        self.config.gamma = Some(gamma);
+    }
+
+    /// Contraction coefficient
+    #[wasm_bindgen(setter)]
+    pub fn set_rho(&mut self, rho: f64) {
+        self.config.rho = Some(rho);
+    }
+
+    /// Shrink coefficient
+    #[wasm_bindgen(setter)]
+    pub fn set_sigma(&mut self, sigma: f64) {
+        self.config.sigma = Some(sigma);
+    }
+
+    /// Minimizes `cost` starting from the initial simplex, given as an array of `n + 1` vertices
+    /// (arrays or `Float64Array`s) for an `n`-dimensional problem.
+    pub fn minimize(
+        &self,
+        cost: Function,
+        simplex: Array,
+        max_iters: u32,
+    ) -> Result<OptimizationResult, JsError> {
+        let solver = NelderMeadConfig {
+            params: simplex
+                .iter()
+                .map(|vertex| Float64Array::new(&vertex).to_vec())
+                .collect(),
+            ..self.config.clone()
+        }
+        .build()
+        .map_err(to_js_error)?;
+        let res = Executor::new(JsProblem { cost }, solver)
+            .configure(|state: IterState<Vec<f64>, (), (), (), (), f64>| {
+                state.max_iters(max_iters.into())
+            })
+            .run()
+            .map_err(to_js_error)?;
+        let state = res.state();
+        Ok(OptimizationResult::new(
+            state,
+            state.get_best_param().cloned(),
+        ))
+    }
+}
+
+/// Particle Swarm Optimization
+#[wasm_bindgen]
+pub struct ParticleSwarm {
+    config: ParticleSwarmConfig<Vec<f64>, f64>,
+}
+
+#[wasm_bindgen]
+impl ParticleSwarm {
+    /// Constructs a Particle Swarm Optimization solver which searches within the box defined by
+    /// `lower` and `upper` using `num_particles` particles.
+    #[wasm_bindgen(constructor)]
+    pub fn new(lower: &[f64], upper: &[f64], num_particles: usize) -> ParticleSwarm {
+        ParticleSwarm {
+            config: ParticleSwarmConfig {
+                bounds: (lower.to_vec(), upper.to_vec()),
+                num_particles,
+                ..Default::default()
+            },
+        }
+    }
+
+    /// Inertia factor
+    #[wasm_bindgen(setter, js_name = inertiaFactor)]
+    pub fn set_inertia_factor(&mut self, factor: f64) {
+        self.config.inertia_factor = Some(factor);
+    }
+
+    /// Cognitive acceleration factor
+    #[wasm_bindgen(setter, js_name = cognitiveFactor)]
+    pub fn set_cognitive_factor(&mut self, factor: f64) {
+        self.config.cognitive_factor = Some(factor);
+    }
+
+    /// Social acceleration factor
+    #[wasm_bindgen(setter, js_name = socialFactor)]
+    pub fn set_social_factor(&mut self, factor: f64) {
+        self.config.social_factor = Some(factor);
+    }
+
+    /// Seed of the random number generator. If not set, the generator is seeded from entropy.
+    #[wasm_bindgen(setter)]
+    pub fn set_seed(&mut self, seed: u32) {
+        self.config.seed = Some(seed.into());
+    }
+
+    /// Minimizes `cost` within the bounds.
+    pub fn minimize(&self, cost: Function, max_iters: u32) -> Result<OptimizationResult, JsError> {
+        if self.config.bounds.0.len() != self.config.bounds.1.len() {
+            return Err(JsError::new(
+                "Lower and upper bound must have the same length.",
+            ));
+        }
+        let solver = self.config.build().map_err(to_js_error)?;
+        let res = Executor::new(JsProblem { cost }, solver)
+            .configure(|state: PopulationState<Particle<Vec<f64>, f64>, f64>| {
+                state.max_iters(max_iters.into())
+            })
+            .run()
+            .map_err(to_js_error)?;
+        let state = res.state();
+        Ok(OptimizationResult::new(
+            state,
+            state.get_best_param().map(|p| p.position.clone()),
+        ))
+    }
+}
+
+/// Limited-memory BFGS method with More-Thuente line search.
+///
+/// The gradient is approximated via central differences of the cost function.
+#[wasm_bindgen]
+pub struct LBFGS {
+    config: LBFGSConfig<f64>,
+    linesearch: MoreThuenteLineSearchConfig<f64>,
+}
+
+#[wasm_bindgen]
+impl LBFGS {
+    /// Constructs an L-BFGS solver with history size `m`.
+    #[wasm_bindgen(constructor)]
+    pub fn new(m: usize) -> LBFGS {
+        LBFGS {
+            config: LBFGSConfig {
+                m,
+                ..Default::default()
+            },
+            linesearch: MoreThuenteLineSearchConfig::default(),
+        }
+    }
+
+    /// Tolerance for the stopping criterion based on the gradient norm
+    #[wasm_bindgen(setter, js_name = toleranceGrad)]
+    pub fn set_tolerance_grad(&mut self, tolerance: f64) {
+        self.config.tolerance_grad = Some(tolerance);
+    }
+
+    /// Tolerance for the stopping criterion based on the change of the cost
+    #[wasm_bindgen(setter, js_name = toleranceCost)]
+    pub fn set_tolerance_cost(&mut self, tolerance: f64) {
+        self.config.tolerance_cost = Some(tolerance);
+    }
+
+    /// Parameters `c1` and `c2` of the strong Wolfe conditions used in the line search
+    #[wasm_bindgen(js_name = setLinesearchC)]
+    pub fn set_linesearch_c(&mut self, c1: f64, c2: f64) {
+        self.linesearch.c = Some((c1, c2));
+    }
+
+    /// Minimizes `cost` starting from `x0`.
+    pub fn minimize(
+        &self,
+        cost: Function,
+        x0: &[f64],
+        max_iters: u32,
+    ) -> Result<OptimizationResult, JsError> {
+        let linesearch = self.linesearch.build().map_err(to_js_error)?;
+        let solver = self.config.build(linesearch).map_err(to_js_error)?;
+        let x0 = x0.to_vec();
+        let res = Executor::new(JsProblem { cost }, solver)
+            .configure(|state: IterState<Vec<f64>, Vec<f64>, (), (), (), f64>| {
+                state.param(x0).max_iters(max_iters.into())
+            })
+            .run()
+            .map_err(to_js_error)?;
+        let state = res.state();
+        Ok(OptimizationResult::new(
+            state,
+            state.get_best_param().cloned(),
+        ))
+    }
+}
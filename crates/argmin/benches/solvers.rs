@@ -0,0 +1,191 @@
+// Copyright 2018-2024 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+//! Benchmarks of complete solver runs.
+//!
+//! Each solver minimizes the extended Rosenbrock function of several dimensions from a fixed
+//! starting point with each backend. A run ends once the cost function value falls below
+//! `TARGET_COST`, the stopping criteria of the solver are met or after `MAX_ITERS` iterations,
+//! therefore the measured time is the time-to-tolerance. The number of iterations of a run is
+//! determined once before measuring (and printed) and passed to criterion as throughput, which
+//! additionally reports the iterations per second.
+//!
+//! The `Vec` backend is always benchmarked, `ndarray` and `nalgebra` require the `_ndarrayl` and
+//! `_nalgebral` features, respectively:
+//!
+//! ```bash
+//! cargo bench -p argmin --bench solvers
+//! cargo bench -p argmin --bench solvers --features _ndarrayl,_nalgebral
+//! ```
+
+use argmin::core::{CostFunction, Error, Executor, Gradient, Solver, State, TerminationStatus};
+use argmin::solver::conjugategradient::{beta::PolakRibiere, NonlinearConjugateGradient};
+use argmin::solver::gradientdescent::SteepestDescent;
+use argmin::solver::linesearch::MoreThuenteLineSearch;
+use argmin::solver::neldermead::NelderMead;
+use argmin::solver::quasinewton::LBFGS;
+use argmin_testfunctions::{rosenbrock, rosenbrock_derivative};
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+
+/// Dimensions of the extended Rosenbrock function
+const DIMS: &[usize] = &[2, 50];
+
+/// Cost function value at which a run is considered converged (the minimum is 0)
+const TARGET_COST: f64 = 1e-10;
+
+/// Maximum number of iterations of a run
+const MAX_ITERS: u64 = 1000;
+
+/// Starting point `(-1.2, 1.0, -1.2, 1.0, ...)`
+fn init_param(n: usize) -> Vec<f64> {
+    (0..n)
+        .map(|i| if i % 2 == 0 { -1.2 } else { 1.0 })
+        .collect()
+}
+
+/// Initial simplex of Nelder-Mead: the starting point and the starting point moved along each axis
+fn init_simplex(n: usize) -> Vec<Vec<f64>> {
+    let x0 = init_param(n);
+    std::iter::once(x0.clone())
+        .chain((0..n).map(|i| {
+            let mut x = x0.clone();
+            x[i] += 0.1;
+            x
+        }))
+        .collect()
+}
+
+/// Runs `executor` and returns the number of iterations and the termination status
+fn run<O, S, I>(executor: Executor<O, S, I>) -> (u64, TerminationStatus)
+where
+    S: Solver<O, I>,
+    I: State,
+{
+    let res = executor.run().unwrap();
+    (
+        res.state.get_iter(),
+        res.state.get_termination_status().clone(),
+    )
+}
+
+/// Benchmarks the runs of `solver` with `backend` on the problem of dimension `n`
+fn bench_run<R>(c: &mut Criterion, solver: &str, backend: &str, n: usize, run: R)
+where
+    R: Fn() -> (u64, TerminationStatus),
+{
+    let (iters, status) = run();
+    println!("{solver}/{backend}/{n}: {iters} iterations, {status}");
+    let mut g = c.benchmark_group(solver);
+    g.throughput(Throughput::Elements(iters));
+    g.bench_function(BenchmarkId::new(backend, n), |b| b.iter(&run));
+    g.finish();
+}
+
+macro_rules! make_bench {
+    ($name:ident, $backend:expr, $p:ty, $from_vec:expr, $as_slice:expr) => {
+        pub fn $name(c: &mut Criterion) {
+            struct Rosenbrock {}
+
+            impl CostFunction for Rosenbrock {
+                type Param = $p;
+                type Output = f64;
+
+                fn cost(&self, p: &Self::Param) -> Result<Self::Output, Error> {
+                    Ok(rosenbrock($as_slice(p)))
+                }
+            }
+
+            impl Gradient for Rosenbrock {
+                type Param = $p;
+                type Gradient = $p;
+
+                fn gradient(&self, p: &Self::Param) -> Result<Self::Gradient, Error> {
+                    Ok($from_vec(rosenbrock_derivative($as_slice(p))))
+                }
+            }
+
+            for &n in DIMS {
+                bench_run(c, "steepest_descent", $backend, n, || {
+                    let solver = SteepestDescent::new(MoreThuenteLineSearch::new());
+                    run(Executor::new(Rosenbrock {}, solver).configure(|state| {
+                        state
+                            .param($from_vec(init_param(n)))
+                            .target_cost(TARGET_COST)
+                            .max_iters(MAX_ITERS)
+                    }))
+                });
+
+                bench_run(c, "lbfgs", $backend, n, || {
+                    let solver = LBFGS::new(MoreThuenteLineSearch::new(), 7);
+                    run(Executor::new(Rosenbrock {}, solver).configure(|state| {
+                        state
+                            .param($from_vec(init_param(n)))
+                            .target_cost(TARGET_COST)
+                            .max_iters(MAX_ITERS)
+                    }))
+                });
+
+                bench_run(c, "nonlinear_cg", $backend, n, || {
+                    let solver = NonlinearConjugateGradient::new(
+                        MoreThuenteLineSearch::new(),
+                        PolakRibiere::new(),
+                    )
+                    .restart_iters(10)
+                    .restart_orthogonality(0.1);
+                    run(Executor::new(Rosenbrock {}, solver).configure(|state| {
+                        state
+                            .param($from_vec(init_param(n)))
+                            .target_cost(TARGET_COST)
+                            .max_iters(MAX_ITERS)
+                    }))
+                });
+
+                bench_run(c, "neldermead", $backend, n, || {
+                    let simplex = init_simplex(n).into_iter().map($from_vec).collect();
+                    let solver = NelderMead::new(simplex).with_sd_tolerance(1e-8).unwrap();
+                    run(Executor::new(Rosenbrock {}, solver)
+                        .configure(|state| state.target_cost(TARGET_COST).max_iters(MAX_ITERS)))
+                });
+            }
+        }
+    };
+}
+
+make_bench!(bench_vec, "vec", Vec<f64>, Vec::<f64>::from, Vec::as_slice);
+
+/// Elements of a contiguous `ndarray` vector
+#[cfg(feature = "_ndarrayl")]
+fn ndarray_as_slice(p: &ndarray::Array1<f64>) -> &[f64] {
+    p.as_slice().unwrap()
+}
+
+#[cfg(feature = "_ndarrayl")]
+make_bench!(
+    bench_ndarray,
+    "ndarray",
+    ndarray::Array1<f64>,
+    ndarray::Array1::from,
+    ndarray_as_slice
+);
+
+#[cfg(feature = "_nalgebral")]
+make_bench!(
+    bench_nalgebra,
+    "nalgebra",
+    nalgebra::DVector<f64>,
+    nalgebra::DVector::from_vec,
+    nalgebra::DVector::<f64>::as_slice
+);
+
+#[cfg(not(feature = "_ndarrayl"))]
+fn bench_ndarray(_c: &mut Criterion) {}
+
+#[cfg(not(feature = "_nalgebral"))]
+fn bench_nalgebra(_c: &mut Criterion) {}
+
+criterion_group!(benches, bench_vec, bench_ndarray, bench_nalgebra);
+criterion_main!(benches);
@@ -0,0 +1,255 @@
+// Copyright 2018-2024 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use crate::core::ArgminFloat;
+#[cfg(feature = "serde1")]
+use serde::{Deserialize, Serialize};
+
+/// Archive of the best distinct parameter vectors found during an optimization run
+///
+/// Holds at most [`capacity`](`Archive::capacity`) pairs of parameter vectors and their cost
+/// function values, sorted by cost in ascending order. Parameter vectors which are already in the
+/// archive are not added again. This is useful for multimodal problems, where several distinct
+/// good solutions are of interest rather than only the best one.
+///
+/// The archive is maintained by the [`Executor`](`crate::core::Executor`) if enabled via
+/// [`Executor::archive`](`crate::core::Executor::archive`) and can be accessed via
+/// [`OptimizationResult::archive`](`crate::core::OptimizationResult::archive`).
+///
+/// # Example
+///
+/// ```
+/// # use argmin::core::Archive;
+/// let mut archive: Archive<Vec<f64>, f64> = Archive::new(2);
+///
+/// archive.insert(vec![1.0], 3.0);
+/// archive.insert(vec![2.0], 1.0);
+/// archive.insert(vec![3.0], 2.0);
+/// // Already in the archive
+/// archive.insert(vec![2.0], 1.0);
+///
+/// assert_eq!(archive.entries(), &[(vec![2.0], 1.0), (vec![3.0], 2.0)]);
+/// ```
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+pub struct Archive<P, F> {
+    /// Maximum number of entries
+    capacity: usize,
+    /// Parameter vectors and their cost function values, sorted by cost
+    entries: Vec<(P, F)>,
+}
+
+impl<P, F> Archive<P, F>
+where
+    F: ArgminFloat,
+{
+    /// Construct a new, empty archive which holds at most `capacity` entries
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::Archive;
+    /// let archive: Archive<Vec<f64>, f64> = Archive::new(10);
+    /// # assert_eq!(archive.capacity(), 10);
+    /// # assert!(archive.is_empty());
+    /// ```
+    pub fn new(capacity: usize) -> Self {
+        Archive {
+            capacity,
+            entries: Vec::with_capacity(capacity),
+        }
+    }
+
+    /// Returns the maximum number of entries
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::Archive;
+    /// # let archive: Archive<Vec<f64>, f64> = Archive::new(10);
+    /// let capacity = archive.capacity();
+    /// # assert_eq!(capacity, 10);
+    /// ```
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Returns the number of entries
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::Archive;
+    /// # let mut archive: Archive<Vec<f64>, f64> = Archive::new(10);
+    /// # archive.insert(vec![1.0], 1.0);
+    /// let len = archive.len();
+    /// # assert_eq!(len, 1);
+    /// ```
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Returns `true` if the archive holds no entries
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::Archive;
+    /// # let archive: Archive<Vec<f64>, f64> = Archive::new(10);
+    /// let is_empty = archive.is_empty();
+    /// # assert!(is_empty);
+    /// ```
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Returns the entries as pairs of parameter vectors and cost function values, sorted by
+    /// cost function value in ascending order
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::Archive;
+    /// # let mut archive: Archive<Vec<f64>, f64> = Archive::new(10);
+    /// # archive.insert(vec![1.0], 2.0);
+    /// # archive.insert(vec![2.0], 1.0);
+    /// for (param, cost) in archive.entries() {
+    ///     println!("{param:?}: {cost}");
+    /// }
+    /// # assert_eq!(archive.entries()[0], (vec![2.0], 1.0));
+    /// ```
+    pub fn entries(&self) -> &[(P, F)] {
+        &self.entries
+    }
+
+    /// Returns the best entry (if any)
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::Archive;
+    /// # let mut archive: Archive<Vec<f64>, f64> = Archive::new(10);
+    /// # archive.insert(vec![1.0], 2.0);
+    /// # archive.insert(vec![2.0], 1.0);
+    /// let (best_param, best_cost) = archive.best().unwrap();
+    /// # assert_eq!(best_param, &vec![2.0]);
+    /// ```
+    pub fn best(&self) -> Option<&(P, F)> {
+        self.entries.first()
+    }
+
+    /// Moves the entries out of the archive
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::Archive;
+    /// # let mut archive: Archive<Vec<f64>, f64> = Archive::new(10);
+    /// # archive.insert(vec![1.0], 2.0);
+    /// let entries: Vec<(Vec<f64>, f64)> = archive.into_entries();
+    /// # assert_eq!(entries.len(), 1);
+    /// ```
+    pub fn into_entries(self) -> Vec<(P, F)> {
+        self.entries
+    }
+
+    /// Returns `true` if a parameter vector `param` with cost function value `cost` would be
+    /// added by [`insert`](`Archive::insert`)
+    ///
+    /// This is the case if `cost` is not NaN, `param` is not yet in the archive and either the
+    /// archive is not full or `cost` is lower than the cost of the worst entry.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::Archive;
+    /// let mut archive: Archive<Vec<f64>, f64> = Archive::new(1);
+    /// archive.insert(vec![1.0], 2.0);
+    ///
+    /// assert!(archive.accepts(&vec![2.0], 1.0));
+    /// assert!(!archive.accepts(&vec![2.0], 3.0));
+    /// assert!(!archive.accepts(&vec![1.0], 2.0));
+    /// ```
+    pub fn accepts(&self, param: &P, cost: F) -> bool
+    where
+        P: PartialEq,
+    {
+        if cost.is_nan() || self.capacity == 0 {
+            return false;
+        }
+        if self.entries.len() == self.capacity
+            && self.entries.last().is_some_and(|(_, worst)| cost >= *worst)
+        {
+            return false;
+        }
+        !self.entries.iter().any(|(p, _)| p == param)
+    }
+
+    /// Adds a parameter vector `param` with cost function value `cost` to the archive if it is
+    /// accepted (see [`accepts`](`Archive::accepts`)). If the archive is full, the worst entry is
+    /// removed. Returns `true` if the entry was added.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::Archive;
+    /// let mut archive: Archive<Vec<f64>, f64> = Archive::new(10);
+    /// assert!(archive.insert(vec![1.0], 2.0));
+    /// assert!(!archive.insert(vec![1.0], 2.0));
+    /// ```
+    pub fn insert(&mut self, param: P, cost: F) -> bool
+    where
+        P: PartialEq,
+    {
+        if !self.accepts(&param, cost) {
+            return false;
+        }
+        let idx = self.entries.partition_point(|(_, c)| *c <= cost);
+        self.entries.insert(idx, (param, cost));
+        self.entries.truncate(self.capacity);
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_insert() {
+        let mut archive: Archive<Vec<f64>, f64> = Archive::new(3);
+        assert!(archive.insert(vec![5.0], 5.0));
+        assert!(archive.insert(vec![1.0], 1.0));
+        assert!(archive.insert(vec![3.0], 3.0));
+        assert!(!archive.insert(vec![3.0], 3.0));
+        assert!(!archive.insert(vec![6.0], 6.0));
+        assert!(!archive.insert(vec![0.0], f64::NAN));
+        assert!(archive.insert(vec![2.0], 2.0));
+        assert_eq!(archive.len(), 3);
+        assert_eq!(
+            archive.into_entries(),
+            vec![(vec![1.0], 1.0), (vec![2.0], 2.0), (vec![3.0], 3.0)]
+        );
+    }
+
+    #[test]
+    fn test_insert_equal_cost() {
+        let mut archive: Archive<Vec<f64>, f64> = Archive::new(2);
+        assert!(archive.insert(vec![1.0], 1.0));
+        assert!(archive.insert(vec![2.0], 1.0));
+        assert!(!archive.insert(vec![3.0], 1.0));
+        assert_eq!(archive.entries(), &[(vec![1.0], 1.0), (vec![2.0], 1.0)]);
+    }
+
+    #[test]
+    fn test_zero_capacity() {
+        let mut archive: Archive<Vec<f64>, f64> = Archive::new(0);
+        assert!(!archive.insert(vec![1.0], 1.0));
+        assert!(archive.is_empty());
+        assert!(archive.best().is_none());
+    }
+}
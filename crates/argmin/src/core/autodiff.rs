@@ -0,0 +1,252 @@
+// Copyright 2018-2024 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+//! # Automatic differentiation
+//!
+//! Cost functions which are written generically over the scalar type can be evaluated with dual
+//! numbers (provided by the `num-dual` crate), which yields exact gradients and Hessians without
+//! hand-coding them and without the cost and inaccuracy of finite differences.
+
+use crate::core::{ArgminFloat, CostFunction, Error, Gradient, Hessian};
+use nalgebra::{DVector, Dyn};
+use num_dual::{try_gradient, try_hessian, Dual2Vec, DualNum, DualNumFloat, DualVec};
+
+/// Cost function which is generic over the scalar type
+///
+/// The cost function is evaluated with `D = Self::Float` for the cost function value and with dual
+/// numbers for the derivatives. Wrapped in [`AutoDiff`], it implements [`CostFunction`],
+/// [`Gradient`] and [`Hessian`].
+///
+/// # Example
+///
+/// ```
+/// use argmin::core::{DualCostFunction, Error};
+/// use num_dual::DualNum;
+///
+/// struct Rosenbrock {
+///     a: f64,
+///     b: f64,
+/// }
+///
+/// impl DualCostFunction for Rosenbrock {
+///     type Float = f64;
+///
+///     fn cost_dual<D: DualNum<f64>>(&self, p: &[D]) -> Result<D, Error> {
+///         let x = p[0].clone();
+///         let y = p[1].clone();
+///         Ok((-x.clone() + self.a).powi(2) + (y - x.powi(2)).powi(2) * self.b)
+///     }
+/// }
+/// ```
+pub trait DualCostFunction {
+    /// Floating point precision of the parameter vector and the cost function value
+    type Float: DualNumFloat;
+
+    /// Compute the cost function value for a parameter vector with scalar type `D`
+    fn cost_dual<D: DualNum<Self::Float>>(&self, param: &[D]) -> Result<D, Error>;
+}
+
+/// Adapter computing exact derivatives of a [`DualCostFunction`] via automatic differentiation
+///
+/// Implements [`CostFunction`], [`Gradient`] and [`Hessian`] with `Vec<F>` as parameter vector,
+/// `Vec<F>` as gradient and `Vec<Vec<F>>` as Hessian. The gradient is computed in a single
+/// evaluation with dual numbers, the Hessian in a single evaluation with hyper-dual numbers.
+///
+/// # Example
+///
+/// ```
+/// # use argmin::core::{AutoDiff, CostFunction, DualCostFunction, Error, Gradient, Hessian};
+/// # use num_dual::DualNum;
+/// struct Sphere {}
+///
+/// impl DualCostFunction for Sphere {
+///     type Float = f64;
+///
+///     fn cost_dual<D: DualNum<f64>>(&self, p: &[D]) -> Result<D, Error> {
+///         Ok(p.iter().map(|x| x.powi(2)).fold(D::zero(), |acc, x| acc + x))
+///     }
+/// }
+///
+/// # fn main() -> Result<(), Error> {
+/// let problem = AutoDiff::new(Sphere {});
+///
+/// assert_eq!(problem.cost(&vec![1.0, 2.0])?, 5.0);
+/// assert_eq!(problem.gradient(&vec![1.0, 2.0])?, vec![2.0, 4.0]);
+/// assert_eq!(
+///     problem.hessian(&vec![1.0, 2.0])?,
+///     vec![vec![2.0, 0.0], vec![0.0, 2.0]]
+/// );
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct AutoDiff<O> {
+    /// Wrapped cost function
+    problem: O,
+}
+
+impl<O> AutoDiff<O> {
+    /// Construct a new instance of `AutoDiff` wrapping `problem`
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::{AutoDiff, DualCostFunction, Error};
+    /// # use num_dual::DualNum;
+    /// # struct Sphere {}
+    /// # impl DualCostFunction for Sphere {
+    /// #     type Float = f64;
+    /// #     fn cost_dual<D: DualNum<f64>>(&self, p: &[D]) -> Result<D, Error> {
+    /// #         Ok(p.iter().map(|x| x.powi(2)).fold(D::zero(), |acc, x| acc + x))
+    /// #     }
+    /// # }
+    /// let problem = AutoDiff::new(Sphere {});
+    /// ```
+    pub fn new(problem: O) -> Self {
+        AutoDiff { problem }
+    }
+
+    /// Returns a reference to the wrapped cost function
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::{AutoDiff, DualCostFunction, Error};
+    /// # use num_dual::DualNum;
+    /// # struct Sphere {}
+    /// # impl DualCostFunction for Sphere {
+    /// #     type Float = f64;
+    /// #     fn cost_dual<D: DualNum<f64>>(&self, p: &[D]) -> Result<D, Error> {
+    /// #         Ok(p.iter().map(|x| x.powi(2)).fold(D::zero(), |acc, x| acc + x))
+    /// #     }
+    /// # }
+    /// let problem = AutoDiff::new(Sphere {});
+    /// let sphere: &Sphere = problem.inner();
+    /// ```
+    pub fn inner(&self) -> &O {
+        &self.problem
+    }
+
+    /// Moves the wrapped cost function out of the adapter
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::{AutoDiff, DualCostFunction, Error};
+    /// # use num_dual::DualNum;
+    /// # struct Sphere {}
+    /// # impl DualCostFunction for Sphere {
+    /// #     type Float = f64;
+    /// #     fn cost_dual<D: DualNum<f64>>(&self, p: &[D]) -> Result<D, Error> {
+    /// #         Ok(p.iter().map(|x| x.powi(2)).fold(D::zero(), |acc, x| acc + x))
+    /// #     }
+    /// # }
+    /// let problem = AutoDiff::new(Sphere {});
+    /// let sphere: Sphere = problem.into_inner();
+    /// ```
+    pub fn into_inner(self) -> O {
+        self.problem
+    }
+}
+
+impl<O, F> CostFunction for AutoDiff<O>
+where
+    O: DualCostFunction<Float = F>,
+    F: ArgminFloat + DualNumFloat + DualNum<F>,
+{
+    type Param = Vec<F>;
+    type Output = F;
+
+    fn cost(&self, param: &Self::Param) -> Result<Self::Output, Error> {
+        self.problem.cost_dual::<F>(param)
+    }
+}
+
+impl<O, F> Gradient for AutoDiff<O>
+where
+    O: DualCostFunction<Float = F>,
+    F: ArgminFloat + DualNumFloat + DualNum<F>,
+{
+    type Param = Vec<F>;
+    type Gradient = Vec<F>;
+
+    fn gradient(&self, param: &Self::Param) -> Result<Self::Gradient, Error> {
+        let (_, grad) = try_gradient(
+            |x: DVector<DualVec<F, F, Dyn>>| self.problem.cost_dual(x.as_slice()),
+            DVector::from_column_slice(param),
+        )?;
+        Ok(grad.iter().copied().collect())
+    }
+}
+
+impl<O, F> Hessian for AutoDiff<O>
+where
+    O: DualCostFunction<Float = F>,
+    F: ArgminFloat + DualNumFloat + DualNum<F>,
+{
+    type Param = Vec<F>;
+    type Hessian = Vec<Vec<F>>;
+
+    fn hessian(&self, param: &Self::Param) -> Result<Self::Hessian, Error> {
+        let (_, _, hessian) = try_hessian(
+            |x: DVector<Dual2Vec<F, F, Dyn>>| self.problem.cost_dual(x.as_slice()),
+            DVector::from_column_slice(param),
+        )?;
+        Ok(hessian
+            .row_iter()
+            .map(|row| row.iter().copied().collect())
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_relative_eq;
+    use argmin_testfunctions::{rosenbrock, rosenbrock_derivative, rosenbrock_hessian};
+
+    #[derive(Clone, Debug, Default, Eq, PartialEq)]
+    struct Rosenbrock {}
+
+    impl DualCostFunction for Rosenbrock {
+        type Float = f64;
+
+        fn cost_dual<D: DualNum<f64>>(&self, p: &[D]) -> Result<D, Error> {
+            Ok(p.windows(2).fold(D::zero(), |acc, w| {
+                acc + (-w[0].clone() + 1.0).powi(2) + (w[1].clone() - w[0].powi(2)).powi(2) * 100.0
+            }))
+        }
+    }
+
+    test_trait_impl!(autodiff, AutoDiff<Rosenbrock>);
+
+    #[test]
+    fn test_derivatives() {
+        let problem = AutoDiff::new(Rosenbrock {});
+        let param = vec![-1.2, 1.0, 0.5];
+
+        assert_relative_eq!(problem.cost(&param).unwrap(), rosenbrock(&param));
+        for (a, b) in problem
+            .gradient(&param)
+            .unwrap()
+            .iter()
+            .zip(rosenbrock_derivative(&param).iter())
+        {
+            assert_relative_eq!(a, b, epsilon = 1e-12);
+        }
+        for (row_a, row_b) in problem
+            .hessian(&param)
+            .unwrap()
+            .iter()
+            .zip(rosenbrock_hessian(&param).iter())
+        {
+            for (a, b) in row_a.iter().zip(row_b.iter()) {
+                assert_relative_eq!(a, b, epsilon = 1e-12);
+            }
+        }
+    }
+}
@@ -0,0 +1,352 @@
+// Copyright 2018-2024 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use crate::core::{Error, Executor, OptimizationResult, SendAlias, Solver, State, SyncAlias};
+#[cfg(all(feature = "rayon", not(target_arch = "wasm32")))]
+use rayon::prelude::*;
+
+/// Runs one solver configuration on many jobs, for instance on a list of problems (e.g. one per
+/// dataset in parameter estimation) or on a list of initial parameter vectors.
+///
+/// Each job consists of a problem and an input `P` which is passed to the `configure` closure
+/// along with a fresh state. Typically `P` is the initial parameter vector; solvers which do not
+/// need one can use `()`. Every job runs on its own clone of the solver.
+///
+/// If the `rayon` feature is enabled, the jobs can be run in parallel via
+/// [`parallel`](`BatchRunner::parallel`). The results are returned in the order in which the jobs
+/// were added, regardless of whether they were run in parallel. A failing job does not affect the
+/// other jobs.
+///
+/// # Example
+///
+/// ```
+/// use argmin::core::{BatchRunner, Error, IterState, State};
+/// use argmin::solver::goldensectionsearch::GoldenSectionSearch;
+/// # use argmin::core::CostFunction;
+/// #
+/// # struct Dataset {
+/// #     mean: f64,
+/// # }
+/// #
+/// # impl CostFunction for Dataset {
+/// #     type Param = f64;
+/// #     type Output = f64;
+/// #     fn cost(&self, p: &Self::Param) -> Result<Self::Output, Error> {
+/// #         Ok((p - self.mean).powi(2))
+/// #     }
+/// # }
+///
+/// # fn main() -> Result<(), Error> {
+/// let solver = GoldenSectionSearch::new(-10.0, 10.0)?;
+/// let results = BatchRunner::new(
+///     solver,
+///     |state: IterState<f64, (), (), (), (), f64>, init_param| {
+///         state.param(init_param).max_iters(100)
+///     },
+/// )
+/// .add_problems(vec![Dataset { mean: 1.0 }, Dataset { mean: 2.0 }], 0.0)
+/// .run();
+///
+/// for result in results {
+///     println!("{}", result?.state.get_best_param().unwrap());
+/// }
+/// # Ok(())
+/// # }
+/// ```
+pub struct BatchRunner<O, S, P, C> {
+    /// Solver which is cloned for every job
+    solver: S,
+    /// Problems and the corresponding inputs of `configure`
+    jobs: Vec<(O, P)>,
+    /// Configures the state of a job
+    configure: C,
+    /// Whether to run the jobs in parallel
+    parallel: bool,
+}
+
+impl<O, S, P, C> BatchRunner<O, S, P, C> {
+    /// Construct a new instance of [`BatchRunner`]
+    ///
+    /// `configure` is called with the initial state and the input of each job and works like the
+    /// closure passed to [`Executor::configure`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::{BatchRunner, IterState};
+    /// # use argmin::core::test_utils::{TestProblem, TestSolver};
+    /// let runner: BatchRunner<TestProblem, _, Vec<f64>, _> = BatchRunner::new(
+    ///     TestSolver::new(),
+    ///     |state: IterState<Vec<f64>, (), (), (), (), f64>, param| state.param(param),
+    /// );
+    /// ```
+    pub fn new(solver: S, configure: C) -> Self {
+        BatchRunner {
+            solver,
+            jobs: vec![],
+            configure,
+            parallel: false,
+        }
+    }
+
+    /// Adds a job consisting of a problem and the input of `configure`
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::{BatchRunner, IterState};
+    /// # use argmin::core::test_utils::{TestProblem, TestSolver};
+    /// let runner = BatchRunner::new(
+    ///     TestSolver::new(),
+    ///     |state: IterState<Vec<f64>, (), (), (), (), f64>, param| state.param(param),
+    /// )
+    /// .add(TestProblem::new(), vec![1.0, 2.0]);
+    /// ```
+    #[must_use]
+    pub fn add(mut self, problem: O, input: P) -> Self {
+        self.jobs.push((problem, input));
+        self
+    }
+
+    /// Adds one job per problem, each with the same input of `configure`
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::{BatchRunner, IterState};
+    /// # use argmin::core::test_utils::{TestProblem, TestSolver};
+    /// let runner = BatchRunner::new(
+    ///     TestSolver::new(),
+    ///     |state: IterState<Vec<f64>, (), (), (), (), f64>, param| state.param(param),
+    /// )
+    /// .add_problems(vec![TestProblem::new(), TestProblem::new()], vec![1.0, 2.0]);
+    /// ```
+    #[must_use]
+    pub fn add_problems<T: IntoIterator<Item = O>>(mut self, problems: T, input: P) -> Self
+    where
+        P: Clone,
+    {
+        self.jobs
+            .extend(problems.into_iter().map(|problem| (problem, input.clone())));
+        self
+    }
+
+    /// Adds one job per input of `configure` (e.g. initial parameter vector), each with a clone
+    /// of `problem`
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::{BatchRunner, IterState};
+    /// # use argmin::core::test_utils::{TestProblem, TestSolver};
+    /// let runner = BatchRunner::new(
+    ///     TestSolver::new(),
+    ///     |state: IterState<Vec<f64>, (), (), (), (), f64>, param| state.param(param),
+    /// )
+    /// .add_inputs(TestProblem::new(), vec![vec![1.0, 2.0], vec![-1.0, 0.5]]);
+    /// ```
+    #[must_use]
+    pub fn add_inputs<T: IntoIterator<Item = P>>(mut self, problem: O, inputs: T) -> Self
+    where
+        O: Clone,
+    {
+        self.jobs
+            .extend(inputs.into_iter().map(|input| (problem.clone(), input)));
+        self
+    }
+
+    /// Run the jobs in parallel (default: `false`)
+    ///
+    /// Requires the `rayon` feature, without it the jobs are always run sequentially.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::{BatchRunner, IterState};
+    /// # use argmin::core::test_utils::{TestProblem, TestSolver};
+    /// let runner: BatchRunner<TestProblem, _, Vec<f64>, _> = BatchRunner::new(
+    ///     TestSolver::new(),
+    ///     |state: IterState<Vec<f64>, (), (), (), (), f64>, param| state.param(param),
+    /// )
+    /// .parallel(true);
+    /// ```
+    #[must_use]
+    pub fn parallel(mut self, parallel: bool) -> Self {
+        self.parallel = parallel;
+        self
+    }
+
+    /// Returns the number of jobs
+    pub fn len(&self) -> usize {
+        self.jobs.len()
+    }
+
+    /// Returns `true` if no jobs were added
+    pub fn is_empty(&self) -> bool {
+        self.jobs.is_empty()
+    }
+
+    /// Runs all jobs and returns their results in the order in which the jobs were added.
+    ///
+    /// Errors are reported per job. The index of the job is attached to the error as context and
+    /// stored as `batch_index` in the metadata of the run (see [`Executor::run_metadata`]).
+    pub fn run<I>(self) -> Vec<Result<OptimizationResult<O, S, I>, Error>>
+    where
+        O: SendAlias,
+        S: Solver<O, I> + Clone + SendAlias + SyncAlias,
+        P: SendAlias,
+        I: State + SendAlias,
+        C: Fn(I, P) -> I + SyncAlias,
+    {
+        let BatchRunner {
+            solver,
+            jobs,
+            configure,
+            parallel,
+        } = self;
+
+        let run_job = |(idx, (problem, input)): (usize, (O, P))| {
+            Executor::new(problem, solver.clone())
+                .configure(|state| configure(state, input))
+                .run_metadata("batch_index", idx.to_string())
+                .run()
+                .map_err(|e| e.context(format!("BatchRunner: job {idx} failed")))
+        };
+
+        #[cfg(all(feature = "rayon", not(target_arch = "wasm32")))]
+        if parallel {
+            return jobs.into_par_iter().enumerate().map(run_job).collect();
+        }
+        #[cfg(any(not(feature = "rayon"), target_arch = "wasm32"))]
+        let _ = parallel;
+
+        jobs.into_iter().enumerate().map(run_job).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::{ArgminError, CostFunction, IterState};
+    use crate::solver::goldensectionsearch::GoldenSectionSearch;
+    use approx::assert_relative_eq;
+
+    /// Squared distance to `mean`, which fails for negative parameters if `fragile`
+    #[derive(Clone)]
+    struct Dataset {
+        mean: f64,
+        fragile: bool,
+    }
+
+    impl Dataset {
+        fn new(mean: f64) -> Self {
+            Dataset {
+                mean,
+                fragile: false,
+            }
+        }
+    }
+
+    impl CostFunction for Dataset {
+        type Param = f64;
+        type Output = f64;
+
+        fn cost(&self, p: &Self::Param) -> Result<Self::Output, Error> {
+            if self.fragile && *p < 0.0 {
+                return Err(argmin_error!(InvalidParameter, "negative"));
+            }
+            Ok((p - self.mean).powi(2))
+        }
+    }
+
+    fn configure(
+        state: IterState<f64, (), (), (), (), f64>,
+        param: f64,
+    ) -> IterState<f64, (), (), (), (), f64> {
+        state.param(param).max_iters(100)
+    }
+
+    #[test]
+    fn test_problems() {
+        let solver = GoldenSectionSearch::new(-10.0, 10.0)
+            .unwrap()
+            .with_tolerance(1e-10, 1e-10)
+            .unwrap();
+        let runner = BatchRunner::new(solver, configure).add_problems(
+            vec![Dataset::new(1.0), Dataset::new(-2.0), Dataset::new(5.0)],
+            0.0,
+        );
+        assert_eq!(runner.len(), 3);
+
+        let results = runner.run();
+        assert_eq!(results.len(), 3);
+        for (res, mean) in results.into_iter().zip([1.0, -2.0, 5.0]) {
+            let res = res.unwrap();
+            assert_relative_eq!(*res.state.get_best_param().unwrap(), mean, epsilon = 1e-3);
+        }
+    }
+
+    #[test]
+    fn test_inputs() {
+        let solver = GoldenSectionSearch::new(-10.0, 10.0)
+            .unwrap()
+            .with_tolerance(1e-10, 1e-10)
+            .unwrap();
+        let results = BatchRunner::new(solver, configure)
+            .add_inputs(Dataset::new(1.0), vec![-5.0, 0.0, 8.0])
+            .run();
+        assert_eq!(results.len(), 3);
+        for res in results {
+            let res = res.unwrap();
+            assert_relative_eq!(*res.state.get_best_param().unwrap(), 1.0, epsilon = 1e-3);
+            assert!(res.state.get_iter() <= 100);
+        }
+    }
+
+    #[test]
+    fn test_failing_job() {
+        let solver = GoldenSectionSearch::new(-10.0, 10.0)
+            .unwrap()
+            .with_tolerance(1e-10, 1e-10)
+            .unwrap();
+        let fragile = Dataset {
+            mean: 1.0,
+            fragile: true,
+        };
+        let results = BatchRunner::new(solver, configure)
+            .add(Dataset::new(1.0), 0.0)
+            .add(fragile, -1.0)
+            .add(Dataset::new(2.0), 0.0)
+            .run();
+        assert!(results[0].is_ok());
+        assert!(results[2].is_ok());
+        let err = results.into_iter().nth(1).unwrap().err().unwrap();
+        assert_eq!(err.to_string(), "BatchRunner: job 1 failed");
+        assert_eq!(
+            err.downcast_ref::<ArgminError>().unwrap().to_string(),
+            "Invalid parameter: \"negative\""
+        );
+    }
+
+    #[cfg(all(feature = "rayon", not(target_arch = "wasm32")))]
+    #[test]
+    fn test_parallel() {
+        let solver = GoldenSectionSearch::new(-10.0, 10.0)
+            .unwrap()
+            .with_tolerance(1e-10, 1e-10)
+            .unwrap();
+        let means: Vec<f64> = (0..16).map(|i| i as f64 / 2.0 - 4.0).collect();
+        let results = BatchRunner::new(solver, configure)
+            .add_problems(means.iter().map(|m| Dataset::new(*m)), 0.0)
+            .parallel(true)
+            .run();
+        for (res, mean) in results.into_iter().zip(means) {
+            let res = res.unwrap();
+            assert_relative_eq!(*res.state.get_best_param().unwrap(), mean, epsilon = 1e-3);
+        }
+    }
+}
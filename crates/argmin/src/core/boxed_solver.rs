@@ -0,0 +1,216 @@
+// Copyright 2018-2024 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use crate::core::{Error, Problem, Solver, State, TerminationStatus, KV};
+use std::fmt;
+
+/// Type-erased solver.
+///
+/// Wraps any solver which implements [`Solver<O, I>`](`Solver`) for a given problem type `O` and
+/// state type `I`. Since the concrete type of the solver is erased, solvers of different types
+/// (but operating on the same problem and state types) can be stored in the same collection and
+/// selected at runtime, for instance based on a configuration file or in plugin systems.
+///
+/// `BoxedSolver` itself implements [`Solver`] and can therefore be used with the
+/// [`Executor`](`crate::core::Executor`) like any other solver. Note that checkpointing is not
+/// available for boxed solvers, because they cannot be (de)serialized.
+///
+/// # Example
+///
+/// ```
+/// use argmin::core::{BoxedSolver, Error, Executor, IterState, Solver};
+/// use argmin::solver::neldermead::NelderMead;
+/// use argmin::solver::simulatedannealing::SimulatedAnnealing;
+/// # use argmin::core::test_utils::TestProblem;
+///
+/// type State = IterState<Vec<f64>, (), (), (), (), f64>;
+///
+/// # fn main() -> Result<(), Error> {
+/// let solvers: Vec<BoxedSolver<TestProblem, State>> = vec![
+///     BoxedSolver::new(NelderMead::new(vec![vec![1.0, 1.0], vec![2.0, 1.0], vec![1.0, 2.0]])),
+///     BoxedSolver::new(SimulatedAnnealing::new(10.0)?),
+/// ];
+///
+/// // Select solver at runtime
+/// let solver = solvers.into_iter().find(|s| s.name() == "Nelder-Mead method").unwrap();
+///
+/// let res = Executor::new(TestProblem::new(), solver)
+///     .configure(|state| state.max_iters(10))
+///     .run()?;
+/// # assert_eq!(res.solver().name(), "Nelder-Mead method");
+/// # Ok(())
+/// # }
+/// ```
+pub struct BoxedSolver<O, I> {
+    solver: Box<dyn Solver<O, I>>,
+}
+
+impl<O, I> BoxedSolver<O, I>
+where
+    I: State,
+{
+    /// Construct a new instance of [`BoxedSolver`] from any solver.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::{BoxedSolver, IterState};
+    /// # use argmin::core::test_utils::TestProblem;
+    /// use argmin::solver::neldermead::NelderMead;
+    ///
+    /// let solver: BoxedSolver<TestProblem, IterState<Vec<f64>, (), (), (), (), f64>> =
+    ///     BoxedSolver::new(NelderMead::new(vec![vec![1.0], vec![2.0]]));
+    /// ```
+    pub fn new<S>(solver: S) -> Self
+    where
+        S: Solver<O, I> + 'static,
+    {
+        BoxedSolver {
+            solver: Box::new(solver),
+        }
+    }
+
+    /// Returns the underlying trait object.
+    pub fn into_inner(self) -> Box<dyn Solver<O, I>> {
+        self.solver
+    }
+}
+
+impl<O, I> From<Box<dyn Solver<O, I>>> for BoxedSolver<O, I>
+where
+    I: State,
+{
+    fn from(solver: Box<dyn Solver<O, I>>) -> Self {
+        BoxedSolver { solver }
+    }
+}
+
+impl<O, I> fmt::Debug for BoxedSolver<O, I>
+where
+    I: State,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("BoxedSolver")
+            .field(&self.solver.name())
+            .finish()
+    }
+}
+
+impl<O, I> Solver<O, I> for BoxedSolver<O, I>
+where
+    I: State,
+{
+    fn name(&self) -> &str {
+        self.solver.name()
+    }
+
+    fn check_state(&self, state: &I) -> Result<(), Error> {
+        self.solver.check_state(state)
+    }
+
+    fn init(&mut self, problem: &mut Problem<O>, state: I) -> Result<(I, Option<KV>), Error> {
+        self.solver.init(problem, state)
+    }
+
+    fn next_iter(&mut self, problem: &mut Problem<O>, state: I) -> Result<(I, Option<KV>), Error> {
+        self.solver.next_iter(problem, state)
+    }
+
+    fn terminate_internal(&mut self, state: &I) -> TerminationStatus {
+        self.solver.terminate_internal(state)
+    }
+
+    fn terminate(&mut self, state: &I) -> TerminationStatus {
+        self.solver.terminate(state)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::test_utils::TestProblem;
+    use crate::core::{CostFunction, Executor, Gradient, IterState, TerminationReason};
+    use crate::solver::gradientdescent::SteepestDescent;
+    use crate::solver::linesearch::condition::ArmijoCondition;
+    use crate::solver::linesearch::{BacktrackingLineSearch, MoreThuenteLineSearch};
+    use crate::solver::quasinewton::LBFGS;
+
+    type GradState = IterState<Vec<f64>, Vec<f64>, (), (), (), f64>;
+
+    struct Rosenbrock {}
+
+    impl CostFunction for Rosenbrock {
+        type Param = Vec<f64>;
+        type Output = f64;
+
+        fn cost(&self, p: &Self::Param) -> Result<Self::Output, Error> {
+            Ok(argmin_testfunctions::rosenbrock(p))
+        }
+    }
+
+    impl Gradient for Rosenbrock {
+        type Param = Vec<f64>;
+        type Gradient = Vec<f64>;
+
+        fn gradient(&self, p: &Self::Param) -> Result<Self::Gradient, Error> {
+            Ok(argmin_testfunctions::rosenbrock_derivative(p))
+        }
+    }
+
+    fn solvers<O>() -> Vec<BoxedSolver<O, GradState>>
+    where
+        O: CostFunction<Param = Vec<f64>, Output = f64>
+            + Gradient<Param = Vec<f64>, Gradient = Vec<f64>>,
+    {
+        let linesearch: MoreThuenteLineSearch<Vec<f64>, Vec<f64>, f64> =
+            MoreThuenteLineSearch::new();
+        vec![
+            BoxedSolver::new(SteepestDescent::new(linesearch.clone())),
+            BoxedSolver::new(LBFGS::new(linesearch, 5)),
+        ]
+    }
+
+    #[test]
+    fn test_name() {
+        let names: Vec<String> = solvers::<TestProblem>()
+            .iter()
+            .map(|s| s.name().to_string())
+            .collect();
+        assert_eq!(names, vec!["Steepest Descent", "L-BFGS"]);
+        assert_eq!(
+            format!("{:?}", solvers::<TestProblem>()[1]),
+            "BoxedSolver(\"L-BFGS\")".to_string()
+        );
+    }
+
+    #[test]
+    fn test_from_box() {
+        let linesearch: BacktrackingLineSearch<Vec<f64>, Vec<f64>, _, f64> =
+            BacktrackingLineSearch::new(ArmijoCondition::new(0.0001f64).unwrap());
+        let solver: Box<dyn Solver<TestProblem, GradState>> =
+            Box::new(SteepestDescent::new(linesearch));
+        let solver = BoxedSolver::from(solver);
+        assert_eq!(solver.name(), "Steepest Descent");
+        assert_eq!(solver.into_inner().name(), "Steepest Descent");
+    }
+
+    #[test]
+    fn test_run() {
+        for solver in solvers() {
+            let res = Executor::new(Rosenbrock {}, solver)
+                .configure(|state| state.param(vec![-1.2, 1.0]).max_iters(3))
+                .run()
+                .unwrap();
+            assert_eq!(
+                res.state.termination_status,
+                TerminationStatus::Terminated(TerminationReason::MaxItersReached)
+            );
+            assert_eq!(res.state.iter, 3);
+            assert!(res.state.best_cost < argmin_testfunctions::rosenbrock(&[-1.2, 1.0]));
+        }
+    }
+}
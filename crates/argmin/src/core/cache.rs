@@ -0,0 +1,375 @@
+// Copyright 2018-2024 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use crate::core::{CostFunction, Error};
+#[cfg(feature = "serde1")]
+use serde::{Deserialize, Serialize};
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashMap;
+use std::fmt::Debug;
+use std::hash::{Hash, Hasher};
+use std::sync::Mutex;
+
+/// Cost function values computed by a [`CachedProblem`]
+///
+/// Obtained via [`CachedProblem::cache`] and passed to another `CachedProblem` via
+/// [`CachedProblem::with_cache`]. With the `serde1` feature, the cache can be serialized with any
+/// serde-compatible format (such as JSON or bincode), which allows one to reuse evaluations
+/// across program runs.
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+pub struct EvaluationCache<P, F> {
+    /// Parameter vectors and the corresponding cost function values
+    entries: Vec<(P, F)>,
+}
+
+impl<P, F> EvaluationCache<P, F> {
+    /// Construct a new, empty `EvaluationCache`
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::EvaluationCache;
+    /// let cache: EvaluationCache<Vec<f64>, f64> = EvaluationCache::new();
+    /// # assert!(cache.is_empty());
+    /// ```
+    pub fn new() -> Self {
+        EvaluationCache { entries: vec![] }
+    }
+
+    /// Returns the number of cached evaluations
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Returns `true` if the cache is empty
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Returns the cached parameter vectors and cost function values
+    pub fn entries(&self) -> &[(P, F)] {
+        &self.entries
+    }
+}
+
+/// Cached evaluations together with an index from the hash of a parameter vector to the
+/// positions of the entries with this hash
+#[derive(Debug)]
+struct Storage<P, F> {
+    cache: EvaluationCache<P, F>,
+    index: HashMap<u64, Vec<usize>>,
+    hits: u64,
+}
+
+/// Hashes the `Debug` representation of `param`.
+///
+/// Floats are formatted with the shortest representation which round-trips, therefore
+/// parameter vectors which differ in any bit (except for the sign of zero) are mapped to different
+/// strings.
+fn debug_hash<P: Debug>(param: &P) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    format!("{param:?}").hash(&mut hasher);
+    hasher.finish()
+}
+
+/// Wraps a problem and caches its cost function values
+///
+/// Before evaluating the cost function of the wrapped problem, the cache is searched for a
+/// previous evaluation at an identical parameter vector. Parameter vectors are looked up via a
+/// hash and compared via `PartialEq`, hence only exact matches are reused. By default, the hash
+/// is computed from the `Debug` representation of the parameter vector; a cheaper hash function
+/// can be provided via [`with_param_hash`](`CachedProblem::with_param_hash`).
+///
+/// The cache can be extracted after a run via [`cache`](`CachedProblem::cache`) and used to
+/// initialize the cache of a later run via [`with_cache`](`CachedProblem::with_cache`). This
+/// avoids recomputing expensive evaluations, for instance when restarting a run with slightly
+/// different settings of the solver. With the `serde1` feature, the [`EvaluationCache`] can be
+/// stored on disk in between.
+///
+/// Only the cost function is cached. The size of the cache is not limited.
+///
+/// # Example
+///
+/// ```
+/// use argmin::core::{CachedProblem, Executor, State};
+/// use argmin::solver::neldermead::NelderMead;
+/// # use argmin::core::{CostFunction, Error};
+/// #
+/// # struct Paraboloid {}
+/// #
+/// # impl CostFunction for Paraboloid {
+/// #     type Param = Vec<f64>;
+/// #     type Output = f64;
+/// #     fn cost(&self, p: &Self::Param) -> Result<Self::Output, Error> {
+/// #         Ok(p[0].powi(2) + 10.0 * p[1].powi(2))
+/// #     }
+/// # }
+///
+/// # fn main() -> Result<(), Error> {
+/// let simplex = vec![vec![1.0, 1.0], vec![2.0, 1.0], vec![1.0, 2.0]];
+///
+/// let res = Executor::new(CachedProblem::new(Paraboloid {}), NelderMead::new(simplex.clone()))
+///     .configure(|state| state.max_iters(10))
+///     .run()?;
+/// let cache = res.problem().problem.as_ref().unwrap().cache();
+///
+/// // Rerun with more iterations: the first iterations are taken from the cache
+/// let problem = CachedProblem::new(Paraboloid {}).with_cache(cache);
+/// let res = Executor::new(problem, NelderMead::new(simplex))
+///     .configure(|state| state.max_iters(20))
+///     .run()?;
+/// # assert!(res.problem().problem.as_ref().unwrap().hits() > 0);
+/// # Ok(())
+/// # }
+/// ```
+pub struct CachedProblem<O: CostFunction> {
+    /// Wrapped problem
+    problem: O,
+    /// Computes the hash of a parameter vector
+    param_hash: fn(&O::Param) -> u64,
+    /// Cached evaluations
+    storage: Mutex<Storage<O::Param, O::Output>>,
+}
+
+impl<O> CachedProblem<O>
+where
+    O: CostFunction,
+    O::Param: Debug,
+{
+    /// Construct a new instance of `CachedProblem` with an empty cache
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::CachedProblem;
+    /// # use argmin::core::test_utils::TestProblem;
+    /// let problem = CachedProblem::new(TestProblem::new());
+    /// ```
+    pub fn new(problem: O) -> Self {
+        CachedProblem {
+            problem,
+            param_hash: debug_hash::<O::Param>,
+            storage: Mutex::new(Storage {
+                cache: EvaluationCache::new(),
+                index: HashMap::new(),
+                hits: 0,
+            }),
+        }
+    }
+}
+
+impl<O: CostFunction> CachedProblem<O> {
+    /// Use `hash` to compute the hash of a parameter vector
+    ///
+    /// Parameter vectors which compare equal must have the same hash.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::CachedProblem;
+    /// # use argmin::core::test_utils::TestProblem;
+    /// use std::hash::{DefaultHasher, Hash, Hasher};
+    ///
+    /// fn hash(param: &Vec<f64>) -> u64 {
+    ///     let mut hasher = DefaultHasher::new();
+    ///     // Map -0.0 to 0.0 because they compare equal
+    ///     param.iter().for_each(|x| (x + 0.0).to_bits().hash(&mut hasher));
+    ///     hasher.finish()
+    /// }
+    ///
+    /// let problem = CachedProblem::new(TestProblem::new()).with_param_hash(hash);
+    /// ```
+    #[must_use]
+    pub fn with_param_hash(mut self, hash: fn(&O::Param) -> u64) -> Self {
+        self.param_hash = hash;
+        let storage = self.storage.get_mut().unwrap();
+        storage.index.clear();
+        for (i, (param, _)) in storage.cache.entries.iter().enumerate() {
+            storage.index.entry(hash(param)).or_default().push(i);
+        }
+        self
+    }
+
+    /// Add the evaluations of `cache` to the cache
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::{CachedProblem, EvaluationCache};
+    /// # use argmin::core::test_utils::TestProblem;
+    /// # let cache = EvaluationCache::new();
+    /// let problem = CachedProblem::new(TestProblem::new()).with_cache(cache);
+    /// ```
+    #[must_use]
+    pub fn with_cache(mut self, cache: EvaluationCache<O::Param, O::Output>) -> Self
+    where
+        O::Param: PartialEq,
+    {
+        let storage = self.storage.get_mut().unwrap();
+        for (param, cost) in cache.entries {
+            storage.insert(self.param_hash, param, cost);
+        }
+        self
+    }
+
+    /// Returns a copy of the cache
+    pub fn cache(&self) -> EvaluationCache<O::Param, O::Output>
+    where
+        O::Param: Clone,
+        O::Output: Clone,
+    {
+        self.storage.lock().unwrap().cache.clone()
+    }
+
+    /// Returns the number of cost function evaluations which were taken from the cache
+    pub fn hits(&self) -> u64 {
+        self.storage.lock().unwrap().hits
+    }
+
+    /// Returns a reference to the wrapped problem
+    pub fn problem(&self) -> &O {
+        &self.problem
+    }
+}
+
+impl<P: PartialEq, F> Storage<P, F> {
+    /// Returns the position of `param` in the cache (if present)
+    fn position(&self, hash: u64, param: &P) -> Option<usize> {
+        self.index
+            .get(&hash)?
+            .iter()
+            .copied()
+            .find(|&i| self.cache.entries[i].0 == *param)
+    }
+
+    /// Adds an evaluation unless the parameter vector is already present
+    fn insert(&mut self, param_hash: fn(&P) -> u64, param: P, cost: F) {
+        let hash = param_hash(&param);
+        if self.position(hash, &param).is_none() {
+            self.index
+                .entry(hash)
+                .or_default()
+                .push(self.cache.entries.len());
+            self.cache.entries.push((param, cost));
+        }
+    }
+}
+
+impl<O> CostFunction for CachedProblem<O>
+where
+    O: CostFunction,
+    O::Param: Clone + PartialEq,
+    O::Output: Clone,
+{
+    type Param = O::Param;
+    type Output = O::Output;
+
+    fn cost(&self, param: &Self::Param) -> Result<Self::Output, Error> {
+        let hash = (self.param_hash)(param);
+        {
+            let mut storage = self.storage.lock().unwrap();
+            if let Some(i) = storage.position(hash, param) {
+                storage.hits += 1;
+                return Ok(storage.cache.entries[i].1.clone());
+            }
+        }
+        // The lock is released during the evaluation such that evaluations can run in parallel.
+        let cost = self.problem.cost(param)?;
+        self.storage
+            .lock()
+            .unwrap()
+            .insert(self.param_hash, param.clone(), cost.clone());
+        Ok(cost)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicU64, Ordering};
+
+    /// Problem which counts its evaluations
+    #[derive(Default)]
+    struct Counting {
+        evaluations: AtomicU64,
+    }
+
+    impl CostFunction for Counting {
+        type Param = Vec<f64>;
+        type Output = f64;
+
+        fn cost(&self, p: &Self::Param) -> Result<Self::Output, Error> {
+            self.evaluations.fetch_add(1, Ordering::SeqCst);
+            Ok(p.iter().sum())
+        }
+    }
+
+    send_sync_test!(cached_problem, CachedProblem<Counting>);
+
+    #[test]
+    fn test_cost() {
+        let problem = CachedProblem::new(Counting::default());
+        assert_eq!(
+            problem.cost(&vec![1.0, 2.0]).unwrap().to_ne_bytes(),
+            3.0f64.to_ne_bytes()
+        );
+        assert_eq!(
+            problem.cost(&vec![1.0, 2.0]).unwrap().to_ne_bytes(),
+            3.0f64.to_ne_bytes()
+        );
+        problem.cost(&vec![2.0, 1.0]).unwrap();
+        assert_eq!(problem.problem().evaluations.load(Ordering::SeqCst), 2);
+        assert_eq!(problem.hits(), 1);
+        assert_eq!(problem.cache().len(), 2);
+    }
+
+    #[test]
+    fn test_with_cache() {
+        let problem = CachedProblem::new(Counting::default());
+        problem.cost(&vec![1.0, 2.0]).unwrap();
+        problem.cost(&vec![3.0, 4.0]).unwrap();
+
+        // A hash function with collisions only affects performance
+        let problem = CachedProblem::new(Counting::default())
+            .with_param_hash(|_| 0)
+            .with_cache(problem.cache());
+        problem.cost(&vec![1.0, 2.0]).unwrap();
+        problem.cost(&vec![3.0, 4.0]).unwrap();
+        problem.cost(&vec![5.0, 6.0]).unwrap();
+        assert_eq!(problem.problem().evaluations.load(Ordering::SeqCst), 1);
+        assert_eq!(problem.hits(), 2);
+        assert_eq!(
+            problem.cache().entries(),
+            &[
+                (vec![1.0, 2.0], 3.0),
+                (vec![3.0, 4.0], 7.0),
+                (vec![5.0, 6.0], 11.0)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_with_param_hash_reindexes() {
+        let problem = CachedProblem::new(Counting::default());
+        problem.cost(&vec![1.0, 2.0]).unwrap();
+        let problem = problem.with_param_hash(|p| p.len() as u64);
+        problem.cost(&vec![1.0, 2.0]).unwrap();
+        assert_eq!(problem.hits(), 1);
+    }
+
+    #[cfg(feature = "factory")]
+    #[test]
+    fn test_serialization() {
+        let problem = CachedProblem::new(Counting::default());
+        problem.cost(&vec![1.0, 2.0]).unwrap();
+
+        let json = serde_json::to_string(&problem.cache()).unwrap();
+        let cache: EvaluationCache<Vec<f64>, f64> = serde_json::from_str(&json).unwrap();
+        assert_eq!(cache, problem.cache());
+    }
+}
@@ -102,7 +102,7 @@
 //! # }
 //! ```
 
-use crate::core::Error;
+use crate::core::{Error, RunInfo};
 use std::default::Default;
 use std::fmt::Display;
 
@@ -187,6 +187,25 @@ pub trait Checkpoint<S, I> {
     ///
     /// Returns enum `CheckpointingFrequency`.
     fn frequency(&self) -> CheckpointingFrequency;
+
+    /// Saves the information about the run
+    ///
+    /// Called by the [`Executor`](`crate::core::Executor`) once before the first iteration unless
+    /// the frequency is `CheckpointingFrequency::Never`. Storing the [`RunInfo`] next to the
+    /// checkpoint allows one to correlate checkpoints with logs of the same run. The default
+    /// implementation does nothing.
+    fn save_run(&self, _run: &RunInfo) -> Result<(), Error> {
+        Ok(())
+    }
+
+    /// Loads the information about the run saved via [`save_run`](`Checkpoint::save_run`)
+    ///
+    /// When resuming from a checkpoint, the [`Executor`](`crate::core::Executor`) takes over the
+    /// identifier of the loaded run, such that the resumed run can be correlated with the
+    /// interrupted one. The default implementation returns `Ok(None)`.
+    fn load_run(&self) -> Result<Option<RunInfo>, Error> {
+        Ok(None)
+    }
 }
 
 /// Defines at which intervals a checkpoint is saved.
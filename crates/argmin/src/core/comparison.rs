@@ -0,0 +1,457 @@
+// Copyright 2018-2024 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use crate::core::observers::{Observe, ObserverMode};
+use crate::core::{ArgminFloat, Error, Executor, IterState, Solver, State, TerminationStatus, KV};
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::{Arc, Mutex};
+use web_time::{Duration, Instant};
+
+/// Runs a solver and summarizes the run
+type Run<O, P, F> = Box<dyn FnOnce(O, P, u64) -> Result<ComparisonEntry<F>, Error>>;
+
+/// Runs several solvers on the same problem with the same initial parameter vector and the same
+/// budget of iterations and summarizes the runs.
+///
+/// Solvers operating on [`IterState`] are added via [`add`](`Comparison::add`). Solvers which use
+/// other states (or need further configuration) are added via
+/// [`add_with`](`Comparison::add_with`). The solvers may be of different types and use different
+/// states as long as the float types agree. Each solver runs on its own clone of the problem.
+///
+/// # Example
+///
+/// ```
+/// use argmin::core::{Comparison, Error};
+/// use argmin::solver::gradientdescent::SteepestDescent;
+/// use argmin::solver::linesearch::MoreThuenteLineSearch;
+/// use argmin::solver::neldermead::NelderMead;
+/// # use argmin::core::{CostFunction, Gradient};
+/// #
+/// # #[derive(Clone)]
+/// # struct Paraboloid {}
+/// #
+/// # impl CostFunction for Paraboloid {
+/// #     type Param = Vec<f64>;
+/// #     type Output = f64;
+/// #     fn cost(&self, p: &Self::Param) -> Result<Self::Output, Error> {
+/// #         Ok(p[0].powi(2) + 10.0 * p[1].powi(2))
+/// #     }
+/// # }
+/// #
+/// # impl Gradient for Paraboloid {
+/// #     type Param = Vec<f64>;
+/// #     type Gradient = Vec<f64>;
+/// #     fn gradient(&self, p: &Self::Param) -> Result<Self::Gradient, Error> {
+/// #         Ok(vec![2.0 * p[0], 20.0 * p[1]])
+/// #     }
+/// # }
+///
+/// # fn main() -> Result<(), Error> {
+/// let summary = Comparison::new(Paraboloid {}, vec![1.0, 1.0])
+///     .max_iters(50)
+///     .add(
+///         "steepest descent",
+///         SteepestDescent::new(MoreThuenteLineSearch::new()),
+///     )
+///     .add_with(
+///         "Nelder-Mead",
+///         NelderMead::new(vec![vec![1.0, 1.0], vec![1.5, 1.0], vec![1.0, 1.5]]),
+///         |state, _param, max_iters| state.max_iters(max_iters),
+///     )
+///     .run()?;
+///
+/// println!("{summary}");
+/// # assert_eq!(summary.entries.len(), 2);
+/// # assert_eq!(summary.best().unwrap().name, "steepest descent");
+/// # Ok(())
+/// # }
+/// ```
+pub struct Comparison<O, P, F> {
+    /// Problem
+    problem: O,
+    /// Initial parameter vector
+    init_param: P,
+    /// Maximum number of iterations of each run
+    max_iters: u64,
+    /// Names of the runs and the runs themselves
+    runs: Vec<(String, Run<O, P, F>)>,
+}
+
+impl<O, P, F> Comparison<O, P, F>
+where
+    O: Clone + 'static,
+    P: Clone + 'static,
+    F: ArgminFloat + 'static,
+{
+    /// Construct a new instance of [`Comparison`] from the problem and the initial parameter
+    /// vector.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::Comparison;
+    /// # use argmin::core::test_utils::TestProblem;
+    /// let comparison: Comparison<_, _, f64> = Comparison::new(TestProblem::new(), vec![1.0, 2.0]);
+    /// ```
+    pub fn new(problem: O, init_param: P) -> Self {
+        Comparison {
+            problem,
+            init_param,
+            max_iters: u64::MAX,
+            runs: vec![],
+        }
+    }
+
+    /// Set the maximum number of iterations of each run (default: `u64::MAX`).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::Comparison;
+    /// # use argmin::core::test_utils::TestProblem;
+    /// let comparison: Comparison<_, _, f64> =
+    ///     Comparison::new(TestProblem::new(), vec![1.0, 2.0]).max_iters(100);
+    /// ```
+    #[must_use]
+    pub fn max_iters(mut self, max_iters: u64) -> Self {
+        self.max_iters = max_iters;
+        self
+    }
+
+    /// Adds a solver operating on [`IterState`]. The state is initialized with the initial
+    /// parameter vector and the maximum number of iterations.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::Comparison;
+    /// # use argmin::core::test_utils::{TestProblem, TestSolver};
+    /// let comparison: Comparison<_, _, f64> = Comparison::new(TestProblem::new(), vec![1.0, 2.0])
+    ///     .add("test solver", TestSolver::new());
+    /// ```
+    #[must_use]
+    pub fn add<S, G, J, H, R>(self, name: &str, solver: S) -> Self
+    where
+        S: Solver<O, IterState<P, G, J, H, R, F>> + 'static,
+        IterState<P, G, J, H, R, F>: State<Param = P, Float = F> + 'static,
+    {
+        self.add_with(
+            name,
+            solver,
+            |state: IterState<P, G, J, H, R, F>, param, iters| state.param(param).max_iters(iters),
+        )
+    }
+
+    /// Adds a solver operating on any state. The state is initialized via `configure`, which is
+    /// given the state, the initial parameter vector and the maximum number of iterations.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::{Comparison, Error};
+    /// # use argmin::core::test_utils::TestProblem;
+    /// use argmin::solver::particleswarm::ParticleSwarm;
+    ///
+    /// # fn main() -> Result<(), Error> {
+    /// let comparison: Comparison<_, _, f64> = Comparison::new(TestProblem::new(), vec![1.0, 2.0])
+    ///     .add_with(
+    ///         "particle swarm",
+    ///         ParticleSwarm::new((vec![-4.0, -4.0], vec![4.0, 4.0]), 40),
+    ///         |state, _param, max_iters| state.max_iters(max_iters),
+    ///     );
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn add_with<S, I, C>(mut self, name: &str, solver: S, configure: C) -> Self
+    where
+        S: Solver<O, I> + 'static,
+        I: State<Float = F> + 'static,
+        C: FnOnce(I, P, u64) -> I + 'static,
+    {
+        let run_name = name.to_string();
+        let run: Run<O, P, F> = Box::new(move |problem, param, max_iters| {
+            let trajectory = Arc::new(Mutex::new(vec![]));
+            let recorder = TrajectoryRecorder {
+                trajectory: Arc::clone(&trajectory),
+            };
+            let start = Instant::now();
+            let mut res = Executor::new(problem, solver)
+                .configure(|state| configure(state, param, max_iters))
+                .add_observer(recorder, ObserverMode::Always)
+                .run()?;
+            let time = start.elapsed();
+            let trajectory = std::mem::take(&mut *trajectory.lock().unwrap());
+            Ok(ComparisonEntry {
+                name: run_name,
+                solver: res.solver.name().to_string(),
+                best_cost: res.state.get_best_cost(),
+                iterations: res.state.get_iter(),
+                termination_status: res.state.get_termination_status().clone(),
+                counts: std::mem::take(&mut res.problem.counts),
+                time,
+                trajectory,
+            })
+        });
+        self.runs.push((name.to_string(), run));
+        self
+    }
+
+    /// Runs all solvers in the order in which they were added and returns the summary.
+    ///
+    /// Fails as soon as one of the runs fails.
+    pub fn run(self) -> Result<ComparisonSummary<F>, Error> {
+        let mut entries = Vec::with_capacity(self.runs.len());
+        for (name, run) in self.runs {
+            let entry = run(
+                self.problem.clone(),
+                self.init_param.clone(),
+                self.max_iters,
+            )
+            .map_err(|e| e.context(format!("Comparison: run `{name}` failed")))?;
+            entries.push(entry);
+        }
+        Ok(ComparisonSummary { entries })
+    }
+}
+
+/// Cost function values after a given number of iterations of a run of a [`Comparison`]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct TrajectoryPoint<F> {
+    /// Number of completed iterations (0 after the initialization of the solver)
+    pub iter: u64,
+    /// Current cost function value
+    pub cost: F,
+    /// Best cost function value so far
+    pub best_cost: F,
+}
+
+/// Summary of a single run of a [`Comparison`]
+#[derive(Clone, Debug, PartialEq)]
+pub struct ComparisonEntry<F> {
+    /// Name given to the run
+    pub name: String,
+    /// Name of the solver
+    pub solver: String,
+    /// Best cost function value
+    pub best_cost: F,
+    /// Number of iterations
+    pub iterations: u64,
+    /// Termination status
+    pub termination_status: TerminationStatus,
+    /// Function evaluation counts
+    pub counts: HashMap<&'static str, u64>,
+    /// Wall time of the run
+    pub time: Duration,
+    /// Cost function values after the initialization and after every iteration
+    pub trajectory: Vec<TrajectoryPoint<F>>,
+}
+
+/// Summary of all runs of a [`Comparison`]
+#[derive(Clone, Debug, PartialEq)]
+pub struct ComparisonSummary<F> {
+    /// Summaries of the runs in the order in which the solvers were added
+    pub entries: Vec<ComparisonEntry<F>>,
+}
+
+impl<F: ArgminFloat> ComparisonSummary<F> {
+    /// Returns the run with the lowest best cost function value (the first one in case of ties)
+    pub fn best(&self) -> Option<&ComparisonEntry<F>> {
+        self.entries.iter().fold(None, |best, entry| match best {
+            Some(b) if b.best_cost <= entry.best_cost => Some(b),
+            _ => Some(entry),
+        })
+    }
+}
+
+impl<F: ArgminFloat> fmt::Display for ComparisonSummary<F> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(
+            f,
+            "{:<24} {:>14} {:>8} {:>12}  evaluations",
+            "name", "best cost", "iters", "time [s]"
+        )?;
+        for entry in &self.entries {
+            let mut counts: Vec<_> = entry.counts.iter().collect();
+            counts.sort();
+            let counts = counts
+                .iter()
+                .map(|(k, v)| format!("{k}: {v}"))
+                .collect::<Vec<_>>()
+                .join(", ");
+            writeln!(
+                f,
+                "{:<24} {:>14} {:>8} {:>12.6}  {}",
+                entry.name,
+                entry.best_cost,
+                entry.iterations,
+                entry.time.as_secs_f64(),
+                counts
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// Observer which records the cost function values of a run
+struct TrajectoryRecorder<F> {
+    trajectory: Arc<Mutex<Vec<TrajectoryPoint<F>>>>,
+}
+
+impl<F> TrajectoryRecorder<F>
+where
+    F: ArgminFloat,
+{
+    fn record<I: State<Float = F>>(&self, iter: u64, state: &I) {
+        self.trajectory.lock().unwrap().push(TrajectoryPoint {
+            iter,
+            cost: state.get_cost(),
+            best_cost: state.get_best_cost(),
+        });
+    }
+}
+
+impl<I, F> Observe<I> for TrajectoryRecorder<F>
+where
+    I: State<Float = F>,
+    F: ArgminFloat,
+{
+    fn observe_init(&mut self, _name: &str, state: &I, _kv: &KV) -> Result<(), Error> {
+        self.record(0, state);
+        Ok(())
+    }
+
+    fn observe_iter(&mut self, state: &I, _kv: &KV) -> Result<(), Error> {
+        // The iteration counter is incremented after the observers are called
+        self.record(state.get_iter() + 1, state);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::{CostFunction, Problem, TerminationReason};
+
+    /// Returns the first entry of the parameter vector as cost
+    #[derive(Clone)]
+    struct FirstEntry {}
+
+    impl CostFunction for FirstEntry {
+        type Param = Vec<f64>;
+        type Output = f64;
+
+        fn cost(&self, p: &Self::Param) -> Result<Self::Output, Error> {
+            Ok(p[0])
+        }
+    }
+
+    /// Solver which halves the parameter vector in every iteration
+    #[derive(Clone)]
+    struct Halving {}
+
+    impl<O> Solver<O, IterState<Vec<f64>, (), (), (), (), f64>> for Halving
+    where
+        O: CostFunction<Param = Vec<f64>, Output = f64>,
+    {
+        fn name(&self) -> &str {
+            "Halving"
+        }
+
+        fn next_iter(
+            &mut self,
+            problem: &mut Problem<O>,
+            mut state: IterState<Vec<f64>, (), (), (), (), f64>,
+        ) -> Result<(IterState<Vec<f64>, (), (), (), (), f64>, Option<KV>), Error> {
+            let param: Vec<f64> = state
+                .take_param()
+                .unwrap()
+                .iter()
+                .map(|x| x / 2.0)
+                .collect();
+            let cost = problem.cost(&param)?;
+            Ok((state.param(param).cost(cost), None))
+        }
+    }
+
+    /// Solver which fails in the first iteration
+    #[derive(Clone)]
+    struct Failing {}
+
+    impl<O> Solver<O, IterState<Vec<f64>, (), (), (), (), f64>> for Failing {
+        fn name(&self) -> &str {
+            "Failing"
+        }
+
+        fn next_iter(
+            &mut self,
+            _problem: &mut Problem<O>,
+            _state: IterState<Vec<f64>, (), (), (), (), f64>,
+        ) -> Result<(IterState<Vec<f64>, (), (), (), (), f64>, Option<KV>), Error> {
+            Err(argmin_error!(PotentialBug, "failure"))
+        }
+    }
+
+    #[test]
+    fn test_comparison() {
+        let summary = Comparison::new(FirstEntry {}, vec![4.0f64])
+            .max_iters(3)
+            .add("halving", Halving {})
+            .add_with("halving, target", Halving {}, |state, param, iters| {
+                state.param(param).max_iters(iters).target_cost(3.0)
+            })
+            .run()
+            .unwrap();
+        assert_eq!(summary.entries.len(), 2);
+
+        let entry = &summary.entries[0];
+        assert_eq!(entry.name, "halving");
+        assert_eq!(entry.solver, "Halving");
+        assert_eq!(entry.iterations, 3);
+        assert_eq!(
+            entry.termination_status,
+            TerminationStatus::Terminated(TerminationReason::MaxItersReached)
+        );
+        assert_eq!(entry.best_cost.to_ne_bytes(), 0.5f64.to_ne_bytes());
+        assert_eq!(entry.counts["cost_count"], 3);
+        let iters: Vec<u64> = entry.trajectory.iter().map(|p| p.iter).collect();
+        assert_eq!(iters, vec![0, 1, 2, 3]);
+        let costs: Vec<f64> = entry.trajectory.iter().skip(1).map(|p| p.cost).collect();
+        assert_eq!(costs, vec![2.0, 1.0, 0.5]);
+
+        let entry = &summary.entries[1];
+        assert_eq!(entry.iterations, 1);
+        assert_eq!(
+            entry.termination_status,
+            TerminationStatus::Terminated(TerminationReason::TargetCostReached)
+        );
+
+        assert_eq!(summary.best().unwrap().name, "halving");
+        let table = format!("{summary}");
+        assert!(table.lines().next().unwrap().starts_with("name"));
+        assert!(table.contains("halving, target"));
+        assert!(table.contains("cost_count: 3"));
+    }
+
+    #[test]
+    fn test_comparison_failure() {
+        let res = Comparison::new(FirstEntry {}, vec![4.0f64])
+            .max_iters(3)
+            .add("halving", Halving {})
+            .add("failing", Failing {})
+            .run();
+        let err = res.unwrap_err();
+        assert_eq!(err.to_string(), "Comparison: run `failing` failed");
+    }
+
+    #[test]
+    fn test_best_empty() {
+        let summary: ComparisonSummary<f64> = ComparisonSummary { entries: vec![] };
+        assert!(summary.best().is_none());
+    }
+}
@@ -0,0 +1,589 @@
+// Copyright 2018-2024 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+//! # Parameter covariance
+//!
+//! When fitting a model to data, the parameters at the optimum are usually reported together with
+//! their uncertainties. The functions in this module estimate the covariance matrix of the
+//! parameters from the curvature of the cost function at the optimum, either via the inverse of
+//! the Hessian or via the Gauss-Newton approximation `JᵀJ` for least squares problems.
+
+use crate::core::{ArgminFloat, Error, Hessian, Jacobian, Operator, OptimizationResult, State};
+
+/// Estimated covariance matrix of the parameters, returned by [`covariance_from_hessian`] and
+/// [`covariance_from_jacobian`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct CovarianceEstimate<F> {
+    /// Parameter vector at which the covariance was estimated
+    pub param: Vec<F>,
+    /// Covariance matrix of the parameters
+    pub covariance: Vec<Vec<F>>,
+    /// Standard errors of the parameters (square roots of the diagonal of the covariance matrix)
+    pub standard_errors: Vec<F>,
+}
+
+impl<F: ArgminFloat> CovarianceEstimate<F> {
+    /// Builds an estimate from a covariance matrix.
+    fn new(param: Vec<F>, covariance: Vec<Vec<F>>) -> Self {
+        let standard_errors = covariance
+            .iter()
+            .enumerate()
+            .map(|(i, row)| row[i].sqrt())
+            .collect();
+        CovarianceEstimate {
+            param,
+            covariance,
+            standard_errors,
+        }
+    }
+
+    /// Returns the correlation matrix of the parameters.
+    pub fn correlation(&self) -> Vec<Vec<F>> {
+        self.covariance
+            .iter()
+            .zip(self.standard_errors.iter())
+            .map(|(row, se_i)| {
+                row.iter()
+                    .zip(self.standard_errors.iter())
+                    .map(|(c, se_j)| *c / (*se_i * *se_j))
+                    .collect()
+            })
+            .collect()
+    }
+
+    /// Returns the intervals `param ± z * standard_error` for all parameters.
+    ///
+    /// For large samples, `z = 1.96` gives approximate 95% confidence intervals.
+    pub fn confidence_intervals(&self, z: F) -> Vec<(F, F)> {
+        self.param
+            .iter()
+            .zip(self.standard_errors.iter())
+            .map(|(p, se)| (*p - z * *se, *p + z * *se))
+            .collect()
+    }
+}
+
+/// Estimates the covariance of the parameters as `scale * H⁻¹`, where `H` is the Hessian of
+/// `problem` at `param`.
+///
+/// If the cost function is a negative log-likelihood, `scale` is `1`. For a least squares cost
+/// `Σ rᵢ²` with residual variance `σ²`, `scale` is `2σ²`.
+///
+/// Returns an error if the Hessian is not symmetric positive definite, in which case `param` is
+/// not a (strict) local minimum and the covariance is undefined.
+///
+/// # Example
+///
+/// ```
+/// use argmin::core::{covariance_from_hessian, Error, Hessian};
+///
+/// struct NegLogLikelihood {}
+///
+/// impl Hessian for NegLogLikelihood {
+///     type Param = Vec<f64>;
+///     type Hessian = Vec<Vec<f64>>;
+///
+///     fn hessian(&self, _p: &Self::Param) -> Result<Self::Hessian, Error> {
+///         Ok(vec![vec![4.0, 0.0], vec![0.0, 25.0]])
+///     }
+/// }
+///
+/// # fn main() -> Result<(), Error> {
+/// let estimate = covariance_from_hessian(&NegLogLikelihood {}, &vec![1.0, 2.0], 1.0)?;
+///
+/// assert_eq!(estimate.standard_errors, vec![0.5, 0.2]);
+/// # Ok(())
+/// # }
+/// ```
+pub fn covariance_from_hessian<O, F>(
+    problem: &O,
+    param: &Vec<F>,
+    scale: F,
+) -> Result<CovarianceEstimate<F>, Error>
+where
+    O: Hessian<Param = Vec<F>, Hessian = Vec<Vec<F>>>,
+    F: ArgminFloat,
+{
+    let n = param.len();
+    let hessian = problem.hessian(param)?;
+    if hessian.len() != n || hessian.iter().any(|row| row.len() != n) {
+        return Err(argmin_error!(
+            InvalidParameter,
+            "Covariance estimation: Hessian must be a square matrix matching the parameter vector."
+        ));
+    }
+    // Use the symmetric part to be robust against small asymmetries of approximated Hessians.
+    let hessian: Vec<Vec<F>> = (0..n)
+        .map(|i| {
+            (0..n)
+                .map(|j| (hessian[i][j] + hessian[j][i]) / float!(2.0))
+                .collect()
+        })
+        .collect();
+    let inv = invert_spd(&hessian).ok_or_else(argmin_error_closure!(
+        ConditionViolated,
+        "Covariance estimation: Hessian is not positive definite."
+    ))?;
+    let covariance = inv
+        .into_iter()
+        .map(|row| row.into_iter().map(|c| c * scale).collect())
+        .collect();
+    Ok(CovarianceEstimate::new(param.clone(), covariance))
+}
+
+/// Estimates the covariance of the parameters of a least squares problem as `s² (JᵀJ)⁻¹`, where
+/// `J` is the Jacobian of the residuals at `param`.
+///
+/// The residuals are the output of the [`Operator`] implementation of `problem`. The residual
+/// variance is estimated as `s² = rᵀr / (m - n)` with `m` residuals and `n` parameters, which
+/// requires `m > n`.
+///
+/// Returns an error if `J` does not have full column rank, in which case some parameters are not
+/// identifiable from the data.
+///
+/// # Example
+///
+/// ```
+/// use argmin::core::{covariance_from_jacobian, Error, Jacobian, Operator};
+///
+/// /// Fit of `y = a + b x`
+/// struct LinearFit {
+///     data: Vec<(f64, f64)>,
+/// }
+///
+/// impl Operator for LinearFit {
+///     type Param = Vec<f64>;
+///     type Output = Vec<f64>;
+///
+///     fn apply(&self, p: &Self::Param) -> Result<Self::Output, Error> {
+///         Ok(self.data.iter().map(|(x, y)| p[0] + p[1] * x - y).collect())
+///     }
+/// }
+///
+/// impl Jacobian for LinearFit {
+///     type Param = Vec<f64>;
+///     type Jacobian = Vec<Vec<f64>>;
+///
+///     fn jacobian(&self, _p: &Self::Param) -> Result<Self::Jacobian, Error> {
+///         Ok(self.data.iter().map(|(x, _)| vec![1.0, *x]).collect())
+///     }
+/// }
+///
+/// # fn main() -> Result<(), Error> {
+/// let problem = LinearFit {
+///     data: vec![(0.0, 1.1), (1.0, 2.9), (2.0, 5.2), (3.0, 7.1), (4.0, 8.8)],
+/// };
+/// let estimate = covariance_from_jacobian(&problem, &vec![1.1, 1.96])?;
+///
+/// let intervals = estimate.confidence_intervals(1.96);
+/// # assert!(intervals[1].0 < 1.96 && intervals[1].1 > 1.96);
+/// # Ok(())
+/// # }
+/// ```
+pub fn covariance_from_jacobian<O, F>(
+    problem: &O,
+    param: &Vec<F>,
+) -> Result<CovarianceEstimate<F>, Error>
+where
+    O: Operator<Param = Vec<F>, Output = Vec<F>> + Jacobian<Param = Vec<F>, Jacobian = Vec<Vec<F>>>,
+    F: ArgminFloat,
+{
+    let n = param.len();
+    let residuals = problem.apply(param)?;
+    let jacobian = problem.jacobian(param)?;
+    let m = residuals.len();
+    if jacobian.len() != m || jacobian.iter().any(|row| row.len() != n) {
+        return Err(argmin_error!(
+            InvalidParameter,
+            "Covariance estimation: Jacobian must have one row per residual and one column per parameter."
+        ));
+    }
+    if m <= n {
+        return Err(argmin_error!(
+            InvalidParameter,
+            "Covariance estimation: Number of residuals must be larger than number of parameters."
+        ));
+    }
+    let jtj: Vec<Vec<F>> = (0..n)
+        .map(|i| {
+            (0..n)
+                .map(|j| {
+                    jacobian
+                        .iter()
+                        .fold(float!(0.0), |acc, row| acc + row[i] * row[j])
+                })
+                .collect()
+        })
+        .collect();
+    let inv = invert_spd(&jtj).ok_or_else(argmin_error_closure!(
+        ConditionViolated,
+        "Covariance estimation: Jacobian does not have full column rank."
+    ))?;
+    let s2 =
+        residuals.iter().fold(float!(0.0), |acc, r| acc + *r * *r) / F::from_usize(m - n).unwrap();
+    let covariance = inv
+        .into_iter()
+        .map(|row| row.into_iter().map(|c| c * s2).collect())
+        .collect();
+    Ok(CovarianceEstimate::new(param.clone(), covariance))
+}
+
+impl<O, S, I, F> OptimizationResult<O, S, I>
+where
+    I: State<Param = Vec<F>, Float = F>,
+    F: ArgminFloat,
+{
+    /// Estimates the covariance of the best parameter vector found during the run via
+    /// [`covariance_from_hessian`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::{Error, Hessian, IterState, OptimizationResult, Problem, State};
+    /// #
+    /// # struct NegLogLikelihood {}
+    /// #
+    /// # impl Hessian for NegLogLikelihood {
+    /// #     type Param = Vec<f64>;
+    /// #     type Hessian = Vec<Vec<f64>>;
+    /// #
+    /// #     fn hessian(&self, _p: &Self::Param) -> Result<Self::Hessian, Error> {
+    /// #         Ok(vec![vec![4.0, 0.0], vec![0.0, 25.0]])
+    /// #     }
+    /// # }
+    /// #
+    /// # let mut state: IterState<Vec<f64>, (), (), (), (), f64> = IterState::new()
+    /// #     .param(vec![1.0, 2.0])
+    /// #     .cost(0.0);
+    /// # state.update();
+    /// # let result = OptimizationResult::new(Problem::new(NegLogLikelihood {}), (), state);
+    /// let estimate = result.covariance_from_hessian(1.0)?;
+    /// # assert_eq!(estimate.standard_errors, vec![0.5, 0.2]);
+    /// # Ok::<(), Error>(())
+    /// ```
+    pub fn covariance_from_hessian(&self, scale: F) -> Result<CovarianceEstimate<F>, Error>
+    where
+        O: Hessian<Param = Vec<F>, Hessian = Vec<Vec<F>>>,
+    {
+        let (problem, param) = self.problem_and_best_param()?;
+        covariance_from_hessian(problem, param, scale)
+    }
+
+    /// Estimates the covariance of the best parameter vector found during the run via
+    /// [`covariance_from_jacobian`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::{Error, IterState, Jacobian, Operator, OptimizationResult, Problem, State};
+    /// #
+    /// # struct LinearFit {
+    /// #     data: Vec<(f64, f64)>,
+    /// # }
+    /// #
+    /// # impl Operator for LinearFit {
+    /// #     type Param = Vec<f64>;
+    /// #     type Output = Vec<f64>;
+    /// #
+    /// #     fn apply(&self, p: &Self::Param) -> Result<Self::Output, Error> {
+    /// #         Ok(self.data.iter().map(|(x, y)| p[0] + p[1] * x - y).collect())
+    /// #     }
+    /// # }
+    /// #
+    /// # impl Jacobian for LinearFit {
+    /// #     type Param = Vec<f64>;
+    /// #     type Jacobian = Vec<Vec<f64>>;
+    /// #
+    /// #     fn jacobian(&self, _p: &Self::Param) -> Result<Self::Jacobian, Error> {
+    /// #         Ok(self.data.iter().map(|(x, _)| vec![1.0, *x]).collect())
+    /// #     }
+    /// # }
+    /// #
+    /// # let problem = LinearFit {
+    /// #     data: vec![(0.0, 1.1), (1.0, 2.9), (2.0, 5.2), (3.0, 7.1), (4.0, 8.8)],
+    /// # };
+    /// # let mut state: IterState<Vec<f64>, (), (), (), (), f64> = IterState::new()
+    /// #     .param(vec![1.1, 1.96])
+    /// #     .cost(0.0);
+    /// # state.update();
+    /// # let result = OptimizationResult::new(Problem::new(problem), (), state);
+    /// let estimate = result.covariance_from_jacobian()?;
+    /// # assert_eq!(estimate.param, vec![1.1, 1.96]);
+    /// # Ok::<(), Error>(())
+    /// ```
+    pub fn covariance_from_jacobian(&self) -> Result<CovarianceEstimate<F>, Error>
+    where
+        O: Operator<Param = Vec<F>, Output = Vec<F>>
+            + Jacobian<Param = Vec<F>, Jacobian = Vec<Vec<F>>>,
+    {
+        let (problem, param) = self.problem_and_best_param()?;
+        covariance_from_jacobian(problem, param)
+    }
+
+    /// Returns the problem and the best parameter vector, or an error if either is missing.
+    fn problem_and_best_param(&self) -> Result<(&O, &Vec<F>), Error> {
+        let problem = self
+            .problem
+            .problem
+            .as_ref()
+            .ok_or_else(argmin_error_closure!(
+                NotInitialized,
+                "Covariance estimation: Problem is not available."
+            ))?;
+        let param = self
+            .state
+            .get_best_param()
+            .ok_or_else(argmin_error_closure!(
+                NotInitialized,
+                "Covariance estimation: No best parameter vector available."
+            ))?;
+        Ok((problem, param))
+    }
+}
+
+/// Inverts a symmetric positive definite matrix via its Cholesky decomposition `A = LLᵀ`.
+///
+/// Returns `None` if the matrix is not (numerically) positive definite.
+fn invert_spd<F: ArgminFloat>(a: &[Vec<F>]) -> Option<Vec<Vec<F>>> {
+    let n = a.len();
+    let mut l = vec![vec![float!(0.0); n]; n];
+    for i in 0..n {
+        for j in 0..=i {
+            let sum = (0..j).fold(a[i][j], |acc, k| acc - l[i][k] * l[j][k]);
+            if i == j {
+                if sum <= float!(0.0) || !sum.is_finite() {
+                    return None;
+                }
+                l[i][i] = sum.sqrt();
+            } else {
+                l[i][j] = sum / l[j][j];
+            }
+        }
+    }
+    // L⁻¹ via forward substitution, then A⁻¹ = L⁻ᵀ L⁻¹
+    let mut l_inv = vec![vec![float!(0.0); n]; n];
+    for (i, l_i) in l.iter().enumerate() {
+        let (done, rest) = l_inv.split_at_mut(i);
+        for (col, l_inv_ij) in rest[0].iter_mut().enumerate().take(i + 1) {
+            let rhs = if i == col { float!(1.0) } else { float!(0.0) };
+            let sum = (col..i).fold(rhs, |acc, k| acc - l_i[k] * done[k][col]);
+            *l_inv_ij = sum / l_i[i];
+        }
+    }
+    Some(
+        (0..n)
+            .map(|i| {
+                (0..n)
+                    .map(|j| {
+                        (i.max(j)..n).fold(float!(0.0), |acc, k| acc + l_inv[k][i] * l_inv[k][j])
+                    })
+                    .collect()
+            })
+            .collect(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::{ArgminError, IterState, Problem};
+    use approx::assert_relative_eq;
+
+    struct Quadratic {
+        hessian: Vec<Vec<f64>>,
+    }
+
+    impl Hessian for Quadratic {
+        type Param = Vec<f64>;
+        type Hessian = Vec<Vec<f64>>;
+
+        fn hessian(&self, _p: &Self::Param) -> Result<Self::Hessian, Error> {
+            Ok(self.hessian.clone())
+        }
+    }
+
+    struct LinearFit {
+        data: Vec<(f64, f64)>,
+    }
+
+    impl Operator for LinearFit {
+        type Param = Vec<f64>;
+        type Output = Vec<f64>;
+
+        fn apply(&self, p: &Self::Param) -> Result<Self::Output, Error> {
+            Ok(self.data.iter().map(|(x, y)| p[0] + p[1] * x - y).collect())
+        }
+    }
+
+    impl Jacobian for LinearFit {
+        type Param = Vec<f64>;
+        type Jacobian = Vec<Vec<f64>>;
+
+        fn jacobian(&self, _p: &Self::Param) -> Result<Self::Jacobian, Error> {
+            Ok(self.data.iter().map(|(x, _)| vec![1.0, *x]).collect())
+        }
+    }
+
+    #[test]
+    fn test_invert_spd() {
+        let a = vec![
+            vec![4.0, 2.0, 0.6],
+            vec![2.0, 5.0, 1.0],
+            vec![0.6, 1.0, 3.0],
+        ];
+        let inv = invert_spd(&a).unwrap();
+        for (i, row) in a.iter().enumerate() {
+            for j in 0..3 {
+                let prod: f64 = row.iter().zip(inv.iter()).map(|(a, b)| a * b[j]).sum();
+                assert_relative_eq!(prod, if i == j { 1.0 } else { 0.0 }, epsilon = 1e-12);
+            }
+        }
+        assert!(invert_spd(&[vec![1.0, 2.0], vec![2.0, 1.0]]).is_none());
+        assert!(invert_spd(&[vec![1.0, 1.0], vec![1.0, 1.0]]).is_none());
+    }
+
+    #[test]
+    fn test_covariance_from_hessian() {
+        let problem = Quadratic {
+            hessian: vec![vec![4.0, 1.0], vec![1.0, 3.0]],
+        };
+        let est = covariance_from_hessian(&problem, &vec![1.0, -1.0], 2.0).unwrap();
+        // 2 * H⁻¹ = 2/11 * [[3, -1], [-1, 4]]
+        let expected = [[6.0 / 11.0, -2.0 / 11.0], [-2.0 / 11.0, 8.0 / 11.0]];
+        for i in 0..2 {
+            for j in 0..2 {
+                assert_relative_eq!(est.covariance[i][j], expected[i][j], epsilon = 1e-12);
+            }
+        }
+        assert_relative_eq!(
+            est.standard_errors[0],
+            (6.0f64 / 11.0).sqrt(),
+            epsilon = 1e-12
+        );
+        assert_relative_eq!(
+            est.standard_errors[1],
+            (8.0f64 / 11.0).sqrt(),
+            epsilon = 1e-12
+        );
+        let corr = est.correlation();
+        assert_relative_eq!(corr[0][0], 1.0, epsilon = 1e-12);
+        assert_relative_eq!(corr[0][1], -1.0 / 12.0f64.sqrt(), epsilon = 1e-12);
+        assert_eq!(est.param, vec![1.0, -1.0]);
+    }
+
+    #[test]
+    fn test_covariance_from_hessian_errors() {
+        let problem = Quadratic {
+            hessian: vec![vec![1.0, 0.0], vec![0.0, -1.0]],
+        };
+        let res = covariance_from_hessian(&problem, &vec![0.0, 0.0], 1.0);
+        assert_error!(
+            res,
+            ArgminError,
+            "Condition violated: \"Covariance estimation: Hessian is not positive definite.\""
+        );
+
+        let problem = Quadratic {
+            hessian: vec![vec![1.0]],
+        };
+        let res = covariance_from_hessian(&problem, &vec![0.0, 0.0], 1.0);
+        assert_error!(
+            res,
+            ArgminError,
+            "Invalid parameter: \"Covariance estimation: Hessian must be a square matrix matching the parameter vector.\""
+        );
+    }
+
+    #[test]
+    fn test_covariance_from_jacobian() {
+        let xs = [0.0, 1.0, 2.0, 3.0, 4.0];
+        let ys = [1.1, 2.9, 5.2, 7.1, 8.8];
+        let problem = LinearFit {
+            data: xs.iter().cloned().zip(ys.iter().cloned()).collect(),
+        };
+
+        // Closed form ordinary least squares solution and covariance
+        let n = xs.len() as f64;
+        let x_mean = xs.iter().sum::<f64>() / n;
+        let y_mean = ys.iter().sum::<f64>() / n;
+        let sxx: f64 = xs.iter().map(|x| (x - x_mean).powi(2)).sum();
+        let sxy: f64 = xs
+            .iter()
+            .zip(ys.iter())
+            .map(|(x, y)| (x - x_mean) * (y - y_mean))
+            .sum();
+        let b = sxy / sxx;
+        let a = y_mean - b * x_mean;
+        let ssr: f64 = xs
+            .iter()
+            .zip(ys.iter())
+            .map(|(x, y)| (a + b * x - y).powi(2))
+            .sum();
+        let s2 = ssr / (n - 2.0);
+
+        let est = covariance_from_jacobian(&problem, &vec![a, b]).unwrap();
+        assert_relative_eq!(
+            est.covariance[0][0],
+            s2 * (1.0 / n + x_mean.powi(2) / sxx),
+            epsilon = 1e-12
+        );
+        assert_relative_eq!(est.covariance[1][1], s2 / sxx, epsilon = 1e-12);
+        assert_relative_eq!(est.covariance[0][1], -x_mean * s2 / sxx, epsilon = 1e-12);
+        assert_relative_eq!(est.covariance[1][0], -x_mean * s2 / sxx, epsilon = 1e-12);
+
+        let intervals = est.confidence_intervals(2.0);
+        assert_relative_eq!(intervals[1].0, b - 2.0 * (s2 / sxx).sqrt(), epsilon = 1e-12);
+        assert_relative_eq!(intervals[1].1, b + 2.0 * (s2 / sxx).sqrt(), epsilon = 1e-12);
+    }
+
+    #[test]
+    fn test_covariance_from_jacobian_errors() {
+        let problem = LinearFit {
+            data: vec![(1.0, 1.0), (2.0, 2.0)],
+        };
+        let res = covariance_from_jacobian(&problem, &vec![0.0, 1.0]);
+        assert_error!(
+            res,
+            ArgminError,
+            "Invalid parameter: \"Covariance estimation: Number of residuals must be larger than number of parameters.\""
+        );
+
+        let problem = LinearFit {
+            data: vec![(1.0, 1.0), (1.0, 2.0), (1.0, 3.0)],
+        };
+        let res = covariance_from_jacobian(&problem, &vec![0.0, 1.0]);
+        assert_error!(
+            res,
+            ArgminError,
+            "Condition violated: \"Covariance estimation: Jacobian does not have full column rank.\""
+        );
+    }
+
+    #[test]
+    fn test_optimization_result() {
+        let problem = Quadratic {
+            hessian: vec![vec![4.0, 0.0], vec![0.0, 25.0]],
+        };
+        let state: IterState<Vec<f64>, (), (), (), (), f64> = IterState::new();
+        let result = OptimizationResult::new(Problem::new(problem), (), state);
+        let res = result.covariance_from_hessian(1.0);
+        assert_error!(
+            res,
+            ArgminError,
+            "Not initialized: \"Covariance estimation: No best parameter vector available.\""
+        );
+
+        let OptimizationResult { problem, state, .. } = result;
+        let mut state = state.param(vec![1.0, 2.0]).cost(0.0);
+        state.update();
+        let result = OptimizationResult::new(problem, (), state);
+        let est = result.covariance_from_hessian(1.0).unwrap();
+        assert_eq!(est.param, vec![1.0, 2.0]);
+        assert_relative_eq!(est.standard_errors[0], 0.5, epsilon = 1e-12);
+        assert_relative_eq!(est.standard_errors[1], 0.2, epsilon = 1e-12);
+    }
+}
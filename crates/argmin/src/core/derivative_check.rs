@@ -0,0 +1,354 @@
+// Copyright 2018-2024 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+//! # Derivative checks
+//!
+//! Wrong user-supplied derivatives are a common source of silently failing optimization runs.
+//! The functions in this module compare analytical gradients, Jacobians and Hessians with
+//! approximations computed via central differences (using the `finitediff` crate) and return a
+//! per-component report.
+
+use crate::core::{ArgminFloat, CostFunction, Error, Gradient, Hessian, Jacobian, Operator};
+use std::fmt;
+
+/// Comparison of a single component of an analytical derivative with its finite difference
+/// approximation.
+#[derive(Clone, Debug, PartialEq)]
+pub struct DerivativeCheckEntry<F> {
+    /// Index of the component as `(row, column)`. Gradients are treated as row vectors, therefore
+    /// the row is always `0`.
+    pub index: (usize, usize),
+    /// Analytically computed value
+    pub analytical: F,
+    /// Value approximated via central differences
+    pub numerical: F,
+    /// Error `|analytical - numerical| / max(1, |numerical|)`
+    pub error: F,
+}
+
+/// Report returned by [`check_gradient`], [`check_jacobian`] and [`check_hessian`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct DerivativeCheckReport<F> {
+    /// Tolerance used for deciding whether a component passed the check
+    pub tol: F,
+    /// Comparison of all components
+    pub entries: Vec<DerivativeCheckEntry<F>>,
+}
+
+impl<F: ArgminFloat> DerivativeCheckReport<F> {
+    /// Builds a report from analytical and numerical derivatives given as rows.
+    fn new(analytical: &[Vec<F>], numerical: &[Vec<F>], tol: F) -> Result<Self, Error> {
+        if analytical.len() != numerical.len()
+            || analytical
+                .iter()
+                .zip(numerical.iter())
+                .any(|(a, n)| a.len() != n.len())
+        {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "Derivative check: dimensions of analytical and numerical derivatives differ."
+            ));
+        }
+        let entries = analytical
+            .iter()
+            .zip(numerical.iter())
+            .enumerate()
+            .flat_map(|(i, (a_row, n_row))| {
+                a_row
+                    .iter()
+                    .zip(n_row.iter())
+                    .enumerate()
+                    .map(move |(j, (&a, &n))| DerivativeCheckEntry {
+                        index: (i, j),
+                        analytical: a,
+                        numerical: n,
+                        error: (a - n).abs() / n.abs().max(float!(1.0)),
+                    })
+            })
+            .collect();
+        Ok(DerivativeCheckReport { tol, entries })
+    }
+
+    /// Returns `true` if the errors of all components are below the tolerance.
+    pub fn passed(&self) -> bool {
+        self.entries.iter().all(|e| e.error <= self.tol)
+    }
+
+    /// Returns all components whose error exceeds the tolerance.
+    pub fn failures(&self) -> Vec<&DerivativeCheckEntry<F>> {
+        self.entries.iter().filter(|e| e.error > self.tol).collect()
+    }
+
+    /// Returns the largest error over all components.
+    pub fn max_error(&self) -> F {
+        self.entries
+            .iter()
+            .fold(float!(0.0), |acc, e| acc.max(e.error))
+    }
+}
+
+impl<F: ArgminFloat> fmt::Display for DerivativeCheckReport<F> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let failures = self.failures();
+        writeln!(
+            f,
+            "Derivative check: {} of {} components exceed tolerance {} (max error: {})",
+            failures.len(),
+            self.entries.len(),
+            self.tol,
+            self.max_error()
+        )?;
+        for e in failures {
+            writeln!(
+                f,
+                "   ({}, {}): analytical: {}, numerical: {}, error: {}",
+                e.index.0, e.index.1, e.analytical, e.numerical, e.error
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// Compares the gradient of `problem` at `param` with a central differences approximation of the
+/// gradient of its cost function.
+///
+/// # Example
+///
+/// ```
+/// use argmin::core::{check_gradient, CostFunction, Error, Gradient};
+///
+/// struct Problem {}
+///
+/// impl CostFunction for Problem {
+///     type Param = Vec<f64>;
+///     type Output = f64;
+///
+///     fn cost(&self, p: &Self::Param) -> Result<Self::Output, Error> {
+///         Ok(p[0].powi(2) + 3.0 * p[1])
+///     }
+/// }
+///
+/// impl Gradient for Problem {
+///     type Param = Vec<f64>;
+///     type Gradient = Vec<f64>;
+///
+///     fn gradient(&self, p: &Self::Param) -> Result<Self::Gradient, Error> {
+///         // Wrong: the second component should be 3.0
+///         Ok(vec![2.0 * p[0], 2.0])
+///     }
+/// }
+///
+/// # fn main() -> Result<(), Error> {
+/// let report = check_gradient(&Problem {}, &vec![1.0, 2.0], 1e-6)?;
+///
+/// assert!(!report.passed());
+/// assert_eq!(report.failures()[0].index, (0, 1));
+/// # Ok(())
+/// # }
+/// ```
+pub fn check_gradient<O, F>(
+    problem: &O,
+    param: &Vec<F>,
+    tol: F,
+) -> Result<DerivativeCheckReport<F>, Error>
+where
+    O: CostFunction<Param = Vec<F>, Output = F> + Gradient<Param = Vec<F>, Gradient = Vec<F>>,
+    F: ArgminFloat,
+{
+    let analytical = problem.gradient(param)?;
+    let cost = |p: &Vec<F>| problem.cost(p);
+    let numerical = finitediff::vec::central_diff(&cost)(param)?;
+    DerivativeCheckReport::new(&[analytical], &[numerical], tol)
+}
+
+/// Compares the Jacobian of `problem` at `param` with a central differences approximation of the
+/// Jacobian of its operator.
+///
+/// The rows of the Jacobian correspond to the outputs of the operator and the columns correspond
+/// to the parameters.
+///
+/// # Example
+///
+/// ```
+/// use argmin::core::{check_jacobian, Error, Jacobian, Operator};
+///
+/// struct Problem {}
+///
+/// impl Operator for Problem {
+///     type Param = Vec<f64>;
+///     type Output = Vec<f64>;
+///
+///     fn apply(&self, p: &Self::Param) -> Result<Self::Output, Error> {
+///         Ok(vec![p[0] * p[1], p[1].powi(2)])
+///     }
+/// }
+///
+/// impl Jacobian for Problem {
+///     type Param = Vec<f64>;
+///     type Jacobian = Vec<Vec<f64>>;
+///
+///     fn jacobian(&self, p: &Self::Param) -> Result<Self::Jacobian, Error> {
+///         Ok(vec![vec![p[1], p[0]], vec![0.0, 2.0 * p[1]]])
+///     }
+/// }
+///
+/// # fn main() -> Result<(), Error> {
+/// let report = check_jacobian(&Problem {}, &vec![1.0, 2.0], 1e-6)?;
+///
+/// assert!(report.passed());
+/// # Ok(())
+/// # }
+/// ```
+pub fn check_jacobian<O, F>(
+    problem: &O,
+    param: &Vec<F>,
+    tol: F,
+) -> Result<DerivativeCheckReport<F>, Error>
+where
+    O: Operator<Param = Vec<F>, Output = Vec<F>> + Jacobian<Param = Vec<F>, Jacobian = Vec<Vec<F>>>,
+    F: ArgminFloat,
+{
+    let analytical = problem.jacobian(param)?;
+    let op = |p: &Vec<F>| problem.apply(p);
+    let numerical = finitediff::vec::central_jacobian(&op)(param)?;
+    DerivativeCheckReport::new(&analytical, &numerical, tol)
+}
+
+/// Compares the Hessian of `problem` at `param` with a central differences approximation computed
+/// from its gradient.
+///
+/// Since the approximation is based on the analytical gradient, the gradient should be checked
+/// first with [`check_gradient`].
+///
+/// # Example
+///
+/// ```
+/// use argmin::core::{check_hessian, Error, Gradient, Hessian};
+///
+/// struct Problem {}
+///
+/// impl Gradient for Problem {
+///     type Param = Vec<f64>;
+///     type Gradient = Vec<f64>;
+///
+///     fn gradient(&self, p: &Self::Param) -> Result<Self::Gradient, Error> {
+///         Ok(vec![2.0 * p[0] * p[1], p[0].powi(2)])
+///     }
+/// }
+///
+/// impl Hessian for Problem {
+///     type Param = Vec<f64>;
+///     type Hessian = Vec<Vec<f64>>;
+///
+///     fn hessian(&self, p: &Self::Param) -> Result<Self::Hessian, Error> {
+///         Ok(vec![vec![2.0 * p[1], 2.0 * p[0]], vec![2.0 * p[0], 0.0]])
+///     }
+/// }
+///
+/// # fn main() -> Result<(), Error> {
+/// let report = check_hessian(&Problem {}, &vec![1.0, 2.0], 1e-6)?;
+///
+/// assert!(report.passed());
+/// # Ok(())
+/// # }
+/// ```
+pub fn check_hessian<O, F>(
+    problem: &O,
+    param: &Vec<F>,
+    tol: F,
+) -> Result<DerivativeCheckReport<F>, Error>
+where
+    O: Gradient<Param = Vec<F>, Gradient = Vec<F>> + Hessian<Param = Vec<F>, Hessian = Vec<Vec<F>>>,
+    F: ArgminFloat,
+{
+    let analytical = problem.hessian(param)?;
+    let grad = |p: &Vec<F>| problem.gradient(p);
+    let numerical = finitediff::vec::central_hessian(&grad)(param)?;
+    DerivativeCheckReport::new(&analytical, &numerical, tol)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Rosenbrock {}
+
+    impl CostFunction for Rosenbrock {
+        type Param = Vec<f64>;
+        type Output = f64;
+
+        fn cost(&self, p: &Self::Param) -> Result<Self::Output, Error> {
+            Ok(argmin_testfunctions::rosenbrock(p))
+        }
+    }
+
+    impl Gradient for Rosenbrock {
+        type Param = Vec<f64>;
+        type Gradient = Vec<f64>;
+
+        fn gradient(&self, p: &Self::Param) -> Result<Self::Gradient, Error> {
+            Ok(argmin_testfunctions::rosenbrock_derivative(p))
+        }
+    }
+
+    impl Hessian for Rosenbrock {
+        type Param = Vec<f64>;
+        type Hessian = Vec<Vec<f64>>;
+
+        fn hessian(&self, p: &Self::Param) -> Result<Self::Hessian, Error> {
+            Ok(argmin_testfunctions::rosenbrock_hessian(p))
+        }
+    }
+
+    #[test]
+    fn test_check_gradient() {
+        let report = check_gradient(&Rosenbrock {}, &vec![-1.2, 1.0, 0.5], 1e-6).unwrap();
+        assert!(report.passed());
+        assert_eq!(report.entries.len(), 3);
+        assert!(report.failures().is_empty());
+        assert!(report.max_error() < 1e-6);
+    }
+
+    #[test]
+    fn test_check_hessian() {
+        let report = check_hessian(&Rosenbrock {}, &vec![-1.2, 1.0], 1e-5).unwrap();
+        assert!(report.passed());
+        assert_eq!(report.entries.len(), 4);
+    }
+
+    #[test]
+    fn test_check_gradient_wrong() {
+        struct Wrong {}
+
+        impl CostFunction for Wrong {
+            type Param = Vec<f64>;
+            type Output = f64;
+
+            fn cost(&self, p: &Self::Param) -> Result<Self::Output, Error> {
+                Ok(p[0].powi(2) + p[1].powi(2))
+            }
+        }
+
+        impl Gradient for Wrong {
+            type Param = Vec<f64>;
+            type Gradient = Vec<f64>;
+
+            fn gradient(&self, p: &Self::Param) -> Result<Self::Gradient, Error> {
+                Ok(vec![2.0 * p[0], p[1]])
+            }
+        }
+
+        let report = check_gradient(&Wrong {}, &vec![1.0, 2.0], 1e-6).unwrap();
+        assert!(!report.passed());
+        let failures = report.failures();
+        assert_eq!(failures.len(), 1);
+        assert_eq!(failures[0].index, (0, 1));
+        assert!((failures[0].numerical - 4.0).abs() < 1e-6);
+        assert!((failures[0].analytical - 2.0).abs() < 1e-12);
+        assert!(format!("{report}").contains("1 of 2 components"));
+    }
+}
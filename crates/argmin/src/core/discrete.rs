@@ -0,0 +1,534 @@
+// Copyright 2018-2024 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+//! # Discrete parameter types
+//!
+//! Ready-made parameter types for combinatorial optimization problems:
+//!
+//! * [`Permutation`]: An ordering of `n` elements, e.g. a tour in a traveling salesman problem.
+//! * [`Subset`]: A selection of elements out of `n`, e.g. the items of a knapsack problem.
+//! * [`Categorical`]: A vector of categorical values, each with its own number of categories.
+//!
+//! All of them implement [`DiscreteParam`], which describes their neighborhood in terms of moves.
+//! This makes it straightforward to implement
+//! [`Anneal`](`crate::solver::simulatedannealing::Anneal`) by applying random moves and
+//! [`Neighborhood`](`crate::solver::tabusearch::Neighborhood`) by enumerating all moves.
+//! [`Subset`] and [`Categorical`] additionally implement
+//! [`ArgminRandom`](`argmin_math::ArgminRandom`) in order to sample between bounds.
+//!
+//! # Example
+//!
+//! ```
+//! use argmin::core::discrete::{DiscreteParam, Permutation};
+//! use argmin::core::{CostFunction, Error};
+//! use argmin::solver::simulatedannealing::Anneal;
+//! use rand::SeedableRng;
+//! use rand_xoshiro::Xoshiro256PlusPlus;
+//! use std::sync::{Arc, Mutex};
+//!
+//! struct Tour {
+//!     distances: Vec<Vec<f64>>,
+//!     rng: Arc<Mutex<Xoshiro256PlusPlus>>,
+//! }
+//!
+//! impl CostFunction for Tour {
+//!     type Param = Permutation;
+//!     type Output = f64;
+//!
+//!     fn cost(&self, tour: &Permutation) -> Result<f64, Error> {
+//!         let t = tour.as_slice();
+//!         Ok((0..t.len())
+//!             .map(|i| self.distances[t[i]][t[(i + 1) % t.len()]])
+//!             .sum())
+//!     }
+//! }
+//!
+//! impl Anneal for Tour {
+//!     type Param = Permutation;
+//!     type Output = Permutation;
+//!     type Float = f64;
+//!
+//!     // Apply more random swaps at higher temperatures
+//!     fn anneal(&self, tour: &Permutation, extent: f64) -> Result<Permutation, Error> {
+//!         let mut rng = self.rng.lock().unwrap();
+//!         let mut tour = tour.clone();
+//!         for _ in 0..(extent.floor() as u64 + 1) {
+//!             if let Some(mv) = tour.random_move(&mut *rng) {
+//!                 tour = tour.apply_move(&mv);
+//!             }
+//!         }
+//!         Ok(tour)
+//!     }
+//! }
+//! # let tour = Tour {
+//! #     distances: vec![vec![0.0, 1.0, 2.0], vec![1.0, 0.0, 1.0], vec![2.0, 1.0, 0.0]],
+//! #     rng: Arc::new(Mutex::new(Xoshiro256PlusPlus::seed_from_u64(1))),
+//! # };
+//! # let p = tour.anneal(&Permutation::new(3), 2.0).unwrap();
+//! # assert_eq!(p.len(), 3);
+//! ```
+
+use crate::core::Error;
+use argmin_math::ArgminRandom;
+use rand::seq::SliceRandom;
+use rand::Rng;
+#[cfg(feature = "serde1")]
+use serde::{Deserialize, Serialize};
+
+/// Parameter types whose neighborhood is defined by a set of moves
+pub trait DiscreteParam: Sized {
+    /// Type of a move
+    type Move: Clone + PartialEq;
+
+    /// Returns all moves which lead to a neighboring parameter
+    fn moves(&self) -> Vec<Self::Move>;
+
+    /// Returns a copy of the parameter with the move applied
+    fn apply_move(&self, mv: &Self::Move) -> Self;
+
+    /// Draws a random move, if there is any
+    fn random_move<R: Rng>(&self, rng: &mut R) -> Option<Self::Move>;
+}
+
+/// A permutation of the elements `0..n`
+///
+/// Moves swap the elements at two positions `(i, j)` with `i < j`.
+///
+/// # Example
+///
+/// ```
+/// use argmin::core::discrete::{DiscreteParam, Permutation};
+/// # use argmin::core::Error;
+/// # fn main() -> Result<(), Error> {
+/// let p = Permutation::from_vec(vec![2, 0, 1])?;
+/// let q = p.apply_move(&(0, 2));
+/// assert_eq!(q.as_slice(), &[1, 0, 2]);
+/// assert_eq!(p.moves().len(), 3);
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+pub struct Permutation(Vec<usize>);
+
+impl Permutation {
+    /// Construct the identity permutation of `n` elements
+    pub fn new(n: usize) -> Self {
+        Permutation((0..n).collect())
+    }
+
+    /// Construct a permutation from a vector
+    ///
+    /// Returns an error if `order` does not contain each of the elements `0..order.len()` exactly
+    /// once.
+    pub fn from_vec(order: Vec<usize>) -> Result<Self, Error> {
+        let mut seen = vec![false; order.len()];
+        for &i in order.iter() {
+            if i >= order.len() || seen[i] {
+                return Err(argmin_error!(
+                    InvalidParameter,
+                    "`Permutation`: each of the elements 0..n must occur exactly once."
+                ));
+            }
+            seen[i] = true;
+        }
+        Ok(Permutation(order))
+    }
+
+    /// Construct a uniformly distributed random permutation of `n` elements
+    pub fn random<R: Rng>(n: usize, rng: &mut R) -> Self {
+        let mut order: Vec<usize> = (0..n).collect();
+        order.shuffle(rng);
+        Permutation(order)
+    }
+
+    /// Returns the number of elements
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Returns `true` if the permutation has no elements
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Returns the order of the elements
+    pub fn as_slice(&self) -> &[usize] {
+        &self.0
+    }
+
+    /// Returns the underlying vector
+    pub fn into_inner(self) -> Vec<usize> {
+        self.0
+    }
+}
+
+impl DiscreteParam for Permutation {
+    type Move = (usize, usize);
+
+    fn moves(&self) -> Vec<Self::Move> {
+        let n = self.len();
+        (0..n)
+            .flat_map(|i| ((i + 1)..n).map(move |j| (i, j)))
+            .collect()
+    }
+
+    fn apply_move(&self, mv: &Self::Move) -> Self {
+        let mut order = self.0.clone();
+        order.swap(mv.0, mv.1);
+        Permutation(order)
+    }
+
+    fn random_move<R: Rng>(&self, rng: &mut R) -> Option<Self::Move> {
+        let n = self.len();
+        if n < 2 {
+            return None;
+        }
+        let i = rng.gen_range(0..n);
+        let j = (i + rng.gen_range(1..n)) % n;
+        Some((i.min(j), i.max(j)))
+    }
+}
+
+/// A subset of the elements `0..n`
+///
+/// Moves add or remove a single element, identified by its index.
+///
+/// [`ArgminRandom::rand_from_range`] samples subsets which contain all elements of `min` and no
+/// elements which are not in `max`; every other element is included with probability `0.5`.
+///
+/// # Example
+///
+/// ```
+/// use argmin::core::discrete::{DiscreteParam, Subset};
+///
+/// let s = Subset::from_indices(4, &[1, 3]);
+/// let t = s.apply_move(&1);
+/// assert_eq!(t.indices(), vec![3]);
+/// assert_eq!(s.moves().len(), 4);
+/// ```
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+pub struct Subset(Vec<bool>);
+
+impl Subset {
+    /// Construct the empty subset of `n` elements
+    pub fn new(n: usize) -> Self {
+        Subset(vec![false; n])
+    }
+
+    /// Construct a subset of `n` elements containing the elements in `indices`
+    ///
+    /// # Panics
+    ///
+    /// Panics if an index is out of bounds.
+    pub fn from_indices(n: usize, indices: &[usize]) -> Self {
+        let mut selected = vec![false; n];
+        for &i in indices {
+            selected[i] = true;
+        }
+        Subset(selected)
+    }
+
+    /// Returns the number of elements of the ground set
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Returns `true` if the ground set has no elements
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Returns `true` if element `i` is in the subset
+    pub fn contains(&self, i: usize) -> bool {
+        self.0[i]
+    }
+
+    /// Returns the number of elements in the subset
+    pub fn count(&self) -> usize {
+        self.0.iter().filter(|s| **s).count()
+    }
+
+    /// Returns the indices of the elements in the subset
+    pub fn indices(&self) -> Vec<usize> {
+        (0..self.len()).filter(|i| self.0[*i]).collect()
+    }
+
+    /// Returns the membership of all elements
+    pub fn as_slice(&self) -> &[bool] {
+        &self.0
+    }
+}
+
+impl DiscreteParam for Subset {
+    type Move = usize;
+
+    fn moves(&self) -> Vec<Self::Move> {
+        (0..self.len()).collect()
+    }
+
+    fn apply_move(&self, mv: &Self::Move) -> Self {
+        let mut selected = self.0.clone();
+        selected[*mv] = !selected[*mv];
+        Subset(selected)
+    }
+
+    fn random_move<R: Rng>(&self, rng: &mut R) -> Option<Self::Move> {
+        (!self.is_empty()).then(|| rng.gen_range(0..self.len()))
+    }
+}
+
+impl ArgminRandom for Subset {
+    fn rand_from_range<R: Rng>(min: &Self, max: &Self, rng: &mut R) -> Self {
+        assert_eq!(min.len(), max.len());
+        Subset(
+            min.0
+                .iter()
+                .zip(max.0.iter())
+                .map(|(&lo, &hi)| lo || (hi && rng.gen::<bool>()))
+                .collect(),
+        )
+    }
+}
+
+/// A vector of categorical values
+///
+/// Each entry `i` takes one of the values `0..cardinalities[i]`. Moves `(i, v)` set entry `i` to
+/// the value `v`.
+///
+/// [`ArgminRandom::rand_from_range`] samples each entry uniformly between the corresponding
+/// entries of `min` and `max` (inclusive).
+///
+/// # Example
+///
+/// ```
+/// use argmin::core::discrete::{Categorical, DiscreteParam};
+/// # use argmin::core::Error;
+/// # fn main() -> Result<(), Error> {
+/// // three choices for the first entry, two for the second
+/// let c = Categorical::new(vec![0, 1], vec![3, 2])?;
+/// assert_eq!(c.moves(), vec![(0, 1), (0, 2), (1, 0)]);
+/// assert_eq!(c.apply_move(&(0, 2)).as_slice(), &[2, 1]);
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+pub struct Categorical {
+    /// Values of the entries
+    values: Vec<usize>,
+    /// Number of categories of each entry
+    cardinalities: Vec<usize>,
+}
+
+impl Categorical {
+    /// Construct a new categorical vector
+    ///
+    /// Returns an error if `values` and `cardinalities` differ in length or if a value is not
+    /// smaller than its cardinality.
+    pub fn new(values: Vec<usize>, cardinalities: Vec<usize>) -> Result<Self, Error> {
+        if values.len() != cardinalities.len() {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "`Categorical`: values and cardinalities must be of equal length."
+            ));
+        }
+        if values.iter().zip(cardinalities.iter()).any(|(v, c)| v >= c) {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "`Categorical`: values must be smaller than their cardinality."
+            ));
+        }
+        Ok(Categorical {
+            values,
+            cardinalities,
+        })
+    }
+
+    /// Construct a categorical vector with uniformly distributed values
+    ///
+    /// Entries with a cardinality of zero are set to zero.
+    pub fn random<R: Rng>(cardinalities: Vec<usize>, rng: &mut R) -> Self {
+        let values = cardinalities
+            .iter()
+            .map(|&c| if c > 0 { rng.gen_range(0..c) } else { 0 })
+            .collect();
+        Categorical {
+            values,
+            cardinalities,
+        }
+    }
+
+    /// Returns the number of entries
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    /// Returns `true` if there are no entries
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    /// Returns the values of the entries
+    pub fn as_slice(&self) -> &[usize] {
+        &self.values
+    }
+
+    /// Returns the number of categories of each entry
+    pub fn cardinalities(&self) -> &[usize] {
+        &self.cardinalities
+    }
+}
+
+impl DiscreteParam for Categorical {
+    type Move = (usize, usize);
+
+    fn moves(&self) -> Vec<Self::Move> {
+        self.values
+            .iter()
+            .zip(self.cardinalities.iter())
+            .enumerate()
+            .flat_map(|(i, (&v, &c))| (0..c).filter(move |u| *u != v).map(move |u| (i, u)))
+            .collect()
+    }
+
+    fn apply_move(&self, mv: &Self::Move) -> Self {
+        let mut values = self.values.clone();
+        values[mv.0] = mv.1;
+        Categorical {
+            values,
+            cardinalities: self.cardinalities.clone(),
+        }
+    }
+
+    fn random_move<R: Rng>(&self, rng: &mut R) -> Option<Self::Move> {
+        let changeable: Vec<usize> = (0..self.len())
+            .filter(|i| self.cardinalities[*i] > 1)
+            .collect();
+        let i = *changeable.choose(rng)?;
+        // draw a value different from the current one
+        let mut v = rng.gen_range(0..self.cardinalities[i] - 1);
+        if v >= self.values[i] {
+            v += 1;
+        }
+        Some((i, v))
+    }
+}
+
+impl ArgminRandom for Categorical {
+    fn rand_from_range<R: Rng>(min: &Self, max: &Self, rng: &mut R) -> Self {
+        assert_eq!(min.len(), max.len());
+        let values = min
+            .values
+            .iter()
+            .zip(max.values.iter())
+            .map(|(&lo, &hi)| rng.gen_range(lo.min(hi)..=lo.max(hi)))
+            .collect();
+        Categorical {
+            values,
+            cardinalities: max.cardinalities.clone(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::ArgminError;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    #[test]
+    fn test_permutation() {
+        assert_eq!(Permutation::new(3).as_slice(), &[0, 1, 2]);
+        assert_eq!(
+            Permutation::from_vec(vec![1, 2, 0]).unwrap().into_inner(),
+            vec![1, 2, 0]
+        );
+        for order in [vec![0, 0, 1], vec![0, 3, 1]] {
+            assert_error!(
+                Permutation::from_vec(order),
+                ArgminError,
+                "Invalid parameter: \"`Permutation`: each of the elements 0..n must occur exactly once.\""
+            );
+        }
+
+        let mut rng = StdRng::seed_from_u64(42);
+        let p = Permutation::random(5, &mut rng);
+        assert!(Permutation::from_vec(p.clone().into_inner()).is_ok());
+        assert_eq!(p.moves().len(), 10);
+        for _ in 0..50 {
+            let (i, j) = p.random_move(&mut rng).unwrap();
+            assert!(i < j && j < 5);
+            let q = p.apply_move(&(i, j));
+            assert_eq!(q.as_slice()[i], p.as_slice()[j]);
+            assert_eq!(q.as_slice()[j], p.as_slice()[i]);
+        }
+        assert!(Permutation::new(1).random_move(&mut rng).is_none());
+    }
+
+    #[test]
+    fn test_subset() {
+        let s = Subset::from_indices(5, &[0, 3]);
+        assert_eq!(s.len(), 5);
+        assert_eq!(s.count(), 2);
+        assert!(s.contains(3) && !s.contains(1));
+        assert_eq!(s.apply_move(&1).indices(), vec![0, 1, 3]);
+        assert_eq!(s.apply_move(&0).indices(), vec![3]);
+        assert_eq!(s.moves(), vec![0, 1, 2, 3, 4]);
+        assert!(Subset::new(0)
+            .random_move(&mut rand::thread_rng())
+            .is_none());
+
+        let mut rng = StdRng::seed_from_u64(42);
+        let min = Subset::from_indices(4, &[0]);
+        let max = Subset::from_indices(4, &[0, 1, 2]);
+        for _ in 0..50 {
+            let r = Subset::rand_from_range(&min, &max, &mut rng);
+            assert!(r.contains(0));
+            assert!(!r.contains(3));
+        }
+    }
+
+    #[test]
+    fn test_categorical() {
+        assert_error!(
+            Categorical::new(vec![0], vec![2, 2]),
+            ArgminError,
+            "Invalid parameter: \"`Categorical`: values and cardinalities must be of equal length.\""
+        );
+        assert_error!(
+            Categorical::new(vec![2], vec![2]),
+            ArgminError,
+            "Invalid parameter: \"`Categorical`: values must be smaller than their cardinality.\""
+        );
+
+        let c = Categorical::new(vec![1, 0, 0], vec![3, 1, 2]).unwrap();
+        assert_eq!(c.moves(), vec![(0, 0), (0, 2), (2, 1)]);
+
+        let mut rng = StdRng::seed_from_u64(42);
+        for _ in 0..50 {
+            let mv = c.random_move(&mut rng).unwrap();
+            assert!(c.moves().contains(&mv));
+        }
+        let fixed = Categorical::new(vec![0], vec![1]).unwrap();
+        assert!(fixed.random_move(&mut rng).is_none());
+
+        let r = Categorical::random(vec![3, 1, 2], &mut rng);
+        assert!(Categorical::new(r.as_slice().to_vec(), vec![3, 1, 2]).is_ok());
+
+        let min = Categorical::new(vec![1, 0, 0], vec![3, 1, 2]).unwrap();
+        let max = Categorical::new(vec![2, 0, 1], vec![3, 1, 2]).unwrap();
+        for _ in 0..50 {
+            let r = Categorical::rand_from_range(&min, &max, &mut rng);
+            assert!((1..=2).contains(&r.as_slice()[0]));
+            assert_eq!(r.as_slice()[1], 0);
+            assert_eq!(r.cardinalities(), &[3, 1, 2]);
+        }
+    }
+}
@@ -7,6 +7,7 @@
 
 //! # Errors
 
+use crate::core::Error;
 use thiserror::Error;
 
 /// Argmin error type
@@ -63,9 +64,182 @@ pub enum ArgminError {
     },
 }
 
+impl ArgminError {
+    /// Returns a machine-readable code identifying the kind of error
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use argmin::core::ArgminError;
+    ///
+    /// let err = ArgminError::InvalidParameter { text: "tolerance must be >= 0".to_string() };
+    /// assert_eq!(err.code(), "invalid_parameter");
+    /// ```
+    pub fn code(&self) -> &'static str {
+        match self {
+            ArgminError::InvalidParameter { .. } => "invalid_parameter",
+            ArgminError::NotImplemented { .. } => "not_implemented",
+            ArgminError::NotInitialized { .. } => "not_initialized",
+            ArgminError::ConditionViolated { .. } => "condition_violated",
+            ArgminError::CheckpointNotFound { .. } => "checkpoint_not_found",
+            ArgminError::PotentialBug { .. } => "potential_bug",
+            ArgminError::ImpossibleError { .. } => "impossible_error",
+        }
+    }
+
+    /// Returns the category of the error
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use argmin::core::{ArgminError, ErrorCategory};
+    ///
+    /// let err = ArgminError::ConditionViolated { text: "NaN encountered".to_string() };
+    /// assert_eq!(err.category(), ErrorCategory::NumericalFailure);
+    /// ```
+    pub fn category(&self) -> ErrorCategory {
+        match self {
+            ArgminError::InvalidParameter { .. } => ErrorCategory::InvalidParameter,
+            ArgminError::NotImplemented { .. } => ErrorCategory::NotImplemented,
+            ArgminError::NotInitialized { .. } => ErrorCategory::NotInitialized,
+            ArgminError::ConditionViolated { .. } => ErrorCategory::NumericalFailure,
+            ArgminError::CheckpointNotFound { .. } => ErrorCategory::Checkpoint,
+            ArgminError::PotentialBug { .. } | ArgminError::ImpossibleError { .. } => {
+                ErrorCategory::Internal
+            }
+        }
+    }
+}
+
+/// Category of an error
+///
+/// Allows applications to react to errors programmatically instead of parsing error messages.
+/// The category of an [`ArgminError`] is returned by [`ArgminError::category`], the category of
+/// any [`Error`](`struct@Error`) by [`ErrorCategory::of`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum ErrorCategory {
+    /// A parameter of a solver or of the executor is invalid
+    InvalidParameter,
+    /// A solver was run without the required initial values (parameter vector, gradient, ...)
+    NotInitialized,
+    /// A method required by the solver is not implemented by the problem
+    NotImplemented,
+    /// A numerical failure occurred, for instance a violated condition or a NaN
+    NumericalFailure,
+    /// Loading or storing a checkpoint failed
+    Checkpoint,
+    /// The error was raised outside of argmin, for instance by the math backend or by the
+    /// implementation of the problem
+    Backend,
+    /// An internal error, which is likely a bug
+    Internal,
+}
+
+impl ErrorCategory {
+    /// Returns the category of `err`
+    ///
+    /// The chain of causes is searched for an [`ArgminError`]. If there is none, the error was
+    /// raised outside of argmin and [`ErrorCategory::Backend`] is returned.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use argmin::core::{ArgminError, Error, ErrorCategory};
+    ///
+    /// let err: Error = ArgminError::NotInitialized { text: "no param".to_string() }.into();
+    /// assert_eq!(ErrorCategory::of(&err), ErrorCategory::NotInitialized);
+    ///
+    /// let err = Error::msg("Non-invertible matrix");
+    /// assert_eq!(ErrorCategory::of(&err), ErrorCategory::Backend);
+    /// ```
+    pub fn of(err: &Error) -> Self {
+        err.chain()
+            .find_map(|e| e.downcast_ref::<ArgminError>())
+            .map(ArgminError::category)
+            .unwrap_or(ErrorCategory::Backend)
+    }
+
+    /// Returns a machine-readable code of the category
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use argmin::core::ErrorCategory;
+    ///
+    /// assert_eq!(ErrorCategory::NumericalFailure.code(), "numerical_failure");
+    /// ```
+    pub fn code(&self) -> &'static str {
+        match self {
+            ErrorCategory::InvalidParameter => "invalid_parameter",
+            ErrorCategory::NotInitialized => "not_initialized",
+            ErrorCategory::NotImplemented => "not_implemented",
+            ErrorCategory::NumericalFailure => "numerical_failure",
+            ErrorCategory::Checkpoint => "checkpoint",
+            ErrorCategory::Backend => "backend",
+            ErrorCategory::Internal => "internal",
+        }
+    }
+}
+
+/// Context attached to errors returned by a solver while it is run by an
+/// [`Executor`](`crate::core::Executor`)
+///
+/// The underlying error remains accessible via `downcast_ref`, the context itself can be
+/// retrieved the same way.
+///
+/// # Example
+///
+/// ```
+/// use argmin::core::{ArgminError, Error, SolverErrorContext};
+///
+/// let err: Error = ArgminError::ConditionViolated { text: "NaN".to_string() }.into();
+/// let err = err.context(SolverErrorContext { solver: "L-BFGS".to_string(), iter: Some(3) });
+///
+/// assert_eq!(err.to_string(), "`L-BFGS` failed in iteration 3");
+/// let context = err.downcast_ref::<SolverErrorContext>().unwrap();
+/// assert_eq!(context.iter, Some(3));
+/// assert!(err.downcast_ref::<ArgminError>().is_some());
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct SolverErrorContext {
+    /// Name of the solver
+    pub solver: String,
+    /// Iteration in which the error occurred, `None` if it occurred during initialization
+    pub iter: Option<u64>,
+}
+
+impl std::fmt::Display for SolverErrorContext {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self.iter {
+            Some(iter) => write!(f, "`{}` failed in iteration {}", self.solver, iter),
+            None => write!(f, "`{}` failed during initialization", self.solver),
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
     send_sync_test!(error, ArgminError);
+    send_sync_test!(error_category, ErrorCategory);
+    send_sync_test!(solver_error_context, SolverErrorContext);
+
+    #[test]
+    fn test_category_of_context_error() {
+        let err: Error = ArgminError::InvalidParameter {
+            text: "x".to_string(),
+        }
+        .into();
+        let err = err.context(SolverErrorContext {
+            solver: "Solver".to_string(),
+            iter: None,
+        });
+        assert_eq!(ErrorCategory::of(&err), ErrorCategory::InvalidParameter);
+        assert_eq!(
+            format!("{err:#}"),
+            "`Solver` failed during initialization: Invalid parameter: \"x\""
+        );
+    }
 }
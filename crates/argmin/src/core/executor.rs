@@ -5,15 +5,25 @@
 // http://opensource.org/licenses/MIT>, at your option. This file may not be
 // copied, modified, or distributed except according to those terms.
 
-use crate::core::checkpointing::Checkpoint;
-use crate::core::observers::{Observe, ObserverMode, Observers};
+use crate::core::checkpointing::{Checkpoint, CheckpointingFrequency};
+use crate::core::observers::{Observe, ObserverErrorPolicy, ObserverMode, Observers};
 use crate::core::{
-    Error, OptimizationResult, Problem, Solver, State, TerminationReason, TerminationStatus, KV,
+    Archive, CapStep, CheckNumerics, ClipParam, CostFunction, Error, GradientNorm,
+    OptimizationResult, PenalizedProblem, Problem, RunInfo, Solver, SolverErrorContext, State,
+    StateWatch, TerminationReason, TerminationStatus, KV,
 };
-use std::sync::atomic::{AtomicBool, Ordering};
-use std::sync::Arc;
+#[cfg(all(feature = "ctrlc", not(target_arch = "wasm32")))]
+use std::sync::atomic::{AtomicU64, Ordering};
+#[cfg(all(feature = "ctrlc", not(target_arch = "wasm32")))]
+use std::sync::OnceLock;
 use web_time::Instant;
 
+/// Clips the parameter vector of a state into its bounds, see [`Executor::clip_params`]
+type ParamClipFn<O, I> = fn(&mut I, &mut Problem<O>) -> Result<Option<bool>, Error>;
+
+/// Caps the step of the parameter vector of a state, see [`Executor::max_step_norm`]
+type StepCapFn<O, I> = Box<dyn Fn(&mut I, &mut Problem<O>) -> Result<bool, Error>>;
+
 /// Solves an optimization problem with a solver
 pub struct Executor<O, S, I> {
     /// Solver
@@ -26,12 +36,45 @@ pub struct Executor<O, S, I> {
     observers: Observers<I>,
     /// Checkpoint
     checkpoint: Option<Box<dyn Checkpoint<S, I>>>,
+    /// Identifier and metadata of the run
+    run: RunInfo,
     /// Timeout
     timeout: Option<std::time::Duration>,
     /// Indicates whether Ctrl-C functionality should be active or not
     ctrlc: bool,
+    /// Returns the number of interrupts received so far
+    interrupt_count: fn() -> u64,
     /// Indicates whether to time execution or not
     timer: bool,
+    /// Checks the state for invalid numeric values after each iteration (if set)
+    numeric_check: Option<fn(&I) -> Option<&'static str>>,
+    /// Clips the parameter vector of the state into its bounds after each iteration (if set)
+    param_clip: Option<ParamClipFn<O, I>>,
+    /// Caps the norm of the step of the parameter vector after each iteration (if set)
+    step_cap: Option<StepCapFn<O, I>>,
+    /// Computes the norm of the gradient which is reported to the observers (if set)
+    gradient_norm: Option<fn(&I) -> Option<f64>>,
+    /// Adds the current parameter vector to the archive of the state after each iteration (if set)
+    archive_update: Option<fn(&mut I)>,
+    /// Publishes the scalar quantities of the state to the state watches (if requested)
+    watch: Option<Box<dyn Fn(&I)>>,
+    /// User callback which is called with the state before each iteration (if set)
+    before_iter: Option<Box<dyn FnMut(&mut I)>>,
+    /// User callback which is called with the state after each iteration (if set)
+    after_iter: Option<Box<dyn FnMut(&mut I)>>,
+    /// Reads a counter maintained by the problem which is added to the function counts (if set)
+    problem_count: Option<(&'static str, fn(&O) -> u64)>,
+    /// Progress of the run (`None` until the run is initialized)
+    progress: Option<Progress>,
+}
+
+/// Bookkeeping of a run which has been initialized
+#[derive(Clone, Copy)]
+struct Progress {
+    /// Start of the run (if timing is enabled)
+    total_time: Option<Instant>,
+    /// Number of interrupts received before the run (if Ctrl-C handling is enabled)
+    interrupts_at_start: Option<u64>,
 }
 
 impl<O, S, I> Executor<O, S, I>
@@ -68,9 +111,21 @@ where
             state,
             observers: Observers::new(),
             checkpoint: None,
+            run: RunInfo::new(),
             timeout: None,
             ctrlc: true,
+            interrupt_count,
             timer: false,
+            numeric_check: None,
+            param_clip: None,
+            step_cap: None,
+            gradient_norm: None,
+            archive_update: None,
+            watch: None,
+            before_iter: None,
+            after_iter: None,
+            problem_count: None,
+            progress: None,
         }
     }
 
@@ -78,7 +133,9 @@ where
     /// initializing the state before running the `Executor`. The options for initialization depend
     /// on the type of state used by the chosen solver. Common types of state are
     /// [`IterState`](`crate::core::IterState`),
-    /// [`PopulationState`](`crate::core::PopulationState`), and
+    /// [`PopulationState`](`crate::core::PopulationState`),
+    /// [`SimplexState`](`crate::core::SimplexState`),
+    /// [`ParetoState`](`crate::core::ParetoState`), and
     /// [`LinearProgramState`](`crate::core::LinearProgramState`). Please see the documentation of
     /// the desired solver for information about which state is used.
     ///
@@ -105,6 +162,45 @@ where
         self
     }
 
+    /// Checks whether the configured state is compatible with the solver, see
+    /// [`Solver::check_state`].
+    ///
+    /// This allows to detect a misconfigured state (for instance a missing initial parameter
+    /// vector) right after [`configure`](`Executor::configure`) rather than when the run starts.
+    /// [`run`](`Executor::run`) performs the same check, therefore calling this method is
+    /// optional.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::{ArgminError, Error, Executor, IterState};
+    /// # use argmin::core::test_utils::TestProblem;
+    /// use argmin::solver::linesearch::MoreThuenteLineSearch;
+    /// use argmin::solver::quasinewton::BFGS;
+    ///
+    /// # fn main() -> Result<(), Error> {
+    /// # let problem = TestProblem::new();
+    /// let solver = BFGS::new(MoreThuenteLineSearch::new());
+    ///
+    /// // BFGS requires an initial inverse Hessian
+    /// let res = Executor::new(problem, solver)
+    ///     .configure(|state: IterState<_, _, _, Vec<Vec<f64>>, _, f64>| {
+    ///         state.param(vec![1.0, 2.0]).max_iters(10)
+    ///     })
+    ///     .validate();
+    ///
+    /// assert!(matches!(
+    ///     res.err().unwrap().downcast_ref::<ArgminError>(),
+    ///     Some(ArgminError::NotInitialized { .. })
+    /// ));
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn validate(self) -> Result<Self, Error> {
+        self.solver.check_state(self.state.as_ref().unwrap())?;
+        Ok(self)
+    }
+
     /// Runs the executor by applying the solver to the optimization problem.
     ///
     /// # Example
@@ -129,12 +225,189 @@ where
     /// # Ok(())
     /// # }
     /// ```
-    pub fn run(mut self) -> Result<OptimizationResult<O, S, I>, Error> {
+    pub fn run(self) -> Result<OptimizationResult<O, S, I>, Error> {
+        self.execute(None)
+    }
+
+    /// Runs the executor within another solver and returns the `KV`s of the inner solver along
+    /// with the result.
+    ///
+    /// This is intended for solvers which internally run another solver, such as a line search
+    /// or the subproblem of a trust region method. CTRL-C handling is disabled. The returned
+    /// `KV` contains all key-value pairs returned by the inner solver in `init` and `next_iter`,
+    /// where later values take precedence. Together with
+    /// [`NestedTelemetry`](`crate::core::NestedTelemetry`) this allows the outer solver to
+    /// report the inner iterations to its own observers.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::{Error, Executor};
+    /// # use argmin::core::test_utils::{TestSolver, TestProblem};
+    /// #
+    /// # fn main() -> Result<(), Error> {
+    /// # let solver = TestSolver::new();
+    /// # let problem = TestProblem::new();
+    /// #
+    /// let (result, kv) = Executor::new(problem, solver)
+    ///     .configure(|state| state.param(vec![1.0f64, 0.0]).max_iters(10))
+    ///     .run_nested()?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn run_nested(self) -> Result<(OptimizationResult<O, S, I>, KV), Error> {
+        let mut kv = KV::new();
+        let result = self.ctrlc(false).execute(Some(&mut kv))?;
+        Ok((result, kv))
+    }
+
+    /// Like [`run_nested`](`Executor::run_nested`), but hands the problem back to the caller if
+    /// the inner solver fails. This allows the outer solver to recover from the failure of the
+    /// inner solver and to keep track of the function evaluations spent on the failed attempt.
+    pub(crate) fn try_run_nested(
+        self,
+    ) -> Result<(OptimizationResult<O, S, I>, KV), (Error, Problem<O>)> {
+        let mut executor = self.ctrlc(false);
+        let mut kv = KV::new();
+        match executor.execute_state(Some(&mut kv)) {
+            Ok(state) => Ok((executor.into_result(state), kv)),
+            Err(e) => Err((e, executor.problem)),
+        }
+    }
+
+    /// Runs the solver. If `recorded_kv` is given, the `KV`s returned by the solver are merged
+    /// into it.
+    fn execute(
+        mut self,
+        recorded_kv: Option<&mut KV>,
+    ) -> Result<OptimizationResult<O, S, I>, Error> {
+        let state = self.execute_state(recorded_kv)?;
+        Ok(self.into_result(state))
+    }
+
+    /// Assembles the result of a run from the final `state`.
+    fn into_result(self, state: I) -> OptimizationResult<O, S, I> {
+        let disabled_observers = self.observers.disabled();
+        let mut result = OptimizationResult::new(self.problem, self.solver, state);
+        result.disabled_observers = disabled_observers;
+        result
+    }
+
+    /// Runs the solver and returns the final state.
+    fn execute_state(&mut self, mut recorded_kv: Option<&mut KV>) -> Result<I, Error> {
+        self.init_internal(&mut recorded_kv)?;
+        while self.step_internal(&mut recorded_kv)?.is_some() {}
+        self.finish_state()
+    }
+
+    /// Initializes the run: Loads the checkpoint (if given), initializes the solver and evaluates
+    /// the stopping criteria of the initial state. Calling this method is optional, because
+    /// [`step`](`Executor::step`) initializes the run if needed. Subsequent calls have no effect.
+    ///
+    /// Together with [`step`](`Executor::step`) and [`finish`](`Executor::finish`) this allows
+    /// to drive the optimization one iteration at a time instead of calling
+    /// [`run`](`Executor::run`), for instance to interleave it with other computations. Observers,
+    /// checkpoints, hooks and all other options of the `Executor` behave like in
+    /// [`run`](`Executor::run`).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::{Error, Executor, State};
+    /// # use argmin::core::test_utils::{TestSolver, TestProblem};
+    /// #
+    /// # fn main() -> Result<(), Error> {
+    /// # let solver = TestSolver::new();
+    /// # let problem = TestProblem::new();
+    /// #
+    /// let mut executor = Executor::new(problem, solver)
+    ///     .configure(|state| state.param(vec![1.0f64, 0.0]).max_iters(10));
+    /// executor.init()?;
+    /// // `step` returns the key-value pairs of the iteration or `None` once the run has ended.
+    /// while let Some(kv) = executor.step()? {
+    ///     println!("iteration {}: {kv}", executor.state()?.get_iter());
+    /// }
+    /// let result = executor.finish()?;
+    /// # assert_eq!(result.state().get_iter(), 10);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn init(&mut self) -> Result<(), Error> {
+        self.init_internal(&mut None)
+    }
+
+    /// Performs a single iteration of the solver and returns the key-value pairs reported by the
+    /// solver for this iteration. Returns `None` without performing an iteration if the run has
+    /// already terminated or has been interrupted. The run is initialized during the first call
+    /// unless [`init`](`Executor::init`) has been called before.
+    ///
+    /// If the solver returns an error, the state is lost and the `Executor` cannot continue.
+    /// See [`init`](`Executor::init`) for an example.
+    pub fn step(&mut self) -> Result<Option<KV>, Error> {
+        self.step_internal(&mut None)
+    }
+
+    /// Ends a run which was driven by [`step`](`Executor::step`) and returns the result. Observers
+    /// are notified about the final state. A run which has not terminated yet (for instance
+    /// because `finish` is called early) keeps its termination status `NotTerminated`.
+    ///
+    /// See [`init`](`Executor::init`) for an example.
+    pub fn finish(mut self) -> Result<OptimizationResult<O, S, I>, Error> {
+        let state = self.finish_state()?;
+        Ok(self.into_result(state))
+    }
+
+    /// Returns the current state of the run.
+    ///
+    /// Fails if the solver returned an error during a previous call of
+    /// [`step`](`Executor::step`), because the state is lost in this case.
+    pub fn state(&self) -> Result<&I, Error> {
+        self.state.as_ref().ok_or_else(state_unavailable)
+    }
+
+    /// Takes the state out of the executor, see [`state`](`Executor::state`).
+    fn take_state(&mut self) -> Result<I, Error> {
+        self.state.take().ok_or_else(state_unavailable)
+    }
+
+    /// Evaluates the stopping criteria of the solver, unless the run has already terminated.
+    fn check_termination(&mut self, state: I) -> I {
+        // If the termination criteria were evaluated on a terminated state, a termination set
+        // within `next_iter()` could be overwritten!
+        if state.terminated() {
+            return state;
+        }
+        match self.solver.terminate_internal(&state) {
+            TerminationStatus::Terminated(reason) => state.terminate_with(reason),
+            TerminationStatus::NotTerminated => state,
+        }
+    }
+
+    /// Returns `true` if an interrupt was received since the run was initialized.
+    fn interrupted(&self) -> bool {
+        self.progress
+            .and_then(|progress| progress.interrupts_at_start)
+            .is_some_and(|start| (self.interrupt_count)() != start)
+    }
+
+    /// Initializes the run unless this has already happened, see [`init`](`Executor::init`).
+    fn init_internal(&mut self, recorded_kv: &mut Option<&mut KV>) -> Result<(), Error> {
+        if self.progress.is_some() {
+            return Ok(());
+        }
+
         // First, load checkpoint if given.
         if let Some(checkpoint) = self.checkpoint.as_ref() {
             if let Some((solver, state)) = checkpoint.load()? {
                 self.state = Some(state);
                 self.solver = solver;
+                // A resumed run keeps the identifier of the interrupted run.
+                if let Some(run) = checkpoint.load_run()? {
+                    self.run = self.run.clone().with_id(run.id());
+                }
+            }
+            if checkpoint.frequency() != CheckpointingFrequency::Never {
+                checkpoint.save_run(&self.run)?;
             }
         }
         let total_time = if self.timer {
@@ -143,144 +416,253 @@ where
             None
         };
 
-        let state = self.state.take().unwrap();
+        let state = self.take_state()?;
 
-        let interrupt = Arc::new(AtomicBool::new(false));
-
-        if self.ctrlc {
-            #[cfg(feature = "ctrlc")]
-            {
-                // Set up the Ctrl-C handler
-                let interp = interrupt.clone();
-                // This is currently a hack to allow checkpoints to be run again within the
-                // same program (usually not really a use case anyway). Unfortunately, this
-                // means that any subsequent run started afterwards will not have Ctrl-C
-                // handling available... This should also be a problem in case one tries to run
-                // two consecutive optimizations. There is ongoing work in the ctrlc crate
-                // (channels and such) which may solve this problem. So far, we have to live
-                // with this.
-                let handler = move || {
-                    interp.store(true, Ordering::SeqCst);
-                };
-                match ctrlc::set_handler(handler) {
-                    Err(ctrlc::Error::MultipleHandlers) => Ok(()),
-                    interp => interp,
-                }?;
-            }
-        }
+        // Interrupts are only taken into account if they are received during this run.
+        let interrupts_at_start = if self.ctrlc {
+            install_interrupt_handler()?;
+            Some((self.interrupt_count)())
+        } else {
+            None
+        };
+        self.progress = Some(Progress {
+            total_time,
+            interrupts_at_start,
+        });
 
         // Only call `init` of `solver` if the current iteration number is 0. This avoids that
         // `init` is called when starting from a checkpoint (because `init` could change the state
         // of the `solver`, which would overwrite the state restored from the checkpoint).
-        let mut state = if state.get_iter() == 0 {
-            let (mut state, kv) = self.solver.init(&mut self.problem, state)?;
-            state.update();
+        let state = if state.get_iter() == 0 {
+            self.solver
+                .check_state(&state)
+                .map_err(|e| self.solver_error(e, None))?;
+            let mut state = state;
+            self.clip_param(&mut state)?;
+            let (mut state, kv) = self
+                .solver
+                .init(&mut self.problem, state)
+                .map_err(|e| self.solver_error(e, None))?;
+            self.cap_step(&mut state)?;
+            record_kv(recorded_kv, &kv);
+            if let Some(quantity) = self.invalid_numeric_value(&state) {
+                state = state.terminate_with(TerminationReason::InvalidNumericValue(quantity));
+            } else {
+                state.update();
+                self.update_archive(&mut state);
+            }
 
             if !self.observers.is_empty() {
-                let kv = kv.unwrap_or(kv![]);
+                let kv = kv.unwrap_or(kv![]).merge(self.run.kv());
 
                 // Observe after init
                 self.observers
                     .observe_init(self.solver.name(), &state, &kv)?;
             }
 
+            self.update_problem_count();
             state.func_counts(&self.problem);
             state
         } else {
             state
         };
 
-        while !interrupt.load(Ordering::SeqCst) {
-            // check first if it has already terminated
-            // This should probably be solved better.
-            // First, check if it isn't already terminated. If it isn't, evaluate the
-            // stopping criteria. If `self.terminate()` is called without the checking
-            // whether it has terminated already, then it may overwrite a termination set
-            // within `next_iter()`!
-            state = if !state.terminated() {
-                let term = self.solver.terminate_internal(&state);
-                if let TerminationStatus::Terminated(reason) = term {
-                    state.terminate_with(reason)
-                } else {
-                    state
-                }
-            } else {
-                state
-            };
-            // Now check once more if the algorithm has terminated. If yes, then break.
-            if state.terminated() {
-                break;
-            }
+        self.publish(&state);
 
-            // Start time measurement
-            let start = if self.timer {
-                Some(Instant::now())
-            } else {
-                None
-            };
+        if !self.interrupted() {
+            self.state = Some(self.check_termination(state));
+        } else {
+            self.state = Some(state);
+        }
+        Ok(())
+    }
 
-            let (state_t, kv) = self.solver.next_iter(&mut self.problem, state)?;
-            state = state_t;
+    /// Performs a single iteration, see [`step`](`Executor::step`).
+    fn step_internal(&mut self, recorded_kv: &mut Option<&mut KV>) -> Result<Option<KV>, Error> {
+        self.init_internal(recorded_kv)?;
+        if self.interrupted() || self.state()?.terminated() {
+            return Ok(None);
+        }
+        let mut state = self.take_state()?;
 
-            state.func_counts(&self.problem);
+        // Start time measurement
+        let start = if self.timer {
+            Some(Instant::now())
+        } else {
+            None
+        };
 
-            // End time measurement
-            let duration = if self.timer {
-                Some(start.unwrap().elapsed())
-            } else {
-                None
-            };
+        if let Some(before_iter) = self.before_iter.as_mut() {
+            before_iter(&mut state);
+        }
 
-            state.update();
+        let iter = state.get_iter();
+        let (state_t, kv) = self
+            .solver
+            .next_iter(&mut self.problem, state)
+            .map_err(|e| self.solver_error(e, Some(iter)))?;
+        state = state_t;
+        let kv = match self.clip_param(&mut state)? {
+            Some(clipped) => Some(kv.unwrap_or_default().merge(kv!("clipped" => clipped;))),
+            None => kv,
+        };
+        let kv = match self.cap_step(&mut state)? {
+            Some(capped) => Some(kv.unwrap_or_default().merge(kv!("step_capped" => capped;))),
+            None => kv,
+        };
+        record_kv(recorded_kv, &kv);
+        let kv = kv.unwrap_or_default();
 
-            if !self.observers.is_empty() {
-                let mut log = if let Some(kv) = kv { kv } else { KV::new() };
-
-                if self.timer {
-                    let duration = duration.unwrap();
-                    let tmp = kv!(
-                        "time" => duration.as_secs_f64();
-                    );
-                    log = log.merge(tmp);
-                }
-                self.observers.observe_iter(&state, &log)?;
-            }
+        if let Some(after_iter) = self.after_iter.as_mut() {
+            after_iter(&mut state);
+        }
 
-            // increment iteration number
-            state.increment_iter();
+        self.update_problem_count();
+        state.func_counts(&self.problem);
 
-            if let Some(checkpoint) = self.checkpoint.as_ref() {
-                checkpoint.save_cond(&self.solver, &state, state.get_iter())?;
-            }
+        // Stop before the state is updated such that invalid values cannot become the best
+        // parameter vector.
+        if let Some(quantity) = self.invalid_numeric_value(&state) {
+            self.state =
+                Some(state.terminate_with(TerminationReason::InvalidNumericValue(quantity)));
+            return Ok(Some(kv));
+        }
+
+        // End time measurement
+        let duration = if self.timer {
+            Some(start.unwrap().elapsed())
+        } else {
+            None
+        };
+
+        state.update();
+        self.update_archive(&mut state);
+
+        if !self.observers.is_empty() {
+            let mut log = kv.clone();
 
             if self.timer {
-                // Increase accumulated total_time
-                total_time.map(|total_time| state.time(Some(total_time.elapsed())));
-
-                // If a timeout is set, check if timeout is reached
-                if let (Some(timeout), Some(total_time)) = (self.timeout, total_time) {
-                    if total_time.elapsed() > timeout {
-                        state = state.terminate_with(TerminationReason::Timeout);
-                    }
-                }
+                let duration = duration.unwrap();
+                let tmp = kv!(
+                    "time" => duration.as_secs_f64();
+                );
+                log = log.merge(tmp);
             }
+            if let Some(norm) = self.gradient_norm.and_then(|norm| norm(&state)) {
+                let tmp = kv!(
+                    "gradient_norm" => norm;
+                );
+                log = log.merge(tmp);
+            }
+            self.observers.observe_iter(&state, &log)?;
+        }
+
+        // increment iteration number
+        state.increment_iter();
+
+        self.publish(&state);
+
+        if let Some(checkpoint) = self.checkpoint.as_ref() {
+            checkpoint.save_cond(&self.solver, &state, state.get_iter())?;
+        }
+
+        if let Some(total_time) = self.progress.and_then(|progress| progress.total_time) {
+            // Increase accumulated total_time
+            state.time(Some(total_time.elapsed()));
 
-            // Check if termination occurred in the meantime
-            if state.terminated() {
-                break;
+            // If a timeout is set, check if timeout is reached
+            if let Some(timeout) = self.timeout {
+                if total_time.elapsed() > timeout {
+                    state = state.terminate_with(TerminationReason::Timeout);
+                }
             }
         }
 
-        if interrupt.load(Ordering::SeqCst) {
+        // Evaluate the stopping criteria for the next call, unless the run has been interrupted.
+        if !self.interrupted() {
+            state = self.check_termination(state);
+        }
+        self.state = Some(state);
+        Ok(Some(kv))
+    }
+
+    /// Ends the run and returns the final state, see [`finish`](`Executor::finish`).
+    fn finish_state(&mut self) -> Result<I, Error> {
+        let mut state = self.take_state()?;
+
+        if self.interrupted() {
             // Solver execution has been interrupted manually
             state = state.terminate_with(TerminationReason::Interrupt);
         }
 
+        self.publish(&state);
+
         if !self.observers.is_empty() {
             self.observers.observe_final(&state)?;
         }
 
-        Ok(OptimizationResult::new(self.problem, self.solver, state))
+        Ok(state)
+    }
+
+    /// Publishes the scalar quantities of the state to the state watches (if requested).
+    fn publish(&self, state: &I) {
+        if let Some(publish) = self.watch.as_ref() {
+            publish(state);
+        }
+    }
+
+    /// Attaches the name of the solver and the current iteration to an error returned by the
+    /// solver.
+    fn solver_error(&self, err: Error, iter: Option<u64>) -> Error {
+        err.context(SolverErrorContext {
+            solver: self.solver.name().to_string(),
+            iter,
+        })
+    }
+
+    /// Returns the name of the quantity holding an invalid numeric value if checking of numeric
+    /// values is enabled. States which have already terminated are not checked.
+    fn invalid_numeric_value(&self, state: &I) -> Option<String> {
+        if state.terminated() {
+            return None;
+        }
+        self.numeric_check
+            .and_then(|check| check(state))
+            .map(|quantity| quantity.to_string())
+    }
+
+    /// Clips the parameter vector of the state into its bounds if clipping is enabled. Returns
+    /// whether the parameter vector was clipped or `None` if clipping is not enabled.
+    fn clip_param(&mut self, state: &mut I) -> Result<Option<bool>, Error> {
+        match self.param_clip {
+            Some(clip) => clip(state, &mut self.problem),
+            None => Ok(None),
+        }
+    }
+
+    /// Caps the norm of the step of the parameter vector of the state if enabled. Returns whether
+    /// the step was capped or `None` if capping is not enabled.
+    fn cap_step(&mut self, state: &mut I) -> Result<Option<bool>, Error> {
+        match self.step_cap.as_ref() {
+            Some(cap) => cap(state, &mut self.problem).map(Some),
+            None => Ok(None),
+        }
+    }
+
+    /// Copies the counter maintained by the problem into the function counts (if enabled).
+    fn update_problem_count(&mut self) {
+        if let (Some((name, count)), Some(problem)) =
+            (self.problem_count, self.problem.problem.as_ref())
+        {
+            self.problem.counts.insert(name, count(problem));
+        }
+    }
+
+    /// Adds the current parameter vector to the archive of the state if the archive is enabled.
+    fn update_archive(&self, state: &mut I) {
+        if let Some(update) = self.archive_update {
+            update(state);
+        }
     }
 
     /// Adds an observer to the executor. Observers are required to implement the
@@ -317,6 +699,47 @@ where
         self
     }
 
+    /// Adds an observer with an [`ObserverErrorPolicy`] which defines how errors returned by the
+    /// observer are handled.
+    ///
+    /// Observers added via [`add_observer`](`Executor::add_observer`) abort the run on error
+    /// ([`ObserverErrorPolicy::FailFast`]). For optional telemetry, such as sending data to a
+    /// spectator over the network, [`ObserverErrorPolicy::WarnAndDisable`] or
+    /// [`ObserverErrorPolicy::Retry`] avoid that a failing observer aborts a long run. Observers
+    /// disabled due to `WarnAndDisable` are reported in
+    /// [`OptimizationResult::disabled_observers`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::{Error, Executor};
+    /// # use argmin::core::observers::{ObserverErrorPolicy, ObserverMode};
+    /// # use argmin::core::test_utils::{TestSolver, TestProblem};
+    /// # use argmin_observer_slog::SlogLogger;
+    /// #
+    /// # fn main() -> Result<(), Error> {
+    /// # let solver = TestSolver::new();
+    /// # let problem = TestProblem::new();
+    /// #
+    /// let executor = Executor::new(problem, solver).add_observer_with_policy(
+    ///     SlogLogger::term(),
+    ///     ObserverMode::Always,
+    ///     ObserverErrorPolicy::WarnAndDisable,
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn add_observer_with_policy<OBS: Observe<I> + 'static>(
+        mut self,
+        observer: OBS,
+        mode: ObserverMode,
+        policy: ObserverErrorPolicy,
+    ) -> Self {
+        self.observers.push_with_policy(observer, mode, policy);
+        self
+    }
+
     /// Configures checkpointing
     ///
     /// # Example
@@ -356,437 +779,1971 @@ where
         self
     }
 
-    /// Enables or disables CTRL-C handling (default: enabled). The CTRL-C handling gracefully
-    /// stops the solver if it is canceled via CTRL-C (SIGINT). Requires the optional `ctrlc`
-    /// feature to be set.
+    /// Replaces the randomly generated identifier of the run (a version 4 UUID) with a user
+    /// provided one.
     ///
-    /// Note that this does not work with nested `Executor`s. If a solver executes another solver
-    /// internally, the inner solver needs to disable CTRL-C handling.
+    /// The identifier is reported to the observers as `run.id` in `observe_init` and saved next to
+    /// checkpoints (see [`RunInfo`]).
     ///
     /// # Example
     ///
     /// ```
-    /// # use argmin::core::{Error, Executor};
+    /// # use argmin::core::Executor;
     /// # use argmin::core::test_utils::{TestSolver, TestProblem};
     /// #
-    /// # fn main() -> Result<(), Error> {
     /// # let solver = TestSolver::new();
     /// # let problem = TestProblem::new();
-    /// #
-    /// // Create instance of `Executor` with `problem` and `solver`
-    /// let executor = Executor::new(problem, solver).ctrlc(false);
-    /// # Ok(())
-    /// # }
+    /// let executor = Executor::new(problem, solver).run_id("experiment-42");
+    /// # assert_eq!(executor.run_info().id(), "experiment-42");
     /// ```
     #[must_use]
-    pub fn ctrlc(mut self, ctrlc: bool) -> Self {
-        self.ctrlc = ctrlc;
+    pub fn run_id<T: Into<String>>(mut self, id: T) -> Self {
+        self.run = self.run.with_id(id);
         self
     }
 
-    /// Enables or disables timing of individual iterations (default: false).
-    ///
-    /// In case a timeout is set, this will automatically be set to true.
+    /// Sets the name of the run, which is reported to the observers as `run.name` in
+    /// `observe_init` and saved next to checkpoints (see [`RunInfo`]).
     ///
     /// # Example
     ///
     /// ```
-    /// # use argmin::core::{Error, Executor};
+    /// # use argmin::core::Executor;
     /// # use argmin::core::test_utils::{TestSolver, TestProblem};
     /// #
-    /// # fn main() -> Result<(), Error> {
     /// # let solver = TestSolver::new();
     /// # let problem = TestProblem::new();
-    /// #
-    /// // Create instance of `Executor` with `problem` and `solver`
-    /// let executor = Executor::new(problem, solver).timer(false);
-    /// # Ok(())
-    /// # }
+    /// let executor = Executor::new(problem, solver).run_name("rosenbrock");
+    /// # assert_eq!(executor.run_info().get_name(), Some("rosenbrock"));
     /// ```
     #[must_use]
-    pub fn timer(mut self, timer: bool) -> Self {
-        if self.timeout.is_none() {
-            self.timer = timer;
-        }
+    pub fn run_name<T: Into<String>>(mut self, name: T) -> Self {
+        self.run = self.run.name(name);
         self
     }
 
-    /// Sets a timeout for the run.
-    ///
-    /// The optimization run is stopped once the timeout is exceeded. Note that the check is
-    /// performed after each iteration, therefore the actual runtime can exceed the the set
-    /// duration.
-    /// This also enables time measurements.
+    /// Adds a tag to the run. The tags are reported to the observers as comma separated list
+    /// `run.tags` in `observe_init` and saved next to checkpoints (see [`RunInfo`]).
     ///
     /// # Example
     ///
     /// ```
-    /// # use argmin::core::{Error, Executor};
+    /// # use argmin::core::Executor;
     /// # use argmin::core::test_utils::{TestSolver, TestProblem};
     /// #
-    /// # fn main() -> Result<(), Error> {
     /// # let solver = TestSolver::new();
     /// # let problem = TestProblem::new();
-    /// #
-    /// // Create instance of `Executor` with `problem` and `solver`
-    /// let executor = Executor::new(problem, solver).timeout(std::time::Duration::from_secs(30));
-    /// # Ok(())
-    /// # }
+    /// let executor = Executor::new(problem, solver)
+    ///     .run_tag("benchmark")
+    ///     .run_tag("nightly");
+    /// # assert_eq!(executor.run_info().get_tags().len(), 2);
     /// ```
     #[must_use]
-    pub fn timeout(mut self, timeout: std::time::Duration) -> Self {
-        self.timer = true;
-        self.timeout = Some(timeout);
+    pub fn run_tag<T: Into<String>>(mut self, tag: T) -> Self {
+        self.run = self.run.tag(tag);
         self
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use crate::core::test_utils::{TestProblem, TestSolver};
-    use crate::core::IterState;
-    use approx::assert_relative_eq;
+    /// Adds a key/value pair to the metadata of the run. Each entry is reported to the observers
+    /// as `run.metadata.<key>` in `observe_init` and saved next to checkpoints (see [`RunInfo`]).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::Executor;
+    /// # use argmin::core::test_utils::{TestSolver, TestProblem};
+    /// #
+    /// # let solver = TestSolver::new();
+    /// # let problem = TestProblem::new();
+    /// let executor = Executor::new(problem, solver).run_metadata("host", "node-1");
+    /// # assert_eq!(executor.run_info().get_metadata()["host"], "node-1");
+    /// ```
+    #[must_use]
+    pub fn run_metadata<K: Into<String>, V: Into<String>>(mut self, key: K, value: V) -> Self {
+        self.run = self.run.metadata(key, value);
+        self
+    }
 
-    #[test]
+    /// Returns the identifier and metadata of the run.
+    ///
+    /// Useful for storing the identifier of a run before it is started. Note that when resuming
+    /// from a checkpoint, the identifier is replaced with the one of the interrupted run once the
+    /// executor is run.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::Executor;
+    /// # use argmin::core::test_utils::{TestSolver, TestProblem};
+    /// #
+    /// # let solver = TestSolver::new();
+    /// # let problem = TestProblem::new();
+    /// let executor = Executor::new(problem, solver);
+    /// let run_id = executor.run_info().id().to_string();
+    /// ```
+    pub fn run_info(&self) -> &RunInfo {
+        &self.run
+    }
+
+    /// Enables or disables CTRL-C handling (default: enabled). The CTRL-C handling gracefully
+    /// stops the solver if the process receives an interrupt (`SIGINT`, `SIGTERM` or `SIGHUP`)
+    /// during the run. The run then terminates with [`TerminationReason::Interrupt`] and the
+    /// result holds the best parameter vector found so far. Requires the optional `ctrlc`
+    /// feature to be set and has no effect on `wasm32` targets. There is no separate
+    /// `KeyboardInterrupt` reason: it was renamed to `Interrupt` when `SIGTERM` and `SIGHUP`
+    /// were included, which the signal handler cannot tell apart from `SIGINT`.
+    ///
+    /// The signal handler is installed once per process and shared by all runs, therefore
+    /// consecutive as well as concurrent runs can be interrupted. If the application installs its
+    /// own signal handler before the first run, interrupts are left to the application.
+    ///
+    /// Solvers which execute another solver internally should disable CTRL-C handling of the
+    /// inner solver (as done by [`run_nested`](`Executor::run_nested`)), such that the
+    /// interrupt is handled by the outer `Executor`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::{Error, Executor};
+    /// # use argmin::core::test_utils::{TestSolver, TestProblem};
+    /// #
+    /// # fn main() -> Result<(), Error> {
+    /// # let solver = TestSolver::new();
+    /// # let problem = TestProblem::new();
+    /// #
+    /// // Create instance of `Executor` with `problem` and `solver`
+    /// let executor = Executor::new(problem, solver).ctrlc(false);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn ctrlc(mut self, ctrlc: bool) -> Self {
+        self.ctrlc = ctrlc;
+        self
+    }
+
+    /// Replaces the process-wide interrupt counter, such that tests can simulate interrupts
+    /// without affecting other runs.
+    #[cfg(all(test, feature = "ctrlc", not(target_arch = "wasm32")))]
+    fn interrupt_source(mut self, interrupt_count: fn() -> u64) -> Self {
+        self.interrupt_count = interrupt_count;
+        self
+    }
+
+    /// Enables or disables timing of individual iterations (default: false).
+    ///
+    /// In case a timeout is set, this will automatically be set to true.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::{Error, Executor};
+    /// # use argmin::core::test_utils::{TestSolver, TestProblem};
+    /// #
+    /// # fn main() -> Result<(), Error> {
+    /// # let solver = TestSolver::new();
+    /// # let problem = TestProblem::new();
+    /// #
+    /// // Create instance of `Executor` with `problem` and `solver`
+    /// let executor = Executor::new(problem, solver).timer(false);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn timer(mut self, timer: bool) -> Self {
+        if self.timeout.is_none() {
+            self.timer = timer;
+        }
+        self
+    }
+
+    /// Sets a timeout for the run.
+    ///
+    /// The optimization run is stopped once the timeout is exceeded. Note that the check is
+    /// performed after each iteration, therefore the actual runtime can exceed the the set
+    /// duration.
+    /// This also enables time measurements.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::{Error, Executor};
+    /// # use argmin::core::test_utils::{TestSolver, TestProblem};
+    /// #
+    /// # fn main() -> Result<(), Error> {
+    /// # let solver = TestSolver::new();
+    /// # let problem = TestProblem::new();
+    /// #
+    /// // Create instance of `Executor` with `problem` and `solver`
+    /// let executor = Executor::new(problem, solver).timeout(std::time::Duration::from_secs(30));
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn timeout(mut self, timeout: std::time::Duration) -> Self {
+        self.timer = true;
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Enables or disables checking for invalid numeric values (default: disabled).
+    ///
+    /// If enabled, the state is checked for NaN and infinite values after the initialization of
+    /// the solver and after each iteration (see [`CheckNumerics`] for which quantities are
+    /// checked). If such a value is encountered, the run is terminated with
+    /// [`TerminationReason::InvalidNumericValue`], which holds the name of the offending quantity.
+    /// Since the state is not updated in that case, the best parameter vector found so far is
+    /// not affected.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::{Error, Executor};
+    /// # use argmin::core::test_utils::{TestSolver, TestProblem};
+    /// #
+    /// # fn main() -> Result<(), Error> {
+    /// # let solver = TestSolver::new();
+    /// # let problem = TestProblem::new();
+    /// #
+    /// // Create instance of `Executor` with `problem` and `solver`
+    /// let executor = Executor::new(problem, solver).check_numerics(true);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn check_numerics(mut self, check: bool) -> Self
+    where
+        I: CheckNumerics,
+    {
+        self.numeric_check = if check {
+            Some(<I as CheckNumerics>::invalid_numeric_value)
+        } else {
+            None
+        };
+        self
+    }
+
+    /// Enables or disables clipping of parameter vectors into the bounds of the state (default:
+    /// disabled).
+    ///
+    /// If enabled, the initial parameter vector as well as every parameter vector produced by the
+    /// solver are clipped into the bounds of the state (see [`ClipParam`] and
+    /// [`IterState::bounds`](`crate::core::IterState::bounds`)). The cost function is evaluated
+    /// at every clipped parameter vector, such that the best parameter vector and the best cost
+    /// function value always belong together. Whether clipping occurred in the current iteration
+    /// is reported to the observers via the key `clipped`.
+    ///
+    /// This is a pragmatic safeguard for solvers which do not support bounds natively. Other
+    /// quantities computed by the solver, such as the gradient, still refer to the parameter
+    /// vector produced by the solver until they are updated in the next iteration. Without bounds
+    /// on the state, this option has no effect.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::{Error, Executor};
+    /// # use argmin::core::test_utils::{TestSolver, TestProblem};
+    /// #
+    /// # fn main() -> Result<(), Error> {
+    /// # let solver = TestSolver::new();
+    /// # let problem = TestProblem::new();
+    /// #
+    /// let executor = Executor::new(problem, solver)
+    ///     .configure(|state| state.param(vec![0.5, 0.5]).bounds(vec![0.0, 0.0], vec![1.0, 1.0]))
+    ///     .clip_params(true);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn clip_params(mut self, clip: bool) -> Self
+    where
+        I: ClipParam,
+        O: CostFunction<Param = I::Param, Output = I::Float>,
+    {
+        self.param_clip = if clip {
+            Some(<I as ClipParam>::clip_param::<O>)
+        } else {
+            None
+        };
+        self
+    }
+
+    /// Limits the norm of the step of the parameter vector per iteration to `max_norm` (must be
+    /// positive).
+    ///
+    /// If set, every step from the previous to the current parameter vector produced by the
+    /// solver whose norm exceeds `max_norm` is shortened to `max_norm`, keeping its direction (see
+    /// [`CapStep`]). Steps are capped after clipping (see
+    /// [`clip_params`](`Executor::clip_params`)). The cost function is evaluated at every capped
+    /// parameter vector, such that the best parameter vector and the best cost function value
+    /// always belong together. Whether the step was capped in the current iteration is reported to
+    /// the observers via the key `step_capped`.
+    ///
+    /// This guards against exploding steps caused by bad curvature estimates on stiff problems
+    /// and is intended for solvers which move a single parameter vector, such as gradient descent
+    /// and (quasi-)Newton methods. Other quantities computed by the solver, such as the gradient,
+    /// still refer to the parameter vector produced by the solver until they are updated in the
+    /// next iteration.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::{Error, Executor};
+    /// # use argmin::core::test_utils::{TestSolver, TestProblem};
+    /// #
+    /// # fn main() -> Result<(), Error> {
+    /// # let solver = TestSolver::new();
+    /// # let problem = TestProblem::new();
+    /// #
+    /// let executor = Executor::new(problem, solver).max_step_norm(0.5);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn max_step_norm(mut self, max_norm: I::Float) -> Self
+    where
+        I: CapStep,
+        O: CostFunction<Param = I::Param, Output = I::Float>,
+    {
+        self.step_cap = Some(Box::new(move |state, problem| {
+            state.cap_step(problem, max_norm)
+        }));
+        self
+    }
+
+    /// Enables or disables reporting the L2 norm of the gradient to the observers (default:
+    /// disabled).
+    ///
+    /// If enabled, the norm of the gradient of the state (see [`GradientNorm`]) is added to the
+    /// key-value pairs passed to the observers after each iteration under the key
+    /// `gradient_norm`. Nothing is reported in iterations in which the state holds no gradient.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::{Error, Executor, IterState};
+    /// # use argmin::core::test_utils::TestProblem;
+    /// # use argmin::solver::gradientdescent::SteepestDescent;
+    /// # use argmin::solver::linesearch::MoreThuenteLineSearch;
+    /// #
+    /// # fn main() -> Result<(), Error> {
+    /// # let problem = TestProblem::new();
+    /// # let solver = SteepestDescent::new(MoreThuenteLineSearch::new());
+    /// let executor = Executor::new(problem, solver).report_gradient_norm(true);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn report_gradient_norm(mut self, report: bool) -> Self
+    where
+        I: GradientNorm,
+    {
+        self.gradient_norm = if report {
+            Some(<I as GradientNorm>::gradient_norm)
+        } else {
+            None
+        };
+        self
+    }
+
+    /// Enables an archive of the best `k` distinct parameter vectors found during the run
+    /// (default: disabled).
+    ///
+    /// After the initialization of the solver and after each iteration, the current parameter
+    /// vector and its cost function value are added to the [`Archive`] of the state if they are
+    /// among the best `k` entries so far and the parameter vector is not yet in the archive. This
+    /// is useful for multimodal problems, where several distinct good solutions are of interest,
+    /// for instance when using Simulated Annealing or Particle Swarm Optimization. The archive
+    /// can be accessed via [`OptimizationResult::archive`].
+    ///
+    /// The archive is stored in the state, which therefore needs to support archives (see
+    /// [`State::set_archive`]). This is the case for
+    /// [`IterState`](`crate::core::IterState`),
+    /// [`PopulationState`](`crate::core::PopulationState`),
+    /// [`SimplexState`](`crate::core::SimplexState`) and
+    /// [`ParetoState`](`crate::core::ParetoState`).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::{Error, Executor};
+    /// # use argmin::core::test_utils::{TestSolver, TestProblem};
+    /// #
+    /// # fn main() -> Result<(), Error> {
+    /// # let solver = TestSolver::new();
+    /// # let problem = TestProblem::new();
+    /// #
+    /// let res = Executor::new(problem, solver)
+    ///     .configure(|state| state.param(vec![1.0, 2.0]).max_iters(10))
+    ///     .archive(5)
+    ///     .run()?;
+    ///
+    /// for (param, cost) in res.archive().unwrap().entries() {
+    ///     println!("{param:?}: {cost}");
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn archive(mut self, k: usize) -> Self
+    where
+        I::Param: Clone + PartialEq,
+    {
+        if let Some(state) = self.state.as_mut() {
+            state.set_archive(Archive::new(k));
+        }
+        self.archive_update = Some(update_archive::<I>);
+        self
+    }
+
+    /// Returns a thread-safe handle which exposes the iteration number, cost, best cost and
+    /// termination status of the state while the `Executor` is running. The handle is updated
+    /// after initialization, after each iteration and after the run has finished. This allows
+    /// applications such as GUIs or web servers to poll the progress of a run from another thread
+    /// without registering an observer. Each call returns a new handle; all of them are updated.
+    ///
+    /// See [`StateWatch`] for a complete example.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::{Error, Executor};
+    /// # use argmin::core::test_utils::{TestSolver, TestProblem};
+    /// #
+    /// # fn main() -> Result<(), Error> {
+    /// # let solver = TestSolver::new();
+    /// # let problem = TestProblem::new();
+    /// #
+    /// let mut executor = Executor::new(problem, solver)
+    ///     .configure(|state| state.param(vec![1.0f64, 0.0]).max_iters(10));
+    /// let watch = executor.state_watch();
+    ///
+    /// executor.run()?;
+    /// assert_eq!(watch.get().iter, 10);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn state_watch(&mut self) -> StateWatch<I::Float>
+    where
+        I: 'static,
+    {
+        let watch = StateWatch::new();
+        let published = watch.clone();
+        let previous = self.watch.take();
+        self.watch = Some(Box::new(move |state: &I| {
+            if let Some(previous) = previous.as_ref() {
+                previous(state);
+            }
+            published.update(state);
+        }));
+        watch
+    }
+
+    /// Registers a callback which is called with mutable access to the state right before each
+    /// call to the `next_iter` method of the solver.
+    ///
+    /// In contrast to observers, which only get to see the state, these callbacks may modify it.
+    /// This allows for enforcing custom constraints or adjusting quantities such as a trust
+    /// region radius without writing a wrapper solver. If called multiple times, the callbacks
+    /// are called in the order in which they were registered.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::{Error, Executor, State};
+    /// # use argmin::core::test_utils::{TestSolver, TestProblem};
+    /// #
+    /// # fn main() -> Result<(), Error> {
+    /// # let solver = TestSolver::new();
+    /// # let problem = TestProblem::new();
+    /// #
+    /// let res = Executor::new(problem, solver)
+    ///     .configure(|state| state.param(vec![1.0f64, 0.0]).max_iters(10))
+    ///     .on_before_iter(|state| {
+    ///         if let Some(param) = state.param.as_mut() {
+    ///             param[1] += 1.0;
+    ///         }
+    ///     })
+    ///     .run()?;
+    ///
+    /// assert_eq!(res.state.get_param().unwrap(), &vec![1.0, 10.0]);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn on_before_iter<F: FnMut(&mut I) + 'static>(mut self, hook: F) -> Self
+    where
+        I: 'static,
+    {
+        self.before_iter = Some(chain_hooks(self.before_iter.take(), hook));
+        self
+    }
+
+    /// Registers a callback which is called with mutable access to the state right after each
+    /// call to the `next_iter` method of the solver.
+    ///
+    /// The callback is called after parameter clipping and step capping, but before the state is
+    /// checked for invalid numeric values and before the best parameter vector is updated.
+    /// Modifications such as rounding of the parameter vector are therefore taken into account
+    /// by all subsequent steps and are seen by the observers. If called multiple times, the
+    /// callbacks are called in the order in which they were registered.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::{Error, Executor, State};
+    /// # use argmin::core::test_utils::{TestSolver, TestProblem};
+    /// #
+    /// # fn main() -> Result<(), Error> {
+    /// # let solver = TestSolver::new();
+    /// # let problem = TestProblem::new();
+    /// #
+    /// let res = Executor::new(problem, solver)
+    ///     .configure(|state| state.param(vec![1.4f64, -0.6]).max_iters(10))
+    ///     .on_after_iter(|state| {
+    ///         if let Some(param) = state.param.as_mut() {
+    ///             param.iter_mut().for_each(|x| *x = x.round());
+    ///         }
+    ///     })
+    ///     .run()?;
+    ///
+    /// assert_eq!(res.state.get_param().unwrap(), &vec![1.0, -1.0]);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn on_after_iter<F: FnMut(&mut I) + 'static>(mut self, hook: F) -> Self
+    where
+        I: 'static,
+    {
+        self.after_iter = Some(chain_hooks(self.after_iter.take(), hook));
+        self
+    }
+}
+
+impl<O, S, I> Executor<PenalizedProblem<O>, S, I>
+where
+    O: CostFunction,
+    S: Solver<PenalizedProblem<O>, I>,
+    I: State,
+{
+    /// Records the number of failed cost function evaluations of a [`PenalizedProblem`] in the
+    /// function counts of the state under the key `cost_failure_count` (default: disabled).
+    ///
+    /// Note that function counts are only tracked if counting is enabled in the state.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::{Error, Executor, PenalizedProblem};
+    /// # use argmin::core::test_utils::{TestSolver, TestProblem};
+    /// #
+    /// # fn main() -> Result<(), Error> {
+    /// # let solver = TestSolver::new();
+    /// let problem = PenalizedProblem::new(TestProblem::new(), f64::INFINITY);
+    /// let executor = Executor::new(problem, solver)
+    ///     .configure(|state| state.counting(true))
+    ///     .count_failures();
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn count_failures(mut self) -> Self {
+        self.problem_count = Some(("cost_failure_count", PenalizedProblem::failures));
+        self
+    }
+}
+
+/// Combines a previously registered callback (if any) with `hook` such that both are called in
+/// the order of registration
+fn chain_hooks<I: 'static>(
+    previous: Option<Box<dyn FnMut(&mut I)>>,
+    mut hook: impl FnMut(&mut I) + 'static,
+) -> Box<dyn FnMut(&mut I)> {
+    match previous {
+        Some(mut previous) => Box::new(move |state: &mut I| {
+            previous(state);
+            hook(state);
+        }),
+        None => Box::new(hook),
+    }
+}
+
+/// Number of interrupts (`SIGINT`, `SIGTERM` and `SIGHUP`) received by the process so far
+#[cfg(all(feature = "ctrlc", not(target_arch = "wasm32")))]
+static INTERRUPTS: AtomicU64 = AtomicU64::new(0);
+
+/// Installs the process-wide interrupt handler, which counts the received interrupts.
+///
+/// The handler is installed only once and shared by all runs, which therefore all support
+/// interrupts, including consecutive and concurrent runs. If the application has already
+/// installed its own handler, interrupts are left to the application.
+#[cfg(all(feature = "ctrlc", not(target_arch = "wasm32")))]
+fn install_interrupt_handler() -> Result<(), Error> {
+    static HANDLER: OnceLock<Result<(), String>> = OnceLock::new();
+    HANDLER
+        .get_or_init(|| {
+            let handler = || {
+                INTERRUPTS.fetch_add(1, Ordering::SeqCst);
+            };
+            match ctrlc::set_handler(handler) {
+                Ok(()) | Err(ctrlc::Error::MultipleHandlers) => Ok(()),
+                Err(e) => Err(e.to_string()),
+            }
+        })
+        .clone()
+        .map_err(Error::msg)
+}
+
+/// Interrupts are not supported without the `ctrlc` feature or on `wasm32`
+#[cfg(not(all(feature = "ctrlc", not(target_arch = "wasm32"))))]
+fn install_interrupt_handler() -> Result<(), Error> {
+    Ok(())
+}
+
+/// Returns the number of interrupts received by the process so far
+#[cfg(all(feature = "ctrlc", not(target_arch = "wasm32")))]
+fn interrupt_count() -> u64 {
+    INTERRUPTS.load(Ordering::SeqCst)
+}
+
+/// Interrupts are not supported without the `ctrlc` feature or on `wasm32`
+#[cfg(not(all(feature = "ctrlc", not(target_arch = "wasm32"))))]
+fn interrupt_count() -> u64 {
+    0
+}
+
+/// Adds the current parameter vector of `state` to its archive (if enabled and accepted)
+fn update_archive<I>(state: &mut I)
+where
+    I: State,
+    I::Param: Clone + PartialEq,
+{
+    let cost = state.get_cost();
+    let param = match (state.get_param(), state.get_archive()) {
+        (Some(param), Some(archive)) if archive.accepts(param, cost) => param.clone(),
+        _ => return,
+    };
+    if let Some(archive) = state.get_archive_mut() {
+        archive.insert(param, cost);
+    }
+}
+
+/// Error returned if the state of the `Executor` has been lost due to an error of the solver
+fn state_unavailable() -> Error {
+    argmin_error!(
+        PotentialBug,
+        "`Executor`: State is not available after the solver returned an error."
+    )
+}
+
+/// Merges `kv` into `recorded_kv` if both are present
+fn record_kv(recorded_kv: &mut Option<&mut KV>, kv: &Option<KV>) {
+    if let (Some(recorded), Some(kv)) = (recorded_kv, kv) {
+        **recorded = std::mem::take(*recorded).merge(kv.clone());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::test_utils::{TestProblem, TestSolver};
+    use crate::core::IterState;
+    use approx::assert_relative_eq;
+
+    #[test]
     fn test_update() {
         let problem = TestProblem::new();
-        let solver = TestSolver::new();
+        let solver = TestSolver::new();
+
+        let mut executor = Executor::new(problem, solver).configure(
+            |config: IterState<Vec<f64>, (), (), (), (), f64>| config.param(vec![0.0, 0.0]),
+        );
+
+        // 1) Parameter vector changes, but not cost (continues to be `Inf`)
+        let new_param = vec![1.0, 1.0];
+        executor.state = Some(executor.state.take().unwrap().param(new_param.clone()));
+        executor.state.as_mut().unwrap().update();
+        assert_eq!(
+            *executor.state.as_ref().unwrap().get_best_param().unwrap(),
+            new_param
+        );
+        assert!(executor
+            .state
+            .as_ref()
+            .unwrap()
+            .get_best_cost()
+            .is_infinite());
+        assert!(executor
+            .state
+            .as_ref()
+            .unwrap()
+            .get_best_cost()
+            .is_sign_positive());
+
+        // 2) Parameter vector and cost changes to something better
+        let new_param = vec![2.0, 2.0];
+        let new_cost = 10.0;
+        executor.state = Some(
+            executor
+                .state
+                .take()
+                .unwrap()
+                .param(new_param.clone())
+                .cost(new_cost),
+        );
+        executor.state.as_mut().unwrap().update();
+        assert_eq!(
+            *executor.state.as_ref().unwrap().get_best_param().unwrap(),
+            new_param
+        );
+        assert_relative_eq!(
+            executor.state.as_ref().unwrap().get_best_cost(),
+            new_cost,
+            epsilon = f64::EPSILON
+        );
+
+        // 3) Parameter vector and cost changes to something worse
+        let old_param = executor
+            .state
+            .as_ref()
+            .unwrap()
+            .get_best_param()
+            .unwrap()
+            .clone();
+        let new_param = vec![3.0, 3.0];
+        let old_cost = executor.state.as_ref().unwrap().get_best_cost();
+        let new_cost = old_cost + 1.0;
+        executor.state = Some(
+            executor
+                .state
+                .take()
+                .unwrap()
+                .param(new_param)
+                .cost(new_cost),
+        );
+        executor.state.as_mut().unwrap().update();
+        assert_eq!(
+            executor
+                .state
+                .as_ref()
+                .unwrap()
+                .get_best_param()
+                .unwrap()
+                .clone(),
+            old_param
+        );
+        assert_relative_eq!(
+            executor.state.as_ref().unwrap().get_best_cost(),
+            old_cost,
+            epsilon = f64::EPSILON
+        );
+
+        // 4) `-Inf` is better than `Inf`
+        let solver = TestSolver {};
+        let mut executor = Executor::new(problem, solver).configure(
+            |config: IterState<Vec<f64>, (), (), (), (), f64>| config.param(vec![0.0, 0.0]),
+        );
+
+        let new_param = vec![1.0, 1.0];
+        let new_cost = f64::NEG_INFINITY;
+        executor.state = Some(
+            executor
+                .state
+                .take()
+                .unwrap()
+                .param(new_param.clone())
+                .cost(new_cost),
+        );
+        executor.state.as_mut().unwrap().update();
+        assert_eq!(
+            *executor.state.as_ref().unwrap().get_best_param().unwrap(),
+            new_param
+        );
+        assert!(executor
+            .state
+            .as_ref()
+            .unwrap()
+            .get_best_cost()
+            .is_infinite());
+        assert!(executor
+            .state
+            .as_ref()
+            .unwrap()
+            .get_best_cost()
+            .is_sign_negative());
+
+        // 5) `Inf` is worse than `-Inf`
+        let old_param = executor
+            .state
+            .as_ref()
+            .unwrap()
+            .get_best_param()
+            .unwrap()
+            .clone();
+        let new_param = vec![6.0, 6.0];
+        let new_cost = f64::INFINITY;
+        executor.state = Some(
+            executor
+                .state
+                .take()
+                .unwrap()
+                .param(new_param)
+                .cost(new_cost),
+        );
+        executor.state.as_mut().unwrap().update();
+        assert_eq!(
+            executor
+                .state
+                .as_ref()
+                .unwrap()
+                .get_best_param()
+                .unwrap()
+                .clone(),
+            old_param
+        );
+        assert!(executor
+            .state
+            .as_ref()
+            .unwrap()
+            .get_best_cost()
+            .is_infinite());
+        assert!(executor
+            .state
+            .as_ref()
+            .unwrap()
+            .get_best_cost()
+            .is_sign_negative());
+    }
+
+    /// The solver's `init` should not be called when started from a checkpoint.
+    /// See https://github.com/argmin-rs/argmin/issues/199.
+    #[test]
+    #[cfg(feature = "serde1")]
+    fn test_checkpointing_solver_initialization() {
+        use std::cell::RefCell;
+
+        use crate::core::{
+            checkpointing::CheckpointingFrequency, test_utils::TestProblem, ArgminFloat,
+            CostFunction,
+        };
+        use serde::{Deserialize, Serialize};
+
+        #[derive(Clone)]
+        pub struct FakeCheckpoint {
+            pub frequency: CheckpointingFrequency,
+            pub solver: RefCell<Option<OptimizationAlgorithm>>,
+            pub state: RefCell<Option<IterState<Vec<f64>, (), (), (), (), f64>>>,
+        }
+
+        impl Checkpoint<OptimizationAlgorithm, IterState<Vec<f64>, (), (), (), (), f64>>
+            for FakeCheckpoint
+        {
+            fn save(
+                &self,
+                solver: &OptimizationAlgorithm,
+                state: &IterState<Vec<f64>, (), (), (), (), f64>,
+            ) -> Result<(), Error> {
+                *self.solver.borrow_mut() = Some(solver.clone());
+                *self.state.borrow_mut() = Some(state.clone());
+                Ok(())
+            }
+
+            fn load(
+                &self,
+            ) -> Result<
+                Option<(
+                    OptimizationAlgorithm,
+                    IterState<Vec<f64>, (), (), (), (), f64>,
+                )>,
+                Error,
+            > {
+                if self.solver.borrow().is_none() {
+                    return Ok(None);
+                }
+                Ok(Some((
+                    self.solver.borrow().clone().unwrap(),
+                    self.state.borrow().clone().unwrap(),
+                )))
+            }
+
+            fn frequency(&self) -> CheckpointingFrequency {
+                self.frequency
+            }
+        }
+
+        // Fake optimization algorithm which holds internal state which changes over time
+        #[derive(Clone, Serialize, Deserialize)]
+        struct OptimizationAlgorithm {
+            pub internal_state: u64,
+        }
+
+        // Implement Solver for OptimizationAlgorithm
+        impl<O, P, F> Solver<O, IterState<P, (), (), (), (), F>> for OptimizationAlgorithm
+        where
+            O: CostFunction<Param = P, Output = F>,
+            P: Clone,
+            F: ArgminFloat,
+        {
+            fn name(&self) -> &str {
+                "OptimizationAlgorithm"
+            }
+
+            // Only resets internal_state to 1
+            fn init(
+                &mut self,
+                _problem: &mut Problem<O>,
+                state: IterState<P, (), (), (), (), F>,
+            ) -> Result<(IterState<P, (), (), (), (), F>, Option<KV>), Error> {
+                self.internal_state = 1;
+                Ok((state, None))
+            }
+
+            // Increment internal_state
+            fn next_iter(
+                &mut self,
+                _problem: &mut Problem<O>,
+                state: IterState<P, (), (), (), (), F>,
+            ) -> Result<(IterState<P, (), (), (), (), F>, Option<KV>), Error> {
+                self.internal_state += 1;
+                Ok((state, None))
+            }
+
+            // Avoid terminating early because param does not change
+            fn terminate(&mut self, _state: &IterState<P, (), (), (), (), F>) -> TerminationStatus {
+                TerminationStatus::NotTerminated
+            }
+
+            // Avoid terminating early because param does not change
+            fn terminate_internal(
+                &mut self,
+                state: &IterState<P, (), (), (), (), F>,
+            ) -> TerminationStatus {
+                if state.get_iter() >= state.get_max_iters() {
+                    TerminationStatus::Terminated(TerminationReason::MaxItersReached)
+                } else {
+                    TerminationStatus::NotTerminated
+                }
+            }
+        }
+
+        // Create random test problem
+        let problem = TestProblem::new();
+
+        // solver instance
+        let solver = OptimizationAlgorithm { internal_state: 0 };
+
+        // Create a checkpoint
+        let checkpoint = FakeCheckpoint {
+            frequency: CheckpointingFrequency::Always,
+            solver: RefCell::new(None),
+            state: RefCell::new(None),
+        };
+
+        // Create and run executor
+        let executor = Executor::new(problem, solver)
+            .configure(|state| state.param(vec![1.0f64, 1.0]).max_iters(10))
+            .checkpointing(checkpoint.clone());
+
+        let OptimizationResult { solver, .. } = executor.run().unwrap();
+
+        // internal_state should be 11
+        // (1 from init plus 10 iterations where it is incremented by 1)
+        assert_eq!(solver.internal_state, 11);
+
+        // Create and run solver again
+        let executor = Executor::new(problem, solver)
+            .configure(|state| state.param(vec![1.0f64, 1.0]).max_iters(10))
+            .checkpointing(checkpoint);
+
+        let OptimizationResult { solver, .. } = executor.run().unwrap();
+
+        // internal_state should still be 11
+        // (1 from init plus 10 iterations where it is incremented by 1)
+        assert_eq!(solver.internal_state, 11);
+
+        // Delete old checkpointing file
+        let _ = std::fs::remove_file(".checkpoints/init_test.arg");
+    }
+
+    #[test]
+    fn test_run_info() {
+        use crate::core::checkpointing::CheckpointingFrequency;
+        use crate::core::observers::Observe;
+        use std::sync::{Arc, Mutex};
+
+        type TState = IterState<Vec<f64>, (), (), (), (), f64>;
+
+        // Observer which records the KV passed to `observe_init`
+        #[derive(Clone, Default)]
+        struct Recorder(Arc<Mutex<Option<KV>>>);
+
+        impl Observe<TState> for Recorder {
+            fn observe_init(&mut self, _name: &str, _state: &TState, kv: &KV) -> Result<(), Error> {
+                *self.0.lock().unwrap() = Some(kv.clone());
+                Ok(())
+            }
+        }
+
+        // Checkpoint which only stores the run information
+        #[derive(Clone)]
+        struct RunCheckpoint {
+            frequency: CheckpointingFrequency,
+            resume: bool,
+            saved: Arc<Mutex<Option<RunInfo>>>,
+        }
+
+        impl Checkpoint<TestSolver, TState> for RunCheckpoint {
+            fn save(&self, _solver: &TestSolver, _state: &TState) -> Result<(), Error> {
+                Ok(())
+            }
+
+            fn load(&self) -> Result<Option<(TestSolver, TState)>, Error> {
+                Ok(self.resume.then(|| {
+                    (
+                        TestSolver::new(),
+                        IterState::new().param(vec![1.0, 0.0]).max_iters(3),
+                    )
+                }))
+            }
+
+            fn frequency(&self) -> CheckpointingFrequency {
+                self.frequency
+            }
+
+            fn save_run(&self, run: &RunInfo) -> Result<(), Error> {
+                *self.saved.lock().unwrap() = Some(run.clone());
+                Ok(())
+            }
+
+            fn load_run(&self) -> Result<Option<RunInfo>, Error> {
+                Ok(Some(RunInfo::new().with_id("interrupted")))
+            }
+        }
+
+        let recorder = Recorder::default();
+        let checkpoint = RunCheckpoint {
+            frequency: CheckpointingFrequency::Always,
+            resume: false,
+            saved: Arc::new(Mutex::new(None)),
+        };
+        let executor = Executor::new(TestProblem::new(), TestSolver::new())
+            .configure(|state| state.param(vec![1.0f64, 0.0]).max_iters(3))
+            .add_observer(recorder.clone(), ObserverMode::Always)
+            .checkpointing(checkpoint.clone())
+            .run_name("test")
+            .run_tag("a")
+            .run_metadata("key", "value");
+        let run = executor.run_info().clone();
+        assert_eq!(run.id().len(), 36);
+        executor.run().unwrap();
+
+        let kv = recorder.0.lock().unwrap().take().unwrap();
+        assert_eq!(kv.get("run.id").unwrap().get_string().unwrap(), run.id());
+        assert_eq!(kv.get("run.name").unwrap().get_string().unwrap(), "test");
+        assert_eq!(kv.get("run.tags").unwrap().get_string().unwrap(), "a");
+        assert_eq!(
+            kv.get("run.metadata.key").unwrap().get_string().unwrap(),
+            "value"
+        );
+        assert_eq!(checkpoint.saved.lock().unwrap().as_ref(), Some(&run));
+
+        // A resumed run takes over the identifier of the interrupted run
+        let checkpoint = RunCheckpoint {
+            frequency: CheckpointingFrequency::Always,
+            resume: true,
+            saved: Arc::new(Mutex::new(None)),
+        };
+        Executor::new(TestProblem::new(), TestSolver::new())
+            .checkpointing(checkpoint.clone())
+            .run_name("resumed")
+            .run()
+            .unwrap();
+        let saved = checkpoint.saved.lock().unwrap().take().unwrap();
+        assert_eq!(saved.id(), "interrupted");
+        assert_eq!(saved.get_name(), Some("resumed"));
+
+        // Run information is not saved if checkpointing is disabled
+        let checkpoint = RunCheckpoint {
+            frequency: CheckpointingFrequency::Never,
+            resume: false,
+            saved: Arc::new(Mutex::new(None)),
+        };
+        Executor::new(TestProblem::new(), TestSolver::new())
+            .configure(|state| state.param(vec![1.0f64, 0.0]).max_iters(3))
+            .checkpointing(checkpoint.clone())
+            .run()
+            .unwrap();
+        assert!(checkpoint.saved.lock().unwrap().is_none());
+    }
+
+    #[test]
+    fn test_check_numerics() {
+        use crate::core::{ArgminFloat, CostFunction};
+
+        // Solver which produces a NaN parameter vector in the third iteration
+        #[derive(Clone)]
+        struct NanSolver {}
+
+        impl<O, F> Solver<O, IterState<Vec<F>, (), (), (), (), F>> for NanSolver
+        where
+            O: CostFunction<Param = Vec<F>, Output = F>,
+            F: ArgminFloat,
+        {
+            fn name(&self) -> &str {
+                "NanSolver"
+            }
+
+            fn next_iter(
+                &mut self,
+                _problem: &mut Problem<O>,
+                state: IterState<Vec<F>, (), (), (), (), F>,
+            ) -> Result<(IterState<Vec<F>, (), (), (), (), F>, Option<KV>), Error> {
+                let iter = F::from_u64(state.get_iter()).unwrap();
+                let param = if state.get_iter() == 2 {
+                    vec![F::nan(), F::nan()]
+                } else {
+                    vec![iter, iter]
+                };
+                Ok((state.param(param).cost(iter), None))
+            }
+
+            fn terminate(
+                &mut self,
+                _state: &IterState<Vec<F>, (), (), (), (), F>,
+            ) -> TerminationStatus {
+                TerminationStatus::NotTerminated
+            }
+        }
+
+        // Without checks, the solver runs until the maximum number of iterations is reached
+        let res = Executor::new(TestProblem::new(), NanSolver {})
+            .configure(|state| state.param(vec![1.0f64, 1.0]).max_iters(5))
+            .run()
+            .unwrap();
+        assert_eq!(
+            res.state.get_termination_reason(),
+            Some(&TerminationReason::MaxItersReached)
+        );
+
+        let res = Executor::new(TestProblem::new(), NanSolver {})
+            .configure(|state| state.param(vec![1.0f64, 1.0]).max_iters(5))
+            .check_numerics(true)
+            .run()
+            .unwrap();
+        assert_eq!(
+            res.state.get_termination_reason(),
+            Some(&TerminationReason::InvalidNumericValue("param".to_string()))
+        );
+        assert_eq!(res.state.get_iter(), 2);
+        assert_eq!(res.state.get_best_param().unwrap(), &vec![0.0, 0.0]);
+
+        // Invalid initial parameter vectors are detected as well
+        let res = Executor::new(TestProblem::new(), NanSolver {})
+            .configure(|state| state.param(vec![f64::INFINITY, 1.0]).max_iters(5))
+            .check_numerics(true)
+            .run()
+            .unwrap();
+        assert_eq!(
+            res.state.get_termination_reason(),
+            Some(&TerminationReason::InvalidNumericValue("param".to_string()))
+        );
+        assert_eq!(res.state.get_iter(), 0);
+    }
 
-        let mut executor = Executor::new(problem, solver).configure(
-            |config: IterState<Vec<f64>, (), (), (), (), f64>| config.param(vec![0.0, 0.0]),
+    #[test]
+    fn test_report_gradient_norm() {
+        use crate::core::observers::Observe;
+        use std::sync::{Arc, Mutex};
+
+        type GradState = IterState<Vec<f64>, Vec<f64>, (), (), (), f64>;
+
+        // Solver which sets the gradient from the second iteration on
+        #[derive(Clone)]
+        struct GradSolver {}
+
+        impl<O> Solver<O, GradState> for GradSolver {
+            fn name(&self) -> &str {
+                "GradSolver"
+            }
+
+            fn next_iter(
+                &mut self,
+                _problem: &mut Problem<O>,
+                state: GradState,
+            ) -> Result<(GradState, Option<KV>), Error> {
+                let state = if state.get_iter() > 0 {
+                    state.gradient(vec![3.0, 4.0])
+                } else {
+                    state
+                };
+                Ok((state, None))
+            }
+        }
+
+        // Observer which records the reported gradient norms
+        #[derive(Clone, Default)]
+        struct Recorder(Arc<Mutex<Vec<Option<f64>>>>);
+
+        impl Observe<GradState> for Recorder {
+            fn observe_iter(&mut self, _state: &GradState, kv: &KV) -> Result<(), Error> {
+                let norm = kv.get("gradient_norm").and_then(|v| v.get_float());
+                self.0.lock().unwrap().push(norm);
+                Ok(())
+            }
+        }
+
+        let recorder = Recorder::default();
+        Executor::new(TestProblem::new(), GradSolver {})
+            .configure(|state| state.max_iters(3))
+            .add_observer(recorder.clone(), ObserverMode::Always)
+            .run()
+            .unwrap();
+        assert_eq!(*recorder.0.lock().unwrap(), vec![None, None, None]);
+
+        let recorder = Recorder::default();
+        Executor::new(TestProblem::new(), GradSolver {})
+            .configure(|state| state.max_iters(3))
+            .add_observer(recorder.clone(), ObserverMode::Always)
+            .report_gradient_norm(true)
+            .run()
+            .unwrap();
+        assert_eq!(
+            *recorder.0.lock().unwrap(),
+            vec![None, Some(5.0), Some(5.0)]
         );
+    }
 
-        // 1) Parameter vector changes, but not cost (continues to be `Inf`)
-        let new_param = vec![1.0, 1.0];
-        executor.state = Some(executor.state.take().unwrap().param(new_param.clone()));
-        executor.state.as_mut().unwrap().update();
+    #[test]
+    fn test_state_watch() {
+        use crate::core::observers::Observe;
+        use crate::core::StateWatch;
+        use std::sync::{Arc, Mutex};
+
+        type TState = IterState<Vec<f64>, (), (), (), (), f64>;
+
+        // Observer which records the iteration numbers published to the watch
+        struct Recorder {
+            watch: StateWatch<f64>,
+            iters: Arc<Mutex<Vec<u64>>>,
+        }
+
+        impl Observe<TState> for Recorder {
+            fn observe_iter(&mut self, _state: &TState, _kv: &KV) -> Result<(), Error> {
+                self.iters.lock().unwrap().push(self.watch.get().iter);
+                Ok(())
+            }
+        }
+
+        let mut executor = Executor::new(TestProblem::new(), TestSolver::new())
+            .configure(|state| state.param(vec![1.0, 0.0]).max_iters(3));
+        let watch = executor.state_watch();
+        let iters = Arc::new(Mutex::new(vec![]));
+        let recorder = Recorder {
+            watch: executor.state_watch(),
+            iters: iters.clone(),
+        };
+        assert_eq!(watch.get().iter, 0);
+        assert!(!watch.get().termination_status.terminated());
+
+        let res = executor
+            .add_observer(recorder, ObserverMode::Always)
+            .run()
+            .unwrap();
+
+        assert_eq!(*iters.lock().unwrap(), vec![0, 1, 2]);
+        let snapshot = watch.get();
+        assert_eq!(snapshot.iter, 3);
         assert_eq!(
-            *executor.state.as_ref().unwrap().get_best_param().unwrap(),
-            new_param
+            snapshot.best_cost.to_ne_bytes(),
+            res.state.get_best_cost().to_ne_bytes()
         );
-        assert!(executor
-            .state
-            .as_ref()
-            .unwrap()
-            .get_best_cost()
-            .is_infinite());
-        assert!(executor
-            .state
-            .as_ref()
-            .unwrap()
-            .get_best_cost()
-            .is_sign_positive());
+        assert_eq!(
+            snapshot.termination_status,
+            TerminationStatus::Terminated(TerminationReason::MaxItersReached)
+        );
+    }
 
-        // 2) Parameter vector and cost changes to something better
-        let new_param = vec![2.0, 2.0];
-        let new_cost = 10.0;
-        executor.state = Some(
-            executor
-                .state
-                .take()
-                .unwrap()
-                .param(new_param.clone())
-                .cost(new_cost),
+    #[test]
+    fn test_iter_hooks() {
+        use std::cell::RefCell;
+        use std::rc::Rc;
+
+        let calls = Rc::new(RefCell::new(vec![]));
+        let (c1, c2, c3) = (calls.clone(), calls.clone(), calls.clone());
+        let res = Executor::new(TestProblem::new(), TestSolver::new())
+            .configure(|state| state.param(vec![1.0, 0.0]).max_iters(2))
+            .on_before_iter(move |state| c1.borrow_mut().push(("before1", state.get_iter())))
+            .on_after_iter(move |state| {
+                c2.borrow_mut().push(("after", state.get_iter()));
+                state.param.as_mut().unwrap()[1] -= 1.0;
+            })
+            .on_before_iter(move |state| c3.borrow_mut().push(("before2", state.get_iter())))
+            .run()
+            .unwrap();
+
+        assert_eq!(
+            *calls.borrow(),
+            vec![
+                ("before1", 0),
+                ("before2", 0),
+                ("after", 0),
+                ("before1", 1),
+                ("before2", 1),
+                ("after", 1),
+            ]
         );
-        executor.state.as_mut().unwrap().update();
+        assert_eq!(res.state.get_param().unwrap(), &vec![1.0, -2.0]);
+    }
+
+    #[test]
+    fn test_stepping() {
+        use crate::core::observers::Observe;
+        use std::sync::{Arc, Mutex};
+
+        type TState = IterState<Vec<f64>, (), (), (), (), f64>;
+
+        // Observer which records which of its methods were called
+        struct Recorder(Arc<Mutex<Vec<&'static str>>>);
+
+        impl Observe<TState> for Recorder {
+            fn observe_init(
+                &mut self,
+                _name: &str,
+                _state: &TState,
+                _kv: &KV,
+            ) -> Result<(), Error> {
+                self.0.lock().unwrap().push("init");
+                Ok(())
+            }
+
+            fn observe_iter(&mut self, _state: &TState, _kv: &KV) -> Result<(), Error> {
+                self.0.lock().unwrap().push("iter");
+                Ok(())
+            }
+
+            fn observe_final(&mut self, _state: &TState) -> Result<(), Error> {
+                self.0.lock().unwrap().push("final");
+                Ok(())
+            }
+        }
+
+        let calls = Arc::new(Mutex::new(vec![]));
+        let mut executor = Executor::new(TestProblem::new(), TestSolver::new())
+            .configure(|state| state.param(vec![1.0, 0.0]).max_iters(2))
+            .add_observer(Recorder(calls.clone()), ObserverMode::Always);
+        executor.init().unwrap();
+        executor.init().unwrap();
+        assert_eq!(*calls.lock().unwrap(), vec!["init"]);
+        assert_eq!(executor.state().unwrap().get_iter(), 0);
+
+        assert!(executor.step().unwrap().is_some());
+        assert_eq!(executor.state().unwrap().get_iter(), 1);
+        assert!(!executor.state().unwrap().terminated());
+        assert!(executor.step().unwrap().is_some());
         assert_eq!(
-            *executor.state.as_ref().unwrap().get_best_param().unwrap(),
-            new_param
+            executor.state().unwrap().get_termination_status(),
+            &TerminationStatus::Terminated(TerminationReason::MaxItersReached)
         );
-        assert_relative_eq!(
-            executor.state.as_ref().unwrap().get_best_cost(),
-            new_cost,
-            epsilon = f64::EPSILON
+        assert!(executor.step().unwrap().is_none());
+        assert_eq!(executor.state().unwrap().get_iter(), 2);
+
+        let res = executor.finish().unwrap();
+        assert_eq!(
+            *calls.lock().unwrap(),
+            vec!["init", "iter", "iter", "final"]
         );
+        assert_eq!(res.state.get_iter(), 2);
+
+        // `step` initializes the run if needed and `finish` may be called early.
+        let mut executor = Executor::new(TestProblem::new(), TestSolver::new())
+            .configure(|state| state.param(vec![1.0, 0.0]).max_iters(10));
+        assert!(executor.step().unwrap().is_some());
+        let res = executor.finish().unwrap();
+        assert_eq!(res.state.get_iter(), 1);
+        assert!(!res.state.terminated());
+    }
 
-        // 3) Parameter vector and cost changes to something worse
-        let old_param = executor
-            .state
-            .as_ref()
-            .unwrap()
-            .get_best_param()
-            .unwrap()
-            .clone();
-        let new_param = vec![3.0, 3.0];
-        let old_cost = executor.state.as_ref().unwrap().get_best_cost();
-        let new_cost = old_cost + 1.0;
-        executor.state = Some(
-            executor
-                .state
-                .take()
+    #[test]
+    fn test_stepping_after_error() {
+        #[derive(Clone)]
+        struct FailingSolver {}
+
+        impl<O> Solver<O, IterState<Vec<f64>, (), (), (), (), f64>> for FailingSolver {
+            fn name(&self) -> &str {
+                "FailingSolver"
+            }
+
+            fn next_iter(
+                &mut self,
+                _problem: &mut Problem<O>,
+                _state: IterState<Vec<f64>, (), (), (), (), f64>,
+            ) -> Result<(IterState<Vec<f64>, (), (), (), (), f64>, Option<KV>), Error> {
+                Err(argmin_error!(ConditionViolated, "failed"))
+            }
+        }
+
+        let mut executor = Executor::new(TestProblem::new(), FailingSolver {})
+            .configure(|state| state.param(vec![1.0]).max_iters(10));
+        executor.init().unwrap();
+        assert!(executor.state().is_ok());
+        assert!(executor.step().is_err());
+        assert!(executor.state().is_err());
+        assert!(executor.step().is_err());
+        assert!(executor.finish().is_err());
+    }
+
+    #[test]
+    fn test_timeout() {
+        let solver = TestSolver::new();
+        let problem = TestProblem::new();
+        let timeout = std::time::Duration::from_secs(2);
+
+        let executor = Executor::new(problem, solver).timer(true);
+        assert!(executor.timer);
+        assert!(executor.timeout.is_none());
+
+        let executor = Executor::new(problem, solver).timer(false);
+        assert!(!executor.timer);
+        assert!(executor.timeout.is_none());
+
+        let executor = Executor::new(problem, solver).timeout(timeout);
+        assert!(executor.timer);
+        assert_eq!(executor.timeout, Some(timeout));
+
+        let executor = Executor::new(problem, solver).timeout(timeout).timer(false);
+        assert!(executor.timer);
+        assert_eq!(executor.timeout, Some(timeout));
+
+        let executor = Executor::new(problem, solver).timer(false).timeout(timeout);
+        assert!(executor.timer);
+        assert_eq!(executor.timeout, Some(timeout));
+    }
+
+    #[test]
+    fn test_archive() {
+        use crate::core::CostFunction;
+
+        // Solver which cycles through the parameter vectors [0], [1], ..., [5] with cost |x - 3|
+        #[derive(Clone)]
+        struct CyclingSolver {}
+
+        impl<O> Solver<O, IterState<Vec<f64>, (), (), (), (), f64>> for CyclingSolver
+        where
+            O: CostFunction<Param = Vec<f64>, Output = f64>,
+        {
+            fn name(&self) -> &str {
+                "CyclingSolver"
+            }
+
+            fn next_iter(
+                &mut self,
+                _problem: &mut Problem<O>,
+                state: IterState<Vec<f64>, (), (), (), (), f64>,
+            ) -> Result<(IterState<Vec<f64>, (), (), (), (), f64>, Option<KV>), Error> {
+                let x = (state.get_iter() % 6) as f64;
+                Ok((state.param(vec![x]).cost((x - 3.0).abs()), None))
+            }
+        }
+
+        let res = Executor::new(TestProblem::new(), CyclingSolver {})
+            .configure(|state| state.param(vec![10.0f64]).cost(7.0).max_iters(20))
+            .run()
+            .unwrap();
+        assert!(res.archive().is_none());
+
+        let res = Executor::new(TestProblem::new(), CyclingSolver {})
+            .configure(|state| state.param(vec![10.0f64]).cost(7.0).max_iters(20))
+            .archive(3)
+            .run()
+            .unwrap();
+        let archive = res.archive().unwrap();
+        assert_eq!(archive.capacity(), 3);
+        assert_eq!(
+            archive.entries(),
+            &[(vec![3.0], 0.0), (vec![2.0], 1.0), (vec![4.0], 1.0)]
+        );
+    }
+
+    /// Cost function `(x - 4)^2` with a single parameter
+    struct ShiftedSquare {}
+
+    impl CostFunction for ShiftedSquare {
+        type Param = Vec<f64>;
+        type Output = f64;
+
+        fn cost(&self, param: &Self::Param) -> Result<Self::Output, Error> {
+            Ok((param[0] - 4.0).powi(2))
+        }
+    }
+
+    #[test]
+    fn test_clip_params() {
+        // Solver which increases the parameter by one in each iteration
+        #[derive(Clone)]
+        struct StepSolver {}
+
+        impl<O> Solver<O, IterState<Vec<f64>, (), (), (), (), f64>> for StepSolver
+        where
+            O: CostFunction<Param = Vec<f64>, Output = f64>,
+        {
+            fn name(&self) -> &str {
+                "StepSolver"
+            }
+
+            fn next_iter(
+                &mut self,
+                problem: &mut Problem<O>,
+                mut state: IterState<Vec<f64>, (), (), (), (), f64>,
+            ) -> Result<(IterState<Vec<f64>, (), (), (), (), f64>, Option<KV>), Error> {
+                let param = vec![state.take_param().unwrap()[0] + 1.0];
+                let cost = problem.cost(&param)?;
+                Ok((state.param(param).cost(cost), None))
+            }
+        }
+
+        let run = |clip: bool, iters: u64| {
+            Executor::new(ShiftedSquare {}, StepSolver {})
+                .configure(|state| {
+                    state
+                        .param(vec![-3.0f64])
+                        .bounds(vec![-0.5], vec![2.5])
+                        .max_iters(iters)
+                })
+                .clip_params(clip)
+                .run_nested()
                 .unwrap()
-                .param(new_param)
-                .cost(new_cost),
+        };
+
+        // Without clipping, bounds have no effect
+        let (res, kv) = run(false, 10);
+        assert_eq!(
+            res.state.get_param().unwrap()[0].to_ne_bytes(),
+            7.0f64.to_ne_bytes()
         );
-        executor.state.as_mut().unwrap().update();
+        assert!(kv.get("clipped").is_none());
+
+        // The initial parameter vector is clipped to -0.5
+        let (res, kv) = run(true, 2);
         assert_eq!(
-            executor
-                .state
-                .as_ref()
-                .unwrap()
-                .get_best_param()
-                .unwrap()
-                .clone(),
-            old_param
+            res.state.get_param().unwrap()[0].to_ne_bytes(),
+            1.5f64.to_ne_bytes()
         );
-        assert_relative_eq!(
-            executor.state.as_ref().unwrap().get_best_cost(),
-            old_cost,
-            epsilon = f64::EPSILON
+        assert_eq!(kv.get("clipped").unwrap().get_bool(), Some(false));
+
+        let (res, kv) = run(true, 10);
+        assert_eq!(
+            res.state.get_param().unwrap()[0].to_ne_bytes(),
+            2.5f64.to_ne_bytes()
         );
+        assert_eq!(kv.get("clipped").unwrap().get_bool(), Some(true));
 
-        // 4) `-Inf` is better than `Inf`
-        let solver = TestSolver {};
-        let mut executor = Executor::new(problem, solver).configure(
-            |config: IterState<Vec<f64>, (), (), (), (), f64>| config.param(vec![0.0, 0.0]),
+        // The cost function is evaluated at the clipped parameter vectors, hence the best cost
+        // function value belongs to the best parameter vector and not to the proposed 3.5.
+        assert_eq!(
+            res.state.get_best_param().unwrap()[0].to_ne_bytes(),
+            2.5f64.to_ne_bytes()
+        );
+        assert_eq!(
+            res.state.get_best_cost().to_ne_bytes(),
+            2.25f64.to_ne_bytes()
         );
+        assert_eq!(res.state.get_cost().to_ne_bytes(), 2.25f64.to_ne_bytes());
+    }
 
-        let new_param = vec![1.0, 1.0];
-        let new_cost = f64::NEG_INFINITY;
-        executor.state = Some(
-            executor
-                .state
-                .take()
-                .unwrap()
-                .param(new_param.clone())
-                .cost(new_cost),
+    #[test]
+    fn test_max_step_norm() {
+        // Solver which increases the parameter by `2 * (iter + 1)` in each iteration
+        #[derive(Clone)]
+        struct StepSolver {}
+
+        impl<O> Solver<O, IterState<Vec<f64>, (), (), (), (), f64>> for StepSolver
+        where
+            O: CostFunction<Param = Vec<f64>, Output = f64>,
+        {
+            fn name(&self) -> &str {
+                "StepSolver"
+            }
+
+            fn next_iter(
+                &mut self,
+                problem: &mut Problem<O>,
+                mut state: IterState<Vec<f64>, (), (), (), (), f64>,
+            ) -> Result<(IterState<Vec<f64>, (), (), (), (), f64>, Option<KV>), Error> {
+                let step = 2.0 * (state.get_iter() + 1) as f64;
+                let param = vec![state.take_param().unwrap()[0] + step];
+                let cost = problem.cost(&param)?;
+                Ok((state.param(param).cost(cost), None))
+            }
+        }
+
+        let run = |max_step_norm: Option<f64>, iters: u64| {
+            let executor = Executor::new(ShiftedSquare {}, StepSolver {})
+                .configure(|state| state.param(vec![0.0f64]).max_iters(iters));
+            match max_step_norm {
+                Some(max_norm) => executor.max_step_norm(max_norm),
+                None => executor,
+            }
+            .run_nested()
+            .unwrap()
+        };
+
+        let (res, kv) = run(None, 3);
+        assert_eq!(
+            res.state.get_param().unwrap()[0].to_ne_bytes(),
+            12.0f64.to_ne_bytes()
         );
-        executor.state.as_mut().unwrap().update();
+        assert!(kv.get("step_capped").is_none());
+
+        // Steps of 2 and 4 are not capped
+        let (res, kv) = run(Some(4.0), 2);
         assert_eq!(
-            *executor.state.as_ref().unwrap().get_best_param().unwrap(),
-            new_param
+            res.state.get_param().unwrap()[0].to_ne_bytes(),
+            6.0f64.to_ne_bytes()
         );
-        assert!(executor
-            .state
-            .as_ref()
-            .unwrap()
-            .get_best_cost()
-            .is_infinite());
-        assert!(executor
-            .state
-            .as_ref()
-            .unwrap()
-            .get_best_cost()
-            .is_sign_negative());
+        assert_eq!(kv.get("step_capped").unwrap().get_bool(), Some(false));
 
-        // 5) `Inf` is worse than `-Inf`
-        let old_param = executor
-            .state
-            .as_ref()
-            .unwrap()
-            .get_best_param()
-            .unwrap()
-            .clone();
-        let new_param = vec![6.0, 6.0];
-        let new_cost = f64::INFINITY;
-        executor.state = Some(
-            executor
-                .state
-                .take()
-                .unwrap()
-                .param(new_param)
-                .cost(new_cost),
+        // Steps of 2, 4 and 6 become 2, 3 and 3
+        let (res, kv) = run(Some(3.0), 3);
+        assert_eq!(
+            res.state.get_param().unwrap()[0].to_ne_bytes(),
+            8.0f64.to_ne_bytes()
         );
-        executor.state.as_mut().unwrap().update();
+        assert_eq!(kv.get("step_capped").unwrap().get_bool(), Some(true));
+
+        // The cost function is evaluated at the capped parameter vectors: The best parameter
+        // vector is 5 instead of the proposed 6 and the best cost function value belongs to it.
+        let best_param = res.state.get_best_param().unwrap();
+        assert_eq!(best_param[0].to_ne_bytes(), 5.0f64.to_ne_bytes());
         assert_eq!(
-            executor
-                .state
-                .as_ref()
-                .unwrap()
-                .get_best_param()
-                .unwrap()
-                .clone(),
-            old_param
+            res.state.get_best_cost().to_ne_bytes(),
+            ShiftedSquare {}.cost(best_param).unwrap().to_ne_bytes()
         );
-        assert!(executor
-            .state
-            .as_ref()
-            .unwrap()
-            .get_best_cost()
-            .is_infinite());
-        assert!(executor
-            .state
-            .as_ref()
-            .unwrap()
-            .get_best_cost()
-            .is_sign_negative());
     }
 
-    /// The solver's `init` should not be called when started from a checkpoint.
-    /// See https://github.com/argmin-rs/argmin/issues/199.
+    #[cfg(all(feature = "ctrlc", not(target_arch = "wasm32")))]
     #[test]
-    #[cfg(feature = "serde1")]
-    fn test_checkpointing_solver_initialization() {
-        use std::cell::RefCell;
+    fn test_interrupt() {
+        use crate::core::CostFunction;
 
-        use crate::core::{
-            checkpointing::CheckpointingFrequency, test_utils::TestProblem, ArgminFloat,
-            CostFunction,
-        };
-        use serde::{Deserialize, Serialize};
+        // Interrupts of this test, which are kept separate from the process-wide counter to
+        // avoid interrupting other tests running concurrently
+        static TEST_INTERRUPTS: AtomicU64 = AtomicU64::new(0);
 
-        #[derive(Clone)]
-        pub struct FakeCheckpoint {
-            pub frequency: CheckpointingFrequency,
-            pub solver: RefCell<Option<OptimizationAlgorithm>>,
-            pub state: RefCell<Option<IterState<Vec<f64>, (), (), (), (), f64>>>,
+        fn test_interrupt_count() -> u64 {
+            TEST_INTERRUPTS.load(Ordering::SeqCst)
         }
 
-        impl Checkpoint<OptimizationAlgorithm, IterState<Vec<f64>, (), (), (), (), f64>>
-            for FakeCheckpoint
+        // Solver which decreases the cost by one in each iteration and simulates an interrupt in
+        // the third iteration
+        #[derive(Clone)]
+        struct InterruptedSolver {}
+
+        impl<O> Solver<O, IterState<Vec<f64>, (), (), (), (), f64>> for InterruptedSolver
+        where
+            O: CostFunction<Param = Vec<f64>, Output = f64>,
         {
-            fn save(
-                &self,
-                solver: &OptimizationAlgorithm,
-                state: &IterState<Vec<f64>, (), (), (), (), f64>,
-            ) -> Result<(), Error> {
-                *self.solver.borrow_mut() = Some(solver.clone());
-                *self.state.borrow_mut() = Some(state.clone());
-                Ok(())
+            fn name(&self) -> &str {
+                "InterruptedSolver"
             }
 
-            fn load(
-                &self,
-            ) -> Result<
-                Option<(
-                    OptimizationAlgorithm,
-                    IterState<Vec<f64>, (), (), (), (), f64>,
-                )>,
-                Error,
-            > {
-                if self.solver.borrow().is_none() {
-                    return Ok(None);
+            fn next_iter(
+                &mut self,
+                _problem: &mut Problem<O>,
+                state: IterState<Vec<f64>, (), (), (), (), f64>,
+            ) -> Result<(IterState<Vec<f64>, (), (), (), (), f64>, Option<KV>), Error> {
+                if state.get_iter() == 2 {
+                    TEST_INTERRUPTS.fetch_add(1, Ordering::SeqCst);
                 }
-                Ok(Some((
-                    self.solver.borrow().clone().unwrap(),
-                    self.state.borrow().clone().unwrap(),
-                )))
-            }
-
-            fn frequency(&self) -> CheckpointingFrequency {
-                self.frequency
+                let x = -(state.get_iter() as f64);
+                Ok((state.param(vec![x]).cost(x), None))
             }
         }
 
-        // Fake optimization algorithm which holds internal state which changes over time
-        #[derive(Clone, Serialize, Deserialize)]
-        struct OptimizationAlgorithm {
-            pub internal_state: u64,
+        // Interrupts are handled in consecutive runs
+        for _ in 0..2 {
+            let res = Executor::new(TestProblem::new(), InterruptedSolver {})
+                .interrupt_source(test_interrupt_count)
+                .configure(|state| state.param(vec![1.0]).cost(1.0).max_iters(10))
+                .run()
+                .unwrap();
+            assert_eq!(
+                res.state.get_termination_reason(),
+                Some(&TerminationReason::Interrupt)
+            );
+            assert_eq!(res.state.get_iter(), 3);
+            assert_eq!(
+                res.state.get_best_param().unwrap()[0].to_ne_bytes(),
+                (-2.0f64).to_ne_bytes()
+            );
         }
 
-        // Implement Solver for OptimizationAlgorithm
-        impl<O, P, F> Solver<O, IterState<P, (), (), (), (), F>> for OptimizationAlgorithm
-        where
-            O: CostFunction<Param = P, Output = F>,
-            P: Clone,
-            F: ArgminFloat,
-        {
+        // Interrupts received before the run are ignored
+        let res = Executor::new(TestProblem::new(), InterruptedSolver {})
+            .interrupt_source(test_interrupt_count)
+            .configure(|state| state.param(vec![1.0]).cost(1.0).max_iters(2))
+            .run()
+            .unwrap();
+        assert_eq!(
+            res.state.get_termination_reason(),
+            Some(&TerminationReason::MaxItersReached)
+        );
+
+        // Without CTRL-C handling, interrupts are ignored
+        let res = Executor::new(TestProblem::new(), InterruptedSolver {})
+            .interrupt_source(test_interrupt_count)
+            .configure(|state| state.param(vec![1.0]).cost(1.0).max_iters(10))
+            .ctrlc(false)
+            .run()
+            .unwrap();
+        assert_eq!(
+            res.state.get_termination_reason(),
+            Some(&TerminationReason::MaxItersReached)
+        );
+    }
+
+    #[test]
+    fn test_solver_error_context() {
+        use crate::core::{ArgminError, ErrorCategory};
+
+        #[derive(Clone)]
+        struct FailingSolver {}
+
+        impl<O> Solver<O, IterState<Vec<f64>, (), (), (), (), f64>> for FailingSolver {
             fn name(&self) -> &str {
-                "OptimizationAlgorithm"
+                "FailingSolver"
             }
 
-            // Only resets internal_state to 1
             fn init(
                 &mut self,
                 _problem: &mut Problem<O>,
-                state: IterState<P, (), (), (), (), F>,
-            ) -> Result<(IterState<P, (), (), (), (), F>, Option<KV>), Error> {
-                self.internal_state = 1;
+                state: IterState<Vec<f64>, (), (), (), (), f64>,
+            ) -> Result<(IterState<Vec<f64>, (), (), (), (), f64>, Option<KV>), Error> {
+                if state.get_param().is_none() {
+                    return Err(argmin_error!(NotInitialized, "no param"));
+                }
                 Ok((state, None))
             }
 
-            // Increment internal_state
             fn next_iter(
                 &mut self,
                 _problem: &mut Problem<O>,
-                state: IterState<P, (), (), (), (), F>,
-            ) -> Result<(IterState<P, (), (), (), (), F>, Option<KV>), Error> {
-                self.internal_state += 1;
+                state: IterState<Vec<f64>, (), (), (), (), f64>,
+            ) -> Result<(IterState<Vec<f64>, (), (), (), (), f64>, Option<KV>), Error> {
+                if state.get_iter() == 2 {
+                    return Err(argmin_error!(ConditionViolated, "failed"));
+                }
                 Ok((state, None))
             }
+        }
 
-            // Avoid terminating early because param does not change
-            fn terminate(&mut self, _state: &IterState<P, (), (), (), (), F>) -> TerminationStatus {
-                TerminationStatus::NotTerminated
+        let err = Executor::new(TestProblem::new(), FailingSolver {})
+            .run()
+            .err()
+            .unwrap();
+        assert_eq!(
+            err.downcast_ref::<SolverErrorContext>(),
+            Some(&SolverErrorContext {
+                solver: "FailingSolver".to_string(),
+                iter: None,
+            })
+        );
+        assert_eq!(ErrorCategory::of(&err), ErrorCategory::NotInitialized);
+        assert_eq!(
+            err.to_string(),
+            "`FailingSolver` failed during initialization"
+        );
+
+        let err = Executor::new(TestProblem::new(), FailingSolver {})
+            .configure(|state| state.param(vec![1.0]).max_iters(10))
+            .run()
+            .err()
+            .unwrap();
+        assert_eq!(
+            err.downcast_ref::<SolverErrorContext>(),
+            Some(&SolverErrorContext {
+                solver: "FailingSolver".to_string(),
+                iter: Some(2),
+            })
+        );
+        assert_eq!(ErrorCategory::of(&err), ErrorCategory::NumericalFailure);
+        assert_eq!(
+            err.downcast_ref::<ArgminError>().unwrap().to_string(),
+            "Condition violated: \"failed\""
+        );
+    }
+
+    #[test]
+    fn test_check_state() {
+        use crate::core::ErrorCategory;
+
+        #[derive(Clone)]
+        struct StrictSolver {
+            init_called: bool,
+        }
+
+        impl<O> Solver<O, IterState<Vec<f64>, (), (), (), (), f64>> for StrictSolver {
+            fn name(&self) -> &str {
+                "StrictSolver"
             }
 
-            // Avoid terminating early because param does not change
-            fn terminate_internal(
-                &mut self,
-                state: &IterState<P, (), (), (), (), F>,
-            ) -> TerminationStatus {
-                if state.get_iter() >= state.get_max_iters() {
-                    TerminationStatus::Terminated(TerminationReason::MaxItersReached)
-                } else {
-                    TerminationStatus::NotTerminated
+            fn check_state(
+                &self,
+                state: &IterState<Vec<f64>, (), (), (), (), f64>,
+            ) -> Result<(), Error> {
+                if state.get_param().is_none() {
+                    return Err(argmin_error!(NotInitialized, "no param"));
                 }
+                Ok(())
+            }
+
+            fn init(
+                &mut self,
+                _problem: &mut Problem<O>,
+                state: IterState<Vec<f64>, (), (), (), (), f64>,
+            ) -> Result<(IterState<Vec<f64>, (), (), (), (), f64>, Option<KV>), Error> {
+                self.init_called = true;
+                Ok((state, None))
+            }
+
+            fn next_iter(
+                &mut self,
+                _problem: &mut Problem<O>,
+                state: IterState<Vec<f64>, (), (), (), (), f64>,
+            ) -> Result<(IterState<Vec<f64>, (), (), (), (), f64>, Option<KV>), Error> {
+                Ok((state, None))
             }
         }
 
-        // Create random test problem
-        let problem = TestProblem::new();
+        let solver = StrictSolver { init_called: false };
 
-        // solver instance
-        let solver = OptimizationAlgorithm { internal_state: 0 };
+        // Explicit validation returns the unwrapped error
+        let err = Executor::new(TestProblem::new(), solver.clone())
+            .validate()
+            .err()
+            .unwrap();
+        assert_eq!(err.to_string(), "Not initialized: \"no param\"");
 
-        // Create a checkpoint
-        let checkpoint = FakeCheckpoint {
-            frequency: CheckpointingFrequency::Always,
-            solver: RefCell::new(None),
-            state: RefCell::new(None),
-        };
+        // `run` performs the check before calling `init`
+        let err = Executor::new(TestProblem::new(), solver.clone())
+            .run()
+            .err()
+            .unwrap();
+        assert_eq!(
+            err.downcast_ref::<SolverErrorContext>(),
+            Some(&SolverErrorContext {
+                solver: "StrictSolver".to_string(),
+                iter: None,
+            })
+        );
+        assert_eq!(ErrorCategory::of(&err), ErrorCategory::NotInitialized);
 
-        // Create and run executor
-        let executor = Executor::new(problem, solver)
-            .configure(|state| state.param(vec![1.0f64, 1.0]).max_iters(10))
-            .checkpointing(checkpoint.clone());
+        let res = Executor::new(TestProblem::new(), solver)
+            .configure(|state| state.param(vec![1.0]).max_iters(2))
+            .validate()
+            .unwrap()
+            .run()
+            .unwrap();
+        assert!(res.solver.init_called);
+    }
 
-        let OptimizationResult { solver, .. } = executor.run().unwrap();
+    #[test]
+    fn test_count_failures() {
+        use crate::core::CostFunction;
+        use std::sync::atomic::{AtomicU64, Ordering};
 
-        // internal_state should be 11
-        // (1 from init plus 10 iterations where it is incremented by 1)
-        assert_eq!(solver.internal_state, 11);
+        type TState = IterState<f64, (), (), (), (), f64>;
 
-        // Create and run solver again
-        let executor = Executor::new(problem, solver)
-            .configure(|state| state.param(vec![1.0f64, 1.0]).max_iters(10))
-            .checkpointing(checkpoint);
+        // Problem which fails for every other evaluation
+        #[derive(Default)]
+        struct Flaky(AtomicU64);
 
-        let OptimizationResult { solver, .. } = executor.run().unwrap();
+        impl CostFunction for Flaky {
+            type Param = f64;
+            type Output = f64;
 
-        // internal_state should still be 11
-        // (1 from init plus 10 iterations where it is incremented by 1)
-        assert_eq!(solver.internal_state, 11);
+            fn cost(&self, param: &f64) -> Result<f64, Error> {
+                if self.0.fetch_add(1, Ordering::Relaxed) % 2 == 1 {
+                    return Err(Error::msg("failed"));
+                }
+                Ok(*param)
+            }
+        }
 
-        // Delete old checkpointing file
-        let _ = std::fs::remove_file(".checkpoints/init_test.arg");
+        // Solver which evaluates the cost function once per iteration
+        struct Evaluate {}
+
+        impl<O: CostFunction<Param = f64, Output = f64>> Solver<O, TState> for Evaluate {
+            fn name(&self) -> &str {
+                "Evaluate"
+            }
+
+            fn next_iter(
+                &mut self,
+                problem: &mut Problem<O>,
+                state: TState,
+            ) -> Result<(TState, Option<KV>), Error> {
+                let cost = problem.cost(&1.0)?;
+                Ok((state.param(1.0).cost(cost), None))
+            }
+        }
+
+        let res = Executor::new(PenalizedProblem::new(Flaky::default(), 1e10), Evaluate {})
+            .configure(|state| state.counting(true).max_iters(4))
+            .count_failures()
+            .run()
+            .unwrap();
+        let counts = res.state().get_func_counts();
+        assert_eq!(counts["cost_count"], 4);
+        assert_eq!(counts["cost_failure_count"], 2);
+
+        let res = Executor::new(PenalizedProblem::new(Flaky::default(), 1e10), Evaluate {})
+            .configure(|state| state.counting(true).max_iters(4))
+            .run()
+            .unwrap();
+        assert!(!res
+            .state()
+            .get_func_counts()
+            .contains_key("cost_failure_count"));
     }
 
     #[test]
-    fn test_timeout() {
-        let solver = TestSolver::new();
-        let problem = TestProblem::new();
-        let timeout = std::time::Duration::from_secs(2);
-
-        let executor = Executor::new(problem, solver).timer(true);
-        assert!(executor.timer);
-        assert!(executor.timeout.is_none());
+    fn test_disabled_observers() {
+        use crate::core::observers::DisabledObserver;
 
-        let executor = Executor::new(problem, solver).timer(false);
-        assert!(!executor.timer);
-        assert!(executor.timeout.is_none());
+        type TState = IterState<Vec<f64>, (), (), (), (), f64>;
 
-        let executor = Executor::new(problem, solver).timeout(timeout);
-        assert!(executor.timer);
-        assert_eq!(executor.timeout, Some(timeout));
+        // Observer which fails in the second iteration
+        struct FailingObs {}
 
-        let executor = Executor::new(problem, solver).timeout(timeout).timer(false);
-        assert!(executor.timer);
-        assert_eq!(executor.timeout, Some(timeout));
+        impl Observe<TState> for FailingObs {
+            fn observe_iter(&mut self, state: &TState, _kv: &KV) -> Result<(), Error> {
+                if state.get_iter() == 2 {
+                    return Err(argmin_error!(ConditionViolated, "disk full"));
+                }
+                Ok(())
+            }
+        }
 
-        let executor = Executor::new(problem, solver).timer(false).timeout(timeout);
-        assert!(executor.timer);
-        assert_eq!(executor.timeout, Some(timeout));
+        let res = Executor::new(TestProblem::new(), TestSolver::new())
+            .configure(|state| state.param(vec![1.0, 0.0]).max_iters(5))
+            .add_observer(FailingObs {}, ObserverMode::Never)
+            .add_observer_with_policy(
+                FailingObs {},
+                ObserverMode::Always,
+                ObserverErrorPolicy::WarnAndDisable,
+            )
+            .run()
+            .unwrap();
+        assert_eq!(res.state().get_iter(), 5);
+        assert_eq!(
+            res.disabled_observers(),
+            &[DisabledObserver {
+                index: 1,
+                method: "observe_iter",
+                iter: 2,
+                error: "Condition violated: \"disk full\"".to_string(),
+            }]
+        );
     }
 }
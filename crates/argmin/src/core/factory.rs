@@ -0,0 +1,456 @@
+// Copyright 2018-2024 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+//! # Solver factory
+//!
+//! A [`SolverFactory`] constructs solvers from a name and a configuration given as a
+//! [`serde_json::Value`]. This allows applications such as command line tools or language
+//! bindings to select a solver at runtime, for instance based on a configuration file.
+
+use crate::core::{
+    ArgminFloat, BoxedSolver, Error, IterState, PopulationState, Solver, State, SyncAlias,
+};
+use crate::solver::gradientdescent::SteepestDescent;
+use crate::solver::linesearch::{MoreThuenteLineSearch, MoreThuenteLineSearchConfig};
+use crate::solver::neldermead::{NelderMead, NelderMeadConfig};
+use crate::solver::particleswarm::{Particle, ParticleSwarm, ParticleSwarmConfig};
+use crate::solver::quasinewton::{LBFGSConfig, LBFGS};
+use crate::solver::simulatedannealing::{SimulatedAnnealing, SimulatedAnnealingConfig};
+use argmin_math::{ArgminAdd, ArgminMul, ArgminRandom, ArgminSub, ArgminZeroLike};
+use rand_xoshiro::Xoshiro256PlusPlus;
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+use std::collections::HashMap;
+
+/// Function which constructs a boxed solver from a configuration
+pub type SolverConstructor<O, I> = fn(&Value) -> Result<BoxedSolver<O, I>, Error>;
+
+/// Constructs boxed solvers from a name and a configuration.
+///
+/// Solvers are registered under a (case-insensitive) name together with a
+/// [`SolverConstructor`] which turns a configuration into a solver. The built-in solvers can be
+/// registered via the `register_*` methods, which are available if the state type `I` matches
+/// the one required by the solver. Arbitrary solvers can be registered via
+/// [`register`](`SolverFactory::register`).
+///
+/// The configuration of the built-in solvers corresponds to the (serialized) configuration
+/// structs of the solvers, such as [`LBFGSConfig`]. Omitted options keep their default values
+/// and `null` is equivalent to an empty configuration. For solvers which use a line search, the
+/// configuration of the line search is given in the `linesearch` field.
+///
+/// The returned solvers are of type [`BoxedSolver`] and can be passed directly to the
+/// [`Executor`](`crate::core::Executor`).
+///
+/// # Example
+///
+/// ```
+/// use argmin::core::{CostFunction, Error, Executor, Gradient, IterState, SolverFactory};
+/// use serde_json::json;
+///
+/// struct Paraboloid {}
+///
+/// impl CostFunction for Paraboloid {
+///     type Param = Vec<f64>;
+///     type Output = f64;
+///
+///     fn cost(&self, p: &Self::Param) -> Result<Self::Output, Error> {
+///         Ok(p[0].powi(2) + p[1].powi(2))
+///     }
+/// }
+///
+/// impl Gradient for Paraboloid {
+///     type Param = Vec<f64>;
+///     type Gradient = Vec<f64>;
+///
+///     fn gradient(&self, p: &Self::Param) -> Result<Self::Gradient, Error> {
+///         Ok(vec![2.0 * p[0], 2.0 * p[1]])
+///     }
+/// }
+///
+/// # fn main() -> Result<(), Error> {
+/// let mut factory: SolverFactory<Paraboloid, IterState<Vec<f64>, Vec<f64>, (), (), (), f64>> =
+///     SolverFactory::new();
+/// factory.register_lbfgs().register_steepest_descent();
+///
+/// // Name and configuration could for instance come from a configuration file
+/// let solver = factory.build(
+///     "lbfgs",
+///     &json!({ "m": 5, "tolerance_grad": 1e-8, "linesearch": { "c": [1e-4, 0.9] } }),
+/// )?;
+///
+/// let res = Executor::new(Paraboloid {}, solver)
+///     .configure(|state| state.param(vec![1.0, 2.0]).max_iters(20))
+///     .run()?;
+/// # assert!(res.state().best_cost < 1e-8);
+/// # Ok(())
+/// # }
+/// ```
+pub struct SolverFactory<O, I> {
+    constructors: HashMap<String, SolverConstructor<O, I>>,
+}
+
+impl<O, I> SolverFactory<O, I>
+where
+    I: State,
+{
+    /// Construct a new instance of [`SolverFactory`] without any registered solvers.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::{IterState, SolverFactory};
+    /// # use argmin::core::test_utils::TestProblem;
+    /// let factory: SolverFactory<TestProblem, IterState<Vec<f64>, (), (), (), (), f64>> =
+    ///     SolverFactory::new();
+    /// # assert!(factory.names().is_empty());
+    /// ```
+    pub fn new() -> Self {
+        SolverFactory {
+            constructors: HashMap::new(),
+        }
+    }
+
+    /// Registers a solver under the given name.
+    ///
+    /// The name is case-insensitive. A solver which was registered earlier under the same name is
+    /// replaced.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::{BoxedSolver, IterState, SolverFactory};
+    /// # use argmin::core::test_utils::TestProblem;
+    /// use argmin::solver::landweber::LandweberConfig;
+    ///
+    /// let mut factory: SolverFactory<TestProblem, IterState<Vec<f64>, Vec<f64>, (), (), (), f64>> =
+    ///     SolverFactory::new();
+    /// factory.register("landweber", |config| {
+    ///     let config: LandweberConfig<f64> = serde_json::from_value(config.clone())?;
+    ///     Ok(BoxedSolver::new(config.build()?))
+    /// });
+    /// # assert!(factory.contains("Landweber"));
+    /// ```
+    pub fn register(&mut self, name: &str, constructor: SolverConstructor<O, I>) -> &mut Self {
+        self.constructors.insert(name.to_lowercase(), constructor);
+        self
+    }
+
+    /// Returns `true` if a solver is registered under the given name.
+    pub fn contains(&self, name: &str) -> bool {
+        self.constructors.contains_key(&name.to_lowercase())
+    }
+
+    /// Returns the (alphabetically sorted) names of all registered solvers.
+    pub fn names(&self) -> Vec<&str> {
+        let mut names: Vec<&str> = self.constructors.keys().map(String::as_str).collect();
+        names.sort_unstable();
+        names
+    }
+
+    /// Constructs the solver registered under `name` from the given configuration.
+    ///
+    /// Returns an error if no solver is registered under `name` or if the configuration is
+    /// invalid.
+    pub fn build(&self, name: &str, config: &Value) -> Result<BoxedSolver<O, I>, Error> {
+        match self.constructors.get(&name.to_lowercase()) {
+            Some(constructor) => constructor(config),
+            None => Err(argmin_error!(
+                InvalidParameter,
+                format!(
+                    "`SolverFactory`: Unknown solver `{name}`. Available solvers: {}.",
+                    self.names().join(", ")
+                )
+            )),
+        }
+    }
+}
+
+impl<O, I> Default for SolverFactory<O, I>
+where
+    I: State,
+{
+    fn default() -> Self {
+        SolverFactory::new()
+    }
+}
+
+impl<O, P, G, F> SolverFactory<O, IterState<P, G, (), (), (), F>>
+where
+    P: Clone + 'static,
+    G: Clone + 'static,
+    F: ArgminFloat + Default + DeserializeOwned,
+{
+    /// Registers [`LBFGS`] with [`MoreThuenteLineSearch`] under the name `lbfgs`.
+    ///
+    /// The configuration corresponds to [`LBFGSConfig`], in particular the history size must be
+    /// provided in the `m` field. The line search is configured via
+    /// [`MoreThuenteLineSearchConfig`] in the `linesearch` field.
+    pub fn register_lbfgs(&mut self) -> &mut Self
+    where
+        LBFGS<MoreThuenteLineSearch<P, G, F>, P, G, F>: Solver<O, IterState<P, G, (), (), (), F>>,
+    {
+        self.register("lbfgs", |config| {
+            let linesearch = parse_config::<MoreThuenteLineSearchConfig<F>>(
+                "lbfgs",
+                config.get("linesearch").unwrap_or(&Value::Null),
+            )?
+            .build()?;
+            let solver = parse_config::<LBFGSConfig<F>>("lbfgs", config)?.build(linesearch)?;
+            Ok(BoxedSolver::new(solver))
+        })
+    }
+
+    /// Registers [`SteepestDescent`] with [`MoreThuenteLineSearch`] under the name
+    /// `steepestdescent`.
+    ///
+    /// The line search is configured via [`MoreThuenteLineSearchConfig`] in the `linesearch`
+    /// field.
+    pub fn register_steepest_descent(&mut self) -> &mut Self
+    where
+        SteepestDescent<MoreThuenteLineSearch<P, G, F>>: Solver<O, IterState<P, G, (), (), (), F>>,
+    {
+        self.register("steepestdescent", |config| {
+            let linesearch = parse_config::<MoreThuenteLineSearchConfig<F>>(
+                "steepestdescent",
+                config.get("linesearch").unwrap_or(&Value::Null),
+            )?
+            .build()?;
+            Ok(BoxedSolver::new(SteepestDescent::new(linesearch)))
+        })
+    }
+}
+
+impl<O, P, F> SolverFactory<O, IterState<P, (), (), (), (), F>>
+where
+    P: Clone + 'static,
+    F: ArgminFloat + Default + DeserializeOwned,
+{
+    /// Registers [`NelderMead`] under the name `neldermead`.
+    ///
+    /// The configuration corresponds to [`NelderMeadConfig`], in particular the initial simplex
+    /// must be provided in the `params` field.
+    pub fn register_neldermead(&mut self) -> &mut Self
+    where
+        P: Default + DeserializeOwned + ArgminAdd<P, P> + ArgminSub<P, P> + ArgminMul<F, P>,
+        NelderMead<P, F>: Solver<O, IterState<P, (), (), (), (), F>>,
+    {
+        self.register("neldermead", |config| {
+            let solver = parse_config::<NelderMeadConfig<P, F>>("neldermead", config)?.build()?;
+            Ok(BoxedSolver::new(solver))
+        })
+    }
+
+    /// Registers [`SimulatedAnnealing`] under the name `simulatedannealing`.
+    ///
+    /// The configuration corresponds to [`SimulatedAnnealingConfig`].
+    pub fn register_simulated_annealing(&mut self) -> &mut Self
+    where
+        SimulatedAnnealing<F, Xoshiro256PlusPlus>: Solver<O, IterState<P, (), (), (), (), F>>,
+    {
+        self.register("simulatedannealing", |config| {
+            let solver = parse_config::<SimulatedAnnealingConfig<F>>("simulatedannealing", config)?
+                .build()?;
+            Ok(BoxedSolver::new(solver))
+        })
+    }
+}
+
+impl<O, P, F> SolverFactory<O, PopulationState<Particle<P, F>, F>>
+where
+    P: Clone
+        + SyncAlias
+        + DeserializeOwned
+        + ArgminSub<P, P>
+        + ArgminMul<F, P>
+        + ArgminRandom
+        + ArgminZeroLike
+        + 'static,
+    F: ArgminFloat + Default + DeserializeOwned,
+{
+    /// Registers [`ParticleSwarm`] under the name `particleswarm`.
+    ///
+    /// The configuration corresponds to [`ParticleSwarmConfig`], in particular the bounds of the
+    /// search space must be provided in the `bounds` field.
+    pub fn register_particle_swarm(&mut self) -> &mut Self
+    where
+        P: Default,
+        ParticleSwarm<P, F, Xoshiro256PlusPlus>: Solver<O, PopulationState<Particle<P, F>, F>>,
+    {
+        self.register("particleswarm", |config| {
+            let solver =
+                parse_config::<ParticleSwarmConfig<P, F>>("particleswarm", config)?.build()?;
+            Ok(BoxedSolver::new(solver))
+        })
+    }
+}
+
+/// Deserializes the configuration of a solver. `null` is treated as an empty configuration.
+fn parse_config<T: DeserializeOwned>(solver: &str, config: &Value) -> Result<T, Error> {
+    let config = if config.is_null() {
+        Value::Object(Default::default())
+    } else {
+        config.clone()
+    };
+    serde_json::from_value(config).map_err(|e| {
+        argmin_error!(
+            InvalidParameter,
+            format!("`SolverFactory`: Invalid configuration for `{solver}`: {e}")
+        )
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::test_utils::TestProblem;
+    use crate::core::{ArgminError, CostFunction, Executor, TerminationReason, TerminationStatus};
+    use serde_json::json;
+
+    type GradState = IterState<Vec<f64>, Vec<f64>, (), (), (), f64>;
+    type DerivativeFreeState = IterState<Vec<f64>, (), (), (), (), f64>;
+
+    struct Sphere {}
+
+    impl CostFunction for Sphere {
+        type Param = Vec<f64>;
+        type Output = f64;
+
+        fn cost(&self, p: &Self::Param) -> Result<Self::Output, Error> {
+            Ok(p.iter().map(|x| x.powi(2)).sum())
+        }
+    }
+
+    #[test]
+    fn test_register_and_names() {
+        let mut factory: SolverFactory<TestProblem, GradState> = SolverFactory::new();
+        assert!(factory.names().is_empty());
+        factory.register_steepest_descent().register_lbfgs();
+        assert_eq!(factory.names(), vec!["lbfgs", "steepestdescent"]);
+        assert!(factory.contains("LBFGS"));
+        assert!(!factory.contains("neldermead"));
+    }
+
+    #[test]
+    fn test_build() {
+        let mut factory: SolverFactory<TestProblem, GradState> = SolverFactory::new();
+        factory.register_lbfgs().register_steepest_descent();
+
+        let solver = factory.build("L-BFGS".replace('-', "").as_str(), &json!({"m": 3}));
+        assert_eq!(solver.unwrap().name(), "L-BFGS");
+
+        let solver = factory.build("steepestdescent", &Value::Null).unwrap();
+        assert_eq!(solver.name(), "Steepest Descent");
+    }
+
+    #[test]
+    fn test_build_errors() {
+        let mut factory: SolverFactory<TestProblem, GradState> = SolverFactory::new();
+        factory.register_lbfgs().register_steepest_descent();
+
+        let res = factory.build("bfgs", &Value::Null);
+        assert_error!(
+            res,
+            ArgminError,
+            concat!(
+                "Invalid parameter: \"`SolverFactory`: Unknown solver `bfgs`. ",
+                "Available solvers: lbfgs, steepestdescent.\""
+            )
+        );
+
+        // validation of the configuration is performed
+        let res = factory.build("lbfgs", &json!({"m": 0}));
+        assert_error!(
+            res,
+            ArgminError,
+            "Invalid parameter: \"`L-BFGS`: history size `m` must be > 0.\""
+        );
+
+        // the history size is required
+        let res = factory.build("lbfgs", &json!({"tolerance_grad": 1e-8}));
+        assert_error!(
+            res,
+            ArgminError,
+            "Invalid parameter: \"`L-BFGS`: history size `m` must be > 0.\""
+        );
+
+        let res = factory.build("lbfgs", &json!({"m": "five"}));
+        assert!(res.err().unwrap().to_string().starts_with(
+            "Invalid parameter: \"`SolverFactory`: Invalid configuration for `lbfgs`"
+        ));
+    }
+
+    #[test]
+    fn test_run_neldermead() {
+        let mut factory: SolverFactory<Sphere, DerivativeFreeState> = SolverFactory::new();
+        factory.register_neldermead();
+
+        let solver = factory
+            .build(
+                "neldermead",
+                &json!({
+                    "params": [[1.0, 0.5], [2.0, 1.5], [0.3, 2.0]],
+                    "sd_tolerance": 1e-8
+                }),
+            )
+            .unwrap();
+        let res = Executor::new(Sphere {}, solver)
+            .configure(|state| state.max_iters(500))
+            .run()
+            .unwrap();
+        assert_eq!(
+            res.state.termination_status,
+            TerminationStatus::Terminated(TerminationReason::SolverConverged)
+        );
+        assert!(res.state.best_cost < 1e-4);
+    }
+
+    #[test]
+    fn test_simulated_annealing() {
+        let mut factory: SolverFactory<TestProblem, DerivativeFreeState> = SolverFactory::new();
+        factory.register_simulated_annealing();
+
+        let solver = factory
+            .build(
+                "SimulatedAnnealing",
+                &json!({"initial_temperature": 100.0, "seed": 42}),
+            )
+            .unwrap();
+        assert_eq!(solver.name(), "Simulated Annealing");
+
+        let res = factory.build("simulatedannealing", &json!({"initial_temperature": -1.0}));
+        assert!(res.is_err());
+    }
+
+    #[test]
+    fn test_particle_swarm() {
+        let mut factory: SolverFactory<Sphere, PopulationState<Particle<Vec<f64>, f64>, f64>> =
+            SolverFactory::new();
+        factory.register_particle_swarm();
+
+        let solver = factory
+            .build(
+                "particleswarm",
+                &json!({
+                    "bounds": [[-1.0, -1.0], [1.0, 1.0]],
+                    "num_particles": 10,
+                    "seed": 42
+                }),
+            )
+            .unwrap();
+        assert_eq!(solver.name(), "Particle Swarm Optimization");
+
+        let res = factory.build(
+            "particleswarm",
+            &json!({"bounds": [[-1.0], [1.0]], "num_particles": 0}),
+        );
+        assert_error!(
+            res,
+            ArgminError,
+            "Invalid parameter: \"`ParticleSwarm`: number of particles must be > 0.\""
+        );
+    }
+}
@@ -13,6 +13,12 @@ use std::fmt::{Debug, Display};
 /// `num_traits`, `std::fmt` and for serialization/deserialization (the latter only if the `serde1`
 /// feature is enabled). It is automatically implemented for all types which fulfill the trait
 /// bounds.
+///
+/// Besides `f32` and `f64`, higher precision types can be used for ill-conditioned problems where
+/// `f64` stagnates. With the `twofloat` feature, `twofloat::TwoFloat` (double-double precision)
+/// fulfills the trait bounds and the math traits are implemented for `Vec`s of it. Since
+/// `num_traits::Float` requires `Copy`, arbitrary precision types (such as `rug::Float`) are not
+/// supported.
 pub trait ArgminFloat:
     'static
     + Float
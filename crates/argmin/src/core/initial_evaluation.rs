@@ -0,0 +1,105 @@
+// Copyright 2018-2024 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use crate::core::Error;
+#[cfg(feature = "serde1")]
+use serde::{Deserialize, Serialize};
+
+/// Policy for evaluating the cost function, gradient and Hessian at the initial parameter vector
+///
+/// Gradient-based solvers need the cost function value, the gradient and possibly the Hessian at
+/// the initial parameter vector. These can either be provided via `Executor`s `configure` method
+/// or computed by the solver in `init`. The policy is set on the state via
+/// [`IterState::initial_evaluation`](`crate::core::IterState::initial_evaluation`) and is
+/// reflected in the function evaluation counts.
+///
+/// # Example
+///
+/// ```
+/// # use argmin::core::{Executor, InitialEvaluation, State};
+/// # use argmin::core::test_utils::{TestSolver, TestProblem};
+/// # let solver = TestSolver::new();
+/// # let problem = TestProblem::new();
+/// let executor = Executor::new(problem, solver).configure(|state| {
+///     state
+///         .param(vec![1.0f64, 2.0])
+///         // The cost function value at the initial parameter vector is already known
+///         .cost(5.0)
+///         .initial_evaluation(InitialEvaluation::Skip)
+/// });
+/// ```
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+pub enum InitialEvaluation {
+    /// Values provided via `configure` are used, missing values are computed in `init`.
+    #[default]
+    Auto,
+    /// All values are computed in `init`, even if they were provided via `configure`.
+    Force,
+    /// Nothing is computed in `init`. Values provided via `configure` are used, missing values are
+    /// computed once they are first needed, which may still be in `init`.
+    Skip,
+}
+
+impl InitialEvaluation {
+    /// Returns the value needed right away in `init`
+    ///
+    /// Returns `provided` unless it is `None` or the policy is [`InitialEvaluation::Force`], in
+    /// which case `eval` is called.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::{Error, InitialEvaluation};
+    /// # fn main() -> Result<(), Error> {
+    /// let eval = || -> Result<f64, Error> { Ok(2.0) };
+    /// assert_eq!(InitialEvaluation::Auto.evaluate(Some(1.0), eval)?, 1.0);
+    /// assert_eq!(InitialEvaluation::Auto.evaluate(None, eval)?, 2.0);
+    /// assert_eq!(InitialEvaluation::Force.evaluate(Some(1.0), eval)?, 2.0);
+    /// assert_eq!(InitialEvaluation::Skip.evaluate(None, eval)?, 2.0);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn evaluate<T, E>(&self, provided: Option<T>, eval: E) -> Result<T, Error>
+    where
+        E: FnOnce() -> Result<T, Error>,
+    {
+        match (self, provided) {
+            (InitialEvaluation::Force, _) | (_, None) => eval(),
+            (_, Some(value)) => Ok(value),
+        }
+    }
+
+    /// Returns the value at the initial parameter vector if it is not needed right away in `init`
+    ///
+    /// Same as [`evaluate`](`InitialEvaluation::evaluate`), except that `eval` is not called for
+    /// [`InitialEvaluation::Skip`] and `provided` is returned instead. Solvers using this method
+    /// need to compute missing values in `next_iter`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::{Error, InitialEvaluation};
+    /// # fn main() -> Result<(), Error> {
+    /// let eval = || -> Result<f64, Error> { Ok(2.0) };
+    /// assert_eq!(InitialEvaluation::Auto.evaluate_lazily(None, eval)?, Some(2.0));
+    /// assert_eq!(InitialEvaluation::Force.evaluate_lazily(Some(1.0), eval)?, Some(2.0));
+    /// assert_eq!(InitialEvaluation::Skip.evaluate_lazily(None, eval)?, None);
+    /// assert_eq!(InitialEvaluation::Skip.evaluate_lazily(Some(1.0), eval)?, Some(1.0));
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn evaluate_lazily<T, E>(&self, provided: Option<T>, eval: E) -> Result<Option<T>, Error>
+    where
+        E: FnOnce() -> Result<T, Error>,
+    {
+        match self {
+            InitialEvaluation::Skip => Ok(provided),
+            _ => self.evaluate(provided, eval).map(Some),
+        }
+    }
+}
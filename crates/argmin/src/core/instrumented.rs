@@ -0,0 +1,437 @@
+// Copyright 2018-2024 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use crate::core::{CostFunction, Error, Gradient};
+#[cfg(feature = "serde1")]
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use std::fmt::{Debug, Display};
+use std::fs::{File, OpenOptions};
+use std::io::{BufWriter, Write};
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use web_time::Instant;
+
+/// Kind of a recorded evaluation
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+pub enum EvaluationKind {
+    /// Evaluation of the cost function
+    Cost,
+    /// Evaluation of the gradient
+    Gradient,
+}
+
+impl Display for EvaluationKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            EvaluationKind::Cost => f.write_str("cost"),
+            EvaluationKind::Gradient => f.write_str("gradient"),
+        }
+    }
+}
+
+/// Recorded input of an evaluation
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+pub enum EvaluationInput<P> {
+    /// Full parameter vector
+    Param(P),
+    /// Hash of the parameter vector (see [`InstrumentedProblem::with_param_hash`])
+    Hash(u64),
+}
+
+/// A single recorded evaluation
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+pub struct Evaluation<P, F> {
+    /// Kind of evaluation
+    pub kind: EvaluationKind,
+    /// Parameter vector (or its hash) at which the problem was evaluated
+    pub input: EvaluationInput<P>,
+    /// Cost function value for cost function evaluations, `None` for gradient evaluations
+    pub cost: Option<F>,
+    /// Time the evaluation took
+    pub duration: Duration,
+}
+
+/// Storage of the evaluations recorded by an [`InstrumentedProblem`]
+#[derive(Debug)]
+struct EvaluationLog<P, F> {
+    /// Most recent evaluations
+    records: VecDeque<Evaluation<P, F>>,
+    /// Maximum number of evaluations kept in `records` (unbounded if `None`)
+    capacity: Option<usize>,
+    /// File every evaluation is written to (if set)
+    file: Option<BufWriter<File>>,
+}
+
+/// Wraps a problem and records every evaluation of the cost function and the gradient
+///
+/// For each evaluation, the kind of evaluation, the parameter vector, the cost function value (for
+/// cost function evaluations) and the time the evaluation took are recorded. This is independent
+/// of observers and hence of the cadence at which observers are called, which allows one to
+/// analyze expensive evaluations or to use them as training data for surrogate models.
+///
+/// The evaluations are kept in memory, where the number of stored evaluations can be limited with
+/// [`with_capacity`](`InstrumentedProblem::with_capacity`) (then only the most recent evaluations
+/// are kept). Instead of the full parameter vector, a hash computed by a user-provided function
+/// can be stored via [`with_param_hash`](`InstrumentedProblem::with_param_hash`). In addition,
+/// all evaluations can be appended to a file via
+/// [`with_file`](`InstrumentedProblem::with_file`). Each line of this file holds the kind of
+/// evaluation, the duration in seconds, the cost function value (empty for gradients) and the
+/// parameter vector (in `Debug` format) or its hash, separated by tabs.
+///
+/// Clones of an `InstrumentedProblem` share the recorded evaluations.
+///
+/// # Example
+///
+/// ```
+/// use argmin::core::{EvaluationKind, Executor, InstrumentedProblem, State};
+/// use argmin::solver::gradientdescent::SteepestDescent;
+/// use argmin::solver::linesearch::MoreThuenteLineSearch;
+/// # use argmin::core::{CostFunction, Error, Gradient};
+/// #
+/// # struct Paraboloid {}
+/// #
+/// # impl CostFunction for Paraboloid {
+/// #     type Param = Vec<f64>;
+/// #     type Output = f64;
+/// #     fn cost(&self, p: &Self::Param) -> Result<Self::Output, Error> {
+/// #         Ok(p[0].powi(2) + 10.0 * p[1].powi(2))
+/// #     }
+/// # }
+/// #
+/// # impl Gradient for Paraboloid {
+/// #     type Param = Vec<f64>;
+/// #     type Gradient = Vec<f64>;
+/// #     fn gradient(&self, p: &Self::Param) -> Result<Self::Gradient, Error> {
+/// #         Ok(vec![2.0 * p[0], 20.0 * p[1]])
+/// #     }
+/// # }
+///
+/// # fn main() -> Result<(), Error> {
+/// // Keep the 1000 most recent evaluations
+/// let problem = InstrumentedProblem::new(Paraboloid {}).with_capacity(1000);
+/// let evaluations = problem.clone();
+///
+/// let solver = SteepestDescent::new(MoreThuenteLineSearch::new());
+/// let res = Executor::new(problem, solver)
+///     .configure(|state| state.param(vec![1.0, 1.0]).max_iters(10).counting(true))
+///     .run()?;
+///
+/// let records = evaluations.evaluations();
+/// let num_cost = records.iter().filter(|e| e.kind == EvaluationKind::Cost).count();
+/// assert_eq!(num_cost as u64, res.state().get_func_counts()["cost_count"]);
+/// # Ok(())
+/// # }
+/// ```
+pub struct InstrumentedProblem<O: CostFunction> {
+    /// Wrapped problem
+    problem: Arc<O>,
+    /// Recorded evaluations
+    log: Arc<Mutex<EvaluationLog<O::Param, O::Output>>>,
+    /// Computes the hash which is stored instead of the parameter vector (if set)
+    param_hash: Option<fn(&O::Param) -> u64>,
+}
+
+impl<O: CostFunction> Clone for InstrumentedProblem<O> {
+    fn clone(&self) -> Self {
+        InstrumentedProblem {
+            problem: Arc::clone(&self.problem),
+            log: Arc::clone(&self.log),
+            param_hash: self.param_hash,
+        }
+    }
+}
+
+impl<O: CostFunction> InstrumentedProblem<O> {
+    /// Construct a new instance of `InstrumentedProblem` which keeps all evaluations in memory
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::InstrumentedProblem;
+    /// # use argmin::core::test_utils::TestProblem;
+    /// let problem = InstrumentedProblem::new(TestProblem::new());
+    /// ```
+    pub fn new(problem: O) -> Self {
+        InstrumentedProblem {
+            problem: Arc::new(problem),
+            log: Arc::new(Mutex::new(EvaluationLog {
+                records: VecDeque::new(),
+                capacity: None,
+                file: None,
+            })),
+            param_hash: None,
+        }
+    }
+
+    /// Only keep the `capacity` most recent evaluations in memory
+    ///
+    /// Setting the capacity to `0` disables recording in memory, which is useful in combination
+    /// with [`with_file`](`InstrumentedProblem::with_file`).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::InstrumentedProblem;
+    /// # use argmin::core::test_utils::TestProblem;
+    /// let problem = InstrumentedProblem::new(TestProblem::new()).with_capacity(100);
+    /// ```
+    #[must_use]
+    pub fn with_capacity(self, capacity: usize) -> Self {
+        {
+            let mut log = self.log.lock().unwrap();
+            log.capacity = Some(capacity);
+            let excess = log.records.len().saturating_sub(capacity);
+            log.records.drain(..excess);
+        }
+        self
+    }
+
+    /// Store the hash computed by `hash` instead of the full parameter vector
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::InstrumentedProblem;
+    /// # use argmin::core::test_utils::TestProblem;
+    /// use std::hash::{DefaultHasher, Hash, Hasher};
+    ///
+    /// fn hash(param: &Vec<f64>) -> u64 {
+    ///     let mut hasher = DefaultHasher::new();
+    ///     param.iter().for_each(|x| x.to_bits().hash(&mut hasher));
+    ///     hasher.finish()
+    /// }
+    ///
+    /// let problem = InstrumentedProblem::new(TestProblem::new()).with_param_hash(hash);
+    /// ```
+    #[must_use]
+    pub fn with_param_hash(mut self, hash: fn(&O::Param) -> u64) -> Self {
+        self.param_hash = Some(hash);
+        self
+    }
+
+    /// Append every evaluation to the file at `path`
+    ///
+    /// The file is created if it does not exist. Writing is buffered; the buffer is flushed when
+    /// the last clone of the problem is dropped or when [`flush`](`InstrumentedProblem::flush`)
+    /// is called.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use argmin::core::{Error, InstrumentedProblem};
+    /// # use argmin::core::test_utils::TestProblem;
+    /// # fn main() -> Result<(), Error> {
+    /// let problem = InstrumentedProblem::new(TestProblem::new()).with_file("evaluations.tsv")?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_file<P: AsRef<Path>>(self, path: P) -> Result<Self, Error> {
+        let file = OpenOptions::new().create(true).append(true).open(path)?;
+        self.log.lock().unwrap().file = Some(BufWriter::new(file));
+        Ok(self)
+    }
+
+    /// Flushes the buffer of the file the evaluations are written to (if set)
+    pub fn flush(&self) -> Result<(), Error> {
+        if let Some(file) = self.log.lock().unwrap().file.as_mut() {
+            file.flush()?;
+        }
+        Ok(())
+    }
+
+    /// Returns a reference to the wrapped problem
+    pub fn problem(&self) -> &O {
+        &self.problem
+    }
+
+    /// Returns a copy of the evaluations currently kept in memory, oldest first
+    pub fn evaluations(&self) -> Vec<Evaluation<O::Param, O::Output>>
+    where
+        O::Param: Clone,
+        O::Output: Clone,
+    {
+        self.log.lock().unwrap().records.iter().cloned().collect()
+    }
+
+    /// Removes the evaluations kept in memory and returns them, oldest first
+    pub fn take_evaluations(&self) -> Vec<Evaluation<O::Param, O::Output>> {
+        self.log.lock().unwrap().records.drain(..).collect()
+    }
+
+    /// Stores an evaluation in memory and writes it to the file (if set)
+    fn record(
+        &self,
+        kind: EvaluationKind,
+        param: &O::Param,
+        cost: Option<O::Output>,
+        duration: Duration,
+    ) -> Result<(), Error>
+    where
+        O::Param: Clone + Debug,
+        O::Output: Display,
+    {
+        let input = match self.param_hash {
+            Some(hash) => EvaluationInput::Hash(hash(param)),
+            None => EvaluationInput::Param(param.clone()),
+        };
+        let mut log = self.log.lock().unwrap();
+        if let Some(file) = log.file.as_mut() {
+            let cost = cost.as_ref().map(|c| c.to_string()).unwrap_or_default();
+            match &input {
+                EvaluationInput::Param(p) => {
+                    writeln!(file, "{kind}\t{}\t{cost}\t{p:?}", duration.as_secs_f64())?
+                }
+                EvaluationInput::Hash(h) => {
+                    writeln!(file, "{kind}\t{}\t{cost}\t{h}", duration.as_secs_f64())?
+                }
+            }
+        }
+        if log.capacity == Some(0) {
+            return Ok(());
+        }
+        if log.capacity == Some(log.records.len()) {
+            log.records.pop_front();
+        }
+        log.records.push_back(Evaluation {
+            kind,
+            input,
+            cost,
+            duration,
+        });
+        Ok(())
+    }
+}
+
+impl<O> CostFunction for InstrumentedProblem<O>
+where
+    O: CostFunction,
+    O::Param: Clone + Debug,
+    O::Output: Clone + Display,
+{
+    type Param = O::Param;
+    type Output = O::Output;
+
+    fn cost(&self, param: &Self::Param) -> Result<Self::Output, Error> {
+        let start = Instant::now();
+        let cost = self.problem.cost(param)?;
+        let duration = start.elapsed();
+        self.record(EvaluationKind::Cost, param, Some(cost.clone()), duration)?;
+        Ok(cost)
+    }
+}
+
+impl<O> Gradient for InstrumentedProblem<O>
+where
+    O: CostFunction + Gradient<Param = <O as CostFunction>::Param>,
+    <O as CostFunction>::Param: Clone + Debug,
+    O::Output: Display,
+{
+    type Param = <O as CostFunction>::Param;
+    type Gradient = O::Gradient;
+
+    fn gradient(&self, param: &Self::Param) -> Result<Self::Gradient, Error> {
+        let start = Instant::now();
+        let gradient = self.problem.gradient(param)?;
+        let duration = start.elapsed();
+        self.record(EvaluationKind::Gradient, param, None, duration)?;
+        Ok(gradient)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::test_utils::TestProblem;
+
+    send_sync_test!(instrumented_problem, InstrumentedProblem<TestProblem>);
+
+    #[test]
+    fn test_record() {
+        let problem = InstrumentedProblem::new(TestProblem::new());
+        let clone = problem.clone();
+        problem.cost(&vec![1.0, 2.0]).unwrap();
+        problem.gradient(&vec![3.0, 4.0]).unwrap();
+
+        let evaluations = clone.evaluations();
+        assert_eq!(evaluations.len(), 2);
+        assert_eq!(evaluations[0].kind, EvaluationKind::Cost);
+        assert_eq!(evaluations[0].input, EvaluationInput::Param(vec![1.0, 2.0]));
+        assert_eq!(
+            evaluations[0].cost.unwrap().to_ne_bytes(),
+            1.0f64.to_ne_bytes()
+        );
+        assert_eq!(evaluations[1].kind, EvaluationKind::Gradient);
+        assert_eq!(evaluations[1].input, EvaluationInput::Param(vec![3.0, 4.0]));
+        assert!(evaluations[1].cost.is_none());
+
+        assert_eq!(problem.take_evaluations().len(), 2);
+        assert!(clone.evaluations().is_empty());
+    }
+
+    #[test]
+    fn test_with_capacity() {
+        let problem = InstrumentedProblem::new(TestProblem::new());
+        for i in 0..5 {
+            problem.cost(&vec![f64::from(i)]).unwrap();
+        }
+        let problem = problem.with_capacity(3);
+        assert_eq!(problem.evaluations().len(), 3);
+
+        problem.cost(&vec![5.0]).unwrap();
+        let inputs: Vec<_> = problem.evaluations().into_iter().map(|e| e.input).collect();
+        assert_eq!(
+            inputs,
+            vec![
+                EvaluationInput::Param(vec![3.0]),
+                EvaluationInput::Param(vec![4.0]),
+                EvaluationInput::Param(vec![5.0]),
+            ]
+        );
+
+        let problem = problem.with_capacity(0);
+        problem.cost(&vec![6.0]).unwrap();
+        assert!(problem.evaluations().is_empty());
+    }
+
+    #[test]
+    fn test_with_param_hash() {
+        let problem =
+            InstrumentedProblem::new(TestProblem::new()).with_param_hash(|p| p.len() as u64);
+        problem.cost(&vec![1.0, 2.0]).unwrap();
+        assert_eq!(problem.evaluations()[0].input, EvaluationInput::Hash(2));
+    }
+
+    #[test]
+    fn test_with_file() {
+        let path = std::env::temp_dir().join("argmin_instrumented_problem_test.tsv");
+        let _ = std::fs::remove_file(&path);
+
+        let problem = InstrumentedProblem::new(TestProblem::new())
+            .with_capacity(0)
+            .with_file(&path)
+            .unwrap();
+        problem.cost(&vec![1.0, 2.0]).unwrap();
+        problem.gradient(&vec![3.0]).unwrap();
+        problem.flush().unwrap();
+
+        let content = std::fs::read_to_string(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        let lines: Vec<Vec<&str>> = content.lines().map(|l| l.split('\t').collect()).collect();
+        assert_eq!(lines.len(), 2);
+        assert_eq!(lines[0][0], "cost");
+        assert_eq!(lines[0][2], "1");
+        assert_eq!(lines[0][3], "[1.0, 2.0]");
+        assert_eq!(lines[1][0], "gradient");
+        assert_eq!(lines[1][2], "");
+        assert_eq!(lines[1][3], "[3.0]");
+    }
+}
@@ -237,6 +237,13 @@ impl From<f32> for KvValue {
     }
 }
 
+#[cfg(feature = "twofloat")]
+impl From<twofloat::TwoFloat> for KvValue {
+    fn from(x: twofloat::TwoFloat) -> KvValue {
+        KvValue::Float(f64::from(x))
+    }
+}
+
 impl From<i64> for KvValue {
     fn from(x: i64) -> KvValue {
         KvValue::Int(x)
@@ -292,6 +299,111 @@ impl Display for KvValue {
     }
 }
 
+/// Preferred scale for displaying a value
+///
+/// Used as a display hint by observers, for instance to plot a quantity which varies over many
+/// orders of magnitude on a logarithmic axis.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+pub enum KvScale {
+    /// Linear scale
+    #[default]
+    Linear,
+    /// Logarithmic scale
+    Log,
+}
+
+/// Metadata of a key in a [`KV`]
+///
+/// Describes how the value of a key should be rendered by observers: the unit of the value, the
+/// preferred scale and a human-readable description. All fields are optional hints and observers
+/// are free to ignore them.
+///
+/// # Example
+///
+/// ```
+/// # use argmin::core::{KvMeta, KvScale};
+/// let meta = KvMeta::new()
+///     .unit("K")
+///     .scale(KvScale::Log)
+///     .description("Temperature");
+/// # assert_eq!(meta.unit, Some("K".to_string()));
+/// # assert_eq!(meta.scale, KvScale::Log);
+/// # assert_eq!(meta.description, Some("Temperature".to_string()));
+/// ```
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+pub struct KvMeta {
+    /// Unit of the value
+    pub unit: Option<String>,
+    /// Preferred scale
+    pub scale: KvScale,
+    /// Description of the value
+    pub description: Option<String>,
+}
+
+impl KvMeta {
+    /// Construct a new `KvMeta` without unit and description and with linear scale
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::{KvMeta, KvScale};
+    /// let meta = KvMeta::new();
+    /// # assert_eq!(meta.unit, None);
+    /// # assert_eq!(meta.scale, KvScale::Linear);
+    /// # assert_eq!(meta.description, None);
+    /// ```
+    pub fn new() -> Self {
+        KvMeta::default()
+    }
+
+    /// Set the unit
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::KvMeta;
+    /// let meta = KvMeta::new().unit("s");
+    /// # assert_eq!(meta.unit, Some("s".to_string()));
+    /// ```
+    #[must_use]
+    pub fn unit<T: AsRef<str>>(mut self, unit: T) -> Self {
+        self.unit = Some(unit.as_ref().to_string());
+        self
+    }
+
+    /// Set the preferred scale
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::{KvMeta, KvScale};
+    /// let meta = KvMeta::new().scale(KvScale::Log);
+    /// # assert_eq!(meta.scale, KvScale::Log);
+    /// ```
+    #[must_use]
+    pub fn scale(mut self, scale: KvScale) -> Self {
+        self.scale = scale;
+        self
+    }
+
+    /// Set the description
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::KvMeta;
+    /// let meta = KvMeta::new().description("Radius of the trust region");
+    /// # assert_eq!(meta.description, Some("Radius of the trust region".to_string()));
+    /// ```
+    #[must_use]
+    pub fn description<T: AsRef<str>>(mut self, description: T) -> Self {
+        self.description = Some(description.as_ref().to_string());
+        self
+    }
+}
+
 /// A simple key-value storage
 ///
 /// Keeps pairs of `(&'static str, KvValue)` and is used to pass key-value pairs to
@@ -318,6 +430,9 @@ impl Display for KvValue {
 pub struct KV {
     /// The actual key value storage
     pub kv: HashMap<String, KvValue>,
+    /// Optional metadata (unit, scale, description) per key
+    #[cfg_attr(feature = "serde1", serde(default))]
+    pub meta: HashMap<String, KvMeta>,
 }
 
 impl Debug for KV {
@@ -329,8 +444,8 @@ impl Debug for KV {
 impl Display for KV {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         writeln!(f, "KV")?;
-        for (key, val) in self.kv.iter() {
-            writeln!(f, "   {key}: {val}")?;
+        for key in self.kv.keys() {
+            writeln!(f, "   {key}: {}", self.format_value(key).unwrap())?;
         }
         Ok(())
     }
@@ -348,7 +463,10 @@ impl KV {
     /// # assert_eq!(kv.kv.len(), 0);
     /// ```
     pub fn new() -> Self {
-        KV { kv: HashMap::new() }
+        KV {
+            kv: HashMap::new(),
+            meta: HashMap::new(),
+        }
     }
 
     /// Insert a key-value pair
@@ -387,6 +505,68 @@ impl KV {
         self.kv.get(key.as_ref())
     }
 
+    /// Set the metadata of a key
+    ///
+    /// The key does not need to be present in the `KV` yet.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::{KV, KvMeta, KvScale, KvValue};
+    /// let mut kv = KV::new();
+    /// kv.insert("t", KvValue::Float(100.0));
+    /// kv.set_meta("t", KvMeta::new().scale(KvScale::Log).description("Temperature"));
+    /// # assert_eq!(kv.get_meta("t").unwrap().scale, KvScale::Log);
+    /// ```
+    pub fn set_meta<T: AsRef<str>>(&mut self, key: T, meta: KvMeta) -> &mut Self {
+        self.meta.insert(key.as_ref().into(), meta);
+        self
+    }
+
+    /// Retrieve the metadata of a key
+    ///
+    /// Returns `Some(<reference to KvMeta>)` if metadata was set for `key` and `None` otherwise.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::{KV, KvMeta, KvValue};
+    /// let mut kv = KV::new();
+    /// kv.insert("time", KvValue::Float(1.5));
+    /// kv.set_meta("time", KvMeta::new().unit("s"));
+    ///
+    /// assert_eq!(kv.get_meta("time"), Some(&KvMeta::new().unit("s")));
+    /// assert_eq!(kv.get_meta("non_existing"), None);
+    /// ```
+    pub fn get_meta<T: AsRef<str>>(&self, key: T) -> Option<&KvMeta> {
+        self.meta.get(key.as_ref())
+    }
+
+    /// Get String representation of the value of a key including its unit (if set)
+    ///
+    /// Returns `None` if `key` is not present.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::{KV, KvMeta, KvValue};
+    /// let mut kv = KV::new();
+    /// kv.insert("time", KvValue::Float(1.5));
+    /// kv.insert("iters", KvValue::Uint(3));
+    /// kv.set_meta("time", KvMeta::new().unit("s"));
+    ///
+    /// assert_eq!(kv.format_value("time"), Some("1.5 s".to_string()));
+    /// assert_eq!(kv.format_value("iters"), Some("3".to_string()));
+    /// assert_eq!(kv.format_value("non_existing"), None);
+    /// ```
+    pub fn format_value<T: AsRef<str>>(&self, key: T) -> Option<String> {
+        let val = self.get(&key)?;
+        match self.get_meta(&key).and_then(|meta| meta.unit.as_ref()) {
+            Some(unit) => Some(format!("{val} {unit}")),
+            None => Some(val.to_string()),
+        }
+    }
+
     /// Returns all available keys and their `KvValue` kind
     ///
     /// # Example
@@ -404,6 +584,8 @@ impl KV {
 
     /// Merge with another `KV`
     ///
+    /// Values and metadata of `other` take precedence.
+    ///
     /// # Example
     ///
     /// ```
@@ -426,8 +608,41 @@ impl KV {
     #[must_use]
     pub fn merge(mut self, other: KV) -> Self {
         self.kv.extend(other.kv);
+        self.meta.extend(other.meta);
         self
     }
+
+    /// Prepends `prefix` followed by a dot to all keys (including the keys of the metadata)
+    ///
+    /// This is useful for merging the `KV` of a nested solver into the `KV` of the outer solver.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::{KV, KvValue};
+    /// let mut kv = KV::new();
+    /// kv.insert("step_length", KvValue::Float(0.5));
+    ///
+    /// let kv = kv.with_prefix("linesearch");
+    /// assert_eq!(kv.get("linesearch.step_length"), Some(&KvValue::Float(0.5)));
+    /// # assert_eq!(kv.get("step_length"), None);
+    /// ```
+    #[must_use]
+    pub fn with_prefix<T: AsRef<str>>(self, prefix: T) -> Self {
+        let prefix = prefix.as_ref();
+        KV {
+            kv: self
+                .kv
+                .into_iter()
+                .map(|(k, v)| (format!("{prefix}.{k}"), v))
+                .collect(),
+            meta: self
+                .meta
+                .into_iter()
+                .map(|(k, v)| (format!("{prefix}.{k}"), v))
+                .collect(),
+        }
+    }
 }
 
 impl std::iter::FromIterator<(&'static str, KvValue)> for KV {
@@ -27,7 +27,10 @@
 #[macro_export]
 macro_rules! kv {
     ($($k:expr =>  $v:expr;)*) => {
-        $crate::core::KV { kv: std::collections::HashMap::from([ $(($k.into(), $v.into())),* ]) }
+        $crate::core::KV {
+            kv: std::collections::HashMap::from([ $(($k.into(), $v.into())),* ]),
+            meta: std::collections::HashMap::new(),
+        }
     };
 }
 
@@ -107,7 +110,7 @@ macro_rules! bulk {
                 $output: SendAlias,
                 Self: SyncAlias,
             {
-                #[cfg(feature = "rayon")]
+                #[cfg(all(feature = "rayon", not(target_arch = "wasm32")))]
                 {
                     if self.parallelize() {
                         params.par_iter().map(|p| self.$method_name(p.borrow())).collect()
@@ -115,7 +118,7 @@ macro_rules! bulk {
                         params.iter().map(|p| self.$method_name(p.borrow())).collect()
                     }
                 }
-                #[cfg(not(feature = "rayon"))]
+                #[cfg(any(not(feature = "rayon"), target_arch = "wasm32"))]
                 {
                     params.iter().map(|p| self.$method_name(p.borrow())).collect()
                 }
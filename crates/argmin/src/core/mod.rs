@@ -13,22 +13,59 @@
 /// Macros
 #[macro_use]
 pub mod macros;
+/// Archive of the best parameter vectors
+mod archive;
+/// Exact derivatives via automatic differentiation
+#[cfg(feature = "autodiff")]
+mod autodiff;
+/// Batch runs of a solver on several problems
+mod batch;
+/// Type-erased solver
+mod boxed_solver;
+/// Caching of cost function evaluations
+mod cache;
 pub mod checkpointing;
+/// Comparison of solvers on the same problem
+mod comparison;
+/// Parameter covariance estimation at the optimum
+mod covariance;
+/// Comparison of analytical derivatives with finite differences
+#[cfg(feature = "finitediff")]
+mod derivative_check;
+pub mod discrete;
 /// Error handling
 mod errors;
 /// Executor
 mod executor;
+/// Construction of solvers by name
+#[cfg(feature = "factory")]
+mod factory;
 /// Trait alias for float types
 mod float;
+/// Policy for evaluations at the initial parameter vector
+mod initial_evaluation;
+/// Problem wrapper which records evaluations
+mod instrumented;
 /// Key value data structure
 mod kv;
+/// Telemetry of nested solvers
+mod nested;
 pub mod observers;
 /// Trait alias for `Send` and `Sync`
 mod parallelization;
+/// Problem wrapper which replaces failed cost function evaluations with a penalty
+mod penalty;
 /// Traits and structs for defining and handling optimization problems
 mod problem;
+pub mod problems;
+/// Profile scans of the cost function
+mod profile;
+/// Recording and replaying of runs
+mod replay;
 /// Definition of the return type of the solvers
 mod result;
+/// Identification and metadata of optimization runs
+mod run;
 /// `Solver` trait
 mod solver;
 /// iteration state
@@ -37,18 +74,63 @@ mod state;
 mod termination;
 /// Convenience utilities for testing
 pub mod test_utils;
+/// Problem wrapper which limits the duration of cost function evaluations
+#[cfg(not(target_arch = "wasm32"))]
+mod timeout;
+/// Parameter transforms
+mod transform;
+/// Thread-safe handle for inspecting the state of a running executor
+mod watch;
+/// Pool of scratch buffers
+mod workspace;
 
 pub use crate::solver::conjugategradient::beta::NLCGBetaUpdate;
 pub use crate::solver::linesearch::LineSearch;
 pub use crate::solver::trustregion::TrustRegionRadius;
 pub use anyhow::Error;
-pub use errors::ArgminError;
+pub use archive::Archive;
+#[cfg(feature = "derive")]
+pub use argmin_derive::{CostFunction, Gradient, Hessian, Jacobian, Operator};
+#[cfg(feature = "autodiff")]
+pub use autodiff::{AutoDiff, DualCostFunction};
+pub use batch::BatchRunner;
+pub use boxed_solver::BoxedSolver;
+pub use cache::{CachedProblem, EvaluationCache};
+pub use comparison::{Comparison, ComparisonEntry, ComparisonSummary, TrajectoryPoint};
+pub use covariance::{covariance_from_hessian, covariance_from_jacobian, CovarianceEstimate};
+#[cfg(feature = "finitediff")]
+pub use derivative_check::{
+    check_gradient, check_hessian, check_jacobian, DerivativeCheckEntry, DerivativeCheckReport,
+};
+pub use errors::{ArgminError, ErrorCategory, SolverErrorContext};
 pub use executor::Executor;
+#[cfg(feature = "factory")]
+pub use factory::{SolverConstructor, SolverFactory};
 pub use float::ArgminFloat;
-pub use kv::{KvValue, KV};
+pub use initial_evaluation::InitialEvaluation;
+pub use instrumented::{Evaluation, EvaluationInput, EvaluationKind, InstrumentedProblem};
+pub use kv::{KvMeta, KvScale, KvValue, KV};
+pub use nested::NestedTelemetry;
 pub use parallelization::{SendAlias, SyncAlias};
-pub use problem::{CostFunction, Gradient, Hessian, Jacobian, LinearProgram, Operator, Problem};
+pub use penalty::PenalizedProblem;
+pub use problem::{
+    AdjointOperator, CostFunction, Gradient, Hessian, Jacobian, JacobianVectorProduct,
+    LinearProgram, Operator, Problem,
+};
+pub use profile::{FixedParameter, Profile, ProfilePoint, ProfileScan};
+pub use replay::{ReplayEntry, ReplayLog, ReplayRecorder, Replayer};
 pub use result::OptimizationResult;
+pub use run::RunInfo;
 pub use solver::Solver;
-pub use state::{IterState, LinearProgramState, PopulationState, State};
+pub use state::{
+    CapStep, CheckNumerics, ClipParam, GradientNorm, IterState, LinearProgramState, ParetoState,
+    PopulationState, SimplexState, State,
+};
 pub use termination::{TerminationReason, TerminationStatus};
+#[cfg(not(target_arch = "wasm32"))]
+pub use timeout::TimeoutProblem;
+pub use transform::{
+    Chain, LogTransform, LogitTransform, SoftmaxTransform, Transform, TransformedProblem,
+};
+pub use watch::{StateSnapshot, StateWatch};
+pub use workspace::Workspace;
@@ -0,0 +1,209 @@
+// Copyright 2018-2024 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use crate::core::{KvValue, Problem, State, KV};
+#[cfg(feature = "serde1")]
+use serde::{Deserialize, Serialize};
+
+/// Telemetry of solvers which are run by another solver
+///
+/// Some solvers internally run other solvers in each iteration, for instance a line search
+/// ([`SteepestDescent`](`crate::solver::gradientdescent::SteepestDescent`)) or a trust region
+/// subproblem ([`TrustRegion`](`crate::solver::trustregion::TrustRegion`)). The function
+/// evaluations of these inner solvers are always added to the function evaluation counts of the
+/// outer problem. In addition, [`record`](`NestedTelemetry::record`) adds the number of inner
+/// iterations to the counts of the outer problem, which end up in the state of the outer solver.
+///
+/// Optionally, the key-value pairs returned by the inner solver (see
+/// [`Executor::run_nested`](`crate::core::Executor::run_nested`)), the number of inner
+/// iterations (`iters`) and the termination reason of the inner solver (`termination`) are
+/// forwarded to the observers of the outer solver. All keys are prefixed with the given prefix
+/// followed by a dot, such as `linesearch.iters`.
+///
+/// # Example
+///
+/// ```
+/// # use argmin::core::NestedTelemetry;
+/// // Only count inner iterations
+/// let telemetry = NestedTelemetry::new();
+/// # assert_eq!(telemetry.prefix(), None);
+///
+/// // Also forward the inner key-value pairs to observers
+/// let telemetry = NestedTelemetry::forward("linesearch");
+/// # assert_eq!(telemetry.prefix(), Some("linesearch"));
+/// ```
+#[derive(Clone, Debug, Default, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+pub struct NestedTelemetry {
+    /// Prefix of the forwarded keys. Nothing is forwarded if `None`.
+    prefix: Option<String>,
+}
+
+impl NestedTelemetry {
+    /// Construct a new instance of `NestedTelemetry` which does not forward anything to observers
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::NestedTelemetry;
+    /// let telemetry = NestedTelemetry::new();
+    /// ```
+    pub fn new() -> Self {
+        NestedTelemetry { prefix: None }
+    }
+
+    /// Construct a new instance of `NestedTelemetry` which forwards the telemetry of the inner
+    /// solver to observers with keys prefixed by `prefix`
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::NestedTelemetry;
+    /// let telemetry = NestedTelemetry::forward("linesearch");
+    /// ```
+    pub fn forward<T: AsRef<str>>(prefix: T) -> Self {
+        NestedTelemetry {
+            prefix: Some(prefix.as_ref().to_string()),
+        }
+    }
+
+    /// Returns the prefix of the forwarded keys (if forwarding is enabled)
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::NestedTelemetry;
+    /// let telemetry = NestedTelemetry::forward("linesearch");
+    /// assert_eq!(telemetry.prefix(), Some("linesearch"));
+    /// ```
+    pub fn prefix(&self) -> Option<&str> {
+        self.prefix.as_deref()
+    }
+
+    /// Records a run of an inner solver
+    ///
+    /// Adds the number of iterations of the inner solver (taken from its final `state`) to the
+    /// count `counts_key` of the outer `problem`. If forwarding is enabled, returns the `KV` of
+    /// the inner solver together with the number of iterations and the termination reason, with
+    /// all keys prefixed. This `KV` is meant to be merged into the `KV` returned by `next_iter`
+    /// of the outer solver.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::{Error, Executor, NestedTelemetry, Problem, KvValue, State};
+    /// # use argmin::core::test_utils::{TestSolver, TestProblem};
+    /// # fn main() -> Result<(), Error> {
+    /// let mut problem = Problem::new(TestProblem::new());
+    /// let telemetry = NestedTelemetry::forward("inner");
+    ///
+    /// let (result, kv) = Executor::new(problem.take_problem().unwrap(), TestSolver::new())
+    ///     .configure(|state| state.param(vec![1.0f64, 0.0]).max_iters(3))
+    ///     .run_nested()?;
+    /// problem.consume_problem(result.problem);
+    ///
+    /// let kv = telemetry.record(&mut problem, "inner_iter_count", &result.state, kv);
+    /// assert_eq!(problem.counts["inner_iter_count"], 3);
+    /// assert_eq!(kv.unwrap().get("inner.iters"), Some(&KvValue::Uint(3)));
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn record<O, I: State>(
+        &self,
+        problem: &mut Problem<O>,
+        counts_key: &'static str,
+        state: &I,
+        kv: KV,
+    ) -> Option<KV> {
+        *problem.counts.entry(counts_key).or_insert(0) += state.get_iter();
+        self.prefix.as_ref().map(|prefix| {
+            let mut kv = kv;
+            kv.insert("iters", KvValue::Uint(state.get_iter()));
+            if let Some(reason) = state.get_termination_reason() {
+                kv.insert("termination", KvValue::Str(reason.to_string()));
+            }
+            kv.with_prefix(prefix)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::test_utils::TestProblem;
+    use crate::core::{Error, Executor, IterState, Solver, TerminationReason};
+
+    #[test]
+    fn test_record() {
+        let mut problem = Problem::new(TestProblem::new());
+        let mut state: IterState<Vec<f64>, (), (), (), (), f64> =
+            IterState::new().terminate_with(TerminationReason::MaxItersReached);
+        for _ in 0..4 {
+            state.increment_iter();
+        }
+
+        let res = NestedTelemetry::new().record(&mut problem, "inner_iter_count", &state, kv!());
+        assert!(res.is_none());
+        assert_eq!(problem.counts["inner_iter_count"], 4);
+
+        let res = NestedTelemetry::forward("inner")
+            .record(
+                &mut problem,
+                "inner_iter_count",
+                &state,
+                kv!("gamma" => 0.5;),
+            )
+            .unwrap();
+        assert_eq!(problem.counts["inner_iter_count"], 8);
+        assert_eq!(res.kv.len(), 3);
+        assert_eq!(res.get("inner.gamma"), Some(&KvValue::Float(0.5)));
+        assert_eq!(res.get("inner.iters"), Some(&KvValue::Uint(4)));
+        assert_eq!(
+            res.get("inner.termination"),
+            Some(&KvValue::Str(
+                TerminationReason::MaxItersReached.to_string()
+            ))
+        );
+    }
+
+    #[test]
+    fn test_run_nested() {
+        struct KvSolver {}
+
+        impl<O> Solver<O, IterState<Vec<f64>, (), (), (), (), f64>> for KvSolver {
+            fn name(&self) -> &str {
+                "KvSolver"
+            }
+
+            fn init(
+                &mut self,
+                _problem: &mut Problem<O>,
+                state: IterState<Vec<f64>, (), (), (), (), f64>,
+            ) -> Result<(IterState<Vec<f64>, (), (), (), (), f64>, Option<KV>), Error> {
+                Ok((state, Some(kv!("init" => true; "iter" => -1i64;))))
+            }
+
+            fn next_iter(
+                &mut self,
+                _problem: &mut Problem<O>,
+                state: IterState<Vec<f64>, (), (), (), (), f64>,
+            ) -> Result<(IterState<Vec<f64>, (), (), (), (), f64>, Option<KV>), Error> {
+                let iter = state.get_iter();
+                Ok((state, Some(kv!("iter" => iter;))))
+            }
+        }
+
+        let (result, kv) = Executor::new(TestProblem::new(), KvSolver {})
+            .configure(|state| state.param(vec![1.0f64, 0.0]).max_iters(5))
+            .run_nested()
+            .unwrap();
+        assert_eq!(result.state.get_iter(), 5);
+        assert_eq!(kv.kv.len(), 2);
+        assert_eq!(kv.get("init"), Some(&KvValue::Bool(true)));
+        assert_eq!(kv.get("iter"), Some(&KvValue::Uint(4)));
+    }
+}
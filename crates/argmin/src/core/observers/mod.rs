@@ -25,6 +25,14 @@
 //! Custom observers can be used as well by implementing the [`crate::core::observers::Observe`]
 //! trait.
 //!
+//! By default, an error returned by an observer aborts the optimization. Observers which are not
+//! essential to the run (for instance the ones sending data over the network) can be added with an
+//! [`ObserverErrorPolicy`] via `Executor::add_observer_with_policy`, which either retries the
+//! failed call or disables the observer for the remainder of the run.
+//!
+//! Slow observers can be moved off the solver thread by wrapping them in a [`ThreadedObserver`],
+//! which calls the observer on a dedicated thread fed via a bounded queue.
+//!
 //! ## Example
 //!
 //! ```rust
@@ -110,6 +118,13 @@
 //! # }
 //! ```
 
+/// Observers running on a dedicated thread
+#[cfg(not(target_arch = "wasm32"))]
+mod threaded;
+
+#[cfg(not(target_arch = "wasm32"))]
+pub use threaded::{QueuePolicy, ThreadedObserver};
+
 #[cfg(feature = "serde1")]
 use serde::{Deserialize, Serialize};
 
@@ -173,16 +188,84 @@ pub trait Observe<I> {
     }
 }
 
-type ObserversVec<I> = Vec<(Arc<Mutex<dyn Observe<I>>>, ObserverMode)>;
+/// An observer with the conditions under which it is called and how its errors are handled
+#[derive(Clone)]
+struct ObserverEntry<I> {
+    /// The observer
+    observer: Arc<Mutex<dyn Observe<I>>>,
+    /// When to call the observer
+    mode: ObserverMode,
+    /// How errors of the observer are handled
+    policy: ObserverErrorPolicy,
+    /// Position of the observer in the order in which observers were added
+    index: usize,
+    /// Set once the observer was disabled due to an error
+    disabled: Option<DisabledObserver>,
+}
+
+impl<I> ObserverEntry<I> {
+    /// Calls the observer via `call` and handles errors according to the error policy.
+    fn dispatch<F>(&mut self, method: &'static str, iter: u64, mut call: F) -> Result<(), Error>
+    where
+        F: FnMut(&mut dyn Observe<I>) -> Result<(), Error>,
+    {
+        if self.disabled.is_some() {
+            return Ok(());
+        }
+        let mut observer = self.observer.lock().unwrap();
+        let mut result = call(&mut *observer);
+        if let ObserverErrorPolicy::Retry(retries) = self.policy {
+            for _ in 0..retries {
+                if result.is_ok() {
+                    break;
+                }
+                result = call(&mut *observer);
+            }
+        }
+        match (result, self.policy) {
+            (Err(e), ObserverErrorPolicy::WarnAndDisable) => {
+                self.disabled = Some(DisabledObserver {
+                    index: self.index,
+                    method,
+                    iter,
+                    error: e.to_string(),
+                });
+                Ok(())
+            }
+            (result, _) => result,
+        }
+    }
+}
+
+/// An observer which was disabled after returning an error, because it was added with
+/// [`ObserverErrorPolicy::WarnAndDisable`]
+///
+/// The observers disabled during a run are available via the
+/// [`disabled_observers`](`crate::core::OptimizationResult::disabled_observers`) of the
+/// `OptimizationResult`.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DisabledObserver {
+    /// Position of the observer in the order in which the observers were added
+    pub index: usize,
+    /// Method of the observer which failed (`observe_init`, `observe_iter` or `observe_final`)
+    pub method: &'static str,
+    /// Iteration in which the observer failed
+    pub iter: u64,
+    /// Message of the error returned by the observer
+    pub error: String,
+}
+
+type ObserversVec<I> = Vec<ObserverEntry<I>>;
 
 /// Container for observers.
 ///
 /// This type also implements [`Observe`] and therefore can be used like a single observer.
 /// Each observer has an [`ObserverMode`] attached which indicates when the observer will be
-/// called.
+/// called and an [`ObserverErrorPolicy`] which defines how errors returned by the observer are
+/// handled.
 #[derive(Clone, Default)]
 pub struct Observers<I> {
-    /// Vector of `Observe`rs with the corresponding `ObserverMode`
+    /// Vector of `Observe`rs with the corresponding `ObserverMode` and `ObserverErrorPolicy`
     observers: ObserversVec<I>,
 }
 
@@ -222,10 +305,59 @@ impl<I> Observers<I> {
         observer: OBS,
         mode: ObserverMode,
     ) -> &mut Self {
-        self.observers.push((Arc::new(Mutex::new(observer)), mode));
+        self.push_with_policy(observer, mode, ObserverErrorPolicy::FailFast)
+    }
+
+    /// Add another observer with a corresponding [`ObserverMode`] and [`ObserverErrorPolicy`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use argmin::core::observers::{ObserverErrorPolicy, Observers, ObserverMode};
+    /// use argmin_observer_slog::SlogLogger;
+    /// use argmin::core::IterState;
+    ///
+    /// let mut observers: Observers<IterState<Vec<f64>, (), (), (), (), f64>> = Observers::new();
+    ///
+    /// let logger = SlogLogger::term();
+    /// observers.push_with_policy(logger, ObserverMode::Always, ObserverErrorPolicy::Retry(3));
+    /// # assert!(!observers.is_empty());
+    /// ```
+    pub fn push_with_policy<OBS: Observe<I> + 'static>(
+        &mut self,
+        observer: OBS,
+        mode: ObserverMode,
+        policy: ObserverErrorPolicy,
+    ) -> &mut Self {
+        self.observers.push(ObserverEntry {
+            observer: Arc::new(Mutex::new(observer)),
+            mode,
+            policy,
+            index: self.observers.len(),
+            disabled: None,
+        });
         self
     }
 
+    /// Returns the observers which were disabled after returning an error, in the order in
+    /// which they were added.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use argmin::core::observers::Observers;
+    /// use argmin::core::IterState;
+    ///
+    /// let observers: Observers<IterState<Vec<f64>, (), (), (), (), f64>> = Observers::new();
+    /// assert!(observers.disabled().is_empty());
+    /// ```
+    pub fn disabled(&self) -> Vec<DisabledObserver> {
+        self.observers
+            .iter()
+            .filter_map(|entry| entry.disabled.clone())
+            .collect()
+    }
+
     /// Returns true if there are no observers stored.
     ///
     /// # Example
@@ -248,8 +380,10 @@ impl<I> Observers<I> {
 impl<I: State> Observe<I> for Observers<I> {
     /// After initialization of the solver, this loops over all stored observers and calls them.
     fn observe_init(&mut self, name: &str, state: &I, kv: &KV) -> Result<(), Error> {
-        for l in self.observers.iter() {
-            l.0.lock().unwrap().observe_init(name, state, kv)?
+        for l in self.observers.iter_mut() {
+            l.dispatch("observe_init", state.get_iter(), |o| {
+                o.observe_init(name, state, kv)
+            })?
         }
         Ok(())
     }
@@ -261,21 +395,25 @@ impl<I: State> Observe<I> for Observers<I> {
     fn observe_iter(&mut self, state: &I, kv: &KV) -> Result<(), Error> {
         for l in self.observers.iter_mut() {
             let iter = state.get_iter();
-            let observer = &mut l.0.lock().unwrap();
-            match l.1 {
-                ObserverMode::Always => observer.observe_iter(state, kv),
-                ObserverMode::Every(i) if iter % i == 0 => observer.observe_iter(state, kv),
-                ObserverMode::NewBest if state.is_best() => observer.observe_iter(state, kv),
-                ObserverMode::Never | ObserverMode::Every(_) | ObserverMode::NewBest => Ok(()),
-            }?
+            let call = match l.mode {
+                ObserverMode::Always => true,
+                ObserverMode::Every(i) => iter % i == 0,
+                ObserverMode::NewBest => state.is_best(),
+                ObserverMode::Never => false,
+            };
+            if call {
+                l.dispatch("observe_iter", iter, |o| o.observe_iter(state, kv))?
+            }
         }
         Ok(())
     }
 
     /// Called at the end of a solver run. Loops over all stored observers and calls `observe_final`
     fn observe_final(&mut self, state: &I) -> Result<(), Error> {
-        for l in self.observers.iter() {
-            l.0.lock().unwrap().observe_final(state)?
+        for l in self.observers.iter_mut() {
+            l.dispatch("observe_final", state.get_iter(), |o| {
+                o.observe_final(state)
+            })?
         }
         Ok(())
     }
@@ -306,11 +444,39 @@ impl Default for ObserverMode {
     }
 }
 
+/// Indicates how errors returned by an observer are handled.
+///
+/// `FailFast` aborts the optimization with the error of the observer. `WarnAndDisable` records
+/// the error as a [`DisabledObserver`] and does not call the observer again for the remainder of
+/// the run, such that optional telemetry cannot abort a long running optimization. `Retry(n)`
+/// calls the observer up to `n` more times and aborts the optimization if all attempts failed.
+///
+/// # Example
+///
+/// ```
+/// use argmin::core::observers::ObserverErrorPolicy;
+///
+/// // The default is `ObserverErrorPolicy::FailFast`
+/// assert_eq!(ObserverErrorPolicy::default(), ObserverErrorPolicy::FailFast);
+/// ```
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+pub enum ObserverErrorPolicy {
+    /// Abort the optimization with the error of the observer
+    #[default]
+    FailFast,
+    /// Record the error and disable the observer
+    WarnAndDisable,
+    /// Retry the call up to N times before aborting the optimization
+    Retry(u32),
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
     send_sync_test!(observermode, ObserverMode);
+    send_sync_test!(observererrorpolicy, ObserverErrorPolicy);
 
     #[test]
     fn test_observers() {
@@ -442,4 +608,86 @@ mod tests {
         assert_eq!(storages[3].lock().unwrap().init_called, 1);
         assert_eq!(storages[3].lock().unwrap().iter_called, 2);
     }
+
+    #[test]
+    fn test_error_policy() {
+        use crate::core::{ArgminError, IterState};
+
+        type TState = IterState<Vec<f64>, (), (), (), (), f64>;
+
+        // Observer which fails the first `failures` calls
+        struct FailingObs {
+            failures: usize,
+            calls: Arc<Mutex<usize>>,
+        }
+
+        impl<I> Observe<I> for FailingObs {
+            fn observe_iter(&mut self, _state: &I, _kv: &KV) -> Result<(), Error> {
+                let mut calls = self.calls.lock().unwrap();
+                *calls += 1;
+                if *calls <= self.failures {
+                    Err(argmin_error!(NotImplemented, "broken pipe"))
+                } else {
+                    Ok(())
+                }
+            }
+        }
+
+        let run = |failures: usize, policy: ObserverErrorPolicy| {
+            let calls = Arc::new(Mutex::new(0));
+            let mut obs: Observers<TState> = Observers::new();
+            obs.push_with_policy(
+                FailingObs {
+                    failures,
+                    calls: calls.clone(),
+                },
+                ObserverMode::Always,
+                policy,
+            );
+            let mut state: TState = IterState::new();
+            let mut results = vec![];
+            for _ in 0..3 {
+                results.push(obs.observe_iter(&state, &kv!()));
+                state.increment_iter();
+            }
+            let calls = *calls.lock().unwrap();
+            (results, calls, obs.disabled())
+        };
+
+        // FailFast propagates each error
+        let (mut results, calls, disabled) = run(1, ObserverErrorPolicy::FailFast);
+        assert!(results[1].is_ok());
+        assert_error!(
+            results.remove(0),
+            ArgminError,
+            "Not implemented: \"broken pipe\""
+        );
+        assert_eq!(calls, 3);
+        assert!(disabled.is_empty());
+
+        // WarnAndDisable swallows the error and stops calling the observer
+        let (results, calls, disabled) = run(1, ObserverErrorPolicy::WarnAndDisable);
+        assert!(results.iter().all(|r| r.is_ok()));
+        assert_eq!(calls, 1);
+        assert_eq!(
+            disabled,
+            vec![DisabledObserver {
+                index: 0,
+                method: "observe_iter",
+                iter: 0,
+                error: "Not implemented: \"broken pipe\"".to_string(),
+            }]
+        );
+
+        // Retry succeeds if one of the retries succeeds
+        let (results, calls, _) = run(2, ObserverErrorPolicy::Retry(2));
+        assert!(results.iter().all(|r| r.is_ok()));
+        assert_eq!(calls, 5);
+
+        // Retry fails if all attempts failed
+        let (results, calls, _) = run(3, ObserverErrorPolicy::Retry(2));
+        assert!(results[0].is_err());
+        assert!(results[1].is_ok());
+        assert_eq!(calls, 5);
+    }
 }
@@ -0,0 +1,464 @@
+// Copyright 2018-2024 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use crate::core::observers::Observe;
+use crate::core::{Error, KV};
+#[cfg(feature = "serde1")]
+use serde::{Deserialize, Serialize};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::mpsc::{sync_channel, SyncSender, TrySendError};
+use std::sync::{Arc, Mutex};
+use std::thread::JoinHandle;
+
+/// Indicates what happens if the queue of a [`ThreadedObserver`] is full.
+///
+/// `Block` waits until the observer thread has processed enough messages, which slows down the
+/// solver to the speed of the observer but does not lose any iterations. `DropOnFull` discards
+/// the iteration instead. The calls after initialization and at the end of a run are never
+/// dropped.
+///
+/// # Example
+///
+/// ```
+/// use argmin::core::observers::QueuePolicy;
+///
+/// // The default is `QueuePolicy::Block`
+/// assert_eq!(QueuePolicy::default(), QueuePolicy::Block);
+/// ```
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+pub enum QueuePolicy {
+    /// Wait until there is space in the queue
+    #[default]
+    Block,
+    /// Discard the iteration if the queue is full
+    DropOnFull,
+}
+
+/// Message sent to the observer thread
+enum Message<I> {
+    Init(String, I, KV),
+    Iter(I, KV),
+    Final(I),
+}
+
+/// Handle of a running observer thread
+struct Worker<I> {
+    sender: SyncSender<Message<I>>,
+    handle: JoinHandle<()>,
+}
+
+/// Runs an observer on a dedicated thread
+///
+/// Slow observers (for instance writing to disk, sending data over the network or updating a GUI)
+/// stall the solver since they are called synchronously after each iteration. Wrapping such an
+/// observer in a `ThreadedObserver` moves the calls to a dedicated thread. The state and the `KV`
+/// are cloned and sent to the thread via a bounded queue. The behavior when the queue is full is
+/// defined by the [`QueuePolicy`].
+///
+/// The thread is started in `observe_init` and joined in `observe_final`, therefore all messages
+/// are processed once the run has finished. Errors returned by the wrapped observer are reported
+/// by the next call of the `ThreadedObserver` (and therefore are subject to the
+/// [`ObserverErrorPolicy`](`crate::core::observers::ObserverErrorPolicy`) it was added with).
+///
+/// # Example
+///
+/// ```
+/// # use argmin::core::{Error, Executor};
+/// # use argmin::core::observers::{ObserverMode, QueuePolicy, ThreadedObserver};
+/// # use argmin::core::test_utils::{TestSolver, TestProblem};
+/// # use argmin_observer_slog::SlogLogger;
+/// # fn main() -> Result<(), Error> {
+/// # let solver = TestSolver::new();
+/// # let problem = TestProblem::new();
+/// let observer = ThreadedObserver::new(SlogLogger::term())
+///     .with_capacity(128)?
+///     .with_policy(QueuePolicy::DropOnFull);
+///
+/// // Number of iterations which were not observed because the queue was full
+/// let dropped = observer.dropped();
+///
+/// let res = Executor::new(problem, solver)
+///     .configure(|state| state.param(vec![1.0f64, 0.0]).max_iters(10))
+///     .add_observer(observer, ObserverMode::Always)
+///     .run()?;
+/// # assert_eq!(dropped.load(std::sync::atomic::Ordering::Relaxed), 0);
+/// # Ok(())
+/// # }
+/// ```
+pub struct ThreadedObserver<I> {
+    /// The wrapped observer
+    observer: Arc<Mutex<dyn Observe<I> + Send>>,
+    /// Maximum number of queued messages
+    capacity: usize,
+    /// What to do if the queue is full
+    policy: QueuePolicy,
+    /// Number of dropped iterations
+    dropped: Arc<AtomicU64>,
+    /// First error returned by the wrapped observer which was not reported yet
+    error: Arc<Mutex<Option<Error>>>,
+    /// Running observer thread
+    worker: Option<Worker<I>>,
+}
+
+impl<I> ThreadedObserver<I>
+where
+    I: Clone + Send + 'static,
+{
+    /// Wraps `observer`, using a queue of capacity 64 and [`QueuePolicy::Block`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::IterState;
+    /// # use argmin::core::observers::ThreadedObserver;
+    /// # use argmin_observer_slog::SlogLogger;
+    /// let observer: ThreadedObserver<IterState<Vec<f64>, (), (), (), (), f64>> =
+    ///     ThreadedObserver::new(SlogLogger::term());
+    /// ```
+    pub fn new<OBS: Observe<I> + Send + 'static>(observer: OBS) -> Self {
+        ThreadedObserver {
+            observer: Arc::new(Mutex::new(observer)),
+            capacity: 64,
+            policy: QueuePolicy::Block,
+            dropped: Arc::new(AtomicU64::new(0)),
+            error: Arc::new(Mutex::new(None)),
+            worker: None,
+        }
+    }
+
+    /// Set the maximum number of messages waiting in the queue
+    ///
+    /// Must be larger than zero and defaults to 64.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::{Error, IterState};
+    /// # use argmin::core::observers::ThreadedObserver;
+    /// # use argmin_observer_slog::SlogLogger;
+    /// # fn main() -> Result<(), Error> {
+    /// let observer: ThreadedObserver<IterState<Vec<f64>, (), (), (), (), f64>> =
+    ///     ThreadedObserver::new(SlogLogger::term()).with_capacity(16)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_capacity(mut self, capacity: usize) -> Result<Self, Error> {
+        if capacity == 0 {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "`ThreadedObserver`: capacity must be > 0."
+            ));
+        }
+        self.capacity = capacity;
+        Ok(self)
+    }
+
+    /// Set the [`QueuePolicy`] (default: [`QueuePolicy::Block`])
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::IterState;
+    /// # use argmin::core::observers::{QueuePolicy, ThreadedObserver};
+    /// # use argmin_observer_slog::SlogLogger;
+    /// let observer: ThreadedObserver<IterState<Vec<f64>, (), (), (), (), f64>> =
+    ///     ThreadedObserver::new(SlogLogger::term()).with_policy(QueuePolicy::DropOnFull);
+    /// ```
+    #[must_use]
+    pub fn with_policy(mut self, policy: QueuePolicy) -> Self {
+        self.policy = policy;
+        self
+    }
+
+    /// Returns a handle to the number of iterations which were dropped because the queue was full
+    ///
+    /// The handle stays valid after the observer was moved into an `Executor`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::IterState;
+    /// # use argmin::core::observers::ThreadedObserver;
+    /// # use argmin_observer_slog::SlogLogger;
+    /// # use std::sync::atomic::Ordering;
+    /// let observer: ThreadedObserver<IterState<Vec<f64>, (), (), (), (), f64>> =
+    ///     ThreadedObserver::new(SlogLogger::term());
+    /// let dropped = observer.dropped();
+    /// assert_eq!(dropped.load(Ordering::Relaxed), 0);
+    /// ```
+    pub fn dropped(&self) -> Arc<AtomicU64> {
+        Arc::clone(&self.dropped)
+    }
+
+    /// Starts the observer thread if it is not running yet.
+    fn start(&mut self) {
+        if self.worker.is_some() {
+            return;
+        }
+        let (sender, receiver) = sync_channel::<Message<I>>(self.capacity);
+        let observer = Arc::clone(&self.observer);
+        let error = Arc::clone(&self.error);
+        let handle = std::thread::spawn(move || {
+            for message in receiver {
+                let mut observer = observer.lock().unwrap();
+                let result = match message {
+                    Message::Init(name, state, kv) => observer.observe_init(&name, &state, &kv),
+                    Message::Iter(state, kv) => observer.observe_iter(&state, &kv),
+                    Message::Final(state) => observer.observe_final(&state),
+                };
+                if let Err(e) = result {
+                    error.lock().unwrap().get_or_insert(e);
+                }
+            }
+        });
+        self.worker = Some(Worker { sender, handle });
+    }
+
+    /// Stops the observer thread after all queued messages were processed.
+    fn stop(&mut self) -> Result<(), Error> {
+        if let Some(Worker { sender, handle }) = self.worker.take() {
+            drop(sender);
+            if handle.join().is_err() {
+                return Err(argmin_error!(
+                    PotentialBug,
+                    "`ThreadedObserver`: observer thread panicked."
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    /// Sends a message to the observer thread. If `may_drop` is true and the policy is
+    /// `DropOnFull`, the message is discarded if the queue is full.
+    fn send(&mut self, message: Message<I>, may_drop: bool) -> Result<(), Error> {
+        self.start();
+        let sender = &self.worker.as_ref().unwrap().sender;
+        let sent = if may_drop && self.policy == QueuePolicy::DropOnFull {
+            match sender.try_send(message) {
+                Err(TrySendError::Full(_)) => {
+                    self.dropped.fetch_add(1, Ordering::Relaxed);
+                    Ok(())
+                }
+                Err(TrySendError::Disconnected(_)) => Err(()),
+                Ok(()) => Ok(()),
+            }
+        } else {
+            sender.send(message).map_err(|_| ())
+        };
+        if sent.is_err() {
+            // The thread terminated, which only happens if the observer panicked.
+            self.stop()?;
+        }
+        self.take_error()
+    }
+
+    /// Returns the first error of the wrapped observer which was not reported yet.
+    fn take_error(&self) -> Result<(), Error> {
+        match self.error.lock().unwrap().take() {
+            Some(e) => Err(e),
+            None => Ok(()),
+        }
+    }
+}
+
+impl<I> Observe<I> for ThreadedObserver<I>
+where
+    I: Clone + Send + 'static,
+{
+    /// Starts the observer thread and queues the call to `observe_init`.
+    fn observe_init(&mut self, name: &str, state: &I, kv: &KV) -> Result<(), Error> {
+        self.send(
+            Message::Init(name.to_string(), state.clone(), kv.clone()),
+            false,
+        )
+    }
+
+    /// Queues the call to `observe_iter` according to the [`QueuePolicy`].
+    fn observe_iter(&mut self, state: &I, kv: &KV) -> Result<(), Error> {
+        self.send(Message::Iter(state.clone(), kv.clone()), true)
+    }
+
+    /// Queues the call to `observe_final` and waits until the observer thread processed all
+    /// messages.
+    fn observe_final(&mut self, state: &I) -> Result<(), Error> {
+        self.send(Message::Final(state.clone()), false)?;
+        self.stop()?;
+        self.take_error()
+    }
+}
+
+impl<I> Drop for ThreadedObserver<I> {
+    fn drop(&mut self) {
+        if let Some(Worker { sender, handle }) = self.worker.take() {
+            drop(sender);
+            let _ = handle.join();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::{ArgminError, IterState, State};
+    use std::sync::mpsc::{channel, Receiver, Sender};
+
+    type TState = IterState<Vec<f64>, (), (), (), (), f64>;
+
+    send_sync_test!(queuepolicy, QueuePolicy);
+
+    /// Observer which records the observed iterations and blocks in `observe_iter` until it
+    /// receives a token
+    struct Recorder {
+        iters: Arc<Mutex<Vec<u64>>>,
+        tokens: Option<Receiver<()>>,
+        fail_at: Option<u64>,
+    }
+
+    impl Recorder {
+        fn new() -> (Self, Arc<Mutex<Vec<u64>>>) {
+            let iters = Arc::new(Mutex::new(vec![]));
+            let recorder = Recorder {
+                iters: Arc::clone(&iters),
+                tokens: None,
+                fail_at: None,
+            };
+            (recorder, iters)
+        }
+
+        fn gated() -> (Self, Arc<Mutex<Vec<u64>>>, Sender<()>) {
+            let (recorder, iters) = Recorder::new();
+            let (sender, receiver) = channel();
+            let recorder = Recorder {
+                tokens: Some(receiver),
+                ..recorder
+            };
+            (recorder, iters, sender)
+        }
+    }
+
+    impl Observe<TState> for Recorder {
+        fn observe_iter(&mut self, state: &TState, _kv: &KV) -> Result<(), Error> {
+            if let Some(tokens) = self.tokens.as_ref() {
+                tokens.recv().unwrap();
+            }
+            if self.fail_at == Some(state.get_iter()) {
+                return Err(argmin_error!(NotImplemented, "broken pipe"));
+            }
+            self.iters.lock().unwrap().push(state.get_iter());
+            Ok(())
+        }
+    }
+
+    fn observe_iters(observer: &mut ThreadedObserver<TState>, n: u64) -> Result<(), Error> {
+        let mut state: TState = IterState::new();
+        for _ in 0..n {
+            observer.observe_iter(&state, &kv!())?;
+            state.increment_iter();
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_new() {
+        let (recorder, _) = Recorder::new();
+        let ThreadedObserver {
+            observer: _,
+            capacity,
+            policy,
+            ref dropped,
+            ref error,
+            ref worker,
+        } = ThreadedObserver::<TState>::new(recorder);
+        assert_eq!(capacity, 64);
+        assert_eq!(policy, QueuePolicy::Block);
+        assert_eq!(dropped.load(Ordering::Relaxed), 0);
+        assert!(error.lock().unwrap().is_none());
+        assert!(worker.is_none());
+    }
+
+    #[test]
+    fn test_with_capacity() {
+        let (recorder, _) = Recorder::new();
+        let observer = ThreadedObserver::<TState>::new(recorder)
+            .with_capacity(3)
+            .unwrap();
+        assert_eq!(observer.capacity, 3);
+
+        let (recorder, _) = Recorder::new();
+        let res = ThreadedObserver::<TState>::new(recorder).with_capacity(0);
+        assert_error!(
+            res,
+            ArgminError,
+            "Invalid parameter: \"`ThreadedObserver`: capacity must be > 0.\""
+        );
+    }
+
+    #[test]
+    fn test_block() {
+        let (recorder, iters) = Recorder::new();
+        let mut observer = ThreadedObserver::new(recorder).with_capacity(1).unwrap();
+        let state: TState = IterState::new();
+        observer.observe_init("test", &state, &kv!()).unwrap();
+        observe_iters(&mut observer, 20).unwrap();
+        observer.observe_final(&state).unwrap();
+        assert_eq!(*iters.lock().unwrap(), (0..20).collect::<Vec<_>>());
+        assert_eq!(observer.dropped().load(Ordering::Relaxed), 0);
+        assert!(observer.worker.is_none());
+    }
+
+    #[test]
+    fn test_drop_on_full() {
+        let (recorder, iters, tokens) = Recorder::gated();
+        let mut observer = ThreadedObserver::new(recorder)
+            .with_capacity(2)
+            .unwrap()
+            .with_policy(QueuePolicy::DropOnFull);
+        let dropped = observer.dropped();
+        let state: TState = IterState::new();
+        observer.observe_init("test", &state, &kv!()).unwrap();
+
+        // The observer thread blocks on the first iteration, two more fit into the queue.
+        observe_iters(&mut observer, 10).unwrap();
+        for _ in 0..10 {
+            tokens.send(()).unwrap();
+        }
+        observer.observe_final(&state).unwrap();
+
+        let observed = iters.lock().unwrap().len() as u64;
+        assert!((1..=3).contains(&observed));
+        assert_eq!(observed + dropped.load(Ordering::Relaxed), 10);
+    }
+
+    #[test]
+    fn test_error() {
+        let (recorder, _) = Recorder::new();
+        let recorder = Recorder {
+            fail_at: Some(2),
+            ..recorder
+        };
+        let mut observer = ThreadedObserver::new(recorder);
+        let state: TState = IterState::new();
+        observer.observe_init("test", &state, &kv!()).unwrap();
+        // The error is reported with a delay, but at the latest at the end of the run.
+        let res = observe_iters(&mut observer, 5).and_then(|_| observer.observe_final(&state));
+        assert_error!(res, ArgminError, "Not implemented: \"broken pipe\"");
+    }
+
+    #[test]
+    fn test_restart() {
+        let (recorder, iters) = Recorder::new();
+        let mut observer = ThreadedObserver::new(recorder);
+        let state: TState = IterState::new();
+        for _ in 0..2 {
+            observer.observe_init("test", &state, &kv!()).unwrap();
+            observe_iters(&mut observer, 3).unwrap();
+            observer.observe_final(&state).unwrap();
+        }
+        assert_eq!(*iters.lock().unwrap(), vec![0, 1, 2, 0, 1, 2]);
+    }
+}
@@ -0,0 +1,194 @@
+// Copyright 2018-2024 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use crate::core::{CostFunction, Error, Gradient};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+
+/// Wraps a problem and replaces failed cost function evaluations with a penalty cost
+///
+/// By default, an `Err` returned by the cost function aborts the optimization run. For
+/// crash-prone objectives such as external simulators it is often preferable to treat the
+/// offending point as infeasible and to continue instead. `PenalizedProblem` returns the
+/// configured penalty (typically `+inf` or a large value) whenever the wrapped cost function
+/// fails. Derivative-free solvers such as Simulated Annealing, Particle Swarm Optimization or
+/// Nelder-Mead will then simply move away from such points.
+///
+/// Failures are counted and can be queried via [`failures`](`PenalizedProblem::failures`); the
+/// most recent error is available via [`last_error`](`PenalizedProblem::last_error`). Clones of
+/// a `PenalizedProblem` share both. Gradients are passed through unchanged, i.e. errors of the
+/// gradient still abort the run.
+///
+/// # Example
+///
+/// ```
+/// use argmin::core::{Executor, PenalizedProblem};
+/// use argmin::solver::neldermead::NelderMead;
+/// # use argmin::core::{CostFunction, Error};
+/// #
+/// # struct Simulation {}
+/// #
+/// # impl CostFunction for Simulation {
+/// #     type Param = Vec<f64>;
+/// #     type Output = f64;
+/// #     fn cost(&self, p: &Self::Param) -> Result<Self::Output, Error> {
+/// #         if p[0] > 1.5 {
+/// #             return Err(Error::msg("simulation diverged"));
+/// #         }
+/// #         Ok(p[0].powi(2) + p[1].powi(2))
+/// #     }
+/// # }
+///
+/// # fn main() -> Result<(), Error> {
+/// // Points at which the simulation fails are assigned an infinite cost.
+/// let problem = PenalizedProblem::new(Simulation {}, f64::INFINITY);
+/// let failures = problem.clone();
+///
+/// let solver = NelderMead::new(vec![vec![1.0, 1.0], vec![2.0, 1.0], vec![1.0, 2.0]]);
+/// let res = Executor::new(problem, solver)
+///     .configure(|state| state.max_iters(10))
+///     .run()?;
+///
+/// assert!(failures.failures() > 0);
+/// # Ok(())
+/// # }
+/// ```
+pub struct PenalizedProblem<O: CostFunction> {
+    /// Wrapped problem
+    problem: Arc<O>,
+    /// Cost returned for failed evaluations
+    penalty: O::Output,
+    /// Number of failed evaluations
+    failures: Arc<AtomicU64>,
+    /// Message of the most recent error
+    last_error: Arc<Mutex<Option<String>>>,
+}
+
+impl<O> Clone for PenalizedProblem<O>
+where
+    O: CostFunction,
+    O::Output: Clone,
+{
+    fn clone(&self) -> Self {
+        PenalizedProblem {
+            problem: Arc::clone(&self.problem),
+            penalty: self.penalty.clone(),
+            failures: Arc::clone(&self.failures),
+            last_error: Arc::clone(&self.last_error),
+        }
+    }
+}
+
+impl<O: CostFunction> PenalizedProblem<O> {
+    /// Construct a new instance of `PenalizedProblem`
+    ///
+    /// Failed evaluations of the cost function return `penalty`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::PenalizedProblem;
+    /// # use argmin::core::test_utils::TestProblem;
+    /// let problem = PenalizedProblem::new(TestProblem::new(), f64::INFINITY);
+    /// ```
+    pub fn new(problem: O, penalty: O::Output) -> Self {
+        PenalizedProblem {
+            problem: Arc::new(problem),
+            penalty,
+            failures: Arc::new(AtomicU64::new(0)),
+            last_error: Arc::new(Mutex::new(None)),
+        }
+    }
+
+    /// Returns a reference to the wrapped problem
+    pub fn problem(&self) -> &O {
+        &self.problem
+    }
+
+    /// Returns the number of failed cost function evaluations
+    pub fn failures(&self) -> u64 {
+        self.failures.load(Ordering::Relaxed)
+    }
+
+    /// Returns the message of the most recent error of the cost function (if any)
+    pub fn last_error(&self) -> Option<String> {
+        self.last_error.lock().unwrap().clone()
+    }
+}
+
+impl<O> CostFunction for PenalizedProblem<O>
+where
+    O: CostFunction,
+    O::Output: Clone,
+{
+    type Param = O::Param;
+    type Output = O::Output;
+
+    fn cost(&self, param: &Self::Param) -> Result<Self::Output, Error> {
+        match self.problem.cost(param) {
+            Ok(cost) => Ok(cost),
+            Err(e) => {
+                self.failures.fetch_add(1, Ordering::Relaxed);
+                *self.last_error.lock().unwrap() = Some(e.to_string());
+                Ok(self.penalty.clone())
+            }
+        }
+    }
+}
+
+impl<O> Gradient for PenalizedProblem<O>
+where
+    O: CostFunction + Gradient<Param = <O as CostFunction>::Param>,
+{
+    type Param = <O as CostFunction>::Param;
+    type Gradient = O::Gradient;
+
+    fn gradient(&self, param: &Self::Param) -> Result<Self::Gradient, Error> {
+        self.problem.gradient(param)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Problem which fails for negative parameters
+    struct Fragile {}
+
+    impl CostFunction for Fragile {
+        type Param = f64;
+        type Output = f64;
+
+        fn cost(&self, param: &Self::Param) -> Result<Self::Output, Error> {
+            if *param < 0.0 {
+                return Err(argmin_error!(InvalidParameter, "negative"));
+            }
+            Ok(*param)
+        }
+    }
+
+    #[test]
+    fn test_penalty() {
+        let problem = PenalizedProblem::new(Fragile {}, f64::INFINITY);
+        let shared = problem.clone();
+
+        assert_eq!(
+            problem.cost(&2.0).unwrap().to_ne_bytes(),
+            2f64.to_ne_bytes()
+        );
+        assert_eq!(problem.failures(), 0);
+        assert!(problem.last_error().is_none());
+
+        assert!(problem.cost(&-1.0).unwrap().is_infinite());
+        assert!(problem.cost(&-2.0).unwrap().is_infinite());
+        assert_eq!(shared.failures(), 2);
+        assert_eq!(
+            shared.last_error().unwrap(),
+            "Invalid parameter: \"negative\""
+        );
+    }
+}
@@ -6,7 +6,7 @@
 // copied, modified, or distributed except according to those terms.
 
 use crate::core::{ArgminFloat, Error, SendAlias, SyncAlias};
-#[cfg(feature = "rayon")]
+#[cfg(all(feature = "rayon", not(target_arch = "wasm32")))]
 use rayon::prelude::*;
 use std::collections::HashMap;
 
@@ -349,6 +349,43 @@ pub trait Operator {
     bulk!(apply, Self::Param, Self::Output);
 }
 
+/// Defines the application of the adjoint (transpose) of a linear [`Operator`].
+///
+/// For an operator `A` this computes `A^T y` (or the conjugate transpose for complex numbers),
+/// which is required by solvers for non-symmetric or rectangular linear systems.
+///
+/// # Example
+///
+/// ```
+/// use argmin::core::{AdjointOperator, Operator, Error};
+/// use argmin_math::{ArgminDot, ArgminTranspose};
+///
+/// struct Model {
+///     matrix: Vec<Vec<f64>>,
+/// }
+///
+/// impl Operator for Model {
+///     type Param = Vec<f64>;
+///     type Output = Vec<f64>;
+///
+///     /// Multiply matrix `self.matrix` with vector `param`
+///     fn apply(&self, param: &Self::Param) -> Result<Self::Output, Error> {
+///         Ok(self.matrix.dot(param))
+///     }
+/// }
+///
+/// impl AdjointOperator for Model {
+///     /// Multiply the transpose of `self.matrix` with vector `y`
+///     fn apply_adjoint(&self, y: &Self::Output) -> Result<Self::Param, Error> {
+///         Ok(self.matrix.clone().t().dot(y))
+///     }
+/// }
+/// ```
+pub trait AdjointOperator: Operator {
+    /// Applies the adjoint of the operator to `y`
+    fn apply_adjoint(&self, y: &Self::Output) -> Result<Self::Param, Error>;
+}
+
 /// Defines computation of a cost function value
 ///
 /// # Example
@@ -478,6 +515,48 @@ pub trait Jacobian {
     bulk!(jacobian, Self::Param, Self::Jacobian);
 }
 
+/// Defines products of the Jacobian of an [`Operator`] with vectors.
+///
+/// This allows solvers to work with the Jacobian `J` without ever forming it, which is useful if
+/// the dense Jacobian does not fit into memory or if the products can be computed more
+/// efficiently (for instance via automatic differentiation).
+///
+/// # Example
+///
+/// ```
+/// use argmin::core::{Error, JacobianVectorProduct};
+///
+/// /// Residuals `r(x) = (x_0 * x_1, x_0 + x_1)`
+/// struct Problem {}
+///
+/// impl JacobianVectorProduct for Problem {
+///     type Param = Vec<f64>;
+///     type Output = Vec<f64>;
+///
+///     /// `J v`
+///     fn jvp(&self, p: &Self::Param, v: &Self::Param) -> Result<Self::Output, Error> {
+///         Ok(vec![p[1] * v[0] + p[0] * v[1], v[0] + v[1]])
+///     }
+///
+///     /// `J^T u`
+///     fn vjp(&self, p: &Self::Param, u: &Self::Output) -> Result<Self::Param, Error> {
+///         Ok(vec![p[1] * u[0] + u[1], p[0] * u[0] + u[1]])
+///     }
+/// }
+/// ```
+pub trait JacobianVectorProduct {
+    /// Type of the parameter vector
+    type Param;
+    /// Type of the output of the operator
+    type Output;
+
+    /// Compute the product `J v` of the Jacobian at `param` with the vector `v`
+    fn jvp(&self, param: &Self::Param, v: &Self::Param) -> Result<Self::Output, Error>;
+
+    /// Compute the product `J^T u` of the transposed Jacobian at `param` with the vector `u`
+    fn vjp(&self, param: &Self::Param, u: &Self::Output) -> Result<Self::Param, Error>;
+}
+
 /// Defines a linear Program
 ///
 /// # Example
@@ -617,6 +696,50 @@ impl<O: Operator> Problem<O> {
     }
 }
 
+/// Wraps a call to `apply_adjoint` defined in the `AdjointOperator` trait and as such allows to
+/// call `apply_adjoint` on an instance of `Problem`. Internally, the number of evaluations of
+/// `apply_adjoint` is counted.
+impl<O: AdjointOperator> Problem<O> {
+    /// Calls `apply_adjoint` defined in the `AdjointOperator` trait and keeps track of the number
+    /// of evaluations.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::{AdjointOperator, Problem, Operator, Error};
+    /// #
+    /// # #[derive(Eq, PartialEq, Debug, Clone)]
+    /// # struct UserDefinedProblem {};
+    /// #
+    /// # impl Operator for UserDefinedProblem {
+    /// #     type Param = Vec<f64>;
+    /// #     type Output = Vec<f64>;
+    /// #
+    /// #     fn apply(&self, param: &Self::Param) -> Result<Self::Output, Error> {
+    /// #         Ok(vec![1.0f64, 1.0f64])
+    /// #     }
+    /// # }
+    /// #
+    /// # impl AdjointOperator for UserDefinedProblem {
+    /// #     fn apply_adjoint(&self, y: &Self::Output) -> Result<Self::Param, Error> {
+    /// #         Ok(vec![2.0f64, 2.0f64])
+    /// #     }
+    /// # }
+    /// // `UserDefinedProblem` implements `AdjointOperator`.
+    /// let mut problem1 = Problem::new(UserDefinedProblem {});
+    ///
+    /// let y = vec![2.0f64, 1.0f64];
+    ///
+    /// let res = problem1.apply_adjoint(&y);
+    ///
+    /// assert_eq!(problem1.counts["adjoint_count"], 1);
+    /// # assert_eq!(res.unwrap(), vec![2.0f64, 2.0f64]);
+    /// ```
+    pub fn apply_adjoint(&mut self, y: &O::Output) -> Result<O::Param, Error> {
+        self.problem("adjoint_count", |problem| problem.apply_adjoint(y))
+    }
+}
+
 /// Wraps a call to `cost` defined in the `CostFunction` trait and as such allows to call `cost` on
 /// an instance of `Problem`. Internally, the number of evaluations of `cost` is counted.
 impl<O: CostFunction> Problem<O> {
@@ -938,6 +1061,84 @@ impl<O: Jacobian> Problem<O> {
     }
 }
 
+/// Wraps calls to `jvp` and `vjp` defined in the `JacobianVectorProduct` trait and as such allows
+/// to call them on an instance of `Problem`. Internally, the number of evaluations is counted.
+impl<O: JacobianVectorProduct> Problem<O> {
+    /// Calls `jvp` defined in the `JacobianVectorProduct` trait and keeps track of the number of
+    /// evaluations.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::{Problem, JacobianVectorProduct, Error};
+    /// #
+    /// # #[derive(Eq, PartialEq, Debug, Clone)]
+    /// # struct UserDefinedProblem {};
+    /// #
+    /// # impl JacobianVectorProduct for UserDefinedProblem {
+    /// #     type Param = Vec<f64>;
+    /// #     type Output = Vec<f64>;
+    /// #
+    /// #     fn jvp(&self, p: &Self::Param, v: &Self::Param) -> Result<Self::Output, Error> {
+    /// #         Ok(v.clone())
+    /// #     }
+    /// #
+    /// #     fn vjp(&self, p: &Self::Param, u: &Self::Output) -> Result<Self::Param, Error> {
+    /// #         Ok(u.clone())
+    /// #     }
+    /// # }
+    /// // `UserDefinedProblem` implements `JacobianVectorProduct`.
+    /// let mut problem1 = Problem::new(UserDefinedProblem {});
+    ///
+    /// let param = vec![2.0f64, 1.0f64];
+    ///
+    /// let res = problem1.jvp(&param, &vec![1.0f64, 0.0f64]);
+    ///
+    /// assert_eq!(problem1.counts["jvp_count"], 1);
+    /// # assert_eq!(res.unwrap(), vec![1.0f64, 0.0f64]);
+    /// ```
+    pub fn jvp(&mut self, param: &O::Param, v: &O::Param) -> Result<O::Output, Error> {
+        self.problem("jvp_count", |problem| problem.jvp(param, v))
+    }
+
+    /// Calls `vjp` defined in the `JacobianVectorProduct` trait and keeps track of the number of
+    /// evaluations.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::{Problem, JacobianVectorProduct, Error};
+    /// #
+    /// # #[derive(Eq, PartialEq, Debug, Clone)]
+    /// # struct UserDefinedProblem {};
+    /// #
+    /// # impl JacobianVectorProduct for UserDefinedProblem {
+    /// #     type Param = Vec<f64>;
+    /// #     type Output = Vec<f64>;
+    /// #
+    /// #     fn jvp(&self, p: &Self::Param, v: &Self::Param) -> Result<Self::Output, Error> {
+    /// #         Ok(v.clone())
+    /// #     }
+    /// #
+    /// #     fn vjp(&self, p: &Self::Param, u: &Self::Output) -> Result<Self::Param, Error> {
+    /// #         Ok(u.clone())
+    /// #     }
+    /// # }
+    /// // `UserDefinedProblem` implements `JacobianVectorProduct`.
+    /// let mut problem1 = Problem::new(UserDefinedProblem {});
+    ///
+    /// let param = vec![2.0f64, 1.0f64];
+    ///
+    /// let res = problem1.vjp(&param, &vec![1.0f64, 0.0f64]);
+    ///
+    /// assert_eq!(problem1.counts["vjp_count"], 1);
+    /// # assert_eq!(res.unwrap(), vec![1.0f64, 0.0f64]);
+    /// ```
+    pub fn vjp(&mut self, param: &O::Param, u: &O::Output) -> Result<O::Param, Error> {
+        self.problem("vjp_count", |problem| problem.vjp(param, u))
+    }
+}
+
 /// Wraps a calls to `c`, `b` and `A` defined in the `LinearProgram` trait and as such allows to
 /// call those methods on an instance of `Problem`.
 impl<O: LinearProgram> Problem<O> {
@@ -0,0 +1,275 @@
+// Copyright 2018-2024 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+//! # Problems defined by closures
+//!
+//! Defining an optimization problem usually requires a dedicated struct which implements
+//! [`CostFunction`], [`Gradient`], [`Hessian`] and so on. For quick experiments and tests, the
+//! helpers in this module build such a problem directly from closures.
+//!
+//! The closures take a reference to the parameter vector and return the cost function value,
+//! gradient or Hessian directly. Problems whose evaluation may fail still need to implement the
+//! traits themselves in order to return an error.
+//!
+//! # Example
+//!
+//! ```
+//! # use argmin::core::{Error, Executor, State};
+//! use argmin::core::problems::from_closures;
+//! use argmin::solver::gradientdescent::SteepestDescent;
+//! use argmin::solver::linesearch::MoreThuenteLineSearch;
+//!
+//! # fn main() -> Result<(), Error> {
+//! let problem = from_closures(
+//!     |p: &Vec<f64>| (p[0] - 1.0).powi(2) + (p[1] + 2.0).powi(2),
+//!     |p: &Vec<f64>| vec![2.0 * (p[0] - 1.0), 2.0 * (p[1] + 2.0)],
+//! );
+//!
+//! let solver = SteepestDescent::new(MoreThuenteLineSearch::new());
+//! let res = Executor::new(problem, solver)
+//!     .configure(|state| state.param(vec![0.0, 0.0]).target_cost(1e-8).max_iters(20))
+//!     .run()?;
+//! # assert!(res.state.get_best_cost() < 1e-8);
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::core::{CostFunction, Error, Gradient, Hessian};
+use std::marker::PhantomData;
+
+/// Optimization problem defined by closures
+///
+/// Implements [`CostFunction`], [`Gradient`] and [`Hessian`] for each of the corresponding
+/// closures which was provided. Constructed via [`from_cost`] or [`from_closures`].
+pub struct ClosureProblem<P, C, G = (), H = ()> {
+    /// Cost function
+    cost: C,
+    /// Gradient
+    gradient: G,
+    /// Hessian
+    hessian: H,
+    /// Type of the parameter vector
+    param: PhantomData<fn(&P)>,
+}
+
+/// Builds a problem from a closure computing the cost function value
+///
+/// # Example
+///
+/// ```
+/// # use argmin::core::{CostFunction, Error};
+/// use argmin::core::problems::from_cost;
+///
+/// # fn main() -> Result<(), Error> {
+/// let problem = from_cost(|p: &Vec<f64>| p.iter().map(|x| x * x).sum::<f64>());
+/// # assert_eq!(problem.cost(&vec![1.0, 2.0])?, 5.0);
+/// # Ok(())
+/// # }
+/// ```
+pub fn from_cost<P, F, C>(cost: C) -> ClosureProblem<P, C>
+where
+    C: Fn(&P) -> F,
+{
+    ClosureProblem {
+        cost,
+        gradient: (),
+        hessian: (),
+        param: PhantomData,
+    }
+}
+
+/// Builds a problem from closures computing the cost function value and the gradient
+///
+/// # Example
+///
+/// ```
+/// # use argmin::core::{CostFunction, Error, Gradient};
+/// use argmin::core::problems::from_closures;
+///
+/// # fn main() -> Result<(), Error> {
+/// let problem = from_closures(
+///     |p: &Vec<f64>| p.iter().map(|x| x * x).sum::<f64>(),
+///     |p: &Vec<f64>| p.iter().map(|x| 2.0 * x).collect::<Vec<f64>>(),
+/// );
+/// # assert_eq!(problem.cost(&vec![1.0, 2.0])?, 5.0);
+/// # assert_eq!(problem.gradient(&vec![1.0, 2.0])?, vec![2.0, 4.0]);
+/// # Ok(())
+/// # }
+/// ```
+pub fn from_closures<P, F, G, C, D>(cost: C, gradient: D) -> ClosureProblem<P, C, D>
+where
+    C: Fn(&P) -> F,
+    D: Fn(&P) -> G,
+{
+    from_cost(cost).with_gradient(gradient)
+}
+
+impl<P, C, H> ClosureProblem<P, C, (), H> {
+    /// Adds a closure computing the gradient
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::{Error, Gradient};
+    /// use argmin::core::problems::from_cost;
+    ///
+    /// # fn main() -> Result<(), Error> {
+    /// let problem = from_cost(|p: &Vec<f64>| p[0].powi(3))
+    ///     .with_gradient(|p: &Vec<f64>| vec![3.0 * p[0].powi(2)]);
+    /// # assert_eq!(problem.gradient(&vec![2.0])?, vec![12.0]);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_gradient<G, D>(self, gradient: D) -> ClosureProblem<P, C, D, H>
+    where
+        D: Fn(&P) -> G,
+    {
+        ClosureProblem {
+            cost: self.cost,
+            gradient,
+            hessian: self.hessian,
+            param: PhantomData,
+        }
+    }
+}
+
+impl<P, C, G> ClosureProblem<P, C, G, ()> {
+    /// Adds a closure computing the Hessian
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::{Error, Hessian};
+    /// use argmin::core::problems::from_closures;
+    ///
+    /// # fn main() -> Result<(), Error> {
+    /// let problem = from_closures(
+    ///     |p: &Vec<f64>| p[0].powi(3),
+    ///     |p: &Vec<f64>| vec![3.0 * p[0].powi(2)],
+    /// )
+    /// .with_hessian(|p: &Vec<f64>| vec![vec![6.0 * p[0]]]);
+    /// # assert_eq!(problem.hessian(&vec![2.0])?, vec![vec![12.0]]);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_hessian<HH, E>(self, hessian: E) -> ClosureProblem<P, C, G, E>
+    where
+        E: Fn(&P) -> HH,
+    {
+        ClosureProblem {
+            cost: self.cost,
+            gradient: self.gradient,
+            hessian,
+            param: PhantomData,
+        }
+    }
+}
+
+impl<P, C, G, H> Clone for ClosureProblem<P, C, G, H>
+where
+    C: Clone,
+    G: Clone,
+    H: Clone,
+{
+    fn clone(&self) -> Self {
+        ClosureProblem {
+            cost: self.cost.clone(),
+            gradient: self.gradient.clone(),
+            hessian: self.hessian.clone(),
+            param: PhantomData,
+        }
+    }
+}
+
+impl<P, C, G, H> std::fmt::Debug for ClosureProblem<P, C, G, H> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ClosureProblem").finish_non_exhaustive()
+    }
+}
+
+impl<P, F, C, G, H> CostFunction for ClosureProblem<P, C, G, H>
+where
+    C: Fn(&P) -> F,
+{
+    type Param = P;
+    type Output = F;
+
+    fn cost(&self, param: &Self::Param) -> Result<Self::Output, Error> {
+        Ok((self.cost)(param))
+    }
+}
+
+impl<P, C, GG, G, H> Gradient for ClosureProblem<P, C, G, H>
+where
+    G: Fn(&P) -> GG,
+{
+    type Param = P;
+    type Gradient = GG;
+
+    fn gradient(&self, param: &Self::Param) -> Result<Self::Gradient, Error> {
+        Ok((self.gradient)(param))
+    }
+}
+
+impl<P, C, G, HH, H> Hessian for ClosureProblem<P, C, G, H>
+where
+    H: Fn(&P) -> HH,
+{
+    type Param = P;
+    type Hessian = HH;
+
+    fn hessian(&self, param: &Self::Param) -> Result<Self::Hessian, Error> {
+        Ok((self.hessian)(param))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::{Executor, Problem};
+    use crate::solver::gradientdescent::SteepestDescent;
+    use crate::solver::linesearch::MoreThuenteLineSearch;
+
+    test_trait_impl!(
+        closure_problem,
+        ClosureProblem<Vec<f64>, fn(&Vec<f64>) -> f64>
+    );
+
+    #[test]
+    fn test_closure_problem() {
+        let mut problem = Problem::new(
+            from_closures(
+                |p: &Vec<f64>| (p[0] - 3.0).powi(2),
+                |p: &Vec<f64>| vec![2.0 * (p[0] - 3.0)],
+            )
+            .with_hessian(|_p: &Vec<f64>| vec![vec![2.0]]),
+        );
+        assert_eq!(
+            problem.cost(&vec![1.0]).unwrap().to_ne_bytes(),
+            4.0f64.to_ne_bytes()
+        );
+        assert_eq!(problem.gradient(&vec![1.0]).unwrap(), vec![-4.0]);
+        assert_eq!(problem.hessian(&vec![1.0]).unwrap(), vec![vec![2.0]]);
+        assert_eq!(problem.counts["cost_count"], 1);
+        assert_eq!(problem.counts["gradient_count"], 1);
+        assert_eq!(problem.counts["hessian_count"], 1);
+    }
+
+    #[test]
+    fn test_steepest_descent() {
+        let problem = from_closures(
+            |p: &Vec<f64>| (p[0] - 3.0).powi(2) + p[1].powi(2),
+            |p: &Vec<f64>| vec![2.0 * (p[0] - 3.0), 2.0 * p[1]],
+        );
+
+        let res = Executor::new(problem, SteepestDescent::new(MoreThuenteLineSearch::new()))
+            .configure(|state| state.param(vec![0.0, 1.0]).target_cost(1e-10).max_iters(10))
+            .run()
+            .unwrap();
+        assert!(res.state.get_best_cost() < 1e-10);
+    }
+}
@@ -0,0 +1,481 @@
+// Copyright 2018-2024 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+//! # Profile scans
+//!
+//! A profile of a cost function with respect to one parameter is obtained by fixing this
+//! parameter at a series of values and re-optimizing all remaining parameters for each of them.
+//! Unlike the covariance estimates based on the curvature at the optimum, profiles also capture
+//! non-quadratic behavior of the cost function. For a negative log-likelihood, the values at which
+//! the profile exceeds the optimum by `χ²₁(α) / 2` bound the `1 - α` profile likelihood
+//! confidence interval (e.g. `1.92` for a 95% interval).
+
+use crate::core::{ArgminFloat, CostFunction, Error, Executor, Gradient, Solver, State};
+
+/// Wraps a problem with parameter vector `Vec<F>` such that the parameter at `index` is held fixed
+/// at `value` and only the remaining parameters are optimized.
+///
+/// The parameter vector of the wrapped problem lacks the entry at `index`. Gradients are reduced
+/// accordingly.
+#[derive(Clone, Debug)]
+pub struct FixedParameter<O, F> {
+    /// Problem
+    problem: O,
+    /// Index of the fixed parameter
+    index: usize,
+    /// Value of the fixed parameter
+    value: F,
+}
+
+impl<O, F: Clone> FixedParameter<O, F> {
+    /// Construct a new instance of [`FixedParameter`]
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::FixedParameter;
+    /// # use argmin::core::test_utils::TestProblem;
+    /// let problem = FixedParameter::new(TestProblem::new(), 1, 0.5f64);
+    /// ```
+    pub fn new(problem: O, index: usize, value: F) -> Self {
+        FixedParameter {
+            problem,
+            index,
+            value,
+        }
+    }
+
+    /// Inserts the fixed parameter into a reduced parameter vector.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::FixedParameter;
+    /// # use argmin::core::test_utils::TestProblem;
+    /// let problem = FixedParameter::new(TestProblem::new(), 1, 0.5f64);
+    /// assert_eq!(problem.expand(&[1.0, 2.0]), vec![1.0, 0.5, 2.0]);
+    /// ```
+    pub fn expand(&self, param: &[F]) -> Vec<F> {
+        insert_at(param, self.index, self.value.clone())
+    }
+
+    /// Removes the fixed parameter from a full parameter vector (or gradient).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::FixedParameter;
+    /// # use argmin::core::test_utils::TestProblem;
+    /// let problem = FixedParameter::new(TestProblem::new(), 1, 0.5f64);
+    /// assert_eq!(problem.reduce(&[1.0, 0.5, 2.0]), vec![1.0, 2.0]);
+    /// ```
+    pub fn reduce(&self, param: &[F]) -> Vec<F> {
+        remove_at(param, self.index)
+    }
+}
+
+/// Returns a copy of `param` with `value` inserted at `index`.
+fn insert_at<F: Clone>(param: &[F], index: usize, value: F) -> Vec<F> {
+    let mut full = Vec::with_capacity(param.len() + 1);
+    full.extend_from_slice(&param[..index]);
+    full.push(value);
+    full.extend_from_slice(&param[index..]);
+    full
+}
+
+/// Returns a copy of `param` without the entry at `index`.
+fn remove_at<F: Clone>(param: &[F], index: usize) -> Vec<F> {
+    let mut reduced = param.to_vec();
+    reduced.remove(index);
+    reduced
+}
+
+impl<O, F> CostFunction for FixedParameter<O, F>
+where
+    O: CostFunction<Param = Vec<F>, Output = F>,
+    F: Clone,
+{
+    type Param = Vec<F>;
+    type Output = F;
+
+    fn cost(&self, param: &Self::Param) -> Result<Self::Output, Error> {
+        self.problem.cost(&self.expand(param))
+    }
+}
+
+impl<O, F> Gradient for FixedParameter<O, F>
+where
+    O: Gradient<Param = Vec<F>, Gradient = Vec<F>>,
+    F: Clone,
+{
+    type Param = Vec<F>;
+    type Gradient = Vec<F>;
+
+    fn gradient(&self, param: &Self::Param) -> Result<Self::Gradient, Error> {
+        Ok(self.reduce(&self.problem.gradient(&self.expand(param))?))
+    }
+}
+
+/// Re-optimized cost function at one value of the scanned parameter
+#[derive(Clone, Debug, PartialEq)]
+pub struct ProfilePoint<F> {
+    /// Value of the scanned parameter
+    pub value: F,
+    /// Best cost function value found for the remaining parameters
+    pub cost: F,
+    /// Full parameter vector (including the scanned parameter) corresponding to `cost`
+    pub param: Vec<F>,
+}
+
+/// Result of [`ProfileScan::scan`]
+#[derive(Clone, Debug, PartialEq)]
+pub struct Profile<F> {
+    /// Index of the scanned parameter
+    pub index: usize,
+    /// Optimum around which the scan was performed
+    pub optimum: Vec<F>,
+    /// Cost function value at the optimum
+    pub optimum_cost: F,
+    /// Profile points, sorted by the value of the scanned parameter
+    pub points: Vec<ProfilePoint<F>>,
+}
+
+impl<F: ArgminFloat> Profile<F> {
+    /// Returns the values of the scanned parameter at which the profile exceeds the cost at the
+    /// optimum by `threshold`, below and above the optimum.
+    ///
+    /// The crossings are linearly interpolated between adjacent profile points. A bound is `None`
+    /// if the profile does not exceed the threshold within the scanned range on that side.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::{Profile, ProfilePoint};
+    /// let profile = Profile {
+    ///     index: 0,
+    ///     optimum: vec![0.0],
+    ///     optimum_cost: 0.0,
+    ///     points: [-2.0f64, -1.0, 0.0, 1.0]
+    ///         .iter()
+    ///         .map(|v| ProfilePoint { value: *v, cost: v.powi(2), param: vec![*v] })
+    ///         .collect(),
+    /// };
+    /// assert_eq!(profile.interval(2.5), (Some(-1.5), None));
+    /// ```
+    pub fn interval(&self, threshold: F) -> (Option<F>, Option<F>) {
+        let center = self.optimum[self.index];
+        let level = self.optimum_cost + threshold;
+        let crossing = |inner: &ProfilePoint<F>, outer: &ProfilePoint<F>| {
+            let t = (level - inner.cost) / (outer.cost - inner.cost);
+            inner.value + t * (outer.value - inner.value)
+        };
+        let first_crossing = |side: &mut dyn Iterator<Item = &ProfilePoint<F>>| {
+            let mut inner = None;
+            for point in side {
+                if point.cost >= level {
+                    return Some(inner.map_or(point.value, |i| crossing(i, point)));
+                }
+                inner = Some(point);
+            }
+            None
+        };
+        let lower = first_crossing(&mut self.points.iter().rev().filter(|p| p.value <= center));
+        let upper = first_crossing(&mut self.points.iter().filter(|p| p.value >= center));
+        (lower, upper)
+    }
+}
+
+/// Computes profiles of a cost function by repeatedly re-optimizing all but one parameter with a
+/// given solver.
+///
+/// The scan starts at the optimum and walks outwards in both directions. Each re-optimization is
+/// warm-started from the result of the neighboring point closer to the optimum. The `configure`
+/// closure is given a fresh state and the initial (reduced) parameter vector and works like the
+/// closure passed to [`Executor::configure`].
+///
+/// # Example
+///
+/// ```
+/// use argmin::core::{CostFunction, Error, Gradient, IterState, ProfileScan, State};
+/// use argmin::solver::linesearch::MoreThuenteLineSearch;
+/// use argmin::solver::quasinewton::LBFGS;
+///
+/// /// Negative log-likelihood with correlated parameters
+/// #[derive(Clone)]
+/// struct NegLogLikelihood {}
+///
+/// impl CostFunction for NegLogLikelihood {
+///     type Param = Vec<f64>;
+///     type Output = f64;
+///
+///     fn cost(&self, p: &Self::Param) -> Result<Self::Output, Error> {
+///         Ok(p[0].powi(2) + p[0] * p[1] + p[1].powi(2))
+///     }
+/// }
+///
+/// impl Gradient for NegLogLikelihood {
+///     type Param = Vec<f64>;
+///     type Gradient = Vec<f64>;
+///
+///     fn gradient(&self, p: &Self::Param) -> Result<Self::Gradient, Error> {
+///         Ok(vec![2.0 * p[0] + p[1], p[0] + 2.0 * p[1]])
+///     }
+/// }
+///
+/// # fn main() -> Result<(), Error> {
+/// let solver = LBFGS::new(MoreThuenteLineSearch::new(), 5);
+/// let scan = ProfileScan::new(
+///     NegLogLikelihood {},
+///     solver,
+///     vec![0.0, 0.0],
+///     |state: IterState<Vec<f64>, Vec<f64>, (), (), (), f64>, param| {
+///         state.param(param).max_iters(50)
+///     },
+/// );
+///
+/// let values: Vec<f64> = (-20..=20).map(|i| i as f64 / 10.0).collect();
+/// let profile = scan.scan(0, &values)?;
+///
+/// // 95% profile likelihood confidence interval of the first parameter
+/// let (lower, upper) = profile.interval(1.92);
+/// # assert!((lower.unwrap() + 1.6).abs() < 1e-2);
+/// # assert!((upper.unwrap() - 1.6).abs() < 1e-2);
+/// # Ok(())
+/// # }
+/// ```
+pub struct ProfileScan<O, S, F, C> {
+    /// Problem
+    problem: O,
+    /// Solver used for re-optimization
+    solver: S,
+    /// Optimum around which the scans are performed
+    optimum: Vec<F>,
+    /// Configures the state of each re-optimization
+    configure: C,
+}
+
+impl<O, S, F, C> ProfileScan<O, S, F, C>
+where
+    O: CostFunction<Param = Vec<F>, Output = F> + Clone,
+    F: ArgminFloat,
+{
+    /// Construct a new instance of [`ProfileScan`]
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::{IterState, ProfileScan};
+    /// # use argmin::core::test_utils::{TestProblem, TestSolver};
+    /// let scan = ProfileScan::new(
+    ///     TestProblem::new(),
+    ///     TestSolver::new(),
+    ///     vec![0.0f64, 0.0],
+    ///     |state: IterState<Vec<f64>, (), (), (), (), f64>, param| state.param(param),
+    /// );
+    /// ```
+    pub fn new(problem: O, solver: S, optimum: Vec<F>, configure: C) -> Self {
+        ProfileScan {
+            problem,
+            solver,
+            optimum,
+            configure,
+        }
+    }
+
+    /// Computes the profile of the parameter at `index` for all `values`.
+    ///
+    /// Fails if `index` is out of bounds or if one of the re-optimizations fails.
+    pub fn scan<I>(&self, index: usize, values: &[F]) -> Result<Profile<F>, Error>
+    where
+        S: Solver<FixedParameter<O, F>, I> + Clone,
+        I: State<Param = Vec<F>, Float = F>,
+        C: Fn(I, Vec<F>) -> I,
+    {
+        if index >= self.optimum.len() {
+            return Err(argmin_error!(
+                InvalidParameter,
+                format!(
+                    "`ProfileScan`: Index {index} out of bounds for parameter vector of length {}.",
+                    self.optimum.len()
+                )
+            ));
+        }
+        let center = self.optimum[index];
+        let mut values = values.to_vec();
+        values.sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
+        let split = values.partition_point(|v| *v < center);
+
+        // Walk downwards and upwards from the optimum, warm-starting from the previous point.
+        let mut below = self.walk(index, values[..split].iter().rev())?;
+        below.reverse();
+        let above = self.walk(index, values[split..].iter())?;
+        let mut points = below;
+        points.extend(above);
+
+        Ok(Profile {
+            index,
+            optimum: self.optimum.clone(),
+            optimum_cost: self.problem.cost(&self.optimum)?,
+            points,
+        })
+    }
+
+    /// Computes the profile points for `values` in the given order, starting at the optimum.
+    fn walk<'a, I>(
+        &self,
+        index: usize,
+        values: impl Iterator<Item = &'a F>,
+    ) -> Result<Vec<ProfilePoint<F>>, Error>
+    where
+        S: Solver<FixedParameter<O, F>, I> + Clone,
+        I: State<Param = Vec<F>, Float = F>,
+        C: Fn(I, Vec<F>) -> I,
+    {
+        let mut start = remove_at(&self.optimum, index);
+        let mut points = vec![];
+        for value in values {
+            let point = self.point(index, *value, start)?;
+            start = remove_at(&point.param, index);
+            points.push(point);
+        }
+        Ok(points)
+    }
+
+    /// Re-optimizes the remaining parameters with the parameter at `index` fixed at `value`.
+    fn point<I>(&self, index: usize, value: F, start: Vec<F>) -> Result<ProfilePoint<F>, Error>
+    where
+        S: Solver<FixedParameter<O, F>, I> + Clone,
+        I: State<Param = Vec<F>, Float = F>,
+        C: Fn(I, Vec<F>) -> I,
+    {
+        let problem = FixedParameter::new(self.problem.clone(), index, value);
+        let res = Executor::new(problem, self.solver.clone())
+            .configure(|state| (self.configure)(state, start))
+            .run()
+            .map_err(|e| {
+                e.context(format!(
+                    "ProfileScan: re-optimization with parameter {index} fixed at {value} failed"
+                ))
+            })?;
+        let cost = res.state.get_best_cost();
+        let reduced = res
+            .state
+            .get_best_param()
+            .ok_or_else(argmin_error_closure!(
+                PotentialBug,
+                "`ProfileScan`: No best parameter vector after re-optimization."
+            ))?;
+        Ok(ProfilePoint {
+            value,
+            cost,
+            param: insert_at(reduced, index, value),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::{ArgminError, IterState};
+    use crate::solver::linesearch::MoreThuenteLineSearch;
+    use crate::solver::quasinewton::LBFGS;
+    use approx::assert_relative_eq;
+
+    /// `(x - 1)² + (x - 1)(y - 2) + (y - 2)²`, whose profile in `x` is `3/4 (x - 1)²` with
+    /// `y = 2 - (x - 1) / 2`.
+    #[derive(Clone)]
+    struct Correlated {}
+
+    impl CostFunction for Correlated {
+        type Param = Vec<f64>;
+        type Output = f64;
+
+        fn cost(&self, p: &Self::Param) -> Result<Self::Output, Error> {
+            let (a, b) = (p[0] - 1.0, p[1] - 2.0);
+            Ok(a.powi(2) + a * b + b.powi(2))
+        }
+    }
+
+    impl Gradient for Correlated {
+        type Param = Vec<f64>;
+        type Gradient = Vec<f64>;
+
+        fn gradient(&self, p: &Self::Param) -> Result<Self::Gradient, Error> {
+            let (a, b) = (p[0] - 1.0, p[1] - 2.0);
+            Ok(vec![2.0 * a + b, a + 2.0 * b])
+        }
+    }
+
+    type LbfgsState = IterState<Vec<f64>, Vec<f64>, (), (), (), f64>;
+
+    fn scan() -> ProfileScan<
+        Correlated,
+        LBFGS<MoreThuenteLineSearch<Vec<f64>, Vec<f64>, f64>, Vec<f64>, Vec<f64>, f64>,
+        f64,
+        impl Fn(LbfgsState, Vec<f64>) -> LbfgsState,
+    > {
+        ProfileScan::new(
+            Correlated {},
+            LBFGS::new(MoreThuenteLineSearch::new(), 5),
+            vec![1.0, 2.0],
+            |state: LbfgsState, param| state.param(param).max_iters(100),
+        )
+    }
+
+    #[test]
+    fn test_fixed_parameter() {
+        let problem = FixedParameter::new(Correlated {}, 0, 2.0);
+        assert_eq!(problem.expand(&[3.0]), vec![2.0, 3.0]);
+        assert_eq!(problem.reduce(&[2.0, 3.0]), vec![3.0]);
+        assert_relative_eq!(problem.cost(&vec![3.0]).unwrap(), 3.0, epsilon = 1e-12);
+        assert_eq!(problem.gradient(&vec![3.0]).unwrap(), vec![3.0]);
+
+        let problem = FixedParameter::new(Correlated {}, 1, 2.0);
+        assert_eq!(problem.expand(&[3.0]), vec![3.0, 2.0]);
+        assert_eq!(problem.gradient(&vec![3.0]).unwrap(), vec![4.0]);
+    }
+
+    #[test]
+    fn test_scan() {
+        let values = [2.0, -1.0, 0.0, 1.0, 0.5, 3.0];
+        let profile = scan().scan(0, &values).unwrap();
+        assert_eq!(profile.index, 0);
+        assert_relative_eq!(profile.optimum_cost, 0.0, epsilon = 1e-12);
+        let sorted: Vec<f64> = profile.points.iter().map(|p| p.value).collect();
+        assert_eq!(sorted, vec![-1.0, 0.0, 0.5, 1.0, 2.0, 3.0]);
+        for point in profile.points.iter() {
+            let a = point.value - 1.0;
+            assert_relative_eq!(point.cost, 0.75 * a.powi(2), epsilon = 1e-6);
+            assert_relative_eq!(point.param[0], point.value);
+            assert_relative_eq!(point.param[1], 2.0 - a / 2.0, epsilon = 1e-3);
+        }
+    }
+
+    #[test]
+    fn test_interval() {
+        let values: Vec<f64> = (-20..=40).map(|i| f64::from(i) / 10.0).collect();
+        let profile = scan().scan(0, &values).unwrap();
+        // 3/4 a² = 1 => a = ±sqrt(4/3)
+        let (lower, upper) = profile.interval(1.0);
+        let half_width = (4.0f64 / 3.0).sqrt();
+        assert_relative_eq!(lower.unwrap(), 1.0 - half_width, epsilon = 1e-2);
+        assert_relative_eq!(upper.unwrap(), 1.0 + half_width, epsilon = 1e-2);
+
+        // Threshold beyond the scanned range
+        assert_eq!(profile.interval(100.0), (None, None));
+    }
+
+    #[test]
+    fn test_invalid_index() {
+        let res = scan().scan(2, &[1.0]);
+        assert_error!(
+            res,
+            ArgminError,
+            "Invalid parameter: \"`ProfileScan`: Index 2 out of bounds for parameter vector of length 2.\""
+        );
+    }
+}
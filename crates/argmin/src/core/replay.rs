@@ -0,0 +1,429 @@
+// Copyright 2018-2024 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use crate::core::observers::Observe;
+use crate::core::{
+    ArgminFloat, CostFunction, Error, IterState, Problem, Solver, State, TerminationReason,
+    TerminationStatus, KV,
+};
+#[cfg(feature = "serde1")]
+use serde::{Deserialize, Serialize};
+use std::sync::{Arc, Mutex};
+
+/// Parameter vector and cost function value of a single iteration of a recorded run
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+pub struct ReplayEntry<P, F> {
+    /// Iteration number
+    pub iter: u64,
+    /// Parameter vector accepted by the solver in this iteration
+    pub param: P,
+    /// Cost function value of `param`
+    pub cost: F,
+}
+
+/// Log of the parameter vectors accepted by a solver during a run
+///
+/// The log is recorded with a [`ReplayRecorder`] and can be replayed with a [`Replayer`]. With
+/// the `serde1` feature, the log can be serialized, for instance to compare runs on different
+/// machines. Since the seeds of the random number generators used by a run are not accessible to
+/// argmin, they can be stored alongside the entries via [`with_seed`](`ReplayLog::with_seed`).
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+pub struct ReplayLog<P, F> {
+    /// Seed of the random number generator(s) of the recorded run (if provided)
+    pub seed: Option<u64>,
+    /// Recorded iterations
+    pub entries: Vec<ReplayEntry<P, F>>,
+}
+
+impl<P, F> ReplayLog<P, F> {
+    /// Construct a new, empty `ReplayLog`
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::ReplayLog;
+    /// let log: ReplayLog<Vec<f64>, f64> = ReplayLog::new();
+    /// # assert!(log.is_empty());
+    /// ```
+    pub fn new() -> Self {
+        ReplayLog {
+            seed: None,
+            entries: vec![],
+        }
+    }
+
+    /// Store the seed of the random number generator(s) of the run
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::ReplayLog;
+    /// let log: ReplayLog<Vec<f64>, f64> = ReplayLog::new().with_seed(1729);
+    /// # assert_eq!(log.seed, Some(1729));
+    /// ```
+    #[must_use]
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = Some(seed);
+        self
+    }
+
+    /// Returns the number of recorded iterations
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Returns `true` if no iterations were recorded
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+}
+
+/// Observer which records a [`ReplayLog`]
+///
+/// After each iteration, the current parameter vector and cost function value are added to the
+/// log. The observer must therefore be added with
+/// [`ObserverMode::Always`](`crate::core::observers::ObserverMode::Always`). Clones of a
+/// `ReplayRecorder` share the same log, which is available via
+/// [`log`](`ReplayRecorder::log`) after the run.
+///
+/// # Example
+///
+/// ```
+/// # use argmin::core::{Error, Executor, ReplayRecorder};
+/// # use argmin::core::observers::ObserverMode;
+/// # use argmin::core::test_utils::{TestSolver, TestProblem};
+/// # fn main() -> Result<(), Error> {
+/// let recorder = ReplayRecorder::new();
+///
+/// Executor::new(TestProblem::new(), TestSolver::new())
+///     .configure(|state| state.param(vec![1.0f64, 0.0]).max_iters(10))
+///     .add_observer(recorder.clone(), ObserverMode::Always)
+///     .run()?;
+///
+/// let log = recorder.log();
+/// assert_eq!(log.len(), 10);
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct ReplayRecorder<P, F> {
+    log: Arc<Mutex<ReplayLog<P, F>>>,
+}
+
+impl<P, F> ReplayRecorder<P, F> {
+    /// Construct a new `ReplayRecorder` with an empty log
+    pub fn new() -> Self {
+        ReplayRecorder {
+            log: Arc::new(Mutex::new(ReplayLog::new())),
+        }
+    }
+
+    /// Construct a new `ReplayRecorder` which stores the seed of the random number generator(s)
+    /// of the run in the log
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::ReplayRecorder;
+    /// let recorder: ReplayRecorder<Vec<f64>, f64> = ReplayRecorder::with_seed(1729);
+    /// # assert_eq!(recorder.log().seed, Some(1729));
+    /// ```
+    pub fn with_seed(seed: u64) -> Self {
+        ReplayRecorder {
+            log: Arc::new(Mutex::new(ReplayLog::new().with_seed(seed))),
+        }
+    }
+
+    /// Returns a copy of the log recorded so far
+    pub fn log(&self) -> ReplayLog<P, F>
+    where
+        P: Clone,
+        F: Clone,
+    {
+        self.log.lock().unwrap().clone()
+    }
+}
+
+impl<I> Observe<I> for ReplayRecorder<I::Param, I::Float>
+where
+    I: State,
+    I::Param: Clone,
+{
+    fn observe_iter(&mut self, state: &I, _kv: &KV) -> Result<(), Error> {
+        if let Some(param) = state.get_param() {
+            self.log.lock().unwrap().entries.push(ReplayEntry {
+                iter: state.get_iter(),
+                param: param.clone(),
+                cost: state.get_cost(),
+            });
+        }
+        Ok(())
+    }
+}
+
+/// Replays a [`ReplayLog`] against a problem
+///
+/// In each iteration, the `Replayer` takes the next parameter vector of the log and evaluates the
+/// cost function of the problem at this parameter vector instead of computing a new one. If the
+/// cost function value deviates from the recorded value by more than the tolerance (default:
+/// `0`, i.e. bit-exact), the run terminates with [`TerminationReason::ReplayDiverged`], naming
+/// the iteration in which the runs diverged. The state then holds the offending parameter vector.
+/// Otherwise the run terminates with [`TerminationReason::ReplayEnded`] once the log is exhausted.
+///
+/// Since the problem can be wrapped, for instance to log or inspect evaluations, this allows one
+/// to debug runs which are not reproducible without rerunning the solver.
+///
+/// # Example
+///
+/// ```
+/// use argmin::core::observers::ObserverMode;
+/// use argmin::core::{Executor, ReplayRecorder, Replayer, State};
+/// use argmin::solver::neldermead::NelderMead;
+/// # use argmin::core::{CostFunction, Error};
+/// #
+/// # struct Paraboloid {}
+/// #
+/// # impl CostFunction for Paraboloid {
+/// #     type Param = Vec<f64>;
+/// #     type Output = f64;
+/// #     fn cost(&self, p: &Self::Param) -> Result<Self::Output, Error> {
+/// #         Ok(p[0].powi(2) + 10.0 * p[1].powi(2))
+/// #     }
+/// # }
+///
+/// # fn main() -> Result<(), Error> {
+/// let solver = NelderMead::new(vec![vec![1.0, 1.0], vec![2.0, 1.0], vec![1.0, 2.0]]);
+///
+/// // Record a run
+/// let recorder = ReplayRecorder::new();
+/// Executor::new(Paraboloid {}, solver)
+///     .configure(|state| state.max_iters(10))
+///     .add_observer(recorder.clone(), ObserverMode::Always)
+///     .run()?;
+///
+/// // Replay the run
+/// let res = Executor::new(Paraboloid {}, Replayer::new(recorder.log())).run()?;
+/// assert_eq!(res.state().get_iter(), 10);
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+pub struct Replayer<P, F> {
+    /// Log to be replayed
+    log: ReplayLog<P, F>,
+    /// Index of the next entry of the log
+    position: usize,
+    /// Tolerated deviation of the cost function value from the recorded value
+    tolerance: F,
+}
+
+impl<P, F> Replayer<P, F>
+where
+    F: ArgminFloat,
+{
+    /// Construct a new `Replayer` from a log
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::{ReplayLog, Replayer};
+    /// # let log: ReplayLog<Vec<f64>, f64> = ReplayLog::new();
+    /// let replayer = Replayer::new(log);
+    /// ```
+    pub fn new(log: ReplayLog<P, F>) -> Self {
+        Replayer {
+            log,
+            position: 0,
+            tolerance: F::zero(),
+        }
+    }
+
+    /// Set the tolerated absolute deviation of the cost function values from the recorded values
+    ///
+    /// Must be non-negative, defaults to `0`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::{Error, ReplayLog, Replayer};
+    /// # fn main() -> Result<(), Error> {
+    /// # let log: ReplayLog<Vec<f64>, f64> = ReplayLog::new();
+    /// let replayer = Replayer::new(log).with_tolerance(1e-12)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_tolerance(mut self, tolerance: F) -> Result<Self, Error> {
+        if tolerance < F::zero() {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "`Replayer`: tolerance must be >= 0."
+            ));
+        }
+        self.tolerance = tolerance;
+        Ok(self)
+    }
+}
+
+impl<O, P, F> Solver<O, IterState<P, (), (), (), (), F>> for Replayer<P, F>
+where
+    O: CostFunction<Param = P, Output = F>,
+    P: Clone,
+    F: ArgminFloat,
+{
+    fn name(&self) -> &str {
+        "Replayer"
+    }
+
+    fn next_iter(
+        &mut self,
+        problem: &mut Problem<O>,
+        state: IterState<P, (), (), (), (), F>,
+    ) -> Result<(IterState<P, (), (), (), (), F>, Option<KV>), Error> {
+        let entry = self
+            .log
+            .entries
+            .get(self.position)
+            .ok_or_else(argmin_error_closure!(
+                PotentialBug,
+                "`Replayer`: End of replay log reached."
+            ))?;
+        self.position += 1;
+
+        let cost = problem.cost(&entry.param)?;
+        let diverged = if cost.is_nan() || entry.cost.is_nan() {
+            cost.is_nan() != entry.cost.is_nan()
+        } else {
+            (cost - entry.cost).abs() > self.tolerance
+        };
+
+        let kv = kv!(
+            "recorded_iter" => entry.iter;
+            "recorded_cost" => entry.cost;
+        );
+        let state = state.param(entry.param.clone()).cost(cost);
+        if diverged {
+            return Ok((
+                state.terminate_with(TerminationReason::ReplayDiverged { iter: entry.iter }),
+                Some(kv),
+            ));
+        }
+        Ok((state, Some(kv)))
+    }
+
+    fn terminate(&mut self, _state: &IterState<P, (), (), (), (), F>) -> TerminationStatus {
+        if self.position >= self.log.len() {
+            return TerminationStatus::Terminated(TerminationReason::ReplayEnded);
+        }
+        TerminationStatus::NotTerminated
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::observers::ObserverMode;
+    use crate::core::test_utils::{TestProblem, TestSolver};
+    use crate::core::{ArgminError, Executor};
+
+    test_trait_impl!(replayer, Replayer<Vec<f64>, f64>);
+
+    send_sync_test!(replay_recorder, ReplayRecorder<Vec<f64>, f64>);
+
+    /// Problem whose cost function value is the first entry of the parameter vector
+    #[derive(Clone)]
+    struct FirstEntry {
+        offset: f64,
+    }
+
+    impl CostFunction for FirstEntry {
+        type Param = Vec<f64>;
+        type Output = f64;
+
+        fn cost(&self, p: &Self::Param) -> Result<Self::Output, Error> {
+            Ok(p[0] + if p[0] > 2.5 { self.offset } else { 0.0 })
+        }
+    }
+
+    fn log() -> ReplayLog<Vec<f64>, f64> {
+        let entries = (0..5u64)
+            .map(|iter| ReplayEntry {
+                iter,
+                param: vec![iter as f64],
+                cost: iter as f64,
+            })
+            .collect();
+        ReplayLog {
+            seed: Some(42),
+            entries,
+        }
+    }
+
+    #[test]
+    fn test_record() {
+        let recorder = ReplayRecorder::with_seed(42);
+        Executor::new(TestProblem::new(), TestSolver::new())
+            .configure(|state| state.param(vec![1.0, 2.0]).cost(3.0).max_iters(4))
+            .add_observer(recorder.clone(), ObserverMode::Always)
+            .run()
+            .unwrap();
+
+        let log = recorder.log();
+        assert_eq!(log.seed, Some(42));
+        assert_eq!(log.len(), 4);
+        for (i, entry) in log.entries.iter().enumerate() {
+            assert_eq!(entry.iter, i as u64);
+            assert_eq!(entry.param, vec![1.0, 2.0]);
+        }
+    }
+
+    #[test]
+    fn test_replay() {
+        let res = Executor::new(FirstEntry { offset: 0.0 }, Replayer::new(log()))
+            .run()
+            .unwrap();
+        let state = res.state();
+        assert_eq!(state.get_iter(), 5);
+        assert_eq!(state.get_best_param().unwrap(), &vec![0.0]);
+        assert_eq!(
+            state.get_termination_reason(),
+            Some(&TerminationReason::ReplayEnded)
+        );
+    }
+
+    #[test]
+    fn test_replay_diverged() {
+        let res = Executor::new(FirstEntry { offset: 1e-10 }, Replayer::new(log()))
+            .run()
+            .unwrap();
+        let state = res.state();
+        assert_eq!(state.get_iter(), 4);
+        assert_eq!(state.get_param().unwrap(), &vec![3.0]);
+        assert_eq!(
+            state.get_termination_reason(),
+            Some(&TerminationReason::ReplayDiverged { iter: 3 })
+        );
+
+        // Deviations within the tolerance are accepted
+        let replayer = Replayer::new(log()).with_tolerance(1e-9).unwrap();
+        let res = Executor::new(FirstEntry { offset: 1e-10 }, replayer)
+            .run()
+            .unwrap();
+        assert_eq!(res.state().get_iter(), 5);
+    }
+
+    #[test]
+    fn test_with_tolerance() {
+        assert_error!(
+            Replayer::new(log()).with_tolerance(-1.0),
+            ArgminError,
+            "Invalid parameter: \"`Replayer`: tolerance must be >= 0.\""
+        );
+    }
+}
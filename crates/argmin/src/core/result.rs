@@ -5,7 +5,8 @@
 // http://opensource.org/licenses/MIT>, at your option. This file may not be
 // copied, modified, or distributed except according to those terms.
 
-use crate::core::{ArgminFloat, Problem, Solver, State};
+use crate::core::observers::DisabledObserver;
+use crate::core::{Archive, ArgminFloat, Problem, Solver, State};
 use num_traits::{Float, FromPrimitive};
 use std::cmp::Ordering;
 use std::fmt;
@@ -23,6 +24,8 @@ pub struct OptimizationResult<O, S, I> {
     pub solver: S,
     /// Iteration state
     pub state: I,
+    /// Observers which were disabled during the run after returning an error
+    pub disabled_observers: Vec<DisabledObserver>,
 }
 
 impl<O, S, I> OptimizationResult<O, S, I> {
@@ -43,7 +46,7 @@ impl<O, S, I> OptimizationResult<O, S, I> {
     /// let solver = SomeSolver {};
     ///
     /// let result = OptimizationResult::new(Problem::new(rosenbrock), solver, state);
-    /// # let OptimizationResult { mut problem, solver, state } = result;
+    /// # let OptimizationResult { mut problem, solver, state, .. } = result;
     /// # assert_eq!(problem.take_problem().unwrap(), TestProblem::new());
     /// # assert_eq!(solver, SomeSolver {});
     /// ```
@@ -52,6 +55,7 @@ impl<O, S, I> OptimizationResult<O, S, I> {
             problem,
             solver,
             state,
+            disabled_observers: vec![],
         }
     }
 
@@ -114,6 +118,62 @@ impl<O, S, I> OptimizationResult<O, S, I> {
     pub fn state(&self) -> &I {
         &self.state
     }
+
+    /// Returns the observers which were disabled during the run after returning an error.
+    ///
+    /// This only happens to observers added with
+    /// [`ObserverErrorPolicy::WarnAndDisable`](`crate::core::observers::ObserverErrorPolicy::WarnAndDisable`).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::{Problem, OptimizationResult, IterState, State};
+    /// #
+    /// # struct Rosenbrock {}
+    /// # let solver = ();
+    /// #
+    /// # let state: IterState<Vec<f64>, (), (), (), (), f64> = IterState::new();
+    /// #
+    /// # let result = OptimizationResult::new(Problem::new(Rosenbrock {}), solver, state);
+    /// #
+    /// for disabled in result.disabled_observers() {
+    ///     println!("Observer {} failed: {}", disabled.index, disabled.error);
+    /// }
+    /// # assert!(result.disabled_observers().is_empty());
+    /// ```
+    pub fn disabled_observers(&self) -> &[DisabledObserver] {
+        &self.disabled_observers
+    }
+}
+
+impl<O, S, I> OptimizationResult<O, S, I>
+where
+    I: State,
+{
+    /// Returns a reference to the archive of the best parameter vectors found during the run.
+    ///
+    /// Returns `None` if the archive was not enabled via
+    /// [`Executor::archive`](`crate::core::Executor::archive`).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::{Archive, Problem, OptimizationResult, IterState, State};
+    /// #
+    /// # struct Rosenbrock {}
+    /// # let solver = ();
+    /// #
+    /// # let mut state: IterState<Vec<f64>, (), (), (), (), f64> = IterState::new();
+    /// # state.set_archive(Archive::new(10));
+    /// #
+    /// # let result = OptimizationResult::new(Problem::new(Rosenbrock {}), solver, state);
+    /// #
+    /// let archive: Option<&Archive<Vec<f64>, f64>> = result.archive();
+    /// # assert!(archive.unwrap().is_empty());
+    /// ```
+    pub fn archive(&self) -> Option<&Archive<I::Param, I::Float>> {
+        self.state.get_archive()
+    }
 }
 
 impl<O, S, I> std::fmt::Display for OptimizationResult<O, S, I>
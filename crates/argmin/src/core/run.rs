@@ -0,0 +1,317 @@
+// Copyright 2018-2024 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use crate::core::{KvValue, KV};
+use rand::RngCore;
+#[cfg(feature = "serde1")]
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Identification and user provided metadata of an optimization run
+///
+/// Every [`Executor`](`crate::core::Executor`) is assigned a random (version 4) UUID on
+/// construction. Optionally, a name, tags and arbitrary key/value pairs can be attached via
+/// [`Executor::run_name`](`crate::core::Executor::run_name`),
+/// [`Executor::run_tag`](`crate::core::Executor::run_tag`) and
+/// [`Executor::run_metadata`](`crate::core::Executor::run_metadata`).
+///
+/// The run information is passed to the observers in `observe_init` (see [`RunInfo::kv`]) and to
+/// checkpoints via [`Checkpoint::save_run`](`crate::core::checkpointing::Checkpoint::save_run`).
+/// This allows one to correlate logs, spectator runs and checkpoints of the same run.
+///
+/// # Example
+///
+/// ```
+/// # use argmin::core::RunInfo;
+/// let run = RunInfo::new()
+///     .name("rosenbrock")
+///     .tag("benchmark")
+///     .metadata("host", "node-1");
+///
+/// assert_eq!(run.id().len(), 36);
+/// assert_eq!(run.get_name(), Some("rosenbrock"));
+/// assert_eq!(run.get_tags(), &["benchmark".to_string()]);
+/// assert_eq!(run.get_metadata()["host"], "node-1");
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+pub struct RunInfo {
+    /// Unique identifier of the run
+    id: String,
+    /// Name of the run
+    name: Option<String>,
+    /// Tags
+    tags: Vec<String>,
+    /// Arbitrary key/value pairs
+    metadata: BTreeMap<String, String>,
+}
+
+impl RunInfo {
+    /// Constructs a new `RunInfo` with a random UUID and without metadata.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::RunInfo;
+    /// let run = RunInfo::new();
+    /// # assert_ne!(run.id(), RunInfo::new().id());
+    /// # assert!(run.get_name().is_none());
+    /// ```
+    pub fn new() -> Self {
+        RunInfo {
+            id: uuid_v4(),
+            name: None,
+            tags: vec![],
+            metadata: BTreeMap::new(),
+        }
+    }
+
+    /// Replaces the generated identifier with a user provided one.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::RunInfo;
+    /// let run = RunInfo::new().with_id("run-42");
+    /// # assert_eq!(run.id(), "run-42");
+    /// ```
+    #[must_use]
+    pub fn with_id<T: Into<String>>(mut self, id: T) -> Self {
+        self.id = id.into();
+        self
+    }
+
+    /// Sets the name of the run.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::RunInfo;
+    /// let run = RunInfo::new().name("rosenbrock");
+    /// # assert_eq!(run.get_name(), Some("rosenbrock"));
+    /// ```
+    #[must_use]
+    pub fn name<T: Into<String>>(mut self, name: T) -> Self {
+        self.name = Some(name.into());
+        self
+    }
+
+    /// Adds a tag to the run.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::RunInfo;
+    /// let run = RunInfo::new().tag("benchmark").tag("nightly");
+    /// # assert_eq!(run.get_tags(), &["benchmark".to_string(), "nightly".to_string()]);
+    /// ```
+    #[must_use]
+    pub fn tag<T: Into<String>>(mut self, tag: T) -> Self {
+        self.tags.push(tag.into());
+        self
+    }
+
+    /// Adds a key/value pair to the metadata of the run.
+    ///
+    /// Existing values are overwritten.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::RunInfo;
+    /// let run = RunInfo::new().metadata("host", "node-1");
+    /// # assert_eq!(run.get_metadata()["host"], "node-1");
+    /// ```
+    #[must_use]
+    pub fn metadata<K: Into<String>, V: Into<String>>(mut self, key: K, value: V) -> Self {
+        self.metadata.insert(key.into(), value.into());
+        self
+    }
+
+    /// Returns the identifier of the run.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::RunInfo;
+    /// let run = RunInfo::new();
+    /// let id: &str = run.id();
+    /// ```
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    /// Returns the name of the run, if set.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::RunInfo;
+    /// let run = RunInfo::new();
+    /// let name: Option<&str> = run.get_name();
+    /// ```
+    pub fn get_name(&self) -> Option<&str> {
+        self.name.as_deref()
+    }
+
+    /// Returns the tags of the run.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::RunInfo;
+    /// let run = RunInfo::new();
+    /// let tags: &[String] = run.get_tags();
+    /// ```
+    pub fn get_tags(&self) -> &[String] {
+        &self.tags
+    }
+
+    /// Returns the metadata of the run.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::RunInfo;
+    /// # use std::collections::BTreeMap;
+    /// let run = RunInfo::new();
+    /// let metadata: &BTreeMap<String, String> = run.get_metadata();
+    /// ```
+    pub fn get_metadata(&self) -> &BTreeMap<String, String> {
+        &self.metadata
+    }
+
+    /// Returns the run information as key-value store.
+    ///
+    /// The identifier is stored under `run.id`, the name (if set) under `run.name`, the tags (if
+    /// any) as comma separated list under `run.tags` and each metadata entry under
+    /// `run.metadata.<key>`. This is what observers receive in `observe_init` in addition to the
+    /// solver's configuration.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::{KvValue, RunInfo};
+    /// let run = RunInfo::new()
+    ///     .with_id("run-42")
+    ///     .tag("a")
+    ///     .tag("b")
+    ///     .metadata("host", "node-1");
+    /// let kv = run.kv();
+    ///
+    /// assert_eq!(kv.get("run.id"), Some(&KvValue::Str("run-42".to_string())));
+    /// assert_eq!(kv.get("run.tags"), Some(&KvValue::Str("a,b".to_string())));
+    /// assert_eq!(kv.get("run.metadata.host"), Some(&KvValue::Str("node-1".to_string())));
+    /// assert!(kv.get("run.name").is_none());
+    /// ```
+    pub fn kv(&self) -> KV {
+        let mut kv = KV::new();
+        kv.insert("id", KvValue::from(self.id.as_str()));
+        if let Some(name) = self.name.as_ref() {
+            kv.insert("name", KvValue::from(name.as_str()));
+        }
+        if !self.tags.is_empty() {
+            kv.insert("tags", KvValue::from(self.tags.join(",")));
+        }
+        for (key, value) in self.metadata.iter() {
+            kv.insert(format!("metadata.{key}"), KvValue::from(value.as_str()));
+        }
+        kv.with_prefix("run")
+    }
+}
+
+impl Default for RunInfo {
+    fn default() -> Self {
+        RunInfo::new()
+    }
+}
+
+/// Generates a random (version 4) UUID in its hyphenated string representation.
+fn uuid_v4() -> String {
+    let mut bytes = [0u8; 16];
+    rand::thread_rng().fill_bytes(&mut bytes);
+    // Version 4
+    bytes[6] = (bytes[6] & 0x0f) | 0x40;
+    // Variant 1 (RFC 4122)
+    bytes[8] = (bytes[8] & 0x3f) | 0x80;
+    let hex: String = bytes.iter().map(|b| format!("{b:02x}")).collect();
+    format!(
+        "{}-{}-{}-{}-{}",
+        &hex[0..8],
+        &hex[8..12],
+        &hex[12..16],
+        &hex[16..20],
+        &hex[20..32]
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    test_trait_impl!(run_info, RunInfo);
+
+    #[test]
+    fn test_new() {
+        let RunInfo {
+            id,
+            name,
+            tags,
+            metadata,
+        } = RunInfo::new();
+        assert_eq!(id.len(), 36);
+        assert!(name.is_none());
+        assert!(tags.is_empty());
+        assert!(metadata.is_empty());
+        assert_ne!(RunInfo::new().id(), RunInfo::new().id());
+    }
+
+    #[test]
+    fn test_uuid_v4_format() {
+        for _ in 0..100 {
+            let id = uuid_v4();
+            let groups: Vec<&str> = id.split('-').collect();
+            assert_eq!(
+                groups.iter().map(|g| g.len()).collect::<Vec<_>>(),
+                vec![8, 4, 4, 4, 12]
+            );
+            assert!(id
+                .chars()
+                .all(|c| c == '-' || c.is_ascii_digit() || ('a'..='f').contains(&c)));
+            assert!(groups[2].starts_with('4'));
+            assert!(groups[3].starts_with(['8', '9', 'a', 'b']));
+        }
+    }
+
+    #[test]
+    fn test_kv() {
+        let run = RunInfo::new()
+            .with_id("abc")
+            .name("test")
+            .tag("t1")
+            .tag("t2")
+            .metadata("k1", "v1")
+            .metadata("k1", "v2")
+            .metadata("k2", "v3");
+        let kv = run.kv();
+        assert_eq!(kv.kv.len(), 5);
+        assert_eq!(kv.get("run.id"), Some(&KvValue::Str("abc".to_string())));
+        assert_eq!(kv.get("run.name"), Some(&KvValue::Str("test".to_string())));
+        assert_eq!(kv.get("run.tags"), Some(&KvValue::Str("t1,t2".to_string())));
+        assert_eq!(
+            kv.get("run.metadata.k1"),
+            Some(&KvValue::Str("v2".to_string()))
+        );
+        assert_eq!(
+            kv.get("run.metadata.k2"),
+            Some(&KvValue::Str("v3".to_string()))
+        );
+
+        let kv = RunInfo::new().with_id("abc").kv();
+        assert_eq!(kv.kv.len(), 1);
+    }
+}
@@ -66,12 +66,27 @@ pub trait Solver<O, I: State> {
     /// Name of the solver. Mainly used in [Observers](`crate::core::observers::Observe`).
     fn name(&self) -> &str;
 
+    /// Checks whether the initial `state` is compatible with the solver.
+    ///
+    /// Solvers which require certain items of the state (such as an initial parameter vector or
+    /// an initial inverse Hessian), or which would silently ignore items provided by the user,
+    /// should report this here with a descriptive error. The
+    /// [`Executor`](`crate::core::Executor`) calls this method before
+    /// [`init`](`Solver::init`), and it can be called explicitly after configuring the state via
+    /// [`Executor::validate`](`crate::core::Executor::validate`).
+    /// The default implementation accepts any state.
+    fn check_state(&self, _state: &I) -> Result<(), Error> {
+        Ok(())
+    }
+
     /// Initializes the algorithm.
     ///
     /// Executed before any iterations are performed and has access to the optimization problem
     /// definition and the internal state of the solver.
     /// Returns an updated `state` and optionally a `KV` which holds key-value pairs used in
     /// [Observers](`crate::core::observers::Observe`).
+    /// The solvers shipped with argmin report their configuration here, using keys prefixed
+    /// with `config.` (for instance `config.tolerance_grad`).
     /// The default implementation returns the unaltered `state` and no `KV`.
     fn init(&mut self, _problem: &mut Problem<O>, state: I) -> Result<(I, Option<KV>), Error> {
         Ok((state, None))
@@ -115,3 +130,37 @@ pub trait Solver<O, I: State> {
         TerminationStatus::NotTerminated
     }
 }
+
+/// Forwards all methods to the boxed solver.
+///
+/// This allows trait objects such as `Box<dyn Solver<O, I>>` to be used with the
+/// [`Executor`](`crate::core::Executor`), for instance when the solver is only known at runtime.
+impl<O, I, S> Solver<O, I> for Box<S>
+where
+    I: State,
+    S: Solver<O, I> + ?Sized,
+{
+    fn name(&self) -> &str {
+        (**self).name()
+    }
+
+    fn check_state(&self, state: &I) -> Result<(), Error> {
+        (**self).check_state(state)
+    }
+
+    fn init(&mut self, problem: &mut Problem<O>, state: I) -> Result<(I, Option<KV>), Error> {
+        (**self).init(problem, state)
+    }
+
+    fn next_iter(&mut self, problem: &mut Problem<O>, state: I) -> Result<(I, Option<KV>), Error> {
+        (**self).next_iter(problem, state)
+    }
+
+    fn terminate_internal(&mut self, state: &I) -> TerminationStatus {
+        (**self).terminate_internal(state)
+    }
+
+    fn terminate(&mut self, state: &I) -> TerminationStatus {
+        (**self).terminate(state)
+    }
+}
@@ -5,7 +5,15 @@
 // http://opensource.org/licenses/MIT>, at your option. This file may not be
 // copied, modified, or distributed except according to those terms.
 
-use crate::core::{ArgminFloat, Problem, State, TerminationReason, TerminationStatus};
+use crate::core::{
+    Archive, ArgminFloat, CapStep, CheckNumerics, ClipParam, CostFunction, Error, GradientNorm,
+    InitialEvaluation, Problem, State, TerminationReason, TerminationStatus,
+};
+use argmin_math::{
+    ArgminAdd, ArgminAllFinite, ArgminBound, ArgminL2Norm, ArgminMinMax, ArgminMul, ArgminRandom,
+    ArgminSub,
+};
+use rand::RngCore;
 #[cfg(feature = "serde1")]
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
@@ -33,6 +41,7 @@ use web_time::Duration;
 ///   annealing,...)
 /// * elapsed time
 /// * termination status
+/// * bounds into which parameter vectors are clipped (if enabled)
 #[derive(Clone, Default, Debug, Eq, PartialEq)]
 #[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
 pub struct IterState<P, G, J, H, R, F> {
@@ -80,6 +89,9 @@ pub struct IterState<P, G, J, H, R, F> {
     pub last_best_iter: u64,
     /// Maximum number of iterations
     pub max_iters: u64,
+    /// Policy for evaluations at the initial parameter vector
+    #[cfg_attr(feature = "serde1", serde(default))]
+    pub initial_evaluation: InitialEvaluation,
     /// Evaluation counts
     pub counts: HashMap<String, u64>,
     /// Update evaluation counts?
@@ -88,6 +100,18 @@ pub struct IterState<P, G, J, H, R, F> {
     pub time: Option<Duration>,
     /// Status of optimization execution
     pub termination_status: TerminationStatus,
+    /// Archive of the best parameter vectors (if enabled)
+    #[cfg_attr(feature = "serde1", serde(default = "Option::default"))]
+    pub archive: Option<Archive<P, F>>,
+    /// Lower and upper bounds of the parameter vector (if set)
+    #[cfg_attr(feature = "serde1", serde(default = "Option::default"))]
+    pub bounds: Option<(P, P)>,
+    /// Samples a parameter vector from bounds; set by [`IterState::bounds`]
+    #[cfg_attr(feature = "serde1", serde(skip))]
+    sample_fn: Option<SampleFn<P>>,
+    /// Parameter vector from which the current step is measured, see [`CapStep`]
+    #[cfg_attr(feature = "serde1", serde(default = "Option::default"))]
+    step_origin: Option<P>,
 }
 
 impl<P, G, J, H, R, F> IterState<P, G, J, H, R, F>
@@ -122,6 +146,43 @@ where
         self
     }
 
+    /// Set parameter vector by copying `param`. Like [`param`](`IterState::param`), this shifts
+    /// the stored parameter vector to the previous parameter vector.
+    ///
+    /// Instead of cloning `param` into a new allocation, it is copied into the previous parameter
+    /// vector which would otherwise be dropped (via [`Clone::clone_from`]). This is useful for
+    /// solvers which keep their own copy of the current parameter vector, such as Nelder-Mead.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::{IterState, State};
+    /// # let state: IterState<Vec<f64>, (), (), (), (), f64> = IterState::new();
+    /// # let state = state.param(vec![1.0f64, 2.0f64]).param(vec![3.0f64, 4.0f64]);
+    /// let param = vec![0.0f64, 3.0f64];
+    /// let state = state.param_from(&param);
+    /// # assert_eq!(state.prev_param.as_ref().unwrap()[0].to_ne_bytes(), 3.0f64.to_ne_bytes());
+    /// # assert_eq!(state.prev_param.as_ref().unwrap()[1].to_ne_bytes(), 4.0f64.to_ne_bytes());
+    /// # assert_eq!(state.param.as_ref().unwrap()[0].to_ne_bytes(), 0.0f64.to_ne_bytes());
+    /// # assert_eq!(state.param.as_ref().unwrap()[1].to_ne_bytes(), 3.0f64.to_ne_bytes());
+    /// ```
+    #[must_use]
+    pub fn param_from(mut self, param: &P) -> Self
+    where
+        P: Clone,
+    {
+        let buffer = self.prev_param.take();
+        std::mem::swap(&mut self.prev_param, &mut self.param);
+        self.param = Some(match buffer {
+            Some(mut buffer) => {
+                buffer.clone_from(param);
+                buffer
+            }
+            None => param.clone(),
+        });
+        self
+    }
+
     /// Set gradient. This shifts the stored gradient to the previous gradient.
     ///
     /// # Example
@@ -287,6 +348,24 @@ where
         self
     }
 
+    /// Set the policy for evaluating the cost function, gradient and Hessian at the initial
+    /// parameter vector. See [`InitialEvaluation`] for details.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::{IterState, State, InitialEvaluation};
+    /// # let state: IterState<Vec<f64>, (), (), (), (), f64> = IterState::new();
+    /// # assert_eq!(state.initial_evaluation, InitialEvaluation::Auto);
+    /// let state = state.initial_evaluation(InitialEvaluation::Force);
+    /// # assert_eq!(state.initial_evaluation, InitialEvaluation::Force);
+    /// ```
+    #[must_use]
+    pub fn initial_evaluation(mut self, policy: InitialEvaluation) -> Self {
+        self.initial_evaluation = policy;
+        self
+    }
+
     /// Set residuals. This shifts the stored residuals to the previous residuals.
     ///
     /// # Example
@@ -389,6 +468,115 @@ where
         self.target_cost
     }
 
+    /// Returns the lower and upper bounds of the parameter vector (if set)
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::{IterState, State};
+    /// let state: IterState<Vec<f64>, (), (), (), (), f64> =
+    ///     IterState::new().bounds(vec![-1.0, 0.0], vec![1.0, 2.0]);
+    /// let (lower, upper) = state.get_bounds().unwrap();
+    /// # assert_eq!(lower, &vec![-1.0, 0.0]);
+    /// # assert_eq!(upper, &vec![1.0, 2.0]);
+    /// ```
+    pub fn get_bounds(&self) -> Option<(&P, &P)> {
+        self.bounds.as_ref().map(|(lower, upper)| (lower, upper))
+    }
+
+    /// Returns the largest step length `alpha` for which `param + alpha * direction` lies within
+    /// the [`bounds`](`IterState::bounds`), or `None` if no bounds are set.
+    ///
+    /// This is infinite if the bounds do not restrict the steps along `direction` and zero if
+    /// `direction` leaves the bounds immediately, which can be avoided by passing the direction
+    /// through [`project_direction`](`IterState::project_direction`) first. Solvers pass this to
+    /// their line search via
+    /// [`LineSearch::max_step_length`](`crate::solver::linesearch::LineSearch::max_step_length`)
+    /// such that the cost function is never evaluated outside of the bounds.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::{IterState, State};
+    /// let state: IterState<Vec<f64>, (), (), (), (), f64> =
+    ///     IterState::new().bounds(vec![-1.0, -1.0], vec![1.0, 1.0]);
+    /// let alpha = state.max_step_in_bounds(&vec![0.0, 0.5], &vec![2.0, 1.0]).unwrap();
+    /// # assert_eq!(alpha.to_ne_bytes(), 0.5f64.to_ne_bytes());
+    /// ```
+    pub fn max_step_in_bounds<D>(&self, param: &P, direction: &D) -> Option<F>
+    where
+        P: ArgminBound<D, F>,
+    {
+        let (lower, upper) = self.bounds.as_ref()?;
+        Some(param.max_step_in_bounds(direction, lower, upper))
+    }
+
+    /// Sets the entries of `direction` to zero which point out of the
+    /// [`bounds`](`IterState::bounds`) at entries of `param` that lie on a bound.
+    ///
+    /// Moving along the returned direction does not immediately leave the bounds, hence
+    /// [`max_step_in_bounds`](`IterState::max_step_in_bounds`) is positive unless the returned
+    /// direction vanishes. If no bounds are set, `direction` is returned unchanged.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::{IterState, State};
+    /// let state: IterState<Vec<f64>, (), (), (), (), f64> =
+    ///     IterState::new().bounds(vec![-1.0, -1.0], vec![1.0, 1.0]);
+    /// let direction = state.project_direction(&vec![0.0, 1.0], vec![2.0, 1.0]);
+    /// # assert_eq!(direction, vec![2.0, 0.0]);
+    /// ```
+    pub fn project_direction<D>(&self, param: &P, direction: D) -> D
+    where
+        P: ArgminBound<D, F>,
+    {
+        match self.bounds.as_ref() {
+            Some((lower, upper)) => param.project_direction(&direction, lower, upper),
+            None => direction,
+        }
+    }
+
+    /// Draws a parameter vector uniformly from the bounds set via
+    /// [`bounds`](`IterState::bounds`).
+    ///
+    /// This allows solvers to choose an initial parameter vector (or an initial simplex or
+    /// population) if none was provided. Returns `None` if no bounds are set.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::{IterState, State};
+    /// use rand::SeedableRng;
+    ///
+    /// let state: IterState<Vec<f64>, (), (), (), (), f64> =
+    ///     IterState::new().bounds(vec![-1.0, 0.0], vec![1.0, 2.0]);
+    /// let mut rng = rand_xoshiro::Xoshiro256PlusPlus::seed_from_u64(42);
+    /// let param = state.sample_param(&mut rng).unwrap();
+    /// # assert!(param[0] >= -1.0 && param[0] <= 1.0);
+    /// # assert!(param[1] >= 0.0 && param[1] <= 2.0);
+    /// ```
+    pub fn sample_param<RNG: RngCore>(&self, rng: &mut RNG) -> Option<P> {
+        match (self.sample_fn, self.bounds.as_ref()) {
+            (Some(SampleFn(sample)), Some((lower, upper))) => Some(sample(lower, upper, rng)),
+            _ => None,
+        }
+    }
+
+    /// Returns the policy for evaluations at the initial parameter vector
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::{IterState, State, InitialEvaluation};
+    /// # let state: IterState<Vec<f64>, (), (), (), (), f64> = IterState::new();
+    /// let policy = state.get_initial_evaluation();
+    /// # assert_eq!(policy, InitialEvaluation::Auto);
+    /// ```
+    pub fn get_initial_evaluation(&self) -> InitialEvaluation {
+        self.initial_evaluation
+    }
+
     /// Moves the current parameter vector out and replaces it internally with `None`
     ///
     /// # Example
@@ -1005,7 +1193,7 @@ where
     /// ```
     /// # extern crate web_time;
     /// # use web_time::Duration;
-    /// # use argmin::core::{IterState, State, ArgminFloat, TerminationStatus};
+    /// # use argmin::core::{IterState, State, ArgminFloat, InitialEvaluation, TerminationStatus};
     /// let state: IterState<Vec<f64>, Vec<f64>, Vec<Vec<f64>>, Vec<Vec<f64>>, Vec<f64>, f64> = IterState::new();
     /// # assert!(state.param.is_none());
     /// # assert!(state.prev_param.is_none());
@@ -1027,9 +1215,12 @@ where
     /// # assert_eq!(state.iter, 0);
     /// # assert_eq!(state.last_best_iter, 0);
     /// # assert_eq!(state.max_iters, u64::MAX);
+    /// # assert_eq!(state.initial_evaluation, InitialEvaluation::Auto);
     /// # assert_eq!(state.counts.len(), 0);
     /// # assert_eq!(state.time.unwrap(), Duration::ZERO);
     /// # assert_eq!(state.termination_status, TerminationStatus::NotTerminated);
+    /// # assert!(state.archive.is_none());
+    /// # assert!(state.bounds.is_none());
     /// ```
     fn new() -> Self {
         IterState {
@@ -1055,10 +1246,15 @@ where
             iter: 0,
             last_best_iter: 0,
             max_iters: u64::MAX,
+            initial_evaluation: InitialEvaluation::Auto,
             counts: HashMap::new(),
             counting_enabled: false,
             time: Some(Duration::ZERO),
             termination_status: TerminationStatus::NotTerminated,
+            archive: None,
+            bounds: None,
+            sample_fn: None,
+            step_origin: None,
         }
     }
 
@@ -1117,10 +1313,14 @@ where
                 && self.best_cost.is_infinite()
                 && self.cost.is_sign_positive() == self.best_cost.is_sign_positive())
         {
-            // If there is no parameter vector, then also don't set the best param.
-            if let Some(param) = self.param.as_ref().cloned() {
+            // If there is no parameter vector, then also don't set the best param. The previous
+            // best parameter vector is overwritten, therefore its allocation is reused.
+            if let Some(param) = self.param.as_ref() {
                 std::mem::swap(&mut self.prev_best_param, &mut self.best_param);
-                self.best_param = Some(param);
+                match self.best_param.as_mut() {
+                    Some(best_param) => best_param.clone_from(param),
+                    None => self.best_param = Some(param.clone()),
+                }
             }
             std::mem::swap(&mut self.prev_best_cost, &mut self.best_cost);
             self.best_cost = self.cost;
@@ -1320,6 +1520,50 @@ where
         }
     }
 
+    /// Enables the archive of the best parameter vectors
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::{Archive, IterState, State};
+    /// # let mut state: IterState<Vec<f64>, (), (), (), (), f64> = IterState::new();
+    /// state.set_archive(Archive::new(10));
+    /// # assert_eq!(state.get_archive().unwrap().capacity(), 10);
+    /// ```
+    fn set_archive(&mut self, archive: Archive<P, F>) {
+        self.archive = Some(archive);
+    }
+
+    /// Returns a reference to the archive of the best parameter vectors (if enabled)
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::{IterState, State};
+    /// # let state: IterState<Vec<f64>, (), (), (), (), f64> = IterState::new();
+    /// let archive = state.get_archive();
+    /// # assert!(archive.is_none());
+    /// ```
+    fn get_archive(&self) -> Option<&Archive<P, F>> {
+        self.archive.as_ref()
+    }
+
+    /// Returns a mutable reference to the archive of the best parameter vectors (if enabled)
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::{Archive, IterState, State};
+    /// # let mut state: IterState<Vec<f64>, (), (), (), (), f64> = IterState::new();
+    /// # state.set_archive(Archive::new(10));
+    /// let archive = state.get_archive_mut().unwrap();
+    /// archive.insert(vec![1.0], 2.0);
+    /// # assert_eq!(state.get_archive().unwrap().len(), 1);
+    /// ```
+    fn get_archive_mut(&mut self) -> Option<&mut Archive<P, F>> {
+        self.archive.as_mut()
+    }
+
     /// Returns the time elapsed since the start of the optimization.
     ///
     /// # Example
@@ -1422,6 +1666,247 @@ where
     }
 }
 
+impl<P, G, J, H, R, F> IterState<P, G, J, H, R, F>
+where
+    Self: State<Float = F>,
+    P: ArgminRandom,
+    F: ArgminFloat,
+{
+    /// Set lower and upper bounds of the parameter vector
+    ///
+    /// The [`Executor`](`crate::core::Executor`) clips parameter vectors into the bounds if this
+    /// is enabled via [`Executor::clip_params`](`crate::core::Executor::clip_params`). Solvers
+    /// which support it use the bounds to sample an initial parameter vector if none is provided
+    /// (see [`sample_param`](`IterState::sample_param`)), for instance
+    /// [`SimulatedAnnealing`](`crate::solver::simulatedannealing::SimulatedAnnealing`) and
+    /// [`NelderMead::from_bounds`](`crate::solver::neldermead::NelderMead::from_bounds`).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::{IterState, State};
+    /// # let state: IterState<Vec<f64>, (), (), (), (), f64> = IterState::new();
+    /// # assert!(state.bounds.is_none());
+    /// let state = state.bounds(vec![-1.0, 0.0], vec![1.0, 2.0]);
+    /// # assert_eq!(state.bounds.as_ref().unwrap().1[1].to_ne_bytes(), 2.0f64.to_ne_bytes());
+    /// ```
+    #[must_use]
+    pub fn bounds(mut self, lower: P, upper: P) -> Self {
+        self.bounds = Some((lower, upper));
+        self.sample_fn = Some(SampleFn(sample_from_bounds::<P>));
+        self
+    }
+}
+
+/// Shortens the step from `prev_param` to `param` to a norm of `max_norm`. Returns `None` if the
+/// norm of the step does not exceed `max_norm`.
+fn cap_step_norm<P, F>(prev_param: &P, param: &P, max_norm: F) -> Option<P>
+where
+    P: ArgminSub<P, P> + ArgminAdd<P, P> + ArgminMul<F, P> + ArgminL2Norm<F>,
+    F: ArgminFloat,
+{
+    let step = param.sub(prev_param);
+    let norm = step.l2_norm();
+    if norm > max_norm {
+        Some(prev_param.add(&step.mul(&(max_norm / norm))))
+    } else {
+        None
+    }
+}
+
+/// Function which samples a parameter vector from bounds, see [`IterState::bounds`]
+///
+/// For a given `P`, this is always [`sample_from_bounds`], therefore all instances compare equal.
+struct SampleFn<P>(fn(&P, &P, &mut dyn RngCore) -> P);
+
+impl<P> Clone for SampleFn<P> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<P> Copy for SampleFn<P> {}
+
+impl<P> PartialEq for SampleFn<P> {
+    fn eq(&self, _other: &Self) -> bool {
+        true
+    }
+}
+
+impl<P> Eq for SampleFn<P> {}
+
+impl<P> std::fmt::Debug for SampleFn<P> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("SampleFn")
+    }
+}
+
+/// Draws a parameter vector uniformly from `[lower, upper]`.
+fn sample_from_bounds<P: ArgminRandom>(lower: &P, upper: &P, mut rng: &mut dyn RngCore) -> P {
+    P::rand_from_range(lower, upper, &mut rng)
+}
+
+/// Clips `param` into `[lower, upper]`. Returns `None` if `param` is within the bounds.
+fn clip_into_bounds<P>(param: &P, lower: &P, upper: &P) -> Option<P>
+where
+    P: ArgminMinMax + PartialEq,
+{
+    let clipped = P::max(&P::min(param, upper), lower);
+    if clipped == *param {
+        None
+    } else {
+        Some(clipped)
+    }
+}
+
+impl<P, G, J, H, R, F> CapStep for IterState<P, G, J, H, R, F>
+where
+    P: Clone + ArgminSub<P, P> + ArgminAdd<P, P> + ArgminMul<F, P> + ArgminL2Norm<F>,
+    F: ArgminFloat,
+{
+    /// Caps the norm of the step from the parameter vector at the last call of this method to the
+    /// current parameter vector and evaluates the cost function at the capped parameter vector.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::{CapStep, IterState, Problem, State};
+    /// # use argmin::core::test_utils::TestProblem;
+    /// # use approx::assert_relative_eq;
+    /// let mut problem = Problem::new(TestProblem::new());
+    /// let mut state: IterState<Vec<f64>, (), (), (), (), f64> =
+    ///     IterState::new().param(vec![0.0, 0.0]);
+    /// # assert!(!state.cap_step(&mut problem, 1.0).unwrap());
+    /// let mut state = state.param(vec![3.0, 4.0]);
+    /// let capped = state.cap_step(&mut problem, 1.0).unwrap();
+    /// # assert!(capped);
+    /// # assert_relative_eq!(state.param.as_ref().unwrap()[0], 0.6, epsilon = f64::EPSILON);
+    /// # assert_relative_eq!(state.param.as_ref().unwrap()[1], 0.8, epsilon = f64::EPSILON);
+    /// # assert_eq!(problem.counts["cost_count"], 1);
+    /// ```
+    fn cap_step<O>(&mut self, problem: &mut Problem<O>, max_norm: F) -> Result<bool, Error>
+    where
+        O: CostFunction<Param = P, Output = F>,
+    {
+        let capped = match (self.step_origin.as_ref(), self.param.as_ref()) {
+            (Some(origin), Some(param)) => cap_step_norm(origin, param, max_norm),
+            _ => None,
+        };
+        let is_capped = capped.is_some();
+        if let Some(param) = capped {
+            // Not set via `cost` because this must not shift the previous cost function value
+            self.cost = problem.cost(&param)?;
+            self.param = Some(param);
+        }
+        // The current parameter vector is the origin of the next step
+        self.step_origin = self.param.clone();
+        Ok(is_capped)
+    }
+}
+
+impl<P, G, J, H, R, F> ClipParam for IterState<P, G, J, H, R, F>
+where
+    P: Clone + ArgminMinMax + PartialEq,
+    F: ArgminFloat,
+{
+    /// Clips the current parameter vector into the [`bounds`](`IterState::bounds`) and evaluates
+    /// the cost function at the clipped parameter vector.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::{ClipParam, IterState, Problem, State};
+    /// # use argmin::core::test_utils::TestProblem;
+    /// let mut problem = Problem::new(TestProblem::new());
+    /// let mut state: IterState<Vec<f64>, (), (), (), (), f64> = IterState::new()
+    ///     .param(vec![-2.0, 0.5])
+    ///     .bounds(vec![-1.0, -1.0], vec![1.0, 1.0]);
+    /// let clipped = state.clip_param(&mut problem).unwrap();
+    /// # assert_eq!(clipped, Some(true));
+    /// # assert_eq!(state.param.as_ref().unwrap()[0].to_ne_bytes(), (-1.0f64).to_ne_bytes());
+    /// # assert_eq!(state.param.as_ref().unwrap()[1].to_ne_bytes(), 0.5f64.to_ne_bytes());
+    /// # assert_eq!(problem.counts["cost_count"], 1);
+    /// # assert_eq!(state.clip_param(&mut problem).unwrap(), Some(false));
+    /// # assert_eq!(problem.counts["cost_count"], 1);
+    /// ```
+    fn clip_param<O>(&mut self, problem: &mut Problem<O>) -> Result<Option<bool>, Error>
+    where
+        O: CostFunction<Param = P, Output = F>,
+    {
+        let (lower, upper) = match self.bounds.as_ref() {
+            Some(bounds) => bounds,
+            None => return Ok(None),
+        };
+        let clipped = self
+            .param
+            .as_ref()
+            .and_then(|param| clip_into_bounds(param, lower, upper));
+        match clipped {
+            Some(param) => {
+                // Not set via `cost` because this must not shift the previous cost function value
+                self.cost = problem.cost(&param)?;
+                self.param = Some(param);
+                Ok(Some(true))
+            }
+            None => Ok(Some(false)),
+        }
+    }
+}
+
+impl<P, G, J, H, R, F> CheckNumerics for IterState<P, G, J, H, R, F>
+where
+    P: ArgminAllFinite,
+    G: ArgminAllFinite,
+    F: ArgminFloat,
+{
+    /// Checks the cost function value for NaN and the parameter vector and the gradient for NaN
+    /// and infinite values.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::{CheckNumerics, IterState, State};
+    /// let state: IterState<Vec<f64>, Vec<f64>, (), (), (), f64> = IterState::new()
+    ///     .param(vec![1.0, 2.0])
+    ///     .gradient(vec![f64::NAN, 2.0]);
+    ///
+    /// assert_eq!(state.invalid_numeric_value(), Some("gradient"));
+    /// ```
+    fn invalid_numeric_value(&self) -> Option<&'static str> {
+        if self.cost.is_nan() {
+            Some("cost")
+        } else if !self.param.as_ref().map(|p| p.all_finite()).unwrap_or(true) {
+            Some("param")
+        } else if !self.grad.as_ref().map(|g| g.all_finite()).unwrap_or(true) {
+            Some("gradient")
+        } else {
+            None
+        }
+    }
+}
+
+impl<P, G, J, H, R, F> GradientNorm for IterState<P, G, J, H, R, F>
+where
+    G: ArgminL2Norm<F>,
+    F: ArgminFloat,
+{
+    /// Returns the L2 norm of the gradient (if set).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::{GradientNorm, IterState, State};
+    /// let state: IterState<Vec<f64>, Vec<f64>, (), (), (), f64> = IterState::new();
+    /// assert_eq!(state.gradient_norm(), None);
+    ///
+    /// let state = state.gradient(vec![3.0, 4.0]);
+    /// assert_eq!(state.gradient_norm(), Some(5.0));
+    /// ```
+    fn gradient_norm(&self) -> Option<f64> {
+        self.grad.as_ref().and_then(|g| g.l2_norm().to_f64())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1626,4 +2111,42 @@ mod tests {
         assert!(!func_counts.contains_key("jacobian_count"));
         assert!(!func_counts.contains_key("modify_count"));
     }
+
+    #[test]
+    fn test_param_from_reuses_allocation() {
+        let state: IterState<Vec<f64>, (), (), (), (), f64> = IterState::new();
+        let state = state.param_from(&vec![1.0, 2.0]);
+        assert_eq!(*state.get_param().unwrap(), vec![1.0, 2.0]);
+        assert!(state.get_prev_param().is_none());
+
+        let state = state.param_from(&vec![3.0, 4.0]);
+        assert_eq!(*state.get_param().unwrap(), vec![3.0, 4.0]);
+        assert_eq!(*state.get_prev_param().unwrap(), vec![1.0, 2.0]);
+
+        // From now on, the previous parameter vector is recycled
+        let ptr = state.get_prev_param().unwrap().as_ptr();
+        let state = state.param_from(&vec![5.0, 6.0]);
+        assert_eq!(*state.get_param().unwrap(), vec![5.0, 6.0]);
+        assert_eq!(*state.get_prev_param().unwrap(), vec![3.0, 4.0]);
+        assert_eq!(state.get_param().unwrap().as_ptr(), ptr);
+    }
+
+    #[test]
+    fn test_update_reuses_allocation() {
+        let mut state: IterState<Vec<f64>, (), (), (), (), f64> = IterState::new();
+        for (i, cost) in [3.0, 2.0, 1.0].into_iter().enumerate() {
+            let ptr = state.get_prev_best_param().map(|p| p.as_ptr());
+            state = state.param(vec![i as f64; 2]).cost(cost);
+            state.update();
+            assert_eq!(*state.get_best_param().unwrap(), vec![i as f64; 2]);
+            assert_eq!(state.get_best_cost().to_ne_bytes(), cost.to_ne_bytes());
+            if let Some(ptr) = ptr {
+                assert_eq!(state.get_best_param().unwrap().as_ptr(), ptr);
+                assert_eq!(
+                    *state.get_prev_best_param().unwrap(),
+                    vec![i as f64 - 1.0; 2]
+                );
+            }
+        }
+    }
 }
@@ -5,7 +5,10 @@
 // http://opensource.org/licenses/MIT>, at your option. This file may not be
 // copied, modified, or distributed except according to those terms.
 
-use crate::core::{ArgminFloat, Problem, State, TerminationReason, TerminationStatus};
+use crate::core::{
+    ArgminFloat, CheckNumerics, Problem, State, TerminationReason, TerminationStatus,
+};
+use argmin_math::ArgminAllFinite;
 #[cfg(feature = "serde1")]
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
@@ -586,3 +589,31 @@ where
         self.last_best_iter == self.iter
     }
 }
+
+impl<P, F> CheckNumerics for LinearProgramState<P, F>
+where
+    P: ArgminAllFinite,
+    F: ArgminFloat,
+{
+    /// Checks the cost function value for NaN and the parameter vector for NaN and infinite
+    /// values.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::{CheckNumerics, LinearProgramState, State};
+    /// let state: LinearProgramState<Vec<f64>, f64> =
+    ///     LinearProgramState::new().param(vec![1.0, f64::NAN]);
+    ///
+    /// assert_eq!(state.invalid_numeric_value(), Some("param"));
+    /// ```
+    fn invalid_numeric_value(&self) -> Option<&'static str> {
+        if self.cost.is_nan() {
+            Some("cost")
+        } else if !self.param.as_ref().map(|p| p.all_finite()).unwrap_or(true) {
+            Some("param")
+        } else {
+            None
+        }
+    }
+}
@@ -7,13 +7,19 @@
 
 pub mod iterstate;
 pub mod linearprogramstate;
+pub mod paretostate;
 pub mod populationstate;
+pub mod simplexstate;
 
 pub use iterstate::IterState;
 pub use linearprogramstate::LinearProgramState;
+pub use paretostate::ParetoState;
 pub use populationstate::PopulationState;
+pub use simplexstate::SimplexState;
 
-use crate::core::{ArgminFloat, Problem, TerminationReason, TerminationStatus};
+use crate::core::{
+    Archive, ArgminFloat, CostFunction, Error, Problem, TerminationReason, TerminationStatus,
+};
 use std::collections::HashMap;
 use web_time::Duration;
 
@@ -121,4 +127,81 @@ pub trait State {
             TerminationStatus::Terminated(_)
         )
     }
+
+    /// Enables the archive of the best parameter vectors by setting it to `archive`
+    ///
+    /// This is used by [`Executor::archive`](`crate::core::Executor::archive`). The default
+    /// implementation ignores the archive, which means that states which do not override this
+    /// method (as well as [`get_archive`](`State::get_archive`) and
+    /// [`get_archive_mut`](`State::get_archive_mut`)) do not support archives.
+    fn set_archive(&mut self, _archive: Archive<Self::Param, Self::Float>) {}
+
+    /// Returns a reference to the archive of the best parameter vectors if it is enabled
+    fn get_archive(&self) -> Option<&Archive<Self::Param, Self::Float>> {
+        None
+    }
+
+    /// Returns a mutable reference to the archive of the best parameter vectors if it is enabled
+    fn get_archive_mut(&mut self) -> Option<&mut Archive<Self::Param, Self::Float>> {
+        None
+    }
+}
+
+/// Clips the parameter vector held by a state into bounds.
+///
+/// This is used by the [`Executor`](`crate::core::Executor`) if
+/// [`clip_params`](`crate::core::Executor::clip_params`) is enabled.
+pub trait ClipParam: State {
+    /// Clips the current parameter vector into the bounds of the state. Returns whether the
+    /// parameter vector was clipped or `None` if the state has no bounds.
+    ///
+    /// If the parameter vector is clipped, the cost function is evaluated at the clipped
+    /// parameter vector, such that the cost function value held by the state (and therefore the
+    /// best cost function value) always belongs to the parameter vector held by the state.
+    fn clip_param<O>(&mut self, problem: &mut Problem<O>) -> Result<Option<bool>, Error>
+    where
+        O: CostFunction<Param = Self::Param, Output = Self::Float>;
+}
+
+/// Caps the norm of the steps between consecutive parameter vectors held by a state.
+///
+/// This is used by the [`Executor`](`crate::core::Executor`) if
+/// [`max_step_norm`](`crate::core::Executor::max_step_norm`) is set.
+pub trait CapStep: State {
+    /// Shortens the step from the parameter vector at the previous call of this method to the
+    /// current parameter vector to a norm of `max_norm` (keeping its direction) and returns
+    /// whether the step was capped. The first call only records the origin of the next step.
+    ///
+    /// If the step is capped, the cost function is evaluated at the new parameter vector, such
+    /// that the cost function value held by the state (and therefore the best cost function value)
+    /// always belongs to the parameter vector held by the state.
+    fn cap_step<O>(
+        &mut self,
+        problem: &mut Problem<O>,
+        max_norm: Self::Float,
+    ) -> Result<bool, Error>
+    where
+        O: CostFunction<Param = Self::Param, Output = Self::Float>;
+}
+
+/// Checks a state for invalid numeric values (NaN or infinite values).
+///
+/// This is used by the [`Executor`](`crate::core::Executor`) if
+/// [`check_numerics`](`crate::core::Executor::check_numerics`) is enabled.
+///
+/// Note that the cost function value is only checked for NaN, because infinite values are used
+/// by solvers which do not compute the cost function value.
+pub trait CheckNumerics {
+    /// Returns the name of the first quantity which holds an invalid numeric value or `None` if
+    /// all quantities are valid.
+    fn invalid_numeric_value(&self) -> Option<&'static str>;
+}
+
+/// Computes the L2 norm of the gradient held by a state.
+///
+/// This is used by the [`Executor`](`crate::core::Executor`) if
+/// [`report_gradient_norm`](`crate::core::Executor::report_gradient_norm`) is enabled.
+pub trait GradientNorm {
+    /// Returns the L2 norm of the current gradient or `None` if no gradient is available.
+    fn gradient_norm(&self) -> Option<f64>;
 }
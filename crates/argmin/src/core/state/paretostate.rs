@@ -0,0 +1,894 @@
+// Copyright 2018-2024 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use crate::core::{
+    Archive, ArgminFloat, CheckNumerics, Problem, State, TerminationReason, TerminationStatus,
+};
+use argmin_math::ArgminAllFinite;
+#[cfg(feature = "serde1")]
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use web_time::Duration;
+
+/// Maintains the state of multi-objective solvers.
+///
+/// Besides the vector of objective values of the current parameter vector, this state holds the
+/// Pareto front found so far, i.e. all parameter vectors whose objective values are not dominated
+/// by any other parameter vector encountered during the run. A vector of objective values `a`
+/// dominates `b` if it is not worse in any objective and strictly better in at least one (all
+/// objectives are minimized).
+///
+/// The scalar cost function value required by [`State`] is defined by the solver, for instance
+/// as a scalarization of the objectives. It is used for the best parameter vector, the target
+/// cost and observers which are not aware of multiple objectives.
+#[derive(Clone, Default, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+pub struct ParetoState<P, F> {
+    /// Current parameter vector
+    pub param: Option<P>,
+    /// Previous parameter vector
+    pub prev_param: Option<P>,
+    /// Current best parameter vector
+    pub best_param: Option<P>,
+    /// Previous best parameter vector
+    pub prev_best_param: Option<P>,
+    /// Current cost function value
+    pub cost: F,
+    /// Previous cost function value
+    pub prev_cost: F,
+    /// Current best cost function value
+    pub best_cost: F,
+    /// Previous best cost function value
+    pub prev_best_cost: F,
+    /// Target cost function value
+    pub target_cost: F,
+    /// Objective values of the current parameter vector
+    pub objectives: Option<Vec<F>>,
+    /// Non-dominated parameter vectors and their objective values
+    pub front: Vec<(P, Vec<F>)>,
+    /// Current iteration
+    pub iter: u64,
+    /// Iteration number of last best cost
+    pub last_best_iter: u64,
+    /// Maximum number of iterations
+    pub max_iters: u64,
+    /// Evaluation counts
+    pub counts: HashMap<String, u64>,
+    /// Update evaluation counts?
+    pub counting_enabled: bool,
+    /// Time required so far
+    pub time: Option<Duration>,
+    /// Status of optimization execution
+    pub termination_status: TerminationStatus,
+    /// Archive of the best parameter vectors (if enabled)
+    #[cfg_attr(feature = "serde1", serde(default = "Option::default"))]
+    pub archive: Option<Archive<P, F>>,
+}
+
+impl<P, F> ParetoState<P, F>
+where
+    Self: State<Float = F>,
+    F: ArgminFloat,
+{
+    /// Set parameter vector. This shifts the stored parameter vector to the previous parameter
+    /// vector.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::{ParetoState, State};
+    /// let state: ParetoState<Vec<f64>, f64> = ParetoState::new().param(vec![1.0, 2.0]);
+    /// # assert!(state.prev_param.is_none());
+    /// let state = state.param(vec![0.0, 3.0]);
+    /// # assert_eq!(state.prev_param.as_ref().unwrap(), &vec![1.0, 2.0]);
+    /// # assert_eq!(state.param.as_ref().unwrap(), &vec![0.0, 3.0]);
+    /// ```
+    #[must_use]
+    pub fn param(mut self, param: P) -> Self {
+        std::mem::swap(&mut self.prev_param, &mut self.param);
+        self.param = Some(param);
+        self
+    }
+
+    /// Set the current cost function value. This shifts the stored cost function value to the
+    /// previous cost function value.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::{ParetoState, State};
+    /// let state: ParetoState<Vec<f64>, f64> = ParetoState::new().cost(1.0);
+    /// # assert_eq!(state.prev_cost.to_ne_bytes(), f64::INFINITY.to_ne_bytes());
+    /// let state = state.cost(0.0);
+    /// # assert_eq!(state.prev_cost.to_ne_bytes(), 1.0f64.to_ne_bytes());
+    /// # assert_eq!(state.cost.to_ne_bytes(), 0.0f64.to_ne_bytes());
+    /// ```
+    #[must_use]
+    pub fn cost(mut self, cost: F) -> Self {
+        std::mem::swap(&mut self.prev_cost, &mut self.cost);
+        self.cost = cost;
+        self
+    }
+
+    /// Set target cost.
+    ///
+    /// When this cost is reached, the algorithm will stop. The default is
+    /// `Self::Float::NEG_INFINITY`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::{ParetoState, State};
+    /// # let state: ParetoState<Vec<f64>, f64> = ParetoState::new();
+    /// # assert_eq!(state.target_cost.to_ne_bytes(), f64::NEG_INFINITY.to_ne_bytes());
+    /// let state = state.target_cost(0.0);
+    /// # assert_eq!(state.target_cost.to_ne_bytes(), 0.0f64.to_ne_bytes());
+    /// ```
+    #[must_use]
+    pub fn target_cost(mut self, target_cost: F) -> Self {
+        self.target_cost = target_cost;
+        self
+    }
+
+    /// Set the objective values of the current parameter vector.
+    ///
+    /// They are added to the Pareto front together with the current parameter vector in
+    /// [`update`](`State::update`).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::{ParetoState, State};
+    /// # let state: ParetoState<Vec<f64>, f64> = ParetoState::new();
+    /// # assert!(state.objectives.is_none());
+    /// let state = state.objectives(vec![1.0, 2.0]);
+    /// # assert_eq!(state.objectives.as_ref().unwrap(), &vec![1.0, 2.0]);
+    /// ```
+    #[must_use]
+    pub fn objectives(mut self, objectives: Vec<F>) -> Self {
+        self.objectives = Some(objectives);
+        self
+    }
+
+    /// Replaces the Pareto front.
+    ///
+    /// The given points are assumed to be mutually non-dominated.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::{ParetoState, State};
+    /// # let state: ParetoState<Vec<f64>, f64> = ParetoState::new();
+    /// let state = state.front(vec![(vec![0.0], vec![0.0, 1.0]), (vec![1.0], vec![1.0, 0.0])]);
+    /// # assert_eq!(state.front.len(), 2);
+    /// ```
+    #[must_use]
+    pub fn front(mut self, front: Vec<(P, Vec<F>)>) -> Self {
+        self.front = front;
+        self
+    }
+
+    /// Set maximum number of iterations
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::{ParetoState, State};
+    /// # let state: ParetoState<Vec<f64>, f64> = ParetoState::new();
+    /// # assert_eq!(state.max_iters, u64::MAX);
+    /// let state = state.max_iters(1000);
+    /// # assert_eq!(state.max_iters, 1000);
+    /// ```
+    #[must_use]
+    pub fn max_iters(mut self, iters: u64) -> Self {
+        self.max_iters = iters;
+        self
+    }
+
+    /// Returns the current cost function value
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::{ParetoState, State};
+    /// # let state: ParetoState<Vec<f64>, f64> = ParetoState::new().cost(2.0);
+    /// let cost = state.get_cost();
+    /// # assert_eq!(cost.to_ne_bytes(), 2.0f64.to_ne_bytes());
+    /// ```
+    pub fn get_cost(&self) -> F {
+        self.cost
+    }
+
+    /// Returns the previous cost function value
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::{ParetoState, State};
+    /// # let mut state: ParetoState<Vec<f64>, f64> = ParetoState::new();
+    /// # state.prev_cost = 2.0;
+    /// let prev_cost = state.get_prev_cost();
+    /// # assert_eq!(prev_cost.to_ne_bytes(), 2.0f64.to_ne_bytes());
+    /// ```
+    pub fn get_prev_cost(&self) -> F {
+        self.prev_cost
+    }
+
+    /// Returns the current best cost function value
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::{ParetoState, State};
+    /// # let mut state: ParetoState<Vec<f64>, f64> = ParetoState::new();
+    /// # state.best_cost = 2.0;
+    /// let best_cost = state.get_best_cost();
+    /// # assert_eq!(best_cost.to_ne_bytes(), 2.0f64.to_ne_bytes());
+    /// ```
+    pub fn get_best_cost(&self) -> F {
+        self.best_cost
+    }
+
+    /// Returns the previous best cost function value
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::{ParetoState, State};
+    /// # let mut state: ParetoState<Vec<f64>, f64> = ParetoState::new();
+    /// # state.prev_best_cost = 2.0;
+    /// let prev_best_cost = state.get_prev_best_cost();
+    /// # assert_eq!(prev_best_cost.to_ne_bytes(), 2.0f64.to_ne_bytes());
+    /// ```
+    pub fn get_prev_best_cost(&self) -> F {
+        self.prev_best_cost
+    }
+
+    /// Returns the target cost function value
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::{ParetoState, State};
+    /// # let state: ParetoState<Vec<f64>, f64> = ParetoState::new().target_cost(0.0);
+    /// let target_cost = state.get_target_cost();
+    /// # assert_eq!(target_cost.to_ne_bytes(), 0.0f64.to_ne_bytes());
+    /// ```
+    pub fn get_target_cost(&self) -> F {
+        self.target_cost
+    }
+
+    /// Moves the current parameter vector out and replaces it internally with `None`
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::{ParetoState, State};
+    /// # let mut state: ParetoState<Vec<f64>, f64> = ParetoState::new().param(vec![1.0, 2.0]);
+    /// let param = state.take_param();  // Option<P>
+    /// # assert!(state.param.is_none());
+    /// # assert_eq!(param.unwrap(), vec![1.0, 2.0]);
+    /// ```
+    pub fn take_param(&mut self) -> Option<P> {
+        self.param.take()
+    }
+
+    /// Returns a reference to the previous parameter vector
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::{ParetoState, State};
+    /// # let mut state: ParetoState<Vec<f64>, f64> = ParetoState::new();
+    /// # state.prev_param = Some(vec![1.0, 2.0]);
+    /// let prev_param = state.get_prev_param();  // Option<&P>
+    /// # assert_eq!(prev_param.unwrap(), &vec![1.0, 2.0]);
+    /// ```
+    pub fn get_prev_param(&self) -> Option<&P> {
+        self.prev_param.as_ref()
+    }
+
+    /// Returns a reference to the previous best parameter vector
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::{ParetoState, State};
+    /// # let mut state: ParetoState<Vec<f64>, f64> = ParetoState::new();
+    /// # state.prev_best_param = Some(vec![1.0, 2.0]);
+    /// let prev_best_param = state.get_prev_best_param();  // Option<&P>
+    /// # assert_eq!(prev_best_param.unwrap(), &vec![1.0, 2.0]);
+    /// ```
+    pub fn get_prev_best_param(&self) -> Option<&P> {
+        self.prev_best_param.as_ref()
+    }
+
+    /// Moves the best parameter vector out and replaces it internally with `None`
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::{ParetoState, State};
+    /// # let mut state: ParetoState<Vec<f64>, f64> = ParetoState::new();
+    /// # state.best_param = Some(vec![1.0, 2.0]);
+    /// let best_param = state.take_best_param();  // Option<P>
+    /// # assert!(state.best_param.is_none());
+    /// # assert_eq!(best_param.unwrap(), vec![1.0, 2.0]);
+    /// ```
+    pub fn take_best_param(&mut self) -> Option<P> {
+        self.best_param.take()
+    }
+
+    /// Returns a reference to the objective values of the current parameter vector
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::{ParetoState, State};
+    /// # let state: ParetoState<Vec<f64>, f64> = ParetoState::new().objectives(vec![1.0, 2.0]);
+    /// let objectives = state.get_objectives();  // Option<&Vec<F>>
+    /// # assert_eq!(objectives.unwrap(), &vec![1.0, 2.0]);
+    /// ```
+    pub fn get_objectives(&self) -> Option<&Vec<F>> {
+        self.objectives.as_ref()
+    }
+
+    /// Returns a reference to the Pareto front
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::{ParetoState, State};
+    /// # let state: ParetoState<Vec<f64>, f64> = ParetoState::new();
+    /// # let state = state.front(vec![(vec![0.0], vec![0.0, 1.0])]);
+    /// let front = state.get_front();  // &Vec<(P, Vec<F>)>
+    /// # assert_eq!(front[0].0, vec![0.0]);
+    /// ```
+    pub fn get_front(&self) -> &Vec<(P, Vec<F>)> {
+        &self.front
+    }
+
+    /// Moves the Pareto front out and replaces it internally with an empty front
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::{ParetoState, State};
+    /// # let state: ParetoState<Vec<f64>, f64> = ParetoState::new();
+    /// # let mut state = state.front(vec![(vec![0.0], vec![0.0, 1.0])]);
+    /// let front = state.take_front();  // Vec<(P, Vec<F>)>
+    /// # assert!(state.front.is_empty());
+    /// # assert_eq!(front.len(), 1);
+    /// ```
+    pub fn take_front(&mut self) -> Vec<(P, Vec<F>)> {
+        std::mem::take(&mut self.front)
+    }
+
+    /// Adds a parameter vector with objective values to the Pareto front.
+    ///
+    /// The point is rejected if it is dominated by or has the same objective values as a point
+    /// already on the front. Otherwise it is added and all points dominated by it are removed.
+    /// Returns whether the point was added.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::{ParetoState, State};
+    /// let mut state: ParetoState<Vec<f64>, f64> = ParetoState::new();
+    ///
+    /// assert!(state.insert_into_front(vec![0.0], vec![1.0, 3.0]));
+    /// assert!(state.insert_into_front(vec![1.0], vec![3.0, 1.0]));
+    /// // Dominated by the first point
+    /// assert!(!state.insert_into_front(vec![2.0], vec![2.0, 3.0]));
+    /// // Dominates the first point, which is removed
+    /// assert!(state.insert_into_front(vec![3.0], vec![1.0, 2.0]));
+    ///
+    /// let params: Vec<_> = state.get_front().iter().map(|(p, _)| p[0]).collect();
+    /// assert_eq!(params, vec![1.0, 3.0]);
+    /// ```
+    pub fn insert_into_front(&mut self, param: P, objectives: Vec<F>) -> bool {
+        if self
+            .front
+            .iter()
+            .any(|(_, o)| *o == objectives || dominates(o, &objectives))
+        {
+            return false;
+        }
+        self.front.retain(|(_, o)| !dominates(&objectives, o));
+        self.front.push((param, objectives));
+        true
+    }
+
+    /// Overrides state of counting function executions (default: false)
+    /// ```
+    /// # use argmin::core::{State, ParetoState};
+    /// # let mut state: ParetoState<Vec<f64>, f64> = ParetoState::new();
+    /// # assert!(!state.counting_enabled);
+    /// let state = state.counting(true);
+    /// # assert!(state.counting_enabled);
+    /// ```
+    #[must_use]
+    pub fn counting(mut self, mode: bool) -> Self {
+        self.counting_enabled = mode;
+        self
+    }
+}
+
+impl<P, F> State for ParetoState<P, F>
+where
+    P: Clone,
+    F: ArgminFloat,
+{
+    /// Type of parameter vector
+    type Param = P;
+    /// Floating point precision
+    type Float = F;
+
+    /// Create a new ParetoState instance
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # extern crate web_time;
+    /// # use web_time::Duration;
+    /// # use argmin::core::{ParetoState, State, TerminationStatus};
+    /// let state: ParetoState<Vec<f64>, f64> = ParetoState::new();
+    /// # assert!(state.param.is_none());
+    /// # assert!(state.prev_param.is_none());
+    /// # assert!(state.best_param.is_none());
+    /// # assert!(state.prev_best_param.is_none());
+    /// # assert_eq!(state.cost.to_ne_bytes(), f64::INFINITY.to_ne_bytes());
+    /// # assert_eq!(state.prev_cost.to_ne_bytes(), f64::INFINITY.to_ne_bytes());
+    /// # assert_eq!(state.best_cost.to_ne_bytes(), f64::INFINITY.to_ne_bytes());
+    /// # assert_eq!(state.prev_best_cost.to_ne_bytes(), f64::INFINITY.to_ne_bytes());
+    /// # assert_eq!(state.target_cost.to_ne_bytes(), f64::NEG_INFINITY.to_ne_bytes());
+    /// # assert!(state.objectives.is_none());
+    /// # assert!(state.front.is_empty());
+    /// # assert_eq!(state.iter, 0);
+    /// # assert_eq!(state.last_best_iter, 0);
+    /// # assert_eq!(state.max_iters, u64::MAX);
+    /// # assert_eq!(state.counts.len(), 0);
+    /// # assert!(!state.counting_enabled);
+    /// # assert_eq!(state.time.unwrap(), Duration::ZERO);
+    /// # assert_eq!(state.termination_status, TerminationStatus::NotTerminated);
+    /// # assert!(state.archive.is_none());
+    /// ```
+    fn new() -> Self {
+        ParetoState {
+            param: None,
+            prev_param: None,
+            best_param: None,
+            prev_best_param: None,
+            cost: F::infinity(),
+            prev_cost: F::infinity(),
+            best_cost: F::infinity(),
+            prev_best_cost: F::infinity(),
+            target_cost: F::neg_infinity(),
+            objectives: None,
+            front: vec![],
+            iter: 0,
+            last_best_iter: 0,
+            max_iters: u64::MAX,
+            counts: HashMap::new(),
+            counting_enabled: false,
+            time: Some(Duration::ZERO),
+            termination_status: TerminationStatus::NotTerminated,
+            archive: None,
+        }
+    }
+
+    /// Adds the current parameter vector to the Pareto front if its objective values are set and
+    /// not dominated (see [`insert_into_front`](`ParetoState::insert_into_front`)).
+    ///
+    /// Furthermore checks if the current parameter vector is better than the previous best
+    /// parameter vector with respect to the scalar cost function value. If a new best parameter
+    /// vector was found, the state is updated accordingly.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::{ParetoState, State};
+    /// let mut state: ParetoState<Vec<f64>, f64> = ParetoState::new();
+    ///
+    /// // Simulating a new, better parameter vector
+    /// state.best_param = Some(vec![1.0f64]);
+    /// state.best_cost = 10.0;
+    /// state.param = Some(vec![2.0f64]);
+    /// state.cost = 5.0;
+    ///
+    /// // Calling update
+    /// state.update();
+    ///
+    /// // Check if update was successful
+    /// assert_eq!(state.best_param.as_ref().unwrap()[0], 2.0f64);
+    /// assert_eq!(state.best_cost.to_ne_bytes(), 5.0f64.to_ne_bytes());
+    /// assert!(state.is_best());
+    ///
+    /// // Add the current parameter vector to the front
+    /// let mut state = state.objectives(vec![1.0, 2.0]);
+    /// state.update();
+    /// assert_eq!(state.front, vec![(vec![2.0f64], vec![1.0, 2.0])]);
+    /// ```
+    fn update(&mut self) {
+        if let (Some(param), Some(objectives)) = (self.param.as_ref(), self.objectives.as_ref()) {
+            let (param, objectives) = (param.clone(), objectives.clone());
+            self.insert_into_front(param, objectives);
+        }
+
+        // Same acceptance rule as in `IterState`: strictly better, or both infinite with the same
+        // sign for solvers which do not compute the cost function.
+        if self.cost < self.best_cost
+            || (self.cost.is_infinite()
+                && self.best_cost.is_infinite()
+                && self.cost.is_sign_positive() == self.best_cost.is_sign_positive())
+        {
+            if let Some(param) = self.param.as_ref().cloned() {
+                std::mem::swap(&mut self.prev_best_param, &mut self.best_param);
+                self.best_param = Some(param);
+            }
+            std::mem::swap(&mut self.prev_best_cost, &mut self.best_cost);
+            self.best_cost = self.cost;
+            self.last_best_iter = self.iter;
+        }
+    }
+
+    /// Returns a reference to the current parameter vector
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::{ParetoState, State};
+    /// # let state: ParetoState<Vec<f64>, f64> = ParetoState::new().param(vec![1.0, 2.0]);
+    /// let param = state.get_param();  // Option<&P>
+    /// # assert_eq!(param.unwrap(), &vec![1.0, 2.0]);
+    /// ```
+    fn get_param(&self) -> Option<&P> {
+        self.param.as_ref()
+    }
+
+    /// Returns a reference to the current best parameter vector
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::{ParetoState, State};
+    /// # let mut state: ParetoState<Vec<f64>, f64> = ParetoState::new();
+    /// # state.best_param = Some(vec![1.0, 2.0]);
+    /// let best_param = state.get_best_param();  // Option<&P>
+    /// # assert_eq!(best_param.unwrap(), &vec![1.0, 2.0]);
+    /// ```
+    fn get_best_param(&self) -> Option<&P> {
+        self.best_param.as_ref()
+    }
+
+    /// Sets the termination status to [`Terminated`](`TerminationStatus::Terminated`) with the given reason
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::{ParetoState, State, TerminationReason, TerminationStatus};
+    /// # let state: ParetoState<Vec<f64>, f64> = ParetoState::new();
+    /// let state = state.terminate_with(TerminationReason::MaxItersReached);
+    /// # assert_eq!(state.termination_status, TerminationStatus::Terminated(TerminationReason::MaxItersReached));
+    /// ```
+    fn terminate_with(mut self, reason: TerminationReason) -> Self {
+        self.termination_status = TerminationStatus::Terminated(reason);
+        self
+    }
+
+    /// Sets the time required so far.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # extern crate web_time;
+    /// # use web_time::Duration;
+    /// # use argmin::core::{ParetoState, State};
+    /// # let mut state: ParetoState<Vec<f64>, f64> = ParetoState::new();
+    /// let state = state.time(Some(Duration::from_nanos(12)));
+    /// # assert_eq!(state.time.unwrap(), Duration::from_nanos(12));
+    /// ```
+    fn time(&mut self, time: Option<Duration>) -> &mut Self {
+        self.time = time;
+        self
+    }
+
+    /// Returns current cost function value.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::{ParetoState, State};
+    /// # let mut state: ParetoState<Vec<f64>, f64> = ParetoState::new();
+    /// # state.cost = 12.0;
+    /// let cost = state.get_cost();
+    /// # assert_eq!(cost.to_ne_bytes(), 12.0f64.to_ne_bytes());
+    /// ```
+    fn get_cost(&self) -> Self::Float {
+        self.cost
+    }
+
+    /// Returns current best cost function value.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::{ParetoState, State};
+    /// # let mut state: ParetoState<Vec<f64>, f64> = ParetoState::new();
+    /// # state.best_cost = 12.0;
+    /// let best_cost = state.get_best_cost();
+    /// # assert_eq!(best_cost.to_ne_bytes(), 12.0f64.to_ne_bytes());
+    /// ```
+    fn get_best_cost(&self) -> Self::Float {
+        self.best_cost
+    }
+
+    /// Returns target cost function value.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::{ParetoState, State};
+    /// # let mut state: ParetoState<Vec<f64>, f64> = ParetoState::new();
+    /// # state.target_cost = 12.0;
+    /// let target_cost = state.get_target_cost();
+    /// # assert_eq!(target_cost.to_ne_bytes(), 12.0f64.to_ne_bytes());
+    /// ```
+    fn get_target_cost(&self) -> Self::Float {
+        self.target_cost
+    }
+
+    /// Returns current number of iterations.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::{ParetoState, State};
+    /// # let mut state: ParetoState<Vec<f64>, f64> = ParetoState::new();
+    /// # state.iter = 12;
+    /// let iter = state.get_iter();
+    /// # assert_eq!(iter, 12);
+    /// ```
+    fn get_iter(&self) -> u64 {
+        self.iter
+    }
+
+    /// Returns iteration number of last best parameter vector
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::{ParetoState, State};
+    /// # let mut state: ParetoState<Vec<f64>, f64> = ParetoState::new();
+    /// # state.last_best_iter = 12;
+    /// let last_best_iter = state.get_last_best_iter();
+    /// # assert_eq!(last_best_iter, 12);
+    /// ```
+    fn get_last_best_iter(&self) -> u64 {
+        self.last_best_iter
+    }
+
+    /// Returns the maximum number of iterations.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::{ParetoState, State};
+    /// # let mut state: ParetoState<Vec<f64>, f64> = ParetoState::new();
+    /// # state.max_iters = 12;
+    /// let max_iters = state.get_max_iters();
+    /// # assert_eq!(max_iters, 12);
+    /// ```
+    fn get_max_iters(&self) -> u64 {
+        self.max_iters
+    }
+
+    /// Returns the termination status.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::{ParetoState, State, TerminationStatus};
+    /// # let state: ParetoState<Vec<f64>, f64> = ParetoState::new();
+    /// let termination_status = state.get_termination_status();
+    /// # assert_eq!(*termination_status, TerminationStatus::NotTerminated);
+    /// ```
+    fn get_termination_status(&self) -> &TerminationStatus {
+        &self.termination_status
+    }
+
+    /// Returns the termination reason if terminated, otherwise None.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::{ParetoState, State};
+    /// # let state: ParetoState<Vec<f64>, f64> = ParetoState::new();
+    /// let termination_reason = state.get_termination_reason();
+    /// # assert_eq!(termination_reason, None);
+    /// ```
+    fn get_termination_reason(&self) -> Option<&TerminationReason> {
+        match &self.termination_status {
+            TerminationStatus::Terminated(reason) => Some(reason),
+            TerminationStatus::NotTerminated => None,
+        }
+    }
+
+    /// Enables the archive of the best parameter vectors
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::{Archive, ParetoState, State};
+    /// # let mut state: ParetoState<Vec<f64>, f64> = ParetoState::new();
+    /// state.set_archive(Archive::new(10));
+    /// # assert_eq!(state.get_archive().unwrap().capacity(), 10);
+    /// ```
+    fn set_archive(&mut self, archive: Archive<P, F>) {
+        self.archive = Some(archive);
+    }
+
+    /// Returns a reference to the archive of the best parameter vectors (if enabled)
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::{ParetoState, State};
+    /// # let state: ParetoState<Vec<f64>, f64> = ParetoState::new();
+    /// let archive = state.get_archive();
+    /// # assert!(archive.is_none());
+    /// ```
+    fn get_archive(&self) -> Option<&Archive<P, F>> {
+        self.archive.as_ref()
+    }
+
+    /// Returns a mutable reference to the archive of the best parameter vectors (if enabled)
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::{Archive, ParetoState, State};
+    /// # let mut state: ParetoState<Vec<f64>, f64> = ParetoState::new();
+    /// # state.set_archive(Archive::new(10));
+    /// let archive = state.get_archive_mut().unwrap();
+    /// archive.insert(vec![1.0], 2.0);
+    /// # assert_eq!(state.get_archive().unwrap().len(), 1);
+    /// ```
+    fn get_archive_mut(&mut self) -> Option<&mut Archive<P, F>> {
+        self.archive.as_mut()
+    }
+
+    /// Returns the time elapsed since the start of the optimization.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # extern crate web_time;
+    /// # use web_time::Duration;
+    /// # use argmin::core::{ParetoState, State};
+    /// # let state: ParetoState<Vec<f64>, f64> = ParetoState::new();
+    /// let time = state.get_time();
+    /// # assert_eq!(time.unwrap(), Duration::ZERO);
+    /// ```
+    fn get_time(&self) -> Option<Duration> {
+        self.time
+    }
+
+    /// Increments the number of iterations by one
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::{ParetoState, State};
+    /// # let mut state: ParetoState<Vec<f64>, f64> = ParetoState::new();
+    /// state.increment_iter();
+    /// # assert_eq!(state.iter, 1);
+    /// ```
+    fn increment_iter(&mut self) {
+        self.iter += 1;
+    }
+
+    /// Set all function evaluation counts to the evaluation counts of another `Problem`.
+    ///
+    /// ```
+    /// # use std::collections::HashMap;
+    /// # use argmin::core::{Problem, ParetoState, State};
+    /// # let mut state: ParetoState<Vec<f64>, f64> = ParetoState::new().counting(true);
+    /// # #[derive(Eq, PartialEq, Debug)]
+    /// # struct UserDefinedProblem {};
+    /// #
+    /// # let mut problem = Problem::new(UserDefinedProblem {});
+    /// # problem.counts.insert("cost_count", 10u64);
+    /// state.func_counts(&problem);
+    /// # assert_eq!(state.counts["cost_count"], 10);
+    /// ```
+    fn func_counts<O>(&mut self, problem: &Problem<O>) {
+        if self.counting_enabled {
+            for (k, &v) in problem.counts.iter() {
+                let count = self.counts.entry(k.to_string()).or_insert(0);
+                *count = v
+            }
+        }
+    }
+
+    /// Returns function evaluation counts
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use std::collections::HashMap;
+    /// # use argmin::core::{ParetoState, State};
+    /// # let mut state: ParetoState<Vec<f64>, f64> = ParetoState::new();
+    /// # state.counts.insert("cost_count".to_string(), 10u64);
+    /// let counts = state.get_func_counts();
+    /// # assert_eq!(counts["cost_count"], 10);
+    /// ```
+    fn get_func_counts(&self) -> &HashMap<String, u64> {
+        &self.counts
+    }
+
+    /// Returns whether the current parameter vector is also the best parameter vector found so
+    /// far.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::{ParetoState, State};
+    /// # let mut state: ParetoState<Vec<f64>, f64> = ParetoState::new();
+    /// # state.last_best_iter = 12;
+    /// # state.iter = 12;
+    /// let is_best = state.is_best();
+    /// # assert!(is_best);
+    /// ```
+    fn is_best(&self) -> bool {
+        self.last_best_iter == self.iter
+    }
+}
+
+impl<P, F> CheckNumerics for ParetoState<P, F>
+where
+    P: ArgminAllFinite,
+    F: ArgminFloat,
+{
+    /// Checks the cost function value and the objective values for NaN and the current parameter
+    /// vector for NaN and infinite values.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::{CheckNumerics, ParetoState, State};
+    /// let state: ParetoState<Vec<f64>, f64> = ParetoState::new()
+    ///     .param(vec![1.0, 2.0])
+    ///     .objectives(vec![1.0, f64::NAN]);
+    ///
+    /// assert_eq!(state.invalid_numeric_value(), Some("objectives"));
+    /// ```
+    fn invalid_numeric_value(&self) -> Option<&'static str> {
+        if self.cost.is_nan() {
+            Some("cost")
+        } else if !self.param.as_ref().map(|p| p.all_finite()).unwrap_or(true) {
+            Some("param")
+        } else if self
+            .objectives
+            .as_ref()
+            .map(|o| o.iter().any(|c| c.is_nan()))
+            .unwrap_or(false)
+        {
+            Some("objectives")
+        } else {
+            None
+        }
+    }
+}
+
+/// Returns true if the objective values `a` dominate `b`, i.e. if `a` is not worse in any
+/// objective and strictly better in at least one.
+fn dominates<F: ArgminFloat>(a: &[F], b: &[F]) -> bool {
+    a.len() == b.len()
+        && a.iter().zip(b).all(|(x, y)| x <= y)
+        && a.iter().zip(b).any(|(x, y)| x < y)
+}
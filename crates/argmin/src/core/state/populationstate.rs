@@ -5,7 +5,10 @@
 // http://opensource.org/licenses/MIT>, at your option. This file may not be
 // copied, modified, or distributed except according to those terms.
 
-use crate::core::{ArgminFloat, Problem, State, TerminationReason, TerminationStatus};
+use crate::core::{
+    Archive, ArgminFloat, CheckNumerics, Problem, State, TerminationReason, TerminationStatus,
+};
+use argmin_math::ArgminAllFinite;
 #[cfg(feature = "serde1")]
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
@@ -65,6 +68,11 @@ pub struct PopulationState<P, F> {
     pub time: Option<Duration>,
     /// Status of optimization execution
     pub termination_status: TerminationStatus,
+    /// Archive of the best parameter vectors (if enabled)
+    // An explicit default function avoids that `P: Default` is required for deserialization,
+    // which individuals such as `Particle` do not implement.
+    #[cfg_attr(feature = "serde1", serde(default = "Option::default"))]
+    pub archive: Option<Archive<P, F>>,
 }
 
 impl<P, F> PopulationState<P, F>
@@ -483,6 +491,7 @@ where
     /// # assert_eq!(state.counts.len(), 0);
     /// # assert_eq!(state.time.unwrap(), Duration::ZERO);
     /// # assert_eq!(state.termination_status, TerminationStatus::NotTerminated);
+    /// # assert!(state.archive.is_none());
     /// ```
     fn new() -> Self {
         PopulationState {
@@ -503,6 +512,7 @@ where
             counting_enabled: false,
             time: Some(Duration::ZERO),
             termination_status: TerminationStatus::NotTerminated,
+            archive: None,
         }
     }
 
@@ -561,10 +571,14 @@ where
                 && self.best_cost.is_infinite()
                 && self.cost.is_sign_positive() == self.best_cost.is_sign_positive())
         {
-            // If there is no individual, then also don't set the best individual.
-            if let Some(individual) = self.individual.as_ref().cloned() {
+            // If there is no individual, then also don't set the best individual. The previous
+            // best individual is overwritten, therefore its allocation is reused.
+            if let Some(individual) = self.individual.as_ref() {
                 std::mem::swap(&mut self.prev_best_individual, &mut self.best_individual);
-                self.best_individual = Some(individual);
+                match self.best_individual.as_mut() {
+                    Some(best_individual) => best_individual.clone_from(individual),
+                    None => self.best_individual = Some(individual.clone()),
+                }
             }
             std::mem::swap(&mut self.prev_best_cost, &mut self.best_cost);
             self.best_cost = self.cost;
@@ -764,6 +778,50 @@ where
         }
     }
 
+    /// Enables the archive of the best parameter vectors
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::{Archive, PopulationState, State};
+    /// # let mut state: PopulationState<Vec<f64>, f64> = PopulationState::new();
+    /// state.set_archive(Archive::new(10));
+    /// # assert_eq!(state.get_archive().unwrap().capacity(), 10);
+    /// ```
+    fn set_archive(&mut self, archive: Archive<P, F>) {
+        self.archive = Some(archive);
+    }
+
+    /// Returns a reference to the archive of the best parameter vectors (if enabled)
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::{PopulationState, State};
+    /// # let state: PopulationState<Vec<f64>, f64> = PopulationState::new();
+    /// let archive = state.get_archive();
+    /// # assert!(archive.is_none());
+    /// ```
+    fn get_archive(&self) -> Option<&Archive<P, F>> {
+        self.archive.as_ref()
+    }
+
+    /// Returns a mutable reference to the archive of the best parameter vectors (if enabled)
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::{Archive, PopulationState, State};
+    /// # let mut state: PopulationState<Vec<f64>, f64> = PopulationState::new();
+    /// # state.set_archive(Archive::new(10));
+    /// let archive = state.get_archive_mut().unwrap();
+    /// archive.insert(vec![1.0], 2.0);
+    /// # assert_eq!(state.get_archive().unwrap().len(), 1);
+    /// ```
+    fn get_archive_mut(&mut self) -> Option<&mut Archive<P, F>> {
+        self.archive.as_mut()
+    }
+
     /// Returns the time elapsed since the start of the optimization.
     ///
     /// # Example
@@ -866,4 +924,45 @@ where
     }
 }
 
+impl<P, F> CheckNumerics for PopulationState<P, F>
+where
+    P: ArgminAllFinite,
+    F: ArgminFloat,
+{
+    /// Checks the cost function value for NaN and the current individual as well as the
+    /// population for NaN and infinite values.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::{CheckNumerics, PopulationState, State};
+    /// let state: PopulationState<Vec<f64>, f64> = PopulationState::new()
+    ///     .individual(vec![1.0, 2.0])
+    ///     .population(vec![vec![1.0, 2.0], vec![f64::INFINITY, 1.0]]);
+    ///
+    /// assert_eq!(state.invalid_numeric_value(), Some("population"));
+    /// ```
+    fn invalid_numeric_value(&self) -> Option<&'static str> {
+        if self.cost.is_nan() {
+            Some("cost")
+        } else if !self
+            .individual
+            .as_ref()
+            .map(|p| p.all_finite())
+            .unwrap_or(true)
+        {
+            Some("individual")
+        } else if !self
+            .population
+            .as_ref()
+            .map(|pop| pop.iter().all(|p| p.all_finite()))
+            .unwrap_or(true)
+        {
+            Some("population")
+        } else {
+            None
+        }
+    }
+}
+
 // TODO: Tests? Actually doc tests should already cover everything.
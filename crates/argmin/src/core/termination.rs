@@ -33,6 +33,7 @@ impl TerminationStatus {
     /// assert!(TerminationStatus::Terminated(TerminationReason::Interrupt).terminated());
     /// assert!(TerminationStatus::Terminated(TerminationReason::Timeout).terminated());
     /// assert!(TerminationStatus::Terminated(TerminationReason::SolverExit("Exit reason".to_string())).terminated());
+    /// assert!(TerminationStatus::Terminated(TerminationReason::InvalidNumericValue("cost".to_string())).terminated());
     /// ```
     pub fn terminated(&self) -> bool {
         matches!(self, TerminationStatus::Terminated(_))
@@ -42,7 +43,7 @@ impl TerminationStatus {
 impl std::fmt::Display for TerminationStatus {
     fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
         match self {
-            TerminationStatus::Terminated(reason) => f.write_str(reason.text()),
+            TerminationStatus::Terminated(reason) => write!(f, "{reason}"),
             TerminationStatus::NotTerminated => f.write_str("Running"),
         }
     }
@@ -50,6 +51,7 @@ impl std::fmt::Display for TerminationStatus {
 
 /// Reasons for optimization algorithms to stop
 #[derive(Debug, Clone, Eq, PartialEq, Hash)]
+#[non_exhaustive]
 #[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
 pub enum TerminationReason {
     /// Reached maximum number of iterations
@@ -63,7 +65,40 @@ pub enum TerminationReason {
     /// Timeout reached
     Timeout,
     /// Solver exit with given reason
+    ///
+    /// Meant for custom solvers. The solvers shipped with argmin use the dedicated variants
+    /// below.
     SolverExit(String),
+    /// A NaN or infinite value was encountered in the given quantity (for instance `"cost"`,
+    /// `"param"` or `"gradient"`)
+    InvalidNumericValue(String),
+    /// The number of consecutive iterations without an accepted move exceeded its limit
+    StallAccepted {
+        /// Number of consecutive iterations without an accepted move
+        iters: u64,
+    },
+    /// The number of consecutive iterations without a new best solution exceeded its limit
+    StallBest {
+        /// Number of consecutive iterations without a new best solution
+        iters: u64,
+    },
+    /// The line search of the solver failed with the given error message
+    LineSearchFailed(String),
+    /// A replayed evaluation differed from the recorded one
+    ReplayDiverged {
+        /// Recorded iteration number of the diverging evaluation
+        iter: u64,
+    },
+    /// All entries of a replay log were evaluated
+    ReplayEnded,
+    /// The directional derivative along the search direction vanished at the starting point of a
+    /// line search, hence no step was taken
+    StationaryDirection,
+    /// All moves to neighboring candidates are forbidden, for instance by a tabu list
+    NoAdmissibleMove,
+    /// Every descent direction leaves the bounds of the parameter vector immediately, i.e. the
+    /// parameter vector is stationary within the bounds
+    NoFeasibleDirection,
 }
 
 impl TerminationReason {
@@ -98,6 +133,42 @@ impl TerminationReason {
     ///     TerminationReason::SolverExit("Aborted".to_string()).text(),
     ///     "Aborted"
     /// );
+    /// assert_eq!(
+    ///     TerminationReason::InvalidNumericValue("cost".to_string()).text(),
+    ///     "Invalid numeric value (NaN or Inf)"
+    /// );
+    /// assert_eq!(
+    ///     TerminationReason::StallAccepted { iters: 100 }.text(),
+    ///     "AcceptedStallIterExceeded"
+    /// );
+    /// assert_eq!(
+    ///     TerminationReason::StallBest { iters: 100 }.text(),
+    ///     "BestStallIterExceeded"
+    /// );
+    /// assert_eq!(
+    ///     TerminationReason::LineSearchFailed("error".to_string()).text(),
+    ///     "Line search failed"
+    /// );
+    /// assert_eq!(
+    ///     TerminationReason::ReplayDiverged { iter: 3 }.text(),
+    ///     "Replay diverged"
+    /// );
+    /// assert_eq!(
+    ///     TerminationReason::ReplayEnded.text(),
+    ///     "End of replay log"
+    /// );
+    /// assert_eq!(
+    ///     TerminationReason::StationaryDirection.text(),
+    ///     "Stationary along search direction"
+    /// );
+    /// assert_eq!(
+    ///     TerminationReason::NoAdmissibleMove.text(),
+    ///     "No admissible move"
+    /// );
+    /// assert_eq!(
+    ///     TerminationReason::NoFeasibleDirection.text(),
+    ///     "No feasible descent direction within bounds"
+    /// );
     /// ```
     pub fn text(&self) -> &str {
         match self {
@@ -107,13 +178,33 @@ impl TerminationReason {
             TerminationReason::SolverConverged => "Solver converged",
             TerminationReason::Timeout => "Timeout reached",
             TerminationReason::SolverExit(reason) => reason.as_ref(),
+            TerminationReason::InvalidNumericValue(_) => "Invalid numeric value (NaN or Inf)",
+            TerminationReason::StallAccepted { .. } => "AcceptedStallIterExceeded",
+            TerminationReason::StallBest { .. } => "BestStallIterExceeded",
+            TerminationReason::LineSearchFailed(_) => "Line search failed",
+            TerminationReason::ReplayDiverged { .. } => "Replay diverged",
+            TerminationReason::ReplayEnded => "End of replay log",
+            TerminationReason::StationaryDirection => "Stationary along search direction",
+            TerminationReason::NoAdmissibleMove => "No admissible move",
+            TerminationReason::NoFeasibleDirection => "No feasible descent direction within bounds",
         }
     }
 }
 
 impl std::fmt::Display for TerminationReason {
     fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
-        write!(f, "{}", self.text())
+        match self {
+            TerminationReason::InvalidNumericValue(quantity) => {
+                write!(f, "{} in {}", self.text(), quantity)
+            }
+            TerminationReason::LineSearchFailed(error) => {
+                write!(f, "Line search terminated with: '{error}'")
+            }
+            TerminationReason::ReplayDiverged { iter } => {
+                write!(f, "{} in recorded iteration {}", self.text(), iter)
+            }
+            _ => write!(f, "{}", self.text()),
+        }
     }
 }
 
@@ -128,4 +219,56 @@ mod tests {
     use super::*;
 
     send_sync_test!(termination_reason, TerminationReason);
+
+    #[test]
+    fn test_display() {
+        assert_eq!(
+            format!("{}", TerminationReason::MaxItersReached),
+            "Maximum number of iterations reached"
+        );
+        assert_eq!(
+            format!(
+                "{}",
+                TerminationStatus::Terminated(TerminationReason::InvalidNumericValue(
+                    "gradient".to_string()
+                ))
+            ),
+            "Invalid numeric value (NaN or Inf) in gradient"
+        );
+        assert_eq!(
+            format!("{}", TerminationReason::StallAccepted { iters: 11 }),
+            "AcceptedStallIterExceeded"
+        );
+        assert_eq!(
+            format!("{}", TerminationReason::StallBest { iters: 11 }),
+            "BestStallIterExceeded"
+        );
+        assert_eq!(
+            format!(
+                "{}",
+                TerminationReason::LineSearchFailed("Condition violated".to_string())
+            ),
+            "Line search terminated with: 'Condition violated'"
+        );
+        assert_eq!(
+            format!("{}", TerminationReason::ReplayDiverged { iter: 4 }),
+            "Replay diverged in recorded iteration 4"
+        );
+        assert_eq!(
+            format!("{}", TerminationReason::ReplayEnded),
+            "End of replay log"
+        );
+        assert_eq!(
+            format!("{}", TerminationReason::StationaryDirection),
+            "Stationary along search direction"
+        );
+        assert_eq!(
+            format!("{}", TerminationReason::NoAdmissibleMove),
+            "No admissible move"
+        );
+        assert_eq!(
+            format!("{}", TerminationReason::NoFeasibleDirection),
+            "No feasible descent direction within bounds"
+        );
+    }
 }
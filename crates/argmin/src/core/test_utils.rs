@@ -303,6 +303,70 @@ impl Gradient for TestSparseProblem {
     }
 }
 
+/// Real-valued quadratic function of complex parameters useful for testing
+///
+/// cost = z^H A z - 2 Re(b^H z)
+///
+/// with the Hermitian positive definite matrix A = [[3, 1 - i], [1 + i, 2]] and
+/// b = [1 + 2i, -1 + i]. The minimum cost = -2 is at z = [0.5 + 0.5i, -0.5].
+///
+/// The gradient 2 (A z - b) holds the derivatives with respect to the real and imaginary parts of
+/// the parameters as real and imaginary parts (twice the Wirtinger derivative with respect to the
+/// complex conjugate of z).
+///
+/// Implements [`CostFunction`] and [`Gradient`].
+#[cfg(test)]
+#[derive(Clone, Copy, Default, Debug, Eq, PartialEq, Hash)]
+pub(crate) struct TestComplexProblem {}
+
+#[cfg(test)]
+impl TestComplexProblem {
+    fn a() -> [[num_complex::Complex64; 2]; 2] {
+        use num_complex::Complex64 as C;
+        [
+            [C::new(3.0, 0.0), C::new(1.0, -1.0)],
+            [C::new(1.0, 1.0), C::new(2.0, 0.0)],
+        ]
+    }
+
+    fn b() -> [num_complex::Complex64; 2] {
+        use num_complex::Complex64 as C;
+        [C::new(1.0, 2.0), C::new(-1.0, 1.0)]
+    }
+
+    fn residual(param: &[num_complex::Complex64]) -> Vec<num_complex::Complex64> {
+        let (a, b) = (Self::a(), Self::b());
+        (0..2)
+            .map(|i| a[i][0] * param[0] + a[i][1] * param[1] - b[i])
+            .collect()
+    }
+}
+
+#[cfg(test)]
+impl CostFunction for TestComplexProblem {
+    type Param = Vec<num_complex::Complex64>;
+    type Output = f64;
+
+    fn cost(&self, param: &Self::Param) -> Result<Self::Output, Error> {
+        // z^H A z - 2 Re(b^H z) = Re(z^H (A z - b)) - Re(b^H z)
+        let r = Self::residual(param);
+        let b = Self::b();
+        Ok((0..2)
+            .map(|i| (param[i].conj() * r[i]).re - (b[i].conj() * param[i]).re)
+            .sum())
+    }
+}
+
+#[cfg(test)]
+impl Gradient for TestComplexProblem {
+    type Param = Vec<num_complex::Complex64>;
+    type Gradient = Vec<num_complex::Complex64>;
+
+    fn gradient(&self, param: &Self::Param) -> Result<Self::Gradient, Error> {
+        Ok(Self::residual(param).into_iter().map(|r| 2.0 * r).collect())
+    }
+}
+
 /// A (non-working) solver useful for testing
 ///
 /// Implements the [`Solver`] trait.
@@ -0,0 +1,226 @@
+// Copyright 2018-2024 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use crate::core::{CostFunction, Error, Gradient};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{mpsc, Arc};
+use std::time::Duration;
+
+/// Wraps a problem and limits the wall-clock time of each cost function evaluation
+///
+/// Every evaluation of the cost function is run on a separate worker thread. If it does not
+/// finish within the given timeout, the evaluation is abandoned and the configured penalty cost
+/// is returned instead, which allows the solver to continue. This is useful for simulation-based
+/// objectives which occasionally hang.
+///
+/// Abandoned evaluations are not cancelled: the worker thread keeps running until the
+/// evaluation returns and its result is discarded. Errors returned by the cost function within
+/// the timeout are passed on unchanged. Gradients are evaluated on the calling thread without a
+/// timeout.
+///
+/// The number of timed out evaluations can be queried via
+/// [`timeouts`](`TimeoutProblem::timeouts`). Clones of a `TimeoutProblem` share this counter.
+///
+/// # Example
+///
+/// ```
+/// use argmin::core::{Executor, TimeoutProblem};
+/// use argmin::solver::neldermead::NelderMead;
+/// # use argmin::core::{CostFunction, Error};
+/// use std::time::Duration;
+/// #
+/// # struct Simulation {}
+/// #
+/// # impl CostFunction for Simulation {
+/// #     type Param = Vec<f64>;
+/// #     type Output = f64;
+/// #     fn cost(&self, p: &Self::Param) -> Result<Self::Output, Error> {
+/// #         Ok(p[0].powi(2) + p[1].powi(2))
+/// #     }
+/// # }
+///
+/// # fn main() -> Result<(), Error> {
+/// // Evaluations taking longer than one second are assigned a cost of `1e10`.
+/// let problem = TimeoutProblem::new(Simulation {}, Duration::from_secs(1), 1e10)?;
+/// let timeouts = problem.clone();
+///
+/// let solver = NelderMead::new(vec![vec![1.0, 1.0], vec![2.0, 1.0], vec![1.0, 2.0]]);
+/// let res = Executor::new(problem, solver)
+///     .configure(|state| state.max_iters(10))
+///     .run()?;
+///
+/// assert_eq!(timeouts.timeouts(), 0);
+/// # Ok(())
+/// # }
+/// ```
+pub struct TimeoutProblem<O: CostFunction> {
+    /// Wrapped problem
+    problem: Arc<O>,
+    /// Maximum wall-clock time of a cost function evaluation
+    timeout: Duration,
+    /// Cost returned for evaluations which exceed `timeout`
+    penalty: O::Output,
+    /// Number of evaluations which exceeded `timeout`
+    timeouts: Arc<AtomicU64>,
+}
+
+impl<O> Clone for TimeoutProblem<O>
+where
+    O: CostFunction,
+    O::Output: Clone,
+{
+    fn clone(&self) -> Self {
+        TimeoutProblem {
+            problem: Arc::clone(&self.problem),
+            timeout: self.timeout,
+            penalty: self.penalty.clone(),
+            timeouts: Arc::clone(&self.timeouts),
+        }
+    }
+}
+
+impl<O: CostFunction> TimeoutProblem<O> {
+    /// Construct a new instance of `TimeoutProblem`
+    ///
+    /// Evaluations of the cost function which take longer than `timeout` return `penalty`.
+    /// `timeout` must be larger than zero.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::{Error, TimeoutProblem};
+    /// # use argmin::core::test_utils::TestProblem;
+    /// # use std::time::Duration;
+    /// # fn main() -> Result<(), Error> {
+    /// let problem = TimeoutProblem::new(TestProblem::new(), Duration::from_secs(10), f64::INFINITY)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn new(problem: O, timeout: Duration, penalty: O::Output) -> Result<Self, Error> {
+        if timeout.is_zero() {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "`TimeoutProblem`: timeout must be larger than zero."
+            ));
+        }
+        Ok(TimeoutProblem {
+            problem: Arc::new(problem),
+            timeout,
+            penalty,
+            timeouts: Arc::new(AtomicU64::new(0)),
+        })
+    }
+
+    /// Returns a reference to the wrapped problem
+    pub fn problem(&self) -> &O {
+        &self.problem
+    }
+
+    /// Returns the number of cost function evaluations which exceeded the timeout
+    pub fn timeouts(&self) -> u64 {
+        self.timeouts.load(Ordering::Relaxed)
+    }
+}
+
+impl<O> CostFunction for TimeoutProblem<O>
+where
+    O: CostFunction + Send + Sync + 'static,
+    O::Param: Clone + Send + 'static,
+    O::Output: Clone + Send + 'static,
+{
+    type Param = O::Param;
+    type Output = O::Output;
+
+    fn cost(&self, param: &Self::Param) -> Result<Self::Output, Error> {
+        let (tx, rx) = mpsc::channel();
+        let problem = Arc::clone(&self.problem);
+        let param = param.clone();
+        std::thread::Builder::new()
+            .name("argmin-evaluation".to_string())
+            .spawn(move || {
+                // The receiver is gone if the evaluation timed out.
+                let _ = tx.send(problem.cost(&param));
+            })?;
+        match rx.recv_timeout(self.timeout) {
+            Ok(cost) => cost,
+            Err(mpsc::RecvTimeoutError::Timeout) => {
+                self.timeouts.fetch_add(1, Ordering::Relaxed);
+                Ok(self.penalty.clone())
+            }
+            Err(mpsc::RecvTimeoutError::Disconnected) => Err(argmin_error!(
+                PotentialBug,
+                "`TimeoutProblem`: evaluation thread terminated without returning a result."
+            )),
+        }
+    }
+}
+
+impl<O> Gradient for TimeoutProblem<O>
+where
+    O: CostFunction + Gradient<Param = <O as CostFunction>::Param>,
+{
+    type Param = <O as CostFunction>::Param;
+    type Gradient = O::Gradient;
+
+    fn gradient(&self, param: &Self::Param) -> Result<Self::Gradient, Error> {
+        self.problem.gradient(param)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::ArgminError;
+
+    /// Problem which sleeps for `param` milliseconds and fails for negative parameters
+    struct Sleepy {}
+
+    impl CostFunction for Sleepy {
+        type Param = i64;
+        type Output = f64;
+
+        fn cost(&self, param: &Self::Param) -> Result<Self::Output, Error> {
+            if *param < 0 {
+                return Err(argmin_error!(InvalidParameter, "negative"));
+            }
+            std::thread::sleep(Duration::from_millis(*param as u64));
+            Ok(*param as f64)
+        }
+    }
+
+    #[test]
+    fn test_new() {
+        let res = TimeoutProblem::new(Sleepy {}, Duration::ZERO, f64::INFINITY);
+        assert_error!(
+            res,
+            ArgminError,
+            "Invalid parameter: \"`TimeoutProblem`: timeout must be larger than zero.\""
+        );
+    }
+
+    #[test]
+    fn test_timeout() {
+        let problem = TimeoutProblem::new(Sleepy {}, Duration::from_millis(200), 1e10).unwrap();
+        let shared = problem.clone();
+
+        assert_eq!(problem.cost(&0).unwrap().to_ne_bytes(), 0f64.to_ne_bytes());
+        assert_eq!(problem.timeouts(), 0);
+
+        assert_eq!(
+            problem.cost(&2000).unwrap().to_ne_bytes(),
+            1e10f64.to_ne_bytes()
+        );
+        assert_eq!(shared.timeouts(), 1);
+
+        assert_error!(
+            problem.cost(&-1),
+            ArgminError,
+            "Invalid parameter: \"negative\""
+        );
+        assert_eq!(shared.timeouts(), 1);
+    }
+}
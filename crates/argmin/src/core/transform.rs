@@ -0,0 +1,600 @@
+// Copyright 2018-2024 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+//! # Parameter transforms
+//!
+//! Transforms map an unconstrained parameter vector onto a constrained domain. Wrapping a problem
+//! in a [`TransformedProblem`] allows any unconstrained solver to be used on problems with
+//! positivity constraints ([`LogTransform`]), box constraints ([`LogitTransform`]) or simplex
+//! constraints ([`SoftmaxTransform`]). Transforms can be chained via [`Transform::then`].
+
+use crate::core::{ArgminFloat, CostFunction, Error, Gradient};
+#[cfg(feature = "serde1")]
+use serde::{Deserialize, Serialize};
+
+/// Defines a bijective mapping from an unconstrained space into a constrained space.
+///
+/// `forward` maps the unconstrained parameter vector `y` to the constrained parameter vector `x`,
+/// `inverse` computes `y` from `x` and `backward` computes the gradient with respect to `y` from
+/// the gradient with respect to `x` (the vector-Jacobian product `J(y)^T g`).
+///
+/// # Example
+///
+/// ```
+/// use argmin::core::{Error, Transform};
+///
+/// /// Shifts every parameter by a constant
+/// struct Shift(f64);
+///
+/// impl Transform<Vec<f64>> for Shift {
+///     fn forward(&self, param: &Vec<f64>) -> Result<Vec<f64>, Error> {
+///         Ok(param.iter().map(|y| y + self.0).collect())
+///     }
+///
+///     fn inverse(&self, param: &Vec<f64>) -> Result<Vec<f64>, Error> {
+///         Ok(param.iter().map(|x| x - self.0).collect())
+///     }
+///
+///     fn backward(&self, _param: &Vec<f64>, grad: &Vec<f64>) -> Result<Vec<f64>, Error> {
+///         Ok(grad.clone())
+///     }
+/// }
+/// ```
+pub trait Transform<P> {
+    /// Maps an unconstrained parameter vector onto the constrained domain
+    fn forward(&self, param: &P) -> Result<P, Error>;
+
+    /// Maps a parameter vector from the constrained domain into the unconstrained space
+    fn inverse(&self, param: &P) -> Result<P, Error>;
+
+    /// Computes the gradient with respect to the unconstrained parameter vector `param` given the
+    /// gradient `grad` with respect to the constrained parameter vector.
+    fn backward(&self, param: &P, grad: &P) -> Result<P, Error>;
+
+    /// Chains `self` with `next`. The resulting transform first applies `self` and then `next`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use argmin::core::{LogTransform, LogitTransform, Transform};
+    /// # use argmin::core::Error;
+    ///
+    /// # fn main() -> Result<(), Error> {
+    /// // Maps the real line onto (0, 1) via (0, inf)
+    /// let transform = LogTransform::new().then(LogitTransform::new(vec![0.0], vec![1.0])?);
+    /// # let x = transform.forward(&vec![0.0f64])?;
+    /// # assert!(x[0] > 0.0 && x[0] < 1.0);
+    /// # Ok(())
+    /// # }
+    /// ```
+    fn then<T>(self, next: T) -> Chain<Self, T>
+    where
+        Self: Sized,
+        T: Transform<P>,
+    {
+        Chain {
+            first: self,
+            second: next,
+        }
+    }
+}
+
+/// Composition of two transforms, created via [`Transform::then`].
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+pub struct Chain<T1, T2> {
+    /// Transform applied first
+    first: T1,
+    /// Transform applied second
+    second: T2,
+}
+
+impl<P, T1, T2> Transform<P> for Chain<T1, T2>
+where
+    T1: Transform<P>,
+    T2: Transform<P>,
+{
+    fn forward(&self, param: &P) -> Result<P, Error> {
+        self.second.forward(&self.first.forward(param)?)
+    }
+
+    fn inverse(&self, param: &P) -> Result<P, Error> {
+        self.first.inverse(&self.second.inverse(param)?)
+    }
+
+    fn backward(&self, param: &P, grad: &P) -> Result<P, Error> {
+        let intermediate = self.first.forward(param)?;
+        let grad = self.second.backward(&intermediate, grad)?;
+        self.first.backward(param, &grad)
+    }
+}
+
+/// Enforces positivity of all parameters via `x = exp(y)`.
+///
+/// The inverse `y = ln(x)` requires all constrained parameters to be strictly positive.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+pub struct LogTransform {}
+
+impl LogTransform {
+    /// Construct a new instance of `LogTransform`
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::LogTransform;
+    /// let transform = LogTransform::new();
+    /// ```
+    pub fn new() -> Self {
+        LogTransform {}
+    }
+}
+
+impl<F: ArgminFloat> Transform<Vec<F>> for LogTransform {
+    fn forward(&self, param: &Vec<F>) -> Result<Vec<F>, Error> {
+        Ok(param.iter().map(|y| y.exp()).collect())
+    }
+
+    fn inverse(&self, param: &Vec<F>) -> Result<Vec<F>, Error> {
+        param
+            .iter()
+            .map(|&x| {
+                if x > float!(0.0) {
+                    Ok(x.ln())
+                } else {
+                    Err(argmin_error!(
+                        InvalidParameter,
+                        "`LogTransform`: parameters must be strictly positive."
+                    ))
+                }
+            })
+            .collect()
+    }
+
+    fn backward(&self, param: &Vec<F>, grad: &Vec<F>) -> Result<Vec<F>, Error> {
+        check_lengths("LogTransform", param.len(), grad.len())?;
+        Ok(param
+            .iter()
+            .zip(grad.iter())
+            .map(|(y, g)| *g * y.exp())
+            .collect())
+    }
+}
+
+/// Enforces box constraints `lower < x < upper` via a scaled logistic sigmoid:
+/// `x = lower + (upper - lower) / (1 + exp(-y))`.
+///
+/// The inverse is the (scaled) logit function, which requires all constrained parameters to be
+/// strictly within the bounds.
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+pub struct LogitTransform<F> {
+    /// Lower bounds
+    lower: Vec<F>,
+    /// Upper bounds
+    upper: Vec<F>,
+}
+
+impl<F: ArgminFloat> LogitTransform<F> {
+    /// Construct a new instance of `LogitTransform`
+    ///
+    /// `lower` and `upper` must be of the same length and each lower bound must be strictly smaller
+    /// than the corresponding upper bound.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::{Error, LogitTransform};
+    /// # fn main() -> Result<(), Error> {
+    /// let transform = LogitTransform::new(vec![-1.0f64, 0.0], vec![1.0, 10.0])?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn new(lower: Vec<F>, upper: Vec<F>) -> Result<Self, Error> {
+        check_lengths("LogitTransform", lower.len(), upper.len())?;
+        if lower.iter().zip(upper.iter()).any(|(l, u)| l >= u) {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "`LogitTransform`: lower bounds must be smaller than upper bounds."
+            ));
+        }
+        Ok(LogitTransform { lower, upper })
+    }
+}
+
+impl<F: ArgminFloat> Transform<Vec<F>> for LogitTransform<F> {
+    fn forward(&self, param: &Vec<F>) -> Result<Vec<F>, Error> {
+        check_lengths("LogitTransform", self.lower.len(), param.len())?;
+        Ok(param
+            .iter()
+            .zip(self.lower.iter().zip(self.upper.iter()))
+            .map(|(&y, (&l, &u))| l + (u - l) * sigmoid(y))
+            .collect())
+    }
+
+    fn inverse(&self, param: &Vec<F>) -> Result<Vec<F>, Error> {
+        check_lengths("LogitTransform", self.lower.len(), param.len())?;
+        param
+            .iter()
+            .zip(self.lower.iter().zip(self.upper.iter()))
+            .map(|(&x, (&l, &u))| {
+                if x > l && x < u {
+                    let s = (x - l) / (u - l);
+                    Ok((s / (float!(1.0) - s)).ln())
+                } else {
+                    Err(argmin_error!(
+                        InvalidParameter,
+                        "`LogitTransform`: parameters must be strictly within the bounds."
+                    ))
+                }
+            })
+            .collect()
+    }
+
+    fn backward(&self, param: &Vec<F>, grad: &Vec<F>) -> Result<Vec<F>, Error> {
+        check_lengths("LogitTransform", self.lower.len(), param.len())?;
+        check_lengths("LogitTransform", param.len(), grad.len())?;
+        Ok(param
+            .iter()
+            .zip(grad.iter())
+            .zip(self.lower.iter().zip(self.upper.iter()))
+            .map(|((&y, &g), (&l, &u))| {
+                let s = sigmoid(y);
+                g * (u - l) * s * (float!(1.0) - s)
+            })
+            .collect())
+    }
+}
+
+/// Maps parameters onto the probability simplex (all parameters positive and summing up to one)
+/// via `x = softmax(y)`.
+///
+/// Since the softmax is invariant to adding a constant to all unconstrained parameters, the
+/// inverse returns the centered logarithm `y_i = ln(x_i) - mean(ln(x))`.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+pub struct SoftmaxTransform {}
+
+impl SoftmaxTransform {
+    /// Construct a new instance of `SoftmaxTransform`
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::SoftmaxTransform;
+    /// let transform = SoftmaxTransform::new();
+    /// ```
+    pub fn new() -> Self {
+        SoftmaxTransform {}
+    }
+}
+
+impl<F: ArgminFloat> Transform<Vec<F>> for SoftmaxTransform {
+    fn forward(&self, param: &Vec<F>) -> Result<Vec<F>, Error> {
+        if param.is_empty() {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "`SoftmaxTransform`: parameter vector must not be empty."
+            ));
+        }
+        // Subtract the maximum for numerical stability
+        let max = param.iter().fold(F::neg_infinity(), |acc, &y| acc.max(y));
+        let exps: Vec<F> = param.iter().map(|&y| (y - max).exp()).collect();
+        let sum = exps.iter().fold(float!(0.0), |acc, &e| acc + e);
+        Ok(exps.into_iter().map(|e| e / sum).collect())
+    }
+
+    fn inverse(&self, param: &Vec<F>) -> Result<Vec<F>, Error> {
+        if param.is_empty() || param.iter().any(|&x| x <= float!(0.0)) {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "`SoftmaxTransform`: parameters must be strictly positive."
+            ));
+        }
+        let logs: Vec<F> = param.iter().map(|x| x.ln()).collect();
+        let mean =
+            logs.iter().fold(float!(0.0), |acc, &l| acc + l) / F::from_usize(logs.len()).unwrap();
+        Ok(logs.into_iter().map(|l| l - mean).collect())
+    }
+
+    fn backward(&self, param: &Vec<F>, grad: &Vec<F>) -> Result<Vec<F>, Error> {
+        check_lengths("SoftmaxTransform", param.len(), grad.len())?;
+        // J^T g = s * (g - <s, g>) since the Jacobian of the softmax is diag(s) - s s^T
+        let s = self.forward(param)?;
+        let sg = s
+            .iter()
+            .zip(grad.iter())
+            .fold(float!(0.0), |acc, (&si, &gi)| acc + si * gi);
+        Ok(s.iter()
+            .zip(grad.iter())
+            .map(|(&si, &gi)| si * (gi - sg))
+            .collect())
+    }
+}
+
+/// Wraps a problem defined on a constrained domain such that it can be solved in the unconstrained
+/// space of a [`Transform`].
+///
+/// The cost function is evaluated at `transform.forward(y)` and the gradient is computed via the
+/// chain rule using `transform.backward(...)`. Initial parameter vectors need to be mapped into
+/// the unconstrained space with [`TransformedProblem::to_unconstrained`] and the final result can
+/// be mapped back with [`TransformedProblem::to_constrained`].
+///
+/// # Example
+///
+/// ```
+/// use argmin::core::{CostFunction, Error, Executor, Gradient, LogTransform, TransformedProblem};
+/// use argmin::solver::linesearch::MoreThuenteLineSearch;
+/// use argmin::solver::quasinewton::LBFGS;
+///
+/// /// (x - 2)^2 subject to x > 0
+/// struct Problem {}
+///
+/// impl CostFunction for Problem {
+///     type Param = Vec<f64>;
+///     type Output = f64;
+///
+///     fn cost(&self, p: &Self::Param) -> Result<Self::Output, Error> {
+///         Ok((p[0] - 2.0).powi(2))
+///     }
+/// }
+///
+/// impl Gradient for Problem {
+///     type Param = Vec<f64>;
+///     type Gradient = Vec<f64>;
+///
+///     fn gradient(&self, p: &Self::Param) -> Result<Self::Gradient, Error> {
+///         Ok(vec![2.0 * (p[0] - 2.0)])
+///     }
+/// }
+///
+/// # fn main() -> Result<(), Error> {
+/// let problem = TransformedProblem::new(Problem {}, LogTransform::new());
+/// let init_param = problem.to_unconstrained(&vec![0.5])?;
+///
+/// let solver = LBFGS::new(MoreThuenteLineSearch::new(), 5);
+/// let res = Executor::new(problem, solver)
+///     .configure(|state| state.param(init_param).max_iters(100))
+///     .run()?;
+///
+/// let problem = res.problem.problem.as_ref().unwrap();
+/// let best = problem.to_constrained(res.state.best_param.as_ref().unwrap())?;
+/// # assert!((best[0] - 2.0).abs() < 1e-4);
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+pub struct TransformedProblem<O, T> {
+    /// Problem defined on the constrained domain
+    pub problem: O,
+    /// Transform from the unconstrained space into the constrained domain
+    pub transform: T,
+}
+
+impl<O, T> TransformedProblem<O, T> {
+    /// Construct a new instance of `TransformedProblem`
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::{LogTransform, TransformedProblem};
+    /// # use argmin::core::test_utils::TestProblem;
+    /// let problem = TransformedProblem::new(TestProblem::new(), LogTransform::new());
+    /// ```
+    pub fn new(problem: O, transform: T) -> Self {
+        TransformedProblem { problem, transform }
+    }
+
+    /// Maps a parameter vector from the unconstrained space onto the constrained domain
+    pub fn to_constrained<P>(&self, param: &P) -> Result<P, Error>
+    where
+        T: Transform<P>,
+    {
+        self.transform.forward(param)
+    }
+
+    /// Maps a parameter vector from the constrained domain into the unconstrained space
+    pub fn to_unconstrained<P>(&self, param: &P) -> Result<P, Error>
+    where
+        T: Transform<P>,
+    {
+        self.transform.inverse(param)
+    }
+}
+
+impl<O, T> CostFunction for TransformedProblem<O, T>
+where
+    O: CostFunction,
+    T: Transform<O::Param>,
+{
+    type Param = O::Param;
+    type Output = O::Output;
+
+    fn cost(&self, param: &Self::Param) -> Result<Self::Output, Error> {
+        self.problem.cost(&self.transform.forward(param)?)
+    }
+}
+
+impl<O, T> Gradient for TransformedProblem<O, T>
+where
+    O: Gradient<Gradient = <O as Gradient>::Param>,
+    T: Transform<<O as Gradient>::Param>,
+{
+    type Param = <O as Gradient>::Param;
+    type Gradient = <O as Gradient>::Param;
+
+    fn gradient(&self, param: &Self::Param) -> Result<Self::Gradient, Error> {
+        let x = self.transform.forward(param)?;
+        let grad = self.problem.gradient(&x)?;
+        self.transform.backward(param, &grad)
+    }
+}
+
+/// Logistic sigmoid `1 / (1 + exp(-y))`
+fn sigmoid<F: ArgminFloat>(y: F) -> F {
+    float!(1.0) / (float!(1.0) + (-y).exp())
+}
+
+/// Returns an error if the lengths `a` and `b` differ.
+fn check_lengths(name: &str, a: usize, b: usize) -> Result<(), Error> {
+    if a != b {
+        return Err(argmin_error!(
+            InvalidParameter,
+            format!("`{name}`: length mismatch ({a} vs. {b}).")
+        ));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::test_utils::TestProblem;
+    use crate::core::ArgminError;
+    use approx::assert_relative_eq;
+
+    test_trait_impl!(log_transform, LogTransform);
+    test_trait_impl!(logit_transform, LogitTransform<f64>);
+    test_trait_impl!(softmax_transform, SoftmaxTransform);
+    test_trait_impl!(
+        transformed_problem,
+        TransformedProblem<TestProblem, LogTransform>
+    );
+
+    /// Compares `backward` against central differences of `forward` (as a weighted sum).
+    fn check_backward<T: Transform<Vec<f64>>>(transform: &T, y: &[f64], g: &[f64]) {
+        let y = y.to_vec();
+        let g = g.to_vec();
+        let analytic = transform.backward(&y, &g).unwrap();
+        let eps = 1e-6;
+        for i in 0..y.len() {
+            let mut yp = y.clone();
+            yp[i] += eps;
+            let mut ym = y.clone();
+            ym[i] -= eps;
+            let fp = transform.forward(&yp).unwrap();
+            let fm = transform.forward(&ym).unwrap();
+            let numeric: f64 = fp
+                .iter()
+                .zip(fm.iter())
+                .zip(g.iter())
+                .map(|((p, m), g)| g * (p - m) / (2.0 * eps))
+                .sum();
+            assert_relative_eq!(analytic[i], numeric, epsilon = 1e-6);
+        }
+    }
+
+    #[test]
+    fn test_log_transform() {
+        let t = LogTransform::new();
+        let y = vec![-1.0f64, 0.0, 2.0];
+        let x = t.forward(&y).unwrap();
+        assert_relative_eq!(x[1], 1.0);
+        let y2 = t.inverse(&x).unwrap();
+        for (a, b) in y.iter().zip(y2.iter()) {
+            assert_relative_eq!(a, b, epsilon = 1e-12);
+        }
+        check_backward(&t, &y, &[1.0, -2.0, 0.5]);
+        assert_error!(
+            t.inverse(&vec![1.0f64, 0.0]),
+            ArgminError,
+            "Invalid parameter: \"`LogTransform`: parameters must be strictly positive.\""
+        );
+    }
+
+    #[test]
+    fn test_logit_transform() {
+        let t = LogitTransform::new(vec![-1.0f64, 0.0], vec![1.0, 10.0]).unwrap();
+        let y = vec![0.3f64, -2.0];
+        let x = t.forward(&y).unwrap();
+        assert!(x[0] > -1.0 && x[0] < 1.0);
+        assert!(x[1] > 0.0 && x[1] < 10.0);
+        let y2 = t.inverse(&x).unwrap();
+        for (a, b) in y.iter().zip(y2.iter()) {
+            assert_relative_eq!(a, b, epsilon = 1e-12);
+        }
+        check_backward(&t, &y, &[1.0, -2.0]);
+        assert_error!(
+            t.inverse(&vec![1.0f64, 5.0]),
+            ArgminError,
+            "Invalid parameter: \"`LogitTransform`: parameters must be strictly within the bounds.\""
+        );
+    }
+
+    #[test]
+    fn test_logit_transform_invalid_bounds() {
+        assert_error!(
+            LogitTransform::new(vec![1.0f64], vec![1.0]),
+            ArgminError,
+            "Invalid parameter: \"`LogitTransform`: lower bounds must be smaller than upper bounds.\""
+        );
+        assert_error!(
+            LogitTransform::new(vec![1.0f64], vec![2.0, 3.0]),
+            ArgminError,
+            "Invalid parameter: \"`LogitTransform`: length mismatch (1 vs. 2).\""
+        );
+    }
+
+    #[test]
+    fn test_softmax_transform() {
+        let t = SoftmaxTransform::new();
+        let y = vec![0.5f64, -1.0, 2.0];
+        let x = t.forward(&y).unwrap();
+        assert_relative_eq!(x.iter().sum::<f64>(), 1.0, epsilon = 1e-12);
+        let x2 = t.forward(&t.inverse(&x).unwrap()).unwrap();
+        for (a, b) in x.iter().zip(x2.iter()) {
+            assert_relative_eq!(a, b, epsilon = 1e-12);
+        }
+        check_backward(&t, &y, &[1.0, -2.0, 0.5]);
+    }
+
+    #[test]
+    fn test_chain() {
+        let t = LogTransform::new().then(LogitTransform::new(vec![0.0f64], vec![1.0]).unwrap());
+        let y = vec![0.7f64];
+        let y2 = t.inverse(&t.forward(&y).unwrap()).unwrap();
+        assert_relative_eq!(y[0], y2[0], epsilon = 1e-12);
+        check_backward(&t, &y, &[1.5]);
+    }
+
+    #[test]
+    fn test_transformed_problem_gradient() {
+        struct Quadratic {}
+
+        impl CostFunction for Quadratic {
+            type Param = Vec<f64>;
+            type Output = f64;
+
+            fn cost(&self, p: &Self::Param) -> Result<Self::Output, Error> {
+                Ok(p.iter().map(|x| (x - 2.0).powi(2)).sum())
+            }
+        }
+
+        impl Gradient for Quadratic {
+            type Param = Vec<f64>;
+            type Gradient = Vec<f64>;
+
+            fn gradient(&self, p: &Self::Param) -> Result<Self::Gradient, Error> {
+                Ok(p.iter().map(|x| 2.0 * (x - 2.0)).collect())
+            }
+        }
+
+        let problem = TransformedProblem::new(Quadratic {}, LogTransform::new());
+        let y = vec![0.1f64, -0.4];
+        let x = problem.to_constrained(&y).unwrap();
+        assert_relative_eq!(
+            problem.cost(&y).unwrap(),
+            Quadratic {}.cost(&x).unwrap(),
+            epsilon = 1e-12
+        );
+        let grad = problem.gradient(&y).unwrap();
+        for i in 0..2 {
+            assert_relative_eq!(grad[i], 2.0 * (x[i] - 2.0) * x[i], epsilon = 1e-12);
+        }
+    }
+}
@@ -0,0 +1,152 @@
+// Copyright 2018-2024 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use crate::core::{ArgminFloat, State, TerminationStatus};
+#[cfg(feature = "serde1")]
+use serde::{Deserialize, Serialize};
+use std::sync::{Arc, RwLock};
+
+/// Snapshot of the scalar quantities of a state
+///
+/// Returned by [`StateWatch::get`].
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+pub struct StateSnapshot<F> {
+    /// Current iteration number
+    pub iter: u64,
+    /// Current cost function value
+    pub cost: F,
+    /// Current best cost function value
+    pub best_cost: F,
+    /// Termination status
+    pub termination_status: TerminationStatus,
+}
+
+impl<F: ArgminFloat> Default for StateSnapshot<F> {
+    fn default() -> Self {
+        StateSnapshot {
+            iter: 0,
+            cost: F::infinity(),
+            best_cost: F::infinity(),
+            termination_status: TerminationStatus::NotTerminated,
+        }
+    }
+}
+
+/// Thread-safe handle for inspecting the progress of a running [`Executor`](`crate::core::Executor`)
+///
+/// Obtained via [`Executor::state_watch`](`crate::core::Executor::state_watch`). The handle can
+/// be cloned and sent to other threads, for instance a GUI or a web server, which can then poll
+/// the current iteration number, cost and best cost without registering an observer. The
+/// executor updates the handle after initialization, after each iteration and once the run has
+/// finished.
+///
+/// # Example
+///
+/// ```
+/// # use argmin::core::{Error, Executor, State};
+/// # use argmin::core::test_utils::{TestSolver, TestProblem};
+/// # fn main() -> Result<(), Error> {
+/// let mut executor = Executor::new(TestProblem::new(), TestSolver::new())
+///     .configure(|state| state.param(vec![1.0f64, 0.0]).max_iters(10));
+///
+/// let watch = executor.state_watch();
+///
+/// // Poll the progress from another thread
+/// let poller = watch.clone();
+/// let handle = std::thread::spawn(move || {
+///     while !poller.get().termination_status.terminated() {
+///         std::thread::sleep(std::time::Duration::from_millis(1));
+///     }
+///     poller.get().iter
+/// });
+///
+/// let res = executor.run()?;
+///
+/// assert_eq!(handle.join().unwrap(), res.state().get_iter());
+/// assert_eq!(watch.get().iter, 10);
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug)]
+pub struct StateWatch<F> {
+    snapshot: Arc<RwLock<StateSnapshot<F>>>,
+}
+
+impl<F: ArgminFloat> StateWatch<F> {
+    /// Construct a new `StateWatch`
+    pub(crate) fn new() -> Self {
+        StateWatch {
+            snapshot: Arc::new(RwLock::new(StateSnapshot::default())),
+        }
+    }
+
+    /// Returns a snapshot of the most recently published state
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::{Executor, IterState};
+    /// # use argmin::core::test_utils::{TestSolver, TestProblem};
+    /// let mut executor: Executor<_, _, IterState<Vec<f64>, (), (), (), (), f64>> =
+    ///     Executor::new(TestProblem::new(), TestSolver::new());
+    /// let watch = executor.state_watch();
+    ///
+    /// let snapshot = watch.get();
+    /// assert_eq!(snapshot.iter, 0);
+    /// ```
+    pub fn get(&self) -> StateSnapshot<F> {
+        // A panic in another thread while holding the lock cannot leave the snapshot in an
+        // inconsistent state, therefore poisoning is ignored.
+        self.snapshot
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .clone()
+    }
+
+    /// Publishes the scalar quantities of `state`
+    pub(crate) fn update<I: State<Float = F>>(&self, state: &I) {
+        let mut snapshot = self.snapshot.write().unwrap_or_else(|e| e.into_inner());
+        snapshot.iter = state.get_iter();
+        snapshot.cost = state.get_cost();
+        snapshot.best_cost = state.get_best_cost();
+        snapshot
+            .termination_status
+            .clone_from(state.get_termination_status());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::{IterState, TerminationReason};
+
+    send_sync_test!(state_watch, StateWatch<f64>);
+
+    #[test]
+    fn test_update() {
+        let watch: StateWatch<f64> = StateWatch::new();
+        let clone = watch.clone();
+
+        let mut state: IterState<Vec<f64>, (), (), (), (), f64> = IterState::new().cost(2.0);
+        state.update();
+        state.increment_iter();
+        let state = state
+            .cost(3.0)
+            .terminate_with(TerminationReason::MaxItersReached);
+        watch.update(&state);
+
+        let snapshot = clone.get();
+        assert_eq!(snapshot.iter, 1);
+        assert_eq!(snapshot.cost.to_ne_bytes(), 3.0f64.to_ne_bytes());
+        assert_eq!(snapshot.best_cost.to_ne_bytes(), 2.0f64.to_ne_bytes());
+        assert_eq!(
+            snapshot.termination_status,
+            TerminationStatus::Terminated(TerminationReason::MaxItersReached)
+        );
+    }
+}
@@ -0,0 +1,223 @@
+// Copyright 2018-2024 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+/// Pool of scratch buffers which are reused across iterations
+///
+/// Many solvers need temporary vectors in each iteration which are discarded at the end of the
+/// iteration, such as the intermediate vector of the two-loop recursion of
+/// [`LBFGS`](`crate::solver::quasinewton::LBFGS`) or the centroid of
+/// [`NelderMead`](`crate::solver::neldermead::NelderMead`). For large problems, allocating these
+/// anew in every iteration is a considerable cost. Instead, a solver can [`take`](`Workspace::take`)
+/// a buffer from its `Workspace`, overwrite it and [`put`](`Workspace::put`) it back once it is not
+/// needed anymore. Buffers are created lazily: the pool is empty in the beginning and only ever
+/// holds the buffers which were returned to it.
+///
+/// The contents of the buffers are meaningless between iterations. Therefore cloning a `Workspace`
+/// results in an empty pool and solvers skip it during (de)serialization.
+///
+/// # Example
+///
+/// ```
+/// # use argmin::core::Workspace;
+/// let mut workspace: Workspace<Vec<f64>> = Workspace::new();
+/// let x = vec![1.0, 2.0, 3.0];
+///
+/// // The pool is empty, therefore `x` is cloned into a new buffer
+/// let mut buffer = workspace.copy_of(&x);
+/// buffer[0] = 4.0;
+/// # assert_eq!(buffer, vec![4.0, 2.0, 3.0]);
+/// workspace.put(buffer);
+/// # assert_eq!(workspace.len(), 1);
+///
+/// // Now the allocation of the returned buffer is reused
+/// let buffer = workspace.copy_of(&x);
+/// # assert_eq!(buffer, x);
+/// # assert!(workspace.is_empty());
+/// ```
+pub struct Workspace<T> {
+    /// Buffers which are currently not in use
+    buffers: Vec<T>,
+}
+
+impl<T> Workspace<T> {
+    /// Construct a new, empty instance of `Workspace`
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::Workspace;
+    /// let workspace: Workspace<Vec<f64>> = Workspace::new();
+    /// # assert!(workspace.is_empty());
+    /// ```
+    pub fn new() -> Self {
+        Workspace {
+            buffers: Vec::new(),
+        }
+    }
+
+    /// Takes a buffer out of the pool. Its contents are unspecified. Returns `None` if the pool is
+    /// empty.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::Workspace;
+    /// let mut workspace: Workspace<Vec<f64>> = Workspace::new();
+    /// assert!(workspace.take().is_none());
+    ///
+    /// workspace.put(vec![1.0, 2.0]);
+    /// let buffer = workspace.take();
+    /// # assert_eq!(buffer, Some(vec![1.0, 2.0]));
+    /// ```
+    pub fn take(&mut self) -> Option<T> {
+        self.buffers.pop()
+    }
+
+    /// Returns a buffer to the pool such that it can be reused
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::Workspace;
+    /// let mut workspace: Workspace<Vec<f64>> = Workspace::new();
+    /// workspace.put(vec![1.0, 2.0]);
+    /// # assert_eq!(workspace.len(), 1);
+    /// ```
+    pub fn put(&mut self, buffer: T) {
+        self.buffers.push(buffer);
+    }
+
+    /// Returns the number of buffers in the pool
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::Workspace;
+    /// let mut workspace: Workspace<Vec<f64>> = Workspace::new();
+    /// workspace.put(vec![1.0, 2.0]);
+    /// assert_eq!(workspace.len(), 1);
+    /// ```
+    pub fn len(&self) -> usize {
+        self.buffers.len()
+    }
+
+    /// Returns `true` if there are no buffers in the pool
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::Workspace;
+    /// let workspace: Workspace<Vec<f64>> = Workspace::new();
+    /// assert!(workspace.is_empty());
+    /// ```
+    pub fn is_empty(&self) -> bool {
+        self.buffers.is_empty()
+    }
+
+    /// Drops all buffers in the pool
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::Workspace;
+    /// let mut workspace: Workspace<Vec<f64>> = Workspace::new();
+    /// workspace.put(vec![1.0, 2.0]);
+    /// workspace.clear();
+    /// assert!(workspace.is_empty());
+    /// ```
+    pub fn clear(&mut self) {
+        self.buffers.clear();
+    }
+}
+
+impl<T: Clone> Workspace<T> {
+    /// Returns a copy of `value`. If the pool is not empty, `value` is copied into one of its
+    /// buffers via [`Clone::clone_from`], which avoids a new allocation for types such as `Vec`,
+    /// `ndarray`s `Array` and `nalgebra`s dynamically sized matrices. Otherwise `value` is cloned.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::Workspace;
+    /// let mut workspace: Workspace<Vec<f64>> = Workspace::new();
+    /// workspace.put(vec![0.0, 0.0]);
+    /// let buffer = workspace.copy_of(&vec![1.0, 2.0]);
+    /// assert_eq!(buffer, vec![1.0, 2.0]);
+    /// # assert!(workspace.is_empty());
+    /// ```
+    pub fn copy_of(&mut self, value: &T) -> T {
+        match self.buffers.pop() {
+            Some(mut buffer) => {
+                buffer.clone_from(value);
+                buffer
+            }
+            None => value.clone(),
+        }
+    }
+}
+
+impl<T> Default for Workspace<T> {
+    fn default() -> Self {
+        Workspace::new()
+    }
+}
+
+impl<T> Clone for Workspace<T> {
+    /// The buffers are not cloned, the clone starts with an empty pool.
+    fn clone(&self) -> Self {
+        Workspace::new()
+    }
+}
+
+impl<T> std::fmt::Debug for Workspace<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Workspace")
+            .field("buffers", &self.buffers.len())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_copy_of_reuses_allocation() {
+        let mut workspace: Workspace<Vec<f64>> = Workspace::new();
+        let buffer = workspace.copy_of(&vec![1.0, 2.0, 3.0]);
+        let ptr = buffer.as_ptr();
+        workspace.put(buffer);
+
+        let buffer = workspace.copy_of(&vec![4.0, 5.0]);
+        assert_eq!(buffer, vec![4.0, 5.0]);
+        assert_eq!(buffer.as_ptr(), ptr);
+        assert!(workspace.is_empty());
+    }
+
+    #[test]
+    fn test_take_put() {
+        let mut workspace: Workspace<Vec<f64>> = Workspace::new();
+        assert!(workspace.take().is_none());
+        workspace.put(vec![1.0]);
+        workspace.put(vec![2.0]);
+        assert_eq!(workspace.len(), 2);
+        assert_eq!(workspace.take(), Some(vec![2.0]));
+        assert_eq!(workspace.take(), Some(vec![1.0]));
+        assert!(workspace.take().is_none());
+    }
+
+    #[test]
+    fn test_clone_is_empty() {
+        let mut workspace: Workspace<Vec<f64>> = Workspace::new();
+        workspace.put(vec![1.0]);
+        assert!(workspace.clone().is_empty());
+        assert_eq!(workspace.len(), 1);
+        assert_eq!(format!("{workspace:?}"), "Workspace { buffers: 1 }");
+        workspace.clear();
+        assert!(workspace.is_empty());
+    }
+}
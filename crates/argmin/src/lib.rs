@@ -48,12 +48,23 @@
 //!   
 //! - [Steepest descent](`crate::solver::gradientdescent::SteepestDescent`)
 //!
+//! - [Accelerated gradient descent (Nesterov)](`crate::solver::gradientdescent::AcceleratedGradientDescent`)
+//!
+//! - [Mirror descent](`crate::solver::mirrordescent::MirrorDescent`)
+//!   - [Euclidean](`crate::solver::mirrordescent::Euclidean`)
+//!   - [Negative entropy (exponentiated gradient)](`crate::solver::mirrordescent::NegativeEntropy`)
+//!
 //! - [Conjugate gradient methods](`crate::solver::conjugategradient`)
 //!   - [Conjugate gradient method](`crate::solver::conjugategradient::ConjugateGradient`)
 //!   - [Nonlinear conjugate gradient method](`crate::solver::conjugategradient::NonlinearConjugateGradient`)
 //!
+//! - [Krylov subspace methods for linear systems](`crate::solver::krylov`)
+//!   - [MINRES](`crate::solver::krylov::MINRES`)
+//!   - [Restarted GMRES](`crate::solver::krylov::GMRES`)
+//!
 //! - [Newton methods](`crate::solver::newton`)
 //!   - [Newton's method](`crate::solver::newton::Newton`)
+//!   - [Newton's method with line search](`crate::solver::newton::NewtonLS`)
 //!   - [Newton-CG](solver/newton/newton_cg/struct.NewtonCG.html)
 //!
 //! - [Quasi-Newton methods](`crate::solver::quasinewton`)
@@ -78,9 +89,24 @@
 //! - [Nelder-Mead method](`crate::solver::neldermead::NelderMead`)
 //!
 //! - [Simulated Annealing](`crate::solver::simulatedannealing::SimulatedAnnealing`)
+//!   - [Threshold Accepting](`crate::solver::simulatedannealing::ThresholdAccepting`)
+//!   - [Great Deluge](`crate::solver::simulatedannealing::GreatDeluge`)
+//!
+//! - [Tabu search](`crate::solver::tabusearch::TabuSearch`)
+//!
+//! - [Simultaneous Perturbation Stochastic Approximation (SPSA)](`crate::solver::stochastic::SPSA`)
 //!
 //! - [Particle Swarm Optimization](`crate::solver::particleswarm::ParticleSwarm`)
 //!
+//! - [Global optimization](`crate::solver::globalopt`)
+//!   - [Random search](`crate::solver::globalopt::RandomSearch`)
+//!   - [Latin hypercube sampling](`crate::solver::globalopt::LatinHypercube`)
+//!   - [Harmony search](`crate::solver::globalopt::HarmonySearch`)
+//!   - [Artificial bee colony](`crate::solver::globalopt::ArtificialBeeColony`)
+//!
+//! - [Interval branch and bound](solver/intervalbb/struct.IntervalBranchAndBound.html) (requires
+//!   the `inari` feature and `RUSTFLAGS='-Ctarget-cpu=haswell'` or later on x86-64)
+//!
 //! ## External solvers compatible with argmin
 //!
 //! External solvers which implement the `Solver` trait are compatible with argmins `Executor`,
@@ -95,6 +95,92 @@ impl<F: ArgminFloat> BrentOpt<F> {
         self.t = t;
         self
     }
+
+    /// Set absolute (`t`) and relative (`eps`) tolerance with validation.
+    ///
+    /// Same as [`set_tolerance`](`BrentOpt::set_tolerance`), but both tolerances must be
+    /// non-negative. Note the order of the arguments: absolute tolerance first.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::brent::BrentOpt;
+    /// # use argmin::core::Error;
+    /// # fn main() -> Result<(), Error> {
+    /// let brent = BrentOpt::new(-10.0f64, 10.0).with_tolerance(1e-6, 1e-8)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_tolerance(self, abs: F, rel: F) -> Result<Self, Error> {
+        if abs < float!(0.0) || rel < float!(0.0) {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "`BrentOpt`: tolerances must be >= 0."
+            ));
+        }
+        Ok(self.set_tolerance(rel, abs))
+    }
+
+    /// Returns the current bracket `[a, b]`, its width, and the estimated accuracy of the current
+    /// best estimate `x` (the largest distance between `x` and the bracket bounds).
+    fn diagnostics(&self) -> KV {
+        kv!(
+            "interval_width" => self.b - self.a;
+            "bracket_lower" => self.a;
+            "bracket_upper" => self.b;
+            "accuracy" => (self.x - self.a).max(self.b - self.x);
+        )
+    }
+}
+
+/// Serializable configuration of [`BrentOpt`]
+///
+/// Options which are `None` keep the defaults of [`BrentOpt`]. The configuration is validated when
+/// the solver is constructed via [`build`](`BrentOptConfig::build`).
+///
+/// # Example
+///
+/// ```
+/// # use argmin::solver::brent::{BrentOpt, BrentOptConfig};
+/// # use argmin::core::Error;
+/// # fn main() -> Result<(), Error> {
+/// let config = BrentOptConfig {
+///     min: -10.0f64,
+///     max: 10.0,
+///     t: Some(1e-6),
+///     ..Default::default()
+/// };
+/// let brent: BrentOpt<f64> = config.build()?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize), serde(default))]
+pub struct BrentOptConfig<F> {
+    /// Lower bound of the interval bracketing the minimum
+    pub min: F,
+    /// Upper bound of the interval bracketing the minimum
+    pub max: F,
+    /// Relative tolerance, see [`BrentOpt::set_tolerance`]
+    pub eps: Option<F>,
+    /// Absolute tolerance, see [`BrentOpt::set_tolerance`]
+    pub t: Option<F>,
+}
+
+impl<F: ArgminFloat> BrentOptConfig<F> {
+    /// Validates the configuration and constructs a [`BrentOpt`] solver.
+    pub fn build(&self) -> Result<BrentOpt<F>, Error> {
+        if self.min >= self.max {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "`BrentOpt`: min must be smaller than max."
+            ));
+        }
+        let solver = BrentOpt::new(self.min, self.max);
+        let eps = self.eps.unwrap_or(solver.eps);
+        let t = self.t.unwrap_or(solver.t);
+        solver.with_tolerance(t, eps)
+    }
 }
 
 impl<O, F> Solver<O, IterState<F, (), (), (), (), F>> for BrentOpt<F>
@@ -120,7 +206,19 @@ where
         self.fv = f;
         self.fw = f;
         self.fx = f;
-        Ok((state.param(self.x).cost(self.fx), None))
+        Ok((
+            state.param(self.x).cost(self.fx),
+            Some(
+                kv!(
+                    "min" => self.a;
+                    "max" => self.b;
+                    "eps" => self.eps;
+                    "t" => self.t;
+                )
+                .with_prefix("config")
+                .merge(self.diagnostics()),
+            ),
+        ))
     }
 
     fn next_iter(
@@ -138,7 +236,7 @@ where
                     .terminate_with(TerminationReason::SolverConverged)
                     .param(self.x)
                     .cost(self.fx),
-                None,
+                Some(self.diagnostics()),
             ));
         }
         let p = (self.x - self.v) * (self.x - self.v) * (self.fx - self.fw)
@@ -204,14 +302,14 @@ where
                 self.fv = fu;
             }
         }
-        Ok((state.param(self.x).cost(self.fx), None))
+        Ok((state.param(self.x).cost(self.fx), Some(self.diagnostics())))
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::core::{Executor, TerminationStatus};
+    use crate::core::{Executor, KvValue, TerminationStatus};
     use approx::assert_relative_eq;
 
     test_trait_impl!(brent, BrentOpt<f64>);
@@ -226,6 +324,93 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_config_build() {
+        let config = BrentOptConfig {
+            min: -10.0f64,
+            max: 10.0,
+            eps: Some(1e-4),
+            t: Some(1e-6),
+        };
+        let brent = config.build().unwrap();
+        assert_eq!(brent.a.to_ne_bytes(), (-10.0f64).to_ne_bytes());
+        assert_eq!(brent.b.to_ne_bytes(), 10.0f64.to_ne_bytes());
+        assert_eq!(brent.eps.to_ne_bytes(), 1e-4f64.to_ne_bytes());
+        assert_eq!(brent.t.to_ne_bytes(), 1e-6f64.to_ne_bytes());
+
+        let brent = BrentOptConfig {
+            min: -10.0f64,
+            max: 10.0,
+            ..Default::default()
+        }
+        .build()
+        .unwrap();
+        assert_eq!(brent.eps.to_ne_bytes(), f64::EPSILON.sqrt().to_ne_bytes());
+        assert_eq!(brent.t.to_ne_bytes(), 1e-5f64.to_ne_bytes());
+
+        let res = BrentOptConfig {
+            min: 10.0f64,
+            max: -10.0,
+            ..Default::default()
+        }
+        .build();
+        assert_error!(
+            res,
+            crate::core::ArgminError,
+            "Invalid parameter: \"`BrentOpt`: min must be smaller than max.\""
+        );
+
+        let res = BrentOptConfig {
+            t: Some(-1.0),
+            ..config
+        }
+        .build();
+        assert_error!(
+            res,
+            crate::core::ArgminError,
+            "Invalid parameter: \"`BrentOpt`: tolerances must be >= 0.\""
+        );
+    }
+
+    #[test]
+    fn test_with_tolerance() {
+        let brent = BrentOpt::new(-10.0f64, 10.0)
+            .with_tolerance(1e-6, 1e-4)
+            .unwrap();
+        assert_eq!(brent.t.to_ne_bytes(), 1e-6f64.to_ne_bytes());
+        assert_eq!(brent.eps.to_ne_bytes(), 1e-4f64.to_ne_bytes());
+
+        for (abs, rel) in [(-1.0, 1e-4), (1e-6, -1.0)] {
+            let res = BrentOpt::new(-10.0f64, 10.0).with_tolerance(abs, rel);
+            assert_error!(
+                res,
+                crate::core::ArgminError,
+                "Invalid parameter: \"`BrentOpt`: tolerances must be >= 0.\""
+            );
+        }
+    }
+
+    #[test]
+    fn test_diagnostics() {
+        let mut brent = BrentOpt::new(-10.0f64, 10.0);
+        let mut problem = Problem::new(TestFunc {});
+        let (state, kv) = brent.init(&mut problem, IterState::new()).unwrap();
+        let kv = kv.unwrap();
+        assert_eq!(kv.get("interval_width"), Some(&KvValue::Float(20.0)));
+        assert_eq!(kv.get("bracket_lower"), Some(&KvValue::Float(-10.0)));
+        assert_eq!(kv.get("bracket_upper"), Some(&KvValue::Float(10.0)));
+
+        let (_, kv) = brent.next_iter(&mut problem, state).unwrap();
+        let kv = kv.unwrap();
+        let width = kv.get("interval_width").unwrap().get_float().unwrap();
+        let accuracy = kv.get("accuracy").unwrap().get_float().unwrap();
+        assert_relative_eq!(width, brent.b - brent.a, epsilon = f64::EPSILON);
+        assert!(width < 20.0);
+        assert!(accuracy <= width);
+        assert_eq!(kv.get("bracket_lower"), Some(&KvValue::Float(brent.a)));
+        assert_eq!(kv.get("bracket_upper"), Some(&KvValue::Float(brent.b)));
+    }
+
     #[test]
     fn test_brent() {
         let cost = TestFunc {};
@@ -78,6 +78,47 @@ impl<F: ArgminFloat> BrentRoot<F> {
     }
 }
 
+/// Serializable configuration of [`BrentRoot`]
+///
+/// The configuration is validated when the solver is constructed via
+/// [`build`](`BrentRootConfig::build`).
+///
+/// # Example
+///
+/// ```
+/// # use argmin::solver::brent::{BrentRoot, BrentRootConfig};
+/// # use argmin::core::Error;
+/// # fn main() -> Result<(), Error> {
+/// let config = BrentRootConfig {
+///     min: 0.0f64,
+///     max: 2.0,
+///     tol: 1e-8,
+/// };
+/// let brent: BrentRoot<f64> = config.build()?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize), serde(default))]
+pub struct BrentRootConfig<F> {
+    /// Lower bound of the interval bracketing the root
+    pub min: F,
+    /// Upper bound of the interval bracketing the root
+    pub max: F,
+    /// Relative error to be targeted, must be non-negative
+    pub tol: F,
+}
+
+impl<F: ArgminFloat> BrentRootConfig<F> {
+    /// Validates the configuration and constructs a [`BrentRoot`] solver.
+    pub fn build(&self) -> Result<BrentRoot<F>, Error> {
+        if self.tol < F::zero() {
+            return Err(BrentRootError::NegativeTol.into());
+        }
+        Ok(BrentRoot::new(self.min, self.max, self.tol))
+    }
+}
+
 impl<O, F> Solver<O, IterState<F, (), (), (), (), F>> for BrentRoot<F>
 where
     O: CostFunction<Param = F, Output = F>,
@@ -102,7 +143,17 @@ where
             return Err(BrentRootError::NegativeTol.into());
         }
         self.fc = self.fb;
-        Ok((state.param(self.b).cost(self.fb.abs()), None))
+        Ok((
+            state.param(self.b).cost(self.fb.abs()),
+            Some(
+                kv!(
+                    "min" => self.a;
+                    "max" => self.b;
+                    "tol" => self.tol;
+                )
+                .with_prefix("config"),
+            ),
+        ))
     }
 
     fn next_iter(
@@ -224,6 +275,29 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_config_build() {
+        let config = BrentRootConfig {
+            min: 0.0f64,
+            max: 2.0,
+            tol: 1e-6,
+        };
+        let brent = config.build().unwrap();
+        assert_eq!(brent.a.to_ne_bytes(), 0.0f64.to_ne_bytes());
+        assert_eq!(brent.b.to_ne_bytes(), 2.0f64.to_ne_bytes());
+        assert_eq!(brent.tol.to_ne_bytes(), 1e-6f64.to_ne_bytes());
+
+        let res = BrentRootConfig {
+            tol: -1e-6,
+            ..config
+        }
+        .build();
+        assert_eq!(
+            res.err().unwrap().to_string(),
+            "BrentRoot error: tol must be positive."
+        );
+    }
+
     #[test]
     fn test_brent_invalid_range() {
         let min: f64 = 2.0;
@@ -32,5 +32,5 @@
 mod brentopt;
 mod brentroot;
 
-pub use brentopt::BrentOpt;
-pub use brentroot::BrentRoot;
+pub use brentopt::{BrentOpt, BrentOptConfig};
+pub use brentroot::{BrentRoot, BrentRootConfig};
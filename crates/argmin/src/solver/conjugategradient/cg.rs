@@ -84,6 +84,36 @@ where
     }
 }
 
+/// Serializable configuration of [`ConjugateGradient`]
+///
+/// # Example
+///
+/// ```
+/// # use argmin::solver::conjugategradient::{ConjugateGradient, ConjugateGradientConfig};
+/// # use argmin::core::Error;
+/// # fn main() -> Result<(), Error> {
+/// let config = ConjugateGradientConfig { b: vec![1.0f64, 1.0] };
+/// let cg: ConjugateGradient<_, f64> = config.build()?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize), serde(default))]
+pub struct ConjugateGradientConfig<P> {
+    /// Right hand side `b` of `A * x = b`
+    pub b: P,
+}
+
+impl<P> ConjugateGradientConfig<P>
+where
+    P: Clone,
+{
+    /// Constructs a [`ConjugateGradient`] solver.
+    pub fn build<F: ArgminFloat>(&self) -> Result<ConjugateGradient<P, F>, Error> {
+        Ok(ConjugateGradient::new(self.b.clone()))
+    }
+}
+
 impl<P, O, R, F> Solver<O, IterState<P, (), (), (), R, F>> for ConjugateGradient<P, F>
 where
     O: Operator<Param = P, Output = P>,
@@ -172,6 +202,16 @@ mod tests {
         assert!(rtr.is_nan());
     }
 
+    #[test]
+    fn test_config_build() {
+        let config = ConjugateGradientConfig {
+            b: vec![1.0f64, 2.0],
+        };
+        let cg: ConjugateGradient<_, f64> = config.build().unwrap();
+        assert_eq!(cg.b, config.b);
+        assert!(cg.p.is_none());
+    }
+
     #[test]
     fn test_get_prev_p_not_initialized() {
         let cg: ConjugateGradient<_, f64> = ConjugateGradient::new(vec![1.0f64, 2.0]);
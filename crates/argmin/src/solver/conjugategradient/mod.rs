@@ -20,5 +20,5 @@ mod nonlinear_cg;
 
 pub mod beta;
 
-pub use self::cg::ConjugateGradient;
-pub use self::nonlinear_cg::NonlinearConjugateGradient;
+pub use self::cg::{ConjugateGradient, ConjugateGradientConfig};
+pub use self::nonlinear_cg::{NonlinearConjugateGradient, NonlinearConjugateGradientConfig};
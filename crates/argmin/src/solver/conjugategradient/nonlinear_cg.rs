@@ -7,9 +7,10 @@
 
 use crate::core::{
     ArgminFloat, CostFunction, Error, Executor, Gradient, IterState, LineSearch, NLCGBetaUpdate,
-    OptimizationResult, Problem, Solver, State, KV,
+    NestedTelemetry, OptimizationResult, Problem, Solver, State, TerminationReason, KV,
 };
-use argmin_math::{ArgminAdd, ArgminDot, ArgminL2Norm, ArgminMul};
+use crate::solver::linesearch::LineSearchRecovery;
+use argmin_math::{ArgminAdd, ArgminAxpy, ArgminDot, ArgminL2Norm, ArgminMul};
 #[cfg(feature = "serde1")]
 use serde::{Deserialize, Serialize};
 
@@ -23,6 +24,17 @@ use serde::{Deserialize, Serialize};
 ///
 /// The optimization problem is required to implement [`CostFunction`] and [`Gradient`].
 ///
+/// ## Line search failures
+///
+/// By default, the algorithm terminates if the line search fails. Alternative ways of recovering
+/// from such failures can be chosen via
+/// [`with_linesearch_recovery`](`NonlinearConjugateGradient::with_linesearch_recovery`).
+///
+/// If the line search reports that the current point is stationary along the conjugate direction
+/// ([`TerminationReason::StationaryDirection`]), the algorithm restarts along the steepest descent
+/// direction. If that direction is stationary as well, the algorithm terminates with the same
+/// reason.
+///
 /// ## Reference
 ///
 /// Jorge Nocedal and Stephen J. Wright (2006). Numerical Optimization.
@@ -42,6 +54,12 @@ pub struct NonlinearConjugateGradient<P, L, B, F> {
     restart_iter: u64,
     /// Restart based on orthogonality
     restart_orthogonality: Option<F>,
+    /// Telemetry of the line search
+    #[cfg_attr(feature = "serde1", serde(default))]
+    inner_telemetry: NestedTelemetry,
+    /// Reaction to failures of the line search
+    #[cfg_attr(feature = "serde1", serde(default))]
+    linesearch_recovery: LineSearchRecovery<F>,
 }
 
 impl<P, L, B, F> NonlinearConjugateGradient<P, L, B, F>
@@ -69,9 +87,32 @@ where
             beta_method,
             restart_iter: u64::MAX,
             restart_orthogonality: None,
+            inner_telemetry: NestedTelemetry::new(),
+            linesearch_recovery: LineSearchRecovery::Stop,
         }
     }
 
+    /// Forward the telemetry of the line search to observers
+    ///
+    /// The key-value pairs returned by the line search as well as its number of iterations and
+    /// its termination reason are forwarded with keys prefixed by `prefix` (for instance
+    /// `linesearch.iters`). Independent of this setting, the line search iterations are counted
+    /// as `linesearch_iter_count`. See [`NestedTelemetry`] for details.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::conjugategradient::NonlinearConjugateGradient;
+    /// # let linesearch = ();
+    /// # let beta_method = ();
+    /// let solver = NonlinearConjugateGradient::<Vec<f64>, _, _, f64>::new(linesearch, beta_method).with_inner_telemetry("linesearch");
+    /// ```
+    #[must_use]
+    pub fn with_inner_telemetry<T: AsRef<str>>(mut self, prefix: T) -> Self {
+        self.inner_telemetry = NestedTelemetry::forward(prefix);
+        self
+    }
+
     /// Specify the number of iterations after which a restart should be performed.
     ///
     /// This allows the algorithm to "forget" previous information which may not be helpful
@@ -116,14 +157,119 @@ where
         self.restart_orthogonality = Some(v);
         self
     }
+
+    /// Sets the policy for recovering from failures of the line search.
+    ///
+    /// Defaults to [`LineSearchRecovery::Stop`], which terminates the run. With
+    /// [`LineSearchRecovery::ResetMemory`], the conjugate direction is discarded and the line
+    /// search is repeated along the steepest descent direction. The step length of
+    /// [`LineSearchRecovery::SmallStep`] must be `> 0.0`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::conjugategradient::NonlinearConjugateGradient;
+    /// # use argmin::solver::linesearch::LineSearchRecovery;
+    /// # use argmin::core::Error;
+    /// # fn main() -> Result<(), Error> {
+    /// # let linesearch = ();
+    /// # let beta_method = ();
+    /// # let nlcg: NonlinearConjugateGradient<Vec<f64>, _, _, f64> = NonlinearConjugateGradient::new(linesearch, beta_method);
+    /// let nlcg = nlcg.with_linesearch_recovery(LineSearchRecovery::Backtracking)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_linesearch_recovery(
+        mut self,
+        policy: LineSearchRecovery<F>,
+    ) -> Result<Self, Error> {
+        if let LineSearchRecovery::SmallStep(step) = policy {
+            if step.is_nan() || step <= float!(0.0) {
+                return Err(argmin_error!(
+                    InvalidParameter,
+                    "`NonlinearConjugateGradient`: step length of line search recovery must be > 0."
+                ));
+            }
+        }
+        self.linesearch_recovery = policy;
+        Ok(self)
+    }
+}
+
+/// Serializable configuration of [`NonlinearConjugateGradient`]
+///
+/// Options which are `None` keep the defaults of [`NonlinearConjugateGradient`]. The
+/// configuration is validated when the solver is constructed via
+/// [`build`](`NonlinearConjugateGradientConfig::build`).
+///
+/// # Example
+///
+/// ```
+/// # use argmin::solver::conjugategradient::{
+/// #     NonlinearConjugateGradient, NonlinearConjugateGradientConfig
+/// # };
+/// # use argmin::core::Error;
+/// # fn main() -> Result<(), Error> {
+/// # let linesearch = ();
+/// # let beta_method = ();
+/// let config = NonlinearConjugateGradientConfig {
+///     restart_iters: Some(10),
+///     restart_orthogonality: Some(0.1),
+///     ..Default::default()
+/// };
+/// let nlcg: NonlinearConjugateGradient<Vec<f64>, _, _, f64> =
+///     config.build(linesearch, beta_method)?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize), serde(default))]
+pub struct NonlinearConjugateGradientConfig<F> {
+    /// See [`NonlinearConjugateGradient::restart_iters`]
+    pub restart_iters: Option<u64>,
+    /// See [`NonlinearConjugateGradient::restart_orthogonality`], must be `> 0`
+    pub restart_orthogonality: Option<F>,
+    /// See [`NonlinearConjugateGradient::with_linesearch_recovery`]
+    pub linesearch_recovery: Option<LineSearchRecovery<F>>,
+}
+
+impl<F> NonlinearConjugateGradientConfig<F>
+where
+    F: ArgminFloat,
+{
+    /// Validates the configuration and constructs a [`NonlinearConjugateGradient`] solver using
+    /// the given line search and beta update method.
+    pub fn build<P, L, B>(
+        &self,
+        linesearch: L,
+        beta_method: B,
+    ) -> Result<NonlinearConjugateGradient<P, L, B, F>, Error> {
+        let mut solver = NonlinearConjugateGradient::new(linesearch, beta_method);
+        if let Some(iters) = self.restart_iters {
+            solver = solver.restart_iters(iters);
+        }
+        if let Some(v) = self.restart_orthogonality {
+            if v <= float!(0.0) {
+                return Err(argmin_error!(
+                    InvalidParameter,
+                    "`NonlinearConjugateGradient`: orthogonality measure must be > 0."
+                ));
+            }
+            solver = solver.restart_orthogonality(v);
+        }
+        if let Some(policy) = self.linesearch_recovery {
+            solver = solver.with_linesearch_recovery(policy)?;
+        }
+        Ok(solver)
+    }
 }
 
 impl<O, P, G, L, B, F> Solver<O, IterState<P, G, (), (), (), F>>
     for NonlinearConjugateGradient<P, L, B, F>
 where
     O: CostFunction<Param = P, Output = F> + Gradient<Param = P, Gradient = G>,
-    P: Clone + ArgminAdd<P, P> + ArgminMul<F, P>,
-    G: Clone + ArgminMul<F, P> + ArgminDot<G, F> + ArgminL2Norm<F>,
+    P: Clone + ArgminAdd<P, P> + ArgminMul<F, P> + ArgminAxpy<P, F>,
+    G: Clone + ArgminMul<F, P> + ArgminDot<G, F> + ArgminDot<P, F> + ArgminL2Norm<F>,
     L: Clone + LineSearch<P, F> + Solver<O, IterState<P, G, (), (), (), F>>,
     B: NLCGBetaUpdate<G, P, F>,
     F: ArgminFloat,
@@ -135,8 +281,9 @@ where
     fn init(
         &mut self,
         problem: &mut Problem<O>,
-        state: IterState<P, G, (), (), (), F>,
+        mut state: IterState<P, G, (), (), (), F>,
     ) -> Result<(IterState<P, G, (), (), (), F>, Option<KV>), Error> {
+        let grad = state.take_gradient();
         let param = state.get_param().ok_or_else(argmin_error_closure!(
             NotInitialized,
             concat!(
@@ -144,10 +291,32 @@ where
                 "Please provide an initial guess via `Executor`s `configure` method."
             )
         ))?;
-        let cost = problem.cost(param)?;
-        let grad = problem.gradient(param)?;
+        let policy = state.get_initial_evaluation();
+        // The cost is computed by the line search if it is missing
+        let cost = state.get_cost();
+        let cost = policy
+            .evaluate_lazily((!cost.is_infinite()).then_some(cost), || {
+                problem.cost(param)
+            })?
+            .unwrap_or(cost);
+        let grad = policy.evaluate(grad, || problem.gradient(param))?;
         self.p = Some(grad.mul(&(float!(-1.0))));
-        Ok((state.cost(cost).gradient(grad), None))
+        let mut config = kv!(
+            "linesearch" => self.linesearch.name();
+            "beta_method" => std::any::type_name::<B>();
+            "restart_iters" => self.restart_iter;
+        );
+        if let Some(restart_orthogonality) = self.restart_orthogonality {
+            config.insert("restart_orthogonality", restart_orthogonality.into());
+        }
+        config.insert(
+            "linesearch_recovery",
+            format!("{:?}", self.linesearch_recovery).into(),
+        );
+        Ok((
+            state.cost(cost).gradient(grad),
+            Some(config.with_prefix("config")),
+        ))
     }
 
     fn next_iter(
@@ -155,7 +324,9 @@ where
         problem: &mut Problem<O>,
         mut state: IterState<P, G, (), (), (), F>,
     ) -> Result<(IterState<P, G, (), (), (), F>, Option<KV>), Error> {
-        let p = self.p.as_ref().ok_or_else(argmin_error_closure!(
+        // The search direction is moved out instead of being cloned. It is replaced at the end of
+        // the iteration.
+        let mut p = self.p.take().ok_or_else(argmin_error_closure!(
             PotentialBug,
             "`NonlinearConjugateGradient`: Field `p` not set"
         ))?;
@@ -169,32 +340,122 @@ where
             .unwrap_or_else(|| problem.gradient(&xk))?;
         let cur_cost = state.cost;
 
-        // Linesearch
-        self.linesearch.search_direction(p.clone());
-
-        // Run solver
-        let OptimizationResult {
-            problem: line_problem,
-            state: mut line_state,
-            ..
-        } = Executor::new(
-            problem.take_problem().ok_or_else(argmin_error_closure!(
-                PotentialBug,
-                "`NonlinearConjugateGradient`: Failed to take `problem` for line search"
-            ))?,
-            self.linesearch.clone(),
-        )
-        .configure(|state| state.param(xk).gradient(grad.clone()).cost(cur_cost))
-        .ctrlc(false)
-        .run()?;
-
-        // takes care of the counts of function evaluations
-        problem.consume_problem(line_problem);
-
-        let xk1 = line_state.take_param().ok_or_else(argmin_error_closure!(
+        // Run line search. If requested, the conjugate direction is discarded once after a
+        // failure and the line search is repeated along the steepest descent direction.
+        let mut line_problem = problem.take_problem().ok_or_else(argmin_error_closure!(
             PotentialBug,
-            "`NonlinearConjugateGradient`: No `param` returned by line search"
+            "`NonlinearConjugateGradient`: Failed to take `problem` for line search"
         ))?;
+        let mut memory_reset = false;
+        let linesearch_result = loop {
+            self.linesearch.search_direction(p.clone());
+            let result = Executor::new(line_problem, self.linesearch.clone())
+                .configure(|state| {
+                    state
+                        .param(xk.clone())
+                        .gradient(grad.clone())
+                        .cost(cur_cost)
+                })
+                .try_run_nested();
+            match result {
+                Err((_, mut failed_problem))
+                    if self.linesearch_recovery == LineSearchRecovery::ResetMemory
+                        && !memory_reset =>
+                {
+                    p = grad.mul(&(float!(-1.0)));
+                    line_problem = failed_problem.take_problem().unwrap();
+                    problem.consume_func_counts(failed_problem);
+                    memory_reset = true;
+                }
+                result => break result,
+            }
+        };
+
+        let mut recovered = memory_reset;
+        let mut stationary = false;
+        let (xk1, linesearch_kv) = match linesearch_result {
+            Ok((
+                OptimizationResult {
+                    problem: line_problem,
+                    state: mut line_state,
+                    ..
+                },
+                linesearch_kv,
+            )) => {
+                // takes care of the counts of function evaluations
+                problem.consume_problem(line_problem);
+                let linesearch_kv = self.inner_telemetry.record(
+                    problem,
+                    "linesearch_iter_count",
+                    &line_state,
+                    linesearch_kv,
+                );
+
+                let xk1 = line_state.take_param().ok_or_else(argmin_error_closure!(
+                    PotentialBug,
+                    "`NonlinearConjugateGradient`: No `param` returned by line search"
+                ))?;
+                stationary = line_state.get_termination_reason()
+                    == Some(&TerminationReason::StationaryDirection);
+                (xk1, linesearch_kv)
+            }
+            Err((e, mut line_problem)) => {
+                let step = self.linesearch_recovery.fallback_step(
+                    &mut line_problem,
+                    &xk,
+                    cur_cost,
+                    &grad,
+                    &p,
+                );
+                problem.consume_problem(line_problem);
+                match step? {
+                    Some((xk1, _)) => {
+                        recovered = true;
+                        (xk1, None)
+                    }
+                    None => {
+                        self.p = Some(p);
+                        return Ok((
+                            state.param(xk).gradient(grad).terminate_with(
+                                TerminationReason::LineSearchFailed(e.root_cause().to_string()),
+                            ),
+                            None,
+                        ));
+                    }
+                }
+            }
+        };
+        let linesearch_kv =
+            if recovered {
+                Some(linesearch_kv.unwrap_or_default().merge(
+                    kv!("linesearch_recovery" => format!("{:?}", self.linesearch_recovery);),
+                ))
+            } else {
+                linesearch_kv
+            };
+
+        // No step was taken because `xk` is stationary along `p`. Unless `p` already was the
+        // steepest descent direction, the next iteration restarts along it. Otherwise the gradient
+        // vanishes and there is nothing left to do.
+        if stationary {
+            let steepest_descent = memory_reset || self.beta.is_nan() || self.beta == float!(0.0);
+            self.beta = float!(0.0);
+            self.p = Some(grad.mul(&(float!(-1.0))));
+            let state = state.param(xk1).cost(cur_cost).gradient(grad);
+            return Ok((
+                if steepest_descent {
+                    state.terminate_with(TerminationReason::StationaryDirection)
+                } else {
+                    state
+                },
+                Some(
+                    kv!("beta" => self.beta;
+                     "restart_stationary" => true;
+                    )
+                    .merge(linesearch_kv.unwrap_or_default()),
+                ),
+            ));
+        }
 
         // Update of beta
         let new_grad = problem.gradient(&xk1)?;
@@ -210,21 +471,26 @@ where
         if restart_iter || restart_orthogonality {
             self.beta = float!(0.0);
         } else {
-            self.beta = self.beta_method.update(&grad, &new_grad, p);
+            self.beta = self.beta_method.update(&grad, &new_grad, &p);
         }
 
         // Update of p
-        self.p = Some(new_grad.mul(&(float!(-1.0))).add(&p.mul(&self.beta)));
+        let mut new_p = new_grad.mul(&(float!(-1.0)));
+        new_p.axpy(&self.beta, &p);
+        self.p = Some(new_p);
 
         // Housekeeping
         let cost = problem.cost(&xk1)?;
 
         Ok((
             state.param(xk1).cost(cost).gradient(new_grad),
-            Some(kv!("beta" => self.beta;
-             "restart_iter" => restart_iter;
-             "restart_orthogonality" => restart_orthogonality;
-            )),
+            Some(
+                kv!("beta" => self.beta;
+                 "restart_iter" => restart_iter;
+                 "restart_orthogonality" => restart_orthogonality;
+                )
+                .merge(linesearch_kv.unwrap_or_default()),
+            ),
         ))
     }
 }
@@ -233,11 +499,11 @@ where
 #[allow(clippy::let_unit_value)]
 mod tests {
     use super::*;
-    use crate::core::test_utils::TestProblem;
-    use crate::core::ArgminError;
+    use crate::core::test_utils::{TestProblem, TestSparseProblem};
+    use crate::core::{ArgminError, TerminationStatus};
     use crate::solver::conjugategradient::beta::PolakRibiere;
     use crate::solver::linesearch::{
-        condition::ArmijoCondition, BacktrackingLineSearch, MoreThuenteLineSearch,
+        condition::ArmijoCondition, BacktrackingLineSearch, FlakyLineSearch, MoreThuenteLineSearch,
     };
     use approx::assert_relative_eq;
 
@@ -270,13 +536,17 @@ mod tests {
             beta_method,
             restart_iter,
             restart_orthogonality,
+            inner_telemetry,
+            linesearch_recovery,
         } = nlcg;
+        assert_eq!(inner_telemetry, NestedTelemetry::new());
         assert!(p.is_none());
         assert!(beta.is_nan());
         assert_eq!(linesearch, linesearch);
         assert_eq!(beta_method, beta_method);
         assert_eq!(restart_iter, u64::MAX);
         assert!(restart_orthogonality.is_none());
+        assert_eq!(linesearch_recovery, LineSearchRecovery::Stop);
     }
 
     #[test]
@@ -304,6 +574,35 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_config_build() {
+        let config = NonlinearConjugateGradientConfig {
+            restart_iters: Some(100),
+            restart_orthogonality: Some(0.1),
+            linesearch_recovery: Some(LineSearchRecovery::ResetMemory),
+        };
+        let nlcg: NonlinearConjugateGradient<Vec<f64>, _, _, f64> =
+            config.build(Linesearch {}, BetaUpdate {}).unwrap();
+        assert_eq!(nlcg.restart_iter, 100);
+        assert_eq!(
+            nlcg.restart_orthogonality.as_ref().unwrap().to_ne_bytes(),
+            0.1f64.to_ne_bytes()
+        );
+        assert_eq!(nlcg.linesearch_recovery, LineSearchRecovery::ResetMemory);
+
+        let config = NonlinearConjugateGradientConfig {
+            restart_orthogonality: Some(0.0),
+            ..Default::default()
+        };
+        let res: Result<NonlinearConjugateGradient<Vec<f64>, _, _, f64>, _> =
+            config.build(Linesearch {}, BetaUpdate {});
+        assert_error!(
+            res,
+            ArgminError,
+            "Invalid parameter: \"`NonlinearConjugateGradient`: orthogonality measure must be > 0.\""
+        );
+    }
+
     #[test]
     fn test_init_param_not_initialized() {
         let linesearch: BacktrackingLineSearch<Vec<f64>, Vec<f64>, ArmijoCondition<f64>, f64> =
@@ -334,7 +633,16 @@ mod tests {
         let (state_out, kv) = nlcg
             .init(&mut Problem::new(TestProblem::new()), state.clone())
             .unwrap();
-        assert!(kv.is_none());
+        assert_eq!(
+            kv.unwrap(),
+            kv!(
+                "linesearch" => "Backtracking line search";
+                "beta_method" => std::any::type_name::<PolakRibiere>();
+                "restart_iters" => u64::MAX;
+                "linesearch_recovery" => "Stop";
+            )
+            .with_prefix("config")
+        );
         assert_ne!(state_out, state);
         assert_eq!(state_out.cost.to_ne_bytes(), 1f64.to_ne_bytes());
         assert_eq!(
@@ -443,8 +751,7 @@ mod tests {
             .param(vec![1.0f64, 2.0])
             .gradient(vec![1.0f64, 2.0]);
         let mut problem = Problem::new(TestProblem::new());
-        let (state, kv) = nlcg.init(&mut problem, state).unwrap();
-        assert!(kv.is_none());
+        let (state, _) = nlcg.init(&mut problem, state).unwrap();
         let (mut state, kv) = nlcg.next_iter(&mut problem, state).unwrap();
         state.update();
         let kv2 = kv!("beta" => 0.0; "restart_iter" => false; "restart_orthogonality" => false;);
@@ -483,4 +790,145 @@ mod tests {
             epsilon = f64::EPSILON
         );
     }
+
+    #[test]
+    fn test_next_iter_stationary() {
+        let mut nlcg: NonlinearConjugateGradient<Vec<f64>, _, _, f64> =
+            NonlinearConjugateGradient::new(MoreThuenteLineSearch::new(), PolakRibiere::new());
+        let mut problem = Problem::new(TestProblem::new());
+        let state = IterState::new().param(vec![1.0f64, 0.0]);
+        let (state, _) = nlcg.init(&mut problem, state).unwrap();
+        // Pretend that the conjugate direction is orthogonal to the gradient
+        nlcg.p = Some(vec![0.0, 1.0]);
+        nlcg.beta = 0.5;
+        let (state, kv) = nlcg.next_iter(&mut problem, state).unwrap();
+
+        // Restart along the steepest descent direction
+        assert!(!state.terminated());
+        assert_eq!(state.param.as_ref().unwrap(), &vec![1.0, 0.0]);
+        assert_eq!(nlcg.p.as_ref().unwrap(), &vec![-1.0, 0.0]);
+        assert_eq!(nlcg.beta.to_ne_bytes(), 0.0f64.to_ne_bytes());
+        assert_eq!(kv.unwrap().get("restart_stationary"), Some(&true.into()));
+
+        // The steepest descent direction is stationary as well
+        let state = IterState::new().param(vec![0.0f64, 0.0]);
+        let (state, _) = nlcg.init(&mut problem, state).unwrap();
+        let (state, _) = nlcg.next_iter(&mut problem, state).unwrap();
+        assert_eq!(
+            state.termination_status,
+            TerminationStatus::Terminated(TerminationReason::StationaryDirection)
+        );
+    }
+
+    #[test]
+    fn test_with_linesearch_recovery() {
+        let nlcg: NonlinearConjugateGradient<Vec<f64>, _, _, f64> =
+            NonlinearConjugateGradient::new(Linesearch {}, BetaUpdate {})
+                .with_linesearch_recovery(LineSearchRecovery::SmallStep(0.1))
+                .unwrap();
+        assert_eq!(nlcg.linesearch_recovery, LineSearchRecovery::SmallStep(0.1));
+
+        let res: Result<NonlinearConjugateGradient<Vec<f64>, _, _, f64>, _> =
+            NonlinearConjugateGradient::new(Linesearch {}, BetaUpdate {})
+                .with_linesearch_recovery(LineSearchRecovery::SmallStep(0.0));
+        assert_error!(
+            res,
+            ArgminError,
+            concat!(
+                "Invalid parameter: \"`NonlinearConjugateGradient`: step length of line search ",
+                "recovery must be > 0.\""
+            )
+        );
+    }
+
+    #[test]
+    fn test_linesearch_failure_stop() {
+        let nlcg: NonlinearConjugateGradient<Vec<f64>, _, _, f64> =
+            NonlinearConjugateGradient::new(FlakyLineSearch::new(vec![2]), PolakRibiere::new());
+
+        let res = Executor::new(TestSparseProblem::new(), nlcg)
+            .configure(|state| state.param(vec![0.0; 4]).max_iters(10))
+            .run()
+            .unwrap();
+
+        assert_eq!(
+            res.state.termination_status,
+            TerminationStatus::Terminated(TerminationReason::LineSearchFailed(
+                "Condition violated: \"search failed\"".to_string()
+            ))
+        );
+        assert_eq!(res.state.param.unwrap(), vec![1.0, 0.5, -1.0, -0.5]);
+        assert!(res.problem.problem.is_some());
+    }
+
+    #[test]
+    fn test_linesearch_failure_fallback_step() {
+        for policy in [
+            LineSearchRecovery::Backtracking,
+            LineSearchRecovery::SmallStep(0.25),
+        ] {
+            let nlcg: NonlinearConjugateGradient<Vec<f64>, _, _, f64> =
+                NonlinearConjugateGradient::new(FlakyLineSearch::new(vec![1]), PolakRibiere::new())
+                    .with_linesearch_recovery(policy)
+                    .unwrap();
+
+            let res = Executor::new(TestSparseProblem::new(), nlcg)
+                .configure(|state| state.param(vec![0.0; 4]).max_iters(1))
+                .run()
+                .unwrap();
+
+            assert_eq!(
+                res.state.termination_status,
+                TerminationStatus::Terminated(TerminationReason::MaxItersReached)
+            );
+            assert_eq!(res.state.param.unwrap(), vec![1.0, 0.5, -1.0, -0.5]);
+            assert_eq!(res.state.cost.to_ne_bytes(), 0.5f64.to_ne_bytes());
+        }
+    }
+
+    #[test]
+    fn test_linesearch_failure_reset_memory() {
+        let linesearch = FlakyLineSearch::new(vec![2]);
+        let attempts = linesearch.attempts.clone();
+        let mut nlcg: NonlinearConjugateGradient<Vec<f64>, _, _, f64> =
+            NonlinearConjugateGradient::new(linesearch, PolakRibiere::new())
+                .with_linesearch_recovery(LineSearchRecovery::ResetMemory)
+                .unwrap();
+        let mut problem = Problem::new(TestSparseProblem::new());
+        let state = IterState::new().param(vec![0.0f64; 4]);
+        let (state, _) = nlcg.init(&mut problem, state).unwrap();
+        let (state, _) = nlcg.next_iter(&mut problem, state).unwrap();
+        // Pretend that the previous direction was not a descent direction anymore
+        nlcg.p = Some(vec![1.0; 4]);
+        let (state, kv) = nlcg.next_iter(&mut problem, state).unwrap();
+
+        assert!(!state.terminated());
+        assert_eq!(attempts.get(), 3);
+        // The line search was repeated along the steepest descent direction
+        assert_eq!(state.param.unwrap(), vec![0.75, 0.25, -0.75, -0.25]);
+        assert_eq!(
+            kv.unwrap().get("linesearch_recovery"),
+            Some(&"ResetMemory".into())
+        );
+    }
+
+    #[test]
+    fn test_complex_param() {
+        use crate::core::test_utils::TestComplexProblem;
+        use num_complex::Complex64;
+
+        let nlcg: NonlinearConjugateGradient<Vec<Complex64>, _, _, f64> =
+            NonlinearConjugateGradient::new(MoreThuenteLineSearch::new(), PolakRibiere::new());
+        let res = Executor::new(TestComplexProblem {}, nlcg)
+            .configure(|state| state.param(vec![Complex64::new(0.0, 0.0); 2]).max_iters(20))
+            .run()
+            .unwrap();
+
+        let param = res.state.get_best_param().unwrap();
+        assert_relative_eq!(param[0].re, 0.5, epsilon = 1e-6);
+        assert_relative_eq!(param[0].im, 0.5, epsilon = 1e-6);
+        assert_relative_eq!(param[1].re, -0.5, epsilon = 1e-6);
+        assert_relative_eq!(param[1].im, 0.0, epsilon = 1e-6);
+        assert_relative_eq!(res.state.get_best_cost(), -2.0, epsilon = 1e-10);
+    }
 }
@@ -6,10 +6,15 @@
 // copied, modified, or distributed except according to those terms.
 
 use crate::core::{
-    ArgminFloat, CostFunction, Error, Executor, Gradient, IterState, Jacobian, LineSearch,
-    Operator, OptimizationResult, Problem, Solver, TerminationReason, TerminationStatus, KV,
+    ArgminFloat, CostFunction, Error, Executor, Gradient, IterState, Jacobian,
+    JacobianVectorProduct, LineSearch, NestedTelemetry, Operator, OptimizationResult, Problem,
+    Solver, TerminationReason, TerminationStatus, KV,
+};
+use crate::solver::gaussnewton::{DenseJacobian, JacobianFreeCG};
+use argmin_math::{
+    ArgminConj, ArgminDot, ArgminInv, ArgminL2Norm, ArgminMul, ArgminScaledAdd, ArgminSub,
+    ArgminTranspose, ArgminZeroLike,
 };
-use argmin_math::{ArgminDot, ArgminInv, ArgminL2Norm, ArgminMul, ArgminTranspose};
 #[cfg(feature = "serde1")]
 use serde::{Deserialize, Serialize};
 
@@ -19,9 +24,17 @@ use serde::{Deserialize, Serialize};
 ///
 /// Requires an initial parameter vector.
 ///
+/// By default, the normal equations are solved by inverting `J^T J` ([`DenseJacobian`]). For
+/// problems where the dense Jacobian does not fit into memory, the normal equations can instead
+/// be solved with the conjugate gradient method, which only requires products of the Jacobian
+/// with vectors ([`JacobianFreeCG`], enabled via
+/// [`jacobian_free`](`GaussNewtonLS::jacobian_free`)). In this mode, the number of CG iterations
+/// is reported as `cg_iters` and counted as `cg_iter_count`.
+///
 /// ## Requirements on the optimization problem
 ///
-/// The optimization problem is required to implement [`Operator`] and [`Jacobian`].
+/// The optimization problem is required to implement [`Operator`] and [`Jacobian`], or
+/// [`Operator`] and [`JacobianVectorProduct`] in the Jacobian-free mode.
 ///
 /// ## Reference
 ///
@@ -29,11 +42,17 @@ use serde::{Deserialize, Serialize};
 /// Springer. ISBN 0-387-30303-0.
 #[derive(Clone)]
 #[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
-pub struct GaussNewtonLS<L, F> {
+pub struct GaussNewtonLS<L, F, M = DenseJacobian> {
     /// linesearch
     linesearch: L,
     /// Tolerance for the stopping criterion based on cost difference
     tol: F,
+    /// Telemetry of the line search
+    #[cfg_attr(feature = "serde1", serde(default))]
+    inner_telemetry: NestedTelemetry,
+    /// Solver for the normal equations
+    #[cfg_attr(feature = "serde1", serde(default))]
+    normal_equations: M,
 }
 
 impl<L, F: ArgminFloat> GaussNewtonLS<L, F> {
@@ -50,9 +69,58 @@ impl<L, F: ArgminFloat> GaussNewtonLS<L, F> {
         GaussNewtonLS {
             linesearch,
             tol: F::epsilon().sqrt(),
+            inner_telemetry: NestedTelemetry::new(),
+            normal_equations: DenseJacobian,
+        }
+    }
+}
+
+impl<L, F: ArgminFloat, M> GaussNewtonLS<L, F, M> {
+    /// Solve the normal equations without forming the Jacobian
+    ///
+    /// The problem is then required to implement [`JacobianVectorProduct`] instead of
+    /// [`Jacobian`]. See [`JacobianFreeCG`] for details.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::gaussnewton::{GaussNewtonLS, JacobianFreeCG};
+    /// # let linesearch = ();
+    /// let solver = GaussNewtonLS::<_, f64>::new(linesearch)
+    ///     .jacobian_free(JacobianFreeCG::new().with_max_iters(100));
+    /// ```
+    pub fn jacobian_free(
+        self,
+        normal_equations: JacobianFreeCG<F>,
+    ) -> GaussNewtonLS<L, F, JacobianFreeCG<F>> {
+        GaussNewtonLS {
+            linesearch: self.linesearch,
+            tol: self.tol,
+            inner_telemetry: self.inner_telemetry,
+            normal_equations,
         }
     }
 
+    /// Forward the telemetry of the line search to observers
+    ///
+    /// The key-value pairs returned by the line search as well as its number of iterations and
+    /// its termination reason are forwarded with keys prefixed by `prefix` (for instance
+    /// `linesearch.iters`). Independent of this setting, the line search iterations are counted
+    /// as `linesearch_iter_count`. See [`NestedTelemetry`] for details.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::gaussnewton::GaussNewtonLS;
+    /// # let linesearch = ();
+    /// let solver = GaussNewtonLS::<_, f64>::new(linesearch).with_inner_telemetry("linesearch");
+    /// ```
+    #[must_use]
+    pub fn with_inner_telemetry<T: AsRef<str>>(mut self, prefix: T) -> Self {
+        self.inner_telemetry = NestedTelemetry::forward(prefix);
+        self
+    }
+
     /// Set tolerance for the stopping criterion based on cost difference.
     ///
     /// Tolerance must be larger than zero and defaults to `sqrt(EPSILON)`.
@@ -80,7 +148,46 @@ impl<L, F: ArgminFloat> GaussNewtonLS<L, F> {
     }
 }
 
-impl<O, L, F, P, G, J, U, R> Solver<O, IterState<P, G, J, (), R, F>> for GaussNewtonLS<L, F>
+/// Serializable configuration of [`GaussNewtonLS`]
+///
+/// Options which are `None` keep the defaults of [`GaussNewtonLS`]. The configuration is validated
+/// when the solver is constructed via [`build`](`GaussNewtonLSConfig::build`).
+///
+/// # Example
+///
+/// ```
+/// # use argmin::solver::gaussnewton::{GaussNewtonLS, GaussNewtonLSConfig};
+/// # use argmin::core::Error;
+/// # fn main() -> Result<(), Error> {
+/// # let linesearch = ();
+/// let config = GaussNewtonLSConfig {
+///     tolerance: Some(1e-4f64),
+/// };
+/// let gauss_newton_ls: GaussNewtonLS<_, f64> = config.build(linesearch)?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize), serde(default))]
+pub struct GaussNewtonLSConfig<F> {
+    /// See [`GaussNewtonLS::with_tolerance`]
+    pub tolerance: Option<F>,
+}
+
+impl<F: ArgminFloat> GaussNewtonLSConfig<F> {
+    /// Validates the configuration and constructs a [`GaussNewtonLS`] solver using the given line
+    /// search.
+    pub fn build<L>(&self, linesearch: L) -> Result<GaussNewtonLS<L, F>, Error> {
+        let mut solver = GaussNewtonLS::new(linesearch);
+        if let Some(tol) = self.tolerance {
+            solver = solver.with_tolerance(tol)?;
+        }
+        Ok(solver)
+    }
+}
+
+impl<O, L, F, P, G, J, U, R> Solver<O, IterState<P, G, J, (), R, F>>
+    for GaussNewtonLS<L, F, DenseJacobian>
 where
     O: Operator<Param = P, Output = U> + Jacobian<Param = P, Jacobian = J>,
     P: Clone + ArgminMul<F, P>,
@@ -100,6 +207,23 @@ where
         "Gauss-Newton method with line search"
     }
 
+    fn init(
+        &mut self,
+        _problem: &mut Problem<O>,
+        state: IterState<P, G, J, (), R, F>,
+    ) -> Result<(IterState<P, G, J, (), R, F>, Option<KV>), Error> {
+        Ok((
+            state,
+            Some(
+                kv!(
+                    "linesearch" => self.linesearch.name();
+                    "tolerance" => self.tol;
+                )
+                .with_prefix("config"),
+            ),
+        ))
+    }
+
     fn next_iter(
         &mut self,
         problem: &mut Problem<O>,
@@ -122,11 +246,14 @@ where
         self.linesearch.search_direction(p.mul(&(float!(-1.0))));
 
         // perform linesearch
-        let OptimizationResult {
-            problem: mut line_problem,
-            state: mut linesearch_state,
-            ..
-        } = Executor::new(
+        let (
+            OptimizationResult {
+                problem: mut line_problem,
+                state: mut linesearch_state,
+                ..
+            },
+            linesearch_kv,
+        ) = Executor::new(
             LineSearchProblem::new(problem.take_problem().ok_or_else(argmin_error_closure!(
                 PotentialBug,
                 "`GaussNewtonLS`: Failed to take `problem` for line search"
@@ -134,8 +261,7 @@ where
             self.linesearch.clone(),
         )
         .configure(|config| config.param(param).gradient(grad).cost(residuals.l2_norm()))
-        .ctrlc(false)
-        .run()?;
+        .run_nested()?;
 
         // Here we cannot use `consume_problem` because the problem we need is hidden inside a
         // `LineSearchProblem` hidden inside a `Problem`. Therefore we have to split this in two
@@ -150,6 +276,12 @@ where
                 .problem,
         );
         problem.consume_func_counts(line_problem);
+        let kv = self.inner_telemetry.record(
+            problem,
+            "linesearch_iter_count",
+            &linesearch_state,
+            linesearch_kv,
+        );
 
         Ok((
             state
@@ -162,7 +294,7 @@ where
                         ))?,
                 )
                 .cost(linesearch_state.get_cost()),
-            None,
+            kv,
         ))
     }
 
@@ -174,6 +306,134 @@ where
     }
 }
 
+impl<O, L, F, P, U, R> Solver<O, IterState<P, P, (), (), R, F>>
+    for GaussNewtonLS<L, F, JacobianFreeCG<F>>
+where
+    O: Operator<Param = P, Output = U> + JacobianVectorProduct<Param = P, Output = U>,
+    P: Clone
+        + ArgminDot<P, F>
+        + ArgminSub<P, P>
+        + ArgminScaledAdd<P, F, P>
+        + ArgminMul<F, P>
+        + ArgminConj
+        + ArgminZeroLike
+        + ArgminL2Norm<F>,
+    U: ArgminL2Norm<F>,
+    L: Clone
+        + LineSearch<P, F>
+        + Solver<JacobianFreeLineSearchProblem<O, F>, IterState<P, P, (), (), R, F>>,
+    F: ArgminFloat + ArgminL2Norm<F>,
+    R: Clone,
+{
+    fn name(&self) -> &str {
+        "Gauss-Newton method with line search"
+    }
+
+    fn init(
+        &mut self,
+        _problem: &mut Problem<O>,
+        state: IterState<P, P, (), (), R, F>,
+    ) -> Result<(IterState<P, P, (), (), R, F>, Option<KV>), Error> {
+        Ok((
+            state,
+            Some(
+                kv!(
+                    "linesearch" => self.linesearch.name();
+                    "tolerance" => self.tol;
+                    "jacobian_free" => true;
+                    "cg_tolerance" => self.normal_equations.tol;
+                    "cg_max_iters" => self.normal_equations.max_iters;
+                )
+                .with_prefix("config"),
+            ),
+        ))
+    }
+
+    fn next_iter(
+        &mut self,
+        problem: &mut Problem<O>,
+        mut state: IterState<P, P, (), (), R, F>,
+    ) -> Result<(IterState<P, P, (), (), R, F>, Option<KV>), Error> {
+        let param = state.take_param().ok_or_else(argmin_error_closure!(
+            NotInitialized,
+            concat!(
+                "`GaussNewtonLS` requires an initial parameter vector. ",
+                "Please provide an initial guess via `Executor`s `configure` method."
+            )
+        ))?;
+        let residuals = problem.apply(&param)?;
+        let grad = problem.vjp(&param, &residuals)?;
+
+        let (p, cg_iters) = self.normal_equations.solve(problem, &param, &grad)?;
+        *problem.counts.entry("cg_iter_count").or_insert(0) += cg_iters;
+
+        self.linesearch.search_direction(p.mul(&(float!(-1.0))));
+
+        // perform linesearch
+        let (
+            OptimizationResult {
+                problem: mut line_problem,
+                state: mut linesearch_state,
+                ..
+            },
+            linesearch_kv,
+        ) = Executor::new(
+            JacobianFreeLineSearchProblem::new(problem.take_problem().ok_or_else(
+                argmin_error_closure!(
+                    PotentialBug,
+                    "`GaussNewtonLS`: Failed to take `problem` for line search"
+                ),
+            )?),
+            self.linesearch.clone(),
+        )
+        .configure(|config| config.param(param).gradient(grad).cost(residuals.l2_norm()))
+        .run_nested()?;
+
+        // See the `DenseJacobian` implementation for why `consume_problem` cannot be used here.
+        problem.problem = Some(
+            line_problem
+                .take_problem()
+                .ok_or_else(argmin_error_closure!(
+                    PotentialBug,
+                    "`GaussNewtonLS`: Failed to take `problem` from line search"
+                ))?
+                .problem,
+        );
+        problem.consume_func_counts(line_problem);
+        let cg_kv = kv!("cg_iters" => cg_iters;);
+        let kv = match self.inner_telemetry.record(
+            problem,
+            "linesearch_iter_count",
+            &linesearch_state,
+            linesearch_kv,
+        ) {
+            Some(kv) => kv.merge(cg_kv),
+            None => cg_kv,
+        };
+
+        Ok((
+            state
+                .param(
+                    linesearch_state
+                        .take_param()
+                        .ok_or_else(argmin_error_closure!(
+                            PotentialBug,
+                            "`GaussNewtonLS`: Failed to take `param` from line search state"
+                        ))?,
+                )
+                .cost(linesearch_state.get_cost()),
+            Some(kv),
+        ))
+    }
+
+    fn terminate(&mut self, state: &IterState<P, P, (), (), R, F>) -> TerminationStatus {
+        if (state.get_prev_cost() - state.get_cost()).abs() < self.tol {
+            return TerminationStatus::Terminated(TerminationReason::SolverConverged);
+        }
+        TerminationStatus::NotTerminated
+    }
+}
+
 #[doc(hidden)]
 #[derive(Clone, Default)]
 #[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
@@ -220,6 +480,50 @@ where
     }
 }
 
+#[doc(hidden)]
+#[derive(Clone, Default)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+struct JacobianFreeLineSearchProblem<O, F> {
+    problem: O,
+    _phantom: std::marker::PhantomData<F>,
+}
+
+impl<O, F> JacobianFreeLineSearchProblem<O, F> {
+    /// Construct a new [`JacobianFreeLineSearchProblem`]
+    fn new(operator: O) -> Self {
+        JacobianFreeLineSearchProblem {
+            problem: operator,
+            _phantom: std::marker::PhantomData,
+        }
+    }
+}
+
+impl<O, P, U, F> CostFunction for JacobianFreeLineSearchProblem<O, F>
+where
+    O: Operator<Param = P, Output = U>,
+    U: ArgminL2Norm<F>,
+    F: ArgminFloat,
+{
+    type Param = P;
+    type Output = F;
+
+    fn cost(&self, p: &Self::Param) -> Result<Self::Output, Error> {
+        Ok(self.problem.apply(p)?.l2_norm())
+    }
+}
+
+impl<O, P, U, F> Gradient for JacobianFreeLineSearchProblem<O, F>
+where
+    O: Operator<Param = P, Output = U> + JacobianVectorProduct<Param = P, Output = U>,
+{
+    type Param = P;
+    type Gradient = P;
+
+    fn gradient(&self, p: &Self::Param) -> Result<Self::Gradient, Error> {
+        self.problem.vjp(p, &self.problem.apply(p)?)
+    }
+}
+
 #[cfg(test)]
 #[allow(clippy::let_unit_value)]
 mod tests {
@@ -244,10 +548,66 @@ mod tests {
         let GaussNewtonLS {
             linesearch: ls,
             tol: t,
+            inner_telemetry,
+            normal_equations,
         } = GaussNewtonLS::<_, f64>::new(MyLinesearch {});
 
         assert_eq!(ls, MyLinesearch {});
         assert_eq!(t.to_ne_bytes(), f64::EPSILON.sqrt().to_ne_bytes());
+        assert_eq!(inner_telemetry, NestedTelemetry::new());
+        assert_eq!(normal_equations, DenseJacobian);
+    }
+
+    #[test]
+    fn test_jacobian_free() {
+        #[derive(Eq, PartialEq, Debug)]
+        struct MyLinesearch {}
+
+        let GaussNewtonLS {
+            linesearch: ls,
+            tol: t,
+            inner_telemetry,
+            normal_equations,
+        } = GaussNewtonLS::<_, f64>::new(MyLinesearch {})
+            .with_tolerance(1e-4)
+            .unwrap()
+            .with_inner_telemetry("linesearch")
+            .jacobian_free(JacobianFreeCG::new().with_max_iters(10));
+
+        assert_eq!(ls, MyLinesearch {});
+        assert_eq!(t.to_ne_bytes(), 1e-4f64.to_ne_bytes());
+        assert_eq!(inner_telemetry, NestedTelemetry::forward("linesearch"));
+        assert_eq!(normal_equations, JacobianFreeCG::new().with_max_iters(10));
+    }
+
+    #[test]
+    fn test_jacobian_free_solver() {
+        use crate::core::{KvValue, State};
+        use crate::solver::gaussnewton::normal_equations::tests::LinearResiduals;
+
+        let linesearch = BacktrackingLineSearch::new(ArmijoCondition::new(1e-4).unwrap());
+        let solver = GaussNewtonLS::new(linesearch).jacobian_free(JacobianFreeCG::new());
+
+        let (res, kv) = Executor::new(LinearResiduals::new(), solver)
+            .configure(|state| state.param(vec![0.0f64, 0.0]).max_iters(10))
+            .run_nested()
+            .unwrap();
+
+        // Least squares solution of `A x = b`
+        let param = res.state.get_best_param().unwrap();
+        assert!((param[0] - 5.0 / 6.0).abs() < 1e-8);
+        assert!((param[1] - 1.5).abs() < 1e-8);
+        assert_eq!(
+            res.state.get_termination_reason(),
+            Some(&TerminationReason::SolverConverged)
+        );
+        assert_eq!(kv.get("config.jacobian_free"), Some(&KvValue::Bool(true)));
+        assert!(res.problem.counts["jvp_count"] > 0);
+        assert_eq!(
+            res.problem.counts["jvp_count"],
+            res.problem.counts["cg_iter_count"] + res.state.get_iter()
+        );
+        assert!(!res.problem.counts.contains_key("jacobian_count"));
     }
 
     #[test]
@@ -283,6 +643,26 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_config_build() {
+        let GaussNewtonLS { tol: t, .. } = GaussNewtonLSConfig {
+            tolerance: Some(1e-4f64),
+        }
+        .build(())
+        .unwrap();
+        assert_eq!(t.to_ne_bytes(), 1e-4f64.to_ne_bytes());
+
+        let error = GaussNewtonLSConfig {
+            tolerance: Some(0.0f64),
+        }
+        .build(());
+        assert_error!(
+            error,
+            ArgminError,
+            "Invalid parameter: \"Gauss-Newton-Linesearch: tol must be positive.\""
+        );
+    }
+
     #[cfg(feature = "_ndarrayl")]
     #[test]
     fn test_line_search_sub_problem() {
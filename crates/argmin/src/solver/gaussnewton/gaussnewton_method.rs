@@ -109,6 +109,48 @@ impl<F: ArgminFloat> Default for GaussNewton<F> {
     }
 }
 
+/// Serializable configuration of [`GaussNewton`]
+///
+/// Options which are `None` keep the defaults of [`GaussNewton`]. The configuration is validated
+/// when the solver is constructed via [`build`](`GaussNewtonConfig::build`).
+///
+/// # Example
+///
+/// ```
+/// # use argmin::solver::gaussnewton::{GaussNewton, GaussNewtonConfig};
+/// # use argmin::core::Error;
+/// # fn main() -> Result<(), Error> {
+/// let config = GaussNewtonConfig {
+///     gamma: Some(0.5f64),
+///     ..Default::default()
+/// };
+/// let gauss_newton: GaussNewton<f64> = config.build()?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize), serde(default))]
+pub struct GaussNewtonConfig<F> {
+    /// See [`GaussNewton::with_gamma`]
+    pub gamma: Option<F>,
+    /// See [`GaussNewton::with_tolerance`]
+    pub tolerance: Option<F>,
+}
+
+impl<F: ArgminFloat> GaussNewtonConfig<F> {
+    /// Validates the configuration and constructs a [`GaussNewton`] solver.
+    pub fn build(&self) -> Result<GaussNewton<F>, Error> {
+        let mut solver = GaussNewton::new();
+        if let Some(gamma) = self.gamma {
+            solver = solver.with_gamma(gamma)?;
+        }
+        if let Some(tol) = self.tolerance {
+            solver = solver.with_tolerance(tol)?;
+        }
+        Ok(solver)
+    }
+}
+
 impl<O, P, J, R, F> Solver<O, IterState<P, (), J, (), R, F>> for GaussNewton<F>
 where
     O: Operator<Param = P, Output = R> + Jacobian<Param = P, Jacobian = J>,
@@ -142,7 +184,13 @@ where
         let cost = residuals.l2_norm();
         Ok((
             state.param(init_param).residuals(residuals).cost(cost),
-            None,
+            Some(
+                kv!(
+                    "gamma" => self.gamma;
+                    "tolerance" => self.tol;
+                )
+                .with_prefix("config"),
+            ),
         ))
     }
 
@@ -259,6 +307,28 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_config_build() {
+        let config = GaussNewtonConfig {
+            gamma: Some(0.5f64),
+            tolerance: Some(1e-4),
+        };
+        let GaussNewton { tol: t, gamma: g } = config.build().unwrap();
+        assert_eq!(g.to_ne_bytes(), 0.5f64.to_ne_bytes());
+        assert_eq!(t.to_ne_bytes(), 1e-4f64.to_ne_bytes());
+
+        let error = GaussNewtonConfig {
+            gamma: Some(2.0f64),
+            ..Default::default()
+        }
+        .build();
+        assert_error!(
+            error,
+            ArgminError,
+            "Invalid parameter: \"Gauss-Newton: gamma must be in  (0, 1].\""
+        );
+    }
+
     #[cfg(feature = "_ndarrayl")]
     #[test]
     fn test_init_param_not_initialized() {
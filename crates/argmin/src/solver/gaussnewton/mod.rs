@@ -10,6 +10,9 @@
 //! * [Gauss-Newton method](`GaussNewton`)
 //! * [Gauss-Newton method with line search](`GaussNewtonLS`)
 //!
+//! Outliers can be downweighted by wrapping the problem in a [`RobustLeastSquares`] with a
+//! [`RobustLoss`].
+//!
 //! ## Reference
 //!
 //! Jorge Nocedal and Stephen J. Wright (2006). Numerical Optimization.
@@ -17,6 +20,10 @@
 
 mod gaussnewton_linesearch;
 mod gaussnewton_method;
+mod normal_equations;
+mod robust;
 
-pub use gaussnewton_linesearch::GaussNewtonLS;
-pub use gaussnewton_method::GaussNewton;
+pub use gaussnewton_linesearch::{GaussNewtonLS, GaussNewtonLSConfig};
+pub use gaussnewton_method::{GaussNewton, GaussNewtonConfig};
+pub use normal_equations::{DenseJacobian, JacobianFreeCG};
+pub use robust::{RobustLeastSquares, RobustLoss};
@@ -0,0 +1,318 @@
+// Copyright 2018-2024 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use crate::core::{
+    ArgminFloat, Error, IterState, JacobianVectorProduct, Operator, Problem, Solver, State,
+};
+use crate::solver::conjugategradient::ConjugateGradient;
+use argmin_math::{
+    ArgminConj, ArgminDot, ArgminL2Norm, ArgminMul, ArgminScaledAdd, ArgminSub, ArgminZeroLike,
+};
+#[cfg(feature = "serde1")]
+use serde::{Deserialize, Serialize};
+
+/// Solve the Gauss-Newton normal equations `J^T J p = J^T r` by inverting `J^T J`
+///
+/// This is the default mode of [`GaussNewtonLS`](`crate::solver::gaussnewton::GaussNewtonLS`) and
+/// requires the problem to implement [`Jacobian`](`crate::core::Jacobian`).
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+pub struct DenseJacobian;
+
+/// Solve the Gauss-Newton normal equations `J^T J p = J^T r` without forming the Jacobian
+///
+/// The normal equations are solved with the conjugate gradient method, which only requires
+/// products of the Jacobian with vectors as provided by [`JacobianVectorProduct`]. Each CG
+/// iteration costs one `jvp` and one `vjp` evaluation.
+///
+/// CG stops once the norm of the residual of the normal equations falls below `tol` times the
+/// norm of the right hand side `J^T r` or after `max_iters` iterations.
+///
+/// # Example
+///
+/// ```
+/// # use argmin::solver::gaussnewton::JacobianFreeCG;
+/// # use argmin::core::Error;
+/// # fn main() -> Result<(), Error> {
+/// let cg: JacobianFreeCG<f64> = JacobianFreeCG::new().with_tolerance(1e-8)?.with_max_iters(50);
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+pub struct JacobianFreeCG<F> {
+    /// Relative tolerance of the residual of the normal equations
+    pub(crate) tol: F,
+    /// Maximum number of CG iterations
+    pub(crate) max_iters: u64,
+}
+
+impl<F> JacobianFreeCG<F>
+where
+    F: ArgminFloat,
+{
+    /// Construct a new instance of [`JacobianFreeCG`]
+    ///
+    /// The tolerance defaults to `sqrt(EPSILON)`, the number of iterations is not limited.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::gaussnewton::JacobianFreeCG;
+    /// let cg: JacobianFreeCG<f64> = JacobianFreeCG::new();
+    /// ```
+    pub fn new() -> Self {
+        JacobianFreeCG {
+            tol: F::epsilon().sqrt(),
+            max_iters: u64::MAX,
+        }
+    }
+
+    /// Set the relative tolerance of the residual of the normal equations
+    ///
+    /// Must be larger than zero and defaults to `sqrt(EPSILON)`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::gaussnewton::JacobianFreeCG;
+    /// # use argmin::core::Error;
+    /// # fn main() -> Result<(), Error> {
+    /// let cg: JacobianFreeCG<f64> = JacobianFreeCG::new().with_tolerance(1e-8)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_tolerance(mut self, tol: F) -> Result<Self, Error> {
+        if tol.is_nan() || tol <= float!(0.0) {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "`JacobianFreeCG`: tol must be positive."
+            ));
+        }
+        self.tol = tol;
+        Ok(self)
+    }
+
+    /// Set the maximum number of CG iterations
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::gaussnewton::JacobianFreeCG;
+    /// let cg: JacobianFreeCG<f64> = JacobianFreeCG::new().with_max_iters(50);
+    /// ```
+    #[must_use]
+    pub fn with_max_iters(mut self, max_iters: u64) -> Self {
+        self.max_iters = max_iters;
+        self
+    }
+
+    /// Solves `J^T J p = b` for `p`, where `J` is the Jacobian at `param`.
+    ///
+    /// Returns the solution and the number of CG iterations. The `jvp` and `vjp` evaluations are
+    /// counted in `problem`.
+    pub(crate) fn solve<O, P, U>(
+        &self,
+        problem: &mut Problem<O>,
+        param: &P,
+        b: &P,
+    ) -> Result<(P, u64), Error>
+    where
+        O: JacobianVectorProduct<Param = P, Output = U>,
+        P: Clone
+            + ArgminDot<P, F>
+            + ArgminSub<P, P>
+            + ArgminScaledAdd<P, F, P>
+            + ArgminMul<F, P>
+            + ArgminConj
+            + ArgminZeroLike
+            + ArgminL2Norm<F>,
+        F: ArgminL2Norm<F>,
+    {
+        let b_norm = b.l2_norm();
+        let x0 = b.zero_like();
+        if b_norm == float!(0.0) {
+            return Ok((x0, 0));
+        }
+
+        let mut cg_problem = Problem::new(NormalEquations {
+            problem: problem.problem.as_ref().ok_or_else(argmin_error_closure!(
+                PotentialBug,
+                "`JacobianFreeCG`: Failed to access `problem`"
+            ))?,
+            param,
+        });
+        let mut cg = ConjugateGradient::new(b.clone());
+        let (mut cg_state, _): (IterState<P, (), (), (), P, F>, _) =
+            cg.init(&mut cg_problem, IterState::new().param(x0))?;
+
+        // The cost of the CG state is the squared norm of the residual.
+        let threshold = (self.tol * b_norm).powi(2);
+        let mut iters = 0;
+        while iters < self.max_iters {
+            (cg_state, _) = cg.next_iter(&mut cg_problem, cg_state)?;
+            iters += 1;
+            let cost = cg_state.get_cost();
+            if cost.is_nan() || cost <= threshold {
+                break;
+            }
+        }
+
+        let applications = cg_problem
+            .counts
+            .get("operator_count")
+            .copied()
+            .unwrap_or(0);
+        *problem.counts.entry("jvp_count").or_insert(0) += applications;
+        *problem.counts.entry("vjp_count").or_insert(0) += applications;
+
+        let p = cg_state.take_param().ok_or_else(argmin_error_closure!(
+            PotentialBug,
+            "`JacobianFreeCG`: Failed to take `param` from CG state"
+        ))?;
+        Ok((p, iters))
+    }
+}
+
+impl<F> Default for JacobianFreeCG<F>
+where
+    F: ArgminFloat,
+{
+    fn default() -> Self {
+        JacobianFreeCG::new()
+    }
+}
+
+/// The operator `v -> J^T J v` of the normal equations at `param`
+struct NormalEquations<'a, O, P> {
+    problem: &'a O,
+    param: &'a P,
+}
+
+impl<O, P, U> Operator for NormalEquations<'_, O, P>
+where
+    O: JacobianVectorProduct<Param = P, Output = U>,
+{
+    type Param = P;
+    type Output = P;
+
+    fn apply(&self, v: &P) -> Result<P, Error> {
+        let jv = self.problem.jvp(self.param, v)?;
+        self.problem.vjp(self.param, &jv)
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use crate::core::ArgminError;
+
+    /// Linear residuals `r(x) = A x - b` with a 3x2 matrix `A`
+    pub(crate) struct LinearResiduals {
+        pub(crate) a: Vec<Vec<f64>>,
+        pub(crate) b: Vec<f64>,
+    }
+
+    impl LinearResiduals {
+        pub(crate) fn new() -> Self {
+            LinearResiduals {
+                a: vec![vec![1.0, 0.0], vec![1.0, 1.0], vec![1.0, 2.0]],
+                b: vec![1.0, 2.0, 4.0],
+            }
+        }
+    }
+
+    impl Operator for LinearResiduals {
+        type Param = Vec<f64>;
+        type Output = Vec<f64>;
+
+        fn apply(&self, p: &Self::Param) -> Result<Self::Output, Error> {
+            Ok(self.a.dot(p).sub(&self.b))
+        }
+    }
+
+    impl JacobianVectorProduct for LinearResiduals {
+        type Param = Vec<f64>;
+        type Output = Vec<f64>;
+
+        fn jvp(&self, _p: &Self::Param, v: &Self::Param) -> Result<Self::Output, Error> {
+            Ok(self.a.dot(v))
+        }
+
+        fn vjp(&self, _p: &Self::Param, u: &Self::Output) -> Result<Self::Param, Error> {
+            Ok(vec![
+                self.a
+                    .iter()
+                    .zip(u.iter())
+                    .map(|(row, ui)| row[0] * ui)
+                    .sum(),
+                self.a
+                    .iter()
+                    .zip(u.iter())
+                    .map(|(row, ui)| row[1] * ui)
+                    .sum(),
+            ])
+        }
+    }
+
+    test_trait_impl!(jacobian_free_cg, JacobianFreeCG<f64>);
+
+    #[test]
+    fn test_new() {
+        let JacobianFreeCG { tol, max_iters } = JacobianFreeCG::<f64>::new();
+        assert_eq!(tol.to_ne_bytes(), f64::EPSILON.sqrt().to_ne_bytes());
+        assert_eq!(max_iters, u64::MAX);
+        assert_eq!(JacobianFreeCG::<f64>::default(), JacobianFreeCG::new());
+    }
+
+    #[test]
+    fn test_with_tolerance() {
+        let cg = JacobianFreeCG::new().with_tolerance(1e-8f64).unwrap();
+        assert_eq!(cg.tol.to_ne_bytes(), 1e-8f64.to_ne_bytes());
+
+        for tol in [0.0f64, -1.0, f64::NAN] {
+            let res = JacobianFreeCG::new().with_tolerance(tol);
+            assert_error!(
+                res,
+                ArgminError,
+                "Invalid parameter: \"`JacobianFreeCG`: tol must be positive.\""
+            );
+        }
+    }
+
+    #[test]
+    fn test_solve() {
+        let mut problem = Problem::new(LinearResiduals::new());
+        let param = vec![0.0f64, 0.0];
+        // J^T J = [[3, 3], [3, 5]], solution [1, 1] for b = [6, 8]
+        let (p, iters) = JacobianFreeCG::new()
+            .solve(&mut problem, &param, &vec![6.0f64, 8.0])
+            .unwrap();
+        assert!((p[0] - 1.0).abs() < 1e-10);
+        assert!((p[1] - 1.0).abs() < 1e-10);
+        assert_eq!(iters, 2);
+        assert_eq!(problem.counts["jvp_count"], 3);
+        assert_eq!(problem.counts["vjp_count"], 3);
+
+        // Limited number of iterations
+        let mut problem = Problem::new(LinearResiduals::new());
+        let (_, iters) = JacobianFreeCG::new()
+            .with_max_iters(1)
+            .solve(&mut problem, &param, &vec![6.0f64, 8.0])
+            .unwrap();
+        assert_eq!(iters, 1);
+
+        // Zero right hand side
+        let mut problem = Problem::new(LinearResiduals::new());
+        let (p, iters) = JacobianFreeCG::new()
+            .solve(&mut problem, &param, &vec![0.0f64, 0.0])
+            .unwrap();
+        assert_eq!(p, vec![0.0, 0.0]);
+        assert_eq!(iters, 0);
+        assert!(problem.counts.is_empty());
+    }
+}
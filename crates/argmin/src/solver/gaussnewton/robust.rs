@@ -0,0 +1,436 @@
+// Copyright 2018-2024 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use crate::core::{ArgminFloat, CostFunction, Error, Jacobian, Operator};
+use argmin_math::{ArgminAdd, ArgminDot, ArgminMul, ArgminZeroLike};
+#[cfg(feature = "serde1")]
+use serde::{Deserialize, Serialize};
+
+/// Robust loss function `rho` applied to the squared residuals
+///
+/// Each variant holds the scale `C` at which residuals start to be treated as outliers. With
+/// `z = r^2 / C^2`, the loss of a residual `r` is `rho(r^2) = C^2 * rho_0(z)` where
+///
+/// * Huber: `rho_0(z) = z` for `z <= 1` and `2 * sqrt(z) - 1` otherwise
+/// * Cauchy: `rho_0(z) = ln(1 + z)`
+/// * Soft L1: `rho_0(z) = 2 * (sqrt(1 + z) - 1)`
+/// * Tukey (biweight): `rho_0(z) = (1 - (1 - z)^3) / 3` for `z <= 1` and `1/3` otherwise
+///
+/// For small residuals, all losses behave like the squared residual `r^2`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+pub enum RobustLoss<F> {
+    /// Huber loss: quadratic for small and linear for large residuals
+    Huber(F),
+    /// Cauchy loss: logarithmic for large residuals
+    Cauchy(F),
+    /// Soft L1 loss: smooth approximation of the absolute value
+    SoftL1(F),
+    /// Tukey's biweight loss: constant for large residuals, which are therefore ignored entirely
+    Tukey(F),
+}
+
+impl<F: ArgminFloat> RobustLoss<F> {
+    /// Returns the scale `C`
+    pub fn scale(&self) -> F {
+        match *self {
+            RobustLoss::Huber(c)
+            | RobustLoss::Cauchy(c)
+            | RobustLoss::SoftL1(c)
+            | RobustLoss::Tukey(c) => c,
+        }
+    }
+
+    /// Evaluates the loss `rho(s)` and its derivative `rho'(s)` for a squared residual `s`
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::gaussnewton::RobustLoss;
+    /// let (rho, drho) = RobustLoss::Huber(1.0f64).evaluate(4.0);
+    /// # assert_eq!(rho.to_ne_bytes(), 3.0f64.to_ne_bytes());
+    /// # assert_eq!(drho.to_ne_bytes(), 0.5f64.to_ne_bytes());
+    /// ```
+    pub fn evaluate(&self, s: F) -> (F, F) {
+        let c2 = self.scale() * self.scale();
+        let z = s / c2;
+        let (rho, drho) = match *self {
+            RobustLoss::Huber(_) => {
+                if z <= float!(1.0) {
+                    (z, float!(1.0))
+                } else {
+                    let sqrt_z = z.sqrt();
+                    (float!(2.0) * sqrt_z - float!(1.0), float!(1.0) / sqrt_z)
+                }
+            }
+            RobustLoss::Cauchy(_) => (z.ln_1p(), float!(1.0) / (float!(1.0) + z)),
+            RobustLoss::SoftL1(_) => {
+                let t = (float!(1.0) + z).sqrt();
+                (float!(2.0) * (t - float!(1.0)), float!(1.0) / t)
+            }
+            RobustLoss::Tukey(_) => {
+                if z <= float!(1.0) {
+                    let t = float!(1.0) - z;
+                    ((float!(1.0) - t * t * t) / float!(3.0), t * t)
+                } else {
+                    (float!(1.0) / float!(3.0), float!(0.0))
+                }
+            }
+        };
+        // `rho(s) = C^2 * rho_0(s / C^2)` and therefore `rho'(s) = rho_0'(s / C^2)`
+        (c2 * rho, drho)
+    }
+
+    /// Returns the weight `sqrt(rho'(r^2))` of a residual `r`
+    fn weight(&self, r: F) -> F {
+        self.evaluate(r * r).1.sqrt()
+    }
+}
+
+/// Wraps a nonlinear least squares problem such that residuals are subject to a [`RobustLoss`]
+///
+/// Solvers such as [`GaussNewton`](`crate::solver::gaussnewton::GaussNewton`) and
+/// [`GaussNewtonLS`](`crate::solver::gaussnewton::GaussNewtonLS`) minimize the sum of squared
+/// residuals, which is sensitive to outliers. This wrapper reweights the residuals and the rows
+/// of the Jacobian by `sqrt(rho'(r_i^2))`, evaluated at the current parameter vector. Every
+/// Gauss-Newton iteration therefore becomes a step of iteratively reweighted least squares
+/// (IRLS), as in Ceres Solver or SciPy's `least_squares` (neglecting second order terms of the
+/// loss). The [`CostFunction`] implementation returns the robust cost
+/// `1/2 * sum_i rho(r_i^2)`.
+///
+/// Computing the Jacobian requires the residuals, hence the wrapped [`Operator`] is evaluated in
+/// every call to `jacobian` as well.
+///
+/// The residuals need to be iterable and collectable from an iterator (such as `Vec<F>` or
+/// `ndarray::Array1<F>`) and the Jacobian is scaled via the outer product of the weights and a
+/// vector of ones.
+///
+/// # Example
+///
+/// ```
+/// # use argmin::core::{Error, Jacobian, Operator};
+/// use argmin::solver::gaussnewton::{RobustLeastSquares, RobustLoss};
+///
+/// # struct Problem {}
+/// # impl Operator for Problem {
+/// #     type Param = Vec<f64>;
+/// #     type Output = Vec<f64>;
+/// #     fn apply(&self, p: &Self::Param) -> Result<Self::Output, Error> {
+/// #         Ok(vec![p[0] - 1.0, p[0] - 10.0])
+/// #     }
+/// # }
+/// # fn main() -> Result<(), Error> {
+/// let problem = RobustLeastSquares::new(Problem {}, RobustLoss::Huber(1.0))?;
+/// let residuals = problem.apply(&vec![0.0])?;
+/// # assert_eq!(residuals[0].to_ne_bytes(), (-1.0f64).to_ne_bytes());
+/// # assert!((residuals[1] + 10.0f64.sqrt()).abs() < 1e-12);
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+pub struct RobustLeastSquares<O, F> {
+    /// Wrapped least squares problem
+    pub problem: O,
+    /// Loss applied to the squared residuals
+    pub loss: RobustLoss<F>,
+}
+
+impl<O, F> RobustLeastSquares<O, F>
+where
+    F: ArgminFloat,
+{
+    /// Construct a new instance of [`RobustLeastSquares`]
+    ///
+    /// The scale of the loss must be larger than 0.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::gaussnewton::{RobustLeastSquares, RobustLoss};
+    /// # use argmin::core::test_utils::TestProblem;
+    /// # use argmin::core::Error;
+    /// # fn main() -> Result<(), Error> {
+    /// let problem = RobustLeastSquares::new(TestProblem::new(), RobustLoss::Cauchy(0.5f64))?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn new(problem: O, loss: RobustLoss<F>) -> Result<Self, Error> {
+        if loss.scale() <= float!(0.0) {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "`RobustLeastSquares`: scale of loss must be > 0."
+            ));
+        }
+        Ok(RobustLeastSquares { problem, loss })
+    }
+
+    /// Computes the weights `sqrt(rho'(r_i^2))` of the residuals
+    fn weights<R>(&self, residuals: &R) -> R
+    where
+        R: FromIterator<F>,
+        for<'a> &'a R: IntoIterator<Item = &'a F>,
+    {
+        residuals
+            .into_iter()
+            .map(|r| self.loss.weight(*r))
+            .collect()
+    }
+}
+
+impl<O, R, F> Operator for RobustLeastSquares<O, F>
+where
+    O: Operator<Output = R>,
+    R: FromIterator<F> + ArgminMul<R, R>,
+    for<'a> &'a R: IntoIterator<Item = &'a F>,
+    F: ArgminFloat,
+{
+    type Param = O::Param;
+    type Output = R;
+
+    fn apply(&self, param: &Self::Param) -> Result<Self::Output, Error> {
+        let residuals = self.problem.apply(param)?;
+        Ok(residuals.mul(&self.weights(&residuals)))
+    }
+}
+
+impl<O, P, R, J, F> Jacobian for RobustLeastSquares<O, F>
+where
+    O: Operator<Param = P, Output = R> + Jacobian<Param = P, Jacobian = J>,
+    P: ArgminZeroLike + ArgminAdd<F, P>,
+    R: FromIterator<F> + ArgminDot<P, J>,
+    for<'a> &'a R: IntoIterator<Item = &'a F>,
+    J: ArgminMul<J, J>,
+    F: ArgminFloat,
+{
+    type Param = P;
+    type Jacobian = J;
+
+    fn jacobian(&self, param: &Self::Param) -> Result<Self::Jacobian, Error> {
+        let weights = self.weights(&self.problem.apply(param)?);
+        let jacobian = self.problem.jacobian(param)?;
+        // Outer product of the weights and a vector of ones scales the rows of the Jacobian
+        let ones = param.zero_like().add(&float!(1.0));
+        Ok(jacobian.mul(&weights.dot(&ones)))
+    }
+}
+
+impl<O, R, F> CostFunction for RobustLeastSquares<O, F>
+where
+    O: Operator<Output = R>,
+    for<'a> &'a R: IntoIterator<Item = &'a F>,
+    F: ArgminFloat,
+{
+    type Param = O::Param;
+    type Output = F;
+
+    fn cost(&self, param: &Self::Param) -> Result<Self::Output, Error> {
+        let residuals = self.problem.apply(param)?;
+        let sum = residuals
+            .into_iter()
+            .fold(float!(0.0), |acc, r| acc + self.loss.evaluate(*r * *r).0);
+        Ok(float!(0.5) * sum)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::ArgminError;
+    use approx::assert_relative_eq;
+
+    test_trait_impl!(robust_loss, RobustLoss<f64>);
+    test_trait_impl!(robust_least_squares, RobustLeastSquares<(), f64>);
+
+    /// Fit of a line `y = a * x + b` with a gross outlier in the last data point
+    struct Line {
+        x: Vec<f64>,
+        y: Vec<f64>,
+    }
+
+    impl Line {
+        fn new() -> Self {
+            let x: Vec<f64> = (0..10).map(f64::from).collect();
+            let mut y: Vec<f64> = x.iter().map(|x| 2.0 * x + 1.0).collect();
+            y[9] = 100.0;
+            Line { x, y }
+        }
+    }
+
+    impl Operator for Line {
+        type Param = Vec<f64>;
+        type Output = Vec<f64>;
+
+        fn apply(&self, p: &Self::Param) -> Result<Self::Output, Error> {
+            Ok(self
+                .x
+                .iter()
+                .zip(self.y.iter())
+                .map(|(x, y)| p[0] * x + p[1] - y)
+                .collect())
+        }
+    }
+
+    impl Jacobian for Line {
+        type Param = Vec<f64>;
+        type Jacobian = Vec<Vec<f64>>;
+
+        fn jacobian(&self, _p: &Self::Param) -> Result<Self::Jacobian, Error> {
+            Ok(self.x.iter().map(|x| vec![*x, 1.0]).collect())
+        }
+    }
+
+    #[test]
+    fn test_evaluate() {
+        let s = 2.25f64;
+        // Inside the scale, Huber and Tukey are close to the squared residual
+        let (rho, drho) = RobustLoss::Huber(2.0).evaluate(s);
+        assert_relative_eq!(rho, s);
+        assert_relative_eq!(drho, 1.0);
+        let (rho, drho) = RobustLoss::Tukey(2.0).evaluate(s);
+        let t = 1.0 - s / 4.0;
+        assert_relative_eq!(rho, 4.0 * (1.0 - t.powi(3)) / 3.0);
+        assert_relative_eq!(drho, t * t);
+        let (rho, drho) = RobustLoss::Cauchy(2.0).evaluate(s);
+        assert_relative_eq!(rho, 4.0 * (1.0 + s / 4.0).ln());
+        assert_relative_eq!(drho, 1.0 / (1.0 + s / 4.0));
+        let (rho, drho) = RobustLoss::SoftL1(2.0).evaluate(s);
+        assert_relative_eq!(rho, 8.0 * ((1.0 + s / 4.0).sqrt() - 1.0));
+        assert_relative_eq!(drho, 1.0 / (1.0 + s / 4.0).sqrt());
+
+        // Outside of the scale
+        let s = 16.0f64;
+        let (rho, drho) = RobustLoss::Huber(2.0).evaluate(s);
+        assert_relative_eq!(rho, 4.0 * (2.0 * 2.0 - 1.0));
+        assert_relative_eq!(drho, 0.5);
+        let (rho, drho) = RobustLoss::Tukey(2.0).evaluate(s);
+        assert_relative_eq!(rho, 4.0 / 3.0);
+        assert_relative_eq!(drho, 0.0);
+    }
+
+    #[test]
+    fn test_derivative() {
+        for loss in [
+            RobustLoss::Huber(1.5f64),
+            RobustLoss::Cauchy(1.5),
+            RobustLoss::SoftL1(1.5),
+            RobustLoss::Tukey(1.5),
+        ] {
+            for s in [0.1, 1.0, 2.0, 5.0] {
+                let h = 1e-6;
+                let fd = (loss.evaluate(s + h).0 - loss.evaluate(s - h).0) / (2.0 * h);
+                assert_relative_eq!(loss.evaluate(s).1, fd, epsilon = 1e-6);
+            }
+        }
+    }
+
+    #[test]
+    fn test_new_invalid_scale() {
+        for scale in [0.0, -1.0] {
+            let res = RobustLeastSquares::new((), RobustLoss::Huber(scale));
+            assert_error!(
+                res,
+                ArgminError,
+                "Invalid parameter: \"`RobustLeastSquares`: scale of loss must be > 0.\""
+            );
+        }
+    }
+
+    #[test]
+    fn test_reweighting() {
+        let problem = RobustLeastSquares::new(Line::new(), RobustLoss::Huber(1.0)).unwrap();
+        let param = vec![2.0, 1.0];
+        let residuals = problem.apply(&param).unwrap();
+        let jacobian = problem.jacobian(&param).unwrap();
+        // Inliers are unaffected
+        for i in 0..9 {
+            assert_relative_eq!(residuals[i], 0.0);
+            assert_relative_eq!(jacobian[i][0], i as f64);
+            assert_relative_eq!(jacobian[i][1], 1.0);
+        }
+        // The outlier with residual -81 is weighted by 1/sqrt(81)
+        assert_relative_eq!(residuals[9], -9.0);
+        assert_relative_eq!(jacobian[9][0], 1.0);
+        assert_relative_eq!(jacobian[9][1], 1.0 / 9.0);
+        // Robust cost: 1/2 * (2 * 81 - 1)
+        assert_relative_eq!(problem.cost(&param).unwrap(), 80.5);
+    }
+
+    #[cfg(feature = "_ndarrayl")]
+    #[test]
+    fn test_gauss_newton() {
+        use crate::core::Executor;
+        use crate::solver::gaussnewton::GaussNewton;
+        use ndarray::{Array1, Array2};
+
+        struct NdLine(Line);
+
+        impl Operator for NdLine {
+            type Param = Array1<f64>;
+            type Output = Array1<f64>;
+
+            fn apply(&self, p: &Self::Param) -> Result<Self::Output, Error> {
+                Ok(Array1::from(self.0.apply(&p.to_vec())?))
+            }
+        }
+
+        impl Jacobian for NdLine {
+            type Param = Array1<f64>;
+            type Jacobian = Array2<f64>;
+
+            fn jacobian(&self, p: &Self::Param) -> Result<Self::Jacobian, Error> {
+                let jacobian = self.0.jacobian(&p.to_vec())?;
+                Ok(Array2::from_shape_vec(
+                    (jacobian.len(), 2),
+                    jacobian.into_iter().flatten().collect(),
+                )?)
+            }
+        }
+
+        let run = |loss: Option<RobustLoss<f64>>| -> Array1<f64> {
+            let init_param = Array1::from(vec![0.0, 0.0]);
+            let solver = GaussNewton::new();
+            match loss {
+                None => Executor::new(NdLine(Line::new()), solver)
+                    .configure(|state| state.param(init_param).max_iters(50))
+                    .run()
+                    .unwrap()
+                    .state
+                    .take_best_param()
+                    .unwrap(),
+                Some(loss) => Executor::new(
+                    RobustLeastSquares::new(NdLine(Line::new()), loss).unwrap(),
+                    solver,
+                )
+                .configure(|state| state.param(init_param).max_iters(50))
+                .run()
+                .unwrap()
+                .state
+                .take_best_param()
+                .unwrap(),
+            }
+        };
+
+        // Ordinary least squares is pulled towards the outlier
+        let ols = run(None);
+        assert!((ols[0] - 2.0).abs() > 1.0);
+
+        for loss in [
+            RobustLoss::Huber(1.0),
+            RobustLoss::Cauchy(1.0),
+            RobustLoss::SoftL1(1.0),
+        ] {
+            let param = run(Some(loss));
+            assert!((param[0] - 2.0).abs() < 0.5 * (ols[0] - 2.0).abs());
+        }
+
+        // Tukey's loss ignores the outlier entirely once it is identified
+        let param = run(Some(RobustLoss::Tukey(10.0)));
+        assert_relative_eq!(param[0], 2.0, epsilon = 1e-6);
+        assert_relative_eq!(param[1], 1.0, epsilon = 1e-6);
+    }
+}
@@ -0,0 +1,612 @@
+// Copyright 2018-2024 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use super::{argmin_cost, check_bounds, sample_uniform};
+use crate::core::{
+    ArgminFloat, CostFunction, Error, PopulationState, Problem, Solver, SyncAlias, KV,
+};
+use rand::{Rng, SeedableRng};
+use rand_xoshiro::Xoshiro256PlusPlus;
+#[cfg(feature = "serde1")]
+use serde::{Deserialize, Serialize};
+
+/// # Artificial bee colony (ABC)
+///
+/// Population-based metaheuristic inspired by the foraging behavior of honey bees. The colony
+/// keeps [`num_food_sources`](`ArtificialBeeColony::new`) food sources (parameter vectors),
+/// which are initialized by sampling uniformly from the search space given by box bounds. Each
+/// iteration consists of three phases:
+///
+/// 1. Employed bees: For every food source, a candidate is created by moving a single randomly
+///    chosen coordinate towards or away from the corresponding coordinate of another randomly
+///    chosen food source. The candidate replaces the food source if it has a lower cost.
+/// 2. Onlooker bees: The same number of candidates is created for food sources chosen with
+///    probability proportional to their fitness and selected greedily as before.
+/// 3. Scout bees: A food source which could not be improved for more than
+///    [`limit`](`ArtificialBeeColony::with_limit`) trials is abandoned and replaced by a sample
+///    drawn uniformly from the search space.
+///
+/// Each phase evaluates its candidates via [`bulk_cost`](`crate::core::Problem::bulk_cost`),
+/// which is computed in parallel if the `rayon` feature is enabled.
+///
+/// The food sources are available as the population of the
+/// [`PopulationState`](`crate::core::PopulationState`), the best food source is the current
+/// individual.
+///
+/// Since candidates are constructed coordinate-wise, the bounds are given as `Vec<F>`. The
+/// parameter vector type `P` needs to implement `From<Vec<F>>`, which is the case for `Vec<F>`,
+/// `ndarray::Array1<F>` and `nalgebra::DVector<F>`.
+///
+/// ## Requirements on the optimization problem
+///
+/// The optimization problem is required to implement [`CostFunction`].
+///
+/// ## Reference
+///
+/// Karaboga, D. and Basturk, B. (2007): A powerful and efficient algorithm for numerical function
+/// optimization: artificial bee colony (ABC) algorithm. Journal of Global Optimization 39,
+/// 459–471. <https://doi.org/10.1007/s10898-007-9149-x>
+#[derive(Clone)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+pub struct ArtificialBeeColony<F, R> {
+    /// Bounds on parameter space
+    bounds: (Vec<F>, Vec<F>),
+    /// Number of food sources
+    num_food_sources: usize,
+    /// Number of failed trials after which a food source is abandoned
+    limit: Option<u64>,
+    /// Food sources
+    sources: Vec<Vec<F>>,
+    /// Cost function values of the food sources
+    costs: Vec<F>,
+    /// Number of failed trials to improve each food source
+    trials: Vec<u64>,
+    /// Random number generator
+    rng_generator: R,
+}
+
+impl<F> ArtificialBeeColony<F, Xoshiro256PlusPlus>
+where
+    F: ArgminFloat,
+{
+    /// Construct a new instance of `ArtificialBeeColony`
+    ///
+    /// `bounds` is a tuple `(lower_bound, upper_bound)` of vectors of the same length as the
+    /// parameter vector. `num_food_sources` is the number of food sources and must be at least
+    /// `2`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::globalopt::ArtificialBeeColony;
+    /// # let lower_bound: Vec<f64> = vec![-1.0, -1.0];
+    /// # let upper_bound: Vec<f64> = vec![1.0, 1.0];
+    /// let solver = ArtificialBeeColony::new((lower_bound, upper_bound), 20);
+    /// ```
+    pub fn new(bounds: (Vec<F>, Vec<F>), num_food_sources: usize) -> Self {
+        ArtificialBeeColony {
+            bounds,
+            num_food_sources,
+            limit: None,
+            sources: vec![],
+            costs: vec![],
+            trials: vec![],
+            rng_generator: Xoshiro256PlusPlus::from_entropy(),
+        }
+    }
+}
+
+impl<F, R0> ArtificialBeeColony<F, R0>
+where
+    F: ArgminFloat,
+    R0: Rng,
+{
+    /// Set the number of failed trials after which a food source is abandoned
+    ///
+    /// Must be larger than 0. Defaults to the number of food sources times the number of
+    /// dimensions.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::globalopt::ArtificialBeeColony;
+    /// # use argmin::core::Error;
+    /// # fn main() -> Result<(), Error> {
+    /// # let lower_bound: Vec<f64> = vec![-1.0, -1.0];
+    /// # let upper_bound: Vec<f64> = vec![1.0, 1.0];
+    /// let solver = ArtificialBeeColony::new((lower_bound, upper_bound), 20).with_limit(50)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_limit(mut self, limit: u64) -> Result<Self, Error> {
+        if limit == 0 {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "`ArtificialBeeColony`: limit must be > 0."
+            ));
+        }
+        self.limit = Some(limit);
+        Ok(self)
+    }
+
+    /// Set the random number generator
+    ///
+    /// Defaults to `Xoshiro256PlusPlus::from_entropy()`
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::globalopt::ArtificialBeeColony;
+    /// use rand::SeedableRng;
+    ///
+    /// # let lower_bound: Vec<f64> = vec![-1.0, -1.0];
+    /// # let upper_bound: Vec<f64> = vec![1.0, 1.0];
+    /// let solver = ArtificialBeeColony::new((lower_bound, upper_bound), 20)
+    ///     .with_rng_generator(rand_xoshiro::Xoroshiro128Plus::seed_from_u64(1));
+    /// ```
+    pub fn with_rng_generator<R1: Rng>(self, generator: R1) -> ArtificialBeeColony<F, R1> {
+        ArtificialBeeColony {
+            bounds: self.bounds,
+            num_food_sources: self.num_food_sources,
+            limit: self.limit,
+            sources: self.sources,
+            costs: self.costs,
+            trials: self.trials,
+            rng_generator: generator,
+        }
+    }
+
+    /// Returns the effective limit of failed trials
+    fn limit(&self) -> u64 {
+        self.limit
+            .unwrap_or((self.num_food_sources * self.bounds.0.len()) as u64)
+    }
+
+    /// Creates a candidate in the neighborhood of food source `idx`
+    ///
+    /// A single coordinate `j` is changed to `x_j + phi * (x_j - y_j)`, where `y` is another
+    /// randomly chosen food source and `phi` is uniformly distributed in `[-1, 1]`.
+    fn neighbor(&mut self, idx: usize) -> Vec<F> {
+        let (lower, upper) = &self.bounds;
+        // draw a partner different from `idx`
+        let mut partner = self.rng_generator.gen_range(0..self.sources.len() - 1);
+        if partner >= idx {
+            partner += 1;
+        }
+        let j = self.rng_generator.gen_range(0..lower.len());
+        let phi: f64 = self.rng_generator.gen_range(-1.0..=1.0);
+
+        let mut candidate = self.sources[idx].clone();
+        let x = candidate[j];
+        candidate[j] = (x + float!(phi) * (x - self.sources[partner][j]))
+            .max(lower[j])
+            .min(upper[j]);
+        candidate
+    }
+
+    /// Replaces food source `idx` with the candidate if the latter has a lower cost
+    fn select(&mut self, idx: usize, candidate: Vec<F>, cost: F) {
+        if cost < self.costs[idx] || (self.costs[idx].is_nan() && !cost.is_nan()) {
+            self.sources[idx] = candidate;
+            self.costs[idx] = cost;
+            self.trials[idx] = 0;
+        } else {
+            self.trials[idx] += 1;
+        }
+    }
+
+    /// Chooses a food source with probability proportional to its fitness
+    fn roulette(&mut self) -> usize {
+        let fitness: Vec<F> = self.costs.iter().map(|c| fitness(*c)).collect();
+        let total = fitness.iter().fold(float!(0.0), |acc, f| acc + *f);
+        if total <= float!(0.0) || !total.is_finite() {
+            return self.rng_generator.gen_range(0..fitness.len());
+        }
+        let mut r = float!(self.rng_generator.gen::<f64>()) * total;
+        for (i, f) in fitness.iter().enumerate() {
+            if r < *f {
+                return i;
+            }
+            r = r - *f;
+        }
+        fitness.len() - 1
+    }
+}
+
+/// Fitness of a food source as defined in the reference
+///
+/// Returns `1 / (1 + cost)` for non-negative costs and `1 + |cost|` otherwise. NaN costs have a
+/// fitness of zero.
+fn fitness<F: ArgminFloat>(cost: F) -> F {
+    if cost.is_nan() {
+        float!(0.0)
+    } else if cost >= float!(0.0) {
+        float!(1.0) / (float!(1.0) + cost)
+    } else {
+        float!(1.0) + cost.abs()
+    }
+}
+
+/// Configuration of [`ArtificialBeeColony`]
+///
+/// # Example
+///
+/// ```
+/// # use argmin::core::Error;
+/// # use argmin::solver::globalopt::ArtificialBeeColonyConfig;
+/// # fn main() -> Result<(), Error> {
+/// let config = ArtificialBeeColonyConfig {
+///     bounds: (vec![-1.0f64, -1.0], vec![1.0, 1.0]),
+///     num_food_sources: 20,
+///     limit: Some(50),
+///     seed: Some(1729),
+/// };
+/// let solver = config.build()?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize), serde(default))]
+pub struct ArtificialBeeColonyConfig<F> {
+    /// Bounds on the parameter space as `(lower_bound, upper_bound)`
+    pub bounds: (Vec<F>, Vec<F>),
+    /// Number of food sources, must be `>= 2`
+    pub num_food_sources: usize,
+    /// See [`ArtificialBeeColony::with_limit`]
+    pub limit: Option<u64>,
+    /// Seed of the `Xoshiro256PlusPlus` RNG. If `None`, the RNG is seeded from entropy.
+    pub seed: Option<u64>,
+}
+
+impl<F> ArtificialBeeColonyConfig<F>
+where
+    F: ArgminFloat,
+{
+    /// Validates the configuration and constructs an [`ArtificialBeeColony`] solver.
+    pub fn build(&self) -> Result<ArtificialBeeColony<F, Xoshiro256PlusPlus>, Error> {
+        check_parameters(&self.bounds, self.num_food_sources)?;
+        let mut solver = ArtificialBeeColony::new(self.bounds.clone(), self.num_food_sources);
+        if let Some(seed) = self.seed {
+            solver = solver.with_rng_generator(Xoshiro256PlusPlus::seed_from_u64(seed));
+        }
+        if let Some(limit) = self.limit {
+            solver = solver.with_limit(limit)?;
+        }
+        Ok(solver)
+    }
+}
+
+/// Checks the bounds and that there are at least two food sources
+fn check_parameters<F>(bounds: &(Vec<F>, Vec<F>), num_food_sources: usize) -> Result<(), Error> {
+    if num_food_sources < 2 {
+        return Err(argmin_error!(
+            InvalidParameter,
+            "`ArtificialBeeColony`: number of food sources must be >= 2."
+        ));
+    }
+    check_bounds("ArtificialBeeColony", bounds)
+}
+
+impl<O, P, F, R> Solver<O, PopulationState<P, F>> for ArtificialBeeColony<F, R>
+where
+    O: CostFunction<Param = P, Output = F> + SyncAlias,
+    P: Clone + SyncAlias + From<Vec<F>>,
+    F: ArgminFloat,
+    R: Rng,
+{
+    fn name(&self) -> &str {
+        "Artificial Bee Colony"
+    }
+
+    fn init(
+        &mut self,
+        problem: &mut Problem<O>,
+        state: PopulationState<P, F>,
+    ) -> Result<(PopulationState<P, F>, Option<KV>), Error> {
+        check_parameters(&self.bounds, self.num_food_sources)?;
+
+        self.sources = (0..self.num_food_sources)
+            .map(|_| sample_uniform(&self.bounds, &mut self.rng_generator))
+            .collect();
+        let sources: Vec<P> = self.sources.iter().cloned().map(P::from).collect();
+        self.costs = problem.bulk_cost(&sources)?;
+        self.trials = vec![0; self.num_food_sources];
+
+        Ok((
+            self.update_state(state, sources),
+            Some(
+                kv!(
+                    "num_food_sources" => self.num_food_sources as u64;
+                    "limit" => self.limit();
+                    "dimensions" => self.bounds.0.len() as u64;
+                    "rng" => std::any::type_name::<R>();
+                )
+                .with_prefix("config"),
+            ),
+        ))
+    }
+
+    fn next_iter(
+        &mut self,
+        problem: &mut Problem<O>,
+        state: PopulationState<P, F>,
+    ) -> Result<(PopulationState<P, F>, Option<KV>), Error> {
+        let n = self.num_food_sources;
+
+        // Employed bees
+        let candidates: Vec<Vec<F>> = (0..n).map(|i| self.neighbor(i)).collect();
+        let params: Vec<P> = candidates.iter().cloned().map(P::from).collect();
+        let costs = problem.bulk_cost(&params)?;
+        for (i, (candidate, cost)) in candidates.into_iter().zip(costs).enumerate() {
+            self.select(i, candidate, cost);
+        }
+
+        // Onlooker bees
+        let chosen: Vec<usize> = (0..n).map(|_| self.roulette()).collect();
+        let candidates: Vec<Vec<F>> = chosen.iter().map(|i| self.neighbor(*i)).collect();
+        let params: Vec<P> = candidates.iter().cloned().map(P::from).collect();
+        let costs = problem.bulk_cost(&params)?;
+        for ((i, candidate), cost) in chosen.into_iter().zip(candidates).zip(costs) {
+            self.select(i, candidate, cost);
+        }
+
+        // Scout bee
+        let (exhausted, trials) = self
+            .trials
+            .iter()
+            .cloned()
+            .enumerate()
+            .max_by_key(|(_, t)| *t)
+            .unwrap_or((0, 0));
+        let scout = trials > self.limit();
+        if scout {
+            let source = sample_uniform(&self.bounds, &mut self.rng_generator);
+            self.costs[exhausted] = problem.cost(&P::from(source.clone()))?;
+            self.sources[exhausted] = source;
+            self.trials[exhausted] = 0;
+        }
+
+        let sources: Vec<P> = self.sources.iter().cloned().map(P::from).collect();
+        Ok((
+            self.update_state(state, sources),
+            Some(kv!("scout" => scout;)),
+        ))
+    }
+}
+
+impl<F, R> ArtificialBeeColony<F, R>
+where
+    F: ArgminFloat,
+{
+    /// Sets the food sources as population and the best food source as current individual
+    fn update_state<P: Clone>(
+        &self,
+        state: PopulationState<P, F>,
+        sources: Vec<P>,
+    ) -> PopulationState<P, F> {
+        match argmin_cost(&self.costs) {
+            Some(idx) => state
+                .individual(sources[idx].clone())
+                .cost(self.costs[idx])
+                .population(sources),
+            None => state.population(sources),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::{ArgminError, Executor, State};
+    use rand::rngs::StdRng;
+
+    test_trait_impl!(artificial_bee_colony, ArtificialBeeColony<f64, StdRng>);
+
+    struct Sphere {}
+
+    impl CostFunction for Sphere {
+        type Param = Vec<f64>;
+        type Output = f64;
+
+        fn cost(&self, p: &Self::Param) -> Result<Self::Output, Error> {
+            Ok(p.iter().map(|x| (x - 0.5).powi(2)).sum())
+        }
+    }
+
+    #[test]
+    fn test_new() {
+        let solver = ArtificialBeeColony::new((vec![-1.0f64, -2.0], vec![1.0, 2.0]), 12);
+        let ArtificialBeeColony {
+            bounds,
+            num_food_sources,
+            limit,
+            sources,
+            costs,
+            trials,
+            ..
+        } = solver;
+        assert_eq!(bounds, (vec![-1.0, -2.0], vec![1.0, 2.0]));
+        assert_eq!(num_food_sources, 12);
+        assert!(limit.is_none());
+        assert!(sources.is_empty());
+        assert!(costs.is_empty());
+        assert!(trials.is_empty());
+    }
+
+    #[test]
+    fn test_with_limit() {
+        let solver = ArtificialBeeColony::new((vec![-1.0f64, -2.0], vec![1.0, 2.0]), 12);
+        assert_eq!(solver.limit(), 24);
+        let solver = solver.with_limit(5).unwrap();
+        assert_eq!(solver.limit(), 5);
+
+        let res = ArtificialBeeColony::new((vec![-1.0f64], vec![1.0]), 12).with_limit(0);
+        assert_error!(
+            res,
+            ArgminError,
+            "Invalid parameter: \"`ArtificialBeeColony`: limit must be > 0.\""
+        );
+    }
+
+    #[test]
+    fn test_fitness() {
+        assert_eq!(fitness(0.0f64).to_ne_bytes(), 1.0f64.to_ne_bytes());
+        assert_eq!(fitness(3.0f64).to_ne_bytes(), 0.25f64.to_ne_bytes());
+        assert_eq!(fitness(-2.0f64).to_ne_bytes(), 3.0f64.to_ne_bytes());
+        assert_eq!(fitness(f64::NAN).to_ne_bytes(), 0.0f64.to_ne_bytes());
+    }
+
+    #[test]
+    fn test_neighbor() {
+        let lower = vec![-1.0f64, 0.0, 10.0];
+        let upper = vec![1.0f64, 5.0, 10.0];
+        let mut solver = ArtificialBeeColony::new((lower.clone(), upper.clone()), 2)
+            .with_rng_generator(StdRng::seed_from_u64(42));
+        solver.sources = vec![vec![0.5, 1.0, 10.0], vec![-0.5, 4.0, 10.0]];
+
+        for _ in 0..100 {
+            let candidate = solver.neighbor(0);
+            // at most one coordinate differs
+            let changed = candidate
+                .iter()
+                .zip(solver.sources[0].iter())
+                .filter(|(a, b)| a.to_ne_bytes() != b.to_ne_bytes())
+                .count();
+            assert!(changed <= 1);
+            for (j, x) in candidate.iter().enumerate() {
+                assert!(*x >= lower[j] && *x <= upper[j]);
+            }
+        }
+    }
+
+    #[test]
+    fn test_select() {
+        let mut solver = ArtificialBeeColony::new((vec![-1.0f64], vec![1.0]), 2);
+        solver.sources = vec![vec![0.5], vec![-0.5]];
+        solver.costs = vec![1.0, 2.0];
+        solver.trials = vec![3, 3];
+
+        solver.select(0, vec![0.4], 1.5);
+        assert_eq!(solver.sources[0], vec![0.5]);
+        assert_eq!(solver.trials[0], 4);
+
+        solver.select(1, vec![0.0], 0.5);
+        assert_eq!(solver.sources[1], vec![0.0]);
+        assert_eq!(solver.costs[1].to_ne_bytes(), 0.5f64.to_ne_bytes());
+        assert_eq!(solver.trials[1], 0);
+    }
+
+    #[test]
+    fn test_config_build() {
+        let config = ArtificialBeeColonyConfig {
+            bounds: (vec![-1.0f64, -1.0], vec![1.0, 1.0]),
+            num_food_sources: 8,
+            limit: Some(10),
+            seed: Some(1729),
+        };
+        let solver = config.build().unwrap();
+        assert_eq!(solver.bounds, config.bounds);
+        assert_eq!(solver.num_food_sources, 8);
+        assert_eq!(solver.limit, Some(10));
+
+        let mut rng1 = config.build().unwrap().rng_generator;
+        let mut rng2 = config.build().unwrap().rng_generator;
+        assert_eq!(rng1.gen::<u64>(), rng2.gen::<u64>());
+
+        let res = ArtificialBeeColonyConfig {
+            num_food_sources: 1,
+            ..config.clone()
+        }
+        .build();
+        assert_error!(
+            res,
+            ArgminError,
+            "Invalid parameter: \"`ArtificialBeeColony`: number of food sources must be >= 2.\""
+        );
+
+        let res = ArtificialBeeColonyConfig {
+            bounds: (vec![-1.0f64, -1.0], vec![1.0]),
+            ..config
+        }
+        .build();
+        assert_error!(
+            res,
+            ArgminError,
+            concat!(
+                "Invalid parameter: \"`ArtificialBeeColony`: ",
+                "lower and upper bound must be non-empty and of equal length.\""
+            )
+        );
+    }
+
+    #[test]
+    fn test_scout() {
+        let mut solver = ArtificialBeeColony::new((vec![-1.0f64, -1.0], vec![1.0, 1.0]), 4)
+            .with_limit(3)
+            .unwrap()
+            .with_rng_generator(StdRng::seed_from_u64(42));
+        let mut problem = Problem::new(Sphere {});
+        let (state, _) = solver.init(&mut problem, PopulationState::new()).unwrap();
+        assert_eq!(problem.counts["cost_count"], 4);
+        assert_eq!(state.get_population().unwrap().len(), 4);
+
+        // The second food source cannot be improved and is abandoned
+        solver.costs[1] = f64::NEG_INFINITY;
+        solver.trials[1] = 5;
+        let (state, kv) = solver.next_iter(&mut problem, state).unwrap();
+        assert!(kv.unwrap().get("scout").unwrap().get_bool().unwrap());
+        assert_eq!(problem.counts["cost_count"], 4 + 2 * 4 + 1);
+        assert_eq!(solver.trials[1], 0);
+        assert!(solver.costs[1].is_finite());
+        assert_eq!(state.get_population().unwrap().len(), 4);
+    }
+
+    #[test]
+    fn test_init_checks_parameters() {
+        let solver = ArtificialBeeColony::new((vec![], vec![]), 10);
+        let res = Executor::new(Sphere {}, solver)
+            .configure(|state| state.max_iters(1))
+            .run();
+        assert_error!(
+            res,
+            ArgminError,
+            concat!(
+                "Invalid parameter: \"`ArtificialBeeColony`: ",
+                "lower and upper bound must be non-empty and of equal length.\""
+            )
+        );
+    }
+
+    #[test]
+    fn test_artificial_bee_colony() {
+        let solver = ArtificialBeeColony::new((vec![-1.0f64, -1.0], vec![1.0, 1.0]), 10)
+            .with_rng_generator(StdRng::seed_from_u64(42));
+        let res = Executor::new(Sphere {}, solver)
+            .configure(|state| state.max_iters(50))
+            .run()
+            .unwrap();
+        assert!(res.problem.counts["cost_count"] >= 1010);
+        assert!(res.state.get_best_cost() < 1e-4);
+        let best = res.state.get_best_param().unwrap();
+        assert!(best.iter().all(|x| (-1.0..=1.0).contains(x)));
+    }
+
+    #[test]
+    fn test_artificial_bee_colony_reproducible() {
+        let run = || {
+            let solver = ArtificialBeeColony::new((vec![-1.0f64, -1.0], vec![1.0, 1.0]), 5)
+                .with_rng_generator(StdRng::seed_from_u64(7));
+            Executor::new(Sphere {}, solver)
+                .configure(|state| state.max_iters(20))
+                .run()
+                .unwrap()
+                .state
+                .best_individual
+        };
+        assert_eq!(run(), run());
+    }
+}
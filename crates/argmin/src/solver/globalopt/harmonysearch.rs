@@ -0,0 +1,626 @@
+// Copyright 2018-2024 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use super::{argmax_cost, argmin_cost, check_bounds, sample_uniform};
+use crate::core::{
+    ArgminFloat, CostFunction, Error, PopulationState, Problem, Solver, SyncAlias, KV,
+};
+use rand::{Rng, SeedableRng};
+use rand_xoshiro::Xoshiro256PlusPlus;
+#[cfg(feature = "serde1")]
+use serde::{Deserialize, Serialize};
+
+/// # Harmony search
+///
+/// Population-based metaheuristic inspired by the improvisation of musicians. The solver keeps a
+/// harmony memory of [`memory_size`](`HarmonySearch::new`) parameter vectors, which is initialized
+/// by sampling uniformly from the search space given by box bounds. In each iteration, a new
+/// harmony is improvised coordinate by coordinate: With probability
+/// [`memory_consideration_rate`](`HarmonySearch::with_memory_consideration_rate`) the coordinate
+/// is taken from a randomly chosen harmony in memory and additionally shifted by a random amount
+/// of at most [`bandwidth`](`HarmonySearch::with_bandwidth`) times the range of the coordinate
+/// with probability [`pitch_adjustment_rate`](`HarmonySearch::with_pitch_adjustment_rate`).
+/// Otherwise the coordinate is sampled uniformly within the bounds. The new harmony replaces the
+/// worst harmony in memory if it has a lower cost.
+///
+/// The harmony memory is available as the population of the
+/// [`PopulationState`](`crate::core::PopulationState`), the best harmony in memory is the current
+/// individual.
+///
+/// Since new harmonies are constructed coordinate-wise, the bounds are given as `Vec<F>`. The
+/// parameter vector type `P` needs to implement `From<Vec<F>>`, which is the case for `Vec<F>`,
+/// `ndarray::Array1<F>` and `nalgebra::DVector<F>`.
+///
+/// ## Requirements on the optimization problem
+///
+/// The optimization problem is required to implement [`CostFunction`].
+///
+/// ## Reference
+///
+/// Geem, Z.W., Kim, J.H. and Loganathan, G.V. (2001): A New Heuristic Optimization Algorithm:
+/// Harmony Search. Simulation 76 (2), 60–68. <https://doi.org/10.1177/003754970107600201>
+#[derive(Clone)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+pub struct HarmonySearch<F, R> {
+    /// Bounds on parameter space
+    bounds: (Vec<F>, Vec<F>),
+    /// Number of harmonies in memory
+    memory_size: usize,
+    /// Probability of taking a coordinate from memory
+    memory_consideration_rate: F,
+    /// Probability of adjusting a coordinate taken from memory
+    pitch_adjustment_rate: F,
+    /// Maximum pitch adjustment relative to the range of a coordinate
+    bandwidth: F,
+    /// Harmony memory
+    memory: Vec<Vec<F>>,
+    /// Cost function values of the harmonies in memory
+    costs: Vec<F>,
+    /// Random number generator
+    rng_generator: R,
+}
+
+impl<F> HarmonySearch<F, Xoshiro256PlusPlus>
+where
+    F: ArgminFloat,
+{
+    /// Construct a new instance of `HarmonySearch`
+    ///
+    /// `bounds` is a tuple `(lower_bound, upper_bound)` of vectors of the same length as the
+    /// parameter vector. `memory_size` is the number of harmonies kept in memory.
+    ///
+    /// The memory consideration rate, pitch adjustment rate and bandwidth default to `0.9`,
+    /// `0.3` and `0.01`, respectively.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::globalopt::HarmonySearch;
+    /// # let lower_bound: Vec<f64> = vec![-1.0, -1.0];
+    /// # let upper_bound: Vec<f64> = vec![1.0, 1.0];
+    /// let solver = HarmonySearch::new((lower_bound, upper_bound), 20);
+    /// ```
+    pub fn new(bounds: (Vec<F>, Vec<F>), memory_size: usize) -> Self {
+        HarmonySearch {
+            bounds,
+            memory_size,
+            memory_consideration_rate: float!(0.9),
+            pitch_adjustment_rate: float!(0.3),
+            bandwidth: float!(0.01),
+            memory: vec![],
+            costs: vec![],
+            rng_generator: Xoshiro256PlusPlus::from_entropy(),
+        }
+    }
+}
+
+impl<F, R0> HarmonySearch<F, R0>
+where
+    F: ArgminFloat,
+    R0: Rng,
+{
+    /// Set the harmony memory consideration rate
+    ///
+    /// Probability of taking a coordinate of a new harmony from memory instead of sampling it
+    /// uniformly within the bounds. Must be in `[0, 1]` and defaults to `0.9`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::globalopt::HarmonySearch;
+    /// # use argmin::core::Error;
+    /// # fn main() -> Result<(), Error> {
+    /// # let lower_bound: Vec<f64> = vec![-1.0, -1.0];
+    /// # let upper_bound: Vec<f64> = vec![1.0, 1.0];
+    /// let solver =
+    ///     HarmonySearch::new((lower_bound, upper_bound), 20).with_memory_consideration_rate(0.95)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_memory_consideration_rate(mut self, rate: F) -> Result<Self, Error> {
+        if !(float!(0.0)..=float!(1.0)).contains(&rate) {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "`HarmonySearch`: memory consideration rate must be in [0, 1]."
+            ));
+        }
+        self.memory_consideration_rate = rate;
+        Ok(self)
+    }
+
+    /// Set the pitch adjustment rate
+    ///
+    /// Probability of adjusting a coordinate taken from memory. Must be in `[0, 1]` and defaults
+    /// to `0.3`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::globalopt::HarmonySearch;
+    /// # use argmin::core::Error;
+    /// # fn main() -> Result<(), Error> {
+    /// # let lower_bound: Vec<f64> = vec![-1.0, -1.0];
+    /// # let upper_bound: Vec<f64> = vec![1.0, 1.0];
+    /// let solver =
+    ///     HarmonySearch::new((lower_bound, upper_bound), 20).with_pitch_adjustment_rate(0.5)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_pitch_adjustment_rate(mut self, rate: F) -> Result<Self, Error> {
+        if !(float!(0.0)..=float!(1.0)).contains(&rate) {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "`HarmonySearch`: pitch adjustment rate must be in [0, 1]."
+            ));
+        }
+        self.pitch_adjustment_rate = rate;
+        Ok(self)
+    }
+
+    /// Set the bandwidth of the pitch adjustment
+    ///
+    /// A pitch adjustment shifts a coordinate by a uniformly distributed amount of at most
+    /// `bandwidth` times the range of the coordinate. Must be `>= 0` and defaults to `0.01`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::globalopt::HarmonySearch;
+    /// # use argmin::core::Error;
+    /// # fn main() -> Result<(), Error> {
+    /// # let lower_bound: Vec<f64> = vec![-1.0, -1.0];
+    /// # let upper_bound: Vec<f64> = vec![1.0, 1.0];
+    /// let solver = HarmonySearch::new((lower_bound, upper_bound), 20).with_bandwidth(0.05)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_bandwidth(mut self, bandwidth: F) -> Result<Self, Error> {
+        if bandwidth < float!(0.0) {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "`HarmonySearch`: bandwidth must be >= 0."
+            ));
+        }
+        self.bandwidth = bandwidth;
+        Ok(self)
+    }
+
+    /// Set the random number generator
+    ///
+    /// Defaults to `Xoshiro256PlusPlus::from_entropy()`
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::globalopt::HarmonySearch;
+    /// use rand::SeedableRng;
+    ///
+    /// # let lower_bound: Vec<f64> = vec![-1.0, -1.0];
+    /// # let upper_bound: Vec<f64> = vec![1.0, 1.0];
+    /// let solver = HarmonySearch::new((lower_bound, upper_bound), 20)
+    ///     .with_rng_generator(rand_xoshiro::Xoroshiro128Plus::seed_from_u64(1));
+    /// ```
+    pub fn with_rng_generator<R1: Rng>(self, generator: R1) -> HarmonySearch<F, R1> {
+        HarmonySearch {
+            bounds: self.bounds,
+            memory_size: self.memory_size,
+            memory_consideration_rate: self.memory_consideration_rate,
+            pitch_adjustment_rate: self.pitch_adjustment_rate,
+            bandwidth: self.bandwidth,
+            memory: self.memory,
+            costs: self.costs,
+            rng_generator: generator,
+        }
+    }
+
+    /// Improvises a new harmony from the harmony memory
+    fn improvise(&mut self) -> Vec<F> {
+        let (lower, upper) = &self.bounds;
+        (0..lower.len())
+            .map(|j| {
+                let (l, u) = (lower[j], upper[j]);
+                if float!(self.rng_generator.gen::<f64>()) >= self.memory_consideration_rate {
+                    return l + float!(self.rng_generator.gen::<f64>()) * (u - l);
+                }
+                let idx = self.rng_generator.gen_range(0..self.memory.len());
+                let mut x = self.memory[idx][j];
+                if float!(self.rng_generator.gen::<f64>()) < self.pitch_adjustment_rate {
+                    let shift: f64 = self.rng_generator.gen_range(-1.0..=1.0);
+                    x = x + float!(shift) * self.bandwidth * (u - l);
+                }
+                x.max(l).min(u)
+            })
+            .collect()
+    }
+}
+
+/// Configuration of [`HarmonySearch`]
+///
+/// # Example
+///
+/// ```
+/// # use argmin::core::Error;
+/// # use argmin::solver::globalopt::HarmonySearchConfig;
+/// # fn main() -> Result<(), Error> {
+/// let config = HarmonySearchConfig {
+///     bounds: (vec![-1.0f64, -1.0], vec![1.0, 1.0]),
+///     memory_size: 20,
+///     pitch_adjustment_rate: Some(0.5),
+///     seed: Some(1729),
+///     ..Default::default()
+/// };
+/// let solver = config.build()?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize), serde(default))]
+pub struct HarmonySearchConfig<F> {
+    /// Bounds on the parameter space as `(lower_bound, upper_bound)`
+    pub bounds: (Vec<F>, Vec<F>),
+    /// Number of harmonies in memory, must be `> 0`
+    pub memory_size: usize,
+    /// See [`HarmonySearch::with_memory_consideration_rate`]
+    pub memory_consideration_rate: Option<F>,
+    /// See [`HarmonySearch::with_pitch_adjustment_rate`]
+    pub pitch_adjustment_rate: Option<F>,
+    /// See [`HarmonySearch::with_bandwidth`]
+    pub bandwidth: Option<F>,
+    /// Seed of the `Xoshiro256PlusPlus` RNG. If `None`, the RNG is seeded from entropy.
+    pub seed: Option<u64>,
+}
+
+impl<F> HarmonySearchConfig<F>
+where
+    F: ArgminFloat,
+{
+    /// Validates the configuration and constructs a [`HarmonySearch`] solver.
+    pub fn build(&self) -> Result<HarmonySearch<F, Xoshiro256PlusPlus>, Error> {
+        check_parameters(&self.bounds, self.memory_size)?;
+        let mut solver = HarmonySearch::new(self.bounds.clone(), self.memory_size);
+        if let Some(seed) = self.seed {
+            solver = solver.with_rng_generator(Xoshiro256PlusPlus::seed_from_u64(seed));
+        }
+        if let Some(rate) = self.memory_consideration_rate {
+            solver = solver.with_memory_consideration_rate(rate)?;
+        }
+        if let Some(rate) = self.pitch_adjustment_rate {
+            solver = solver.with_pitch_adjustment_rate(rate)?;
+        }
+        if let Some(bandwidth) = self.bandwidth {
+            solver = solver.with_bandwidth(bandwidth)?;
+        }
+        Ok(solver)
+    }
+}
+
+/// Checks the bounds and that the harmony memory is non-empty
+fn check_parameters<F>(bounds: &(Vec<F>, Vec<F>), memory_size: usize) -> Result<(), Error> {
+    if memory_size == 0 {
+        return Err(argmin_error!(
+            InvalidParameter,
+            "`HarmonySearch`: memory size must be > 0."
+        ));
+    }
+    check_bounds("HarmonySearch", bounds)
+}
+
+impl<O, P, F, R> Solver<O, PopulationState<P, F>> for HarmonySearch<F, R>
+where
+    O: CostFunction<Param = P, Output = F> + SyncAlias,
+    P: Clone + SyncAlias + From<Vec<F>>,
+    F: ArgminFloat,
+    R: Rng,
+{
+    fn name(&self) -> &str {
+        "Harmony Search"
+    }
+
+    fn init(
+        &mut self,
+        problem: &mut Problem<O>,
+        state: PopulationState<P, F>,
+    ) -> Result<(PopulationState<P, F>, Option<KV>), Error> {
+        check_parameters(&self.bounds, self.memory_size)?;
+
+        self.memory = (0..self.memory_size)
+            .map(|_| sample_uniform(&self.bounds, &mut self.rng_generator))
+            .collect();
+        let harmonies: Vec<P> = self.memory.iter().cloned().map(P::from).collect();
+        self.costs = problem.bulk_cost(&harmonies)?;
+
+        Ok((
+            self.update_state(state, harmonies),
+            Some(
+                kv!(
+                    "memory_size" => self.memory_size as u64;
+                    "memory_consideration_rate" => self.memory_consideration_rate;
+                    "pitch_adjustment_rate" => self.pitch_adjustment_rate;
+                    "bandwidth" => self.bandwidth;
+                    "dimensions" => self.bounds.0.len() as u64;
+                    "rng" => std::any::type_name::<R>();
+                )
+                .with_prefix("config"),
+            ),
+        ))
+    }
+
+    fn next_iter(
+        &mut self,
+        problem: &mut Problem<O>,
+        state: PopulationState<P, F>,
+    ) -> Result<(PopulationState<P, F>, Option<KV>), Error> {
+        let harmony = self.improvise();
+        let cost = problem.cost(&P::from(harmony.clone()))?;
+
+        let worst = argmax_cost(&self.costs);
+        let replaced = cost < self.costs[worst] || self.costs[worst].is_nan();
+        if replaced {
+            self.memory[worst] = harmony;
+            self.costs[worst] = cost;
+        }
+
+        let harmonies: Vec<P> = self.memory.iter().cloned().map(P::from).collect();
+        Ok((
+            self.update_state(state, harmonies),
+            Some(kv!("replaced" => replaced;)),
+        ))
+    }
+}
+
+impl<F, R> HarmonySearch<F, R>
+where
+    F: ArgminFloat,
+{
+    /// Sets the harmony memory as population and the best harmony as current individual
+    fn update_state<P: Clone>(
+        &self,
+        state: PopulationState<P, F>,
+        harmonies: Vec<P>,
+    ) -> PopulationState<P, F> {
+        match argmin_cost(&self.costs) {
+            Some(idx) => state
+                .individual(harmonies[idx].clone())
+                .cost(self.costs[idx])
+                .population(harmonies),
+            None => state.population(harmonies),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::{ArgminError, Executor, State};
+    use rand::rngs::StdRng;
+
+    test_trait_impl!(harmony_search, HarmonySearch<f64, StdRng>);
+
+    struct Sphere {}
+
+    impl CostFunction for Sphere {
+        type Param = Vec<f64>;
+        type Output = f64;
+
+        fn cost(&self, p: &Self::Param) -> Result<Self::Output, Error> {
+            Ok(p.iter().map(|x| (x - 0.5).powi(2)).sum())
+        }
+    }
+
+    #[test]
+    fn test_new() {
+        let solver = HarmonySearch::new((vec![-1.0f64, -2.0], vec![1.0, 2.0]), 12);
+        let HarmonySearch {
+            bounds,
+            memory_size,
+            memory_consideration_rate,
+            pitch_adjustment_rate,
+            bandwidth,
+            memory,
+            costs,
+            ..
+        } = solver;
+        assert_eq!(bounds, (vec![-1.0, -2.0], vec![1.0, 2.0]));
+        assert_eq!(memory_size, 12);
+        assert_eq!(
+            memory_consideration_rate.to_ne_bytes(),
+            0.9f64.to_ne_bytes()
+        );
+        assert_eq!(pitch_adjustment_rate.to_ne_bytes(), 0.3f64.to_ne_bytes());
+        assert_eq!(bandwidth.to_ne_bytes(), 0.01f64.to_ne_bytes());
+        assert!(memory.is_empty());
+        assert!(costs.is_empty());
+    }
+
+    #[test]
+    fn test_setters() {
+        let bounds = (vec![-1.0f64], vec![1.0]);
+        let solver = HarmonySearch::new(bounds.clone(), 5)
+            .with_memory_consideration_rate(1.0)
+            .unwrap()
+            .with_pitch_adjustment_rate(0.0)
+            .unwrap()
+            .with_bandwidth(0.1)
+            .unwrap();
+        assert_eq!(
+            solver.memory_consideration_rate.to_ne_bytes(),
+            1.0f64.to_ne_bytes()
+        );
+        assert_eq!(
+            solver.pitch_adjustment_rate.to_ne_bytes(),
+            0.0f64.to_ne_bytes()
+        );
+        assert_eq!(solver.bandwidth.to_ne_bytes(), 0.1f64.to_ne_bytes());
+
+        for rate in [-0.1, 1.1] {
+            let res = HarmonySearch::new(bounds.clone(), 5).with_memory_consideration_rate(rate);
+            assert_error!(
+                res,
+                ArgminError,
+                "Invalid parameter: \"`HarmonySearch`: memory consideration rate must be in [0, 1].\""
+            );
+            let res = HarmonySearch::new(bounds.clone(), 5).with_pitch_adjustment_rate(rate);
+            assert_error!(
+                res,
+                ArgminError,
+                "Invalid parameter: \"`HarmonySearch`: pitch adjustment rate must be in [0, 1].\""
+            );
+        }
+
+        let res = HarmonySearch::new(bounds, 5).with_bandwidth(-0.1);
+        assert_error!(
+            res,
+            ArgminError,
+            "Invalid parameter: \"`HarmonySearch`: bandwidth must be >= 0.\""
+        );
+    }
+
+    #[test]
+    fn test_improvise() {
+        let lower = vec![-1.0f64, 0.0, 10.0];
+        let upper = vec![1.0f64, 5.0, 10.0];
+        let mut solver = HarmonySearch::new((lower.clone(), upper.clone()), 3)
+            .with_rng_generator(StdRng::seed_from_u64(42));
+        solver.memory = vec![
+            vec![0.5, 1.0, 10.0],
+            vec![-0.5, 2.0, 10.0],
+            vec![0.0, 3.0, 10.0],
+        ];
+
+        for _ in 0..100 {
+            let harmony = solver.improvise();
+            assert_eq!(harmony.len(), 3);
+            for (j, x) in harmony.iter().enumerate() {
+                assert!(*x >= lower[j] && *x <= upper[j]);
+            }
+        }
+
+        // Without randomization, every coordinate is taken from memory
+        let mut solver = solver
+            .with_memory_consideration_rate(1.0)
+            .unwrap()
+            .with_pitch_adjustment_rate(0.0)
+            .unwrap();
+        for _ in 0..100 {
+            let harmony = solver.improvise();
+            for (j, x) in harmony.iter().enumerate() {
+                assert!(solver
+                    .memory
+                    .iter()
+                    .any(|h| h[j].to_ne_bytes() == x.to_ne_bytes()));
+            }
+        }
+    }
+
+    #[test]
+    fn test_config_build() {
+        let config = HarmonySearchConfig {
+            bounds: (vec![-1.0f64, -1.0], vec![1.0, 1.0]),
+            memory_size: 8,
+            memory_consideration_rate: Some(0.8),
+            pitch_adjustment_rate: Some(0.4),
+            bandwidth: Some(0.1),
+            seed: Some(1729),
+        };
+        let solver = config.build().unwrap();
+        assert_eq!(solver.bounds, config.bounds);
+        assert_eq!(solver.memory_size, 8);
+        assert_eq!(
+            solver.memory_consideration_rate.to_ne_bytes(),
+            0.8f64.to_ne_bytes()
+        );
+        assert_eq!(
+            solver.pitch_adjustment_rate.to_ne_bytes(),
+            0.4f64.to_ne_bytes()
+        );
+        assert_eq!(solver.bandwidth.to_ne_bytes(), 0.1f64.to_ne_bytes());
+
+        let mut rng1 = config.build().unwrap().rng_generator;
+        let mut rng2 = config.build().unwrap().rng_generator;
+        assert_eq!(rng1.gen::<u64>(), rng2.gen::<u64>());
+
+        let res = HarmonySearchConfig {
+            memory_size: 0,
+            ..config.clone()
+        }
+        .build();
+        assert_error!(
+            res,
+            ArgminError,
+            "Invalid parameter: \"`HarmonySearch`: memory size must be > 0.\""
+        );
+
+        let res = HarmonySearchConfig {
+            bounds: (vec![-1.0f64, -1.0], vec![1.0]),
+            ..config
+        }
+        .build();
+        assert_error!(
+            res,
+            ArgminError,
+            concat!(
+                "Invalid parameter: \"`HarmonySearch`: ",
+                "lower and upper bound must be non-empty and of equal length.\""
+            )
+        );
+    }
+
+    #[test]
+    fn test_init() {
+        let mut solver = HarmonySearch::new((vec![-1.0f64, -1.0], vec![1.0, 1.0]), 10)
+            .with_rng_generator(StdRng::seed_from_u64(42));
+        let mut problem = Problem::new(Sphere {});
+        let (state, _) = solver.init(&mut problem, PopulationState::new()).unwrap();
+
+        assert_eq!(problem.counts["cost_count"], 10);
+        assert_eq!(state.get_population().unwrap().len(), 10);
+        let best = solver.costs.iter().cloned().fold(f64::INFINITY, f64::min);
+        assert_eq!(state.get_cost().to_ne_bytes(), best.to_ne_bytes());
+
+        let solver = HarmonySearch::new((vec![], vec![]), 10);
+        let res = Executor::new(Sphere {}, solver)
+            .configure(|state| state.max_iters(1))
+            .run();
+        assert_error!(
+            res,
+            ArgminError,
+            concat!(
+                "Invalid parameter: \"`HarmonySearch`: ",
+                "lower and upper bound must be non-empty and of equal length.\""
+            )
+        );
+    }
+
+    #[test]
+    fn test_harmony_search() {
+        let solver = HarmonySearch::new((vec![-1.0f64, -1.0], vec![1.0, 1.0]), 10)
+            .with_rng_generator(StdRng::seed_from_u64(42));
+        let res = Executor::new(Sphere {}, solver)
+            .configure(|state| state.max_iters(500))
+            .run()
+            .unwrap();
+        assert_eq!(res.problem.counts["cost_count"], 510);
+        assert!(res.state.get_best_cost() < 1e-3);
+        let best = res.state.get_best_param().unwrap();
+        assert!(best.iter().all(|x| (-1.0..=1.0).contains(x)));
+    }
+
+    #[test]
+    fn test_harmony_search_reproducible() {
+        let run = || {
+            let solver = HarmonySearch::new((vec![-1.0f64, -1.0], vec![1.0, 1.0]), 5)
+                .with_rng_generator(StdRng::seed_from_u64(7));
+            Executor::new(Sphere {}, solver)
+                .configure(|state| state.max_iters(20))
+                .run()
+                .unwrap()
+                .state
+                .best_individual
+        };
+        assert_eq!(run(), run());
+    }
+}
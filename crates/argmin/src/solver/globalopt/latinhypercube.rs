@@ -0,0 +1,375 @@
+// Copyright 2018-2024 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use super::{argmin_cost, check_bounds};
+use crate::core::{
+    ArgminFloat, CostFunction, Error, IterState, Problem, Solver, State, SyncAlias, KV,
+};
+use rand::seq::SliceRandom;
+use rand::{Rng, SeedableRng};
+use rand_xoshiro::Xoshiro256PlusPlus;
+#[cfg(feature = "serde1")]
+use serde::{Deserialize, Serialize};
+
+/// # Latin hypercube sampling
+///
+/// In each iteration, a Latin hypercube design of [`num_samples`](`LatinHypercube::new`) samples
+/// is drawn from the search space given by box bounds and evaluated via
+/// [`bulk_cost`](`crate::core::Problem::bulk_cost`). The range of each coordinate is divided into
+/// `num_samples` intervals of equal width and each interval contains exactly one sample, where
+/// the intervals are randomly paired across coordinates and the position within an interval is
+/// uniformly distributed. Compared to [`RandomSearch`](`crate::solver::globalopt::RandomSearch`)
+/// this covers the range of each individual coordinate more evenly.
+///
+/// The best sample of a design becomes the current parameter vector. If an initial parameter
+/// vector is provided via `Executor`s `configure` method, it is evaluated along with the first
+/// design.
+///
+/// Since the design is constructed coordinate-wise, the bounds are given as `Vec<F>`. The
+/// parameter vector type `P` needs to implement `From<Vec<F>>`, which is the case for `Vec<F>`,
+/// `ndarray::Array1<F>` and `nalgebra::DVector<F>`.
+///
+/// The `rayon` feature enables parallel computation of the cost function within a design.
+///
+/// ## Requirements on the optimization problem
+///
+/// The optimization problem is required to implement [`CostFunction`].
+///
+/// ## Reference
+///
+/// McKay, M.D., Beckman, R.J. and Conover, W.J. (1979): A Comparison of Three Methods for
+/// Selecting Values of Input Variables in the Analysis of Output from a Computer Code.
+/// Technometrics 21 (2), 239–245. <https://doi.org/10.2307/1268522>
+#[derive(Clone)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+pub struct LatinHypercube<F, R> {
+    /// Bounds on parameter space
+    bounds: (Vec<F>, Vec<F>),
+    /// Number of samples per design
+    num_samples: usize,
+    /// Random number generator
+    rng_generator: R,
+}
+
+impl<F> LatinHypercube<F, Xoshiro256PlusPlus>
+where
+    F: ArgminFloat,
+{
+    /// Construct a new instance of `LatinHypercube`
+    ///
+    /// `bounds` is a tuple `(lower_bound, upper_bound)` of vectors of the same length as the
+    /// parameter vector. `num_samples` is the number of samples of the design drawn in each
+    /// iteration.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::globalopt::LatinHypercube;
+    /// # let lower_bound: Vec<f64> = vec![-1.0, -1.0];
+    /// # let upper_bound: Vec<f64> = vec![1.0, 1.0];
+    /// let solver = LatinHypercube::new((lower_bound, upper_bound), 20);
+    /// ```
+    pub fn new(bounds: (Vec<F>, Vec<F>), num_samples: usize) -> Self {
+        LatinHypercube {
+            bounds,
+            num_samples,
+            rng_generator: Xoshiro256PlusPlus::from_entropy(),
+        }
+    }
+}
+
+impl<F, R0> LatinHypercube<F, R0>
+where
+    F: ArgminFloat,
+    R0: Rng,
+{
+    /// Set the random number generator
+    ///
+    /// Defaults to `Xoshiro256PlusPlus::from_entropy()`
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::globalopt::LatinHypercube;
+    /// use rand::SeedableRng;
+    ///
+    /// # let lower_bound: Vec<f64> = vec![-1.0, -1.0];
+    /// # let upper_bound: Vec<f64> = vec![1.0, 1.0];
+    /// let solver = LatinHypercube::new((lower_bound, upper_bound), 20)
+    ///     .with_rng_generator(rand_xoshiro::Xoroshiro128Plus::seed_from_u64(1));
+    /// ```
+    pub fn with_rng_generator<R1: Rng>(self, generator: R1) -> LatinHypercube<F, R1> {
+        LatinHypercube {
+            bounds: self.bounds,
+            num_samples: self.num_samples,
+            rng_generator: generator,
+        }
+    }
+
+    /// Draws a Latin hypercube design of `num_samples` samples
+    fn sample_design(&mut self) -> Vec<Vec<F>> {
+        let n = self.num_samples;
+        let (lower, upper) = &self.bounds;
+        let mut design = vec![Vec::with_capacity(lower.len()); n];
+        let mut strata: Vec<usize> = (0..n).collect();
+        for (l, u) in lower.iter().zip(upper.iter()) {
+            strata.shuffle(&mut self.rng_generator);
+            for (sample, stratum) in design.iter_mut().zip(strata.iter()) {
+                let offset: f64 = self.rng_generator.gen();
+                let t = (float!(*stratum as f64) + float!(offset)) / float!(n as f64);
+                sample.push(*l + t * (*u - *l));
+            }
+        }
+        design
+    }
+}
+
+/// Configuration of [`LatinHypercube`]
+///
+/// # Example
+///
+/// ```
+/// # use argmin::core::Error;
+/// # use argmin::solver::globalopt::LatinHypercubeConfig;
+/// # fn main() -> Result<(), Error> {
+/// let config = LatinHypercubeConfig {
+///     bounds: (vec![-1.0f64, -1.0], vec![1.0, 1.0]),
+///     num_samples: 20,
+///     seed: Some(1729),
+/// };
+/// let solver = config.build()?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize), serde(default))]
+pub struct LatinHypercubeConfig<F> {
+    /// Bounds on the parameter space as `(lower_bound, upper_bound)`
+    pub bounds: (Vec<F>, Vec<F>),
+    /// Number of samples per design, must be `> 0`
+    pub num_samples: usize,
+    /// Seed of the `Xoshiro256PlusPlus` RNG. If `None`, the RNG is seeded from entropy.
+    pub seed: Option<u64>,
+}
+
+impl<F> LatinHypercubeConfig<F>
+where
+    F: ArgminFloat,
+{
+    /// Validates the configuration and constructs a [`LatinHypercube`] solver.
+    pub fn build(&self) -> Result<LatinHypercube<F, Xoshiro256PlusPlus>, Error> {
+        check_parameters(&self.bounds, self.num_samples)?;
+        let mut solver = LatinHypercube::new(self.bounds.clone(), self.num_samples);
+        if let Some(seed) = self.seed {
+            solver = solver.with_rng_generator(Xoshiro256PlusPlus::seed_from_u64(seed));
+        }
+        Ok(solver)
+    }
+}
+
+/// Checks that the design and the bounds are non-empty and that the bounds are of equal length
+fn check_parameters<F>(bounds: &(Vec<F>, Vec<F>), num_samples: usize) -> Result<(), Error> {
+    if num_samples == 0 {
+        return Err(argmin_error!(
+            InvalidParameter,
+            "`LatinHypercube`: number of samples must be > 0."
+        ));
+    }
+    check_bounds("LatinHypercube", bounds)
+}
+
+impl<O, P, F, R> Solver<O, IterState<P, (), (), (), (), F>> for LatinHypercube<F, R>
+where
+    O: CostFunction<Param = P, Output = F> + SyncAlias,
+    P: Clone + SyncAlias + From<Vec<F>>,
+    F: ArgminFloat,
+    R: Rng,
+{
+    fn name(&self) -> &str {
+        "Latin Hypercube Sampling"
+    }
+
+    fn init(
+        &mut self,
+        _problem: &mut Problem<O>,
+        state: IterState<P, (), (), (), (), F>,
+    ) -> Result<(IterState<P, (), (), (), (), F>, Option<KV>), Error> {
+        check_parameters(&self.bounds, self.num_samples)?;
+        Ok((
+            state,
+            Some(
+                kv!(
+                    "num_samples" => self.num_samples as u64;
+                    "dimensions" => self.bounds.0.len() as u64;
+                    "rng" => std::any::type_name::<R>();
+                )
+                .with_prefix("config"),
+            ),
+        ))
+    }
+
+    fn next_iter(
+        &mut self,
+        problem: &mut Problem<O>,
+        mut state: IterState<P, (), (), (), (), F>,
+    ) -> Result<(IterState<P, (), (), (), (), F>, Option<KV>), Error> {
+        let mut samples: Vec<P> = Vec::with_capacity(self.num_samples + 1);
+        // An initial guess is evaluated along with the first design
+        if state.get_iter() == 0 {
+            if let Some(param) = state.take_param() {
+                samples.push(param);
+            }
+        }
+        samples.extend(self.sample_design().into_iter().map(P::from));
+        let costs = problem.bulk_cost(&samples)?;
+
+        Ok(match argmin_cost(&costs) {
+            Some(idx) => (state.param(samples.swap_remove(idx)).cost(costs[idx]), None),
+            None => (state, None),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::{ArgminError, Executor};
+    use rand::rngs::StdRng;
+
+    test_trait_impl!(latin_hypercube, LatinHypercube<f64, StdRng>);
+
+    struct Sphere {}
+
+    impl CostFunction for Sphere {
+        type Param = Vec<f64>;
+        type Output = f64;
+
+        fn cost(&self, p: &Self::Param) -> Result<Self::Output, Error> {
+            Ok(p.iter().map(|x| (x - 0.5).powi(2)).sum())
+        }
+    }
+
+    #[test]
+    fn test_new() {
+        let solver = LatinHypercube::new((vec![-1.0f64, -2.0], vec![1.0, 2.0]), 12);
+        let LatinHypercube {
+            bounds,
+            num_samples,
+            ..
+        } = solver;
+        assert_eq!(bounds, (vec![-1.0, -2.0], vec![1.0, 2.0]));
+        assert_eq!(num_samples, 12);
+    }
+
+    #[test]
+    fn test_sample_design() {
+        let n = 10;
+        let lower = vec![-1.0f64, 0.0, 10.0];
+        let upper = vec![1.0f64, 5.0, 10.0];
+        let mut solver = LatinHypercube::new((lower.clone(), upper.clone()), n)
+            .with_rng_generator(StdRng::seed_from_u64(42));
+
+        for _ in 0..5 {
+            let design = solver.sample_design();
+            assert_eq!(design.len(), n);
+            for j in 0..lower.len() {
+                let width = (upper[j] - lower[j]) / n as f64;
+                let mut strata: Vec<usize> = design
+                    .iter()
+                    .map(|s| {
+                        assert!(s[j] >= lower[j] && s[j] <= upper[j]);
+                        if width > 0.0 {
+                            (((s[j] - lower[j]) / width) as usize).min(n - 1)
+                        } else {
+                            0
+                        }
+                    })
+                    .collect();
+                strata.sort();
+                if width > 0.0 {
+                    // every stratum contains exactly one sample
+                    assert_eq!(strata, (0..n).collect::<Vec<_>>());
+                } else {
+                    assert!(design
+                        .iter()
+                        .all(|s| s[j].to_ne_bytes() == lower[j].to_ne_bytes()));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_config_build() {
+        let config = LatinHypercubeConfig {
+            bounds: (vec![-1.0f64, -1.0], vec![1.0, 1.0]),
+            num_samples: 8,
+            seed: Some(1729),
+        };
+        let solver = config.build().unwrap();
+        assert_eq!(solver.bounds, config.bounds);
+        assert_eq!(solver.num_samples, 8);
+
+        let mut rng1 = config.build().unwrap().rng_generator;
+        let mut rng2 = config.build().unwrap().rng_generator;
+        assert_eq!(rng1.gen::<u64>(), rng2.gen::<u64>());
+
+        let res = LatinHypercubeConfig {
+            num_samples: 0,
+            ..config.clone()
+        }
+        .build();
+        assert_error!(
+            res,
+            ArgminError,
+            "Invalid parameter: \"`LatinHypercube`: number of samples must be > 0.\""
+        );
+
+        let res = LatinHypercubeConfig {
+            bounds: (vec![-1.0f64, -1.0], vec![1.0]),
+            ..config
+        }
+        .build();
+        assert_error!(
+            res,
+            ArgminError,
+            concat!(
+                "Invalid parameter: \"`LatinHypercube`: ",
+                "lower and upper bound must be non-empty and of equal length.\""
+            )
+        );
+    }
+
+    #[test]
+    fn test_init_checks_parameters() {
+        let solver = LatinHypercube::new((vec![], vec![]), 10);
+        let res = Executor::new(Sphere {}, solver)
+            .configure(|state| state.max_iters(1))
+            .run();
+        assert_error!(
+            res,
+            ArgminError,
+            concat!(
+                "Invalid parameter: \"`LatinHypercube`: ",
+                "lower and upper bound must be non-empty and of equal length.\""
+            )
+        );
+    }
+
+    #[test]
+    fn test_latin_hypercube() {
+        let solver = LatinHypercube::new((vec![-1.0f64, -1.0], vec![1.0, 1.0]), 10)
+            .with_rng_generator(StdRng::seed_from_u64(42));
+        let res = Executor::new(Sphere {}, solver)
+            .configure(|state| state.param(vec![1.0, 1.0]).max_iters(50))
+            .run()
+            .unwrap();
+        assert_eq!(res.problem.counts["cost_count"], 501);
+        assert!(res.state.get_best_cost() < 1e-2);
+        let best = res.state.get_best_param().unwrap();
+        assert!(best.iter().all(|x| (-1.0..=1.0).contains(x)));
+    }
+}
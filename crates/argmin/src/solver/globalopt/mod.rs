@@ -0,0 +1,103 @@
+// Copyright 2018-2024 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+//! # Global optimization
+//!
+//! Derivative-free methods which explore the search space given by box bounds.
+//!
+//! The sampling based methods do not use any information about the cost function. They are rarely
+//! competitive on their own, but serve as baselines when benchmarking other solvers and as robust
+//! defaults for expensive, noisy or discontinuous cost functions such as in hyperparameter tuning.
+//!
+//! * [`RandomSearch`]: Samples uniformly from the search space.
+//! * [`LatinHypercube`]: Samples stratified Latin hypercube designs.
+//!
+//! Both solvers evaluate a batch of samples per iteration via
+//! [`bulk_cost`](`crate::core::Problem::bulk_cost`), which is computed in parallel if the `rayon`
+//! feature is enabled.
+//!
+//! The population-based metaheuristics maintain a set of parameter vectors which is improved over
+//! the iterations. They operate on a [`PopulationState`](`crate::core::PopulationState`), which
+//! exposes the population to observers.
+//!
+//! * [`HarmonySearch`]: Improvises new parameter vectors from a harmony memory.
+//! * [`ArtificialBeeColony`]: Explores the neighborhood of food sources, guided by their fitness.
+//!
+//! ## References
+//!
+//! \[0\] Bergstra, J. and Bengio, Y. (2012): Random Search for Hyper-Parameter Optimization.
+//! Journal of Machine Learning Research 13, 281–305.
+//!
+//! \[1\] McKay, M.D., Beckman, R.J. and Conover, W.J. (1979): A Comparison of Three Methods for
+//! Selecting Values of Input Variables in the Analysis of Output from a Computer Code.
+//! Technometrics 21 (2), 239–245. <https://doi.org/10.2307/1268522>
+//!
+//! \[2\] Geem, Z.W., Kim, J.H. and Loganathan, G.V. (2001): A New Heuristic Optimization
+//! Algorithm: Harmony Search. Simulation 76 (2), 60–68. <https://doi.org/10.1177/003754970107600201>
+//!
+//! \[3\] Karaboga, D. and Basturk, B. (2007): A powerful and efficient algorithm for numerical
+//! function optimization: artificial bee colony (ABC) algorithm. Journal of Global Optimization
+//! 39, 459–471. <https://doi.org/10.1007/s10898-007-9149-x>
+
+mod beecolony;
+mod harmonysearch;
+mod latinhypercube;
+mod randomsearch;
+
+pub use self::beecolony::{ArtificialBeeColony, ArtificialBeeColonyConfig};
+pub use self::harmonysearch::{HarmonySearch, HarmonySearchConfig};
+pub use self::latinhypercube::{LatinHypercube, LatinHypercubeConfig};
+pub use self::randomsearch::{RandomSearch, RandomSearchConfig};
+
+use crate::core::{ArgminFloat, Error};
+use rand::Rng;
+
+/// Returns the index of the smallest cost, ignoring NaNs
+fn argmin_cost<F: ArgminFloat>(costs: &[F]) -> Option<usize> {
+    costs
+        .iter()
+        .enumerate()
+        .filter(|(_, c)| !c.is_nan())
+        .min_by(|a, b| a.1.partial_cmp(b.1).unwrap_or(std::cmp::Ordering::Equal))
+        .map(|(i, _)| i)
+}
+
+/// Returns the index of the largest cost, where NaNs are considered larger than any other cost
+fn argmax_cost<F: ArgminFloat>(costs: &[F]) -> usize {
+    costs
+        .iter()
+        .enumerate()
+        .max_by(|a, b| match (a.1.is_nan(), b.1.is_nan()) {
+            (true, true) => std::cmp::Ordering::Equal,
+            (true, false) => std::cmp::Ordering::Greater,
+            (false, true) => std::cmp::Ordering::Less,
+            (false, false) => a.1.partial_cmp(b.1).unwrap(),
+        })
+        .map(|(i, _)| i)
+        .unwrap_or(0)
+}
+
+/// Checks that the bounds are non-empty and of equal length
+fn check_bounds<F>(solver: &str, bounds: &(Vec<F>, Vec<F>)) -> Result<(), Error> {
+    if bounds.0.is_empty() || bounds.0.len() != bounds.1.len() {
+        return Err(argmin_error!(
+            InvalidParameter,
+            format!("`{solver}`: lower and upper bound must be non-empty and of equal length.")
+        ));
+    }
+    Ok(())
+}
+
+/// Samples a parameter vector uniformly from the search space given by `bounds`
+fn sample_uniform<F: ArgminFloat, R: Rng>(bounds: &(Vec<F>, Vec<F>), rng: &mut R) -> Vec<F> {
+    bounds
+        .0
+        .iter()
+        .zip(bounds.1.iter())
+        .map(|(l, u)| *l + float!(rng.gen::<f64>()) * (*u - *l))
+        .collect()
+}
@@ -0,0 +1,350 @@
+// Copyright 2018-2024 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use super::argmin_cost;
+use crate::core::{
+    ArgminFloat, CostFunction, Error, IterState, Problem, Solver, State, SyncAlias, KV,
+};
+use argmin_math::ArgminRandom;
+use rand::{Rng, SeedableRng};
+use rand_xoshiro::Xoshiro256PlusPlus;
+#[cfg(feature = "serde1")]
+use serde::{Deserialize, Serialize};
+
+/// # Random search
+///
+/// Samples parameter vectors uniformly from the search space given by box bounds. In each
+/// iteration, a batch of [`batch_size`](`RandomSearch::with_batch_size`) samples is evaluated via
+/// [`bulk_cost`](`crate::core::Problem::bulk_cost`) and the best sample of the batch becomes the
+/// current parameter vector. The best parameter vector found so far is tracked by the state as
+/// usual.
+///
+/// If an initial parameter vector is provided via `Executor`s `configure` method, it is evaluated
+/// in the first iteration and treated as the first sample.
+///
+/// The `rayon` feature enables parallel computation of the cost function within a batch.
+///
+/// ## Requirements on the optimization problem
+///
+/// The optimization problem is required to implement [`CostFunction`].
+///
+/// ## Reference
+///
+/// Bergstra, J. and Bengio, Y. (2012): Random Search for Hyper-Parameter Optimization. Journal of
+/// Machine Learning Research 13, 281–305.
+#[derive(Clone)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+pub struct RandomSearch<P, R> {
+    /// Bounds on parameter space
+    bounds: (P, P),
+    /// Number of samples evaluated per iteration
+    batch_size: usize,
+    /// Random number generator
+    rng_generator: R,
+}
+
+impl<P> RandomSearch<P, Xoshiro256PlusPlus>
+where
+    P: ArgminRandom,
+{
+    /// Construct a new instance of `RandomSearch`
+    ///
+    /// `bounds` is a tuple `(lower_bound, upper_bound)`, where `lower_bound` and `upper_bound`
+    /// are of the same type and length as the parameter vector. By default, one sample is
+    /// evaluated per iteration.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::globalopt::RandomSearch;
+    /// # let lower_bound: Vec<f64> = vec![-1.0, -1.0];
+    /// # let upper_bound: Vec<f64> = vec![1.0, 1.0];
+    /// let solver = RandomSearch::new((lower_bound, upper_bound));
+    /// ```
+    pub fn new(bounds: (P, P)) -> Self {
+        RandomSearch {
+            bounds,
+            batch_size: 1,
+            rng_generator: Xoshiro256PlusPlus::from_entropy(),
+        }
+    }
+}
+
+impl<P, R0> RandomSearch<P, R0>
+where
+    P: ArgminRandom,
+    R0: Rng,
+{
+    /// Set the number of samples evaluated per iteration
+    ///
+    /// Must be larger than 0. Defaults to 1.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::globalopt::RandomSearch;
+    /// # use argmin::core::Error;
+    /// # fn main() -> Result<(), Error> {
+    /// # let lower_bound: Vec<f64> = vec![-1.0, -1.0];
+    /// # let upper_bound: Vec<f64> = vec![1.0, 1.0];
+    /// let solver = RandomSearch::new((lower_bound, upper_bound)).with_batch_size(20)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_batch_size(mut self, batch_size: usize) -> Result<Self, Error> {
+        if batch_size == 0 {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "`RandomSearch`: batch size must be > 0."
+            ));
+        }
+        self.batch_size = batch_size;
+        Ok(self)
+    }
+
+    /// Set the random number generator
+    ///
+    /// Defaults to `Xoshiro256PlusPlus::from_entropy()`
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::globalopt::RandomSearch;
+    /// use rand::SeedableRng;
+    ///
+    /// # let lower_bound: Vec<f64> = vec![-1.0, -1.0];
+    /// # let upper_bound: Vec<f64> = vec![1.0, 1.0];
+    /// let solver = RandomSearch::new((lower_bound, upper_bound))
+    ///     .with_rng_generator(rand_xoshiro::Xoroshiro128Plus::seed_from_u64(1));
+    /// ```
+    pub fn with_rng_generator<R1: Rng>(self, generator: R1) -> RandomSearch<P, R1> {
+        RandomSearch {
+            bounds: self.bounds,
+            batch_size: self.batch_size,
+            rng_generator: generator,
+        }
+    }
+}
+
+/// Configuration of [`RandomSearch`]
+///
+/// # Example
+///
+/// ```
+/// # use argmin::core::Error;
+/// # use argmin::solver::globalopt::RandomSearchConfig;
+/// # fn main() -> Result<(), Error> {
+/// let config = RandomSearchConfig {
+///     bounds: (vec![-1.0f64, -1.0], vec![1.0, 1.0]),
+///     batch_size: Some(20),
+///     seed: Some(1729),
+/// };
+/// let solver = config.build()?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize), serde(default))]
+pub struct RandomSearchConfig<P> {
+    /// Bounds on the parameter space as `(lower_bound, upper_bound)`
+    pub bounds: (P, P),
+    /// See [`RandomSearch::with_batch_size`]
+    pub batch_size: Option<usize>,
+    /// Seed of the `Xoshiro256PlusPlus` RNG. If `None`, the RNG is seeded from entropy.
+    pub seed: Option<u64>,
+}
+
+impl<P> RandomSearchConfig<P>
+where
+    P: Clone + ArgminRandom,
+{
+    /// Validates the configuration and constructs a [`RandomSearch`] solver.
+    pub fn build(&self) -> Result<RandomSearch<P, Xoshiro256PlusPlus>, Error> {
+        let mut solver = RandomSearch::new(self.bounds.clone());
+        if let Some(seed) = self.seed {
+            solver = solver.with_rng_generator(Xoshiro256PlusPlus::seed_from_u64(seed));
+        }
+        if let Some(batch_size) = self.batch_size {
+            solver = solver.with_batch_size(batch_size)?;
+        }
+        Ok(solver)
+    }
+}
+
+impl<O, P, F, R> Solver<O, IterState<P, (), (), (), (), F>> for RandomSearch<P, R>
+where
+    O: CostFunction<Param = P, Output = F> + SyncAlias,
+    P: Clone + SyncAlias + ArgminRandom,
+    F: ArgminFloat,
+    R: Rng,
+{
+    fn name(&self) -> &str {
+        "Random Search"
+    }
+
+    fn init(
+        &mut self,
+        _problem: &mut Problem<O>,
+        state: IterState<P, (), (), (), (), F>,
+    ) -> Result<(IterState<P, (), (), (), (), F>, Option<KV>), Error> {
+        Ok((
+            state,
+            Some(
+                kv!(
+                    "batch_size" => self.batch_size as u64;
+                    "rng" => std::any::type_name::<R>();
+                )
+                .with_prefix("config"),
+            ),
+        ))
+    }
+
+    fn next_iter(
+        &mut self,
+        problem: &mut Problem<O>,
+        mut state: IterState<P, (), (), (), (), F>,
+    ) -> Result<(IterState<P, (), (), (), (), F>, Option<KV>), Error> {
+        let (lower, upper) = &self.bounds;
+        let mut samples: Vec<P> = Vec::with_capacity(self.batch_size + 1);
+        // An initial guess is evaluated along with the first batch
+        if state.get_iter() == 0 {
+            if let Some(param) = state.take_param() {
+                samples.push(param);
+            }
+        }
+        samples.extend(
+            (0..self.batch_size).map(|_| P::rand_from_range(lower, upper, &mut self.rng_generator)),
+        );
+        let costs = problem.bulk_cost(&samples)?;
+
+        Ok(match argmin_cost(&costs) {
+            Some(idx) => (state.param(samples.swap_remove(idx)).cost(costs[idx]), None),
+            None => (state, None),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::{ArgminError, Executor};
+    use rand::rngs::StdRng;
+
+    test_trait_impl!(random_search, RandomSearch<Vec<f64>, StdRng>);
+
+    struct Sphere {}
+
+    impl CostFunction for Sphere {
+        type Param = Vec<f64>;
+        type Output = f64;
+
+        fn cost(&self, p: &Self::Param) -> Result<Self::Output, Error> {
+            Ok(p.iter().map(|x| (x - 0.5).powi(2)).sum())
+        }
+    }
+
+    #[test]
+    fn test_new() {
+        let solver = RandomSearch::new((vec![-1.0f64, -2.0], vec![1.0, 2.0]));
+        let RandomSearch {
+            bounds, batch_size, ..
+        } = solver;
+        assert_eq!(bounds, (vec![-1.0, -2.0], vec![1.0, 2.0]));
+        assert_eq!(batch_size, 1);
+    }
+
+    #[test]
+    fn test_with_batch_size() {
+        let solver = RandomSearch::new((vec![-1.0f64], vec![1.0]))
+            .with_batch_size(10)
+            .unwrap();
+        assert_eq!(solver.batch_size, 10);
+
+        let res = RandomSearch::new((vec![-1.0f64], vec![1.0])).with_batch_size(0);
+        assert_error!(
+            res,
+            ArgminError,
+            "Invalid parameter: \"`RandomSearch`: batch size must be > 0.\""
+        );
+    }
+
+    #[test]
+    fn test_config_build() {
+        let config = RandomSearchConfig {
+            bounds: (vec![-1.0f64, -1.0], vec![1.0, 1.0]),
+            batch_size: Some(5),
+            seed: Some(1729),
+        };
+        let solver = config.build().unwrap();
+        assert_eq!(solver.bounds, config.bounds);
+        assert_eq!(solver.batch_size, 5);
+
+        let mut rng1 = config.build().unwrap().rng_generator;
+        let mut rng2 = config.build().unwrap().rng_generator;
+        assert_eq!(rng1.gen::<u64>(), rng2.gen::<u64>());
+
+        let res = RandomSearchConfig {
+            batch_size: Some(0),
+            ..config
+        }
+        .build();
+        assert_error!(
+            res,
+            ArgminError,
+            "Invalid parameter: \"`RandomSearch`: batch size must be > 0.\""
+        );
+    }
+
+    #[test]
+    fn test_random_search() {
+        let solver = RandomSearch::new((vec![-1.0f64, -1.0], vec![1.0, 1.0]))
+            .with_batch_size(10)
+            .unwrap()
+            .with_rng_generator(StdRng::seed_from_u64(42));
+        let res = Executor::new(Sphere {}, solver)
+            .configure(|state| state.max_iters(50))
+            .run()
+            .unwrap();
+        assert_eq!(res.problem.counts["cost_count"], 500);
+        assert!(res.state.get_best_cost() < 1e-2);
+        let best = res.state.get_best_param().unwrap();
+        assert!(best.iter().all(|x| (-1.0..=1.0).contains(x)));
+    }
+
+    #[test]
+    fn test_random_search_initial_param() {
+        let solver = RandomSearch::new((vec![-1.0f64, -1.0], vec![1.0, 1.0]))
+            .with_rng_generator(StdRng::seed_from_u64(42));
+        let res = Executor::new(Sphere {}, solver)
+            .configure(|state| state.param(vec![0.5, 0.5]).max_iters(3))
+            .run()
+            .unwrap();
+        assert_eq!(res.problem.counts["cost_count"], 4);
+        assert_eq!(res.state.get_best_param().unwrap(), &vec![0.5, 0.5]);
+        assert_eq!(
+            res.state.get_best_cost().to_ne_bytes(),
+            0.0f64.to_ne_bytes()
+        );
+    }
+
+    #[test]
+    fn test_random_search_reproducible() {
+        let run = || {
+            let solver = RandomSearch::new((vec![-1.0f64, -1.0], vec![1.0, 1.0]))
+                .with_batch_size(3)
+                .unwrap()
+                .with_rng_generator(StdRng::seed_from_u64(7));
+            Executor::new(Sphere {}, solver)
+                .configure(|state| state.max_iters(5))
+                .run()
+                .unwrap()
+                .state
+                .best_param
+        };
+        assert_eq!(run(), run());
+    }
+}
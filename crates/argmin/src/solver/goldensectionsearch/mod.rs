@@ -58,7 +58,8 @@ pub struct GoldenSectionSearch<F> {
     g2: F,
     min_bound: F,
     max_bound: F,
-    tolerance: F,
+    tol_abs: F,
+    tol_rel: F,
 
     x0: F,
     x1: F,
@@ -98,7 +99,8 @@ where
             g2: F::from(G2).unwrap(),
             min_bound,
             max_bound,
-            tolerance: F::from(0.01).unwrap(),
+            tol_abs: F::zero(),
+            tol_rel: F::from(0.01).unwrap(),
             x0: min_bound,
             x1: F::zero(),
             x2: F::zero(),
@@ -108,9 +110,12 @@ where
         })
     }
 
-    /// Set tolerance.
+    /// Set absolute and relative tolerance.
     ///
-    /// Must be larger than `0` and defaults to `0.01`.
+    /// The search terminates once the width of the bracketing interval falls below
+    /// `abs + rel * (|x1| + |x2|)`, where `x1` and `x2` are the two inner points. Both must be
+    /// non-negative and at least one of them must be larger than `0`. Defaults to `abs = 0` and
+    /// `rel = 0.01`.
     ///
     /// # Example
     ///
@@ -118,20 +123,90 @@ where
     /// # use argmin::solver::goldensectionsearch::GoldenSectionSearch;
     /// # use argmin::core::Error;
     /// # fn main() -> Result<(), Error> {
-    /// let gss = GoldenSectionSearch::new(-2.5f64, 3.0f64)?.with_tolerance(0.0001)?;
+    /// let gss = GoldenSectionSearch::new(-2.5f64, 3.0f64)?.with_tolerance(1e-8, 0.0001)?;
     /// # Ok(())
     /// # }
     /// ```
-    pub fn with_tolerance(mut self, tolerance: F) -> Result<Self, Error> {
-        if tolerance <= float!(0.0) {
+    pub fn with_tolerance(mut self, abs: F, rel: F) -> Result<Self, Error> {
+        if abs < float!(0.0) || rel < float!(0.0) {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "`GoldenSectionSearch`: Tolerances must be >= 0."
+            ));
+        }
+        if abs <= float!(0.0) && rel <= float!(0.0) {
             return Err(argmin_error!(
                 InvalidParameter,
                 "`GoldenSectionSearch`: Tolerance must be larger than 0."
             ));
         }
-        self.tolerance = tolerance;
+        self.tol_abs = abs;
+        self.tol_rel = rel;
         Ok(self)
     }
+
+    /// Returns the current bracket `[lower, upper]`, its width, and the estimated accuracy of the
+    /// current best estimate (the largest distance between the estimate and the bracket bounds).
+    fn diagnostics(&self) -> KV {
+        let best = if self.f1 < self.f2 { self.x1 } else { self.x2 };
+        kv!(
+            "interval_width" => (self.x3 - self.x0).abs();
+            "bracket_lower" => self.x0;
+            "bracket_upper" => self.x3;
+            "accuracy" => (best - self.x0).abs().max((self.x3 - best).abs());
+        )
+    }
+}
+
+/// Serializable configuration of [`GoldenSectionSearch`]
+///
+/// Options which are `None` keep the defaults of [`GoldenSectionSearch`]. The configuration is
+/// validated when the solver is constructed via [`build`](`GoldenSectionSearchConfig::build`).
+///
+/// # Example
+///
+/// ```
+/// # use argmin::solver::goldensectionsearch::GoldenSectionSearchConfig;
+/// # use argmin::core::Error;
+/// # fn main() -> Result<(), Error> {
+/// let config = GoldenSectionSearchConfig {
+///     min_bound: -2.5f64,
+///     max_bound: 3.0,
+///     tolerance_rel: Some(0.0001),
+///     ..Default::default()
+/// };
+/// let gss = config.build()?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize), serde(default))]
+pub struct GoldenSectionSearchConfig<F> {
+    /// Lower bound of the interval bracketing the minimum
+    pub min_bound: F,
+    /// Upper bound of the interval bracketing the minimum
+    pub max_bound: F,
+    /// Absolute tolerance, see [`GoldenSectionSearch::with_tolerance`]
+    pub tolerance_abs: Option<F>,
+    /// Relative tolerance, see [`GoldenSectionSearch::with_tolerance`]
+    #[cfg_attr(feature = "serde1", serde(alias = "tolerance"))]
+    pub tolerance_rel: Option<F>,
+}
+
+impl<F> GoldenSectionSearchConfig<F>
+where
+    F: ArgminFloat,
+{
+    /// Validates the configuration and constructs a [`GoldenSectionSearch`] solver.
+    pub fn build(&self) -> Result<GoldenSectionSearch<F>, Error> {
+        let mut solver = GoldenSectionSearch::new(self.min_bound, self.max_bound)?;
+        if self.tolerance_abs.is_some() || self.tolerance_rel.is_some() {
+            let abs = self.tolerance_abs.unwrap_or(solver.tol_abs);
+            let rel = self.tolerance_rel.unwrap_or(solver.tol_rel);
+            solver = solver.with_tolerance(abs, rel)?;
+        }
+        Ok(solver)
+    }
 }
 
 impl<O, F> Solver<O, IterState<F, (), (), (), (), F>> for GoldenSectionSearch<F>
@@ -172,10 +247,18 @@ where
             self.x2 = x2;
             self.f1 = problem.cost(&self.x1)?;
             self.f2 = problem.cost(&self.x2)?;
+            let config = kv!(
+                "min_bound" => self.min_bound;
+                "max_bound" => self.max_bound;
+                "tolerance_abs" => self.tol_abs;
+                "tolerance_rel" => self.tol_rel;
+            )
+            .with_prefix("config")
+            .merge(self.diagnostics());
             if self.f1 < self.f2 {
-                Ok((state.param(self.x1).cost(self.f1), None))
+                Ok((state.param(self.x1).cost(self.f1), Some(config)))
             } else {
-                Ok((state.param(self.x2).cost(self.f2), None))
+                Ok((state.param(self.x2).cost(self.f2), Some(config)))
             }
         }
     }
@@ -198,15 +281,18 @@ where
             self.f2 = self.f1;
             self.f1 = problem.cost(&self.x1)?;
         }
+        let kv = self.diagnostics();
         if self.f1 < self.f2 {
-            Ok((state.param(self.x1).cost(self.f1), None))
+            Ok((state.param(self.x1).cost(self.f1), Some(kv)))
         } else {
-            Ok((state.param(self.x2).cost(self.f2), None))
+            Ok((state.param(self.x2).cost(self.f2), Some(kv)))
         }
     }
 
     fn terminate(&mut self, _state: &IterState<F, (), (), (), (), F>) -> TerminationStatus {
-        if self.tolerance * (self.x1.abs() + self.x2.abs()) >= (self.x3 - self.x0).abs() {
+        if self.tol_abs + self.tol_rel * (self.x1.abs() + self.x2.abs())
+            >= (self.x3 - self.x0).abs()
+        {
             return TerminationStatus::Terminated(TerminationReason::SolverConverged);
         }
         TerminationStatus::NotTerminated
@@ -216,7 +302,7 @@ where
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::core::{ArgminError, State};
+    use crate::core::{ArgminError, KvValue, State};
     use approx::assert_relative_eq;
 
     #[derive(Clone)]
@@ -240,7 +326,8 @@ mod tests {
             g2,
             min_bound,
             max_bound,
-            tolerance,
+            tol_abs,
+            tol_rel,
             x0,
             x1,
             x2,
@@ -253,7 +340,8 @@ mod tests {
         assert_eq!(g2.to_ne_bytes(), G2.to_ne_bytes());
         assert_eq!(min_bound.to_ne_bytes(), (-2.5f64).to_ne_bytes());
         assert_eq!(max_bound.to_ne_bytes(), 3.0f64.to_ne_bytes());
-        assert_eq!(tolerance.to_ne_bytes(), 0.01f64.to_ne_bytes());
+        assert_eq!(tol_abs.to_ne_bytes(), 0f64.to_ne_bytes());
+        assert_eq!(tol_rel.to_ne_bytes(), 0.01f64.to_ne_bytes());
         assert_eq!(x0.to_ne_bytes(), min_bound.to_ne_bytes());
         assert_eq!(x1.to_ne_bytes(), 0f64.to_ne_bytes());
         assert_eq!(x2.to_ne_bytes(), 0f64.to_ne_bytes());
@@ -289,28 +377,79 @@ mod tests {
 
     #[test]
     fn test_tolerance() {
-        let GoldenSectionSearch { tolerance, .. } = GoldenSectionSearch::new(-2.5f64, 3.0f64)
+        let GoldenSectionSearch {
+            tol_abs, tol_rel, ..
+        } = GoldenSectionSearch::new(-2.5f64, 3.0f64)
             .unwrap()
-            .with_tolerance(0.001)
+            .with_tolerance(1e-8, 0.001)
             .unwrap();
 
-        assert_eq!(tolerance.to_ne_bytes(), 0.001f64.to_ne_bytes());
+        assert_eq!(tol_abs.to_ne_bytes(), 1e-8f64.to_ne_bytes());
+        assert_eq!(tol_rel.to_ne_bytes(), 0.001f64.to_ne_bytes());
+
+        let GoldenSectionSearch {
+            tol_abs, tol_rel, ..
+        } = GoldenSectionSearch::new(-2.5f64, 3.0f64)
+            .unwrap()
+            .with_tolerance(1e-8, 0.0)
+            .unwrap();
+
+        assert_eq!(tol_abs.to_ne_bytes(), 1e-8f64.to_ne_bytes());
+        assert_eq!(tol_rel.to_ne_bytes(), 0f64.to_ne_bytes());
     }
 
     #[test]
     fn test_tolerance_errors() {
         let res = GoldenSectionSearch::new(-2.5f64, 3.0f64)
             .unwrap()
-            .with_tolerance(0.0);
+            .with_tolerance(0.0, 0.0);
         assert_error!(
             res,
             ArgminError,
             "Invalid parameter: \"`GoldenSectionSearch`: Tolerance must be larger than 0.\""
         );
 
-        let res = GoldenSectionSearch::new(-2.5f64, 3.0f64)
-            .unwrap()
-            .with_tolerance(-1.0);
+        for (abs, rel) in [(-1.0, 0.01), (0.01, -1.0)] {
+            let res = GoldenSectionSearch::new(-2.5f64, 3.0f64)
+                .unwrap()
+                .with_tolerance(abs, rel);
+            assert_error!(
+                res,
+                ArgminError,
+                "Invalid parameter: \"`GoldenSectionSearch`: Tolerances must be >= 0.\""
+            );
+        }
+    }
+
+    #[test]
+    fn test_config_build() {
+        let config = GoldenSectionSearchConfig {
+            min_bound: -2.5f64,
+            max_bound: 3.0,
+            tolerance_abs: None,
+            tolerance_rel: Some(0.001),
+        };
+        let gss = config.build().unwrap();
+        assert_eq!(gss.min_bound.to_ne_bytes(), (-2.5f64).to_ne_bytes());
+        assert_eq!(gss.max_bound.to_ne_bytes(), 3.0f64.to_ne_bytes());
+        assert_eq!(gss.tol_abs.to_ne_bytes(), 0f64.to_ne_bytes());
+        assert_eq!(gss.tol_rel.to_ne_bytes(), 0.001f64.to_ne_bytes());
+
+        let res = GoldenSectionSearchConfig::<f64>::default().build();
+        assert_error!(
+            res,
+            ArgminError,
+            concat!(
+                "Invalid parameter: \"`GoldenSectionSearch`: ",
+                "`min_bound` must be smaller than `max_bound`.\""
+            )
+        );
+
+        let res = GoldenSectionSearchConfig {
+            tolerance_rel: Some(0.0),
+            ..config
+        }
+        .build();
         assert_error!(
             res,
             ArgminError,
@@ -359,14 +498,26 @@ mod tests {
             )
             .unwrap();
 
-        assert!(kv.is_none());
+        let kv = kv.unwrap();
+        assert_eq!(kv.get("config.min_bound"), Some(&KvValue::Float(-2.5f64)));
+        assert_eq!(kv.get("config.max_bound"), Some(&KvValue::Float(3.0f64)));
+        assert_eq!(
+            kv.get("config.tolerance_abs"),
+            Some(&KvValue::Float(0.0f64))
+        );
+        assert_eq!(
+            kv.get("config.tolerance_rel"),
+            Some(&KvValue::Float(0.01f64))
+        );
+        assert_eq!(kv.get("interval_width"), Some(&KvValue::Float(5.5f64)));
 
         let GoldenSectionSearch {
             g1,
             g2,
             min_bound,
             max_bound,
-            tolerance,
+            tol_abs,
+            tol_rel,
             x0,
             x1,
             x2,
@@ -391,7 +542,8 @@ mod tests {
         assert_eq!(g2.to_ne_bytes(), G2.to_ne_bytes());
         assert_eq!(min_bound.to_ne_bytes(), (-2.5f64).to_ne_bytes());
         assert_eq!(max_bound.to_ne_bytes(), 3.0f64.to_ne_bytes());
-        assert_eq!(tolerance.to_ne_bytes(), 0.01f64.to_ne_bytes());
+        assert_eq!(tol_abs.to_ne_bytes(), 0f64.to_ne_bytes());
+        assert_eq!(tol_rel.to_ne_bytes(), 0.01f64.to_ne_bytes());
         assert_eq!(x0.to_ne_bytes(), min_bound.to_ne_bytes());
         assert_eq!(x3.to_ne_bytes(), max_bound.to_ne_bytes());
     }
@@ -412,14 +564,21 @@ mod tests {
             .next_iter(&mut problem, IterState::new().param(-0.5f64))
             .unwrap();
 
-        assert!(kv.is_none());
+        let kv = kv.unwrap();
+        assert_eq!(
+            kv.get("interval_width"),
+            Some(&KvValue::Float(gss.x3 - gss.x0))
+        );
+        assert_eq!(kv.get("bracket_lower"), Some(&KvValue::Float(gss.x0)));
+        assert_eq!(kv.get("bracket_upper"), Some(&KvValue::Float(gss.x3)));
 
         let GoldenSectionSearch {
             g1,
             g2,
             min_bound,
             max_bound,
-            tolerance,
+            tol_abs,
+            tol_rel,
             x0,
             x1,
             x2,
@@ -437,7 +596,8 @@ mod tests {
         assert_eq!(g2.to_ne_bytes(), G2.to_ne_bytes());
         assert_eq!(min_bound.to_ne_bytes(), (-2.5f64).to_ne_bytes());
         assert_eq!(max_bound.to_ne_bytes(), 3.0f64.to_ne_bytes());
-        assert_eq!(tolerance.to_ne_bytes(), 0.01f64.to_ne_bytes());
+        assert_eq!(tol_abs.to_ne_bytes(), 0f64.to_ne_bytes());
+        assert_eq!(tol_rel.to_ne_bytes(), 0.01f64.to_ne_bytes());
         if f1 < f2 {
             assert_relative_eq!(*state.param.as_ref().unwrap(), x1, epsilon = f64::EPSILON);
             assert_relative_eq!(state.cost, f1, epsilon = f64::EPSILON);
@@ -463,14 +623,21 @@ mod tests {
             .next_iter(&mut problem, IterState::new().param(-0.5f64))
             .unwrap();
 
-        assert!(kv.is_none());
+        let kv = kv.unwrap();
+        assert_eq!(
+            kv.get("interval_width"),
+            Some(&KvValue::Float(gss.x3 - gss.x0))
+        );
+        assert_eq!(kv.get("bracket_lower"), Some(&KvValue::Float(gss.x0)));
+        assert_eq!(kv.get("bracket_upper"), Some(&KvValue::Float(gss.x3)));
 
         let GoldenSectionSearch {
             g1,
             g2,
             min_bound,
             max_bound,
-            tolerance,
+            tol_abs,
+            tol_rel,
             x0,
             x1,
             x2,
@@ -489,7 +656,8 @@ mod tests {
         assert_eq!(g2.to_ne_bytes(), G2.to_ne_bytes());
         assert_eq!(min_bound.to_ne_bytes(), (-2.5f64).to_ne_bytes());
         assert_eq!(max_bound.to_ne_bytes(), 3.0f64.to_ne_bytes());
-        assert_eq!(tolerance.to_ne_bytes(), 0.01f64.to_ne_bytes());
+        assert_eq!(tol_abs.to_ne_bytes(), 0f64.to_ne_bytes());
+        assert_eq!(tol_rel.to_ne_bytes(), 0.01f64.to_ne_bytes());
         if f1 < f2 {
             assert_relative_eq!(*state.param.as_ref().unwrap(), x1, epsilon = f64::EPSILON);
             assert_relative_eq!(state.cost, f1, epsilon = f64::EPSILON);
@@ -0,0 +1,601 @@
+// Copyright 2018-2024 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use crate::core::{
+    ArgminFloat, CostFunction, Error, Gradient, IterState, Problem, Solver, State, KV,
+};
+use argmin_math::{ArgminDot, ArgminScaledAdd, ArgminSub};
+#[cfg(feature = "serde1")]
+use serde::{Deserialize, Serialize};
+
+/// Step size of [`AcceleratedGradientDescent`]
+///
+/// The step size is `1/L`, where `L` is the Lipschitz constant of the gradient.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+pub enum StepSize<F> {
+    /// Fixed step size `1/L` for a known Lipschitz constant `L` of the gradient
+    Lipschitz(F),
+    /// Estimate the Lipschitz constant via backtracking
+    ///
+    /// Starting from `initial`, the estimate is multiplied by `factor` until the sufficient
+    /// decrease condition `f(x) <= f(y) + g^T (x - y) + L/2 ||x - y||^2` holds. The estimate
+    /// is kept for subsequent iterations.
+    Backtracking {
+        /// Initial estimate of the Lipschitz constant
+        initial: F,
+        /// Factor by which the estimate is increased
+        factor: F,
+    },
+}
+
+impl<F: ArgminFloat> Default for StepSize<F> {
+    fn default() -> Self {
+        StepSize::Backtracking {
+            initial: float!(1.0),
+            factor: float!(2.0),
+        }
+    }
+}
+
+/// Adaptive restart scheme of [`AcceleratedGradientDescent`]
+///
+/// Restarting resets the momentum, which avoids the oscillations typical for accelerated methods
+/// and recovers fast convergence on strongly convex problems without knowledge of the strong
+/// convexity parameter.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+pub enum AdaptiveRestart {
+    /// Never restart
+    #[default]
+    Never,
+    /// Restart if the cost function value increases
+    Function,
+    /// Restart if the momentum points in a direction of ascent, i.e. if
+    /// `g^T (x_k - x_{k-1}) > 0` where `g` is the gradient used to compute `x_k`.
+    Gradient,
+}
+
+/// # Accelerated gradient descent
+///
+/// Nesterov's accelerated gradient method for smooth convex problems. In each iteration, a
+/// gradient step with step size `1/L` is taken from an extrapolated point `y_k`:
+///
+/// `x_k = y_k - 1/L * \nabla f(y_k)`
+///
+/// `y_{k+1} = x_k + (t_k - 1)/t_{k+1} * (x_k - x_{k-1})`
+///
+/// with `t_1 = 1` and `t_{k+1} = (1 + sqrt(1 + 4 * t_k^2)) / 2`. For convex functions with
+/// `L`-Lipschitz continuous gradient, the cost converges at a rate of `O(1/k^2)` instead of
+/// `O(1/k)` for gradient descent.
+///
+/// The Lipschitz constant `L` is either known in advance or estimated via backtracking (see
+/// [`StepSize`], default: backtracking). Optionally, the momentum is reset whenever the cost
+/// increases or the momentum points in a direction of ascent (see [`AdaptiveRestart`],
+/// default: no restart).
+///
+/// The cost is evaluated at the initial parameter vector and at every new iterate.
+///
+/// ## Requirements on the optimization problem
+///
+/// The optimization problem is required to implement [`CostFunction`] and [`Gradient`].
+///
+/// ## References
+///
+/// Yurii Nesterov (1983). A method for solving the convex programming problem with convergence
+/// rate O(1/k^2). Soviet Mathematics Doklady 27, 372-376.
+///
+/// Amir Beck and Marc Teboulle (2009). A fast iterative shrinkage-thresholding algorithm for
+/// linear inverse problems. SIAM Journal on Imaging Sciences 2(1), 183-202.
+///
+/// Brendan O'Donoghue and Emmanuel Candès (2015). Adaptive restart for accelerated gradient
+/// schemes. Foundations of Computational Mathematics 15, 715-732.
+#[derive(Clone)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+pub struct AcceleratedGradientDescent<P, F> {
+    /// Step size
+    step_size: StepSize<F>,
+    /// Restart scheme
+    restart: AdaptiveRestart,
+    /// Current estimate of the Lipschitz constant
+    lipschitz: F,
+    /// Momentum parameter `t_k`
+    t: F,
+    /// Extrapolated point
+    y: Option<P>,
+}
+
+impl<P, F> AcceleratedGradientDescent<P, F>
+where
+    F: ArgminFloat,
+{
+    /// Construct a new instance of [`AcceleratedGradientDescent`]
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::gradientdescent::AcceleratedGradientDescent;
+    /// let agd: AcceleratedGradientDescent<Vec<f64>, f64> = AcceleratedGradientDescent::new();
+    /// ```
+    pub fn new() -> Self {
+        AcceleratedGradientDescent {
+            step_size: StepSize::default(),
+            restart: AdaptiveRestart::default(),
+            lipschitz: F::nan(),
+            t: float!(1.0),
+            y: None,
+        }
+    }
+
+    /// Set the step size
+    ///
+    /// The Lipschitz constant as well as its initial estimate must be larger than 0 and the
+    /// backtracking factor must be larger than 1. Defaults to backtracking with an initial
+    /// estimate of 1 and a factor of 2.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::gradientdescent::{AcceleratedGradientDescent, StepSize};
+    /// # use argmin::core::Error;
+    /// # fn main() -> Result<(), Error> {
+    /// let agd: AcceleratedGradientDescent<Vec<f64>, f64> =
+    ///     AcceleratedGradientDescent::new().with_step_size(StepSize::Lipschitz(100.0))?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_step_size(mut self, step_size: StepSize<F>) -> Result<Self, Error> {
+        match step_size {
+            StepSize::Lipschitz(l) if l <= float!(0.0) => {
+                return Err(argmin_error!(
+                    InvalidParameter,
+                    "`AcceleratedGradientDescent`: Lipschitz constant must be > 0."
+                ));
+            }
+            StepSize::Backtracking { initial, .. } if initial <= float!(0.0) => {
+                return Err(argmin_error!(
+                    InvalidParameter,
+                    "`AcceleratedGradientDescent`: initial Lipschitz constant must be > 0."
+                ));
+            }
+            StepSize::Backtracking { factor, .. } if factor <= float!(1.0) => {
+                return Err(argmin_error!(
+                    InvalidParameter,
+                    "`AcceleratedGradientDescent`: backtracking factor must be > 1."
+                ));
+            }
+            _ => {}
+        }
+        self.step_size = step_size;
+        Ok(self)
+    }
+
+    /// Set the adaptive restart scheme (default: [`AdaptiveRestart::Never`])
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::gradientdescent::{AcceleratedGradientDescent, AdaptiveRestart};
+    /// let agd: AcceleratedGradientDescent<Vec<f64>, f64> =
+    ///     AcceleratedGradientDescent::new().with_restart(AdaptiveRestart::Gradient);
+    /// ```
+    #[must_use]
+    pub fn with_restart(mut self, restart: AdaptiveRestart) -> Self {
+        self.restart = restart;
+        self
+    }
+}
+
+impl<P, F> Default for AcceleratedGradientDescent<P, F>
+where
+    F: ArgminFloat,
+{
+    fn default() -> Self {
+        AcceleratedGradientDescent::new()
+    }
+}
+
+/// Serializable configuration of [`AcceleratedGradientDescent`]
+///
+/// Options which are `None` keep the defaults of [`AcceleratedGradientDescent`]. The
+/// configuration is validated when the solver is constructed via
+/// [`build`](`AcceleratedGradientDescentConfig::build`).
+///
+/// # Example
+///
+/// ```
+/// # use argmin::solver::gradientdescent::{
+/// #     AcceleratedGradientDescent, AcceleratedGradientDescentConfig, AdaptiveRestart,
+/// # };
+/// # use argmin::core::Error;
+/// # fn main() -> Result<(), Error> {
+/// let config = AcceleratedGradientDescentConfig {
+///     restart: Some(AdaptiveRestart::Function),
+///     ..Default::default()
+/// };
+/// let agd: AcceleratedGradientDescent<Vec<f64>, f64> = config.build()?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize), serde(default))]
+pub struct AcceleratedGradientDescentConfig<F> {
+    /// See [`AcceleratedGradientDescent::with_step_size`]
+    pub step_size: Option<StepSize<F>>,
+    /// See [`AcceleratedGradientDescent::with_restart`]
+    pub restart: Option<AdaptiveRestart>,
+}
+
+impl<F> AcceleratedGradientDescentConfig<F>
+where
+    F: ArgminFloat,
+{
+    /// Validates the configuration and constructs an [`AcceleratedGradientDescent`] solver.
+    pub fn build<P>(&self) -> Result<AcceleratedGradientDescent<P, F>, Error> {
+        let mut solver = AcceleratedGradientDescent::new();
+        if let Some(step_size) = self.step_size {
+            solver = solver.with_step_size(step_size)?;
+        }
+        if let Some(restart) = self.restart {
+            solver = solver.with_restart(restart);
+        }
+        Ok(solver)
+    }
+}
+
+impl<O, P, G, F> Solver<O, IterState<P, G, (), (), (), F>> for AcceleratedGradientDescent<P, F>
+where
+    O: CostFunction<Param = P, Output = F> + Gradient<Param = P, Gradient = G>,
+    P: Clone
+        + ArgminSub<P, P>
+        + ArgminDot<P, F>
+        + ArgminScaledAdd<P, F, P>
+        + ArgminScaledAdd<G, F, P>,
+    G: ArgminDot<P, F>,
+    F: ArgminFloat,
+{
+    fn name(&self) -> &str {
+        "Accelerated Gradient Descent"
+    }
+
+    fn init(
+        &mut self,
+        problem: &mut Problem<O>,
+        state: IterState<P, G, (), (), (), F>,
+    ) -> Result<(IterState<P, G, (), (), (), F>, Option<KV>), Error> {
+        let param = state.get_param().ok_or_else(argmin_error_closure!(
+            NotInitialized,
+            concat!(
+                "`AcceleratedGradientDescent` requires an initial parameter vector. ",
+                "Please provide an initial guess via `Executor`s `configure` method."
+            )
+        ))?;
+        let cost = problem.cost(param)?;
+        self.lipschitz = match self.step_size {
+            StepSize::Lipschitz(l) => l,
+            StepSize::Backtracking { initial, .. } => initial,
+        };
+        self.t = float!(1.0);
+        self.y = Some(param.clone());
+        Ok((
+            state.cost(cost),
+            Some(
+                kv!(
+                    "step_size" => format!("{:?}", self.step_size);
+                    "restart" => format!("{:?}", self.restart);
+                )
+                .with_prefix("config"),
+            ),
+        ))
+    }
+
+    fn next_iter(
+        &mut self,
+        problem: &mut Problem<O>,
+        mut state: IterState<P, G, (), (), (), F>,
+    ) -> Result<(IterState<P, G, (), (), (), F>, Option<KV>), Error> {
+        let x_prev = state.take_param().ok_or_else(argmin_error_closure!(
+            PotentialBug,
+            "`AcceleratedGradientDescent`: Parameter vector in `state` not set"
+        ))?;
+        let y = self.y.take().ok_or_else(argmin_error_closure!(
+            PotentialBug,
+            "`AcceleratedGradientDescent`: Extrapolated point not set"
+        ))?;
+        let grad = problem.gradient(&y)?;
+
+        // Gradient step from the extrapolated point. With backtracking, the estimate of the
+        // Lipschitz constant is increased until the sufficient decrease condition holds.
+        let (x, cost) = match self.step_size {
+            StepSize::Lipschitz(_) => {
+                let x = y.scaled_add(&(float!(-1.0) / self.lipschitz), &grad);
+                let cost = problem.cost(&x)?;
+                (x, cost)
+            }
+            StepSize::Backtracking { factor, .. } => {
+                let cost_y = problem.cost(&y)?;
+                loop {
+                    let x = y.scaled_add(&(float!(-1.0) / self.lipschitz), &grad);
+                    let cost = problem.cost(&x)?;
+                    let d = x.sub(&y);
+                    let bound = cost_y + grad.dot(&d) + self.lipschitz * float!(0.5) * d.dot(&d);
+                    if cost <= bound || !self.lipschitz.is_finite() {
+                        break (x, cost);
+                    }
+                    self.lipschitz = self.lipschitz * factor;
+                }
+            }
+        };
+
+        let restart = match self.restart {
+            AdaptiveRestart::Never => false,
+            AdaptiveRestart::Function => cost > state.get_cost(),
+            AdaptiveRestart::Gradient => grad.dot(&x.sub(&x_prev)) > float!(0.0),
+        };
+        let momentum = if restart {
+            self.t = float!(1.0);
+            self.y = Some(x.clone());
+            float!(0.0)
+        } else {
+            let t_next =
+                (float!(1.0) + (float!(1.0) + float!(4.0) * self.t * self.t).sqrt()) / float!(2.0);
+            let momentum = (self.t - float!(1.0)) / t_next;
+            self.t = t_next;
+            self.y = Some(x.scaled_add(&momentum, &x.sub(&x_prev)));
+            momentum
+        };
+
+        Ok((
+            state.param(x).cost(cost),
+            Some(kv!(
+                "lipschitz" => self.lipschitz;
+                "momentum" => momentum;
+                "restart" => restart;
+            )),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::{test_utils::TestProblem, ArgminError, Executor};
+    use approx::assert_relative_eq;
+
+    test_trait_impl!(
+        accelerated_gradient_descent,
+        AcceleratedGradientDescent<Vec<f64>, f64>
+    );
+
+    /// `f(x) = 0.5 * sum_i a_i * x_i^2`
+    struct Quadratic {
+        a: Vec<f64>,
+    }
+
+    impl CostFunction for Quadratic {
+        type Param = Vec<f64>;
+        type Output = f64;
+
+        fn cost(&self, p: &Self::Param) -> Result<Self::Output, Error> {
+            Ok(0.5
+                * p.iter()
+                    .zip(self.a.iter())
+                    .map(|(x, a)| a * x * x)
+                    .sum::<f64>())
+        }
+    }
+
+    impl Gradient for Quadratic {
+        type Param = Vec<f64>;
+        type Gradient = Vec<f64>;
+
+        fn gradient(&self, p: &Self::Param) -> Result<Self::Gradient, Error> {
+            Ok(p.iter().zip(self.a.iter()).map(|(x, a)| a * x).collect())
+        }
+    }
+
+    fn quadratic() -> Quadratic {
+        Quadratic {
+            a: vec![1.0, 10.0, 100.0, 1000.0],
+        }
+    }
+
+    fn run(
+        solver: AcceleratedGradientDescent<Vec<f64>, f64>,
+        max_iters: u64,
+    ) -> IterState<Vec<f64>, Vec<f64>, (), (), (), f64> {
+        Executor::new(quadratic(), solver)
+            .configure(|state| state.param(vec![1.0; 4]).max_iters(max_iters))
+            .run()
+            .unwrap()
+            .state
+    }
+
+    #[test]
+    fn test_new() {
+        let agd: AcceleratedGradientDescent<Vec<f64>, f64> = AcceleratedGradientDescent::new();
+        let AcceleratedGradientDescent {
+            step_size,
+            restart,
+            lipschitz,
+            t,
+            y,
+        } = agd;
+        assert_eq!(
+            step_size,
+            StepSize::Backtracking {
+                initial: 1.0,
+                factor: 2.0
+            }
+        );
+        assert_eq!(restart, AdaptiveRestart::Never);
+        assert!(lipschitz.is_nan());
+        assert_eq!(t.to_ne_bytes(), 1.0f64.to_ne_bytes());
+        assert!(y.is_none());
+    }
+
+    #[test]
+    fn test_with_step_size() {
+        let agd: AcceleratedGradientDescent<Vec<f64>, f64> = AcceleratedGradientDescent::new()
+            .with_step_size(StepSize::Lipschitz(10.0))
+            .unwrap();
+        assert_eq!(agd.step_size, StepSize::Lipschitz(10.0));
+
+        for (step_size, msg) in [
+            (StepSize::Lipschitz(0.0), "Lipschitz constant must be > 0."),
+            (
+                StepSize::Backtracking {
+                    initial: -1.0,
+                    factor: 2.0,
+                },
+                "initial Lipschitz constant must be > 0.",
+            ),
+            (
+                StepSize::Backtracking {
+                    initial: 1.0,
+                    factor: 1.0,
+                },
+                "backtracking factor must be > 1.",
+            ),
+        ] {
+            let res = AcceleratedGradientDescent::<Vec<f64>, f64>::new().with_step_size(step_size);
+            assert_error!(
+                res,
+                ArgminError,
+                format!("Invalid parameter: \"`AcceleratedGradientDescent`: {msg}\"")
+            );
+        }
+    }
+
+    #[test]
+    fn test_config_build() {
+        let config = AcceleratedGradientDescentConfig {
+            step_size: Some(StepSize::Lipschitz(5.0f64)),
+            restart: Some(AdaptiveRestart::Gradient),
+        };
+        let agd: AcceleratedGradientDescent<Vec<f64>, f64> = config.build().unwrap();
+        assert_eq!(agd.step_size, StepSize::Lipschitz(5.0));
+        assert_eq!(agd.restart, AdaptiveRestart::Gradient);
+
+        let config = AcceleratedGradientDescentConfig {
+            step_size: Some(StepSize::Lipschitz(-5.0f64)),
+            restart: None,
+        };
+        assert!(config.build::<Vec<f64>>().is_err());
+    }
+
+    #[test]
+    fn test_init_param_not_initialized() {
+        let mut agd: AcceleratedGradientDescent<Vec<f64>, f64> = AcceleratedGradientDescent::new();
+        let res = agd.init(&mut Problem::new(TestProblem::new()), IterState::new());
+        assert_error!(
+            res,
+            ArgminError,
+            concat!(
+                "Not initialized: \"`AcceleratedGradientDescent` requires an initial parameter ",
+                "vector. Please provide an initial guess via `Executor`s `configure` method.\""
+            )
+        );
+    }
+
+    #[test]
+    fn test_next_iter_not_initialized() {
+        let mut agd: AcceleratedGradientDescent<Vec<f64>, f64> = AcceleratedGradientDescent::new();
+        let res = agd.next_iter(
+            &mut Problem::new(TestProblem::new()),
+            IterState::new().param(vec![1.0]),
+        );
+        assert_error!(
+            res,
+            ArgminError,
+            concat!(
+                "Potential bug: \"`AcceleratedGradientDescent`: Extrapolated point not set\". ",
+                "This is potentially a bug. ",
+                "Please file a report on https://github.com/argmin-rs/argmin/issues"
+            )
+        );
+    }
+
+    #[test]
+    fn test_first_iteration() {
+        // The first iteration is a gradient step without momentum
+        let mut problem = Problem::new(quadratic());
+        let mut agd = AcceleratedGradientDescent::new()
+            .with_step_size(StepSize::Lipschitz(1000.0))
+            .unwrap();
+        let (state, _) = agd
+            .init(&mut problem, IterState::new().param(vec![1.0; 4]))
+            .unwrap();
+        assert_relative_eq!(state.get_cost(), 555.5);
+        let (state, kv) = agd.next_iter(&mut problem, state).unwrap();
+        let param = state.get_param().unwrap();
+        assert_relative_eq!(param[0], 0.999);
+        assert_relative_eq!(param[3], 0.0);
+        assert_relative_eq!(agd.y.as_ref().unwrap()[0], 0.999);
+        assert_relative_eq!(
+            state.get_cost(),
+            0.5 * (0.999f64.powi(2) + 10.0 * 0.99f64.powi(2) + 100.0 * 0.9f64.powi(2))
+        );
+        let kv = kv.unwrap();
+        assert_eq!(kv.get("momentum").unwrap().get_float(), Some(0.0));
+        assert_eq!(kv.get("restart").unwrap().get_bool(), Some(false));
+    }
+
+    #[test]
+    fn test_backtracking() {
+        let res = Executor::new(quadratic(), AcceleratedGradientDescent::new())
+            .configure(|state| state.param(vec![1.0; 4]).max_iters(500))
+            .run()
+            .unwrap();
+        // Starting at 1, the estimate is doubled until it exceeds the Lipschitz constant 1000
+        assert_eq!(res.solver.lipschitz.to_ne_bytes(), 1024.0f64.to_ne_bytes());
+        assert!(res.state.get_best_cost() < 1e-6);
+        // Backtracking requires more than one cost function evaluation per gradient evaluation
+        assert!(res.problem.counts["cost_count"] > res.problem.counts["gradient_count"]);
+    }
+
+    #[test]
+    fn test_faster_than_gradient_descent() {
+        let agd = run(
+            AcceleratedGradientDescent::new()
+                .with_step_size(StepSize::Lipschitz(1000.0))
+                .unwrap(),
+            200,
+        );
+        // Gradient descent with the same step size
+        let mut x = [1.0f64, 1.0, 1.0, 1.0];
+        let a = quadratic().a;
+        for _ in 0..200 {
+            for i in 0..4 {
+                x[i] -= a[i] * x[i] / 1000.0;
+            }
+        }
+        let gd_cost = quadratic().cost(&x.to_vec()).unwrap();
+        assert!(agd.get_best_cost() < 1e-2 * gd_cost);
+    }
+
+    #[test]
+    fn test_restart() {
+        for restart in [AdaptiveRestart::Function, AdaptiveRestart::Gradient] {
+            let no_restart = run(
+                AcceleratedGradientDescent::new()
+                    .with_step_size(StepSize::Lipschitz(1000.0))
+                    .unwrap(),
+                300,
+            );
+            let with_restart = run(
+                AcceleratedGradientDescent::new()
+                    .with_step_size(StepSize::Lipschitz(1000.0))
+                    .unwrap()
+                    .with_restart(restart),
+                300,
+            );
+            assert!(with_restart.get_best_cost() < no_restart.get_best_cost());
+            assert!(with_restart.get_best_cost() < 1e-8);
+        }
+    }
+}
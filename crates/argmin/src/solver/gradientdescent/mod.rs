@@ -7,13 +7,19 @@
 
 //! # Gradient descent methods
 //!
-//! [`SteepestDescent`]
+//! * [`SteepestDescent`]
+//! * [`AcceleratedGradientDescent`]
 //!
 //! ## Reference
 //!
 //! Jorge Nocedal and Stephen J. Wright (2006). Numerical Optimization.
 //! Springer. ISBN 0-387-30303-0.
+//!
+//! Yurii Nesterov (1983). A method for solving the convex programming problem with convergence
+//! rate O(1/k^2). Soviet Mathematics Doklady 27, 372-376.
 
+mod accelerated;
 mod steepestdescent;
 
+pub use self::accelerated::*;
 pub use self::steepestdescent::*;
@@ -6,10 +6,10 @@
 // copied, modified, or distributed except according to those terms.
 
 use crate::core::{
-    ArgminFloat, CostFunction, Error, Executor, Gradient, IterState, LineSearch,
-    OptimizationResult, Problem, Solver, State, KV,
+    ArgminFloat, CostFunction, Error, Executor, Gradient, IterState, LineSearch, NestedTelemetry,
+    OptimizationResult, Problem, Solver, State, TerminationReason, KV,
 };
-use argmin_math::ArgminMul;
+use argmin_math::{ArgminBound, ArgminL2Norm, ArgminMul};
 #[cfg(feature = "serde1")]
 use serde::{Deserialize, Serialize};
 
@@ -18,6 +18,18 @@ use serde::{Deserialize, Serialize};
 /// Iteratively takes steps in the direction of the strongest negative gradient. In each iteration,
 /// a line search is used to obtain an appropriate step length.
 ///
+/// If [`bounds`](`IterState::bounds`) are set on the state, entries of the search direction which
+/// point out of the bounds at active bounds are dropped (projected gradient method) and the step
+/// length of the line search is limited such that the cost function is not evaluated outside of
+/// the bounds. Once no entry of the search direction is left, the solver terminates with
+/// [`TerminationReason::NoFeasibleDirection`].
+///
+/// Unlike most other solvers, [`SteepestDescent`] has no configuration struct, since apart from
+/// the forwarding of telemetry its only option is the line search, which is configured via the
+/// configuration struct of the line search (for instance [`MoreThuenteLineSearchConfig`]).
+///
+/// [`MoreThuenteLineSearchConfig`]: `crate::solver::linesearch::MoreThuenteLineSearchConfig`
+///
 /// ## Requirements on the optimization problem
 ///
 /// The optimization problem is required to implement [`CostFunction`] and [`Gradient`].
@@ -31,6 +43,9 @@ use serde::{Deserialize, Serialize};
 pub struct SteepestDescent<L> {
     /// line search
     linesearch: L,
+    /// Telemetry of the line search
+    #[cfg_attr(feature = "serde1", serde(default))]
+    inner_telemetry: NestedTelemetry,
 }
 
 impl<L> SteepestDescent<L> {
@@ -46,15 +61,38 @@ impl<L> SteepestDescent<L> {
     /// let sd = SteepestDescent::new(linesearch);
     /// ```
     pub fn new(linesearch: L) -> Self {
-        SteepestDescent { linesearch }
+        SteepestDescent {
+            linesearch,
+            inner_telemetry: NestedTelemetry::new(),
+        }
+    }
+
+    /// Forward the telemetry of the line search to observers
+    ///
+    /// The key-value pairs returned by the line search as well as its number of iterations and
+    /// its termination reason are forwarded with keys prefixed by `prefix` (for instance
+    /// `linesearch.iters`). Independent of this setting, the line search iterations are counted
+    /// as `linesearch_iter_count`. See [`NestedTelemetry`] for details.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::gradientdescent::SteepestDescent;
+    /// # let linesearch = ();
+    /// let sd = SteepestDescent::new(linesearch).with_inner_telemetry("linesearch");
+    /// ```
+    #[must_use]
+    pub fn with_inner_telemetry<T: AsRef<str>>(mut self, prefix: T) -> Self {
+        self.inner_telemetry = NestedTelemetry::forward(prefix);
+        self
     }
 }
 
 impl<O, L, P, G, F> Solver<O, IterState<P, G, (), (), (), F>> for SteepestDescent<L>
 where
     O: CostFunction<Param = P, Output = F> + Gradient<Param = P, Gradient = G>,
-    P: Clone,
-    G: Clone + ArgminMul<F, G>,
+    P: Clone + ArgminBound<G, F>,
+    G: Clone + ArgminMul<F, G> + ArgminL2Norm<F>,
     L: Clone + LineSearch<G, F> + Solver<O, IterState<P, G, (), (), (), F>>,
     F: ArgminFloat,
 {
@@ -62,6 +100,17 @@ where
         "Steepest Descent"
     }
 
+    fn init(
+        &mut self,
+        _problem: &mut Problem<O>,
+        state: IterState<P, G, (), (), (), F>,
+    ) -> Result<(IterState<P, G, (), (), (), F>, Option<KV>), Error> {
+        Ok((
+            state,
+            Some(kv!("linesearch" => self.linesearch.name();).with_prefix("config")),
+        ))
+    }
+
     fn next_iter(
         &mut self,
         problem: &mut Problem<O>,
@@ -80,15 +129,31 @@ where
         let new_cost = problem.cost(&param_new)?;
         let new_grad = problem.gradient(&param_new)?;
 
-        self.linesearch
-            .search_direction(new_grad.mul(&(float!(-1.0))));
+        let direction = state.project_direction(&param_new, new_grad.mul(&(float!(-1.0))));
+        if let Some(max_step) = state.max_step_in_bounds(&param_new, &direction) {
+            // The gradient only points out of the bounds, hence there is no point within the
+            // bounds with a lower cost nearby.
+            if direction.l2_norm() == float!(0.0) {
+                return Ok((
+                    state
+                        .cost(new_cost)
+                        .terminate_with(TerminationReason::NoFeasibleDirection),
+                    None,
+                ));
+            }
+            self.linesearch.max_step_length(max_step)?;
+        }
+        self.linesearch.search_direction(direction);
 
         // Run line search
-        let OptimizationResult {
-            problem: line_problem,
-            state: mut linesearch_state,
-            ..
-        } = Executor::new(
+        let (
+            OptimizationResult {
+                problem: line_problem,
+                state: mut linesearch_state,
+                ..
+            },
+            linesearch_kv,
+        ) = Executor::new(
             problem.take_problem().ok_or_else(argmin_error_closure!(
                 PotentialBug,
                 "`SteepestDescent`: Failed to take `problem` for line search"
@@ -96,25 +161,40 @@ where
             self.linesearch.clone(),
         )
         .configure(|config| config.param(param_new).gradient(new_grad).cost(new_cost))
-        .ctrlc(false)
-        .run()?;
+        .run_nested()?;
 
         // Get back problem and function evaluation counts
         problem.consume_problem(line_problem);
+        let kv = self.inner_telemetry.record(
+            problem,
+            "linesearch_iter_count",
+            &linesearch_state,
+            linesearch_kv,
+        );
 
-        Ok((
-            state
-                .param(
-                    linesearch_state
-                        .take_param()
-                        .ok_or_else(argmin_error_closure!(
-                            PotentialBug,
-                            "`GradientDescent`: No `param` returned by line search"
-                        ))?,
-                )
-                .cost(linesearch_state.get_cost()),
-            None,
-        ))
+        let state = state
+            .param(
+                linesearch_state
+                    .take_param()
+                    .ok_or_else(argmin_error_closure!(
+                        PotentialBug,
+                        "`GradientDescent`: No `param` returned by line search"
+                    ))?,
+            )
+            .cost(linesearch_state.get_cost());
+
+        // The steepest descent direction is only orthogonal to the gradient if the latter
+        // vanishes, therefore there is no other direction to try.
+        if linesearch_state.get_termination_reason()
+            == Some(&TerminationReason::StationaryDirection)
+        {
+            return Ok((
+                state.terminate_with(TerminationReason::StationaryDirection),
+                kv,
+            ));
+        }
+
+        Ok((state, kv))
     }
 }
 
@@ -137,8 +217,12 @@ mod tests {
     fn test_new() {
         let linesearch: BacktrackingLineSearch<Vec<f64>, Vec<f64>, ArmijoCondition<f64>, f64> =
             BacktrackingLineSearch::new(ArmijoCondition::new(0.2).unwrap());
-        let SteepestDescent { linesearch: ls } = SteepestDescent::new(linesearch.clone());
+        let SteepestDescent {
+            linesearch: ls,
+            inner_telemetry,
+        } = SteepestDescent::new(linesearch.clone());
         assert_eq!(ls, linesearch);
+        assert_eq!(inner_telemetry, NestedTelemetry::new());
     }
 
     #[test]
@@ -157,6 +241,22 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_next_iter_stationary() {
+        let mut sd = SteepestDescent::new(MoreThuenteLineSearch::new());
+        let (state, _kv) = sd
+            .next_iter(
+                &mut Problem::new(TestProblem::new()),
+                IterState::new().param(vec![0.0, 0.0]),
+            )
+            .unwrap();
+        assert_eq!(
+            state.get_termination_reason(),
+            Some(&TerminationReason::StationaryDirection)
+        );
+        assert_eq!(state.get_param().unwrap(), &vec![0.0, 0.0]);
+    }
+
     #[test]
     fn test_next_iter_prev_param_not_erased() {
         let linesearch: BacktrackingLineSearch<Vec<f64>, Vec<f64>, ArmijoCondition<f64>, f64> =
@@ -217,4 +317,139 @@ mod tests {
         );
         assert_relative_eq!(state.cost, 1.048820000000001, epsilon = f64::EPSILON);
     }
+
+    #[test]
+    fn test_next_iter_bounds() {
+        /// Sphere function which must not be evaluated outside of `[0.5, 2]^2`
+        struct SDProblem {}
+
+        impl CostFunction for SDProblem {
+            type Param = Vec<f64>;
+            type Output = f64;
+
+            fn cost(&self, p: &Self::Param) -> Result<Self::Output, Error> {
+                assert!(p.iter().all(|x| (0.5..=2.0).contains(x)));
+                Ok(p[0].powi(2) + p[1].powi(2))
+            }
+        }
+
+        impl Gradient for SDProblem {
+            type Param = Vec<f64>;
+            type Gradient = Vec<f64>;
+
+            fn gradient(&self, p: &Self::Param) -> Result<Self::Param, Error> {
+                Ok(vec![2.0 * p[0], 2.0 * p[1]])
+            }
+        }
+
+        let linesearch: BacktrackingLineSearch<Vec<f64>, Vec<f64>, ArmijoCondition<f64>, f64> =
+            BacktrackingLineSearch::new(ArmijoCondition::new(0.2).unwrap());
+        let mut sd = SteepestDescent::new(linesearch);
+        let (state, _) = sd
+            .next_iter(
+                &mut Problem::new(SDProblem {}),
+                IterState::new()
+                    .param(vec![1.0, 2.0])
+                    .bounds(vec![0.5, 0.5], vec![2.0, 2.0]),
+            )
+            .unwrap();
+
+        // The step length is limited to 0.25 by the lower bound of the first parameter
+        assert_relative_eq!(
+            state.param.as_ref().unwrap()[0],
+            0.5,
+            epsilon = f64::EPSILON
+        );
+        assert_relative_eq!(
+            state.param.as_ref().unwrap()[1],
+            1.0,
+            epsilon = f64::EPSILON
+        );
+    }
+
+    #[test]
+    fn test_bounds() {
+        /// Sphere function which must not be evaluated outside of `[0.5, 2]^2`
+        struct SDProblem {}
+
+        impl CostFunction for SDProblem {
+            type Param = Vec<f64>;
+            type Output = f64;
+
+            fn cost(&self, p: &Self::Param) -> Result<Self::Output, Error> {
+                assert!(p.iter().all(|x| (0.5..=2.0).contains(x)));
+                Ok(p[0].powi(2) + p[1].powi(2))
+            }
+        }
+
+        impl Gradient for SDProblem {
+            type Param = Vec<f64>;
+            type Gradient = Vec<f64>;
+
+            fn gradient(&self, p: &Self::Param) -> Result<Self::Param, Error> {
+                assert!(p.iter().all(|x| (0.5..=2.0).contains(x)));
+                Ok(vec![2.0 * p[0], 2.0 * p[1]])
+            }
+        }
+
+        let linesearch: BacktrackingLineSearch<Vec<f64>, Vec<f64>, ArmijoCondition<f64>, f64> =
+            BacktrackingLineSearch::new(ArmijoCondition::new(0.2).unwrap());
+        let res = Executor::new(SDProblem {}, SteepestDescent::new(linesearch))
+            .configure(|state| {
+                state
+                    .param(vec![1.0, 2.0])
+                    .bounds(vec![0.5, 0.5], vec![2.0, 2.0])
+                    .max_iters(10)
+            })
+            .run()
+            .unwrap();
+
+        // After the first parameter hits its lower bound, the search continues along the second
+        // parameter only until the minimum within the bounds at the corner is reached. The third
+        // iteration finds no feasible direction.
+        assert_eq!(
+            res.state.get_termination_reason(),
+            Some(&TerminationReason::NoFeasibleDirection)
+        );
+        assert_eq!(res.state.get_iter(), 3);
+        assert_eq!(res.state.get_best_param().unwrap(), &vec![0.5, 0.5]);
+        assert_relative_eq!(res.state.get_best_cost(), 0.5, epsilon = f64::EPSILON);
+    }
+
+    #[test]
+    fn test_next_iter_inner_telemetry() {
+        struct SDProblem {}
+
+        impl CostFunction for SDProblem {
+            type Param = Vec<f64>;
+            type Output = f64;
+
+            fn cost(&self, p: &Self::Param) -> Result<Self::Output, Error> {
+                Ok(p[0].powi(2) + p[1].powi(2))
+            }
+        }
+
+        impl Gradient for SDProblem {
+            type Param = Vec<f64>;
+            type Gradient = Vec<f64>;
+
+            fn gradient(&self, p: &Self::Param) -> Result<Self::Param, Error> {
+                Ok(vec![2.0 * p[0], 2.0 * p[1]])
+            }
+        }
+
+        let linesearch: BacktrackingLineSearch<Vec<f64>, Vec<f64>, ArmijoCondition<f64>, f64> =
+            BacktrackingLineSearch::new(ArmijoCondition::new(0.2).unwrap());
+        let mut sd = SteepestDescent::new(linesearch).with_inner_telemetry("ls");
+        let mut problem = Problem::new(SDProblem {});
+        let (_state, kv) = sd
+            .next_iter(&mut problem, IterState::new().param(vec![1.0, 2.0]))
+            .unwrap();
+
+        let kv = kv.unwrap();
+        let iters = kv.get("ls.iters").unwrap().get_uint().unwrap();
+        assert!(iters > 0);
+        assert_eq!(problem.counts["linesearch_iter_count"], iters);
+        assert!(kv.get("ls.termination").is_some());
+    }
 }
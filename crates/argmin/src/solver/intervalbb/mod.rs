@@ -0,0 +1,634 @@
+// Copyright 2018-2024 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+//! # Interval branch and bound
+//!
+//! Deterministic global optimization using interval arithmetic (provided by the `inari` crate).
+//! For details see [`IntervalBranchAndBound`].
+//!
+//! Requires the `inari` feature, which in turn requires `RUSTFLAGS='-Ctarget-cpu=haswell'` (or a
+//! later CPU) on x86-64.
+//!
+//! ## References
+//!
+//! Hansen, E. and Walster, G.W. (2004): Global Optimization Using Interval Analysis. Second
+//! Edition. Marcel Dekker, New York.
+//!
+//! Moore, R.E., Kearfott, R.B. and Cloud, M.J. (2009): Introduction to Interval Analysis. SIAM,
+//! Philadelphia. <https://doi.org/10.1137/1.9780898717716>
+
+use crate::core::{Error, IterState, Problem, Solver, TerminationReason, TerminationStatus, KV};
+use inari::Interval;
+#[cfg(feature = "serde1")]
+use serde::{Deserialize, Serialize};
+
+/// Cost function evaluated with interval arithmetic. Problems which are to be solved using
+/// [`IntervalBranchAndBound`] must implement this trait.
+///
+/// The returned interval must enclose the range of the cost function over the box `param`, i.e.
+/// `f(x)` must be contained in the returned interval for all `x` in `param`. This is the case if
+/// the cost function is written using the operations of `inari::Interval`, which round outwards.
+/// The tighter the enclosure, the faster the solver converges.
+///
+/// # Example
+///
+/// ```
+/// # use argmin::core::Error;
+/// use argmin::solver::intervalbb::IntervalCostFunction;
+/// use inari::{const_interval, Interval};
+///
+/// struct Sphere {}
+///
+/// impl IntervalCostFunction for Sphere {
+///     fn cost_interval(&self, param: &[Interval]) -> Result<Interval, Error> {
+///         Ok(param
+///             .iter()
+///             .fold(const_interval!(0.0, 0.0), |acc, x| acc + x.sqr()))
+///     }
+/// }
+/// ```
+pub trait IntervalCostFunction {
+    /// Compute an enclosure of the range of the cost function over the box `param`
+    fn cost_interval(&self, param: &[Interval]) -> Result<Interval, Error>;
+}
+
+/// Wraps a call to `cost_interval` defined in the `IntervalCostFunction` trait and as such allows
+/// to call `cost_interval` on an instance of `Problem`. Internally, the number of evaluations of
+/// `cost_interval` is counted.
+impl<O: IntervalCostFunction> Problem<O> {
+    /// Calls `cost_interval` defined in the `IntervalCostFunction` trait and keeps track of the
+    /// number of evaluations.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::{Error, Problem};
+    /// # use argmin::solver::intervalbb::IntervalCostFunction;
+    /// # use inari::{const_interval, Interval};
+    /// #
+    /// # struct Sphere {}
+    /// #
+    /// # impl IntervalCostFunction for Sphere {
+    /// #     fn cost_interval(&self, param: &[Interval]) -> Result<Interval, Error> {
+    /// #         Ok(param
+    /// #             .iter()
+    /// #             .fold(const_interval!(0.0, 0.0), |acc, x| acc + x.sqr()))
+    /// #     }
+    /// # }
+    /// # fn main() -> Result<(), Error> {
+    /// let mut problem = Problem::new(Sphere {});
+    ///
+    /// let range = problem.cost_interval(&[const_interval!(-1.0, 2.0)])?;
+    /// # assert_eq!(range.inf(), 0.0);
+    /// # assert_eq!(range.sup(), 4.0);
+    /// # assert_eq!(problem.counts["cost_interval_count"], 1);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn cost_interval(&mut self, param: &[Interval]) -> Result<Interval, Error> {
+        self.problem("cost_interval_count", |problem| {
+            problem.cost_interval(param)
+        })
+    }
+}
+
+/// Box in the search space which may contain a global minimizer
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+struct Candidate {
+    /// Lower and upper bound for each dimension
+    bounds: Vec<(f64, f64)>,
+    /// Lower bound of the cost function on the box
+    lower: f64,
+}
+
+impl Candidate {
+    /// Returns the box as intervals
+    fn intervals(&self) -> Result<Vec<Interval>, Error> {
+        self.bounds.iter().map(|&b| interval(b)).collect()
+    }
+
+    /// Returns the midpoint of the box
+    fn midpoint(&self) -> Vec<f64> {
+        self.bounds.iter().map(|&(l, u)| midpoint(l, u)).collect()
+    }
+
+    /// Returns the index and the width of the widest dimension
+    fn widest(&self) -> (usize, f64) {
+        self.bounds.iter().map(|(l, u)| u - l).enumerate().fold(
+            (0, f64::NEG_INFINITY),
+            |acc, (i, w)| {
+                if w > acc.1 {
+                    (i, w)
+                } else {
+                    acc
+                }
+            },
+        )
+    }
+}
+
+/// Constructs an interval from its bounds
+fn interval(bounds: (f64, f64)) -> Result<Interval, Error> {
+    Interval::try_from(bounds).map_err(|_| {
+        argmin_error!(
+            InvalidParameter,
+            format!("`IntervalBranchAndBound`: invalid interval {bounds:?}.")
+        )
+    })
+}
+
+/// Midpoint of `[l, u]` which is guaranteed to lie within the interval
+fn midpoint(l: f64, u: f64) -> f64 {
+    (l + 0.5 * (u - l)).clamp(l, u)
+}
+
+/// # Interval branch and bound
+///
+/// Deterministic global optimization of low-dimensional problems within box bounds. The problem
+/// is defined via [`IntervalCostFunction`], which computes enclosures of the range of the cost
+/// function over boxes using interval arithmetic.
+///
+/// The solver maintains a list of candidate boxes together with lower bounds of the cost function
+/// on each box. In each iteration, the box with the smallest lower bound is bisected along its
+/// widest dimension. Upper bounds of the global minimum are obtained by evaluating the interval
+/// cost function at the midpoints of the new boxes. Boxes whose lower bound exceeds the best
+/// upper bound cannot contain a global minimizer and are discarded. Since interval arithmetic
+/// rounds outwards, the global minimum is guaranteed to lie within
+/// [`enclosure`](`IntervalBranchAndBound::enclosure`) and all global minimizers are guaranteed to
+/// lie within the remaining [`boxes`](`IntervalBranchAndBound::boxes`).
+///
+/// The solver terminates once the width of the enclosure of the global minimum is below
+/// [`tol`](`IntervalBranchAndBound::with_tolerance`) or all remaining boxes are narrower than
+/// [`min_width`](`IntervalBranchAndBound::with_min_width`). The parameter vector of the state is
+/// the midpoint with the lowest upper bound found so far and its cost is this upper bound.
+///
+/// The number of boxes grows exponentially with the dimension of the problem and with decreasing
+/// tolerance, therefore this solver is only suitable for low-dimensional problems.
+///
+/// Requires the `inari` feature. `inari` only supports x86-64 (and experimentally AArch64) and
+/// must be compiled with `RUSTFLAGS='-Ctarget-cpu=haswell'` (or a later CPU) on x86-64.
+///
+/// ## Requirements on the optimization problem
+///
+/// The optimization problem is required to implement [`IntervalCostFunction`].
+///
+/// ## References
+///
+/// Hansen, E. and Walster, G.W. (2004): Global Optimization Using Interval Analysis. Second
+/// Edition. Marcel Dekker, New York.
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+pub struct IntervalBranchAndBound {
+    /// Bounds on parameter space
+    bounds: (Vec<f64>, Vec<f64>),
+    /// Tolerance on the width of the enclosure of the global minimum
+    tol: f64,
+    /// Boxes narrower than this are not bisected any further
+    min_width: f64,
+    /// Boxes which are bisected further
+    queue: Vec<Candidate>,
+    /// Boxes which are narrower than `min_width`
+    done: Vec<Candidate>,
+    /// Best upper bound of the global minimum
+    upper: f64,
+}
+
+impl IntervalBranchAndBound {
+    /// Construct a new instance of `IntervalBranchAndBound`
+    ///
+    /// `bounds` is a tuple `(lower_bound, upper_bound)` of the search space.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::intervalbb::IntervalBranchAndBound;
+    /// let solver = IntervalBranchAndBound::new((vec![-1.0, -1.0], vec![1.0, 1.0]));
+    /// ```
+    pub fn new(bounds: (Vec<f64>, Vec<f64>)) -> Self {
+        IntervalBranchAndBound {
+            bounds,
+            tol: 1e-8,
+            min_width: 1e-12,
+            queue: vec![],
+            done: vec![],
+            upper: f64::INFINITY,
+        }
+    }
+
+    /// Set the tolerance on the width of the enclosure of the global minimum
+    ///
+    /// Must be non-negative. Defaults to `1e-8`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::intervalbb::IntervalBranchAndBound;
+    /// # use argmin::core::Error;
+    /// # fn main() -> Result<(), Error> {
+    /// let solver = IntervalBranchAndBound::new((vec![-1.0], vec![1.0])).with_tolerance(1e-6)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_tolerance(mut self, tol: f64) -> Result<Self, Error> {
+        if tol.is_nan() || tol < 0.0 {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "`IntervalBranchAndBound`: tolerance must be >= 0."
+            ));
+        }
+        self.tol = tol;
+        Ok(self)
+    }
+
+    /// Set the width below which boxes are not bisected any further
+    ///
+    /// Must be positive. Defaults to `1e-12`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::intervalbb::IntervalBranchAndBound;
+    /// # use argmin::core::Error;
+    /// # fn main() -> Result<(), Error> {
+    /// let solver = IntervalBranchAndBound::new((vec![-1.0], vec![1.0])).with_min_width(1e-6)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_min_width(mut self, min_width: f64) -> Result<Self, Error> {
+        if min_width.is_nan() || min_width <= 0.0 {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "`IntervalBranchAndBound`: minimum width must be > 0."
+            ));
+        }
+        self.min_width = min_width;
+        Ok(self)
+    }
+
+    /// Returns the enclosure `(lower, upper)` of the global minimum
+    ///
+    /// Before the solver is run, this is `(-inf, inf)`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::intervalbb::IntervalBranchAndBound;
+    /// let solver = IntervalBranchAndBound::new((vec![-1.0], vec![1.0]));
+    /// let (lower, upper) = solver.enclosure();
+    /// # assert_eq!(lower, f64::NEG_INFINITY);
+    /// # assert_eq!(upper, f64::INFINITY);
+    /// ```
+    pub fn enclosure(&self) -> (f64, f64) {
+        let lower = self
+            .queue
+            .iter()
+            .chain(self.done.iter())
+            .map(|c| c.lower)
+            .fold(f64::INFINITY, f64::min);
+        if self.upper.is_infinite() {
+            (f64::NEG_INFINITY, self.upper)
+        } else {
+            (lower.min(self.upper), self.upper)
+        }
+    }
+
+    /// Returns the remaining boxes, given as `(lower_bound, upper_bound)`, which may contain a
+    /// global minimizer
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::intervalbb::IntervalBranchAndBound;
+    /// let solver = IntervalBranchAndBound::new((vec![-1.0], vec![1.0]));
+    /// for (lower, upper) in solver.boxes() {
+    ///     println!("{lower:?} - {upper:?}");
+    /// }
+    /// # assert!(solver.boxes().is_empty());
+    /// ```
+    pub fn boxes(&self) -> Vec<(Vec<f64>, Vec<f64>)> {
+        self.queue
+            .iter()
+            .chain(self.done.iter())
+            .map(|c| c.bounds.iter().copied().unzip())
+            .collect()
+    }
+
+    /// Evaluates the interval cost function on `bounds`. Returns `None` if the box can be
+    /// discarded, otherwise the box is returned as candidate and the upper bound and `state` are
+    /// updated with the cost function value at the midpoint.
+    fn evaluate<O: IntervalCostFunction>(
+        &mut self,
+        problem: &mut Problem<O>,
+        bounds: Vec<(f64, f64)>,
+        parent_lower: f64,
+        state: &mut IterState<Vec<f64>, (), (), (), (), f64>,
+    ) -> Result<Option<Candidate>, Error> {
+        let mut candidate = Candidate {
+            bounds,
+            lower: parent_lower,
+        };
+        let range = problem.cost_interval(&candidate.intervals()?)?;
+        if range.is_empty() || range.inf().is_nan() {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "`IntervalBranchAndBound`: cost function returned an empty interval."
+            ));
+        }
+        candidate.lower = candidate.lower.max(range.inf());
+        if candidate.lower > self.upper {
+            return Ok(None);
+        }
+        let mid = candidate.midpoint();
+        let point: Vec<Interval> = mid
+            .iter()
+            .map(|&x| interval((x, x)))
+            .collect::<Result<_, _>>()?;
+        let value = problem.cost_interval(&point)?.sup();
+        if value < self.upper {
+            self.upper = value;
+            state.param = Some(mid);
+            state.cost = value;
+        }
+        Ok(Some(candidate))
+    }
+
+    /// Sorts `candidate` into the queue or the list of finished boxes
+    fn push(&mut self, candidate: Candidate) {
+        if candidate.widest().1 <= self.min_width {
+            self.done.push(candidate);
+        } else {
+            self.queue.push(candidate);
+        }
+    }
+
+    /// Discards all boxes which cannot contain a global minimizer
+    fn prune(&mut self) {
+        let upper = self.upper;
+        self.queue.retain(|c| c.lower <= upper);
+        self.done.retain(|c| c.lower <= upper);
+    }
+
+    /// Returns the key-value pairs reported to observers
+    fn kv(&self) -> KV {
+        let (lower, upper) = self.enclosure();
+        kv!(
+            "lower_bound" => lower;
+            "upper_bound" => upper;
+            "boxes" => (self.queue.len() + self.done.len()) as u64;
+        )
+    }
+}
+
+/// Serializable configuration of [`IntervalBranchAndBound`]
+///
+/// Options which are `None` keep the defaults of [`IntervalBranchAndBound`]. The configuration is
+/// validated when the solver is constructed via [`build`](`IntervalBranchAndBoundConfig::build`).
+///
+/// # Example
+///
+/// ```
+/// # use argmin::solver::intervalbb::IntervalBranchAndBoundConfig;
+/// # use argmin::core::Error;
+/// # fn main() -> Result<(), Error> {
+/// let config = IntervalBranchAndBoundConfig {
+///     bounds: (vec![-1.0, -1.0], vec![1.0, 1.0]),
+///     tolerance: Some(1e-6),
+///     ..Default::default()
+/// };
+/// let solver = config.build()?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize), serde(default))]
+pub struct IntervalBranchAndBoundConfig {
+    /// Bounds on the parameter space as `(lower_bound, upper_bound)`
+    pub bounds: (Vec<f64>, Vec<f64>),
+    /// See [`IntervalBranchAndBound::with_tolerance`]
+    pub tolerance: Option<f64>,
+    /// See [`IntervalBranchAndBound::with_min_width`]
+    pub min_width: Option<f64>,
+}
+
+impl IntervalBranchAndBoundConfig {
+    /// Validates the configuration and constructs an [`IntervalBranchAndBound`] solver.
+    pub fn build(&self) -> Result<IntervalBranchAndBound, Error> {
+        let mut solver = IntervalBranchAndBound::new(self.bounds.clone());
+        if let Some(tol) = self.tolerance {
+            solver = solver.with_tolerance(tol)?;
+        }
+        if let Some(min_width) = self.min_width {
+            solver = solver.with_min_width(min_width)?;
+        }
+        Ok(solver)
+    }
+}
+
+impl<O> Solver<O, IterState<Vec<f64>, (), (), (), (), f64>> for IntervalBranchAndBound
+where
+    O: IntervalCostFunction,
+{
+    fn name(&self) -> &str {
+        "Interval Branch and Bound"
+    }
+
+    fn init(
+        &mut self,
+        problem: &mut Problem<O>,
+        mut state: IterState<Vec<f64>, (), (), (), (), f64>,
+    ) -> Result<(IterState<Vec<f64>, (), (), (), (), f64>, Option<KV>), Error> {
+        let (lower, upper) = &self.bounds;
+        if lower.is_empty() || lower.len() != upper.len() {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "`IntervalBranchAndBound`: lower and upper bound must be non-empty and of the same length."
+            ));
+        }
+        let bounds: Vec<(f64, f64)> = lower.iter().copied().zip(upper.iter().copied()).collect();
+        if bounds
+            .iter()
+            .any(|&(l, u)| !l.is_finite() || !u.is_finite() || l > u)
+        {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "`IntervalBranchAndBound`: bounds must be finite and lower bound must not exceed upper bound."
+            ));
+        }
+        self.queue.clear();
+        self.done.clear();
+        self.upper = f64::INFINITY;
+        state.cost = f64::INFINITY;
+        if let Some(root) = self.evaluate(problem, bounds, f64::NEG_INFINITY, &mut state)? {
+            self.push(root);
+        }
+        let config = kv!(
+            "tolerance" => self.tol;
+            "min_width" => self.min_width;
+            "dimensions" => self.bounds.0.len() as u64;
+        )
+        .with_prefix("config");
+        Ok((state, Some(self.kv().merge(config))))
+    }
+
+    fn next_iter(
+        &mut self,
+        problem: &mut Problem<O>,
+        mut state: IterState<Vec<f64>, (), (), (), (), f64>,
+    ) -> Result<(IterState<Vec<f64>, (), (), (), (), f64>, Option<KV>), Error> {
+        let idx = self
+            .queue
+            .iter()
+            .enumerate()
+            .fold(None, |acc: Option<(usize, f64)>, (i, c)| match acc {
+                Some((_, lower)) if lower <= c.lower => acc,
+                _ => Some((i, c.lower)),
+            })
+            .map(|(i, _)| i);
+        if let Some(idx) = idx {
+            let candidate = self.queue.swap_remove(idx);
+            let (dim, _) = candidate.widest();
+            let (l, u) = candidate.bounds[dim];
+            let mid = midpoint(l, u);
+            for half in [(l, mid), (mid, u)] {
+                let mut bounds = candidate.bounds.clone();
+                bounds[dim] = half;
+                if let Some(child) = self.evaluate(problem, bounds, candidate.lower, &mut state)? {
+                    self.push(child);
+                }
+            }
+            self.prune();
+        }
+        Ok((state, Some(self.kv())))
+    }
+
+    fn terminate(
+        &mut self,
+        _state: &IterState<Vec<f64>, (), (), (), (), f64>,
+    ) -> TerminationStatus {
+        let (lower, upper) = self.enclosure();
+        if upper - lower <= self.tol || self.queue.is_empty() {
+            return TerminationStatus::Terminated(TerminationReason::SolverConverged);
+        }
+        TerminationStatus::NotTerminated
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::{ArgminError, Executor, State};
+    use inari::const_interval;
+
+    test_trait_impl!(interval_branch_and_bound, IntervalBranchAndBound);
+
+    /// Six-hump camel function with global minima at (0.0898, -0.7126) and (-0.0898, 0.7126)
+    struct SixHumpCamel {}
+
+    impl IntervalCostFunction for SixHumpCamel {
+        fn cost_interval(&self, p: &[Interval]) -> Result<Interval, Error> {
+            let x = p[0];
+            let y = p[1];
+            let x2 = x.sqr();
+            let y2 = y.sqr();
+            Ok((const_interval!(4.0, 4.0) - const_interval!(2.1, 2.1) * x2
+                + x2.sqr() / const_interval!(3.0, 3.0))
+                * x2
+                + x * y
+                + (const_interval!(-4.0, -4.0) + const_interval!(4.0, 4.0) * y2) * y2)
+        }
+    }
+
+    #[test]
+    fn test_new() {
+        let solver = IntervalBranchAndBound::new((vec![-1.0, -2.0], vec![1.0, 2.0]));
+        assert_eq!(solver.bounds, (vec![-1.0, -2.0], vec![1.0, 2.0]));
+        assert_eq!(solver.tol.to_ne_bytes(), 1e-8f64.to_ne_bytes());
+        assert_eq!(solver.min_width.to_ne_bytes(), 1e-12f64.to_ne_bytes());
+        assert!(solver.boxes().is_empty());
+    }
+
+    #[test]
+    fn test_config_build() {
+        let config = IntervalBranchAndBoundConfig {
+            bounds: (vec![-1.0, -2.0], vec![1.0, 2.0]),
+            tolerance: Some(1e-4),
+            min_width: Some(1e-6),
+        };
+        let solver = config.build().unwrap();
+        assert_eq!(solver.bounds, (vec![-1.0, -2.0], vec![1.0, 2.0]));
+        assert_eq!(solver.tol.to_ne_bytes(), 1e-4f64.to_ne_bytes());
+        assert_eq!(solver.min_width.to_ne_bytes(), 1e-6f64.to_ne_bytes());
+
+        let res = IntervalBranchAndBoundConfig {
+            min_width: Some(0.0),
+            ..Default::default()
+        }
+        .build();
+        assert_error!(
+            res,
+            ArgminError,
+            "Invalid parameter: \"`IntervalBranchAndBound`: minimum width must be > 0.\""
+        );
+    }
+
+    #[test]
+    fn test_invalid_parameters() {
+        let res = IntervalBranchAndBound::new((vec![-1.0], vec![1.0])).with_tolerance(-1.0);
+        assert_error!(
+            res,
+            ArgminError,
+            "Invalid parameter: \"`IntervalBranchAndBound`: tolerance must be >= 0.\""
+        );
+        let res = IntervalBranchAndBound::new((vec![-1.0], vec![1.0])).with_min_width(0.0);
+        assert_error!(
+            res,
+            ArgminError,
+            "Invalid parameter: \"`IntervalBranchAndBound`: minimum width must be > 0.\""
+        );
+        let res = Executor::new(
+            SixHumpCamel {},
+            IntervalBranchAndBound::new((vec![1.0, 0.0], vec![-1.0, 1.0])),
+        )
+        .run();
+        assert_error!(
+            res,
+            ArgminError,
+            "Invalid parameter: \"`IntervalBranchAndBound`: bounds must be finite and lower bound must not exceed upper bound.\""
+        );
+    }
+
+    #[test]
+    fn test_six_hump_camel() {
+        let solver = IntervalBranchAndBound::new((vec![-3.0, -2.0], vec![3.0, 2.0]))
+            .with_tolerance(1e-2)
+            .unwrap();
+        let res = Executor::new(SixHumpCamel {}, solver)
+            .configure(|state| state.max_iters(10_000))
+            .run()
+            .unwrap();
+        assert_eq!(
+            res.state.get_termination_reason(),
+            Some(&TerminationReason::SolverConverged)
+        );
+
+        let f_opt = -1.031_628_453_489_877;
+        let (lower, upper) = res.solver.enclosure();
+        assert!(upper - lower <= 1e-2);
+        assert!(lower <= f_opt && f_opt <= upper);
+        assert!((res.state.get_best_cost() - f_opt).abs() <= 1e-2);
+
+        // Both global minimizers are contained in the remaining boxes
+        for opt in [[0.089_842, -0.712_656], [-0.089_842, 0.712_656]] {
+            assert!(res
+                .solver
+                .boxes()
+                .iter()
+                .any(|(l, u)| { (0..2).all(|i| l[i] - 1e-5 <= opt[i] && opt[i] <= u[i] + 1e-5) }));
+        }
+    }
+}
@@ -0,0 +1,537 @@
+// Copyright 2018-2024 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use crate::core::{
+    ArgminFloat, Error, IterState, Operator, Problem, Solver, State, TerminationReason,
+    TerminationStatus, KV,
+};
+use argmin_math::{ArgminDot, ArgminL2Norm, ArgminMul, ArgminScaledAdd, ArgminSub};
+#[cfg(feature = "serde1")]
+use serde::{Deserialize, Serialize};
+
+/// # Restarted GMRES method
+///
+/// A solver for systems of linear equations with a general (nonsymmetric) matrix.
+///
+/// Solves systems of the form `A * x = b` where `x` and `b` are vectors and `A` is a square
+/// matrix. In each iteration, the Arnoldi process (with modified Gram-Schmidt
+/// orthogonalization) extends the Krylov subspace by one vector and the iterate minimizing the
+/// norm of the residual `b - A * x` over this subspace is computed. Since all basis vectors need
+/// to be stored, the method is restarted from the current iterate every `restart` iterations
+/// (see [`with_restart`](`GMRES::with_restart`)), which is usually referred to as GMRES(m).
+///
+/// The cost is the norm of the residual, which is obtained from the Givens rotations used to
+/// solve the least squares problem. The solver terminates once the norm of the residual is
+/// smaller than `tol * ||b||` (see [`with_tolerance`](`GMRES::with_tolerance`)).
+///
+/// Requires an initial parameter vector.
+///
+/// ## Requirements on the optimization problem
+///
+/// The optimization problem is required to implement [`Operator`], which applies `A` to a
+/// vector.
+///
+/// ## Reference
+///
+/// Youcef Saad and Martin H. Schultz (1986). GMRES: A generalized minimal residual algorithm for
+/// solving nonsymmetric linear systems. SIAM Journal on Scientific and Statistical Computing
+/// 7(3), 856-869.
+///
+/// Yousef Saad (2003). Iterative Methods for Sparse Linear Systems. 2nd edition, SIAM.
+#[derive(Clone)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+pub struct GMRES<P, F> {
+    /// b (right hand side of `A * x = b`)
+    b: P,
+    /// Relative tolerance of the norm of the residual
+    tol: F,
+    /// Number of iterations after which the method is restarted
+    restart: usize,
+    /// Norm of b
+    norm_b: F,
+    /// Parameter vector at the start of the current cycle
+    x0: Option<P>,
+    /// Orthonormal basis of the Krylov subspace
+    basis: Vec<P>,
+    /// Columns of the upper triangular matrix obtained by rotating the Hessenberg matrix
+    r: Vec<Vec<F>>,
+    /// Cosines and sines of the Givens rotations
+    rotations: Vec<(F, F)>,
+    /// Right hand side of the rotated least squares problem
+    g: Vec<F>,
+}
+
+impl<P, F> GMRES<P, F>
+where
+    F: ArgminFloat,
+{
+    /// Constructs an instance of [`GMRES`]
+    ///
+    /// Takes `b`, the right hand side of `A * x = b` as input.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::krylov::GMRES;
+    /// # let b = vec![1.0f64, 1.0];
+    /// let gmres: GMRES<_, f64> = GMRES::new(b);
+    /// ```
+    pub fn new(b: P) -> Self {
+        GMRES {
+            b,
+            tol: F::epsilon().sqrt(),
+            restart: 30,
+            norm_b: F::nan(),
+            x0: None,
+            basis: vec![],
+            r: vec![],
+            rotations: vec![],
+            g: vec![],
+        }
+    }
+
+    /// Set relative tolerance of the norm of the residual
+    ///
+    /// The solver terminates once `||b - A * x|| <= tol * ||b||`. Must be larger than 0 and
+    /// defaults to `sqrt(EPSILON)`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::krylov::GMRES;
+    /// # use argmin::core::Error;
+    /// # fn main() -> Result<(), Error> {
+    /// # let b = vec![1.0f64, 1.0];
+    /// let gmres: GMRES<_, f64> = GMRES::new(b).with_tolerance(1e-10)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_tolerance(mut self, tol: F) -> Result<Self, Error> {
+        if tol <= float!(0.0) {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "`GMRES`: tolerance must be > 0."
+            ));
+        }
+        self.tol = tol;
+        Ok(self)
+    }
+
+    /// Set number of iterations after which the method is restarted
+    ///
+    /// Larger values usually lead to faster convergence at the expense of memory (one vector
+    /// per iteration) and computation time for the orthogonalization. Must be larger than 0 and
+    /// defaults to 30.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::krylov::GMRES;
+    /// # use argmin::core::Error;
+    /// # fn main() -> Result<(), Error> {
+    /// # let b = vec![1.0f64, 1.0];
+    /// let gmres: GMRES<_, f64> = GMRES::new(b).with_restart(50)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_restart(mut self, restart: usize) -> Result<Self, Error> {
+        if restart == 0 {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "`GMRES`: restart must be > 0."
+            ));
+        }
+        self.restart = restart;
+        Ok(self)
+    }
+
+    /// Starts a new cycle at `x0` with residual `r0`
+    fn start_cycle(&mut self, x0: P, r0: P) -> F
+    where
+        P: ArgminL2Norm<F> + ArgminMul<F, P>,
+    {
+        let beta = r0.l2_norm();
+        // If `x0` solves the system, the solver terminates before the basis vector is used.
+        let v = if beta > float!(0.0) {
+            r0.mul(&(float!(1.0) / beta))
+        } else {
+            r0
+        };
+        self.x0 = Some(x0);
+        self.basis = vec![v];
+        self.r = vec![];
+        self.rotations = vec![];
+        self.g = vec![beta];
+        beta
+    }
+}
+
+/// Serializable configuration of [`GMRES`]
+///
+/// Options which are `None` keep the defaults of [`GMRES`]. The configuration is validated when
+/// the solver is constructed via [`build`](`GMRESConfig::build`).
+///
+/// # Example
+///
+/// ```
+/// # use argmin::solver::krylov::{GMRES, GMRESConfig};
+/// # use argmin::core::Error;
+/// # fn main() -> Result<(), Error> {
+/// let config = GMRESConfig {
+///     b: vec![1.0f64, 1.0],
+///     restart: Some(10),
+///     ..Default::default()
+/// };
+/// let gmres: GMRES<_, f64> = config.build()?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize), serde(default))]
+pub struct GMRESConfig<P, F> {
+    /// Right hand side `b` of `A * x = b`
+    pub b: P,
+    /// See [`GMRES::with_tolerance`]
+    pub tolerance: Option<F>,
+    /// See [`GMRES::with_restart`]
+    pub restart: Option<usize>,
+}
+
+impl<P, F> GMRESConfig<P, F>
+where
+    P: Clone,
+    F: ArgminFloat,
+{
+    /// Validates the configuration and constructs a [`GMRES`] solver.
+    pub fn build(&self) -> Result<GMRES<P, F>, Error> {
+        let mut solver = GMRES::new(self.b.clone());
+        if let Some(tol) = self.tolerance {
+            solver = solver.with_tolerance(tol)?;
+        }
+        if let Some(restart) = self.restart {
+            solver = solver.with_restart(restart)?;
+        }
+        Ok(solver)
+    }
+}
+
+impl<P, O, F> Solver<O, IterState<P, (), (), (), (), F>> for GMRES<P, F>
+where
+    O: Operator<Param = P, Output = P>,
+    P: Clone
+        + ArgminSub<P, P>
+        + ArgminDot<P, F>
+        + ArgminL2Norm<F>
+        + ArgminScaledAdd<P, F, P>
+        + ArgminMul<F, P>,
+    F: ArgminFloat,
+{
+    fn name(&self) -> &str {
+        "GMRES"
+    }
+
+    fn init(
+        &mut self,
+        problem: &mut Problem<O>,
+        state: IterState<P, (), (), (), (), F>,
+    ) -> Result<(IterState<P, (), (), (), (), F>, Option<KV>), Error> {
+        let init_param = state.get_param().ok_or_else(argmin_error_closure!(
+            NotInitialized,
+            concat!(
+                "`GMRES` requires an initial parameter vector. ",
+                "Please provide an initial guess via `Executor`s `configure` method."
+            )
+        ))?;
+        self.norm_b = self.b.l2_norm();
+        let r0 = self.b.sub(&problem.apply(init_param)?);
+        let beta = self.start_cycle(init_param.clone(), r0);
+        Ok((
+            state.cost(beta),
+            Some(
+                kv!(
+                    "tolerance" => self.tol;
+                    "restart" => self.restart as u64;
+                )
+                .with_prefix("config"),
+            ),
+        ))
+    }
+
+    /// Perform one iteration of GMRES
+    fn next_iter(
+        &mut self,
+        problem: &mut Problem<O>,
+        mut state: IterState<P, (), (), (), (), F>,
+    ) -> Result<(IterState<P, (), (), (), (), F>, Option<KV>), Error> {
+        if self.basis.is_empty() {
+            return Err(argmin_error!(
+                PotentialBug,
+                "`GMRES`: Krylov subspace basis not set"
+            ));
+        }
+        // Restart from the current iterate once the maximum dimension of the Krylov subspace is
+        // reached.
+        if self.r.len() >= self.restart {
+            let param = state.take_param().ok_or_else(argmin_error_closure!(
+                PotentialBug,
+                "`GMRES`: Parameter vector in `state` not set"
+            ))?;
+            let r0 = self.b.sub(&problem.apply(&param)?);
+            self.start_cycle(param, r0);
+        }
+        let k = self.r.len();
+
+        // Arnoldi step with modified Gram-Schmidt orthogonalization
+        let mut w = problem.apply(&self.basis[k])?;
+        let mut h = Vec::with_capacity(k + 2);
+        for v in self.basis.iter() {
+            let h_i = w.dot(v);
+            w = w.scaled_add(&(-h_i), v);
+            h.push(h_i);
+        }
+        let h_next = w.l2_norm();
+
+        // Apply previous Givens rotations to the new column of the Hessenberg matrix and compute
+        // a new rotation which eliminates `h_next`.
+        for (i, &(c, s)) in self.rotations.iter().enumerate() {
+            let tmp = c * h[i] + s * h[i + 1];
+            h[i + 1] = -s * h[i] + c * h[i + 1];
+            h[i] = tmp;
+        }
+        let denom = h[k].hypot(h_next);
+        let (c, s) = (h[k] / denom, h_next / denom);
+        h[k] = denom;
+        self.rotations.push((c, s));
+        let g_k = self.g[k];
+        self.g[k] = c * g_k;
+        self.g.push(-s * g_k);
+        self.r.push(h);
+        self.basis.push(if h_next > float!(0.0) {
+            w.mul(&(float!(1.0) / h_next))
+        } else {
+            w
+        });
+
+        // Solve the upper triangular system `R * y = g` and update the parameter vector
+        let mut y = vec![float!(0.0); k + 1];
+        for i in (0..=k).rev() {
+            let mut sum = self.g[i];
+            for (j, y_j) in y.iter().enumerate().skip(i + 1) {
+                sum = sum - self.r[j][i] * *y_j;
+            }
+            y[i] = sum / self.r[i][i];
+        }
+        let x0 = self.x0.as_ref().ok_or_else(argmin_error_closure!(
+            PotentialBug,
+            "`GMRES`: Parameter vector at start of cycle not set"
+        ))?;
+        let new_param = y
+            .iter()
+            .zip(self.basis.iter())
+            .fold(x0.clone(), |x, (y_i, v)| x.scaled_add(y_i, v));
+        let norm_r = self.g[k + 1].abs();
+
+        Ok((
+            state.param(new_param).cost(norm_r),
+            Some(kv!("krylov_dim" => (k + 1) as u64;)),
+        ))
+    }
+
+    fn terminate(&mut self, state: &IterState<P, (), (), (), (), F>) -> TerminationStatus {
+        if state.get_cost() <= self.tol * self.norm_b {
+            return TerminationStatus::Terminated(TerminationReason::SolverConverged);
+        }
+        TerminationStatus::NotTerminated
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::{test_utils::TestProblem, ArgminError, Executor};
+    use approx::assert_relative_eq;
+
+    test_trait_impl!(gmres, GMRES<Vec<f64>, f64>);
+
+    /// Nonsymmetric matrix
+    struct Matrix(Vec<Vec<f64>>);
+
+    impl Operator for Matrix {
+        type Param = Vec<f64>;
+        type Output = Vec<f64>;
+
+        fn apply(&self, p: &Self::Param) -> Result<Self::Output, Error> {
+            Ok(self.0.dot(p))
+        }
+    }
+
+    fn matrix() -> Vec<Vec<f64>> {
+        vec![
+            vec![4.0, 1.0, 0.0, 2.0, 0.0],
+            vec![-1.0, 3.0, 1.0, 0.0, 0.0],
+            vec![0.0, 2.0, 5.0, -1.0, 1.0],
+            vec![1.0, 0.0, -2.0, 4.0, 0.5],
+            vec![0.0, 1.0, 0.0, 3.0, 6.0],
+        ]
+    }
+
+    #[test]
+    fn test_new() {
+        let gmres: GMRES<_, f64> = GMRES::new(vec![1.0f64, 2.0]);
+        assert_eq!(gmres.b, vec![1.0, 2.0]);
+        assert_eq!(gmres.tol.to_ne_bytes(), f64::EPSILON.sqrt().to_ne_bytes());
+        assert_eq!(gmres.restart, 30);
+        assert!(gmres.x0.is_none());
+        assert!(gmres.basis.is_empty());
+    }
+
+    #[test]
+    fn test_with_tolerance() {
+        let gmres: GMRES<_, f64> = GMRES::new(vec![1.0f64]).with_tolerance(1e-4).unwrap();
+        assert_eq!(gmres.tol.to_ne_bytes(), 1e-4f64.to_ne_bytes());
+
+        for tol in [0.0, -1.0] {
+            let res = GMRES::new(vec![1.0f64]).with_tolerance(tol);
+            assert_error!(
+                res,
+                ArgminError,
+                "Invalid parameter: \"`GMRES`: tolerance must be > 0.\""
+            );
+        }
+    }
+
+    #[test]
+    fn test_with_restart() {
+        let gmres: GMRES<_, f64> = GMRES::new(vec![1.0f64]).with_restart(5).unwrap();
+        assert_eq!(gmres.restart, 5);
+
+        let res = GMRES::<_, f64>::new(vec![1.0f64]).with_restart(0);
+        assert_error!(
+            res,
+            ArgminError,
+            "Invalid parameter: \"`GMRES`: restart must be > 0.\""
+        );
+    }
+
+    #[test]
+    fn test_config_build() {
+        let config = GMRESConfig {
+            b: vec![1.0f64, 2.0],
+            tolerance: Some(1e-4),
+            restart: Some(3),
+        };
+        let gmres: GMRES<_, f64> = config.build().unwrap();
+        assert_eq!(gmres.b, config.b);
+        assert_eq!(gmres.tol.to_ne_bytes(), 1e-4f64.to_ne_bytes());
+        assert_eq!(gmres.restart, 3);
+
+        let config: GMRESConfig<_, f64> = GMRESConfig {
+            b: vec![1.0f64, 2.0],
+            restart: Some(0),
+            ..Default::default()
+        };
+        assert!(config.build().is_err());
+    }
+
+    #[test]
+    fn test_init_param_not_initialized() {
+        let mut gmres: GMRES<_, f64> = GMRES::new(vec![1.0f64, 2.0]);
+        let res = gmres.init(&mut Problem::new(TestProblem::new()), IterState::new());
+        assert_error!(
+            res,
+            ArgminError,
+            concat!(
+                "Not initialized: \"`GMRES` requires an initial parameter vector. ",
+                "Please provide an initial guess via `Executor`s `configure` method.\""
+            )
+        );
+    }
+
+    #[test]
+    fn test_next_iter_not_initialized() {
+        let mut gmres: GMRES<_, f64> = GMRES::new(vec![1.0f64, 2.0]);
+        let res = gmres.next_iter(
+            &mut Problem::new(TestProblem::new()),
+            IterState::new().param(vec![1.0, 2.0]),
+        );
+        assert_error!(
+            res,
+            ArgminError,
+            concat!(
+                "Potential bug: \"`GMRES`: Krylov subspace basis not set\". ",
+                "This is potentially a bug. ",
+                "Please file a report on https://github.com/argmin-rs/argmin/issues"
+            )
+        );
+    }
+
+    #[test]
+    fn test_init() {
+        let mut gmres: GMRES<_, f64> = GMRES::new(vec![1.0f64, 2.0]);
+        let state: IterState<Vec<f64>, (), (), (), (), f64> =
+            IterState::new().param(vec![4.0, 6.0]);
+        let (state, kv) = gmres
+            .init(&mut Problem::new(TestProblem::new()), state)
+            .unwrap();
+        assert_eq!(
+            kv.unwrap(),
+            kv!(
+                "tolerance" => f64::EPSILON.sqrt();
+                "restart" => 30u64;
+            )
+            .with_prefix("config")
+        );
+        assert_relative_eq!(state.get_cost(), 5.0);
+        assert_eq!(gmres.basis.len(), 1);
+        assert_relative_eq!(gmres.basis[0][0], -0.6);
+        assert_relative_eq!(gmres.basis[0][1], -0.8);
+        assert_eq!(gmres.g, vec![5.0]);
+    }
+
+    #[test]
+    fn test_nonsymmetric_system() {
+        let a = matrix();
+        let x = vec![1.0, -2.0, 3.0, 0.5, -1.0];
+        let b = a.dot(&x);
+        let solver = GMRES::new(b).with_tolerance(1e-12).unwrap();
+        let res = Executor::new(Matrix(a), solver)
+            .configure(|state| state.param(vec![0.0; 5]).max_iters(20))
+            .run()
+            .unwrap();
+        assert_eq!(
+            res.state.get_termination_reason(),
+            Some(&TerminationReason::SolverConverged)
+        );
+        assert!(res.state.get_iter() <= 5);
+        let param = res.state.get_best_param().unwrap();
+        for i in 0..5 {
+            assert_relative_eq!(param[i], x[i], epsilon = 1e-10);
+        }
+    }
+
+    #[test]
+    fn test_restart() {
+        let a = matrix();
+        let b = vec![1.0, 2.0, 3.0, 4.0, 5.0];
+        let mut problem = Problem::new(Matrix(a.clone()));
+        let mut gmres: GMRES<_, f64> = GMRES::new(b.clone()).with_restart(2).unwrap();
+        let (mut state, _) = gmres
+            .init(&mut problem, IterState::new().param(vec![0.0; 5]))
+            .unwrap();
+        let mut prev_cost = state.get_cost();
+        for i in 0..40 {
+            (state, _) = gmres.next_iter(&mut problem, state).unwrap();
+            assert_eq!(gmres.r.len(), i % 2 + 1);
+            // The cost equals the norm of the residual and does not increase
+            let r = b.sub(&a.dot(state.get_param().unwrap()));
+            assert_relative_eq!(state.get_cost(), r.l2_norm(), epsilon = 1e-10);
+            assert!(state.get_cost() <= prev_cost + 1e-12);
+            prev_cost = state.get_cost();
+        }
+        assert!(prev_cost < 1e-6);
+    }
+}
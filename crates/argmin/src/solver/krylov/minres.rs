@@ -0,0 +1,468 @@
+// Copyright 2018-2024 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use crate::core::{
+    ArgminFloat, Error, IterState, Operator, Problem, Solver, State, TerminationReason,
+    TerminationStatus, KV,
+};
+use argmin_math::{ArgminDot, ArgminL2Norm, ArgminMul, ArgminScaledAdd, ArgminSub, ArgminZeroLike};
+#[cfg(feature = "serde1")]
+use serde::{Deserialize, Serialize};
+
+/// # MINRES method
+///
+/// A solver for systems of linear equations with a symmetric, possibly indefinite matrix.
+///
+/// Solves systems of the form `A * x = b` where `x` and `b` are vectors and `A` is a symmetric
+/// matrix. In contrast to [`ConjugateGradient`](`crate::solver::conjugategradient::ConjugateGradient`),
+/// `A` does not need to be positive definite. In each iteration, the Lanczos process extends the
+/// Krylov subspace by one vector and the iterate minimizing the norm of the residual
+/// `b - A * x` over this subspace is computed. Only a constant number of vectors is stored.
+///
+/// The cost is the norm of the residual, which is updated via the recurrence of the method. The
+/// solver terminates once the norm of the residual is smaller than `tol * ||b||` (see
+/// [`with_tolerance`](`MINRES::with_tolerance`)).
+///
+/// Requires an initial parameter vector.
+///
+/// ## Requirements on the optimization problem
+///
+/// The optimization problem is required to implement [`Operator`], which applies `A` to a
+/// vector.
+///
+/// ## Reference
+///
+/// Christopher C. Paige and Michael A. Saunders (1975). Solution of sparse indefinite systems of
+/// linear equations. SIAM Journal on Numerical Analysis 12(4), 617-629.
+///
+/// Anne Greenbaum (1997). Iterative Methods for Solving Linear Systems. SIAM.
+#[derive(Clone)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+pub struct MINRES<P, F> {
+    /// b (right hand side of `A * x = b`)
+    b: P,
+    /// Relative tolerance of the norm of the residual
+    tol: F,
+    /// Norm of b
+    norm_b: F,
+    /// Current Lanczos vector
+    v: Option<P>,
+    /// Previous Lanczos vector
+    v_prev: Option<P>,
+    /// Previous search direction
+    w: Option<P>,
+    /// Search direction before the previous one
+    w_prev: Option<P>,
+    /// Off-diagonal element of the Lanczos tridiagonal matrix
+    beta: F,
+    /// Current and previous cosine of the Givens rotations
+    c: (F, F),
+    /// Current and previous sine of the Givens rotations
+    s: (F, F),
+    /// Right hand side of the rotated least squares problem
+    eta: F,
+}
+
+impl<P, F> MINRES<P, F>
+where
+    F: ArgminFloat,
+{
+    /// Constructs an instance of [`MINRES`]
+    ///
+    /// Takes `b`, the right hand side of `A * x = b` as input.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::krylov::MINRES;
+    /// # let b = vec![1.0f64, 1.0];
+    /// let minres: MINRES<_, f64> = MINRES::new(b);
+    /// ```
+    pub fn new(b: P) -> Self {
+        MINRES {
+            b,
+            tol: F::epsilon().sqrt(),
+            norm_b: F::nan(),
+            v: None,
+            v_prev: None,
+            w: None,
+            w_prev: None,
+            beta: F::nan(),
+            c: (float!(1.0), float!(1.0)),
+            s: (float!(0.0), float!(0.0)),
+            eta: F::nan(),
+        }
+    }
+
+    /// Set relative tolerance of the norm of the residual
+    ///
+    /// The solver terminates once `||b - A * x|| <= tol * ||b||`. Must be larger than 0 and
+    /// defaults to `sqrt(EPSILON)`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::krylov::MINRES;
+    /// # use argmin::core::Error;
+    /// # fn main() -> Result<(), Error> {
+    /// # let b = vec![1.0f64, 1.0];
+    /// let minres: MINRES<_, f64> = MINRES::new(b).with_tolerance(1e-10)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_tolerance(mut self, tol: F) -> Result<Self, Error> {
+        if tol <= float!(0.0) {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "`MINRES`: tolerance must be > 0."
+            ));
+        }
+        self.tol = tol;
+        Ok(self)
+    }
+}
+
+/// Serializable configuration of [`MINRES`]
+///
+/// Options which are `None` keep the defaults of [`MINRES`]. The configuration is validated when
+/// the solver is constructed via [`build`](`MINRESConfig::build`).
+///
+/// # Example
+///
+/// ```
+/// # use argmin::solver::krylov::{MINRES, MINRESConfig};
+/// # use argmin::core::Error;
+/// # fn main() -> Result<(), Error> {
+/// let config = MINRESConfig {
+///     b: vec![1.0f64, 1.0],
+///     tolerance: Some(1e-10),
+/// };
+/// let minres: MINRES<_, f64> = config.build()?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize), serde(default))]
+pub struct MINRESConfig<P, F> {
+    /// Right hand side `b` of `A * x = b`
+    pub b: P,
+    /// See [`MINRES::with_tolerance`]
+    pub tolerance: Option<F>,
+}
+
+impl<P, F> MINRESConfig<P, F>
+where
+    P: Clone,
+    F: ArgminFloat,
+{
+    /// Validates the configuration and constructs a [`MINRES`] solver.
+    pub fn build(&self) -> Result<MINRES<P, F>, Error> {
+        let mut solver = MINRES::new(self.b.clone());
+        if let Some(tol) = self.tolerance {
+            solver = solver.with_tolerance(tol)?;
+        }
+        Ok(solver)
+    }
+}
+
+impl<P, O, F> Solver<O, IterState<P, (), (), (), (), F>> for MINRES<P, F>
+where
+    O: Operator<Param = P, Output = P>,
+    P: Clone
+        + ArgminSub<P, P>
+        + ArgminDot<P, F>
+        + ArgminL2Norm<F>
+        + ArgminScaledAdd<P, F, P>
+        + ArgminMul<F, P>
+        + ArgminZeroLike,
+    F: ArgminFloat,
+{
+    fn name(&self) -> &str {
+        "MINRES"
+    }
+
+    fn init(
+        &mut self,
+        problem: &mut Problem<O>,
+        state: IterState<P, (), (), (), (), F>,
+    ) -> Result<(IterState<P, (), (), (), (), F>, Option<KV>), Error> {
+        let init_param = state.get_param().ok_or_else(argmin_error_closure!(
+            NotInitialized,
+            concat!(
+                "`MINRES` requires an initial parameter vector. ",
+                "Please provide an initial guess via `Executor`s `configure` method."
+            )
+        ))?;
+        let r0 = self.b.sub(&problem.apply(init_param)?);
+        let beta = r0.l2_norm();
+        let zero = init_param.zero_like();
+
+        self.norm_b = self.b.l2_norm();
+        // If the initial guess solves the system, the solver terminates before the first
+        // iteration and the Lanczos vector is never used.
+        self.v = Some(if beta > float!(0.0) {
+            r0.mul(&(float!(1.0) / beta))
+        } else {
+            r0
+        });
+        self.v_prev = Some(zero.clone());
+        self.w = Some(zero.clone());
+        self.w_prev = Some(zero);
+        self.beta = beta;
+        self.c = (float!(1.0), float!(1.0));
+        self.s = (float!(0.0), float!(0.0));
+        self.eta = beta;
+        Ok((
+            state.cost(beta),
+            Some(kv!("tolerance" => self.tol;).with_prefix("config")),
+        ))
+    }
+
+    /// Perform one iteration of MINRES
+    fn next_iter(
+        &mut self,
+        problem: &mut Problem<O>,
+        mut state: IterState<P, (), (), (), (), F>,
+    ) -> Result<(IterState<P, (), (), (), (), F>, Option<KV>), Error> {
+        let (v, v_prev, w, w_prev) = match (
+            self.v.take(),
+            self.v_prev.take(),
+            self.w.take(),
+            self.w_prev.take(),
+        ) {
+            (Some(v), Some(v_prev), Some(w), Some(w_prev)) => (v, v_prev, w, w_prev),
+            _ => {
+                return Err(argmin_error!(
+                    PotentialBug,
+                    "`MINRES`: Lanczos vectors or search directions not set"
+                ))
+            }
+        };
+        let param = state.take_param().ok_or_else(argmin_error_closure!(
+            PotentialBug,
+            "`MINRES`: Parameter vector in `state` not set"
+        ))?;
+
+        // Lanczos step
+        let av = problem.apply(&v)?;
+        let alpha = v.dot(&av);
+        let v_next = av
+            .scaled_add(&(-alpha), &v)
+            .scaled_add(&(-self.beta), &v_prev);
+        let beta_next = v_next.l2_norm();
+
+        // Apply the previous two Givens rotations to the new column of the tridiagonal matrix
+        // and compute a new rotation which eliminates `beta_next`.
+        let (c, c_prev) = self.c;
+        let (s, s_prev) = self.s;
+        let delta = c * alpha - c_prev * s * self.beta;
+        let rho1 = delta.hypot(beta_next);
+        let rho2 = s * alpha + c_prev * c * self.beta;
+        let rho3 = s_prev * self.beta;
+        let c_next = delta / rho1;
+        let s_next = beta_next / rho1;
+
+        // Update search direction and parameter vector
+        let w_next = v
+            .scaled_add(&(-rho3), &w_prev)
+            .scaled_add(&(-rho2), &w)
+            .mul(&(float!(1.0) / rho1));
+        let new_param = param.scaled_add(&(c_next * self.eta), &w_next);
+        let norm_r = s_next.abs() * state.get_cost();
+
+        self.eta = -s_next * self.eta;
+        self.c = (c_next, c);
+        self.s = (s_next, s);
+        self.beta = beta_next;
+        self.v = Some(if beta_next > float!(0.0) {
+            v_next.mul(&(float!(1.0) / beta_next))
+        } else {
+            v_next
+        });
+        self.v_prev = Some(v);
+        self.w = Some(w_next);
+        self.w_prev = Some(w);
+
+        Ok((
+            state.param(new_param).cost(norm_r),
+            Some(kv!("alpha" => alpha; "beta" => beta_next;)),
+        ))
+    }
+
+    fn terminate(&mut self, state: &IterState<P, (), (), (), (), F>) -> TerminationStatus {
+        if state.get_cost() <= self.tol * self.norm_b {
+            return TerminationStatus::Terminated(TerminationReason::SolverConverged);
+        }
+        TerminationStatus::NotTerminated
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::{test_utils::TestProblem, ArgminError, Executor};
+    use approx::assert_relative_eq;
+
+    test_trait_impl!(minres, MINRES<Vec<f64>, f64>);
+
+    /// Symmetric matrix
+    struct Matrix(Vec<Vec<f64>>);
+
+    impl Operator for Matrix {
+        type Param = Vec<f64>;
+        type Output = Vec<f64>;
+
+        fn apply(&self, p: &Self::Param) -> Result<Self::Output, Error> {
+            Ok(self.0.dot(p))
+        }
+    }
+
+    #[test]
+    fn test_new() {
+        let minres: MINRES<_, f64> = MINRES::new(vec![1.0f64, 2.0]);
+        assert_eq!(minres.b, vec![1.0, 2.0]);
+        assert_eq!(minres.tol.to_ne_bytes(), f64::EPSILON.sqrt().to_ne_bytes());
+        assert!(minres.v.is_none());
+        assert!(minres.w.is_none());
+        assert!(minres.eta.is_nan());
+    }
+
+    #[test]
+    fn test_with_tolerance() {
+        let minres: MINRES<_, f64> = MINRES::new(vec![1.0f64]).with_tolerance(1e-4).unwrap();
+        assert_eq!(minres.tol.to_ne_bytes(), 1e-4f64.to_ne_bytes());
+
+        for tol in [0.0, -1.0] {
+            let res = MINRES::new(vec![1.0f64]).with_tolerance(tol);
+            assert_error!(
+                res,
+                ArgminError,
+                "Invalid parameter: \"`MINRES`: tolerance must be > 0.\""
+            );
+        }
+    }
+
+    #[test]
+    fn test_config_build() {
+        let config = MINRESConfig {
+            b: vec![1.0f64, 2.0],
+            tolerance: Some(1e-4),
+        };
+        let minres: MINRES<_, f64> = config.build().unwrap();
+        assert_eq!(minres.b, config.b);
+        assert_eq!(minres.tol.to_ne_bytes(), 1e-4f64.to_ne_bytes());
+
+        let config = MINRESConfig {
+            b: vec![1.0f64, 2.0],
+            tolerance: Some(0.0),
+        };
+        assert!(config.build().is_err());
+    }
+
+    #[test]
+    fn test_init_param_not_initialized() {
+        let mut minres: MINRES<_, f64> = MINRES::new(vec![1.0f64, 2.0]);
+        let res = minres.init(&mut Problem::new(TestProblem::new()), IterState::new());
+        assert_error!(
+            res,
+            ArgminError,
+            concat!(
+                "Not initialized: \"`MINRES` requires an initial parameter vector. ",
+                "Please provide an initial guess via `Executor`s `configure` method.\""
+            )
+        );
+    }
+
+    #[test]
+    fn test_next_iter_not_initialized() {
+        let mut minres: MINRES<_, f64> = MINRES::new(vec![1.0f64, 2.0]);
+        let res = minres.next_iter(
+            &mut Problem::new(TestProblem::new()),
+            IterState::new().param(vec![1.0, 2.0]),
+        );
+        assert_error!(
+            res,
+            ArgminError,
+            concat!(
+                "Potential bug: \"`MINRES`: Lanczos vectors or search directions not set\". ",
+                "This is potentially a bug. ",
+                "Please file a report on https://github.com/argmin-rs/argmin/issues"
+            )
+        );
+    }
+
+    #[test]
+    fn test_init() {
+        let mut minres: MINRES<_, f64> = MINRES::new(vec![1.0f64, 2.0]);
+        let state: IterState<Vec<f64>, (), (), (), (), f64> =
+            IterState::new().param(vec![4.0, 6.0]);
+        let (state, kv) = minres
+            .init(&mut Problem::new(TestProblem::new()), state)
+            .unwrap();
+        assert_eq!(
+            kv.unwrap(),
+            kv!("tolerance" => f64::EPSILON.sqrt();).with_prefix("config")
+        );
+        assert_relative_eq!(state.get_cost(), 5.0);
+        assert_relative_eq!(minres.norm_b, 5.0f64.sqrt());
+        let v = minres.v.unwrap();
+        assert_relative_eq!(v[0], -0.6);
+        assert_relative_eq!(v[1], -0.8);
+    }
+
+    #[test]
+    fn test_indefinite_system() {
+        // Symmetric and indefinite, eigenvalues are -1, 2 and 4
+        let a = vec![
+            vec![1.0, 2.0, 0.0],
+            vec![2.0, 1.0, 1.0],
+            vec![0.0, 1.0, 3.0],
+        ];
+        let x = vec![1.0, -2.0, 3.0];
+        let b = a.dot(&x);
+        let solver = MINRES::new(b).with_tolerance(1e-12).unwrap();
+        let res = Executor::new(Matrix(a), solver)
+            .configure(|state| state.param(vec![0.0; 3]).max_iters(10))
+            .run()
+            .unwrap();
+        assert_eq!(
+            res.state.get_termination_reason(),
+            Some(&TerminationReason::SolverConverged)
+        );
+        assert!(res.state.get_iter() <= 3);
+        let param = res.state.get_best_param().unwrap();
+        for i in 0..3 {
+            assert_relative_eq!(param[i], x[i], epsilon = 1e-10);
+        }
+    }
+
+    #[test]
+    fn test_residual_norm() {
+        let a = vec![
+            vec![4.0, 1.0, 0.0, 0.0],
+            vec![1.0, -3.0, 1.0, 0.0],
+            vec![0.0, 1.0, 2.0, 1.0],
+            vec![0.0, 0.0, 1.0, -1.0],
+        ];
+        let b = vec![1.0, 2.0, 3.0, 4.0];
+        let mut problem = Problem::new(Matrix(a.clone()));
+        let mut minres: MINRES<_, f64> = MINRES::new(b.clone());
+        let (mut state, _) = minres
+            .init(&mut problem, IterState::new().param(vec![1.0; 4]))
+            .unwrap();
+        let mut prev_cost = state.get_cost();
+        for _ in 0..4 {
+            (state, _) = minres.next_iter(&mut problem, state).unwrap();
+            // The cost equals the norm of the residual and does not increase
+            let r = b.sub(&a.dot(state.get_param().unwrap()));
+            assert_relative_eq!(state.get_cost(), r.l2_norm(), epsilon = 1e-10);
+            assert!(state.get_cost() <= prev_cost + 1e-12);
+            prev_cost = state.get_cost();
+        }
+        assert!(prev_cost < 1e-10);
+    }
+}
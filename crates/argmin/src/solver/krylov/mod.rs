@@ -0,0 +1,35 @@
+// Copyright 2018-2024 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+//! # Krylov subspace methods for linear systems
+//!
+//! * [MINRES](`MINRES`) for symmetric, possibly indefinite matrices
+//! * [Restarted GMRES](`GMRES`) for general (nonsymmetric) matrices
+//!
+//! For symmetric and positive definite matrices, see
+//! [`ConjugateGradient`](`crate::solver::conjugategradient::ConjugateGradient`).
+//!
+//! Like `ConjugateGradient`, both solvers only require the matrix to be applied to vectors via
+//! [`Operator`](`crate::core::Operator`). They can be run via the
+//! [`Executor`](`crate::core::Executor`) or step by step via `init` and `next_iter` of the
+//! [`Solver`](`crate::core::Solver`) trait, for instance to approximately solve the linear
+//! systems arising in Newton-Krylov methods.
+//!
+//! ## References
+//!
+//! Christopher C. Paige and Michael A. Saunders (1975). Solution of sparse indefinite systems of
+//! linear equations. SIAM Journal on Numerical Analysis 12(4), 617-629.
+//!
+//! Youcef Saad and Martin H. Schultz (1986). GMRES: A generalized minimal residual algorithm for
+//! solving nonsymmetric linear systems. SIAM Journal on Scientific and Statistical Computing
+//! 7(3), 856-869.
+
+mod gmres;
+mod minres;
+
+pub use self::gmres::{GMRESConfig, GMRES};
+pub use self::minres::{MINRESConfig, MINRES};
@@ -63,6 +63,45 @@ impl<F> Landweber<F> {
     }
 }
 
+/// Serializable configuration of [`Landweber`]
+///
+/// The configuration is validated when the solver is constructed via
+/// [`build`](`LandweberConfig::build`).
+///
+/// # Example
+///
+/// ```
+/// # use argmin::solver::landweber::LandweberConfig;
+/// # use argmin::core::Error;
+/// # fn main() -> Result<(), Error> {
+/// let config = LandweberConfig { omega: 0.5f64 };
+/// let landweber = config.build()?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize), serde(default))]
+pub struct LandweberConfig<F> {
+    /// Step length `omega`, must be `> 0`
+    pub omega: F,
+}
+
+impl<F> LandweberConfig<F>
+where
+    F: ArgminFloat,
+{
+    /// Validates the configuration and constructs a [`Landweber`] solver.
+    pub fn build(&self) -> Result<Landweber<F>, Error> {
+        if self.omega <= float!(0.0) {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "`Landweber`: omega must be > 0."
+            ));
+        }
+        Ok(Landweber::new(self.omega))
+    }
+}
+
 impl<O, F, P, G> Solver<O, IterState<P, G, (), (), (), F>> for Landweber<F>
 where
     O: Gradient<Param = P, Gradient = G>,
@@ -73,6 +112,17 @@ where
         "Landweber"
     }
 
+    fn init(
+        &mut self,
+        _problem: &mut Problem<O>,
+        state: IterState<P, G, (), (), (), F>,
+    ) -> Result<(IterState<P, G, (), (), (), F>, Option<KV>), Error> {
+        Ok((
+            state,
+            Some(kv!("omega" => self.omega;).with_prefix("config")),
+        ))
+    }
+
     fn next_iter(
         &mut self,
         problem: &mut Problem<O>,
@@ -106,6 +156,19 @@ mod tests {
         assert_eq!(omega.to_ne_bytes(), omega_in.to_ne_bytes());
     }
 
+    #[test]
+    fn test_config_build() {
+        let Landweber { omega } = LandweberConfig { omega: 0.5f64 }.build().unwrap();
+        assert_eq!(omega.to_ne_bytes(), 0.5f64.to_ne_bytes());
+
+        let res = LandweberConfig { omega: 0.0f64 }.build();
+        assert_error!(
+            res,
+            ArgminError,
+            "Invalid parameter: \"`Landweber`: omega must be > 0.\""
+        );
+    }
+
     #[test]
     fn test_next_iter_param_not_initialized() {
         let omega: f64 = 0.5;
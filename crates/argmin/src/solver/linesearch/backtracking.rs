@@ -46,6 +46,9 @@ pub struct BacktrackingLineSearch<P, G, L, F> {
     condition: L,
     /// alpha
     alpha: F,
+    /// Maximum step length
+    #[cfg_attr(feature = "serde1", serde(default))]
+    max_alpha: Option<F>,
 }
 
 impl<P, G, L, F> BacktrackingLineSearch<P, G, L, F>
@@ -72,6 +75,7 @@ where
             rho: float!(0.9),
             condition,
             alpha: float!(1.0),
+            max_alpha: None,
         }
     }
 
@@ -104,6 +108,49 @@ where
     }
 }
 
+/// Serializable configuration of [`BacktrackingLineSearch`]
+///
+/// Options which are `None` keep the defaults of [`BacktrackingLineSearch`]. The configuration is
+/// validated when the line search is constructed via [`build`](`BacktrackingLineSearchConfig::build`).
+///
+/// # Example
+///
+/// ```
+/// # use argmin::solver::linesearch::{BacktrackingLineSearch, BacktrackingLineSearchConfig};
+/// # use argmin::solver::linesearch::condition::ArmijoCondition;
+/// # use argmin::core::Error;
+/// # fn main() -> Result<(), Error> {
+/// let config = BacktrackingLineSearchConfig { rho: Some(0.5f64) };
+/// let backtracking: BacktrackingLineSearch<Vec<f64>, Vec<f64>, _, f64> =
+///     config.build(ArmijoCondition::new(0.0001f64)?)?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize), serde(default))]
+pub struct BacktrackingLineSearchConfig<F> {
+    /// See [`BacktrackingLineSearch::rho`]
+    pub rho: Option<F>,
+}
+
+impl<F> BacktrackingLineSearchConfig<F>
+where
+    F: ArgminFloat,
+{
+    /// Validates the configuration and constructs a [`BacktrackingLineSearch`] using the given
+    /// line search condition.
+    pub fn build<P, G, L>(
+        &self,
+        condition: L,
+    ) -> Result<BacktrackingLineSearch<P, G, L, F>, Error> {
+        let mut linesearch = BacktrackingLineSearch::new(condition);
+        if let Some(rho) = self.rho {
+            linesearch = linesearch.rho(rho)?;
+        }
+        Ok(linesearch)
+    }
+}
+
 impl<P, G, L, F> LineSearch<G, F> for BacktrackingLineSearch<P, G, L, F>
 where
     F: ArgminFloat,
@@ -124,6 +171,20 @@ where
         self.alpha = alpha;
         Ok(())
     }
+
+    /// Set maximum step length
+    ///
+    /// The initial step length is reduced to the maximum step length if necessary.
+    fn max_step_length(&mut self, alpha: F) -> Result<(), Error> {
+        if alpha <= float!(0.0) {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "LineSearch: Maximum alpha must be > 0."
+            ));
+        }
+        self.max_alpha = Some(alpha);
+        Ok(())
+    }
 }
 
 impl<P, G, L, F> BacktrackingLineSearch<P, G, L, F>
@@ -207,23 +268,28 @@ where
             )
         ))?;
 
-        let cost = state.get_cost();
+        let policy = state.get_initial_evaluation();
 
-        self.init_cost = if cost.is_infinite() {
-            problem.cost(&init_param)?
-        } else {
-            cost
-        };
+        let cost = state.get_cost();
+        self.init_cost = policy.evaluate((!cost.is_infinite()).then_some(cost), || {
+            problem.cost(&init_param)
+        })?;
 
-        let init_grad = state
-            .take_gradient()
-            .map(Result::Ok)
-            .unwrap_or_else(|| problem.gradient(&init_param))?;
+        let init_grad = policy.evaluate(state.take_gradient(), || problem.gradient(&init_param))?;
 
         self.init_param = Some(init_param);
         self.init_grad = Some(init_grad);
+        if let Some(max_alpha) = self.max_alpha {
+            self.alpha = self.alpha.min(max_alpha);
+        }
+        let config = kv!(
+            "rho" => self.rho;
+            "condition" => std::any::type_name::<L>();
+            "initial_step_length" => self.alpha;
+        )
+        .with_prefix("config");
         let state = self.backtracking_step(problem, state)?;
-        Ok((state, None))
+        Ok((state, Some(config)))
     }
 
     fn next_iter(
@@ -338,6 +404,25 @@ mod tests {
         assert!(ls.rho(1.0f64 - f64::EPSILON).is_ok());
     }
 
+    #[test]
+    fn test_config_build() {
+        let armijo = ArmijoCondition::new(0.01f64).unwrap();
+        let ls: BacktrackingLineSearch<Vec<f64>, Vec<f64>, ArmijoCondition<f64>, f64> =
+            BacktrackingLineSearchConfig { rho: Some(0.5) }
+                .build(armijo)
+                .unwrap();
+        assert_eq!(ls.rho.to_ne_bytes(), 0.5f64.to_ne_bytes());
+
+        let armijo = ArmijoCondition::new(0.01f64).unwrap();
+        let res: Result<BacktrackingLineSearch<Vec<f64>, Vec<f64>, ArmijoCondition<f64>, f64>, _> =
+            BacktrackingLineSearchConfig { rho: Some(1.0) }.build(armijo);
+        assert_error!(
+            res,
+            ArgminError,
+            "Invalid parameter: \"BacktrackingLineSearch: Contraction factor rho must be in (0, 1).\""
+        );
+    }
+
     #[test]
     fn test_search_direction() {
         let c: f64 = 0.01;
@@ -365,6 +450,27 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_max_step_length() {
+        let mut ls: BacktrackingLineSearch<Vec<f64>, Vec<f64>, ArmijoCondition<f64>, f64> =
+            BacktrackingLineSearch::new(ArmijoCondition::new(0.01).unwrap());
+
+        assert_error!(
+            ls.max_step_length(0.0f64),
+            ArgminError,
+            "Invalid parameter: \"LineSearch: Maximum alpha must be > 0.\""
+        );
+
+        ls.max_step_length(0.25).unwrap();
+        ls.search_direction(vec![2.0f64, 0.0]);
+        let state = IterState::new().param(vec![-1.0, 0.0]);
+        let (state, _) = ls.init(&mut Problem::new(BTTestProblem {}), state).unwrap();
+
+        // The initial step length of 1 is reduced to 0.25
+        assert_relative_eq!(ls.alpha, 0.25, epsilon = f64::EPSILON);
+        assert_relative_eq!(state.get_param().unwrap()[0], -0.5, epsilon = f64::EPSILON);
+    }
+
     #[test]
     fn test_init_param_not_initialized() {
         let mut linesearch: BacktrackingLineSearch<Vec<f64>, Vec<f64>, ArmijoCondition<f64>, f64> =
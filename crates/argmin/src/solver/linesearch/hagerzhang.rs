@@ -47,6 +47,12 @@ pub struct HagerZhangLineSearch<P, G, F> {
     gamma: F,
     /// eta: (0, infinity), used in the lower bound for beta_k^N
     eta: F,
+    /// rho: (1, infinity), expansion factor of the initial step (disabled if `None`)
+    #[cfg_attr(feature = "serde1", serde(default))]
+    rho: Option<F>,
+    /// Accept steps which satisfy the approximate Wolfe conditions
+    #[cfg_attr(feature = "serde1", serde(default = "default_approximate_wolfe"))]
+    approximate_wolfe: bool,
     /// initial a
     a_x_init: F,
     /// a
@@ -87,6 +93,14 @@ pub struct HagerZhangLineSearch<P, G, F> {
     search_direction: Option<G>,
     /// Search direction in 1D
     dginit: F,
+    /// Maximum step length set via [`LineSearch::max_step_length`]
+    #[cfg_attr(feature = "serde1", serde(default))]
+    max_step_length: Option<F>,
+}
+
+#[cfg(feature = "serde1")]
+fn default_approximate_wolfe() -> bool {
+    true
 }
 
 impl<P, G, F> HagerZhangLineSearch<P, G, F>
@@ -112,6 +126,8 @@ where
             theta: float!(0.5),
             gamma: float!(0.66),
             eta: float!(0.01),
+            rho: None,
+            approximate_wolfe: true,
             a_x_init: F::epsilon(),
             a_x: F::nan(),
             a_f: F::nan(),
@@ -132,6 +148,7 @@ where
             search_direction: None,
             dginit: F::nan(),
             finit: F::infinity(),
+            max_step_length: None,
         }
     }
 
@@ -279,6 +296,58 @@ where
         Ok(self)
     }
 
+    /// Set rho
+    ///
+    /// Expansion factor of the initial step: As long as the cost function decreases sufficiently
+    /// along a descent direction, the initial step is multiplied by `rho` (up to the maximum step
+    /// length) before the interval is narrowed down. This avoids many iterations if the initial
+    /// step is far too short, for instance on badly scaled problems.
+    ///
+    /// Must be larger than 1. By default, the initial step is not expanded (the reference value
+    /// in the paper is `5`).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::linesearch::HagerZhangLineSearch;
+    /// # use argmin::core::Error;
+    /// # fn main() -> Result<(), Error> {
+    /// let hzls: HagerZhangLineSearch<Vec<f64>, Vec<f64>, f64> =
+    ///     HagerZhangLineSearch::new().with_rho(5.0)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_rho(mut self, rho: F) -> Result<Self, Error> {
+        if rho <= float!(1.0) {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "`HagerZhangLineSearch`: rho must be > 1."
+            ));
+        }
+        self.rho = Some(rho);
+        Ok(self)
+    }
+
+    /// Enables or disables the approximate Wolfe conditions (default: enabled)
+    ///
+    /// If enabled, the line search terminates if either the Wolfe conditions or the approximate
+    /// Wolfe conditions (controlled by [`with_epsilon`](`HagerZhangLineSearch::with_epsilon`))
+    /// are satisfied. The approximate conditions are numerically more robust close to the
+    /// minimum, but may accept steps with little decrease on badly scaled problems.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::linesearch::HagerZhangLineSearch;
+    /// let hzls: HagerZhangLineSearch<Vec<f64>, Vec<f64>, f64> =
+    ///     HagerZhangLineSearch::new().with_approximate_wolfe(false);
+    /// ```
+    #[must_use]
+    pub fn with_approximate_wolfe(mut self, approximate_wolfe: bool) -> Self {
+        self.approximate_wolfe = approximate_wolfe;
+        self
+    }
+
     /// Set lower and upper bound of step
     ///
     /// Defaults to a minimum step length of `EPSILON` and a maximum step length of `1e5`.
@@ -482,7 +551,89 @@ where
     }
 }
 
-impl<P, G, F> LineSearch<G, F> for HagerZhangLineSearch<P, G, F> {
+/// Serializable configuration of [`HagerZhangLineSearch`]
+///
+/// Options which are `None` keep the defaults of [`HagerZhangLineSearch`]. The configuration is
+/// validated when the line search is constructed via [`build`](`HagerZhangLineSearchConfig::build`).
+///
+/// # Example
+///
+/// ```
+/// # use argmin::solver::linesearch::{HagerZhangLineSearch, HagerZhangLineSearchConfig};
+/// # use argmin::core::Error;
+/// # fn main() -> Result<(), Error> {
+/// let config = HagerZhangLineSearchConfig {
+///     delta_sigma: Some((0.2f64, 0.8)),
+///     ..Default::default()
+/// };
+/// let hzls: HagerZhangLineSearch<Vec<f64>, Vec<f64>, f64> = config.build()?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize), serde(default))]
+pub struct HagerZhangLineSearchConfig<F> {
+    /// Parameters `(delta, sigma)`, see [`HagerZhangLineSearch::with_delta_sigma`]
+    pub delta_sigma: Option<(F, F)>,
+    /// See [`HagerZhangLineSearch::with_epsilon`]
+    pub epsilon: Option<F>,
+    /// See [`HagerZhangLineSearch::with_theta`]
+    pub theta: Option<F>,
+    /// See [`HagerZhangLineSearch::with_gamma`]
+    pub gamma: Option<F>,
+    /// See [`HagerZhangLineSearch::with_eta`]
+    pub eta: Option<F>,
+    /// See [`HagerZhangLineSearch::with_rho`]
+    pub rho: Option<F>,
+    /// See [`HagerZhangLineSearch::with_approximate_wolfe`]
+    pub approximate_wolfe: Option<bool>,
+    /// Step length bounds `(step_min, step_max)`, see [`HagerZhangLineSearch::with_bounds`]
+    pub bounds: Option<(F, F)>,
+}
+
+impl<F> HagerZhangLineSearchConfig<F>
+where
+    F: ArgminFloat,
+{
+    /// Validates the configuration and constructs a [`HagerZhangLineSearch`].
+    pub fn build<P, G>(&self) -> Result<HagerZhangLineSearch<P, G, F>, Error>
+    where
+        P: ArgminScaledAdd<G, F, P>,
+        G: ArgminDot<G, F>,
+    {
+        let mut linesearch = HagerZhangLineSearch::new();
+        if let Some((delta, sigma)) = self.delta_sigma {
+            linesearch = linesearch.with_delta_sigma(delta, sigma)?;
+        }
+        if let Some(epsilon) = self.epsilon {
+            linesearch = linesearch.with_epsilon(epsilon)?;
+        }
+        if let Some(theta) = self.theta {
+            linesearch = linesearch.with_theta(theta)?;
+        }
+        if let Some(gamma) = self.gamma {
+            linesearch = linesearch.with_gamma(gamma)?;
+        }
+        if let Some(eta) = self.eta {
+            linesearch = linesearch.with_eta(eta)?;
+        }
+        if let Some(rho) = self.rho {
+            linesearch = linesearch.with_rho(rho)?;
+        }
+        if let Some(approximate_wolfe) = self.approximate_wolfe {
+            linesearch = linesearch.with_approximate_wolfe(approximate_wolfe);
+        }
+        if let Some((step_min, step_max)) = self.bounds {
+            linesearch = linesearch.with_bounds(step_min, step_max)?;
+        }
+        Ok(linesearch)
+    }
+}
+
+impl<P, G, F> LineSearch<G, F> for HagerZhangLineSearch<P, G, F>
+where
+    F: ArgminFloat,
+{
     /// Set search direction
     fn search_direction(&mut self, search_direction: G) {
         self.search_direction = Some(search_direction);
@@ -493,6 +644,21 @@ impl<P, G, F> LineSearch<G, F> for HagerZhangLineSearch<P, G, F> {
         self.c_x_init = alpha;
         Ok(())
     }
+
+    /// Set maximum step length
+    ///
+    /// Further restricts the upper bound of the step length set via
+    /// [`with_bounds`](`HagerZhangLineSearch::with_bounds`).
+    fn max_step_length(&mut self, alpha: F) -> Result<(), Error> {
+        if alpha <= float!(0.0) {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "HagerZhangLineSearch: Maximum alpha must be > 0."
+            ));
+        }
+        self.max_step_length = Some(alpha);
+        Ok(())
+    }
 }
 
 impl<P, G, O, F> Solver<O, IterState<P, G, (), (), (), F>> for HagerZhangLineSearch<P, G, F>
@@ -527,23 +693,23 @@ where
             )
         ))?);
 
+        let policy = state.get_initial_evaluation();
+        let init_param = self.init_param.as_ref().unwrap();
+
         let cost = state.get_cost();
-        self.finit = if cost.is_infinite() {
-            problem.cost(self.init_param.as_ref().unwrap())?
-        } else {
-            cost
-        };
+        self.finit = policy.evaluate((!cost.is_infinite()).then_some(cost), || {
+            problem.cost(init_param)
+        })?;
 
-        self.init_grad = Some(
-            state
-                .take_gradient()
-                .map(Result::Ok)
-                .unwrap_or_else(|| problem.gradient(self.init_param.as_ref().unwrap()))?,
-        );
+        self.init_grad =
+            Some(policy.evaluate(state.take_gradient(), || problem.gradient(init_param))?);
 
-        self.a_x = self.a_x_init;
-        self.b_x = self.b_x_init;
-        self.c_x = self.c_x_init;
+        self.b_x = match self.max_step_length {
+            Some(max_step_length) => self.b_x_init.min(max_step_length),
+            None => self.b_x_init,
+        };
+        self.a_x = self.a_x_init.min(float!(0.5) * self.b_x);
+        self.c_x = self.c_x_init.min(self.b_x);
 
         self.a_f = self.calc(problem, self.a_x)?;
         self.a_g = self.calc_grad(problem, self.a_x)?;
@@ -554,6 +720,25 @@ where
 
         self.epsilon_k = self.epsilon * self.finit.abs();
 
+        // Expand the initial step while the cost function decreases sufficiently along a descent
+        // direction. The last step with sufficient decrease becomes the lower end of the interval
+        // and the first one without becomes the upper end.
+        if let Some(rho) = self.rho {
+            while self.c_g < float!(0.0) && self.c_f <= self.finit + self.epsilon_k {
+                (self.a_x, self.a_f, self.a_g) = (self.c_x, self.c_f, self.c_g);
+                let c_x = rho * self.c_x;
+                if c_x >= self.b_x {
+                    break;
+                }
+                self.c_x = c_x;
+                self.c_f = self.calc(problem, c_x)?;
+                self.c_g = self.calc_grad(problem, c_x)?;
+                if self.c_g >= float!(0.0) || self.c_f > self.finit + self.epsilon_k {
+                    (self.b_x, self.b_f, self.b_g) = (self.c_x, self.c_f, self.c_g);
+                }
+            }
+        }
+
         self.dginit = self
             .init_grad
             .as_ref()
@@ -568,7 +753,26 @@ where
             .scaled_add(&self.best_x, self.search_direction.as_ref().unwrap());
         let best_f = self.best_f;
 
-        Ok((state.param(new_param).cost(best_f), None))
+        let mut config = kv!(
+            "delta" => self.delta;
+            "sigma" => self.sigma;
+            "epsilon" => self.epsilon;
+            "theta" => self.theta;
+            "gamma" => self.gamma;
+            "eta" => self.eta;
+            "approximate_wolfe" => self.approximate_wolfe;
+            "step_min" => self.a_x_init;
+            "step_max" => self.b_x_init;
+            "initial_step_length" => self.c_x_init;
+        );
+        if let Some(rho) = self.rho {
+            config = config.merge(kv!("rho" => rho;));
+        }
+
+        Ok((
+            state.param(new_param).cost(best_f),
+            Some(config.with_prefix("config")),
+        ))
     }
 
     fn next_iter(
@@ -630,7 +834,8 @@ where
         {
             return TerminationStatus::Terminated(TerminationReason::SolverConverged);
         }
-        if (float!(2.0) * self.delta - float!(1.0)) * self.dginit >= self.best_g
+        if self.approximate_wolfe
+            && (float!(2.0) * self.delta - float!(1.0)) * self.dginit >= self.best_g
             && self.best_g >= self.sigma * self.dginit
             && self.best_f <= self.finit + self.epsilon_k
         {
@@ -658,6 +863,8 @@ mod tests {
             theta,
             gamma,
             eta,
+            rho,
+            approximate_wolfe,
             a_x_init,
             a_x,
             a_f,
@@ -678,6 +885,7 @@ mod tests {
             search_direction,
             dginit,
             finit,
+            max_step_length,
         } = hzls;
 
         assert_eq!(delta.to_ne_bytes(), 0.1f64.to_ne_bytes());
@@ -687,6 +895,8 @@ mod tests {
         assert_eq!(theta.to_ne_bytes(), 0.5f64.to_ne_bytes());
         assert_eq!(gamma.to_ne_bytes(), 0.66f64.to_ne_bytes());
         assert_eq!(eta.to_ne_bytes(), 0.01f64.to_ne_bytes());
+        assert!(rho.is_none());
+        assert!(approximate_wolfe);
         assert_eq!(a_x_init.to_ne_bytes(), f64::EPSILON.to_ne_bytes());
         assert!(a_x.is_nan());
         assert!(a_f.is_nan());
@@ -709,6 +919,7 @@ mod tests {
         assert!(dginit.is_nan());
         assert!(finit.is_infinite());
         assert!(finit.is_sign_positive());
+        assert!(max_step_length.is_none());
     }
 
     #[test]
@@ -863,6 +1074,36 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_with_rho() {
+        // correct parameters
+        for rho in [1.0 + f64::EPSILON, 2.0, 5.0, 100.0] {
+            let hzls: HagerZhangLineSearch<Vec<f64>, Vec<f64>, f64> = HagerZhangLineSearch::new();
+            let res = hzls.with_rho(rho);
+            assert!(res.is_ok());
+
+            let hzls = res.unwrap();
+            assert_eq!(hzls.rho.unwrap().to_ne_bytes(), rho.to_ne_bytes());
+        }
+
+        // incorrect parameters
+        for rho in [1.0, 0.5, 0.0, -5.0] {
+            let hzls: HagerZhangLineSearch<Vec<f64>, Vec<f64>, f64> = HagerZhangLineSearch::new();
+            let res = hzls.with_rho(rho);
+            assert_error!(
+                res,
+                ArgminError,
+                "Invalid parameter: \"`HagerZhangLineSearch`: rho must be > 1.\""
+            );
+        }
+    }
+
+    #[test]
+    fn test_with_approximate_wolfe() {
+        let hzls: HagerZhangLineSearch<Vec<f64>, Vec<f64>, f64> = HagerZhangLineSearch::new();
+        assert!(!hzls.with_approximate_wolfe(false).approximate_wolfe);
+    }
+
     #[test]
     fn test_with_bounds() {
         // correct parameters
@@ -904,6 +1145,43 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_config_build() {
+        let config = HagerZhangLineSearchConfig {
+            delta_sigma: Some((0.2f64, 0.8)),
+            epsilon: Some(1e-4),
+            theta: Some(0.3),
+            gamma: Some(0.4),
+            eta: Some(0.05),
+            rho: Some(3.0),
+            approximate_wolfe: Some(false),
+            bounds: Some((0.1, 2.0)),
+        };
+        let hzls: HagerZhangLineSearch<Vec<f64>, Vec<f64>, f64> = config.build().unwrap();
+        assert_eq!(hzls.delta.to_ne_bytes(), 0.2f64.to_ne_bytes());
+        assert_eq!(hzls.sigma.to_ne_bytes(), 0.8f64.to_ne_bytes());
+        assert_eq!(hzls.epsilon.to_ne_bytes(), 1e-4f64.to_ne_bytes());
+        assert_eq!(hzls.theta.to_ne_bytes(), 0.3f64.to_ne_bytes());
+        assert_eq!(hzls.gamma.to_ne_bytes(), 0.4f64.to_ne_bytes());
+        assert_eq!(hzls.eta.to_ne_bytes(), 0.05f64.to_ne_bytes());
+        assert_eq!(hzls.rho.unwrap().to_ne_bytes(), 3.0f64.to_ne_bytes());
+        assert!(!hzls.approximate_wolfe);
+        assert_eq!(hzls.a_x_init.to_ne_bytes(), 0.1f64.to_ne_bytes());
+        assert_eq!(hzls.b_x_init.to_ne_bytes(), 2.0f64.to_ne_bytes());
+
+        let res: Result<HagerZhangLineSearch<Vec<f64>, Vec<f64>, f64>, _> =
+            HagerZhangLineSearchConfig {
+                theta: Some(1.0),
+                ..Default::default()
+            }
+            .build();
+        assert_error!(
+            res,
+            ArgminError,
+            "Invalid parameter: \"`HagerZhangLineSearch`: theta must be in (0, 1).\""
+        );
+    }
+
     #[test]
     fn test_init_search_direction_not_set() {
         let mut hzls: HagerZhangLineSearch<Vec<f64>, Vec<f64>, f64> = HagerZhangLineSearch::new();
@@ -932,4 +1210,90 @@ mod tests {
             )
         );
     }
+
+    #[test]
+    fn test_init_rho() {
+        use crate::core::Gradient;
+
+        /// `(x - 100)^2`
+        struct Far {}
+
+        impl CostFunction for Far {
+            type Param = Vec<f64>;
+            type Output = f64;
+
+            fn cost(&self, p: &Self::Param) -> Result<Self::Output, Error> {
+                Ok((p[0] - 100.0).powi(2))
+            }
+        }
+
+        impl Gradient for Far {
+            type Param = Vec<f64>;
+            type Gradient = Vec<f64>;
+
+            fn gradient(&self, p: &Self::Param) -> Result<Self::Gradient, Error> {
+                Ok(vec![2.0 * (p[0] - 100.0)])
+            }
+        }
+
+        // The initial step of 1 is expanded to 5, 25 and 125, where the slope is positive.
+        let mut hzls: HagerZhangLineSearch<Vec<f64>, Vec<f64>, f64> =
+            HagerZhangLineSearch::new().with_rho(5.0).unwrap();
+        hzls.search_direction(vec![1.0]);
+        hzls.init(&mut Problem::new(Far {}), IterState::new().param(vec![0.0]))
+            .unwrap();
+        assert_eq!(hzls.a_x.to_ne_bytes(), 25.0f64.to_ne_bytes());
+        assert_eq!(hzls.b_x.to_ne_bytes(), 125.0f64.to_ne_bytes());
+
+        // Without expansion, the interval is given by the bounds of the step length
+        let mut hzls: HagerZhangLineSearch<Vec<f64>, Vec<f64>, f64> = HagerZhangLineSearch::new();
+        hzls.search_direction(vec![1.0]);
+        hzls.init(&mut Problem::new(Far {}), IterState::new().param(vec![0.0]))
+            .unwrap();
+        assert_eq!(hzls.a_x.to_ne_bytes(), f64::EPSILON.to_ne_bytes());
+        assert_eq!(hzls.b_x.to_ne_bytes(), 1e5f64.to_ne_bytes());
+    }
+
+    #[test]
+    fn test_max_step_length() {
+        use crate::core::{Executor, Gradient};
+
+        /// `(x - 2)^2`, which must not be evaluated for `x > 0.5`
+        struct Bounded {}
+
+        impl CostFunction for Bounded {
+            type Param = Vec<f64>;
+            type Output = f64;
+
+            fn cost(&self, p: &Self::Param) -> Result<Self::Output, Error> {
+                assert!(p[0] <= 0.5);
+                Ok((p[0] - 2.0).powi(2))
+            }
+        }
+
+        impl Gradient for Bounded {
+            type Param = Vec<f64>;
+            type Gradient = Vec<f64>;
+
+            fn gradient(&self, p: &Self::Param) -> Result<Self::Gradient, Error> {
+                assert!(p[0] <= 0.5);
+                Ok(vec![2.0 * (p[0] - 2.0)])
+            }
+        }
+
+        let mut hzls: HagerZhangLineSearch<Vec<f64>, Vec<f64>, f64> = HagerZhangLineSearch::new();
+        assert_error!(
+            hzls.max_step_length(0.0),
+            ArgminError,
+            "Invalid parameter: \"HagerZhangLineSearch: Maximum alpha must be > 0.\""
+        );
+
+        hzls.search_direction(vec![1.0]);
+        hzls.max_step_length(0.5).unwrap();
+        let res = Executor::new(Bounded {}, hzls)
+            .configure(|state| state.param(vec![0.0]).max_iters(20))
+            .run()
+            .unwrap();
+        assert!(res.state.get_best_param().unwrap()[0] <= 0.5);
+    }
 }
@@ -38,10 +38,14 @@ mod backtracking;
 pub mod condition;
 mod hagerzhang;
 mod morethuente;
+mod recovery;
 
-pub use self::backtracking::BacktrackingLineSearch;
-pub use self::hagerzhang::HagerZhangLineSearch;
-pub use self::morethuente::MoreThuenteLineSearch;
+pub use self::backtracking::{BacktrackingLineSearch, BacktrackingLineSearchConfig};
+pub use self::hagerzhang::{HagerZhangLineSearch, HagerZhangLineSearchConfig};
+pub use self::morethuente::{MoreThuenteLineSearch, MoreThuenteLineSearchConfig};
+#[cfg(test)]
+pub(crate) use self::recovery::tests::FlakyLineSearch;
+pub use self::recovery::LineSearchRecovery;
 
 /// # Line search trait
 ///
@@ -84,4 +88,14 @@ pub trait LineSearch<D, F> {
     ///
     /// This indicates the first step length which will be tried.
     fn initial_step_length(&mut self, step_length: F) -> Result<(), crate::core::Error>;
+
+    /// Set the maximum step length
+    ///
+    /// Line searches which support this do not evaluate the cost function at step lengths larger
+    /// than `step_length`. Solvers use this to keep the line search within the bounds of the
+    /// parameter vector (see [`IterState::max_step_in_bounds`](`crate::core::IterState::max_step_in_bounds`)).
+    /// The default implementation ignores the maximum step length.
+    fn max_step_length(&mut self, _step_length: F) -> Result<(), crate::core::Error> {
+        Ok(())
+    }
 }
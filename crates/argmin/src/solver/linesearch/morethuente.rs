@@ -35,6 +35,11 @@ use serde::{Deserialize, Serialize};
 /// be set via [`with_width_tolerance`](`MoreThuenteLineSearch::with_width_tolerance`) and must be
 /// non-negative.
 ///
+/// If the directional derivative at the initial point vanishes (the initial point is stationary
+/// along the search direction), no step is taken. The line search then terminates right after
+/// initialization with [`TerminationReason::StationaryDirection`] and reports `stationary` in its
+/// KV, leaving it to the calling solver to restart along another direction or to stop.
+///
 /// TODO: Add missing stopping criteria!
 ///
 /// ## Requirements on the optimization problem
@@ -82,6 +87,9 @@ pub struct MoreThuenteLineSearch<P, G, F> {
     stpmin: F,
     /// stpmax
     stpmax: F,
+    /// Maximum step length set via [`LineSearch::max_step_length`]
+    #[cfg_attr(feature = "serde1", serde(default))]
+    max_step_length: Option<F>,
     /// current step
     stp: Step<F>,
     /// stx (one endpoint of uncertainty interval)
@@ -155,6 +163,7 @@ where
             alpha: float!(1.0),
             stpmin: F::epsilon().sqrt(),
             stpmax: F::infinity(),
+            max_step_length: None,
             stp: Step::default(),
             stx: Step::default(),
             sty: Step::default(),
@@ -273,6 +282,56 @@ where
     }
 }
 
+/// Serializable configuration of [`MoreThuenteLineSearch`]
+///
+/// Options which are `None` keep the defaults of [`MoreThuenteLineSearch`]. The configuration is
+/// validated when the line search is constructed via [`build`](`MoreThuenteLineSearchConfig::build`).
+///
+/// # Example
+///
+/// ```
+/// # use argmin::solver::linesearch::{MoreThuenteLineSearch, MoreThuenteLineSearchConfig};
+/// # use argmin::core::Error;
+/// # fn main() -> Result<(), Error> {
+/// let config = MoreThuenteLineSearchConfig {
+///     c: Some((1e-4f64, 0.5)),
+///     ..Default::default()
+/// };
+/// let mtls: MoreThuenteLineSearch<Vec<f64>, Vec<f64>, f64> = config.build()?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize), serde(default))]
+pub struct MoreThuenteLineSearchConfig<F> {
+    /// Parameters `(c1, c2)`, see [`MoreThuenteLineSearch::with_c`]
+    pub c: Option<(F, F)>,
+    /// Step length bounds `(step_min, step_max)`, see [`MoreThuenteLineSearch::with_bounds`]
+    pub bounds: Option<(F, F)>,
+    /// See [`MoreThuenteLineSearch::with_width_tolerance`]
+    pub width_tolerance: Option<F>,
+}
+
+impl<F> MoreThuenteLineSearchConfig<F>
+where
+    F: ArgminFloat,
+{
+    /// Validates the configuration and constructs a [`MoreThuenteLineSearch`].
+    pub fn build<P, G>(&self) -> Result<MoreThuenteLineSearch<P, G, F>, Error> {
+        let mut linesearch = MoreThuenteLineSearch::new();
+        if let Some((c1, c2)) = self.c {
+            linesearch = linesearch.with_c(c1, c2)?;
+        }
+        if let Some((step_min, step_max)) = self.bounds {
+            linesearch = linesearch.with_bounds(step_min, step_max)?;
+        }
+        if let Some(xtol) = self.width_tolerance {
+            linesearch = linesearch.with_width_tolerance(xtol)?;
+        }
+        Ok(linesearch)
+    }
+}
+
 impl<P, G, F> LineSearch<G, F> for MoreThuenteLineSearch<P, G, F>
 where
     F: ArgminFloat,
@@ -293,6 +352,36 @@ where
         self.alpha = alpha;
         Ok(())
     }
+
+    /// Set maximum step length
+    ///
+    /// Further restricts the upper bound of the step length set via
+    /// [`with_bounds`](`MoreThuenteLineSearch::with_bounds`).
+    fn max_step_length(&mut self, alpha: F) -> Result<(), Error> {
+        if alpha <= float!(0.0) {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "MoreThuenteLineSearch: Maximum alpha must be > 0."
+            ));
+        }
+        self.max_step_length = Some(alpha);
+        Ok(())
+    }
+}
+
+impl<P, G, F> MoreThuenteLineSearch<P, G, F>
+where
+    F: ArgminFloat,
+{
+    /// Returns the lower and upper bound of the step length, taking the maximum step length into
+    /// account
+    fn step_bounds(&self) -> (F, F) {
+        let stpmax = match self.max_step_length {
+            Some(max_step_length) => self.stpmax.min(max_step_length),
+            None => self.stpmax,
+        };
+        (self.stpmin.min(stpmax), stpmax)
+    }
 }
 
 impl<P, G, O, F> Solver<O, IterState<P, G, (), (), (), F>> for MoreThuenteLineSearch<P, G, F>
@@ -327,19 +416,16 @@ where
             )
         ))?);
 
+        let policy = state.get_initial_evaluation();
+        let init_param = self.init_param.as_ref().unwrap();
+
         let cost = state.get_cost();
-        self.finit = if cost.is_infinite() {
-            problem.cost(self.init_param.as_ref().unwrap())?
-        } else {
-            cost
-        };
+        self.finit = policy.evaluate((!cost.is_infinite()).then_some(cost), || {
+            problem.cost(init_param)
+        })?;
 
-        self.init_grad = Some(
-            state
-                .take_gradient()
-                .map(Result::Ok)
-                .unwrap_or_else(|| problem.gradient(self.init_param.as_ref().unwrap()))?,
-        );
+        self.init_grad =
+            Some(policy.evaluate(state.take_gradient(), || problem.gradient(init_param))?);
 
         self.dginit = self
             .init_grad
@@ -347,6 +433,25 @@ where
             .unwrap()
             .dot(self.search_direction.as_ref().unwrap());
 
+        // If the directional derivative vanishes, the initial parameter vector is already
+        // stationary along the search direction. Instead of failing, no step is taken and it is
+        // left to the caller to decide how to proceed (for instance by restarting along another
+        // direction or by stopping).
+        let init_grad = self.init_grad.as_ref().unwrap();
+        let direction = self.search_direction.as_ref().unwrap();
+        if self.dginit.abs()
+            <= F::epsilon() * (init_grad.dot(init_grad) * direction.dot(direction)).sqrt()
+        {
+            return Ok((
+                state
+                    .param(self.init_param.clone().unwrap())
+                    .gradient(self.init_grad.clone().unwrap())
+                    .cost(self.finit)
+                    .terminate_with(TerminationReason::StationaryDirection),
+                Some(kv!("stationary" => true;)),
+            ));
+        }
+
         // compute search direction in 1D
         if self.dginit >= float!(0.0) {
             return Err(argmin_error!(
@@ -359,7 +464,8 @@ where
         self.brackt = false;
 
         self.dgtest = self.ftol * self.dginit;
-        self.width = self.stpmax - self.stpmin;
+        let (stpmin, stpmax) = self.step_bounds();
+        self.width = stpmax - stpmin;
         self.width1 = float!(2.0) * self.width;
         self.f = self.finit;
 
@@ -367,7 +473,20 @@ where
         self.stx = Step::new(float!(0.0), self.finit, self.dginit);
         self.sty = Step::new(float!(0.0), self.finit, self.dginit);
 
-        Ok((state, None))
+        Ok((
+            state,
+            Some(
+                kv!(
+                    "c1" => self.ftol;
+                    "c2" => self.gtol;
+                    "step_min" => self.stpmin;
+                    "step_max" => self.stpmax;
+                    "width_tolerance" => self.xtol;
+                    "initial_step_length" => self.alpha;
+                )
+                .with_prefix("config"),
+            ),
+        ))
     }
 
     fn next_iter(
@@ -387,8 +506,9 @@ where
         };
 
         // alpha needs to be within bounds
-        self.stp.x = self.stp.x.max(self.stpmin);
-        self.stp.x = self.stp.x.min(self.stpmax);
+        let (stpmin, stpmax) = self.step_bounds();
+        self.stp.x = self.stp.x.max(stpmin);
+        self.stp.x = self.stp.x.min(stpmax);
 
         // If an unusual termination is to occur then let alpha be the lowest point obtained so
         // far.
@@ -420,13 +540,11 @@ where
             info = 6;
         }
 
-        if (self.stp.x - self.stpmax).abs() < F::epsilon() && self.f <= ftest1 && dg <= self.dgtest
-        {
+        if (self.stp.x - stpmax).abs() < F::epsilon() && self.f <= ftest1 && dg <= self.dgtest {
             info = 5;
         }
 
-        if (self.stp.x - self.stpmin).abs() < F::epsilon() && (self.f > ftest1 || dg >= self.dgtest)
-        {
+        if (self.stp.x - stpmin).abs() < F::epsilon() && (self.f > ftest1 || dg >= self.dgtest) {
             info = 4;
         }
 
@@ -745,6 +863,7 @@ mod tests {
             alpha,
             stpmin,
             stpmax,
+            max_step_length,
             stp,
             stx,
             sty,
@@ -771,6 +890,7 @@ mod tests {
         assert_eq!(stpmin.to_ne_bytes(), f64::EPSILON.sqrt().to_ne_bytes());
         assert!(stpmax.is_infinite());
         assert!(stpmax.is_sign_positive());
+        assert!(max_step_length.is_none());
         assert_eq!(stp, Step::default());
         assert_eq!(stx, Step::default());
         assert_eq!(sty, Step::default());
@@ -896,6 +1016,36 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_config_build() {
+        let config = MoreThuenteLineSearchConfig {
+            c: Some((0.1f64, 0.5)),
+            bounds: Some((1e-3, 10.0)),
+            width_tolerance: Some(1e-8),
+        };
+        let mtls: MoreThuenteLineSearch<Vec<f64>, Vec<f64>, f64> = config.build().unwrap();
+        assert_eq!(mtls.ftol.to_ne_bytes(), 0.1f64.to_ne_bytes());
+        assert_eq!(mtls.gtol.to_ne_bytes(), 0.5f64.to_ne_bytes());
+        assert_eq!(mtls.stpmin.to_ne_bytes(), 1e-3f64.to_ne_bytes());
+        assert_eq!(mtls.stpmax.to_ne_bytes(), 10.0f64.to_ne_bytes());
+        assert_eq!(mtls.xtol.to_ne_bytes(), 1e-8f64.to_ne_bytes());
+
+        let res: Result<MoreThuenteLineSearch<Vec<f64>, Vec<f64>, f64>, _> =
+            MoreThuenteLineSearchConfig {
+                bounds: Some((1.0, 0.5)),
+                ..Default::default()
+            }
+            .build();
+        assert_error!(
+            res,
+            ArgminError,
+            concat!(
+                "Invalid parameter: \"`MoreThuenteLineSearch`: ",
+                "step_min must be smaller than step_max.\""
+            )
+        );
+    }
+
     #[test]
     fn test_init_search_direction_not_set() {
         let mut mtls: MoreThuenteLineSearch<Vec<f64>, Vec<f64>, f64> = MoreThuenteLineSearch::new();
@@ -924,4 +1074,73 @@ mod tests {
             )
         );
     }
+
+    #[test]
+    fn test_init_stationary() {
+        use crate::core::{Executor, State};
+
+        // The gradient of `TestProblem` is the parameter vector itself, which is orthogonal to
+        // the search direction.
+        let mut mtls: MoreThuenteLineSearch<Vec<f64>, Vec<f64>, f64> = MoreThuenteLineSearch::new();
+        mtls.search_direction(vec![0.0, 1.0]);
+        let res = Executor::new(TestProblem::new(), mtls)
+            .configure(|state| state.param(vec![1.0, 0.0]).max_iters(20))
+            .run()
+            .unwrap();
+        assert_eq!(
+            res.state.get_termination_reason(),
+            Some(&TerminationReason::StationaryDirection)
+        );
+        assert_eq!(res.state.get_iter(), 0);
+        assert_eq!(res.state.get_best_param().unwrap(), &vec![1.0, 0.0]);
+        assert_eq!(
+            res.state.get_best_cost().to_ne_bytes(),
+            1.0f64.to_ne_bytes()
+        );
+    }
+
+    #[test]
+    fn test_max_step_length() {
+        use crate::core::{CostFunction, Executor, Gradient, State};
+
+        /// `(x - 2)^2`, which must not be evaluated for `x > 0.5`
+        struct Bounded {}
+
+        impl CostFunction for Bounded {
+            type Param = Vec<f64>;
+            type Output = f64;
+
+            fn cost(&self, p: &Self::Param) -> Result<Self::Output, Error> {
+                assert!(p[0] <= 0.5);
+                Ok((p[0] - 2.0).powi(2))
+            }
+        }
+
+        impl Gradient for Bounded {
+            type Param = Vec<f64>;
+            type Gradient = Vec<f64>;
+
+            fn gradient(&self, p: &Self::Param) -> Result<Self::Gradient, Error> {
+                assert!(p[0] <= 0.5);
+                Ok(vec![2.0 * (p[0] - 2.0)])
+            }
+        }
+
+        let mut mtls: MoreThuenteLineSearch<Vec<f64>, Vec<f64>, f64> = MoreThuenteLineSearch::new();
+        assert_error!(
+            mtls.max_step_length(0.0),
+            ArgminError,
+            "Invalid parameter: \"MoreThuenteLineSearch: Maximum alpha must be > 0.\""
+        );
+
+        // The minimum along the direction is at a step length of 2, but the cost function must
+        // not be evaluated beyond a step length of 0.5.
+        mtls.search_direction(vec![1.0]);
+        mtls.max_step_length(0.5).unwrap();
+        let res = Executor::new(Bounded {}, mtls)
+            .configure(|state| state.param(vec![0.0]).max_iters(20))
+            .run()
+            .unwrap();
+        assert!(res.state.get_best_param().unwrap()[0] <= 0.5);
+    }
 }
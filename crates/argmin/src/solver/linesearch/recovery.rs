@@ -0,0 +1,260 @@
+// Copyright 2018-2024 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use crate::core::{ArgminFloat, CostFunction, Error, Problem};
+use argmin_math::{ArgminAdd, ArgminDot, ArgminMul};
+#[cfg(feature = "serde1")]
+use serde::{Deserialize, Serialize};
+
+/// Sufficient decrease parameter of the backtracking fallback
+const ARMIJO_C: f64 = 1e-4;
+
+/// Factor by which the step length of the backtracking fallback is reduced in each trial
+const BACKTRACKING_RHO: f64 = 0.5;
+
+/// Maximum number of step lengths tried by the backtracking fallback
+const BACKTRACKING_MAX_TRIALS: usize = 30;
+
+/// # Line search recovery policy
+///
+/// Determines how gradient based solvers such as [`LBFGS`](`crate::solver::quasinewton::LBFGS`)
+/// and [`NonlinearConjugateGradient`](`crate::solver::conjugategradient::NonlinearConjugateGradient`)
+/// react if their line search fails.
+///
+/// If the chosen recovery does not succeed either, the solver terminates with
+/// [`TerminationReason::LineSearchFailed`](`crate::core::TerminationReason::LineSearchFailed`).
+///
+/// # Example
+///
+/// ```
+/// # use argmin::solver::linesearch::LineSearchRecovery;
+/// let policy: LineSearchRecovery<f64> = LineSearchRecovery::default();
+/// assert_eq!(policy, LineSearchRecovery::Stop);
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+pub enum LineSearchRecovery<F> {
+    /// Terminate the run (default)
+    #[default]
+    Stop,
+    /// Fall back to a backtracking line search along the same search direction which starts
+    /// from a step length of 1 and only enforces the Armijo (sufficient decrease) condition
+    Backtracking,
+    /// Discard the curvature information gathered so far (the memory of quasi-Newton methods or
+    /// the conjugate direction) and repeat the line search along the steepest descent direction
+    ResetMemory,
+    /// Take a step of the given length along the search direction, provided that it decreases
+    /// the cost function
+    SmallStep(F),
+}
+
+impl<F> LineSearchRecovery<F>
+where
+    F: ArgminFloat,
+{
+    /// Tries to find a new parameter vector along `direction` without relying on the line
+    /// search.
+    ///
+    /// Returns the new parameter vector and its cost, or `None` if the policy does not provide
+    /// such a step or if no decrease of the cost function was found.
+    pub(crate) fn fallback_step<O, P, G>(
+        &self,
+        problem: &mut Problem<O>,
+        param: &P,
+        cost: F,
+        gradient: &G,
+        direction: &P,
+    ) -> Result<Option<(P, F)>, Error>
+    where
+        O: CostFunction<Param = P, Output = F>,
+        P: ArgminAdd<P, P> + ArgminMul<F, P>,
+        G: ArgminDot<P, F>,
+    {
+        match *self {
+            LineSearchRecovery::Backtracking => {
+                let slope = gradient.dot(direction);
+                // Backtracking cannot succeed if `direction` is not a descent direction.
+                if slope.is_nan() || slope >= float!(0.0) {
+                    return Ok(None);
+                }
+                let mut step = float!(1.0);
+                for _ in 0..BACKTRACKING_MAX_TRIALS {
+                    let candidate = param.add(&direction.mul(&step));
+                    let candidate_cost = problem.cost(&candidate)?;
+                    if candidate_cost <= cost + float!(ARMIJO_C) * step * slope {
+                        return Ok(Some((candidate, candidate_cost)));
+                    }
+                    step = step * float!(BACKTRACKING_RHO);
+                }
+                Ok(None)
+            }
+            LineSearchRecovery::SmallStep(step) => {
+                let candidate = param.add(&direction.mul(&step));
+                let candidate_cost = problem.cost(&candidate)?;
+                Ok((candidate_cost < cost).then_some((candidate, candidate_cost)))
+            }
+            LineSearchRecovery::Stop | LineSearchRecovery::ResetMemory => Ok(None),
+        }
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use crate::core::test_utils::TestProblem;
+    use crate::core::{IterState, LineSearch, Solver, State, TerminationReason, KV};
+    use std::cell::Cell;
+    use std::rc::Rc;
+
+    /// Line search which fails in the given attempts (counted from 1) and otherwise takes a step
+    /// of length 0.25 along the search direction.
+    #[derive(Clone)]
+    pub(crate) struct FlakyLineSearch {
+        direction: Vec<f64>,
+        pub(crate) attempts: Rc<Cell<u64>>,
+        fail_on: Vec<u64>,
+    }
+
+    impl FlakyLineSearch {
+        pub(crate) fn new(fail_on: Vec<u64>) -> Self {
+            FlakyLineSearch {
+                direction: vec![],
+                attempts: Rc::new(Cell::new(0)),
+                fail_on,
+            }
+        }
+    }
+
+    impl LineSearch<Vec<f64>, f64> for FlakyLineSearch {
+        fn search_direction(&mut self, direction: Vec<f64>) {
+            self.direction = direction;
+        }
+
+        fn initial_step_length(&mut self, _step_length: f64) -> Result<(), Error> {
+            Ok(())
+        }
+    }
+
+    impl<O> Solver<O, IterState<Vec<f64>, Vec<f64>, (), (), (), f64>> for FlakyLineSearch
+    where
+        O: CostFunction<Param = Vec<f64>, Output = f64>,
+    {
+        fn name(&self) -> &str {
+            "Flaky line search"
+        }
+
+        fn next_iter(
+            &mut self,
+            problem: &mut Problem<O>,
+            mut state: IterState<Vec<f64>, Vec<f64>, (), (), (), f64>,
+        ) -> Result<(IterState<Vec<f64>, Vec<f64>, (), (), (), f64>, Option<KV>), Error> {
+            self.attempts.set(self.attempts.get() + 1);
+            if self.fail_on.contains(&self.attempts.get()) {
+                return Err(argmin_error!(ConditionViolated, "search failed"));
+            }
+            let param = state.take_param().unwrap();
+            let param = param.add(&self.direction.mul(&0.25));
+            let cost = problem.cost(&param)?;
+            Ok((
+                state
+                    .param(param)
+                    .cost(cost)
+                    .terminate_with(TerminationReason::SolverConverged),
+                None,
+            ))
+        }
+    }
+
+    struct Quadratic {}
+
+    impl CostFunction for Quadratic {
+        type Param = Vec<f64>;
+        type Output = f64;
+
+        fn cost(&self, p: &Self::Param) -> Result<Self::Output, Error> {
+            Ok(p.iter().map(|x| x * x).sum())
+        }
+    }
+
+    #[test]
+    fn test_default() {
+        assert_eq!(
+            LineSearchRecovery::<f64>::default(),
+            LineSearchRecovery::Stop
+        );
+    }
+
+    #[test]
+    fn test_backtracking() {
+        let mut problem = Problem::new(Quadratic {});
+        let param = vec![1.0f64, 1.0];
+        let gradient = vec![2.0f64, 2.0];
+        // A step length of 1 overshoots to `[-9, -9]`.
+        let direction = vec![-10.0f64, -10.0];
+        let (new_param, cost) = LineSearchRecovery::Backtracking
+            .fallback_step(&mut problem, &param, 2.0, &gradient, &direction)
+            .unwrap()
+            .unwrap();
+        assert_eq!(new_param, vec![-0.25, -0.25]);
+        assert_eq!(cost.to_ne_bytes(), 0.125f64.to_ne_bytes());
+        assert_eq!(problem.counts["cost_count"], 4);
+    }
+
+    #[test]
+    fn test_backtracking_no_descent_direction() {
+        let mut problem = Problem::new(Quadratic {});
+        let res = LineSearchRecovery::Backtracking
+            .fallback_step(
+                &mut problem,
+                &vec![1.0f64, 1.0],
+                2.0,
+                &vec![2.0f64, 2.0],
+                &vec![1.0f64, 1.0],
+            )
+            .unwrap();
+        assert!(res.is_none());
+        assert!(problem.counts.is_empty());
+    }
+
+    #[test]
+    fn test_small_step() {
+        let mut problem = Problem::new(Quadratic {});
+        let param = vec![1.0f64, 1.0];
+        let gradient = vec![2.0f64, 2.0];
+        let direction = vec![-10.0f64, -10.0];
+        let (new_param, cost) = LineSearchRecovery::SmallStep(0.05)
+            .fallback_step(&mut problem, &param, 2.0, &gradient, &direction)
+            .unwrap()
+            .unwrap();
+        assert_eq!(new_param, vec![0.5, 0.5]);
+        assert_eq!(cost.to_ne_bytes(), 0.5f64.to_ne_bytes());
+
+        // Steps which do not decrease the cost function are rejected
+        let res = LineSearchRecovery::SmallStep(0.5)
+            .fallback_step(&mut problem, &param, 2.0, &gradient, &direction)
+            .unwrap();
+        assert!(res.is_none());
+    }
+
+    #[test]
+    fn test_no_fallback_step() {
+        let mut problem = Problem::new(TestProblem::new());
+        for policy in [LineSearchRecovery::Stop, LineSearchRecovery::ResetMemory] {
+            let res = policy
+                .fallback_step(
+                    &mut problem,
+                    &vec![1.0f64, 1.0],
+                    2.0,
+                    &vec![2.0f64, 2.0],
+                    &vec![-1.0f64, -1.0],
+                )
+                .unwrap();
+            assert!(res.is_none());
+        }
+        assert!(problem.counts.is_empty());
+    }
+}
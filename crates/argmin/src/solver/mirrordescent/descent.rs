@@ -0,0 +1,471 @@
+// Copyright 2018-2024 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use super::MirrorMap;
+use crate::core::{
+    ArgminFloat, CostFunction, Error, Gradient, IterState, Problem, Solver, State, KV,
+};
+#[cfg(feature = "serde1")]
+use serde::{Deserialize, Serialize};
+
+/// Step size schedule of [`MirrorDescent`]
+///
+/// The step size in iteration `k` (starting at `k = 0`) is derived from the initial step size
+/// `eta`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+pub enum StepSizeSchedule<F> {
+    /// Constant step size `eta`
+    Constant(F),
+    /// Step size `eta / sqrt(k + 1)`, suited for convex Lipschitz continuous (online) problems
+    InverseSqrt(F),
+    /// Step size `eta / (k + 1)`, suited for strongly convex problems
+    Inverse(F),
+}
+
+impl<F: ArgminFloat> StepSizeSchedule<F> {
+    /// Returns the step size in iteration `iter`
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::mirrordescent::StepSizeSchedule;
+    /// let schedule = StepSizeSchedule::InverseSqrt(1.0f64);
+    /// # assert_eq!(schedule.step_size(3).to_ne_bytes(), 0.5f64.to_ne_bytes());
+    /// let step_size = schedule.step_size(3);
+    /// ```
+    pub fn step_size(&self, iter: u64) -> F {
+        let k = F::from_u64(iter).unwrap() + float!(1.0);
+        match *self {
+            StepSizeSchedule::Constant(eta) => eta,
+            StepSizeSchedule::InverseSqrt(eta) => eta / k.sqrt(),
+            StepSizeSchedule::Inverse(eta) => eta / k,
+        }
+    }
+
+    fn initial(&self) -> F {
+        match *self {
+            StepSizeSchedule::Constant(eta)
+            | StepSizeSchedule::InverseSqrt(eta)
+            | StepSizeSchedule::Inverse(eta) => eta,
+        }
+    }
+}
+
+impl<F: ArgminFloat> Default for StepSizeSchedule<F> {
+    fn default() -> Self {
+        StepSizeSchedule::InverseSqrt(float!(1.0))
+    }
+}
+
+/// # Mirror descent
+///
+/// In each iteration, the next iterate is obtained from the gradient `g_k` at the current
+/// iterate `x_k` as
+///
+/// `x_{k+1} = argmin_x { eta_k * g_k^T x + D(x, x_k) }`
+///
+/// where `D` is the Bregman divergence induced by a [`MirrorMap`] and `eta_k` is given by a
+/// [`StepSizeSchedule`] (default: `1/sqrt(k + 1)`). With the [`Euclidean`](`super::Euclidean`)
+/// mirror map this is gradient descent; with [`NegativeEntropy`](`super::NegativeEntropy`) the
+/// iterates stay on the probability simplex, which makes the method well suited for
+/// simplex-constrained and online problems such as learning with expert advice.
+///
+/// The cost is evaluated at the initial parameter vector and at every new iterate. Since mirror
+/// descent is not a descent method, the best parameter vector found so far is tracked via the
+/// state.
+///
+/// ## Requirements on the optimization problem
+///
+/// The optimization problem is required to implement [`CostFunction`] and [`Gradient`].
+///
+/// ## References
+///
+/// Arkadi Nemirovski and David Yudin (1983). Problem Complexity and Method Efficiency in
+/// Optimization. Wiley.
+///
+/// Amir Beck and Marc Teboulle (2003). Mirror descent and nonlinear projected subgradient methods
+/// for convex optimization. Operations Research Letters 31(3), 167-175.
+#[derive(Clone)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+pub struct MirrorDescent<M, F> {
+    /// Mirror map
+    mirror_map: M,
+    /// Step size schedule
+    step_size: StepSizeSchedule<F>,
+}
+
+impl<M, F> MirrorDescent<M, F>
+where
+    F: ArgminFloat,
+{
+    /// Construct a new instance of [`MirrorDescent`]
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::mirrordescent::{MirrorDescent, NegativeEntropy};
+    /// let md: MirrorDescent<_, f64> = MirrorDescent::new(NegativeEntropy);
+    /// ```
+    pub fn new(mirror_map: M) -> Self {
+        MirrorDescent {
+            mirror_map,
+            step_size: StepSizeSchedule::default(),
+        }
+    }
+
+    /// Set the step size schedule
+    ///
+    /// The initial step size must be larger than 0. Defaults to
+    /// [`StepSizeSchedule::InverseSqrt`] with an initial step size of 1.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::mirrordescent::{MirrorDescent, NegativeEntropy, StepSizeSchedule};
+    /// # use argmin::core::Error;
+    /// # fn main() -> Result<(), Error> {
+    /// let md: MirrorDescent<_, f64> = MirrorDescent::new(NegativeEntropy)
+    ///     .with_step_size(StepSizeSchedule::Constant(0.1))?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_step_size(mut self, step_size: StepSizeSchedule<F>) -> Result<Self, Error> {
+        if step_size.initial() <= float!(0.0) {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "`MirrorDescent`: step size must be > 0."
+            ));
+        }
+        self.step_size = step_size;
+        Ok(self)
+    }
+}
+
+/// Serializable configuration of [`MirrorDescent`]
+///
+/// Options which are `None` keep the defaults of [`MirrorDescent`]. The configuration is
+/// validated when the solver is constructed via [`build`](`MirrorDescentConfig::build`).
+///
+/// # Example
+///
+/// ```
+/// # use argmin::solver::mirrordescent::{
+/// #     MirrorDescent, MirrorDescentConfig, NegativeEntropy, StepSizeSchedule,
+/// # };
+/// # use argmin::core::Error;
+/// # fn main() -> Result<(), Error> {
+/// let config = MirrorDescentConfig {
+///     step_size: Some(StepSizeSchedule::Inverse(2.0f64)),
+/// };
+/// let md = config.build(NegativeEntropy)?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize), serde(default))]
+pub struct MirrorDescentConfig<F> {
+    /// See [`MirrorDescent::with_step_size`]
+    pub step_size: Option<StepSizeSchedule<F>>,
+}
+
+impl<F> MirrorDescentConfig<F>
+where
+    F: ArgminFloat,
+{
+    /// Validates the configuration and constructs a [`MirrorDescent`] solver with the given
+    /// mirror map.
+    pub fn build<M>(&self, mirror_map: M) -> Result<MirrorDescent<M, F>, Error> {
+        let mut solver = MirrorDescent::new(mirror_map);
+        if let Some(step_size) = self.step_size {
+            solver = solver.with_step_size(step_size)?;
+        }
+        Ok(solver)
+    }
+}
+
+impl<O, M, P, G, F> Solver<O, IterState<P, G, (), (), (), F>> for MirrorDescent<M, F>
+where
+    O: CostFunction<Param = P, Output = F> + Gradient<Param = P, Gradient = G>,
+    M: MirrorMap<P, G, F>,
+    P: Clone,
+    F: ArgminFloat,
+{
+    fn name(&self) -> &str {
+        "Mirror Descent"
+    }
+
+    fn init(
+        &mut self,
+        problem: &mut Problem<O>,
+        state: IterState<P, G, (), (), (), F>,
+    ) -> Result<(IterState<P, G, (), (), (), F>, Option<KV>), Error> {
+        let param = state.get_param().ok_or_else(argmin_error_closure!(
+            NotInitialized,
+            concat!(
+                "`MirrorDescent` requires an initial parameter vector. ",
+                "Please provide an initial guess via `Executor`s `configure` method."
+            )
+        ))?;
+        let cost = problem.cost(param)?;
+        Ok((
+            state.cost(cost),
+            Some(
+                kv!(
+                    "mirror_map" => std::any::type_name::<M>();
+                    "step_size" => format!("{:?}", self.step_size);
+                )
+                .with_prefix("config"),
+            ),
+        ))
+    }
+
+    fn next_iter(
+        &mut self,
+        problem: &mut Problem<O>,
+        mut state: IterState<P, G, (), (), (), F>,
+    ) -> Result<(IterState<P, G, (), (), (), F>, Option<KV>), Error> {
+        let param = state.take_param().ok_or_else(argmin_error_closure!(
+            PotentialBug,
+            "`MirrorDescent`: Parameter vector in `state` not set"
+        ))?;
+        let grad = problem.gradient(&param)?;
+        let step_size = self.step_size.step_size(state.get_iter());
+        let new_param = self.mirror_map.step(&param, &grad, step_size)?;
+        let cost = problem.cost(&new_param)?;
+        Ok((
+            state.param(new_param).gradient(grad).cost(cost),
+            Some(kv!("step_size" => step_size;)),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::{test_utils::TestProblem, ArgminError, Executor};
+    use crate::solver::mirrordescent::{Euclidean, NegativeEntropy};
+    use approx::assert_relative_eq;
+
+    test_trait_impl!(mirror_descent, MirrorDescent<NegativeEntropy, f64>);
+
+    /// `f(x) = 0.5 * ||x - c||^2`
+    struct Distance {
+        c: Vec<f64>,
+    }
+
+    impl CostFunction for Distance {
+        type Param = Vec<f64>;
+        type Output = f64;
+
+        fn cost(&self, p: &Self::Param) -> Result<Self::Output, Error> {
+            Ok(0.5
+                * p.iter()
+                    .zip(self.c.iter())
+                    .map(|(x, c)| (x - c).powi(2))
+                    .sum::<f64>())
+        }
+    }
+
+    impl Gradient for Distance {
+        type Param = Vec<f64>;
+        type Gradient = Vec<f64>;
+
+        fn gradient(&self, p: &Self::Param) -> Result<Self::Gradient, Error> {
+            Ok(p.iter().zip(self.c.iter()).map(|(x, c)| x - c).collect())
+        }
+    }
+
+    /// Linear cost `f(x) = c^T x`
+    struct Linear {
+        c: Vec<f64>,
+    }
+
+    impl CostFunction for Linear {
+        type Param = Vec<f64>;
+        type Output = f64;
+
+        fn cost(&self, p: &Self::Param) -> Result<Self::Output, Error> {
+            Ok(p.iter().zip(self.c.iter()).map(|(x, c)| x * c).sum())
+        }
+    }
+
+    impl Gradient for Linear {
+        type Param = Vec<f64>;
+        type Gradient = Vec<f64>;
+
+        fn gradient(&self, _p: &Self::Param) -> Result<Self::Gradient, Error> {
+            Ok(self.c.clone())
+        }
+    }
+
+    #[test]
+    fn test_new() {
+        let md: MirrorDescent<_, f64> = MirrorDescent::new(NegativeEntropy);
+        let MirrorDescent {
+            mirror_map,
+            step_size,
+        } = md;
+        assert_eq!(mirror_map, NegativeEntropy);
+        assert_eq!(step_size, StepSizeSchedule::InverseSqrt(1.0));
+    }
+
+    #[test]
+    fn test_step_size_schedule() {
+        let eta = 2.0f64;
+        for k in 0..5u64 {
+            let kf = k as f64 + 1.0;
+            assert_relative_eq!(StepSizeSchedule::Constant(eta).step_size(k), eta);
+            assert_relative_eq!(
+                StepSizeSchedule::InverseSqrt(eta).step_size(k),
+                eta / kf.sqrt()
+            );
+            assert_relative_eq!(StepSizeSchedule::Inverse(eta).step_size(k), eta / kf);
+        }
+    }
+
+    #[test]
+    fn test_with_step_size() {
+        let md: MirrorDescent<_, f64> = MirrorDescent::new(Euclidean)
+            .with_step_size(StepSizeSchedule::Inverse(0.5))
+            .unwrap();
+        assert_eq!(md.step_size, StepSizeSchedule::Inverse(0.5));
+
+        for step_size in [
+            StepSizeSchedule::Constant(0.0),
+            StepSizeSchedule::InverseSqrt(-1.0),
+            StepSizeSchedule::Inverse(-0.5),
+        ] {
+            let res = MirrorDescent::new(Euclidean).with_step_size(step_size);
+            assert_error!(
+                res,
+                ArgminError,
+                "Invalid parameter: \"`MirrorDescent`: step size must be > 0.\""
+            );
+        }
+    }
+
+    #[test]
+    fn test_config_build() {
+        let config = MirrorDescentConfig {
+            step_size: Some(StepSizeSchedule::Constant(0.1f64)),
+        };
+        let md = config.build(Euclidean).unwrap();
+        assert_eq!(md.step_size, StepSizeSchedule::Constant(0.1));
+
+        let config = MirrorDescentConfig {
+            step_size: Some(StepSizeSchedule::Constant(-0.1f64)),
+        };
+        assert!(config.build(Euclidean).is_err());
+    }
+
+    #[test]
+    fn test_init_param_not_initialized() {
+        let mut md: MirrorDescent<_, f64> = MirrorDescent::new(Euclidean);
+        let res = md.init(&mut Problem::new(TestProblem::new()), IterState::new());
+        assert_error!(
+            res,
+            ArgminError,
+            concat!(
+                "Not initialized: \"`MirrorDescent` requires an initial parameter vector. ",
+                "Please provide an initial guess via `Executor`s `configure` method.\""
+            )
+        );
+    }
+
+    #[test]
+    fn test_next_iter_param_not_initialized() {
+        let mut md: MirrorDescent<_, f64> = MirrorDescent::new(Euclidean);
+        let res = md.next_iter(&mut Problem::new(TestProblem::new()), IterState::new());
+        assert_error!(
+            res,
+            ArgminError,
+            concat!(
+                "Potential bug: \"`MirrorDescent`: Parameter vector in `state` not set\". ",
+                "This is potentially a bug. ",
+                "Please file a report on https://github.com/argmin-rs/argmin/issues"
+            )
+        );
+    }
+
+    #[test]
+    fn test_euclidean() {
+        let res = Executor::new(
+            Distance {
+                c: vec![1.0, -2.0, 3.0],
+            },
+            MirrorDescent::new(Euclidean)
+                .with_step_size(StepSizeSchedule::Constant(0.5))
+                .unwrap(),
+        )
+        .configure(|state| state.param(vec![0.0; 3]).max_iters(60))
+        .run()
+        .unwrap();
+        let param = res.state.get_best_param().unwrap();
+        assert_relative_eq!(param[0], 1.0, epsilon = 1e-12);
+        assert_relative_eq!(param[1], -2.0, epsilon = 1e-12);
+        assert_relative_eq!(param[2], 3.0, epsilon = 1e-12);
+    }
+
+    #[test]
+    fn test_entropic_simplex() {
+        // The minimizer lies in the interior of the simplex
+        let c = vec![0.5, 0.3, 0.2];
+        let res = Executor::new(
+            Distance { c: c.clone() },
+            MirrorDescent::new(NegativeEntropy)
+                .with_step_size(StepSizeSchedule::Constant(1.0))
+                .unwrap(),
+        )
+        .configure(|state| state.param(vec![1.0 / 3.0; 3]).max_iters(200))
+        .run()
+        .unwrap();
+        let param = res.state.get_best_param().unwrap();
+        assert_relative_eq!(param.iter().sum::<f64>(), 1.0, epsilon = 1e-12);
+        for (x, c) in param.iter().zip(c.iter()) {
+            assert_relative_eq!(x, c, epsilon = 1e-6);
+        }
+    }
+
+    #[test]
+    fn test_entropic_vertex() {
+        // Minimizing a linear function over the simplex concentrates the mass on the smallest
+        // entry of `c`.
+        let res = Executor::new(
+            Linear {
+                c: vec![3.0, 1.0, 2.0, 4.0],
+            },
+            MirrorDescent::new(NegativeEntropy),
+        )
+        .configure(|state| state.param(vec![0.25; 4]).max_iters(500))
+        .run()
+        .unwrap();
+        let param = res.state.get_param().unwrap();
+        assert!(param.iter().all(|x| *x >= 0.0));
+        assert_relative_eq!(param.iter().sum::<f64>(), 1.0, epsilon = 1e-12);
+        assert!(param[1] > 0.99);
+        assert_relative_eq!(res.state.get_best_cost(), 1.0, epsilon = 1e-2);
+    }
+
+    #[test]
+    fn test_kv() {
+        let mut problem = Problem::new(Linear { c: vec![1.0, 2.0] });
+        let mut md = MirrorDescent::new(NegativeEntropy)
+            .with_step_size(StepSizeSchedule::Inverse(2.0))
+            .unwrap();
+        let (state, _) = md
+            .init(&mut problem, IterState::new().param(vec![0.5, 0.5]))
+            .unwrap();
+        assert_relative_eq!(state.get_cost(), 1.5);
+        let (state, kv) = md.next_iter(&mut problem, state).unwrap();
+        assert_eq!(kv.unwrap().get("step_size").unwrap().get_float(), Some(2.0));
+        let mut state = state;
+        state.increment_iter();
+        let (_, kv) = md.next_iter(&mut problem, state).unwrap();
+        assert_eq!(kv.unwrap().get("step_size").unwrap().get_float(), Some(1.0));
+    }
+}
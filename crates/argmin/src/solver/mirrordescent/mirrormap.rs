@@ -0,0 +1,161 @@
+// Copyright 2018-2024 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use crate::core::{ArgminFloat, Error};
+use argmin_math::ArgminScaledSub;
+#[cfg(feature = "serde1")]
+use serde::{Deserialize, Serialize};
+
+/// Interface for mirror maps used in [`MirrorDescent`](`super::MirrorDescent`)
+///
+/// A mirror map is a strictly convex function `h` which induces the Bregman divergence
+/// `D(x, y) = h(x) - h(y) - \nabla h(y)^T (x - y)`. Implementors compute a single mirror descent
+/// step, i.e. the solution of
+///
+/// `argmin_x { step_size * g^T x + D(x, param) }`
+///
+/// over the domain of `h`. Other divergences can be used by implementing this trait.
+pub trait MirrorMap<P, G, F> {
+    /// Computes the mirror descent step from `param` along the gradient `grad`
+    fn step(&self, param: &P, grad: &G, step_size: F) -> Result<P, Error>;
+}
+
+/// Squared Euclidean distance `D(x, y) = 1/2 ||x - y||^2`
+///
+/// The mirror descent step is the gradient descent step `x - step_size * g`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+pub struct Euclidean;
+
+impl<P, G, F> MirrorMap<P, G, F> for Euclidean
+where
+    P: ArgminScaledSub<G, F, P>,
+{
+    fn step(&self, param: &P, grad: &G, step_size: F) -> Result<P, Error> {
+        Ok(param.scaled_sub(&step_size, grad))
+    }
+}
+
+/// Negative entropy `h(x) = sum_i x_i * ln(x_i)` on the probability simplex
+///
+/// The induced Bregman divergence is the Kullback-Leibler divergence. The mirror descent step
+/// is the exponentiated gradient update
+///
+/// `x_i <- x_i * exp(-step_size * g_i) / sum_j x_j * exp(-step_size * g_j)`
+///
+/// which keeps the iterates on the probability simplex. Entries of the initial parameter vector
+/// must be nonnegative and not all zero; it is normalized by the first step. Entries which are
+/// zero remain zero, therefore the initial parameter vector should lie in the interior of the
+/// simplex, for instance `1/n` for all entries.
+///
+/// Implemented for all parameter vectors and gradients which can be iterated over and collected
+/// from an iterator, such as `Vec<F>`, `ndarray::Array1<F>` or `nalgebra::DVector<F>`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+pub struct NegativeEntropy;
+
+impl<P, G, F> MirrorMap<P, G, F> for NegativeEntropy
+where
+    P: FromIterator<F>,
+    for<'a> &'a P: IntoIterator<Item = &'a F>,
+    for<'a> &'a G: IntoIterator<Item = &'a F>,
+    F: ArgminFloat,
+{
+    fn step(&self, param: &P, grad: &G, step_size: F) -> Result<P, Error> {
+        if param.into_iter().any(|x| *x < float!(0.0) || x.is_nan()) {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "`NegativeEntropy`: parameter vector must be nonnegative."
+            ));
+        }
+        // The update is computed in log space and shifted by the maximum exponent to avoid
+        // overflow for large steps.
+        let exponents: Vec<F> = param
+            .into_iter()
+            .zip(grad)
+            .map(|(x, g)| x.ln() - step_size * *g)
+            .collect();
+        let max = exponents
+            .iter()
+            .fold(F::neg_infinity(), |max, e| if *e > max { *e } else { max });
+        if !max.is_finite() {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "`NegativeEntropy`: parameter vector must not be zero."
+            ));
+        }
+        let weights: Vec<F> = exponents.iter().map(|e| (*e - max).exp()).collect();
+        let sum = weights.iter().fold(float!(0.0), |acc, w| acc + *w);
+        Ok(weights.into_iter().map(|w| w / sum).collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::ArgminError;
+    use approx::assert_relative_eq;
+
+    test_trait_impl!(euclidean, Euclidean);
+    test_trait_impl!(negative_entropy, NegativeEntropy);
+
+    #[test]
+    fn test_euclidean_step() {
+        let x: Vec<f64> = Euclidean
+            .step(&vec![1.0, 2.0], &vec![2.0, -1.0], 0.5)
+            .unwrap();
+        assert_relative_eq!(x[0], 0.0);
+        assert_relative_eq!(x[1], 2.5);
+    }
+
+    #[test]
+    fn test_negative_entropy_step() {
+        let x: Vec<f64> = NegativeEntropy
+            .step(&vec![0.5, 0.25, 0.25], &vec![0.0, 1.0, 2.0], 2.0f64.ln())
+            .unwrap();
+        // Weights 0.5, 0.125, 0.0625 normalized by 0.6875
+        assert_relative_eq!(x[0], 0.5 / 0.6875);
+        assert_relative_eq!(x[1], 0.125 / 0.6875);
+        assert_relative_eq!(x[2], 0.0625 / 0.6875);
+        assert_relative_eq!(x.iter().sum::<f64>(), 1.0);
+    }
+
+    #[test]
+    fn test_negative_entropy_normalizes() {
+        let x: Vec<f64> = NegativeEntropy
+            .step(&vec![2.0, 0.0, 6.0], &vec![0.0; 3], 1.0)
+            .unwrap();
+        assert_relative_eq!(x[0], 0.25);
+        assert_relative_eq!(x[1], 0.0);
+        assert_relative_eq!(x[2], 0.75);
+    }
+
+    #[test]
+    fn test_negative_entropy_large_step() {
+        let x: Vec<f64> = NegativeEntropy
+            .step(&vec![0.5, 0.5], &vec![1e6, 0.0], 1e3)
+            .unwrap();
+        assert_relative_eq!(x[0], 0.0);
+        assert_relative_eq!(x[1], 1.0);
+    }
+
+    #[test]
+    fn test_negative_entropy_invalid_param() {
+        let res: Result<Vec<f64>, _> = NegativeEntropy.step(&vec![-0.5, 1.5], &vec![0.0; 2], 1.0);
+        assert_error!(
+            res,
+            ArgminError,
+            "Invalid parameter: \"`NegativeEntropy`: parameter vector must be nonnegative.\""
+        );
+        let res: Result<Vec<f64>, _> = NegativeEntropy.step(&vec![0.0, 0.0], &vec![0.0; 2], 1.0);
+        assert_error!(
+            res,
+            ArgminError,
+            "Invalid parameter: \"`NegativeEntropy`: parameter vector must not be zero.\""
+        );
+    }
+}
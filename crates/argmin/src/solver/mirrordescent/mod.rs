@@ -0,0 +1,32 @@
+// Copyright 2018-2024 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+//! # Mirror descent
+//!
+//! [`MirrorDescent`] generalizes gradient descent by replacing the squared Euclidean distance
+//! in the proximal formulation of a gradient step by a Bregman divergence. The divergence is
+//! defined via a [`MirrorMap`]:
+//!
+//! * [`Euclidean`]: Squared Euclidean distance; mirror descent reduces to gradient descent.
+//! * [`NegativeEntropy`]: Kullback-Leibler divergence on the probability simplex; mirror descent
+//!   becomes the exponentiated gradient (multiplicative weights) method.
+//!
+//! The step size follows a [`StepSizeSchedule`].
+//!
+//! ## References
+//!
+//! Arkadi Nemirovski and David Yudin (1983). Problem Complexity and Method Efficiency in
+//! Optimization. Wiley.
+//!
+//! Amir Beck and Marc Teboulle (2003). Mirror descent and nonlinear projected subgradient methods
+//! for convex optimization. Operations Research Letters 31(3), 167-175.
+
+mod descent;
+mod mirrormap;
+
+pub use self::descent::{MirrorDescent, MirrorDescentConfig, StepSizeSchedule};
+pub use self::mirrormap::{Euclidean, MirrorMap, NegativeEntropy};
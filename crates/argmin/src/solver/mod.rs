@@ -8,13 +8,20 @@
 pub mod brent;
 pub mod conjugategradient;
 pub mod gaussnewton;
+pub mod globalopt;
 pub mod goldensectionsearch;
 pub mod gradientdescent;
+#[cfg(feature = "inari")]
+pub mod intervalbb;
+pub mod krylov;
 pub mod landweber;
 pub mod linesearch;
+pub mod mirrordescent;
 pub mod neldermead;
 pub mod newton;
 pub mod particleswarm;
 pub mod quasinewton;
 pub mod simulatedannealing;
+pub mod stochastic;
+pub mod tabusearch;
 pub mod trustregion;
@@ -19,10 +19,12 @@
 //! <http://www.scholarpedia.org/article/Nelder-Mead_algorithm#Simplex_transformation_algorithm>
 
 use crate::core::{
-    ArgminFloat, CostFunction, Error, IterState, Problem, Solver, TerminationReason,
-    TerminationStatus, KV,
+    ArgminFloat, CostFunction, Error, IterState, Problem, Solver, State, TerminationReason,
+    TerminationStatus, Workspace, KV,
 };
-use argmin_math::{ArgminAdd, ArgminMul, ArgminSub};
+use argmin_math::{ArgminAdd, ArgminAxpy, ArgminMul, ArgminSub};
+use rand::SeedableRng;
+use rand_xoshiro::Xoshiro256PlusPlus;
 #[cfg(feature = "serde1")]
 use serde::{Deserialize, Serialize};
 use std::fmt;
@@ -49,6 +51,9 @@ use std::fmt;
 /// 4) Shrink (Parameter `sigma`, defaults to `0.5`, configurable via
 ///    [`with_sigma`](`NelderMead::with_sigma`))
 ///
+/// Instead of providing the initial simplex, it can be generated randomly within the bounds set
+/// on the state via [`from_bounds`](`NelderMead::from_bounds`).
+///
 /// ## Requirements on the optimization problem
 ///
 /// The optimization problem is required to implement [`CostFunction`].
@@ -73,6 +78,12 @@ pub struct NelderMead<P, F> {
     params: Vec<(P, F)>,
     /// Sample standard deviation tolerance
     sd_tolerance: F,
+    /// Number of vertices and seed of a simplex generated from the bounds of the state
+    #[cfg_attr(feature = "serde1", serde(default))]
+    random_simplex: Option<(usize, u64)>,
+    /// Scratch buffers for the centroid and shrinking
+    #[cfg_attr(feature = "serde1", serde(skip))]
+    workspace: Workspace<P>,
 }
 
 impl<P, F> NelderMead<P, F>
@@ -100,9 +111,47 @@ where
             sigma: float!(0.5),
             params: params.into_iter().map(|p| (p, F::nan())).collect(),
             sd_tolerance: F::epsilon(),
+            random_simplex: None,
+            workspace: Workspace::new(),
         }
     }
 
+    /// Construct a new instance of `NelderMead` with a random initial simplex
+    ///
+    /// The initial simplex is generated at initialization from the bounds set on the state via
+    /// [`IterState::bounds`]: A point is drawn uniformly from the bounds and the remaining vertices
+    /// are placed around it at a tenth of the distance to further random points within the bounds.
+    /// `num_vertices` must be `n + 1` where `n` is the number of optimization parameters. The
+    /// random number generator is seeded with `seed`, which makes runs reproducible.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::neldermead::NelderMead;
+    /// # use argmin::core::{Error, Executor, IterState};
+    /// # use argmin::core::test_utils::TestProblem;
+    /// # fn main() -> Result<(), Error> {
+    /// let nm: NelderMead<Vec<f64>, f64> = NelderMead::from_bounds(3, 42)?;
+    /// let res = Executor::new(TestProblem::new(), nm)
+    ///     .configure(|state: IterState<Vec<f64>, (), (), (), (), f64>| {
+    ///         state.bounds(vec![-1.0, -1.0], vec![1.0, 1.0]).max_iters(10)
+    ///     })
+    ///     .run()?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn from_bounds(num_vertices: usize, seed: u64) -> Result<Self, Error> {
+        if num_vertices < 2 {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "`Nelder-Mead`: initial simplex must consist of at least 2 vertices."
+            ));
+        }
+        let mut nm = NelderMead::new(vec![]);
+        nm.random_simplex = Some((num_vertices, seed));
+        Ok(nm)
+    }
+
     /// Set sample standard deviation tolerance
     ///
     /// Must be non-negative and defaults to `EPSILON`.
@@ -245,9 +294,14 @@ where
     }
 
     /// Calculate centroid of all vectors but the worst
-    fn calculate_centroid(&self) -> P {
+    fn calculate_centroid(&mut self) -> P
+    where
+        P: ArgminAxpy<P, F>,
+    {
         // Number of parameters is number of parameter vectors minus 1
         let num_param = self.params.len() - 1;
+        // The sum is accumulated in place, starting from a copy of the first vector
+        let mut sum = self.workspace.copy_of(&self.params[0].0);
         self.params
             .iter()
             // Avoid the worst vector
@@ -255,9 +309,11 @@ where
             // First one is used as the accumulator, therefore exclude it from the iterator
             .skip(1)
             // Add all vectors to the first
-            .fold(self.params[0].0.clone(), |acc, p| acc.add(&p.0))
-            // Scale
-            .mul(&(float!(1.0) / (float!(num_param as f64))))
+            .for_each(|p| sum.axpy(&float!(1.0), &p.0));
+        // Scale
+        let centroid = sum.mul(&(float!(1.0) / (float!(num_param as f64))));
+        self.workspace.put(sum);
+        centroid
     }
 
     /// Reflect
@@ -280,8 +336,8 @@ where
     where
         S: FnMut(&P) -> Result<F, Error>,
     {
-        // The best parameter vector unfortunately has to be cloned once.
-        let x0 = self.params[0].0.clone();
+        // The best parameter vector unfortunately has to be copied once.
+        let x0 = self.workspace.copy_of(&self.params[0].0);
         self.params
             .iter_mut()
             // Best one is not modified
@@ -291,6 +347,7 @@ where
                 *c = (cost)(p)?;
                 Ok(())
             })?;
+        self.workspace.put(x0);
         Ok(())
     }
 }
@@ -316,21 +373,128 @@ impl fmt::Display for Action {
     }
 }
 
+/// Serializable configuration of [`NelderMead`]
+///
+/// Options which are `None` keep the defaults of [`NelderMead`]. The configuration is validated
+/// when the solver is constructed via [`build`](`NelderMeadConfig::build`).
+///
+/// # Example
+///
+/// ```
+/// # use argmin::solver::neldermead::{NelderMead, NelderMeadConfig};
+/// # use argmin::core::Error;
+/// # fn main() -> Result<(), Error> {
+/// let config = NelderMeadConfig {
+///     params: vec![vec![1.0f64], vec![2.0]],
+///     sd_tolerance: Some(1e-6),
+///     ..Default::default()
+/// };
+/// let nm: NelderMead<Vec<f64>, f64> = config.build()?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize), serde(default))]
+pub struct NelderMeadConfig<P, F> {
+    /// Vertices of the initial simplex (`n + 1` parameter vectors)
+    pub params: Vec<P>,
+    /// See [`NelderMead::with_sd_tolerance`]
+    pub sd_tolerance: Option<F>,
+    /// See [`NelderMead::with_alpha`]
+    pub alpha: Option<F>,
+    /// See [`NelderMead::with_gamma`]
+    pub gamma: Option<F>,
+    /// See [`NelderMead::with_rho`]
+    pub rho: Option<F>,
+    /// See [`NelderMead::with_sigma`]
+    pub sigma: Option<F>,
+}
+
+impl<P, F> NelderMeadConfig<P, F>
+where
+    P: Clone + ArgminAdd<P, P> + ArgminSub<P, P> + ArgminMul<F, P>,
+    F: ArgminFloat,
+{
+    /// Validates the configuration and constructs a [`NelderMead`] solver.
+    pub fn build(&self) -> Result<NelderMead<P, F>, Error> {
+        if self.params.len() < 2 {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "`Nelder-Mead`: initial simplex must consist of at least 2 vertices."
+            ));
+        }
+        let mut solver = NelderMead::new(self.params.clone());
+        if let Some(tol) = self.sd_tolerance {
+            solver = solver.with_sd_tolerance(tol)?;
+        }
+        if let Some(alpha) = self.alpha {
+            solver = solver.with_alpha(alpha)?;
+        }
+        if let Some(gamma) = self.gamma {
+            solver = solver.with_gamma(gamma)?;
+        }
+        if let Some(rho) = self.rho {
+            solver = solver.with_rho(rho)?;
+        }
+        if let Some(sigma) = self.sigma {
+            solver = solver.with_sigma(sigma)?;
+        }
+        Ok(solver)
+    }
+}
+
 impl<O, P, F> Solver<O, IterState<P, (), (), (), (), F>> for NelderMead<P, F>
 where
     O: CostFunction<Param = P, Output = F>,
-    P: Clone + ArgminSub<P, P> + ArgminAdd<P, P> + ArgminMul<F, P>,
+    P: Clone + ArgminSub<P, P> + ArgminAdd<P, P> + ArgminMul<F, P> + ArgminAxpy<P, F>,
     F: ArgminFloat + std::iter::Sum<F>,
 {
     fn name(&self) -> &str {
         "Nelder-Mead method"
     }
 
+    fn check_state(&self, state: &IterState<P, (), (), (), (), F>) -> Result<(), Error> {
+        if state.get_param().is_some() {
+            return Err(argmin_error!(
+                InvalidParameter,
+                concat!(
+                    "`Nelder-Mead` does not use an initial parameter vector provided via the state. ",
+                    "Please provide the initial simplex via `NelderMead::new` instead."
+                )
+            ));
+        }
+        if self.random_simplex.is_some() && self.params.is_empty() && state.get_bounds().is_none() {
+            return Err(argmin_error!(
+                NotInitialized,
+                concat!(
+                    "`Nelder-Mead` requires bounds to generate the initial simplex. ",
+                    "Please provide them via `Executor`s `configure` method."
+                )
+            ));
+        }
+        Ok(())
+    }
+
     fn init(
         &mut self,
         problem: &mut Problem<O>,
         state: IterState<P, (), (), (), (), F>,
     ) -> Result<(IterState<P, (), (), (), (), F>, Option<KV>), Error> {
+        if let Some((num_vertices, seed)) = self.random_simplex.filter(|_| self.params.is_empty()) {
+            let mut rng = Xoshiro256PlusPlus::seed_from_u64(seed);
+            let bounds_missing = argmin_error_closure!(
+                NotInitialized,
+                "`Nelder-Mead` requires bounds to generate the initial simplex."
+            );
+            let center = state.sample_param(&mut rng).ok_or_else(bounds_missing)?;
+            for _ in 1..num_vertices {
+                let vertex = state.sample_param(&mut rng).ok_or_else(bounds_missing)?;
+                let vertex = center.add(&vertex.sub(&center).mul(&float!(0.1)));
+                self.params.push((vertex, F::nan()));
+            }
+            self.params.insert(0, (center, F::nan()));
+        }
+
         self.params
             .iter_mut()
             .for_each(|(p, c)| *c = problem.cost(p).unwrap());
@@ -338,8 +502,18 @@ where
         self.sort_param_vecs();
 
         Ok((
-            state.param(self.params[0].0.clone()).cost(self.params[0].1),
-            None,
+            state.param_from(&self.params[0].0).cost(self.params[0].1),
+            Some(
+                kv!(
+                    "alpha" => self.alpha;
+                    "gamma" => self.gamma;
+                    "rho" => self.rho;
+                    "sigma" => self.sigma;
+                    "sd_tolerance" => self.sd_tolerance;
+                    "num_vertices" => self.params.len() as u64;
+                )
+                .with_prefix("config"),
+            ),
         ))
     }
 
@@ -410,7 +584,7 @@ where
         self.sort_param_vecs();
 
         Ok((
-            state.param(self.params[0].0.clone()).cost(self.params[0].1),
+            state.param_from(&self.params[0].0).cost(self.params[0].1),
             Some(kv!("action" => format!("{action}");)),
         ))
     }
@@ -463,6 +637,8 @@ mod tests {
             sigma,
             params,
             sd_tolerance,
+            random_simplex,
+            workspace,
         } = nm;
 
         assert_eq!(alpha.to_ne_bytes(), 1.0f64.to_ne_bytes());
@@ -474,6 +650,22 @@ mod tests {
         assert_eq!(params[0].1.to_ne_bytes(), f64::NAN.to_ne_bytes());
         assert_eq!(params[1].1.to_ne_bytes(), f64::NAN.to_ne_bytes());
         assert_eq!(sd_tolerance.to_ne_bytes(), f64::EPSILON.to_ne_bytes());
+        assert!(random_simplex.is_none());
+        assert!(workspace.is_empty());
+    }
+
+    #[test]
+    fn test_from_bounds() {
+        let nm: NelderMead<Vec<f64>, f64> = NelderMead::from_bounds(3, 42).unwrap();
+        assert!(nm.params.is_empty());
+        assert_eq!(nm.random_simplex, Some((3, 42)));
+
+        let res: Result<NelderMead<Vec<f64>, f64>, _> = NelderMead::from_bounds(1, 42);
+        assert_error!(
+            res,
+            ArgminError,
+            "Invalid parameter: \"`Nelder-Mead`: initial simplex must consist of at least 2 vertices.\""
+        );
     }
 
     #[test]
@@ -592,6 +784,47 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_config_build() {
+        let config = NelderMeadConfig {
+            params: vec![vec![1.0f64], vec![2.0]],
+            sd_tolerance: Some(1e-6),
+            alpha: Some(0.9),
+            gamma: Some(1.9),
+            rho: Some(0.4),
+            sigma: Some(0.3),
+        };
+        let nm: NelderMead<Vec<f64>, f64> = config.build().unwrap();
+        assert_eq!(nm.params.len(), 2);
+        assert_eq!(nm.sd_tolerance.to_ne_bytes(), 1e-6f64.to_ne_bytes());
+        assert_eq!(nm.alpha.to_ne_bytes(), 0.9f64.to_ne_bytes());
+        assert_eq!(nm.gamma.to_ne_bytes(), 1.9f64.to_ne_bytes());
+        assert_eq!(nm.rho.to_ne_bytes(), 0.4f64.to_ne_bytes());
+        assert_eq!(nm.sigma.to_ne_bytes(), 0.3f64.to_ne_bytes());
+
+        let res: Result<NelderMead<Vec<f64>, f64>, _> = NelderMeadConfig {
+            params: vec![vec![1.0f64]],
+            ..Default::default()
+        }
+        .build();
+        assert_error!(
+            res,
+            ArgminError,
+            "Invalid parameter: \"`Nelder-Mead`: initial simplex must consist of at least 2 vertices.\""
+        );
+
+        let res: Result<NelderMead<Vec<f64>, f64>, _> = NelderMeadConfig {
+            rho: Some(0.6),
+            ..config
+        }
+        .build();
+        assert_error!(
+            res,
+            ArgminError,
+            "Invalid parameter: \"`Nelder-Mead`: rho must be in (0, 0.5].\""
+        );
+    }
+
     #[test]
     fn test_sort_param_vecs() {
         let params: Vec<Vec<f64>> = vec![vec![2.0], vec![1.0], vec![3.0]];
@@ -695,7 +928,18 @@ mod tests {
         let problem = MwProblem {};
         let (state_out, kv) = nm.init(&mut Problem::new(problem), state).unwrap();
 
-        assert!(kv.is_none());
+        assert_eq!(
+            kv.unwrap(),
+            kv!(
+                "alpha" => 1.0f64;
+                "gamma" => 2.0f64;
+                "rho" => 0.5f64;
+                "sigma" => 0.5f64;
+                "sd_tolerance" => f64::EPSILON;
+                "num_vertices" => 3u64;
+            )
+            .with_prefix("config")
+        );
 
         for ((p, c), (ps, cs)) in nm.params.iter().zip(params_sorted.iter()) {
             assert_relative_eq!(c, cs, epsilon = f64::EPSILON);
@@ -718,6 +962,66 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_check_state() {
+        let nm: NelderMead<Vec<f64>, f64> =
+            NelderMead::new(vec![vec![-1.0, 1.0], vec![-0.5, 2.0], vec![0.7, -1.0]]);
+        let state: IterState<Vec<f64>, (), (), (), (), f64> = IterState::new();
+        assert!(<NelderMead<_, _> as Solver<MwProblem, _>>::check_state(&nm, &state).is_ok());
+
+        let state = state.param(vec![1.0, 1.0]);
+        let res = <NelderMead<_, _> as Solver<MwProblem, _>>::check_state(&nm, &state);
+        assert_error!(
+            res,
+            ArgminError,
+            concat!(
+                "Invalid parameter: \"`Nelder-Mead` does not use an initial parameter vector ",
+                "provided via the state. Please provide the initial simplex via `NelderMead::new` ",
+                "instead.\""
+            )
+        );
+
+        let nm: NelderMead<Vec<f64>, f64> = NelderMead::from_bounds(3, 42).unwrap();
+        let state: IterState<Vec<f64>, (), (), (), (), f64> = IterState::new();
+        let res = <NelderMead<_, _> as Solver<MwProblem, _>>::check_state(&nm, &state);
+        assert_error!(
+            res,
+            ArgminError,
+            concat!(
+                "Not initialized: \"`Nelder-Mead` requires bounds to generate the initial simplex. ",
+                "Please provide them via `Executor`s `configure` method.\""
+            )
+        );
+
+        let state = state.bounds(vec![-1.0, -1.0], vec![1.0, 1.0]);
+        assert!(<NelderMead<_, _> as Solver<MwProblem, _>>::check_state(&nm, &state).is_ok());
+    }
+
+    #[test]
+    fn test_init_from_bounds() {
+        let lower = vec![-1.0, 2.0];
+        let upper = vec![1.0, 3.0];
+        let mut nm: NelderMead<Vec<f64>, f64> = NelderMead::from_bounds(3, 42).unwrap();
+        let state: IterState<Vec<f64>, (), (), (), (), f64> =
+            IterState::new().bounds(lower.clone(), upper.clone());
+        let (state_out, _) = nm.init(&mut Problem::new(MwProblem {}), state).unwrap();
+
+        assert_eq!(nm.params.len(), 3);
+        for (p, c) in nm.params.iter() {
+            for i in 0..2 {
+                assert!(p[i] >= lower[i] && p[i] <= upper[i]);
+            }
+            assert_relative_eq!(*c, MwProblem {}.cost(p).unwrap(), epsilon = f64::EPSILON);
+        }
+        assert_eq!(state_out.get_param().unwrap(), &nm.params[0].0);
+
+        // Same seed, same simplex
+        let mut nm2: NelderMead<Vec<f64>, f64> = NelderMead::from_bounds(3, 42).unwrap();
+        let state: IterState<Vec<f64>, (), (), (), (), f64> = IterState::new().bounds(lower, upper);
+        nm2.init(&mut Problem::new(MwProblem {}), state).unwrap();
+        assert_eq!(nm.params, nm2.params);
+    }
+
     #[test]
     fn test_next_iter_reflection() {
         let params: Vec<Vec<f64>> = vec![vec![-1.0, 0.0], vec![-0.1, 0.65], vec![-0.1, -0.95]];
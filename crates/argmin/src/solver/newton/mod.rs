@@ -8,6 +8,7 @@
 //! # Newton Methods
 //!
 //! * [`Newton`]
+//! * [`NewtonLS`]
 //! * [`NewtonCG`]
 //!
 //! # Reference
@@ -17,8 +18,11 @@
 
 /// Newton-CG method
 mod newton_cg;
+/// Newton's method with line search
+mod newton_linesearch;
 /// Newton's method
 mod newton_method;
 
-pub use self::newton_cg::NewtonCG;
-pub use self::newton_method::Newton;
+pub use self::newton_cg::{NewtonCG, NewtonCGConfig};
+pub use self::newton_linesearch::{NewtonLS, NewtonLSConfig};
+pub use self::newton_method::{Newton, NewtonConfig};
@@ -6,8 +6,8 @@
 // copied, modified, or distributed except according to those terms.
 
 use crate::core::{
-    ArgminFloat, Error, Executor, Gradient, Hessian, IterState, LineSearch, Operator,
-    OptimizationResult, Problem, Solver, State, TerminationReason, TerminationStatus, KV,
+    ArgminFloat, Error, Executor, Gradient, Hessian, IterState, LineSearch, NestedTelemetry,
+    Operator, OptimizationResult, Problem, Solver, State, TerminationReason, TerminationStatus, KV,
 };
 use crate::solver::conjugategradient::ConjugateGradient;
 use argmin_math::{
@@ -38,6 +38,9 @@ pub struct NewtonCG<L, F> {
     curvature_threshold: F,
     /// Tolerance for the stopping criterion based on cost difference
     tol: F,
+    /// Telemetry of the line search
+    #[cfg_attr(feature = "serde1", serde(default))]
+    inner_telemetry: NestedTelemetry,
 }
 
 impl<L, F> NewtonCG<L, F>
@@ -58,9 +61,30 @@ where
             linesearch,
             curvature_threshold: float!(0.0),
             tol: F::epsilon(),
+            inner_telemetry: NestedTelemetry::new(),
         }
     }
 
+    /// Forward the telemetry of the line search to observers
+    ///
+    /// The key-value pairs returned by the line search as well as its number of iterations and
+    /// its termination reason are forwarded with keys prefixed by `prefix` (for instance
+    /// `linesearch.iters`). Independent of this setting, the line search iterations are counted
+    /// as `linesearch_iter_count`. See [`NestedTelemetry`] for details.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::newton::NewtonCG;
+    /// # let linesearch = ();
+    /// let solver = NewtonCG::<_, f64>::new(linesearch).with_inner_telemetry("linesearch");
+    /// ```
+    #[must_use]
+    pub fn with_inner_telemetry<T: AsRef<str>>(mut self, prefix: T) -> Self {
+        self.inner_telemetry = NestedTelemetry::forward(prefix);
+        self
+    }
+
     /// Set curvature threshold
     ///
     /// Defaults to 0.
@@ -105,6 +129,53 @@ where
     }
 }
 
+/// Serializable configuration of [`NewtonCG`]
+///
+/// Options which are `None` keep the defaults of [`NewtonCG`]. The configuration is validated when
+/// the solver is constructed via [`build`](`NewtonCGConfig::build`).
+///
+/// # Example
+///
+/// ```
+/// # use argmin::solver::newton::{NewtonCG, NewtonCGConfig};
+/// # use argmin::core::Error;
+/// # fn main() -> Result<(), Error> {
+/// # let linesearch = ();
+/// let config = NewtonCGConfig {
+///     tolerance: Some(1e-6f64),
+///     ..Default::default()
+/// };
+/// let newton_cg: NewtonCG<_, f64> = config.build(linesearch)?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize), serde(default))]
+pub struct NewtonCGConfig<F> {
+    /// See [`NewtonCG::with_curvature_threshold`]
+    pub curvature_threshold: Option<F>,
+    /// See [`NewtonCG::with_tolerance`]
+    pub tolerance: Option<F>,
+}
+
+impl<F> NewtonCGConfig<F>
+where
+    F: ArgminFloat,
+{
+    /// Validates the configuration and constructs a [`NewtonCG`] solver using the given line
+    /// search.
+    pub fn build<L>(&self, linesearch: L) -> Result<NewtonCG<L, F>, Error> {
+        let mut solver = NewtonCG::new(linesearch);
+        if let Some(threshold) = self.curvature_threshold {
+            solver = solver.with_curvature_threshold(threshold);
+        }
+        if let Some(tol) = self.tolerance {
+            solver = solver.with_tolerance(tol)?;
+        }
+        Ok(solver)
+    }
+}
+
 impl<O, L, P, G, H, F> Solver<O, IterState<P, G, (), H, (), F>> for NewtonCG<L, F>
 where
     O: Gradient<Param = P, Gradient = G> + Hessian<Param = P, Hessian = H>,
@@ -124,6 +195,24 @@ where
         "Newton-CG"
     }
 
+    fn init(
+        &mut self,
+        _problem: &mut Problem<O>,
+        state: IterState<P, G, (), H, (), F>,
+    ) -> Result<(IterState<P, G, (), H, (), F>, Option<KV>), Error> {
+        Ok((
+            state,
+            Some(
+                kv!(
+                    "linesearch" => self.linesearch.name();
+                    "curvature_threshold" => self.curvature_threshold;
+                    "tolerance" => self.tol;
+                )
+                .with_prefix("config"),
+            ),
+        ))
+    }
+
     fn next_iter(
         &mut self,
         problem: &mut Problem<O>,
@@ -190,22 +279,30 @@ where
         let line_cost = state.get_cost();
 
         // Run solver
-        let OptimizationResult {
-            problem: line_problem,
-            state: mut linesearch_state,
-            ..
-        } = Executor::new(problem.take_problem().unwrap(), self.linesearch.clone())
+        let (
+            OptimizationResult {
+                problem: line_problem,
+                state: mut linesearch_state,
+                ..
+            },
+            linesearch_kv,
+        ) = Executor::new(problem.take_problem().unwrap(), self.linesearch.clone())
             .configure(|state| state.param(param).gradient(grad).cost(line_cost))
-            .ctrlc(false)
-            .run()?;
+            .run_nested()?;
 
         problem.consume_problem(line_problem);
+        let linesearch_kv = self.inner_telemetry.record(
+            problem,
+            "linesearch_iter_count",
+            &linesearch_state,
+            linesearch_kv,
+        );
 
         Ok((
             state
                 .param(linesearch_state.take_param().unwrap())
                 .cost(linesearch_state.get_cost()),
-            None,
+            linesearch_kv,
         ))
     }
 
@@ -283,7 +380,9 @@ mod tests {
             linesearch,
             curvature_threshold,
             tol,
+            inner_telemetry,
         } = ncg;
+        assert_eq!(inner_telemetry, NestedTelemetry::new());
         assert_eq!(linesearch, ls);
         assert_eq!(curvature_threshold.to_ne_bytes(), 0.0f64.to_ne_bytes());
         assert_eq!(tol.to_ne_bytes(), f64::EPSILON.to_ne_bytes());
@@ -299,7 +398,9 @@ mod tests {
             linesearch,
             curvature_threshold,
             tol,
+            inner_telemetry,
         } = ncg;
+        assert_eq!(inner_telemetry, NestedTelemetry::new());
         assert_eq!(linesearch, ls);
         assert_eq!(curvature_threshold.to_ne_bytes(), 1e-6f64.to_ne_bytes());
         assert_eq!(tol.to_ne_bytes(), f64::EPSILON.to_ne_bytes());
@@ -323,6 +424,28 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_config_build() {
+        let config = NewtonCGConfig {
+            curvature_threshold: Some(1e-6f64),
+            tolerance: Some(1e-4),
+        };
+        let ncg: NewtonCG<_, f64> = config.build(()).unwrap();
+        assert_eq!(ncg.curvature_threshold.to_ne_bytes(), 1e-6f64.to_ne_bytes());
+        assert_eq!(ncg.tol.to_ne_bytes(), 1e-4f64.to_ne_bytes());
+
+        let res = NewtonCGConfig {
+            tolerance: Some(0.0f64),
+            ..Default::default()
+        }
+        .build(());
+        assert_error!(
+            res,
+            ArgminError,
+            "Invalid parameter: \"`NewtonCG`: tol must be > 0.\""
+        );
+    }
+
     #[test]
     fn test_next_iter_param_not_initialized() {
         use crate::solver::linesearch::{condition::ArmijoCondition, BacktrackingLineSearch};
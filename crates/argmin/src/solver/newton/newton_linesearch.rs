@@ -0,0 +1,453 @@
+// Copyright 2018-2024 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use crate::core::{
+    ArgminFloat, Error, Executor, Gradient, Hessian, IterState, LineSearch, NestedTelemetry,
+    OptimizationResult, Problem, Solver, TerminationReason, TerminationStatus, KV,
+};
+use argmin_math::{ArgminDot, ArgminInv, ArgminMul};
+#[cfg(feature = "serde1")]
+use serde::{Deserialize, Serialize};
+
+/// # Damped Newton's method
+///
+/// Newton's method where the step length along the Newton direction `-H^-1 g` is obtained by a
+/// line search. In contrast to the pure [`Newton`](`crate::solver::newton::Newton`) method, this
+/// converges also from starting points far away from the optimum, provided that the Hessian is
+/// positive definite along the way (otherwise the Newton direction may not be a descent
+/// direction).
+///
+/// Usually constructed via [`Newton::with_linesearch`](`crate::solver::newton::Newton::with_linesearch`).
+/// The line search is started with a step length of `1`, such that the full Newton step is taken
+/// whenever it is acceptable.
+///
+/// Terminates if the absolute difference of the cost function values of two consecutive
+/// iterations is below the tolerance set via [`with_tolerance`](`NewtonLS::with_tolerance`).
+///
+/// ## Requirements on the optimization problem
+///
+/// The optimization problem is required to implement [`Gradient`] and [`Hessian`]. The line
+/// search typically additionally requires
+/// [`CostFunction`](`crate::core::CostFunction`).
+///
+/// ## Reference
+///
+/// Jorge Nocedal and Stephen J. Wright (2006). Numerical Optimization.
+/// Springer. ISBN 0-387-30303-0.
+#[derive(Clone)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+pub struct NewtonLS<L, F> {
+    /// line search
+    linesearch: L,
+    /// Tolerance for the stopping criterion based on cost difference
+    tol: F,
+    /// Telemetry of the line search
+    #[cfg_attr(feature = "serde1", serde(default))]
+    inner_telemetry: NestedTelemetry,
+}
+
+impl<L, F> NewtonLS<L, F>
+where
+    F: ArgminFloat,
+{
+    /// Construct a new instance of [`NewtonLS`]
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::newton::NewtonLS;
+    /// # let linesearch = ();
+    /// let newton: NewtonLS<_, f64> = NewtonLS::new(linesearch);
+    /// ```
+    pub fn new(linesearch: L) -> Self {
+        NewtonLS {
+            linesearch,
+            tol: F::epsilon(),
+            inner_telemetry: NestedTelemetry::new(),
+        }
+    }
+
+    /// Forward the telemetry of the line search to observers
+    ///
+    /// The key-value pairs returned by the line search as well as its number of iterations and
+    /// its termination reason are forwarded with keys prefixed by `prefix` (for instance
+    /// `linesearch.iters`). Independent of this setting, the line search iterations are counted
+    /// as `linesearch_iter_count`. See [`NestedTelemetry`] for details.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::newton::NewtonLS;
+    /// # let linesearch = ();
+    /// let newton = NewtonLS::<_, f64>::new(linesearch).with_inner_telemetry("linesearch");
+    /// ```
+    #[must_use]
+    pub fn with_inner_telemetry<T: AsRef<str>>(mut self, prefix: T) -> Self {
+        self.inner_telemetry = NestedTelemetry::forward(prefix);
+        self
+    }
+
+    /// Set tolerance for the stopping criterion based on cost difference
+    ///
+    /// Tolerance must be larger than zero and defaults to `EPSILON`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::newton::NewtonLS;
+    /// # use argmin::core::Error;
+    /// # fn main() -> Result<(), Error> {
+    /// # let linesearch = ();
+    /// let newton = NewtonLS::new(linesearch).with_tolerance(1e-6f64)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_tolerance(mut self, tol: F) -> Result<Self, Error> {
+        if tol.is_nan() || tol <= float!(0.0) {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "`NewtonLS`: tol must be positive."
+            ));
+        }
+        self.tol = tol;
+        Ok(self)
+    }
+}
+
+/// Serializable configuration of [`NewtonLS`]
+///
+/// Options which are `None` keep the defaults of [`NewtonLS`]. The configuration is validated when
+/// the solver is constructed via [`build`](`NewtonLSConfig::build`).
+///
+/// # Example
+///
+/// ```
+/// # use argmin::solver::newton::{NewtonLS, NewtonLSConfig};
+/// # use argmin::core::Error;
+/// # fn main() -> Result<(), Error> {
+/// # let linesearch = ();
+/// let config = NewtonLSConfig {
+///     tolerance: Some(1e-6f64),
+/// };
+/// let newton: NewtonLS<_, f64> = config.build(linesearch)?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize), serde(default))]
+pub struct NewtonLSConfig<F> {
+    /// See [`NewtonLS::with_tolerance`]
+    pub tolerance: Option<F>,
+}
+
+impl<F> NewtonLSConfig<F>
+where
+    F: ArgminFloat,
+{
+    /// Validates the configuration and constructs a [`NewtonLS`] solver using the given line
+    /// search.
+    pub fn build<L>(&self, linesearch: L) -> Result<NewtonLS<L, F>, Error> {
+        let mut solver = NewtonLS::new(linesearch);
+        if let Some(tol) = self.tolerance {
+            solver = solver.with_tolerance(tol)?;
+        }
+        Ok(solver)
+    }
+}
+
+impl<O, L, P, G, H, F> Solver<O, IterState<P, G, (), H, (), F>> for NewtonLS<L, F>
+where
+    O: Gradient<Param = P, Gradient = G> + Hessian<Param = P, Hessian = H>,
+    P: Clone + ArgminMul<F, P>,
+    H: ArgminInv<H> + ArgminDot<G, P>,
+    L: Clone + LineSearch<P, F> + Solver<O, IterState<P, G, (), (), (), F>>,
+    F: ArgminFloat,
+{
+    fn name(&self) -> &str {
+        "Newton method with line search"
+    }
+
+    fn init(
+        &mut self,
+        _problem: &mut Problem<O>,
+        state: IterState<P, G, (), H, (), F>,
+    ) -> Result<(IterState<P, G, (), H, (), F>, Option<KV>), Error> {
+        Ok((
+            state,
+            Some(
+                kv!(
+                    "linesearch" => self.linesearch.name();
+                    "tolerance" => self.tol;
+                )
+                .with_prefix("config"),
+            ),
+        ))
+    }
+
+    fn next_iter(
+        &mut self,
+        problem: &mut Problem<O>,
+        mut state: IterState<P, G, (), H, (), F>,
+    ) -> Result<(IterState<P, G, (), H, (), F>, Option<KV>), Error> {
+        let param = state.take_param().ok_or_else(argmin_error_closure!(
+            NotInitialized,
+            concat!(
+                "`NewtonLS` requires an initial parameter vector. ",
+                "Please provide an initial guess via `Executor`s `configure` method."
+            )
+        ))?;
+        let grad = problem.gradient(&param)?;
+        let hessian = problem.hessian(&param)?;
+        let direction = hessian.inv()?.dot(&grad).mul(&float!(-1.0));
+
+        self.linesearch.search_direction(direction);
+        self.linesearch.initial_step_length(float!(1.0))?;
+
+        let line_cost = state.get_cost();
+
+        let (
+            OptimizationResult {
+                problem: line_problem,
+                state: mut linesearch_state,
+                ..
+            },
+            linesearch_kv,
+        ) = Executor::new(
+            problem.take_problem().ok_or_else(argmin_error_closure!(
+                PotentialBug,
+                "`NewtonLS`: Failed to take `problem` for line search"
+            ))?,
+            self.linesearch.clone(),
+        )
+        .configure(|config| config.param(param).gradient(grad).cost(line_cost))
+        .run_nested()?;
+
+        problem.consume_problem(line_problem);
+        let kv = self.inner_telemetry.record(
+            problem,
+            "linesearch_iter_count",
+            &linesearch_state,
+            linesearch_kv,
+        );
+
+        Ok((
+            state
+                .param(
+                    linesearch_state
+                        .take_param()
+                        .ok_or_else(argmin_error_closure!(
+                            PotentialBug,
+                            "`NewtonLS`: Failed to take `param` from line search state"
+                        ))?,
+                )
+                .cost(linesearch_state.get_cost()),
+            kv,
+        ))
+    }
+
+    fn terminate(&mut self, state: &IterState<P, G, (), H, (), F>) -> TerminationStatus {
+        if (state.get_prev_cost() - state.get_cost()).abs() < self.tol {
+            return TerminationStatus::Terminated(TerminationReason::SolverConverged);
+        }
+        TerminationStatus::NotTerminated
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::ArgminError;
+    use crate::solver::linesearch::{condition::ArmijoCondition, BacktrackingLineSearch};
+    use crate::solver::newton::Newton;
+
+    test_trait_impl!(
+        newton_linesearch,
+        NewtonLS<BacktrackingLineSearch<Vec<f64>, Vec<f64>, ArmijoCondition<f64>, f64>, f64>
+    );
+
+    #[test]
+    fn test_new() {
+        #[derive(Eq, PartialEq, Debug)]
+        struct MyLinesearch {}
+
+        let newton: NewtonLS<_, f64> = NewtonLS::new(MyLinesearch {});
+        let NewtonLS {
+            linesearch,
+            tol,
+            inner_telemetry,
+        } = newton;
+
+        assert_eq!(linesearch, MyLinesearch {});
+        assert_eq!(tol.to_ne_bytes(), f64::EPSILON.to_ne_bytes());
+        assert_eq!(inner_telemetry, NestedTelemetry::new());
+    }
+
+    #[test]
+    fn test_from_newton() {
+        #[derive(Eq, PartialEq, Debug)]
+        struct MyLinesearch {}
+
+        let newton: NewtonLS<_, f64> = Newton::new()
+            .with_gamma(0.5)
+            .unwrap()
+            .with_linesearch(MyLinesearch {});
+        let NewtonLS {
+            linesearch,
+            tol,
+            inner_telemetry,
+        } = newton;
+
+        assert_eq!(linesearch, MyLinesearch {});
+        assert_eq!(tol.to_ne_bytes(), f64::EPSILON.to_ne_bytes());
+        assert_eq!(inner_telemetry, NestedTelemetry::new());
+    }
+
+    #[test]
+    fn test_with_tolerance() {
+        for tol in [f64::EPSILON, 1e-6, 1.0] {
+            let newton: NewtonLS<_, f64> = NewtonLS::new(()).with_tolerance(tol).unwrap();
+            assert_eq!(newton.tol.to_ne_bytes(), tol.to_ne_bytes());
+        }
+
+        for tol in [0.0, -1.0, f64::NAN] {
+            let res = NewtonLS::new(()).with_tolerance(tol);
+            assert_error!(
+                res,
+                ArgminError,
+                "Invalid parameter: \"`NewtonLS`: tol must be positive.\""
+            );
+        }
+    }
+
+    #[test]
+    fn test_config_build() {
+        let newton = NewtonLSConfig {
+            tolerance: Some(1e-6f64),
+        }
+        .build(())
+        .unwrap();
+        assert_eq!(newton.tol.to_ne_bytes(), 1e-6f64.to_ne_bytes());
+
+        let newton = NewtonLSConfig::<f64>::default().build(()).unwrap();
+        assert_eq!(newton.tol.to_ne_bytes(), f64::EPSILON.to_ne_bytes());
+
+        let res = NewtonLSConfig {
+            tolerance: Some(0.0f64),
+        }
+        .build(());
+        assert_error!(
+            res,
+            ArgminError,
+            "Invalid parameter: \"`NewtonLS`: tol must be positive.\""
+        );
+    }
+
+    #[cfg(feature = "_ndarrayl")]
+    #[test]
+    fn test_next_iter_param_not_initialized() {
+        use crate::core::{CostFunction, State};
+        use ndarray::{Array, Array1, Array2};
+
+        struct NewtonProblem {}
+
+        impl CostFunction for NewtonProblem {
+            type Param = Array1<f64>;
+            type Output = f64;
+
+            fn cost(&self, p: &Self::Param) -> Result<Self::Output, Error> {
+                Ok(p.dot(p))
+            }
+        }
+
+        impl Gradient for NewtonProblem {
+            type Param = Array1<f64>;
+            type Gradient = Array1<f64>;
+
+            fn gradient(&self, p: &Self::Param) -> Result<Self::Gradient, Error> {
+                Ok(p * 2.0)
+            }
+        }
+
+        impl Hessian for NewtonProblem {
+            type Param = Array1<f64>;
+            type Hessian = Array2<f64>;
+
+            fn hessian(&self, _p: &Self::Param) -> Result<Self::Hessian, Error> {
+                Ok(Array::from_shape_vec((2, 2), vec![2.0f64, 0.0, 0.0, 2.0])?)
+            }
+        }
+
+        let linesearch: BacktrackingLineSearch<_, _, _, f64> =
+            BacktrackingLineSearch::new(ArmijoCondition::new(0.2).unwrap());
+        let mut newton: NewtonLS<_, f64> = NewtonLS::new(linesearch);
+        let res = newton.next_iter(&mut Problem::new(NewtonProblem {}), IterState::new());
+        assert_error!(
+            res,
+            ArgminError,
+            concat!(
+                "Not initialized: \"`NewtonLS` requires an initial parameter vector. ",
+                "Please provide an initial guess via `Executor`s `configure` method.\""
+            )
+        );
+    }
+
+    #[cfg(feature = "_ndarrayl")]
+    #[test]
+    fn test_solver() {
+        use crate::core::{CostFunction, State};
+        use approx::assert_relative_eq;
+        use ndarray::{array, Array1, Array2};
+
+        /// `f(x) = sqrt(1 + x_0^2) + sqrt(1 + x_1^2)`. The pure Newton method diverges for
+        /// starting points with `|x_i| > 1`.
+        struct Problem {}
+
+        impl CostFunction for Problem {
+            type Param = Array1<f64>;
+            type Output = f64;
+
+            fn cost(&self, p: &Self::Param) -> Result<Self::Output, Error> {
+                Ok(p.iter().map(|x| (1.0 + x * x).sqrt()).sum())
+            }
+        }
+
+        impl Gradient for Problem {
+            type Param = Array1<f64>;
+            type Gradient = Array1<f64>;
+
+            fn gradient(&self, p: &Self::Param) -> Result<Self::Gradient, Error> {
+                Ok(p.mapv(|x| x / (1.0 + x * x).sqrt()))
+            }
+        }
+
+        impl Hessian for Problem {
+            type Param = Array1<f64>;
+            type Hessian = Array2<f64>;
+
+            fn hessian(&self, p: &Self::Param) -> Result<Self::Hessian, Error> {
+                Ok(Array2::from_diag(&p.mapv(|x| (1.0 + x * x).powf(-1.5))))
+            }
+        }
+
+        let linesearch: BacktrackingLineSearch<_, _, _, f64> =
+            BacktrackingLineSearch::new(ArmijoCondition::new(1e-4).unwrap());
+        let solver = NewtonLS::new(linesearch);
+
+        let res = Executor::new(Problem {}, solver)
+            .configure(|config| config.param(array![2.0, -3.0]).max_iters(50))
+            .run()
+            .unwrap();
+
+        assert_eq!(
+            res.state.get_termination_reason(),
+            Some(&TerminationReason::SolverConverged)
+        );
+        let param = res.state.get_best_param().unwrap();
+        assert_relative_eq!(param[0], 0.0, epsilon = 1e-6);
+        assert_relative_eq!(param[1], 0.0, epsilon = 1e-6);
+    }
+}
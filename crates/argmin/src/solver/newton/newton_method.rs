@@ -6,6 +6,7 @@
 // copied, modified, or distributed except according to those terms.
 
 use crate::core::{ArgminFloat, Error, Gradient, Hessian, IterState, Problem, Solver, KV};
+use crate::solver::newton::NewtonLS;
 use argmin_math::{ArgminDot, ArgminInv, ArgminScaledSub};
 #[cfg(feature = "serde1")]
 use serde::{Deserialize, Serialize};
@@ -18,6 +19,10 @@ use serde::{Deserialize, Serialize};
 /// The stepsize `gamma` can be adjusted with the [`with_gamma`](`Newton::with_gamma`) method. It
 /// must be in `(0, 1])` and defaults to `1`.
 ///
+/// Pure Newton steps may diverge from starting points far away from the optimum. A damped Newton
+/// method which obtains the step length from a line search is available via
+/// [`with_linesearch`](`Newton::with_linesearch`).
+///
 /// ## Requirements on the optimization problem
 ///
 /// The optimization problem is required to implement [`Gradient`] and [`Hessian`].
@@ -73,6 +78,28 @@ where
         self.gamma = gamma;
         Ok(self)
     }
+
+    /// Use a line search to determine the step length along the Newton direction
+    ///
+    /// Turns the solver into the damped Newton method [`NewtonLS`]. The step size `gamma` is not
+    /// used by the damped method.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::newton::{Newton, NewtonLS};
+    /// # use argmin::solver::linesearch::{condition::ArmijoCondition, BacktrackingLineSearch};
+    /// # use argmin::core::Error;
+    /// # fn main() -> Result<(), Error> {
+    /// let linesearch: BacktrackingLineSearch<Vec<f64>, Vec<f64>, _, f64> =
+    ///     BacktrackingLineSearch::new(ArmijoCondition::new(1e-4)?);
+    /// let newton: NewtonLS<_, f64> = Newton::new().with_linesearch(linesearch);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_linesearch<L>(self, linesearch: L) -> NewtonLS<L, F> {
+        NewtonLS::new(linesearch)
+    }
 }
 
 impl<F> Default for Newton<F>
@@ -84,6 +111,43 @@ where
     }
 }
 
+/// Serializable configuration of [`Newton`]
+///
+/// Options which are `None` keep the defaults of [`Newton`]. The configuration is validated when
+/// the solver is constructed via [`build`](`NewtonConfig::build`).
+///
+/// # Example
+///
+/// ```
+/// # use argmin::solver::newton::{Newton, NewtonConfig};
+/// # use argmin::core::Error;
+/// # fn main() -> Result<(), Error> {
+/// let config = NewtonConfig { gamma: Some(0.5f64) };
+/// let newton: Newton<f64> = config.build()?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize), serde(default))]
+pub struct NewtonConfig<F> {
+    /// See [`Newton::with_gamma`]
+    pub gamma: Option<F>,
+}
+
+impl<F> NewtonConfig<F>
+where
+    F: ArgminFloat,
+{
+    /// Validates the configuration and constructs a [`Newton`] solver.
+    pub fn build(&self) -> Result<Newton<F>, Error> {
+        let mut solver = Newton::new();
+        if let Some(gamma) = self.gamma {
+            solver = solver.with_gamma(gamma)?;
+        }
+        Ok(solver)
+    }
+}
+
 impl<O, P, G, H, F> Solver<O, IterState<P, G, (), H, (), F>> for Newton<F>
 where
     O: Gradient<Param = P, Gradient = G> + Hessian<Param = P, Hessian = H>,
@@ -95,6 +159,17 @@ where
         "Newton method"
     }
 
+    fn init(
+        &mut self,
+        _problem: &mut Problem<O>,
+        state: IterState<P, G, (), H, (), F>,
+    ) -> Result<(IterState<P, G, (), H, (), F>, Option<KV>), Error> {
+        Ok((
+            state,
+            Some(kv!("gamma" => self.gamma;).with_prefix("config")),
+        ))
+    }
+
     fn next_iter(
         &mut self,
         problem: &mut Problem<O>,
@@ -158,6 +233,29 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_config_build() {
+        let solver = NewtonConfig {
+            gamma: Some(0.5f64),
+        }
+        .build()
+        .unwrap();
+        assert_eq!(solver.gamma.to_ne_bytes(), 0.5f64.to_ne_bytes());
+
+        let solver = NewtonConfig::<f64>::default().build().unwrap();
+        assert_eq!(solver.gamma.to_ne_bytes(), 1.0f64.to_ne_bytes());
+
+        let res = NewtonConfig {
+            gamma: Some(2.0f64),
+        }
+        .build();
+        assert_error!(
+            res,
+            ArgminError,
+            "Invalid parameter: \"Newton: gamma must be in  (0, 1].\""
+        );
+    }
+
     #[cfg(feature = "_ndarrayl")]
     #[test]
     fn test_next_iter_param_not_initialized() {
@@ -25,6 +25,7 @@ use crate::core::{
 };
 use argmin_math::{ArgminAdd, ArgminMinMax, ArgminMul, ArgminRandom, ArgminSub, ArgminZeroLike};
 use rand::{Rng, SeedableRng};
+use rand_xoshiro::Xoshiro256PlusPlus;
 #[cfg(feature = "serde1")]
 use serde::{Deserialize, Serialize};
 
@@ -65,7 +66,7 @@ pub struct ParticleSwarm<P, F, R> {
     rng_generator: R,
 }
 
-impl<P, F> ParticleSwarm<P, F, rand::rngs::StdRng>
+impl<P, F> ParticleSwarm<P, F, Xoshiro256PlusPlus>
 where
     P: Clone + SyncAlias + ArgminSub<P, P> + ArgminMul<F, P> + ArgminRandom + ArgminZeroLike,
     F: ArgminFloat,
@@ -87,6 +88,10 @@ where
     /// * cognitive: `0.5 + ln(2)`
     /// * social: `0.5 + ln(2)`
     ///
+    /// Uses the `Xoshiro256PlusPlus` RNG internally, which is serialized into checkpoints if the
+    /// `serde1` feature is enabled. For use of another RNG, consider using
+    /// [`with_rng_generator`](`ParticleSwarm::with_rng_generator`).
+    ///
     /// # Example
     ///
     /// ```
@@ -102,7 +107,7 @@ where
             weight_social: float!(0.5 + 2.0f64.ln()),
             bounds,
             num_particles,
-            rng_generator: rand::rngs::StdRng::from_entropy(),
+            rng_generator: Xoshiro256PlusPlus::from_entropy(),
         }
     }
 }
@@ -114,7 +119,8 @@ where
 {
     /// Set the random number generator
     ///
-    /// Defaults to `rand::rngs::StdRng::from_entropy()`
+    /// Defaults to `Xoshiro256PlusPlus::from_entropy()`. When checkpointing is used, the RNG
+    /// must implement `Serialize` and `Deserialize` in order to resume a run exactly.
     ///
     /// # Example
     /// ```
@@ -275,6 +281,75 @@ where
     }
 }
 
+/// Serializable configuration of [`ParticleSwarm`]
+///
+/// Options which are `None` keep the defaults of [`ParticleSwarm`]. The configuration is validated
+/// when the solver is constructed via [`build`](`ParticleSwarmConfig::build`).
+///
+/// # Example
+///
+/// ```
+/// # use argmin::solver::particleswarm::{ParticleSwarm, ParticleSwarmConfig};
+/// # use argmin::core::Error;
+/// # fn main() -> Result<(), Error> {
+/// let config = ParticleSwarmConfig {
+///     bounds: (vec![-1.0f64, -1.0], vec![1.0, 1.0]),
+///     num_particles: 40,
+///     inertia_factor: Some(0.5),
+///     seed: Some(1729),
+///     ..Default::default()
+/// };
+/// let pso = config.build()?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize), serde(default))]
+pub struct ParticleSwarmConfig<P, F> {
+    /// Bounds on the parameter space as `(lower_bound, upper_bound)`
+    pub bounds: (P, P),
+    /// Number of particles, must be `> 0`
+    pub num_particles: usize,
+    /// See [`ParticleSwarm::with_inertia_factor`]
+    pub inertia_factor: Option<F>,
+    /// See [`ParticleSwarm::with_cognitive_factor`]
+    pub cognitive_factor: Option<F>,
+    /// See [`ParticleSwarm::with_social_factor`]
+    pub social_factor: Option<F>,
+    /// Seed of the `Xoshiro256PlusPlus` RNG. If `None`, the RNG is seeded from entropy.
+    pub seed: Option<u64>,
+}
+
+impl<P, F> ParticleSwarmConfig<P, F>
+where
+    P: Clone + SyncAlias + ArgminSub<P, P> + ArgminMul<F, P> + ArgminRandom + ArgminZeroLike,
+    F: ArgminFloat,
+{
+    /// Validates the configuration and constructs a [`ParticleSwarm`] solver.
+    pub fn build(&self) -> Result<ParticleSwarm<P, F, Xoshiro256PlusPlus>, Error> {
+        if self.num_particles == 0 {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "`ParticleSwarm`: number of particles must be > 0."
+            ));
+        }
+        let mut solver = ParticleSwarm::new(self.bounds.clone(), self.num_particles);
+        if let Some(seed) = self.seed {
+            solver = solver.with_rng_generator(Xoshiro256PlusPlus::seed_from_u64(seed));
+        }
+        if let Some(factor) = self.inertia_factor {
+            solver = solver.with_inertia_factor(factor)?;
+        }
+        if let Some(factor) = self.cognitive_factor {
+            solver = solver.with_cognitive_factor(factor)?;
+        }
+        if let Some(factor) = self.social_factor {
+            solver = solver.with_social_factor(factor)?;
+        }
+        Ok(solver)
+    }
+}
+
 impl<O, P, F, R> Solver<O, PopulationState<Particle<P, F>, F>> for ParticleSwarm<P, F, R>
 where
     O: CostFunction<Param = P, Output = F> + SyncAlias,
@@ -327,7 +402,16 @@ where
                 .individual(particles[0].clone())
                 .cost(particles[0].cost)
                 .population(particles),
-            None,
+            Some(
+                kv!(
+                    "num_particles" => self.num_particles as u64;
+                    "inertia_factor" => self.weight_inertia;
+                    "cognitive_factor" => self.weight_cognitive;
+                    "social_factor" => self.weight_social;
+                    "rng" => std::any::type_name::<R>();
+                )
+                .with_prefix("config"),
+            ),
         ))
     }
 
@@ -453,19 +537,19 @@ where
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::core::{test_utils::TestProblem, ArgminError, State};
+    use crate::core::{test_utils::TestProblem, ArgminError, KvValue, State};
     use approx::assert_relative_eq;
 
     test_trait_impl!(
         particleswarm,
-        ParticleSwarm<Vec<f64>, f64, rand::rngs::StdRng>
+        ParticleSwarm<Vec<f64>, f64, Xoshiro256PlusPlus>
     );
 
     #[test]
     fn test_new() {
         let lower_bound: Vec<f64> = vec![-1.0, -1.0];
         let upper_bound: Vec<f64> = vec![1.0, 1.0];
-        let pso: ParticleSwarm<_, f64, rand::rngs::StdRng> =
+        let pso: ParticleSwarm<_, f64, Xoshiro256PlusPlus> =
             ParticleSwarm::new((lower_bound.clone(), upper_bound.clone()), 40);
         let ParticleSwarm {
             weight_inertia,
@@ -585,6 +669,50 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_config_build() {
+        let config = ParticleSwarmConfig {
+            bounds: (vec![-1.0f64, -1.0], vec![1.0, 1.0]),
+            num_particles: 20,
+            inertia_factor: Some(0.1),
+            cognitive_factor: Some(0.2),
+            social_factor: Some(0.3),
+            seed: Some(1729),
+        };
+        let pso = config.build().unwrap();
+        assert_eq!(pso.num_particles, 20);
+        assert_eq!(pso.bounds, config.bounds);
+        assert_eq!(pso.weight_inertia.to_ne_bytes(), 0.1f64.to_ne_bytes());
+        assert_eq!(pso.weight_cognitive.to_ne_bytes(), 0.2f64.to_ne_bytes());
+        assert_eq!(pso.weight_social.to_ne_bytes(), 0.3f64.to_ne_bytes());
+
+        let mut rng1 = config.build().unwrap().rng_generator;
+        let mut rng2 = config.build().unwrap().rng_generator;
+        assert_eq!(rng1.gen::<u64>(), rng2.gen::<u64>());
+
+        let res = ParticleSwarmConfig {
+            num_particles: 0,
+            ..config.clone()
+        }
+        .build();
+        assert_error!(
+            res,
+            ArgminError,
+            "Invalid parameter: \"`ParticleSwarm`: number of particles must be > 0.\""
+        );
+
+        let res = ParticleSwarmConfig {
+            social_factor: Some(-1.0),
+            ..config
+        }
+        .build();
+        assert_error!(
+            res,
+            ArgminError,
+            "Invalid parameter: \"`ParticleSwarm`: social factor must be >=0.\""
+        );
+    }
+
     #[test]
     fn test_initialize_positions_and_velocities() {
         let lower_bound: Vec<f64> = vec![-1.0, -1.0];
@@ -708,7 +836,17 @@ mod tests {
         let res = pso.init(&mut Problem::new(TestProblem::new()), state);
         assert!(res.is_ok());
         let (mut state, kv) = res.unwrap();
-        assert!(kv.is_none());
+        assert_eq!(
+            kv.unwrap(),
+            kv!(
+                "num_particles" => 2u64;
+                "inertia_factor" => 1.0f64 / (2.0 * 2.0f64.ln());
+                "cognitive_factor" => 0.5 + 2.0f64.ln();
+                "social_factor" => 0.5 + 2.0f64.ln();
+                "rng" => std::any::type_name::<Xoshiro256PlusPlus>();
+            )
+            .with_prefix("config")
+        );
         assert_eq!(*state.get_param().unwrap(), particle_b);
         let population = state.take_population().unwrap();
         // assert that it was sorted!
@@ -725,7 +863,10 @@ mod tests {
         let res = pso.init(&mut Problem::new(TestProblem::new()), state);
         assert!(res.is_ok());
         let (mut state, kv) = res.unwrap();
-        assert!(kv.is_none());
+        assert_eq!(
+            kv.unwrap().get("config.num_particles"),
+            Some(&KvValue::Uint(40))
+        );
         assert!(state.get_param().is_some());
         let population = state.take_population().unwrap();
         assert_eq!(population.len(), 40);
@@ -6,9 +6,10 @@
 // copied, modified, or distributed except according to those terms.
 
 use crate::core::{
-    ArgminFloat, CostFunction, Error, Executor, Gradient, IterState, LineSearch,
-    OptimizationResult, Problem, Solver, TerminationReason, TerminationStatus, KV,
+    ArgminFloat, CostFunction, Error, Executor, Gradient, IterState, LineSearch, NestedTelemetry,
+    OptimizationResult, Problem, Solver, State, TerminationReason, TerminationStatus, KV,
 };
+use crate::solver::quasinewton::CurvatureUpdate;
 use argmin_math::{
     ArgminAdd, ArgminDot, ArgminEye, ArgminL2Norm, ArgminMul, ArgminSub, ArgminTranspose,
 };
@@ -37,6 +38,12 @@ use serde::{Deserialize, Serialize};
 /// other. If the change is below this tolerance (default: `EPSILON`), the algorithm stops. This
 /// parameter can be set via [`with_tolerance_cost`](`BFGS::with_tolerance_cost`).
 ///
+/// Updates which violate the curvature condition can be skipped or damped via
+/// [`with_curvature_update`](`BFGS::with_curvature_update`).
+///
+/// On badly scaled problems it is beneficial to scale the initial inverse Hessian before the
+/// first update, which can be enabled via [`with_initial_scaling`](`BFGS::with_initial_scaling`).
+///
 /// ## Requirements on the optimization problem
 ///
 /// The optimization problem is required to implement [`CostFunction`] and [`Gradient`].
@@ -54,6 +61,15 @@ pub struct BFGS<L, F> {
     tol_grad: F,
     /// Tolerance for the stopping criterion based on the change of the cost stopping criterion
     tol_cost: F,
+    /// Telemetry of the line search
+    #[cfg_attr(feature = "serde1", serde(default))]
+    inner_telemetry: NestedTelemetry,
+    /// Treatment of updates which violate the curvature condition
+    #[cfg_attr(feature = "serde1", serde(default))]
+    curvature_update: CurvatureUpdate<F>,
+    /// Scale the initial inverse Hessian before the first update
+    #[cfg_attr(feature = "serde1", serde(default))]
+    initial_scaling: bool,
 }
 
 impl<L, F> BFGS<L, F>
@@ -74,9 +90,32 @@ where
             linesearch,
             tol_grad: F::epsilon().sqrt(),
             tol_cost: F::epsilon(),
+            inner_telemetry: NestedTelemetry::new(),
+            curvature_update: CurvatureUpdate::Always,
+            initial_scaling: false,
         }
     }
 
+    /// Forward the telemetry of the line search to observers
+    ///
+    /// The key-value pairs returned by the line search as well as its number of iterations and
+    /// its termination reason are forwarded with keys prefixed by `prefix` (for instance
+    /// `linesearch.iters`). Independent of this setting, the line search iterations are counted
+    /// as `linesearch_iter_count`. See [`NestedTelemetry`] for details.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::quasinewton::BFGS;
+    /// # let linesearch = ();
+    /// let solver = BFGS::<_, f64>::new(linesearch).with_inner_telemetry("linesearch");
+    /// ```
+    #[must_use]
+    pub fn with_inner_telemetry<T: AsRef<str>>(mut self, prefix: T) -> Self {
+        self.inner_telemetry = NestedTelemetry::forward(prefix);
+        self
+    }
+
     /// The algorithm stops if the norm of the gradient is below `tol_grad`.
     ///
     /// The provided value must be non-negative. Defaults to `sqrt(EPSILON)`.
@@ -128,6 +167,109 @@ where
         self.tol_cost = tol_cost;
         Ok(self)
     }
+
+    /// Sets how updates which violate the curvature condition `s^T y > 0` are treated.
+    ///
+    /// Defaults to [`CurvatureUpdate::Always`]. The threshold of [`CurvatureUpdate::Damped`] must
+    /// be in `(0, 1)`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::quasinewton::{BFGS, CurvatureUpdate};
+    /// # use argmin::core::Error;
+    /// # fn main() -> Result<(), Error> {
+    /// # let linesearch = ();
+    /// let bfgs: BFGS<_, f64> = BFGS::new(linesearch).with_curvature_update(CurvatureUpdate::Damped(0.2))?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_curvature_update(mut self, policy: CurvatureUpdate<F>) -> Result<Self, Error> {
+        if !policy.is_valid() {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "`BFGS`: damping threshold must be in (0, 1)."
+            ));
+        }
+        self.curvature_update = policy;
+        Ok(self)
+    }
+
+    /// Replaces the initial inverse Hessian by `gamma * I` with `gamma = s^T y / y^T y` before the
+    /// first update (Nocedal & Wright, eq. 6.20).
+    ///
+    /// This makes the size of the first steps independent of the scaling of the problem.
+    /// The provided initial inverse Hessian is then only used to compute the first step.
+    /// Defaults to `false`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::quasinewton::BFGS;
+    /// # let linesearch = ();
+    /// let bfgs: BFGS<_, f64> = BFGS::new(linesearch).with_initial_scaling(true);
+    /// ```
+    #[must_use]
+    pub fn with_initial_scaling(mut self, scaling: bool) -> Self {
+        self.initial_scaling = scaling;
+        self
+    }
+}
+
+/// Serializable configuration of [`BFGS`]
+///
+/// Options which are `None` keep the defaults of [`BFGS`]. The configuration is validated when
+/// the solver is constructed via [`build`](`BFGSConfig::build`).
+///
+/// # Example
+///
+/// ```
+/// # use argmin::solver::quasinewton::{BFGS, BFGSConfig};
+/// # use argmin::core::Error;
+/// # fn main() -> Result<(), Error> {
+/// # let linesearch = ();
+/// let config = BFGSConfig {
+///     tolerance_grad: Some(1e-6),
+///     ..Default::default()
+/// };
+/// let bfgs: BFGS<_, f64> = config.build(linesearch)?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize), serde(default))]
+pub struct BFGSConfig<F> {
+    /// See [`BFGS::with_tolerance_grad`]
+    pub tolerance_grad: Option<F>,
+    /// See [`BFGS::with_tolerance_cost`]
+    pub tolerance_cost: Option<F>,
+    /// See [`BFGS::with_curvature_update`]
+    pub curvature_update: Option<CurvatureUpdate<F>>,
+    /// See [`BFGS::with_initial_scaling`]
+    pub initial_scaling: Option<bool>,
+}
+
+impl<F> BFGSConfig<F>
+where
+    F: ArgminFloat,
+{
+    /// Validates the configuration and constructs a [`BFGS`] solver using the given line search.
+    pub fn build<L>(&self, linesearch: L) -> Result<BFGS<L, F>, Error> {
+        let mut solver = BFGS::new(linesearch);
+        if let Some(tol_grad) = self.tolerance_grad {
+            solver = solver.with_tolerance_grad(tol_grad)?;
+        }
+        if let Some(tol_cost) = self.tolerance_cost {
+            solver = solver.with_tolerance_cost(tol_cost)?;
+        }
+        if let Some(policy) = self.curvature_update {
+            solver = solver.with_curvature_update(policy)?;
+        }
+        if let Some(scaling) = self.initial_scaling {
+            solver = solver.with_initial_scaling(scaling);
+        }
+        Ok(solver)
+    }
 }
 
 impl<O, L, P, G, H, F> Solver<O, IterState<P, G, (), H, (), F>> for BFGS<L, F>
@@ -139,6 +281,8 @@ where
         + ArgminMul<F, P>
         + ArgminMul<F, G>
         + ArgminDot<P, F>
+        + ArgminDot<G, F>
+        + ArgminAdd<G, G>
         + ArgminSub<G, G>,
     H: ArgminSub<H, H>
         + ArgminDot<G, G>
@@ -154,46 +298,71 @@ where
         "BFGS"
     }
 
+    fn check_state(&self, state: &IterState<P, G, (), H, (), F>) -> Result<(), Error> {
+        if state.get_param().is_none() {
+            return Err(argmin_error!(
+                NotInitialized,
+                concat!(
+                    "`BFGS` requires an initial parameter vector. ",
+                    "Please provide an initial guess via `Executor`s `configure` method."
+                )
+            ));
+        }
+        if state.get_inv_hessian().is_none() {
+            return Err(argmin_error!(
+                NotInitialized,
+                concat!(
+                    "`BFGS` requires an initial inverse Hessian. ",
+                    "Please provide an initial guess via `Executor`s `configure` method."
+                )
+            ));
+        }
+        Ok(())
+    }
+
     fn init(
         &mut self,
         problem: &mut Problem<O>,
         mut state: IterState<P, G, (), H, (), F>,
     ) -> Result<(IterState<P, G, (), H, (), F>, Option<KV>), Error> {
+        self.check_state(&state)?;
         let param = state.take_param().ok_or_else(argmin_error_closure!(
-            NotInitialized,
-            concat!(
-                "`BFGS` requires an initial parameter vector. ",
-                "Please provide an initial guess via `Executor`s `configure` method."
-            )
+            PotentialBug,
+            "`BFGS`: Parameter vector in state not set."
         ))?;
 
         let inv_hessian = state.take_inv_hessian().ok_or_else(argmin_error_closure!(
-            NotInitialized,
-            concat!(
-                "`BFGS` requires an initial inverse Hessian. ",
-                "Please provide an initial guess via `Executor`s `configure` method."
-            )
+            PotentialBug,
+            "`BFGS`: Inverse Hessian in state not set."
         ))?;
 
+        let policy = state.get_initial_evaluation();
+
         let cost = state.get_cost();
-        let cost = if cost.is_infinite() {
-            problem.cost(&param)?
-        } else {
-            cost
-        };
+        let cost = policy
+            .evaluate_lazily((!cost.is_infinite()).then_some(cost), || {
+                problem.cost(&param)
+            })?
+            .unwrap_or(cost);
 
-        let grad = state
-            .take_gradient()
-            .map(Result::Ok)
-            .unwrap_or_else(|| problem.gradient(&param))?;
+        let grad = policy.evaluate_lazily(state.take_gradient(), || problem.gradient(&param))?;
 
+        let mut state = state.param(param).cost(cost).inv_hessian(inv_hessian);
+        if let Some(grad) = grad {
+            state = state.gradient(grad);
+        }
         Ok((
-            state
-                .param(param)
-                .cost(cost)
-                .gradient(grad)
-                .inv_hessian(inv_hessian),
-            None,
+            state,
+            Some(
+                kv!(
+                    "linesearch" => self.linesearch.name();
+                    "tolerance_grad" => self.tol_grad;
+                    "tolerance_cost" => self.tol_cost;
+                    "curvature_update" => format!("{:?}", self.curvature_update);
+                    "initial_scaling" => self.initial_scaling;
+                )
+                .with_prefix("config"),
+            ),
         ))
     }
 
@@ -209,10 +378,11 @@ where
 
         let cur_cost = state.get_cost();
 
-        let prev_grad = state.take_gradient().ok_or_else(argmin_error_closure!(
-            PotentialBug,
-            "`BFGS`: Gradient in state not set."
-        ))?;
+        let prev_grad = state
+            .take_gradient()
+            .map(Result::Ok)
+            // Missing if the initial evaluation was skipped
+            .unwrap_or_else(|| problem.gradient(&param))?;
 
         let inv_hessian = state.take_inv_hessian().ok_or_else(argmin_error_closure!(
             PotentialBug,
@@ -221,22 +391,24 @@ where
 
         let g: G = inv_hessian.dot(&prev_grad).mul(&float!(-1.0));
 
-        self.linesearch.search_direction(g);
+        self.linesearch.search_direction(g.clone());
 
         // Run solver
-        let OptimizationResult {
-            problem: line_problem,
-            state: mut sub_state,
-            ..
-        } = Executor::new(problem.take_problem().unwrap(), self.linesearch.clone())
+        let (
+            OptimizationResult {
+                problem: line_problem,
+                state: mut sub_state,
+                ..
+            },
+            linesearch_kv,
+        ) = Executor::new(problem.take_problem().unwrap(), self.linesearch.clone())
             .configure(|config| {
                 config
                     .param(param.clone())
                     .gradient(prev_grad.clone())
                     .cost(cur_cost)
             })
-            .ctrlc(false)
-            .run()?;
+            .run_nested()?;
 
         let xk1 = sub_state.take_param().ok_or_else(argmin_error_closure!(
             PotentialBug,
@@ -247,6 +419,12 @@ where
 
         // take care of function eval counts
         problem.consume_problem(line_problem);
+        let linesearch_kv = self.inner_telemetry.record(
+            problem,
+            "linesearch_iter_count",
+            &sub_state,
+            linesearch_kv,
+        );
 
         let grad = problem.gradient(&xk1)?;
 
@@ -254,6 +432,25 @@ where
 
         let sk = xk1.sub(&param);
 
+        // Since `g = -H g_k` and `s = alpha g`, the product of the Hessian approximation and `s`
+        // is `-alpha g_k`.
+        let (yk, curvature_kv) = self.curvature_update.correct(&sk, yk, || {
+            let alpha: F = g.dot(&sk) / g.dot(&g);
+            prev_grad.mul(&(-alpha))
+        });
+        let linesearch_kv = Some(linesearch_kv.unwrap_or_default().merge(curvature_kv))
+            .filter(|kv| !kv.kv.is_empty());
+        let Some(yk) = yk else {
+            return Ok((
+                state
+                    .param(xk1)
+                    .cost(next_cost)
+                    .gradient(grad)
+                    .inv_hessian(inv_hessian),
+                linesearch_kv,
+            ));
+        };
+
         let yksk: F = yk.dot(&sk);
         let rhok = float!(1.0) / yksk;
 
@@ -269,11 +466,18 @@ where
         let sksk: H = sk.dot(&sk);
         let sksk = sksk.mul(&rhok);
 
-        // if state.get_iter() == 0 {
-        //     let ykyk: f64 = yk.dot(&yk);
-        //     self.inv_hessian = self.inv_hessian.eye_like().mul(&(yksk / ykyk));
-        //     println!("{:?}", self.inv_hessian);
-        // }
+        // Replace the initial inverse Hessian by a scaled identity before the first update
+        let inv_hessian = if self.initial_scaling && state.get_iter() == 0 {
+            let ykyk: F = yk.dot(&yk);
+            let gamma = yksk / ykyk;
+            if gamma.is_finite() && gamma > float!(0.0) {
+                e.mul(&gamma)
+            } else {
+                inv_hessian
+            }
+        } else {
+            inv_hessian
+        };
 
         let inv_hessian = tmp1.dot(&inv_hessian.dot(&tmp2)).add(&sksk);
 
@@ -283,12 +487,16 @@ where
                 .cost(next_cost)
                 .gradient(grad)
                 .inv_hessian(inv_hessian),
-            None,
+            linesearch_kv,
         ))
     }
 
     fn terminate(&mut self, state: &IterState<P, G, (), H, (), F>) -> TerminationStatus {
-        if state.get_gradient().unwrap().l2_norm() < self.tol_grad {
+        // The gradient is missing after `init` if the initial evaluation was skipped
+        if state
+            .get_gradient()
+            .is_some_and(|grad| grad.l2_norm() < self.tol_grad)
+        {
             return TerminationStatus::Terminated(TerminationReason::SolverConverged);
         }
         if (state.get_prev_cost() - state.cost).abs() < self.tol_cost {
@@ -301,8 +509,9 @@ where
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::core::{test_utils::TestProblem, ArgminError, State};
-    use crate::solver::linesearch::MoreThuenteLineSearch;
+    use crate::core::{test_utils::TestProblem, ArgminError, InitialEvaluation, KvValue, State};
+    use crate::solver::linesearch::{FlakyLineSearch, MoreThuenteLineSearch};
+    use approx::assert_relative_eq;
 
     test_trait_impl!(
         bfgs,
@@ -319,7 +528,13 @@ mod tests {
             linesearch,
             tol_grad,
             tol_cost,
+            inner_telemetry,
+            curvature_update,
+            initial_scaling,
         } = bfgs;
+        assert_eq!(inner_telemetry, NestedTelemetry::new());
+        assert_eq!(curvature_update, CurvatureUpdate::Always);
+        assert!(!initial_scaling);
 
         assert_eq!(linesearch, MyFakeLineSearch {});
         assert_eq!(tol_grad.to_ne_bytes(), f64::EPSILON.sqrt().to_ne_bytes());
@@ -380,6 +595,42 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_config_build() {
+        #[derive(Eq, PartialEq, Debug, Clone, Copy)]
+        struct MyFakeLineSearch {}
+
+        let config = BFGSConfig {
+            tolerance_grad: Some(1e-4),
+            tolerance_cost: Some(1e-5),
+            curvature_update: Some(CurvatureUpdate::Skip),
+            initial_scaling: Some(true),
+        };
+        let bfgs: BFGS<_, f64> = config.build(MyFakeLineSearch {}).unwrap();
+        assert_eq!(bfgs.tol_grad.to_ne_bytes(), 1e-4f64.to_ne_bytes());
+        assert_eq!(bfgs.tol_cost.to_ne_bytes(), 1e-5f64.to_ne_bytes());
+        assert_eq!(bfgs.curvature_update, CurvatureUpdate::Skip);
+        assert!(bfgs.initial_scaling);
+
+        let bfgs: BFGS<_, f64> = BFGSConfig::default().build(MyFakeLineSearch {}).unwrap();
+        assert_eq!(
+            bfgs.tol_grad.to_ne_bytes(),
+            f64::EPSILON.sqrt().to_ne_bytes()
+        );
+        assert_eq!(bfgs.tol_cost.to_ne_bytes(), f64::EPSILON.to_ne_bytes());
+
+        let config = BFGSConfig {
+            tolerance_cost: Some(-1.0),
+            ..Default::default()
+        };
+        let res: Result<BFGS<_, f64>, _> = config.build(MyFakeLineSearch {});
+        assert_error!(
+            res,
+            ArgminError,
+            "Invalid parameter: \"`BFGS`: cost tolerance must be >= 0.\""
+        );
+    }
+
     #[test]
     fn test_init() {
         let linesearch = MoreThuenteLineSearch::new().with_c(1e-4, 0.9).unwrap();
@@ -424,7 +675,17 @@ mod tests {
         let problem = TestProblem::new();
         let (mut state_out, kv) = bfgs.init(&mut Problem::new(problem), state).unwrap();
 
-        assert!(kv.is_none());
+        assert_eq!(
+            kv.unwrap(),
+            kv!(
+                "linesearch" => "More-Thuente Line search";
+                "tolerance_grad" => f64::EPSILON.sqrt();
+                "tolerance_cost" => f64::EPSILON;
+                "curvature_update" => "Always";
+                "initial_scaling" => false;
+            )
+            .with_prefix("config")
+        );
 
         let s_param = state_out.take_param().unwrap();
 
@@ -468,7 +729,7 @@ mod tests {
         let problem = TestProblem::new();
         let (state_out, kv) = bfgs.init(&mut Problem::new(problem), state).unwrap();
 
-        assert!(kv.is_none());
+        assert!(kv.is_some());
 
         assert_eq!(state_out.get_cost().to_ne_bytes(), 1234.0f64.to_ne_bytes())
     }
@@ -491,7 +752,7 @@ mod tests {
         let problem = TestProblem::new();
         let (mut state_out, kv) = bfgs.init(&mut Problem::new(problem), state).unwrap();
 
-        assert!(kv.is_none());
+        assert!(kv.is_some());
 
         let s_grad = state_out.take_gradient().unwrap();
 
@@ -499,4 +760,227 @@ mod tests {
             assert_eq!(s.to_ne_bytes(), g.to_ne_bytes());
         }
     }
+
+    #[test]
+    fn test_init_force() {
+        let linesearch = MoreThuenteLineSearch::new().with_c(1e-4, 0.9).unwrap();
+
+        let param: Vec<f64> = vec![-1.0, 1.0];
+        let gradient: Vec<f64> = vec![4.0, 9.0];
+        let inv_hessian: Vec<Vec<f64>> = vec![vec![1.0, 2.0], vec![3.0, 4.0]];
+
+        let mut bfgs: BFGS<_, f64> = BFGS::new(linesearch);
+
+        let state: IterState<Vec<f64>, Vec<f64>, (), Vec<Vec<f64>>, (), f64> = IterState::new()
+            .param(param.clone())
+            .inv_hessian(inv_hessian)
+            .gradient(gradient)
+            .cost(1234.0)
+            .initial_evaluation(InitialEvaluation::Force);
+
+        let mut problem = Problem::new(TestProblem::new());
+        let (state_out, _) = bfgs.init(&mut problem, state).unwrap();
+
+        assert_eq!(problem.counts["cost_count"], 1);
+        assert_eq!(problem.counts["gradient_count"], 1);
+        assert_eq!(state_out.get_cost().to_ne_bytes(), 1.0f64.to_ne_bytes());
+        assert_eq!(state_out.get_gradient().unwrap(), &param);
+    }
+
+    #[test]
+    fn test_init_skip() {
+        let linesearch = MoreThuenteLineSearch::new().with_c(1e-4, 0.9).unwrap();
+
+        let param: Vec<f64> = vec![-1.0, 1.0];
+        let inv_hessian: Vec<Vec<f64>> = vec![vec![1.0, 0.0], vec![0.0, 1.0]];
+
+        let mut bfgs: BFGS<_, f64> = BFGS::new(linesearch);
+
+        let state: IterState<Vec<f64>, Vec<f64>, (), Vec<Vec<f64>>, (), f64> = IterState::new()
+            .param(param.clone())
+            .inv_hessian(inv_hessian.clone())
+            .initial_evaluation(InitialEvaluation::Skip);
+
+        let mut problem = Problem::new(TestProblem::new());
+        let (state_out, _) = bfgs.init(&mut problem, state).unwrap();
+
+        assert!(problem.counts.is_empty());
+        assert!(state_out.get_cost().is_infinite());
+        assert!(state_out.get_gradient().is_none());
+
+        struct Sphere {}
+
+        impl CostFunction for Sphere {
+            type Param = Vec<f64>;
+            type Output = f64;
+
+            fn cost(&self, p: &Self::Param) -> Result<Self::Output, Error> {
+                Ok(p.iter().map(|x| x * x).sum())
+            }
+        }
+
+        impl Gradient for Sphere {
+            type Param = Vec<f64>;
+            type Gradient = Vec<f64>;
+
+            fn gradient(&self, p: &Self::Param) -> Result<Self::Gradient, Error> {
+                Ok(p.iter().map(|x| 2.0 * x).collect())
+            }
+        }
+
+        // Missing values are computed in the first iteration
+        let res = Executor::new(Sphere {}, bfgs)
+            .configure(|state| {
+                state
+                    .param(param)
+                    .inv_hessian(inv_hessian)
+                    .initial_evaluation(InitialEvaluation::Skip)
+                    .max_iters(1)
+            })
+            .run()
+            .unwrap();
+        assert_eq!(res.state.get_iter(), 1);
+        assert!(res.problem.counts["gradient_count"] >= 1);
+    }
+
+    /// f(x) = -x^T x, which violates the curvature condition everywhere
+    struct Concave {}
+
+    impl CostFunction for Concave {
+        type Param = Vec<f64>;
+        type Output = f64;
+
+        fn cost(&self, p: &Self::Param) -> Result<Self::Output, Error> {
+            Ok(-p.iter().map(|x| x * x).sum::<f64>())
+        }
+    }
+
+    impl Gradient for Concave {
+        type Param = Vec<f64>;
+        type Gradient = Vec<f64>;
+
+        fn gradient(&self, p: &Self::Param) -> Result<Self::Gradient, Error> {
+            Ok(p.iter().map(|x| -2.0 * x).collect())
+        }
+    }
+
+    fn concave_step(
+        policy: CurvatureUpdate<f64>,
+    ) -> (
+        IterState<Vec<f64>, Vec<f64>, (), Vec<Vec<f64>>, (), f64>,
+        Option<KV>,
+    ) {
+        // Takes a step of 0.25 along the search direction `[2, 2]`.
+        let mut bfgs: BFGS<_, f64> = BFGS::new(FlakyLineSearch::new(vec![]))
+            .with_curvature_update(policy)
+            .unwrap();
+        let mut problem = Problem::new(Concave {});
+        let state = IterState::new()
+            .param(vec![1.0f64, 1.0])
+            .inv_hessian(vec![vec![1.0f64, 0.0], vec![0.0, 1.0]]);
+        let (state, _) = bfgs.init(&mut problem, state).unwrap();
+        let (state, kv) = bfgs.next_iter(&mut problem, state).unwrap();
+        assert_eq!(state.param.as_ref().unwrap(), &vec![1.5, 1.5]);
+        (state, kv)
+    }
+
+    #[test]
+    fn test_with_curvature_update() {
+        #[derive(Eq, PartialEq, Debug, Clone, Copy)]
+        struct MyFakeLineSearch {}
+
+        let bfgs: BFGS<_, f64> = BFGS::new(MyFakeLineSearch {})
+            .with_curvature_update(CurvatureUpdate::Damped(0.2))
+            .unwrap();
+        assert_eq!(bfgs.curvature_update, CurvatureUpdate::Damped(0.2));
+
+        let res: Result<BFGS<_, f64>, _> =
+            BFGS::new(MyFakeLineSearch {}).with_curvature_update(CurvatureUpdate::Damped(1.0));
+        assert_error!(
+            res,
+            ArgminError,
+            "Invalid parameter: \"`BFGS`: damping threshold must be in (0, 1).\""
+        );
+    }
+
+    #[test]
+    fn test_curvature_update_skip() {
+        let (state, kv) = concave_step(CurvatureUpdate::Skip);
+        assert_eq!(
+            state.inv_hessian.unwrap(),
+            vec![vec![1.0, 0.0], vec![0.0, 1.0]]
+        );
+        assert_eq!(kv.unwrap(), kv!("update_skipped" => true;));
+    }
+
+    #[test]
+    fn test_curvature_update_damped() {
+        let (state, kv) = concave_step(CurvatureUpdate::Damped(0.2));
+        let kv = kv.unwrap();
+        // s = [0.5, 0.5], y = [-1, -1], B s = s: theta = 0.8 * 0.5 / 1.5
+        assert_relative_eq!(
+            kv.get("damping").unwrap().get_float().unwrap(),
+            0.4 / 1.5,
+            epsilon = f64::EPSILON
+        );
+        assert_eq!(kv.get("update_skipped"), Some(&KvValue::Bool(false)));
+        // The approximation remains positive definite
+        let h = state.inv_hessian.unwrap();
+        assert!(h[0][0] > 0.0);
+        assert!(h[0][0] * h[1][1] - h[0][1] * h[1][0] > 0.0);
+
+        // Without damping, the approximation becomes indefinite
+        let (state, kv) = concave_step(CurvatureUpdate::Always);
+        assert!(kv.is_none());
+        let h = state.inv_hessian.unwrap();
+        assert!(h[0][0] * h[1][1] - h[0][1] * h[1][0] < 0.0 || h[0][0] < 0.0);
+    }
+
+    #[test]
+    fn test_initial_scaling() {
+        // f(x) = 50 x^T x, x0 = [1, 1], H0 = I and a step of 0.25 along `-H0 g = [-100, -100]`
+        struct Quadratic {}
+
+        impl CostFunction for Quadratic {
+            type Param = Vec<f64>;
+            type Output = f64;
+
+            fn cost(&self, p: &Self::Param) -> Result<Self::Output, Error> {
+                Ok(50.0 * p.iter().map(|x| x * x).sum::<f64>())
+            }
+        }
+
+        impl Gradient for Quadratic {
+            type Param = Vec<f64>;
+            type Gradient = Vec<f64>;
+
+            fn gradient(&self, p: &Self::Param) -> Result<Self::Gradient, Error> {
+                Ok(p.iter().map(|x| 100.0 * x).collect())
+            }
+        }
+
+        let step = |scaling| {
+            let mut bfgs: BFGS<_, f64> =
+                BFGS::new(FlakyLineSearch::new(vec![])).with_initial_scaling(scaling);
+            let mut problem = Problem::new(Quadratic {});
+            let state = IterState::new()
+                .param(vec![1.0f64, 1.0])
+                .inv_hessian(vec![vec![1.0f64, 0.0], vec![0.0, 1.0]]);
+            let (state, _) = bfgs.init(&mut problem, state).unwrap();
+            let (state, _) = bfgs.next_iter(&mut problem, state).unwrap();
+            state.inv_hessian.unwrap()
+        };
+
+        // s = [-25, -25], y = [-2500, -2500]: gamma = 0.01, which equals the inverse curvature.
+        // The update therefore leaves the scaled approximation unchanged.
+        let h = step(true);
+        assert_relative_eq!(h[0][0], 0.01, epsilon = 1e-12);
+        assert_relative_eq!(h[0][1], 0.0, epsilon = 1e-12);
+        assert_relative_eq!(h[1][1], 0.01, epsilon = 1e-12);
+
+        // Without scaling, the curvature perpendicular to `s` is still that of `H0 = I`.
+        let h = step(false);
+        assert_relative_eq!(h[0][0] - h[0][1], 1.0, epsilon = 1e-12);
+        assert_relative_eq!(h[0][0] + h[0][1], 0.01, epsilon = 1e-12);
+    }
 }
@@ -0,0 +1,170 @@
+// Copyright 2018-2024 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use crate::core::{ArgminFloat, KV};
+use argmin_math::{ArgminAdd, ArgminDot, ArgminMul};
+#[cfg(feature = "serde1")]
+use serde::{Deserialize, Serialize};
+
+/// # Curvature update policy
+///
+/// The BFGS update of the (inverse) Hessian approximation with the step `s = x_{k+1} - x_k` and
+/// the change of the gradient `y = g_{k+1} - g_k` only preserves positive definiteness if the
+/// curvature condition `s^T y > 0` holds. This is guaranteed by line searches which enforce the
+/// Wolfe conditions, but not by others (such as a backtracking line search) or on nonconvex
+/// problems. This policy determines how [`BFGS`](`crate::solver::quasinewton::BFGS`) and
+/// [`LBFGS`](`crate::solver::quasinewton::LBFGS`) deal with pairs which violate the curvature
+/// condition.
+///
+/// ## Reference
+///
+/// Jorge Nocedal and Stephen J. Wright (2006). Numerical Optimization.
+/// Springer. ISBN 0-387-30303-0. (Procedure 18.2)
+///
+/// # Example
+///
+/// ```
+/// # use argmin::solver::quasinewton::CurvatureUpdate;
+/// let policy: CurvatureUpdate<f64> = CurvatureUpdate::default();
+/// assert_eq!(policy, CurvatureUpdate::Always);
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+pub enum CurvatureUpdate<F> {
+    /// Always update the approximation (default)
+    #[default]
+    Always,
+    /// Skip the update if `s^T y <= 0`. Reports `update_skipped` in each iteration.
+    Skip,
+    /// Powell damping with threshold `c` (typically `0.2`): If `s^T y < c s^T B s`, `y` is
+    /// replaced by `theta y + (1 - theta) B s` with `theta` chosen such that `s^T y = c s^T B s`.
+    /// `B` denotes the current Hessian approximation. Reports the factor `theta` as `damping`
+    /// and `update_skipped` in each iteration. The update is skipped if `s^T B s <= 0`.
+    ///
+    /// `c` must be in `(0, 1)`.
+    Damped(F),
+}
+
+impl<F> CurvatureUpdate<F>
+where
+    F: ArgminFloat,
+{
+    /// Returns whether the parameters of the policy are valid.
+    pub(crate) fn is_valid(&self) -> bool {
+        match *self {
+            CurvatureUpdate::Damped(c) => c > float!(0.0) && c < float!(1.0),
+            CurvatureUpdate::Always | CurvatureUpdate::Skip => true,
+        }
+    }
+
+    /// Returns the vector `y` which is to be used in the update, or `None` if the update is to be
+    /// skipped, along with the key-value pairs to be reported.
+    ///
+    /// `bs` computes the product of the current Hessian approximation and `s` and is only called
+    /// for [`CurvatureUpdate::Damped`].
+    pub(crate) fn correct<P, G>(&self, s: &P, y: G, bs: impl FnOnce() -> G) -> (Option<G>, KV)
+    where
+        G: ArgminDot<P, F> + ArgminAdd<G, G> + ArgminMul<F, G>,
+    {
+        match *self {
+            CurvatureUpdate::Always => (Some(y), KV::new()),
+            CurvatureUpdate::Skip => {
+                let sy = y.dot(s);
+                if sy > float!(0.0) {
+                    (Some(y), kv!("update_skipped" => false;))
+                } else {
+                    (None, kv!("update_skipped" => true;))
+                }
+            }
+            CurvatureUpdate::Damped(c) => {
+                let bs = bs();
+                let sbs = bs.dot(s);
+                // Damping cannot restore the curvature condition if the approximation is not
+                // positive definite along `s`.
+                if sbs.is_nan() || sbs <= float!(0.0) {
+                    return (None, kv!("update_skipped" => true;));
+                }
+                let sy = y.dot(s);
+                if sy >= c * sbs {
+                    return (
+                        Some(y),
+                        kv!("damping" => float!(1.0); "update_skipped" => false;),
+                    );
+                }
+                let theta = (float!(1.0) - c) * sbs / (sbs - sy);
+                let y = y.mul(&theta).add(&bs.mul(&(float!(1.0) - theta)));
+                (Some(y), kv!("damping" => theta; "update_skipped" => false;))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_valid() {
+        assert!(CurvatureUpdate::<f64>::Always.is_valid());
+        assert!(CurvatureUpdate::<f64>::Skip.is_valid());
+        assert!(CurvatureUpdate::Damped(0.2f64).is_valid());
+        for c in [0.0f64, 1.0, -0.2, f64::NAN] {
+            assert!(!CurvatureUpdate::Damped(c).is_valid());
+        }
+    }
+
+    #[test]
+    fn test_always() {
+        let (y, kv) = CurvatureUpdate::<f64>::Always.correct(
+            &vec![1.0f64, 0.0],
+            vec![-1.0f64, 0.0],
+            || unreachable!(),
+        );
+        assert_eq!(y, Some(vec![-1.0, 0.0]));
+        assert_eq!(kv, KV::new());
+    }
+
+    #[test]
+    fn test_skip() {
+        let s = vec![1.0f64, 0.0];
+        let (y, kv) =
+            CurvatureUpdate::<f64>::Skip.correct(&s, vec![2.0f64, 1.0], || unreachable!());
+        assert_eq!(y, Some(vec![2.0, 1.0]));
+        assert_eq!(kv, kv!("update_skipped" => false;));
+
+        let (y, kv) =
+            CurvatureUpdate::<f64>::Skip.correct(&s, vec![0.0f64, 1.0], || unreachable!());
+        assert!(y.is_none());
+        assert_eq!(kv, kv!("update_skipped" => true;));
+    }
+
+    #[test]
+    fn test_damped() {
+        let s = vec![1.0f64, 0.0];
+        let bs = || vec![2.0f64, 0.0];
+
+        // Curvature condition is satisfied sufficiently
+        let (y, kv) = CurvatureUpdate::Damped(0.2).correct(&s, vec![1.0f64, 3.0], bs);
+        assert_eq!(y, Some(vec![1.0, 3.0]));
+        assert_eq!(kv, kv!("damping" => 1.0; "update_skipped" => false;));
+
+        // s^T y = -2, s^T B s = 2: theta = 0.5 * 2 / 4 = 0.25
+        let (y, kv) = CurvatureUpdate::Damped(0.5).correct(&s, vec![-2.0f64, 4.0], bs);
+        let y = y.unwrap();
+        assert_eq!(y, vec![1.0, 1.0]);
+        // The curvature condition holds with equality for the damped `y`
+        let sy: f64 = y.dot(&s);
+        assert_eq!(sy.to_ne_bytes(), 1.0f64.to_ne_bytes());
+        assert_eq!(kv, kv!("damping" => 0.25; "update_skipped" => false;));
+
+        // Approximation is not positive definite along `s`
+        let (y, kv) =
+            CurvatureUpdate::Damped(0.2).correct(&s, vec![1.0f64, 3.0], || vec![-1.0f64, 0.0]);
+        assert!(y.is_none());
+        assert_eq!(kv, kv!("update_skipped" => true;));
+    }
+}
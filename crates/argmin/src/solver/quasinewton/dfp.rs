@@ -6,8 +6,8 @@
 // copied, modified, or distributed except according to those terms.
 
 use crate::core::{
-    ArgminFloat, CostFunction, Error, Executor, Gradient, IterState, LineSearch,
-    OptimizationResult, Problem, Solver, TerminationReason, TerminationStatus, KV,
+    ArgminFloat, CostFunction, Error, Executor, Gradient, IterState, LineSearch, NestedTelemetry,
+    OptimizationResult, Problem, Solver, State, TerminationReason, TerminationStatus, KV,
 };
 use argmin_math::{ArgminAdd, ArgminDot, ArgminL2Norm, ArgminMul, ArgminSub};
 #[cfg(feature = "serde1")]
@@ -46,6 +46,9 @@ pub struct DFP<L, F> {
     linesearch: L,
     /// Tolerance for the stopping criterion based on the change of the norm on the gradient
     tol_grad: F,
+    /// Telemetry of the line search
+    #[cfg_attr(feature = "serde1", serde(default))]
+    inner_telemetry: NestedTelemetry,
 }
 
 impl<L, F> DFP<L, F>
@@ -65,9 +68,30 @@ where
         DFP {
             linesearch,
             tol_grad: F::epsilon().sqrt(),
+            inner_telemetry: NestedTelemetry::new(),
         }
     }
 
+    /// Forward the telemetry of the line search to observers
+    ///
+    /// The key-value pairs returned by the line search as well as its number of iterations and
+    /// its termination reason are forwarded with keys prefixed by `prefix` (for instance
+    /// `linesearch.iters`). Independent of this setting, the line search iterations are counted
+    /// as `linesearch_iter_count`. See [`NestedTelemetry`] for details.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::quasinewton::DFP;
+    /// # let linesearch = ();
+    /// let solver = DFP::<_, f64>::new(linesearch).with_inner_telemetry("linesearch");
+    /// ```
+    #[must_use]
+    pub fn with_inner_telemetry<T: AsRef<str>>(mut self, prefix: T) -> Self {
+        self.inner_telemetry = NestedTelemetry::forward(prefix);
+        self
+    }
+
     /// The algorithm stops if the norm of the gradient is below `tol_grad`.
     ///
     /// The provided value must be non-negative. Defaults to `sqrt(EPSILON)`.
@@ -95,6 +119,46 @@ where
     }
 }
 
+/// Serializable configuration of [`DFP`]
+///
+/// Options which are `None` keep the defaults of [`DFP`]. The configuration is validated when
+/// the solver is constructed via [`build`](`DFPConfig::build`).
+///
+/// # Example
+///
+/// ```
+/// # use argmin::solver::quasinewton::{DFP, DFPConfig};
+/// # use argmin::core::Error;
+/// # fn main() -> Result<(), Error> {
+/// # let linesearch = ();
+/// let config = DFPConfig {
+///     tolerance_grad: Some(1e-6),
+/// };
+/// let dfp: DFP<_, f64> = config.build(linesearch)?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize), serde(default))]
+pub struct DFPConfig<F> {
+    /// See [`DFP::with_tolerance_grad`]
+    pub tolerance_grad: Option<F>,
+}
+
+impl<F> DFPConfig<F>
+where
+    F: ArgminFloat,
+{
+    /// Validates the configuration and constructs a [`DFP`] solver using the given line search.
+    pub fn build<L>(&self, linesearch: L) -> Result<DFP<L, F>, Error> {
+        let mut solver = DFP::new(linesearch);
+        if let Some(tol_grad) = self.tolerance_grad {
+            solver = solver.with_tolerance_grad(tol_grad)?;
+        }
+        Ok(solver)
+    }
+}
+
 impl<O, L, P, G, H, F> Solver<O, IterState<P, G, (), H, (), F>> for DFP<L, F>
 where
     O: CostFunction<Param = P, Output = F> + Gradient<Param = P, Gradient = G>,
@@ -108,46 +172,68 @@ where
         "DFP"
     }
 
+    fn check_state(&self, state: &IterState<P, G, (), H, (), F>) -> Result<(), Error> {
+        if state.get_param().is_none() {
+            return Err(argmin_error!(
+                NotInitialized,
+                concat!(
+                    "`DFP` requires an initial parameter vector. ",
+                    "Please provide an initial guess via `Executor`s `configure` method."
+                )
+            ));
+        }
+        if state.get_inv_hessian().is_none() {
+            return Err(argmin_error!(
+                NotInitialized,
+                concat!(
+                    "`DFP` requires an initial inverse Hessian. ",
+                    "Please provide an initial guess via `Executor`s `configure` method."
+                )
+            ));
+        }
+        Ok(())
+    }
+
     fn init(
         &mut self,
         problem: &mut Problem<O>,
         mut state: IterState<P, G, (), H, (), F>,
     ) -> Result<(IterState<P, G, (), H, (), F>, Option<KV>), Error> {
+        self.check_state(&state)?;
         let param = state.take_param().ok_or_else(argmin_error_closure!(
-            NotInitialized,
-            concat!(
-                "`DFP` requires an initial parameter vector. ",
-                "Please provide an initial guess via `Executor`s `configure` method."
-            )
+            PotentialBug,
+            "`DFP`: Parameter vector in state not set."
         ))?;
 
         let inv_hessian = state.take_inv_hessian().ok_or_else(argmin_error_closure!(
-            NotInitialized,
-            concat!(
-                "`DFP` requires an initial inverse Hessian. ",
-                "Please provide an initial guess via `Executor`s `configure` method."
-            )
+            PotentialBug,
+            "`DFP`: Inverse Hessian in state not set."
         ))?;
 
+        let policy = state.get_initial_evaluation();
+
         let cost = state.get_cost();
-        let cost = if cost.is_infinite() {
-            problem.cost(&param)?
-        } else {
-            cost
-        };
+        let cost = policy
+            .evaluate_lazily((!cost.is_infinite()).then_some(cost), || {
+                problem.cost(&param)
+            })?
+            .unwrap_or(cost);
 
-        let grad = state
-            .take_gradient()
-            .map(Result::Ok)
-            .unwrap_or_else(|| problem.gradient(&param))?;
+        let grad = policy.evaluate_lazily(state.take_gradient(), || problem.gradient(&param))?;
 
+        let mut state = state.param(param).cost(cost).inv_hessian(inv_hessian);
+        if let Some(grad) = grad {
+            state = state.gradient(grad);
+        }
         Ok((
-            state
-                .param(param)
-                .cost(cost)
-                .gradient(grad)
-                .inv_hessian(inv_hessian),
-            None,
+            state,
+            Some(
+                kv!(
+                    "linesearch" => self.linesearch.name();
+                    "tolerance_grad" => self.tol_grad;
+                )
+                .with_prefix("config"),
+            ),
         ))
     }
 
@@ -162,10 +248,11 @@ where
         ))?;
         let cost = state.get_cost();
 
-        let prev_grad = state.take_gradient().ok_or_else(argmin_error_closure!(
-            PotentialBug,
-            "`DFP`: Gradient in state not set."
-        ))?;
+        let prev_grad = state
+            .take_gradient()
+            .map(Result::Ok)
+            // Missing if the initial evaluation was skipped
+            .unwrap_or_else(|| problem.gradient(&param))?;
 
         let inv_hessian = state.take_inv_hessian().ok_or_else(argmin_error_closure!(
             PotentialBug,
@@ -176,19 +263,21 @@ where
 
         self.linesearch.search_direction(p);
 
-        let OptimizationResult {
-            problem: line_problem,
-            state: mut linesearch_state,
-            ..
-        } = Executor::new(problem.take_problem().unwrap(), self.linesearch.clone())
+        let (
+            OptimizationResult {
+                problem: line_problem,
+                state: mut linesearch_state,
+                ..
+            },
+            linesearch_kv,
+        ) = Executor::new(problem.take_problem().unwrap(), self.linesearch.clone())
             .configure(|config| {
                 config
                     .param(param.clone())
                     .gradient(prev_grad.clone())
                     .cost(cost)
             })
-            .ctrlc(false)
-            .run()?;
+            .run_nested()?;
 
         let xk1 = linesearch_state
             .take_param()
@@ -201,6 +290,12 @@ where
 
         // take care of function eval counts
         problem.consume_problem(line_problem);
+        let linesearch_kv = self.inner_telemetry.record(
+            problem,
+            "linesearch_iter_count",
+            &linesearch_state,
+            linesearch_kv,
+        );
 
         let grad = problem.gradient(&xk1)?;
         let yk = grad.sub(&prev_grad);
@@ -224,12 +319,16 @@ where
                 .cost(next_cost)
                 .gradient(grad)
                 .inv_hessian(inv_hessian),
-            None,
+            linesearch_kv,
         ))
     }
 
     fn terminate(&mut self, state: &IterState<P, G, (), H, (), F>) -> TerminationStatus {
-        if state.get_gradient().unwrap().l2_norm() < self.tol_grad {
+        // The gradient is missing after `init` if the initial evaluation was skipped
+        if state
+            .get_gradient()
+            .is_some_and(|grad| grad.l2_norm() < self.tol_grad)
+        {
             return TerminationStatus::Terminated(TerminationReason::SolverConverged);
         }
         TerminationStatus::NotTerminated
@@ -256,7 +355,9 @@ mod tests {
         let DFP {
             linesearch,
             tol_grad,
+            inner_telemetry,
         } = dfp;
+        assert_eq!(inner_telemetry, NestedTelemetry::new());
 
         assert_eq!(linesearch, MyFakeLineSearch {});
         assert_eq!(tol_grad.to_ne_bytes(), f64::EPSILON.sqrt().to_ne_bytes());
@@ -289,6 +390,28 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_config_build() {
+        #[derive(Eq, PartialEq, Debug, Clone, Copy)]
+        struct MyFakeLineSearch {}
+
+        let config = DFPConfig {
+            tolerance_grad: Some(1e-4),
+        };
+        let dfp: DFP<_, f64> = config.build(MyFakeLineSearch {}).unwrap();
+        assert_eq!(dfp.tol_grad.to_ne_bytes(), 1e-4f64.to_ne_bytes());
+
+        let config = DFPConfig {
+            tolerance_grad: Some(-1.0),
+        };
+        let res: Result<DFP<_, f64>, _> = config.build(MyFakeLineSearch {});
+        assert_error!(
+            res,
+            ArgminError,
+            "Invalid parameter: \"`DFP`: gradient tolerance must be >= 0.\""
+        );
+    }
+
     #[test]
     fn test_init() {
         let linesearch = MoreThuenteLineSearch::new().with_c(1e-4, 0.9).unwrap();
@@ -333,7 +456,14 @@ mod tests {
         let problem = TestProblem::new();
         let (mut state_out, kv) = dfp.init(&mut Problem::new(problem), state).unwrap();
 
-        assert!(kv.is_none());
+        assert_eq!(
+            kv.unwrap(),
+            kv!(
+                "linesearch" => "More-Thuente Line search";
+                "tolerance_grad" => f64::EPSILON.sqrt();
+            )
+            .with_prefix("config")
+        );
 
         let s_param = state_out.take_param().unwrap();
 
@@ -377,7 +507,7 @@ mod tests {
         let problem = TestProblem::new();
         let (state_out, kv) = dfp.init(&mut Problem::new(problem), state).unwrap();
 
-        assert!(kv.is_none());
+        assert!(kv.is_some());
 
         assert_eq!(state_out.get_cost().to_ne_bytes(), 1234.0f64.to_ne_bytes())
     }
@@ -400,7 +530,7 @@ mod tests {
         let problem = TestProblem::new();
         let (mut state_out, kv) = dfp.init(&mut Problem::new(problem), state).unwrap();
 
-        assert!(kv.is_none());
+        assert!(kv.is_some());
 
         let s_grad = state_out.take_gradient().unwrap();
 
@@ -6,12 +6,15 @@
 // copied, modified, or distributed except according to those terms.
 
 use crate::core::{
-    ArgminFloat, CostFunction, Error, Executor, Gradient, IterState, LineSearch,
-    OptimizationResult, Problem, Solver, State, TerminationReason, TerminationStatus, KV,
+    ArgminFloat, CostFunction, Error, Executor, Gradient, IterState, LineSearch, NestedTelemetry,
+    OptimizationResult, Problem, Solver, State, TerminationReason, TerminationStatus, Workspace,
+    KV,
 };
+use crate::solver::linesearch::LineSearchRecovery;
+use crate::solver::quasinewton::CurvatureUpdate;
 use argmin_math::{
-    ArgminAdd, ArgminDot, ArgminL1Norm, ArgminL2Norm, ArgminMinMax, ArgminMul, ArgminSignum,
-    ArgminSub, ArgminZeroLike,
+    ArgminAdd, ArgminAxpy, ArgminBound, ArgminDot, ArgminL1Norm, ArgminL2Norm, ArgminMinMax,
+    ArgminMul, ArgminScal, ArgminSignum, ArgminSub, ArgminZeroLike,
 };
 #[cfg(feature = "serde1")]
 use serde::{Deserialize, Serialize};
@@ -53,6 +56,23 @@ where
 /// other. If the change is below this tolerance (default: `EPSILON`), the algorithm stops. This
 /// parameter can be set via [`with_tolerance_cost`](`LBFGS::with_tolerance_cost`).
 ///
+/// By default, the algorithm terminates if the line search fails. Alternative ways of recovering
+/// from such failures can be chosen via
+/// [`with_linesearch_recovery`](`LBFGS::with_linesearch_recovery`). Pairs which violate the
+/// curvature condition can be skipped or damped via
+/// [`with_curvature_update`](`LBFGS::with_curvature_update`).
+///
+/// In each iteration, the initial inverse Hessian approximation `H0` is scaled with
+/// `gamma = s^T y / y^T y` of the most recent pair. This can be disabled via
+/// [`with_initial_scaling`](`LBFGS::with_initial_scaling`).
+///
+/// If [`bounds`](`IterState::bounds`) are set on the state, entries of the search direction which
+/// point out of active bounds are dropped and the step length of the line search is limited such
+/// that the cost function is not evaluated outside of the bounds. If the resulting direction is
+/// not a descent direction, the projected steepest descent direction is used instead. Once no
+/// feasible descent direction is left, the solver terminates with
+/// [`TerminationReason::NoFeasibleDirection`].
+///
 /// ## Orthant-Wise Limited-memory Quasi-Newton (OWL-QN) method
 ///
 /// OWL-QN is a method that adapts L-BFGS to L1-regularization. The original L-BFGS requires a
@@ -92,6 +112,29 @@ pub struct LBFGS<L, P, G, F> {
     l1_coeff: Option<F>,
     /// Unregularized gradient used for calculation of `y`.
     l1_prev_unreg_grad: Option<G>,
+    /// Telemetry of the line search
+    #[cfg_attr(feature = "serde1", serde(default))]
+    inner_telemetry: NestedTelemetry,
+    /// Reaction to failures of the line search
+    #[cfg_attr(feature = "serde1", serde(default))]
+    linesearch_recovery: LineSearchRecovery<F>,
+    /// Treatment of pairs which violate the curvature condition
+    #[cfg_attr(feature = "serde1", serde(default))]
+    curvature_update: CurvatureUpdate<F>,
+    /// Scale the initial inverse Hessian approximation in each iteration
+    #[cfg_attr(feature = "serde1", serde(default = "default_initial_scaling"))]
+    initial_scaling: bool,
+    /// Scratch buffers of the two-loop recursion
+    #[cfg_attr(feature = "serde1", serde(skip))]
+    workspace: Workspace<G>,
+    /// Scratch buffers for the coefficients `alpha` and `rho` of the two-loop recursion
+    #[cfg_attr(feature = "serde1", serde(skip))]
+    coefficients: Workspace<Vec<F>>,
+}
+
+#[cfg(feature = "serde1")]
+fn default_initial_scaling() -> bool {
+    true
 }
 
 impl<L, P, G, F> LBFGS<L, P, G, F>
@@ -117,9 +160,35 @@ where
             tol_cost: F::epsilon(),
             l1_coeff: None,
             l1_prev_unreg_grad: None,
+            inner_telemetry: NestedTelemetry::new(),
+            linesearch_recovery: LineSearchRecovery::Stop,
+            curvature_update: CurvatureUpdate::Always,
+            initial_scaling: true,
+            workspace: Workspace::new(),
+            coefficients: Workspace::new(),
         }
     }
 
+    /// Forward the telemetry of the line search to observers
+    ///
+    /// The key-value pairs returned by the line search as well as its number of iterations and
+    /// its termination reason are forwarded with keys prefixed by `prefix` (for instance
+    /// `linesearch.iters`). Independent of this setting, the line search iterations are counted
+    /// as `linesearch_iter_count`. See [`NestedTelemetry`] for details.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::quasinewton::LBFGS;
+    /// # let linesearch = ();
+    /// let solver = LBFGS::<_, Vec<f64>, Vec<f64>, f64>::new(linesearch, 5).with_inner_telemetry("linesearch");
+    /// ```
+    #[must_use]
+    pub fn with_inner_telemetry<T: AsRef<str>>(mut self, prefix: T) -> Self {
+        self.inner_telemetry = NestedTelemetry::forward(prefix);
+        self
+    }
+
     /// The algorithm stops if the norm of the gradient is below `tol_grad`.
     ///
     /// The provided value must be non-negative. Defaults to `sqrt(EPSILON)`.
@@ -196,6 +265,165 @@ where
         self.l1_coeff = Some(l1_coeff);
         Ok(self)
     }
+
+    /// Sets the policy for recovering from failures of the line search.
+    ///
+    /// Defaults to [`LineSearchRecovery::Stop`], which terminates the run. The step length of
+    /// [`LineSearchRecovery::SmallStep`] must be `> 0.0`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::quasinewton::LBFGS;
+    /// # use argmin::solver::linesearch::LineSearchRecovery;
+    /// # use argmin::core::Error;
+    /// # fn main() -> Result<(), Error> {
+    /// # let linesearch = ();
+    /// let lbfgs: LBFGS<_, Vec<f64>, Vec<f64>, f64> =
+    ///     LBFGS::new(linesearch, 3).with_linesearch_recovery(LineSearchRecovery::ResetMemory)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_linesearch_recovery(
+        mut self,
+        policy: LineSearchRecovery<F>,
+    ) -> Result<Self, Error> {
+        if let LineSearchRecovery::SmallStep(step) = policy {
+            if step.is_nan() || step <= float!(0.0) {
+                return Err(argmin_error!(
+                    InvalidParameter,
+                    "`L-BFGS`: step length of line search recovery must be > 0."
+                ));
+            }
+        }
+        self.linesearch_recovery = policy;
+        Ok(self)
+    }
+
+    /// Sets how pairs `(s, y)` which violate the curvature condition `s^T y > 0` are treated.
+    ///
+    /// Skipped pairs are not stored in the history. Defaults to [`CurvatureUpdate::Always`]. The
+    /// threshold of [`CurvatureUpdate::Damped`] must be in `(0, 1)`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::quasinewton::{CurvatureUpdate, LBFGS};
+    /// # use argmin::core::Error;
+    /// # fn main() -> Result<(), Error> {
+    /// # let linesearch = ();
+    /// let lbfgs: LBFGS<_, Vec<f64>, Vec<f64>, f64> =
+    ///     LBFGS::new(linesearch, 3).with_curvature_update(CurvatureUpdate::Skip)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_curvature_update(mut self, policy: CurvatureUpdate<F>) -> Result<Self, Error> {
+        if !policy.is_valid() {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "`L-BFGS`: damping threshold must be in (0, 1)."
+            ));
+        }
+        self.curvature_update = policy;
+        Ok(self)
+    }
+
+    /// Enables or disables the scaling of the initial inverse Hessian approximation `H0 = gamma *
+    /// I` with `gamma = s^T y / y^T y` of the most recent pair (Nocedal & Wright, eq. 7.20).
+    ///
+    /// Without scaling, `gamma = 1` is used, which leads to poorly scaled steps on badly scaled
+    /// problems. Defaults to `true`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::quasinewton::LBFGS;
+    /// # let linesearch = ();
+    /// let lbfgs: LBFGS<_, Vec<f64>, Vec<f64>, f64> =
+    ///     LBFGS::new(linesearch, 3).with_initial_scaling(false);
+    /// ```
+    #[must_use]
+    pub fn with_initial_scaling(mut self, scaling: bool) -> Self {
+        self.initial_scaling = scaling;
+        self
+    }
+}
+
+/// Serializable configuration of [`LBFGS`]
+///
+/// Options which are `None` keep the defaults of [`LBFGS`]. Like in [`LBFGS::new`], there is no
+/// default for the history size `m`, which must be set explicitly. The configuration is validated
+/// when the solver is constructed via [`build`](`LBFGSConfig::build`).
+///
+/// # Example
+///
+/// ```
+/// # use argmin::solver::quasinewton::{LBFGS, LBFGSConfig};
+/// # use argmin::solver::linesearch::MoreThuenteLineSearch;
+/// # use argmin::core::Error;
+/// # fn main() -> Result<(), Error> {
+/// let config = LBFGSConfig {
+///     m: 7,
+///     tolerance_grad: Some(1e-6),
+///     ..Default::default()
+/// };
+/// let linesearch: MoreThuenteLineSearch<Vec<f64>, Vec<f64>, f64> = MoreThuenteLineSearch::new();
+/// let lbfgs: LBFGS<_, Vec<f64>, Vec<f64>, f64> = config.build(linesearch)?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize), serde(default))]
+pub struct LBFGSConfig<F> {
+    /// History size, must be `> 0` (see [`LBFGS::new`])
+    pub m: usize,
+    /// See [`LBFGS::with_tolerance_grad`]
+    pub tolerance_grad: Option<F>,
+    /// See [`LBFGS::with_tolerance_cost`]
+    pub tolerance_cost: Option<F>,
+    /// See [`LBFGS::with_l1_regularization`]
+    pub l1_coeff: Option<F>,
+    /// See [`LBFGS::with_linesearch_recovery`]
+    pub linesearch_recovery: Option<LineSearchRecovery<F>>,
+    /// See [`LBFGS::with_curvature_update`]
+    pub curvature_update: Option<CurvatureUpdate<F>>,
+    /// See [`LBFGS::with_initial_scaling`]
+    pub initial_scaling: Option<bool>,
+}
+
+impl<F> LBFGSConfig<F>
+where
+    F: ArgminFloat,
+{
+    /// Validates the configuration and constructs an [`LBFGS`] solver using the given line search.
+    pub fn build<L, P, G>(&self, linesearch: L) -> Result<LBFGS<L, P, G, F>, Error> {
+        if self.m == 0 {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "`L-BFGS`: history size `m` must be > 0."
+            ));
+        }
+        let mut solver = LBFGS::new(linesearch, self.m);
+        if let Some(tol_grad) = self.tolerance_grad {
+            solver = solver.with_tolerance_grad(tol_grad)?;
+        }
+        if let Some(tol_cost) = self.tolerance_cost {
+            solver = solver.with_tolerance_cost(tol_cost)?;
+        }
+        if let Some(l1_coeff) = self.l1_coeff {
+            solver = solver.with_l1_regularization(l1_coeff)?;
+        }
+        if let Some(policy) = self.linesearch_recovery {
+            solver = solver.with_linesearch_recovery(policy)?;
+        }
+        if let Some(policy) = self.curvature_update {
+            solver = solver.with_curvature_update(policy)?;
+        }
+        if let Some(scaling) = self.initial_scaling {
+            solver = solver.with_initial_scaling(scaling);
+        }
+        Ok(solver)
+    }
 }
 
 /// Wrapper problem for supporting constrained line search.
@@ -310,13 +538,16 @@ where
         + ArgminAdd<P, P>
         + ArgminAdd<F, P>
         + ArgminDot<G, F>
+        + ArgminDot<P, F>
         + ArgminMul<F, P>
         + ArgminMul<P, P>
         + ArgminMul<G, P>
         + ArgminL1Norm<F>
         + ArgminSignum
         + ArgminZeroLike
-        + ArgminMinMax,
+        + ArgminMinMax
+        + ArgminAxpy<P, F>
+        + ArgminBound<P, F>,
     G: Clone
         + ArgminL2Norm<F>
         + ArgminSub<G, G>
@@ -326,6 +557,9 @@ where
         + ArgminDot<P, F>
         + ArgminMul<F, G>
         + ArgminMul<F, P>
+        + ArgminAxpy<G, F>
+        + ArgminAxpy<P, F>
+        + ArgminScal<F>
         + ArgminZeroLike
         + ArgminMinMax,
     L: Clone
@@ -337,36 +571,68 @@ where
         "L-BFGS"
     }
 
+    fn check_state(&self, state: &IterState<P, G, (), (), (), F>) -> Result<(), Error> {
+        if state.get_param().is_none() {
+            return Err(argmin_error!(
+                NotInitialized,
+                concat!(
+                    "`L-BFGS` requires an initial parameter vector. ",
+                    "Please provide an initial guess via `Executor`s `configure` method."
+                )
+            ));
+        }
+        Ok(())
+    }
+
     fn init(
         &mut self,
         problem: &mut Problem<O>,
         mut state: IterState<P, G, (), (), (), F>,
     ) -> Result<(IterState<P, G, (), (), (), F>, Option<KV>), Error> {
+        self.check_state(&state)?;
         let param = state.take_param().ok_or_else(argmin_error_closure!(
-            NotInitialized,
-            concat!(
-                "`L-BFGS` requires an initial parameter vector. ",
-                "Please provide an initial guess via `Executor`s `configure` method."
-            )
+            PotentialBug,
+            "`L-BFGS`: Parameter vector in state not set."
         ))?;
 
-        let cost = state.get_cost();
-        let cost = if cost.is_infinite() {
-            if let Some(l1_coeff) = self.l1_coeff {
-                problem.cost(&param)? + l1_coeff * param.l1_norm()
-            } else {
-                problem.cost(&param)?
-            }
-        } else {
-            cost
-        };
-
-        let grad = state
-            .take_gradient()
-            .map(Result::Ok)
-            .unwrap_or_else(|| problem.gradient(&param))?;
+        let policy = state.get_initial_evaluation();
 
-        Ok((state.param(param).cost(cost).gradient(grad), None))
+        let cost = state.get_cost();
+        let cost = policy
+            .evaluate_lazily((!cost.is_infinite()).then_some(cost), || {
+                if let Some(l1_coeff) = self.l1_coeff {
+                    Ok(problem.cost(&param)? + l1_coeff * param.l1_norm())
+                } else {
+                    problem.cost(&param)
+                }
+            })?
+            .unwrap_or(cost);
+
+        let grad = policy.evaluate_lazily(state.take_gradient(), || problem.gradient(&param))?;
+
+        let mut state = state.param(param).cost(cost);
+        if let Some(grad) = grad {
+            state = state.gradient(grad);
+        }
+        let mut config = kv!(
+            "linesearch" => self.linesearch.name();
+            "m" => self.m as u64;
+            "tolerance_grad" => self.tol_grad;
+            "tolerance_cost" => self.tol_cost;
+        );
+        if let Some(l1_coeff) = self.l1_coeff {
+            config.insert("l1_coeff", l1_coeff.into());
+        }
+        config.insert(
+            "linesearch_recovery",
+            format!("{:?}", self.linesearch_recovery).into(),
+        );
+        config.insert(
+            "curvature_update",
+            format!("{:?}", self.curvature_update).into(),
+        );
+        config.insert("initial_scaling", self.initial_scaling.into());
+        Ok((state, Some(config.with_prefix("config"))))
     }
 
     fn next_iter(
@@ -381,10 +647,11 @@ where
         let cur_cost = state.get_cost();
 
         // If L1 regularization is enabled, the state contains pseudo gradient.
-        let mut prev_grad = state.take_gradient().ok_or_else(argmin_error_closure!(
-            PotentialBug,
-            "`L-BFGS`: Gradient in state not set."
-        ))?;
+        let mut prev_grad = state
+            .take_gradient()
+            .map(Result::Ok)
+            // Missing if the initial evaluation was skipped
+            .unwrap_or_else(|| problem.gradient(&param))?;
         if let Some(l1_coeff) = self.l1_coeff {
             if self.l1_prev_unreg_grad.is_none() {
                 self.l1_prev_unreg_grad = Some(prev_grad.clone());
@@ -392,119 +659,239 @@ where
             }
         }
 
-        let gamma: F = if let (Some(sk), Some(yk)) = (self.s.back(), self.y.back()) {
-            sk.dot(yk) / yk.dot(yk)
-        } else {
-            float!(1.0)
+        let gamma: F = match (self.s.back(), self.y.back()) {
+            (Some(sk), Some(yk)) if self.initial_scaling => sk.dot(yk) / yk.dot(yk),
+            _ => float!(1.0),
         };
 
-        // L-BFGS two-loop recursion
-        #[allow(clippy::redundant_clone)]
-        let mut q = prev_grad.clone();
+        // L-BFGS two-loop recursion. The intermediate vector `q`, which becomes `r = H g`, and the
+        // coefficients `alpha` and `rho` are updated in place in buffers which are reused across
+        // iterations. The coefficient buffers are resized to the current number of pairs.
+        let mut q = self.workspace.copy_of(&prev_grad);
         let cur_m = self.s.len();
-        let mut alpha: Vec<F> = vec![float!(0.0); cur_m];
-        let mut rho: Vec<F> = vec![float!(0.0); cur_m];
+        let mut alpha = self.coefficients.take().unwrap_or_default();
+        let mut rho = self.coefficients.take().unwrap_or_default();
+        alpha.resize(cur_m, float!(0.0));
+        rho.resize(cur_m, float!(0.0));
         for (i, (sk, yk)) in self.s.iter().rev().zip(self.y.iter().rev()).enumerate() {
             let yksk: F = yk.dot(sk);
             let rho_t = float!(1.0) / yksk;
             let skq: F = sk.dot(&q);
             let alpha_t = skq.mul(rho_t);
-            q = q.sub(&yk.mul(&alpha_t));
+            q.axpy(&(-alpha_t), yk);
             rho[cur_m - i - 1] = rho_t;
             alpha[cur_m - i - 1] = alpha_t;
         }
-        let mut r: P = q.mul(&gamma);
+        q.scal(&gamma);
         for (i, (sk, yk)) in self.s.iter().zip(self.y.iter()).enumerate() {
-            let beta: F = yk.dot(&r);
+            let beta: F = yk.dot(&q);
             let beta = beta.mul(rho[i]);
-            r = r.add(&sk.mul(&(alpha[i] - beta)));
+            q.axpy(&(alpha[i] - beta), sk);
         }
+        self.coefficients.put(alpha);
+        self.coefficients.put(rho);
+        let mut r = q;
 
         let mut line_problem = LineSearchProblem::new(problem.take_problem().unwrap());
-        let d = if let Some(l1_coeff) = self.l1_coeff {
+        if let Some(l1_coeff) = self.l1_coeff {
             line_problem.with_l1_constraint(l1_coeff, &param, &prev_grad);
-            let zeros = r.zero_like();
-            P::max(
-                &r.mul(&prev_grad).sub(&F::min_positive_value()).signum(),
-                &zeros,
-            )
-            .mul(&r)
-            .mul(&float!(-1.0))
-        } else {
-            r.mul(&float!(-1.0))
+        }
+        let l1 = self.l1_coeff.is_some();
+        let search_direction = |r: &G| -> P {
+            if l1 {
+                let r: P = r.mul(&float!(1.0));
+                let zeros = r.zero_like();
+                P::max(
+                    &r.mul(&prev_grad).sub(&F::min_positive_value()).signum(),
+                    &zeros,
+                )
+                .mul(&r)
+                .mul(&float!(-1.0))
+            } else {
+                r.mul(&float!(-1.0))
+            }
         };
 
-        self.linesearch.search_direction(d);
-
-        // Run line search
-        let linesearch_result = Executor::new(line_problem, self.linesearch.clone())
-            .configure(|config| {
-                config
-                    .param(param.clone())
-                    .gradient(prev_grad.clone())
-                    .cost(cur_cost)
-            })
-            .ctrlc(false)
-            .run();
-
-        let OptimizationResult {
-            problem: mut line_problem,
-            state: mut linesearch_state,
-            ..
-        } = match linesearch_result {
-            Ok(res) => res,
-            Err(e) => {
-                return Ok((
-                    state.terminate_with(TerminationReason::SolverExit(format!(
-                        "Line search terminated with: '{}'",
-                        e,
-                    ))),
-                    Some(kv!("gamma" => gamma;)),
-                ))
+        // Run line search. If requested, the curvature information is discarded once after a
+        // failure and the line search is repeated along the steepest descent direction.
+        let mut memory_reset = false;
+        let (linesearch_result, d) = loop {
+            let mut d = search_direction(&r);
+            if state.get_bounds().is_some() {
+                // Entries of the search direction which point out of active bounds are dropped.
+                // If this spoils the descent property, the projected steepest descent direction
+                // is used instead.
+                d = state.project_direction(&param, d);
+                if d.dot(&prev_grad) >= float!(0.0) {
+                    d = state.project_direction(&param, search_direction(&prev_grad));
+                }
+                if d.dot(&prev_grad) >= float!(0.0) {
+                    problem.problem = Some(line_problem.problem);
+                    return Ok((
+                        state
+                            .param(param)
+                            .gradient(prev_grad)
+                            .terminate_with(TerminationReason::NoFeasibleDirection),
+                        Some(kv!("gamma" => gamma;)),
+                    ));
+                }
+            }
+            if let Some(max_step) = state.max_step_in_bounds(&param, &d) {
+                self.linesearch.max_step_length(max_step)?;
+            }
+            self.linesearch.search_direction(d.clone());
+            let result = Executor::new(line_problem, self.linesearch.clone())
+                .configure(|config| {
+                    config
+                        .param(param.clone())
+                        .gradient(prev_grad.clone())
+                        .cost(cur_cost)
+                })
+                .try_run_nested();
+            match result {
+                Err((_, mut failed_problem))
+                    if self.linesearch_recovery == LineSearchRecovery::ResetMemory
+                        && !memory_reset =>
+                {
+                    self.s.clear();
+                    self.y.clear();
+                    r.clone_from(&prev_grad);
+                    line_problem = failed_problem.take_problem().unwrap();
+                    problem.consume_func_counts(failed_problem);
+                    memory_reset = true;
+                }
+                result => break (result, d),
             }
         };
-
-        let mut xk1 = linesearch_state.take_param().unwrap();
-        let next_cost = linesearch_state.get_cost();
-
-        // take back problem and take care of function evaluation counts
-        let mut internal_line_problem = line_problem.take_problem().unwrap();
+        self.workspace.put(r);
+
+        let mut recovered = memory_reset;
+        let mut stationary = false;
+        let (mut xk1, next_cost, mut internal_line_problem, linesearch_kv) =
+            match linesearch_result {
+                Ok((
+                    OptimizationResult {
+                        problem: mut line_problem,
+                        state: mut linesearch_state,
+                        ..
+                    },
+                    linesearch_kv,
+                )) => {
+                    let xk1 = linesearch_state.take_param().unwrap();
+                    let next_cost = linesearch_state.get_cost();
+                    stationary = linesearch_state.get_termination_reason()
+                        == Some(&TerminationReason::StationaryDirection);
+                    // take back problem and take care of function evaluation counts
+                    let internal_line_problem = line_problem.take_problem().unwrap();
+                    problem.consume_func_counts(line_problem);
+                    let linesearch_kv = self.inner_telemetry.record(
+                        problem,
+                        "linesearch_iter_count",
+                        &linesearch_state,
+                        linesearch_kv,
+                    );
+                    (xk1, next_cost, internal_line_problem, linesearch_kv)
+                }
+                Err((e, mut line_problem)) => {
+                    let step = self.linesearch_recovery.fallback_step(
+                        &mut line_problem,
+                        &param,
+                        cur_cost,
+                        &prev_grad,
+                        &d,
+                    );
+                    let internal_line_problem = line_problem.take_problem().unwrap();
+                    problem.consume_func_counts(line_problem);
+                    match step {
+                        Ok(Some((xk1, next_cost))) => {
+                            recovered = true;
+                            (xk1, next_cost, internal_line_problem, None)
+                        }
+                        step => {
+                            problem.problem = Some(internal_line_problem.problem);
+                            step?;
+                            return Ok((
+                                state.param(param).terminate_with(
+                                    TerminationReason::LineSearchFailed(e.root_cause().to_string()),
+                                ),
+                                Some(kv!("gamma" => gamma;)),
+                            ));
+                        }
+                    }
+                }
+            };
         let xi = internal_line_problem.xi.take();
         problem.problem = Some(internal_line_problem.problem);
-        problem.consume_func_counts(line_problem);
+
+        // Since the inverse Hessian approximation is positive definite, the search direction can
+        // only be orthogonal to the gradient if the latter vanishes. There is nothing left to do.
+        if stationary {
+            return Ok((
+                state
+                    .param(xk1)
+                    .cost(next_cost)
+                    .gradient(prev_grad)
+                    .terminate_with(TerminationReason::StationaryDirection),
+                Some(kv!("gamma" => gamma;).merge(linesearch_kv.unwrap_or_default())),
+            ));
+        }
+        let linesearch_kv =
+            if recovered {
+                Some(linesearch_kv.unwrap_or_default().merge(
+                    kv!("linesearch_recovery" => format!("{:?}", self.linesearch_recovery);),
+                ))
+            } else {
+                linesearch_kv
+            };
         if let Some(xi) = xi {
             let zeros = xk1.zero_like();
             xk1 = P::max(&xk1.mul(&xi).signum(), &zeros).mul(&xk1);
         }
 
-        if state.get_iter() >= self.m as u64 {
-            self.s.pop_front();
-            self.y.pop_front();
-        }
-
         let grad = problem.gradient(&xk1)?;
 
-        self.s.push_back(xk1.sub(&param));
-        let grad = if let Some(l1_coeff) = self.l1_coeff {
+        let sk = xk1.sub(&param);
+        let (yk, grad) = if let Some(l1_coeff) = self.l1_coeff {
             // Stores unregularized gradient and returns L1 gradient.
             let pseudo_grad = calculate_pseudo_gradient(l1_coeff, &xk1, &grad);
-            self.y
-                .push_back(grad.sub(self.l1_prev_unreg_grad.as_ref().unwrap()));
+            let yk = grad.sub(self.l1_prev_unreg_grad.as_ref().unwrap());
             self.l1_prev_unreg_grad = Some(grad);
-            pseudo_grad
+            (yk, pseudo_grad)
         } else {
-            self.y.push_back(grad.sub(&prev_grad));
-            grad
+            (grad.sub(&prev_grad), grad)
         };
 
+        // Since `d = -H g_k` and `s = alpha d`, the product of the Hessian approximation and `s`
+        // is `-alpha g_k`.
+        let (yk, curvature_kv) = self.curvature_update.correct(&sk, yk, || {
+            let alpha: F = d.dot(&sk) / d.dot(&d);
+            prev_grad.mul(&(-alpha))
+        });
+        if let Some(yk) = yk {
+            if self.s.len() >= self.m {
+                self.s.pop_front();
+                self.y.pop_front();
+            }
+            self.s.push_back(sk);
+            self.y.push_back(yk);
+        }
+
         Ok((
             state.param(xk1).cost(next_cost).gradient(grad),
-            Some(kv!("gamma" => gamma;)),
+            Some(
+                kv!("gamma" => gamma;)
+                    .merge(linesearch_kv.unwrap_or_default())
+                    .merge(curvature_kv),
+            ),
         ))
     }
 
     fn terminate(&mut self, state: &IterState<P, G, (), (), (), F>) -> TerminationStatus {
-        if state.get_gradient().unwrap().l2_norm() < self.tol_grad {
+        // The gradient is missing after `init` if the initial evaluation was skipped
+        if state
+            .get_gradient()
+            .is_some_and(|grad| grad.l2_norm() < self.tol_grad)
+        {
             return TerminationStatus::Terminated(TerminationReason::SolverConverged);
         }
         if (state.get_prev_cost() - state.get_cost()).abs() < self.tol_cost {
@@ -519,9 +906,9 @@ mod tests {
     use super::*;
     use crate::core::{
         test_utils::{TestProblem, TestSparseProblem},
-        ArgminError,
+        ArgminError, KvValue,
     };
-    use crate::solver::linesearch::MoreThuenteLineSearch;
+    use crate::solver::linesearch::{FlakyLineSearch, MoreThuenteLineSearch};
 
     test_trait_impl!(
         lbfgs,
@@ -543,7 +930,14 @@ mod tests {
             y,
             l1_coeff,
             l1_prev_unreg_grad,
+            inner_telemetry,
+            linesearch_recovery,
+            curvature_update,
+            initial_scaling,
+            workspace,
+            coefficients,
         } = lbfgs;
+        assert_eq!(inner_telemetry, NestedTelemetry::new());
 
         assert_eq!(linesearch, MyFakeLineSearch {});
         assert_eq!(tol_grad.to_ne_bytes(), f64::EPSILON.sqrt().to_ne_bytes());
@@ -553,6 +947,11 @@ mod tests {
         assert!(y.capacity() >= 3);
         assert!(l1_coeff.is_none());
         assert!(l1_prev_unreg_grad.is_none());
+        assert_eq!(linesearch_recovery, LineSearchRecovery::Stop);
+        assert_eq!(curvature_update, CurvatureUpdate::Always);
+        assert!(initial_scaling);
+        assert!(workspace.is_empty());
+        assert!(coefficients.is_empty());
     }
 
     #[test]
@@ -609,6 +1008,54 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_config_build() {
+        #[derive(Eq, PartialEq, Debug, Clone, Copy)]
+        struct MyFakeLineSearch {}
+
+        let config = LBFGSConfig {
+            m: 5,
+            tolerance_grad: Some(1e-4),
+            tolerance_cost: Some(1e-5),
+            l1_coeff: Some(0.5),
+            linesearch_recovery: Some(LineSearchRecovery::SmallStep(0.1)),
+            curvature_update: Some(CurvatureUpdate::Damped(0.2)),
+            initial_scaling: Some(false),
+        };
+        let lbfgs: LBFGS<_, Vec<f64>, Vec<f64>, f64> = config.build(MyFakeLineSearch {}).unwrap();
+        assert_eq!(lbfgs.m, 5);
+        assert_eq!(lbfgs.tol_grad.to_ne_bytes(), 1e-4f64.to_ne_bytes());
+        assert_eq!(lbfgs.tol_cost.to_ne_bytes(), 1e-5f64.to_ne_bytes());
+        assert_eq!(lbfgs.l1_coeff, Some(0.5));
+        assert_eq!(
+            lbfgs.linesearch_recovery,
+            LineSearchRecovery::SmallStep(0.1)
+        );
+        assert_eq!(lbfgs.curvature_update, CurvatureUpdate::Damped(0.2));
+        assert!(!lbfgs.initial_scaling);
+
+        // The history size has no default
+        let res: Result<LBFGS<_, Vec<f64>, Vec<f64>, f64>, _> =
+            LBFGSConfig::default().build(MyFakeLineSearch {});
+        assert_error!(
+            res,
+            ArgminError,
+            "Invalid parameter: \"`L-BFGS`: history size `m` must be > 0.\""
+        );
+
+        let config = LBFGSConfig {
+            m: 5,
+            tolerance_grad: Some(-1.0),
+            ..Default::default()
+        };
+        let res: Result<LBFGS<_, Vec<f64>, Vec<f64>, f64>, _> = config.build(MyFakeLineSearch {});
+        assert_error!(
+            res,
+            ArgminError,
+            "Invalid parameter: \"`L-BFGS`: gradient tolerance must be >= 0.\""
+        );
+    }
+
     #[test]
     fn test_init() {
         let linesearch = MoreThuenteLineSearch::new().with_c(1e-4, 0.9).unwrap();
@@ -636,7 +1083,19 @@ mod tests {
         let problem = TestProblem::new();
         let (mut state_out, kv) = lbfgs.init(&mut Problem::new(problem), state).unwrap();
 
-        assert!(kv.is_none());
+        assert_eq!(
+            kv.unwrap(),
+            kv!(
+                "linesearch" => "More-Thuente Line search";
+                "m" => 3u64;
+                "tolerance_grad" => f64::EPSILON.sqrt();
+                "tolerance_cost" => f64::EPSILON;
+                "linesearch_recovery" => "Stop";
+                "curvature_update" => "Always";
+                "initial_scaling" => true;
+            )
+            .with_prefix("config")
+        );
 
         let s_param = state_out.take_param().unwrap();
 
@@ -667,7 +1126,7 @@ mod tests {
         let problem = TestProblem::new();
         let (state_out, kv) = lbfgs.init(&mut Problem::new(problem), state).unwrap();
 
-        assert!(kv.is_none());
+        assert!(kv.is_some());
 
         assert_eq!(state_out.get_cost().to_ne_bytes(), 1234.0f64.to_ne_bytes())
     }
@@ -687,7 +1146,7 @@ mod tests {
         let problem = TestProblem::new();
         let (mut state_out, kv) = lbfgs.init(&mut Problem::new(problem), state).unwrap();
 
-        assert!(kv.is_none());
+        assert!(kv.is_some());
 
         let s_grad = state_out.take_gradient().unwrap();
 
@@ -742,4 +1201,343 @@ mod tests {
             assert!((result_param[3]).abs() < 1e-6);
         }
     }
+
+    #[test]
+    fn test_with_linesearch_recovery() {
+        #[derive(Eq, PartialEq, Debug, Clone, Copy)]
+        struct MyFakeLineSearch {}
+
+        let lbfgs: LBFGS<_, Vec<f64>, Vec<f64>, f64> = LBFGS::new(MyFakeLineSearch {}, 3)
+            .with_linesearch_recovery(LineSearchRecovery::Backtracking)
+            .unwrap();
+        assert_eq!(lbfgs.linesearch_recovery, LineSearchRecovery::Backtracking);
+
+        for step in [0.0, -1.0, f64::NAN] {
+            let res = LBFGS::<_, Vec<f64>, Vec<f64>, f64>::new(MyFakeLineSearch {}, 3)
+                .with_linesearch_recovery(LineSearchRecovery::SmallStep(step));
+            assert_error!(
+                res,
+                ArgminError,
+                "Invalid parameter: \"`L-BFGS`: step length of line search recovery must be > 0.\""
+            );
+        }
+    }
+
+    #[test]
+    fn test_linesearch_failure_stop() {
+        let linesearch = FlakyLineSearch::new(vec![2]);
+        let lbfgs: LBFGS<_, Vec<f64>, Vec<f64>, f64> = LBFGS::new(linesearch, 3);
+
+        let res = Executor::new(TestSparseProblem::new(), lbfgs)
+            .configure(|state| state.param(vec![0.0; 4]).max_iters(10))
+            .run()
+            .unwrap();
+
+        assert_eq!(
+            res.state.termination_status,
+            TerminationStatus::Terminated(TerminationReason::LineSearchFailed(
+                "Condition violated: \"search failed\"".to_string()
+            ))
+        );
+        assert_eq!(res.state.param.unwrap(), vec![1.0, 0.5, -1.0, -0.5]);
+        // The problem is handed back to the solver after the failure
+        assert!(res.problem.problem.is_some());
+    }
+
+    #[test]
+    fn test_linesearch_failure_fallback_step() {
+        for policy in [
+            LineSearchRecovery::Backtracking,
+            LineSearchRecovery::SmallStep(0.25),
+        ] {
+            let linesearch = FlakyLineSearch::new(vec![1]);
+            let lbfgs: LBFGS<_, Vec<f64>, Vec<f64>, f64> = LBFGS::new(linesearch, 3)
+                .with_linesearch_recovery(policy)
+                .unwrap();
+
+            let res = Executor::new(TestSparseProblem::new(), lbfgs)
+                .configure(|state| state.param(vec![0.0; 4]).max_iters(1))
+                .run()
+                .unwrap();
+
+            assert_eq!(
+                res.state.termination_status,
+                TerminationStatus::Terminated(TerminationReason::MaxItersReached)
+            );
+            assert_eq!(res.state.param.unwrap(), vec![1.0, 0.5, -1.0, -0.5]);
+            assert_eq!(res.state.cost.to_ne_bytes(), 0.5f64.to_ne_bytes());
+            assert_eq!(res.solver.s.len(), 1);
+        }
+    }
+
+    #[test]
+    fn test_linesearch_failure_reset_memory() {
+        let linesearch = FlakyLineSearch::new(vec![2]);
+        let attempts = linesearch.attempts.clone();
+        let lbfgs: LBFGS<_, Vec<f64>, Vec<f64>, f64> = LBFGS::new(linesearch, 3)
+            .with_linesearch_recovery(LineSearchRecovery::ResetMemory)
+            .unwrap();
+
+        let res = Executor::new(TestSparseProblem::new(), lbfgs)
+            .configure(|state| state.param(vec![0.0; 4]).max_iters(2))
+            .run()
+            .unwrap();
+
+        assert_eq!(
+            res.state.termination_status,
+            TerminationStatus::Terminated(TerminationReason::MaxItersReached)
+        );
+        assert_eq!(attempts.get(), 3);
+        // The memory was cleared before the update of the second iteration
+        assert_eq!(res.solver.s.len(), 1);
+        assert_eq!(res.solver.y.len(), 1);
+
+        // The run stops if the line search fails again after the reset
+        let linesearch = FlakyLineSearch::new(vec![2, 3]);
+        let lbfgs: LBFGS<_, Vec<f64>, Vec<f64>, f64> = LBFGS::new(linesearch, 3)
+            .with_linesearch_recovery(LineSearchRecovery::ResetMemory)
+            .unwrap();
+
+        let res = Executor::new(TestSparseProblem::new(), lbfgs)
+            .configure(|state| state.param(vec![0.0; 4]).max_iters(2))
+            .run()
+            .unwrap();
+
+        assert!(matches!(
+            res.state.termination_status,
+            TerminationStatus::Terminated(TerminationReason::LineSearchFailed(_))
+        ));
+        assert!(res.problem.problem.is_some());
+    }
+
+    #[test]
+    fn test_next_iter_stationary() {
+        let mut lbfgs: LBFGS<_, Vec<f64>, Vec<f64>, f64> =
+            LBFGS::new(MoreThuenteLineSearch::new(), 3);
+        let mut problem = Problem::new(TestProblem::new());
+        let state = IterState::new().param(vec![0.0f64, 0.0]);
+        let (state, _) = lbfgs.init(&mut problem, state).unwrap();
+        let (state, kv) = lbfgs.next_iter(&mut problem, state).unwrap();
+
+        assert_eq!(
+            state.termination_status,
+            TerminationStatus::Terminated(TerminationReason::StationaryDirection)
+        );
+        assert_eq!(state.param.unwrap(), vec![0.0, 0.0]);
+        assert!(kv.unwrap().get("gamma").is_some());
+        assert!(lbfgs.s.is_empty());
+        assert!(lbfgs.y.is_empty());
+    }
+
+    #[test]
+    fn test_with_curvature_update() {
+        #[derive(Eq, PartialEq, Debug, Clone, Copy)]
+        struct MyFakeLineSearch {}
+
+        let lbfgs: LBFGS<_, Vec<f64>, Vec<f64>, f64> = LBFGS::new(MyFakeLineSearch {}, 3)
+            .with_curvature_update(CurvatureUpdate::Skip)
+            .unwrap();
+        assert_eq!(lbfgs.curvature_update, CurvatureUpdate::Skip);
+
+        let res: Result<LBFGS<_, Vec<f64>, Vec<f64>, f64>, _> =
+            LBFGS::new(MyFakeLineSearch {}, 3).with_curvature_update(CurvatureUpdate::Damped(0.0));
+        assert_error!(
+            res,
+            ArgminError,
+            "Invalid parameter: \"`L-BFGS`: damping threshold must be in (0, 1).\""
+        );
+    }
+
+    #[test]
+    fn test_curvature_update() {
+        /// f(x) = -x^T x, which violates the curvature condition everywhere
+        struct Concave {}
+
+        impl CostFunction for Concave {
+            type Param = Vec<f64>;
+            type Output = f64;
+
+            fn cost(&self, p: &Self::Param) -> Result<Self::Output, Error> {
+                Ok(-p.iter().map(|x| x * x).sum::<f64>())
+            }
+        }
+
+        impl Gradient for Concave {
+            type Param = Vec<f64>;
+            type Gradient = Vec<f64>;
+
+            fn gradient(&self, p: &Self::Param) -> Result<Self::Gradient, Error> {
+                Ok(p.iter().map(|x| -2.0 * x).collect())
+            }
+        }
+
+        let step = |policy| {
+            let mut lbfgs: LBFGS<_, Vec<f64>, Vec<f64>, f64> =
+                LBFGS::new(FlakyLineSearch::new(vec![]), 3)
+                    .with_curvature_update(policy)
+                    .unwrap();
+            let mut problem = Problem::new(Concave {});
+            let state = IterState::new().param(vec![1.0f64, 1.0]);
+            let (state, _) = lbfgs.init(&mut problem, state).unwrap();
+            let (state, kv) = lbfgs.next_iter(&mut problem, state).unwrap();
+            // Step of 0.25 along `[2, 2]`
+            assert_eq!(state.param.unwrap(), vec![1.5, 1.5]);
+            (lbfgs, kv.unwrap())
+        };
+
+        let (lbfgs, kv) = step(CurvatureUpdate::Always);
+        assert_eq!(lbfgs.y.len(), 1);
+        assert!(kv.get("update_skipped").is_none());
+
+        let (lbfgs, kv) = step(CurvatureUpdate::Skip);
+        assert!(lbfgs.s.is_empty());
+        assert!(lbfgs.y.is_empty());
+        assert_eq!(kv.get("update_skipped"), Some(&KvValue::Bool(true)));
+
+        // s = [0.5, 0.5], y = [-1, -1] and B s = s, therefore theta = 0.8 * 0.5 / 1.5 and the
+        // damped y satisfies s^T y = 0.2 s^T s.
+        let (lbfgs, kv) = step(CurvatureUpdate::Damped(0.2));
+        assert_eq!(lbfgs.y.len(), 1);
+        let sy: f64 = lbfgs.s[0].dot(&lbfgs.y[0]);
+        assert!((sy - 0.1).abs() < 1e-12);
+        assert_eq!(kv.get("update_skipped"), Some(&KvValue::Bool(false)));
+        assert!((kv.get("damping").unwrap().get_float().unwrap() - 0.4 / 1.5).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_initial_scaling() {
+        let step = |scaling| {
+            let mut lbfgs: LBFGS<_, Vec<f64>, Vec<f64>, f64> =
+                LBFGS::new(FlakyLineSearch::new(vec![]), 3).with_initial_scaling(scaling);
+            let mut problem = Problem::new(TestProblem::new());
+            // Pretend that the previous step had a curvature of 4
+            lbfgs.s.push_back(vec![1.0, 0.0]);
+            lbfgs.y.push_back(vec![4.0, 0.0]);
+            let state = IterState::new()
+                .param(vec![0.0f64, 1.0])
+                .gradient(vec![0.0f64, 1.0]);
+            let (state, kv) = lbfgs.next_iter(&mut problem, state).unwrap();
+            (state.param.unwrap(), kv.unwrap())
+        };
+
+        // `H = gamma * I` perpendicular to `s`
+        let (param, kv) = step(true);
+        assert_eq!(param, vec![0.0, 1.0 - 0.25 * 0.25]);
+        assert_eq!(kv.get("gamma"), Some(&KvValue::Float(0.25)));
+
+        let (param, kv) = step(false);
+        assert_eq!(param, vec![0.0, 0.75]);
+        assert_eq!(kv.get("gamma"), Some(&KvValue::Float(1.0)));
+    }
+
+    #[test]
+    fn test_workspace() {
+        let mut lbfgs: LBFGS<_, Vec<f64>, Vec<f64>, f64> =
+            LBFGS::new(FlakyLineSearch::new(vec![]), 3);
+        let mut problem = Problem::new(TestProblem::new());
+        lbfgs.s.push_back(vec![1.0, 0.0]);
+        lbfgs.y.push_back(vec![4.0, 0.0]);
+        let mut state = IterState::new()
+            .param(vec![0.0f64, 1.0])
+            .gradient(vec![0.0f64, 1.0]);
+        for _ in 0..2 {
+            let pairs = lbfgs.s.len();
+            state = lbfgs.next_iter(&mut problem, state).unwrap().0;
+            // The buffers of `q`, `alpha` and `rho` are returned and resized to the number of pairs
+            assert_eq!(lbfgs.workspace.len(), 1);
+            assert_eq!(lbfgs.coefficients.len(), 2);
+            let alpha = lbfgs.coefficients.take().unwrap();
+            let rho = lbfgs.coefficients.take().unwrap();
+            assert_eq!((alpha.len(), rho.len()), (pairs, pairs));
+            lbfgs.coefficients.put(alpha);
+            lbfgs.coefficients.put(rho);
+        }
+        assert_eq!(lbfgs.s.len(), 3);
+    }
+
+    #[test]
+    fn test_bounds() {
+        use crate::core::{CostFunction, Executor};
+
+        /// Minimum at `(2, 2)`, must not be evaluated outside of `[-1, 1]^2`
+        struct BoundedProblem {}
+
+        impl CostFunction for BoundedProblem {
+            type Param = Vec<f64>;
+            type Output = f64;
+
+            fn cost(&self, p: &Self::Param) -> Result<Self::Output, Error> {
+                assert!(p.iter().all(|x| (-1.0..=1.0).contains(x)));
+                Ok(p.iter().map(|x| (x - 2.0).powi(2)).sum())
+            }
+        }
+
+        impl Gradient for BoundedProblem {
+            type Param = Vec<f64>;
+            type Gradient = Vec<f64>;
+
+            fn gradient(&self, p: &Self::Param) -> Result<Self::Gradient, Error> {
+                assert!(p.iter().all(|x| (-1.0..=1.0).contains(x)));
+                Ok(p.iter().map(|x| 2.0 * (x - 2.0)).collect())
+            }
+        }
+
+        let run = |init_param: Vec<f64>, max_iters: u64| {
+            let lbfgs: LBFGS<_, Vec<f64>, Vec<f64>, f64> =
+                LBFGS::new(MoreThuenteLineSearch::new(), 3);
+            Executor::new(BoundedProblem {}, lbfgs)
+                .configure(|state| {
+                    state
+                        .param(init_param)
+                        .bounds(vec![-1.0, -1.0], vec![1.0, 1.0])
+                        .max_iters(max_iters)
+                })
+                .run()
+                .unwrap()
+        };
+
+        // The first step along `(4, 3)` is limited to 1/6 by the upper bound of the second
+        // parameter
+        let res = run(vec![0.0, 0.5], 1);
+        let param = res.state.get_param().unwrap();
+        assert!((param[0] - 2.0 / 3.0).abs() < 1e-12);
+        assert!((param[1] - 1.0).abs() < 1e-12);
+
+        // Afterwards, the second parameter stays on its bound while the first one moves to its
+        // bound as well. There, every descent direction leaves the bounds.
+        for init_param in [vec![0.0, 0.5], vec![0.0, 1.0]] {
+            let res = run(init_param, 100);
+            assert_eq!(
+                res.state.get_termination_reason(),
+                Some(&TerminationReason::NoFeasibleDirection)
+            );
+            assert!(res.state.get_iter() < 5);
+            let param = res.state.get_best_param().unwrap();
+            assert!((param[0] - 1.0).abs() < 1e-12);
+            assert!((param[1] - 1.0).abs() < 1e-12);
+            assert!((res.state.get_best_cost() - 2.0).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn test_complex_param() {
+        use crate::core::test_utils::TestComplexProblem;
+        use num_complex::Complex64;
+
+        let lbfgs: LBFGS<_, Vec<Complex64>, Vec<Complex64>, f64> =
+            LBFGS::new(MoreThuenteLineSearch::new(), 3);
+        let res = Executor::new(TestComplexProblem {}, lbfgs)
+            .configure(|state| state.param(vec![Complex64::new(0.0, 0.0); 2]).max_iters(20))
+            .run()
+            .unwrap();
+
+        assert_eq!(
+            res.state.get_termination_reason(),
+            Some(&TerminationReason::SolverConverged)
+        );
+        let param = res.state.get_best_param().unwrap();
+        assert!((param[0] - Complex64::new(0.5, 0.5)).norm() < 1e-6);
+        assert!((param[1] - Complex64::new(-0.5, 0.0)).norm() < 1e-6);
+        assert!((res.state.get_best_cost() + 2.0).abs() < 1e-10);
+    }
 }
@@ -19,13 +19,15 @@
 //! Springer. ISBN 0-387-30303-0.
 
 mod bfgs;
+mod curvature;
 mod dfp;
 mod lbfgs;
 mod sr1;
 mod sr1_trustregion;
 
-pub use self::bfgs::BFGS;
-pub use self::dfp::DFP;
-pub use self::lbfgs::LBFGS;
-pub use self::sr1::SR1;
-pub use self::sr1_trustregion::SR1TrustRegion;
+pub use self::bfgs::{BFGSConfig, BFGS};
+pub use self::curvature::CurvatureUpdate;
+pub use self::dfp::{DFPConfig, DFP};
+pub use self::lbfgs::{LBFGSConfig, LBFGS};
+pub use self::sr1::{SR1Config, SR1};
+pub use self::sr1_trustregion::{SR1TrustRegion, SR1TrustRegionConfig};
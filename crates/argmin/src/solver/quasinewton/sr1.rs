@@ -6,8 +6,8 @@
 // copied, modified, or distributed except according to those terms.
 
 use crate::core::{
-    ArgminFloat, CostFunction, Error, Executor, Gradient, IterState, LineSearch,
-    OptimizationResult, Problem, Solver, TerminationReason, TerminationStatus, KV,
+    ArgminFloat, CostFunction, Error, Executor, Gradient, IterState, LineSearch, NestedTelemetry,
+    OptimizationResult, Problem, Solver, State, TerminationReason, TerminationStatus, KV,
 };
 use argmin_math::{ArgminAdd, ArgminDot, ArgminL2Norm, ArgminMul, ArgminSub};
 #[cfg(feature = "serde1")]
@@ -36,6 +36,9 @@ pub struct SR1<L, F> {
     tol_grad: F,
     /// Tolerance for the stopping criterion based on the change of the cost stopping criterion
     tol_cost: F,
+    /// Telemetry of the line search
+    #[cfg_attr(feature = "serde1", serde(default))]
+    inner_telemetry: NestedTelemetry,
 }
 
 impl<L, F> SR1<L, F>
@@ -57,9 +60,30 @@ where
             linesearch,
             tol_grad: F::epsilon().sqrt(),
             tol_cost: F::epsilon(),
+            inner_telemetry: NestedTelemetry::new(),
         }
     }
 
+    /// Forward the telemetry of the line search to observers
+    ///
+    /// The key-value pairs returned by the line search as well as its number of iterations and
+    /// its termination reason are forwarded with keys prefixed by `prefix` (for instance
+    /// `linesearch.iters`). Independent of this setting, the line search iterations are counted
+    /// as `linesearch_iter_count`. See [`NestedTelemetry`] for details.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::quasinewton::SR1;
+    /// # let linesearch = ();
+    /// let solver = SR1::<_, f64>::new(linesearch).with_inner_telemetry("linesearch");
+    /// ```
+    #[must_use]
+    pub fn with_inner_telemetry<T: AsRef<str>>(mut self, prefix: T) -> Self {
+        self.inner_telemetry = NestedTelemetry::forward(prefix);
+        self
+    }
+
     /// Set denominator factor
     ///
     /// If the denominator of the update is below the `denominator_factor` (scaled with other
@@ -144,6 +168,57 @@ where
     }
 }
 
+/// Serializable configuration of [`SR1`]
+///
+/// Options which are `None` keep the defaults of [`SR1`]. The configuration is validated when
+/// the solver is constructed via [`build`](`SR1Config::build`).
+///
+/// # Example
+///
+/// ```
+/// # use argmin::solver::quasinewton::{SR1, SR1Config};
+/// # use argmin::core::Error;
+/// # fn main() -> Result<(), Error> {
+/// # let linesearch = ();
+/// let config = SR1Config {
+///     denominator_factor: Some(1e-6),
+///     ..Default::default()
+/// };
+/// let sr1: SR1<_, f64> = config.build(linesearch)?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize), serde(default))]
+pub struct SR1Config<F> {
+    /// See [`SR1::with_denominator_factor`]
+    pub denominator_factor: Option<F>,
+    /// See [`SR1::with_tolerance_grad`]
+    pub tolerance_grad: Option<F>,
+    /// See [`SR1::with_tolerance_cost`]
+    pub tolerance_cost: Option<F>,
+}
+
+impl<F> SR1Config<F>
+where
+    F: ArgminFloat,
+{
+    /// Validates the configuration and constructs an [`SR1`] solver using the given line search.
+    pub fn build<L>(&self, linesearch: L) -> Result<SR1<L, F>, Error> {
+        let mut solver = SR1::new(linesearch);
+        if let Some(denominator_factor) = self.denominator_factor {
+            solver = solver.with_denominator_factor(denominator_factor)?;
+        }
+        if let Some(tol_grad) = self.tolerance_grad {
+            solver = solver.with_tolerance_grad(tol_grad)?;
+        }
+        if let Some(tol_cost) = self.tolerance_cost {
+            solver = solver.with_tolerance_cost(tol_cost)?;
+        }
+        Ok(solver)
+    }
+}
+
 impl<O, L, P, G, H, F> Solver<O, IterState<P, G, (), H, (), F>> for SR1<L, F>
 where
     O: CostFunction<Param = P, Output = F> + Gradient<Param = P, Gradient = G>,
@@ -163,45 +238,69 @@ where
         "SR1"
     }
 
+    fn check_state(&self, state: &IterState<P, G, (), H, (), F>) -> Result<(), Error> {
+        if state.get_param().is_none() {
+            return Err(argmin_error!(
+                NotInitialized,
+                concat!(
+                    "`SR1` requires an initial parameter vector. ",
+                    "Please provide an initial guess via `Executor`s `configure` method."
+                )
+            ));
+        }
+        if state.get_inv_hessian().is_none() {
+            return Err(argmin_error!(
+                NotInitialized,
+                concat!(
+                    "`SR1` requires an initial inverse Hessian. ",
+                    "Please provide an initial guess via `Executor`s `configure` method."
+                )
+            ));
+        }
+        Ok(())
+    }
+
     fn init(
         &mut self,
         problem: &mut Problem<O>,
         mut state: IterState<P, G, (), H, (), F>,
     ) -> Result<(IterState<P, G, (), H, (), F>, Option<KV>), Error> {
+        self.check_state(&state)?;
         let param = state.take_param().ok_or_else(argmin_error_closure!(
-            NotInitialized,
-            concat!(
-                "`SR1` requires an initial parameter vector. ",
-                "Please provide an initial guess via `Executor`s `configure` method."
-            )
+            PotentialBug,
+            "`SR1`: Parameter vector in state not set."
         ))?;
 
         let inv_hessian = state.take_inv_hessian().ok_or_else(argmin_error_closure!(
-            NotInitialized,
-            concat!(
-                "`SR1` requires an initial inverse Hessian. ",
-                "Please provide an initial guess via `Executor`s `configure` method."
-            )
+            PotentialBug,
+            "`SR1`: Inverse Hessian in state not set."
         ))?;
 
-        let cost = state.get_cost();
-        let cost = if cost.is_infinite() {
-            problem.cost(&param)?
-        } else {
-            cost
-        };
+        let policy = state.get_initial_evaluation();
 
-        let grad = state
-            .take_gradient()
-            .map(Result::Ok)
-            .unwrap_or_else(|| problem.gradient(&param))?;
+        let cost = state.get_cost();
+        let cost = policy
+            .evaluate_lazily((!cost.is_infinite()).then_some(cost), || {
+                problem.cost(&param)
+            })?
+            .unwrap_or(cost);
+
+        let grad = policy.evaluate_lazily(state.take_gradient(), || problem.gradient(&param))?;
+        let mut state = state.param(param).cost(cost).inv_hessian(inv_hessian);
+        if let Some(grad) = grad {
+            state = state.gradient(grad);
+        }
         Ok((
-            state
-                .param(param)
-                .cost(cost)
-                .gradient(grad)
-                .inv_hessian(inv_hessian),
-            None,
+            state,
+            Some(
+                kv!(
+                    "linesearch" => self.linesearch.name();
+                    "denominator_factor" => self.denominator_factor;
+                    "tolerance_grad" => self.tol_grad;
+                    "tolerance_cost" => self.tol_cost;
+                )
+                .with_prefix("config"),
+            ),
         ))
     }
 
@@ -216,10 +315,11 @@ where
         ))?;
         let cost = state.get_cost();
 
-        let prev_grad = state.take_gradient().ok_or_else(argmin_error_closure!(
-            PotentialBug,
-            "`SR1`: Gradient in state not set."
-        ))?;
+        let prev_grad = state
+            .take_gradient()
+            .map(Result::Ok)
+            // Missing if the initial evaluation was skipped
+            .unwrap_or_else(|| problem.gradient(&param))?;
 
         let mut inv_hessian = state.take_inv_hessian().ok_or_else(argmin_error_closure!(
             PotentialBug,
@@ -231,25 +331,33 @@ where
         self.linesearch.search_direction(p);
 
         // Run solver
-        let OptimizationResult {
-            problem: line_problem,
-            state: mut linesearch_state,
-            ..
-        } = Executor::new(problem.take_problem().unwrap(), self.linesearch.clone())
+        let (
+            OptimizationResult {
+                problem: line_problem,
+                state: mut linesearch_state,
+                ..
+            },
+            linesearch_kv,
+        ) = Executor::new(problem.take_problem().unwrap(), self.linesearch.clone())
             .configure(|config| {
                 config
                     .param(param.clone())
                     .gradient(prev_grad.clone())
                     .cost(cost)
             })
-            .ctrlc(false)
-            .run()?;
+            .run_nested()?;
 
         let xk1 = linesearch_state.take_param().unwrap();
         let next_cost = linesearch_state.get_cost();
 
         // take care of function eval counts
         problem.consume_problem(line_problem);
+        let linesearch_kv = self.inner_telemetry.record(
+            problem,
+            "linesearch_iter_count",
+            &linesearch_state,
+            linesearch_kv,
+        );
 
         let grad = problem.gradient(&xk1)?;
         let yk = grad.sub(&prev_grad);
@@ -276,12 +384,19 @@ where
                 .cost(next_cost)
                 .gradient(grad)
                 .inv_hessian(inv_hessian),
-            Some(kv!["denominator" => b; "hessian_update" => hessian_update;]),
+            Some(
+                kv!["denominator" => b; "hessian_update" => hessian_update;]
+                    .merge(linesearch_kv.unwrap_or_default()),
+            ),
         ))
     }
 
     fn terminate(&mut self, state: &IterState<P, G, (), H, (), F>) -> TerminationStatus {
-        if state.get_gradient().unwrap().l2_norm() < self.tol_grad {
+        // The gradient is missing after `init` if the initial evaluation was skipped
+        if state
+            .get_gradient()
+            .is_some_and(|grad| grad.l2_norm() < self.tol_grad)
+        {
             return TerminationStatus::Terminated(TerminationReason::SolverConverged);
         }
         if (state.get_prev_cost() - state.cost).abs() < self.tol_cost {
@@ -313,7 +428,9 @@ mod tests {
             linesearch,
             tol_grad,
             tol_cost,
+            inner_telemetry,
         } = sr1;
+        assert_eq!(inner_telemetry, NestedTelemetry::new());
 
         assert_eq!(linesearch, MyFakeLineSearch {});
         assert_eq!(tol_grad.to_ne_bytes(), f64::EPSILON.sqrt().to_ne_bytes());
@@ -402,6 +519,64 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_config_build() {
+        #[derive(Eq, PartialEq, Debug, Clone, Copy)]
+        struct MyFakeLineSearch {}
+
+        let config = SR1Config {
+            denominator_factor: Some(0.5),
+            tolerance_grad: Some(1e-4),
+            tolerance_cost: Some(1e-5),
+        };
+        let sr1: SR1<_, f64> = config.build(MyFakeLineSearch {}).unwrap();
+        assert_eq!(sr1.denominator_factor.to_ne_bytes(), 0.5f64.to_ne_bytes());
+        assert_eq!(sr1.tol_grad.to_ne_bytes(), 1e-4f64.to_ne_bytes());
+        assert_eq!(sr1.tol_cost.to_ne_bytes(), 1e-5f64.to_ne_bytes());
+
+        let config = SR1Config {
+            denominator_factor: Some(1.0),
+            ..Default::default()
+        };
+        let res: Result<SR1<_, f64>, _> = config.build(MyFakeLineSearch {});
+        assert_error!(
+            res,
+            ArgminError,
+            "Invalid parameter: \"`SR1`: denominator_factor must be in (0, 1).\""
+        );
+    }
+
+    #[test]
+    fn test_check_state() {
+        let sr1: SR1<MoreThuenteLineSearch<Vec<f64>, Vec<f64>, f64>, f64> =
+            SR1::new(MoreThuenteLineSearch::new());
+
+        let state: IterState<Vec<f64>, Vec<f64>, (), Vec<Vec<f64>>, (), f64> = IterState::new();
+        let res = <SR1<_, _> as Solver<TestProblem, _>>::check_state(&sr1, &state);
+        assert_error!(
+            res,
+            ArgminError,
+            concat!(
+                "Not initialized: \"`SR1` requires an initial parameter vector. Please ",
+                "provide an initial guess via `Executor`s `configure` method.\""
+            )
+        );
+
+        let state = state.param(vec![-1.0, 1.0]);
+        let res = <SR1<_, _> as Solver<TestProblem, _>>::check_state(&sr1, &state);
+        assert_error!(
+            res,
+            ArgminError,
+            concat!(
+                "Not initialized: \"`SR1` requires an initial inverse Hessian. Please ",
+                "provide an initial guess via `Executor`s `configure` method.\""
+            )
+        );
+
+        let state = state.inv_hessian(vec![vec![1.0, 0.0], vec![0.0, 1.0]]);
+        assert!(<SR1<_, _> as Solver<TestProblem, _>>::check_state(&sr1, &state).is_ok());
+    }
+
     #[test]
     fn test_init() {
         let linesearch = MoreThuenteLineSearch::new().with_c(1e-4, 0.9).unwrap();
@@ -446,7 +621,16 @@ mod tests {
         let problem = TestProblem::new();
         let (mut state_out, kv) = sr1.init(&mut Problem::new(problem), state).unwrap();
 
-        assert!(kv.is_none());
+        assert_eq!(
+            kv.unwrap(),
+            kv!(
+                "linesearch" => "More-Thuente Line search";
+                "denominator_factor" => 1e-8f64;
+                "tolerance_grad" => f64::EPSILON.sqrt();
+                "tolerance_cost" => f64::EPSILON;
+            )
+            .with_prefix("config")
+        );
 
         let s_param = state_out.take_param().unwrap();
 
@@ -490,7 +674,7 @@ mod tests {
         let problem = TestProblem::new();
         let (state_out, kv) = sr1.init(&mut Problem::new(problem), state).unwrap();
 
-        assert!(kv.is_none());
+        assert!(kv.is_some());
 
         assert_eq!(state_out.get_cost().to_ne_bytes(), 1234.0f64.to_ne_bytes())
     }
@@ -513,7 +697,7 @@ mod tests {
         let problem = TestProblem::new();
         let (mut state_out, kv) = sr1.init(&mut Problem::new(problem), state).unwrap();
 
-        assert!(kv.is_none());
+        assert!(kv.is_some());
 
         let s_grad = state_out.take_gradient().unwrap();
 
@@ -6,8 +6,9 @@
 // copied, modified, or distributed except according to those terms.
 
 use crate::core::{
-    ArgminFloat, CostFunction, Error, Executor, Gradient, Hessian, IterState, OptimizationResult,
-    Problem, Solver, TerminationReason, TerminationStatus, TrustRegionRadius, KV,
+    ArgminFloat, CostFunction, Error, Executor, Gradient, Hessian, IterState, NestedTelemetry,
+    OptimizationResult, Problem, Solver, State, TerminationReason, TerminationStatus,
+    TrustRegionRadius, KV,
 };
 use argmin_math::{
     ArgminAdd, ArgminDot, ArgminL2Norm, ArgminMul, ArgminSub, ArgminWeightedDot, ArgminZeroLike,
@@ -44,6 +45,9 @@ pub struct SR1TrustRegion<R, F> {
     eta: F,
     /// Tolerance for the stopping criterion based on the change of the norm on the gradient
     tol_grad: F,
+    /// Telemetry of the subproblem
+    #[cfg_attr(feature = "serde1", serde(default))]
+    inner_telemetry: NestedTelemetry,
 }
 
 impl<R, F> SR1TrustRegion<R, F>
@@ -71,9 +75,31 @@ where
             radius: float!(1.0),
             eta: float!(0.5 * 1e-3),
             tol_grad: float!(1e-3),
+            inner_telemetry: NestedTelemetry::new(),
         }
     }
 
+    /// Forward the telemetry of the subproblem to observers
+    ///
+    /// The key-value pairs returned by the subproblem as well as its number of iterations and
+    /// its termination reason are forwarded with keys prefixed by `prefix` (for instance
+    /// `subproblem.iters`). Independent of this setting, the subproblem iterations are counted
+    /// as `subproblem_iter_count`. See [`NestedTelemetry`] for details.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::quasinewton::SR1TrustRegion;
+    /// # let subproblem: argmin::solver::trustregion::Steihaug<Vec<f64>, f64> =
+    /// #     argmin::solver::trustregion::Steihaug::new();
+    /// let solver = SR1TrustRegion::<_, f64>::new(subproblem).with_inner_telemetry("subproblem");
+    /// ```
+    #[must_use]
+    pub fn with_inner_telemetry<T: AsRef<str>>(mut self, prefix: T) -> Self {
+        self.inner_telemetry = NestedTelemetry::forward(prefix);
+        self
+    }
+
     /// Set denominator factor
     ///
     /// If the denominator of the update is below the `denominator_factor` (scaled with other
@@ -179,6 +205,64 @@ where
     }
 }
 
+/// Serializable configuration of [`SR1TrustRegion`]
+///
+/// Options which are `None` keep the defaults of [`SR1TrustRegion`]. The configuration is
+/// validated when the solver is constructed via [`build`](`SR1TrustRegionConfig::build`).
+///
+/// # Example
+///
+/// ```
+/// # use argmin::solver::quasinewton::{SR1TrustRegion, SR1TrustRegionConfig};
+/// # use argmin::core::Error;
+/// # fn main() -> Result<(), Error> {
+/// # let subproblem = ();
+/// let config = SR1TrustRegionConfig {
+///     radius: Some(2.0),
+///     eta: Some(1e-4),
+///     ..Default::default()
+/// };
+/// let sr1: SR1TrustRegion<_, f64> = config.build(subproblem)?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize), serde(default))]
+pub struct SR1TrustRegionConfig<F> {
+    /// See [`SR1TrustRegion::with_denominator_factor`]
+    pub denominator_factor: Option<F>,
+    /// See [`SR1TrustRegion::with_radius`]
+    pub radius: Option<F>,
+    /// See [`SR1TrustRegion::with_eta`]
+    pub eta: Option<F>,
+    /// See [`SR1TrustRegion::with_tolerance_grad`]
+    pub tolerance_grad: Option<F>,
+}
+
+impl<F> SR1TrustRegionConfig<F>
+where
+    F: ArgminFloat,
+{
+    /// Validates the configuration and constructs an [`SR1TrustRegion`] solver using the given
+    /// trust region subproblem solver.
+    pub fn build<R>(&self, subproblem: R) -> Result<SR1TrustRegion<R, F>, Error> {
+        let mut solver = SR1TrustRegion::new(subproblem);
+        if let Some(denominator_factor) = self.denominator_factor {
+            solver = solver.with_denominator_factor(denominator_factor)?;
+        }
+        if let Some(radius) = self.radius {
+            solver = solver.with_radius(radius);
+        }
+        if let Some(eta) = self.eta {
+            solver = solver.with_eta(eta)?;
+        }
+        if let Some(tol_grad) = self.tolerance_grad {
+            solver = solver.with_tolerance_grad(tol_grad)?;
+        }
+        Ok(solver)
+    }
+}
+
 impl<O, R, P, G, B, F> Solver<O, IterState<P, G, (), B, (), F>> for SR1TrustRegion<R, F>
 where
     O: CostFunction<Param = P, Output = F>
@@ -200,35 +284,40 @@ where
         "SR1 trust region"
     }
 
+    fn check_state(&self, state: &IterState<P, G, (), B, (), F>) -> Result<(), Error> {
+        if state.get_param().is_none() {
+            return Err(argmin_error!(
+                NotInitialized,
+                concat!(
+                    "`SR1TrustRegion` requires an initial parameter vector. ",
+                    "Please provide an initial guess via `Executor`s `configure` method."
+                )
+            ));
+        }
+        Ok(())
+    }
+
     fn init(
         &mut self,
         problem: &mut Problem<O>,
         mut state: IterState<P, G, (), B, (), F>,
     ) -> Result<(IterState<P, G, (), B, (), F>, Option<KV>), Error> {
+        self.check_state(&state)?;
         let param = state.take_param().ok_or_else(argmin_error_closure!(
-            NotInitialized,
-            concat!(
-                "`SR1TrustRegion` requires an initial parameter vector. ",
-                "Please provide an initial guess via `Executor`s `configure` method."
-            )
+            PotentialBug,
+            "`SR1TrustRegion`: Parameter vector in state not set."
         ))?;
 
+        let policy = state.get_initial_evaluation();
+
         let cost = state.get_cost();
-        let cost = if cost.is_infinite() {
-            problem.cost(&param)?
-        } else {
-            cost
-        };
+        let cost = policy.evaluate((!cost.is_infinite()).then_some(cost), || {
+            problem.cost(&param)
+        })?;
 
-        let grad = state
-            .take_gradient()
-            .map(Result::Ok)
-            .unwrap_or_else(|| problem.gradient(&param))?;
+        let grad = policy.evaluate(state.take_gradient(), || problem.gradient(&param))?;
 
-        let hessian = state
-            .take_hessian()
-            .map(Result::Ok)
-            .unwrap_or_else(|| problem.hessian(&param))?;
+        let hessian = policy.evaluate(state.take_hessian(), || problem.hessian(&param))?;
 
         Ok((
             state
@@ -236,7 +325,16 @@ where
                 .cost(cost)
                 .gradient(grad)
                 .hessian(hessian),
-            None,
+            Some(
+                kv!(
+                    "subproblem" => self.subproblem.name();
+                    "denominator_factor" => self.denominator_factor;
+                    "radius" => self.radius;
+                    "eta" => self.eta;
+                    "tolerance_grad" => self.tol_grad;
+                )
+                .with_prefix("config"),
+            ),
         ))
     }
 
@@ -264,11 +362,14 @@ where
 
         self.subproblem.set_radius(self.radius);
 
-        let OptimizationResult {
-            problem: sub_problem,
-            state: mut sub_state,
-            ..
-        } = Executor::new(problem.take_problem().unwrap(), self.subproblem.clone())
+        let (
+            OptimizationResult {
+                problem: sub_problem,
+                state: mut sub_state,
+                ..
+            },
+            sub_kv,
+        ) = Executor::new(problem.take_problem().unwrap(), self.subproblem.clone())
             .configure(|config| {
                 config
                     .param(xk.zero_like())
@@ -276,8 +377,7 @@ where
                     .gradient(prev_grad.clone())
                     .cost(cost)
             })
-            .ctrlc(false)
-            .run()?;
+            .run_nested()?;
 
         let sk = sub_state.take_param().ok_or_else(argmin_error_closure!(
             PotentialBug,
@@ -285,6 +385,9 @@ where
         ))?;
 
         problem.consume_problem(sub_problem);
+        let sub_kv =
+            self.inner_telemetry
+                .record(problem, "subproblem_iter_count", &sub_state, sub_kv);
 
         let xksk = xk.add(&sk);
         let dfk1 = problem.gradient(&xksk)?;
@@ -332,11 +435,14 @@ where
 
         Ok((
             state.param(xk1).cost(fk1).gradient(dfk1).hessian(hessian),
-            Some(kv!["ared" => ared;
+            Some(
+                kv!["ared" => ared;
                          "pred" => pred;
                          "ap" => ap;
                          "radius" => self.radius;
-                         "hessian_update" => hessian_update;]),
+                         "hessian_update" => hessian_update;]
+                .merge(sub_kv.unwrap_or_default()),
+            ),
         ))
     }
 
@@ -368,7 +474,9 @@ mod tests {
             radius,
             eta,
             tol_grad,
+            inner_telemetry,
         } = sr1;
+        assert_eq!(inner_telemetry, NestedTelemetry::new());
 
         assert_eq!(denominator_factor.to_ne_bytes(), 1e-8f64.to_ne_bytes());
         assert_eq!(subproblem, MyFakeSubProblem {});
@@ -431,6 +539,35 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_config_build() {
+        #[derive(Eq, PartialEq, Debug, Clone, Copy)]
+        struct MyFakeSubProblem {}
+
+        let config = SR1TrustRegionConfig {
+            denominator_factor: Some(0.5),
+            radius: Some(2.0),
+            eta: Some(1e-4),
+            tolerance_grad: Some(1e-5),
+        };
+        let sr1: SR1TrustRegion<_, f64> = config.build(MyFakeSubProblem {}).unwrap();
+        assert_eq!(sr1.denominator_factor.to_ne_bytes(), 0.5f64.to_ne_bytes());
+        assert_eq!(sr1.radius.to_ne_bytes(), 2.0f64.to_ne_bytes());
+        assert_eq!(sr1.eta.to_ne_bytes(), 1e-4f64.to_ne_bytes());
+        assert_eq!(sr1.tol_grad.to_ne_bytes(), 1e-5f64.to_ne_bytes());
+
+        let config = SR1TrustRegionConfig {
+            eta: Some(1.0),
+            ..Default::default()
+        };
+        let res: Result<SR1TrustRegion<_, f64>, _> = config.build(MyFakeSubProblem {});
+        assert_error!(
+            res,
+            ArgminError,
+            "Invalid parameter: \"SR1TrustRegion: eta must be in (0, 10^-3).\""
+        );
+    }
+
     #[test]
     fn test_init() {
         let subproblem = CauchyPoint::new();
@@ -458,7 +595,17 @@ mod tests {
         let problem = TestProblem::new();
         let (mut state_out, kv) = sr1.init(&mut Problem::new(problem), state).unwrap();
 
-        assert!(kv.is_none());
+        assert_eq!(
+            kv.unwrap(),
+            kv!(
+                "subproblem" => "Cauchy Point";
+                "denominator_factor" => 1e-8f64;
+                "radius" => 1.0f64;
+                "eta" => 0.5f64 * 1e-3;
+                "tolerance_grad" => 1e-3f64;
+            )
+            .with_prefix("config")
+        );
 
         let s_param = state_out.take_param().unwrap();
 
@@ -489,7 +636,7 @@ mod tests {
         let problem = TestProblem::new();
         let (state_out, kv) = sr1.init(&mut Problem::new(problem), state).unwrap();
 
-        assert!(kv.is_none());
+        assert!(kv.is_some());
 
         assert_eq!(state_out.get_cost().to_ne_bytes(), 1234.0f64.to_ne_bytes())
     }
@@ -509,7 +656,7 @@ mod tests {
         let problem = TestProblem::new();
         let (mut state_out, kv) = sr1.init(&mut Problem::new(problem), state).unwrap();
 
-        assert!(kv.is_none());
+        assert!(kv.is_some());
 
         let s_grad = state_out.take_gradient().unwrap();
 
@@ -536,7 +683,7 @@ mod tests {
         let problem = TestProblem::new();
         let (mut state_out, kv) = sr1.init(&mut Problem::new(problem), state).unwrap();
 
-        assert!(kv.is_none());
+        assert!(kv.is_some());
 
         let s_hessian = state_out.take_hessian().unwrap();
 
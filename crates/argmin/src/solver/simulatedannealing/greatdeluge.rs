@@ -0,0 +1,540 @@
+// Copyright 2018-2024 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use super::Anneal;
+use crate::core::{
+    ArgminFloat, CostFunction, Error, IterState, Problem, Solver, TerminationReason,
+    TerminationStatus, KV,
+};
+#[cfg(feature = "serde1")]
+use serde::{Deserialize, Serialize};
+
+/// # Great Deluge
+///
+/// The Great Deluge algorithm is a deterministic relative of
+/// [`SimulatedAnnealing`](`super::SimulatedAnnealing`). In each iteration, the parameter vector
+/// is modified via [`Anneal::anneal`] and the new parameter vector is accepted if it improves on
+/// the current one or if its cost function value is below the current "water level". The water
+/// level starts at the initial level (by default the cost function value of the initial
+/// parameter vector) and is lowered by the rain speed after each iteration, which continuously
+/// narrows down the region of acceptable solutions.
+///
+/// The extent passed to [`Anneal::anneal`] is constant and can be set via
+/// [`GreatDeluge::with_extent`] (defaults to `1`).
+///
+/// The algorithm stops if no new parameter vector was accepted for a given number of iterations
+/// ([`GreatDeluge::with_stall_accepted`]) or if no new best parameter vector was found for a given
+/// number of iterations ([`GreatDeluge::with_stall_best`]).
+///
+/// Requires an initial parameter vector, which has to be provided via
+/// [`configure`](`crate::core::Executor::configure`) of [`Executor`](`crate::core::Executor`).
+///
+/// ## Requirements on the optimization problem
+///
+/// The optimization problem is required to implement [`CostFunction`] and [`Anneal`].
+///
+/// ## Reference
+///
+/// G Dueck. (1993). "New optimization heuristics: The great deluge algorithm and the
+/// record-to-record travel". Journal of Computational Physics, 104(1), pp. 86-92.
+/// DOI: 10.1006/jcph.1993.1010
+#[derive(Clone)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+pub struct GreatDeluge<F> {
+    /// Amount the water level is lowered by after each iteration
+    rain_speed: F,
+    /// Initial water level (cost function value of the initial parameter vector if `None`)
+    init_level: Option<F>,
+    /// Current water level
+    level: F,
+    /// Extent passed to `anneal`
+    extent: F,
+    /// Number of iterations since the last accepted solution
+    stall_iter_accepted: u64,
+    /// Stop if `stall_iter_accepted` exceeds this number
+    stall_iter_accepted_limit: u64,
+    /// Number of iterations since the last best solution was found
+    stall_iter_best: u64,
+    /// Stop if `stall_iter_best` exceeds this number
+    stall_iter_best_limit: u64,
+}
+
+impl<F> GreatDeluge<F>
+where
+    F: ArgminFloat,
+{
+    /// Construct a new instance of [`GreatDeluge`]
+    ///
+    /// Takes the rain speed as input, which must be > 0.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::simulatedannealing::GreatDeluge;
+    /// # use argmin::core::Error;
+    /// # fn main() -> Result<(), Error> {
+    /// let gd = GreatDeluge::new(0.01f64)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn new(rain_speed: F) -> Result<Self, Error> {
+        if rain_speed <= float!(0.0) {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "`GreatDeluge`: Rain speed must be > 0."
+            ));
+        }
+        Ok(GreatDeluge {
+            rain_speed,
+            init_level: None,
+            level: F::infinity(),
+            extent: float!(1.0),
+            stall_iter_accepted: 0,
+            stall_iter_accepted_limit: u64::MAX,
+            stall_iter_best: 0,
+            stall_iter_best_limit: u64::MAX,
+        })
+    }
+
+    /// Set the initial water level
+    ///
+    /// Defaults to the cost function value of the initial parameter vector.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::simulatedannealing::GreatDeluge;
+    /// # use argmin::core::Error;
+    /// # fn main() -> Result<(), Error> {
+    /// let gd = GreatDeluge::new(0.01f64)?.with_initial_level(100.0);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn with_initial_level(mut self, level: F) -> Self {
+        self.init_level = Some(level);
+        self
+    }
+
+    /// Set the extent passed to [`Anneal::anneal`]
+    ///
+    /// Must be > 0 and defaults to `1`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::simulatedannealing::GreatDeluge;
+    /// # use argmin::core::Error;
+    /// # fn main() -> Result<(), Error> {
+    /// let gd = GreatDeluge::new(0.01f64)?.with_extent(0.5)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_extent(mut self, extent: F) -> Result<Self, Error> {
+        if extent <= float!(0.0) {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "`GreatDeluge`: Extent must be > 0."
+            ));
+        }
+        self.extent = extent;
+        Ok(self)
+    }
+
+    /// If there are no accepted solutions for `iter` iterations, the algorithm stops.
+    ///
+    /// Defaults to `u64::MAX`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::simulatedannealing::GreatDeluge;
+    /// # use argmin::core::Error;
+    /// # fn main() -> Result<(), Error> {
+    /// let gd = GreatDeluge::new(0.01f64)?.with_stall_accepted(1000);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn with_stall_accepted(mut self, iter: u64) -> Self {
+        self.stall_iter_accepted_limit = iter;
+        self
+    }
+
+    /// If there are no new best solutions for `iter` iterations, the algorithm stops.
+    ///
+    /// Defaults to `u64::MAX`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::simulatedannealing::GreatDeluge;
+    /// # use argmin::core::Error;
+    /// # fn main() -> Result<(), Error> {
+    /// let gd = GreatDeluge::new(0.01f64)?.with_stall_best(2000);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn with_stall_best(mut self, iter: u64) -> Self {
+        self.stall_iter_best_limit = iter;
+        self
+    }
+}
+
+/// Serializable configuration of [`GreatDeluge`]
+///
+/// Options which are `None` keep the defaults of [`GreatDeluge`]. The configuration is validated
+/// when the solver is constructed via [`build`](`GreatDelugeConfig::build`).
+///
+/// # Example
+///
+/// ```
+/// # use argmin::solver::simulatedannealing::GreatDelugeConfig;
+/// # use argmin::core::Error;
+/// # fn main() -> Result<(), Error> {
+/// let config = GreatDelugeConfig {
+///     rain_speed: 0.01f64,
+///     initial_level: Some(100.0),
+///     ..Default::default()
+/// };
+/// let gd = config.build()?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize), serde(default))]
+pub struct GreatDelugeConfig<F> {
+    /// Rain speed, must be `> 0`
+    pub rain_speed: F,
+    /// See [`GreatDeluge::with_initial_level`]
+    pub initial_level: Option<F>,
+    /// See [`GreatDeluge::with_extent`]
+    pub extent: Option<F>,
+    /// See [`GreatDeluge::with_stall_accepted`]
+    pub stall_accepted: Option<u64>,
+    /// See [`GreatDeluge::with_stall_best`]
+    pub stall_best: Option<u64>,
+}
+
+impl<F> GreatDelugeConfig<F>
+where
+    F: ArgminFloat,
+{
+    /// Validates the configuration and constructs a [`GreatDeluge`] solver.
+    pub fn build(&self) -> Result<GreatDeluge<F>, Error> {
+        let mut solver = GreatDeluge::new(self.rain_speed)?;
+        if let Some(level) = self.initial_level {
+            solver = solver.with_initial_level(level);
+        }
+        if let Some(extent) = self.extent {
+            solver = solver.with_extent(extent)?;
+        }
+        if let Some(iter) = self.stall_accepted {
+            solver = solver.with_stall_accepted(iter);
+        }
+        if let Some(iter) = self.stall_best {
+            solver = solver.with_stall_best(iter);
+        }
+        Ok(solver)
+    }
+}
+
+impl<O, P, F> Solver<O, IterState<P, (), (), (), (), F>> for GreatDeluge<F>
+where
+    O: CostFunction<Param = P, Output = F> + Anneal<Param = P, Output = P, Float = F>,
+    P: Clone,
+    F: ArgminFloat,
+{
+    fn name(&self) -> &str {
+        "Great Deluge"
+    }
+
+    fn init(
+        &mut self,
+        problem: &mut Problem<O>,
+        mut state: IterState<P, (), (), (), (), F>,
+    ) -> Result<(IterState<P, (), (), (), (), F>, Option<KV>), Error> {
+        let param = state.take_param().ok_or_else(argmin_error_closure!(
+            NotInitialized,
+            concat!(
+                "`GreatDeluge` requires an initial parameter vector. ",
+                "Please provide an initial guess via `Executor`s `configure` method."
+            )
+        ))?;
+
+        let cost = state.get_cost();
+        let cost = state
+            .get_initial_evaluation()
+            .evaluate((!cost.is_infinite()).then_some(cost), || {
+                problem.cost(&param)
+            })?;
+
+        self.level = self.init_level.unwrap_or(cost);
+
+        Ok((
+            state.param(param).cost(cost),
+            Some(
+                kv!(
+                    "rain_speed" => self.rain_speed;
+                    "initial_level" => self.level;
+                    "extent" => self.extent;
+                    "stall_iter_accepted_limit" => self.stall_iter_accepted_limit;
+                    "stall_iter_best_limit" => self.stall_iter_best_limit;
+                )
+                .with_prefix("config"),
+            ),
+        ))
+    }
+
+    fn next_iter(
+        &mut self,
+        problem: &mut Problem<O>,
+        mut state: IterState<P, (), (), (), (), F>,
+    ) -> Result<(IterState<P, (), (), (), (), F>, Option<KV>), Error> {
+        let prev_param = state.take_param().ok_or_else(argmin_error_closure!(
+            PotentialBug,
+            "`GreatDeluge`: Parameter vector in state not set."
+        ))?;
+        let prev_cost = state.get_cost();
+
+        let new_param = problem.anneal(&prev_param, self.extent)?;
+        let new_cost = problem.cost(&new_param)?;
+
+        // Accept improvements as well as everything below the water level.
+        let accepted = new_cost <= prev_cost || new_cost <= self.level;
+        let new_best_found = new_cost < state.best_cost;
+
+        self.stall_iter_accepted = if accepted {
+            0
+        } else {
+            self.stall_iter_accepted + 1
+        };
+        self.stall_iter_best = if new_best_found {
+            0
+        } else {
+            self.stall_iter_best + 1
+        };
+
+        self.level = self.level - self.rain_speed;
+
+        let kv = kv!(
+            "level" => self.level;
+            "new_be" => new_best_found;
+            "acc" => accepted;
+            "st_i_be" => self.stall_iter_best;
+            "st_i_ac" => self.stall_iter_accepted;
+        );
+
+        Ok((
+            if accepted {
+                state.param(new_param).cost(new_cost)
+            } else {
+                state.param(prev_param).cost(prev_cost)
+            },
+            Some(kv),
+        ))
+    }
+
+    fn terminate(&mut self, _state: &IterState<P, (), (), (), (), F>) -> TerminationStatus {
+        if self.stall_iter_accepted > self.stall_iter_accepted_limit {
+            return TerminationStatus::Terminated(TerminationReason::StallAccepted {
+                iters: self.stall_iter_accepted,
+            });
+        }
+        if self.stall_iter_best > self.stall_iter_best_limit {
+            return TerminationStatus::Terminated(TerminationReason::StallBest {
+                iters: self.stall_iter_best,
+            });
+        }
+        TerminationStatus::NotTerminated
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::{ArgminError, State};
+
+    test_trait_impl!(great_deluge, GreatDeluge<f64>);
+
+    /// Uses the parameter as cost and moves uphill by the extent
+    struct Uphill {}
+
+    impl CostFunction for Uphill {
+        type Param = f64;
+        type Output = f64;
+
+        fn cost(&self, param: &f64) -> Result<f64, Error> {
+            Ok(*param)
+        }
+    }
+
+    impl Anneal for Uphill {
+        type Param = f64;
+        type Output = f64;
+        type Float = f64;
+
+        fn anneal(&self, param: &f64, extent: f64) -> Result<f64, Error> {
+            Ok(param + extent)
+        }
+    }
+
+    #[test]
+    fn test_new() {
+        let GreatDeluge {
+            rain_speed,
+            init_level,
+            level,
+            extent,
+            stall_iter_accepted,
+            stall_iter_accepted_limit,
+            stall_iter_best,
+            stall_iter_best_limit,
+        } = GreatDeluge::new(0.5f64).unwrap();
+
+        assert_eq!(rain_speed.to_ne_bytes(), 0.5f64.to_ne_bytes());
+        assert!(init_level.is_none());
+        assert!(level.is_infinite());
+        assert_eq!(extent.to_ne_bytes(), 1.0f64.to_ne_bytes());
+        assert_eq!(stall_iter_accepted, 0);
+        assert_eq!(stall_iter_accepted_limit, u64::MAX);
+        assert_eq!(stall_iter_best, 0);
+        assert_eq!(stall_iter_best_limit, u64::MAX);
+
+        for rain_speed in [0.0, -1.0] {
+            let res = GreatDeluge::new(rain_speed);
+            assert_error!(
+                res,
+                ArgminError,
+                "Invalid parameter: \"`GreatDeluge`: Rain speed must be > 0.\""
+            );
+        }
+    }
+
+    #[test]
+    fn test_with_extent() {
+        let gd = GreatDeluge::new(0.5f64).unwrap().with_extent(2.0).unwrap();
+        assert_eq!(gd.extent.to_ne_bytes(), 2.0f64.to_ne_bytes());
+
+        for extent in [0.0, -1.0] {
+            let res = GreatDeluge::new(0.5f64).unwrap().with_extent(extent);
+            assert_error!(
+                res,
+                ArgminError,
+                "Invalid parameter: \"`GreatDeluge`: Extent must be > 0.\""
+            );
+        }
+    }
+
+    #[test]
+    fn test_config_build() {
+        let gd = GreatDelugeConfig {
+            rain_speed: 0.5f64,
+            initial_level: Some(10.0),
+            extent: Some(2.0),
+            stall_accepted: Some(10),
+            stall_best: Some(20),
+        }
+        .build()
+        .unwrap();
+        assert_eq!(gd.rain_speed.to_ne_bytes(), 0.5f64.to_ne_bytes());
+        assert_eq!(gd.init_level, Some(10.0));
+        assert_eq!(gd.extent.to_ne_bytes(), 2.0f64.to_ne_bytes());
+        assert_eq!(gd.stall_iter_accepted_limit, 10);
+        assert_eq!(gd.stall_iter_best_limit, 20);
+
+        let res = GreatDelugeConfig::<f64>::default().build();
+        assert_error!(
+            res,
+            ArgminError,
+            "Invalid parameter: \"`GreatDeluge`: Rain speed must be > 0.\""
+        );
+    }
+
+    #[test]
+    fn test_init() {
+        let mut gd = GreatDeluge::new(0.5f64).unwrap();
+        let res = gd.init(&mut Problem::new(Uphill {}), IterState::new());
+        assert_error!(
+            res,
+            ArgminError,
+            concat!(
+                "Not initialized: \"`GreatDeluge` requires an initial parameter vector. ",
+                "Please provide an initial guess via `Executor`s `configure` method.\""
+            )
+        );
+
+        // Level defaults to the initial cost
+        gd.init(&mut Problem::new(Uphill {}), IterState::new().param(3.0))
+            .unwrap();
+        assert_eq!(gd.level.to_ne_bytes(), 3.0f64.to_ne_bytes());
+
+        let mut gd = GreatDeluge::new(0.5f64).unwrap().with_initial_level(10.0);
+        gd.init(&mut Problem::new(Uphill {}), IterState::new().param(3.0))
+            .unwrap();
+        assert_eq!(gd.level.to_ne_bytes(), 10.0f64.to_ne_bytes());
+    }
+
+    #[test]
+    fn test_next_iter() {
+        let mut gd = GreatDeluge::new(1.0f64)
+            .unwrap()
+            .with_initial_level(2.5)
+            .with_extent(1.0)
+            .unwrap();
+        let mut problem = Problem::new(Uphill {});
+        let (mut state, _) = gd
+            .init(&mut problem, IterState::new().param(0.0f64))
+            .unwrap();
+        state.update();
+
+        // Level 2.5: cost 1 is accepted
+        let (mut state, kv) = gd.next_iter(&mut problem, state).unwrap();
+        assert_eq!(state.get_cost().to_ne_bytes(), 1.0f64.to_ne_bytes());
+        assert_eq!(gd.level.to_ne_bytes(), 1.5f64.to_ne_bytes());
+        assert_eq!(gd.stall_iter_accepted, 0);
+        assert_eq!(gd.stall_iter_best, 1);
+        assert!(kv.unwrap().get("acc").unwrap().get_bool().unwrap());
+        state.update();
+
+        // Level 1.5: cost 2 is rejected
+        let (state, kv) = gd.next_iter(&mut problem, state).unwrap();
+        assert_eq!(state.get_cost().to_ne_bytes(), 1.0f64.to_ne_bytes());
+        assert_eq!(gd.level.to_ne_bytes(), 0.5f64.to_ne_bytes());
+        assert_eq!(gd.stall_iter_accepted, 1);
+        assert!(!kv.unwrap().get("acc").unwrap().get_bool().unwrap());
+    }
+
+    #[test]
+    fn test_terminate() {
+        let mut gd = GreatDeluge::new(0.5f64)
+            .unwrap()
+            .with_stall_accepted(10)
+            .with_stall_best(20);
+        let state: IterState<f64, (), (), (), (), f64> = IterState::new();
+
+        gd.stall_iter_accepted = 10;
+        gd.stall_iter_best = 20;
+        assert_eq!(
+            Solver::<Uphill, _>::terminate(&mut gd, &state),
+            TerminationStatus::NotTerminated
+        );
+
+        gd.stall_iter_best = 21;
+        assert_eq!(
+            Solver::<Uphill, _>::terminate(&mut gd, &state),
+            TerminationStatus::Terminated(TerminationReason::StallBest { iters: 21 })
+        );
+
+        gd.stall_iter_accepted = 11;
+        assert_eq!(
+            Solver::<Uphill, _>::terminate(&mut gd, &state),
+            TerminationStatus::Terminated(TerminationReason::StallAccepted { iters: 11 })
+        );
+    }
+}
@@ -10,6 +10,10 @@
 //! Simulated Annealing (SA) is a stochastic optimization method which imitates annealing in
 //! metallurgy. For details see [`SimulatedAnnealing`].
 //!
+//! The module also contains the deterministic relatives [`ThresholdAccepting`] and
+//! [`GreatDeluge`], which replace the probabilistic acceptance criterion with a threshold and a
+//! water level, respectively. All solvers share the [`Anneal`] trait.
+//!
 //! ## References
 //!
 //! [Wikipedia](https://en.wikipedia.org/wiki/Simulated_annealing)
@@ -18,9 +22,15 @@
 //! Science 13 May 1983, Vol. 220, Issue 4598, pp. 671-680
 //! DOI: 10.1126/science.220.4598.671
 
+mod greatdeluge;
+mod thresholdaccepting;
+
+pub use greatdeluge::{GreatDeluge, GreatDelugeConfig};
+pub use thresholdaccepting::{ThresholdAccepting, ThresholdAcceptingConfig};
+
 use crate::core::{
-    ArgminFloat, CostFunction, Error, IterState, Problem, Solver, TerminationReason,
-    TerminationStatus, KV,
+    ArgminFloat, CostFunction, Error, IterState, KvMeta, KvScale, Problem, Solver,
+    TerminationReason, TerminationStatus, KV,
 };
 use rand::prelude::*;
 use rand_xoshiro::Xoshiro256PlusPlus;
@@ -131,6 +141,14 @@ pub enum SATempFunc<F> {
 /// modified. Please see the Simulated Annealing example for one approach to do so for floating
 /// point parameters.
 ///
+/// By default, the current temperature is passed to [`Anneal::anneal`] as the extent of the
+/// move. With [`SimulatedAnnealing::with_adaptive_scale`], the temperature is multiplied by a
+/// scale factor which is adapted such that the ratio of accepted moves stays close to a target.
+///
+/// With the `serde1` feature, the state of the RNG of the solver is stored in checkpoints, such
+/// that a resumed run continues with the same sequence of random numbers. A RNG owned by the
+/// problem (for instance for use in `anneal`) is not part of a checkpoint.
+///
 /// ## Requirements on the optimization problem
 ///
 /// The optimization problem is required to implement [`CostFunction`].
@@ -173,10 +191,46 @@ pub struct SimulatedAnnealing<F, R> {
     reanneal_iter_best: u64,
     /// current temperature
     cur_temp: F,
+    /// Adaptive scale of the moves (disabled if `None`)
+    #[cfg_attr(feature = "serde1", serde(default))]
+    adaptive_scale: Option<AdaptiveScale<F>>,
     /// random number generator
     rng: R,
 }
 
+/// Controller of the scale of the moves of [`SimulatedAnnealing`]
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+struct AdaptiveScale<F> {
+    /// Target acceptance ratio
+    target: F,
+    /// Number of iterations over which the acceptance ratio is measured
+    window: u64,
+    /// Number of iterations in the current window
+    window_iter: u64,
+    /// Number of accepted moves in the current window
+    window_accepted: u64,
+    /// Factor the temperature is multiplied with before it is passed to `anneal`
+    factor: F,
+}
+
+impl<F: ArgminFloat> AdaptiveScale<F> {
+    /// Records whether a move was accepted and updates the factor at the end of each window.
+    fn update(&mut self, accepted: bool) {
+        self.window_iter += 1;
+        if accepted {
+            self.window_accepted += 1;
+        }
+        if self.window_iter >= self.window {
+            let ratio =
+                F::from_u64(self.window_accepted).unwrap() / F::from_u64(self.window_iter).unwrap();
+            self.factor = self.factor * (float!(2.0) * (ratio - self.target)).exp();
+            self.window_iter = 0;
+            self.window_accepted = 0;
+        }
+    }
+}
+
 impl<F> SimulatedAnnealing<F, Xoshiro256PlusPlus>
 where
     F: ArgminFloat,
@@ -246,6 +300,7 @@ where
                 reanneal_best: u64::MAX,
                 reanneal_iter_best: 0,
                 cur_temp: init_temp,
+                adaptive_scale: None,
                 rng,
             })
         }
@@ -384,6 +439,49 @@ where
         self
     }
 
+    /// Adapt the extent of the moves to keep the acceptance ratio close to `target`
+    ///
+    /// The extent passed to [`Anneal::anneal`] becomes the current temperature multiplied by a
+    /// scale factor, which is initially `1`. Every `window` iterations, the ratio of accepted
+    /// moves in the past `window` iterations is computed and the scale factor is multiplied by
+    /// `exp(2 * (ratio - target))`. Thus, moves become larger if too many are accepted and smaller
+    /// if too few are accepted. A common choice for `target` is `0.44`.
+    ///
+    /// `target` must be in `(0, 1)` and `window` must be larger than `0`. Disabled by default.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::simulatedannealing::SimulatedAnnealing;
+    /// # use argmin::core::Error;
+    /// # fn main() -> Result<(), Error> {
+    /// let sa = SimulatedAnnealing::new(100.0f64)?.with_adaptive_scale(0.44, 100)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_adaptive_scale(mut self, target: F, window: u64) -> Result<Self, Error> {
+        if target <= float!(0.0) || target >= float!(1.0) {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "`SimulatedAnnealing`: Target acceptance ratio must be in (0, 1)."
+            ));
+        }
+        if window == 0 {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "`SimulatedAnnealing`: Window of adaptive scale must be > 0."
+            ));
+        }
+        self.adaptive_scale = Some(AdaptiveScale {
+            target,
+            window,
+            window_iter: 0,
+            window_accepted: 0,
+            factor: float!(1.0),
+        });
+        Ok(self)
+    }
+
     /// Update the temperature based on the current iteration number.
     ///
     /// Updates are performed based on specific update functions. See `SATempFunc` for details.
@@ -435,6 +533,100 @@ where
     }
 }
 
+/// Serializable configuration of [`SimulatedAnnealing`]
+///
+/// Options which are `None` keep the defaults of [`SimulatedAnnealing`]. The configuration is
+/// validated when the solver is constructed via [`build`](`SimulatedAnnealingConfig::build`) or
+/// [`build_with_rng`](`SimulatedAnnealingConfig::build_with_rng`).
+///
+/// # Example
+///
+/// ```
+/// # use argmin::solver::simulatedannealing::{SATempFunc, SimulatedAnnealingConfig};
+/// # use argmin::core::Error;
+/// # fn main() -> Result<(), Error> {
+/// let config = SimulatedAnnealingConfig {
+///     initial_temperature: 15.0f64,
+///     temp_func: Some(SATempFunc::Boltzmann),
+///     stall_best: Some(1000),
+///     seed: Some(42),
+///     ..Default::default()
+/// };
+/// let sa = config.build()?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize), serde(default))]
+pub struct SimulatedAnnealingConfig<F> {
+    /// Initial temperature, must be `> 0`
+    pub initial_temperature: F,
+    /// See [`SimulatedAnnealing::with_temp_func`]
+    pub temp_func: Option<SATempFunc<F>>,
+    /// See [`SimulatedAnnealing::with_stall_accepted`]
+    pub stall_accepted: Option<u64>,
+    /// See [`SimulatedAnnealing::with_stall_best`]
+    pub stall_best: Option<u64>,
+    /// See [`SimulatedAnnealing::with_reannealing_fixed`]
+    pub reannealing_fixed: Option<u64>,
+    /// See [`SimulatedAnnealing::with_reannealing_accepted`]
+    pub reannealing_accepted: Option<u64>,
+    /// See [`SimulatedAnnealing::with_reannealing_best`]
+    pub reannealing_best: Option<u64>,
+    /// Target acceptance ratio, see [`SimulatedAnnealing::with_adaptive_scale`]
+    pub adaptive_scale_target: Option<F>,
+    /// Window of the adaptive scale, see [`SimulatedAnnealing::with_adaptive_scale`]. Defaults
+    /// to `100` if only the target is given.
+    pub adaptive_scale_window: Option<u64>,
+    /// Seed of the `Xoshiro256PlusPlus` RNG used by [`build`](`SimulatedAnnealingConfig::build`).
+    /// If `None`, the RNG is seeded from entropy.
+    pub seed: Option<u64>,
+}
+
+impl<F> SimulatedAnnealingConfig<F>
+where
+    F: ArgminFloat,
+{
+    /// Validates the configuration and constructs a [`SimulatedAnnealing`] solver using the
+    /// `Xoshiro256PlusPlus` RNG.
+    pub fn build(&self) -> Result<SimulatedAnnealing<F, Xoshiro256PlusPlus>, Error> {
+        let rng = match self.seed {
+            Some(seed) => Xoshiro256PlusPlus::seed_from_u64(seed),
+            None => Xoshiro256PlusPlus::from_entropy(),
+        };
+        self.build_with_rng(rng)
+    }
+
+    /// Validates the configuration and constructs a [`SimulatedAnnealing`] solver using the given
+    /// RNG. The `seed` option is ignored.
+    pub fn build_with_rng<R>(&self, rng: R) -> Result<SimulatedAnnealing<F, R>, Error> {
+        let mut solver = SimulatedAnnealing::new_with_rng(self.initial_temperature, rng)?;
+        if let Some(temp_func) = self.temp_func {
+            solver = solver.with_temp_func(temp_func);
+        }
+        if let Some(iter) = self.stall_accepted {
+            solver = solver.with_stall_accepted(iter);
+        }
+        if let Some(iter) = self.stall_best {
+            solver = solver.with_stall_best(iter);
+        }
+        if let Some(iter) = self.reannealing_fixed {
+            solver = solver.with_reannealing_fixed(iter);
+        }
+        if let Some(iter) = self.reannealing_accepted {
+            solver = solver.with_reannealing_accepted(iter);
+        }
+        if let Some(iter) = self.reannealing_best {
+            solver = solver.with_reannealing_best(iter);
+        }
+        if let Some(target) = self.adaptive_scale_target {
+            solver =
+                solver.with_adaptive_scale(target, self.adaptive_scale_window.unwrap_or(100))?;
+        }
+        Ok(solver)
+    }
+}
+
 impl<O, P, F, R> Solver<O, IterState<P, (), (), (), (), F>> for SimulatedAnnealing<F, R>
 where
     O: CostFunction<Param = P, Output = F> + Anneal<Param = P, Output = P, Float = F>,
@@ -450,31 +642,48 @@ where
         problem: &mut Problem<O>,
         mut state: IterState<P, (), (), (), (), F>,
     ) -> Result<(IterState<P, (), (), (), (), F>, Option<KV>), Error> {
-        let param = state.take_param().ok_or_else(argmin_error_closure!(
-            NotInitialized,
-            concat!(
-                "`SimulatedAnnealing` requires an initial parameter vector. ",
-                "Please provide an initial guess via `Executor`s `configure` method."
-            )
-        ))?;
+        // Without an initial guess, draw one from the bounds (if provided)
+        let param = match state.take_param() {
+            Some(param) => param,
+            None => state
+                .sample_param(&mut self.rng)
+                .ok_or_else(argmin_error_closure!(
+                    NotInitialized,
+                    concat!(
+                        "`SimulatedAnnealing` requires an initial parameter vector. ",
+                        "Please provide an initial guess or bounds via `Executor`s `configure` ",
+                        "method."
+                    )
+                ))?,
+        };
 
         let cost = state.get_cost();
-        let cost = if cost.is_infinite() {
-            problem.cost(&param)?
-        } else {
-            cost
-        };
+        let cost = state
+            .get_initial_evaluation()
+            .evaluate((!cost.is_infinite()).then_some(cost), || {
+                problem.cost(&param)
+            })?;
+
+        let mut config = kv!(
+            "initial_temperature" => self.init_temp;
+            "temp_func" => format!("{:?}", self.temp_func);
+            "stall_iter_accepted_limit" => self.stall_iter_accepted_limit;
+            "stall_iter_best_limit" => self.stall_iter_best_limit;
+            "reanneal_fixed" => self.reanneal_fixed;
+            "reanneal_accepted" => self.reanneal_accepted;
+            "reanneal_best" => self.reanneal_best;
+            "rng" => std::any::type_name::<R>();
+        );
+        if let Some(scale) = self.adaptive_scale.as_ref() {
+            config = config.merge(kv!(
+                "adaptive_scale_target" => scale.target;
+                "adaptive_scale_window" => scale.window;
+            ));
+        }
 
         Ok((
             state.param(param).cost(cost),
-            Some(kv!(
-                "initial_temperature" => self.init_temp;
-                "stall_iter_accepted_limit" => self.stall_iter_accepted_limit;
-                "stall_iter_best_limit" => self.stall_iter_best_limit;
-                "reanneal_fixed" => self.reanneal_fixed;
-                "reanneal_accepted" => self.reanneal_accepted;
-                "reanneal_best" => self.reanneal_best;
-            )),
+            Some(config.with_prefix("config")),
         ))
     }
 
@@ -495,7 +704,11 @@ where
         let prev_cost = state.get_cost();
 
         // Make a move
-        let new_param = problem.anneal(&prev_param, self.cur_temp)?;
+        let extent = match self.adaptive_scale.as_ref() {
+            Some(scale) => self.cur_temp * scale.factor,
+            None => self.cur_temp,
+        };
+        let new_param = problem.anneal(&prev_param, extent)?;
 
         // Evaluate cost function with new parameter vector
         let new_cost = problem.cost(&new_param)?;
@@ -522,6 +735,10 @@ where
         // Update stall iter variables
         self.update_stall_and_reanneal_iter(accepted, new_best_found);
 
+        if let Some(scale) = self.adaptive_scale.as_mut() {
+            scale.update(accepted);
+        }
+
         let (r_fixed, r_accepted, r_best) = self.reanneal();
 
         // Update temperature for next iteration.
@@ -532,38 +749,55 @@ where
 
         self.update_temperature();
 
+        let mut kv = kv!(
+            "t" => self.cur_temp;
+            "new_be" => new_best_found;
+            "acc" => accepted;
+            "st_i_be" => self.stall_iter_best;
+            "st_i_ac" => self.stall_iter_accepted;
+            "ra_i_fi" => self.reanneal_iter_fixed;
+            "ra_i_be" => self.reanneal_iter_best;
+            "ra_i_ac" => self.reanneal_iter_accepted;
+            "ra_fi" => r_fixed;
+            "ra_be" => r_best;
+            "ra_ac" => r_accepted;
+        );
+        kv.set_meta(
+            "t",
+            KvMeta::new()
+                .scale(KvScale::Log)
+                .description("Current temperature"),
+        );
+        if let Some(scale) = self.adaptive_scale.as_ref() {
+            kv.insert("scale", scale.factor.into());
+            kv.set_meta(
+                "scale",
+                KvMeta::new()
+                    .scale(KvScale::Log)
+                    .description("Scale factor of the moves"),
+            );
+        }
+
         Ok((
             if accepted {
                 state.param(new_param).cost(new_cost)
             } else {
                 state.param(prev_param).cost(prev_cost)
             },
-            Some(kv!(
-                "t" => self.cur_temp;
-                "new_be" => new_best_found;
-                "acc" => accepted;
-                "st_i_be" => self.stall_iter_best;
-                "st_i_ac" => self.stall_iter_accepted;
-                "ra_i_fi" => self.reanneal_iter_fixed;
-                "ra_i_be" => self.reanneal_iter_best;
-                "ra_i_ac" => self.reanneal_iter_accepted;
-                "ra_fi" => r_fixed;
-                "ra_be" => r_best;
-                "ra_ac" => r_accepted;
-            )),
+            Some(kv),
         ))
     }
 
     fn terminate(&mut self, _state: &IterState<P, (), (), (), (), F>) -> TerminationStatus {
         if self.stall_iter_accepted > self.stall_iter_accepted_limit {
-            return TerminationStatus::Terminated(TerminationReason::SolverExit(
-                "AcceptedStallIterExceeded".to_string(),
-            ));
+            return TerminationStatus::Terminated(TerminationReason::StallAccepted {
+                iters: self.stall_iter_accepted,
+            });
         }
         if self.stall_iter_best > self.stall_iter_best_limit {
-            return TerminationStatus::Terminated(TerminationReason::SolverExit(
-                "BestStallIterExceeded".to_string(),
-            ));
+            return TerminationStatus::Terminated(TerminationReason::StallBest {
+                iters: self.stall_iter_best,
+            });
         }
         TerminationStatus::NotTerminated
     }
@@ -596,6 +830,7 @@ mod tests {
             reanneal_best,
             reanneal_iter_best,
             cur_temp,
+            adaptive_scale,
             rng: _rng,
         } = sa;
 
@@ -613,6 +848,7 @@ mod tests {
         assert_eq!(reanneal_best, u64::MAX);
         assert_eq!(reanneal_iter_best, 0);
         assert_eq!(cur_temp.to_ne_bytes(), 100.0f64.to_ne_bytes());
+        assert!(adaptive_scale.is_none());
 
         for temp in [0.0, -1.0, -f64::EPSILON, -100.0] {
             let res = SimulatedAnnealing::new(temp);
@@ -646,6 +882,7 @@ mod tests {
             reanneal_best,
             reanneal_iter_best,
             cur_temp,
+            adaptive_scale,
             rng,
         } = sa;
 
@@ -663,6 +900,7 @@ mod tests {
         assert_eq!(reanneal_best, u64::MAX);
         assert_eq!(reanneal_iter_best, 0);
         assert_eq!(cur_temp.to_ne_bytes(), 100.0f64.to_ne_bytes());
+        assert!(adaptive_scale.is_none());
         // important part
         assert_eq!(rng, MyRng {});
 
@@ -740,6 +978,183 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_config_build() {
+        let config = SimulatedAnnealingConfig {
+            initial_temperature: 10.0f64,
+            temp_func: Some(SATempFunc::Exponential(0.8)),
+            stall_accepted: Some(1),
+            stall_best: Some(2),
+            reannealing_fixed: Some(3),
+            reannealing_accepted: Some(4),
+            reannealing_best: Some(5),
+            adaptive_scale_target: Some(0.44),
+            adaptive_scale_window: None,
+            seed: Some(42),
+        };
+        let sa = config.build().unwrap();
+        assert_eq!(sa.init_temp.to_ne_bytes(), 10.0f64.to_ne_bytes());
+        assert_eq!(sa.temp_func, SATempFunc::Exponential(0.8));
+        assert_eq!(sa.stall_iter_accepted_limit, 1);
+        assert_eq!(sa.stall_iter_best_limit, 2);
+        assert_eq!(sa.reanneal_fixed, 3);
+        assert_eq!(sa.reanneal_accepted, 4);
+        assert_eq!(sa.reanneal_best, 5);
+        let scale = sa.adaptive_scale.unwrap();
+        assert_eq!(scale.target.to_ne_bytes(), 0.44f64.to_ne_bytes());
+        assert_eq!(scale.window, 100);
+
+        // Same seed yields the same RNG
+        let mut rng1 = config.build().unwrap().rng;
+        let mut rng2 = config.build().unwrap().rng;
+        assert_eq!(rng1.next_u64(), rng2.next_u64());
+
+        let sa = SimulatedAnnealingConfig {
+            initial_temperature: 10.0f64,
+            ..Default::default()
+        }
+        .build()
+        .unwrap();
+        assert_eq!(sa.temp_func, SATempFunc::TemperatureFast);
+        assert_eq!(sa.stall_iter_best_limit, u64::MAX);
+
+        let res = SimulatedAnnealingConfig {
+            adaptive_scale_target: Some(1.0),
+            ..config
+        }
+        .build();
+        assert_error!(
+            res,
+            ArgminError,
+            "Invalid parameter: \"`SimulatedAnnealing`: Target acceptance ratio must be in (0, 1).\""
+        );
+
+        let res = SimulatedAnnealingConfig::<f64>::default().build();
+        assert_error!(
+            res,
+            ArgminError,
+            "Invalid parameter: \"`SimulatedAnnealing`: Initial temperature must be > 0.\""
+        );
+    }
+
+    #[test]
+    fn test_with_adaptive_scale() {
+        let sa = SimulatedAnnealing::new(100.0f64)
+            .unwrap()
+            .with_adaptive_scale(0.44, 50)
+            .unwrap();
+        assert_eq!(
+            sa.adaptive_scale,
+            Some(AdaptiveScale {
+                target: 0.44,
+                window: 50,
+                window_iter: 0,
+                window_accepted: 0,
+                factor: 1.0,
+            })
+        );
+
+        for target in [0.0, 1.0, -0.5, 1.5] {
+            let res = SimulatedAnnealing::new(100.0f64)
+                .unwrap()
+                .with_adaptive_scale(target, 50);
+            assert_error!(
+                res,
+                ArgminError,
+                "Invalid parameter: \"`SimulatedAnnealing`: Target acceptance ratio must be in (0, 1).\""
+            );
+        }
+
+        let res = SimulatedAnnealing::new(100.0f64)
+            .unwrap()
+            .with_adaptive_scale(0.44, 0);
+        assert_error!(
+            res,
+            ArgminError,
+            "Invalid parameter: \"`SimulatedAnnealing`: Window of adaptive scale must be > 0.\""
+        );
+    }
+
+    #[test]
+    fn test_adaptive_scale_update() {
+        let mut scale = SimulatedAnnealing::new(100.0f64)
+            .unwrap()
+            .with_adaptive_scale(0.5, 4)
+            .unwrap()
+            .adaptive_scale
+            .unwrap();
+
+        // Acceptance ratio above the target increases the scale
+        for accepted in [true, true, true, false] {
+            scale.update(accepted);
+        }
+        assert_relative_eq!(scale.factor, 0.5f64.exp(), epsilon = f64::EPSILON);
+        assert_eq!(scale.window_iter, 0);
+        assert_eq!(scale.window_accepted, 0);
+
+        // Acceptance ratio below the target decreases the scale
+        for _ in 0..4 {
+            scale.update(false);
+        }
+        assert_relative_eq!(scale.factor, (-0.5f64).exp(), epsilon = f64::EPSILON);
+
+        // Scale is only updated at the end of a window
+        scale.update(true);
+        assert_relative_eq!(scale.factor, (-0.5f64).exp(), epsilon = f64::EPSILON);
+        assert_eq!(scale.window_iter, 1);
+        assert_eq!(scale.window_accepted, 1);
+    }
+
+    #[test]
+    fn test_adaptive_scale_extent() {
+        use std::cell::RefCell;
+
+        // Records the extent passed to `anneal`, every move is accepted
+        struct Recorder(RefCell<Vec<f64>>);
+
+        impl CostFunction for Recorder {
+            type Param = f64;
+            type Output = f64;
+
+            fn cost(&self, param: &f64) -> Result<f64, Error> {
+                Ok(*param)
+            }
+        }
+
+        impl Anneal for Recorder {
+            type Param = f64;
+            type Output = f64;
+            type Float = f64;
+
+            fn anneal(&self, param: &f64, extent: f64) -> Result<f64, Error> {
+                self.0.borrow_mut().push(extent);
+                Ok(*param - 1.0)
+            }
+        }
+
+        let mut sa = SimulatedAnnealing::new(100.0f64)
+            .unwrap()
+            .with_temp_func(SATempFunc::Exponential(1.0))
+            .with_adaptive_scale(0.5, 2)
+            .unwrap();
+        let mut problem = Problem::new(Recorder(RefCell::new(vec![])));
+        let mut state = IterState::new().param(0.0f64).cost(0.0);
+        let mut kv = None;
+        for _ in 0..4 {
+            (state, kv) = sa.next_iter(&mut problem, state).unwrap();
+        }
+        let extents = problem.problem.unwrap().0.into_inner();
+        assert_relative_eq!(extents[0], 100.0, epsilon = f64::EPSILON);
+        assert_relative_eq!(extents[1], 100.0, epsilon = f64::EPSILON);
+        assert_relative_eq!(extents[2], 100.0 * 1f64.exp(), epsilon = 1e-12);
+        assert_relative_eq!(extents[3], 100.0 * 1f64.exp(), epsilon = 1e-12);
+        assert_relative_eq!(
+            kv.unwrap().get("scale").unwrap().get_float().unwrap(),
+            2f64.exp(),
+            epsilon = 1e-12
+        );
+    }
+
     #[test]
     fn test_update_temperature() {
         for (func, val) in [
@@ -852,7 +1267,8 @@ mod tests {
             ArgminError,
             concat!(
                 "Not initialized: \"`SimulatedAnnealing` requires an initial parameter vector. ",
-                "Please provide an initial guess via `Executor`s `configure` method.\""
+                "Please provide an initial guess or bounds via `Executor`s `configure` ",
+                "method.\""
             )
         );
 
@@ -863,12 +1279,15 @@ mod tests {
 
         let kv_expected = kv!(
             "initial_temperature" => 100.0f64;
+            "temp_func" => "TemperatureFast";
             "stall_iter_accepted_limit" => stall_iter_accepted_limit;
             "stall_iter_best_limit" => stall_iter_best_limit;
             "reanneal_fixed" => reanneal_fixed;
             "reanneal_accepted" => reanneal_accepted;
             "reanneal_best" => reanneal_best;
-        );
+            "rng" => std::any::type_name::<Xoshiro256PlusPlus>();
+        )
+        .with_prefix("config");
 
         assert_eq!(kv.unwrap(), kv_expected);
 
@@ -880,4 +1299,47 @@ mod tests {
 
         assert_eq!(state_out.get_cost().to_ne_bytes(), 1.0f64.to_ne_bytes())
     }
+
+    #[test]
+    fn test_init_from_bounds() {
+        let mut sa = SimulatedAnnealing::new_with_rng(100.0f64, StdRng::seed_from_u64(42)).unwrap();
+
+        let state: IterState<Vec<f64>, (), (), (), (), f64> =
+            IterState::new().bounds(vec![-1.0, 2.0], vec![1.0, 3.0]);
+        let (mut state_out, _) = sa
+            .init(&mut Problem::new(TestProblem::new()), state)
+            .unwrap();
+
+        let param = state_out.take_param().unwrap();
+        assert!((-1.0..=1.0).contains(&param[0]));
+        assert!((2.0..=3.0).contains(&param[1]));
+    }
+
+    #[test]
+    fn test_terminate() {
+        let mut sa = SimulatedAnnealing::new(100.0f64)
+            .unwrap()
+            .with_stall_accepted(10)
+            .with_stall_best(20);
+        let state: IterState<Vec<f64>, (), (), (), (), f64> = IterState::new();
+
+        sa.stall_iter_accepted = 10;
+        sa.stall_iter_best = 20;
+        assert_eq!(
+            Solver::<TestProblem, _>::terminate(&mut sa, &state),
+            TerminationStatus::NotTerminated
+        );
+
+        sa.stall_iter_best = 21;
+        assert_eq!(
+            Solver::<TestProblem, _>::terminate(&mut sa, &state),
+            TerminationStatus::Terminated(TerminationReason::StallBest { iters: 21 })
+        );
+
+        sa.stall_iter_accepted = 11;
+        assert_eq!(
+            Solver::<TestProblem, _>::terminate(&mut sa, &state),
+            TerminationStatus::Terminated(TerminationReason::StallAccepted { iters: 11 })
+        );
+    }
 }
@@ -0,0 +1,511 @@
+// Copyright 2018-2024 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use super::Anneal;
+use crate::core::{
+    ArgminFloat, CostFunction, Error, IterState, KvMeta, KvScale, Problem, Solver,
+    TerminationReason, TerminationStatus, KV,
+};
+#[cfg(feature = "serde1")]
+use serde::{Deserialize, Serialize};
+
+/// # Threshold Accepting
+///
+/// Threshold Accepting is a deterministic relative of [`SimulatedAnnealing`](`super::SimulatedAnnealing`).
+/// In each iteration, the parameter vector is modified via [`Anneal::anneal`] and the new
+/// parameter vector is accepted if its cost function value is not worse than the current one by
+/// more than the current threshold. Instead of a random acceptance decision, only a comparison
+/// is needed, which makes the method cheap and robust for noisy, combinatorial-style problems.
+///
+/// The threshold starts at the initial threshold and is multiplied by a decay factor after each
+/// iteration (see [`ThresholdAccepting::with_decay`]). The current threshold is passed to
+/// [`Anneal::anneal`] as the extent of the move.
+///
+/// The algorithm stops if no new parameter vector was accepted for a given number of iterations
+/// ([`ThresholdAccepting::with_stall_accepted`]) or if no new best parameter vector was found for
+/// a given number of iterations ([`ThresholdAccepting::with_stall_best`]).
+///
+/// Requires an initial parameter vector, which has to be provided via
+/// [`configure`](`crate::core::Executor::configure`) of [`Executor`](`crate::core::Executor`).
+///
+/// ## Requirements on the optimization problem
+///
+/// The optimization problem is required to implement [`CostFunction`] and [`Anneal`].
+///
+/// ## Reference
+///
+/// G Dueck, T Scheuer. (1990). "Threshold accepting: A general purpose optimization algorithm
+/// appearing superior to simulated annealing". Journal of Computational Physics, 90(1),
+/// pp. 161-175. DOI: 10.1016/0021-9991(90)90201-B
+#[derive(Clone)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+pub struct ThresholdAccepting<F> {
+    /// Initial threshold
+    init_threshold: F,
+    /// Current threshold
+    threshold: F,
+    /// Factor the threshold is multiplied with after each iteration
+    decay: F,
+    /// Number of iterations since the last accepted solution
+    stall_iter_accepted: u64,
+    /// Stop if `stall_iter_accepted` exceeds this number
+    stall_iter_accepted_limit: u64,
+    /// Number of iterations since the last best solution was found
+    stall_iter_best: u64,
+    /// Stop if `stall_iter_best` exceeds this number
+    stall_iter_best_limit: u64,
+}
+
+impl<F> ThresholdAccepting<F>
+where
+    F: ArgminFloat,
+{
+    /// Construct a new instance of [`ThresholdAccepting`]
+    ///
+    /// Takes the initial threshold as input, which must be > 0.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::simulatedannealing::ThresholdAccepting;
+    /// # use argmin::core::Error;
+    /// # fn main() -> Result<(), Error> {
+    /// let ta = ThresholdAccepting::new(10.0f64)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn new(initial_threshold: F) -> Result<Self, Error> {
+        if initial_threshold <= float!(0.0) {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "`ThresholdAccepting`: Initial threshold must be > 0."
+            ));
+        }
+        Ok(ThresholdAccepting {
+            init_threshold: initial_threshold,
+            threshold: initial_threshold,
+            decay: float!(0.99),
+            stall_iter_accepted: 0,
+            stall_iter_accepted_limit: u64::MAX,
+            stall_iter_best: 0,
+            stall_iter_best_limit: u64::MAX,
+        })
+    }
+
+    /// Set the factor the threshold is multiplied with after each iteration
+    ///
+    /// Must be in `(0, 1]` and defaults to `0.99`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::simulatedannealing::ThresholdAccepting;
+    /// # use argmin::core::Error;
+    /// # fn main() -> Result<(), Error> {
+    /// let ta = ThresholdAccepting::new(10.0f64)?.with_decay(0.95)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_decay(mut self, decay: F) -> Result<Self, Error> {
+        if decay <= float!(0.0) || decay > float!(1.0) {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "`ThresholdAccepting`: Decay must be in (0, 1]."
+            ));
+        }
+        self.decay = decay;
+        Ok(self)
+    }
+
+    /// If there are no accepted solutions for `iter` iterations, the algorithm stops.
+    ///
+    /// Defaults to `u64::MAX`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::simulatedannealing::ThresholdAccepting;
+    /// # use argmin::core::Error;
+    /// # fn main() -> Result<(), Error> {
+    /// let ta = ThresholdAccepting::new(10.0f64)?.with_stall_accepted(1000);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn with_stall_accepted(mut self, iter: u64) -> Self {
+        self.stall_iter_accepted_limit = iter;
+        self
+    }
+
+    /// If there are no new best solutions for `iter` iterations, the algorithm stops.
+    ///
+    /// Defaults to `u64::MAX`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::simulatedannealing::ThresholdAccepting;
+    /// # use argmin::core::Error;
+    /// # fn main() -> Result<(), Error> {
+    /// let ta = ThresholdAccepting::new(10.0f64)?.with_stall_best(2000);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn with_stall_best(mut self, iter: u64) -> Self {
+        self.stall_iter_best_limit = iter;
+        self
+    }
+}
+
+/// Serializable configuration of [`ThresholdAccepting`]
+///
+/// Options which are `None` keep the defaults of [`ThresholdAccepting`]. The configuration is
+/// validated when the solver is constructed via [`build`](`ThresholdAcceptingConfig::build`).
+///
+/// # Example
+///
+/// ```
+/// # use argmin::solver::simulatedannealing::ThresholdAcceptingConfig;
+/// # use argmin::core::Error;
+/// # fn main() -> Result<(), Error> {
+/// let config = ThresholdAcceptingConfig {
+///     initial_threshold: 10.0f64,
+///     decay: Some(0.95),
+///     ..Default::default()
+/// };
+/// let ta = config.build()?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize), serde(default))]
+pub struct ThresholdAcceptingConfig<F> {
+    /// Initial threshold, must be `> 0`
+    pub initial_threshold: F,
+    /// See [`ThresholdAccepting::with_decay`]
+    pub decay: Option<F>,
+    /// See [`ThresholdAccepting::with_stall_accepted`]
+    pub stall_accepted: Option<u64>,
+    /// See [`ThresholdAccepting::with_stall_best`]
+    pub stall_best: Option<u64>,
+}
+
+impl<F> ThresholdAcceptingConfig<F>
+where
+    F: ArgminFloat,
+{
+    /// Validates the configuration and constructs a [`ThresholdAccepting`] solver.
+    pub fn build(&self) -> Result<ThresholdAccepting<F>, Error> {
+        let mut solver = ThresholdAccepting::new(self.initial_threshold)?;
+        if let Some(decay) = self.decay {
+            solver = solver.with_decay(decay)?;
+        }
+        if let Some(iter) = self.stall_accepted {
+            solver = solver.with_stall_accepted(iter);
+        }
+        if let Some(iter) = self.stall_best {
+            solver = solver.with_stall_best(iter);
+        }
+        Ok(solver)
+    }
+}
+
+impl<O, P, F> Solver<O, IterState<P, (), (), (), (), F>> for ThresholdAccepting<F>
+where
+    O: CostFunction<Param = P, Output = F> + Anneal<Param = P, Output = P, Float = F>,
+    P: Clone,
+    F: ArgminFloat,
+{
+    fn name(&self) -> &str {
+        "Threshold Accepting"
+    }
+
+    fn init(
+        &mut self,
+        problem: &mut Problem<O>,
+        mut state: IterState<P, (), (), (), (), F>,
+    ) -> Result<(IterState<P, (), (), (), (), F>, Option<KV>), Error> {
+        let param = state.take_param().ok_or_else(argmin_error_closure!(
+            NotInitialized,
+            concat!(
+                "`ThresholdAccepting` requires an initial parameter vector. ",
+                "Please provide an initial guess via `Executor`s `configure` method."
+            )
+        ))?;
+
+        let cost = state.get_cost();
+        let cost = state
+            .get_initial_evaluation()
+            .evaluate((!cost.is_infinite()).then_some(cost), || {
+                problem.cost(&param)
+            })?;
+
+        Ok((
+            state.param(param).cost(cost),
+            Some(
+                kv!(
+                    "initial_threshold" => self.init_threshold;
+                    "decay" => self.decay;
+                    "stall_iter_accepted_limit" => self.stall_iter_accepted_limit;
+                    "stall_iter_best_limit" => self.stall_iter_best_limit;
+                )
+                .with_prefix("config"),
+            ),
+        ))
+    }
+
+    fn next_iter(
+        &mut self,
+        problem: &mut Problem<O>,
+        mut state: IterState<P, (), (), (), (), F>,
+    ) -> Result<(IterState<P, (), (), (), (), F>, Option<KV>), Error> {
+        let prev_param = state.take_param().ok_or_else(argmin_error_closure!(
+            PotentialBug,
+            "`ThresholdAccepting`: Parameter vector in state not set."
+        ))?;
+        let prev_cost = state.get_cost();
+
+        let new_param = problem.anneal(&prev_param, self.threshold)?;
+        let new_cost = problem.cost(&new_param)?;
+
+        // Accept everything which is not worse than the current solution by more than the
+        // threshold.
+        let accepted = new_cost - prev_cost < self.threshold;
+        let new_best_found = new_cost < state.best_cost;
+
+        self.stall_iter_accepted = if accepted {
+            0
+        } else {
+            self.stall_iter_accepted + 1
+        };
+        self.stall_iter_best = if new_best_found {
+            0
+        } else {
+            self.stall_iter_best + 1
+        };
+
+        self.threshold = self.threshold * self.decay;
+
+        let mut kv = kv!(
+            "threshold" => self.threshold;
+            "new_be" => new_best_found;
+            "acc" => accepted;
+            "st_i_be" => self.stall_iter_best;
+            "st_i_ac" => self.stall_iter_accepted;
+        );
+        kv.set_meta(
+            "threshold",
+            KvMeta::new()
+                .scale(KvScale::Log)
+                .description("Current threshold"),
+        );
+
+        Ok((
+            if accepted {
+                state.param(new_param).cost(new_cost)
+            } else {
+                state.param(prev_param).cost(prev_cost)
+            },
+            Some(kv),
+        ))
+    }
+
+    fn terminate(&mut self, _state: &IterState<P, (), (), (), (), F>) -> TerminationStatus {
+        if self.stall_iter_accepted > self.stall_iter_accepted_limit {
+            return TerminationStatus::Terminated(TerminationReason::StallAccepted {
+                iters: self.stall_iter_accepted,
+            });
+        }
+        if self.stall_iter_best > self.stall_iter_best_limit {
+            return TerminationStatus::Terminated(TerminationReason::StallBest {
+                iters: self.stall_iter_best,
+            });
+        }
+        TerminationStatus::NotTerminated
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::{ArgminError, State};
+
+    test_trait_impl!(threshold_accepting, ThresholdAccepting<f64>);
+
+    /// Uses the parameter as cost and moves uphill by an amount depending on the extent
+    struct Uphill {}
+
+    impl CostFunction for Uphill {
+        type Param = f64;
+        type Output = f64;
+
+        fn cost(&self, param: &f64) -> Result<f64, Error> {
+            Ok(*param)
+        }
+    }
+
+    impl Anneal for Uphill {
+        type Param = f64;
+        type Output = f64;
+        type Float = f64;
+
+        fn anneal(&self, param: &f64, extent: f64) -> Result<f64, Error> {
+            Ok(if extent > 1.0 {
+                param + extent / 2.0
+            } else {
+                param + 2.0 * extent
+            })
+        }
+    }
+
+    #[test]
+    fn test_new() {
+        let ThresholdAccepting {
+            init_threshold,
+            threshold,
+            decay,
+            stall_iter_accepted,
+            stall_iter_accepted_limit,
+            stall_iter_best,
+            stall_iter_best_limit,
+        } = ThresholdAccepting::new(10.0f64).unwrap();
+
+        assert_eq!(init_threshold.to_ne_bytes(), 10.0f64.to_ne_bytes());
+        assert_eq!(threshold.to_ne_bytes(), 10.0f64.to_ne_bytes());
+        assert_eq!(decay.to_ne_bytes(), 0.99f64.to_ne_bytes());
+        assert_eq!(stall_iter_accepted, 0);
+        assert_eq!(stall_iter_accepted_limit, u64::MAX);
+        assert_eq!(stall_iter_best, 0);
+        assert_eq!(stall_iter_best_limit, u64::MAX);
+
+        for threshold in [0.0, -1.0] {
+            let res = ThresholdAccepting::new(threshold);
+            assert_error!(
+                res,
+                ArgminError,
+                "Invalid parameter: \"`ThresholdAccepting`: Initial threshold must be > 0.\""
+            );
+        }
+    }
+
+    #[test]
+    fn test_with_decay() {
+        let ta = ThresholdAccepting::new(10.0f64)
+            .unwrap()
+            .with_decay(1.0)
+            .unwrap();
+        assert_eq!(ta.decay.to_ne_bytes(), 1.0f64.to_ne_bytes());
+
+        for decay in [0.0, -0.5, 1.5] {
+            let res = ThresholdAccepting::new(10.0f64).unwrap().with_decay(decay);
+            assert_error!(
+                res,
+                ArgminError,
+                "Invalid parameter: \"`ThresholdAccepting`: Decay must be in (0, 1].\""
+            );
+        }
+    }
+
+    #[test]
+    fn test_config_build() {
+        let ta = ThresholdAcceptingConfig {
+            initial_threshold: 5.0f64,
+            decay: Some(0.5),
+            stall_accepted: Some(10),
+            stall_best: Some(20),
+        }
+        .build()
+        .unwrap();
+        assert_eq!(ta.init_threshold.to_ne_bytes(), 5.0f64.to_ne_bytes());
+        assert_eq!(ta.decay.to_ne_bytes(), 0.5f64.to_ne_bytes());
+        assert_eq!(ta.stall_iter_accepted_limit, 10);
+        assert_eq!(ta.stall_iter_best_limit, 20);
+
+        let res = ThresholdAcceptingConfig::<f64>::default().build();
+        assert_error!(
+            res,
+            ArgminError,
+            "Invalid parameter: \"`ThresholdAccepting`: Initial threshold must be > 0.\""
+        );
+    }
+
+    #[test]
+    fn test_init_param_not_initialized() {
+        let mut ta = ThresholdAccepting::new(10.0f64).unwrap();
+        let res = ta.init(&mut Problem::new(Uphill {}), IterState::new());
+        assert_error!(
+            res,
+            ArgminError,
+            concat!(
+                "Not initialized: \"`ThresholdAccepting` requires an initial parameter vector. ",
+                "Please provide an initial guess via `Executor`s `configure` method.\""
+            )
+        );
+    }
+
+    #[test]
+    fn test_next_iter() {
+        let mut ta = ThresholdAccepting::new(4.0f64)
+            .unwrap()
+            .with_decay(0.5)
+            .unwrap();
+        let mut problem = Problem::new(Uphill {});
+        let (mut state, _) = ta
+            .init(&mut problem, IterState::new().param(0.0f64))
+            .unwrap();
+        state.update();
+
+        // Threshold 4: moves uphill by 2, which is accepted
+        let (mut state, kv) = ta.next_iter(&mut problem, state).unwrap();
+        assert_eq!(state.get_cost().to_ne_bytes(), 2.0f64.to_ne_bytes());
+        assert_eq!(ta.threshold.to_ne_bytes(), 2.0f64.to_ne_bytes());
+        assert_eq!(ta.stall_iter_accepted, 0);
+        assert_eq!(ta.stall_iter_best, 1);
+        assert!(kv.unwrap().get("acc").unwrap().get_bool().unwrap());
+        state.update();
+
+        // Threshold 2: moves uphill by 1, which is accepted
+        let (mut state, _) = ta.next_iter(&mut problem, state).unwrap();
+        assert_eq!(state.get_cost().to_ne_bytes(), 3.0f64.to_ne_bytes());
+        state.update();
+
+        // Threshold 1: moves uphill by 2, which is rejected
+        let (state, kv) = ta.next_iter(&mut problem, state).unwrap();
+        assert_eq!(state.get_cost().to_ne_bytes(), 3.0f64.to_ne_bytes());
+        assert_eq!(ta.stall_iter_accepted, 1);
+        assert!(!kv.unwrap().get("acc").unwrap().get_bool().unwrap());
+    }
+
+    #[test]
+    fn test_terminate() {
+        let mut ta = ThresholdAccepting::new(10.0f64)
+            .unwrap()
+            .with_stall_accepted(10)
+            .with_stall_best(20);
+        let state: IterState<f64, (), (), (), (), f64> = IterState::new();
+
+        ta.stall_iter_accepted = 10;
+        ta.stall_iter_best = 20;
+        assert_eq!(
+            Solver::<Uphill, _>::terminate(&mut ta, &state),
+            TerminationStatus::NotTerminated
+        );
+
+        ta.stall_iter_best = 21;
+        assert_eq!(
+            Solver::<Uphill, _>::terminate(&mut ta, &state),
+            TerminationStatus::Terminated(TerminationReason::StallBest { iters: 21 })
+        );
+
+        ta.stall_iter_accepted = 11;
+        assert_eq!(
+            Solver::<Uphill, _>::terminate(&mut ta, &state),
+            TerminationStatus::Terminated(TerminationReason::StallAccepted { iters: 11 })
+        );
+    }
+}
@@ -0,0 +1,26 @@
+// Copyright 2018-2024 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+//! # Stochastic approximation
+//!
+//! Methods for minimizing cost functions which can only be evaluated with noise, for instance
+//! because they are the outcome of a simulation.
+//!
+//! * [Simultaneous Perturbation Stochastic Approximation](`SPSA`)
+//!
+//! ## References
+//!
+//! James C. Spall (1992). Multivariate stochastic approximation using a simultaneous perturbation
+//! gradient approximation. IEEE Transactions on Automatic Control 37(3), 332-341.
+//!
+//! James C. Spall (1998). Implementation of the simultaneous perturbation algorithm for
+//! stochastic optimization. IEEE Transactions on Aerospace and Electronic Systems 34(3),
+//! 817-823.
+
+mod spsa;
+
+pub use self::spsa::{SPSAConfig, SPSA};
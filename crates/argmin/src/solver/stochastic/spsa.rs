@@ -0,0 +1,669 @@
+// Copyright 2018-2024 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use crate::core::{ArgminFloat, CostFunction, Error, IterState, Problem, Solver, State, KV};
+use argmin_math::{
+    ArgminAdd, ArgminRandom, ArgminScaledAdd, ArgminScaledSub, ArgminSignum, ArgminSub,
+    ArgminZeroLike,
+};
+use rand::prelude::*;
+use rand_xoshiro::Xoshiro256PlusPlus;
+#[cfg(feature = "serde1")]
+use serde::{Deserialize, Serialize};
+
+/// # Simultaneous Perturbation Stochastic Approximation (SPSA)
+///
+/// Gradient-free stochastic approximation method which estimates the gradient from two
+/// (possibly noisy) cost function evaluations per iteration, independent of the dimension of
+/// the parameter vector. In iteration `k`, all entries of the parameter vector are perturbed
+/// simultaneously along a random direction `delta_k` whose entries are `+1` or `-1` with equal
+/// probability:
+///
+/// `g_k = (f(x_k + c_k * delta_k) - f(x_k - c_k * delta_k)) / (2 * c_k) * delta_k`
+///
+/// `x_{k+1} = x_k - a_k * g_k`
+///
+/// The gain sequences decay as
+///
+/// `a_k = a / (k + 1 + A)^alpha`
+///
+/// `c_k = c / (k + 1)^gamma`
+///
+/// with step gain `a` (default: `0.1`), perturbation `c` (default: `0.1`), stability constant `A`
+/// (default: `0`) and exponents `alpha` (default: `0.602`) and `gamma` (default: `0.101`). Good
+/// gains are problem dependent: `c` should be approximately the standard deviation of the noise
+/// and `A` about 10% of the expected number of iterations, with `a` chosen such that the first
+/// steps have the desired magnitude.
+///
+/// Since SPSA itself does not evaluate the cost function at the iterates, the state holds an
+/// infinite cost and the best parameter vector is always the latest one. Optionally, the cost
+/// function is evaluated at every new iterate (see [`SPSA::with_cost_evaluation`]), which
+/// requires a third evaluation per iteration. For noisy cost functions, the best parameter vector
+/// is then subject to the noise as well.
+///
+/// ## Requirements on the optimization problem
+///
+/// The optimization problem is required to implement [`CostFunction`].
+///
+/// ## References
+///
+/// James C. Spall (1992). Multivariate stochastic approximation using a simultaneous perturbation
+/// gradient approximation. IEEE Transactions on Automatic Control 37(3), 332-341.
+///
+/// James C. Spall (1998). Implementation of the simultaneous perturbation algorithm for
+/// stochastic optimization. IEEE Transactions on Aerospace and Electronic Systems 34(3),
+/// 817-823.
+#[derive(Clone)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+pub struct SPSA<F, R> {
+    /// Step gain `a`
+    step_gain: F,
+    /// Perturbation `c`
+    perturbation: F,
+    /// Stability constant `A`
+    stability_constant: F,
+    /// Decay exponent `alpha` of the step gain
+    alpha: F,
+    /// Decay exponent `gamma` of the perturbation
+    gamma: F,
+    /// Evaluate the cost function at every iterate
+    evaluate_cost: bool,
+    /// Random number generator
+    rng: R,
+}
+
+impl<F> SPSA<F, Xoshiro256PlusPlus>
+where
+    F: ArgminFloat,
+{
+    /// Construct a new instance of [`SPSA`]
+    ///
+    /// Uses the `Xoshiro256PlusPlus` RNG internally. For use of another RNG, consider using
+    /// [`SPSA::new_with_rng`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::stochastic::SPSA;
+    /// let spsa: SPSA<f64, _> = SPSA::new();
+    /// ```
+    pub fn new() -> Self {
+        SPSA::new_with_rng(Xoshiro256PlusPlus::from_entropy())
+    }
+}
+
+impl<F> Default for SPSA<F, Xoshiro256PlusPlus>
+where
+    F: ArgminFloat,
+{
+    fn default() -> Self {
+        SPSA::new()
+    }
+}
+
+impl<F, R> SPSA<F, R>
+where
+    F: ArgminFloat,
+{
+    /// Construct a new instance of [`SPSA`] with a given RNG
+    ///
+    /// Requires a RNG which must implement `rand::Rng` (and `serde::Serialize` if the `serde1`
+    /// feature is enabled).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::stochastic::SPSA;
+    /// # use rand::SeedableRng;
+    /// let spsa: SPSA<f64, _> = SPSA::new_with_rng(rand::rngs::StdRng::seed_from_u64(42));
+    /// ```
+    pub fn new_with_rng(rng: R) -> Self {
+        SPSA {
+            step_gain: float!(0.1),
+            perturbation: float!(0.1),
+            stability_constant: float!(0.0),
+            alpha: float!(0.602),
+            gamma: float!(0.101),
+            evaluate_cost: false,
+            rng,
+        }
+    }
+
+    /// Set the step gain `a`
+    ///
+    /// Must be larger than 0. Defaults to `0.1`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::stochastic::SPSA;
+    /// # use argmin::core::Error;
+    /// # fn main() -> Result<(), Error> {
+    /// let spsa: SPSA<f64, _> = SPSA::new().with_step_gain(0.5)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_step_gain(mut self, step_gain: F) -> Result<Self, Error> {
+        if step_gain <= float!(0.0) {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "`SPSA`: step gain must be > 0."
+            ));
+        }
+        self.step_gain = step_gain;
+        Ok(self)
+    }
+
+    /// Set the perturbation `c`
+    ///
+    /// Must be larger than 0. Defaults to `0.1`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::stochastic::SPSA;
+    /// # use argmin::core::Error;
+    /// # fn main() -> Result<(), Error> {
+    /// let spsa: SPSA<f64, _> = SPSA::new().with_perturbation(0.01)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_perturbation(mut self, perturbation: F) -> Result<Self, Error> {
+        if perturbation <= float!(0.0) {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "`SPSA`: perturbation must be > 0."
+            ));
+        }
+        self.perturbation = perturbation;
+        Ok(self)
+    }
+
+    /// Set the stability constant `A`
+    ///
+    /// Must be non-negative. Defaults to `0`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::stochastic::SPSA;
+    /// # use argmin::core::Error;
+    /// # fn main() -> Result<(), Error> {
+    /// let spsa: SPSA<f64, _> = SPSA::new().with_stability_constant(100.0)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_stability_constant(mut self, stability_constant: F) -> Result<Self, Error> {
+        if stability_constant < float!(0.0) {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "`SPSA`: stability constant must be >= 0."
+            ));
+        }
+        self.stability_constant = stability_constant;
+        Ok(self)
+    }
+
+    /// Set the decay exponents `alpha` of the step gain and `gamma` of the perturbation
+    ///
+    /// Both must be larger than 0. Defaults to `alpha = 0.602` and `gamma = 0.101`. Asymptotically
+    /// optimal values are `alpha = 1` and `gamma = 1/6`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::stochastic::SPSA;
+    /// # use argmin::core::Error;
+    /// # fn main() -> Result<(), Error> {
+    /// let spsa: SPSA<f64, _> = SPSA::new().with_exponents(1.0, 1.0 / 6.0)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_exponents(mut self, alpha: F, gamma: F) -> Result<Self, Error> {
+        if alpha <= float!(0.0) || gamma <= float!(0.0) {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "`SPSA`: exponents must be > 0."
+            ));
+        }
+        self.alpha = alpha;
+        self.gamma = gamma;
+        Ok(self)
+    }
+
+    /// Evaluate the cost function at every iterate (default: `false`)
+    ///
+    /// This allows to keep track of the best parameter vector at the expense of an additional
+    /// cost function evaluation per iteration.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::stochastic::SPSA;
+    /// let spsa: SPSA<f64, _> = SPSA::new().with_cost_evaluation(true);
+    /// ```
+    #[must_use]
+    pub fn with_cost_evaluation(mut self, evaluate_cost: bool) -> Self {
+        self.evaluate_cost = evaluate_cost;
+        self
+    }
+}
+
+/// Serializable configuration of [`SPSA`]
+///
+/// Options which are `None` keep the defaults of [`SPSA`]. The configuration is validated when
+/// the solver is constructed via [`build`](`SPSAConfig::build`) or
+/// [`build_with_rng`](`SPSAConfig::build_with_rng`).
+///
+/// # Example
+///
+/// ```
+/// # use argmin::solver::stochastic::SPSAConfig;
+/// # use argmin::core::Error;
+/// # fn main() -> Result<(), Error> {
+/// let config = SPSAConfig {
+///     step_gain: Some(0.5f64),
+///     stability_constant: Some(100.0),
+///     seed: Some(42),
+///     ..Default::default()
+/// };
+/// let spsa = config.build()?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize), serde(default))]
+pub struct SPSAConfig<F> {
+    /// See [`SPSA::with_step_gain`]
+    pub step_gain: Option<F>,
+    /// See [`SPSA::with_perturbation`]
+    pub perturbation: Option<F>,
+    /// See [`SPSA::with_stability_constant`]
+    pub stability_constant: Option<F>,
+    /// `(alpha, gamma)`, see [`SPSA::with_exponents`]
+    pub exponents: Option<(F, F)>,
+    /// See [`SPSA::with_cost_evaluation`]
+    pub evaluate_cost: Option<bool>,
+    /// Seed of the `Xoshiro256PlusPlus` RNG used by [`build`](`SPSAConfig::build`).
+    /// If `None`, the RNG is seeded from entropy.
+    pub seed: Option<u64>,
+}
+
+impl<F> SPSAConfig<F>
+where
+    F: ArgminFloat,
+{
+    /// Validates the configuration and constructs a [`SPSA`] solver using the
+    /// `Xoshiro256PlusPlus` RNG.
+    pub fn build(&self) -> Result<SPSA<F, Xoshiro256PlusPlus>, Error> {
+        let rng = match self.seed {
+            Some(seed) => Xoshiro256PlusPlus::seed_from_u64(seed),
+            None => Xoshiro256PlusPlus::from_entropy(),
+        };
+        self.build_with_rng(rng)
+    }
+
+    /// Validates the configuration and constructs a [`SPSA`] solver using the given RNG. The
+    /// `seed` option is ignored.
+    pub fn build_with_rng<R>(&self, rng: R) -> Result<SPSA<F, R>, Error> {
+        let mut solver = SPSA::new_with_rng(rng);
+        if let Some(step_gain) = self.step_gain {
+            solver = solver.with_step_gain(step_gain)?;
+        }
+        if let Some(perturbation) = self.perturbation {
+            solver = solver.with_perturbation(perturbation)?;
+        }
+        if let Some(stability_constant) = self.stability_constant {
+            solver = solver.with_stability_constant(stability_constant)?;
+        }
+        if let Some((alpha, gamma)) = self.exponents {
+            solver = solver.with_exponents(alpha, gamma)?;
+        }
+        if let Some(evaluate_cost) = self.evaluate_cost {
+            solver = solver.with_cost_evaluation(evaluate_cost);
+        }
+        Ok(solver)
+    }
+}
+
+impl<O, P, F, R> Solver<O, IterState<P, (), (), (), (), F>> for SPSA<F, R>
+where
+    O: CostFunction<Param = P, Output = F>,
+    P: Clone
+        + ArgminZeroLike
+        + ArgminAdd<F, P>
+        + ArgminSub<F, P>
+        + ArgminRandom
+        + ArgminSignum
+        + ArgminScaledAdd<P, F, P>
+        + ArgminScaledSub<P, F, P>,
+    F: ArgminFloat,
+    R: Rng,
+{
+    fn name(&self) -> &str {
+        "SPSA"
+    }
+
+    fn init(
+        &mut self,
+        problem: &mut Problem<O>,
+        state: IterState<P, (), (), (), (), F>,
+    ) -> Result<(IterState<P, (), (), (), (), F>, Option<KV>), Error> {
+        let param = state.get_param().ok_or_else(argmin_error_closure!(
+            NotInitialized,
+            concat!(
+                "`SPSA` requires an initial parameter vector. ",
+                "Please provide an initial guess via `Executor`s `configure` method."
+            )
+        ))?;
+        let config = kv!(
+            "step_gain" => self.step_gain;
+            "perturbation" => self.perturbation;
+            "stability_constant" => self.stability_constant;
+            "alpha" => self.alpha;
+            "gamma" => self.gamma;
+            "evaluate_cost" => self.evaluate_cost;
+            "rng" => std::any::type_name::<R>();
+        )
+        .with_prefix("config");
+        if self.evaluate_cost {
+            let cost = problem.cost(param)?;
+            return Ok((state.cost(cost), Some(config)));
+        }
+        Ok((state, Some(config)))
+    }
+
+    fn next_iter(
+        &mut self,
+        problem: &mut Problem<O>,
+        mut state: IterState<P, (), (), (), (), F>,
+    ) -> Result<(IterState<P, (), (), (), (), F>, Option<KV>), Error> {
+        let param = state.take_param().ok_or_else(argmin_error_closure!(
+            PotentialBug,
+            "`SPSA`: Parameter vector in `state` not set"
+        ))?;
+
+        let k = F::from_u64(state.get_iter()).unwrap() + float!(1.0);
+        let a_k = self.step_gain / (k + self.stability_constant).powf(self.alpha);
+        let c_k = self.perturbation / k.powf(self.gamma);
+
+        // Rademacher distributed perturbation: the signs of entries drawn uniformly from [-1, 1)
+        let zero = param.zero_like();
+        let delta = P::rand_from_range(
+            &zero.sub(&float!(1.0)),
+            &zero.add(&float!(1.0)),
+            &mut self.rng,
+        )
+        .signum();
+
+        let cost_plus = problem.cost(&param.scaled_add(&c_k, &delta))?;
+        let cost_minus = problem.cost(&param.scaled_sub(&c_k, &delta))?;
+
+        // Since the entries of `delta` are +-1, dividing by `delta` is the same as multiplying.
+        let scale = (cost_plus - cost_minus) / (float!(2.0) * c_k);
+        let new_param = param.scaled_sub(&(a_k * scale), &delta);
+
+        let kv = kv!(
+            "step_size" => a_k;
+            "perturbation" => c_k;
+        );
+        if self.evaluate_cost {
+            let cost = problem.cost(&new_param)?;
+            return Ok((state.param(new_param).cost(cost), Some(kv)));
+        }
+        Ok((state.param(new_param), Some(kv)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::{test_utils::TestProblem, ArgminError, Executor};
+    use approx::assert_relative_eq;
+    use std::sync::Mutex;
+
+    test_trait_impl!(spsa, SPSA<f64, Xoshiro256PlusPlus>);
+
+    /// `f(x) = sum_i i * x_i^2` with optional additive Gaussian-like noise
+    struct NoisyQuadratic {
+        noise: f64,
+        rng: Mutex<Xoshiro256PlusPlus>,
+    }
+
+    impl NoisyQuadratic {
+        fn new(noise: f64) -> Self {
+            NoisyQuadratic {
+                noise,
+                rng: Mutex::new(Xoshiro256PlusPlus::seed_from_u64(1)),
+            }
+        }
+    }
+
+    impl CostFunction for NoisyQuadratic {
+        type Param = Vec<f64>;
+        type Output = f64;
+
+        fn cost(&self, p: &Self::Param) -> Result<Self::Output, Error> {
+            let noise = if self.noise > 0.0 {
+                self.rng.lock().unwrap().gen_range(-self.noise..self.noise)
+            } else {
+                0.0
+            };
+            Ok(p.iter()
+                .enumerate()
+                .map(|(i, x)| (i + 1) as f64 * x * x)
+                .sum::<f64>()
+                + noise)
+        }
+    }
+
+    fn solver(seed: u64) -> SPSA<f64, Xoshiro256PlusPlus> {
+        SPSA::new_with_rng(Xoshiro256PlusPlus::seed_from_u64(seed))
+            .with_step_gain(0.5)
+            .unwrap()
+            .with_stability_constant(10.0)
+            .unwrap()
+    }
+
+    #[test]
+    fn test_new() {
+        let spsa: SPSA<f64, _> = SPSA::new_with_rng(());
+        let SPSA {
+            step_gain,
+            perturbation,
+            stability_constant,
+            alpha,
+            gamma,
+            evaluate_cost,
+            rng,
+        } = spsa;
+        assert_eq!(step_gain.to_ne_bytes(), 0.1f64.to_ne_bytes());
+        assert_eq!(perturbation.to_ne_bytes(), 0.1f64.to_ne_bytes());
+        assert_eq!(stability_constant.to_ne_bytes(), 0.0f64.to_ne_bytes());
+        assert_eq!(alpha.to_ne_bytes(), 0.602f64.to_ne_bytes());
+        assert_eq!(gamma.to_ne_bytes(), 0.101f64.to_ne_bytes());
+        assert!(!evaluate_cost);
+        assert_eq!(rng, ());
+    }
+
+    #[test]
+    fn test_setters() {
+        let spsa: SPSA<f64, _> = SPSA::new_with_rng(())
+            .with_step_gain(2.0)
+            .unwrap()
+            .with_perturbation(0.5)
+            .unwrap()
+            .with_stability_constant(20.0)
+            .unwrap()
+            .with_exponents(1.0, 0.25)
+            .unwrap()
+            .with_cost_evaluation(true);
+        assert_eq!(spsa.step_gain.to_ne_bytes(), 2.0f64.to_ne_bytes());
+        assert_eq!(spsa.perturbation.to_ne_bytes(), 0.5f64.to_ne_bytes());
+        assert_eq!(spsa.stability_constant.to_ne_bytes(), 20.0f64.to_ne_bytes());
+        assert_eq!(spsa.alpha.to_ne_bytes(), 1.0f64.to_ne_bytes());
+        assert_eq!(spsa.gamma.to_ne_bytes(), 0.25f64.to_ne_bytes());
+        assert!(spsa.evaluate_cost);
+    }
+
+    #[test]
+    fn test_invalid_parameters() {
+        let spsa = || SPSA::<f64, _>::new_with_rng(());
+        assert_error!(
+            spsa().with_step_gain(0.0),
+            ArgminError,
+            "Invalid parameter: \"`SPSA`: step gain must be > 0.\""
+        );
+        assert_error!(
+            spsa().with_perturbation(-1.0),
+            ArgminError,
+            "Invalid parameter: \"`SPSA`: perturbation must be > 0.\""
+        );
+        assert_error!(
+            spsa().with_stability_constant(-1.0),
+            ArgminError,
+            "Invalid parameter: \"`SPSA`: stability constant must be >= 0.\""
+        );
+        for (alpha, gamma) in [(0.0, 0.1), (0.6, -0.1)] {
+            assert_error!(
+                spsa().with_exponents(alpha, gamma),
+                ArgminError,
+                "Invalid parameter: \"`SPSA`: exponents must be > 0.\""
+            );
+        }
+    }
+
+    #[test]
+    fn test_config_build() {
+        let config = SPSAConfig {
+            step_gain: Some(0.5f64),
+            perturbation: Some(0.2),
+            stability_constant: Some(5.0),
+            exponents: Some((1.0, 0.2)),
+            evaluate_cost: Some(true),
+            seed: Some(42),
+        };
+        let mut spsa1 = config.build().unwrap();
+        assert_eq!(spsa1.step_gain.to_ne_bytes(), 0.5f64.to_ne_bytes());
+        assert_eq!(spsa1.perturbation.to_ne_bytes(), 0.2f64.to_ne_bytes());
+        assert_eq!(spsa1.stability_constant.to_ne_bytes(), 5.0f64.to_ne_bytes());
+        assert_eq!(spsa1.alpha.to_ne_bytes(), 1.0f64.to_ne_bytes());
+        assert_eq!(spsa1.gamma.to_ne_bytes(), 0.2f64.to_ne_bytes());
+        assert!(spsa1.evaluate_cost);
+        let mut spsa2 = config.build().unwrap();
+        assert_eq!(spsa1.rng.next_u64(), spsa2.rng.next_u64());
+
+        let config = SPSAConfig {
+            perturbation: Some(0.0f64),
+            ..Default::default()
+        };
+        assert!(config.build().is_err());
+    }
+
+    #[test]
+    fn test_init_param_not_initialized() {
+        let mut spsa: SPSA<f64, _> = SPSA::new();
+        let res = spsa.init(&mut Problem::new(TestProblem::new()), IterState::new());
+        assert_error!(
+            res,
+            ArgminError,
+            concat!(
+                "Not initialized: \"`SPSA` requires an initial parameter vector. ",
+                "Please provide an initial guess via `Executor`s `configure` method.\""
+            )
+        );
+    }
+
+    #[test]
+    fn test_next_iter_param_not_initialized() {
+        let mut spsa: SPSA<f64, _> = SPSA::new();
+        let res = spsa.next_iter(&mut Problem::new(TestProblem::new()), IterState::new());
+        assert_error!(
+            res,
+            ArgminError,
+            concat!(
+                "Potential bug: \"`SPSA`: Parameter vector in `state` not set\". ",
+                "This is potentially a bug. ",
+                "Please file a report on https://github.com/argmin-rs/argmin/issues"
+            )
+        );
+    }
+
+    #[test]
+    fn test_two_evaluations_per_iteration() {
+        let res = Executor::new(NoisyQuadratic::new(0.0), solver(0))
+            .configure(|state| state.param(vec![1.0; 4]).max_iters(10))
+            .run()
+            .unwrap();
+        assert_eq!(res.problem.counts["cost_count"], 20);
+        assert!(res.state.get_cost().is_infinite());
+
+        let res = Executor::new(
+            NoisyQuadratic::new(0.0),
+            solver(0).with_cost_evaluation(true),
+        )
+        .configure(|state| state.param(vec![1.0; 4]).max_iters(100))
+        .run()
+        .unwrap();
+        assert_eq!(res.problem.counts["cost_count"], 301);
+        assert!(res.state.get_best_cost() < 1e-2);
+    }
+
+    #[test]
+    fn test_kv() {
+        let mut problem = Problem::new(NoisyQuadratic::new(0.0));
+        let mut spsa = solver(0);
+        let (_, kv) = spsa
+            .next_iter(&mut problem, IterState::new().param(vec![1.0; 4]))
+            .unwrap();
+        let kv = kv.unwrap();
+        assert_relative_eq!(
+            kv.get("step_size").unwrap().get_float().unwrap(),
+            0.5 / 11.0f64.powf(0.602)
+        );
+        assert_relative_eq!(kv.get("perturbation").unwrap().get_float().unwrap(), 0.1);
+    }
+
+    #[test]
+    fn test_converges() {
+        let res = Executor::new(NoisyQuadratic::new(0.0), solver(0))
+            .configure(|state| state.param(vec![1.0; 4]).max_iters(2000))
+            .run()
+            .unwrap();
+        for x in res.state.get_param().unwrap() {
+            assert!(x.abs() < 1e-3);
+        }
+    }
+
+    #[test]
+    fn test_converges_noisy() {
+        let res = Executor::new(NoisyQuadratic::new(0.01), solver(0))
+            .configure(|state| state.param(vec![1.0; 4]).max_iters(5000))
+            .run()
+            .unwrap();
+        for x in res.state.get_param().unwrap() {
+            assert!(x.abs() < 5e-2);
+        }
+    }
+
+    #[test]
+    fn test_reproducible() {
+        let run = || {
+            Executor::new(NoisyQuadratic::new(0.0), solver(7))
+                .configure(|state| state.param(vec![1.0; 4]).max_iters(50))
+                .run()
+                .unwrap()
+                .state
+                .take_param()
+                .unwrap()
+        };
+        let (a, b) = (run(), run());
+        for (a, b) in a.iter().zip(b.iter()) {
+            assert_eq!(a.to_ne_bytes(), b.to_ne_bytes());
+        }
+    }
+}
@@ -0,0 +1,733 @@
+// Copyright 2018-2024 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+//! # Tabu search
+//!
+//! Tabu search is a local search metaheuristic for discrete and combinatorial optimization
+//! problems. For details see [`TabuSearch`].
+//!
+//! ## References
+//!
+//! [Wikipedia](https://en.wikipedia.org/wiki/Tabu_search)
+//!
+//! F Glover. (1989). "Tabu Search—Part I". ORSA Journal on Computing 1(3), pp. 190-206.
+//! DOI: 10.1287/ijoc.1.3.190
+
+use crate::core::{
+    ArgminFloat, CostFunction, Error, IterState, Problem, SendAlias, Solver, SyncAlias,
+    TerminationReason, TerminationStatus, KV,
+};
+#[cfg(feature = "serde1")]
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+
+/// This trait defines the neighborhood of a parameter vector in terms of moves. Problems which
+/// are to be solved using [`TabuSearch`] must implement this trait.
+///
+/// Each move has an attribute which is stored in the tabu list once the move has been made.
+/// Candidate moves whose attribute is in the tabu list are forbidden. Typical attributes are the
+/// index of the changed coordinate or the reverse of the move, which both prevent the search from
+/// immediately undoing a move.
+///
+/// # Example
+///
+/// ```
+/// use argmin::solver::tabusearch::Neighborhood;
+/// use argmin::core::Error;
+///
+/// struct BitString {}
+///
+/// impl Neighborhood for BitString {
+///     type Param = Vec<bool>;
+///     // Flip the bit at a given position
+///     type Move = usize;
+///     // Flipping a bit again is tabu
+///     type Attribute = usize;
+///
+///     fn neighbors(&self, param: &Self::Param) -> Result<Vec<Self::Move>, Error> {
+///         Ok((0..param.len()).collect())
+///     }
+///
+///     fn apply_move(&self, param: &Self::Param, mv: &Self::Move) -> Result<Self::Param, Error> {
+///         let mut param = param.clone();
+///         param[*mv] = !param[*mv];
+///         Ok(param)
+///     }
+///
+///     fn attribute(&self, mv: &Self::Move) -> Self::Attribute {
+///         *mv
+///     }
+/// }
+/// ```
+pub trait Neighborhood {
+    /// Type of the parameter vector
+    type Param;
+    /// Type of a move
+    type Move;
+    /// Type of the attribute of a move which is stored in the tabu list
+    type Attribute;
+
+    /// Returns all moves which lead from `param` to a neighboring parameter vector
+    fn neighbors(&self, param: &Self::Param) -> Result<Vec<Self::Move>, Error>;
+
+    /// Applies a move to a parameter vector
+    fn apply_move(&self, param: &Self::Param, mv: &Self::Move) -> Result<Self::Param, Error>;
+
+    /// Returns the attribute of a move
+    fn attribute(&self, mv: &Self::Move) -> Self::Attribute;
+}
+
+/// Wraps the calls to the methods of the `Neighborhood` trait and as such allows to call them on
+/// an instance of `Problem`. Internally, the number of evaluations of `neighbors` and `apply_move` is
+/// counted.
+impl<O: Neighborhood> Problem<O> {
+    /// Calls `neighbors` defined in the `Neighborhood` trait and keeps track of the number of
+    /// evaluations.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::{Problem, Error};
+    /// # use argmin::solver::tabusearch::Neighborhood;
+    /// #
+    /// # struct UserDefinedProblem {};
+    /// #
+    /// # impl Neighborhood for UserDefinedProblem {
+    /// #     type Param = Vec<i64>;
+    /// #     type Move = usize;
+    /// #     type Attribute = usize;
+    /// #
+    /// #     fn neighbors(&self, param: &Self::Param) -> Result<Vec<Self::Move>, Error> {
+    /// #         Ok((0..param.len()).collect())
+    /// #     }
+    /// #     fn apply_move(&self, param: &Self::Param, mv: &Self::Move) -> Result<Self::Param, Error> {
+    /// #         Ok(param.clone())
+    /// #     }
+    /// #     fn attribute(&self, mv: &Self::Move) -> Self::Attribute {
+    /// #         *mv
+    /// #     }
+    /// # }
+    /// // `UserDefinedProblem` implements `Neighborhood`.
+    /// let mut problem1 = Problem::new(UserDefinedProblem {});
+    ///
+    /// let res = problem1.neighbors(&vec![2, 1]);
+    ///
+    /// assert_eq!(problem1.counts["neighbors_count"], 1);
+    /// # assert_eq!(res.unwrap(), vec![0, 1]);
+    /// ```
+    pub fn neighbors(&mut self, param: &O::Param) -> Result<Vec<O::Move>, Error> {
+        self.problem("neighbors_count", |problem| problem.neighbors(param))
+    }
+
+    /// Calls `apply_move` defined in the `Neighborhood` trait and keeps track of the number of
+    /// evaluations.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::core::{Problem, Error};
+    /// # use argmin::solver::tabusearch::Neighborhood;
+    /// #
+    /// # struct UserDefinedProblem {};
+    /// #
+    /// # impl Neighborhood for UserDefinedProblem {
+    /// #     type Param = Vec<i64>;
+    /// #     type Move = usize;
+    /// #     type Attribute = usize;
+    /// #
+    /// #     fn neighbors(&self, param: &Self::Param) -> Result<Vec<Self::Move>, Error> {
+    /// #         Ok((0..param.len()).collect())
+    /// #     }
+    /// #     fn apply_move(&self, param: &Self::Param, mv: &Self::Move) -> Result<Self::Param, Error> {
+    /// #         let mut param = param.clone();
+    /// #         param[*mv] += 1;
+    /// #         Ok(param)
+    /// #     }
+    /// #     fn attribute(&self, mv: &Self::Move) -> Self::Attribute {
+    /// #         *mv
+    /// #     }
+    /// # }
+    /// // `UserDefinedProblem` implements `Neighborhood`.
+    /// let mut problem1 = Problem::new(UserDefinedProblem {});
+    ///
+    /// let res = problem1.apply_move(&vec![2, 1], &1);
+    ///
+    /// assert_eq!(problem1.counts["apply_move_count"], 1);
+    /// # assert_eq!(res.unwrap(), vec![2, 2]);
+    /// ```
+    pub fn apply_move(&mut self, param: &O::Param, mv: &O::Move) -> Result<O::Param, Error> {
+        self.problem("apply_move_count", |problem| problem.apply_move(param, mv))
+    }
+}
+
+/// Aspiration criterion of [`TabuSearch`]
+///
+/// Determines whether a tabu move may be made nevertheless.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+pub enum Aspiration {
+    /// Tabu moves are never made
+    Never,
+    /// Tabu moves are made if they lead to a parameter vector which is better than the best one
+    /// found so far
+    #[default]
+    ImprovesBest,
+}
+
+/// # Tabu search
+///
+/// In each iteration, all moves in the neighborhood of the current parameter vector are evaluated
+/// and the best admissible move is made, even if it leads to a worse parameter vector. This allows
+/// the search to escape from local minima. To prevent cycling, the attributes of the last
+/// [`tenure`](`TabuSearch::new`) moves are kept in a tabu list and moves with an attribute in the
+/// tabu list are not admissible, unless they satisfy the
+/// [aspiration criterion](`TabuSearch::with_aspiration`).
+///
+/// The cost function values of the neighbors are computed via
+/// [`bulk_cost`](`crate::core::Problem::bulk_cost`), which is done in parallel if the `rayon`
+/// feature is enabled.
+///
+/// The algorithm stops if none of the neighbors is admissible
+/// ([`TerminationReason::NoAdmissibleMove`]) or if no new best parameter vector was found for a
+/// given number of iterations ([`TabuSearch::with_stall_best`]).
+///
+/// Requires an initial parameter vector, which has to be provided via
+/// [`configure`](`crate::core::Executor::configure`) of [`Executor`](`crate::core::Executor`).
+///
+/// ## Requirements on the optimization problem
+///
+/// The optimization problem is required to implement [`CostFunction`] and [`Neighborhood`].
+///
+/// ## References
+///
+/// [Wikipedia](https://en.wikipedia.org/wiki/Tabu_search)
+///
+/// F Glover. (1989). "Tabu Search—Part I". ORSA Journal on Computing 1(3), pp. 190-206.
+/// DOI: 10.1287/ijoc.1.3.190
+#[derive(Clone)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+pub struct TabuSearch<A> {
+    /// Number of iterations a move attribute stays in the tabu list
+    tenure: usize,
+    /// Attributes of the most recent moves
+    tabu_list: VecDeque<A>,
+    /// Aspiration criterion
+    aspiration: Aspiration,
+    /// Whether the last iteration did not find an admissible move
+    no_admissible_move: bool,
+    /// Number of iterations since the last best solution was found
+    stall_iter_best: u64,
+    /// Stop if `stall_iter_best` exceeds this number
+    stall_iter_best_limit: u64,
+}
+
+impl<A> TabuSearch<A>
+where
+    A: Clone + PartialEq,
+{
+    /// Construct a new instance of [`TabuSearch`]
+    ///
+    /// Takes the tenure, i.e. the number of iterations a move attribute stays in the tabu list,
+    /// as input, which must be > 0.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::tabusearch::TabuSearch;
+    /// # use argmin::core::Error;
+    /// # fn main() -> Result<(), Error> {
+    /// let ts: TabuSearch<usize> = TabuSearch::new(7)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn new(tenure: usize) -> Result<Self, Error> {
+        if tenure == 0 {
+            return Err(argmin_error!(
+                InvalidParameter,
+                "`TabuSearch`: Tenure must be > 0."
+            ));
+        }
+        Ok(TabuSearch {
+            tenure,
+            tabu_list: VecDeque::with_capacity(tenure),
+            aspiration: Aspiration::default(),
+            no_admissible_move: false,
+            stall_iter_best: 0,
+            stall_iter_best_limit: u64::MAX,
+        })
+    }
+
+    /// Set the aspiration criterion
+    ///
+    /// Defaults to [`Aspiration::ImprovesBest`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::tabusearch::{Aspiration, TabuSearch};
+    /// # use argmin::core::Error;
+    /// # fn main() -> Result<(), Error> {
+    /// let ts: TabuSearch<usize> = TabuSearch::new(7)?.with_aspiration(Aspiration::Never);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn with_aspiration(mut self, aspiration: Aspiration) -> Self {
+        self.aspiration = aspiration;
+        self
+    }
+
+    /// If there are no new best solutions for `iter` iterations, the algorithm stops.
+    ///
+    /// Defaults to `u64::MAX`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::tabusearch::TabuSearch;
+    /// # use argmin::core::Error;
+    /// # fn main() -> Result<(), Error> {
+    /// let ts: TabuSearch<usize> = TabuSearch::new(7)?.with_stall_best(100);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn with_stall_best(mut self, iter: u64) -> Self {
+        self.stall_iter_best_limit = iter;
+        self
+    }
+
+    /// Returns `true` if the attribute is in the tabu list
+    fn is_tabu(&self, attribute: &A) -> bool {
+        self.tabu_list.contains(attribute)
+    }
+
+    /// Adds an attribute to the tabu list and removes the oldest attributes exceeding the tenure
+    fn make_tabu(&mut self, attribute: A) {
+        self.tabu_list.push_back(attribute);
+        while self.tabu_list.len() > self.tenure {
+            self.tabu_list.pop_front();
+        }
+    }
+}
+
+/// Serializable configuration of [`TabuSearch`]
+///
+/// Options which are `None` keep the defaults of [`TabuSearch`]. The configuration is validated
+/// when the solver is constructed via [`build`](`TabuSearchConfig::build`).
+///
+/// # Example
+///
+/// ```
+/// # use argmin::solver::tabusearch::{Aspiration, TabuSearch, TabuSearchConfig};
+/// # use argmin::core::Error;
+/// # fn main() -> Result<(), Error> {
+/// let config = TabuSearchConfig {
+///     tenure: 7,
+///     aspiration: Some(Aspiration::Never),
+///     ..Default::default()
+/// };
+/// let ts: TabuSearch<usize> = config.build()?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize), serde(default))]
+pub struct TabuSearchConfig {
+    /// Tenure, must be `> 0`
+    pub tenure: usize,
+    /// See [`TabuSearch::with_aspiration`]
+    pub aspiration: Option<Aspiration>,
+    /// See [`TabuSearch::with_stall_best`]
+    pub stall_best: Option<u64>,
+}
+
+impl TabuSearchConfig {
+    /// Validates the configuration and constructs a [`TabuSearch`] solver.
+    pub fn build<A: Clone + PartialEq>(&self) -> Result<TabuSearch<A>, Error> {
+        let mut solver = TabuSearch::new(self.tenure)?;
+        if let Some(aspiration) = self.aspiration {
+            solver = solver.with_aspiration(aspiration);
+        }
+        if let Some(iter) = self.stall_best {
+            solver = solver.with_stall_best(iter);
+        }
+        Ok(solver)
+    }
+}
+
+impl<O, P, M, A, F> Solver<O, IterState<P, (), (), (), (), F>> for TabuSearch<A>
+where
+    O: CostFunction<Param = P, Output = F>
+        + Neighborhood<Param = P, Move = M, Attribute = A>
+        + SyncAlias,
+    P: Clone + SyncAlias,
+    A: Clone + PartialEq,
+    F: ArgminFloat + SendAlias,
+{
+    fn name(&self) -> &str {
+        "Tabu Search"
+    }
+
+    fn init(
+        &mut self,
+        problem: &mut Problem<O>,
+        mut state: IterState<P, (), (), (), (), F>,
+    ) -> Result<(IterState<P, (), (), (), (), F>, Option<KV>), Error> {
+        let param = state.take_param().ok_or_else(argmin_error_closure!(
+            NotInitialized,
+            concat!(
+                "`TabuSearch` requires an initial parameter vector. ",
+                "Please provide an initial guess via `Executor`s `configure` method."
+            )
+        ))?;
+
+        let cost = state.get_cost();
+        let cost = state
+            .get_initial_evaluation()
+            .evaluate((!cost.is_infinite()).then_some(cost), || {
+                problem.cost(&param)
+            })?;
+
+        Ok((
+            state.param(param).cost(cost),
+            Some(
+                kv!(
+                    "tenure" => self.tenure as u64;
+                    "aspiration" => format!("{:?}", self.aspiration);
+                    "stall_iter_best_limit" => self.stall_iter_best_limit;
+                )
+                .with_prefix("config"),
+            ),
+        ))
+    }
+
+    fn next_iter(
+        &mut self,
+        problem: &mut Problem<O>,
+        mut state: IterState<P, (), (), (), (), F>,
+    ) -> Result<(IterState<P, (), (), (), (), F>, Option<KV>), Error> {
+        let param = state.take_param().ok_or_else(argmin_error_closure!(
+            PotentialBug,
+            "`TabuSearch`: Parameter vector in state not set."
+        ))?;
+
+        let moves = problem.neighbors(&param)?;
+        let candidates = moves
+            .iter()
+            .map(|mv| problem.apply_move(&param, mv))
+            .collect::<Result<Vec<P>, Error>>()?;
+        let costs = problem.bulk_cost(&candidates)?;
+        let user_problem = problem.problem.as_ref().ok_or_else(argmin_error_closure!(
+            PotentialBug,
+            "`TabuSearch`: Failed to access problem."
+        ))?;
+
+        // Find the best admissible move
+        let best_cost = state.get_best_cost();
+        let mut num_tabu = 0u64;
+        let mut selected: Option<(usize, A, bool)> = None;
+        for (idx, (mv, cost)) in moves.iter().zip(costs.iter()).enumerate() {
+            let cost = *cost;
+            if cost.is_nan() {
+                continue;
+            }
+            let attribute = user_problem.attribute(mv);
+            let tabu = self.is_tabu(&attribute);
+            num_tabu += u64::from(tabu);
+            let admissible = !tabu
+                || match self.aspiration {
+                    Aspiration::Never => false,
+                    Aspiration::ImprovesBest => cost < best_cost,
+                };
+            let better = match &selected {
+                Some((best_idx, _, _)) => cost < costs[*best_idx],
+                None => true,
+            };
+            if admissible && better {
+                selected = Some((idx, attribute, tabu));
+            }
+        }
+
+        self.no_admissible_move = selected.is_none();
+        let Some((idx, attribute, aspiration)) = selected else {
+            self.stall_iter_best += 1;
+            return Ok((
+                state.param(param),
+                Some(kv!(
+                    "neighbors" => moves.len() as u64;
+                    "tabu" => num_tabu;
+                )),
+            ));
+        };
+
+        self.make_tabu(attribute);
+        let cost = costs[idx];
+        self.stall_iter_best = if cost < best_cost {
+            0
+        } else {
+            self.stall_iter_best + 1
+        };
+
+        let kv = kv!(
+            "neighbors" => moves.len() as u64;
+            "tabu" => num_tabu;
+            "aspiration" => aspiration;
+            "st_i_be" => self.stall_iter_best;
+        );
+
+        Ok((
+            state
+                .param(candidates.into_iter().nth(idx).unwrap())
+                .cost(cost),
+            Some(kv),
+        ))
+    }
+
+    fn terminate(&mut self, _state: &IterState<P, (), (), (), (), F>) -> TerminationStatus {
+        if self.no_admissible_move {
+            return TerminationStatus::Terminated(TerminationReason::NoAdmissibleMove);
+        }
+        if self.stall_iter_best > self.stall_iter_best_limit {
+            return TerminationStatus::Terminated(TerminationReason::StallBest {
+                iters: self.stall_iter_best,
+            });
+        }
+        TerminationStatus::NotTerminated
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::{ArgminError, Executor, State};
+
+    test_trait_impl!(tabu_search, TabuSearch<usize>);
+
+    /// Integer lattice where each move changes one coordinate by +/-1 and the changed coordinate
+    /// becomes tabu
+    struct Lattice {
+        target: Vec<i64>,
+    }
+
+    impl CostFunction for Lattice {
+        type Param = Vec<i64>;
+        type Output = f64;
+
+        fn cost(&self, param: &Self::Param) -> Result<Self::Output, Error> {
+            Ok(param
+                .iter()
+                .zip(self.target.iter())
+                .map(|(x, t)| ((x - t) as f64).powi(2))
+                .sum())
+        }
+    }
+
+    impl Neighborhood for Lattice {
+        type Param = Vec<i64>;
+        type Move = (usize, i64);
+        type Attribute = usize;
+
+        fn neighbors(&self, param: &Self::Param) -> Result<Vec<Self::Move>, Error> {
+            Ok((0..param.len()).flat_map(|i| [(i, 1), (i, -1)]).collect())
+        }
+
+        fn apply_move(&self, param: &Self::Param, mv: &Self::Move) -> Result<Self::Param, Error> {
+            let mut param = param.clone();
+            param[mv.0] += mv.1;
+            Ok(param)
+        }
+
+        fn attribute(&self, mv: &Self::Move) -> Self::Attribute {
+            mv.0
+        }
+    }
+
+    fn lattice() -> Lattice {
+        Lattice {
+            target: vec![3, -2, 5],
+        }
+    }
+
+    #[test]
+    fn test_new() {
+        let TabuSearch {
+            tenure,
+            tabu_list,
+            aspiration,
+            no_admissible_move,
+            stall_iter_best,
+            stall_iter_best_limit,
+        }: TabuSearch<usize> = TabuSearch::new(5).unwrap();
+
+        assert_eq!(tenure, 5);
+        assert!(tabu_list.is_empty());
+        assert_eq!(aspiration, Aspiration::ImprovesBest);
+        assert!(!no_admissible_move);
+        assert_eq!(stall_iter_best, 0);
+        assert_eq!(stall_iter_best_limit, u64::MAX);
+
+        let res: Result<TabuSearch<usize>, _> = TabuSearch::new(0);
+        assert_error!(
+            res,
+            ArgminError,
+            "Invalid parameter: \"`TabuSearch`: Tenure must be > 0.\""
+        );
+    }
+
+    #[test]
+    fn test_config_build() {
+        let ts: TabuSearch<usize> = TabuSearchConfig {
+            tenure: 3,
+            aspiration: Some(Aspiration::Never),
+            stall_best: Some(10),
+        }
+        .build()
+        .unwrap();
+        assert_eq!(ts.tenure, 3);
+        assert_eq!(ts.aspiration, Aspiration::Never);
+        assert_eq!(ts.stall_iter_best_limit, 10);
+
+        let res: Result<TabuSearch<usize>, _> = TabuSearchConfig::default().build();
+        assert_error!(
+            res,
+            ArgminError,
+            "Invalid parameter: \"`TabuSearch`: Tenure must be > 0.\""
+        );
+    }
+
+    #[test]
+    fn test_make_tabu() {
+        let mut ts: TabuSearch<usize> = TabuSearch::new(2).unwrap();
+        ts.make_tabu(1);
+        ts.make_tabu(2);
+        assert!(ts.is_tabu(&1) && ts.is_tabu(&2));
+        ts.make_tabu(3);
+        assert!(!ts.is_tabu(&1));
+        assert!(ts.is_tabu(&2) && ts.is_tabu(&3));
+    }
+
+    #[test]
+    fn test_init_param_not_initialized() {
+        let mut ts: TabuSearch<usize> = TabuSearch::new(2).unwrap();
+        let res = ts.init(&mut Problem::new(lattice()), IterState::new());
+        assert_error!(
+            res,
+            ArgminError,
+            concat!(
+                "Not initialized: \"`TabuSearch` requires an initial parameter vector. ",
+                "Please provide an initial guess via `Executor`s `configure` method.\""
+            )
+        );
+    }
+
+    #[test]
+    fn test_next_iter() {
+        let mut ts = TabuSearch::new(1)
+            .unwrap()
+            .with_aspiration(Aspiration::Never);
+        let mut problem = Problem::new(lattice());
+        let (mut state, _) = ts
+            .init(&mut problem, IterState::new().param(vec![0, 0, 0]))
+            .unwrap();
+        state.update();
+
+        // The steepest descent moves the last coordinate, which then becomes tabu
+        let (mut state, kv) = ts.next_iter(&mut problem, state).unwrap();
+        assert_eq!(state.get_param().unwrap(), &vec![0, 0, 1]);
+        assert_eq!(problem.counts["neighbors_count"], 1);
+        assert_eq!(problem.counts["apply_move_count"], 6);
+        assert!(ts.is_tabu(&2));
+        let kv = kv.unwrap();
+        assert_eq!(kv.get("neighbors").unwrap().get_uint().unwrap(), 6);
+        assert_eq!(kv.get("tabu").unwrap().get_uint().unwrap(), 0);
+        state.update();
+
+        // Moving the last coordinate again would be best, but it is tabu
+        let (state, kv) = ts.next_iter(&mut problem, state).unwrap();
+        assert_eq!(state.get_param().unwrap(), &vec![1, 0, 1]);
+        assert!(ts.is_tabu(&0));
+        assert!(!ts.is_tabu(&2));
+        assert_eq!(kv.unwrap().get("tabu").unwrap().get_uint().unwrap(), 2);
+    }
+
+    #[test]
+    fn test_aspiration() {
+        // Both moves are tabu, but stepping onto the optimum improves the best solution
+        let mut ts = TabuSearch::new(1).unwrap();
+        ts.make_tabu(0);
+        let mut problem = Problem::new(Lattice { target: vec![3] });
+        let (mut state, _) = ts
+            .init(&mut problem, IterState::new().param(vec![2]))
+            .unwrap();
+        state.update();
+
+        let (state, kv) = ts.next_iter(&mut problem, state).unwrap();
+        assert_eq!(state.get_param().unwrap(), &vec![3]);
+        assert!(kv.unwrap().get("aspiration").unwrap().get_bool().unwrap());
+        assert_eq!(
+            Solver::<Lattice, _>::terminate(&mut ts, &state),
+            TerminationStatus::NotTerminated
+        );
+    }
+
+    #[test]
+    fn test_no_admissible_move() {
+        let mut ts = TabuSearch::new(1)
+            .unwrap()
+            .with_aspiration(Aspiration::Never);
+        ts.make_tabu(0);
+        let mut problem = Problem::new(Lattice { target: vec![3] });
+        let (mut state, _) = ts
+            .init(&mut problem, IterState::new().param(vec![2]))
+            .unwrap();
+        state.update();
+
+        let (state, _) = ts.next_iter(&mut problem, state).unwrap();
+        assert_eq!(state.get_param().unwrap(), &vec![2]);
+        assert_eq!(
+            Solver::<Lattice, _>::terminate(&mut ts, &state),
+            TerminationStatus::Terminated(TerminationReason::NoAdmissibleMove)
+        );
+    }
+
+    #[test]
+    fn test_terminate() {
+        let mut ts: TabuSearch<usize> = TabuSearch::new(1).unwrap().with_stall_best(10);
+        let state: IterState<Vec<i64>, (), (), (), (), f64> = IterState::new();
+
+        ts.stall_iter_best = 10;
+        assert_eq!(
+            Solver::<Lattice, _>::terminate(&mut ts, &state),
+            TerminationStatus::NotTerminated
+        );
+
+        ts.stall_iter_best = 11;
+        assert_eq!(
+            Solver::<Lattice, _>::terminate(&mut ts, &state),
+            TerminationStatus::Terminated(TerminationReason::StallBest { iters: 11 })
+        );
+    }
+
+    #[test]
+    fn test_tabu_search() {
+        let solver = TabuSearch::new(1).unwrap().with_stall_best(5);
+        let res = Executor::new(lattice(), solver)
+            .configure(|state| state.param(vec![0, 0, 0]).max_iters(100))
+            .run()
+            .unwrap();
+        assert_eq!(res.state.get_best_param().unwrap(), &vec![3, -2, 5]);
+        assert_eq!(
+            res.state.get_best_cost().to_ne_bytes(),
+            0.0f64.to_ne_bytes()
+        );
+        assert_eq!(
+            res.state.get_termination_reason(),
+            Some(&TerminationReason::StallBest { iters: 6 })
+        );
+    }
+}
@@ -19,6 +19,9 @@ use std::fmt::Debug;
 /// The Cauchy point is the minimum of the quadratic approximation of the cost function within the
 /// trust region along the direction given by the first derivative.
 ///
+/// Unlike most other solvers, [`CauchyPoint`] has no configuration struct, since it has no options
+/// (the radius is set by [`TrustRegion`](`crate::solver::trustregion::TrustRegion`)).
+///
 /// ## Requirements on the optimization problem
 ///
 /// The optimization problem is required to implement [`Gradient`] and [`Hessian`].
@@ -62,6 +65,17 @@ where
         "Cauchy Point"
     }
 
+    fn init(
+        &mut self,
+        _problem: &mut Problem<O>,
+        state: IterState<P, G, (), H, (), F>,
+    ) -> Result<(IterState<P, G, (), H, (), F>, Option<KV>), Error> {
+        Ok((
+            state,
+            Some(kv!("radius" => self.radius;).with_prefix("config")),
+        ))
+    }
+
     fn next_iter(
         &mut self,
         problem: &mut Problem<O>,
@@ -10,17 +10,62 @@ use crate::core::{
     TerminationStatus, TrustRegionRadius, KV,
 };
 use argmin_math::{
-    ArgminAdd, ArgminDot, ArgminInv, ArgminL2Norm, ArgminMul, ArgminSub, ArgminWeightedDot,
+    ArgminAdd, ArgminDot, ArgminEye, ArgminInv, ArgminL2Norm, ArgminMul, ArgminSub,
+    ArgminWeightedDot,
 };
 #[cfg(feature = "serde1")]
 use serde::{Deserialize, Serialize};
 
+/// Maximum number of attempts to regularize the Hessian with an increasing shift
+const MAX_REGULARIZATION_TRIALS: usize = 10;
+
+/// # Fallback of the Dogleg method for non positive definite Hessians
+///
+/// The Dogleg method requires the Hessian to be positive definite. The Newton step is considered
+/// to have failed if the Hessian cannot be inverted, if the Newton step is not finite or if the
+/// curvature along the Newton step or along the steepest descent direction is not positive (up
+/// to rounding errors). This determines how [`Dogleg`] proceeds in this case.
+///
+/// # Example
+///
+/// ```
+/// # use argmin::solver::trustregion::DoglegFallback;
+/// let fallback: DoglegFallback<f64> = DoglegFallback::default();
+/// assert_eq!(fallback, DoglegFallback::Cauchy);
+/// ```
+#[derive(Clone, Debug, Copy, PartialEq, Eq, PartialOrd, Default)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize))]
+pub enum DoglegFallback<F> {
+    /// Return an error
+    Error,
+    /// Take the Cauchy step, i.e. the minimizer of the quadratic model along the steepest descent
+    /// direction within the trust region (default)
+    #[default]
+    Cauchy,
+    /// Compute the Dogleg step for the regularized Hessian `H + lambda I`, starting from the
+    /// given `lambda`. `lambda` is increased tenfold until the Newton step succeeds. If this fails
+    /// after 10 attempts, the Cauchy step is taken.
+    ///
+    /// `lambda` must be positive.
+    Regularize(F),
+    /// Do not check the Newton step and compute the Dogleg step from it regardless, as versions
+    /// prior to the introduction of the fallbacks did. Only fails if the Hessian cannot be
+    /// inverted.
+    Unchecked,
+}
+
 /// # Dogleg method
 ///
 /// The Dogleg method computes the intersection of the trust region boundary with a path given by
 /// the unconstrained minimum along the steepest descent direction and the optimum of the quadratic
 /// approximation of the cost function at the current point.
 ///
+/// If the Hessian is not positive definite, the step is computed according to the
+/// [`DoglegFallback`] policy set via [`with_fallback`](`Dogleg::with_fallback`). By default, the
+/// Cauchy step is taken. Whenever a fallback is used, it is reported as `fallback` (either
+/// `"cauchy"` or `"regularized"`) together with the final shift `regularization` of the
+/// regularized Hessian.
+///
 /// ## Requirements on the optimization problem
 ///
 /// The optimization problem is required to implement [`Gradient`] and [`Hessian`].
@@ -34,6 +79,9 @@ use serde::{Deserialize, Serialize};
 pub struct Dogleg<F> {
     /// Radius
     radius: F,
+    /// Fallback if the Newton step fails
+    #[cfg_attr(feature = "serde1", serde(default))]
+    fallback: DoglegFallback<F>,
 }
 
 impl<F> Dogleg<F>
@@ -49,7 +97,179 @@ where
     /// let dl: Dogleg<f64> = Dogleg::new();
     /// ```
     pub fn new() -> Self {
-        Dogleg { radius: F::nan() }
+        Dogleg {
+            radius: F::nan(),
+            fallback: DoglegFallback::default(),
+        }
+    }
+
+    /// Set the fallback for non positive definite Hessians
+    ///
+    /// Defaults to [`DoglegFallback::Cauchy`]. The initial shift of
+    /// [`DoglegFallback::Regularize`] must be positive.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::trustregion::{Dogleg, DoglegFallback};
+    /// # use argmin::core::Error;
+    /// # fn main() -> Result<(), Error> {
+    /// let dl: Dogleg<f64> = Dogleg::new().with_fallback(DoglegFallback::Regularize(1e-3))?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_fallback(mut self, fallback: DoglegFallback<F>) -> Result<Self, Error> {
+        if let DoglegFallback::Regularize(lambda) = fallback {
+            if lambda.is_nan() || lambda <= float!(0.0) {
+                return Err(argmin_error!(
+                    InvalidParameter,
+                    "`Dogleg`: regularization parameter must be > 0."
+                ));
+            }
+        }
+        self.fallback = fallback;
+        Ok(self)
+    }
+
+    /// Computes the Newton step `-H^-1 g`.
+    ///
+    /// Returns `None` if `H` cannot be inverted, if the Newton step is not finite or if the
+    /// curvature along the Newton step or along `g` is not positive.
+    fn newton_step<P, H>(h: &H, g: &P) -> Option<P>
+    where
+        P: ArgminMul<F, P> + ArgminL2Norm<F> + ArgminDot<P, F> + ArgminWeightedDot<P, F, H>,
+        H: ArgminInv<H> + ArgminDot<P, P>,
+    {
+        let pb = h.inv().ok()?.dot(g).mul(&float!(-1.0));
+        if !pb.l2_norm().is_finite() {
+            return None;
+        }
+        // Nothing to check at a stationary point
+        let g_norm = g.l2_norm();
+        if g_norm == float!(0.0) {
+            return Some(pb);
+        }
+        // Since `H pb = -g`, the curvature along `pb` is `pb^T H pb = -g^T pb`. A Newton step
+        // which is orthogonal to the gradient up to rounding errors is rejected as well.
+        let gpb = g.dot(&pb);
+        let gpb_max = -F::epsilon().sqrt() * g_norm * pb.l2_norm();
+        let ghg = g.weighted_dot(h, g);
+        if gpb.is_nan() || gpb >= gpb_max || ghg.is_nan() || ghg <= float!(0.0) {
+            return None;
+        }
+        Some(pb)
+    }
+
+    /// Computes the Cauchy step, the minimizer of the quadratic model along `-g` within the trust
+    /// region.
+    fn cauchy_step<P, H>(&self, h: &H, g: &P) -> P
+    where
+        P: ArgminMul<F, P> + ArgminL2Norm<F> + ArgminWeightedDot<P, F, H>,
+    {
+        let g_norm = g.l2_norm();
+        if g_norm == float!(0.0) {
+            return g.mul(&float!(0.0));
+        }
+        let ghg = g.weighted_dot(h, g);
+        let tau = if ghg <= float!(0.0) {
+            float!(1.0)
+        } else {
+            float!(1.0).min(g_norm.powi(3) / (self.radius * ghg))
+        };
+        g.mul(&(-tau * self.radius / g_norm))
+    }
+
+    /// Computes the Dogleg step for the Newton step `pb`.
+    fn dogleg_step<P, H>(&self, h: &H, g: &P, pb: P) -> Result<P, Error>
+    where
+        P: Clone
+            + ArgminMul<F, P>
+            + ArgminL2Norm<F>
+            + ArgminDot<P, F>
+            + ArgminAdd<P, P>
+            + ArgminSub<P, P>
+            + ArgminWeightedDot<P, F, H>,
+    {
+        if pb.l2_norm() <= self.radius {
+            return Ok(pb);
+        }
+
+        // pu = - (g^Tg)/(g^THg) * g
+        let pu = g.mul(&(-g.dot(g) / g.weighted_dot(h, g)));
+
+        // The boundary is crossed on the first segment. This also covers the degenerate case of
+        // `p^b` and `p^u` coinciding, as happens for strongly regularized Hessians.
+        let pu_norm = pu.l2_norm();
+        if pu_norm >= self.radius {
+            return Ok(pu.mul(&(self.radius / pu_norm)));
+        }
+
+        let k = pb.sub(&pu); // p^b - p^u
+        let c = pu.dot(&k); // p^u^T * (p^b - p^u)
+        let k = k.dot(&k); // (p^b - p^u)^T (p^b - p^u)
+        let u = pu.dot(&pu); // p^u^T p^u
+
+        let delta_squared = self.radius.powi(2);
+        let t1 = (c.powi(2) + delta_squared * k - k * u).sqrt();
+        let tau = [
+            -(t1 + c - k) / k,
+            (t1 - c + k) / k,
+            (float!(2.0) * c + delta_squared - u) / (float!(2.0) * c),
+        ]
+        .into_iter()
+        .filter(|t| !t.is_nan() && !t.is_infinite() && *t >= float!(0.0) && *t <= float!(2.0))
+        .fold(float!(0.0), |acc, t| if t >= acc { t } else { acc });
+
+        if tau >= float!(0.0) && tau < float!(1.0) {
+            Ok(pu.mul(&tau))
+        } else if tau >= float!(1.0) && tau <= float!(2.0) {
+            Ok(pu.add(&pb.sub(&pu).mul(&(tau - float!(1.0)))))
+        } else {
+            Err(argmin_error!(
+                PotentialBug,
+                "tau is outside the range [0, 2], this is not supposed to happen."
+            ))
+        }
+    }
+}
+
+/// Serializable configuration of [`Dogleg`]
+///
+/// Options which are `None` keep the defaults of [`Dogleg`]. The configuration is validated when
+/// the solver is constructed via [`build`](`DoglegConfig::build`). The radius is not part of the
+/// configuration since it is set by [`TrustRegion`](`crate::solver::trustregion::TrustRegion`).
+///
+/// # Example
+///
+/// ```
+/// # use argmin::solver::trustregion::{Dogleg, DoglegConfig, DoglegFallback};
+/// # use argmin::core::Error;
+/// # fn main() -> Result<(), Error> {
+/// let config = DoglegConfig {
+///     fallback: Some(DoglegFallback::Regularize(1e-3f64)),
+/// };
+/// let dl: Dogleg<f64> = config.build()?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize), serde(default))]
+pub struct DoglegConfig<F> {
+    /// See [`Dogleg::with_fallback`]
+    pub fallback: Option<DoglegFallback<F>>,
+}
+
+impl<F> DoglegConfig<F>
+where
+    F: ArgminFloat,
+{
+    /// Validates the configuration and constructs a [`Dogleg`] solver.
+    pub fn build(&self) -> Result<Dogleg<F>, Error> {
+        let mut solver = Dogleg::new();
+        if let Some(fallback) = self.fallback {
+            solver = solver.with_fallback(fallback)?;
+        }
+        Ok(solver)
     }
 }
 
@@ -62,13 +282,30 @@ where
         + ArgminDot<P, F>
         + ArgminAdd<P, P>
         + ArgminSub<P, P>,
-    H: ArgminInv<H> + ArgminDot<P, P>,
+    H: ArgminInv<H> + ArgminDot<P, P> + ArgminEye + ArgminAdd<H, H> + ArgminMul<F, H>,
     F: ArgminFloat,
 {
     fn name(&self) -> &str {
         "Dogleg"
     }
 
+    fn init(
+        &mut self,
+        _problem: &mut Problem<O>,
+        state: IterState<P, P, (), H, (), F>,
+    ) -> Result<(IterState<P, P, (), H, (), F>, Option<KV>), Error> {
+        Ok((
+            state,
+            Some(
+                kv!(
+                    "radius" => self.radius;
+                    "fallback" => format!("{:?}", self.fallback);
+                )
+                .with_prefix("config"),
+            ),
+        ))
+    }
+
     fn next_iter(
         &mut self,
         problem: &mut Problem<O>,
@@ -92,50 +329,49 @@ where
             .map(Result::Ok)
             .unwrap_or_else(|| problem.hessian(&param))?;
 
-        let pstar;
+        if let DoglegFallback::Unchecked = self.fallback {
+            // pb = -H^-1g
+            let pb = (h.inv()?).dot(&g).mul(&float!(-1.0));
+            let pstar = self.dogleg_step(&h, &g, pb)?;
+            return Ok((state.param(pstar).gradient(g).hessian(h), None));
+        }
 
-        // pb = -H^-1g
-        let pb = (h.inv()?).dot(&g).mul(&float!(-1.0));
+        if let Some(pb) = Self::newton_step(&h, &g) {
+            let pstar = self.dogleg_step(&h, &g, pb)?;
+            return Ok((state.param(pstar).gradient(g).hessian(h), None));
+        }
 
-        if pb.l2_norm() <= self.radius {
-            pstar = pb;
-        } else {
-            // pu = - (g^Tg)/(g^THg) * g
-            let pu = g.mul(&(-g.dot(&g) / g.weighted_dot(&h, &g)));
-
-            let k = pb.sub(&pu); // p^b - p^u
-            let c = pu.dot(&k); // p^u^T * (p^b - p^u)
-            let k = k.dot(&k); // (p^b - p^u)^T (p^b - p^u)
-            let u = pu.dot(&pu); // p^u^T p^u
-
-            let delta_squared = self.radius.powi(2);
-            let t1 = (c.powi(2) + delta_squared * k - k * u).sqrt();
-            let tau = [
-                -(t1 + c - k) / k,
-                (t1 - c + k) / k,
-                (float!(2.0) * c + delta_squared - u) / (float!(2.0) * c),
-            ]
-            .into_iter()
-            // .enumerate()
-            // .map(|(i, t)| {
-            //     println!("tau{}: {}", i, t);
-            //     t
-            // })
-            .filter(|t| !t.is_nan() && !t.is_infinite() && *t >= float!(0.0) && *t <= float!(2.0))
-            .fold(float!(0.0), |acc, t| if t >= acc { t } else { acc });
-
-            if tau >= float!(0.0) && tau < float!(1.0) {
-                pstar = pu.mul(&tau);
-            } else if tau >= float!(1.0) && tau <= float!(2.0) {
-                pstar = pu.add(&pb.sub(&pu).mul(&(tau - float!(1.0))));
-            } else {
+        let (pstar, kv) = match self.fallback {
+            // `Unchecked` has been handled above
+            DoglegFallback::Error | DoglegFallback::Unchecked => {
                 return Err(argmin_error!(
-                    PotentialBug,
-                    "tau is outside the range [0, 2], this is not supposed to happen."
-                ));
+                    ConditionViolated,
+                    "`Dogleg`: Hessian is not positive definite."
+                ))
             }
-        }
-        Ok((state.param(pstar).gradient(g).hessian(h), None))
+            DoglegFallback::Cauchy => (self.cauchy_step(&h, &g), kv!("fallback" => "cauchy";)),
+            DoglegFallback::Regularize(lambda) => {
+                let eye = h.eye_like();
+                let mut lambda = lambda;
+                let mut step = None;
+                for _ in 0..MAX_REGULARIZATION_TRIALS {
+                    let h_reg = h.add(&eye.mul(&lambda));
+                    if let Some(pb) = Self::newton_step(&h_reg, &g) {
+                        step = Some(self.dogleg_step(&h_reg, &g, pb)?);
+                        break;
+                    }
+                    lambda = lambda * float!(10.0);
+                }
+                match step {
+                    Some(pstar) => (
+                        pstar,
+                        kv!("fallback" => "regularized"; "regularization" => lambda;),
+                    ),
+                    None => (self.cauchy_step(&h, &g), kv!("fallback" => "cauchy";)),
+                }
+            }
+        };
+        Ok((state.param(pstar).gradient(g).hessian(h), Some(kv)))
     }
 
     fn terminate(&mut self, state: &IterState<P, P, (), H, (), F>) -> TerminationStatus {
@@ -167,7 +403,6 @@ impl<F: ArgminFloat> TrustRegionRadius<F> for Dogleg<F> {
 #[cfg(test)]
 mod tests {
     use super::*;
-    #[cfg(feature = "_ndarrayl")]
     use crate::core::ArgminError;
 
     test_trait_impl!(dogleg, Dogleg<f64>);
@@ -176,14 +411,61 @@ mod tests {
     fn test_new() {
         let dl: Dogleg<f64> = Dogleg::new();
 
-        let Dogleg { radius } = dl;
+        let Dogleg { radius, fallback } = dl;
 
         assert_eq!(radius.to_ne_bytes(), f64::NAN.to_ne_bytes());
+        assert_eq!(fallback, DoglegFallback::Cauchy);
+    }
+
+    #[test]
+    fn test_with_fallback() {
+        for fallback in [
+            DoglegFallback::Error,
+            DoglegFallback::Cauchy,
+            DoglegFallback::Regularize(1e-3),
+            DoglegFallback::Unchecked,
+        ] {
+            let dl: Dogleg<f64> = Dogleg::new().with_fallback(fallback).unwrap();
+            assert_eq!(dl.fallback, fallback);
+        }
+
+        for lambda in [0.0, -1e-3, f64::NAN] {
+            let res: Result<Dogleg<f64>, _> =
+                Dogleg::new().with_fallback(DoglegFallback::Regularize(lambda));
+            assert_error!(
+                res,
+                ArgminError,
+                "Invalid parameter: \"`Dogleg`: regularization parameter must be > 0.\""
+            );
+        }
+    }
+
+    #[test]
+    fn test_config_build() {
+        let config = DoglegConfig {
+            fallback: Some(DoglegFallback::Regularize(1e-3f64)),
+        };
+        let dl: Dogleg<f64> = config.build().unwrap();
+        assert_eq!(dl.fallback, DoglegFallback::Regularize(1e-3));
+
+        let dl: Dogleg<f64> = DoglegConfig::default().build().unwrap();
+        assert_eq!(dl.fallback, DoglegFallback::Cauchy);
+
+        let res: Result<Dogleg<f64>, _> = DoglegConfig {
+            fallback: Some(DoglegFallback::Regularize(0.0)),
+        }
+        .build();
+        assert_error!(
+            res,
+            ArgminError,
+            "Invalid parameter: \"`Dogleg`: regularization parameter must be > 0.\""
+        );
     }
 
     #[cfg(feature = "_ndarrayl")]
     #[test]
     fn test_next_iter() {
+        use crate::core::KvValue;
         use approx::assert_relative_eq;
         use ndarray::{Array, Array1, Array2};
 
@@ -225,7 +507,40 @@ mod tests {
             )
         );
 
-        // All good.
+        // The Hessian is not positive definite: the Newton step `(-1, 0.25)` is orthogonal to the
+        // gradient. Therefore the Cauchy step is taken by default.
+        let state: IterState<Array1<f64>, Array1<f64>, (), Array2<f64>, (), f64> =
+            IterState::new().param(param.clone());
+        let problem = TestProblem {};
+        let (mut state_out, kv) = dl.next_iter(&mut Problem::new(problem), state).unwrap();
+
+        assert_eq!(
+            kv.unwrap().get("fallback"),
+            Some(&KvValue::Str("cauchy".to_string()))
+        );
+
+        let s_param = state_out.take_param().unwrap();
+
+        assert_relative_eq!(s_param[0], -0.1, epsilon = 1e-12);
+        assert_relative_eq!(s_param[1], -0.4, epsilon = 1e-12);
+
+        let mut dl: Dogleg<f64> = Dogleg::new().with_fallback(DoglegFallback::Error).unwrap();
+        dl.set_radius(1.0);
+        let state: IterState<Array1<f64>, Array1<f64>, (), Array2<f64>, (), f64> =
+            IterState::new().param(param.clone());
+        let problem = TestProblem {};
+        let res = dl.next_iter(&mut Problem::new(problem), state);
+        assert_error!(
+            res,
+            ArgminError,
+            "Condition violated: \"`Dogleg`: Hessian is not positive definite.\""
+        );
+
+        // The unchecked Newton step leads to the same result as before the fallbacks existed.
+        let mut dl: Dogleg<f64> = Dogleg::new()
+            .with_fallback(DoglegFallback::Unchecked)
+            .unwrap();
+        dl.set_radius(1.0);
         let state: IterState<Array1<f64>, Array1<f64>, (), Array2<f64>, (), f64> =
             IterState::new().param(param);
         let problem = TestProblem {};
@@ -238,4 +553,121 @@ mod tests {
         assert_relative_eq!(s_param[0], -0.9730617585026127, epsilon = f64::EPSILON);
         assert_relative_eq!(s_param[1], 0.2305446033629983, epsilon = f64::EPSILON);
     }
+
+    #[cfg(feature = "_ndarrayl")]
+    #[test]
+    fn test_next_iter_positive_definite() {
+        use approx::assert_relative_eq;
+        use ndarray::{Array, Array1, Array2};
+
+        struct TestProblem {}
+
+        impl Gradient for TestProblem {
+            type Param = Array1<f64>;
+            type Gradient = Array1<f64>;
+
+            fn gradient(&self, _p: &Self::Param) -> Result<Self::Gradient, Error> {
+                Ok(Array1::from_vec(vec![0.5, 2.0]))
+            }
+        }
+
+        impl Hessian for TestProblem {
+            type Param = Array1<f64>;
+            type Hessian = Array2<f64>;
+
+            fn hessian(&self, _p: &Self::Param) -> Result<Self::Hessian, Error> {
+                Ok(Array::from_shape_vec((2, 2), vec![2f64, 1.0, 1.0, 4.0])?)
+            }
+        }
+
+        let param: Array1<f64> = Array1::from_vec(vec![-1.0, 1.0]);
+
+        let mut dl: Dogleg<f64> = Dogleg::new();
+        dl.set_radius(0.49);
+
+        let state: IterState<Array1<f64>, Array1<f64>, (), Array2<f64>, (), f64> =
+            IterState::new().param(param);
+        let problem = TestProblem {};
+        let (mut state_out, kv) = dl.next_iter(&mut Problem::new(problem), state).unwrap();
+
+        assert!(kv.is_none());
+
+        let s_param = state_out.take_param().unwrap();
+
+        assert_relative_eq!(s_param[0], -0.031139662056007558, epsilon = 1e-12);
+        assert_relative_eq!(s_param[1], -0.48900953103905614, epsilon = 1e-12);
+    }
+
+    #[cfg(feature = "_ndarrayl")]
+    #[test]
+    fn test_fallback() {
+        use crate::core::KvValue;
+        use approx::assert_relative_eq;
+        use ndarray::{Array, Array1, Array2};
+
+        struct TestProblem {}
+
+        impl Gradient for TestProblem {
+            type Param = Array1<f64>;
+            type Gradient = Array1<f64>;
+
+            fn gradient(&self, _p: &Self::Param) -> Result<Self::Gradient, Error> {
+                Ok(Array1::from_vec(vec![0.5, 1.0]))
+            }
+        }
+
+        impl Hessian for TestProblem {
+            type Param = Array1<f64>;
+            type Hessian = Array2<f64>;
+
+            fn hessian(&self, _p: &Self::Param) -> Result<Self::Hessian, Error> {
+                // Indefinite, with negative curvature along the gradient
+                Ok(Array::from_shape_vec((2, 2), vec![1f64, 0.0, 0.0, -1.0])?)
+            }
+        }
+
+        let run = |fallback| {
+            let mut dl: Dogleg<f64> = Dogleg::new().with_fallback(fallback).unwrap();
+            dl.set_radius(1.0);
+            let state: IterState<Array1<f64>, Array1<f64>, (), Array2<f64>, (), f64> =
+                IterState::new().param(Array1::from_vec(vec![0.0, 0.0]));
+            dl.next_iter(&mut Problem::new(TestProblem {}), state)
+        };
+
+        // Step along the steepest descent direction to the boundary of the trust region
+        let (mut state, kv) = run(DoglegFallback::Cauchy).unwrap();
+        let p = state.take_param().unwrap();
+        let g_norm = 1.25f64.sqrt();
+        assert_relative_eq!(p[0], -0.5 / g_norm, epsilon = 1e-12);
+        assert_relative_eq!(p[1], -1.0 / g_norm, epsilon = 1e-12);
+        let kv = kv.unwrap();
+        assert_eq!(
+            kv.get("fallback"),
+            Some(&KvValue::Str("cauchy".to_string()))
+        );
+
+        // `lambda = 0.1` and `lambda = 1` are not sufficient; `lambda = 10` results in a Newton
+        // step within the trust region.
+        let (mut state, kv) = run(DoglegFallback::Regularize(0.1)).unwrap();
+        let p = state.take_param().unwrap();
+        assert_relative_eq!(p[0], -0.5 / 11.0, epsilon = 1e-12);
+        assert_relative_eq!(p[1], -1.0 / 9.0, epsilon = 1e-12);
+        let kv = kv.unwrap();
+        assert_eq!(
+            kv.get("fallback"),
+            Some(&KvValue::Str("regularized".to_string()))
+        );
+        assert_relative_eq!(
+            kv.get("regularization").unwrap().get_float().unwrap(),
+            10.0,
+            epsilon = 1e-12
+        );
+
+        let res = run(DoglegFallback::Error);
+        assert_error!(
+            res,
+            ArgminError,
+            "Condition violated: \"`Dogleg`: Hessian is not positive definite.\""
+        );
+    }
 }
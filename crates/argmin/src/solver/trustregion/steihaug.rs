@@ -20,6 +20,17 @@ use serde::{Deserialize, Serialize};
 /// The Steihaug method is a conjugate gradients based approach for finding an approximate solution
 /// to the second order approximation of the cost function within the trust region.
 ///
+/// The conjugate gradient iterations can be preconditioned with a diagonal preconditioner via
+/// [`with_preconditioner`](`Steihaug::with_preconditioner`).
+///
+/// In each iteration, the norm of the residual is reported as `residual`. The flags
+/// `boundary_hit` and `negative_curvature` indicate whether the step was truncated at the
+/// boundary of the trust region and whether this was due to a direction of nonpositive
+/// curvature. Together with [`TrustRegion::with_inner_telemetry`] this allows to diagnose why
+/// steps are truncated.
+///
+/// [`TrustRegion::with_inner_telemetry`]: `crate::solver::trustregion::TrustRegion::with_inner_telemetry`
+///
 /// ## Reference
 ///
 /// Jorge Nocedal and Stephen J. Wright (2006). Numerical Optimization.
@@ -35,7 +46,7 @@ pub struct Steihaug<P, F> {
     p: Option<P>,
     /// residual
     r: Option<P>,
-    /// r^Tr
+    /// r^T z, where z is the preconditioned residual (r^T r without preconditioner)
     rtr: F,
     /// initial residual
     r_0_norm: F,
@@ -43,6 +54,9 @@ pub struct Steihaug<P, F> {
     d: Option<P>,
     /// max iters
     max_iters: u64,
+    /// Elementwise inverse of the diagonal preconditioner
+    #[cfg_attr(feature = "serde1", serde(default))]
+    preconditioner: Option<P>,
 }
 
 impl<P, F> Steihaug<P, F>
@@ -68,6 +82,7 @@ where
             r_0_norm: F::nan(),
             d: None,
             max_iters: u64::MAX,
+            preconditioner: None,
         }
     }
 
@@ -117,6 +132,38 @@ where
         self
     }
 
+    /// Set a diagonal preconditioner
+    ///
+    /// `inv_diag` holds the elementwise inverse of the diagonal of the preconditioner `M`, such
+    /// that the preconditioned residual is computed as `z = M^-1 r = inv_diag * r`. A common
+    /// choice is the inverse of the diagonal of the Hessian (Jacobi preconditioner). The trust
+    /// region boundary is still measured in the Euclidean norm.
+    ///
+    /// All entries of `inv_diag` must be positive.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::trustregion::Steihaug;
+    /// let sh: Steihaug<Vec<f64>, f64> = Steihaug::new().with_preconditioner(vec![0.5, 0.1]);
+    /// ```
+    #[must_use]
+    pub fn with_preconditioner(mut self, inv_diag: P) -> Self {
+        self.preconditioner = Some(inv_diag);
+        self
+    }
+
+    /// Applies the preconditioner to the residual `r`
+    fn precondition(&self, r: &P) -> P
+    where
+        P: Clone + ArgminMul<P, P>,
+    {
+        match self.preconditioner.as_ref() {
+            Some(inv_diag) => r.mul(inv_diag),
+            None => r.clone(),
+        }
+    }
+
     /// evaluate m(p) (without considering f_init because it is not available)
     fn eval_m<H>(&self, p: &P, g: &P, h: &H) -> F
     where
@@ -178,11 +225,60 @@ where
     }
 }
 
+/// Serializable configuration of [`Steihaug`]
+///
+/// Options which are `None` keep the defaults of [`Steihaug`]. The configuration is validated when
+/// the solver is constructed via [`build`](`SteihaugConfig::build`).
+///
+/// # Example
+///
+/// ```
+/// # use argmin::solver::trustregion::{Steihaug, SteihaugConfig};
+/// # use argmin::core::Error;
+/// # fn main() -> Result<(), Error> {
+/// let config = SteihaugConfig {
+///     max_iters: Some(20),
+///     ..Default::default()
+/// };
+/// let sh: Steihaug<Vec<f64>, f64> = config.build()?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize), serde(default))]
+pub struct SteihaugConfig<F> {
+    /// See [`Steihaug::with_epsilon`]
+    pub epsilon: Option<F>,
+    /// See [`Steihaug::with_max_iters`]
+    pub max_iters: Option<u64>,
+}
+
+impl<F> SteihaugConfig<F>
+where
+    F: ArgminFloat,
+{
+    /// Validates the configuration and constructs a [`Steihaug`] solver.
+    pub fn build<P>(&self) -> Result<Steihaug<P, F>, Error>
+    where
+        P: ArgminMul<F, P> + ArgminDot<P, F> + ArgminAdd<P, P>,
+    {
+        let mut solver = Steihaug::new();
+        if let Some(epsilon) = self.epsilon {
+            solver = solver.with_epsilon(epsilon)?;
+        }
+        if let Some(iters) = self.max_iters {
+            solver = solver.with_max_iters(iters);
+        }
+        Ok(solver)
+    }
+}
+
 impl<P, O, F, H> Solver<O, IterState<P, P, (), H, (), F>> for Steihaug<P, F>
 where
     P: Clone
         + ArgminMul<F, P>
         + ArgminL2Norm<F>
+        + ArgminMul<P, P>
         + ArgminDot<P, F>
         + ArgminAdd<P, P>
         + ArgminZeroLike,
@@ -220,14 +316,26 @@ where
         }
 
         self.r_0_norm = r.l2_norm();
-        self.rtr = r.dot(&r);
-        self.d = Some(r.mul(&float!(-1.0)));
+        let z = self.precondition(&r);
+        self.rtr = r.dot(&z);
+        self.d = Some(z.mul(&float!(-1.0)));
         let p = r.zero_like();
         self.p = Some(p.clone());
 
         self.r = Some(r);
 
-        Ok((state.param(p), None))
+        Ok((
+            state.param(p),
+            Some(
+                kv!(
+                    "radius" => self.radius;
+                    "epsilon" => self.epsilon;
+                    "max_iters" => self.max_iters;
+                    "preconditioned" => self.preconditioner.is_some();
+                )
+                .with_prefix("config"),
+            ),
+        ))
     }
 
     fn next_iter(
@@ -256,7 +364,7 @@ where
                 state
                     .param(p.add(&d.mul(&tau)))
                     .terminate_with(TerminationReason::SolverConverged),
-                None,
+                Some(kv!("boundary_hit" => true; "negative_curvature" => true;)),
             ));
         }
 
@@ -270,32 +378,39 @@ where
                 state
                     .param(p.add(&d.mul(&tau)))
                     .terminate_with(TerminationReason::SolverConverged),
-                None,
+                Some(kv!("boundary_hit" => true; "negative_curvature" => false;)),
             ));
         }
 
         let r = self.r.as_ref().unwrap();
         let r_n = r.add(&h.dot(d).mul(&alpha));
+        let r_n_norm = r_n.l2_norm();
+        let kv = kv!(
+            "residual" => r_n_norm;
+            "boundary_hit" => false;
+            "negative_curvature" => false;
+        );
 
-        if r_n.l2_norm() < self.epsilon * self.r_0_norm {
+        if r_n_norm < self.epsilon * self.r_0_norm {
             return Ok((
                 state
                     .param(p_n)
                     .terminate_with(TerminationReason::SolverConverged),
-                None,
+                Some(kv),
             ));
         }
 
-        let rjtrj = r_n.dot(&r_n);
+        let z_n = self.precondition(&r_n);
+        let rjtrj = r_n.dot(&z_n);
         let beta = rjtrj / self.rtr;
-        self.d = Some(r_n.mul(&float!(-1.0)).add(&d.mul(&beta)));
+        self.d = Some(z_n.mul(&float!(-1.0)).add(&d.mul(&beta)));
         self.r = Some(r_n);
         self.p = Some(p_n.clone());
         self.rtr = rjtrj;
 
         Ok((
             state.param(p_n).cost(self.rtr).gradient(grad).hessian(h),
-            None,
+            Some(kv),
         ))
     }
 
@@ -331,7 +446,7 @@ impl<P, F: ArgminFloat> TrustRegionRadius<F> for Steihaug<P, F> {
 mod tests {
     use super::*;
     use crate::core::test_utils::TestProblem;
-    use crate::core::ArgminError;
+    use crate::core::{ArgminError, Executor, KvValue};
     use approx::assert_relative_eq;
 
     test_trait_impl!(steihaug, Steihaug<TestProblem, f64>);
@@ -349,6 +464,7 @@ mod tests {
             r_0_norm,
             d,
             max_iters,
+            preconditioner,
         } = sh;
 
         assert_eq!(radius.to_ne_bytes(), f64::NAN.to_ne_bytes());
@@ -359,6 +475,7 @@ mod tests {
         assert_eq!(r_0_norm.to_ne_bytes(), f64::NAN.to_ne_bytes());
         assert!(d.is_none());
         assert_eq!(max_iters, u64::MAX);
+        assert!(preconditioner.is_none());
     }
 
     #[test]
@@ -395,6 +512,28 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_config_build() {
+        let config = SteihaugConfig {
+            epsilon: Some(1e-6f64),
+            max_iters: Some(20),
+        };
+        let sh: Steihaug<Vec<f64>, f64> = config.build().unwrap();
+        assert_eq!(sh.epsilon.to_ne_bytes(), 1e-6f64.to_ne_bytes());
+        assert_eq!(sh.max_iters, 20);
+
+        let res: Result<Steihaug<Vec<f64>, f64>, _> = SteihaugConfig {
+            epsilon: Some(0.0),
+            ..Default::default()
+        }
+        .build();
+        assert_error!(
+            res,
+            ArgminError,
+            "Invalid parameter: \"`Steihaug`: epsilon must be > 0.0.\""
+        );
+    }
+
     #[test]
     fn test_init() {
         let grad: Vec<f64> = vec![1.0, 2.0];
@@ -436,7 +575,10 @@ mod tests {
         let problem = TestProblem::new();
         let (mut state_out, kv) = sh.init(&mut Problem::new(problem), state).unwrap();
 
-        assert!(kv.is_none());
+        let kv = kv.unwrap();
+        assert_eq!(kv.get("config.epsilon"), Some(&KvValue::Float(10e-10)));
+        assert_eq!(kv.get("config.max_iters"), Some(&KvValue::Uint(u64::MAX)));
+        assert_eq!(kv.get("config.preconditioned"), Some(&KvValue::Bool(false)));
 
         let s_param = state_out.take_param().unwrap();
 
@@ -452,6 +594,7 @@ mod tests {
             r_0_norm,
             d,
             max_iters,
+            preconditioner,
         } = sh;
 
         assert_eq!(radius.to_ne_bytes(), 1.0f64.to_ne_bytes());
@@ -465,5 +608,78 @@ mod tests {
         assert_relative_eq!(d.as_ref().unwrap()[0], -grad[0], epsilon = f64::EPSILON);
         assert_relative_eq!(d.as_ref().unwrap()[1], -grad[1], epsilon = f64::EPSILON);
         assert_eq!(max_iters, u64::MAX);
+        assert!(preconditioner.is_none());
+    }
+
+    fn run(
+        sh: Steihaug<Vec<f64>, f64>,
+        grad: Vec<f64>,
+        hessian: Vec<Vec<f64>>,
+    ) -> (
+        IterState<Vec<f64>, Vec<f64>, (), Vec<Vec<f64>>, (), f64>,
+        KV,
+    ) {
+        let (result, kv) = Executor::new(TestProblem::new(), sh)
+            .configure(|state| state.gradient(grad).hessian(hessian))
+            .run_nested()
+            .unwrap();
+        (result.state, kv)
+    }
+
+    #[test]
+    fn test_inner_stats() {
+        let grad = vec![1.0f64, 1.0];
+        let hessian = vec![vec![1.0f64, 0.0], vec![0.0, 100.0]];
+
+        // The Newton step `[-1, -0.01]` lies within the trust region.
+        let mut sh: Steihaug<Vec<f64>, f64> = Steihaug::new();
+        sh.set_radius(10.0);
+        let (state, kv) = run(sh, grad.clone(), hessian.clone());
+        assert_eq!(state.get_iter(), 2);
+        assert_relative_eq!(state.param.as_ref().unwrap()[0], -1.0, epsilon = 1e-12);
+        assert_relative_eq!(state.param.as_ref().unwrap()[1], -0.01, epsilon = 1e-12);
+        assert_eq!(kv.get("boundary_hit"), Some(&KvValue::Bool(false)));
+        assert_eq!(kv.get("negative_curvature"), Some(&KvValue::Bool(false)));
+        assert!(kv.get("residual").unwrap().get_float().unwrap() < 1e-10);
+
+        // The step is truncated at the boundary.
+        let mut sh: Steihaug<Vec<f64>, f64> = Steihaug::new();
+        sh.set_radius(0.1);
+        let (state, kv) = run(sh, grad.clone(), hessian);
+        assert_relative_eq!(
+            state.param.as_ref().unwrap().l2_norm(),
+            0.1,
+            epsilon = 1e-12
+        );
+        assert_eq!(kv.get("boundary_hit"), Some(&KvValue::Bool(true)));
+        assert_eq!(kv.get("negative_curvature"), Some(&KvValue::Bool(false)));
+
+        // Negative curvature along the first direction
+        let mut sh: Steihaug<Vec<f64>, f64> = Steihaug::new();
+        sh.set_radius(1.0);
+        let (state, kv) = run(sh, grad, vec![vec![-1.0f64, 0.0], vec![0.0, -1.0]]);
+        assert_relative_eq!(
+            state.param.as_ref().unwrap().l2_norm(),
+            1.0,
+            epsilon = 1e-12
+        );
+        assert_eq!(kv.get("boundary_hit"), Some(&KvValue::Bool(true)));
+        assert_eq!(kv.get("negative_curvature"), Some(&KvValue::Bool(true)));
+    }
+
+    #[test]
+    fn test_preconditioner() {
+        let grad = vec![1.0f64, 1.0];
+        let hessian = vec![vec![1.0f64, 0.0], vec![0.0, 100.0]];
+
+        // With the exact inverse of the (diagonal) Hessian, a single iteration suffices.
+        let mut sh: Steihaug<Vec<f64>, f64> = Steihaug::new().with_preconditioner(vec![1.0, 0.01]);
+        sh.set_radius(10.0);
+        let (state, kv) = run(sh, grad, hessian);
+        assert_eq!(state.get_iter(), 1);
+        assert_relative_eq!(state.param.as_ref().unwrap()[0], -1.0, epsilon = 1e-12);
+        assert_relative_eq!(state.param.as_ref().unwrap()[1], -0.01, epsilon = 1e-12);
+        assert_eq!(kv.get("config.preconditioned"), Some(&KvValue::Bool(true)));
+        assert_eq!(kv.get("boundary_hit"), Some(&KvValue::Bool(false)));
     }
 }
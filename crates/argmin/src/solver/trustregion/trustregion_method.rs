@@ -6,8 +6,8 @@
 // copied, modified, or distributed except according to those terms.
 
 use crate::core::{
-    ArgminFloat, CostFunction, Error, Executor, Gradient, Hessian, IterState, OptimizationResult,
-    Problem, Solver, TerminationStatus, TrustRegionRadius, KV,
+    ArgminFloat, CostFunction, Error, Executor, Gradient, Hessian, IterState, KvMeta, KvScale,
+    NestedTelemetry, OptimizationResult, Problem, Solver, TerminationStatus, TrustRegionRadius, KV,
 };
 use crate::solver::trustregion::reduction_ratio;
 use argmin_math::{ArgminAdd, ArgminDot, ArgminL2Norm, ArgminWeightedDot};
@@ -51,6 +51,9 @@ pub struct TrustRegion<R, F> {
     fxk: F,
     /// mk(0)
     mk0: F,
+    /// Telemetry of the subproblem
+    #[cfg_attr(feature = "serde1", serde(default))]
+    inner_telemetry: NestedTelemetry,
 }
 
 impl<R, F> TrustRegion<R, F>
@@ -74,9 +77,30 @@ where
             subproblem,
             fxk: F::nan(),
             mk0: F::nan(),
+            inner_telemetry: NestedTelemetry::new(),
         }
     }
 
+    /// Forward the telemetry of the subproblem to observers
+    ///
+    /// The key-value pairs returned by the subproblem as well as its number of iterations and
+    /// its termination reason are forwarded with keys prefixed by `prefix` (for instance
+    /// `subproblem.iters`). Independent of this setting, the subproblem iterations are counted
+    /// as `subproblem_iter_count`. See [`NestedTelemetry`] for details.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use argmin::solver::trustregion::{CauchyPoint, TrustRegion};
+    /// # let subproblem: CauchyPoint<f64> = CauchyPoint::new();
+    /// let solver = TrustRegion::<_, f64>::new(subproblem).with_inner_telemetry("subproblem");
+    /// ```
+    #[must_use]
+    pub fn with_inner_telemetry<T: AsRef<str>>(mut self, prefix: T) -> Self {
+        self.inner_telemetry = NestedTelemetry::forward(prefix);
+        self
+    }
+
     /// Set radius
     ///
     /// Defaults to `1.0`.
@@ -156,6 +180,59 @@ where
     }
 }
 
+/// Serializable configuration of [`TrustRegion`]
+///
+/// Options which are `None` keep the defaults of [`TrustRegion`]. The configuration is validated
+/// when the solver is constructed via [`build`](`TrustRegionConfig::build`).
+///
+/// # Example
+///
+/// ```
+/// # use argmin::solver::trustregion::{CauchyPoint, TrustRegion, TrustRegionConfig};
+/// # use argmin::core::Error;
+/// # fn main() -> Result<(), Error> {
+/// let config = TrustRegionConfig {
+///     radius: Some(0.8),
+///     eta: Some(0.2),
+///     ..Default::default()
+/// };
+/// let cp: CauchyPoint<f64> = CauchyPoint::new();
+/// let tr: TrustRegion<_, f64> = config.build(cp)?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde1", derive(Serialize, Deserialize), serde(default))]
+pub struct TrustRegionConfig<F> {
+    /// See [`TrustRegion::with_radius`]
+    pub radius: Option<F>,
+    /// See [`TrustRegion::with_max_radius`]
+    pub max_radius: Option<F>,
+    /// See [`TrustRegion::with_eta`]
+    pub eta: Option<F>,
+}
+
+impl<F> TrustRegionConfig<F>
+where
+    F: ArgminFloat,
+{
+    /// Validates the configuration and constructs a [`TrustRegion`] solver using the given
+    /// subproblem solver.
+    pub fn build<R>(&self, subproblem: R) -> Result<TrustRegion<R, F>, Error> {
+        let mut solver = TrustRegion::new(subproblem);
+        if let Some(radius) = self.radius {
+            solver = solver.with_radius(radius)?;
+        }
+        if let Some(max_radius) = self.max_radius {
+            solver = solver.with_max_radius(max_radius)?;
+        }
+        if let Some(eta) = self.eta {
+            solver = solver.with_eta(eta)?;
+        }
+        Ok(solver)
+    }
+}
+
 impl<O, R, F, P, G, H> Solver<O, IterState<P, G, (), H, (), F>> for TrustRegion<R, F>
 where
     O: CostFunction<Param = P, Output = F>
@@ -184,22 +261,17 @@ where
             )
         ))?;
 
-        let grad = state
-            .take_gradient()
-            .map(Result::Ok)
-            .unwrap_or_else(|| problem.gradient(&param))?;
+        let policy = state.get_initial_evaluation();
+
+        let grad = policy.evaluate(state.take_gradient(), || problem.gradient(&param))?;
 
-        let hessian = state
-            .take_hessian()
-            .map(Result::Ok)
-            .unwrap_or_else(|| problem.hessian(&param))?;
+        let hessian = policy.evaluate(state.take_hessian(), || problem.hessian(&param))?;
 
         let cost = state.get_cost();
-        self.fxk = if cost.is_infinite() && cost.is_sign_positive() {
-            problem.cost(&param)?
-        } else {
-            cost
-        };
+        self.fxk = policy.evaluate(
+            (!(cost.is_infinite() && cost.is_sign_positive())).then_some(cost),
+            || problem.cost(&param),
+        )?;
 
         self.mk0 = self.fxk;
         Ok((
@@ -208,7 +280,15 @@ where
                 .cost(self.fxk)
                 .gradient(grad)
                 .hessian(hessian),
-            None,
+            Some(
+                kv!(
+                    "subproblem" => self.subproblem.name();
+                    "radius" => self.radius;
+                    "max_radius" => self.max_radius;
+                    "eta" => self.eta;
+                )
+                .with_prefix("config"),
+            ),
         ))
     }
 
@@ -234,24 +314,29 @@ where
 
         self.subproblem.set_radius(self.radius);
 
-        let OptimizationResult {
-            problem: sub_problem,
-            state: mut sub_state,
-            ..
-        } = Executor::new(problem.take_problem().unwrap(), self.subproblem.clone())
+        let (
+            OptimizationResult {
+                problem: sub_problem,
+                state: mut sub_state,
+                ..
+            },
+            sub_kv,
+        ) = Executor::new(problem.take_problem().unwrap(), self.subproblem.clone())
             .configure(|config| {
                 config
                     .param(param.clone())
                     .gradient(grad.clone())
                     .hessian(hessian.clone())
             })
-            .ctrlc(false)
-            .run()?;
+            .run_nested()?;
 
         let pk = sub_state.take_param().unwrap();
 
         // Consume intermediate problem again. This takes care of the function evaluation counts.
         problem.consume_problem(sub_problem);
+        let sub_kv =
+            self.inner_telemetry
+                .record(problem, "subproblem_iter_count", &sub_state, sub_kv);
 
         let new_param = pk.add(&param);
         let fxkpk = problem.cost(&new_param)?;
@@ -272,6 +357,14 @@ where
             self.radius
         };
 
+        let mut kv = kv!("radius" => cur_radius;);
+        kv.set_meta(
+            "radius",
+            KvMeta::new()
+                .scale(KvScale::Log)
+                .description("Radius of the trust region"),
+        );
+
         Ok((
             if rho > self.eta {
                 self.fxk = fxkpk;
@@ -290,7 +383,7 @@ where
                     .gradient(grad)
                     .hessian(hessian)
             },
-            Some(kv!("radius" => cur_radius;)),
+            Some(kv.merge(sub_kv.unwrap_or_default())),
         ))
     }
 
@@ -320,6 +413,7 @@ mod tests {
             subproblem: _,
             fxk,
             mk0,
+            inner_telemetry,
         } = tr;
 
         assert_eq!(radius.to_ne_bytes(), 1.0f64.to_ne_bytes());
@@ -327,6 +421,33 @@ mod tests {
         assert_eq!(eta.to_ne_bytes(), 0.125f64.to_ne_bytes());
         assert_eq!(fxk.to_ne_bytes(), f64::NAN.to_ne_bytes());
         assert_eq!(mk0.to_ne_bytes(), f64::NAN.to_ne_bytes());
+        assert_eq!(inner_telemetry, NestedTelemetry::new());
+    }
+
+    #[test]
+    fn test_config_build() {
+        let config = TrustRegionConfig {
+            radius: Some(0.5f64),
+            max_radius: Some(10.0),
+            eta: Some(0.2),
+        };
+        let cp: CauchyPoint<f64> = CauchyPoint::new();
+        let tr: TrustRegion<_, f64> = config.build(cp).unwrap();
+        assert_eq!(tr.radius.to_ne_bytes(), 0.5f64.to_ne_bytes());
+        assert_eq!(tr.max_radius.to_ne_bytes(), 10.0f64.to_ne_bytes());
+        assert_eq!(tr.eta.to_ne_bytes(), 0.2f64.to_ne_bytes());
+
+        let cp: CauchyPoint<f64> = CauchyPoint::new();
+        let res = TrustRegionConfig {
+            max_radius: Some(0.0f64),
+            ..Default::default()
+        }
+        .build(cp);
+        assert_error!(
+            res,
+            ArgminError,
+            "Invalid parameter: \"`TrustRegion`: maximum radius must be > 0.\""
+        );
     }
 
     #[test]
@@ -407,7 +528,16 @@ mod tests {
         let problem = TestProblem::new();
         let (mut state_out, kv) = tr.init(&mut Problem::new(problem), state).unwrap();
 
-        assert!(kv.is_none());
+        assert_eq!(
+            kv.unwrap(),
+            kv!(
+                "subproblem" => "Cauchy Point";
+                "radius" => 1.0f64;
+                "max_radius" => 100.0f64;
+                "eta" => 0.125f64;
+            )
+            .with_prefix("config")
+        );
 
         let s_param = state_out.take_param().unwrap();
 
@@ -421,6 +551,7 @@ mod tests {
             subproblem: _,
             fxk,
             mk0,
+            inner_telemetry: _,
         } = tr;
 
         assert_eq!(radius.to_ne_bytes(), 1.0f64.to_ne_bytes());
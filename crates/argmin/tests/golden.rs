@@ -0,0 +1,439 @@
+// Copyright 2018-2024 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+//! Golden tests: regression tests of the numerical behavior of the solvers.
+//!
+//! Each test runs a solver on a fixed problem from a fixed starting point (and with a seeded RNG
+//! for stochastic solvers) and compares the cost function value after initialization and after
+//! each iteration to the trace stored in `tests/golden/<name>.txt`. This ensures that refactorings
+//! of the core, the states or the math backends do not silently change the iterates of a solver.
+//!
+//! If a change of the numerical behavior is intended, the stored traces of the affected solvers
+//! are regenerated with
+//!
+//! ```bash
+//! ARGMIN_BLESS=1 cargo test -p argmin --test golden -- <test name>
+//! ```
+//!
+//! and the diff of the trace files is reviewed along with the change. The commit changing a trace
+//! lists the affected solvers and the reason for the change. Traces are never regenerated to
+//! accommodate a change of a random number generator or of its seeding; the test is adapted to
+//! reproduce the previous random sequence instead.
+
+use approx::relative_eq;
+use argmin::core::observers::{Observe, ObserverMode};
+use argmin::core::{CostFunction, Error, Executor, Gradient, Hessian, Operator, Solver, State, KV};
+use argmin::solver::brent::BrentOpt;
+use argmin::solver::conjugategradient::{
+    beta::{FletcherReeves, PolakRibiere},
+    ConjugateGradient, NonlinearConjugateGradient,
+};
+use argmin::solver::goldensectionsearch::GoldenSectionSearch;
+use argmin::solver::gradientdescent::SteepestDescent;
+use argmin::solver::linesearch::{
+    condition::ArmijoCondition, BacktrackingLineSearch, HagerZhangLineSearch, MoreThuenteLineSearch,
+};
+use argmin::solver::neldermead::NelderMead;
+use argmin::solver::newton::NewtonCG;
+use argmin::solver::particleswarm::ParticleSwarm;
+use argmin::solver::quasinewton::{SR1TrustRegion, BFGS, DFP, LBFGS};
+use argmin::solver::simulatedannealing::{Anneal, SimulatedAnnealing};
+use argmin::solver::stochastic::SPSA;
+use argmin::solver::trustregion::{CauchyPoint, Steihaug, TrustRegion};
+use argmin_testfunctions::{rastrigin, rosenbrock, rosenbrock_derivative, rosenbrock_hessian};
+use rand::{Rng, SeedableRng};
+use rand_xoshiro::Xoshiro256PlusPlus;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+
+/// Maximum number of iterations of each run
+const MAX_ITERS: u64 = 25;
+
+/// Seed of the random number generators
+const SEED: u64 = 42;
+
+/// Maximum relative deviation of a cost function value from the stored trace
+const MAX_RELATIVE: f64 = 1e-8;
+
+/// Maximum absolute deviation of a cost function value from the stored trace, which matters
+/// for values close to zero
+const EPSILON: f64 = 1e-12;
+
+/// Starting point of the Rosenbrock problems
+fn init_param() -> Vec<f64> {
+    vec![-1.2, 1.0]
+}
+
+/// Identity matrix of dimension 2
+fn eye() -> Vec<Vec<f64>> {
+    vec![vec![1.0, 0.0], vec![0.0, 1.0]]
+}
+
+/// Two-dimensional Rosenbrock function
+struct Rosenbrock {}
+
+impl CostFunction for Rosenbrock {
+    type Param = Vec<f64>;
+    type Output = f64;
+
+    fn cost(&self, p: &Self::Param) -> Result<Self::Output, Error> {
+        Ok(rosenbrock(p))
+    }
+}
+
+impl Gradient for Rosenbrock {
+    type Param = Vec<f64>;
+    type Gradient = Vec<f64>;
+
+    fn gradient(&self, p: &Self::Param) -> Result<Self::Gradient, Error> {
+        Ok(rosenbrock_derivative(p))
+    }
+}
+
+impl Hessian for Rosenbrock {
+    type Param = Vec<f64>;
+    type Hessian = Vec<Vec<f64>>;
+
+    fn hessian(&self, p: &Self::Param) -> Result<Self::Hessian, Error> {
+        Ok(rosenbrock_hessian(p))
+    }
+}
+
+/// Two-dimensional Rastrigin function with a seeded neighborhood for simulated annealing
+struct Rastrigin {
+    rng: Mutex<Xoshiro256PlusPlus>,
+}
+
+impl Rastrigin {
+    fn new() -> Self {
+        Rastrigin {
+            rng: Mutex::new(Xoshiro256PlusPlus::seed_from_u64(SEED)),
+        }
+    }
+}
+
+impl CostFunction for Rastrigin {
+    type Param = Vec<f64>;
+    type Output = f64;
+
+    fn cost(&self, p: &Self::Param) -> Result<Self::Output, Error> {
+        Ok(rastrigin(p))
+    }
+}
+
+impl Anneal for Rastrigin {
+    type Param = Vec<f64>;
+    type Output = Vec<f64>;
+    type Float = f64;
+
+    fn anneal(&self, p: &Self::Param, extent: Self::Float) -> Result<Self::Output, Error> {
+        let mut rng = self.rng.lock().unwrap();
+        Ok(p.iter()
+            .map(|x| x + rng.gen_range(-0.1..0.1) * extent)
+            .collect())
+    }
+}
+
+/// One-dimensional quartic `x^4 - 3x^3 + 2` with its minimum at `x = 2.25`
+struct Quartic {}
+
+impl CostFunction for Quartic {
+    type Param = f64;
+    type Output = f64;
+
+    fn cost(&self, x: &Self::Param) -> Result<Self::Output, Error> {
+        Ok(x.powi(4) - 3.0 * x.powi(3) + 2.0)
+    }
+}
+
+/// Linear system `Ax = b` with a symmetric positive definite matrix `A`
+struct LinearSystem {}
+
+impl Operator for LinearSystem {
+    type Param = Vec<f64>;
+    type Output = Vec<f64>;
+
+    fn apply(&self, p: &Self::Param) -> Result<Self::Output, Error> {
+        Ok(vec![
+            4.0 * p[0] + p[1],
+            p[0] + 3.0 * p[1] - p[2],
+            -p[1] + 2.0 * p[2],
+        ])
+    }
+}
+
+/// Records the cost function value after initialization and after each iteration
+struct Trace(Arc<Mutex<Vec<f64>>>);
+
+impl<I: State<Float = f64>> Observe<I> for Trace {
+    fn observe_init(&mut self, _name: &str, state: &I, _kv: &KV) -> Result<(), Error> {
+        self.0.lock().unwrap().push(state.get_cost());
+        Ok(())
+    }
+
+    fn observe_iter(&mut self, state: &I, _kv: &KV) -> Result<(), Error> {
+        self.0.lock().unwrap().push(state.get_cost());
+        Ok(())
+    }
+}
+
+/// Runs `executor` and compares the trace of cost function values to the stored trace `name`.
+/// With `ARGMIN_BLESS` set, the stored trace is overwritten instead.
+fn check<O, S, I>(name: &str, executor: Executor<O, S, I>)
+where
+    S: Solver<O, I>,
+    I: State<Float = f64> + 'static,
+{
+    let trace = Arc::new(Mutex::new(vec![]));
+    executor
+        .add_observer(Trace(trace.clone()), ObserverMode::Always)
+        .run()
+        .unwrap();
+    let trace = trace.lock().unwrap().clone();
+
+    let path = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("tests")
+        .join("golden")
+        .join(format!("{name}.txt"));
+
+    if std::env::var_os("ARGMIN_BLESS").is_some() {
+        let content: String = trace.iter().map(|c| format!("{c:e}\n")).collect();
+        std::fs::write(&path, content).unwrap();
+        return;
+    }
+
+    let expected: Vec<f64> = std::fs::read_to_string(&path)
+        .unwrap_or_else(|e| {
+            panic!(
+                "Cannot read {}: {e}. Run with `ARGMIN_BLESS=1` to create it.",
+                path.display()
+            )
+        })
+        .lines()
+        .map(|line| line.parse().unwrap())
+        .collect();
+
+    for (iter, (c, e)) in trace.iter().zip(expected.iter()).enumerate() {
+        assert!(
+            relative_eq!(c, e, epsilon = EPSILON, max_relative = MAX_RELATIVE)
+                || (c.is_nan() && e.is_nan()),
+            "{name}: cost {c:e} deviates from stored cost {e:e} in iteration {iter}"
+        );
+    }
+    assert_eq!(
+        trace.len(),
+        expected.len(),
+        "{name}: number of recorded costs differs from stored trace"
+    );
+}
+
+#[test]
+fn test_steepest_descent_morethuente() {
+    let solver = SteepestDescent::new(MoreThuenteLineSearch::new());
+    check(
+        "steepest_descent_morethuente",
+        Executor::new(Rosenbrock {}, solver)
+            .configure(|state| state.param(init_param()).max_iters(MAX_ITERS)),
+    );
+}
+
+#[test]
+fn test_steepest_descent_hagerzhang() {
+    let solver = SteepestDescent::new(HagerZhangLineSearch::new());
+    check(
+        "steepest_descent_hagerzhang",
+        Executor::new(Rosenbrock {}, solver)
+            .configure(|state| state.param(init_param()).max_iters(MAX_ITERS)),
+    );
+}
+
+#[test]
+fn test_steepest_descent_backtracking() {
+    let linesearch = BacktrackingLineSearch::new(ArmijoCondition::new(0.0001).unwrap());
+    let solver = SteepestDescent::new(linesearch);
+    check(
+        "steepest_descent_backtracking",
+        Executor::new(Rosenbrock {}, solver)
+            .configure(|state| state.param(init_param()).max_iters(MAX_ITERS)),
+    );
+}
+
+#[test]
+fn test_nonlinear_cg_polakribiere() {
+    let solver = NonlinearConjugateGradient::new(MoreThuenteLineSearch::new(), PolakRibiere::new())
+        .restart_iters(10)
+        .restart_orthogonality(0.1);
+    check(
+        "nonlinear_cg_polakribiere",
+        Executor::new(Rosenbrock {}, solver)
+            .configure(|state| state.param(init_param()).max_iters(MAX_ITERS)),
+    );
+}
+
+#[test]
+fn test_nonlinear_cg_fletcherreeves() {
+    let solver =
+        NonlinearConjugateGradient::new(MoreThuenteLineSearch::new(), FletcherReeves::new());
+    check(
+        "nonlinear_cg_fletcherreeves",
+        Executor::new(Rosenbrock {}, solver)
+            .configure(|state| state.param(init_param()).max_iters(MAX_ITERS)),
+    );
+}
+
+#[test]
+fn test_lbfgs() {
+    let solver = LBFGS::new(MoreThuenteLineSearch::new(), 7);
+    check(
+        "lbfgs",
+        Executor::new(Rosenbrock {}, solver)
+            .configure(|state| state.param(init_param()).max_iters(MAX_ITERS)),
+    );
+}
+
+#[test]
+fn test_bfgs() {
+    let solver = BFGS::new(MoreThuenteLineSearch::new());
+    check(
+        "bfgs",
+        Executor::new(Rosenbrock {}, solver).configure(|state| {
+            state
+                .param(init_param())
+                .inv_hessian(eye())
+                .max_iters(MAX_ITERS)
+        }),
+    );
+}
+
+#[test]
+fn test_dfp() {
+    let solver = DFP::new(MoreThuenteLineSearch::new());
+    check(
+        "dfp",
+        Executor::new(Rosenbrock {}, solver).configure(|state| {
+            state
+                .param(init_param())
+                .inv_hessian(eye())
+                .max_iters(MAX_ITERS)
+        }),
+    );
+}
+
+#[test]
+fn test_sr1_trustregion() {
+    let solver = SR1TrustRegion::new(Steihaug::new().with_max_iters(20));
+    check(
+        "sr1_trustregion",
+        Executor::new(Rosenbrock {}, solver).configure(|state| {
+            state
+                .param(init_param())
+                .hessian(eye())
+                .max_iters(MAX_ITERS)
+        }),
+    );
+}
+
+#[test]
+fn test_newton_cg() {
+    let solver = NewtonCG::new(MoreThuenteLineSearch::new());
+    check(
+        "newton_cg",
+        Executor::new(Rosenbrock {}, solver)
+            .configure(|state| state.param(init_param()).max_iters(MAX_ITERS)),
+    );
+}
+
+#[test]
+fn test_trustregion_steihaug() {
+    let solver = TrustRegion::new(Steihaug::new().with_max_iters(20));
+    check(
+        "trustregion_steihaug",
+        Executor::new(Rosenbrock {}, solver)
+            .configure(|state| state.param(init_param()).max_iters(MAX_ITERS)),
+    );
+}
+
+#[test]
+fn test_trustregion_cauchypoint() {
+    let solver = TrustRegion::new(CauchyPoint::new());
+    check(
+        "trustregion_cauchypoint",
+        Executor::new(Rosenbrock {}, solver)
+            .configure(|state| state.param(init_param()).max_iters(MAX_ITERS)),
+    );
+}
+
+#[test]
+fn test_neldermead() {
+    let solver = NelderMead::new(vec![vec![-1.2, 1.0], vec![-1.0, 1.0], vec![-1.2, 1.2]]);
+    check(
+        "neldermead",
+        Executor::new(Rosenbrock {}, solver).configure(|state| state.max_iters(MAX_ITERS)),
+    );
+}
+
+#[test]
+fn test_conjugate_gradient() {
+    let solver = ConjugateGradient::new(vec![1.0, 2.0, 3.0]);
+    check(
+        "conjugate_gradient",
+        Executor::new(LinearSystem {}, solver)
+            .configure(|state| state.param(vec![0.0; 3]).max_iters(MAX_ITERS)),
+    );
+}
+
+#[test]
+fn test_brent_opt() {
+    let solver = BrentOpt::new(0.0, 5.0);
+    check(
+        "brent_opt",
+        Executor::new(Quartic {}, solver).configure(|state| state.param(1.0).max_iters(MAX_ITERS)),
+    );
+}
+
+#[test]
+fn test_golden_section_search() {
+    let solver = GoldenSectionSearch::new(0.0, 5.0).unwrap();
+    check(
+        "golden_section_search",
+        Executor::new(Quartic {}, solver).configure(|state| state.param(1.0).max_iters(MAX_ITERS)),
+    );
+}
+
+#[test]
+fn test_simulated_annealing() {
+    let rng = Xoshiro256PlusPlus::seed_from_u64(SEED);
+    let solver = SimulatedAnnealing::new_with_rng(10.0, rng).unwrap();
+    check(
+        "simulated_annealing",
+        Executor::new(Rastrigin::new(), solver)
+            .configure(|state| state.param(vec![3.0, -2.0]).max_iters(MAX_ITERS)),
+    );
+}
+
+#[test]
+fn test_particle_swarm() {
+    let solver = ParticleSwarm::new((vec![-5.12, -5.12], vec![5.12, 5.12]), 20)
+        .with_rng_generator(Xoshiro256PlusPlus::seed_from_u64(SEED));
+    check(
+        "particle_swarm",
+        Executor::new(Rastrigin::new(), solver).configure(|state| state.max_iters(MAX_ITERS)),
+    );
+}
+
+#[test]
+fn test_spsa() {
+    let solver = SPSA::new_with_rng(Xoshiro256PlusPlus::seed_from_u64(SEED))
+        .with_step_gain(0.001)
+        .unwrap()
+        .with_cost_evaluation(true);
+    check(
+        "spsa",
+        Executor::new(Rosenbrock {}, solver)
+            .configure(|state| state.param(init_param()).max_iters(MAX_ITERS)),
+    );
+}
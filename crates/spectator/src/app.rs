@@ -7,6 +7,7 @@
 
 use std::{
     collections::{HashMap, HashSet},
+    path::PathBuf,
     sync::{Arc, Mutex},
 };
 
@@ -20,8 +21,9 @@ use egui_extras::{Column, TableBuilder};
 use egui_plot::{Bar, BarChart, Legend, Line, Plot, PlotPoints};
 
 use crate::{
+    comparison::Comparison,
     connection::server,
-    data::{RunName, Storage},
+    data::{apply_scale, every_kth, min_max_mean, Downsampling, RunName, Storage},
 };
 
 #[derive(Clone, Debug)]
@@ -42,6 +44,9 @@ struct MyContext {
 pub struct PlotterApp {
     context: MyContext,
     dock_state: Arc<Mutex<DockState<String>>>,
+    show_load_dialog: bool,
+    load_error: Option<String>,
+    comparison: Comparison,
 }
 
 impl PlotterApp {
@@ -49,6 +54,9 @@ impl PlotterApp {
         cc: &eframe::CreationContext<'_>,
         host: String,
         port: u16,
+        max_points: usize,
+        runs_dir: PathBuf,
+        record: bool,
     ) -> Result<Self, anyhow::Error> {
         let dock_state: DockState<String> = DockState::new(vec![]);
 
@@ -71,7 +79,12 @@ impl PlotterApp {
         //     HashMap::new()
         // };
 
-        let storage = Arc::new(Storage::new(Arc::clone(&dock_state)));
+        let storage = Arc::new(Storage::new(
+            Arc::clone(&dock_state),
+            max_points,
+            runs_dir,
+            record,
+        ));
         let db2 = Arc::clone(&storage);
         let egui_ctx = cc.egui_ctx.clone();
         std::thread::spawn(move || server(db2, egui_ctx, host, port));
@@ -85,8 +98,41 @@ impl PlotterApp {
         Ok(Self {
             context,
             dock_state,
+            show_load_dialog: false,
+            load_error: None,
+            comparison: Comparison::new(),
         })
     }
+
+    fn load_dialog(&mut self, ctx: &egui::Context) {
+        let mut open = self.show_load_dialog;
+        egui::Window::new("Load previous run")
+            .open(&mut open)
+            .show(ctx, |ui| {
+                let runs = self.context.storage.recorded_runs();
+                if runs.is_empty() {
+                    ui.label("No recorded runs found.");
+                }
+                egui::ScrollArea::vertical().show(ui, |ui| {
+                    for path in runs {
+                        let name = path.file_stem().unwrap_or_default().to_string_lossy();
+                        if ui.button(name).clicked() {
+                            match self.context.storage.load_run(&path) {
+                                Ok(()) => {
+                                    self.load_error = None;
+                                    self.show_load_dialog = false;
+                                }
+                                Err(e) => self.load_error = Some(e.to_string()),
+                            }
+                        }
+                    }
+                });
+                if let Some(error) = &self.load_error {
+                    ui.colored_label(Color32::RED, error);
+                }
+            });
+        self.show_load_dialog &= open;
+    }
 }
 
 impl TabViewer for MyContext {
@@ -122,12 +168,31 @@ impl MyContext {
                         .body(|mut body| {
                             body.row(30.0, |mut row| {
                                 row.col(|ui| {
-                                    for (metric_name, selected) in run.get_metrics() {
-                                        ui.checkbox(selected, metric_name);
+                                    for (metric_name, description, selected) in run.get_metrics() {
+                                        let response = ui.checkbox(selected, metric_name);
+                                        if let Some(description) = description {
+                                            response.on_hover_text(description);
+                                        }
                                     }
                                 });
                             });
                         });
+                    ui.separator();
+                    ui.heading("Samples");
+                    ui.radio_value(&mut run.downsampling, Downsampling::Raw, "Raw");
+                    ui.radio_value(&mut run.downsampling, Downsampling::EveryKth, "Every k-th");
+                    ui.radio_value(
+                        &mut run.downsampling,
+                        Downsampling::MinMaxMean,
+                        "Min/max/mean",
+                    );
+                    if run.downsampling != Downsampling::Raw {
+                        ui.add(
+                            egui::DragValue::new(&mut run.plot_points)
+                                .clamp_range(10..=100_000)
+                                .prefix("Points: "),
+                        );
+                    }
                 });
                 egui::ScrollArea::vertical()
                     .id_source("fufu")
@@ -143,12 +208,34 @@ impl MyContext {
                                     ui.group(|ui| {
                                         // dodgy
                                         ui.set_max_height(height / num_metrics - 20.0);
-                                        let curve: PlotPoints = metric.get_data().clone().into();
-                                        let line = Line::new(curve).name(&name);
+                                        let data = metric.get_data();
+                                        let label = metric.label(&name);
+                                        let lines = match run.downsampling {
+                                            Downsampling::Raw => vec![(data.clone(), label)],
+                                            Downsampling::EveryKth => {
+                                                vec![(every_kth(data, run.plot_points), label)]
+                                            }
+                                            Downsampling::MinMaxMean => {
+                                                let [min, max, mean] =
+                                                    min_max_mean(data, run.plot_points);
+                                                vec![
+                                                    (mean, label.clone()),
+                                                    (min, format!("{label} (min)")),
+                                                    (max, format!("{label} (max)")),
+                                                ]
+                                            }
+                                        };
+                                        let scale = metric.get_meta().scale;
                                         Plot::new(&name)
                                             .allow_scroll(false)
                                             .legend(Legend::default())
-                                            .show(ui, |plot_ui| plot_ui.line(line));
+                                            .show(ui, |plot_ui| {
+                                                for (data, name) in lines {
+                                                    let curve: PlotPoints =
+                                                        apply_scale(data, scale).into();
+                                                    plot_ui.line(Line::new(curve).name(name));
+                                                }
+                                            });
                                     });
                                 }
                             }
@@ -336,9 +423,24 @@ impl eframe::App for PlotterApp {
         // eframe::set_value(storage, eframe::APP_KEY, &self.context.storage.selected);
     }
 
+    fn on_exit(&mut self, _gl: Option<&eframe::glow::Context>) {
+        self.context.storage.flush_recordings();
+    }
+
     fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
         ctx.set_pixels_per_point(1.0);
 
+        egui::TopBottomPanel::top("menu").show(ctx, |ui| {
+            ui.horizontal(|ui| {
+                if ui.button("Load previous run").clicked() {
+                    self.show_load_dialog = true;
+                }
+                if ui.button("Compare runs").clicked() {
+                    self.comparison.open = true;
+                }
+            });
+        });
+
         CentralPanel::default().show(ctx, |_ui| {
             let layer_id = LayerId::background();
             let max_rect = ctx.available_rect();
@@ -357,5 +459,12 @@ impl eframe::App for PlotterApp {
                 .show_close_buttons(true)
                 .show_inside(&mut ui, &mut self.context);
         });
+
+        if self.show_load_dialog {
+            self.load_dialog(ctx);
+        }
+        if self.comparison.open {
+            self.comparison.show(ctx, &self.context.storage);
+        }
     }
 }
@@ -0,0 +1,137 @@
+// Copyright 2018-2024 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use std::collections::HashSet;
+
+use eframe::egui::{self, Ui};
+use egui_plot::{Legend, Line, Plot, PlotPoints};
+use itertools::Itertools;
+
+use crate::data::{apply_scale, RunName, Storage};
+
+/// Quantity shown on the x-axis of the comparison plot
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum XAxis {
+    Iteration,
+    WallTime,
+}
+
+/// Overlays the same metric of several runs in a single plot
+pub struct Comparison {
+    pub open: bool,
+    metric: Option<String>,
+    runs: HashSet<RunName>,
+    x_axis: XAxis,
+    /// Scale the x-axis of each run to `[0, 1]`
+    normalize: bool,
+}
+
+impl Comparison {
+    pub fn new() -> Self {
+        Comparison {
+            open: false,
+            metric: None,
+            runs: HashSet::new(),
+            x_axis: XAxis::Iteration,
+            normalize: false,
+        }
+    }
+
+    pub fn show(&mut self, ctx: &egui::Context, storage: &Storage) {
+        let mut open = self.open;
+        egui::Window::new("Compare runs")
+            .open(&mut open)
+            .default_size([800.0, 500.0])
+            .show(ctx, |ui| {
+                ui.horizontal_top(|ui| {
+                    ui.vertical(|ui| self.show_settings(ui, storage));
+                    self.show_plot(ui, storage);
+                });
+            });
+        self.open = open;
+    }
+
+    fn show_settings(&mut self, ui: &mut Ui, storage: &Storage) {
+        let run_names: Vec<RunName> = storage
+            .runs
+            .iter()
+            .map(|r| r.key().clone())
+            .sorted()
+            .collect();
+        let metric_names: Vec<String> = storage
+            .runs
+            .iter()
+            .flat_map(|r| r.metrics.keys().cloned().collect::<Vec<_>>())
+            .unique()
+            .sorted()
+            .collect();
+        if self.metric.is_none() {
+            self.metric = metric_names
+                .iter()
+                .find(|name| *name == "best_cost")
+                .or(metric_names.first())
+                .cloned();
+        }
+
+        ui.heading("Metric");
+        egui::ComboBox::from_id_source("comparison_metric")
+            .selected_text(self.metric.clone().unwrap_or_default())
+            .show_ui(ui, |ui| {
+                for name in metric_names {
+                    ui.selectable_value(&mut self.metric, Some(name.clone()), name);
+                }
+            });
+
+        ui.heading("x-axis");
+        ui.radio_value(&mut self.x_axis, XAxis::Iteration, "Iteration");
+        ui.radio_value(&mut self.x_axis, XAxis::WallTime, "Wall time [s]");
+        ui.checkbox(&mut self.normalize, "Normalize");
+
+        ui.heading("Runs");
+        for name in run_names {
+            let mut selected = self.runs.contains(&name);
+            if ui.checkbox(&mut selected, &name).changed() {
+                if selected {
+                    self.runs.insert(name);
+                } else {
+                    self.runs.remove(&name);
+                }
+            }
+        }
+    }
+
+    fn show_plot(&self, ui: &mut Ui, storage: &Storage) {
+        let Some(metric_name) = &self.metric else {
+            return;
+        };
+        Plot::new("comparison")
+            .allow_scroll(false)
+            .legend(Legend::default())
+            .show(ui, |plot_ui| {
+                for name in self.runs.iter().sorted() {
+                    let Some(run) = storage.runs.get(name) else {
+                        continue;
+                    };
+                    let Some(metric) = run.metrics.get(metric_name) else {
+                        continue;
+                    };
+                    let mut data = match self.x_axis {
+                        XAxis::Iteration => metric.get_data().clone(),
+                        XAxis::WallTime => metric.get_data_over_time(),
+                    };
+                    if self.normalize {
+                        let max = data.iter().map(|[x, _]| *x).fold(0.0, f64::max);
+                        if max > 0.0 {
+                            data.iter_mut().for_each(|[x, _]| *x /= max);
+                        }
+                    }
+                    let curve: PlotPoints = apply_scale(data, metric.get_meta().scale).into();
+                    plot_ui.line(Line::new(curve).name(name));
+                }
+            });
+    }
+}
@@ -14,8 +14,9 @@ use tokio::net::{TcpListener, TcpStream};
 use tokio_stream::StreamExt;
 use tokio_util::codec::{Framed, LengthDelimitedCodec};
 
-use crate::data::{FuncCount, Metric};
-use crate::{data::Run, message::Message};
+use spectator::Message;
+
+use crate::data::{Downsampling, FuncCount, Metric, Run, DEFAULT_PLOT_POINTS};
 
 use super::data::Storage;
 
@@ -51,140 +52,143 @@ async fn handle_connection(
     while let Some(result) = lines.next().await {
         ctx.request_repaint();
         match result {
-            Ok(line) => {
-                match Message::unpack(&line) {
-                    Ok(msg) => {
-                        match msg {
-                            Message::NewRun {
-                                name,
-                                solver,
-                                max_iter,
-                                target_cost,
-                                init_param,
-                                settings,
-                                selected,
-                            } => {
-                                let mut tree = storage.tree.lock().unwrap();
-                                tree.push_to_first_leaf(name.clone());
-                                drop(tree);
-
-                                let settings = settings
-                                    .kv
-                                    .into_iter()
-                                    .map(|(k, v)| (k, v.as_string()))
-                                    .collect();
+            Ok(line) => match Message::unpack(&line) {
+                Ok(msg) => {
+                    storage.record(&msg);
+                    process_message(&storage, msg);
+                }
+                Err(e) => {
+                    tracing::error!("Error: {e:?}");
+                }
+            },
+            Err(e) => {
+                tracing::error!("Error on decoding from socket: {:?}", e);
+            }
+        }
+    }
+    Ok(())
+}
 
-                                storage.runs.insert(
-                                    name.clone(),
-                                    Run {
-                                        name: name.clone(),
-                                        solver,
-                                        settings,
-                                        selected,
-                                        init_param: init_param.clone(),
-                                        max_iter,
-                                        target_cost,
-                                        curr_iter: 0,
-                                        best_iter: 0,
-                                        curr_cost: f64::INFINITY,
-                                        curr_best_cost: f64::INFINITY,
-                                        time: Duration::new(0, 0),
-                                        termination_status: TerminationStatus::NotTerminated,
-                                        metrics: HashMap::new(),
-                                        func_counts: HashMap::new(),
-                                        func_cumulative: true,
-                                        param: init_param.clone().map(|ip| (0, ip)),
-                                        best_param: init_param.map(|ip| (0, ip)),
-                                    },
-                                );
-                            }
-                            Message::Samples {
-                                name,
-                                iter,
-                                time,
-                                termination_status,
-                                kv,
-                            } => {
-                                if let Some(mut run) = storage.runs.get_mut(&name) {
-                                    run.curr_iter = iter;
-                                    run.time = time;
-                                    run.termination_status = termination_status;
-                                    for (k, _) in kv.keys() {
-                                        let kv_val = kv.get(&k).unwrap().get_float().unwrap();
-                                        // for easier access in overview window
-                                        if k == "cost" {
-                                            run.curr_cost = kv_val;
-                                        }
-                                        if k == "best_cost" {
-                                            run.curr_best_cost = kv_val;
-                                        }
-                                        if let Some(val) = run.metrics.get_mut(&k) {
-                                            val.push([f64::from(iter as u32), kv_val]);
-                                        } else {
-                                            let mut metric = Metric::new();
+/// Incorporates a message into the data of the corresponding run
+pub fn process_message(storage: &Storage, msg: Message) {
+    match msg {
+        Message::NewRun {
+            name,
+            solver,
+            max_iter,
+            target_cost,
+            init_param,
+            settings,
+            selected,
+        } => {
+            let mut tree = storage.tree.lock().unwrap();
+            tree.push_to_first_leaf(name.clone());
+            drop(tree);
 
-                                            metric.selected(
-                                                run.selected.is_empty()
-                                                    || run.selected.contains(&k),
-                                            );
+            let settings = settings
+                .kv
+                .into_iter()
+                .map(|(k, v)| (k, v.as_string()))
+                .collect();
 
-                                            metric.push([f64::from(iter as u32), kv_val]);
-                                            run.add_metric(&k, metric);
-                                        }
-                                    }
-                                }
-                            }
-                            Message::FuncCounts { name, iter, kv } => {
-                                if let Some(mut run) = storage.runs.get_mut(&name) {
-                                    for k in kv.keys() {
-                                        let counts = kv.get(k).unwrap();
-                                        if let Some(val) = run.func_counts.get_mut(k) {
-                                            val.push([
-                                                f64::from(iter as u32),
-                                                f64::from(*counts as u32),
-                                            ]);
-                                        } else {
-                                            let mut count = FuncCount::new();
+            storage.runs.insert(
+                name.clone(),
+                Run {
+                    name: name.clone(),
+                    solver,
+                    settings,
+                    selected,
+                    init_param: init_param.clone(),
+                    max_iter,
+                    target_cost,
+                    curr_iter: 0,
+                    best_iter: 0,
+                    curr_cost: f64::INFINITY,
+                    curr_best_cost: f64::INFINITY,
+                    time: Duration::new(0, 0),
+                    termination_status: TerminationStatus::NotTerminated,
+                    metrics: HashMap::new(),
+                    func_counts: HashMap::new(),
+                    func_cumulative: true,
+                    downsampling: Downsampling::Raw,
+                    plot_points: DEFAULT_PLOT_POINTS,
+                    param: init_param.clone().map(|ip| (0, ip)),
+                    best_param: init_param.map(|ip| (0, ip)),
+                },
+            );
+        }
+        Message::Samples {
+            name,
+            iter,
+            time,
+            termination_status,
+            kv,
+        } => {
+            if let Some(mut run) = storage.runs.get_mut(&name) {
+                run.curr_iter = iter;
+                run.time = time;
+                run.termination_status = termination_status;
+                for (k, _) in kv.keys() {
+                    let kv_val = kv.get(&k).unwrap().get_float().unwrap();
+                    // for easier access in overview window
+                    if k == "cost" {
+                        run.curr_cost = kv_val;
+                    }
+                    if k == "best_cost" {
+                        run.curr_best_cost = kv_val;
+                    }
+                    if let Some(val) = run.metrics.get_mut(&k) {
+                        val.push([f64::from(iter as u32), kv_val], time.as_seconds_f64());
+                        if let Some(meta) = kv.get_meta(&k) {
+                            val.set_meta(meta.clone());
+                        }
+                    } else {
+                        let mut metric = Metric::new(storage.max_points);
 
-                                            count.push([
-                                                f64::from(iter as u32),
-                                                f64::from(*counts as u32),
-                                            ]);
-                                            run.add_func_counts(k, count);
-                                        }
-                                    }
-                                }
-                            }
-                            Message::Param { name, iter, param } => {
-                                if let Some(mut run) = storage.runs.get_mut(&name) {
-                                    run.param = Some((iter, param));
-                                }
-                            }
-                            Message::BestParam { name, iter, param } => {
-                                if let Some(mut run) = storage.runs.get_mut(&name) {
-                                    run.best_iter = iter;
-                                    run.best_param = Some((iter, param));
-                                }
-                            }
-                            Message::Termination {
-                                name,
-                                termination_status,
-                            } => {
-                                if let Some(mut run) = storage.runs.get_mut(&name) {
-                                    run.termination_status = termination_status;
-                                }
-                            }
+                        metric.selected(run.selected.is_empty() || run.selected.contains(&k));
+                        if let Some(meta) = kv.get_meta(&k) {
+                            metric.set_meta(meta.clone());
                         }
+
+                        metric.push([f64::from(iter as u32), kv_val], time.as_seconds_f64());
+                        run.add_metric(&k, metric);
                     }
-                    Err(e) => {
-                        tracing::error!("Error: {e:?}");
+                }
+            }
+        }
+        Message::FuncCounts { name, iter, kv } => {
+            if let Some(mut run) = storage.runs.get_mut(&name) {
+                for k in kv.keys() {
+                    let counts = kv.get(k).unwrap();
+                    if let Some(val) = run.func_counts.get_mut(k) {
+                        val.push([f64::from(iter as u32), f64::from(*counts as u32)]);
+                    } else {
+                        let mut count = FuncCount::new(storage.max_points);
+
+                        count.push([f64::from(iter as u32), f64::from(*counts as u32)]);
+                        run.add_func_counts(k, count);
                     }
                 }
             }
-            Err(e) => {
-                tracing::error!("Error on decoding from socket: {:?}", e);
+        }
+        Message::Param { name, iter, param } => {
+            if let Some(mut run) = storage.runs.get_mut(&name) {
+                run.param = Some((iter, param));
+            }
+        }
+        Message::BestParam { name, iter, param } => {
+            if let Some(mut run) = storage.runs.get_mut(&name) {
+                run.best_iter = iter;
+                run.best_param = Some((iter, param));
+            }
+        }
+        Message::Termination {
+            name,
+            termination_status,
+        } => {
+            if let Some(mut run) = storage.runs.get_mut(&name) {
+                run.termination_status = termination_status;
             }
         }
     }
-    Ok(())
 }
@@ -7,35 +7,134 @@
 
 use std::{
     collections::{HashMap, HashSet},
+    path::{Path, PathBuf},
     sync::{Arc, Mutex},
 };
 
-use argmin::core::TerminationStatus;
+use anyhow::bail;
+use argmin::core::{KvMeta, KvScale, TerminationStatus};
 use dashmap::DashMap;
 use egui_dock::DockState;
 use itertools::Itertools;
+use spectator::{
+    record::{self, run_file_name, RunRecorder, RUN_FILE_EXTENSION},
+    Message,
+};
 use time::Duration;
 
+use crate::connection::process_message;
+
 pub type RunName = String;
 type MetricName = String;
 type CountName = String;
 type SettingName = String;
 
+/// Default number of points (or buckets) metrics are reduced to when downsampling is enabled
+pub const DEFAULT_PLOT_POINTS: usize = 1000;
+
+/// Reduction applied to the samples of a metric before they are plotted
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Downsampling {
+    /// All stored samples
+    Raw,
+    /// Every k-th sample
+    EveryKth,
+    /// Minimum, maximum and mean per bucket of samples
+    MinMaxMean,
+}
+
+/// Removes every other element of `data`
+fn decimate<T: Copy>(data: &mut Vec<T>) {
+    let mut idx = 0;
+    data.retain(|_| {
+        idx += 1;
+        idx % 2 == 1
+    });
+}
+
+/// Reduces `data` to at most `num_points` samples by keeping every k-th sample
+pub fn every_kth(data: &[[f64; 2]], num_points: usize) -> Vec<[f64; 2]> {
+    let k = data.len().div_ceil(num_points.max(1)).max(1);
+    data.iter().step_by(k).copied().collect()
+}
+
+/// Splits `data` into at most `num_buckets` buckets and returns the minimum, maximum and mean of
+/// each bucket. The samples are located at the mean x-value of the corresponding bucket.
+pub fn min_max_mean(data: &[[f64; 2]], num_buckets: usize) -> [Vec<[f64; 2]>; 3] {
+    let bucket_size = data.len().div_ceil(num_buckets.max(1)).max(1);
+    let mut out = [vec![], vec![], vec![]];
+    for bucket in data.chunks(bucket_size) {
+        let n = bucket.len() as f64;
+        let x = bucket.iter().map(|[x, _]| x).sum::<f64>() / n;
+        let ys = bucket.iter().map(|[_, y]| *y);
+        out[0].push([x, ys.clone().fold(f64::INFINITY, f64::min)]);
+        out[1].push([x, ys.clone().fold(f64::NEG_INFINITY, f64::max)]);
+        out[2].push([x, ys.sum::<f64>() / n]);
+    }
+    out
+}
+
+/// Transforms the y-values of `data` according to `scale`.
+///
+/// For logarithmic scale, the base 10 logarithm is taken and samples with non-positive values are
+/// removed.
+pub fn apply_scale(data: Vec<[f64; 2]>, scale: KvScale) -> Vec<[f64; 2]> {
+    match scale {
+        KvScale::Linear => data,
+        KvScale::Log => data
+            .into_iter()
+            .filter(|[_, y]| *y > 0.0)
+            .map(|[x, y]| [x, y.log10()])
+            .collect(),
+    }
+}
+
 pub struct Metric {
     data: Vec<[f64; 2]>,
+    /// Elapsed time (in seconds) at which each sample was taken
+    times: Vec<f64>,
+    /// Maximum number of stored samples
+    max_points: usize,
+    /// Only every `stride`-th received sample is stored
+    stride: usize,
+    /// Number of received samples
+    received: usize,
     selected: bool,
+    /// Display hints provided by the solver
+    meta: KvMeta,
 }
 
 impl Metric {
-    pub fn new() -> Self {
+    /// Creates a metric which stores at most `max_points` samples.
+    ///
+    /// Once this limit is reached, every other stored sample is dropped and from then on only
+    /// every other received sample is stored. This keeps the memory consumption bounded for
+    /// arbitrarily long runs.
+    pub fn new(max_points: usize) -> Self {
+        let max_points = max_points.max(2);
         Self {
-            data: Vec::with_capacity(1_000_000),
+            data: Vec::with_capacity(max_points.min(1_000_000)),
+            times: Vec::with_capacity(max_points.min(1_000_000)),
+            max_points,
+            stride: 1,
+            received: 0,
             selected: true,
+            meta: KvMeta::new(),
         }
     }
 
-    pub fn push(&mut self, val: [f64; 2]) -> &mut Self {
+    pub fn push(&mut self, val: [f64; 2], time: f64) -> &mut Self {
+        self.received += 1;
+        if (self.received - 1) % self.stride != 0 {
+            return self;
+        }
         self.data.push(val);
+        self.times.push(time);
+        if self.data.len() >= self.max_points {
+            decimate(&mut self.data);
+            decimate(&mut self.times);
+            self.stride *= 2;
+        }
         self
     }
 
@@ -44,24 +143,72 @@ impl Metric {
         self
     }
 
+    pub fn set_meta(&mut self, meta: KvMeta) -> &mut Self {
+        self.meta = meta;
+        self
+    }
+
+    pub fn get_meta(&self) -> &KvMeta {
+        &self.meta
+    }
+
+    /// Returns the label of the metric including unit and scale, e.g. `log10(t [K])`
+    pub fn label(&self, name: &str) -> String {
+        let label = match &self.meta.unit {
+            Some(unit) => format!("{name} [{unit}]"),
+            None => name.to_string(),
+        };
+        match self.meta.scale {
+            KvScale::Linear => label,
+            KvScale::Log => format!("log10({label})"),
+        }
+    }
+
     pub fn get_data(&self) -> &Vec<[f64; 2]> {
         &self.data
     }
+
+    /// Returns the samples with the elapsed time (in seconds) instead of the iteration number
+    pub fn get_data_over_time(&self) -> Vec<[f64; 2]> {
+        self.data
+            .iter()
+            .zip(self.times.iter())
+            .map(|(&[_, val], &time)| [time, val])
+            .collect()
+    }
 }
 
 pub struct FuncCount {
     data: Vec<[f64; 2]>,
+    /// Maximum number of stored samples (see [`Metric::new`])
+    max_points: usize,
+    /// Only every `stride`-th received sample is stored
+    stride: usize,
+    /// Number of received samples
+    received: usize,
 }
 
 impl FuncCount {
-    pub fn new() -> Self {
+    pub fn new(max_points: usize) -> Self {
+        let max_points = max_points.max(2);
         Self {
-            data: Vec::with_capacity(1_000_000),
+            data: Vec::with_capacity(max_points.min(1_000_000)),
+            max_points,
+            stride: 1,
+            received: 0,
         }
     }
 
     pub fn push(&mut self, val: [f64; 2]) -> &mut Self {
+        self.received += 1;
+        if (self.received - 1) % self.stride != 0 {
+            return self;
+        }
         self.data.push(val);
+        if self.data.len() >= self.max_points {
+            decimate(&mut self.data);
+            self.stride *= 2;
+        }
         self
     }
 
@@ -96,6 +243,9 @@ pub struct Run {
     pub metrics: HashMap<MetricName, Metric>,
     pub func_counts: HashMap<CountName, FuncCount>,
     pub func_cumulative: bool,
+    pub downsampling: Downsampling,
+    /// Number of points (or buckets) metrics are reduced to when downsampling is enabled
+    pub plot_points: usize,
     pub param: Option<(u64, Vec<f64>)>,
     pub best_param: Option<(u64, Vec<f64>)>,
 }
@@ -106,11 +256,12 @@ impl Run {
         self
     }
 
-    pub fn get_metrics(&mut self) -> Vec<(String, &mut bool)> {
+    /// Returns name, description and selection state of all metrics
+    pub fn get_metrics(&mut self) -> Vec<(String, Option<String>, &mut bool)> {
         self.metrics
             .iter_mut()
-            .map(|(k, m)| (k.clone(), &mut m.selected))
-            .sorted()
+            .map(|(k, m)| (k.clone(), m.meta.description.clone(), &mut m.selected))
+            .sorted_by(|a, b| a.0.cmp(&b.0))
             .collect()
     }
 
@@ -132,13 +283,163 @@ impl Run {
 pub struct Storage {
     pub runs: DashMap<RunName, Run>,
     pub tree: Arc<Mutex<DockState<RunName>>>,
+    /// Maximum number of samples stored per metric and function count
+    pub max_points: usize,
+    /// Directory runs are recorded to and loaded from
+    runs_dir: PathBuf,
+    /// Whether incoming runs are recorded
+    record: bool,
+    recorders: DashMap<RunName, RunRecorder>,
 }
 
 impl Storage {
-    pub fn new(tree: Arc<Mutex<DockState<String>>>) -> Self {
+    pub fn new(
+        tree: Arc<Mutex<DockState<String>>>,
+        max_points: usize,
+        runs_dir: PathBuf,
+        record: bool,
+    ) -> Self {
         Storage {
             runs: DashMap::new(),
             tree,
+            max_points,
+            runs_dir,
+            record,
+            recorders: DashMap::new(),
+        }
+    }
+
+    /// Records a message to the file of the corresponding run.
+    ///
+    /// A new file is created for every `NewRun` message and closed after the `Termination`
+    /// message.
+    pub fn record(&self, msg: &Message) {
+        if !self.record {
+            return;
+        }
+        let name = msg.name();
+        if let Message::NewRun { .. } = msg {
+            match RunRecorder::create(self.runs_dir.join(run_file_name(name))) {
+                Ok(recorder) => {
+                    self.recorders.insert(name.to_string(), recorder);
+                }
+                Err(e) => tracing::error!("Failed to record run {name}: {e:?}"),
+            }
+        }
+        if let Some(mut recorder) = self.recorders.get_mut(name) {
+            if let Err(e) = recorder.record(msg) {
+                tracing::error!("Failed to record message of run {name}: {e:?}");
+            }
+        }
+        if let Message::Termination { .. } = msg {
+            if let Some((_, mut recorder)) = self.recorders.remove(name) {
+                if let Err(e) = recorder.flush() {
+                    tracing::error!("Failed to record run {name}: {e:?}");
+                }
+            }
+        }
+    }
+
+    /// Writes the buffered messages of all ongoing recordings to disk
+    pub fn flush_recordings(&self) {
+        for mut recorder in self.recorders.iter_mut() {
+            if let Err(e) = recorder.flush() {
+                tracing::error!("Failed to record run {}: {e:?}", recorder.key());
+            }
         }
     }
+
+    /// Returns the files of all recorded runs, most recent first
+    pub fn recorded_runs(&self) -> Vec<PathBuf> {
+        let Ok(entries) = std::fs::read_dir(&self.runs_dir) else {
+            return vec![];
+        };
+        entries
+            .filter_map(|entry| entry.ok())
+            .filter(|entry| {
+                entry
+                    .path()
+                    .extension()
+                    .is_some_and(|ext| ext == RUN_FILE_EXTENSION)
+            })
+            .filter_map(|entry| Some((entry.metadata().ok()?.modified().ok()?, entry.path())))
+            .sorted()
+            .rev()
+            .map(|(_, path)| path)
+            .collect()
+    }
+
+    /// Loads a recorded run and opens it in a new tab
+    pub fn load_run(&self, path: &Path) -> Result<(), anyhow::Error> {
+        let messages = record::load_run(path)?;
+        match messages.first() {
+            Some(Message::NewRun { name, .. }) if self.runs.contains_key(name) => {
+                bail!("Run {name} is already open.")
+            }
+            Some(Message::NewRun { .. }) => {}
+            _ => bail!("{} does not contain a recorded run.", path.display()),
+        }
+        for msg in messages {
+            process_message(self, msg);
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_metric_bounded() {
+        let mut metric = Metric::new(4);
+        for i in 0..10 {
+            metric.push([f64::from(i), 0.0], f64::from(i));
+        }
+        // Stride is 4 after two decimations: samples 0, 4 and 8 remain
+        assert_eq!(metric.get_data(), &vec![[0.0, 0.0], [4.0, 0.0], [8.0, 0.0]]);
+        assert_eq!(
+            metric.get_data_over_time(),
+            vec![[0.0, 0.0], [4.0, 0.0], [8.0, 0.0]]
+        );
+    }
+
+    #[test]
+    fn test_metric_label() {
+        let mut metric = Metric::new(4);
+        assert_eq!(metric.label("t"), "t");
+        metric.set_meta(KvMeta::new().unit("K"));
+        assert_eq!(metric.label("t"), "t [K]");
+        metric.set_meta(KvMeta::new().unit("K").scale(KvScale::Log));
+        assert_eq!(metric.label("t"), "log10(t [K])");
+    }
+
+    #[test]
+    fn test_apply_scale() {
+        let data = vec![[0.0, 100.0], [1.0, 0.0], [2.0, 0.1]];
+        assert_eq!(apply_scale(data.clone(), KvScale::Linear), data);
+        assert_eq!(
+            apply_scale(data, KvScale::Log),
+            vec![[0.0, 2.0], [2.0, -1.0]]
+        );
+    }
+
+    #[test]
+    fn test_every_kth() {
+        let data: Vec<[f64; 2]> = (0..10).map(|i| [f64::from(i), 0.0]).collect();
+        assert_eq!(every_kth(&data, 20), data);
+        assert_eq!(
+            every_kth(&data, 4),
+            vec![[0.0, 0.0], [3.0, 0.0], [6.0, 0.0], [9.0, 0.0]]
+        );
+    }
+
+    #[test]
+    fn test_min_max_mean() {
+        let data = [[0.0, 1.0], [1.0, 3.0], [2.0, 2.0], [3.0, -2.0], [4.0, 5.0]];
+        let [min, max, mean] = min_max_mean(&data, 2);
+        assert_eq!(min, vec![[1.0, 1.0], [3.5, -2.0]]);
+        assert_eq!(max, vec![[1.0, 3.0], [3.5, 5.0]]);
+        assert_eq!(mean, vec![[1.0, 2.0], [3.5, 1.5]]);
+    }
 }
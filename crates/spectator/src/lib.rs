@@ -44,10 +44,24 @@
 //! The optional options `--host` and `--port` indicate the host and port spectator binds to.
 //! By default, spectator will bind to `0.0.0.0:5498`.
 //!
+//! All received runs are recorded to the directory given by `--dir` (default: `.spectator`) such
+//! that they can be inspected after spectator was closed. Previously recorded runs can be opened
+//! via the "Load previous run" button. Recording can be disabled with `--no-record`.
+//!
+//! The "Compare runs" button opens a plot which overlays the same metric (e.g. `best_cost`) of
+//! several runs. The x-axis shows either the iteration or the elapsed wall time and can be
+//! normalized to `[0, 1]` for each run.
+//!
+//! To keep the memory consumption bounded for long runs, at most `--max-points` (default:
+//! 1000000) samples are stored per metric; once this limit is exceeded, every other sample is
+//! dropped. For plotting, the samples can additionally be reduced to every k-th sample or to the
+//! minimum, maximum and mean per bucket of samples.
+//!
 //! ## Library
 //!
 //! This crate can also be used as a library and exposes the [`Message`] type used to encode data
 //! sent to spectator and [`DEFAULT_PORT`] which defines the default port used by spectator.
+//! The [`record`] module contains the functionality to save runs to disk and to load them again.
 //!
 //! # License
 //!
@@ -70,6 +84,7 @@
 #![warn(missing_docs)]
 
 mod message;
+pub mod record;
 
 pub use message::Message;
 
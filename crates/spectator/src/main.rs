@@ -6,11 +6,13 @@
 // copied, modified, or distributed except according to those terms.
 
 mod app;
+mod comparison;
 mod connection;
 mod data;
-mod message;
 mod telemetry;
 
+use std::path::PathBuf;
+
 use anyhow::Error;
 use uuid::Uuid;
 
@@ -22,6 +24,8 @@ use spectator::DEFAULT_PORT;
 
 const NAME: &str = "spectator";
 const DEFAULT_HOST: &str = "0.0.0.0";
+const DEFAULT_RUNS_DIR: &str = ".spectator";
+const DEFAULT_MAX_POINTS: usize = 1_000_000;
 
 #[derive(Parser, Debug)]
 #[command(author, version, about, long_about = None)]
@@ -33,10 +37,29 @@ struct Args {
     /// Port to bind to
     #[arg(short, long, default_value_t = DEFAULT_PORT)]
     port: u16,
+
+    /// Maximum number of samples stored per metric. If exceeded, every other sample is dropped.
+    #[arg(short, long, default_value_t = DEFAULT_MAX_POINTS)]
+    max_points: usize,
+
+    /// Directory runs are recorded to
+    #[arg(short, long, default_value_t = DEFAULT_RUNS_DIR.to_string())]
+    dir: String,
+
+    /// Do not record runs to disk
+    #[arg(long)]
+    no_record: bool,
 }
 
 fn run() -> Result<(), Error> {
-    let Args { host, port } = Args::parse();
+    let Args {
+        host,
+        port,
+        max_points,
+        dir,
+        no_record,
+    } = Args::parse();
+    let runs_dir = PathBuf::from(dir);
 
     // Set up logging
     let subscriber = get_subscriber(NAME.into(), "info".into(), std::io::stdout);
@@ -49,7 +72,12 @@ fn run() -> Result<(), Error> {
     eframe::run_native(
         NAME,
         options,
-        Box::new(move |cc| Box::new(PlotterApp::new(cc, host, port).expect("Failed to start GUI"))),
+        Box::new(move |cc| {
+            Box::new(
+                PlotterApp::new(cc, host, port, max_points, runs_dir, !no_record)
+                    .expect("Failed to start GUI"),
+            )
+        }),
     )
     .expect("Failed to start GUI.");
     Ok(())
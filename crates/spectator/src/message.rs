@@ -83,6 +83,18 @@ pub enum Message {
 }
 
 impl Message {
+    /// Returns the name of the run the message belongs to
+    pub fn name(&self) -> &str {
+        match self {
+            Message::NewRun { name, .. }
+            | Message::Samples { name, .. }
+            | Message::FuncCounts { name, .. }
+            | Message::Param { name, .. }
+            | Message::BestParam { name, .. }
+            | Message::Termination { name, .. } => name,
+        }
+    }
+
     /// Serialize message
     #[allow(unused)]
     pub fn pack(&self) -> Result<Bytes, Error> {
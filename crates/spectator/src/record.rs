@@ -0,0 +1,155 @@
+// Copyright 2018-2024 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+//! Recording of optimization runs to disk.
+//!
+//! Spectator records all messages it receives for a run with [`RunRecorder`]. Past runs can be
+//! restored by replaying the messages returned by [`load_run`].
+
+use std::{
+    fs::File,
+    io::{BufWriter, Write},
+    path::Path,
+};
+
+use anyhow::Error;
+use bytes::BytesMut;
+
+use crate::Message;
+
+/// File extension of recorded runs
+pub const RUN_FILE_EXTENSION: &str = "spectator";
+
+/// Returns the name of the file a run with name `name` is recorded to.
+///
+/// All characters which are neither alphanumeric nor `-` or `_` are replaced with `_`.
+pub fn run_file_name(name: &str) -> String {
+    let name: String = name
+        .chars()
+        .map(|c| {
+            if c.is_alphanumeric() || c == '-' || c == '_' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    format!("{name}.{RUN_FILE_EXTENSION}")
+}
+
+/// Records the messages of an optimization run to a file.
+///
+/// Each message is stored in the same format as it is sent over the network: a 4 byte big-endian
+/// length followed by the MessagePack encoded message. Replaying the messages with
+/// [`load_run`] restores the run.
+pub struct RunRecorder {
+    writer: BufWriter<File>,
+}
+
+impl RunRecorder {
+    /// Creates the file at `path` (and all missing parent directories). An existing file is
+    /// overwritten.
+    pub fn create<P: AsRef<Path>>(path: P) -> Result<Self, Error> {
+        let path = path.as_ref();
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir)?;
+        }
+        Ok(RunRecorder {
+            writer: BufWriter::new(File::create(path)?),
+        })
+    }
+
+    /// Appends a message to the file
+    pub fn record(&mut self, msg: &Message) -> Result<(), Error> {
+        let buf = msg.pack()?;
+        self.writer
+            .write_all(&u32::try_from(buf.len())?.to_be_bytes())?;
+        self.writer.write_all(&buf)?;
+        Ok(())
+    }
+
+    /// Writes all buffered messages to the file
+    pub fn flush(&mut self) -> Result<(), Error> {
+        self.writer.flush()?;
+        Ok(())
+    }
+}
+
+/// Reads all messages of a run recorded with [`RunRecorder`].
+///
+/// An incomplete message at the end of the file (for instance because spectator was killed while
+/// writing) is ignored.
+pub fn load_run<P: AsRef<Path>>(path: P) -> Result<Vec<Message>, Error> {
+    let data = std::fs::read(path)?;
+    let mut messages = vec![];
+    let mut rest = &data[..];
+    while rest.len() >= 4 {
+        let len = u32::from_be_bytes([rest[0], rest[1], rest[2], rest[3]]) as usize;
+        if rest.len() < 4 + len {
+            break;
+        }
+        messages.push(Message::unpack(&BytesMut::from(&rest[4..4 + len]))?);
+        rest = &rest[4 + len..];
+    }
+    Ok(messages)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use argmin::core::{TerminationReason, TerminationStatus, KV};
+    use std::collections::HashSet;
+
+    #[test]
+    fn test_run_file_name() {
+        assert_eq!(run_file_name("run_1-a"), "run_1-a.spectator");
+        assert_eq!(run_file_name("../a b"), "___a_b.spectator");
+    }
+
+    #[test]
+    fn test_record_and_load() {
+        let dir = std::env::temp_dir().join(format!("spectator_record_{}", std::process::id()));
+        let path = dir.join(run_file_name("test"));
+        let messages = vec![
+            Message::NewRun {
+                name: "test".to_string(),
+                solver: "solver".to_string(),
+                max_iter: 10,
+                target_cost: 0.0,
+                init_param: Some(vec![1.0, 2.0]),
+                settings: KV::new(),
+                selected: HashSet::new(),
+            },
+            Message::Param {
+                name: "test".to_string(),
+                iter: 1,
+                param: vec![0.5, 1.5],
+            },
+            Message::Termination {
+                name: "test".to_string(),
+                termination_status: TerminationStatus::Terminated(
+                    TerminationReason::MaxItersReached,
+                ),
+            },
+        ];
+
+        let mut recorder = RunRecorder::create(&path).unwrap();
+        for msg in messages.iter() {
+            recorder.record(msg).unwrap();
+        }
+        recorder.flush().unwrap();
+        assert_eq!(load_run(&path).unwrap(), messages);
+
+        // Incomplete trailing message is ignored
+        let mut data = std::fs::read(&path).unwrap();
+        data.extend_from_slice(&[0, 0, 1, 0, 42]);
+        std::fs::write(&path, data).unwrap();
+        assert_eq!(load_run(&path).unwrap(), messages);
+
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+}
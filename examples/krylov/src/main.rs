@@ -0,0 +1,76 @@
+// Copyright 2018-2024 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use argmin::{
+    core::{observers::ObserverMode, Error, Executor, Operator},
+    solver::krylov::{GMRES, MINRES},
+};
+use argmin_observer_slog::SlogLogger;
+
+/// Symmetric and indefinite matrix
+struct Symmetric {}
+
+impl Operator for Symmetric {
+    type Param = Vec<f64>;
+    type Output = Vec<f64>;
+
+    fn apply(&self, p: &Self::Param) -> Result<Self::Output, Error> {
+        Ok(vec![
+            1.0 * p[0] + 2.0 * p[1],
+            2.0 * p[0] + 1.0 * p[1] + 1.0 * p[2],
+            1.0 * p[1] + 3.0 * p[2],
+        ])
+    }
+}
+
+/// Nonsymmetric matrix
+struct Nonsymmetric {}
+
+impl Operator for Nonsymmetric {
+    type Param = Vec<f64>;
+    type Output = Vec<f64>;
+
+    fn apply(&self, p: &Self::Param) -> Result<Self::Output, Error> {
+        Ok(vec![
+            4.0 * p[0] + 1.0 * p[1],
+            -1.0 * p[0] + 3.0 * p[1] + 1.0 * p[2],
+            2.0 * p[1] + 5.0 * p[2],
+        ])
+    }
+}
+
+fn run() -> Result<(), Error> {
+    // Define the right hand side `b` of `A * x = b`
+    let b = vec![1.0, 2.0, 3.0];
+
+    // MINRES for the symmetric system
+    let solver: MINRES<_, f64> = MINRES::new(b.clone()).with_tolerance(1e-10)?;
+
+    let res = Executor::new(Symmetric {}, solver)
+        .configure(|state| state.param(vec![0.0; 3]).max_iters(10))
+        .add_observer(SlogLogger::term(), ObserverMode::Always)
+        .run()?;
+
+    println!("{res}");
+
+    // Restarted GMRES for the nonsymmetric system
+    let solver: GMRES<_, f64> = GMRES::new(b).with_tolerance(1e-10)?.with_restart(2)?;
+
+    let res = Executor::new(Nonsymmetric {}, solver)
+        .configure(|state| state.param(vec![0.0; 3]).max_iters(20))
+        .add_observer(SlogLogger::term(), ObserverMode::Always)
+        .run()?;
+
+    println!("{res}");
+    Ok(())
+}
+
+fn main() {
+    if let Err(ref e) = run() {
+        println!("{e}");
+    }
+}
@@ -0,0 +1,71 @@
+// Copyright 2018-2024 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use argmin::{
+    core::{observers::ObserverMode, CostFunction, Error, Executor, Gradient},
+    solver::{linesearch::MoreThuenteLineSearch, quasinewton::LBFGS},
+};
+use argmin_observer_trajectory::{ContourPlot, Trajectory};
+use argmin_testfunctions::{rosenbrock, rosenbrock_derivative};
+
+struct Rosenbrock {}
+
+impl CostFunction for Rosenbrock {
+    type Param = Vec<f64>;
+    type Output = f64;
+
+    fn cost(&self, p: &Self::Param) -> Result<Self::Output, Error> {
+        Ok(rosenbrock(p))
+    }
+}
+
+impl Gradient for Rosenbrock {
+    type Param = Vec<f64>;
+    type Gradient = Vec<f64>;
+
+    fn gradient(&self, p: &Self::Param) -> Result<Self::Gradient, Error> {
+        Ok(rosenbrock_derivative(p))
+    }
+}
+
+fn run() -> Result<(), Error> {
+    // Define cost function
+    let cost = Rosenbrock {};
+
+    // Define initial parameter vector
+    let init_param: Vec<f64> = vec![-1.2, 1.0];
+
+    // Set up solver
+    let solver = LBFGS::new(MoreThuenteLineSearch::new(), 7);
+
+    // Records the parameter vectors; clones share the recorded points
+    let trajectory = Trajectory::new();
+
+    let res = Executor::new(cost, solver)
+        .configure(|state| state.param(init_param).max_iters(100))
+        .add_observer(trajectory.clone(), ObserverMode::Always)
+        .run()?;
+
+    // Print result
+    println!("{res}");
+
+    // Export the trajectory and plot it over the contours of the Rosenbrock function
+    trajectory.write_csv("trajectory.csv")?;
+    ContourPlot::new(|p: &[f64]| rosenbrock(p))
+        .x_range(-2.0, 2.0)
+        .y_range(-1.0, 3.0)
+        .log_scale(true)
+        .render(&trajectory, "trajectory.png")?;
+    Ok(())
+}
+
+fn main() {
+    if let Err(ref e) = run() {
+        println!("{e}");
+        std::process::exit(1);
+    }
+}
@@ -0,0 +1,117 @@
+// Copyright 2018-2024 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use crate::problem::PyProblem;
+use crate::result::PyOptimizationResult;
+use crate::state::{state_dict, ParamVector};
+use crate::to_pyerr;
+use argmin::core::{Executor, Solver};
+use pyo3::prelude::*;
+use pyo3::types::PyDict;
+
+/// Runs a solver one iteration at a time, independent of the solver and state types
+trait Step {
+    /// Performs the next iteration and describes the state afterwards. Returns `None` if the run
+    /// has already terminated.
+    fn step<'py>(&mut self, py: Python<'py>) -> PyResult<Option<Bound<'py, PyDict>>>;
+
+    /// Returns `true` if the run has terminated (or failed)
+    fn terminated(&self) -> bool;
+
+    /// Summary of the run so far
+    fn result(&self) -> PyResult<PyOptimizationResult>;
+}
+
+/// Drives argmin's `Executor` through its stepping API, with each call of `step` corresponding
+/// to one iteration. Initialization of the solver happens during the first call.
+struct Stepper<S, I> {
+    executor: Executor<PyProblem, S, I>,
+    /// Name of the solver, which is not accessible through the executor
+    solver: String,
+}
+
+impl<S, I> Step for Stepper<S, I>
+where
+    S: Solver<PyProblem, I>,
+    I: ParamVector,
+{
+    fn step<'py>(&mut self, py: Python<'py>) -> PyResult<Option<Bound<'py, PyDict>>> {
+        let Some(kv) = self.executor.step().map_err(to_pyerr)? else {
+            return Ok(None);
+        };
+        let state = self.executor.state().map_err(to_pyerr)?;
+        let info = state_dict(py, state, &kv)?;
+        // The executor has already advanced the iteration number, whereas callbacks receive the
+        // number of the iteration which has just been performed.
+        info.set_item("iter", state.get_iter().saturating_sub(1))?;
+        Ok(Some(info))
+    }
+
+    fn terminated(&self) -> bool {
+        self.executor
+            .state()
+            .map_or(true, |state| state.terminated())
+    }
+
+    fn result(&self) -> PyResult<PyOptimizationResult> {
+        let state = self.executor.state().map_err(to_pyerr)?;
+        Ok(PyOptimizationResult::from_state(state, &self.solver))
+    }
+}
+
+/// Executor which runs a solver one iteration at a time.
+///
+/// Returned by the `executor` methods of the solvers, which take the same arguments as their
+/// `minimize` methods except for the callback. This allows writing custom loops in Python, for
+/// instance to implement problem-specific stopping rules or to interleave the optimization with
+/// other computations. Errors raised by the solver or the callables leave the executor unusable.
+#[pyclass(name = "Executor", module = "argmin_py", unsendable)]
+pub(crate) struct PyExecutor {
+    stepper: Box<dyn Step>,
+}
+
+impl PyExecutor {
+    /// Creates an executor for `solver` on `problem` with the initial state modified by
+    /// `configure`.
+    pub(crate) fn new<S, I>(problem: PyProblem, solver: S, configure: impl FnOnce(I) -> I) -> Self
+    where
+        S: Solver<PyProblem, I> + 'static,
+        I: ParamVector + 'static,
+    {
+        let solver_name = solver.name().to_string();
+        // Interrupts are left to Python, which raises `KeyboardInterrupt` between the steps.
+        let executor = Executor::new(problem, solver)
+            .configure(configure)
+            .ctrlc(false);
+        PyExecutor {
+            stepper: Box::new(Stepper {
+                executor,
+                solver: solver_name,
+            }),
+        }
+    }
+}
+
+#[pymethods]
+impl PyExecutor {
+    /// Performs a single iteration and returns a dict describing the state afterwards, with the
+    /// same keys as the dict passed to callbacks. Returns `None` once the run has terminated.
+    fn step<'py>(&mut self, py: Python<'py>) -> PyResult<Option<Bound<'py, PyDict>>> {
+        self.stepper.step(py)
+    }
+
+    /// `True` if the run has terminated, in which case `step` returns `None`
+    #[getter]
+    fn terminated(&self) -> bool {
+        self.stepper.terminated()
+    }
+
+    /// Summarizes the run so far as an `OptimizationResult`
+    fn result(&self) -> PyResult<PyOptimizationResult> {
+        self.stepper.result()
+    }
+}
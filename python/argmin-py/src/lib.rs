@@ -0,0 +1,41 @@
+// Copyright 2018-2024 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+mod executor;
+mod observer;
+mod problem;
+mod result;
+mod solvers;
+mod state;
+
+use argmin::core::{Error, ErrorCategory};
+use pyo3::exceptions::{PyRuntimeError, PyValueError};
+use pyo3::prelude::*;
+
+/// Converts an argmin error into a Python exception.
+///
+/// Exceptions raised in Python callables are passed through unchanged, invalid parameters are
+/// turned into a `ValueError` and all other errors into a `RuntimeError`.
+pub(crate) fn to_pyerr(err: Error) -> PyErr {
+    match err.downcast::<PyErr>() {
+        Ok(err) => err,
+        Err(err) => match ErrorCategory::of(&err) {
+            ErrorCategory::InvalidParameter => PyValueError::new_err(format!("{err:#}")),
+            _ => PyRuntimeError::new_err(format!("{err:#}")),
+        },
+    }
+}
+
+#[pymodule]
+fn argmin_py(_py: Python, m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add_class::<executor::PyExecutor>()?;
+    m.add_class::<result::PyOptimizationResult>()?;
+    m.add_class::<solvers::PyLBFGS>()?;
+    m.add_class::<solvers::PyNelderMead>()?;
+    m.add_class::<solvers::PyParticleSwarm>()?;
+    Ok(())
+}
@@ -0,0 +1,95 @@
+// Copyright 2018-2024 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use crate::state::{state_dict, ParamVector};
+use argmin::core::observers::Observe;
+use argmin::core::{Error, Problem, Solver, State, TerminationReason, TerminationStatus, KV};
+use pyo3::prelude::*;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+/// Observer which calls a Python callable after every iteration.
+///
+/// The callable receives a dict describing the state after the iteration, see [`state_dict`].
+/// If the callable returns `False`, the run is stopped; any other return value (including `None`) continues the
+/// run.
+pub(crate) struct PyCallback {
+    callback: Py<PyAny>,
+    stop: Arc<AtomicBool>,
+}
+
+impl PyCallback {
+    /// Creates a new observer which sets `stop` if the callable returns `False`.
+    pub(crate) fn new(callback: Py<PyAny>, stop: Arc<AtomicBool>) -> Self {
+        PyCallback { callback, stop }
+    }
+}
+
+impl<I> Observe<I> for PyCallback
+where
+    I: ParamVector,
+{
+    fn observe_iter(&mut self, state: &I, kv: &KV) -> Result<(), Error> {
+        Python::with_gil(|py| {
+            let info = state_dict(py, state, kv)?;
+            if let Ok(false) = self.callback.call1(py, (info,))?.extract::<bool>(py) {
+                self.stop.store(true, Ordering::SeqCst);
+            }
+            Ok(())
+        })
+    }
+}
+
+/// Wraps a solver such that it terminates once `stop` is set.
+///
+/// This is how a [`PyCallback`] stops a run. Since the termination criteria are checked before
+/// each iteration, the iteration during which `stop` was set is completed.
+pub(crate) struct Stoppable<S> {
+    solver: S,
+    stop: Arc<AtomicBool>,
+}
+
+impl<S> Stoppable<S> {
+    pub(crate) fn new(solver: S, stop: Arc<AtomicBool>) -> Self {
+        Stoppable { solver, stop }
+    }
+}
+
+impl<O, I, S> Solver<O, I> for Stoppable<S>
+where
+    I: State,
+    S: Solver<O, I>,
+{
+    fn name(&self) -> &str {
+        self.solver.name()
+    }
+
+    fn check_state(&self, state: &I) -> Result<(), Error> {
+        self.solver.check_state(state)
+    }
+
+    fn init(&mut self, problem: &mut Problem<O>, state: I) -> Result<(I, Option<KV>), Error> {
+        self.solver.init(problem, state)
+    }
+
+    fn next_iter(&mut self, problem: &mut Problem<O>, state: I) -> Result<(I, Option<KV>), Error> {
+        self.solver.next_iter(problem, state)
+    }
+
+    fn terminate_internal(&mut self, state: &I) -> TerminationStatus {
+        if self.stop.load(Ordering::SeqCst) {
+            return TerminationStatus::Terminated(TerminationReason::SolverExit(
+                "Stopped by callback".to_string(),
+            ));
+        }
+        self.solver.terminate_internal(state)
+    }
+
+    fn terminate(&mut self, state: &I) -> TerminationStatus {
+        self.solver.terminate(state)
+    }
+}
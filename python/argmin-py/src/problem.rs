@@ -0,0 +1,57 @@
+// Copyright 2018-2024 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use argmin::core::{CostFunction, Error, Gradient};
+use numpy::{AllowTypeChange, PyArray1, PyArrayLike1};
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+
+/// Optimization problem defined by Python callables.
+///
+/// The callables are called with the parameter vector as a one-dimensional numpy array. The cost
+/// function must return a float and the gradient must return an array-like object of the same
+/// length as the parameter vector.
+pub(crate) struct PyProblem {
+    cost: Py<PyAny>,
+    gradient: Option<Py<PyAny>>,
+}
+
+impl PyProblem {
+    pub(crate) fn new(cost: Py<PyAny>, gradient: Option<Py<PyAny>>) -> Self {
+        PyProblem { cost, gradient }
+    }
+}
+
+impl CostFunction for PyProblem {
+    type Param = Vec<f64>;
+    type Output = f64;
+
+    fn cost(&self, param: &Self::Param) -> Result<Self::Output, Error> {
+        Python::with_gil(|py| {
+            let param = PyArray1::from_slice(py, param);
+            Ok(self.cost.call1(py, (param,))?.extract::<f64>(py)?)
+        })
+    }
+}
+
+impl Gradient for PyProblem {
+    type Param = Vec<f64>;
+    type Gradient = Vec<f64>;
+
+    fn gradient(&self, param: &Self::Param) -> Result<Self::Gradient, Error> {
+        Python::with_gil(|py| {
+            let gradient = self
+                .gradient
+                .as_ref()
+                .ok_or_else(|| PyValueError::new_err("solver requires a gradient"))?;
+            let param = PyArray1::from_slice(py, param);
+            let grad = gradient.call1(py, (param,))?;
+            let grad = grad.extract::<PyArrayLike1<f64, AllowTypeChange>>(py)?;
+            Ok(grad.as_array().to_vec())
+        })
+    }
+}
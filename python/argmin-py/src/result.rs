@@ -0,0 +1,78 @@
+// Copyright 2018-2024 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use crate::state::ParamVector;
+use argmin::core::{OptimizationResult, Solver, TerminationStatus};
+use numpy::PyArray1;
+use pyo3::prelude::*;
+
+/// Result of an optimization run
+#[pyclass(name = "OptimizationResult", module = "argmin_py", frozen)]
+pub(crate) struct PyOptimizationResult {
+    best_param: Vec<f64>,
+    /// Best cost function value
+    #[pyo3(get)]
+    best_cost: f64,
+    /// Number of iterations
+    #[pyo3(get)]
+    iterations: u64,
+    /// Number of cost function evaluations
+    #[pyo3(get)]
+    cost_count: u64,
+    /// Textual representation of why the solver terminated
+    #[pyo3(get)]
+    termination_reason: String,
+    /// Name of the solver
+    #[pyo3(get)]
+    solver: String,
+}
+
+impl PyOptimizationResult {
+    /// Extracts the relevant information from the result of an optimization run.
+    pub(crate) fn new<O, S, I>(result: &OptimizationResult<O, S, I>) -> Self
+    where
+        S: Solver<O, I>,
+        I: ParamVector,
+    {
+        PyOptimizationResult::from_state(result.state(), result.solver().name())
+    }
+
+    /// Extracts the relevant information from the final `state` of a run of the solver `solver`.
+    pub(crate) fn from_state<I: ParamVector>(state: &I, solver: &str) -> Self {
+        PyOptimizationResult {
+            best_param: state.best_param_vector().cloned().unwrap_or_default(),
+            best_cost: state.get_best_cost(),
+            iterations: state.get_iter(),
+            cost_count: state
+                .get_func_counts()
+                .get("cost_count")
+                .copied()
+                .unwrap_or(0),
+            termination_reason: match state.get_termination_status() {
+                TerminationStatus::Terminated(reason) => reason.text().to_string(),
+                TerminationStatus::NotTerminated => "Not terminated".to_string(),
+            },
+            solver: solver.to_string(),
+        }
+    }
+}
+
+#[pymethods]
+impl PyOptimizationResult {
+    /// Best parameter vector as numpy array
+    #[getter]
+    fn best_param<'py>(&self, py: Python<'py>) -> Bound<'py, PyArray1<f64>> {
+        PyArray1::from_slice(py, &self.best_param)
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "OptimizationResult(solver={:?}, best_param={:?}, best_cost={}, iterations={}, termination_reason={:?})",
+            self.solver, self.best_param, self.best_cost, self.iterations, self.termination_reason
+        )
+    }
+}
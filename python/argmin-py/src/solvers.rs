@@ -0,0 +1,414 @@
+// Copyright 2018-2024 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use crate::executor::PyExecutor;
+use crate::observer::{PyCallback, Stoppable};
+use crate::problem::PyProblem;
+use crate::result::PyOptimizationResult;
+use crate::state::ParamVector;
+use crate::to_pyerr;
+use argmin::core::observers::ObserverMode;
+use argmin::core::{BoxedSolver, Executor, IterState, PopulationState, Solver};
+use argmin::solver::linesearch::{HagerZhangLineSearchConfig, MoreThuenteLineSearchConfig};
+use argmin::solver::neldermead::{NelderMead, NelderMeadConfig};
+use argmin::solver::particleswarm::{Particle, ParticleSwarmConfig};
+use argmin::solver::quasinewton::LBFGSConfig;
+use numpy::{AllowTypeChange, PyArrayLike1};
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+use std::sync::atomic::AtomicBool;
+use std::sync::Arc;
+
+type GradientState = IterState<Vec<f64>, Vec<f64>, (), (), (), f64>;
+type DerivativeFreeState = IterState<Vec<f64>, (), (), (), (), f64>;
+type ParticleSwarmState = PopulationState<Particle<Vec<f64>, f64>, f64>;
+
+/// Converts an array-like Python object into a `Vec<f64>`
+fn to_vec(array: PyArrayLike1<f64, AllowTypeChange>) -> Vec<f64> {
+    array.as_array().to_vec()
+}
+
+/// Runs `solver` on `problem` with the initial state modified by `configure`.
+///
+/// If a `callback` is given, it is called after every iteration and may stop the run by
+/// returning `False`.
+fn run<S, I>(
+    problem: PyProblem,
+    solver: S,
+    callback: Option<Py<PyAny>>,
+    configure: impl FnOnce(I) -> I,
+) -> PyResult<PyOptimizationResult>
+where
+    S: Solver<PyProblem, I>,
+    I: ParamVector + 'static,
+{
+    let stop = Arc::new(AtomicBool::new(false));
+    let mut executor =
+        Executor::new(problem, Stoppable::new(solver, stop.clone())).configure(configure);
+    if let Some(callback) = callback {
+        executor = executor.add_observer(PyCallback::new(callback, stop), ObserverMode::Always);
+    }
+    let res = executor.run().map_err(to_pyerr)?;
+    Ok(PyOptimizationResult::new(&res))
+}
+
+/// Line search used by gradient based solvers
+#[derive(Clone, Debug)]
+enum LineSearchConfig {
+    MoreThuente(MoreThuenteLineSearchConfig<f64>),
+    HagerZhang(HagerZhangLineSearchConfig<f64>),
+}
+
+impl LineSearchConfig {
+    /// Creates the line search configuration from its name and the (optional) parameters
+    /// `c1` and `c2`. If only one of them is given, the other one keeps its default value.
+    fn new(name: &str, c1: Option<f64>, c2: Option<f64>) -> PyResult<Self> {
+        let c = |d1: f64, d2: f64| match (c1, c2) {
+            (None, None) => None,
+            (c1, c2) => Some((c1.unwrap_or(d1), c2.unwrap_or(d2))),
+        };
+        match name.to_lowercase().as_str() {
+            "morethuente" => Ok(LineSearchConfig::MoreThuente(MoreThuenteLineSearchConfig {
+                c: c(1e-4, 0.9),
+                ..Default::default()
+            })),
+            "hagerzhang" => Ok(LineSearchConfig::HagerZhang(HagerZhangLineSearchConfig {
+                delta_sigma: c(0.1, 0.9),
+                ..Default::default()
+            })),
+            _ => Err(PyValueError::new_err(format!(
+                "unknown line search `{name}`; available line searches: morethuente, hagerzhang"
+            ))),
+        }
+    }
+}
+
+/// Limited-memory BFGS method.
+///
+/// Requires the gradient of the cost function. The line search is either `"morethuente"`
+/// (default) or `"hagerzhang"`, with `c1` and `c2` being the parameters of the (strong) Wolfe
+/// conditions (`delta` and `sigma` in case of Hager-Zhang).
+#[pyclass(name = "LBFGS", module = "argmin_py")]
+pub(crate) struct PyLBFGS {
+    config: LBFGSConfig<f64>,
+    linesearch: LineSearchConfig,
+}
+
+impl PyLBFGS {
+    /// Initial state of a run starting from `x0`
+    fn configure(
+        x0: Vec<f64>,
+        max_iters: u64,
+        target_cost: Option<f64>,
+    ) -> impl FnOnce(GradientState) -> GradientState {
+        move |state| {
+            let state = state.param(x0).max_iters(max_iters);
+            match target_cost {
+                Some(target_cost) => state.target_cost(target_cost),
+                None => state,
+            }
+        }
+    }
+
+    fn build(&self) -> PyResult<BoxedSolver<PyProblem, GradientState>> {
+        Ok(match &self.linesearch {
+            LineSearchConfig::MoreThuente(config) => {
+                let linesearch = config.build().map_err(to_pyerr)?;
+                BoxedSolver::new(self.config.build(linesearch).map_err(to_pyerr)?)
+            }
+            LineSearchConfig::HagerZhang(config) => {
+                let linesearch = config.build().map_err(to_pyerr)?;
+                BoxedSolver::new(self.config.build(linesearch).map_err(to_pyerr)?)
+            }
+        })
+    }
+}
+
+#[pymethods]
+impl PyLBFGS {
+    #[new]
+    #[pyo3(signature = (
+        m = 7,
+        tolerance_grad = None,
+        tolerance_cost = None,
+        l1_coeff = None,
+        linesearch = "morethuente",
+        c1 = None,
+        c2 = None
+    ))]
+    fn new(
+        m: usize,
+        tolerance_grad: Option<f64>,
+        tolerance_cost: Option<f64>,
+        l1_coeff: Option<f64>,
+        linesearch: &str,
+        c1: Option<f64>,
+        c2: Option<f64>,
+    ) -> PyResult<Self> {
+        let solver = PyLBFGS {
+            config: LBFGSConfig {
+                m,
+                tolerance_grad,
+                tolerance_cost,
+                l1_coeff,
+                linesearch_recovery: None,
+                curvature_update: None,
+                initial_scaling: None,
+            },
+            linesearch: LineSearchConfig::new(linesearch, c1, c2)?,
+        };
+        // Validate all options early
+        solver.build()?;
+        Ok(solver)
+    }
+
+    /// Minimizes `cost` starting from `x0`.
+    ///
+    /// `cost` and `gradient` are called with the parameter vector as numpy array. The optional
+    /// `callback` is called after every iteration, see [`PyCallback`].
+    #[pyo3(signature = (cost, gradient, x0, max_iters = 100, target_cost = None, callback = None))]
+    fn minimize(
+        &self,
+        cost: Py<PyAny>,
+        gradient: Py<PyAny>,
+        x0: PyArrayLike1<f64, AllowTypeChange>,
+        max_iters: u64,
+        target_cost: Option<f64>,
+        callback: Option<Py<PyAny>>,
+    ) -> PyResult<PyOptimizationResult> {
+        let problem = PyProblem::new(cost, Some(gradient));
+        let configure = Self::configure(to_vec(x0), max_iters, target_cost);
+        run(problem, self.build()?, callback, configure)
+    }
+
+    /// Returns an `Executor` which minimizes `cost` starting from `x0` one iteration at a time,
+    /// see [`PyExecutor`].
+    #[pyo3(signature = (cost, gradient, x0, max_iters = 100, target_cost = None))]
+    fn executor(
+        &self,
+        cost: Py<PyAny>,
+        gradient: Py<PyAny>,
+        x0: PyArrayLike1<f64, AllowTypeChange>,
+        max_iters: u64,
+        target_cost: Option<f64>,
+    ) -> PyResult<PyExecutor> {
+        let problem = PyProblem::new(cost, Some(gradient));
+        let configure = Self::configure(to_vec(x0), max_iters, target_cost);
+        Ok(PyExecutor::new(problem, self.build()?, configure))
+    }
+}
+
+/// Nelder-Mead method.
+///
+/// Derivative-free method which requires an initial simplex consisting of `n + 1` vertices for
+/// an `n`-dimensional problem.
+#[pyclass(name = "NelderMead", module = "argmin_py")]
+pub(crate) struct PyNelderMead {
+    config: NelderMeadConfig<Vec<f64>, f64>,
+}
+
+impl PyNelderMead {
+    /// Initial state of a run
+    fn configure(
+        max_iters: u64,
+        target_cost: Option<f64>,
+    ) -> impl FnOnce(DerivativeFreeState) -> DerivativeFreeState {
+        move |state| {
+            let state = state.max_iters(max_iters);
+            match target_cost {
+                Some(target_cost) => state.target_cost(target_cost),
+                None => state,
+            }
+        }
+    }
+
+    fn build(
+        &self,
+        simplex: Vec<PyArrayLike1<f64, AllowTypeChange>>,
+    ) -> PyResult<NelderMead<Vec<f64>, f64>> {
+        NelderMeadConfig {
+            params: simplex.into_iter().map(to_vec).collect(),
+            ..self.config.clone()
+        }
+        .build()
+        .map_err(to_pyerr)
+    }
+}
+
+#[pymethods]
+impl PyNelderMead {
+    #[new]
+    #[pyo3(signature = (sd_tolerance = None, alpha = None, gamma = None, rho = None, sigma = None))]
+    fn new(
+        sd_tolerance: Option<f64>,
+        alpha: Option<f64>,
+        gamma: Option<f64>,
+        rho: Option<f64>,
+        sigma: Option<f64>,
+    ) -> PyResult<Self> {
+        let config = NelderMeadConfig {
+            params: vec![],
+            sd_tolerance,
+            alpha,
+            gamma,
+            rho,
+            sigma,
+        };
+        // Validate all options early; the simplex is only known in `minimize`
+        NelderMeadConfig {
+            params: vec![vec![0.0], vec![1.0]],
+            ..config.clone()
+        }
+        .build()
+        .map_err(to_pyerr)?;
+        Ok(PyNelderMead { config })
+    }
+
+    /// Minimizes `cost` starting from the initial simplex `simplex`, which is a sequence of
+    /// `n + 1` array-like vertices.
+    ///
+    /// `cost` is called with the parameter vector as numpy array. The optional `callback` is
+    /// called after every iteration, see [`PyCallback`].
+    #[pyo3(signature = (cost, simplex, max_iters = 100, target_cost = None, callback = None))]
+    fn minimize(
+        &self,
+        cost: Py<PyAny>,
+        simplex: Vec<PyArrayLike1<f64, AllowTypeChange>>,
+        max_iters: u64,
+        target_cost: Option<f64>,
+        callback: Option<Py<PyAny>>,
+    ) -> PyResult<PyOptimizationResult> {
+        run(
+            PyProblem::new(cost, None),
+            self.build(simplex)?,
+            callback,
+            Self::configure(max_iters, target_cost),
+        )
+    }
+
+    /// Returns an `Executor` which minimizes `cost` starting from the initial simplex `simplex`
+    /// one iteration at a time, see [`PyExecutor`].
+    #[pyo3(signature = (cost, simplex, max_iters = 100, target_cost = None))]
+    fn executor(
+        &self,
+        cost: Py<PyAny>,
+        simplex: Vec<PyArrayLike1<f64, AllowTypeChange>>,
+        max_iters: u64,
+        target_cost: Option<f64>,
+    ) -> PyResult<PyExecutor> {
+        Ok(PyExecutor::new(
+            PyProblem::new(cost, None),
+            self.build(simplex)?,
+            Self::configure(max_iters, target_cost),
+        ))
+    }
+}
+
+/// Particle Swarm Optimization.
+///
+/// Derivative-free global optimization method which searches within the box defined by `bounds`,
+/// given as `(lower_bound, upper_bound)`.
+#[pyclass(name = "ParticleSwarm", module = "argmin_py")]
+pub(crate) struct PyParticleSwarm {
+    config: ParticleSwarmConfig<Vec<f64>, f64>,
+}
+
+impl PyParticleSwarm {
+    /// Initial state of a run
+    fn configure(
+        max_iters: u64,
+        target_cost: Option<f64>,
+    ) -> impl FnOnce(ParticleSwarmState) -> ParticleSwarmState {
+        move |state| {
+            let state = state.max_iters(max_iters);
+            match target_cost {
+                Some(target_cost) => state.target_cost(target_cost),
+                None => state,
+            }
+        }
+    }
+}
+
+#[pymethods]
+impl PyParticleSwarm {
+    #[new]
+    #[pyo3(signature = (
+        bounds,
+        num_particles = 40,
+        inertia_factor = None,
+        cognitive_factor = None,
+        social_factor = None,
+        seed = None
+    ))]
+    fn new(
+        bounds: (
+            PyArrayLike1<f64, AllowTypeChange>,
+            PyArrayLike1<f64, AllowTypeChange>,
+        ),
+        num_particles: usize,
+        inertia_factor: Option<f64>,
+        cognitive_factor: Option<f64>,
+        social_factor: Option<f64>,
+        seed: Option<u64>,
+    ) -> PyResult<Self> {
+        let (lower, upper) = (to_vec(bounds.0), to_vec(bounds.1));
+        if lower.len() != upper.len() {
+            return Err(PyValueError::new_err(
+                "lower and upper bound must have the same length",
+            ));
+        }
+        let config = ParticleSwarmConfig {
+            bounds: (lower, upper),
+            num_particles,
+            inertia_factor,
+            cognitive_factor,
+            social_factor,
+            seed,
+        };
+        // Validate all options early
+        config.build().map_err(to_pyerr)?;
+        Ok(PyParticleSwarm { config })
+    }
+
+    /// Minimizes `cost` within the bounds.
+    ///
+    /// `cost` is called with the parameter vector as numpy array. The optional `callback` is
+    /// called after every iteration, see [`PyCallback`].
+    #[pyo3(signature = (cost, max_iters = 100, target_cost = None, callback = None))]
+    fn minimize(
+        &self,
+        cost: Py<PyAny>,
+        max_iters: u64,
+        target_cost: Option<f64>,
+        callback: Option<Py<PyAny>>,
+    ) -> PyResult<PyOptimizationResult> {
+        let solver = self.config.build().map_err(to_pyerr)?;
+        run(
+            PyProblem::new(cost, None),
+            solver,
+            callback,
+            Self::configure(max_iters, target_cost),
+        )
+    }
+
+    /// Returns an `Executor` which minimizes `cost` within the bounds one iteration at a time,
+    /// see [`PyExecutor`].
+    #[pyo3(signature = (cost, max_iters = 100, target_cost = None))]
+    fn executor(
+        &self,
+        cost: Py<PyAny>,
+        max_iters: u64,
+        target_cost: Option<f64>,
+    ) -> PyResult<PyExecutor> {
+        let solver = self.config.build().map_err(to_pyerr)?;
+        Ok(PyExecutor::new(
+            PyProblem::new(cost, None),
+            solver,
+            Self::configure(max_iters, target_cost),
+        ))
+    }
+}
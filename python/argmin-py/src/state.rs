@@ -0,0 +1,87 @@
+// Copyright 2018-2024 argmin developers
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use argmin::core::{IterState, KvValue, PopulationState, State, KV};
+use argmin::solver::particleswarm::Particle;
+use numpy::PyArray1;
+use pyo3::prelude::*;
+use pyo3::types::PyDict;
+use pyo3::IntoPyObjectExt;
+
+/// Uniform access to the parameter vectors of the different state types
+pub(crate) trait ParamVector: State<Float = f64> {
+    /// Current parameter vector
+    fn param_vector(&self) -> Option<&Vec<f64>>;
+
+    /// Best parameter vector
+    fn best_param_vector(&self) -> Option<&Vec<f64>>;
+}
+
+impl<G, J, H, R> ParamVector for IterState<Vec<f64>, G, J, H, R, f64>
+where
+    Self: State<Param = Vec<f64>, Float = f64>,
+{
+    fn param_vector(&self) -> Option<&Vec<f64>> {
+        self.get_param()
+    }
+
+    fn best_param_vector(&self) -> Option<&Vec<f64>> {
+        self.get_best_param()
+    }
+}
+
+impl ParamVector for PopulationState<Particle<Vec<f64>, f64>, f64> {
+    fn param_vector(&self) -> Option<&Vec<f64>> {
+        self.get_param().map(|particle| &particle.position)
+    }
+
+    fn best_param_vector(&self) -> Option<&Vec<f64>> {
+        self.get_best_param().map(|particle| &particle.position)
+    }
+}
+
+/// Converts a value of a `KV` into a Python object
+fn kv_value_to_py(py: Python<'_>, value: &KvValue) -> PyResult<Py<PyAny>> {
+    match value {
+        KvValue::Float(x) => x.into_py_any(py),
+        KvValue::Int(x) => x.into_py_any(py),
+        KvValue::Uint(x) => x.into_py_any(py),
+        KvValue::Bool(x) => x.into_py_any(py),
+        KvValue::Str(x) => x.into_py_any(py),
+    }
+}
+
+/// Describes the state after an iteration as a Python dict.
+///
+/// The dict contains the keys `iter`, `cost`, `best_cost`, `param`, `best_param` (numpy arrays or
+/// `None`) and `kv` (a dict of the solver-specific metrics returned by the iteration).
+pub(crate) fn state_dict<'py, I: ParamVector>(
+    py: Python<'py>,
+    state: &I,
+    kv: &KV,
+) -> PyResult<Bound<'py, PyDict>> {
+    let metrics = PyDict::new(py);
+    for (key, value) in kv.kv.iter() {
+        metrics.set_item(key, kv_value_to_py(py, value)?)?;
+    }
+    let info = PyDict::new(py);
+    info.set_item("iter", state.get_iter())?;
+    info.set_item("cost", state.get_cost())?;
+    info.set_item("best_cost", state.get_best_cost())?;
+    info.set_item(
+        "param",
+        state.param_vector().map(|p| PyArray1::from_slice(py, p)),
+    )?;
+    info.set_item(
+        "best_param",
+        state
+            .best_param_vector()
+            .map(|p| PyArray1::from_slice(py, p)),
+    )?;
+    info.set_item("kv", metrics)?;
+    Ok(info)
+}